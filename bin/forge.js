@@ -0,0 +1,152 @@
+#!/usr/bin/env node
+// `forge` command-line companion. Talks to a running Forge instance over the local
+// instance socket, launching Forge first if none is running.
+//
+//   forge [paths...]              open files/folders (path:line:column supported)
+//   forge open <paths...>         same as above
+//   forge diff <left> <right>     open a side-by-side diff
+//   forge wait <file>             block until the file is closed in Forge
+//
+// Flags: --wait / -w (return once opened files are closed, for $EDITOR),
+//        --new-window / -n, --help / -h, --version / -v
+
+const net = require('net');
+const path = require('path');
+const { spawn } = require('child_process');
+const { getSocketPath, encodeMessage, createMessageReader, parsePathSpec } = require('../electron/cliProtocol');
+
+const CONNECT_TIMEOUT = 15000;
+const USAGE = `Usage:
+  forge [options] [paths...]
+  forge open [options] <paths...>
+  forge diff [options] <left> <right>
+  forge wait <file>
+
+Options:
+  -w, --wait         Wait for the opened files to be closed before returning
+  -n, --new-window   Open in a new window
+  -h, --help         Show this help
+  -v, --version      Print the version of the running Forge instance
+
+Paths may include a position, e.g. src/main.ts:42:7`;
+
+function parseArgs(argv) {
+  const flags = { wait: false, newWindow: false, help: false, version: false };
+  const positional = [];
+  for (const arg of argv) {
+    if (arg === '-w' || arg === '--wait') flags.wait = true;
+    else if (arg === '-n' || arg === '--new-window') flags.newWindow = true;
+    else if (arg === '-h' || arg === '--help') flags.help = true;
+    else if (arg === '-v' || arg === '--version') flags.version = true;
+    else if (arg.startsWith('-') && arg !== '-') throw new Error(`Unknown option: ${arg}`);
+    else positional.push(arg);
+  }
+
+  let command = 'open';
+  if (['open', 'diff', 'wait'].includes(positional[0])) {
+    command = positional.shift();
+  }
+  return { command, flags, positional };
+}
+
+function connect(socketPath) {
+  return new Promise((resolve, reject) => {
+    const socket = net.connect(socketPath);
+    socket.once('connect', () => resolve(socket));
+    socket.once('error', reject);
+  });
+}
+
+// Launch Forge when nothing is listening. FORGE_EXECUTABLE points at the app binary;
+// otherwise fall back to the platform's usual install location.
+function launchForge() {
+  const executable = process.env.FORGE_EXECUTABLE;
+  let child;
+  if (executable) {
+    child = spawn(executable, [], { detached: true, stdio: 'ignore' });
+  } else if (process.platform === 'darwin') {
+    child = spawn('open', ['-a', 'Forge MOI'], { detached: true, stdio: 'ignore' });
+  } else if (process.platform === 'win32') {
+    child = spawn('cmd', ['/c', 'start', '', 'Forge MOI'], { detached: true, stdio: 'ignore' });
+  } else {
+    child = spawn('forge-moi', [], { detached: true, stdio: 'ignore' });
+  }
+  child.on('error', () => {});
+  child.unref();
+}
+
+async function connectOrLaunch() {
+  const socketPath = getSocketPath();
+  try {
+    return await connect(socketPath);
+  } catch {
+    launchForge();
+  }
+
+  const deadline = Date.now() + CONNECT_TIMEOUT;
+  while (Date.now() < deadline) {
+    await new Promise((resolve) => setTimeout(resolve, 300));
+    try {
+      return await connect(socketPath);
+    } catch {
+      // Still starting
+    }
+  }
+  throw new Error('Could not reach Forge. Is it installed? Set FORGE_EXECUTABLE to its binary path.');
+}
+
+function call(socket, method, params) {
+  return new Promise((resolve, reject) => {
+    const id = 1;
+    socket.on('data', createMessageReader((message) => {
+      if (message.id !== id) return;
+      if (message.error) reject(new Error(message.error.message));
+      else resolve(message.result);
+    }));
+    socket.once('close', () => reject(new Error('Forge closed the connection')));
+    socket.write(encodeMessage({ id, method, params }));
+  });
+}
+
+async function main() {
+  const { command, flags, positional } = parseArgs(process.argv.slice(2));
+  if (flags.help) {
+    console.log(USAGE);
+    return;
+  }
+
+  let method;
+  let params;
+  if (flags.version) {
+    method = 'ping';
+    params = {};
+  } else if (command === 'diff') {
+    if (positional.length !== 2) throw new Error('diff needs exactly two files');
+    method = 'diff';
+    params = { left: path.resolve(positional[0]), right: path.resolve(positional[1]), wait: flags.wait };
+  } else if (command === 'wait') {
+    if (positional.length !== 1) throw new Error('wait needs exactly one file');
+    method = 'wait';
+    params = { path: path.resolve(positional[0]) };
+  } else {
+    method = 'open';
+    params = {
+      targets: (positional.length > 0 ? positional : ['.']).map((spec) => parsePathSpec(spec)),
+      newWindow: flags.newWindow,
+      wait: flags.wait
+    };
+  }
+
+  const socket = await connectOrLaunch();
+  try {
+    const result = await call(socket, method, params);
+    if (flags.version) console.log(`Forge ${result.version}`);
+  } finally {
+    socket.end();
+  }
+}
+
+main().catch((error) => {
+  console.error(`forge: ${error.message}`);
+  process.exit(1);
+});
@@ -0,0 +1,343 @@
+const fs = require('fs').promises;
+const fsSync = require('fs');
+const path = require('path');
+const { app, powerMonitor } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { terminalEvents, getTerminal } = require('./terminal');
+
+const log = createLogger('activity');
+
+const TICK_MS = 30 * 1000;
+// Open spans are written out this often so a crash loses at most a few minutes
+const CHECKPOINT_MS = 5 * 60 * 1000;
+// Shorter spans are tab flicking, not work
+const MIN_SPAN_MS = 1000;
+const DEFAULT_RANGE_DAYS = 7;
+const MAX_RANGE_DAYS = 366;
+const DATE_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
+
+const store = new JsonStore('activity', {
+  settings: {
+    enabled: true,
+    // No input for this long stops the clock until the next keystroke
+    idleMinutes: 5
+  }
+});
+
+// Per window: what it is working on and, while the clock runs, since when.
+// { target: { kind, target }, workspace, startedAt }
+const windows = new Map();
+let idle = false;
+let writeQueue = Promise.resolve();
+
+// Activity never leaves this machine: one JSON line per span in a file per month,
+// { kind: 'file' | 'terminal', target, workspace, startedAt, endedAt }
+function activityDirectory() {
+  return path.join(app.getPath('userData'), 'activity');
+}
+
+const pad = (value) => String(value).padStart(2, '0');
+const dayKey = (date) => `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+const monthFile = (year, month) => path.join(activityDirectory(), `${year}-${pad(month + 1)}.jsonl`);
+
+function getSettings() {
+  return store.get('settings');
+}
+
+function queueWrite(task) {
+  writeQueue = writeQueue.then(task).catch((error) => log.warn(`Could not save activity: ${error.message}`));
+  return writeQueue;
+}
+
+// Write out the span that ran until `end` and stop the clock for this window
+function closeSpan(state, end = Date.now(), { sync = false } = {}) {
+  if (!state.startedAt) return;
+  const startedAt = state.startedAt;
+  state.startedAt = null;
+  if (end - startedAt < MIN_SPAN_MS) return;
+  const span = {
+    kind: state.target.kind,
+    target: state.target.target,
+    workspace: state.workspace,
+    startedAt: new Date(startedAt).toISOString(),
+    endedAt: new Date(end).toISOString()
+  };
+  const started = new Date(startedAt);
+  const file = monthFile(started.getFullYear(), started.getMonth());
+  const line = JSON.stringify(span) + '\n';
+  if (sync) {
+    // Quitting: an async write would not finish
+    try {
+      fsSync.mkdirSync(path.dirname(file), { recursive: true });
+      fsSync.appendFileSync(file, line, 'utf-8');
+    } catch (error) {
+      log.warn(`Could not save activity: ${error.message}`);
+    }
+    return;
+  }
+  queueWrite(async () => {
+    await fs.mkdir(path.dirname(file), { recursive: true });
+    await fs.appendFile(file, line, 'utf-8');
+  });
+}
+
+function openSpan(state) {
+  if (state.startedAt || !state.target || idle || !getSettings().enabled) return;
+  if (!state.window || state.window.isDestroyed() || !state.window.isFocused()) return;
+  state.startedAt = Date.now();
+}
+
+function stateFor(context) {
+  let state = windows.get(context.window.id);
+  if (!state) {
+    state = { window: context.window, target: null, workspace: null, startedAt: null };
+    windows.set(context.window.id, state);
+  }
+  state.workspace = context.workspacePath || null;
+  return state;
+}
+
+// Switch a window to a new file or terminal; null means nothing trackable has focus
+function setTarget(context, target) {
+  const state = stateFor(context);
+  const same = state.target && target && state.target.kind === target.kind && state.target.target === target.target;
+  if (!same) {
+    closeSpan(state);
+    state.target = target;
+  }
+  openSpan(state);
+}
+
+function resolveTarget(target) {
+  if (target === null) return null;
+  if (!target || typeof target !== 'object') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Activity needs { kind: "file", path } or { kind: "terminal", terminalId }');
+  }
+  if (target.kind === 'file') {
+    if (typeof target.path !== 'string' || !path.isAbsolute(target.path)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'File activity needs an absolute path');
+    }
+    return { kind: 'file', target: path.resolve(target.path) };
+  }
+  if (target.kind === 'terminal') {
+    const session = getTerminal(target.terminalId);
+    if (!session) throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${target.terminalId} not found`);
+    // Terminal ids don't outlive the session, so time is booked to the directory
+    return { kind: 'terminal', target: session.cwd || app.getPath('home') };
+  }
+  throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Activity kind must be file or terminal');
+}
+
+// Close every running span at `end`, e.g. when the user went idle `idleSeconds` ago
+function closeAll(end, options) {
+  for (const state of windows.values()) closeSpan(state, end, options);
+}
+
+function resumeFocused() {
+  for (const state of windows.values()) openSpan(state);
+}
+
+function tick() {
+  const now = Date.now();
+  for (const [windowId, state] of windows) {
+    if (state.window.isDestroyed()) {
+      closeSpan(state, now);
+      windows.delete(windowId);
+    }
+  }
+  const { idleMinutes } = getSettings();
+  const idleSeconds = powerMonitor.getSystemIdleTime();
+  if (idleSeconds >= idleMinutes * 60) {
+    if (!idle) {
+      idle = true;
+      closeAll(now - idleSeconds * 1000);
+    }
+    return;
+  }
+  if (idle) {
+    idle = false;
+    resumeFocused();
+  }
+  for (const state of windows.values()) {
+    if (state.startedAt && now - state.startedAt >= CHECKPOINT_MS) {
+      closeSpan(state, now);
+      openSpan(state);
+    }
+  }
+}
+
+// Spans in the month files covering [start, end)
+async function readSpans(start, end) {
+  const spans = [];
+  const cursor = new Date(start.getFullYear(), start.getMonth(), 1);
+  while (cursor < end) {
+    let text = '';
+    try {
+      text = await fs.readFile(monthFile(cursor.getFullYear(), cursor.getMonth()), 'utf-8');
+    } catch (error) {
+      if (error.code !== 'ENOENT') log.warn(`Could not read activity: ${error.message}`);
+    }
+    for (const line of text.split('\n')) {
+      if (!line.trim()) continue;
+      try {
+        spans.push(JSON.parse(line));
+      } catch {
+        // A torn final line from a crash mid-append
+      }
+    }
+    cursor.setMonth(cursor.getMonth() + 1);
+  }
+  return spans;
+}
+
+function parseDay(value, name) {
+  if (!DATE_PATTERN.test(value)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${name} must be a YYYY-MM-DD date`);
+  const [year, month, day] = value.split('-').map(Number);
+  const date = new Date(year, month - 1, day);
+  if (dayKey(date) !== value) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${value} is not a date`);
+  return date;
+}
+
+const addSeconds = (map, key, seconds, make) => {
+  const entry = map.get(key) || map.set(key, make()).get(key);
+  entry.seconds += seconds;
+  return entry;
+};
+
+const finish = (entries) => Array.from(entries)
+  .map((entry) => ({ ...entry, seconds: Math.round(entry.seconds) }))
+  .filter((entry) => entry.seconds > 0)
+  .sort((a, b) => b.seconds - a.seconds);
+
+// Time per day, per file or terminal directory, and per workspace between `from` and `to`
+// (local YYYY-MM-DD dates, both inclusive; the last week by default). `workspace`
+// limits it to one project. Spans over midnight count towards both days.
+async function getActivitySummary(range = {}) {
+  const today = new Date();
+  const end = range.to ? parseDay(range.to, 'to') : new Date(today.getFullYear(), today.getMonth(), today.getDate());
+  const start = range.from ? parseDay(range.from, 'from') : new Date(end.getFullYear(), end.getMonth(), end.getDate() - DEFAULT_RANGE_DAYS + 1);
+  end.setDate(end.getDate() + 1);
+  if (start >= end) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'from must not be after to');
+  if ((end - start) / 86400000 > MAX_RANGE_DAYS + 1) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `A summary covers at most ${MAX_RANGE_DAYS} days`);
+  }
+  const workspace = range.workspace ? path.resolve(range.workspace) : null;
+
+  const spans = await readSpans(start, end);
+  // The clock that is running now counts too
+  for (const state of windows.values()) {
+    if (state.startedAt) {
+      spans.push({ ...state.target, workspace: state.workspace, startedAt: new Date(state.startedAt).toISOString(), endedAt: new Date().toISOString() });
+    }
+  }
+
+  const days = new Map();
+  const items = new Map();
+  const workspaces = new Map();
+  let total = 0;
+  for (const span of spans) {
+    if (workspace && span.workspace !== workspace) continue;
+    let from = Math.max(new Date(span.startedAt).getTime(), start.getTime());
+    const to = Math.min(new Date(span.endedAt).getTime(), end.getTime());
+    while (from < to) {
+      const at = new Date(from);
+      const midnight = new Date(at.getFullYear(), at.getMonth(), at.getDate() + 1).getTime();
+      const until = Math.min(to, midnight);
+      const seconds = (until - from) / 1000;
+      const itemKey = `${span.kind}\0${span.target}\0${span.workspace}`;
+      const day = addSeconds(days, dayKey(at), seconds, () => ({ date: dayKey(at), seconds: 0, items: new Map() }));
+      addSeconds(day.items, itemKey, seconds, () => ({ kind: span.kind, target: span.target, workspace: span.workspace, seconds: 0 }));
+      addSeconds(items, itemKey, seconds, () => ({ kind: span.kind, target: span.target, workspace: span.workspace, seconds: 0 }));
+      addSeconds(workspaces, span.workspace, seconds, () => ({ workspace: span.workspace, seconds: 0 }));
+      total += seconds;
+      from = until;
+    }
+  }
+
+  const last = new Date(end);
+  last.setDate(last.getDate() - 1);
+  return {
+    from: dayKey(start),
+    to: dayKey(last),
+    totalSeconds: Math.round(total),
+    days: Array.from(days.values())
+      .map((day) => ({ date: day.date, seconds: Math.round(day.seconds), items: finish(day.items.values()) }))
+      .filter((day) => day.seconds > 0)
+      .sort((a, b) => a.date.localeCompare(b.date)),
+    items: finish(items.values()),
+    workspaces: finish(workspaces.values())
+  };
+}
+
+async function clearActivity() {
+  closeAll(Date.now());
+  await queueWrite(() => fs.rm(activityDirectory(), { recursive: true, force: true }));
+  resumeFocused();
+  log.info('Activity log cleared');
+  return true;
+}
+
+function updateSettings(changes = {}) {
+  const settings = { ...getSettings(), ...changes };
+  settings.enabled = settings.enabled !== false;
+  settings.idleMinutes = Math.max(1, Math.min(120, Number(settings.idleMinutes) || 5));
+  store.set('settings', settings);
+  if (settings.enabled) {
+    resumeFocused();
+  } else {
+    closeAll(Date.now());
+  }
+  return settings;
+}
+
+// Typing in a terminal makes it the window's active target, even without a focus report
+terminalEvents.on('input', (terminalId) => {
+  const session = getTerminal(terminalId);
+  const context = session ? windowManager.get(session.windowId) : null;
+  if (!context) return;
+  if (idle) {
+    idle = false;
+    resumeFocused();
+  }
+  setTarget(context, { kind: 'terminal', target: session.cwd || app.getPath('home') });
+});
+
+function setupActivityHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // Spans only run while a window has focus and the user isn't idle
+  app.on('browser-window-blur', (event, win) => {
+    const state = windows.get(win.id);
+    if (state) closeSpan(state);
+  });
+  app.on('browser-window-focus', (event, win) => {
+    const state = windows.get(win.id);
+    if (state) openSpan(state);
+  });
+  app.on('before-quit', () => closeAll(Date.now(), { sync: true }));
+  powerMonitor.on('lock-screen', () => closeAll(Date.now()));
+  powerMonitor.on('suspend', () => closeAll(Date.now()));
+  setInterval(tick, TICK_MS).unref();
+
+  // The renderer reports the focused editor or terminal: { kind: 'file', path },
+  // { kind: 'terminal', terminalId }, or null
+  ipcMain.handle('activity:focus', async (event, target) => {
+    const context = windowManager.fromSender(event.sender);
+    if (!context) return false;
+    setTarget(context, resolveTarget(target));
+    return true;
+  });
+
+  ipcMain.handle('activity:getSummary', async (event, range) => getActivitySummary(range));
+
+  ipcMain.handle('activity:clear', async () => clearActivity());
+
+  ipcMain.handle('activity:getSettings', async () => getSettings());
+
+  ipcMain.handle('activity:updateSettings', async (event, changes) => updateSettings(changes));
+}
+
+module.exports = { setupActivityHandlers, getActivitySummary };
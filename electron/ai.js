@@ -0,0 +1,390 @@
+const path = require('path');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { getSecret } = require('./secrets');
+const { readTextFile } = require('./search');
+const { getTerminal } = require('./terminal');
+
+const log = createLogger('ai');
+
+const REQUEST_TIMEOUT = 120000;
+// Streamed tokens are batched so the renderer isn't flooded with one IPC message per token
+const TOKEN_FLUSH_MS = 30;
+const DEFAULT_CONTEXT_CHARS = 48000;
+const TERMINAL_CONTEXT_CHARS = 8000;
+
+// Provider types: 'openai' covers any OpenAI-compatible endpoint (OpenAI, LM Studio,
+// vLLM, OpenRouter); 'ollama' talks to the native Ollama chat API.
+const DEFAULT_PROVIDERS = [
+  { id: 'openai', name: 'OpenAI', type: 'openai', baseUrl: 'https://api.openai.com/v1', model: 'gpt-4o-mini', apiKeySecret: 'openai.apiKey' },
+  { id: 'ollama', name: 'Ollama (local)', type: 'ollama', baseUrl: 'http://127.0.0.1:11434', model: 'llama3.1', apiKeySecret: null }
+];
+
+const store = new JsonStore('ai', {
+  providers: DEFAULT_PROVIDERS,
+  defaultProvider: 'openai'
+});
+
+// eslint-disable-next-line no-control-regex
+const ANSI_PATTERN = /\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]/g;
+
+function httpError(status, body, provider) {
+  const message = `${provider.name} returned ${status}: ${body.slice(0, 500)}`;
+  if (status === 401 || status === 403) return new ForgeError(ErrorCode.ACCESS_DENIED, message, { recoverable: false });
+  if (status === 404) return new ForgeError(ErrorCode.NOT_FOUND, message);
+  if (status === 429) return new ForgeError(ErrorCode.BUSY, message);
+  return new ForgeError(ErrorCode.IO_ERROR, message);
+}
+
+// Yield complete lines from a streamed fetch body
+async function* readLines(body) {
+  const decoder = new TextDecoder();
+  let buffer = '';
+  for await (const chunk of body) {
+    buffer += decoder.decode(chunk, { stream: true });
+    let newline;
+    while ((newline = buffer.indexOf('\n')) >= 0) {
+      yield buffer.slice(0, newline).replace(/\r$/, '');
+      buffer = buffer.slice(newline + 1);
+    }
+  }
+  if (buffer) yield buffer;
+}
+
+const ADAPTERS = {
+  openai: {
+    url: (provider) => `${provider.baseUrl.replace(/\/+$/, '')}/chat/completions`,
+    body: (request, model) => ({
+      model,
+      messages: request.messages,
+      stream: true,
+      temperature: request.temperature,
+      max_tokens: request.maxTokens,
+      stop: request.stop
+    }),
+    // Server-sent events: `data: {...}` lines ending with `data: [DONE]`
+    parse(line) {
+      if (!line.startsWith('data:')) return null;
+      const data = line.slice(5).trim();
+      if (data === '[DONE]') return { done: true };
+      const json = JSON.parse(data);
+      const choice = json.choices && json.choices[0];
+      return {
+        delta: choice && choice.delta ? choice.delta.content || '' : '',
+        finishReason: choice ? choice.finish_reason : null,
+        usage: json.usage ? { promptTokens: json.usage.prompt_tokens, completionTokens: json.usage.completion_tokens } : null
+      };
+    },
+    modelsUrl: (provider) => `${provider.baseUrl.replace(/\/+$/, '')}/models`,
+    parseModels: (json) => (json.data || []).map((model) => model.id),
+    embeddingsUrl: (provider) => `${provider.baseUrl.replace(/\/+$/, '')}/embeddings`,
+    embeddingsBody: (texts, model) => ({ model, input: texts }),
+    parseEmbeddings: (json) => (json.data || []).sort((a, b) => a.index - b.index).map((item) => item.embedding),
+    defaultEmbeddingModel: 'text-embedding-3-small'
+  },
+  ollama: {
+    url: (provider) => `${provider.baseUrl.replace(/\/+$/, '')}/api/chat`,
+    body: (request, model) => ({
+      model,
+      messages: request.messages,
+      stream: true,
+      options: { temperature: request.temperature, num_predict: request.maxTokens, stop: request.stop }
+    }),
+    // Newline-delimited JSON
+    parse(line) {
+      if (!line.trim()) return null;
+      const json = JSON.parse(line);
+      return {
+        delta: json.message ? json.message.content || '' : '',
+        done: Boolean(json.done),
+        finishReason: json.done ? json.done_reason || 'stop' : null,
+        usage: json.done ? { promptTokens: json.prompt_eval_count, completionTokens: json.eval_count } : null
+      };
+    },
+    modelsUrl: (provider) => `${provider.baseUrl.replace(/\/+$/, '')}/api/tags`,
+    parseModels: (json) => (json.models || []).map((model) => model.name),
+    embeddingsUrl: (provider) => `${provider.baseUrl.replace(/\/+$/, '')}/api/embed`,
+    embeddingsBody: (texts, model) => ({ model, input: texts }),
+    parseEmbeddings: (json) => json.embeddings || [],
+    defaultEmbeddingModel: 'nomic-embed-text'
+  }
+};
+
+class AiManager {
+  constructor() {
+    // requestId -> AbortController for in-flight requests
+    this.active = new Map();
+  }
+
+  listProviders() {
+    return store.get('providers').map((provider) => ({
+      ...provider,
+      isDefault: provider.id === store.get('defaultProvider'),
+      hasApiKey: provider.apiKeySecret ? Boolean(getSecret(provider.apiKeySecret)) : null
+    }));
+  }
+
+  getProvider(providerId) {
+    const id = providerId || store.get('defaultProvider');
+    const provider = store.get('providers').find((candidate) => candidate.id === id);
+    if (!provider) {
+      throw new ForgeError(ErrorCode.NOT_FOUND, `AI provider ${id} is not configured`);
+    }
+    return provider;
+  }
+
+  saveProvider(provider) {
+    if (!provider || !provider.id || !ADAPTERS[provider.type] || !provider.baseUrl) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A provider needs an id, a baseUrl, and a type of "openai" or "ollama"');
+    }
+    const saved = {
+      id: provider.id,
+      name: provider.name || provider.id,
+      type: provider.type,
+      baseUrl: provider.baseUrl,
+      model: provider.model || null,
+      apiKeySecret: provider.apiKeySecret || null
+    };
+    const providers = store.get('providers').filter((candidate) => candidate.id !== saved.id);
+    store.set('providers', [...providers, saved]);
+    return saved;
+  }
+
+  deleteProvider(providerId) {
+    const providers = store.get('providers');
+    const remaining = providers.filter((provider) => provider.id !== providerId);
+    if (remaining.length === providers.length) return false;
+    store.set('providers', remaining);
+    if (store.get('defaultProvider') === providerId) {
+      store.set('defaultProvider', remaining.length > 0 ? remaining[0].id : null);
+    }
+    return true;
+  }
+
+  setDefaultProvider(providerId) {
+    this.getProvider(providerId);
+    store.set('defaultProvider', providerId);
+    return true;
+  }
+
+  headers(provider) {
+    const headers = { 'Content-Type': 'application/json' };
+    if (provider.apiKeySecret) {
+      const apiKey = getSecret(provider.apiKeySecret);
+      if (!apiKey) {
+        throw new ForgeError(ErrorCode.ACCESS_DENIED, `No API key stored for ${provider.name} (secret "${provider.apiKeySecret}")`, { recoverable: false });
+      }
+      headers.Authorization = `Bearer ${apiKey}`;
+    }
+    return headers;
+  }
+
+  async listModels(providerId) {
+    const provider = this.getProvider(providerId);
+    const adapter = ADAPTERS[provider.type];
+    const response = await fetch(adapter.modelsUrl(provider), {
+      headers: this.headers(provider),
+      signal: AbortSignal.timeout(15000)
+    });
+    if (!response.ok) throw httpError(response.status, await response.text(), provider);
+    return adapter.parseModels(await response.json());
+  }
+
+  // Embed a batch of texts; resolves with one vector per input, in order
+  async embed(texts, options = {}) {
+    const provider = this.getProvider(options.providerId);
+    const adapter = ADAPTERS[provider.type];
+    const model = options.model || adapter.defaultEmbeddingModel;
+    let response;
+    try {
+      response = await fetch(adapter.embeddingsUrl(provider), {
+        method: 'POST',
+        headers: this.headers(provider),
+        body: JSON.stringify(adapter.embeddingsBody(texts, model)),
+        signal: options.signal ? AbortSignal.any([options.signal, AbortSignal.timeout(REQUEST_TIMEOUT)]) : AbortSignal.timeout(REQUEST_TIMEOUT)
+      });
+    } catch (error) {
+      throw this.abortError(error, options.signal, provider);
+    }
+    if (!response.ok) throw httpError(response.status, await response.text(), provider);
+    const vectors = adapter.parseEmbeddings(await response.json());
+    if (vectors.length !== texts.length) {
+      throw new ForgeError(ErrorCode.INTERNAL, `${provider.name} returned ${vectors.length} embeddings for ${texts.length} inputs`);
+    }
+    return { vectors, model, providerId: provider.id };
+  }
+
+  // Build a system message from open files and terminal output, trimmed to a character budget.
+  // context: { files: [{ path, content?, selection? }], terminals: [terminalId], instructions? }
+  async assembleContext(context = {}, maxChars = DEFAULT_CONTEXT_CHARS) {
+    const sections = [];
+    let remaining = maxChars;
+    const add = (header, body) => {
+      if (remaining <= 0 || !body) return;
+      const text = body.length > remaining ? `${body.slice(0, remaining)}\n…(truncated)` : body;
+      sections.push(`${header}\n${text}`);
+      remaining -= text.length;
+    };
+
+    if (context.instructions) add('Instructions:', context.instructions);
+
+    for (const file of context.files || []) {
+      if (file.selection) add(`Selected text in ${file.path}:`, `\`\`\`\n${file.selection}\n\`\`\``);
+      // Unsaved buffers are sent by the renderer; otherwise read from disk
+      const content = file.content !== undefined ? file.content : await readTextFile(file.path);
+      const language = path.extname(file.path).slice(1);
+      if (content) add(`File ${file.path}:`, `\`\`\`${language}\n${content}\n\`\`\``);
+    }
+
+    for (const terminalId of context.terminals || []) {
+      const session = getTerminal(terminalId);
+      if (!session || !session.scrollback) continue;
+      const output = session.scrollback.replace(ANSI_PATTERN, '').slice(-TERMINAL_CONTEXT_CHARS);
+      add(`Recent output of terminal ${terminalId} (cwd ${session.cwd}):`, output);
+    }
+
+    return sections.length > 0 ? sections.join('\n\n') : null;
+  }
+
+  // Stream a chat completion. Calls onToken(delta) as text arrives and resolves with the
+  // full response. request: { messages, providerId?, model?, temperature?, maxTokens?, stop?, context? }
+  async chat(request, { onToken, signal } = {}) {
+    if (!request || !Array.isArray(request.messages) || request.messages.length === 0) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A chat request needs at least one message');
+    }
+    const provider = this.getProvider(request.providerId);
+    const adapter = ADAPTERS[provider.type];
+    const model = request.model || provider.model;
+    if (!model) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `No model selected for ${provider.name}`);
+    }
+
+    const contextText = request.context ? await this.assembleContext(request.context, request.contextChars) : null;
+    const messages = contextText
+      ? [{ role: 'system', content: contextText }, ...request.messages]
+      : request.messages;
+
+    const timeout = AbortSignal.timeout(request.timeout || REQUEST_TIMEOUT);
+    const combined = signal ? AbortSignal.any([signal, timeout]) : timeout;
+    const startedAt = Date.now();
+
+    let response;
+    try {
+      response = await fetch(adapter.url(provider), {
+        method: 'POST',
+        headers: this.headers(provider),
+        body: JSON.stringify(adapter.body({ ...request, messages }, model)),
+        signal: combined
+      });
+    } catch (error) {
+      throw this.abortError(error, signal, provider);
+    }
+    if (!response.ok) throw httpError(response.status, await response.text(), provider);
+
+    let content = '';
+    let finishReason = null;
+    let usage = null;
+    try {
+      for await (const line of readLines(response.body)) {
+        let parsed;
+        try {
+          parsed = adapter.parse(line);
+        } catch {
+          log.debug(`Skipping unparseable stream line from ${provider.name}`);
+          continue;
+        }
+        if (!parsed) continue;
+        if (parsed.delta) {
+          content += parsed.delta;
+          if (onToken) onToken(parsed.delta);
+        }
+        if (parsed.finishReason) finishReason = parsed.finishReason;
+        if (parsed.usage) usage = parsed.usage;
+        if (parsed.done) break;
+      }
+    } catch (error) {
+      throw this.abortError(error, signal, provider);
+    }
+
+    log.info(`Chat via ${provider.id}/${model} finished in ${Date.now() - startedAt}ms`);
+    return { content, finishReason, usage, providerId: provider.id, model };
+  }
+
+  abortError(error, signal, provider) {
+    if (signal && signal.aborted) return new ForgeError(ErrorCode.CANCELLED, 'AI request was cancelled');
+    if (error && error.name === 'TimeoutError') return new ForgeError(ErrorCode.TIMEOUT, `${provider.name} did not respond in time`);
+    if (error instanceof ForgeError) return error;
+    return ForgeError.from(error, `Could not reach ${provider.name}`, ErrorCode.IO_ERROR);
+  }
+
+  // Start a streamed request for the renderer; tokens arrive as ai:token events on `target`
+  startStream(request, target) {
+    const requestId = `ai_${uuidv4()}`;
+    const controller = new AbortController();
+    this.active.set(requestId, controller);
+
+    let pending = '';
+    let flushTimer = null;
+    const flush = () => {
+      flushTimer = null;
+      if (!pending) return;
+      emit('ai:token', { requestId, delta: pending }, { target });
+      pending = '';
+    };
+
+    this.chat(request, {
+      signal: controller.signal,
+      onToken: (delta) => {
+        pending += delta;
+        if (!flushTimer) flushTimer = setTimeout(flush, TOKEN_FLUSH_MS);
+      }
+    })
+      .then((result) => {
+        clearTimeout(flushTimer);
+        flush();
+        emit('ai:done', { requestId, ...result }, { target });
+      })
+      .catch((error) => {
+        clearTimeout(flushTimer);
+        flush();
+        const forgeError = ForgeError.from(error);
+        if (forgeError.code !== ErrorCode.CANCELLED) log.warn(`AI request ${requestId} failed: ${forgeError.message}`);
+        emit('ai:error', { requestId, code: forgeError.code, message: forgeError.message }, { target });
+      })
+      .finally(() => this.active.delete(requestId));
+
+    return { requestId };
+  }
+
+  cancel(requestId) {
+    const controller = this.active.get(requestId);
+    if (!controller) return false;
+    controller.abort();
+    return true;
+  }
+}
+
+const aiManager = new AiManager();
+
+function setupAiHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('ai:chat', async (event, request) => aiManager.startStream(request, event.sender));
+
+  ipcMain.handle('ai:cancel', async (event, requestId) => aiManager.cancel(requestId));
+
+  ipcMain.handle('ai:listProviders', async () => aiManager.listProviders());
+
+  ipcMain.handle('ai:saveProvider', async (event, provider) => aiManager.saveProvider(provider));
+
+  ipcMain.handle('ai:deleteProvider', async (event, providerId) => aiManager.deleteProvider(providerId));
+
+  ipcMain.handle('ai:setDefaultProvider', async (event, providerId) => aiManager.setDefaultProvider(providerId));
+
+  ipcMain.handle('ai:listModels', async (event, providerId) => aiManager.listModels(providerId));
+}
+
+module.exports = { setupAiHandlers, aiManager, AiManager };
@@ -0,0 +1,350 @@
+const vm = require('vm');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob } = require('./jobs');
+const { registerCommand, unregisterCommand, executeCommand, listCommands, commandEvents } = require('./commands');
+const { terminalEvents, getTerminal, listTerminals, getScreenOutput } = require('./terminal');
+const { openPaths } = require('./openRouter');
+const { windowManager } = require('./windows');
+
+const log = createLogger('automation');
+
+const NAME_PATTERN = /^[\w .-]{1,64}$/;
+const MAX_WAIT_MS = 60000;
+// Synchronous script code (before the first await) gets this long
+const SCRIPT_SYNC_TIMEOUT = 1000;
+// Input typed at a prompt like these is never recorded: sudo, ssh, gpg, git credential and
+// similar prompts turn echo off, and what's typed there is a secret
+const SECRET_PROMPT = /(?:password|passphrase|passcode|\bpin\b|token|secret|otp|verification code)[^\n]*[:?]\s*$/i;
+const PROMPT_TAIL = 256;
+const ANSI_PATTERN = /\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]/g;
+
+// Macros are recorded action lists; scripts are JavaScript run against the `forge` API below.
+// Actions: { type: 'command', id, args } | { type: 'terminalInput', terminalId, text }
+//        | { type: 'openFile', path, line?, column? } | { type: 'wait', ms }
+const store = new JsonStore('automation', { macros: {}, scripts: {} });
+
+// The one in-progress recording: { name, windowId, actions, lastAt, secretInput, redacted }.
+// `secretInput` holds terminals whose input is being skipped until the line is submitted.
+let recording = null;
+
+function validateName(name) {
+  if (typeof name !== 'string' || !NAME_PATTERN.test(name)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid name: ${name}`);
+  }
+}
+
+function commandIdFor(kind, name) {
+  return `automation.${kind}.${name.replace(/[^\w]+/g, '-')}`;
+}
+
+// Names are slugged into command ids, so "a b" and "a-b" can't both be saved
+function checkCommandId(kind, name) {
+  const id = commandIdFor(kind, name);
+  const clash = Object.keys(store.get(kind === 'macro' ? 'macros' : 'scripts')).find((other) => other !== name && commandIdFor(kind, other) === id);
+  if (clash) {
+    throw new ForgeError(ErrorCode.ALREADY_EXISTS, `"${name}" is too similar to the existing ${kind} "${clash}"; choose another name`, { details: { name, existing: clash } });
+  }
+}
+
+function recordAction(action) {
+  if (!recording) return;
+  const now = Date.now();
+  // Keep the user's pacing where it matters (e.g. waiting for a build), but skip typing gaps
+  const gap = now - recording.lastAt;
+  if (recording.actions.length > 0 && gap > 1500) {
+    recording.actions.push({ type: 'wait', ms: Math.min(gap, MAX_WAIT_MS) });
+  }
+  recording.lastAt = now;
+
+  const previous = recording.actions[recording.actions.length - 1];
+  if (action.type === 'terminalInput' && previous && previous.type === 'terminalInput' && previous.terminalId === action.terminalId) {
+    // Keystrokes arrive one at a time; merge them
+    previous.text += action.text;
+  } else {
+    recording.actions.push(action);
+  }
+  emitRecordingState();
+}
+
+function emitRecordingState() {
+  emit('automation:recordingChanged', {
+    recording: Boolean(recording),
+    name: recording ? recording.name : null,
+    actionCount: recording ? recording.actions.length : 0
+  });
+}
+
+commandEvents.on('executed', (id, args) => {
+  if (!id.startsWith('automation.')) recordAction({ type: 'command', id, args });
+});
+function atSecretPrompt(session) {
+  const tail = getScreenOutput(session).slice(-PROMPT_TAIL).replace(ANSI_PATTERN, '');
+  return SECRET_PROMPT.test(tail.split(/\r?\n|\r/).pop());
+}
+
+terminalEvents.on('input', (terminalId, text) => {
+  const session = getTerminal(terminalId);
+  if (!recording || !session || session.windowId !== recording.windowId) return;
+  if (!recording.secretInput.has(terminalId) && atSecretPrompt(session)) recording.secretInput.add(terminalId);
+  if (recording.secretInput.has(terminalId)) {
+    // Dropped through the Enter that submits it; replaying a macro can't answer the prompt anyway
+    const submit = text.search(/[\r\n]/);
+    if (submit === -1) return;
+    recording.secretInput.delete(terminalId);
+    recording.redacted++;
+    text = text.slice(submit + 1);
+    if (!text) return;
+  }
+  recordAction({ type: 'terminalInput', terminalId, text });
+});
+
+function startRecording(name, windowId) {
+  validateName(name);
+  checkCommandId('macro', name);
+  if (recording) {
+    throw new ForgeError(ErrorCode.BUSY, `Already recording "${recording.name}"`);
+  }
+  recording = { name, windowId, actions: [], lastAt: Date.now(), secretInput: new Set(), redacted: 0 };
+  log.info(`Recording macro "${name}"`);
+  emitRecordingState();
+  return true;
+}
+
+function stopRecording(save = true) {
+  if (!recording) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Not recording');
+  }
+  const { name, actions, redacted } = recording;
+  recording = null;
+  emitRecordingState();
+  if (redacted) log.info(`Left ${redacted} password prompt answer(s) out of macro "${name}"`);
+  if (!save || actions.length === 0) return null;
+  return saveMacro(name, actions);
+}
+
+function saveMacro(name, actions) {
+  validateName(name);
+  checkCommandId('macro', name);
+  if (!Array.isArray(actions)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A macro needs a list of actions');
+  }
+  const macros = store.get('macros');
+  macros[name] = { name, actions, updatedAt: new Date().toISOString() };
+  store.set('macros', macros);
+  registerAutomationCommand('macro', name);
+  log.info(`Saved macro "${name}" with ${actions.length} actions`);
+  return macros[name];
+}
+
+function deleteEntry(kind, name) {
+  const key = kind === 'macro' ? 'macros' : 'scripts';
+  const entries = store.get(key);
+  if (!entries[name]) return false;
+  delete entries[name];
+  store.set(key, entries);
+  unregisterCommand(commandIdFor(kind, name));
+  return true;
+}
+
+// Terminal ids don't survive restarts; fall back to the window's first terminal
+function resolveTerminal(terminalId, windowId) {
+  if (terminalId && getTerminal(terminalId)) return getTerminal(terminalId);
+  const [first] = listTerminals(windowId);
+  if (!first) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, 'No terminal is open to send input to');
+  }
+  return getTerminal(first.id);
+}
+
+function delay(ms, signal) {
+  return new Promise((resolve, reject) => {
+    const timer = setTimeout(resolve, Math.min(Math.max(0, ms), MAX_WAIT_MS));
+    if (signal) {
+      signal.addEventListener('abort', () => {
+        clearTimeout(timer);
+        reject(new ForgeError(ErrorCode.CANCELLED, 'Automation was cancelled'));
+      }, { once: true });
+    }
+  });
+}
+
+async function runAction(action, target, handle) {
+  handle.throwIfCancelled();
+  switch (action.type) {
+    case 'command':
+      return executeCommand(action.id, action.args || [], { sender: target.sender });
+    case 'terminalInput':
+      resolveTerminal(action.terminalId, target.windowId).pty.write(action.text);
+      return true;
+    case 'openFile':
+      return openPaths([{ path: action.path, line: action.line, column: action.column }], { windowId: target.windowId, source: 'automation' });
+    case 'wait':
+      return delay(action.ms, handle.signal);
+    default:
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown action type: ${action.type}`);
+  }
+}
+
+// target: { sender, windowId } — where renderer-handled commands and terminal input go
+function playMacro(name, target) {
+  const macro = store.get('macros')[name];
+  if (!macro) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `Macro "${name}" not found`);
+  }
+  const job = runJob('automation', `Run macro ${name}`, async (handle) => {
+    for (let i = 0; i < macro.actions.length; i++) {
+      await runAction(macro.actions[i], target, handle);
+      handle.reportProgress((i + 1) / macro.actions.length);
+    }
+    return macro.actions.length;
+  });
+  return { jobId: job.id, promise: job.promise };
+}
+
+// The API scripts see as `forge`. Every call checks for cancellation so a stuck
+// script can be stopped from the jobs panel.
+function createScriptApi(target, handle, output) {
+  const guard = (fn) => async (...args) => {
+    handle.throwIfCancelled();
+    return fn(...args);
+  };
+  return Object.freeze({
+    commands: Object.freeze({
+      execute: guard((id, ...args) => executeCommand(id, args, { sender: target.sender })),
+      list: guard(() => listCommands().map(({ id, title, category }) => ({ id, title, category })))
+    }),
+    terminal: Object.freeze({
+      send: guard((text, terminalId) => {
+        resolveTerminal(terminalId, target.windowId).pty.write(String(text));
+        return true;
+      }),
+      run: guard((command, terminalId) => {
+        resolveTerminal(terminalId, target.windowId).pty.write(`${command}\r`);
+        return true;
+      }),
+      list: guard(() => listTerminals(target.windowId))
+    }),
+    workbench: Object.freeze({
+      open: guard((path, line, column) => openPaths([{ path, line, column }], { windowId: target.windowId, source: 'automation' }))
+    }),
+    macros: Object.freeze({
+      play: guard((name) => playMacro(name, target).promise)
+    }),
+    wait: guard((ms) => delay(ms, handle.signal)),
+    log: (...args) => {
+      output.push(args.map((arg) => (typeof arg === 'string' ? arg : JSON.stringify(arg))).join(' '));
+    }
+  });
+}
+
+// Run a script. This is a convenience sandbox for the user's own automation, not a
+// security boundary: scripts only get the `forge` API, but they run in the main process.
+function runScript(source, target, title = 'Run script') {
+  if (typeof source !== 'string' || !source.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Script source is empty');
+  }
+  const output = [];
+  const job = runJob('automation', title, async (handle) => {
+    const context = vm.createContext({ forge: createScriptApi(target, handle, output), console: { log: (...args) => output.push(args.join(' ')) } });
+    let script;
+    try {
+      script = new vm.Script(`(async () => {\n${source}\n})()`, { filename: 'automation-script.js' });
+    } catch (error) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Script has a syntax error: ${error.message}`);
+    }
+    const result = await script.runInContext(context, { timeout: SCRIPT_SYNC_TIMEOUT });
+    return { result: result === undefined ? null : result, output };
+  });
+  return { jobId: job.id, promise: job.promise };
+}
+
+function saveScript(name, source) {
+  validateName(name);
+  checkCommandId('script', name);
+  try {
+    new vm.Script(`(async () => {\n${source}\n})()`);
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Script has a syntax error: ${error.message}`);
+  }
+  const scripts = store.get('scripts');
+  scripts[name] = { name, source, updatedAt: new Date().toISOString() };
+  store.set('scripts', scripts);
+  registerAutomationCommand('script', name);
+  return scripts[name];
+}
+
+// Saved macros and scripts appear in the command palette
+function registerAutomationCommand(kind, name) {
+  const id = commandIdFor(kind, name);
+  unregisterCommand(id);
+  registerCommand({
+    id,
+    title: `${kind === 'macro' ? 'Run Macro' : 'Run Script'}: ${name}`,
+    category: 'automation',
+    source: 'automation',
+    handler: (...args) => {
+      const context = args.pop();
+      const target = { sender: context.sender, windowId: context.window ? context.window.id : undefined };
+      if (kind === 'macro') return playMacro(name, target).promise;
+      const script = store.get('scripts')[name];
+      if (!script) throw new ForgeError(ErrorCode.NOT_FOUND, `Script "${name}" not found`);
+      return runScript(script.source, target, `Run script ${name}`).promise;
+    }
+  });
+}
+
+function targetFor(event) {
+  const context = windowManager.fromSender(event.sender);
+  return { sender: event.sender, windowId: context ? context.window.id : undefined };
+}
+
+function setupAutomationHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  Object.keys(store.get('macros')).forEach((name) => registerAutomationCommand('macro', name));
+  Object.keys(store.get('scripts')).forEach((name) => registerAutomationCommand('script', name));
+
+  ipcMain.handle('automation:startRecording', async (event, name) => startRecording(name, targetFor(event).windowId));
+
+  ipcMain.handle('automation:stopRecording', async (event, save = true) => stopRecording(save));
+
+  // Renderer-side actions (e.g. opening a file from the explorer) are reported here while recording
+  ipcMain.handle('automation:recordAction', async (event, action) => {
+    if (!action || !['openFile', 'command', 'wait'].includes(action.type)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Unsupported action');
+    }
+    recordAction(action);
+    return Boolean(recording);
+  });
+
+  ipcMain.handle('automation:getRecording', async () => (recording ? { name: recording.name, actions: recording.actions } : null));
+
+  ipcMain.handle('automation:listMacros', async () => Object.values(store.get('macros')));
+
+  ipcMain.handle('automation:saveMacro', async (event, name, actions) => saveMacro(name, actions));
+
+  ipcMain.handle('automation:deleteMacro', async (event, name) => deleteEntry('macro', name));
+
+  // The renderer follows the run through its job; a failing step is logged rather than left unhandled
+  ipcMain.handle('automation:playMacro', async (event, name) => {
+    const { jobId, promise } = playMacro(name, targetFor(event));
+    promise.catch((error) => {
+      if (error.code !== ErrorCode.CANCELLED) log.warn(`Macro "${name}" failed: ${error.message}`);
+    });
+    return { jobId };
+  });
+
+  ipcMain.handle('automation:listScripts', async () => Object.values(store.get('scripts')));
+
+  ipcMain.handle('automation:saveScript', async (event, name, source) => saveScript(name, source));
+
+  ipcMain.handle('automation:deleteScript', async (event, name) => deleteEntry('script', name));
+
+  // Resolves with the script's return value and forge.log output
+  ipcMain.handle('automation:runScript', async (event, source) => runScript(source, targetFor(event)).promise);
+}
+
+module.exports = { setupAutomationHandlers, playMacro, runScript };
@@ -0,0 +1,400 @@
+const http = require('http');
+const crypto = require('crypto');
+const { app } = require('electron');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { acceptWebSocket, tokensMatch } = require('./websocket');
+const { windowManager } = require('./windows');
+const { acquireDebugger, releaseDebugger } = require('./devtoolsProtocol');
+
+const log = createLogger('automation-bridge');
+
+const HOLDER = 'automation';
+const HOST = '127.0.0.1';
+const MAX_CLIENTS = 8;
+const BROWSER_CONTEXT_ID = 'forge-previews';
+// CDP error codes as Chromium reports them
+const SERVER_ERROR = -32000;
+const METHOD_NOT_FOUND = -32601;
+
+// Every preview in the app, registered by browser.js: id -> { view, windowId }.
+// A bridge only exposes the previews of the window that started it.
+const targets = new Map();
+// The running bridge: { server, token, port, browserId, windowId, owner, clients }
+let bridge = null;
+// Preview id -> { client, sessionId, onMessage, onDetach }; one automation client per preview
+const controllers = new Map();
+let nextClientId = 1;
+
+function sessionIdFor() {
+  return crypto.randomBytes(16).toString('hex').toUpperCase();
+}
+
+function visibleTargets() {
+  if (!bridge) return [];
+  return Array.from(targets.entries())
+    .filter(([, target]) => target.windowId === bridge.windowId && !target.view.webContents.isDestroyed())
+    .map(([id]) => id);
+}
+
+function targetInfo(id) {
+  const { webContents } = targets.get(id).view;
+  return {
+    targetId: id,
+    type: 'page',
+    title: webContents.getTitle(),
+    url: webContents.getURL(),
+    attached: controllers.has(id),
+    canAccessOpener: false,
+    browserContextId: BROWSER_CONTEXT_ID
+  };
+}
+
+function endpoints() {
+  const base = `${HOST}:${bridge.port}/${bridge.token}`;
+  return { endpoint: `http://${base}`, wsEndpoint: `ws://${base}/devtools/browser/${bridge.browserId}`, pageBase: `ws://${base}/devtools/page/` };
+}
+
+function describe() {
+  if (!bridge) return { running: false, port: null, endpoint: null, wsEndpoint: null, clients: 0, controlled: [] };
+  const { endpoint, wsEndpoint } = endpoints();
+  return { running: true, port: bridge.port, endpoint, wsEndpoint, clients: bridge.clients.size, controlled: Array.from(controllers.keys()) };
+}
+
+// Tell the workbench which previews are under automation, e.g. to badge their tabs
+function announce(owner = bridge && bridge.owner) {
+  if (owner && !owner.isDestroyed()) emit('browser:automationChanged', describe(), { target: owner });
+}
+
+function send(client, message) {
+  client.connection.send(JSON.stringify(message));
+}
+
+// Events for every client watching targets (Target.setDiscoverTargets)
+function broadcastTargetEvent(method, params) {
+  if (!bridge) return;
+  for (const client of bridge.clients) {
+    if (client.discover) send(client, { method, params });
+  }
+}
+
+function versionInfo() {
+  return {
+    protocolVersion: '1.3',
+    product: `Forge/${app.getVersion()}`,
+    revision: '',
+    userAgent: app.userAgentFallback,
+    jsVersion: process.versions.v8
+  };
+}
+
+// Take control of a preview for `client`. Pages driven over a flat session get their
+// events tagged with its id; a page endpoint connection (`implicit`) gets them untagged.
+function attach(client, id, { implicit = false } = {}) {
+  if (!targets.has(id) || !visibleTargets().includes(id)) {
+    throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `No target with id ${id}`);
+  }
+  const existing = controllers.get(id);
+  if (existing && existing.client === client) return existing.sessionId;
+  if (existing) throw new ForgeError(ErrorCode.BUSY, `Preview ${id} is already controlled by another automation client`);
+
+  const { webContents } = targets.get(id).view;
+  acquireDebugger(webContents, HOLDER);
+  const sessionId = sessionIdFor();
+  const onMessage = (event, method, params, childSessionId) => {
+    // Iframes and workers the page auto-attaches get child sessions of their own
+    if (method === 'Target.attachedToTarget') client.children.set(params.sessionId, id);
+    if (method === 'Target.detachedFromTarget') client.children.delete(params.sessionId);
+    const tag = childSessionId || (implicit ? undefined : sessionId);
+    send(client, tag ? { method, params, sessionId: tag } : { method, params });
+  };
+  // The page closed, or someone opened its DevTools
+  const onDetach = () => detach(client, sessionId, { destroyed: webContents.isDestroyed() });
+  webContents.debugger.on('message', onMessage);
+  webContents.debugger.once('detach', onDetach);
+  controllers.set(id, { client, sessionId, onMessage, onDetach });
+  client.sessions.set(sessionId, id);
+  if (implicit) client.implicitSession = sessionId;
+  else send(client, { method: 'Target.attachedToTarget', params: { sessionId, targetInfo: targetInfo(id), waitingForDebugger: false } });
+  log.info(`Automation client ${client.id} attached to ${id}`);
+  announce();
+  return sessionId;
+}
+
+function detach(client, sessionId, { destroyed = false } = {}) {
+  const id = client.sessions.get(sessionId);
+  if (!id) return false;
+  client.sessions.delete(sessionId);
+  for (const [child, owner] of Array.from(client.children)) {
+    if (owner === id) client.children.delete(child);
+  }
+  const controller = controllers.get(id);
+  if (controller && controller.client === client) {
+    controllers.delete(id);
+    const target = targets.get(id);
+    if (target && !target.view.webContents.isDestroyed()) {
+      const { debugger: protocol } = target.view.webContents;
+      protocol.removeListener('message', controller.onMessage);
+      protocol.removeListener('detach', controller.onDetach);
+      releaseDebugger(target.view.webContents, HOLDER);
+    }
+  }
+  if (client.implicitSession === sessionId) {
+    client.connection.close(1000, destroyed ? 'Target closed' : 'Detached');
+  } else if (client.connection.isOpen) {
+    send(client, { method: 'Target.detachedFromTarget', params: { sessionId, targetId: id } });
+  }
+  log.info(`Automation client ${client.id} detached from ${id}`);
+  announce();
+  return true;
+}
+
+// Methods on the browser session. Previews belong to the workbench, so clients can't
+// create, close or isolate them; they drive the ones that are open.
+async function browserCommand(client, method, params) {
+  switch (method) {
+    case 'Browser.getVersion':
+      return versionInfo();
+    case 'Browser.close':
+    case 'Browser.setDownloadBehavior':
+    case 'Browser.grantPermissions':
+    case 'Browser.resetPermissions':
+    case 'Target.activateTarget':
+      return {};
+    case 'Target.getBrowserContexts':
+      return { browserContextIds: [] };
+    case 'Target.getTargets':
+      return { targetInfos: visibleTargets().map(targetInfo) };
+    case 'Target.getTargetInfo':
+      if (params.targetId && targets.has(params.targetId)) return { targetInfo: targetInfo(params.targetId) };
+      return { targetInfo: { targetId: bridge.browserId, type: 'browser', title: '', url: '', attached: true, canAccessOpener: false } };
+    case 'Target.setDiscoverTargets':
+      client.discover = Boolean(params.discover);
+      if (client.discover) {
+        for (const id of visibleTargets()) send(client, { method: 'Target.targetCreated', params: { targetInfo: targetInfo(id) } });
+      }
+      return {};
+    case 'Target.setAutoAttach':
+      if (params.autoAttach && !params.flatten) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Only flat sessions are supported; pass flatten: true');
+      client.autoAttach = Boolean(params.autoAttach);
+      if (client.autoAttach) {
+        for (const id of visibleTargets()) {
+          if (!controllers.has(id)) attach(client, id);
+        }
+      }
+      return {};
+    case 'Target.attachToTarget':
+      if (!params.flatten) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Only flat sessions are supported; pass flatten: true');
+      return { sessionId: attach(client, params.targetId) };
+    case 'Target.detachFromTarget':
+      if (!detach(client, params.sessionId)) throw new ForgeError(ErrorCode.NOT_FOUND, 'No session with given id');
+      return {};
+    case 'Target.createTarget':
+    case 'Target.closeTarget':
+    case 'Target.createBrowserContext':
+    case 'Target.disposeBrowserContext':
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${method} isn't available: open and close previews in Forge`);
+    default:
+      throw new ForgeError(ErrorCode.NOT_FOUND, `'${method}' wasn't found`, { details: { cdpCode: METHOD_NOT_FOUND } });
+  }
+}
+
+// Page methods that would tear the preview out from under the workbench
+const BLOCKED_PAGE_METHODS = new Set(['Page.close', 'Browser.close', 'Target.closeTarget']);
+
+async function pageCommand(client, sessionId, method, params) {
+  const root = client.sessions.get(sessionId);
+  const id = root || client.children.get(sessionId);
+  const target = id && targets.get(id);
+  if (!target || target.view.webContents.isDestroyed()) throw new ForgeError(ErrorCode.NOT_FOUND, 'No session with given id');
+  if (BLOCKED_PAGE_METHODS.has(method)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${method} isn't available: close previews in Forge`);
+  return target.view.webContents.debugger.sendCommand(method, params, root ? undefined : sessionId);
+}
+
+async function handleMessage(client, data) {
+  let message;
+  try {
+    message = JSON.parse(typeof data === 'string' ? data : data.toString('utf-8'));
+  } catch {
+    client.connection.close(1003, 'Messages must be JSON');
+    return;
+  }
+  const { id, method, params = {} } = message;
+  const sessionId = message.sessionId || client.implicitSession;
+  const tag = message.sessionId ? { sessionId: message.sessionId } : {};
+  try {
+    const result = sessionId ? await pageCommand(client, sessionId, method, params) : await browserCommand(client, method, params);
+    send(client, { id, result: result || {}, ...tag });
+  } catch (error) {
+    send(client, { id, error: { code: (error.details && error.details.cdpCode) || SERVER_ERROR, message: error.message }, ...tag });
+  }
+}
+
+function connectClient(connection, pageId) {
+  const client = {
+    id: nextClientId++,
+    connection,
+    sessions: new Map(),
+    children: new Map(),
+    implicitSession: null,
+    discover: false,
+    autoAttach: false
+  };
+  bridge.clients.add(client);
+  connection.on('message', (data) => handleMessage(client, data));
+  connection.on('close', () => {
+    if (bridge) bridge.clients.delete(client);
+    for (const sessionId of Array.from(client.sessions.keys())) detach(client, sessionId);
+    log.info(`Automation client ${client.id} disconnected`);
+    announce();
+  });
+  log.info(`Automation client ${client.id} connected${pageId ? ` to ${pageId}` : ''}`);
+  if (pageId) {
+    try {
+      attach(client, pageId, { implicit: true });
+    } catch (error) {
+      connection.close(1011, error.message);
+    }
+  }
+  announce();
+}
+
+// DNS rebinding guard, as Chromium's own DevTools server does: only loopback Host headers
+function hostAllowed(request) {
+  const host = String(request.headers.host || '');
+  return host === `${HOST}:${bridge.port}` || host === `localhost:${bridge.port}`;
+}
+
+// Paths are /<token>/json/version, /<token>/json/list, /<token>/devtools/{browser,page}/<id>
+function parseRequestPath(request) {
+  const [, token, ...rest] = new URL(request.url, `http://${HOST}`).pathname.split('/');
+  return { authorized: tokensMatch(bridge.token, token), route: rest.filter(Boolean) };
+}
+
+function handleRequest(request, response) {
+  const { authorized, route } = parseRequestPath(request);
+  const json = (status, body) => {
+    response.writeHead(status, { 'Content-Type': 'application/json; charset=UTF-8', 'Cache-Control': 'no-store' });
+    response.end(JSON.stringify(body, null, 2));
+  };
+  if (!hostAllowed(request) || !authorized || route[0] !== 'json') return json(404, { error: 'Not found' });
+  const { wsEndpoint, pageBase } = endpoints();
+  if (route[1] === 'version') {
+    const version = versionInfo();
+    return json(200, {
+      'Browser': version.product,
+      'Protocol-Version': version.protocolVersion,
+      'User-Agent': version.userAgent,
+      'V8-Version': version.jsVersion,
+      'WebKit-Version': process.versions.chrome,
+      'webSocketDebuggerUrl': wsEndpoint
+    });
+  }
+  if (!route[1] || route[1] === 'list') {
+    return json(200, visibleTargets().map((id) => {
+      const info = targetInfo(id);
+      return { id, type: 'page', title: info.title, url: info.url, description: '', webSocketDebuggerUrl: `${pageBase}${encodeURIComponent(id)}` };
+    }));
+  }
+  return json(404, { error: 'Not found' });
+}
+
+function handleUpgrade(request, socket, head) {
+  const { authorized, route } = parseRequestPath(request);
+  const [devtools, kind, id] = route;
+  const valid = hostAllowed(request) && authorized && devtools === 'devtools' &&
+    ((kind === 'browser' && id === bridge.browserId) || (kind === 'page' && targets.has(decodeURIComponent(id || ''))));
+  if (!valid || bridge.clients.size >= MAX_CLIENTS) {
+    socket.end(`HTTP/1.1 ${valid ? '503 Service Unavailable' : '404 Not Found'}\r\nConnection: close\r\n\r\n`);
+    return;
+  }
+  const connection = acceptWebSocket(request, socket, head);
+  if (connection) connectClient(connection, kind === 'page' ? decodeURIComponent(id) : null);
+}
+
+// Serve the CDP endpoint for `windowId`'s previews on loopback. Playwright connects with
+// chromium.connectOverCDP(endpoint); Puppeteer with connect({ browserWSEndpoint: wsEndpoint }).
+// The token in the path keeps other local processes and web pages out.
+async function startAutomationBridge({ windowId, owner, port = 0 }) {
+  if (bridge) {
+    if (bridge.windowId === windowId) return describe();
+    throw new ForgeError(ErrorCode.BUSY, 'The automation bridge is already running for another window');
+  }
+  if (!Number.isInteger(port) || port < 0 || port > 65535) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Port must be 0-65535');
+  const server = http.createServer(handleRequest);
+  server.on('upgrade', handleUpgrade);
+  await new Promise((resolve, reject) => {
+    server.once('error', (error) => reject(ForgeError.from(error, 'Could not start the automation bridge')));
+    server.listen(port, HOST, resolve);
+  });
+  bridge = {
+    server,
+    token: crypto.randomBytes(24).toString('base64url'),
+    port: server.address().port,
+    browserId: crypto.randomUUID(),
+    windowId,
+    owner,
+    clients: new Set()
+  };
+  log.info(`Automation bridge listening on ${HOST}:${bridge.port} for window ${windowId}`);
+  announce();
+  return describe();
+}
+
+function stopAutomationBridge(reason = 'stopped') {
+  if (!bridge) return false;
+  const { server, clients, owner } = bridge;
+  for (const client of Array.from(clients)) client.connection.close(1001, 'Bridge stopped');
+  server.close();
+  bridge = null;
+  log.info(`Automation bridge stopped (${reason})`);
+  announce(owner);
+  return true;
+}
+
+// browser.js reports previews as they open and close
+function registerAutomationTarget(id, view, ownerWindow) {
+  targets.set(id, { view, windowId: ownerWindow.id });
+  const changed = () => {
+    if (targets.has(id) && visibleTargets().includes(id)) broadcastTargetEvent('Target.targetInfoChanged', { targetInfo: targetInfo(id) });
+  };
+  view.webContents.on('did-navigate', changed);
+  view.webContents.on('page-title-updated', changed);
+  if (!bridge || bridge.windowId !== ownerWindow.id) return;
+  broadcastTargetEvent('Target.targetCreated', { targetInfo: targetInfo(id) });
+  for (const client of bridge.clients) {
+    if (client.autoAttach && !controllers.has(id)) attach(client, id);
+  }
+}
+
+function unregisterAutomationTarget(id) {
+  const target = targets.get(id);
+  if (!target) return;
+  const controller = controllers.get(id);
+  if (controller) detach(controller.client, controller.sessionId, { destroyed: true });
+  targets.delete(id);
+  if (bridge && bridge.windowId === target.windowId) broadcastTargetEvent('Target.targetDestroyed', { targetId: id });
+}
+
+function setupAutomationBridgeHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('browser:startAutomationBridge', async (event, options = {}) => {
+    const context = windowManager.fromSender(event.sender);
+    if (!context) throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Window not available');
+    const wasRunning = Boolean(bridge);
+    const result = await startAutomationBridge({ windowId: context.window.id, owner: event.sender, port: options.port });
+    // The bridge ends with the window whose previews it serves
+    if (!wasRunning) context.window.once('closed', () => stopAutomationBridge('window closed'));
+    return result;
+  });
+
+  ipcMain.handle('browser:stopAutomationBridge', async () => stopAutomationBridge());
+
+  ipcMain.handle('browser:getAutomationBridge', async () => describe());
+
+  app.on('will-quit', () => stopAutomationBridge('quitting'));
+}
+
+module.exports = { setupAutomationBridgeHandlers, startAutomationBridge, stopAutomationBridge, registerAutomationTarget, unregisterAutomationTarget };
@@ -0,0 +1,120 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { cdTerminalTo } = require('./terminal');
+
+const log = createLogger('bookmarks');
+
+const MAX_BOOKMARKS = 200;
+
+const store = new JsonStore('bookmarks', {
+  // { id, path, label, createdAt, lastUsedAt, useCount }
+  directories: []
+});
+
+function publish() {
+  emit('bookmarks:changed', { count: store.get('directories').length });
+}
+
+// Most used first, then alphabetically
+function listBookmarks() {
+  return store.get('directories').slice().sort((a, b) => b.useCount - a.useCount || a.label.localeCompare(b.label));
+}
+
+function getBookmark(id) {
+  const bookmark = store.get('directories').find((item) => item.id === id);
+  if (!bookmark) throw new ForgeError(ErrorCode.NOT_FOUND, `Bookmark ${id} not found`);
+  return bookmark;
+}
+
+async function addBookmark(directory, label) {
+  if (typeof directory !== 'string' || !path.isAbsolute(directory)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Bookmarks need an absolute directory path');
+  }
+  const resolved = path.resolve(directory);
+  let stats;
+  try {
+    stats = await fs.stat(resolved);
+  } catch (error) {
+    throw ForgeError.from(error, `Cannot bookmark ${resolved}`);
+  }
+  if (!stats.isDirectory()) {
+    throw new ForgeError(ErrorCode.NOT_DIRECTORY, `${resolved} is not a directory`, { details: { path: resolved } });
+  }
+
+  const directories = store.get('directories');
+  const existing = directories.find((item) => item.path === resolved);
+  if (existing) {
+    if (label) return updateBookmark(existing.id, { label });
+    return existing;
+  }
+  if (directories.length >= MAX_BOOKMARKS) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `At most ${MAX_BOOKMARKS} directories can be bookmarked`);
+  }
+  const bookmark = {
+    id: `bookmark_${uuidv4()}`,
+    path: resolved,
+    label: (typeof label === 'string' && label.trim()) || path.basename(resolved) || resolved,
+    createdAt: new Date().toISOString(),
+    lastUsedAt: null,
+    useCount: 0
+  };
+  store.set('directories', [...directories, bookmark]);
+  log.info(`Bookmarked ${resolved}`);
+  publish();
+  return bookmark;
+}
+
+function updateBookmark(id, changes = {}) {
+  const bookmark = { ...getBookmark(id) };
+  if (changes.label !== undefined) {
+    if (typeof changes.label !== 'string' || !changes.label.trim()) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Bookmark label cannot be empty');
+    }
+    bookmark.label = changes.label.trim();
+  }
+  if (changes.used) {
+    bookmark.lastUsedAt = new Date().toISOString();
+    bookmark.useCount += 1;
+  }
+  store.set('directories', store.get('directories').map((item) => (item.id === id ? bookmark : item)));
+  publish();
+  return bookmark;
+}
+
+function removeBookmark(id) {
+  const directories = store.get('directories');
+  const remaining = directories.filter((item) => item.id !== id);
+  if (remaining.length === directories.length) return false;
+  store.set('directories', remaining);
+  publish();
+  return true;
+}
+
+// Change a terminal to a bookmarked directory
+async function openBookmarkInTerminal(id, terminalId) {
+  const bookmark = getBookmark(id);
+  const result = await cdTerminalTo(terminalId, bookmark.path);
+  updateBookmark(id, { used: true });
+  return result;
+}
+
+function setupBookmarkHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('bookmarks:list', async () => listBookmarks());
+
+  ipcMain.handle('bookmarks:add', async (event, directory, label) => addBookmark(directory, label));
+
+  ipcMain.handle('bookmarks:update', async (event, id, changes) => updateBookmark(id, { label: changes && changes.label }));
+
+  ipcMain.handle('bookmarks:remove', async (event, id) => removeBookmark(id));
+
+  ipcMain.handle('bookmarks:cdTerminal', async (event, id, terminalId) => openBookmarkInTerminal(id, terminalId));
+}
+
+module.exports = { setupBookmarkHandlers, listBookmarks, addBookmark, removeBookmark };
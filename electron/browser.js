@@ -2,17 +2,44 @@ const { BrowserView, shell, Menu, MenuItem, clipboard, desktopCapturer } = requi
 const { v4: uuidv4 } = require('uuid');
 const fs = require('fs').promises;
 const path = require('path');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { startPageFor } = require('./startPage');
+const { requireProfile, recordVisit, updateTitle } = require('./browserHistory');
+const { classifyInput, searchUrl } = require('./browserInput');
+const { attachConsoleCapture, detachConsoleCapture } = require('./browserConsole');
+const { enforceNavigationPolicy, navigationBlockReason, reportBlocked } = require('./navigationPolicy');
+const { trackBrowserView, updateBrowserView, untrackBrowserView, applySavedViewState, recordDevToolsMode } = require('./browserSession');
+const { registerAutomationTarget, unregisterAutomationTarget } = require('./automationBridge');
+
+const log = createLogger('browser');
 
 // Browser view management
-function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
+function setupBrowserHandlers(ipcMain, getWindow, browserViews) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // Views whose pending navigation came from the address bar
+  const typedNavigations = new Set();
+
   // Create a new browser view
   ipcMain.handle('browser:create', async (event, options = {}) => {
-    const mainWindow = getMainWindow();
-    if (!mainWindow) {
-      throw new Error('Main window not available');
+    const ownerWindow = getWindow(event);
+    if (!ownerWindow) {
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Window not available');
     }
 
     const id = options.id || `browser_${uuidv4()}`;
+    // History and bookmarks are kept per profile
+    const profile = requireProfile(options.profile);
+    const sendToOwner = (channel, payload) => emit(channel, payload, { target: ownerWindow.webContents });
+    const workspaceOf = () => {
+      const context = windowManager.get(ownerWindow.id);
+      return context ? context.workspacePath : null;
+    };
+    // Without a URL a tab opens the project's start page
+    const initialUrl = options.url || startPageFor(workspaceOf(), profile);
     
     // Create browser view
     const view = new BrowserView({
@@ -26,7 +53,21 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
 
     // Store the view
     browserViews.set(id, view);
-    console.log(`[Browser] Created browser view ${id}. Total views: ${browserViews.size}`);
+    attachConsoleCapture(id, view, ownerWindow);
+    enforceNavigationPolicy(id, view, ownerWindow);
+    trackBrowserView(id, view, ownerWindow, { profile, bounds: options.bounds || null, visible: !options.hidden });
+    registerAutomationTarget(id, view, ownerWindow);
+    log.info(`Created browser view ${id}. Total views: ${browserViews.size}`);
+
+    // Views live and die with the window that created them
+    ownerWindow.once('closed', () => {
+      if (browserViews.get(id) !== view) return;
+      if (!view.webContents.isDestroyed()) view.webContents.destroy();
+      browserViews.delete(id);
+      detachConsoleCapture(id);
+      untrackBrowserView(id);
+      unregisterAutomationTarget(id);
+    });
 
     // Inject script to handle middle clicks
     view.webContents.on('dom-ready', () => {
@@ -57,25 +98,33 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
 
     // Set up event handlers
     view.webContents.on('did-navigate', (event, url) => {
-      mainWindow.webContents.send('browser:navigate', { id, url });
+      sendToOwner('browser:navigate', { id, url });
+      recordVisit(url, { profile, workspace: workspaceOf(), typed: typedNavigations.delete(id) });
     });
 
     view.webContents.on('page-title-updated', (event, title) => {
-      mainWindow.webContents.send('browser:titleUpdate', { id, title });
+      sendToOwner('browser:titleUpdate', { id, title });
+      updateTitle(view.webContents.getURL(), title, { profile });
     });
 
     view.webContents.on('did-start-loading', () => {
-      mainWindow.webContents.send('browser:loadStart', { id });
+      sendToOwner('browser:loadStart', { id });
     });
 
     view.webContents.on('did-stop-loading', () => {
-      mainWindow.webContents.send('browser:loadStop', { id });
+      sendToOwner('browser:loadStop', { id });
     });
 
     // Handle new window requests - create new tab instead of external browser
     view.webContents.setWindowOpenHandler(({ url, disposition }) => {
+      // Pages can't open tabs outside the window's navigation policy either
+      const blocked = navigationBlockReason(ownerWindow.id, url);
+      if (blocked) {
+        reportBlocked(id, ownerWindow, url, blocked, disposition);
+        return { action: 'deny' };
+      }
       // Send event to create new tab in the app
-      mainWindow.webContents.send('browser:newTabRequest', { url, disposition });
+      sendToOwner('browser:newTabRequest', { url, disposition });
       return { action: 'deny' };
     });
 
@@ -131,8 +180,8 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
         menu.append(new MenuItem({
           label: `Search for "${params.selectionText.slice(0, 20)}${params.selectionText.length > 20 ? '...' : ''}"`,
           click: () => {
-            mainWindow.webContents.send('browser:newTabRequest', { 
-              url: `https://www.google.com/search?q=${encodeURIComponent(params.selectionText)}`, 
+            sendToOwner('browser:newTabRequest', { 
+              url: searchUrl(params.selectionText), 
               disposition: 'foreground-tab' 
             });
           }
@@ -148,7 +197,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
         menu.append(new MenuItem({
           label: 'Open Link in New Tab',
           click: () => {
-            mainWindow.webContents.send('browser:newTabRequest', { 
+            sendToOwner('browser:newTabRequest', { 
               url: params.linkURL, 
               disposition: 'foreground-tab' 
             });
@@ -158,7 +207,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
         menu.append(new MenuItem({
           label: 'Open Link in Background Tab',
           click: () => {
-            mainWindow.webContents.send('browser:newTabRequest', { 
+            sendToOwner('browser:newTabRequest', { 
               url: params.linkURL, 
               disposition: 'background-tab' 
             });
@@ -194,7 +243,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
         menu.append(new MenuItem({
           label: 'Open Image in New Tab',
           click: () => {
-            mainWindow.webContents.send('browser:newTabRequest', { 
+            sendToOwner('browser:newTabRequest', { 
               url: params.srcURL, 
               disposition: 'foreground-tab' 
             });
@@ -216,7 +265,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
         label: 'View Page Source',
         click: () => {
           const sourceUrl = view.webContents.getURL();
-          mainWindow.webContents.send('browser:newTabRequest', { 
+          sendToOwner('browser:newTabRequest', { 
             url: `view-source:${sourceUrl}`, 
             disposition: 'foreground-tab' 
           });
@@ -236,10 +285,9 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
       menu.popup();
     });
 
-    // Load initial URL if provided
-    if (options.url) {
-      view.webContents.loadURL(options.url);
-    }
+    view.webContents.loadURL(initialUrl);
+    // Views recreated from a saved session come back zoomed and with devtools as they were
+    applySavedViewState(id, view, options);
 
     // Set initial bounds if provided
     if (options.bounds) {
@@ -248,12 +296,12 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
 
     // Add to window if not hidden
     if (!options.hidden) {
-      mainWindow.addBrowserView(view);
+      ownerWindow.addBrowserView(view);
     }
 
     return {
       id,
-      url: options.url || 'about:blank'
+      url: initialUrl
     };
   });
 
@@ -261,20 +309,17 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:navigate', async (event, id, url) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
-    // Normalize URL
-    let normalizedUrl = url;
-    if (!/^https?:\/\//i.test(url)) {
-      if (/^[\w.-]+\.\w+/.test(url)) {
-        normalizedUrl = `https://${url}`;
-      } else {
-        normalizedUrl = `https://www.google.com/search?q=${encodeURIComponent(url)}`;
-      }
+    // Address bar text: a URL, localhost shorthand, file path, or search
+    const target = classifyInput(url);
+    if (target.kind === 'invalid') {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, target.error, { details: { id, input: url } });
     }
 
-    view.webContents.loadURL(normalizedUrl);
+    typedNavigations.add(id);
+    view.webContents.loadURL(target.url);
     return true;
   });
 
@@ -282,7 +327,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:goBack', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     if (view.webContents.navigationHistory.canGoBack()) {
@@ -295,7 +340,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:goForward', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     if (view.webContents.navigationHistory.canGoForward()) {
@@ -308,7 +353,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:refresh', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     view.webContents.reload();
@@ -317,7 +362,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
 
   // Close browser view
   ipcMain.handle('browser:close', async (event, id) => {
-    const mainWindow = getMainWindow();
+    const ownerWindow = getWindow(event);
     const view = browserViews.get(id);
     if (!view) {
       return true; // Already closed
@@ -325,8 +370,8 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
 
     try {
       // Remove from window
-      if (mainWindow && !mainWindow.isDestroyed()) {
-        mainWindow.removeBrowserView(view);
+      if (ownerWindow && !ownerWindow.isDestroyed()) {
+        ownerWindow.removeBrowserView(view);
       }
 
       // Destroy web contents if not already destroyed
@@ -334,12 +379,16 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
         view.webContents.destroy();
       }
     } catch (e) {
-      console.error(`[Browser] Error closing browser view ${id}:`, e);
+      log.error(`Error closing browser view ${id}:`, e);
     }
     
     // Remove from map
     browserViews.delete(id);
-    console.log(`[Browser] Closed browser view ${id}. Total views: ${browserViews.size}`);
+    typedNavigations.delete(id);
+    detachConsoleCapture(id);
+    untrackBrowserView(id);
+    unregisterAutomationTarget(id);
+    log.info(`Closed browser view ${id}. Total views: ${browserViews.size}`);
     return true;
   });
 
@@ -347,82 +396,88 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:setBounds', async (event, id, bounds) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
-    view.setBounds({
+    const rounded = {
       x: Math.round(bounds.x),
       y: Math.round(bounds.y),
       width: Math.round(bounds.width),
       height: Math.round(bounds.height)
-    });
+    };
+    view.setBounds(rounded);
+    updateBrowserView(id, { bounds: rounded });
     return true;
   });
 
   // Show browser view
   ipcMain.handle('browser:show', async (event, id) => {
-    const mainWindow = getMainWindow();
+    const ownerWindow = getWindow(event);
     const view = browserViews.get(id);
-    if (!view || !mainWindow) {
-      console.warn(`[Browser] Cannot show view ${id}: view or window not found`);
+    if (!view || !ownerWindow) {
+      log.warn(`Cannot show view ${id}: view or window not found`);
       return false;
     }
     
-    if (mainWindow.isDestroyed() || view.webContents.isDestroyed()) {
-      console.warn(`[Browser] Cannot show view ${id}: window or view destroyed`);
+    if (ownerWindow.isDestroyed() || view.webContents.isDestroyed()) {
+      log.warn(`Cannot show view ${id}: window or view destroyed`);
       return false;
     }
 
     // Hide all other browser views first to prevent overlap
-    const currentViews = mainWindow.getBrowserViews();
+    const currentViews = ownerWindow.getBrowserViews();
     for (const existingView of currentViews) {
       // Check if this view belongs to our browser views
       const existingId = Array.from(browserViews.entries()).find(([_, v]) => v === existingView)?.[0];
       if (existingId && existingId !== id) {
-        mainWindow.removeBrowserView(existingView);
-        console.log(`[Browser] Hid browser view ${existingId} to show ${id}`);
+        ownerWindow.removeBrowserView(existingView);
+        log.debug(`Hid browser view ${existingId} to show ${id}`);
       }
     }
 
     // Add the target view if not already added
     if (!currentViews.includes(view)) {
-      mainWindow.addBrowserView(view);
-      console.log(`[Browser] Showing browser view ${id}`);
+      ownerWindow.addBrowserView(view);
+      log.debug(`Showing browser view ${id}`);
     }
+    updateBrowserView(id, { visible: true });
     return true;
   });
 
   // Hide browser view
   ipcMain.handle('browser:hide', async (event, id) => {
-    const mainWindow = getMainWindow();
+    const ownerWindow = getWindow(event);
     const view = browserViews.get(id);
-    if (!view || !mainWindow) {
-      console.warn(`[Browser] Cannot hide view ${id}: view or window not found`);
+    if (!view || !ownerWindow) {
+      log.warn(`Cannot hide view ${id}: view or window not found`);
       return false;
     }
     
-    if (mainWindow.isDestroyed() || view.webContents.isDestroyed()) {
-      console.warn(`[Browser] Cannot hide view ${id}: window or view destroyed`);
+    if (ownerWindow.isDestroyed() || view.webContents.isDestroyed()) {
+      log.warn(`Cannot hide view ${id}: window or view destroyed`);
       return false;
     }
 
     try {
-      mainWindow.removeBrowserView(view);
+      ownerWindow.removeBrowserView(view);
     } catch (e) {
-      console.error(`[Browser] Error hiding view ${id}:`, e);
+      log.error(`Error hiding view ${id}:`, e);
       return false;
     }
+    updateBrowserView(id, { visible: false });
     return true;
   });
 
   // Open DevTools
-  ipcMain.handle('browser:openDevTools', async (event, id) => {
+  ipcMain.handle('browser:openDevTools', async (event, id, options = {}) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
-    view.webContents.openDevTools();
+    // The dock position is remembered with the session
+    recordDevToolsMode(id, options.mode);
+    view.webContents.openDevTools(options.mode ? { mode: options.mode } : undefined);
     return true;
   });
 
@@ -430,7 +485,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:closeDevTools', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     view.webContents.closeDevTools();
@@ -441,7 +496,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:captureScreenshot', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     const image = await view.webContents.capturePage();
@@ -452,18 +507,18 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:captureRegion', async (event, id, rect) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     // Validate rect parameters
     if (!rect || typeof rect.x !== 'number' || typeof rect.y !== 'number' || 
         typeof rect.width !== 'number' || typeof rect.height !== 'number') {
-      throw new Error('Invalid capture region parameters');
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Invalid capture region parameters');
     }
 
     // Ensure positive dimensions
     if (rect.width <= 0 || rect.height <= 0) {
-      throw new Error('Capture region must have positive dimensions');
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Capture region must have positive dimensions');
     }
 
     // Capture the specified region
@@ -481,7 +536,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:saveScreenshot', async (event, id, filePath) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     const image = await view.webContents.capturePage();
@@ -502,12 +557,12 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:startRecording', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     // Check if already recording
     if (recordingSessions.has(id)) {
-      throw new Error('Already recording this browser view');
+      throw new ForgeError(ErrorCode.BUSY, 'Already recording this browser view');
     }
 
     // Get the browser view bounds for recording
@@ -519,13 +574,13 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
     });
 
     // Find the main window
-    const mainWindow = getMainWindow();
+    const ownerWindow = getWindow(event);
     const mainSource = sources.find(source => 
-      source.id === `window:${mainWindow.getMediaSourceId()}:0`
+      source.id === `window:${ownerWindow.getMediaSourceId()}:0`
     );
 
     if (!mainSource) {
-      throw new Error('Could not find window to record');
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Could not find window to record');
     }
 
     // Store recording session info
@@ -545,7 +600,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:stopRecording', async (event, id) => {
     const session = recordingSessions.get(id);
     if (!session) {
-      throw new Error('No recording session found');
+      throw new ForgeError(ErrorCode.NOT_FOUND, 'No recording session found');
     }
 
     recordingSessions.delete(id);
@@ -564,7 +619,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:getUrl', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     return view.webContents.getURL();
@@ -574,7 +629,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:getTitle', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     return view.webContents.getTitle();
@@ -584,7 +639,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:canGoBack', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     return view.webContents.navigationHistory.canGoBack();
@@ -594,7 +649,7 @@ function setupBrowserHandlers(ipcMain, getMainWindow, browserViews) {
   ipcMain.handle('browser:canGoForward', async (event, id) => {
     const view = browserViews.get(id);
     if (!view) {
-      throw new Error(`Browser view ${id} not found`);
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
     }
 
     return view.webContents.navigationHistory.canGoForward();
@@ -0,0 +1,212 @@
+const { webFrameMain } = require('electron');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('browser-console');
+
+const MAX_ENTRIES = 2000;
+const MAX_MESSAGE_LENGTH = 10000;
+const DEFAULT_LIMIT = 500;
+// Hook reports travel as console.debug lines starting with this marker
+const REPORT_PREFIX = '\u2063forge-frame:';
+// Other Forge hooks (the action recorder) report the same way; they aren't page output
+const INTERNAL_PREFIX = '\u2063forge-';
+const LEVELS = ['debug', 'info', 'warning', 'error'];
+
+// Installed into every frame once it loads. Forwards uncaught errors and rejections (with
+// their stacks, which the native console message lacks) through the frame's own console,
+// so they stay attributed to it, and hooks same-origin child iframes as they appear:
+// about:blank and srcdoc frames often never fire a load event of their own.
+const FRAME_HOOK = `(() => {
+  const PREFIX = ${JSON.stringify(REPORT_PREFIX)};
+  const MARK = '__forgeFrameHook';
+  const watched = new WeakSet();
+  const install = (win) => {
+    try {
+      if (!win || win[MARK]) return;
+      Object.defineProperty(win, MARK, { value: true });
+    } catch {
+      return; // cross-origin: that frame gets its own injection
+    }
+    const report = (detail) => {
+      try { win.console.debug(PREFIX + JSON.stringify(detail)); } catch {}
+    };
+    win.addEventListener('error', (event) => {
+      if (!(event instanceof win.ErrorEvent)) return; // failed <img>/<script> loads
+      report({ kind: 'error', message: event.message, source: event.filename, line: event.lineno, column: event.colno, stack: event.error && event.error.stack });
+    });
+    win.addEventListener('unhandledrejection', (event) => {
+      const reason = event.reason;
+      report({ kind: 'rejection', message: reason && reason.message ? reason.message : String(reason), stack: reason && reason.stack });
+    });
+    const hookChildren = () => {
+      for (const frame of win.document.querySelectorAll('iframe, frame')) {
+        install(frame.contentWindow);
+        if (watched.has(frame)) continue;
+        watched.add(frame);
+        frame.addEventListener('load', () => install(frame.contentWindow));
+      }
+    };
+    hookChildren();
+    new win.MutationObserver(hookChildren).observe(win.document, { childList: true, subtree: true });
+    report({ kind: 'ready' });
+  };
+  install(window);
+})();`;
+
+// Per browser view: { view, windowId, entries, nextId, hookedFrames }
+const captures = new Map();
+
+function describeFrame(frame) {
+  if (!frame) return { frameId: null, frameUrl: null, frameOrigin: null, frameName: null, isMainFrame: true };
+  try {
+    return {
+      frameId: frame.frameTreeNodeId,
+      frameUrl: frame.url,
+      frameOrigin: frame.origin,
+      frameName: frame.name || null,
+      isMainFrame: !frame.parent
+    };
+  } catch {
+    // The frame was detached while the message was in flight
+    return { frameId: null, frameUrl: null, frameOrigin: null, frameName: null, isMainFrame: false };
+  }
+}
+
+function record(viewId, capture, fields) {
+  const entry = {
+    id: capture.nextId++,
+    viewId,
+    windowId: capture.windowId,
+    timestamp: new Date().toISOString(),
+    ...fields,
+    message: String(fields.message || '').slice(0, MAX_MESSAGE_LENGTH)
+  };
+  capture.entries.push(entry);
+  if (capture.entries.length > MAX_ENTRIES) capture.entries.splice(0, capture.entries.length - MAX_ENTRIES);
+  if (!capture.owner.isDestroyed()) emit('browser:console', entry, { target: capture.owner.webContents });
+}
+
+function onConsoleMessage(viewId, capture, event, legacyLevel, legacyMessage, legacyLine, legacySource) {
+  const message = event.message !== undefined ? event.message : legacyMessage;
+  const frame = describeFrame(event.frame);
+  if (typeof message === 'string' && message.startsWith(REPORT_PREFIX)) {
+    let report;
+    try {
+      report = JSON.parse(message.slice(REPORT_PREFIX.length));
+    } catch {
+      return;
+    }
+    if (report.kind === 'ready') {
+      if (frame.frameId !== null) capture.hookedFrames.add(frame.frameId);
+      return;
+    }
+    record(viewId, capture, {
+      kind: report.kind === 'rejection' ? 'rejection' : 'error',
+      level: 'error',
+      message: report.message,
+      source: report.source || null,
+      line: Number.isInteger(report.line) ? report.line : null,
+      column: Number.isInteger(report.column) ? report.column : null,
+      stack: typeof report.stack === 'string' ? report.stack : null,
+      ...frame
+    });
+    return;
+  }
+  if (typeof message === 'string' && message.startsWith(INTERNAL_PREFIX)) return;
+  // Hooked frames report their uncaught errors with stacks; skip Chromium's copy
+  if (frame.frameId !== null && capture.hookedFrames.has(frame.frameId) && /^Uncaught /.test(message)) return;
+  const level = typeof event.level === 'string' ? event.level : LEVELS[legacyLevel] || 'info';
+  record(viewId, capture, {
+    kind: 'console',
+    level,
+    message,
+    source: event.sourceId !== undefined ? event.sourceId : legacySource || null,
+    line: event.lineNumber !== undefined ? event.lineNumber : legacyLine || null,
+    column: null,
+    stack: null,
+    ...frame
+  });
+}
+
+function injectHook(frame) {
+  if (!frame || frame.detached) return;
+  frame.executeJavaScript(FRAME_HOOK).catch((error) => log.debug(`Could not hook frame ${frame.url}: ${error.message}`));
+}
+
+// Start capturing console output and errors from every frame of a browser view
+function attachConsoleCapture(viewId, view, owner) {
+  const capture = { view, owner, windowId: owner.id, entries: [], nextId: 1, hookedFrames: new Set() };
+  captures.set(viewId, capture);
+  const contents = view.webContents;
+  contents.on('console-message', (...args) => onConsoleMessage(viewId, capture, ...args));
+  contents.on('did-frame-finish-load', (event, isMainFrame, frameProcessId, frameRoutingId) => {
+    injectHook(webFrameMain.fromId(frameProcessId, frameRoutingId));
+  });
+  // A navigated frame is a new document; its hook has to be installed again
+  contents.on('did-frame-navigate', (event, url, httpResponseCode, httpStatusText, isMainFrame, frameProcessId, frameRoutingId) => {
+    const frame = webFrameMain.fromId(frameProcessId, frameRoutingId);
+    if (frame) capture.hookedFrames.delete(frame.frameTreeNodeId);
+  });
+}
+
+function detachConsoleCapture(viewId) {
+  captures.delete(viewId);
+}
+
+function requireCapture(viewId) {
+  const capture = captures.get(viewId);
+  if (!capture) throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${viewId} not found`, { details: { id: viewId } });
+  return capture;
+}
+
+// Captured entries, oldest first. Filters: `frameId`, `frameUrl` (substring), `origin`,
+// `level` (that level and above), `kind`, `after` (entries with a larger id), `limit`.
+function getConsoleEntries(viewId, filters = {}) {
+  const capture = requireCapture(viewId);
+  const minimum = filters.level ? LEVELS.indexOf(filters.level) : 0;
+  if (minimum < 0) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Level must be one of ${LEVELS.join(', ')}`);
+  const matches = capture.entries.filter((entry) =>
+    (filters.frameId === undefined || entry.frameId === filters.frameId) &&
+    (!filters.frameUrl || (entry.frameUrl || '').includes(filters.frameUrl)) &&
+    (!filters.origin || entry.frameOrigin === filters.origin) &&
+    (!filters.kind || entry.kind === filters.kind) &&
+    (!filters.after || entry.id > filters.after) &&
+    LEVELS.indexOf(entry.level) >= minimum);
+  return matches.slice(-(filters.limit || DEFAULT_LIMIT));
+}
+
+function getConsoleEntry(viewId, entryId) {
+  const entry = requireCapture(viewId).entries.find((item) => item.id === entryId);
+  if (!entry) throw new ForgeError(ErrorCode.NOT_FOUND, `Console entry ${entryId} is no longer captured`);
+  return entry;
+}
+
+// The view's frame tree, flattened, to offer as console filters
+function listFrames(viewId) {
+  const { view } = requireCapture(viewId);
+  if (view.webContents.isDestroyed()) return [];
+  return view.webContents.mainFrame.framesInSubtree.map((frame) => ({
+    ...describeFrame(frame),
+    parentId: frame.parent ? frame.parent.frameTreeNodeId : null,
+    hooked: captures.get(viewId).hookedFrames.has(frame.frameTreeNodeId)
+  }));
+}
+
+function clearConsole(viewId) {
+  requireCapture(viewId).entries = [];
+  return true;
+}
+
+function setupBrowserConsoleHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('browser:getConsole', async (event, id, filters) => getConsoleEntries(id, filters));
+
+  ipcMain.handle('browser:listFrames', async (event, id) => listFrames(id));
+
+  ipcMain.handle('browser:clearConsole', async (event, id) => clearConsole(id));
+}
+
+module.exports = { setupBrowserConsoleHandlers, attachConsoleCapture, detachConsoleCapture, getConsoleEntries, getConsoleEntry };
@@ -0,0 +1,326 @@
+const fs = require('fs').promises;
+const fsSync = require('fs');
+const path = require('path');
+const { app } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { fuzzyScore } = require('./fuzzy');
+const { getSettingsSnapshot } = require('./ipc');
+
+const log = createLogger('browser-history');
+
+const DEFAULT_PROFILE = 'default';
+const PROFILE_PATTERN = /^[\w-]{1,40}$/;
+const MAX_HISTORY = 10000;
+const MAX_BOOKMARKS = 5000;
+const MAX_TITLE_LENGTH = 300;
+const DEFAULT_SUGGESTIONS = 8;
+const DEFAULT_SEARCH_LIMIT = 100;
+// History is rewritten at most this often while browsing
+const SAVE_DELAY = 2000;
+// Frecency: each visit counts for less the older it is, halving every week
+const HALF_LIFE_MS = 7 * 24 * 60 * 60 * 1000;
+
+// Bookmarks per browser profile: { id, url, title, folder, createdAt }
+const bookmarkStore = new JsonStore('browser-bookmarks', { profiles: {}, migrated: false });
+
+// History per profile, loaded on first use: url -> { url, title, visitCount, typedCount,
+// firstVisitedAt, lastVisitedAt, workspace }. Written to one JSON file per profile.
+const histories = new Map();
+const saveTimers = new Map();
+let writeQueue = Promise.resolve();
+
+function requireProfile(profile) {
+  const name = profile || DEFAULT_PROFILE;
+  if (typeof name !== 'string' || !PROFILE_PATTERN.test(name)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A browser profile name is 1-40 letters, digits, - or _');
+  }
+  return name;
+}
+
+function historyFile(profile) {
+  return path.join(app.getPath('userData'), 'browser-history', `${profile}.json`);
+}
+
+function loadHistory(profile) {
+  let history = histories.get(profile);
+  if (history) return history;
+  history = new Map();
+  try {
+    for (const entry of JSON.parse(fsSync.readFileSync(historyFile(profile), 'utf-8'))) {
+      if (entry && typeof entry.url === 'string') history.set(entry.url, entry);
+    }
+  } catch (error) {
+    if (error.code !== 'ENOENT') log.warn(`Could not read ${profile} browser history: ${error.message}`);
+  }
+  histories.set(profile, history);
+  return history;
+}
+
+function serialize(profile) {
+  return JSON.stringify(Array.from(loadHistory(profile).values()));
+}
+
+function saveHistory(profile) {
+  clearTimeout(saveTimers.get(profile));
+  saveTimers.delete(profile);
+  const data = serialize(profile);
+  writeQueue = writeQueue.then(async () => {
+    const file = historyFile(profile);
+    await fs.mkdir(path.dirname(file), { recursive: true });
+    await fs.writeFile(`${file}.tmp`, data, 'utf-8');
+    await fs.rename(`${file}.tmp`, file);
+  }).catch((error) => log.warn(`Could not save browser history: ${error.message}`));
+  return writeQueue;
+}
+
+function scheduleSave(profile) {
+  if (saveTimers.has(profile)) return;
+  saveTimers.set(profile, setTimeout(() => saveHistory(profile), SAVE_DELAY));
+}
+
+// Quitting: pending rewrites have to land before the process exits
+function flushHistory() {
+  for (const profile of Array.from(saveTimers.keys())) {
+    clearTimeout(saveTimers.get(profile));
+    saveTimers.delete(profile);
+    try {
+      const file = historyFile(profile);
+      fsSync.mkdirSync(path.dirname(file), { recursive: true });
+      fsSync.writeFileSync(file, serialize(profile), 'utf-8');
+    } catch (error) {
+      log.warn(`Could not save browser history: ${error.message}`);
+    }
+  }
+}
+
+const isRecordable = (url) => typeof url === 'string' && /^(https?|file):/i.test(url);
+const cleanTitle = (title) => (typeof title === 'string' ? title.trim().slice(0, MAX_TITLE_LENGTH) : '');
+
+// A browser view finished navigating. `typed` marks URLs entered in the address bar,
+// which autocomplete prefers over links that were only followed.
+function recordVisit(url, { profile, title, workspace, typed = false } = {}) {
+  if (!isRecordable(url)) return null;
+  const name = requireProfile(profile);
+  const history = loadHistory(name);
+  const now = new Date().toISOString();
+  const entry = history.get(url) || { url, title: '', visitCount: 0, typedCount: 0, firstVisitedAt: now };
+  entry.title = cleanTitle(title) || entry.title;
+  entry.visitCount++;
+  if (typed) entry.typedCount++;
+  entry.lastVisitedAt = now;
+  entry.workspace = workspace || entry.workspace || null;
+  // Re-insert so the map stays oldest-first and trimming drops the stalest URL
+  history.delete(url);
+  history.set(url, entry);
+  if (history.size > MAX_HISTORY) history.delete(history.keys().next().value);
+  scheduleSave(name);
+  return entry;
+}
+
+function updateTitle(url, title, { profile } = {}) {
+  const name = requireProfile(profile);
+  const entry = loadHistory(name).get(url);
+  const clean = cleanTitle(title);
+  if (!entry || !clean || entry.title === clean) return;
+  entry.title = clean;
+  scheduleSave(name);
+}
+
+// Most recent first; `workspace` keeps pages last visited from that folder, `before`
+// (ISO time) pages through older history
+function listHistory({ profile, workspace, before, limit = DEFAULT_SEARCH_LIMIT } = {}) {
+  const entries = Array.from(loadHistory(requireProfile(profile)).values()).reverse();
+  return entries
+    .filter((entry) => (!workspace || entry.workspace === path.resolve(workspace)) && (!before || entry.lastVisitedAt < before))
+    .slice(0, limit);
+}
+
+// `url` forgets one page; a { from, to } range (ISO times) or nothing clears visits in bulk
+function deleteHistory({ profile, url, from, to } = {}) {
+  const name = requireProfile(profile);
+  const history = loadHistory(name);
+  let removed = 0;
+  for (const entry of Array.from(history.values())) {
+    const matches = url ? entry.url === url : (!from || entry.lastVisitedAt >= from) && (!to || entry.lastVisitedAt <= to);
+    if (matches) {
+      history.delete(entry.url);
+      removed++;
+    }
+  }
+  if (removed > 0) saveHistory(name);
+  return removed;
+}
+
+// Bookmarks the renderer kept in its own persisted store before they moved here
+function migrateRendererBookmarks() {
+  if (bookmarkStore.get('migrated')) return;
+  const persisted = getSettingsSnapshot()['browser-storage'];
+  const old = persisted && persisted.state && Array.isArray(persisted.state.bookmarks) ? persisted.state.bookmarks : [];
+  const bookmarks = old
+    .filter((bookmark) => bookmark && isRecordable(bookmark.url))
+    .map((bookmark) => ({
+      id: `bookmark_${uuidv4()}`,
+      url: bookmark.url,
+      title: cleanTitle(bookmark.title) || bookmark.url,
+      folder: typeof bookmark.folder === 'string' && bookmark.folder ? bookmark.folder : null,
+      createdAt: new Date(bookmark.created_at || Date.now()).toISOString()
+    }));
+  const profiles = bookmarkStore.get('profiles');
+  bookmarkStore.set('profiles', { ...profiles, [DEFAULT_PROFILE]: [...(profiles[DEFAULT_PROFILE] || []), ...bookmarks] });
+  bookmarkStore.set('migrated', true);
+  if (bookmarks.length > 0) log.info(`Moved ${bookmarks.length} bookmark(s) from the renderer store`);
+}
+
+function getBookmarks(profile) {
+  migrateRendererBookmarks();
+  return bookmarkStore.get('profiles')[profile] || [];
+}
+
+function setBookmarks(profile, bookmarks) {
+  bookmarkStore.set('profiles', { ...bookmarkStore.get('profiles'), [profile]: bookmarks });
+  emit('browser:bookmarksChanged', { profile });
+}
+
+function listBookmarks({ profile, folder } = {}) {
+  const bookmarks = getBookmarks(requireProfile(profile));
+  return folder === undefined ? bookmarks : bookmarks.filter((bookmark) => bookmark.folder === (folder || null));
+}
+
+// Bookmarking a URL twice updates the existing bookmark
+function addBookmark({ url, title, folder } = {}, { profile } = {}) {
+  if (!isRecordable(url)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Only http(s) and file URLs can be bookmarked');
+  const name = requireProfile(profile);
+  const bookmarks = getBookmarks(name);
+  const existing = bookmarks.find((bookmark) => bookmark.url === url);
+  if (existing) return updateBookmark(existing.id, { title, folder }, { profile: name });
+  if (bookmarks.length >= MAX_BOOKMARKS) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `A profile can have at most ${MAX_BOOKMARKS} bookmarks`);
+  }
+  const visited = loadHistory(name).get(url);
+  const bookmark = {
+    id: `bookmark_${uuidv4()}`,
+    url,
+    title: cleanTitle(title) || (visited && visited.title) || url,
+    folder: typeof folder === 'string' && folder.trim() ? folder.trim() : null,
+    createdAt: new Date().toISOString()
+  };
+  setBookmarks(name, [...bookmarks, bookmark]);
+  return bookmark;
+}
+
+function updateBookmark(id, changes = {}, { profile } = {}) {
+  const name = requireProfile(profile);
+  const bookmarks = getBookmarks(name);
+  const bookmark = bookmarks.find((item) => item.id === id);
+  if (!bookmark) throw new ForgeError(ErrorCode.NOT_FOUND, `Bookmark ${id} not found`);
+  const updated = {
+    ...bookmark,
+    ...(cleanTitle(changes.title) ? { title: cleanTitle(changes.title) } : {}),
+    ...(changes.folder !== undefined ? { folder: typeof changes.folder === 'string' && changes.folder.trim() ? changes.folder.trim() : null } : {})
+  };
+  setBookmarks(name, bookmarks.map((item) => (item.id === id ? updated : item)));
+  return updated;
+}
+
+function removeBookmark(id, { profile } = {}) {
+  const name = requireProfile(profile);
+  const bookmarks = getBookmarks(name);
+  if (!bookmarks.some((bookmark) => bookmark.id === id)) return false;
+  setBookmarks(name, bookmarks.filter((bookmark) => bookmark.id !== id));
+  return true;
+}
+
+// What people type: no scheme, no www.
+const bareUrl = (url) => url.replace(/^[a-z]+:\/\/(www\.)?/i, '');
+
+// Address bar suggestions from bookmarks and history. URLs are matched fuzzily on their
+// bare form and title; typed and frequently, recently visited pages rank first, and a
+// bookmark counts as a few typed visits. `inline` is the completion to show after the
+// caret when the best URL starts with what was typed.
+function autocomplete(query, { profile, limit = DEFAULT_SUGGESTIONS } = {}) {
+  const name = requireProfile(profile);
+  const text = typeof query === 'string' ? query.trim() : '';
+  if (!text) return { query: text, inline: null, suggestions: [] };
+  const bare = bareUrl(text).toLowerCase();
+  const now = Date.now();
+
+  const candidates = new Map();
+  for (const entry of loadHistory(name).values()) candidates.set(entry.url, { ...entry, bookmarked: false });
+  for (const bookmark of getBookmarks(name)) {
+    const entry = candidates.get(bookmark.url) || { url: bookmark.url, title: bookmark.title, visitCount: 0, typedCount: 0, lastVisitedAt: null };
+    candidates.set(bookmark.url, { ...entry, title: bookmark.title || entry.title, bookmarked: true });
+  }
+
+  const scored = [];
+  for (const candidate of candidates.values()) {
+    const urlMatch = fuzzyScore(bare, bareUrl(candidate.url));
+    const titleMatch = candidate.title ? fuzzyScore(text, candidate.title) : null;
+    if (!urlMatch && !titleMatch) continue;
+    const prefix = bareUrl(candidate.url).toLowerCase().startsWith(bare);
+    const match = Math.max(urlMatch ? urlMatch.score : 0, titleMatch ? titleMatch.score * 0.8 : 0) + (prefix ? 50 : 0);
+    const age = candidate.lastVisitedAt ? now - new Date(candidate.lastVisitedAt).getTime() : Infinity;
+    const frecency = (candidate.visitCount + candidate.typedCount * 3 + (candidate.bookmarked ? 5 : 0)) *
+      (Number.isFinite(age) ? 0.5 + Math.pow(0.5, age / HALF_LIFE_MS) : 0.5);
+    scored.push({ candidate, prefix, score: match * (1 + Math.log1p(frecency)) });
+  }
+  scored.sort((a, b) => b.score - a.score);
+
+  const best = scored.find((item) => item.prefix);
+  return {
+    query: text,
+    // Only offered for the top suggestion, so Tab never completes something unexpected
+    inline: best && best === scored[0] ? bareUrl(best.candidate.url).slice(bare.length) || null : null,
+    suggestions: scored.slice(0, limit).map(({ candidate, score }) => ({
+      url: candidate.url,
+      title: candidate.title || candidate.url,
+      bookmarked: candidate.bookmarked,
+      visitCount: candidate.visitCount,
+      lastVisitedAt: candidate.lastVisitedAt,
+      score
+    }))
+  };
+}
+
+// Full-text search over titles and URLs, most recent first
+function searchHistory(query, { profile, limit = DEFAULT_SEARCH_LIMIT } = {}) {
+  const needle = typeof query === 'string' ? query.trim().toLowerCase() : '';
+  return listHistory({ profile, limit: MAX_HISTORY })
+    .filter((entry) => !needle || entry.url.toLowerCase().includes(needle) || entry.title.toLowerCase().includes(needle))
+    .slice(0, limit);
+}
+
+function setupBrowserHistoryHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  app.on('before-quit', flushHistory);
+
+  ipcMain.handle('browser:autocomplete', async (event, query, options) => autocomplete(query, options));
+
+  ipcMain.handle('browser:searchHistory', async (event, query, options) => searchHistory(query, options));
+
+  ipcMain.handle('browser:listHistory', async (event, options) => listHistory(options));
+
+  ipcMain.handle('browser:deleteHistory', async (event, options) => deleteHistory(options));
+
+  ipcMain.handle('browser:listBookmarks', async (event, options) => listBookmarks(options));
+
+  ipcMain.handle('browser:addBookmark', async (event, bookmark, options) => addBookmark(bookmark, options));
+
+  ipcMain.handle('browser:updateBookmark', async (event, id, changes, options) => updateBookmark(id, changes, options));
+
+  ipcMain.handle('browser:removeBookmark', async (event, id, options) => removeBookmark(id, options));
+}
+
+module.exports = {
+  setupBrowserHistoryHandlers,
+  requireProfile,
+  recordVisit,
+  updateTitle,
+  listHistory,
+  listBookmarks,
+  autocomplete
+};
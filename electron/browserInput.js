@@ -0,0 +1,177 @@
+const os = require('os');
+const path = require('path');
+const url = require('url');
+const { withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { autocomplete, requireProfile } = require('./browserHistory');
+const { detectDevServers } = require('./startPage');
+const { DEEP_LINK_SCHEME } = require('./deepLinks');
+
+const SEARCH_URL = 'https://www.google.com/search?q=';
+const DEFAULT_SUGGESTIONS = 8;
+// Dev servers are probed at most this often while typing
+const DEV_SERVER_TTL = 5000;
+
+const LOCAL_HOSTS = new Set(['localhost', '127.0.0.1', '0.0.0.0', '[::1]']);
+// Schemes the address bar opens as typed; others are refused rather than searched for
+const ALLOWED_SCHEMES = new Set(['http:', 'https:', 'file:', `${DEEP_LINK_SCHEME}:`, 'about:', 'view-source:']);
+const SCHEME_PATTERN = /^([a-z][a-z0-9+.-]*):/i;
+const PORT_SHORTHAND = /^:(\d{1,5})(\/.*)?$/;
+const HOST_AND_PORT = /^([^\s/?#:]+|\[[0-9a-f:]+\]):(\d{1,5})([/?#].*)?$/i;
+// host.tld, optionally followed by a path, query or fragment
+const DOMAIN_LIKE = /^[^\s/?#.]+(\.[^\s/?#.]+)+\.?([/?#].*)?$/;
+const IPV4 = /^\d{1,3}(\.\d{1,3}){3}$/;
+
+const devServerCache = new Map();
+
+function searchUrl(query) {
+  return `${SEARCH_URL}${encodeURIComponent(query)}`;
+}
+
+const validPort = (port) => Number(port) >= 1 && Number(port) <= 65535;
+
+// The URL as people read it: unicode host instead of punycode, no trailing slash on a bare host
+function displayUrl(href) {
+  try {
+    const parsed = new URL(href);
+    if (!parsed.hostname) return href;
+    const host = url.domainToUnicode(parsed.hostname) || parsed.hostname;
+    const rest = `${parsed.pathname === '/' ? '' : parsed.pathname}${parsed.search}${parsed.hash}`;
+    return `${parsed.protocol}//${parsed.username ? `${parsed.username}@` : ''}${host}${parsed.port ? `:${parsed.port}` : ''}${rest}`;
+  } catch {
+    return href;
+  }
+}
+
+// new URL() punycodes hosts and rejects malformed ones
+function parseWeb(candidate) {
+  try {
+    const parsed = new URL(candidate);
+    return parsed.hostname || parsed.protocol === 'file:' ? parsed : null;
+  } catch {
+    return null;
+  }
+}
+
+function looksLikeHost(hostname) {
+  if (IPV4.test(hostname)) return hostname.split('.').every((part) => Number(part) <= 255);
+  // The last label must be alphabetic (a TLD) or punycode, so "v2.0" stays a search
+  const labels = hostname.replace(/\.$/, '').split('.');
+  return /^([a-z]{2,63}|xn--[a-z0-9-]+)$/i.test(url.domainToASCII(labels[labels.length - 1]) || '');
+}
+
+// Work out what address bar text means: an explicit URL, a local server shorthand
+// (localhost:5173, :5173), a file path, a bare domain, or a search. Hosts are punycoded
+// and missing schemes filled in: http for local servers, https otherwise.
+// { kind: 'url' | 'localhost' | 'file' | 'internal' | 'search' | 'invalid', url, display, error }
+function classifyInput(text) {
+  const input = typeof text === 'string' ? text.trim() : '';
+  const result = (kind, href, error) => ({ input, kind, url: href, display: href ? displayUrl(href) : null, error: error || null });
+  if (!input) return result('invalid', null, 'Type a URL or search');
+
+  const shorthand = input.match(PORT_SHORTHAND);
+  if (shorthand) {
+    if (!validPort(shorthand[1])) return result('invalid', null, `Port ${shorthand[1]} is out of range`);
+    return result('localhost', `http://localhost:${shorthand[1]}${shorthand[2] || '/'}`);
+  }
+
+  // Paths typed or pasted from a terminal
+  if (path.isAbsolute(input) || input.startsWith('~/') || /^[a-zA-Z]:[\\/]/.test(input)) {
+    const resolved = input.startsWith('~/') ? path.join(os.homedir(), input.slice(2)) : input;
+    return result('file', url.pathToFileURL(resolved).href);
+  }
+
+  const hostAndPort = input.match(HOST_AND_PORT);
+  if (hostAndPort && !/\s/.test(input)) {
+    const [, host, port] = hostAndPort;
+    if (!validPort(port)) return result('invalid', null, `Port ${port} is out of range`);
+    const local = LOCAL_HOSTS.has(host.toLowerCase());
+    if (local || looksLikeHost(host) || !host.includes('.')) {
+      const parsed = parseWeb(`${local ? 'http' : 'https'}://${input}`);
+      if (parsed) return result(local ? 'localhost' : 'url', parsed.href);
+    }
+  }
+
+  const scheme = input.match(SCHEME_PATTERN);
+  if (scheme && !hostAndPort) {
+    const protocol = `${scheme[1].toLowerCase()}:`;
+    if (!ALLOWED_SCHEMES.has(protocol)) return result('invalid', null, `${scheme[1]}: links can't be opened in the browser`);
+    if (protocol === 'about:' || protocol === 'view-source:') return result('internal', input);
+    if (protocol === `${DEEP_LINK_SCHEME}:`) return result('internal', input);
+    const parsed = parseWeb(input);
+    if (!parsed) return result('invalid', null, `${input} is not a valid URL`);
+    return result(LOCAL_HOSTS.has(parsed.hostname) ? 'localhost' : parsed.protocol === 'file:' ? 'file' : 'url', parsed.href);
+  }
+
+  if (/\s/.test(input)) return result('search', searchUrl(input));
+
+  if (/^localhost([/?#].*)?$/i.test(input)) return result('localhost', `http://${input}`);
+
+  // Judge the host as typed: the URL parser would read "1.5" as the address 1.0.0.5.
+  // An @ before the host is an email address, not credentials.
+  const host = input.split(/[/?#]/)[0];
+  if (DOMAIN_LIKE.test(input) && !host.includes('@') && looksLikeHost(host)) {
+    const parsed = parseWeb(`https://${input}`);
+    if (parsed) return result('url', parsed.href);
+  }
+  return result('search', searchUrl(input));
+}
+
+async function devServersFor(workspace) {
+  const key = workspace || '';
+  const cached = devServerCache.get(key);
+  if (cached && Date.now() - cached.at < DEV_SERVER_TTL) return cached.servers;
+  const servers = await detectDevServers(workspace);
+  devServerCache.set(key, { at: Date.now(), servers });
+  return servers;
+}
+
+// Running dev servers whose URL, port or "localhost" matches what was typed
+function matchingDevServers(servers, input) {
+  const needle = input.toLowerCase().replace(/^https?:\/\//, '').replace(/^:/, '');
+  if (!needle) return servers;
+  return servers.filter((server) => {
+    const bare = server.url.replace(/^https?:\/\//, '');
+    return bare.startsWith(needle) || String(server.port).startsWith(needle) || (needle.length >= 3 && 'localhost'.startsWith(needle));
+  });
+}
+
+// classifyInput plus what to suggest under the address bar: the resolved target first,
+// then running dev servers, then history and bookmarks. `inline` is history's completion
+// for the text after the caret.
+async function resolveBrowserInput(text, { profile, workspace, limit = DEFAULT_SUGGESTIONS } = {}) {
+  const resolved = classifyInput(text);
+  const name = requireProfile(profile);
+  const servers = resolved.input ? matchingDevServers(await devServersFor(workspace || null), resolved.input) : [];
+  const history = autocomplete(resolved.input, { profile: name, limit });
+
+  const seen = new Set();
+  const suggestions = [];
+  const add = (suggestion) => {
+    if (!suggestion.url || seen.has(suggestion.url) || suggestions.length >= limit) return;
+    seen.add(suggestion.url);
+    suggestions.push({ ...suggestion, display: displayUrl(suggestion.url) });
+  };
+  if (resolved.kind !== 'invalid') {
+    add({ kind: resolved.kind === 'search' ? 'search' : 'navigate', url: resolved.url, title: resolved.kind === 'search' ? `Search for "${resolved.input}"` : resolved.display });
+  }
+  for (const server of servers) add({ kind: 'devServer', url: server.url, title: `Dev server on port ${server.port}` });
+  for (const item of history.suggestions) add({ kind: item.bookmarked ? 'bookmark' : 'history', url: item.url, title: item.title });
+  // A search is always on offer for text that resolved to a URL
+  if (resolved.kind !== 'search' && resolved.input && !resolved.input.includes('://')) {
+    add({ kind: 'search', url: searchUrl(resolved.input), title: `Search for "${resolved.input}"` });
+  }
+  return { ...resolved, inline: history.inline, suggestions };
+}
+
+function setupBrowserInputHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // Suggestions default to the calling window's workspace for dev server detection
+  ipcMain.handle('browser:resolveInput', async (event, text, options = {}) => {
+    const context = windowManager.fromSender(event.sender);
+    return resolveBrowserInput(text, { workspace: context ? context.workspacePath : null, ...options });
+  });
+}
+
+module.exports = { setupBrowserInputHandlers, classifyInput, resolveBrowserInput, searchUrl };
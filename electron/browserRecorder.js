@@ -0,0 +1,483 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { webFrameMain } = require('electron');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('browser-recorder');
+
+const FORMAT = 'forge-browser-actions';
+const FORMAT_VERSION = 1;
+const MAX_ACTIONS = 5000;
+// Recorded actions travel as console.debug lines starting with this marker
+const REPORT_PREFIX = '\u2063forge-recorder:';
+const ACTION_TYPES = ['goto', 'navigate', 'click', 'fill', 'check', 'select', 'press'];
+const DEFAULT_TIMEOUT = 5000;
+const DEFAULT_DELAY = 100;
+
+// Installed into every frame while recording. Reports what the user does with a selector
+// that's unique at the time (test ids, then ids, names and labels, then a DOM path) and
+// the path of same-origin iframes leading to the frame. Typing is reported once per field,
+// when the user moves on; password values are never reported.
+const RECORDER_HOOK = `(() => {
+  const PREFIX = ${JSON.stringify(REPORT_PREFIX)};
+  if (window.__forgeRecorder) return;
+  const controller = new AbortController();
+  const options = { capture: true, signal: controller.signal };
+  Object.defineProperty(window, '__forgeRecorder', { value: { stop: () => controller.abort() }, configurable: true });
+  const TEST_ATTRIBUTES = ['data-testid', 'data-test-id', 'data-test', 'data-cy', 'data-qa'];
+  const GENERATED_ID = /\\d{3,}|^\\d|:|^(ember|react|radix|headlessui)/i;
+  const CLICKABLE = 'a, button, input, select, textarea, label, summary, [role=button], [role=link], [role=tab], [role=menuitem], [role=option], [onclick]';
+  const unique = (doc, selector) => {
+    try { return doc.querySelectorAll(selector).length === 1; } catch { return false; }
+  };
+  const attributeSelector = (el, name) => el.localName + '[' + name + '="' + CSS.escape(el.getAttribute(name)) + '"]';
+  const selectorFor = (el) => {
+    const doc = el.ownerDocument;
+    for (const name of TEST_ATTRIBUTES) {
+      if (!el.getAttribute(name)) continue;
+      const selector = '[' + name + '="' + CSS.escape(el.getAttribute(name)) + '"]';
+      if (unique(doc, selector)) return { selector, testId: name === 'data-testid' ? el.getAttribute(name) : null };
+    }
+    if (el.id && !GENERATED_ID.test(el.id) && unique(doc, '#' + CSS.escape(el.id))) return { selector: '#' + CSS.escape(el.id) };
+    for (const name of ['name', 'aria-label', 'placeholder']) {
+      if (el.getAttribute(name) && unique(doc, attributeSelector(el, name))) return { selector: attributeSelector(el, name) };
+    }
+    const parts = [];
+    for (let node = el; node && node.nodeType === 1 && node !== doc.documentElement; node = node.parentElement) {
+      if (node !== el && node.id && !GENERATED_ID.test(node.id)) {
+        parts.unshift('#' + CSS.escape(node.id));
+      } else {
+        const siblings = node.parentElement ? Array.from(node.parentElement.children).filter((child) => child.localName === node.localName) : [];
+        parts.unshift(node.localName + (siblings.length > 1 ? ':nth-of-type(' + (siblings.indexOf(node) + 1) + ')' : ''));
+      }
+      if (unique(doc, parts.join(' > '))) break;
+    }
+    return { selector: parts.join(' > ') };
+  };
+  // Selectors of the iframes from the top document down to this one, as far as same-origin
+  // ancestors can be walked
+  const framePath = () => {
+    const path = [];
+    try {
+      for (let win = window; win.frameElement; win = win.parent) path.unshift(selectorFor(win.frameElement).selector);
+    } catch {}
+    return path;
+  };
+  const describe = (el) => {
+    const text = (el.innerText || el.value || '').trim().replace(/\\s+/g, ' ').slice(0, 80);
+    const label = el.labels && el.labels[0] ? el.labels[0].innerText.trim().slice(0, 80) : el.getAttribute('aria-label');
+    return { ...selectorFor(el), tag: el.localName, text: text || null, label: label || null, frame: framePath() };
+  };
+  const report = (action) => {
+    try { console.debug(PREFIX + JSON.stringify({ ...action, frameUrl: location.href })); } catch {}
+  };
+  const isToggle = (el) => el.localName === 'input' && (el.type === 'checkbox' || el.type === 'radio');
+  const isTextField = (el) => (el.localName === 'input' && !isToggle(el) && !['button', 'submit', 'reset', 'file', 'image'].includes(el.type)) ||
+    el.localName === 'textarea' || el.isContentEditable;
+  let pending = null;
+  const flush = () => {
+    if (!pending) return;
+    const { el } = pending;
+    pending = null;
+    const secret = el.type === 'password';
+    report({ type: 'fill', ...describe(el), text: null, value: secret ? '' : (el.isContentEditable ? el.innerText : el.value), redacted: secret || undefined });
+  };
+  document.addEventListener('input', (event) => {
+    const el = event.target;
+    if (!event.isTrusted || !isTextField(el)) return;
+    if (pending && pending.el !== el) flush();
+    pending = { el };
+  }, options);
+  document.addEventListener('change', (event) => {
+    const el = event.target;
+    if (!event.isTrusted) return;
+    if (isToggle(el)) report({ type: 'check', ...describe(el), checked: el.checked });
+    else if (el.localName === 'select') report({ type: 'select', ...describe(el), values: Array.from(el.selectedOptions).map((option) => option.value) });
+    else if (pending && pending.el === el) flush();
+  }, options);
+  document.addEventListener('focusout', (event) => {
+    if (pending && pending.el === event.target) flush();
+  }, options);
+  document.addEventListener('click', (event) => {
+    if (!event.isTrusted || !(event.target instanceof Element)) return;
+    const el = event.target.closest(CLICKABLE) || event.target;
+    // Toggles and dropdowns are recorded by what they change to
+    if (isToggle(el) || el.localName === 'select' || el.localName === 'option') return;
+    if (el.localName === 'label' && el.control && (isToggle(el.control) || el.control.localName === 'select')) return;
+    flush();
+    report({ type: 'click', ...describe(el), button: event.button });
+  }, options);
+  const SPECIAL_KEYS = ['Enter', 'Escape', 'Tab', 'ArrowUp', 'ArrowDown', 'ArrowLeft', 'ArrowRight', 'Backspace', 'Delete', 'PageUp', 'PageDown', 'Home', 'End'];
+  document.addEventListener('keydown', (event) => {
+    if (!event.isTrusted || event.isComposing || event.repeat) return;
+    const chord = event.ctrlKey || event.metaKey || event.altKey;
+    if (!SPECIAL_KEYS.includes(event.key) && !(chord && event.key.length === 1)) return;
+    // Backspace and friends are part of typing into a field
+    if (!chord && ['Backspace', 'Delete', 'ArrowLeft', 'ArrowRight', 'Home', 'End'].includes(event.key) && isTextField(event.target)) return;
+    flush();
+    const modifiers = [event.ctrlKey && 'Control', event.metaKey && 'Meta', event.altKey && 'Alt', event.shiftKey && chord && 'Shift'].filter(Boolean);
+    const target = event.target instanceof Element && event.target !== document.body ? describe(event.target) : { selector: null, frame: framePath() };
+    report({ type: 'press', ...target, key: [...modifiers, event.key].join('+') });
+  }, options);
+  window.addEventListener('pagehide', flush, options);
+})();`;
+
+// Runs in the top frame during replay: waits for an action's element (through same-origin
+// iframes), scrolls it into view, and performs `op` on it
+const REPLAY_HELPER = `async (action, op, timeout) => {
+  const locate = () => {
+    let doc = document;
+    let x = 0;
+    let y = 0;
+    for (const selector of action.frame || []) {
+      const frame = doc.querySelector(selector);
+      if (!frame || !frame.contentDocument) return null;
+      const rect = frame.getBoundingClientRect();
+      x += rect.left + frame.clientLeft;
+      y += rect.top + frame.clientTop;
+      doc = frame.contentDocument;
+    }
+    const el = doc.querySelector(action.selector);
+    return el ? { el, x, y } : null;
+  };
+  const deadline = Date.now() + timeout;
+  let found;
+  while (!(found = locate())) {
+    if (Date.now() > deadline) throw new Error('No element matches ' + action.selector);
+    await new Promise((resolve) => setTimeout(resolve, 100));
+  }
+  found.el.scrollIntoView({ block: 'center', inline: 'center' });
+  const { el, x, y } = locate() || found;
+  const realm = el.ownerDocument.defaultView;
+  const fire = (type) => el.dispatchEvent(new realm.Event(type, { bubbles: true }));
+  switch (op) {
+    case 'point': {
+      const rect = el.getBoundingClientRect();
+      return { x: x + rect.left + rect.width / 2, y: y + rect.top + rect.height / 2 };
+    }
+    case 'focus':
+      el.focus();
+      return true;
+    case 'fill': {
+      el.focus();
+      if (el.isContentEditable) {
+        el.textContent = action.value;
+      } else {
+        // The native setter, so frameworks tracking the value see the change
+        const proto = el.localName === 'textarea' ? realm.HTMLTextAreaElement.prototype : realm.HTMLInputElement.prototype;
+        Object.getOwnPropertyDescriptor(proto, 'value').set.call(el, action.value);
+      }
+      fire('input');
+      fire('change');
+      return true;
+    }
+    case 'check':
+      if (el.checked !== action.checked) el.click();
+      return true;
+    case 'select':
+      for (const option of el.options) option.selected = action.values.includes(option.value);
+      fire('input');
+      fire('change');
+      return true;
+    default:
+      throw new Error('Unknown replay step ' + op);
+  }
+}`;
+
+// DOM key names to Electron's input event key codes
+const KEY_CODES = { ArrowUp: 'Up', ArrowDown: 'Down', ArrowLeft: 'Left', ArrowRight: 'Right', Escape: 'Escape', Enter: 'Enter' };
+
+// Per browser view: { viewId, view, target, startedAt, startUrl, viewport, actions, pageNavigation, listeners }
+const recordings = new Map();
+
+function injectRecorder(frame) {
+  if (!frame || frame.detached) return;
+  frame.executeJavaScript(RECORDER_HOOK).catch((error) => log.debug(`Could not hook frame ${frame.url}: ${error.message}`));
+}
+
+function addAction(recording, action) {
+  if (recording.actions.length >= MAX_ACTIONS) return;
+  const entry = { ...action, at: Date.now() - recording.startedAt };
+  recording.actions.push(entry);
+  emit('browser:actionRecorded', { id: recording.viewId, index: recording.actions.length - 1, action: entry }, { target: recording.target });
+}
+
+function onConsoleMessage(recording, event, legacyLevel, legacyMessage) {
+  const message = event.message !== undefined ? event.message : legacyMessage;
+  if (typeof message !== 'string' || !message.startsWith(REPORT_PREFIX)) return;
+  let report;
+  try {
+    report = JSON.parse(message.slice(REPORT_PREFIX.length));
+  } catch {
+    return;
+  }
+  if (!ACTION_TYPES.includes(report.type) || report.type === 'goto' || report.type === 'navigate') return;
+  // Frames are found again through `frame`; `frameUrl` is kept for subframes to explain
+  // why an action from a cross-origin one (no walkable path) can't be replayed
+  const { frameUrl, ...action } = report;
+  if (!action.frame || action.frame.length === 0) delete action.frame;
+  if (event.frame && event.frame.parent) action.frameUrl = frameUrl;
+  addAction(recording, action);
+}
+
+// Record what the user does in a browser view until stopActionRecording. Navigations the
+// page starts (links, forms, redirects, client-side routing) are recorded as URLs to wait
+// for; ones the user starts from the address bar or history as URLs to load.
+function startActionRecording(viewId, view, target) {
+  if (recordings.has(viewId)) throw new ForgeError(ErrorCode.BUSY, `Browser view ${viewId} is already recording`);
+  const contents = view.webContents;
+  const bounds = view.getBounds();
+  const recording = {
+    viewId,
+    view,
+    target,
+    startedAt: Date.now(),
+    startUrl: contents.getURL(),
+    viewport: { width: bounds.width, height: bounds.height },
+    actions: [],
+    pageNavigation: false,
+    listeners: []
+  };
+  const on = (name, listener) => {
+    contents.on(name, listener);
+    recording.listeners.push([name, listener]);
+  };
+  on('console-message', (...args) => onConsoleMessage(recording, ...args));
+  on('did-frame-finish-load', (event, isMainFrame, frameProcessId, frameRoutingId) => {
+    injectRecorder(webFrameMain.fromId(frameProcessId, frameRoutingId));
+  });
+  on('will-navigate', (event) => {
+    if (event.isMainFrame !== false) recording.pageNavigation = true;
+  });
+  on('did-navigate', (event, url) => {
+    addAction(recording, { type: recording.pageNavigation ? 'navigate' : 'goto', url });
+    recording.pageNavigation = false;
+  });
+  on('did-navigate-in-page', (event, url, isMainFrame) => {
+    if (isMainFrame) addAction(recording, { type: 'navigate', url });
+  });
+  contents.once('destroyed', () => recordings.delete(viewId));
+  recordings.set(viewId, recording);
+  for (const frame of contents.mainFrame.framesInSubtree) injectRecorder(frame);
+  log.info(`Recording actions in ${viewId}`);
+  return { id: viewId, startedAt: new Date(recording.startedAt).toISOString(), startUrl: recording.startUrl };
+}
+
+function stopActionRecording(viewId) {
+  const recording = recordings.get(viewId);
+  if (!recording) throw new ForgeError(ErrorCode.NOT_FOUND, `Browser view ${viewId} isn't recording`);
+  recordings.delete(viewId);
+  const contents = recording.view.webContents;
+  if (!contents.isDestroyed()) {
+    for (const [name, listener] of recording.listeners) contents.removeListener(name, listener);
+    for (const frame of contents.mainFrame.framesInSubtree) {
+      if (!frame.detached) frame.executeJavaScript('window.__forgeRecorder && window.__forgeRecorder.stop(); delete window.__forgeRecorder;').catch(() => {});
+    }
+  }
+  log.info(`Recorded ${recording.actions.length} actions in ${viewId}`);
+  return {
+    format: FORMAT,
+    version: FORMAT_VERSION,
+    recordedAt: new Date(recording.startedAt).toISOString(),
+    startUrl: recording.startUrl,
+    viewport: recording.viewport,
+    actions: recording.actions
+  };
+}
+
+function validateScript(script) {
+  if (!script || script.format !== FORMAT || !Array.isArray(script.actions)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Not a Forge browser action script');
+  }
+  if (script.version > FORMAT_VERSION) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Action script format ${script.version} is newer than this version of Forge supports`);
+  }
+  const invalid = script.actions.findIndex((action) => !action || !ACTION_TYPES.includes(action.type));
+  if (invalid >= 0) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Action ${invalid + 1} has an unknown type`);
+  return script;
+}
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+async function waitForUrl(contents, url, timeout) {
+  const deadline = Date.now() + timeout;
+  while (contents.getURL() !== url) {
+    if (Date.now() > deadline) throw new Error(`Page didn't reach ${url} (at ${contents.getURL()})`);
+    await sleep(100);
+  }
+}
+
+async function inPage(contents, action, op, timeout) {
+  if (action.frameUrl && !action.frame) throw new Error(`Recorded in a cross-origin frame (${action.frameUrl}), which replay can't reach`);
+  return contents.executeJavaScript(`(${REPLAY_HELPER})(${JSON.stringify(action)}, ${JSON.stringify(op)}, ${timeout})`, true);
+}
+
+// Clicks and key presses are sent as real input so pages see trusted events
+async function replayAction(contents, action, timeout) {
+  switch (action.type) {
+    case 'goto':
+      await contents.loadURL(action.url).catch((error) => {
+        // Redirects abort the original load; what matters is where the page ends up
+        if (error.code !== 'ERR_ABORTED') throw error;
+      });
+      return;
+    case 'navigate':
+      await waitForUrl(contents, action.url, timeout * 2);
+      return;
+    case 'click': {
+      const point = await inPage(contents, action, 'point', timeout);
+      const zoom = contents.getZoomFactor();
+      const x = Math.round(point.x * zoom);
+      const y = Math.round(point.y * zoom);
+      const button = ['left', 'middle', 'right'][action.button || 0] || 'left';
+      contents.sendInputEvent({ type: 'mouseMove', x, y });
+      contents.sendInputEvent({ type: 'mouseDown', x, y, button, clickCount: 1 });
+      contents.sendInputEvent({ type: 'mouseUp', x, y, button, clickCount: 1 });
+      return;
+    }
+    case 'fill':
+    case 'check':
+    case 'select':
+      await inPage(contents, action, action.type, timeout);
+      return;
+    case 'press': {
+      if (action.selector) await inPage(contents, action, 'focus', timeout);
+      const parts = action.key.split('+');
+      const key = parts.pop() || '+';
+      const modifiers = parts.map((modifier) => modifier.toLowerCase());
+      const keyCode = KEY_CODES[key] || key;
+      contents.sendInputEvent({ type: 'keyDown', keyCode, modifiers });
+      if (key === 'Enter' || (key.length === 1 && modifiers.length === 0)) contents.sendInputEvent({ type: 'char', keyCode: key === 'Enter' ? '\r' : key, modifiers });
+      contents.sendInputEvent({ type: 'keyUp', keyCode, modifiers });
+      return;
+    }
+    default:
+      throw new Error(`Unknown action ${action.type}`);
+  }
+}
+
+// Play a script back in a browser view. Stops at the first action that fails.
+// Options: `timeout` per element lookup (ms), `delay` between actions (ms).
+async function replayActions(viewId, view, script, { timeout = DEFAULT_TIMEOUT, delay = DEFAULT_DELAY } = {}) {
+  validateScript(script);
+  if (recordings.has(viewId)) throw new ForgeError(ErrorCode.BUSY, `Stop recording in ${viewId} before replaying`);
+  const contents = view.webContents;
+  const { actions } = script;
+  // Scripts start where the recording did
+  if (script.startUrl && actions[0] && actions[0].type !== 'goto') {
+    await replayAction(contents, { type: 'goto', url: script.startUrl }, timeout);
+  }
+  for (let index = 0; index < actions.length; index++) {
+    if (contents.isDestroyed()) return { completed: index, total: actions.length, error: { index, message: 'The browser view was closed' } };
+    try {
+      await replayAction(contents, actions[index], timeout);
+    } catch (error) {
+      log.info(`Replay in ${viewId} stopped at action ${index + 1}: ${error.message}`);
+      return { completed: index, total: actions.length, error: { index, message: error.message } };
+    }
+    await sleep(delay);
+  }
+  return { completed: actions.length, total: actions.length, error: null };
+}
+
+function locatorFor(action) {
+  const base = (action.frame || []).reduce((chain, selector) => `${chain}.frameLocator(${JSON.stringify(selector)})`, 'page');
+  if (action.testId) return `${base}.getByTestId(${JSON.stringify(action.testId)})`;
+  return `${base}.locator(${JSON.stringify(action.selector)})`;
+}
+
+// The script as a Playwright test
+function toPlaywright(script) {
+  validateScript(script);
+  const lines = [];
+  if (script.viewport && script.viewport.width > 0) {
+    lines.push(`await page.setViewportSize({ width: ${script.viewport.width}, height: ${script.viewport.height} });`);
+  }
+  if (script.startUrl && (!script.actions[0] || script.actions[0].type !== 'goto')) lines.push(`await page.goto(${JSON.stringify(script.startUrl)});`);
+  for (const action of script.actions) {
+    switch (action.type) {
+      case 'goto':
+        lines.push(`await page.goto(${JSON.stringify(action.url)});`);
+        break;
+      case 'navigate':
+        lines.push(`await page.waitForURL(${JSON.stringify(action.url)});`);
+        break;
+      case 'click':
+        lines.push(`await ${locatorFor(action)}.click(${action.button === 2 ? "{ button: 'right' }" : action.button === 1 ? "{ button: 'middle' }" : ''});`);
+        break;
+      case 'fill':
+        lines.push(action.redacted
+          ? `await ${locatorFor(action)}.fill(process.env.FORGE_RECORDED_PASSWORD ?? ''); // password not recorded`
+          : `await ${locatorFor(action)}.fill(${JSON.stringify(action.value)});`);
+        break;
+      case 'check':
+        lines.push(`await ${locatorFor(action)}.${action.checked ? 'check' : 'uncheck'}();`);
+        break;
+      case 'select':
+        lines.push(`await ${locatorFor(action)}.selectOption(${JSON.stringify(action.values)});`);
+        break;
+      case 'press':
+        lines.push(action.selector
+          ? `await ${locatorFor(action)}.press(${JSON.stringify(action.key)});`
+          : `await page.keyboard.press(${JSON.stringify(action.key)});`);
+        break;
+    }
+  }
+  const title = script.recordedAt ? `recorded in Forge ${script.recordedAt}` : 'recorded in Forge';
+  return [
+    "import { test } from '@playwright/test';",
+    '',
+    `test(${JSON.stringify(title)}, async ({ page }) => {`,
+    ...lines.map((line) => `  ${line}`),
+    '});',
+    ''
+  ].join('\n');
+}
+
+function setupBrowserRecorderHandlers(ipcMain, browserViews) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  const requireView = (id) => {
+    const view = browserViews.get(id);
+    if (!view || view.webContents.isDestroyed()) {
+      throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
+    }
+    return view;
+  };
+
+  ipcMain.handle('browser:startActionRecording', async (event, id) => startActionRecording(id, requireView(id), event.sender));
+
+  // Resolves with the recorded script
+  ipcMain.handle('browser:stopActionRecording', async (event, id) => stopActionRecording(id));
+
+  ipcMain.handle('browser:getActionRecording', async (event, id) => {
+    const recording = recordings.get(id);
+    return recording ? { recording: true, startUrl: recording.startUrl, actions: recording.actions } : { recording: false, startUrl: null, actions: [] };
+  });
+
+  ipcMain.handle('browser:replayActions', async (event, id, script, options) => replayActions(id, requireView(id), script, options));
+
+  ipcMain.handle('browser:exportPlaywright', async (event, script) => toPlaywright(script));
+
+  // `format` 'playwright' writes a test file; otherwise the script as JSON
+  ipcMain.handle('browser:saveActionScript', async (event, script, filePath, format = 'json') => {
+    const content = format === 'playwright' ? toPlaywright(script) : JSON.stringify(validateScript(script), null, 2);
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, content);
+    return filePath;
+  });
+
+  ipcMain.handle('browser:loadActionScript', async (event, filePath) => {
+    let script;
+    try {
+      script = JSON.parse(await fs.readFile(filePath, 'utf-8'));
+    } catch (error) {
+      throw ForgeError.from(error, `Could not read ${filePath}`);
+    }
+    return validateScript(script);
+  });
+}
+
+module.exports = { setupBrowserRecorderHandlers, startActionRecording, stopActionRecording, replayActions, toPlaywright };
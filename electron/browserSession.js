@@ -0,0 +1,187 @@
+const { app } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+
+const log = createLogger('browser-session');
+
+const SAVE_DELAY = 1000;
+const MAX_VIEWS = 50;
+const DEVTOOLS_MODES = ['right', 'bottom', 'left', 'undocked', 'detach'];
+
+// Workspace id -> { savedAt, activeId, views: [{ id, url, title, profile, bounds, zoomFactor, devTools }] }
+const store = new JsonStore('browser-sessions', { workspaces: {} });
+
+// Browser view id -> { id, view, owner, profile, bounds, visible, devToolsMode }
+const tracked = new Map();
+// Windows being torn down keep their last saved session
+const closingWindows = new Set();
+const trackedWindows = new WeakSet();
+const saveTimers = new Map();
+
+function describeView(entry) {
+  const contents = entry.view.webContents;
+  if (contents.isDestroyed()) return null;
+  const url = contents.getURL();
+  if (!url) return null;
+  return {
+    id: entry.id,
+    url,
+    title: contents.getTitle() || null,
+    profile: entry.profile,
+    bounds: entry.bounds,
+    zoomFactor: contents.getZoomFactor(),
+    devTools: contents.isDevToolsOpened() ? { mode: entry.devToolsMode || 'right' } : null
+  };
+}
+
+function saveWindowSession(windowId) {
+  clearTimeout(saveTimers.get(windowId));
+  saveTimers.delete(windowId);
+  const context = windowManager.get(windowId);
+  if (!context) return;
+  const entries = Array.from(tracked.values()).filter((entry) => entry.owner.id === windowId);
+  const views = entries.map(describeView).filter(Boolean).slice(0, MAX_VIEWS);
+  const active = entries.find((entry) => entry.visible);
+  store.set('workspaces', {
+    ...store.get('workspaces'),
+    [context.workspaceId]: { savedAt: new Date().toISOString(), activeId: active ? active.id : null, views }
+  });
+}
+
+function scheduleSave(windowId) {
+  if (closingWindows.has(windowId)) return;
+  clearTimeout(saveTimers.get(windowId));
+  saveTimers.set(windowId, setTimeout(() => saveWindowSession(windowId), SAVE_DELAY));
+}
+
+// Keep a browser view in its window's workspace session until it's closed. Navigation,
+// zoom and devtools changes are picked up from the view; bounds and visibility are
+// reported by browser.js because the view can't tell.
+function trackBrowserView(id, view, owner, { profile, bounds = null, visible = true } = {}) {
+  const entry = { id, view, owner, profile, bounds, visible, devToolsMode: null };
+  tracked.set(id, entry);
+  const windowId = owner.id;
+  if (!trackedWindows.has(owner)) {
+    trackedWindows.add(owner);
+    // Views are destroyed once the window is gone, so the session is written while closing
+    owner.on('close', () => {
+      saveWindowSession(windowId);
+      closingWindows.add(windowId);
+    });
+    owner.once('closed', () => {
+      closingWindows.delete(windowId);
+      clearTimeout(saveTimers.get(windowId));
+      saveTimers.delete(windowId);
+    });
+  }
+  const save = () => scheduleSave(windowId);
+  view.webContents.on('did-navigate', save);
+  view.webContents.on('did-navigate-in-page', save);
+  view.webContents.on('page-title-updated', save);
+  view.webContents.on('zoom-changed', save);
+  view.webContents.on('devtools-opened', save);
+  view.webContents.on('devtools-closed', save);
+  save();
+}
+
+function updateBrowserView(id, changes) {
+  const entry = tracked.get(id);
+  if (!entry) return;
+  if (changes.visible && !entry.visible) {
+    // Only one view shows at a time in a window
+    for (const other of tracked.values()) {
+      if (other.owner === entry.owner) other.visible = false;
+    }
+  }
+  Object.assign(entry, changes);
+  scheduleSave(entry.owner.id);
+}
+
+// Views that go away with their window stay in its session
+function untrackBrowserView(id) {
+  const entry = tracked.get(id);
+  if (!entry) return;
+  tracked.delete(id);
+  scheduleSave(entry.owner.id);
+}
+
+// Reinstate zoom and devtools on a view created from a saved session
+function applySavedViewState(id, view, { zoomFactor, devTools } = {}) {
+  if (typeof zoomFactor === 'number' && zoomFactor > 0 && zoomFactor !== 1) {
+    // Chromium resets zoom when a page loads, so apply it to the first page
+    view.webContents.once('did-finish-load', () => {
+      if (!view.webContents.isDestroyed()) view.webContents.setZoomFactor(zoomFactor);
+    });
+  }
+  if (devTools) {
+    const mode = DEVTOOLS_MODES.includes(devTools.mode) ? devTools.mode : 'right';
+    recordDevToolsMode(id, mode);
+    view.webContents.openDevTools({ mode });
+  }
+}
+
+function recordDevToolsMode(id, mode) {
+  const entry = tracked.get(id);
+  if (entry) entry.devToolsMode = DEVTOOLS_MODES.includes(mode) ? mode : null;
+}
+
+function getBrowserSession(workspaceId) {
+  return store.get('workspaces')[workspaceId] || { savedAt: null, activeId: null, views: [] };
+}
+
+// Replace a workspace's saved views, e.g. from an imported state bundle
+function setBrowserSession(workspaceId, session) {
+  if (!session || !Array.isArray(session.views)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A browser session needs a views list');
+  }
+  const views = session.views
+    .filter((view) => view && typeof view.url === 'string')
+    .slice(0, MAX_VIEWS);
+  store.set('workspaces', {
+    ...store.get('workspaces'),
+    [workspaceId]: { savedAt: new Date().toISOString(), activeId: session.activeId || null, views }
+  });
+  return getBrowserSession(workspaceId);
+}
+
+function clearBrowserSession(workspaceId) {
+  const workspaces = { ...store.get('workspaces') };
+  delete workspaces[workspaceId];
+  store.set('workspaces', workspaces);
+  return true;
+}
+
+function setupBrowserSessionHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  const workspaceIdOf = (event) => {
+    const context = windowManager.fromSender(event.sender);
+    if (!context) throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Window not available');
+    return context.workspaceId;
+  };
+
+  // The views open when the calling window's workspace was last used; the workbench
+  // recreates them with browser:create, passing each saved entry as options
+  ipcMain.handle('browser:getSession', async (event) => getBrowserSession(workspaceIdOf(event)));
+
+  ipcMain.handle('browser:clearSession', async (event) => clearBrowserSession(workspaceIdOf(event)));
+
+  // Pending saves would be lost with the process
+  app.on('before-quit', () => {
+    for (const windowId of Array.from(saveTimers.keys())) saveWindowSession(windowId);
+    log.debug(`Saved browser sessions for ${tracked.size} views`);
+  });
+}
+
+module.exports = {
+  setupBrowserSessionHandlers,
+  trackBrowserView,
+  updateBrowserView,
+  untrackBrowserView,
+  applySavedViewState,
+  recordDevToolsMode,
+  getBrowserSession,
+  setBrowserSession
+};
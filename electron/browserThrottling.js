@@ -0,0 +1,139 @@
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { acquireDebugger, releaseDebugger, holdsDebugger } = require('./devtoolsProtocol');
+
+const log = createLogger('browser-throttling');
+
+const HOLDER = 'throttling';
+// Chrome DevTools' presets; throughput in bytes per second, latency in ms
+const NETWORK_PROFILES = {
+  'none': null,
+  'fast-4g': { label: 'Fast 4G', latency: 165, downloadThroughput: 1012500, uploadThroughput: 168750, offline: false },
+  'slow-4g': { label: 'Slow 4G', latency: 562.5, downloadThroughput: 180000, uploadThroughput: 84375, offline: false },
+  'slow-3g': { label: 'Slow 3G', latency: 2000, downloadThroughput: 50000, uploadThroughput: 50000, offline: false },
+  'offline': { label: 'Offline', latency: 0, downloadThroughput: 0, uploadThroughput: 0, offline: true }
+};
+const CPU_RATES = [1, 2, 4, 6, 20];
+const MAX_LATENCY = 60000;
+
+// Browser view id -> { network: { profile, ...conditions } | null, cpuRate, target }
+// `target` is the workbench that set it, told when throttling changes or ends
+const throttles = new Map();
+
+function describe(viewId) {
+  const state = throttles.get(viewId);
+  return { id: viewId, network: state ? state.network : null, cpuRate: state ? state.cpuRate : 1 };
+}
+
+// A preset name, or { latency, downloadThroughput, uploadThroughput, offline } for custom
+// conditions; a throughput of -1 leaves that direction unthrottled
+function resolveNetworkProfile(profile) {
+  if (profile === null || profile === undefined) return null;
+  if (typeof profile === 'string') {
+    if (!Object.prototype.hasOwnProperty.call(NETWORK_PROFILES, profile)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown network profile ${profile}; use one of ${Object.keys(NETWORK_PROFILES).join(', ')} or custom conditions`);
+    }
+    return NETWORK_PROFILES[profile] ? { profile, ...NETWORK_PROFILES[profile] } : null;
+  }
+  if (typeof profile !== 'object') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A network profile name or conditions object is required');
+  const number = (value, fallback) => (value === undefined ? fallback : value);
+  const conditions = {
+    profile: 'custom',
+    label: 'Custom',
+    latency: number(profile.latency, 0),
+    downloadThroughput: number(profile.downloadThroughput, -1),
+    uploadThroughput: number(profile.uploadThroughput, -1),
+    offline: Boolean(profile.offline)
+  };
+  if (![conditions.latency, conditions.downloadThroughput, conditions.uploadThroughput].every(Number.isFinite) ||
+      conditions.latency < 0 || conditions.latency > MAX_LATENCY) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Latency must be 0-${MAX_LATENCY} ms and throughputs numbers of bytes per second`);
+  }
+  return conditions;
+}
+
+// Emulation goes through the view's own DevTools protocol session, so it applies to
+// that preview only and survives navigations
+async function send(viewId, view, method, params) {
+  const { debugger: protocol } = view.webContents;
+  if (!holdsDebugger(view.webContents, HOLDER)) {
+    acquireDebugger(view.webContents, HOLDER);
+    // Closing the view, or another client taking over, ends the emulation with the session
+    protocol.once('detach', () => {
+      const state = throttles.get(viewId);
+      if (!state) return;
+      throttles.delete(viewId);
+      log.debug(`Throttling of ${viewId} ended with its protocol session`);
+      emit('browser:throttlingChanged', describe(viewId), { target: state.target });
+    });
+    await protocol.sendCommand('Network.enable');
+  }
+  return protocol.sendCommand(method, params);
+}
+
+function requireView(browserViews, viewId) {
+  const view = browserViews.get(viewId);
+  if (!view || view.webContents.isDestroyed()) {
+    throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${viewId} not found`, { details: { id: viewId } });
+  }
+  return view;
+}
+
+function updateState(viewId, view, changes, target) {
+  const state = { ...describe(viewId), ...changes };
+  if (!state.network && state.cpuRate === 1) {
+    // Nothing emulated any more; let DevTools have the page to itself
+    throttles.delete(viewId);
+    releaseDebugger(view.webContents, HOLDER);
+  } else {
+    throttles.set(viewId, { network: state.network, cpuRate: state.cpuRate, target });
+  }
+  const result = describe(viewId);
+  emit('browser:throttlingChanged', result, { target });
+  return result;
+}
+
+async function setNetworkConditions(browserViews, viewId, profile, { target } = {}) {
+  const view = requireView(browserViews, viewId);
+  const conditions = resolveNetworkProfile(profile);
+  if (!conditions && !throttles.has(viewId)) return describe(viewId);
+  await send(viewId, view, 'Network.emulateNetworkConditions', conditions
+    ? { offline: conditions.offline, latency: conditions.latency, downloadThroughput: conditions.downloadThroughput, uploadThroughput: conditions.uploadThroughput }
+    : { offline: false, latency: 0, downloadThroughput: -1, uploadThroughput: -1 });
+  log.info(`Network for ${viewId}: ${conditions ? conditions.label : 'no throttling'}`);
+  return updateState(viewId, view, { network: conditions }, target);
+}
+
+// `rate` is the slowdown factor: 4 runs scripts as if on a CPU four times slower
+async function setCpuThrottling(browserViews, viewId, rate, { target } = {}) {
+  const view = requireView(browserViews, viewId);
+  if (!CPU_RATES.includes(rate)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `CPU slowdown must be one of ${CPU_RATES.join(', ')}`);
+  }
+  if (rate === 1 && !throttles.has(viewId)) return describe(viewId);
+  await send(viewId, view, 'Emulation.setCPUThrottlingRate', { rate });
+  log.info(`CPU for ${viewId}: ${rate}x slowdown`);
+  return updateState(viewId, view, { cpuRate: rate }, target);
+}
+
+function setupBrowserThrottlingHandlers(ipcMain, browserViews) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('browser:listNetworkProfiles', async () => ({
+    network: Object.entries(NETWORK_PROFILES).map(([id, conditions]) => ({ id, ...(conditions || { label: 'No throttling' }) })),
+    cpuRates: CPU_RATES
+  }));
+
+  ipcMain.handle('browser:getThrottling', async (event, id) => {
+    requireView(browserViews, id);
+    return describe(id);
+  });
+
+  // null or 'none' turns network throttling off
+  ipcMain.handle('browser:setNetworkConditions', async (event, id, profile) => setNetworkConditions(browserViews, id, profile, { target: event.sender }));
+
+  ipcMain.handle('browser:setCpuThrottling', async (event, id, rate) => setCpuThrottling(browserViews, id, rate, { target: event.sender }));
+}
+
+module.exports = { setupBrowserThrottlingHandlers, setNetworkConditions, setCpuThrottling, NETWORK_PROFILES };
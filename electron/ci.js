@@ -0,0 +1,343 @@
+const crypto = require('crypto');
+const path = require('path');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+const { runGit } = require('./git');
+const { getForgeRemote, forgeRequest, projectApiPath } = require('./forgeRemote');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('ci');
+
+// Poll faster while something is still running
+const ACTIVE_POLL_MS = 20000;
+const IDLE_POLL_MS = 60000;
+const LOG_POLL_MS = 5000;
+const MAX_RUNS = 50;
+// GitHub prefixes every log line with an ISO timestamp
+const GITHUB_TIMESTAMP = /^\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d(\.\d+)?Z /gm;
+
+// Repository root -> { owners: Set<webContents>, timer, options, last (change key), payload }
+const watches = new Map();
+// Stream id -> { id, owner, timer, offset, stopped }
+const logStreams = new Map();
+
+// Normalized job and run states: queued, running, success, failure, cancelled, skipped
+function githubState(status, conclusion) {
+  if (status !== 'completed') return status === 'in_progress' ? 'running' : 'queued';
+  if (conclusion === 'success') return 'success';
+  if (conclusion === 'cancelled') return 'cancelled';
+  if (['skipped', 'neutral', 'stale'].includes(conclusion)) return 'skipped';
+  return 'failure';
+}
+
+function gitlabState(status) {
+  if (status === 'running') return 'running';
+  if (status === 'success') return 'success';
+  if (status === 'failed') return 'failure';
+  if (status === 'canceled') return 'cancelled';
+  if (status === 'skipped') return 'skipped';
+  return 'queued';
+}
+
+// A failure anywhere is reported even while other runs are still going
+function overallState(runs) {
+  if (!runs.length) return 'none';
+  for (const state of ['failure', 'running', 'queued', 'cancelled']) {
+    if (runs.some((run) => run.state === state)) return state;
+  }
+  return 'success';
+}
+
+async function resolveRepo(rootPath) {
+  if (typeof rootPath !== 'string' || !rootPath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A workspace folder is required');
+  try {
+    return (await runGit(path.resolve(rootPath), ['rev-parse', '--show-toplevel'])).trim();
+  } catch {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `${rootPath} is not in a git repository`);
+  }
+}
+
+// Checked-out branch (null when detached) and commit
+async function localHead(repoRoot) {
+  const [branch, sha] = await Promise.all([
+    runGit(repoRoot, ['rev-parse', '--abbrev-ref', 'HEAD']).then((output) => output.trim(), () => null),
+    runGit(repoRoot, ['rev-parse', 'HEAD']).then((output) => output.trim(), () => null)
+  ]);
+  return { branch: branch === 'HEAD' ? null : branch, sha };
+}
+
+function githubRun(run) {
+  return {
+    id: String(run.id),
+    name: run.name,
+    state: githubState(run.status, run.conclusion),
+    sha: run.head_sha,
+    event: run.event,
+    url: run.html_url,
+    attempt: run.run_attempt || 1,
+    startedAt: run.run_started_at || run.created_at,
+    updatedAt: run.updated_at
+  };
+}
+
+function githubJob(job) {
+  return {
+    id: String(job.id),
+    name: job.name,
+    state: githubState(job.status, job.conclusion),
+    url: job.html_url,
+    startedAt: job.started_at || null,
+    completedAt: job.completed_at || null,
+    // The step that failed, so the log can be opened at the right place
+    failedStep: ((job.steps || []).find((step) => step.conclusion === 'failure') || {}).name || null
+  };
+}
+
+function gitlabJob(job) {
+  return {
+    id: String(job.id),
+    name: job.stage ? `${job.stage}: ${job.name}` : job.name,
+    state: gitlabState(job.status),
+    url: job.web_url,
+    startedAt: job.started_at || null,
+    completedAt: job.finished_at || null,
+    failedStep: null
+  };
+}
+
+// The newest workflow runs for the commit: the local HEAD when CI has run on it, else the
+// latest commit on the branch that has runs. One run per workflow, the newest.
+async function githubStatus(remote, head) {
+  const base = projectApiPath(remote);
+  const query = head.branch ? { branch: head.branch, per_page: MAX_RUNS } : { head_sha: head.sha, per_page: MAX_RUNS };
+  const [{ workflow_runs: runs = [] }, pulls] = await Promise.all([
+    forgeRequest(remote, 'GET', `${base}/actions/runs`, { query }),
+    head.branch ? forgeRequest(remote, 'GET', `${base}/pulls`, { query: { head: `${remote.owner}:${head.branch}`, state: 'open', per_page: 1 } }) : []
+  ]);
+  const sha = runs.some((run) => run.head_sha === head.sha) ? head.sha : runs.length ? runs[0].head_sha : null;
+  const latest = new Map();
+  for (const run of runs) {
+    if (run.head_sha === sha && !latest.has(run.workflow_id)) latest.set(run.workflow_id, githubRun(run));
+  }
+  const pull = pulls[0];
+  return { sha, runs: Array.from(latest.values()), pullRequest: pull ? { number: pull.number, title: pull.title, url: pull.html_url } : null };
+}
+
+// The newest pipeline for the commit, preferring the local HEAD as on GitHub
+async function gitlabStatus(remote, head) {
+  const base = projectApiPath(remote);
+  const query = head.branch ? { ref: head.branch, per_page: MAX_RUNS } : { sha: head.sha, per_page: MAX_RUNS };
+  const [pipelines, requests] = await Promise.all([
+    forgeRequest(remote, 'GET', `${base}/pipelines`, { query }),
+    head.branch ? forgeRequest(remote, 'GET', `${base}/merge_requests`, { query: { source_branch: head.branch, state: 'opened', per_page: 1 } }) : []
+  ]);
+  const pipeline = pipelines.find((entry) => entry.sha === head.sha) || pipelines[0];
+  const request = requests[0];
+  const pullRequest = request ? { number: request.iid, title: request.title, url: request.web_url } : null;
+  if (!pipeline) return { sha: null, runs: [], pullRequest };
+  return {
+    sha: pipeline.sha,
+    runs: [{
+      id: String(pipeline.id),
+      name: `Pipeline #${pipeline.iid || pipeline.id}`,
+      state: gitlabState(pipeline.status),
+      sha: pipeline.sha,
+      event: pipeline.source || null,
+      url: pipeline.web_url,
+      attempt: 1,
+      startedAt: pipeline.created_at,
+      updatedAt: pipeline.updated_at
+    }],
+    pullRequest
+  };
+}
+
+// CI state of the checked-out branch (or detached commit): its newest runs, the overall
+// state, and the open pull / merge request. `upToDate` is false when the runs are for an
+// older commit, e.g. before the local one is pushed.
+async function getCiStatus(rootPath, { remote: remoteName = 'origin' } = {}) {
+  const repoRoot = await resolveRepo(rootPath);
+  const remote = await getForgeRemote(repoRoot, remoteName);
+  const head = await localHead(repoRoot);
+  if (!head.sha) throw new ForgeError(ErrorCode.NOT_FOUND, 'This repository has no commits');
+  const status = remote.kind === 'github' ? await githubStatus(remote, head) : await gitlabStatus(remote, head);
+  return {
+    repoRoot,
+    provider: remote.kind,
+    project: remote.projectPath,
+    branch: head.branch,
+    sha: status.sha,
+    upToDate: status.sha === head.sha,
+    state: overallState(status.runs),
+    runs: status.runs,
+    pullRequest: status.pullRequest,
+    checkedAt: new Date().toISOString()
+  };
+}
+
+// Jobs of a workflow run (GitHub) or pipeline (GitLab)
+async function getCiJobs(rootPath, runId, { remote: remoteName = 'origin' } = {}) {
+  if (!runId) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A run id is required');
+  const repoRoot = await resolveRepo(rootPath);
+  const remote = await getForgeRemote(repoRoot, remoteName);
+  const base = projectApiPath(remote);
+  if (remote.kind === 'github') {
+    const { jobs = [] } = await forgeRequest(remote, 'GET', `${base}/actions/runs/${encodeURIComponent(runId)}/jobs`, { query: { per_page: 100 } });
+    return jobs.map(githubJob);
+  }
+  const jobs = await forgeRequest(remote, 'GET', `${base}/pipelines/${encodeURIComponent(runId)}/jobs`, { query: { per_page: 100 } });
+  return jobs.map(gitlabJob);
+}
+
+function statusKey(status) {
+  return JSON.stringify([status.state, status.branch, status.sha, status.runs.map((run) => [run.id, run.state, run.attempt]), status.pullRequest && status.pullRequest.number]);
+}
+
+async function poll(repoRoot) {
+  const watch = watches.get(repoRoot);
+  if (!watch) return;
+  let delay = IDLE_POLL_MS;
+  try {
+    const status = await getCiStatus(repoRoot, watch.options);
+    if (['running', 'queued'].includes(status.state)) delay = ACTIVE_POLL_MS;
+    const key = statusKey(status);
+    if (key !== watch.last) {
+      watch.last = key;
+      watch.payload = { ...status, error: null };
+      emit('ci:status', watch.payload);
+    }
+  } catch (error) {
+    const key = `error:${error.message}`;
+    if (key !== watch.last) {
+      watch.last = key;
+      log.warn(`CI status for ${repoRoot} failed: ${error.message}`);
+      watch.payload = { repoRoot, state: 'unknown', runs: [], checkedAt: new Date().toISOString(), error: error.message };
+      emit('ci:status', watch.payload);
+    }
+  }
+  if (watches.get(repoRoot) === watch) watch.timer = setTimeout(() => poll(repoRoot), delay);
+}
+
+// Polls the repository's CI and emits ci:status whenever it changes (and once on start).
+// Watches are shared between windows and stop when the last watching window closes.
+async function watchCi(rootPath, owner, options = {}) {
+  const repoRoot = await resolveRepo(rootPath);
+  let watch = watches.get(repoRoot);
+  if (watch) {
+    watch.owners.add(owner);
+    // A late subscriber gets the current state straight away
+    if (watch.payload) emit('ci:status', watch.payload, { target: owner });
+    return { repoRoot };
+  }
+  watch = { owners: new Set([owner]), timer: null, options: { remote: options.remote || 'origin' }, last: null, payload: null };
+  watches.set(repoRoot, watch);
+  poll(repoRoot);
+  return { repoRoot };
+}
+
+function unwatchCi(repoRoot, owner) {
+  const watch = watches.get(repoRoot);
+  if (!watch) return false;
+  watch.owners.delete(owner);
+  if (watch.owners.size) return true;
+  clearTimeout(watch.timer);
+  watches.delete(repoRoot);
+  return true;
+}
+
+async function fetchJob(remote, jobId) {
+  const base = projectApiPath(remote);
+  if (remote.kind === 'github') return githubJob(await forgeRequest(remote, 'GET', `${base}/actions/jobs/${encodeURIComponent(jobId)}`));
+  return gitlabJob(await forgeRequest(remote, 'GET', `${base}/jobs/${encodeURIComponent(jobId)}`));
+}
+
+// Whole log so far. GitHub only serves logs once a job finishes, so a running job has none.
+async function fetchJobLog(remote, jobId) {
+  const base = projectApiPath(remote);
+  if (remote.kind === 'gitlab') return forgeRequest(remote, 'GET', `${base}/jobs/${encodeURIComponent(jobId)}/trace`, { text: true });
+  try {
+    const text = await forgeRequest(remote, 'GET', `${base}/actions/jobs/${encodeURIComponent(jobId)}/logs`, { text: true });
+    return text.replace(GITHUB_TIMESTAMP, '');
+  } catch (error) {
+    if (error.code === ErrorCode.NOT_FOUND) return '';
+    throw error;
+  }
+}
+
+function endLogStream(stream, status, error = null) {
+  if (stream.stopped) return;
+  stream.stopped = true;
+  clearTimeout(stream.timer);
+  logStreams.delete(stream.id);
+  emit('ci:logEnded', { streamId: stream.id, jobId: stream.jobId, status, error }, { target: stream.owner });
+}
+
+// Streams a job's log as ci:log chunks (raw text with ANSI colors, ready for a terminal
+// buffer) until the job finishes, then emits ci:logEnded with the job's final state
+async function followJobLog(rootPath, jobId, owner, { remote: remoteName = 'origin' } = {}) {
+  if (!jobId) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A job id is required');
+  const repoRoot = await resolveRepo(rootPath);
+  const remote = await getForgeRemote(repoRoot, remoteName);
+  const job = await fetchJob(remote, String(jobId));
+  const stream = { id: crypto.randomUUID(), jobId: String(jobId), owner, timer: null, offset: 0, stopped: false };
+  logStreams.set(stream.id, stream);
+
+  const tick = async () => {
+    try {
+      const current = await fetchJob(remote, stream.jobId);
+      const finished = !['running', 'queued'].includes(current.state);
+      const text = await fetchJobLog(remote, stream.jobId);
+      if (stream.stopped) return;
+      // A retried job restarts its log
+      if (text.length < stream.offset) stream.offset = 0;
+      if (text.length > stream.offset) {
+        emit('ci:log', { streamId: stream.id, jobId: stream.jobId, chunk: text.slice(stream.offset) }, { target: stream.owner });
+        stream.offset = text.length;
+      }
+      if (finished) endLogStream(stream, current.state);
+      else stream.timer = setTimeout(tick, LOG_POLL_MS);
+    } catch (error) {
+      endLogStream(stream, null, error.message);
+    }
+  };
+  tick();
+  return { streamId: stream.id, job };
+}
+
+function stopJobLog(streamId) {
+  const stream = logStreams.get(streamId);
+  if (!stream) return false;
+  endLogStream(stream, null, 'stopped');
+  return true;
+}
+
+function setupCiHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  const watchedSenders = new WeakSet();
+  const watchSender = (sender) => {
+    if (watchedSenders.has(sender)) return;
+    watchedSenders.add(sender);
+    sender.once('destroyed', () => {
+      for (const repoRoot of Array.from(watches.keys())) unwatchCi(repoRoot, sender);
+      for (const stream of Array.from(logStreams.values())) {
+        if (stream.owner === sender) endLogStream(stream, null, 'window closed');
+      }
+    });
+  };
+
+  ipcMain.handle('ci:getStatus', async (event, rootPath, options) => getCiStatus(rootPath, options || {}));
+  ipcMain.handle('ci:getJobs', async (event, rootPath, runId, options) => getCiJobs(rootPath, runId, options || {}));
+  ipcMain.handle('ci:watch', async (event, rootPath, options) => {
+    watchSender(event.sender);
+    return watchCi(rootPath, event.sender, options || {});
+  });
+  ipcMain.handle('ci:unwatch', async (event, rootPath) => unwatchCi(await resolveRepo(rootPath), event.sender));
+  ipcMain.handle('ci:followLog', async (event, rootPath, jobId, options) => {
+    watchSender(event.sender);
+    return followJobLog(rootPath, jobId, event.sender, options || {});
+  });
+  ipcMain.handle('ci:stopLog', async (event, streamId) => stopJobLog(streamId));
+}
+
+module.exports = { setupCiHandlers, getCiStatus, getCiJobs };
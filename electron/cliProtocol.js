@@ -0,0 +1,73 @@
+// Wire protocol shared by the Forge app (instance server) and the `forge` CLI.
+// Messages are newline-delimited JSON over a per-user Unix socket / named pipe:
+//   request  { id, method, params }
+//   response { id, result } | { id, error: { code, message } }
+// This file must not depend on electron so the CLI can run under plain Node.
+
+const os = require('os');
+const path = require('path');
+
+const PROTOCOL_VERSION = 1;
+
+function userTag() {
+  try {
+    const info = os.userInfo();
+    return process.platform === 'win32' ? info.username : String(info.uid);
+  } catch {
+    return 'default';
+  }
+}
+
+// Where the running instance listens. FORGE_IPC_PATH overrides it, and terminals
+// spawned by Forge inherit it so the CLI reaches the instance that owns them. The
+// per-user runtime directory is preferred over the shared temp directory.
+function getSocketPath() {
+  if (process.env.FORGE_IPC_PATH) return process.env.FORGE_IPC_PATH;
+  if (process.platform === 'win32') {
+    return `\\\\.\\pipe\\forge-${userTag()}`;
+  }
+  if (process.env.XDG_RUNTIME_DIR) return path.join(process.env.XDG_RUNTIME_DIR, 'forge', 'instance.sock');
+  return path.join(os.tmpdir(), `forge-${userTag()}`, 'instance.sock');
+}
+
+function encodeMessage(message) {
+  return `${JSON.stringify(message)}\n`;
+}
+
+// Split a socket stream into JSON messages
+function createMessageReader(onMessage) {
+  let buffer = '';
+  return (chunk) => {
+    buffer += chunk.toString('utf-8');
+    let newline;
+    while ((newline = buffer.indexOf('\n')) >= 0) {
+      const line = buffer.slice(0, newline).trim();
+      buffer = buffer.slice(newline + 1);
+      if (!line) continue;
+      try {
+        onMessage(JSON.parse(line));
+      } catch {
+        // Ignore malformed lines rather than tearing down the connection
+      }
+    }
+  };
+}
+
+// "src/app.ts:12:5" -> { path, line: 12, column: 5 }. Windows drive letters are preserved.
+function parsePathSpec(spec, cwd = process.cwd()) {
+  const match = /^(.*?)(?::(\d+))?(?::(\d+))?$/.exec(spec);
+  const filePath = match && match[1] ? match[1] : spec;
+  return {
+    path: path.resolve(cwd, filePath),
+    line: match && match[2] ? Number(match[2]) : undefined,
+    column: match && match[3] ? Number(match[3]) : undefined
+  };
+}
+
+module.exports = {
+  PROTOCOL_VERSION,
+  getSocketPath,
+  encodeMessage,
+  createMessageReader,
+  parsePathSpec
+};
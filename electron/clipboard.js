@@ -0,0 +1,218 @@
+const { clipboard } = require('electron');
+const crypto = require('crypto');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('clipboard');
+
+const POLL_INTERVAL = 750;
+const MAX_ITEM_LENGTH = 1024 * 1024;
+const SENSITIVE_CLEAR_DELAY = 30000;
+
+const store = new JsonStore('clipboard-history', {
+  settings: {
+    enabled: true,
+    maxItems: 100,
+    // Skip text that looks like a credential
+    excludeSecrets: true
+  },
+  // Only pinned items are persisted; the rest of the history lives in memory
+  pinned: []
+});
+
+// Newest first
+let history = store.get('pinned', []).map((item) => ({ ...item, pinned: true }));
+let lastSeenHash = null;
+let pollTimer = null;
+
+// Hashes of content Forge itself placed on the clipboard as sensitive
+const sensitiveHashes = new Set();
+
+// Credential shapes that should never land in history
+const SECRET_PATTERNS = [
+  /-----BEGIN [A-Z ]*PRIVATE KEY-----/,
+  /\b(?:ghp|gho|ghu|ghs|ghr|github_pat)_[A-Za-z0-9_]{20,}\b/,
+  /\bsk-[A-Za-z0-9_-]{20,}\b/,
+  /\bxox[abposr]-[A-Za-z0-9-]{10,}\b/,
+  /\bAKIA[0-9A-Z]{16}\b/,
+  /\beyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}\b/
+];
+
+const hashText = (text) => crypto.createHash('sha256').update(text).digest('hex');
+
+function isSensitive(text, hash) {
+  if (sensitiveHashes.has(hash)) return true;
+  // Password managers mark copies with this pasteboard type on macOS
+  if (process.platform === 'darwin' && clipboard.availableFormats().includes('org.nspasteboard.ConcealedType')) return true;
+  return store.get('settings').excludeSecrets && SECRET_PATTERNS.some((pattern) => pattern.test(text));
+}
+
+function persistPinned() {
+  store.set('pinned', history.filter((item) => item.pinned));
+}
+
+function serialize(item) {
+  return {
+    id: item.id,
+    text: item.text,
+    preview: item.text.slice(0, 200),
+    hasHtml: Boolean(item.html),
+    source: item.source,
+    pinned: item.pinned,
+    createdAt: item.createdAt,
+    lastUsedAt: item.lastUsedAt
+  };
+}
+
+function trimHistory() {
+  const { maxItems } = store.get('settings');
+  const unpinned = history.filter((item) => !item.pinned);
+  if (unpinned.length <= maxItems) return;
+  const drop = new Set(unpinned.slice(maxItems).map((item) => item.id));
+  history = history.filter((item) => !drop.has(item.id));
+}
+
+function addItem(text, html, source) {
+  const hash = hashText(text);
+  const existing = history.find((item) => item.hash === hash);
+  const now = new Date().toISOString();
+
+  if (existing) {
+    // Re-copying moves the entry back to the top
+    history = [existing, ...history.filter((item) => item !== existing)];
+    existing.lastUsedAt = now;
+  } else {
+    history.unshift({ id: `clip_${uuidv4()}`, hash, text, html: html || null, source, pinned: false, createdAt: now, lastUsedAt: now });
+    trimHistory();
+  }
+  emit('clipboard:changed', serialize(history[0]));
+}
+
+function poll() {
+  const text = clipboard.readText();
+  if (!text || text.length > MAX_ITEM_LENGTH) return;
+  const hash = hashText(text);
+  if (hash === lastSeenHash) return;
+  lastSeenHash = hash;
+
+  if (isSensitive(text, hash)) {
+    log.debug('Skipped sensitive clipboard content');
+    return;
+  }
+  addItem(text, clipboard.readHTML(), 'system');
+}
+
+function startMonitoring() {
+  if (pollTimer) return;
+  lastSeenHash = hashText(clipboard.readText() || '');
+  pollTimer = setInterval(poll, POLL_INTERVAL);
+  pollTimer.unref();
+}
+
+function stopMonitoring() {
+  clearInterval(pollTimer);
+  pollTimer = null;
+}
+
+function getHistory(query, limit) {
+  const needle = (query || '').toLowerCase();
+  const items = history
+    .filter((item) => !needle || item.text.toLowerCase().includes(needle))
+    // Pinned entries stay at the top
+    .sort((a, b) => b.pinned - a.pinned);
+  return items.slice(0, limit || items.length).map(serialize);
+}
+
+function findItem(id) {
+  const item = history.find((entry) => entry.id === id);
+  if (!item) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `Clipboard item ${id} not found`);
+  }
+  return item;
+}
+
+// Put a history item back on the clipboard, optionally pasting into the requesting window
+function pasteHistoryItem(id, sender, options = {}) {
+  const item = findItem(id);
+  lastSeenHash = item.hash;
+  clipboard.write(item.html ? { text: item.text, html: item.html } : { text: item.text });
+  addItem(item.text, item.html, item.source);
+
+  if (options.paste && sender && !sender.isDestroyed()) {
+    sender.paste();
+  }
+  return serialize(item);
+}
+
+// Copy without recording in history; cleared after a delay if still present
+function writeSensitive(text, clearAfterMs = SENSITIVE_CLEAR_DELAY) {
+  const hash = hashText(text);
+  sensitiveHashes.add(hash);
+  lastSeenHash = hash;
+  clipboard.writeText(text);
+
+  if (clearAfterMs > 0) {
+    setTimeout(() => {
+      if (hashText(clipboard.readText() || '') === hash) clipboard.clear();
+      sensitiveHashes.delete(hash);
+    }, clearAfterMs).unref();
+  }
+  return true;
+}
+
+function setupClipboardHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  if (store.get('settings').enabled) startMonitoring();
+
+  ipcMain.handle('clipboard:getHistory', async (event, query, limit) => {
+    return getHistory(query, limit);
+  });
+
+  ipcMain.handle('clipboard:pasteHistoryItem', async (event, id, options) => {
+    return pasteHistoryItem(id, event.sender, options);
+  });
+
+  ipcMain.handle('clipboard:pin', async (event, id, pinned) => {
+    const item = findItem(id);
+    item.pinned = Boolean(pinned);
+    persistPinned();
+    trimHistory();
+    return serialize(item);
+  });
+
+  ipcMain.handle('clipboard:remove', async (event, id) => {
+    const item = findItem(id);
+    history = history.filter((entry) => entry !== item);
+    if (item.pinned) persistPinned();
+    return true;
+  });
+
+  ipcMain.handle('clipboard:clear', async (event, includePinned) => {
+    history = includePinned ? [] : history.filter((item) => item.pinned);
+    if (includePinned) persistPinned();
+    return true;
+  });
+
+  ipcMain.handle('clipboard:writeSensitive', async (event, text, clearAfterMs) => {
+    return writeSensitive(text, clearAfterMs);
+  });
+
+  ipcMain.handle('clipboard:getSettings', async () => {
+    return store.get('settings');
+  });
+
+  ipcMain.handle('clipboard:updateSettings', async (event, changes) => {
+    const settings = { ...store.get('settings'), ...changes };
+    store.set('settings', settings);
+    if (settings.enabled) startMonitoring();
+    else stopMonitoring();
+    trimHistory();
+    return settings;
+  });
+}
+
+module.exports = { setupClipboardHandlers, writeSensitive, getHistory, SECRET_PATTERNS };
@@ -0,0 +1,266 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { createLogger } = require('./logger');
+const { runJob } = require('./jobs');
+const { readTextFile } = require('./search');
+const { detectLanguage, getLanguageConfig } = require('./languages');
+const { listUnignoredFiles } = require('./snapshots');
+const { ensureIndex, getIndexedFiles } = require('./fileIndex');
+const { globToRegExp } = require('./editorconfig');
+const { anchorPattern } = require('./indexScope');
+const { configHash, loadCache, saveCache } = require('./indexCache');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('code-stats');
+
+// Bump when counting rules change so cached counts are recomputed
+const COUNTER_VERSION = 1;
+const CACHE_NAME = 'code-stats';
+const READ_CONCURRENCY = 8;
+const TOP_FILES = 20;
+
+// Comment syntax comes from the language registry; these decide how strings are skipped so
+// a "//" or "#" inside a literal isn't taken for a comment
+const PROSE_LANGUAGES = new Set(['markdown', 'mdx', 'restructuredtext', 'plaintext', 'latex', 'csv', 'log', 'diff', 'html', 'xml', 'vue', 'svelte', 'astro']);
+const QUOTES = {
+  default: ['"', "'"],
+  // 'a is a lifetime, not a character literal
+  rust: ['"'],
+  javascript: ['"', "'", '`'],
+  javascriptreact: ['"', "'", '`'],
+  typescript: ['"', "'", '`'],
+  typescriptreact: ['"', "'", '`']
+};
+// Quotes that may run across lines; other unterminated strings end with their line
+const MULTILINE_QUOTES = new Set(['`']);
+const NESTED_COMMENTS = new Set(['rust', 'haskell', 'swift', 'scala', 'dart', 'kotlin']);
+
+// Root -> { hash, files: Map<relative path, entry> } where an entry is
+// { mtimeMs, size, language, code, comment, blank } (language null for skipped files, with
+// `skipped` set to 'binary' or 'unknown')
+const caches = new Map();
+
+function syntaxFor(languageId) {
+  const config = getLanguageConfig(languageId);
+  const comments = config.comments || {};
+  return {
+    line: comments.line ? [comments.line] : [],
+    block: comments.block && comments.block[0] ? comments.block : null,
+    quotes: PROSE_LANGUAGES.has(languageId) ? [] : QUOTES[languageId] || QUOTES.default,
+    nested: NESTED_COMMENTS.has(languageId)
+  };
+}
+
+// Classifies every line as code, comment or blank. A line with any code outside
+// comments counts as code, as tokei and cloc do.
+function countText(text, syntax) {
+  const counts = { code: 0, comment: 0, blank: 0 };
+  let depth = 0;
+  let openQuote = null;
+  const simple = !syntax.line.length && !syntax.block && !syntax.quotes.length;
+  const lines = text.split(/\r?\n/);
+  // A trailing newline doesn't start another line
+  if (lines.length > 1 && lines[lines.length - 1] === '') lines.pop();
+  for (const line of lines) {
+    const trimmed = line.trim();
+    if (!trimmed && !openQuote) {
+      counts.blank++;
+      continue;
+    }
+    if (simple) {
+      counts.code++;
+      continue;
+    }
+    let hasCode = Boolean(openQuote);
+    let hasComment = depth > 0;
+    let i = openQuote ? 0 : line.length - line.trimStart().length;
+    while (i < line.length) {
+      if (openQuote) {
+        const ch = line[i];
+        if (ch === '\\') i += 2;
+        else if (line.startsWith(openQuote, i)) {
+          i += openQuote.length;
+          openQuote = null;
+        } else i++;
+        continue;
+      }
+      if (depth > 0) {
+        hasComment = true;
+        if (syntax.nested && line.startsWith(syntax.block[0], i)) {
+          depth++;
+          i += syntax.block[0].length;
+        } else if (line.startsWith(syntax.block[1], i)) {
+          depth--;
+          i += syntax.block[1].length;
+        } else i++;
+        continue;
+      }
+      const ch = line[i];
+      if (ch === ' ' || ch === '\t') {
+        i++;
+        continue;
+      }
+      if (syntax.line.some((token) => line.startsWith(token, i))) {
+        hasComment = true;
+        break;
+      }
+      if (syntax.block && line.startsWith(syntax.block[0], i)) {
+        depth = 1;
+        hasComment = true;
+        i += syntax.block[0].length;
+        continue;
+      }
+      hasCode = true;
+      const quote = syntax.quotes.find((candidate) => line.startsWith(candidate, i));
+      if (quote) {
+        openQuote = quote;
+        i += quote.length;
+        continue;
+      }
+      i++;
+    }
+    if (openQuote && !MULTILINE_QUOTES.has(openQuote)) openQuote = null;
+    if (hasCode) counts.code++;
+    else if (hasComment) counts.comment++;
+    else counts.blank++;
+  }
+  return counts;
+}
+
+function compileExcludes(patterns) {
+  if (!Array.isArray(patterns)) return [];
+  return patterns.filter((pattern) => typeof pattern === 'string' && pattern.trim()).map((pattern) => globToRegExp(anchorPattern(pattern.trim())));
+}
+
+async function countFile(filePath) {
+  const text = await readTextFile(filePath);
+  if (text === null) return { language: null, skipped: 'binary' };
+  const language = detectLanguage(filePath, text.slice(0, 256));
+  if (!language) return { language: null, skipped: 'unknown' };
+  return { language, ...countText(text, syntaxFor(language)) };
+}
+
+async function loadFileCache(root, hash) {
+  const memory = caches.get(root);
+  if (memory && memory.hash === hash) return memory.files;
+  const cached = await loadCache(root, CACHE_NAME, hash);
+  const files = new Map(cached ? Object.entries(cached.data.files) : []);
+  caches.set(root, { hash, files });
+  return files;
+}
+
+function summarize(root, entries, ignoreSource, reused) {
+  const languages = new Map();
+  const directories = new Map();
+  const totals = { files: 0, code: 0, comment: 0, blank: 0, lines: 0 };
+  const skipped = { binary: 0, unknown: 0 };
+  const files = [];
+  for (const [relative, entry] of entries) {
+    if (!entry.language) {
+      skipped[entry.skipped] = (skipped[entry.skipped] || 0) + 1;
+      continue;
+    }
+    const lines = entry.code + entry.comment + entry.blank;
+    if (!languages.has(entry.language)) {
+      languages.set(entry.language, { id: entry.language, name: getLanguageConfig(entry.language).name, files: 0, code: 0, comment: 0, blank: 0, lines: 0 });
+    }
+    const topLevel = relative.includes('/') ? relative.slice(0, relative.indexOf('/')) : '.';
+    if (!directories.has(topLevel)) directories.set(topLevel, { path: topLevel, files: 0, code: 0, comment: 0, blank: 0, lines: 0 });
+    for (const bucket of [totals, languages.get(entry.language), directories.get(topLevel)]) {
+      bucket.files++;
+      bucket.code += entry.code;
+      bucket.comment += entry.comment;
+      bucket.blank += entry.blank;
+      bucket.lines += lines;
+    }
+    files.push({ path: relative, language: entry.language, code: entry.code, comment: entry.comment, blank: entry.blank });
+  }
+  const byCode = (a, b) => b.code - a.code || a.path.localeCompare(b.path);
+  return {
+    rootPath: root,
+    ignoreSource,
+    totals,
+    languages: Array.from(languages.values()).sort((a, b) => b.code - a.code),
+    // Totals per top-level folder; files at the root are under "."
+    directories: Array.from(directories.values()).sort(byCode),
+    largestFiles: files.sort(byCode).slice(0, TOP_FILES),
+    skipped,
+    reused,
+    countedAt: new Date().toISOString()
+  };
+}
+
+// tokei-style line counts for a workspace: per-language and per-folder file, code, comment
+// and blank totals. Honors .gitignore (through git when the folder is a checkout) plus
+// `exclude` globs. Counts are cached per file by mtime and size, so recounting only reads
+// files that changed.
+async function countLinesOfCode(rootPath, { exclude = [], respectGitignore = true } = {}) {
+  if (typeof rootPath !== 'string' || !rootPath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A workspace folder is required');
+  const root = path.resolve(rootPath);
+  const stats = await fs.stat(root).catch((error) => {
+    throw ForgeError.from(error, 'Workspace folder not found');
+  });
+  if (!stats.isDirectory()) throw new ForgeError(ErrorCode.NOT_DIRECTORY, `${root} is not a folder`);
+  const excludes = compileExcludes(exclude);
+
+  const job = runJob('code-stats', `Count lines in ${path.basename(root)}`, async (handle) => {
+    handle.reportProgress(null, 'Listing files');
+    const hash = configHash(COUNTER_VERSION, respectGitignore);
+    const cache = await loadFileCache(root, hash);
+    let listing;
+    if (respectGitignore) {
+      listing = await listUnignoredFiles(root, handle.signal);
+    } else {
+      await ensureIndex(root).ready;
+      listing = { files: getIndexedFiles(root).map((filePath) => path.relative(root, filePath).split(path.sep).join('/')), ignoreSource: null };
+    }
+    handle.throwIfCancelled();
+    const relatives = listing.files.filter((relative) => !excludes.some((regex) => regex.test(relative)));
+
+    const entries = new Map();
+    let reused = 0;
+    let next = 0;
+    let done = 0;
+    const worker = async () => {
+      while (next < relatives.length) {
+        handle.throwIfCancelled();
+        const relative = relatives[next++];
+        const filePath = path.join(root, relative);
+        const fileStats = await fs.lstat(filePath).catch(() => null);
+        if (fileStats && fileStats.isFile()) {
+          const known = cache.get(relative);
+          if (known && known.mtimeMs === fileStats.mtimeMs && known.size === fileStats.size) {
+            entries.set(relative, known);
+            reused++;
+          } else {
+            const counted = await countFile(filePath);
+            const entry = { mtimeMs: fileStats.mtimeMs, size: fileStats.size, ...counted };
+            entries.set(relative, entry);
+            cache.set(relative, entry);
+          }
+        }
+        done++;
+        if (done % 200 === 0) handle.reportProgress(done / relatives.length, `${done} of ${relatives.length} files`);
+      }
+    };
+    await Promise.all(Array.from({ length: READ_CONCURRENCY }, worker));
+
+    // Files that no longer exist or are now ignored drop out of the cache
+    for (const relative of Array.from(cache.keys())) {
+      if (!entries.has(relative)) cache.delete(relative);
+    }
+    await saveCache(root, CACHE_NAME, hash, { files: Object.fromEntries(cache) });
+    const result = summarize(root, entries, listing.ignoreSource, reused);
+    log.info(`Counted ${result.totals.files} files (${result.totals.code} lines of code) in ${root}; ${reused} unchanged`);
+    return result;
+  });
+  return job.promise;
+}
+
+function setupCodeStatsHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('stats:countLinesOfCode', async (event, rootPath, options) => countLinesOfCode(rootPath, options || {}));
+}
+
+module.exports = { setupCodeStatsHandlers, countLinesOfCode, countText };
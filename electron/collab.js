@@ -0,0 +1,361 @@
+const http = require('http');
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { v4: uuidv4 } = require('uuid');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { acceptWebSocket, rejectUpgrade, tokensMatch, lanAddress } = require('./websocket');
+const { watchPath } = require('./watchHub');
+const { windowManager } = require('./windows');
+const { TextDocument } = require('./crdt');
+
+const log = createLogger('collab');
+
+const MAX_PARTICIPANTS = 20;
+const MAX_SHARED_FILES = 50;
+const MAX_DOCUMENT_SIZE = 2 * 1024 * 1024;
+// Replica ids reserved for the host's editor and for edits the session makes itself
+// (initial content, reloads from disk)
+const HOST_CLIENT = 'host';
+const DISK_CLIENT = 'disk';
+
+// Sessions by id: { id, token, server, url, owner, docs, peers, nextPeer }
+// docs: label -> { filePath, label, doc, savedText, watcher, conflict }
+// peers: peerId -> { id, name, connection, open: Set<label> }
+const sessions = new Map();
+
+function getSession(sessionId) {
+  const session = sessions.get(sessionId);
+  if (!session) throw new ForgeError(ErrorCode.SESSION_NOT_FOUND, `Collaboration session ${sessionId} not found`, { details: { sessionId } });
+  return session;
+}
+
+function getDocument(session, label) {
+  const entry = session.docs.get(label);
+  if (!entry) throw new ForgeError(ErrorCode.NOT_FOUND, `${label} is not part of this session`);
+  return entry;
+}
+
+function participants(session) {
+  return [{ peerId: HOST_CLIENT, name: 'Host' }, ...Array.from(session.peers.values(), (peer) => ({ peerId: peer.id, name: peer.name }))];
+}
+
+function sendTo(peer, message) {
+  peer.connection.send(JSON.stringify(message));
+}
+
+// Relay to every peer with `label` open except `exceptPeerId`
+function relay(session, label, message, exceptPeerId) {
+  const data = JSON.stringify(message);
+  for (const peer of session.peers.values()) {
+    if (peer.id !== exceptPeerId && peer.open.has(label)) peer.connection.send(data);
+  }
+}
+
+function broadcastParticipants(session) {
+  const list = participants(session);
+  const data = JSON.stringify({ type: 'participants', participants: list });
+  for (const peer of session.peers.values()) peer.connection.send(data);
+  emit('collab:participants', { sessionId: session.id, participants: list }, { target: session.owner });
+}
+
+// Apply ops from one replica and fan them out to everyone else. Ops are applied and
+// forwarded in arrival order, which keeps delivery causal for every replica.
+function applyOps(session, entry, ops, fromPeerId) {
+  if (!Array.isArray(ops)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'ops must be a list');
+  let added = 0;
+  for (const op of ops) {
+    if (op && op.type === 'insert') {
+      if (!Array.isArray(op.id) || op.id[0] !== fromPeerId || typeof op.text !== 'string') {
+        throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Inserts must carry the sender\'s own replica id');
+      }
+      added += op.text.length;
+    } else if (!op || op.type !== 'delete' || !Array.isArray(op.ranges)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown operation: ${op && op.type}`);
+    }
+  }
+  if (entry.doc.length + added > MAX_DOCUMENT_SIZE) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${entry.label} would exceed the shared document size limit`);
+  }
+
+  // Whatever was applied is forwarded even if a later op fails, so replicas never diverge
+  const changes = [];
+  const applied = [];
+  let failure = null;
+  for (const op of ops) {
+    try {
+      changes.push(...entry.doc.apply(op));
+      applied.push(op);
+    } catch (error) {
+      failure = new ForgeError(ErrorCode.CONFLICT, `Out-of-order edit for ${entry.label}: ${error.message}`, { cause: error });
+      break;
+    }
+  }
+  if (applied.length > 0) {
+    relay(session, entry.label, { type: 'ops', path: entry.label, ops: applied, peerId: fromPeerId }, fromPeerId);
+    if (fromPeerId !== HOST_CLIENT) {
+      emit('collab:ops', { sessionId: session.id, path: entry.label, peerId: fromPeerId, ops: applied, changes }, { target: session.owner });
+    }
+  }
+  if (failure) throw failure;
+  return changes;
+}
+
+function handlePeerMessage(session, peer, raw) {
+  let message;
+  try {
+    message = JSON.parse(raw);
+  } catch {
+    sendTo(peer, { type: 'error', message: 'Malformed message' });
+    return;
+  }
+  const entry = session.docs.get(message.path);
+  if (!entry) {
+    sendTo(peer, { type: 'error', message: `Unknown file: ${message.path}` });
+    return;
+  }
+  switch (message.type) {
+    case 'open':
+      peer.open.add(entry.label);
+      sendTo(peer, { type: 'state', path: entry.label, state: entry.doc.getState(), dirty: entry.doc.toString() !== entry.savedText });
+      break;
+    case 'close':
+      peer.open.delete(entry.label);
+      break;
+    case 'ops':
+      try {
+        applyOps(session, entry, message.ops, peer.id);
+      } catch (error) {
+        // Resync the peer from the authoritative copy
+        sendTo(peer, { type: 'error', message: error.message });
+        sendTo(peer, { type: 'state', path: entry.label, state: entry.doc.getState() });
+      }
+      break;
+    case 'presence':
+      relay(session, entry.label, { type: 'presence', path: entry.label, peerId: peer.id, name: peer.name, selection: message.selection }, peer.id);
+      emit('collab:presence', { sessionId: session.id, path: entry.label, peerId: peer.id, name: peer.name, selection: message.selection }, { target: session.owner });
+      break;
+    default:
+      sendTo(peer, { type: 'error', message: `Unknown message type: ${message.type}` });
+  }
+}
+
+function handlePeer(session, request, socket, head) {
+  const url = new URL(request.url, 'http://localhost');
+  if (url.pathname !== '/collab' || !tokensMatch(session.token, url.searchParams.get('token'))) {
+    rejectUpgrade(socket, 403, 'Forbidden');
+    return;
+  }
+  if (session.peers.size >= MAX_PARTICIPANTS) {
+    rejectUpgrade(socket, 503, 'Session Full');
+    return;
+  }
+  const connection = acceptWebSocket(request, socket, head);
+  if (!connection) return;
+
+  const peer = {
+    id: `p${++session.nextPeer}`,
+    name: (url.searchParams.get('name') || 'Guest').slice(0, 64),
+    connection,
+    open: new Set()
+  };
+  session.peers.set(peer.id, peer);
+  log.info(`${peer.name} (${peer.id}) joined collaboration session ${session.id}`);
+  sendTo(peer, { type: 'welcome', peerId: peer.id, files: Array.from(session.docs.keys()), participants: participants(session) });
+  connection.on('message', (data) => handlePeerMessage(session, peer, String(data)));
+  connection.on('close', () => {
+    session.peers.delete(peer.id);
+    if (sessions.has(session.id)) broadcastParticipants(session);
+  });
+  broadcastParticipants(session);
+}
+
+// Disk and document agree up to `savedText`. An external change to a file nobody has
+// edited since is merged in as an edit; otherwise the host decides via reload or save.
+async function handleDiskChange(session, entry) {
+  let text;
+  try {
+    text = await fs.readFile(entry.filePath, 'utf-8');
+  } catch {
+    return;
+  }
+  if (text === entry.savedText) return;
+  const current = entry.doc.toString();
+  if (text === current) {
+    entry.savedText = text;
+    return;
+  }
+  if (current === entry.savedText) {
+    reloadFromDisk(session, entry, text);
+    return;
+  }
+  entry.conflict = true;
+  log.info(`${entry.filePath} changed on disk while it has unsaved collaborative edits`);
+  relay(session, entry.label, { type: 'diskChanged', path: entry.label });
+  emit('collab:diskChanged', { sessionId: session.id, path: entry.label }, { target: session.owner });
+}
+
+function reloadFromDisk(session, entry, text) {
+  const ops = entry.doc.replaceText(text);
+  entry.savedText = text;
+  entry.conflict = false;
+  if (ops.length === 0) return;
+  relay(session, entry.label, { type: 'ops', path: entry.label, ops, peerId: DISK_CLIENT });
+  emit('collab:ops', { sessionId: session.id, path: entry.label, peerId: DISK_CLIENT, ops }, { target: session.owner });
+  relay(session, entry.label, { type: 'saved', path: entry.label });
+}
+
+function displayPath(filePath, workspacePath) {
+  if (workspacePath) {
+    const relative = path.relative(workspacePath, filePath);
+    if (relative && !relative.startsWith('..') && !path.isAbsolute(relative)) return relative.split(path.sep).join('/');
+  }
+  return path.basename(filePath);
+}
+
+// Host a session for `files`. `buffers` maps file path -> unsaved editor content to start
+// from instead of the disk copy. The host's editor joins as replica "host" through IPC.
+async function startCollabSession({ files, buffers = {}, lan = false, port = 0 }, { owner, workspacePath }) {
+  if (!Array.isArray(files) || files.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Choose at least one file to share');
+  }
+  if (files.length > MAX_SHARED_FILES) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `At most ${MAX_SHARED_FILES} files can be shared`);
+  }
+  const session = {
+    id: `collab_${uuidv4()}`,
+    token: crypto.randomBytes(24).toString('base64url'),
+    owner,
+    docs: new Map(),
+    peers: new Map(),
+    nextPeer: 0,
+    server: null,
+    url: null
+  };
+
+  for (const filePath of files) {
+    if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Shared files must be absolute paths');
+    }
+    const resolved = path.resolve(filePath);
+    const savedText = await fs.readFile(resolved, 'utf-8');
+    const initial = typeof buffers[filePath] === 'string' ? buffers[filePath] : savedText;
+    if (initial.length > MAX_DOCUMENT_SIZE) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(resolved)} is too large to share`);
+    }
+    let label = displayPath(resolved, workspacePath);
+    while (session.docs.has(label)) label = `${label}~`;
+    session.docs.set(label, { filePath: resolved, label, doc: TextDocument.fromText(DISK_CLIENT, initial), savedText, watcher: null, conflict: false });
+  }
+
+  session.server = http.createServer((request, response) => {
+    response.writeHead(426, { 'Content-Type': 'text/plain' });
+    response.end('Connect with a Forge collaboration client');
+  });
+  session.server.on('upgrade', (request, socket, head) => handlePeer(session, request, socket, head));
+  const host = lan ? '0.0.0.0' : '127.0.0.1';
+  await new Promise((resolve, reject) => {
+    session.server.once('error', (error) => reject(ForgeError.from(error, 'Could not start collaboration server')));
+    session.server.listen(port, host, resolve);
+  });
+  session.url = `ws://${lan ? lanAddress() : '127.0.0.1'}:${session.server.address().port}/collab?token=${session.token}`;
+
+  for (const entry of session.docs.values()) {
+    entry.watcher = watchPath(entry.filePath, (type) => {
+      if (type === 'change') handleDiskChange(session, entry);
+    }, { kind: 'file', workspaceRoot: workspacePath, owner: 'collab' });
+  }
+  sessions.set(session.id, session);
+  log.info(`Collaboration session ${session.id} started with ${session.docs.size} file(s) on ${host}:${session.server.address().port}`);
+  return describe(session);
+}
+
+function stopCollabSession(sessionId, reason = 'stopped') {
+  const session = sessions.get(sessionId);
+  if (!session) return false;
+  sessions.delete(sessionId);
+  for (const peer of session.peers.values()) peer.connection.close(1001, 'Session ended');
+  for (const entry of session.docs.values()) {
+    if (entry.watcher) entry.watcher.close();
+  }
+  session.server.close();
+  log.info(`Collaboration session ${sessionId} ended (${reason})`);
+  emit('collab:ended', { sessionId, reason }, { target: session.owner });
+  return true;
+}
+
+function describe(session) {
+  return {
+    sessionId: session.id,
+    url: session.url,
+    files: Array.from(session.docs.values(), (entry) => ({
+      path: entry.label,
+      filePath: entry.filePath,
+      dirty: entry.doc.toString() !== entry.savedText,
+      conflict: entry.conflict
+    })),
+    participants: participants(session)
+  };
+}
+
+function setupCollabHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('collab:start', async (event, options = {}) => {
+    const context = windowManager.fromSender(event.sender);
+    const result = await startCollabSession(options, { owner: event.sender, workspacePath: context ? context.workspacePath : null });
+    const win = windowManager.windowFromSender(event.sender);
+    if (win) win.once('closed', () => stopCollabSession(result.sessionId, 'window closed'));
+    return result;
+  });
+
+  ipcMain.handle('collab:stop', async (event, sessionId) => stopCollabSession(sessionId));
+
+  ipcMain.handle('collab:getSession', async (event, sessionId) => describe(getSession(sessionId)));
+
+  // Snapshot for the host editor's replica, which must use client id "host"
+  ipcMain.handle('collab:getDocument', async (event, sessionId, label) => {
+    const entry = getDocument(getSession(sessionId), label);
+    return { path: entry.label, filePath: entry.filePath, clientId: HOST_CLIENT, state: entry.doc.getState(), text: entry.doc.toString() };
+  });
+
+  ipcMain.handle('collab:applyOps', async (event, sessionId, label, ops) => {
+    const session = getSession(sessionId);
+    return applyOps(session, getDocument(session, label), ops, HOST_CLIENT);
+  });
+
+  ipcMain.handle('collab:setPresence', async (event, sessionId, label, selection) => {
+    const session = getSession(sessionId);
+    const entry = getDocument(session, label);
+    relay(session, entry.label, { type: 'presence', path: entry.label, peerId: HOST_CLIENT, name: 'Host', selection });
+    return true;
+  });
+
+  // Write the shared document to disk, overwriting any external change
+  ipcMain.handle('collab:save', async (event, sessionId, label) => {
+    const session = getSession(sessionId);
+    const entry = getDocument(session, label);
+    const text = entry.doc.toString();
+    await fs.writeFile(entry.filePath, text, 'utf-8');
+    entry.savedText = text;
+    entry.conflict = false;
+    relay(session, entry.label, { type: 'saved', path: entry.label });
+    return true;
+  });
+
+  // Take the disk copy, discarding collaborative edits that weren't saved
+  ipcMain.handle('collab:reload', async (event, sessionId, label) => {
+    const session = getSession(sessionId);
+    const entry = getDocument(session, label);
+    reloadFromDisk(session, entry, await fs.readFile(entry.filePath, 'utf-8'));
+    return { text: entry.doc.toString() };
+  });
+
+  process.on('exit', () => {
+    for (const id of Array.from(sessions.keys())) stopCollabSession(id, 'quit');
+  });
+}
+
+module.exports = { setupCollabHandlers, startCollabSession, stopCollabSession };
@@ -0,0 +1,222 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { app } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { withForgeErrors } = require('./errors');
+const { terminalEvents, getTerminal } = require('./terminal');
+const { SECRET_PATTERNS } = require('./clipboard');
+const { fuzzyScore } = require('./fuzzy');
+
+const log = createLogger('command-history');
+
+const MAX_COMMAND_LENGTH = 8192;
+const DEFAULT_SEARCH_LIMIT = 50;
+// Frecency: each use counts for less the older it is, halving every two weeks
+const HALF_LIFE_MS = 14 * 24 * 60 * 60 * 1000;
+
+const store = new JsonStore('command-history', {
+  settings: {
+    enabled: true,
+    maxEntries: 10000,
+    // Skip commands that look like they contain a credential
+    excludeSecrets: true,
+    // Like HISTCONTROL=ignorespace: a leading space keeps a command out of history
+    ignoreLeadingSpace: true
+  }
+});
+
+// Executed commands across every terminal, oldest first. Persisted as one JSON line per
+// command so recording is an append; the file is rewritten when it outgrows the limit.
+let entries = null;
+let loading = null;
+let lineCount = 0;
+let writeQueue = Promise.resolve();
+
+function historyFile() {
+  return path.join(app.getPath('userData'), 'command-history.jsonl');
+}
+
+function getSettings() {
+  return store.get('settings');
+}
+
+async function loadEntries() {
+  if (entries) return entries;
+  if (!loading) {
+    loading = (async () => {
+      let text = '';
+      try {
+        text = await fs.readFile(historyFile(), 'utf-8');
+      } catch (error) {
+        if (error.code !== 'ENOENT') log.warn(`Could not read command history: ${error.message}`);
+      }
+      const loaded = [];
+      for (const line of text.split('\n')) {
+        if (!line.trim()) continue;
+        try {
+          loaded.push(JSON.parse(line));
+        } catch {
+          // A torn final line from a crash mid-append
+        }
+      }
+      lineCount = loaded.length;
+      entries = loaded.slice(-getSettings().maxEntries);
+      return entries;
+    })();
+  }
+  return loading;
+}
+
+// Serialize file writes so an append never interleaves with a rewrite
+function queueWrite(task) {
+  writeQueue = writeQueue.then(task).catch((error) => log.warn(`Could not save command history: ${error.message}`));
+  return writeQueue;
+}
+
+function rewriteFile() {
+  const snapshot = entries.slice();
+  return queueWrite(async () => {
+    const file = historyFile();
+    const temp = `${file}.tmp`;
+    await fs.writeFile(temp, snapshot.map((entry) => JSON.stringify(entry)).join('\n') + (snapshot.length ? '\n' : ''), 'utf-8');
+    await fs.rename(temp, file);
+    lineCount = snapshot.length;
+  });
+}
+
+function shouldRecord(command) {
+  const settings = getSettings();
+  if (!settings.enabled || !command.trim()) return false;
+  if (settings.ignoreLeadingSpace && /^\s/.test(command)) return false;
+  if (settings.excludeSecrets && SECRET_PATTERNS.some((pattern) => pattern.test(command))) return false;
+  return true;
+}
+
+async function recordCommand({ command, cwd, exitCode, startedAt, terminalId, shell }) {
+  if (typeof command !== 'string' || !shouldRecord(command)) return null;
+  await loadEntries();
+  const finishedAt = new Date();
+  const entry = {
+    id: uuidv4(),
+    command: command.trim().slice(0, MAX_COMMAND_LENGTH),
+    cwd: cwd || null,
+    exitCode: typeof exitCode === 'number' ? exitCode : null,
+    durationMs: startedAt ? Math.max(0, finishedAt - new Date(startedAt)) : null,
+    terminalId,
+    shell: shell || null,
+    startedAt: startedAt || null,
+    finishedAt: finishedAt.toISOString()
+  };
+  entries.push(entry);
+  const { maxEntries } = getSettings();
+  if (entries.length > maxEntries) entries.splice(0, entries.length - maxEntries);
+
+  // Let the file grow a quarter past the limit before compacting it
+  if (lineCount + 1 > maxEntries * 1.25) {
+    rewriteFile();
+  } else {
+    lineCount++;
+    queueWrite(() => fs.appendFile(historyFile(), JSON.stringify(entry) + '\n', 'utf-8'));
+  }
+  emit('commandHistory:added', entry);
+  return entry;
+}
+
+// Commands matching `query` (fuzzy) and the filters, one result per distinct command
+// with how often and how recently it ran. `rank` is 'frecency' (default), 'frequency',
+// or 'recent'.
+async function searchCommandHistory(query = '', filters = {}) {
+  const all = await loadEntries();
+  const since = filters.since ? new Date(filters.since).getTime() : null;
+  const now = Date.now();
+
+  const groups = new Map();
+  for (const entry of all) {
+    if (filters.cwd && entry.cwd !== filters.cwd) continue;
+    if (filters.terminalId && entry.terminalId !== filters.terminalId) continue;
+    if (filters.successOnly && entry.exitCode !== 0) continue;
+    if (filters.failedOnly && (entry.exitCode === 0 || entry.exitCode === null)) continue;
+    if (since && new Date(entry.finishedAt).getTime() < since) continue;
+
+    let group = groups.get(entry.command);
+    if (!group) {
+      const match = fuzzyScore(query, entry.command);
+      if (!match) continue;
+      group = { command: entry.command, count: 0, frecency: 0, match, last: null };
+      groups.set(entry.command, group);
+    }
+    group.count++;
+    group.frecency += Math.pow(0.5, (now - new Date(entry.finishedAt).getTime()) / HALF_LIFE_MS);
+    group.last = entry;
+  }
+
+  const rank = filters.rank || 'frecency';
+  const rankScore = (group) => {
+    if (rank === 'recent') return new Date(group.last.finishedAt).getTime();
+    return rank === 'frequency' ? group.count : group.frecency;
+  };
+  return Array.from(groups.values())
+    // Closer text matches first, then the chosen ranking
+    .sort((a, b) => (query ? b.match.score - a.match.score : 0) || rankScore(b) - rankScore(a))
+    .slice(0, filters.limit || DEFAULT_SEARCH_LIMIT)
+    .map((group) => ({
+      command: group.command,
+      count: group.count,
+      lastUsedAt: group.last.finishedAt,
+      lastExitCode: group.last.exitCode,
+      lastCwd: group.last.cwd,
+      lastDurationMs: group.last.durationMs,
+      positions: group.match.positions
+    }));
+}
+
+async function deleteCommand(command) {
+  await loadEntries();
+  const before = entries.length;
+  entries = entries.filter((entry) => entry.command !== command);
+  if (entries.length === before) return false;
+  await rewriteFile();
+  return true;
+}
+
+async function clearCommandHistory() {
+  await loadEntries();
+  entries = [];
+  await rewriteFile();
+  log.info('Command history cleared');
+  return true;
+}
+
+function updateSettings(changes = {}) {
+  const settings = { ...getSettings(), ...changes };
+  settings.maxEntries = Math.max(100, Math.min(100000, Number(settings.maxEntries) || 10000));
+  store.set('settings', settings);
+  return settings;
+}
+
+// Shell integration reports each command's line (633;E) and exit (133;D)
+// The untrimmed line, so ignoreLeadingSpace can see the space
+terminalEvents.on('commandFinished', (terminalId, { exitCode, commandLine, cwd, startedAt }) => {
+  if (!commandLine) return;
+  const terminal = getTerminal(terminalId);
+  recordCommand({ command: commandLine, cwd, exitCode, startedAt, terminalId, shell: terminal ? terminal.shell : null });
+});
+
+function setupCommandHistoryHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('commandHistory:search', async (event, query, filters) => searchCommandHistory(query, filters));
+
+  ipcMain.handle('commandHistory:delete', async (event, command) => deleteCommand(command));
+
+  ipcMain.handle('commandHistory:clear', async () => clearCommandHistory());
+
+  ipcMain.handle('commandHistory:getSettings', async () => getSettings());
+
+  ipcMain.handle('commandHistory:updateSettings', async (event, changes) => updateSettings(changes));
+}
+
+module.exports = { setupCommandHistoryHandlers, recordCommand, searchCommandHistory };
@@ -0,0 +1,173 @@
+const { BrowserWindow } = require('electron');
+const { EventEmitter } = require('events');
+const { recordEvent } = require('./telemetry');
+
+// Emits 'executed' (id, args) for every command run, so features like macro recording can observe them
+const commandEvents = new EventEmitter();
+
+// Registered commands keyed by id
+const commands = new Map();
+
+// Global context keys used to evaluate `when` clauses (e.g. terminalFocus)
+const contextKeys = new Map();
+
+// Evaluate a `when` clause against the current context.
+// Supports functions and simple expressions like "terminalFocus && !editorReadOnly".
+function isEnabled(command, context = {}) {
+  if (!command.when) return true;
+
+  const lookup = (key) => (key in context ? context[key] : contextKeys.get(key));
+
+  if (typeof command.when === 'function') {
+    try {
+      return Boolean(command.when(lookup));
+    } catch {
+      return false;
+    }
+  }
+
+  return command.when.split('||').some((clause) =>
+    clause.split('&&').every((term) => {
+      const trimmed = term.trim();
+      if (!trimmed) return true;
+      if (trimmed.startsWith('!')) return !lookup(trimmed.slice(1).trim());
+      return Boolean(lookup(trimmed));
+    })
+  );
+}
+
+// Register a command. Commands without a handler are dispatched to the
+// renderer that requested execution, so UI-only actions still live in the registry.
+// `palette: false` keeps commands that need arguments (a terminal id, a file path)
+// out of the command palette, which runs them with none.
+function registerCommand(command) {
+  if (!command || !command.id || !command.title) {
+    throw new Error('Command requires an id and a title');
+  }
+  if (commands.has(command.id)) {
+    throw new Error(`Command ${command.id} is already registered`);
+  }
+
+  commands.set(command.id, {
+    id: command.id,
+    title: command.title,
+    category: command.category || 'general',
+    keybinding: command.keybinding,
+    when: command.when,
+    source: command.source || 'core',
+    palette: command.palette !== false,
+    handler: command.handler
+  });
+
+  return () => unregisterCommand(command.id);
+}
+
+function unregisterCommand(id) {
+  return commands.delete(id);
+}
+
+function setContext(key, value) {
+  if (value === undefined || value === null) {
+    contextKeys.delete(key);
+  } else {
+    contextKeys.set(key, value);
+  }
+}
+
+function listCommands(context = {}) {
+  return Array.from(commands.values())
+    .map((command) => ({
+      id: command.id,
+      title: command.title,
+      category: command.category,
+      keybinding: command.keybinding,
+      source: command.source,
+      palette: command.palette,
+      enabled: isEnabled(command, context),
+      handledBy: command.handler ? 'backend' : 'renderer'
+    }))
+    .sort((a, b) => a.category.localeCompare(b.category) || a.title.localeCompare(b.title));
+}
+
+async function executeCommand(id, args = [], options = {}) {
+  const command = commands.get(id);
+  if (!command) {
+    throw new Error(`Command ${id} not found`);
+  }
+  if (!isEnabled(command, options.context)) {
+    throw new Error(`Command ${id} is not enabled in the current context`);
+  }
+  commandEvents.emit('executed', id, Array.isArray(args) ? args : [args]);
+
+  if (!command.handler) {
+    const sender = options.sender;
+    if (!sender || sender.isDestroyed()) {
+      throw new Error(`Command ${id} must be executed from a window`);
+    }
+    sender.send('commands:invoke', { id, args });
+    return null;
+  }
+
+  return command.handler(...(Array.isArray(args) ? args : [args]), {
+    sender: options.sender,
+    window: options.sender ? BrowserWindow.fromWebContents(options.sender) : null
+  });
+}
+
+// Built-in workbench commands. Their actions live in the renderer.
+function registerBuiltinCommands() {
+  const workbench = [
+    { id: 'workbench.openBrowser', title: 'Open Browser', category: 'navigation', keybinding: 'CmdOrCtrl+B' },
+    { id: 'workbench.newFile', title: 'New Code File', category: 'navigation', keybinding: 'CmdOrCtrl+N' },
+    { id: 'workbench.openTerminal', title: 'Open Terminal', category: 'navigation', keybinding: 'CmdOrCtrl+T' },
+    { id: 'workbench.openExplorer', title: 'File Explorer', category: 'navigation', keybinding: 'CmdOrCtrl+E' },
+    { id: 'ai.generateImage', title: 'Generate Image', category: 'ai' },
+    { id: 'ai.generateAudio', title: 'Generate Audio', category: 'ai' },
+    { id: 'ai.generateVideo', title: 'Generate Video', category: 'ai' },
+    { id: 'ai.openAssistant', title: 'AI Assistant', category: 'ai', keybinding: 'CmdOrCtrl+/' },
+    { id: 'workbench.changeTheme', title: 'Change Theme', category: 'settings' },
+    { id: 'workbench.openSettings', title: 'Settings', category: 'settings', keybinding: 'CmdOrCtrl+,' }
+  ];
+  workbench.forEach(registerCommand);
+
+  registerCommand({
+    id: 'window.toggleAlwaysOnTop',
+    title: 'Toggle Always on Top',
+    category: 'settings',
+    handler: (...args) => {
+      const context = args.pop();
+      const win = context.window;
+      if (!win || win.isDestroyed()) return false;
+      win.setAlwaysOnTop(!win.isAlwaysOnTop());
+      return win.isAlwaysOnTop();
+    }
+  });
+}
+
+function setupCommandHandlers(ipcMain) {
+  registerBuiltinCommands();
+
+  ipcMain.handle('commands:list', async (event, context) => {
+    return listCommands(context);
+  });
+
+  ipcMain.handle('commands:execute', async (event, id, args, context) => {
+    recordEvent('command.executed', { id });
+    return executeCommand(id, args, { sender: event.sender, context });
+  });
+
+  ipcMain.handle('commands:setContext', async (event, key, value) => {
+    setContext(key, value);
+    return true;
+  });
+}
+
+module.exports = {
+  setupCommandHandlers,
+  registerCommand,
+  unregisterCommand,
+  listCommands,
+  executeCommand,
+  setContext,
+  commandEvents
+};
@@ -0,0 +1,407 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { clipboard } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob } = require('./jobs');
+const { registerCommand } = require('./commands');
+const { runGit } = require('./git');
+const { imageFormat, decodeImage, compareImages } = require('./imageDiff');
+
+const log = createLogger('compare');
+
+const MAX_COMPARE_SIZE = 20 * 1024 * 1024;
+// Past this many changed lines the diff is reported as one block rather than searched further
+const MAX_EDIT_DISTANCE = 4000;
+const CONTEXT_LINES = 3;
+const MAX_SESSIONS = 50;
+const MAX_DIRECTORY_FILES = 50000;
+const SKIPPED_DIRECTORIES = new Set(['.git', 'node_modules']);
+const MAX_BYTE_RANGES = 1000;
+const BYTE_RANGE_GAP = 8;
+
+// Open diff views by id, oldest first
+const sessions = new Map();
+
+// Edit script turning `a` into `b` as one op per line: '=' kept, '-' removed, '+' added.
+// Myers' algorithm, keeping only the diagonals each step can reach so memory is O(D²).
+function diffSequences(a, b) {
+  let prefix = 0;
+  while (prefix < a.length && prefix < b.length && a[prefix] === b[prefix]) prefix++;
+  let suffix = 0;
+  while (suffix < a.length - prefix && suffix < b.length - prefix && a[a.length - 1 - suffix] === b[b.length - 1 - suffix]) suffix++;
+  const oldMiddle = a.slice(prefix, a.length - suffix);
+  const newMiddle = b.slice(prefix, b.length - suffix);
+  const middle = myers(oldMiddle, newMiddle) ||
+    [...Array(oldMiddle.length).fill('-'), ...Array(newMiddle.length).fill('+')];
+  return [...Array(prefix).fill('='), ...middle, ...Array(suffix).fill('=')];
+}
+
+function myers(a, b) {
+  const n = a.length;
+  const m = b.length;
+  const max = n + m;
+  const offset = max + 1;
+  const v = new Int32Array(2 * max + 3);
+  // trace[d] holds the furthest x on diagonals -d..d before step d
+  const trace = [];
+  for (let d = 0; d <= Math.min(max, MAX_EDIT_DISTANCE); d++) {
+    trace.push(v.slice(offset - d, offset + d + 1));
+    for (let k = -d; k <= d; k += 2) {
+      let x = k === -d || (k !== d && v[offset + k - 1] < v[offset + k + 1]) ? v[offset + k + 1] : v[offset + k - 1] + 1;
+      let y = x - k;
+      while (x < n && y < m && a[x] === b[y]) {
+        x++;
+        y++;
+      }
+      v[offset + k] = x;
+      if (x >= n && y >= m) return backtrack(trace, n, m);
+    }
+  }
+  return null;
+}
+
+function backtrack(trace, n, m) {
+  const ops = [];
+  let x = n;
+  let y = m;
+  for (let d = trace.length - 1; d > 0; d--) {
+    const v = trace[d];
+    const k = x - y;
+    const previousK = k === -d || (k !== d && v[k - 1 + d] < v[k + 1 + d]) ? k + 1 : k - 1;
+    const previousX = v[previousK + d];
+    const previousY = previousX - previousK;
+    while (x > previousX && y > previousY) {
+      ops.push('=');
+      x--;
+      y--;
+    }
+    ops.push(x === previousX ? '+' : '-');
+    x = previousX;
+    y = previousY;
+  }
+  while (x > 0 && y > 0) {
+    ops.push('=');
+    x--;
+    y--;
+  }
+  return ops.reverse();
+}
+
+// Unified-diff hunks ({ oldStart, oldLines, newStart, newLines, lines }, 1-based) with
+// a few lines of context; each line is prefixed ' ', '-' or '+'
+function diffLines(oldText, newText) {
+  const oldLines = oldText.split('\n');
+  const newLines = newText.split('\n');
+  const ops = diffSequences(oldLines, newLines);
+
+  // Old and new line index before each op
+  const positions = [];
+  let i = 0;
+  let j = 0;
+  for (const op of ops) {
+    positions.push([i, j]);
+    if (op !== '+') i++;
+    if (op !== '-') j++;
+  }
+  positions.push([i, j]);
+
+  const changes = [];
+  ops.forEach((op, index) => {
+    if (op !== '=') changes.push(index);
+  });
+  const hunks = [];
+  let added = 0;
+  let removed = 0;
+  for (let start = 0; start < changes.length;) {
+    let end = start;
+    while (end + 1 < changes.length && changes[end + 1] - changes[end] <= CONTEXT_LINES * 2 + 1) end++;
+    const from = Math.max(0, changes[start] - CONTEXT_LINES);
+    const to = Math.min(ops.length, changes[end] + CONTEXT_LINES + 1);
+    const lines = [];
+    for (let index = from; index < to; index++) {
+      const [oldIndex, newIndex] = positions[index];
+      if (ops[index] === '+') {
+        lines.push(`+${newLines[newIndex]}`);
+        added++;
+      } else {
+        lines.push(`${ops[index] === '-' ? '-' : ' '}${oldLines[oldIndex]}`);
+        if (ops[index] === '-') removed++;
+      }
+    }
+    hunks.push({
+      oldStart: positions[from][0] + 1,
+      oldLines: positions[to][0] - positions[from][0],
+      newStart: positions[from][1] + 1,
+      newLines: positions[to][1] - positions[from][1],
+      lines
+    });
+    start = end + 1;
+  }
+  return { hunks, added, removed };
+}
+
+async function readForCompare(filePath) {
+  const stats = await fs.stat(filePath).catch((error) => {
+    throw ForgeError.from(error, `Cannot compare ${filePath}`);
+  });
+  if (stats.isDirectory()) return { stats, buffer: null };
+  if (stats.size > MAX_COMPARE_SIZE) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} is too large to compare`);
+  }
+  return { stats, buffer: await fs.readFile(filePath) };
+}
+
+const isBinary = (buffer) => buffer.subarray(0, 8192).includes(0) || imageFormat(buffer) !== null;
+
+// Where two buffers differ, as [start, end) byte ranges. Differences closer than a few
+// bytes are merged; bytes past the shorter buffer's end count as one range.
+function byteRanges(left, right) {
+  const ranges = [];
+  const shorter = Math.min(left.length, right.length);
+  let differentBytes = Math.abs(left.length - right.length);
+  let truncated = false;
+  for (let i = 0; i < shorter; i++) {
+    if (left[i] === right[i]) continue;
+    differentBytes++;
+    const last = ranges[ranges.length - 1];
+    if (last && i - last.end <= BYTE_RANGE_GAP) {
+      last.end = i + 1;
+    } else if (ranges.length < MAX_BYTE_RANGES) {
+      ranges.push({ start: i, end: i + 1 });
+    } else {
+      truncated = true;
+    }
+  }
+  if (left.length !== right.length) {
+    if (ranges.length < MAX_BYTE_RANGES) ranges.push({ start: shorter, end: Math.max(left.length, right.length) });
+    else truncated = true;
+  }
+  return { leftSize: left.length, rightSize: right.length, differentBytes, ranges, truncated };
+}
+
+// Two versions of an image, compared pixel by pixel; null unless both decode
+function imageComparison(leftBuffer, rightBuffer, threshold) {
+  const left = decodeImage(leftBuffer);
+  const right = left && decodeImage(rightBuffer);
+  if (!left || !right) return null;
+  const { width, height, differentPixels, ratio, diff } = compareImages(left, right, { threshold });
+  return {
+    left: { format: left.format, width: left.width, height: left.height },
+    right: { format: right.format, width: right.width, height: right.height },
+    width,
+    height,
+    differentPixels,
+    ratio,
+    diff: `data:image/png;base64,${diff.toString('base64')}`
+  };
+}
+
+// Text is diffed by line. Binary files report the byte ranges that differ and, for
+// images, a perceptual pixel diff.
+function compareBuffers(leftBuffer, rightBuffer, { threshold } = {}) {
+  if (isBinary(leftBuffer) || isBinary(rightBuffer)) {
+    const identical = leftBuffer.equals(rightBuffer);
+    return {
+      binary: true,
+      identical,
+      hunks: [],
+      added: 0,
+      removed: 0,
+      bytes: byteRanges(leftBuffer, rightBuffer),
+      image: identical ? null : imageComparison(leftBuffer, rightBuffer, threshold)
+    };
+  }
+  const { hunks, added, removed } = diffLines(leftBuffer.toString('utf-8'), rightBuffer.toString('utf-8'));
+  return { binary: false, identical: hunks.length === 0, hunks, added, removed };
+}
+
+function addSession(session, { sender, open = true } = {}) {
+  sessions.set(session.id, session);
+  if (sessions.size > MAX_SESSIONS) sessions.delete(sessions.keys().next().value);
+  if (open && sender) {
+    emit('workbench:openDiff', {
+      left: session.left.path,
+      right: session.right.path || session.right.label,
+      sessionId: session.id,
+      source: 'compare'
+    }, { target: sender });
+  }
+  return session;
+}
+
+// Every file under `root` by POSIX relative path, without .git and node_modules
+async function listFiles(root, signal) {
+  const files = new Map();
+  const pending = [root];
+  while (pending.length > 0 && !signal.aborted) {
+    const dir = pending.pop();
+    const entries = await fs.readdir(dir, { withFileTypes: true }).catch(() => []);
+    for (const entry of entries) {
+      const entryPath = path.join(dir, entry.name);
+      if (entry.isDirectory()) {
+        if (!SKIPPED_DIRECTORIES.has(entry.name)) pending.push(entryPath);
+      } else if (entry.isFile()) {
+        files.set(path.relative(root, entryPath).split(path.sep).join('/'), entryPath);
+        if (files.size > MAX_DIRECTORY_FILES) {
+          throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${root} has more than ${MAX_DIRECTORY_FILES} files to compare`);
+        }
+      }
+    }
+  }
+  return files;
+}
+
+async function sameContent(leftPath, rightPath) {
+  const [leftStats, rightStats] = await Promise.all([fs.stat(leftPath), fs.stat(rightPath)]);
+  if (leftStats.size !== rightStats.size) return false;
+  const [leftBuffer, rightBuffer] = await Promise.all([fs.readFile(leftPath), fs.readFile(rightPath)]);
+  return leftBuffer.equals(rightBuffer);
+}
+
+// Pair files by relative path. `added` are only on the right, `removed` only on the left.
+// Runs as a cancellable job.
+function compareDirectories(left, right) {
+  const job = runJob('compare', `Compare ${path.basename(left)} with ${path.basename(right)}`, async (handle) => {
+    handle.reportProgress(null, 'Listing files');
+    const [leftFiles, rightFiles] = await Promise.all([listFiles(left, handle.signal), listFiles(right, handle.signal)]);
+    handle.throwIfCancelled();
+    const added = [];
+    const removed = [];
+    const modified = [];
+    let unchanged = 0;
+    let checked = 0;
+    for (const [relative, leftPath] of leftFiles) {
+      handle.throwIfCancelled();
+      const rightPath = rightFiles.get(relative);
+      if (!rightPath) removed.push(relative);
+      else if (await sameContent(leftPath, rightPath).catch(() => false)) unchanged++;
+      else modified.push(relative);
+      if (++checked % 200 === 0) handle.reportProgress(checked / leftFiles.size, `${checked} of ${leftFiles.size} files`);
+    }
+    for (const relative of rightFiles.keys()) {
+      if (!leftFiles.has(relative)) added.push(relative);
+    }
+    const sort = (list) => list.sort((a, b) => a.localeCompare(b));
+    return { added: sort(added), removed: sort(removed), modified: sort(modified), unchanged };
+  });
+  return job.promise;
+}
+
+// Compare two files, or two folders. The session is kept so the diff view can fetch it,
+// and opened in the calling window unless `open` is false.
+async function compareFiles(pathA, pathB, options = {}) {
+  if (typeof pathA !== 'string' || typeof pathB !== 'string' || !pathA || !pathB) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Two paths are required');
+  }
+  const left = path.resolve(pathA);
+  const right = path.resolve(pathB);
+  const [a, b] = await Promise.all([readForCompare(left), readForCompare(right)]);
+  const leftIsDirectory = a.stats.isDirectory();
+  if (leftIsDirectory !== b.stats.isDirectory()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Compare a file with a file, or a folder with a folder');
+  }
+  const base = { id: uuidv4(), left: { path: left }, right: { path: right }, createdAt: new Date().toISOString() };
+  if (leftIsDirectory) {
+    const result = await compareDirectories(left, right);
+    log.info(`Compared ${left} with ${right}: ${result.added.length} added, ${result.removed.length} removed, ${result.modified.length} modified`);
+    return addSession({ ...base, kind: 'directories', ...result }, options);
+  }
+  return addSession({ ...base, kind: 'files', ...compareBuffers(a.buffer, b.buffer, options) }, options);
+}
+
+// Compare a file on disk (left) with text that isn't saved anywhere (right), such as the
+// clipboard or an editor buffer
+async function compareWithContent(filePath, text, options = {}) {
+  if (typeof filePath !== 'string' || !filePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A file path is required');
+  if (typeof text !== 'string') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Text to compare with is required');
+  const left = path.resolve(filePath);
+  const { stats, buffer } = await readForCompare(left);
+  if (stats.isDirectory()) throw new ForgeError(ErrorCode.IS_DIRECTORY, `${left} is a folder`);
+  return addSession({
+    id: uuidv4(),
+    kind: 'content',
+    left: { path: left },
+    right: { label: options.label || 'Untitled', content: text },
+    createdAt: new Date().toISOString(),
+    ...compareBuffers(buffer, Buffer.from(text, 'utf-8'), options)
+  }, options);
+}
+
+// Compare a file with its version at a git revision (left), e.g. to review a changed asset
+async function compareWithRevision(filePath, ref = 'HEAD', options = {}) {
+  if (typeof filePath !== 'string' || !filePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A file path is required');
+  if (typeof ref !== 'string' || !ref || ref.startsWith('-')) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid revision: ${ref}`);
+  const right = path.resolve(filePath);
+  const { stats, buffer } = await readForCompare(right);
+  if (stats.isDirectory()) throw new ForgeError(ErrorCode.IS_DIRECTORY, `${right} is a folder`);
+  const previous = await runGit(path.dirname(right), ['show', `${ref}:./${path.basename(right)}`], { encoding: 'buffer' }).catch((error) => {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `${path.basename(right)} is not in ${ref}`, { cause: error });
+  });
+  return addSession({
+    id: uuidv4(),
+    kind: 'revision',
+    left: { path: right, revision: ref },
+    right: { path: right },
+    createdAt: new Date().toISOString(),
+    ...compareBuffers(previous, buffer, options)
+  }, options);
+}
+
+function getSession(id) {
+  const session = sessions.get(id);
+  if (!session) throw new ForgeError(ErrorCode.NOT_FOUND, `Diff session ${id} not found`, { details: { id } });
+  return session;
+}
+
+function setupCompareHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('compare:files', async (event, pathA, pathB, options = {}) => {
+    return compareFiles(pathA, pathB, { ...options, sender: event.sender });
+  });
+
+  ipcMain.handle('compare:withContent', async (event, filePath, text, options = {}) => {
+    return compareWithContent(filePath, text, { ...options, sender: event.sender });
+  });
+
+  ipcMain.handle('compare:withClipboard', async (event, filePath, options = {}) => {
+    return compareWithContent(filePath, clipboard.readText(), { label: 'Clipboard', ...options, sender: event.sender });
+  });
+
+  ipcMain.handle('compare:withRevision', async (event, filePath, ref, options = {}) => {
+    return compareWithRevision(filePath, ref, { ...options, sender: event.sender });
+  });
+
+  ipcMain.handle('compare:getSession', async (event, id) => getSession(id));
+
+  ipcMain.handle('compare:closeSession', async (event, id) => sessions.delete(id));
+
+  registerCommand({
+    id: 'workspace.compareFiles',
+    title: 'Compare Files',
+    category: 'workspace',
+    palette: false,
+    handler: (...args) => {
+      const context = args.pop();
+      const [pathA, pathB] = args;
+      return compareFiles(pathA, pathB, { sender: context.sender });
+    }
+  });
+
+  registerCommand({
+    id: 'editor.compareWithClipboard',
+    title: 'Compare with Clipboard',
+    category: 'editor',
+    palette: false,
+    handler: (...args) => {
+      const context = args.pop();
+      const [filePath] = args;
+      return compareWithContent(filePath, clipboard.readText(), { label: 'Clipboard', sender: context.sender });
+    }
+  });
+}
+
+module.exports = { setupCompareHandlers, compareFiles, compareWithContent, compareWithRevision, diffLines };
@@ -0,0 +1,297 @@
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { aiManager } = require('./ai');
+const { readTextFile } = require('./search');
+const { lookupSymbols } = require('./symbolIndex');
+
+const log = createLogger('completion');
+
+// Rough token estimate; good enough for budgeting prompt sections
+const CHARS_PER_TOKEN = 4;
+const CACHE_LIMIT = 200;
+const NEIGHBOR_FILE_LIMIT = 3;
+const SYMBOL_LIMIT = 12;
+
+// Share of the context budget given to each prompt section
+const BUDGET_SHARES = { prefix: 0.6, suffix: 0.15, neighbors: 0.15, symbols: 0.1 };
+
+const store = new JsonStore('completion', {
+  settings: {
+    enabled: true,
+    providerId: null,
+    model: null,
+    debounceMs: 150,
+    maxTokens: 128,
+    contextTokens: 2048
+  }
+});
+
+// key -> { prefix, insertions } in insertion order for LRU eviction
+const cache = new Map();
+// document key -> { timer, controller, resolve } for the request waiting on its debounce
+const pending = new Map();
+const stats = { requests: 0, cacheHits: 0, debounced: 0, failures: 0, totalLatencyMs: 0, completed: 0 };
+
+function getSettings() {
+  return store.get('settings');
+}
+
+function updateSettings(changes) {
+  const settings = { ...getSettings(), ...changes };
+  store.set('settings', settings);
+  return settings;
+}
+
+function hash(...parts) {
+  return crypto.createHash('sha1').update(parts.join('\u0000')).digest('hex');
+}
+
+// Identifiers used near the cursor, most recent first
+function nearbyIdentifiers(prefix) {
+  const tail = prefix.slice(-1500);
+  const seen = new Set();
+  const identifiers = tail.match(/[A-Za-z_$][\w$]{2,}/g) || [];
+  for (let i = identifiers.length - 1; i >= 0 && seen.size < SYMBOL_LIMIT * 2; i--) {
+    seen.add(identifiers[i]);
+  }
+  return Array.from(seen);
+}
+
+async function neighborSnippets(filePath, neighborPaths, budgetChars) {
+  const candidates = [...(neighborPaths || [])];
+  // Fall back to siblings with the same extension
+  if (candidates.length < NEIGHBOR_FILE_LIMIT) {
+    try {
+      const ext = path.extname(filePath);
+      const siblings = (await fs.readdir(path.dirname(filePath)))
+        .filter((name) => path.extname(name) === ext && name !== path.basename(filePath))
+        .slice(0, NEIGHBOR_FILE_LIMIT)
+        .map((name) => path.join(path.dirname(filePath), name));
+      candidates.push(...siblings);
+    } catch {
+      // Unsaved or virtual document
+    }
+  }
+
+  const snippets = [];
+  let remaining = budgetChars;
+  const perFile = Math.floor(budgetChars / NEIGHBOR_FILE_LIMIT);
+  for (const candidate of Array.from(new Set(candidates)).slice(0, NEIGHBOR_FILE_LIMIT)) {
+    if (remaining <= 0) break;
+    const text = await readTextFile(candidate);
+    if (!text) continue;
+    const snippet = text.slice(0, Math.min(perFile, remaining));
+    snippets.push(`// File: ${candidate}\n${snippet}`);
+    remaining -= snippet.length;
+  }
+  return snippets.join('\n\n');
+}
+
+async function symbolSignatures(rootPath, prefix, budgetChars) {
+  if (!rootPath) return '';
+  const symbols = lookupSymbols(rootPath, nearbyIdentifiers(prefix)).slice(0, SYMBOL_LIMIT);
+  const lines = [];
+  let remaining = budgetChars;
+  const fileCache = new Map();
+  for (const symbol of symbols) {
+    if (!fileCache.has(symbol.path)) fileCache.set(symbol.path, ((await readTextFile(symbol.path)) || '').split('\n'));
+    const line = (fileCache.get(symbol.path)[symbol.line - 1] || '').trim();
+    const entry = `// ${path.basename(symbol.path)}:${symbol.line}: ${line}`;
+    if (entry.length > remaining) break;
+    lines.push(entry);
+    remaining -= entry.length;
+  }
+  return lines.join('\n');
+}
+
+// Prefix and suffix around the cursor plus neighboring files and referenced symbols,
+// each trimmed to its share of the token budget
+async function buildPrompt(request, settings) {
+  const budgetChars = settings.contextTokens * CHARS_PER_TOKEN;
+  const prefixFull = request.content.slice(0, request.offset);
+  const suffixFull = request.content.slice(request.offset);
+  const prefix = prefixFull.slice(-Math.floor(budgetChars * BUDGET_SHARES.prefix));
+  const suffix = suffixFull.slice(0, Math.floor(budgetChars * BUDGET_SHARES.suffix));
+
+  const [neighbors, symbols] = await Promise.all([
+    neighborSnippets(request.path, request.neighborPaths, Math.floor(budgetChars * BUDGET_SHARES.neighbors)),
+    symbolSignatures(request.rootPath, prefixFull, Math.floor(budgetChars * BUDGET_SHARES.symbols))
+  ]);
+
+  const language = request.languageId || path.extname(request.path).slice(1) || 'text';
+  const sections = [];
+  if (symbols) sections.push(`Relevant definitions:\n${symbols}`);
+  if (neighbors) sections.push(`Related files:\n${neighbors}`);
+  sections.push(`Current file (${request.path}, ${language}):\n${prefix}<CURSOR>${suffix}`);
+
+  return {
+    prefix,
+    suffix,
+    messages: [
+      {
+        role: 'system',
+        content: 'You are a code completion engine. Reply with only the text to insert at <CURSOR>: ' +
+          'no explanations, no code fences, and do not repeat text that already follows the cursor.'
+      },
+      { role: 'user', content: sections.join('\n\n') }
+    ]
+  };
+}
+
+function stripFences(text) {
+  const fenced = /^```[\w-]*\n([\s\S]*?)\n?```\s*$/.exec(text.trim());
+  return fenced ? fenced[1] : text;
+}
+
+// Turn the raw reply into ranked insertions: the full suggestion and its first line.
+// Suggestions that just repeat the text after the cursor are dropped.
+function rankInsertions(raw, suffix) {
+  const text = stripFences(raw).replace(/\s+$/, '');
+  if (!text.trim()) return [];
+  const candidates = [{ text, kind: 'full' }];
+  const firstLine = text.split('\n')[0];
+  if (firstLine !== text && firstLine.trim()) candidates.push({ text: firstLine, kind: 'line' });
+
+  const nextSuffix = suffix.trimStart().slice(0, 80);
+  return candidates
+    .filter((candidate) => !nextSuffix || !nextSuffix.startsWith(candidate.text.trim()))
+    .map((candidate) => ({
+      ...candidate,
+      // Multi-line suggestions carry more risk; prefer them only when they're substantial
+      score: candidate.kind === 'full' ? Math.min(1, 0.5 + candidate.text.length / 400) : 0.6
+    }))
+    .sort((a, b) => b.score - a.score);
+}
+
+function remember(key, prefix, insertions) {
+  cache.delete(key);
+  cache.set(key, { prefix, insertions });
+  if (cache.size > CACHE_LIMIT) cache.delete(cache.keys().next().value);
+}
+
+// A cached completion still applies when the user has since typed its first characters
+function fromCache(documentKey, prefix) {
+  for (const [key, entry] of Array.from(cache.entries()).reverse()) {
+    if (!key.startsWith(`${documentKey}:`) || !prefix.startsWith(entry.prefix)) continue;
+    const typed = prefix.slice(entry.prefix.length);
+    if (typed.length > 200) continue;
+    const insertions = entry.insertions
+      .filter((insertion) => insertion.text.startsWith(typed) && insertion.text.length > typed.length)
+      .map((insertion) => ({ ...insertion, text: insertion.text.slice(typed.length) }));
+    if (insertions.length > 0) return insertions;
+  }
+  return null;
+}
+
+async function complete(request, settings, signal) {
+  const startedAt = Date.now();
+  const prompt = await buildPrompt(request, settings);
+  const result = await aiManager.chat({
+    messages: prompt.messages,
+    providerId: settings.providerId || undefined,
+    model: settings.model || undefined,
+    temperature: 0.1,
+    maxTokens: settings.maxTokens,
+    timeout: 15000
+  }, { signal });
+  const latencyMs = Date.now() - startedAt;
+  stats.completed++;
+  stats.totalLatencyMs += latencyMs;
+  return { insertions: rankInsertions(result.content, prompt.suffix), latencyMs };
+}
+
+// Request a completion for `request` ({ path, content, offset, languageId?, rootPath?, neighborPaths? }).
+// Requests for the same document are debounced: a newer one supersedes the waiting or
+// in-flight one, which resolves with { cancelled: true }.
+function requestCompletion(request, ownerId) {
+  const settings = getSettings();
+  if (!settings.enabled) return Promise.resolve({ insertions: [], disabled: true });
+  if (!request || typeof request.content !== 'string' || typeof request.offset !== 'number' || !request.path) {
+    return Promise.reject(new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A completion request needs path, content, and offset'));
+  }
+  stats.requests++;
+
+  const documentKey = `${ownerId}:${request.path}`;
+  const prefix = request.content.slice(0, request.offset);
+  const cached = fromCache(documentKey, prefix);
+  if (cached) {
+    stats.cacheHits++;
+    return Promise.resolve({ insertions: cached, cached: true, latencyMs: 0 });
+  }
+
+  cancelCompletion(documentKey);
+  return new Promise((resolve, reject) => {
+    const controller = new AbortController();
+    const entry = { controller, resolve, timer: null };
+    pending.set(documentKey, entry);
+
+    entry.timer = setTimeout(() => {
+      entry.timer = null;
+      complete(request, settings, controller.signal)
+        .then((result) => {
+          const key = `${documentKey}:${hash(prefix.slice(-2000), request.content.slice(request.offset, request.offset + 500))}`;
+          remember(key, prefix, result.insertions);
+          resolve({ ...result, cached: false });
+        })
+        .catch((error) => {
+          if (controller.signal.aborted) {
+            resolve({ insertions: [], cancelled: true });
+            return;
+          }
+          stats.failures++;
+          log.debug(`Completion failed: ${error.message}`);
+          reject(error);
+        })
+        .finally(() => {
+          if (pending.get(documentKey) === entry) pending.delete(documentKey);
+        });
+    }, settings.debounceMs);
+  });
+}
+
+function cancelCompletion(documentKey) {
+  const entry = pending.get(documentKey);
+  if (!entry) return false;
+  pending.delete(documentKey);
+  stats.debounced++;
+  if (entry.timer) {
+    clearTimeout(entry.timer);
+    entry.resolve({ insertions: [], cancelled: true });
+  } else {
+    entry.controller.abort();
+  }
+  return true;
+}
+
+function getStats() {
+  return {
+    ...stats,
+    averageLatencyMs: stats.completed > 0 ? Math.round(stats.totalLatencyMs / stats.completed) : null,
+    cacheSize: cache.size
+  };
+}
+
+function setupCompletionHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('completion:request', async (event, request) => requestCompletion(request, event.sender.id));
+
+  ipcMain.handle('completion:cancel', async (event, filePath) => cancelCompletion(`${event.sender.id}:${filePath}`));
+
+  ipcMain.handle('completion:getSettings', async () => getSettings());
+
+  ipcMain.handle('completion:updateSettings', async (event, changes) => updateSettings(changes));
+
+  ipcMain.handle('completion:getStats', async () => getStats());
+
+  ipcMain.handle('completion:clearCache', async () => {
+    cache.clear();
+    return true;
+  });
+}
+
+module.exports = { setupCompletionHandlers, requestCompletion, cancelCompletion };
@@ -0,0 +1,206 @@
+const fs = require('fs');
+const path = require('path');
+const os = require('os');
+const { app, crashReporter, net } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+
+const log = createLogger('crash');
+
+// Upload is strictly opt-in; nothing leaves the machine unless enabled
+const settings = new JsonStore('crash-reporting', {
+  uploadEnabled: false,
+  endpoint: process.env.FORGE_CRASH_ENDPOINT || null
+});
+
+let isQuitting = false;
+
+function getCrashDir() {
+  const dir = path.join(app.getPath('userData'), 'crash-reports');
+  fs.mkdirSync(dir, { recursive: true });
+  return dir;
+}
+
+function getReportPath(id) {
+  if (!/^[\w-]+$/.test(id)) {
+    throw new Error(`Invalid crash report id: ${id}`);
+  }
+  return path.join(getCrashDir(), `${id}.json`);
+}
+
+// Write a crash report to disk and tell the UI that Forge kept running
+function captureError(error, { source = 'main', fatal = false, context } = {}) {
+  const err = error instanceof Error ? error : new Error(String(error));
+  const report = {
+    id: `crash_${Date.now()}_${uuidv4().slice(0, 8)}`,
+    timestamp: new Date().toISOString(),
+    source,
+    fatal,
+    name: err.name,
+    message: err.message,
+    stack: err.stack,
+    context,
+    app: { version: app.getVersion(), electron: process.versions.electron, node: process.versions.node },
+    os: { platform: process.platform, arch: process.arch, release: os.release() },
+    uploaded: false
+  };
+
+  try {
+    fs.writeFileSync(getReportPath(report.id), JSON.stringify(report, null, 2));
+  } catch (writeError) {
+    log.error('Failed to write crash report:', writeError);
+  }
+
+  log.error(`Captured ${fatal ? 'fatal ' : ''}error from ${source}: ${err.message}`, { reportId: report.id });
+
+  if (!fatal && !isQuitting) {
+    emit('app:recoveredFromError', {
+      reportId: report.id,
+      source,
+      message: err.message
+    });
+  }
+
+  return report;
+}
+
+// Run an async background task and capture (rather than propagate) any failure. With
+// `rethrow`, the failure still rejects for the caller to handle, and only unexpected ones
+// are captured: errors without a code, i.e. bugs rather than ForgeErrors or failed syscalls.
+function guardTask(name, task, { rethrow = false } = {}) {
+  return Promise.resolve()
+    .then(task)
+    .catch((error) => {
+      if (!rethrow) {
+        captureError(error, { source: `task:${name}` });
+        return undefined;
+      }
+      if (!error || !error.code) captureError(error, { source: `task:${name}` });
+      throw error;
+    });
+}
+
+// Install process-level hooks. Called before app is ready so early failures are captured.
+function installCrashHandlers() {
+  crashReporter.start({ uploadToServer: false, compress: true });
+
+  process.on('uncaughtException', (error) => {
+    captureError(error, { source: 'uncaughtException', fatal: isQuitting });
+  });
+
+  process.on('unhandledRejection', (reason) => {
+    captureError(reason, { source: 'unhandledRejection' });
+  });
+
+  app.on('render-process-gone', (event, webContents, details) => {
+    if (details.reason === 'clean-exit') return;
+    captureError(new Error(`Renderer process gone: ${details.reason}`), {
+      source: 'renderer',
+      context: { exitCode: details.exitCode, url: webContents.getURL() }
+    });
+  });
+
+  app.on('child-process-gone', (event, details) => {
+    if (details.reason === 'clean-exit') return;
+    captureError(new Error(`${details.type} process gone: ${details.reason}`), {
+      source: 'child-process',
+      context: { type: details.type, exitCode: details.exitCode, name: details.name }
+    });
+  });
+
+  app.on('before-quit', () => {
+    isQuitting = true;
+  });
+}
+
+function listCrashReports() {
+  return fs.readdirSync(getCrashDir())
+    .filter((file) => file.endsWith('.json'))
+    .map((file) => {
+      try {
+        const report = JSON.parse(fs.readFileSync(path.join(getCrashDir(), file), 'utf-8'));
+        return {
+          id: report.id,
+          timestamp: report.timestamp,
+          source: report.source,
+          message: report.message,
+          fatal: report.fatal,
+          uploaded: report.uploaded
+        };
+      } catch {
+        return null;
+      }
+    })
+    .filter(Boolean)
+    .sort((a, b) => b.timestamp.localeCompare(a.timestamp));
+}
+
+async function uploadCrashReport(id) {
+  if (!settings.get('uploadEnabled')) {
+    throw new Error('Crash report upload is disabled');
+  }
+  const endpoint = settings.get('endpoint');
+  if (!endpoint) {
+    throw new Error('No crash report endpoint configured');
+  }
+
+  const reportPath = getReportPath(id);
+  const report = JSON.parse(fs.readFileSync(reportPath, 'utf-8'));
+
+  const response = await net.fetch(endpoint, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(report)
+  });
+  if (!response.ok) {
+    throw new Error(`Upload failed with status ${response.status}`);
+  }
+
+  report.uploaded = true;
+  fs.writeFileSync(reportPath, JSON.stringify(report, null, 2));
+  return true;
+}
+
+function setupCrashHandlers(ipcMain) {
+  ipcMain.handle('crash:list', async () => {
+    return listCrashReports();
+  });
+
+  ipcMain.handle('crash:get', async (event, id) => {
+    return JSON.parse(fs.readFileSync(getReportPath(id), 'utf-8'));
+  });
+
+  ipcMain.handle('crash:delete', async (event, id) => {
+    fs.rmSync(getReportPath(id), { force: true });
+    return true;
+  });
+
+  ipcMain.handle('crash:getSettings', async () => {
+    return { ...settings.store };
+  });
+
+  ipcMain.handle('crash:setUploadEnabled', async (event, enabled, endpoint) => {
+    settings.set('uploadEnabled', Boolean(enabled));
+    if (endpoint !== undefined) {
+      settings.set('endpoint', endpoint);
+    }
+    return { ...settings.store };
+  });
+
+  ipcMain.handle('crash:upload', async (event, id) => {
+    return uploadCrashReport(id);
+  });
+
+  // Renderer-side errors caught by error boundaries
+  ipcMain.handle('crash:reportRendererError', async (event, error) => {
+    const report = captureError(Object.assign(new Error(error.message), { stack: error.stack }), {
+      source: 'renderer',
+      context: { componentStack: error.componentStack, url: event.sender.getURL() }
+    });
+    return report.id;
+  });
+}
+
+module.exports = { installCrashHandlers, setupCrashHandlers, captureError, guardTask };
@@ -0,0 +1,210 @@
+// Replicated text document: an RGA sequence CRDT over runs of characters.
+//
+// Every character has an id (client, clock) where clock is a Lamport timestamp; a run
+// inserted in one operation takes consecutive clocks. An insert names the character it
+// was typed after (`origin`) and lands after it, skipping any neighbours with a newer
+// timestamp, so concurrent inserts at the same place order identically on every replica.
+// Deleted characters stay as tombstones so later operations can still refer to them.
+//
+// Operations must be applied in causal order (a relay that forwards in the order it
+// applies gives that), and applying one twice is harmless. No Node dependencies, so the
+// renderer can bundle the same implementation for its replica.
+//
+// Ops:
+//   { type: 'insert', id: [client, clock], origin: [client, clock] | null, text }
+//   { type: 'delete', ranges: [[client, clock, length], ...] }
+
+function compareIds(clockA, clientA, clockB, clientB) {
+  if (clockA !== clockB) return clockA - clockB;
+  return clientA < clientB ? -1 : clientA > clientB ? 1 : 0;
+}
+
+class TextDocument {
+  constructor(clientId, state) {
+    this.clientId = String(clientId);
+    // Document order, tombstones included: { client, clock, text, deleted }
+    this.items = state ? state.items.map((item) => ({ ...item })) : [];
+    this.clock = state ? state.clock : 0;
+  }
+
+  static fromText(clientId, text) {
+    const doc = new TextDocument(clientId);
+    if (text) doc.insert(0, text);
+    return doc;
+  }
+
+  // Serializable snapshot for a replica joining late
+  getState() {
+    return { clock: this.clock, items: this.items.map((item) => ({ ...item })) };
+  }
+
+  get length() {
+    let length = 0;
+    for (const item of this.items) if (!item.deleted) length += item.text.length;
+    return length;
+  }
+
+  toString() {
+    let text = '';
+    for (const item of this.items) if (!item.deleted) text += item.text;
+    return text;
+  }
+
+  // { index, offset } of the character with this id, or null
+  locate(client, clock) {
+    for (let index = 0; index < this.items.length; index++) {
+      const item = this.items[index];
+      if (item.client === client && clock >= item.clock && clock < item.clock + item.text.length) {
+        return { index, offset: clock - item.clock };
+      }
+    }
+    return null;
+  }
+
+  // Split items[index] so a new item starts at `offset`; returns the right half's index
+  split(index, offset) {
+    const item = this.items[index];
+    if (offset <= 0) return index;
+    if (offset >= item.text.length) return index + 1;
+    const right = { client: item.client, clock: item.clock + offset, text: item.text.slice(offset), deleted: item.deleted };
+    item.text = item.text.slice(0, offset);
+    this.items.splice(index + 1, 0, right);
+    return index + 1;
+  }
+
+  // Visible characters before items[index]
+  visibleOffset(index) {
+    let offset = 0;
+    for (let i = 0; i < index; i++) if (!this.items[i].deleted) offset += this.items[i].text.length;
+    return offset;
+  }
+
+  // Apply an op from any replica (including our own). Returns the resulting plain-text
+  // changes [{ index, deleteCount, text }] in application order, empty if nothing changed.
+  apply(op) {
+    if (op.type === 'insert') return this.integrateInsert(op);
+    if (op.type === 'delete') return this.integrateDelete(op);
+    throw new Error(`Unknown operation: ${op.type}`);
+  }
+
+  integrateInsert(op) {
+    const [client, clock] = op.id;
+    if (!op.text || this.locate(client, clock)) return [];
+
+    let position = 0;
+    if (op.origin) {
+      const origin = this.locate(op.origin[0], op.origin[1]);
+      if (!origin) throw new Error(`Insert ${client}:${clock} arrived before its origin`);
+      position = this.split(origin.index, origin.offset + 1);
+    }
+    const afterOrigin = position;
+    while (position < this.items.length) {
+      const next = this.items[position];
+      if (compareIds(next.clock, next.client, clock, client) < 0) break;
+      position++;
+    }
+
+    // Typing extends the previous run instead of adding an item per keystroke
+    const previous = this.items[position - 1];
+    if (position === afterOrigin && previous && !previous.deleted && previous.client === client &&
+        previous.clock + previous.text.length === clock && op.origin &&
+        op.origin[0] === client && op.origin[1] === clock - 1) {
+      previous.text += op.text;
+      position--;
+    } else {
+      this.items.splice(position, 0, { client, clock, text: op.text, deleted: false });
+    }
+    this.clock = Math.max(this.clock, clock + op.text.length - 1);
+    const index = this.visibleOffset(position) + (this.items[position].text.length - op.text.length);
+    return [{ index, deleteCount: 0, text: op.text }];
+  }
+
+  integrateDelete(op) {
+    const changes = [];
+    for (const [client, clock, length] of op.ranges) {
+      let next = clock;
+      const end = clock + length;
+      while (next < end) {
+        const found = this.locate(client, next);
+        if (!found) {
+          next++;
+          continue;
+        }
+        const start = this.split(found.index, found.offset);
+        const item = this.items[start];
+        const count = Math.min(end - next, item.text.length);
+        this.split(start, count);
+        if (!item.deleted) {
+          changes.push({ index: this.visibleOffset(start), deleteCount: count, text: '' });
+          item.deleted = true;
+        }
+        next += count;
+      }
+    }
+    return changes;
+  }
+
+  // Local edits: build the op, apply it, and return it for broadcasting
+  insert(index, text) {
+    if (!text) return null;
+    let origin = null;
+    if (index > 0) {
+      let remaining = index;
+      for (const item of this.items) {
+        if (item.deleted) continue;
+        if (remaining <= item.text.length) {
+          origin = [item.client, item.clock + remaining - 1];
+          break;
+        }
+        remaining -= item.text.length;
+      }
+      if (!origin) throw new RangeError(`Insert position ${index} is past the end of the document`);
+    }
+    const op = { type: 'insert', id: [this.clientId, this.clock + 1], origin, text };
+    this.apply(op);
+    return op;
+  }
+
+  delete(index, length) {
+    const ranges = [];
+    let position = 0;
+    for (const item of this.items) {
+      if (item.deleted) continue;
+      const start = Math.max(index, position);
+      const end = Math.min(index + length, position + item.text.length);
+      if (start < end) {
+        const last = ranges[ranges.length - 1];
+        const clock = item.clock + start - position;
+        if (last && last[0] === item.client && last[1] + last[2] === clock) {
+          last[2] += end - start;
+        } else {
+          ranges.push([item.client, clock, end - start]);
+        }
+      }
+      position += item.text.length;
+      if (position >= index + length) break;
+    }
+    if (ranges.length === 0) return null;
+    const op = { type: 'delete', ranges };
+    this.apply(op);
+    return op;
+  }
+
+  // Ops turning this document's text into `text` via one replaced span (common prefix/suffix)
+  replaceText(text) {
+    const current = this.toString();
+    let prefix = 0;
+    while (prefix < current.length && prefix < text.length && current[prefix] === text[prefix]) prefix++;
+    let suffix = 0;
+    while (suffix < current.length - prefix && suffix < text.length - prefix &&
+      current[current.length - 1 - suffix] === text[text.length - 1 - suffix]) suffix++;
+    const ops = [];
+    const removed = this.delete(prefix, current.length - prefix - suffix);
+    if (removed) ops.push(removed);
+    const inserted = this.insert(prefix, text.slice(prefix, text.length - suffix));
+    if (inserted) ops.push(inserted);
+    return ops;
+  }
+}
+
+module.exports = { TextDocument };
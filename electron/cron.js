@@ -0,0 +1,405 @@
+// Cron expressions: the five standard fields, an optional leading seconds field, names
+// (JAN, MON), ranges, lists and steps, the @daily-style macros, and the L (last day),
+// nW (nearest weekday), dow#n (nth weekday) and dowL (last weekday) extensions.
+// When both day-of-month and day-of-week are restricted a day matching either runs,
+// as in Vixie cron. Schedules are evaluated in an IANA timezone, skipping wall times
+// that a DST change jumps over and running once in repeated ones.
+
+class CronError extends Error {}
+
+const MACROS = {
+  '@yearly': '0 0 1 1 *',
+  '@annually': '0 0 1 1 *',
+  '@monthly': '0 0 1 * *',
+  '@weekly': '0 0 * * 0',
+  '@daily': '0 0 * * *',
+  '@midnight': '0 0 * * *',
+  '@hourly': '0 * * * *'
+};
+
+const MONTH_NAMES = ['JAN', 'FEB', 'MAR', 'APR', 'MAY', 'JUN', 'JUL', 'AUG', 'SEP', 'OCT', 'NOV', 'DEC'];
+const DAY_NAMES = ['SUN', 'MON', 'TUE', 'WED', 'THU', 'FRI', 'SAT'];
+const MONTH_LABELS = ['January', 'February', 'March', 'April', 'May', 'June', 'July', 'August', 'September', 'October', 'November', 'December'];
+const DAY_LABELS = ['Sunday', 'Monday', 'Tuesday', 'Wednesday', 'Thursday', 'Friday', 'Saturday'];
+
+const FIELDS = {
+  second: { min: 0, max: 59 },
+  minute: { min: 0, max: 59 },
+  hour: { min: 0, max: 23 },
+  dayOfMonth: { min: 1, max: 31 },
+  month: { min: 1, max: 12, names: MONTH_NAMES, nameBase: 1 },
+  dayOfWeek: { min: 0, max: 7, names: DAY_NAMES, nameBase: 0 }
+};
+
+// A year with no match means the expression can never fire (e.g. 30 February)
+const SEARCH_YEARS = 8;
+const MAX_RUNS = 500;
+
+function parseValue(text, name, spec) {
+  const upper = text.toUpperCase();
+  if (spec.names && spec.names.includes(upper)) return spec.names.indexOf(upper) + spec.nameBase;
+  if (!/^\d+$/.test(text)) throw new CronError(`Invalid ${name} value "${text}"`);
+  const value = Number(text);
+  if (value < spec.min || value > spec.max) throw new CronError(`${name} value ${value} is outside ${spec.min}-${spec.max}`);
+  return value;
+}
+
+// Field text -> { any, values: Set, parts, special } where parts keep the written structure
+// for descriptions
+function parseField(text, name) {
+  const spec = FIELDS[name];
+  const field = { text, any: false, values: new Set(), parts: [], special: [] };
+  if (text === '*' || (text === '?' && (name === 'dayOfMonth' || name === 'dayOfWeek'))) {
+    field.any = true;
+    for (let value = spec.min; value <= spec.max; value++) field.values.add(value);
+    field.parts.push({ start: spec.min, end: spec.max, step: 1, all: true });
+    if (name === 'dayOfWeek') field.values.delete(7);
+    return field;
+  }
+  for (const item of text.split(',')) {
+    if (!item) throw new CronError(`Empty item in ${name} field "${text}"`);
+    if (name === 'dayOfMonth' && /^L(-\d+)?$/i.test(item)) {
+      field.special.push({ kind: 'last', offset: item.length > 1 ? Number(item.slice(2)) : 0 });
+      continue;
+    }
+    if (name === 'dayOfMonth' && /^(\d+|L)W$/i.test(item)) {
+      const day = item[0].toUpperCase() === 'L' ? 'L' : parseValue(item.slice(0, -1), name, spec);
+      field.special.push({ kind: 'weekday', day });
+      continue;
+    }
+    if (name === 'dayOfWeek' && /^\w+#[1-5]$/.test(item)) {
+      const [day, nth] = item.split('#');
+      field.special.push({ kind: 'nth', day: parseValue(day, name, spec) % 7, nth: Number(nth) });
+      continue;
+    }
+    if (name === 'dayOfWeek' && /^\w+L$/i.test(item) && item.length > 1) {
+      field.special.push({ kind: 'lastWeekday', day: parseValue(item.slice(0, -1), name, spec) % 7 });
+      continue;
+    }
+    const [range, stepText] = item.split('/');
+    if (stepText !== undefined && !/^\d+$/.test(stepText)) throw new CronError(`Invalid step in ${name} field "${item}"`);
+    const step = stepText === undefined ? 1 : Number(stepText);
+    if (step < 1) throw new CronError(`Step must be at least 1 in ${name} field "${item}"`);
+    let start;
+    let end;
+    if (range === '*') {
+      start = spec.min;
+      end = name === 'dayOfWeek' ? 6 : spec.max;
+    } else if (range.includes('-')) {
+      const [from, to] = range.split('-');
+      start = parseValue(from, name, spec);
+      end = parseValue(to, name, spec);
+      if (end < start) throw new CronError(`Range ${range} in the ${name} field runs backwards`);
+    } else {
+      start = parseValue(range, name, spec);
+      // "5/15" means from 5 to the end of the field in steps of 15
+      end = stepText === undefined ? start : spec.max;
+    }
+    for (let value = start; value <= end; value += step) field.values.add(name === 'dayOfWeek' ? value % 7 : value);
+    field.parts.push({ start, end, step, all: range === '*' });
+  }
+  return field;
+}
+
+function parseCron(expression) {
+  if (typeof expression !== 'string' || !expression.trim()) throw new CronError('A cron expression is required');
+  let text = expression.trim().replace(/\s+/g, ' ');
+  if (text.toLowerCase() === '@reboot') throw new CronError('@reboot runs at startup and has no schedule');
+  if (text.startsWith('@')) {
+    const macro = MACROS[text.toLowerCase()];
+    if (!macro) throw new CronError(`Unknown macro ${text}`);
+    text = macro;
+  }
+  const parts = text.split(' ');
+  if (parts.length !== 5 && parts.length !== 6) throw new CronError(`Expected 5 fields (or 6 with seconds) but found ${parts.length}`);
+  const hasSeconds = parts.length === 6;
+  const [second, minute, hour, dayOfMonth, month, dayOfWeek] = hasSeconds ? parts : ['0', ...parts];
+  return {
+    expression: expression.trim(),
+    normalized: text,
+    hasSeconds,
+    second: parseField(second, 'second'),
+    minute: parseField(minute, 'minute'),
+    hour: parseField(hour, 'hour'),
+    dayOfMonth: parseField(dayOfMonth, 'dayOfMonth'),
+    month: parseField(month, 'month'),
+    dayOfWeek: parseField(dayOfWeek, 'dayOfWeek')
+  };
+}
+
+// Timezones
+
+function checkTimezone(timezone) {
+  try {
+    return new Intl.DateTimeFormat('en-US', { timeZone: timezone }).resolvedOptions().timeZone;
+  } catch (error) {
+    throw new CronError(`Unknown timezone ${timezone}`);
+  }
+}
+
+const formatters = new Map();
+function partsFormatter(timezone) {
+  if (!formatters.has(timezone)) {
+    formatters.set(timezone, new Intl.DateTimeFormat('en-US', {
+      timeZone: timezone,
+      hourCycle: 'h23',
+      year: 'numeric',
+      month: 'numeric',
+      day: 'numeric',
+      hour: 'numeric',
+      minute: 'numeric',
+      second: 'numeric'
+    }));
+  }
+  return formatters.get(timezone);
+}
+
+// Instant -> wall clock in `timezone`, as a Date whose UTC fields are the wall fields
+function toWall(ms, timezone) {
+  const fields = {};
+  for (const part of partsFormatter(timezone).formatToParts(new Date(ms))) fields[part.type] = Number(part.value);
+  return Date.UTC(fields.year, fields.month - 1, fields.day, fields.hour, fields.minute, fields.second, new Date(ms).getUTCMilliseconds());
+}
+
+// Minutes east of UTC at an instant
+function offsetAt(ms, timezone) {
+  return Math.round((toWall(ms, timezone) - ms) / 60000);
+}
+
+// Wall clock -> instant, or null when DST skips that wall time. A repeated wall time
+// resolves to its first occurrence.
+function fromWall(wall, timezone) {
+  const candidates = new Set([wall - offsetAt(wall, timezone) * 60000]);
+  candidates.add(wall - offsetAt(wall - 864e5, timezone) * 60000);
+  candidates.add(wall - offsetAt(wall + 864e5, timezone) * 60000);
+  const matches = Array.from(candidates).filter((ms) => toWall(ms, timezone) === wall).sort((a, b) => a - b);
+  return matches.length ? matches[0] : null;
+}
+
+function formatOffset(minutes) {
+  const sign = minutes < 0 ? '-' : '+';
+  const abs = Math.abs(minutes);
+  return `${sign}${String(Math.floor(abs / 60)).padStart(2, '0')}:${String(abs % 60).padStart(2, '0')}`;
+}
+
+// ISO-8601 in the zone's local time with its offset, e.g. 2026-03-08T03:00:00.000-07:00
+function zonedIso(ms, timezone) {
+  const wall = new Date(toWall(ms, timezone)).toISOString().slice(0, -1);
+  return `${wall}${formatOffset(offsetAt(ms, timezone))}`;
+}
+
+// Matching
+
+function daysInMonth(year, month) {
+  return new Date(Date.UTC(year, month, 0)).getUTCDate();
+}
+
+function nearestWeekday(year, month, day) {
+  const last = daysInMonth(year, month);
+  const target = Math.min(day, last);
+  const weekday = new Date(Date.UTC(year, month - 1, target)).getUTCDay();
+  if (weekday === 6) return target === 1 ? 3 : target - 1;
+  if (weekday === 0) return target === last ? target - 2 : target + 1;
+  return target;
+}
+
+function specialMatches(special, year, month, day, weekday) {
+  const last = daysInMonth(year, month);
+  switch (special.kind) {
+    case 'last':
+      return day === last - special.offset;
+    case 'weekday':
+      return day === nearestWeekday(year, month, special.day === 'L' ? last : special.day);
+    case 'nth':
+      return weekday === special.day && Math.ceil(day / 7) === special.nth;
+    case 'lastWeekday':
+      return weekday === special.day && day + 7 > last;
+    default:
+      return false;
+  }
+}
+
+function dayMatches(schedule, year, month, day) {
+  const weekday = new Date(Date.UTC(year, month - 1, day)).getUTCDay();
+  const { dayOfMonth, dayOfWeek } = schedule;
+  const domMatch = dayOfMonth.values.has(day) || dayOfMonth.special.some((special) => specialMatches(special, year, month, day, weekday));
+  const dowMatch = dayOfWeek.values.has(weekday) || dayOfWeek.special.some((special) => specialMatches(special, year, month, day, weekday));
+  if (dayOfMonth.any) return dowMatch;
+  if (dayOfWeek.any) return domMatch;
+  return domMatch || dowMatch;
+}
+
+function nextValue(field, current, max) {
+  for (let value = current; value <= max; value++) {
+    if (field.values.has(value)) return value;
+  }
+  return null;
+}
+
+// Next wall time at or after `wall` that matches, searching field by field
+function nextWall(schedule, wall, limit) {
+  let date = new Date(wall);
+  while (date.getTime() <= limit) {
+    const year = date.getUTCFullYear();
+    const month = date.getUTCMonth() + 1;
+    if (!schedule.month.values.has(month)) {
+      date = new Date(Date.UTC(year, month, 1));
+      continue;
+    }
+    const day = date.getUTCDate();
+    if (!dayMatches(schedule, year, month, day)) {
+      date = new Date(Date.UTC(year, month - 1, day + 1));
+      continue;
+    }
+    const hour = nextValue(schedule.hour, date.getUTCHours(), 23);
+    if (hour === null) {
+      date = new Date(Date.UTC(year, month - 1, day + 1));
+      continue;
+    }
+    if (hour !== date.getUTCHours()) {
+      date = new Date(Date.UTC(year, month - 1, day, hour));
+      continue;
+    }
+    const minute = nextValue(schedule.minute, date.getUTCMinutes(), 59);
+    if (minute === null) {
+      date = new Date(Date.UTC(year, month - 1, day, hour + 1));
+      continue;
+    }
+    if (minute !== date.getUTCMinutes()) {
+      date = new Date(Date.UTC(year, month - 1, day, hour, minute));
+      continue;
+    }
+    const second = nextValue(schedule.second, date.getUTCSeconds(), 59);
+    if (second === null) {
+      date = new Date(Date.UTC(year, month - 1, day, hour, minute + 1));
+      continue;
+    }
+    return Date.UTC(year, month - 1, day, hour, minute, second);
+  }
+  return null;
+}
+
+// The next `count` run instants (ms) strictly after `from`
+function nextRuns(schedule, { count = 5, from = Date.now(), timezone = 'UTC' } = {}) {
+  const runs = [];
+  const start = Math.floor(from / 1000) * 1000 + 1000;
+  let wall = toWall(start, timezone);
+  const limit = wall + SEARCH_YEARS * 366 * 864e5;
+  while (runs.length < Math.min(count, MAX_RUNS)) {
+    const found = nextWall(schedule, wall, limit);
+    if (found === null) break;
+    const instant = fromWall(found, timezone);
+    if (instant !== null && instant >= start && (!runs.length || instant > runs[runs.length - 1])) runs.push(instant);
+    wall = found + 1000;
+  }
+  return runs;
+}
+
+// Descriptions
+
+function ordinal(n) {
+  const suffix = n % 100 >= 11 && n % 100 <= 13 ? 'th' : ({ 1: 'st', 2: 'nd', 3: 'rd' }[n % 10] || 'th');
+  return `${n}${suffix}`;
+}
+
+function joinList(items, conjunction = 'and') {
+  if (items.length <= 1) return items.join('');
+  return `${items.slice(0, -1).join(', ')} ${conjunction} ${items[items.length - 1]}`;
+}
+
+// Steps read as "every 2nd hour" (or "every 20th minute from 5 through 59")
+function describeParts(field, label, unit = '') {
+  return joinList(field.parts.map((part) => {
+    if (part.step > 1) {
+      const from = part.all ? '' : ` from ${label(part.start)} through ${label(part.end)}`;
+      return `every ${ordinal(part.step)}${unit ? ` ${unit}` : ''}${from}`;
+    }
+    return part.start === part.end ? label(part.start) : `${label(part.start)} through ${label(part.end)}`;
+  }));
+}
+
+function stepsOnly(field) {
+  return field.parts.length > 0 && field.parts.every((part) => part.step > 1);
+}
+
+// "every 15 minutes" rather than "every 15th minute" for plain */n
+function everyN(field) {
+  return field.parts.length === 1 && field.parts[0].all && field.parts[0].step > 1 ? field.parts[0].step : null;
+}
+
+function single(field) {
+  return !field.any && field.special.length === 0 && field.parts.length === 1 && field.parts[0].start === field.parts[0].end ? field.parts[0].start : null;
+}
+
+function pad(value) {
+  return String(value).padStart(2, '0');
+}
+
+function describeUnit(field, unit) {
+  if (everyN(field)) return `every ${everyN(field)} ${unit}s`;
+  if (stepsOnly(field)) return describeParts(field, String, unit);
+  return `at ${unit} ${describeParts(field, String)}`;
+}
+
+function describeTime(schedule) {
+  const { second, minute, hour, hasSeconds } = schedule;
+  const sec = single(second);
+  const min = single(minute);
+  const hr = single(hour);
+  if (min !== null && hr !== null && (!hasSeconds || sec !== null)) {
+    return `At ${pad(hr)}:${pad(min)}${hasSeconds && sec !== 0 ? `:${pad(sec)}` : ''}`;
+  }
+  const phrases = [];
+  if (hasSeconds && sec !== 0) phrases.push(second.any ? 'every second' : describeUnit(second, 'second'));
+  if (!minute.any) phrases.push(describeUnit(minute, 'minute'));
+  else if (!phrases.length) phrases.push('every minute');
+  if (!hour.any) {
+    phrases.push(stepsOnly(hour) ? `past ${describeParts(hour, String, 'hour')}` : `past hour ${describeParts(hour, String)}`);
+  } else if (!minute.any && !stepsOnly(minute)) {
+    phrases.push('past every hour');
+  }
+  const text = phrases.join(' ');
+  return text[0].toUpperCase() + text.slice(1);
+}
+
+function describeSpecial(special) {
+  switch (special.kind) {
+    case 'last':
+      return special.offset ? `${special.offset} day${special.offset === 1 ? '' : 's'} before the last day of the month` : 'the last day of the month';
+    case 'weekday':
+      return special.day === 'L' ? 'the last weekday of the month' : `the weekday nearest day ${special.day}`;
+    case 'nth':
+      return `the ${ordinal(special.nth)} ${DAY_LABELS[special.day]} of the month`;
+    case 'lastWeekday':
+      return `the last ${DAY_LABELS[special.day]} of the month`;
+    default:
+      return '';
+  }
+}
+
+function describeDays(schedule) {
+  const { dayOfMonth, dayOfWeek } = schedule;
+  const phrases = [];
+  if (!dayOfMonth.any) {
+    const items = [];
+    if (stepsOnly(dayOfMonth)) items.push(`${describeParts(dayOfMonth, String, 'day')} of the month`);
+    else if (dayOfMonth.parts.length) items.push(`day ${describeParts(dayOfMonth, String)} of the month`);
+    items.push(...dayOfMonth.special.map(describeSpecial));
+    phrases.push(`on ${joinList(items, 'or')}`);
+  }
+  if (!dayOfWeek.any) {
+    const items = [];
+    if (dayOfWeek.parts.length) items.push(describeParts(dayOfWeek, (value) => DAY_LABELS[value % 7]));
+    items.push(...dayOfWeek.special.map(describeSpecial));
+    phrases.push(`on ${joinList(items, 'or')}`);
+  }
+  return phrases.join(dayOfMonth.any || dayOfWeek.any ? '' : ' or ');
+}
+
+function describeCron(schedule) {
+  const pieces = [describeTime(schedule), describeDays(schedule)];
+  if (!schedule.month.any) pieces.push(`in ${describeParts(schedule.month, (value) => MONTH_LABELS[value - 1])}`);
+  return pieces.filter(Boolean).join(', ');
+}
+
+module.exports = { parseCron, describeCron, nextRuns, checkTimezone, toWall, fromWall, offsetAt, zonedIso, formatOffset, CronError };
@@ -0,0 +1,551 @@
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+// Color parsing and conversion (hex, rgb, hsl, hwb, oklab, oklch, named colors), WCAG 2
+// contrast checks, and CSS length conversion. Colors are handled in sRGB; oklch values
+// outside the sRGB gamut are clipped and flagged.
+
+const NAMED_COLORS = {
+  aliceblue: 'f0f8ff', antiquewhite: 'faebd7', aqua: '00ffff', aquamarine: '7fffd4', azure: 'f0ffff', beige: 'f5f5dc', bisque: 'ffe4c4',
+  black: '000000', blanchedalmond: 'ffebcd', blue: '0000ff', blueviolet: '8a2be2', brown: 'a52a2a', burlywood: 'deb887', cadetblue: '5f9ea0',
+  chartreuse: '7fff00', chocolate: 'd2691e', coral: 'ff7f50', cornflowerblue: '6495ed', cornsilk: 'fff8dc', crimson: 'dc143c', cyan: '00ffff',
+  darkblue: '00008b', darkcyan: '008b8b', darkgoldenrod: 'b8860b', darkgray: 'a9a9a9', darkgreen: '006400', darkgrey: 'a9a9a9', darkkhaki: 'bdb76b',
+  darkmagenta: '8b008b', darkolivegreen: '556b2f', darkorange: 'ff8c00', darkorchid: '9932cc', darkred: '8b0000', darksalmon: 'e9967a',
+  darkseagreen: '8fbc8f', darkslateblue: '483d8b', darkslategray: '2f4f4f', darkslategrey: '2f4f4f', darkturquoise: '00ced1', darkviolet: '9400d3',
+  deeppink: 'ff1493', deepskyblue: '00bfff', dimgray: '696969', dimgrey: '696969', dodgerblue: '1e90ff', firebrick: 'b22222', floralwhite: 'fffaf0',
+  forestgreen: '228b22', fuchsia: 'ff00ff', gainsboro: 'dcdcdc', ghostwhite: 'f8f8ff', gold: 'ffd700', goldenrod: 'daa520', gray: '808080',
+  green: '008000', greenyellow: 'adff2f', grey: '808080', honeydew: 'f0fff0', hotpink: 'ff69b4', indianred: 'cd5c5c', indigo: '4b0082',
+  ivory: 'fffff0', khaki: 'f0e68c', lavender: 'e6e6fa', lavenderblush: 'fff0f5', lawngreen: '7cfc00', lemonchiffon: 'fffacd', lightblue: 'add8e6',
+  lightcoral: 'f08080', lightcyan: 'e0ffff', lightgoldenrodyellow: 'fafad2', lightgray: 'd3d3d3', lightgreen: '90ee90', lightgrey: 'd3d3d3',
+  lightpink: 'ffb6c1', lightsalmon: 'ffa07a', lightseagreen: '20b2aa', lightskyblue: '87cefa', lightslategray: '778899', lightslategrey: '778899',
+  lightsteelblue: 'b0c4de', lightyellow: 'ffffe0', lime: '00ff00', limegreen: '32cd32', linen: 'faf0e6', magenta: 'ff00ff', maroon: '800000',
+  mediumaquamarine: '66cdaa', mediumblue: '0000cd', mediumorchid: 'ba55d3', mediumpurple: '9370db', mediumseagreen: '3cb371',
+  mediumslateblue: '7b68ee', mediumspringgreen: '00fa9a', mediumturquoise: '48d1cc', mediumvioletred: 'c71585', midnightblue: '191970',
+  mintcream: 'f5fffa', mistyrose: 'ffe4e1', moccasin: 'ffe4b5', navajowhite: 'ffdead', navy: '000080', oldlace: 'fdf5e6', olive: '808000',
+  olivedrab: '6b8e23', orange: 'ffa500', orangered: 'ff4500', orchid: 'da70d6', palegoldenrod: 'eee8aa', palegreen: '98fb98', paleturquoise: 'afeeee',
+  palevioletred: 'db7093', papayawhip: 'ffefd5', peachpuff: 'ffdab9', peru: 'cd853f', pink: 'ffc0cb', plum: 'dda0dd', powderblue: 'b0e0e6',
+  purple: '800080', rebeccapurple: '663399', red: 'ff0000', rosybrown: 'bc8f8f', royalblue: '4169e1', saddlebrown: '8b4513', salmon: 'fa8072',
+  sandybrown: 'f4a460', seagreen: '2e8b57', seashell: 'fff5ee', sienna: 'a0522d', silver: 'c0c0c0', skyblue: '87ceeb', slateblue: '6a5acd',
+  slategray: '708090', slategrey: '708090', snow: 'fffafa', springgreen: '00ff7f', steelblue: '4682b4', tan: 'd2b48c', teal: '008080',
+  thistle: 'd8bfd8', tomato: 'ff6347', turquoise: '40e0d0', violet: 'ee82ee', wheat: 'f5deb3', white: 'ffffff', whitesmoke: 'f5f5f5',
+  yellow: 'ffff00', yellowgreen: '9acd32'
+};
+const HEX_TO_NAME = Object.fromEntries(Object.entries(NAMED_COLORS).reverse().map(([name, hex]) => [hex, name]));
+
+// WCAG 2.x thresholds; large text is 24px, or 18.66px (14pt) at weight 700+
+const WCAG = { aa: 4.5, aaLarge: 3, aaa: 7, aaaLarge: 4.5, nonText: 3 };
+const FUNCTION_PATTERN = /\b(rgba?|hsla?|hwb|oklch|oklab|color)\(\s*([^()]*)\)/gi;
+const HEX_PATTERN = /#(?:[0-9a-fA-F]{8}|[0-9a-fA-F]{6}|[0-9a-fA-F]{3,4})\b/g;
+const MAX_FOUND_COLORS = 5000;
+const MAX_AUDITED_ELEMENTS = 2000;
+
+const round = (value, digits = 3) => Math.round(value * 10 ** digits) / 10 ** digits;
+const clamp = (value, min = 0, max = 1) => Math.min(max, Math.max(min, value));
+
+// Color spaces. RGB channels are 0..1 floats throughout.
+
+function srgbToLinear(c) {
+  return Math.abs(c) <= 0.04045 ? c / 12.92 : Math.sign(c) * ((Math.abs(c) + 0.055) / 1.055) ** 2.4;
+}
+
+function linearToSrgb(c) {
+  return Math.abs(c) <= 0.0031308 ? c * 12.92 : Math.sign(c) * (1.055 * Math.abs(c) ** (1 / 2.4) - 0.055);
+}
+
+function rgbToOklab([r, g, b]) {
+  const [lr, lg, lb] = [r, g, b].map(srgbToLinear);
+  const l = Math.cbrt(0.4122214708 * lr + 0.5363325363 * lg + 0.0514459929 * lb);
+  const m = Math.cbrt(0.2119034982 * lr + 0.6806995451 * lg + 0.1073969566 * lb);
+  const s = Math.cbrt(0.0883024619 * lr + 0.2817188376 * lg + 0.6299787005 * lb);
+  return [
+    0.2104542553 * l + 0.793617785 * m - 0.0040720468 * s,
+    1.9779984951 * l - 2.428592205 * m + 0.4505937099 * s,
+    0.0259040371 * l + 0.7827717662 * m - 0.808675766 * s
+  ];
+}
+
+function oklabToRgb([L, a, b]) {
+  const l = (L + 0.3963377774 * a + 0.2158037573 * b) ** 3;
+  const m = (L - 0.1055613458 * a - 0.0638541728 * b) ** 3;
+  const s = (L - 0.0894841775 * a - 1.291485548 * b) ** 3;
+  return [
+    4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+    -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+    -0.0041960863 * l - 0.7034186147 * m + 1.707614701 * s
+  ].map(linearToSrgb);
+}
+
+function oklabToOklch([L, a, b]) {
+  const C = Math.sqrt(a * a + b * b);
+  const H = C < 1e-6 ? 0 : ((Math.atan2(b, a) * 180) / Math.PI + 360) % 360;
+  return [L, C, H];
+}
+
+function oklchToOklab([L, C, H]) {
+  const radians = (H * Math.PI) / 180;
+  return [L, C * Math.cos(radians), C * Math.sin(radians)];
+}
+
+function hslToRgb([h, s, l]) {
+  const k = (n) => (n + h / 30) % 12;
+  const a = s * Math.min(l, 1 - l);
+  const f = (n) => l - a * Math.max(-1, Math.min(k(n) - 3, 9 - k(n), 1));
+  return [f(0), f(8), f(4)];
+}
+
+function rgbToHsl([r, g, b]) {
+  const max = Math.max(r, g, b);
+  const min = Math.min(r, g, b);
+  const l = (max + min) / 2;
+  const d = max - min;
+  if (d === 0) return [0, 0, l];
+  const s = d / (1 - Math.abs(2 * l - 1));
+  let h;
+  if (max === r) h = ((g - b) / d) % 6;
+  else if (max === g) h = (b - r) / d + 2;
+  else h = (r - g) / d + 4;
+  return [(h * 60 + 360) % 360, s, l];
+}
+
+function hwbToRgb([h, w, bl]) {
+  if (w + bl >= 1) {
+    const gray = w / (w + bl);
+    return [gray, gray, gray];
+  }
+  return hslToRgb([h, 1, 0.5]).map((c) => c * (1 - w - bl) + w);
+}
+
+function rgbToHwb(rgb) {
+  const [h] = rgbToHsl(rgb);
+  return [h, Math.min(...rgb), 1 - Math.max(...rgb)];
+}
+
+// Parsing
+
+function parseNumber(token, { percentScale = 1, angle = false } = {}) {
+  const match = /^([-+]?(?:\d+\.?\d*|\.\d+)(?:e[-+]?\d+)?)(%|deg|grad|rad|turn)?$/i.exec(token);
+  if (!match) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid color component "${token}"`);
+  const value = Number(match[1]);
+  const unit = (match[2] || '').toLowerCase();
+  if (unit === '%') return (value / 100) * percentScale;
+  if (!angle && unit) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unexpected unit in "${token}"`);
+  if (unit === 'grad') return value * 0.9;
+  if (unit === 'rad') return (value * 180) / Math.PI;
+  if (unit === 'turn') return value * 360;
+  return value;
+}
+
+// "r g b / a" or legacy "r, g, b, a" -> { channels, alpha }; `none` reads as 0
+function splitArguments(body) {
+  const [main, alphaText] = body.includes('/') ? body.split('/') : [body, undefined];
+  let channels = main.includes(',') ? main.split(',') : main.trim().split(/\s+/);
+  channels = channels.map((token) => token.trim()).filter(Boolean);
+  let alpha = alphaText === undefined ? undefined : alphaText.trim();
+  if (alpha === undefined && channels.length === 4) alpha = channels.pop();
+  if (channels.length !== 3) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Expected three color channels in "${body}"`);
+  const read = (token, options) => (token.toLowerCase() === 'none' ? 0 : parseNumber(token, options));
+  return { read, channels, alpha: alpha === undefined ? 1 : clamp(read(alpha)) };
+}
+
+function parseHex(hex) {
+  let digits = hex.slice(1);
+  if (digits.length <= 4) digits = digits.split('').map((d) => d + d).join('');
+  const values = digits.match(/../g).map((pair) => parseInt(pair, 16) / 255);
+  return { rgb: values.slice(0, 3), alpha: values.length === 4 ? values[3] : 1 };
+}
+
+// CSS color text -> { rgb: [0..1]x3 (may fall outside 0..1 for wide-gamut input), alpha }
+function parseColor(text) {
+  if (typeof text !== 'string' || !text.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A color is required');
+  const input = text.trim().toLowerCase();
+  if (/^#[0-9a-f]+$/.test(input)) {
+    if (![4, 5, 7, 9].includes(input.length)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid hex color ${text}`);
+    return parseHex(input);
+  }
+  if (input === 'transparent') return { rgb: [0, 0, 0], alpha: 0 };
+  if (NAMED_COLORS[input]) return parseHex(`#${NAMED_COLORS[input]}`);
+  const match = /^([a-z]+)\(\s*([^()]*)\)$/.exec(input);
+  if (!match) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unrecognized color ${text}`);
+  const [, name, body] = match;
+  if (name === 'color') {
+    const [space, ...rest] = body.trim().split(/\s+/);
+    if (space !== 'srgb' && space !== 'srgb-linear') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `The ${space} color space is not supported`);
+    const { read, channels, alpha } = splitArguments(rest.join(' '));
+    const rgb = channels.map((token) => read(token));
+    return { rgb: space === 'srgb' ? rgb : rgb.map(linearToSrgb), alpha };
+  }
+  const { read, channels, alpha } = splitArguments(body);
+  switch (name) {
+    case 'rgb':
+    case 'rgba':
+      return { rgb: channels.map((token) => read(token, { percentScale: 255 }) / 255), alpha };
+    case 'hsl':
+    case 'hsla': {
+      const [h, s, l] = [read(channels[0], { angle: true }), read(channels[1], { percentScale: 100 }), read(channels[2], { percentScale: 100 })];
+      return { rgb: hslToRgb([((h % 360) + 360) % 360, clamp(s / 100), clamp(l / 100)]), alpha };
+    }
+    case 'hwb': {
+      const [h, w, b] = [read(channels[0], { angle: true }), read(channels[1], { percentScale: 100 }), read(channels[2], { percentScale: 100 })];
+      return { rgb: hwbToRgb([((h % 360) + 360) % 360, clamp(w / 100), clamp(b / 100)]), alpha };
+    }
+    case 'oklab':
+      return { rgb: oklabToRgb([read(channels[0]), read(channels[1], { percentScale: 0.4 }), read(channels[2], { percentScale: 0.4 })]), alpha };
+    case 'oklch':
+      return { rgb: oklabToRgb(oklchToOklab([read(channels[0]), read(channels[1], { percentScale: 0.4 }), read(channels[2], { angle: true })])), alpha };
+    default:
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unsupported color function ${name}()`);
+  }
+}
+
+// Formatting
+
+function toHexByte(c) {
+  return Math.round(clamp(c) * 255).toString(16).padStart(2, '0');
+}
+
+function alphaSuffix(alpha, legacy) {
+  if (alpha >= 1) return '';
+  return legacy ? `, ${round(alpha)}` : ` / ${round(alpha * 100, 1)}%`;
+}
+
+// Hues just under 360 would round up to 360; keep them in [0, 360)
+function roundHue(hue, digits) {
+  return round(hue, digits) % 360;
+}
+
+// Every representation of a parsed color; `inGamut` is false when an oklch/oklab input
+// had to be clipped to sRGB
+function describeColor({ rgb, alpha }) {
+  const inGamut = rgb.every((c) => c >= -1e-4 && c <= 1 + 1e-4);
+  const clipped = rgb.map((c) => clamp(c));
+  const hex = `#${clipped.map(toHexByte).join('')}${alpha < 1 ? toHexByte(alpha) : ''}`;
+  const bytes = clipped.map((c) => Math.round(c * 255));
+  const [h, s, l] = rgbToHsl(clipped);
+  const [hh, w, b] = rgbToHwb(clipped);
+  const oklab = rgbToOklab(rgb);
+  const [L, C, H] = oklabToOklch(oklab);
+  return {
+    hex,
+    rgb: `rgb(${bytes.join(' ')}${alphaSuffix(alpha)})`,
+    hsl: `hsl(${roundHue(h, 1)} ${round(s * 100, 1)}% ${round(l * 100, 1)}%${alphaSuffix(alpha)})`,
+    hwb: `hwb(${roundHue(hh, 1)} ${round(w * 100, 1)}% ${round(b * 100, 1)}%${alphaSuffix(alpha)})`,
+    oklab: `oklab(${round(oklab[0] * 100, 2)}% ${round(oklab[1], 4)} ${round(oklab[2], 4)}${alphaSuffix(alpha)})`,
+    oklch: `oklch(${round(L * 100, 2)}% ${round(C, 4)} ${roundHue(H, 2)}${alphaSuffix(alpha)})`,
+    name: alpha === 1 ? HEX_TO_NAME[hex.slice(1)] || null : alpha === 0 ? 'transparent' : null,
+    components: {
+      rgb: bytes,
+      hsl: [roundHue(h, 2), round(s * 100, 2), round(l * 100, 2)],
+      oklch: [round(L, 4), round(C, 4), roundHue(H, 2)],
+      alpha: round(alpha, 4)
+    },
+    luminance: round(relativeLuminance(clipped), 4),
+    inGamut
+  };
+}
+
+function convertColor(text) {
+  return { input: text, ...describeColor(parseColor(text)) };
+}
+
+// Contrast
+
+function relativeLuminance(rgb) {
+  const [r, g, b] = rgb.map((c) => srgbToLinear(clamp(c)));
+  return 0.2126 * r + 0.7152 * g + 0.0722 * b;
+}
+
+// Paints a translucent color over an opaque backdrop
+function composite(color, backdrop) {
+  return color.rgb.map((c, i) => c * color.alpha + backdrop[i] * (1 - color.alpha));
+}
+
+function ratioOf(foreground, background) {
+  const [a, b] = [relativeLuminance(foreground), relativeLuminance(background)];
+  return (Math.max(a, b) + 0.05) / (Math.min(a, b) + 0.05);
+}
+
+function requiredRatio({ large = false, level = 'AA' } = {}) {
+  if (level === 'AAA') return large ? WCAG.aaaLarge : WCAG.aaa;
+  return large ? WCAG.aaLarge : WCAG.aa;
+}
+
+// Walks oklch lightness toward black or white until the foreground reaches `target`;
+// null when neither direction gets there
+function suggestForeground(foreground, background, target) {
+  const [L, C, H] = oklabToOklch(rgbToOklab(foreground));
+  let best = null;
+  for (const direction of [-1, 1]) {
+    let lo = L;
+    let hi = direction < 0 ? 0 : 1;
+    const at = (lightness) => oklabToRgb(oklchToOklab([lightness, C, H])).map((c) => clamp(c));
+    if (ratioOf(at(hi), background) < target) continue;
+    for (let i = 0; i < 24; i++) {
+      const mid = (lo + hi) / 2;
+      if (ratioOf(at(mid), background) >= target) hi = mid;
+      else lo = mid;
+    }
+    // Rounding to hex can land just under the target, so keep stepping until it doesn't
+    let hex = null;
+    for (let step = 0; step < 50 && hi >= 0 && hi <= 1; step++, hi += direction * 0.002) {
+      const candidate = `#${at(hi).map(toHexByte).join('')}`;
+      if (ratioOf(parseHex(candidate).rgb, background) >= target) {
+        hex = candidate;
+        break;
+      }
+    }
+    const delta = Math.abs(hi - L);
+    if (hex && (!best || delta < best.delta)) best = { hex, delta };
+  }
+  return best ? best.hex : null;
+}
+
+// WCAG 2 contrast of `foreground` on `background`. A translucent background is painted over
+// `backdrop` (white unless given) and the foreground over the result.
+function checkContrast(foregroundText, backgroundText, { backdrop = '#ffffff', large = false } = {}) {
+  const base = parseColor(backdrop);
+  const backgroundColor = parseColor(backgroundText);
+  const background = composite(backgroundColor, composite(base, [1, 1, 1]));
+  const foreground = composite(parseColor(foregroundText), background);
+  const ratio = ratioOf(foreground, background);
+  const passes = {
+    aa: ratio >= WCAG.aa,
+    aaLarge: ratio >= WCAG.aaLarge,
+    aaa: ratio >= WCAG.aaa,
+    aaaLarge: ratio >= WCAG.aaaLarge,
+    nonText: ratio >= WCAG.nonText
+  };
+  const target = requiredRatio({ large });
+  return {
+    foreground: describeColor({ rgb: foreground, alpha: 1 }).hex,
+    background: describeColor({ rgb: background, alpha: 1 }).hex,
+    ratio: round(ratio, 2),
+    passes,
+    // Closest foreground (by oklch lightness) that meets AA for this text size
+    suggestion: ratio >= target ? null : suggestForeground(foreground, background, target)
+  };
+}
+
+// Finding colors in text, for inline swatches
+
+function lineStarts(text) {
+  const starts = [0];
+  for (let i = 0; i < text.length; i++) if (text[i] === '\n') starts.push(i + 1);
+  return starts;
+}
+
+function positionOf(starts, offset) {
+  let lo = 0;
+  let hi = starts.length - 1;
+  while (lo < hi) {
+    const mid = (lo + hi + 1) >> 1;
+    if (starts[mid] <= offset) lo = mid;
+    else hi = mid - 1;
+  }
+  return { line: lo + 1, column: offset - starts[lo] + 1 };
+}
+
+// Hex values and color functions, plus named colors when `names` is set (off by default
+// since words like "red" are common outside stylesheets)
+function findColors(text, { names = false } = {}) {
+  const source = String(text || '');
+  const found = [];
+  const add = (start, end) => {
+    if (found.length >= MAX_FOUND_COLORS) return;
+    try {
+      const color = describeColor(parseColor(source.slice(start, end)));
+      found.push({ start, end, text: source.slice(start, end), hex: color.hex, rgb: color.components.rgb, alpha: color.components.alpha });
+    } catch (error) {
+      // Looked like a color but wasn't one
+    }
+  };
+  for (const match of source.matchAll(HEX_PATTERN)) {
+    // Skip fragment links and ids such as href="#abc"
+    if (/[\w-]/.test(source[match.index + match[0].length] || '')) continue;
+    add(match.index, match.index + match[0].length);
+  }
+  for (const match of source.matchAll(FUNCTION_PATTERN)) add(match.index, match.index + match[0].length);
+  if (names) {
+    for (const match of source.matchAll(/(?<![\w$#.-])[a-z]+(?![\w-])/gi)) {
+      const word = match[0].toLowerCase();
+      if (NAMED_COLORS[word] || word === 'transparent') add(match.index, match.index + match[0].length);
+    }
+  }
+  const starts = lineStarts(source);
+  return found
+    .sort((a, b) => a.start - b.start)
+    .map((entry) => ({ ...entry, ...positionOf(starts, entry.start) }));
+}
+
+// Lengths
+
+const ABSOLUTE_UNITS = { px: 1, in: 96, cm: 96 / 2.54, mm: 96 / 25.4, q: 96 / 101.6, pt: 96 / 72, pc: 16 };
+const RELATIVE_UNITS = ['rem', 'em', '%', 'vw', 'vh', 'vmin', 'vmax', 'ch', 'ex'];
+
+// px per unit for the given context. ch and ex use the common 0.5em approximation.
+function unitSizes({ rootFontSize = 16, fontSize = 16, viewportWidth = 1280, viewportHeight = 800, percentOf = null } = {}) {
+  const sizes = {
+    ...ABSOLUTE_UNITS,
+    rem: rootFontSize,
+    em: fontSize,
+    vw: viewportWidth / 100,
+    vh: viewportHeight / 100,
+    vmin: Math.min(viewportWidth, viewportHeight) / 100,
+    vmax: Math.max(viewportWidth, viewportHeight) / 100,
+    ch: fontSize / 2,
+    ex: fontSize / 2
+  };
+  if (Number.isFinite(percentOf)) sizes['%'] = percentOf / 100;
+  return sizes;
+}
+
+// "1.5rem" (or a number with `from`) -> the length in px and every other unit
+function convertUnit(input, options = {}) {
+  let value;
+  let unit;
+  if (typeof input === 'number') {
+    value = input;
+    unit = (options.from || 'px').toLowerCase();
+  } else {
+    const match = /^\s*([-+]?(?:\d+\.?\d*|\.\d+)(?:e[-+]?\d+)?)\s*([a-z%]*)\s*$/i.exec(String(input || ''));
+    if (!match) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid CSS length "${input}"`);
+    value = Number(match[1]);
+    unit = (match[2] || options.from || 'px').toLowerCase();
+  }
+  const sizes = unitSizes(options);
+  if (!sizes[unit]) {
+    const known = [...Object.keys(ABSOLUTE_UNITS), ...RELATIVE_UNITS];
+    const hint = unit === '%' ? '; % needs percentOf' : '';
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unsupported unit "${unit}"${hint}`, { details: { units: known } });
+  }
+  const px = value * sizes[unit];
+  const conversions = Object.fromEntries(Object.entries(sizes).map(([name, size]) => [name === 'q' ? 'Q' : name, round(px / size, 4)]));
+  const result = { value, unit, px: round(px, 4), conversions };
+  if (options.to) {
+    const to = options.to.toLowerCase();
+    if (!sizes[to]) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unsupported unit "${options.to}"`);
+    result.converted = `${round(px / sizes[to], 4)}${to === 'q' ? 'Q' : to}`;
+  }
+  return result;
+}
+
+// Contrast audit of a browser preview
+
+// Collects elements with their own visible text, resolving the effective background by
+// walking up through transparent ancestors. Background images make the result unknown.
+const AUDIT_SCRIPT = `(() => {
+  const MAX = ${MAX_AUDITED_ELEMENTS};
+  const selectorOf = (el) => {
+    const parts = [];
+    for (let node = el; node && node.nodeType === 1 && parts.length < 5; node = node.parentElement) {
+      if (node.id) { parts.unshift('#' + CSS.escape(node.id)); break; }
+      let part = node.localName;
+      const classes = Array.from(node.classList).slice(0, 2);
+      if (classes.length) part += '.' + classes.map((c) => CSS.escape(c)).join('.');
+      const parent = node.parentElement;
+      if (parent) {
+        const same = Array.from(parent.children).filter((child) => child.localName === node.localName);
+        if (same.length > 1) part += ':nth-of-type(' + (same.indexOf(node) + 1) + ')';
+      }
+      parts.unshift(part);
+    }
+    return parts.join(' > ');
+  };
+  const backgroundsOf = (el) => {
+    const layers = [];
+    for (let node = el; node; node = node.parentElement) {
+      const style = getComputedStyle(node);
+      if (style.backgroundImage && style.backgroundImage !== 'none') return { layers, image: true };
+      layers.push(style.backgroundColor);
+      // Computed colors are rgb() when opaque and rgba() otherwise
+      if (/^rgb\(/.test(style.backgroundColor)) break;
+    }
+    return { layers, image: false };
+  };
+  const results = [];
+  const walker = document.createTreeWalker(document.body || document.documentElement, NodeFilter.SHOW_ELEMENT);
+  for (let el = walker.currentNode; el && results.length < MAX; el = walker.nextNode()) {
+    const text = Array.from(el.childNodes).filter((n) => n.nodeType === 3).map((n) => n.textContent).join('').trim();
+    if (!text) continue;
+    const style = getComputedStyle(el);
+    if (style.visibility === 'hidden' || style.display === 'none' || Number(style.opacity) === 0) continue;
+    const rect = el.getBoundingClientRect();
+    if (rect.width === 0 || rect.height === 0) continue;
+    const { layers, image } = backgroundsOf(el);
+    results.push({
+      selector: selectorOf(el),
+      text: text.slice(0, 80),
+      color: style.color,
+      backgrounds: layers,
+      backgroundImage: image,
+      fontSize: parseFloat(style.fontSize),
+      fontWeight: Number(style.fontWeight) || 400
+    });
+  }
+  return { url: location.href, elements: results };
+})()`;
+
+async function auditContrast(browserViews, viewId, { level = 'AA' } = {}) {
+  const view = browserViews.get(viewId);
+  if (!view || view.webContents.isDestroyed()) {
+    throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${viewId} not found`, { details: { id: viewId } });
+  }
+  if (level !== 'AA' && level !== 'AAA') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'level must be AA or AAA');
+  const { url, elements } = await view.webContents.executeJavaScript(AUDIT_SCRIPT, true);
+  const failures = [];
+  let passed = 0;
+  let indeterminate = 0;
+  for (const element of elements) {
+    let background;
+    let foreground;
+    try {
+      // Innermost layer last so each paints over the ones behind it
+      background = element.backgrounds.reduceRight((backdrop, layer) => composite(parseColor(layer), backdrop), [1, 1, 1]);
+      foreground = composite(parseColor(element.color), background);
+    } catch (error) {
+      indeterminate++;
+      continue;
+    }
+    if (element.backgroundImage) {
+      indeterminate++;
+      continue;
+    }
+    const large = element.fontSize >= 24 || (element.fontSize >= 18.66 && element.fontWeight >= 700);
+    const required = requiredRatio({ large, level });
+    const ratio = ratioOf(foreground, background);
+    if (ratio >= required) {
+      passed++;
+      continue;
+    }
+    const foregroundHex = describeColor({ rgb: foreground, alpha: 1 }).hex;
+    const backgroundHex = describeColor({ rgb: background, alpha: 1 }).hex;
+    failures.push({
+      selector: element.selector,
+      text: element.text,
+      foreground: foregroundHex,
+      background: backgroundHex,
+      ratio: round(ratio, 2),
+      required,
+      large,
+      fontSize: element.fontSize,
+      fontWeight: element.fontWeight,
+      suggestion: suggestForeground(foreground, background, required)
+    });
+  }
+  failures.sort((a, b) => a.ratio - b.ratio);
+  return { url, level, checked: elements.length, passed, indeterminate, failures, truncated: elements.length >= MAX_AUDITED_ELEMENTS };
+}
+
+function setupCssHandlers(ipcMain, browserViews) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('css:convertColor', async (event, color) => convertColor(color));
+
+  ipcMain.handle('css:checkContrast', async (event, foreground, background, options) => checkContrast(foreground, background, options || {}));
+
+  ipcMain.handle('css:findColors', async (event, text, options) => findColors(text, options || {}));
+
+  ipcMain.handle('css:convertUnit', async (event, input, options) => convertUnit(input, options || {}));
+
+  ipcMain.handle('css:auditContrast', async (event, viewId, options) => auditContrast(browserViews, viewId, options || {}));
+}
+
+module.exports = { setupCssHandlers, parseColor, convertColor, checkContrast, findColors, convertUnit };
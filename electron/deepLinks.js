@@ -0,0 +1,101 @@
+const path = require('path');
+const { app, protocol } = require('electron');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode } = require('./errors');
+
+const log = createLogger('deep-links');
+
+const DEEP_LINK_SCHEME = 'forge';
+
+// Register forge:// with the OS. Unpackaged builds must pass the app path so the
+// link relaunches `electron .` rather than bare Electron.
+function registerDeepLinkProtocol() {
+  const registered = process.defaultApp && process.argv.length >= 2
+    ? app.setAsDefaultProtocolClient(DEEP_LINK_SCHEME, process.execPath, [path.resolve(process.argv[1])])
+    : app.setAsDefaultProtocolClient(DEEP_LINK_SCHEME);
+  if (!registered) log.warn(`Could not register ${DEEP_LINK_SCHEME}:// as the default protocol handler`);
+  return registered;
+}
+
+// Inside Forge, forge:// also serves internal pages (the browser start page). They need a
+// standard scheme to resolve relative URLs, and this must happen before the app is ready.
+function registerInternalScheme() {
+  protocol.registerSchemesAsPrivileged([{ scheme: DEEP_LINK_SCHEME, privileges: { standard: true, secure: true } }]);
+}
+
+function isDeepLink(value) {
+  return typeof value === 'string' && value.toLowerCase().startsWith(`${DEEP_LINK_SCHEME}://`);
+}
+
+function positiveInt(value) {
+  if (value === null || value === '') return undefined;
+  const number = Number(value);
+  return Number.isInteger(number) && number > 0 ? number : undefined;
+}
+
+// Links come from untrusted places (CI logs, issue trackers), so only absolute paths are
+// accepted and the only actions are opening and diffing — never running anything.
+function requireAbsolutePath(value, param) {
+  if (!value) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Deep link is missing the "${param}" parameter`);
+  }
+  // file:// URLs are common in tooling output
+  const filePath = value.startsWith('file://') ? decodeURIComponent(new URL(value).pathname) : value;
+  if (!path.isAbsolute(filePath)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Deep link path must be absolute: ${filePath}`);
+  }
+  return path.normalize(filePath);
+}
+
+// forge://open?path=/abs/file.ts&line=12&column=4
+// forge://diff?left=/abs/a&right=/abs/b
+function parseDeepLink(url) {
+  let parsed;
+  try {
+    parsed = new URL(url);
+  } catch {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Malformed deep link: ${url}`);
+  }
+  if (parsed.protocol !== `${DEEP_LINK_SCHEME}:`) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Not a ${DEEP_LINK_SCHEME}:// link: ${url}`);
+  }
+
+  // forge://open?... puts the action in the host; forge:open?... in the path
+  const action = (parsed.hostname || parsed.pathname.replace(/^\/+/, '')).toLowerCase();
+  const params = parsed.searchParams;
+
+  switch (action) {
+    case 'open':
+      return {
+        action,
+        target: {
+          path: requireAbsolutePath(params.get('path') || params.get('file'), 'path'),
+          line: positiveInt(params.get('line')),
+          column: positiveInt(params.get('column') || params.get('col'))
+        },
+        newWindow: params.get('newWindow') === 'true'
+      };
+    case 'diff':
+      return {
+        action,
+        left: requireAbsolutePath(params.get('left'), 'left'),
+        right: requireAbsolutePath(params.get('right'), 'right')
+      };
+    default:
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unsupported deep link action: ${action || '(none)'}`);
+  }
+}
+
+// Windows and Linux pass the link as a command-line argument
+function extractDeepLinks(argv) {
+  return argv.filter(isDeepLink);
+}
+
+module.exports = {
+  DEEP_LINK_SCHEME,
+  registerDeepLinkProtocol,
+  registerInternalScheme,
+  isDeepLink,
+  parseDeepLink,
+  extractDeepLinks
+};
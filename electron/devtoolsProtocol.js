@@ -0,0 +1,55 @@
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode } = require('./errors');
+
+const log = createLogger('devtools-protocol');
+
+const PROTOCOL_VERSION = '1.3';
+
+// webContents -> Set of holder names. A view has one protocol session, shared by every
+// feature that needs it (throttling, the automation bridge); it's detached when the last
+// holder lets go, so one feature finishing doesn't cut off another.
+const holders = new WeakMap();
+const watched = new WeakSet();
+
+// Attach to `webContents`' protocol session on behalf of `holder`. Returns true if this
+// call attached it.
+function acquireDebugger(webContents, holder) {
+  const { debugger: protocol } = webContents;
+  if (!watched.has(webContents)) {
+    watched.add(webContents);
+    // Closing the page or opening its DevTools can end the session under every holder
+    protocol.on('detach', (event, reason) => {
+      log.debug(`Protocol session detached: ${reason}`);
+      holders.delete(webContents);
+    });
+  }
+  let current = holders.get(webContents);
+  if (!current) {
+    current = new Set();
+    holders.set(webContents, current);
+  }
+  let attached = false;
+  if (!protocol.isAttached()) {
+    try {
+      protocol.attach(PROTOCOL_VERSION);
+      attached = true;
+    } catch (error) {
+      throw new ForgeError(ErrorCode.BUSY, `Can't open a DevTools protocol session: ${error.message}`);
+    }
+  }
+  current.add(holder);
+  return attached;
+}
+
+function releaseDebugger(webContents, holder) {
+  const current = holders.get(webContents);
+  if (!current || !current.delete(holder) || current.size > 0) return;
+  if (!webContents.isDestroyed() && webContents.debugger.isAttached()) webContents.debugger.detach();
+}
+
+function holdsDebugger(webContents, holder) {
+  const current = holders.get(webContents);
+  return Boolean(current && current.has(holder) && webContents.debugger.isAttached());
+}
+
+module.exports = { acquireDebugger, releaseDebugger, holdsDebugger };
@@ -0,0 +1,143 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { openPaths } = require('./openRouter');
+const { getTerminal } = require('./terminal');
+
+const log = createLogger('drag-drop');
+
+// What a drop does, per drop target:
+//   folder: 'openWorkspace' | 'openInNewWindow' | 'replaceWorkspace' | 'ignore'
+//   file:   'open' | 'ignore'
+//   terminal action: 'insertPath' | 'cd' | 'ignore'
+const DEFAULT_SETTINGS = {
+  workbench: { folder: 'openWorkspace', file: 'open' },
+  editor: { folder: 'openWorkspace', file: 'open' },
+  terminal: { action: 'insertPath', trailingSpace: true }
+};
+
+const store = new JsonStore('drag-drop', { settings: DEFAULT_SETTINGS });
+
+function getSettings() {
+  const saved = store.get('settings');
+  return Object.fromEntries(Object.entries(DEFAULT_SETTINGS).map(([target, defaults]) => [target, { ...defaults, ...saved[target] }]));
+}
+
+function updateSettings(changes) {
+  const current = getSettings();
+  for (const [target, values] of Object.entries(changes || {})) {
+    if (!DEFAULT_SETTINGS[target]) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown drop target: ${target}`);
+    }
+    current[target] = { ...current[target], ...values };
+  }
+  store.set('settings', current);
+  return current;
+}
+
+function shellKind(shell) {
+  const name = path.basename(shell || '').toLowerCase().replace(/\.exe$/, '');
+  if (name === 'powershell' || name === 'pwsh') return 'powershell';
+  if (name === 'cmd') return 'cmd';
+  return 'posix';
+}
+
+// Quote a path for the shell running in the terminal. Plain paths are left bare
+// so the common case reads naturally.
+function escapeForShell(filePath, shell) {
+  const kind = shellKind(shell);
+  if (/^[\w@%+=:,./\\-]+$/.test(filePath)) return filePath;
+  if (kind === 'powershell') return `'${filePath.replace(/'/g, "''")}'`;
+  if (kind === 'cmd') return `"${filePath.replace(/"/g, '""')}"`;
+  return `'${filePath.replace(/'/g, `'\\''`)}'`;
+}
+
+async function classify(paths) {
+  const folders = [];
+  const files = [];
+  for (const item of paths) {
+    const resolved = path.resolve(item);
+    try {
+      const stats = await fs.stat(resolved);
+      (stats.isDirectory() ? folders : files).push(resolved);
+    } catch (error) {
+      log.warn(`Skipping dropped path ${resolved}: ${error.message}`);
+    }
+  }
+  return { folders, files };
+}
+
+async function dropOnTerminal(terminalId, paths, settings) {
+  const session = getTerminal(terminalId);
+  if (!session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { id: terminalId } });
+  }
+  if (settings.action === 'ignore') return { action: 'ignore', paths: [] };
+
+  const escaped = paths.map((item) => escapeForShell(path.resolve(item), session.shell));
+  let text;
+  if (settings.action === 'cd' && paths.length === 1) {
+    const { folders } = await classify(paths);
+    text = folders.length === 1 ? `cd ${escaped[0]}` : escaped[0];
+  } else {
+    text = escaped.join(' ');
+  }
+  // Never append a newline: the user decides whether to run it
+  session.pty.write(settings.trailingSpace ? `${text} ` : text);
+  return { action: settings.action, paths: paths.map((item) => path.resolve(item)) };
+}
+
+async function dropOnWorkbench(win, paths, settings) {
+  const { folders, files } = await classify(paths);
+  const opened = [];
+
+  for (const folder of folders) {
+    if (settings.folder === 'ignore') continue;
+    if (settings.folder === 'replaceWorkspace' && win) {
+      windowManager.setWorkspace(win.id, folder);
+      opened.push({ path: folder, kind: 'folder', windowId: win.id });
+    } else {
+      opened.push(...await openPaths([{ path: folder }], { newWindow: settings.folder === 'openInNewWindow', source: 'drop' }));
+    }
+  }
+
+  if (files.length > 0 && settings.file === 'open') {
+    // Dropped files open where they were dropped, not in whichever window owns their workspace
+    opened.push(...await openPaths(files.map((file) => ({ path: file })), { windowId: win ? win.id : undefined, source: 'drop' }));
+  }
+  return { action: 'open', opened };
+}
+
+// Route a drop from the renderer. target is 'workbench', 'editor', or 'terminal'
+// (with terminalId); paths come from webUtils.getPathForFile in the preload.
+async function handleDrop(win, drop) {
+  if (!drop || !Array.isArray(drop.paths) || drop.paths.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A drop needs at least one path');
+  }
+  const settings = getSettings();
+  const target = drop.target || 'workbench';
+  if (!settings[target]) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown drop target: ${target}`);
+  }
+
+  log.debug(`Drop of ${drop.paths.length} path(s) on ${target}`);
+  if (target === 'terminal') return dropOnTerminal(drop.terminalId, drop.paths, settings.terminal);
+  return dropOnWorkbench(win, drop.paths, settings[target]);
+}
+
+function setupDragDropHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('dragDrop:drop', async (event, drop) => {
+    return handleDrop(windowManager.windowFromSender(event.sender), drop);
+  });
+
+  ipcMain.handle('dragDrop:getSettings', async () => getSettings());
+
+  ipcMain.handle('dragDrop:updateSettings', async (event, changes) => updateSettings(changes));
+}
+
+module.exports = { setupDragDropHandlers, handleDrop, escapeForShell };
@@ -0,0 +1,223 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { withForgeErrors } = require('./errors');
+
+const log = createLogger('editorconfig');
+
+const store = new JsonStore('editorconfig', {
+  // Apply trim_trailing_whitespace / insert_final_newline when saving
+  applyOnSave: true
+});
+
+// Parsed .editorconfig files keyed by path, invalidated by mtime
+const cache = new Map();
+
+// Properties whose values are case-insensitive per the spec
+const LOWERCASE_PROPERTIES = new Set(['indent_style', 'indent_size', 'tab_width', 'end_of_line', 'charset', 'trim_trailing_whitespace', 'insert_final_newline', 'root']);
+
+function parseEditorConfig(text) {
+  const result = { root: false, sections: [] };
+  let section = null;
+  for (const rawLine of text.split(/\r?\n/)) {
+    const line = rawLine.trim();
+    if (!line || line.startsWith('#') || line.startsWith(';')) continue;
+
+    const header = /^\[(.*)\]$/.exec(line);
+    if (header) {
+      section = { pattern: header[1], properties: {} };
+      result.sections.push(section);
+      continue;
+    }
+
+    const separator = line.indexOf('=');
+    if (separator < 0) continue;
+    const key = line.slice(0, separator).trim().toLowerCase();
+    let value = line.slice(separator + 1).trim();
+    if (LOWERCASE_PROPERTIES.has(key)) value = value.toLowerCase();
+
+    if (!section) {
+      if (key === 'root') result.root = value === 'true';
+    } else {
+      section.properties[key] = value;
+    }
+  }
+  return result;
+}
+
+// Translate an EditorConfig glob into a RegExp matched against '/'-separated paths
+function globToRegExp(glob) {
+  let source = '';
+  let braceDepth = 0;
+  for (let i = 0; i < glob.length; i++) {
+    const char = glob[i];
+    if (char === '\\') {
+      source += `\\${glob[++i] || '\\'}`;
+    } else if (char === '*') {
+      if (glob[i + 1] === '*') {
+        // "**/" may match zero directories
+        if (glob[i + 2] === '/') {
+          source += '(?:.*/)?';
+          i += 2;
+        } else {
+          source += '.*';
+          i += 1;
+        }
+      } else {
+        source += '[^/]*';
+      }
+    } else if (char === '?') {
+      source += '[^/]';
+    } else if (char === '[') {
+      const end = glob.indexOf(']', i + 1);
+      if (end < 0) {
+        source += '\\[';
+      } else {
+        const body = glob.slice(i + 1, end).replace(/\\/g, '\\\\');
+        source += body.startsWith('!') ? `[^${body.slice(1)}]` : `[${body}]`;
+        i = end;
+      }
+    } else if (char === '{') {
+      const end = glob.indexOf('}', i + 1);
+      const range = end > 0 ? /^(-?\d+)\.\.(-?\d+)$/.exec(glob.slice(i + 1, end)) : null;
+      if (range) {
+        const [low, high] = [Number(range[1]), Number(range[2])].sort((a, b) => a - b);
+        const numbers = [];
+        for (let n = low; n <= high; n++) numbers.push(n);
+        source += `(?:${numbers.join('|')})`;
+        i = end;
+      } else if (end < 0 || !glob.slice(i + 1, end).includes(',')) {
+        source += '\\{';
+      } else {
+        source += '(?:';
+        braceDepth += 1;
+      }
+    } else if (char === '}' && braceDepth > 0) {
+      source += ')';
+      braceDepth -= 1;
+    } else if (char === ',' && braceDepth > 0) {
+      source += '|';
+    } else {
+      source += char.replace(/[.+^$()|]/g, '\\$&');
+    }
+  }
+  return new RegExp(`^${source}$`);
+}
+
+function sectionMatches(pattern, relativePath) {
+  // Patterns without a slash match at any depth; others are anchored to the config's folder
+  const anchored = pattern.includes('/') ? pattern.replace(/^\//, '') : `**/${pattern}`;
+  return globToRegExp(anchored).test(relativePath);
+}
+
+async function loadConfigFile(configPath) {
+  let stats;
+  try {
+    stats = await fs.stat(configPath);
+  } catch {
+    cache.delete(configPath);
+    return null;
+  }
+  const cached = cache.get(configPath);
+  if (cached && cached.mtimeMs === stats.mtimeMs) return cached.config;
+
+  const config = parseEditorConfig(await fs.readFile(configPath, 'utf-8'));
+  cache.set(configPath, { mtimeMs: stats.mtimeMs, config });
+  return config;
+}
+
+// Resolve the effective EditorConfig properties for a file
+async function resolveEditorConfig(filePath) {
+  const absolutePath = path.resolve(filePath);
+
+  // Collect configs from the file's folder up to the nearest root=true
+  const configs = [];
+  let dir = path.dirname(absolutePath);
+  while (true) {
+    const configPath = path.join(dir, '.editorconfig');
+    const config = await loadConfigFile(configPath);
+    if (config) {
+      configs.unshift({ dir, configPath, config });
+      if (config.root) break;
+    }
+    const parent = path.dirname(dir);
+    if (parent === dir) break;
+    dir = parent;
+  }
+
+  // Closer files and later sections win
+  const properties = {};
+  for (const { dir: configDir, config } of configs) {
+    const relativePath = path.relative(configDir, absolutePath).split(path.sep).join('/');
+    for (const section of config.sections) {
+      if (sectionMatches(section.pattern, relativePath)) {
+        Object.assign(properties, section.properties);
+      }
+    }
+  }
+
+  // Spec defaults: indent_size=tab uses tab_width; tab_width defaults to indent_size
+  if (properties.indent_style === 'tab' && !properties.indent_size) properties.indent_size = 'tab';
+  if (properties.indent_size === 'tab' && properties.tab_width) properties.indent_size = properties.tab_width;
+  if (properties.indent_size && properties.indent_size !== 'tab' && !properties.tab_width) properties.tab_width = properties.indent_size;
+
+  const toNumber = (value) => (value && /^\d+$/.test(value) ? Number(value) : null);
+  const toBoolean = (value) => (value === 'true' ? true : value === 'false' ? false : null);
+
+  return {
+    indentStyle: ['tab', 'space'].includes(properties.indent_style) ? properties.indent_style : null,
+    indentSize: toNumber(properties.indent_size),
+    tabWidth: toNumber(properties.tab_width),
+    endOfLine: ['lf', 'crlf', 'cr'].includes(properties.end_of_line) ? properties.end_of_line : null,
+    charset: properties.charset && properties.charset !== 'unset' ? properties.charset : null,
+    trimTrailingWhitespace: toBoolean(properties.trim_trailing_whitespace),
+    insertFinalNewline: toBoolean(properties.insert_final_newline),
+    maxLineLength: toNumber(properties.max_line_length),
+    sources: configs.map(({ configPath }) => configPath),
+    properties
+  };
+}
+
+// Save-time transform for trim_trailing_whitespace and insert_final_newline
+async function applyEditorConfigOnSave(filePath, content) {
+  if (!store.get('applyOnSave') || typeof content !== 'string') return content;
+
+  let config;
+  try {
+    config = await resolveEditorConfig(filePath);
+  } catch (error) {
+    log.warn(`Could not resolve .editorconfig for ${filePath}: ${error.message}`);
+    return content;
+  }
+
+  let result = content;
+  if (config.trimTrailingWhitespace) {
+    result = result.replace(/[ \t]+(?=\r?\n|$)/g, '');
+  }
+  if (config.insertFinalNewline === true && result.length > 0 && !/\n$/.test(result)) {
+    result += config.endOfLine === 'crlf' ? '\r\n' : config.endOfLine === 'cr' ? '\r' : '\n';
+  } else if (config.insertFinalNewline === false) {
+    result = result.replace(/(?:\r?\n|\r)+$/, '');
+  }
+  return result;
+}
+
+function setupEditorConfigHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('editorconfig:resolve', async (event, filePath) => {
+    return resolveEditorConfig(filePath);
+  });
+
+  ipcMain.handle('editorconfig:getApplyOnSave', async () => {
+    return store.get('applyOnSave');
+  });
+
+  ipcMain.handle('editorconfig:setApplyOnSave', async (event, enabled) => {
+    store.set('applyOnSave', Boolean(enabled));
+    return Boolean(enabled);
+  });
+}
+
+module.exports = { setupEditorConfigHandlers, resolveEditorConfig, applyEditorConfigOnSave, globToRegExp };
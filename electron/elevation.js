@@ -0,0 +1,183 @@
+const fs = require('fs').promises;
+const { constants: fsConstants } = require('fs');
+const os = require('os');
+const path = require('path');
+const { execFile } = require('child_process');
+const { dialog } = require('electron');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode } = require('./errors');
+const { windowManager } = require('./windows');
+
+const log = createLogger('elevation');
+
+// Long enough for the user to find and answer the OS authentication prompt
+const ELEVATION_TIMEOUT = 5 * 60 * 1000;
+const PERMISSION_CODES = new Set(['EACCES', 'EPERM', 'EROFS']);
+// sudo askpass helpers commonly shipped by desktop environments
+const ASKPASS_CANDIDATES = ['ksshaskpass', 'ssh-askpass', 'x11-ssh-askpass', 'lxqt-openssh-askpass', 'ssh-askpass-fullscreen'];
+
+function run(command, args, options = {}) {
+  return new Promise((resolve, reject) => {
+    execFile(command, args, { timeout: ELEVATION_TIMEOUT, windowsHide: true, ...options }, (error, stdout, stderr) => {
+      if (error) {
+        error.stderr = String(stderr || '');
+        reject(error);
+        return;
+      }
+      resolve(stdout);
+    });
+  });
+}
+
+async function findExecutable(name) {
+  const dirs = (process.env.PATH || '').split(path.delimiter).filter(Boolean);
+  for (const dir of [...dirs, '/usr/lib/ssh', '/usr/libexec/openssh']) {
+    const candidate = path.join(dir, name);
+    try {
+      await fs.access(candidate, fsConstants.X_OK);
+      return candidate;
+    } catch {
+      // Keep looking
+    }
+  }
+  return null;
+}
+
+async function findAskpass() {
+  if (process.env.SUDO_ASKPASS) return process.env.SUDO_ASKPASS;
+  for (const name of ASKPASS_CANDIDATES) {
+    const found = await findExecutable(name);
+    if (found) return found;
+  }
+  return null;
+}
+
+// The mechanism available on this machine, or null when elevation isn't possible
+async function detectElevationMethod() {
+  if (process.platform === 'darwin') return 'osascript';
+  if (process.platform === 'win32') return 'uac';
+  if (await findExecutable('pkexec')) return 'pkexec';
+  if (await findExecutable('sudo') && await findAskpass()) return 'sudo-askpass';
+  return null;
+}
+
+// Whether the current user can write `filePath` (or create it in its directory)
+async function getWriteAccess(filePath) {
+  let exists = true;
+  let writable;
+  try {
+    await fs.access(filePath, fsConstants.W_OK);
+    writable = true;
+  } catch (error) {
+    if (error.code === 'ENOENT') {
+      exists = false;
+      writable = await fs.access(path.dirname(filePath), fsConstants.W_OK).then(() => true, () => false);
+    } else {
+      writable = false;
+    }
+  }
+  const method = writable ? null : await detectElevationMethod();
+  return { path: filePath, exists, writable, readOnly: !writable, elevationAvailable: Boolean(method), method };
+}
+
+function quotePowerShell(value) {
+  return `'${String(value).replace(/'/g, "''")}'`;
+}
+
+// Copy `source` over `target` with administrator rights. Copying onto an existing file
+// keeps its owner and mode, so /etc files stay root-owned.
+async function copyElevated(method, source, target) {
+  switch (method) {
+    case 'pkexec':
+      return run('pkexec', ['/bin/cp', '--', source, target]);
+    case 'sudo-askpass':
+      return run('sudo', ['-A', '--', '/bin/cp', '--', source, target], { env: { ...process.env, SUDO_ASKPASS: await findAskpass() } });
+    case 'osascript':
+      // Paths travel as argv so nothing is interpolated into the AppleScript source
+      return run('osascript', [
+        '-e', 'on run argv',
+        '-e', 'do shell script "/bin/cp -- " & quoted form of item 1 of argv & " " & quoted form of item 2 of argv with administrator privileges',
+        '-e', 'end run',
+        source, target
+      ]);
+    case 'uac': {
+      const inner = `Copy-Item -LiteralPath ${quotePowerShell(source)} -Destination ${quotePowerShell(target)} -Force -ErrorAction Stop`;
+      const encoded = Buffer.from(inner, 'utf16le').toString('base64');
+      const outer = 'try { $p = Start-Process -FilePath powershell.exe -Verb RunAs -Wait -PassThru -WindowStyle Hidden ' +
+        `-ArgumentList '-NoProfile','-NonInteractive','-EncodedCommand','${encoded}'; exit $p.ExitCode } catch { exit 1223 }`;
+      return run('powershell.exe', ['-NoProfile', '-NonInteractive', '-Command', outer]);
+    }
+    default:
+      throw new ForgeError(ErrorCode.PERMISSION_DENIED, 'No way to request administrator rights is available on this system', { recoverable: false });
+  }
+}
+
+// pkexec: 126 dismissed, 127 not authorized; osascript: -128 user canceled; UAC: 1223 declined
+function isDeclined(method, error) {
+  if (method === 'pkexec') return error.code === 126 || error.code === 127;
+  if (method === 'osascript') return /-128|User canceled/i.test(error.stderr);
+  if (method === 'uac') return error.code === 1223;
+  return /no password was provided|incorrect password/i.test(error.stderr);
+}
+
+async function confirmElevation(sender, filePath) {
+  const win = windowManager.windowFromSender(sender);
+  const options = {
+    type: 'warning',
+    buttons: ['Retry as Administrator', 'Cancel'],
+    defaultId: 0,
+    cancelId: 1,
+    message: `You don't have permission to save ${path.basename(filePath)}`,
+    detail: `${filePath} is not writable by your account. Forge can save it with administrator rights; your system will ask you to authenticate.`
+  };
+  const { response } = win ? await dialog.showMessageBox(win, options) : await dialog.showMessageBox(options);
+  return response === 0;
+}
+
+// Save `content` to `filePath`, and if that fails for lack of permission, ask the user
+// and retry through the platform's elevation prompt. Resolves { saved, elevated }.
+async function saveWithElevation(filePath, content, { sender, confirm = true } = {}) {
+  try {
+    await fs.writeFile(filePath, content, 'utf-8');
+    return { saved: true, elevated: false };
+  } catch (error) {
+    if (!PERMISSION_CODES.has(error.code)) throw ForgeError.from(error, 'Failed to write file');
+    if (error.code === 'EROFS') {
+      throw new ForgeError(ErrorCode.PERMISSION_DENIED, `${filePath} is on a read-only filesystem`, { details: { path: filePath }, recoverable: false });
+    }
+  }
+
+  const method = await detectElevationMethod();
+  if (!method) {
+    throw new ForgeError(ErrorCode.PERMISSION_DENIED, `Permission denied saving ${filePath}, and no elevation helper (pkexec or a sudo askpass program) was found`, {
+      details: { path: filePath },
+      recoverable: false
+    });
+  }
+  if (confirm && !await confirmElevation(sender, filePath)) {
+    throw new ForgeError(ErrorCode.CANCELLED, 'Save cancelled');
+  }
+
+  // Stage the content somewhere only we can read, then copy it into place elevated
+  const stagingDir = await fs.mkdtemp(path.join(os.tmpdir(), 'forge-save-'));
+  const stagedFile = path.join(stagingDir, path.basename(filePath));
+  try {
+    await fs.writeFile(stagedFile, content, { encoding: 'utf-8', mode: 0o600 });
+    await copyElevated(method, stagedFile, filePath);
+    log.info(`Saved ${filePath} with elevation (${method})`);
+    return { saved: true, elevated: true, method };
+  } catch (error) {
+    if (error instanceof ForgeError) throw error;
+    if (isDeclined(method, error)) {
+      throw new ForgeError(ErrorCode.CANCELLED, 'Authentication was cancelled or refused', { details: { path: filePath, method } });
+    }
+    throw new ForgeError(ErrorCode.PERMISSION_DENIED, `Elevated save failed: ${(error.stderr || error.message).trim()}`, {
+      details: { path: filePath, method },
+      cause: error
+    });
+  } finally {
+    await fs.rm(stagingDir, { recursive: true, force: true }).catch(() => {});
+  }
+}
+
+module.exports = { saveWithElevation, getWriteAccess, detectElevationMethod };
@@ -0,0 +1,338 @@
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { app } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob, cancelJob } = require('./jobs');
+const { aiManager } = require('./ai');
+const { readTextFile } = require('./search');
+const { ensureIndex, getIndexedFiles, indexEvents } = require('./fileIndex');
+const { isPathIgnored } = require('./indexScope');
+
+const log = createLogger('embeddings');
+
+// Source and prose files worth embedding; everything else is skipped
+const EMBEDDABLE_EXTENSIONS = new Set([
+  '.js', '.jsx', '.mjs', '.cjs', '.ts', '.tsx', '.py', '.rs', '.go', '.java', '.kt', '.rb', '.php',
+  '.c', '.h', '.cc', '.cpp', '.hpp', '.cs', '.swift', '.scala', '.lua', '.sh', '.sql',
+  '.css', '.scss', '.html', '.vue', '.svelte', '.md', '.mdx', '.txt', '.yaml', '.yml', '.toml'
+]);
+const MAX_EMBED_FILE_SIZE = 512 * 1024;
+const REFRESH_DELAY = 5000;
+const PREVIEW_CHARS = 300;
+
+const store = new JsonStore('embeddings', {
+  settings: {
+    // null uses the AI layer's default provider
+    providerId: null,
+    model: null,
+    chunkLines: 60,
+    overlapLines: 10,
+    batchSize: 32
+  }
+});
+
+// Workspace root -> { root, model, dims, chunks: [{ path, startLine, endLine, hash, mtimeMs }],
+//                     vectors: Float32Array[], dirty: Set, refreshTimer, jobId }
+const indexes = new Map();
+
+function getSettings() {
+  return store.get('settings');
+}
+
+function indexDir() {
+  return path.join(app.getPath('userData'), 'embeddings');
+}
+
+function indexFiles(root) {
+  const id = crypto.createHash('sha1').update(root).digest('hex').slice(0, 16);
+  return { meta: path.join(indexDir(), `${id}.json`), vectors: path.join(indexDir(), `${id}.bin`) };
+}
+
+function normalize(vector) {
+  const result = Float32Array.from(vector);
+  let norm = 0;
+  for (let i = 0; i < result.length; i++) norm += result[i] * result[i];
+  norm = Math.sqrt(norm) || 1;
+  for (let i = 0; i < result.length; i++) result[i] /= norm;
+  return result;
+}
+
+function dot(a, b) {
+  let sum = 0;
+  for (let i = 0; i < a.length; i++) sum += a[i] * b[i];
+  return sum;
+}
+
+// Overlapping line windows; each chunk is prefixed with its path so the model sees where it lives
+function chunkFile(filePath, text, settings) {
+  const lines = text.split('\n');
+  const chunks = [];
+  const step = Math.max(1, settings.chunkLines - settings.overlapLines);
+  for (let start = 0; start < lines.length; start += step) {
+    const end = Math.min(lines.length, start + settings.chunkLines);
+    const body = lines.slice(start, end).join('\n');
+    if (body.trim()) {
+      chunks.push({ path: filePath, startLine: start + 1, endLine: end, text: body, hash: crypto.createHash('sha1').update(body).digest('hex') });
+    }
+    if (end === lines.length) break;
+  }
+  return chunks;
+}
+
+async function loadIndex(root) {
+  const files = indexFiles(root);
+  try {
+    const meta = JSON.parse(await fs.readFile(files.meta, 'utf-8'));
+    const buffer = await fs.readFile(files.vectors);
+    const all = new Float32Array(buffer.buffer, buffer.byteOffset, buffer.byteLength / 4);
+    const vectors = meta.chunks.map((chunk, i) => all.slice(i * meta.dims, (i + 1) * meta.dims));
+    return { root, model: meta.model, providerId: meta.providerId, dims: meta.dims, chunks: meta.chunks, vectors, builtAt: meta.builtAt };
+  } catch {
+    return null;
+  }
+}
+
+async function saveIndex(index) {
+  const files = indexFiles(index.root);
+  await fs.mkdir(indexDir(), { recursive: true });
+  const all = new Float32Array(index.vectors.length * index.dims);
+  index.vectors.forEach((vector, i) => all.set(vector, i * index.dims));
+  await fs.writeFile(`${files.vectors}.tmp`, Buffer.from(all.buffer));
+  await fs.rename(`${files.vectors}.tmp`, files.vectors);
+  const meta = { root: index.root, model: index.model, providerId: index.providerId, dims: index.dims, chunks: index.chunks, builtAt: index.builtAt };
+  await fs.writeFile(`${files.meta}.tmp`, JSON.stringify(meta));
+  await fs.rename(`${files.meta}.tmp`, files.meta);
+}
+
+function isEmbeddable(filePath) {
+  return EMBEDDABLE_EXTENSIONS.has(path.extname(filePath).toLowerCase());
+}
+
+// Embed `files` into `index`, reusing vectors for chunks whose text hasn't changed
+async function embedFiles(index, files, handle) {
+  const settings = getSettings();
+  const reusable = new Map();
+  index.chunks.forEach((chunk, i) => reusable.set(chunk.hash, index.vectors[i]));
+
+  const fileSet = new Set(files);
+  const keptChunks = [];
+  const keptVectors = [];
+  index.chunks.forEach((chunk, i) => {
+    if (!fileSet.has(chunk.path)) {
+      keptChunks.push(chunk);
+      keptVectors.push(index.vectors[i]);
+    }
+  });
+
+  const toEmbed = [];
+  let processed = 0;
+  for (const filePath of files) {
+    handle.throwIfCancelled();
+    processed++;
+    let stats;
+    try {
+      stats = await fs.stat(filePath);
+    } catch {
+      continue; // Deleted since it was queued
+    }
+    if (stats.size > MAX_EMBED_FILE_SIZE) continue;
+    const text = await readTextFile(filePath);
+    if (!text) continue;
+    for (const chunk of chunkFile(filePath, text, settings)) {
+      const entry = { path: chunk.path, startLine: chunk.startLine, endLine: chunk.endLine, hash: chunk.hash, mtimeMs: stats.mtimeMs };
+      const vector = reusable.get(chunk.hash);
+      if (vector) {
+        keptChunks.push(entry);
+        keptVectors.push(vector);
+      } else {
+        toEmbed.push({ entry, text: `${path.relative(index.root, filePath)}\n${chunk.text}` });
+      }
+    }
+    if (processed % 200 === 0) handle.reportProgress(null, `Chunked ${processed}/${files.length} files`);
+  }
+
+  for (let i = 0; i < toEmbed.length; i += settings.batchSize) {
+    handle.throwIfCancelled();
+    const batch = toEmbed.slice(i, i + settings.batchSize);
+    const { vectors, model, providerId } = await aiManager.embed(batch.map((item) => item.text), {
+      providerId: settings.providerId || undefined,
+      model: settings.model || undefined,
+      signal: handle.signal
+    });
+    if (index.model && (index.model !== model || index.providerId !== providerId)) {
+      throw new ForgeError(ErrorCode.CONFLICT, 'The embedding model changed; rebuild the index', { details: { was: index.model, now: model } });
+    }
+    index.model = model;
+    index.providerId = providerId;
+    index.dims = vectors[0].length;
+    batch.forEach((item, j) => {
+      keptChunks.push(item.entry);
+      keptVectors.push(normalize(vectors[j]));
+    });
+    handle.reportProgress(Math.min(1, (i + batch.length) / toEmbed.length), `Embedded ${i + batch.length}/${toEmbed.length} chunks`);
+  }
+
+  index.chunks = keptChunks;
+  index.vectors = keptVectors;
+  index.builtAt = new Date().toISOString();
+  await saveIndex(index);
+  return { files: files.length, embedded: toEmbed.length, chunks: keptChunks.length };
+}
+
+function startJob(index, title, files) {
+  if (index.jobId) cancelJob(index.jobId);
+  const job = runJob('embeddings', title, (handle) => embedFiles(index, files, handle));
+  index.jobId = job.id;
+  job.promise
+    .then((result) => log.info(`Embeddings for ${index.root}: ${result.embedded} new chunks, ${result.chunks} total`))
+    .catch((error) => {
+      if (error.code !== ErrorCode.CANCELLED) log.warn(`Embedding ${index.root} failed: ${error.message}`);
+    })
+    .finally(() => {
+      if (index.jobId === job.id) index.jobId = null;
+    });
+  return job;
+}
+
+// Build (or incrementally update) the semantic index for a workspace
+async function indexWorkspace(rootPath, options = {}) {
+  const root = path.resolve(rootPath);
+  let index = indexes.get(root);
+  if (!index) {
+    index = (!options.rebuild && await loadIndex(root)) || { root, model: null, providerId: null, dims: 0, chunks: [], vectors: [], builtAt: null };
+    index.dirty = new Set();
+    index.refreshTimer = null;
+    index.jobId = null;
+    indexes.set(root, index);
+  } else if (options.rebuild) {
+    Object.assign(index, { model: null, providerId: null, dims: 0, chunks: [], vectors: [] });
+  }
+
+  const fileIndex = ensureIndex(root);
+  await fileIndex.ready;
+  const files = getIndexedFiles(root).filter(isEmbeddable);
+  const job = startJob(index, `Build semantic index for ${path.basename(root)}`, files);
+  return { jobId: job.id, files: files.length };
+}
+
+// Re-embed changed files a few seconds after edits settle
+function markDirty(root, filePath) {
+  const index = indexes.get(root);
+  if (!index || !isEmbeddable(filePath)) return;
+  index.dirty.add(filePath);
+  clearTimeout(index.refreshTimer);
+  index.refreshTimer = setTimeout(() => {
+    if (index.jobId) {
+      markDirty(root, filePath);
+      return;
+    }
+    const files = Array.from(index.dirty);
+    index.dirty.clear();
+    startJob(index, `Update semantic index for ${path.basename(root)}`, files);
+  }, REFRESH_DELAY);
+}
+
+indexEvents.on('added', markDirty);
+indexEvents.on('changed', markDirty);
+indexEvents.on('removed', markDirty);
+
+async function semanticSearch(rootPath, query, options = {}) {
+  if (typeof query !== 'string' || !query.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A search query is required');
+  }
+  const root = path.resolve(rootPath);
+  let index = indexes.get(root);
+  if (!index) {
+    const loaded = await loadIndex(root);
+    if (!loaded) {
+      throw new ForgeError(ErrorCode.NOT_FOUND, `No semantic index for ${root}; build it first`, { details: { rootPath: root } });
+    }
+    index = Object.assign(loaded, { dirty: new Set(), refreshTimer: null, jobId: null });
+    indexes.set(root, index);
+  }
+  if (index.vectors.length === 0) return [];
+
+  const { vectors } = await aiManager.embed([query], { providerId: index.providerId, model: index.model });
+  const queryVector = normalize(vectors[0]);
+  if (queryVector.length !== index.dims) {
+    throw new ForgeError(ErrorCode.CONFLICT, 'Query embedding does not match the index; rebuild it');
+  }
+
+  const limit = options.limit || 20;
+  const scored = index.vectors
+    .map((vector, i) => ({ i, score: dot(queryVector, vector) }))
+    .filter((entry) => entry.score >= (options.minScore || 0) && !isPathIgnored(root, index.chunks[entry.i].path, 'file'))
+    .sort((a, b) => b.score - a.score)
+    .slice(0, limit);
+
+  const results = [];
+  for (const { i, score } of scored) {
+    const chunk = index.chunks[i];
+    const text = await readTextFile(chunk.path);
+    const preview = text ? text.split('\n').slice(chunk.startLine - 1, chunk.endLine).join('\n').slice(0, PREVIEW_CHARS) : '';
+    results.push({
+      path: chunk.path,
+      relativePath: path.relative(root, chunk.path).split(path.sep).join('/'),
+      startLine: chunk.startLine,
+      endLine: chunk.endLine,
+      score,
+      preview
+    });
+  }
+  return results;
+}
+
+async function getStatus(rootPath) {
+  const root = path.resolve(rootPath);
+  const index = indexes.get(root) || await loadIndex(root);
+  if (!index) return { indexed: false };
+  return {
+    indexed: index.vectors.length > 0,
+    chunks: index.chunks.length,
+    files: new Set(index.chunks.map((chunk) => chunk.path)).size,
+    model: index.model,
+    providerId: index.providerId,
+    dims: index.dims,
+    builtAt: index.builtAt,
+    jobId: index.jobId || null,
+    pendingFiles: index.dirty ? index.dirty.size : 0
+  };
+}
+
+async function clearIndex(rootPath) {
+  const root = path.resolve(rootPath);
+  const index = indexes.get(root);
+  if (index) {
+    if (index.jobId) cancelJob(index.jobId);
+    clearTimeout(index.refreshTimer);
+    indexes.delete(root);
+  }
+  const files = indexFiles(root);
+  await Promise.all([fs.rm(files.meta, { force: true }), fs.rm(files.vectors, { force: true })]);
+  return true;
+}
+
+function setupEmbeddingHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('embeddings:index', async (event, rootPath, options = {}) => indexWorkspace(rootPath, options));
+
+  ipcMain.handle('embeddings:search', async (event, rootPath, query, options = {}) => semanticSearch(rootPath, query, options));
+
+  ipcMain.handle('embeddings:getStatus', async (event, rootPath) => getStatus(rootPath));
+
+  ipcMain.handle('embeddings:clear', async (event, rootPath) => clearIndex(rootPath));
+
+  ipcMain.handle('embeddings:getSettings', async () => getSettings());
+
+  ipcMain.handle('embeddings:updateSettings', async (event, changes) => {
+    const settings = { ...getSettings(), ...changes };
+    store.set('settings', settings);
+    return settings;
+  });
+}
+
+module.exports = { setupEmbeddingHandlers, indexWorkspace, semanticSearch };
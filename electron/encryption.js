@@ -0,0 +1,313 @@
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { spawn } = require('child_process');
+const { safeStorage } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob } = require('./jobs');
+
+const log = createLogger('encryption');
+
+// Kinds of locally persisted data that may hold sensitive content (source text, command
+// history) and can be encrypted at rest
+const CATEGORIES = ['editorSessions', 'terminalLogs', 'recovery'];
+const PROVIDERS = ['aes-gcm', 'age'];
+
+// Sealed payloads: "FORGEENC" + format version + provider id + provider payload.
+// Anything without the magic is legacy plaintext and read as-is.
+const MAGIC = Buffer.from('FORGEENC');
+const FORMAT_VERSION = 1;
+const PROVIDER_IDS = { 'aes-gcm': 1, age: 2 };
+const HEADER_LENGTH = MAGIC.length + 2;
+const AGE_TIMEOUT = 60 * 1000;
+
+const store = new JsonStore('encryption', {
+  provider: 'aes-gcm',
+  categories: { editorSessions: false, terminalLogs: false, recovery: false },
+  // Data key for aes-gcm, itself encrypted with the OS keychain (safeStorage)
+  wrappedKey: null,
+  age: { recipients: [], identityFile: null }
+});
+
+let dataKey = null;
+// category -> [async (reseal) => { migrated, failed }]
+const migrations = new Map();
+
+function getDataKey(create) {
+  if (dataKey) return dataKey;
+  const wrapped = store.get('wrappedKey');
+  if (!safeStorage.isEncryptionAvailable()) {
+    throw new ForgeError(ErrorCode.ACCESS_DENIED, 'The OS keychain is not available to hold the encryption key', { recoverable: false });
+  }
+  if (wrapped) {
+    dataKey = Buffer.from(safeStorage.decryptString(Buffer.from(wrapped, 'base64')), 'base64');
+  } else if (create) {
+    dataKey = crypto.randomBytes(32);
+    store.set('wrappedKey', safeStorage.encryptString(dataKey.toString('base64')).toString('base64'));
+    log.info('Generated a new data encryption key');
+  } else {
+    throw new ForgeError(ErrorCode.ACCESS_DENIED, 'No encryption key is stored on this machine', { recoverable: false });
+  }
+  return dataKey;
+}
+
+// iv (12) + auth tag (16) + ciphertext
+function aesEncrypt(plaintext) {
+  const iv = crypto.randomBytes(12);
+  const cipher = crypto.createCipheriv('aes-256-gcm', getDataKey(true), iv);
+  const ciphertext = Buffer.concat([cipher.update(plaintext), cipher.final()]);
+  return Buffer.concat([iv, cipher.getAuthTag(), ciphertext]);
+}
+
+function aesDecrypt(payload) {
+  const decipher = crypto.createDecipheriv('aes-256-gcm', getDataKey(false), payload.subarray(0, 12));
+  decipher.setAuthTag(payload.subarray(12, 28));
+  return Buffer.concat([decipher.update(payload.subarray(28)), decipher.final()]);
+}
+
+// Pipe `input` through the age CLI
+function runAge(args, input) {
+  return new Promise((resolve, reject) => {
+    const child = spawn('age', args, { windowsHide: true, timeout: AGE_TIMEOUT });
+    const stdout = [];
+    let stderr = '';
+    child.stdout.on('data', (chunk) => stdout.push(chunk));
+    child.stderr.on('data', (chunk) => { stderr += chunk; });
+    child.on('error', (error) => {
+      reject(error.code === 'ENOENT'
+        ? new ForgeError(ErrorCode.NOT_FOUND, 'The age executable was not found on PATH', { recoverable: false })
+        : error);
+    });
+    child.on('close', (code) => {
+      if (code === 0) {
+        resolve(Buffer.concat(stdout));
+      } else {
+        reject(new ForgeError(ErrorCode.INTERNAL, `age exited with code ${code}: ${stderr.trim()}`));
+      }
+    });
+    child.stdin.end(input);
+  });
+}
+
+function ageEncrypt(plaintext) {
+  const { recipients } = store.get('age');
+  if (!recipients.length) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'age encryption needs at least one recipient');
+  }
+  return runAge(recipients.flatMap((recipient) => ['-r', recipient]), plaintext);
+}
+
+function ageDecrypt(payload) {
+  const { identityFile } = store.get('age');
+  if (!identityFile) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Decrypting age data needs an identity file');
+  }
+  return runAge(['-d', '-i', identityFile], payload);
+}
+
+function isSealed(buffer) {
+  return buffer.length >= HEADER_LENGTH && buffer.subarray(0, MAGIC.length).equals(MAGIC);
+}
+
+function isEncrypted(category) {
+  return Boolean(store.get('categories')[category]);
+}
+
+// Encrypt `data` (string or Buffer) if `category` is configured for it; otherwise return it unchanged
+async function seal(category, data) {
+  const plaintext = Buffer.isBuffer(data) ? data : Buffer.from(data, 'utf-8');
+  if (!isEncrypted(category)) return plaintext;
+  const provider = store.get('provider');
+  const payload = provider === 'age' ? await ageEncrypt(plaintext) : aesEncrypt(plaintext);
+  return Buffer.concat([MAGIC, Buffer.from([FORMAT_VERSION, PROVIDER_IDS[provider]]), payload]);
+}
+
+// Decrypt a sealed buffer with whichever provider sealed it; plaintext passes through
+async function open(buffer) {
+  if (!isSealed(buffer)) return buffer;
+  const version = buffer[MAGIC.length];
+  if (version > FORMAT_VERSION) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Encrypted data uses format ${version}, newer than this version of Forge supports`);
+  }
+  const payload = buffer.subarray(HEADER_LENGTH);
+  try {
+    switch (buffer[MAGIC.length + 1]) {
+      case PROVIDER_IDS['aes-gcm']:
+        return aesDecrypt(payload);
+      case PROVIDER_IDS.age:
+        return await ageDecrypt(payload);
+      default:
+        throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Encrypted with an unknown provider');
+    }
+  } catch (error) {
+    if (error instanceof ForgeError) throw error;
+    throw new ForgeError(ErrorCode.ACCESS_DENIED, `Could not decrypt data: ${error.message}`, { cause: error });
+  }
+}
+
+async function writeProtectedFile(category, filePath, text) {
+  await fs.mkdir(path.dirname(filePath), { recursive: true });
+  await fs.writeFile(filePath, await seal(category, text));
+}
+
+async function readProtectedFile(filePath) {
+  return (await open(await fs.readFile(filePath))).toString('utf-8');
+}
+
+// Store records (electron-store / JsonStore values) can't hold binary, so sealed records
+// become { sealed: base64 } alongside any `clear` fields needed to look them up
+async function sealRecord(category, value, clear = {}) {
+  if (!isEncrypted(category)) return value;
+  return { ...clear, sealed: (await seal(category, JSON.stringify(value))).toString('base64') };
+}
+
+async function openRecord(record) {
+  if (!record || typeof record.sealed !== 'string') return record;
+  return JSON.parse((await open(Buffer.from(record.sealed, 'base64'))).toString('utf-8'));
+}
+
+// Subsystems holding data in `category` register how to rewrite it in place. `migrate`
+// receives `reseal(buffer) -> buffer` and resolves { migrated, failed }.
+function registerMigration(category, migrate) {
+  if (!migrations.has(category)) migrations.set(category, []);
+  migrations.get(category).push(migrate);
+}
+
+// Migration for a flat directory of protected files (optionally filtered by `filter(name)`)
+function migrateDirectory(dir, filter = () => true) {
+  return async (reseal) => {
+    const names = await fs.readdir(dir).catch(() => []);
+    let migrated = 0;
+    let failed = 0;
+    for (const name of names.filter(filter)) {
+      const filePath = path.join(dir, name);
+      try {
+        const current = await fs.readFile(filePath);
+        const next = await reseal(current);
+        if (next.equals(current)) continue;
+        await fs.writeFile(`${filePath}.tmp`, next);
+        await fs.rename(`${filePath}.tmp`, filePath);
+        migrated++;
+      } catch (error) {
+        log.warn(`Could not migrate ${filePath}: ${error.message}`);
+        failed++;
+      }
+    }
+    return { migrated, failed };
+  };
+}
+
+// Re-encrypt (or decrypt) existing data so it matches the current settings
+function startMigration(categories = CATEGORIES) {
+  const job = runJob('encryption-migrate', 'Migrating stored data encryption', async (handle) => {
+    const summary = {};
+    for (const [index, category] of categories.entries()) {
+      handle.throwIfCancelled();
+      handle.reportProgress(index / categories.length, category);
+      const reseal = async (buffer) => {
+        // Leave data already in the target form untouched
+        const sealed = isSealed(buffer);
+        if (!sealed && !isEncrypted(category)) return buffer;
+        if (sealed && isEncrypted(category) && buffer[MAGIC.length + 1] === PROVIDER_IDS[store.get('provider')]) return buffer;
+        return seal(category, await open(buffer));
+      };
+      summary[category] = { migrated: 0, failed: 0 };
+      for (const migrate of migrations.get(category) || []) {
+        const result = await migrate(reseal);
+        summary[category].migrated += result.migrated;
+        summary[category].failed += result.failed;
+      }
+    }
+    log.info('Encryption migration finished', summary);
+    return summary;
+  }, { emitResult: true });
+  // Callers only hand out the job id; the outcome reaches the renderer through the job's events
+  job.promise.catch((error) => {
+    if (error.code !== ErrorCode.CANCELLED) log.warn(`Encryption migration failed: ${error.message}`);
+  });
+  return job;
+}
+
+function getStatus() {
+  const age = store.get('age');
+  return {
+    provider: store.get('provider'),
+    categories: { ...store.get('categories') },
+    age: { recipients: age.recipients, identityFile: age.identityFile },
+    keychainAvailable: safeStorage.isEncryptionAvailable(),
+    hasKey: Boolean(store.get('wrappedKey'))
+  };
+}
+
+async function configure(changes = {}) {
+  if (changes.provider !== undefined) {
+    if (!PROVIDERS.includes(changes.provider)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown encryption provider: ${changes.provider}`);
+    }
+    store.set('provider', changes.provider);
+  }
+  if (changes.age !== undefined) {
+    const current = store.get('age');
+    const recipients = changes.age.recipients !== undefined ? changes.age.recipients : current.recipients;
+    if (!Array.isArray(recipients) || recipients.some((recipient) => typeof recipient !== 'string' || !recipient.trim())) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'age recipients must be a list of public keys');
+    }
+    const identityFile = changes.age.identityFile !== undefined ? changes.age.identityFile : current.identityFile;
+    if (identityFile !== null && (typeof identityFile !== 'string' || !path.isAbsolute(identityFile))) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'The age identity file must be an absolute path');
+    }
+    store.set('age', { recipients: recipients.map((recipient) => recipient.trim()), identityFile });
+  }
+  if (changes.categories !== undefined) {
+    const categories = { ...store.get('categories') };
+    for (const [category, enabled] of Object.entries(changes.categories)) {
+      if (!CATEGORIES.includes(category)) {
+        throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown data category: ${category}`);
+      }
+      categories[category] = Boolean(enabled);
+    }
+    store.set('categories', categories);
+  }
+
+  // Fail now rather than on the next save if the chosen provider can't encrypt
+  if (Object.values(store.get('categories')).some(Boolean)) {
+    await seal(CATEGORIES.find(isEncrypted), 'forge');
+  }
+  log.info(`Encryption configured: ${store.get('provider')}`, store.get('categories'));
+  return getStatus();
+}
+
+function setupEncryptionHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('encryption:getStatus', async () => getStatus());
+
+  // Existing data is migrated in the background unless `migrate` is false
+  ipcMain.handle('encryption:configure', async (event, changes, options = {}) => {
+    const status = await configure(changes);
+    const migrationJobId = options.migrate === false ? null : startMigration().id;
+    return { ...status, migrationJobId };
+  });
+
+  ipcMain.handle('encryption:migrate', async (event, categories) => {
+    if (categories !== undefined && (!Array.isArray(categories) || categories.some((category) => !CATEGORIES.includes(category)))) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Categories must be among: ${CATEGORIES.join(', ')}`);
+    }
+    return { jobId: startMigration(categories).id };
+  });
+}
+
+module.exports = {
+  setupEncryptionHandlers,
+  seal,
+  open,
+  isSealed,
+  writeProtectedFile,
+  readProtectedFile,
+  sealRecord,
+  openRecord,
+  registerMigration,
+  migrateDirectory
+};
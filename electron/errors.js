@@ -0,0 +1,118 @@
+// Unified error type for IPC handlers. Electron only forwards `message` across
+// ipcMain.handle (and contextBridge drops custom properties), so the structured
+// error is encoded into the message and decoded by parseForgeError in the renderer.
+
+const ERROR_MARKER = 'ForgeError:';
+
+const ErrorCode = {
+  NOT_FOUND: 'NOT_FOUND',
+  ALREADY_EXISTS: 'ALREADY_EXISTS',
+  ACCESS_DENIED: 'ACCESS_DENIED',
+  PERMISSION_DENIED: 'PERMISSION_DENIED',
+  INVALID_ARGUMENT: 'INVALID_ARGUMENT',
+  IS_DIRECTORY: 'IS_DIRECTORY',
+  NOT_DIRECTORY: 'NOT_DIRECTORY',
+  NO_SPACE: 'NO_SPACE',
+  BUSY: 'BUSY',
+  CONFLICT: 'CONFLICT',
+  IO_ERROR: 'IO_ERROR',
+  TIMEOUT: 'TIMEOUT',
+  CANCELLED: 'CANCELLED',
+  TERMINAL_NOT_FOUND: 'TERMINAL_NOT_FOUND',
+  TERMINAL_SPAWN_FAILED: 'TERMINAL_SPAWN_FAILED',
+  BROWSER_VIEW_NOT_FOUND: 'BROWSER_VIEW_NOT_FOUND',
+  WINDOW_UNAVAILABLE: 'WINDOW_UNAVAILABLE',
+  SESSION_NOT_FOUND: 'SESSION_NOT_FOUND',
+  // A guarded operation needs the user's go-ahead; details carry a confirmationToken
+  CONFIRMATION_REQUIRED: 'CONFIRMATION_REQUIRED',
+  INTERNAL: 'INTERNAL'
+};
+
+// Errors the user can usually fix and retry
+const RECOVERABLE_CODES = new Set([
+  ErrorCode.NOT_FOUND,
+  ErrorCode.ALREADY_EXISTS,
+  ErrorCode.PERMISSION_DENIED,
+  ErrorCode.INVALID_ARGUMENT,
+  ErrorCode.NO_SPACE,
+  ErrorCode.BUSY,
+  ErrorCode.CONFLICT,
+  ErrorCode.TIMEOUT,
+  ErrorCode.WINDOW_UNAVAILABLE,
+  ErrorCode.CONFIRMATION_REQUIRED
+]);
+
+// Node errno -> ForgeError code
+const ERRNO_CODES = {
+  ENOENT: ErrorCode.NOT_FOUND,
+  EEXIST: ErrorCode.ALREADY_EXISTS,
+  EACCES: ErrorCode.PERMISSION_DENIED,
+  EPERM: ErrorCode.PERMISSION_DENIED,
+  EROFS: ErrorCode.PERMISSION_DENIED,
+  EISDIR: ErrorCode.IS_DIRECTORY,
+  ENOTDIR: ErrorCode.NOT_DIRECTORY,
+  ENOTEMPTY: ErrorCode.INVALID_ARGUMENT,
+  ENOSPC: ErrorCode.NO_SPACE,
+  EBUSY: ErrorCode.BUSY,
+  ETIMEDOUT: ErrorCode.TIMEOUT
+};
+
+class ForgeError extends Error {
+  constructor(code, message, options = {}) {
+    super(message);
+    this.name = 'ForgeError';
+    this.code = code;
+    this.details = options.details;
+    this.recoverable = options.recoverable !== undefined ? options.recoverable : RECOVERABLE_CODES.has(code);
+    if (options.cause) {
+      this.cause = options.cause;
+    }
+  }
+
+  toJSON() {
+    return {
+      code: this.code,
+      message: this.message,
+      details: this.details,
+      recoverable: this.recoverable
+    };
+  }
+
+  // Wrap any thrown value, mapping Node errno codes where possible
+  static from(error, context, fallbackCode = ErrorCode.INTERNAL) {
+    if (error instanceof ForgeError) {
+      return error;
+    }
+    const err = error instanceof Error ? error : new Error(String(error));
+    const code = (err.code && ERRNO_CODES[err.code]) || fallbackCode;
+    const message = context ? `${context}: ${err.message}` : err.message;
+    return new ForgeError(code, message, {
+      details: err.code ? { errno: err.code, path: err.path, syscall: err.syscall } : undefined,
+      cause: err
+    });
+  }
+}
+
+function encodeForIpc(error) {
+  const forgeError = ForgeError.from(error);
+  return new Error(`${ERROR_MARKER}${JSON.stringify(forgeError.toJSON())}`);
+}
+
+// Wrap ipcMain so every handler rejects with an encoded ForgeError
+function withForgeErrors(ipcMain) {
+  return {
+    handle(channel, handler) {
+      ipcMain.handle(channel, async (...args) => {
+        try {
+          return await handler(...args);
+        } catch (error) {
+          throw encodeForIpc(error);
+        }
+      });
+    },
+    removeHandler: (channel) => ipcMain.removeHandler(channel),
+    on: (...args) => ipcMain.on(...args)
+  };
+}
+
+module.exports = { ForgeError, ErrorCode, withForgeErrors, encodeForIpc, ERROR_MARKER };
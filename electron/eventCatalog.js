@@ -0,0 +1,380 @@
+// Typed catalog of every event the main process emits to renderers.
+// Field types: string | number | boolean | object | array | any, suffix "?" for optional.
+// `replay` events are kept in the replay buffer for reloading webviews.
+// `throttleMs` coalesces bursts per channel (and per `throttleKey` field when given),
+// delivering the latest payload at the end of each window.
+//
+// Run `bun run generate:events` after editing to refresh the TypeScript types.
+
+const EVENT_CATALOG = {
+  'terminal:data': {
+    description: 'Output bytes from a terminal PTY',
+    fields: { terminalId: 'string', data: 'array' },
+    replay: false
+  },
+  'terminal:exit': {
+    description: 'A terminal process exited',
+    fields: { terminalId: 'string', exitCode: 'number' },
+    replay: true
+  },
+  'fs:fileChanged': {
+    description: 'A watched file changed on disk; renamed files carry their new path',
+    fields: { path: 'string', type: 'string', newPath: 'string?' },
+    replay: true
+  },
+  'browser:navigate': {
+    description: 'A browser view navigated to a new URL',
+    fields: { id: 'string', url: 'string' },
+    replay: true
+  },
+  'browser:titleUpdate': {
+    description: 'A browser view page title changed',
+    fields: { id: 'string', title: 'string' },
+    replay: true
+  },
+  'browser:loadStart': {
+    description: 'A browser view started loading',
+    fields: { id: 'string' },
+    replay: false
+  },
+  'browser:loadStop': {
+    description: 'A browser view finished loading',
+    fields: { id: 'string' },
+    replay: true
+  },
+  'browser:newTabRequest': {
+    description: 'A page asked to open a URL in a new tab',
+    fields: { url: 'string', disposition: 'string' },
+    replay: false
+  },
+  'updater:status': {
+    description: 'Auto-updater status change or download progress',
+    fields: { status: 'string', data: 'any?' },
+    replay: true,
+    throttleMs: 250,
+    throttleKey: 'status'
+  },
+  'app:recoveredFromError': {
+    description: 'Forge captured an error and kept running',
+    fields: { reportId: 'string', source: 'string', message: 'string' },
+    replay: true
+  },
+  'job:started': {
+    description: 'A background job started',
+    fields: { id: 'string', kind: 'string', title: 'string', status: 'string', cancellable: 'boolean', progress: 'number?', message: 'string?', startedAt: 'string' },
+    replay: true
+  },
+  'job:progress': {
+    description: 'Progress update for a running job (0..1, or null when indeterminate)',
+    fields: { id: 'string', progress: 'number?', message: 'string?' },
+    replay: false,
+    throttleMs: 100,
+    throttleKey: 'id'
+  },
+  'job:completed': {
+    description: 'A background job succeeded, failed, or was cancelled',
+    fields: { id: 'string', status: 'string', error: 'object?', result: 'any?' },
+    replay: true
+  },
+  'trust:changed': {
+    description: 'A workspace trust decision changed',
+    fields: { path: 'string', trusted: 'boolean', decidedAt: 'string?', inherited: 'boolean' },
+    replay: true
+  },
+  'refactor:applied': {
+    description: 'A multi-file edit was applied and recorded in the undo journal',
+    fields: { journalId: 'string', description: 'string', paths: 'array' },
+    replay: false
+  },
+  'todos:updated': {
+    description: 'The TODO index for a workspace changed',
+    fields: { rootPath: 'string', count: 'number', indexing: 'boolean' },
+    replay: true,
+    throttleMs: 500,
+    throttleKey: 'rootPath'
+  },
+  'clipboard:changed': {
+    description: 'A new or re-used entry moved to the top of the clipboard history',
+    fields: { id: 'string', text: 'string', preview: 'string', hasHtml: 'boolean', source: 'string', pinned: 'boolean', createdAt: 'string', lastUsedAt: 'string' },
+    replay: false,
+    throttleMs: 250
+  },
+  'notification:clicked': {
+    description: 'A native notification (or one of its action buttons) was clicked',
+    fields: { id: 'string', action: 'string?', data: 'any?' },
+    replay: false
+  },
+  'window:opened': {
+    description: 'A workbench window was opened',
+    fields: { windowId: 'number', workspaceId: 'string', workspacePath: 'string?' },
+    replay: false
+  },
+  'window:closed': {
+    description: 'A workbench window was closed',
+    fields: { windowId: 'number', workspaceId: 'string' },
+    replay: false
+  },
+  'terminal:attached': {
+    description: 'A live terminal was moved into this window, with its scrollback for repainting',
+    fields: { terminalId: 'string', fromWindowId: 'number', shell: 'string', cwd: 'string', restricted: 'boolean', cols: 'number', rows: 'number', scrollback: 'string' },
+    replay: false
+  },
+  'terminal:detached': {
+    description: 'A terminal was moved out of this window',
+    fields: { terminalId: 'string', toWindowId: 'number' },
+    replay: false
+  },
+  'editor:attached': {
+    description: 'An editor was moved into this window from another one',
+    fields: { fromWindowId: 'number?', editor: 'object' },
+    replay: false
+  },
+  'workbench:openFile': {
+    description: 'Open a file in this window, optionally at a position. waitId is set when a caller waits for it to close',
+    fields: { path: 'string', line: 'number?', column: 'number?', exists: 'boolean', waitId: 'string?', source: 'string' },
+    replay: false
+  },
+  'workbench:openDiff': {
+    description: 'Open a side-by-side diff of two files or folders in this window. sessionId names a compare session holding the result; for unsaved text, right is its label',
+    fields: { left: 'string', right: 'string', waitId: 'string?', sessionId: 'string?', source: 'string' },
+    replay: false
+  },
+  'ai:token': {
+    description: 'Streamed text from an in-flight AI request, batched every few milliseconds',
+    fields: { requestId: 'string', delta: 'string' },
+    replay: false
+  },
+  'ai:done': {
+    description: 'An AI request finished streaming',
+    fields: { requestId: 'string', content: 'string', finishReason: 'string?', usage: 'object?', providerId: 'string', model: 'string' },
+    replay: false
+  },
+  'ai:error': {
+    description: 'An AI request failed or was cancelled',
+    fields: { requestId: 'string', code: 'string', message: 'string' },
+    replay: false
+  },
+  'automation:recordingChanged': {
+    description: 'A macro recording started, stopped, or captured another action',
+    fields: { recording: 'boolean', name: 'string?', actionCount: 'number' },
+    replay: true,
+    throttleMs: 100
+  },
+  'watcher:health': {
+    description: 'A file watcher changed state: healthy, polling, rescanning, degraded, or failed',
+    fields: { root: 'string', owner: 'string', status: 'string', mode: 'string', fsType: 'string?', reason: 'string?', hint: 'string?', lastRescanAt: 'string?' },
+    replay: true,
+    throttleMs: 250,
+    throttleKey: 'root'
+  },
+  'index:status': {
+    description: 'Progress and staleness of a workspace file or symbol index; stale while a cached index is being verified',
+    fields: { rootPath: 'string', index: 'string', state: 'string', stale: 'boolean', fromCache: 'boolean', processed: 'number?', total: 'number?', count: 'number', savedAt: 'string?' },
+    replay: false
+  },
+  'liveShare:viewers': {
+    description: 'The number of viewers watching a live share session changed',
+    fields: { sessionId: 'string', viewers: 'number' },
+    replay: true,
+    throttleMs: 250,
+    throttleKey: 'sessionId'
+  },
+  'liveShare:ended': {
+    description: 'A live share session stopped: by request, with its window, or because its terminal exited',
+    fields: { sessionId: 'string', reason: 'string' },
+    replay: false
+  },
+  'collab:ops': {
+    description: 'CRDT operations from a collaborator or a disk reload, for the host editor to apply; changes are plain-text edits as applied to the shared copy',
+    fields: { sessionId: 'string', path: 'string', peerId: 'string', ops: 'array', changes: 'array?' },
+    replay: false
+  },
+  'collab:participants': {
+    description: 'Someone joined or left a collaboration session',
+    fields: { sessionId: 'string', participants: 'array' },
+    replay: true
+  },
+  'collab:presence': {
+    description: 'A collaborator moved their cursor or selection',
+    fields: { sessionId: 'string', path: 'string', peerId: 'string', name: 'string', selection: 'any?' },
+    replay: false,
+    throttleMs: 50,
+    throttleKey: 'peerId'
+  },
+  'collab:diskChanged': {
+    description: 'A shared file changed on disk while it had unsaved collaborative edits; the host should save or reload',
+    fields: { sessionId: 'string', path: 'string' },
+    replay: false
+  },
+  'collab:ended': {
+    description: 'A collaboration session stopped',
+    fields: { sessionId: 'string', reason: 'string' },
+    replay: false
+  },
+  'tunnel:status': {
+    description: 'A tunnel is starting, open with a public URL, closed, or failed',
+    fields: { id: 'string', port: 'number', host: 'string', provider: 'string', status: 'string', url: 'string?', error: 'string?', startedAt: 'string' },
+    replay: true
+  },
+  'task:status': {
+    description: 'A task in a pipeline run changed status',
+    fields: { runId: 'string', pipeline: 'string?', task: 'string', status: 'string', attempt: 'number?', exitCode: 'number?', durationMs: 'number?', allowed: 'boolean?' },
+    replay: true
+  },
+  'task:output': {
+    description: 'Output from a running pipeline task',
+    fields: { runId: 'string', task: 'string', stream: 'string', data: 'string' },
+    replay: false
+  },
+  'task:runFinished': {
+    description: 'A task or pipeline run finished',
+    fields: { runId: 'string', jobId: 'string', pipeline: 'string?', status: 'string', tasks: 'object', startedAt: 'string', finishedAt: 'string' },
+    replay: true
+  },
+  'problems:changed': {
+    description: 'The problems reported by one source changed',
+    fields: { source: 'string', count: 'number', errors: 'number', warnings: 'number' },
+    replay: true,
+    throttleMs: 200,
+    throttleKey: 'source'
+  },
+  'commandHistory:added': {
+    description: 'A command run in a terminal was added to the history',
+    fields: { id: 'string', command: 'string', cwd: 'string?', exitCode: 'number?', durationMs: 'number?', terminalId: 'string', shell: 'string?', startedAt: 'string?', finishedAt: 'string' },
+    replay: false
+  },
+  'bookmarks:changed': {
+    description: 'Directory bookmarks were added, renamed, used or removed',
+    fields: { count: 'number' },
+    replay: true
+  },
+  'terminal:image': {
+    description: 'An inline image (Sixel, iTerm2 or Kitty) finished decoding in terminal output',
+    fields: { terminalId: 'string', imageId: 'string', protocol: 'string', data: 'string', width: 'number', height: 'number', placement: 'object' },
+    replay: false
+  },
+  'terminal:mouseMode': {
+    description: 'Mouse reporting requested by a terminal program, or its forwarding setting, changed',
+    fields: { terminalId: 'string', tracking: 'string?', encoding: 'string', forwarding: 'boolean', active: 'boolean' },
+    replay: false
+  },
+  'terminal:screenChanged': {
+    description: 'A terminal switched between the normal and alternate screen buffer',
+    fields: { terminalId: 'string', altScreen: 'boolean' },
+    replay: false
+  },
+  'terminalAnnotations:changed': {
+    description: 'Annotations on a terminal output were added, removed or trimmed',
+    fields: { terminalId: 'string', count: 'number' },
+    replay: false,
+    throttleMs: 200,
+    throttleKey: 'terminalId'
+  },
+  'watcher:changes': {
+    description: 'A batch of file changes matching one watch subscription',
+    fields: { subscriptionId: 'string', root: 'string', changes: 'array', overflow: 'boolean' },
+    replay: false
+  },
+  'saveHooks:completed': {
+    description: 'Save hooks finished for a saved file',
+    fields: { path: 'string', hooks: 'array' },
+    replay: false
+  },
+  'decorations:changed': {
+    description: 'File decorations in a workspace were assigned or removed',
+    fields: { rootPath: 'string', paths: 'array' },
+    replay: false
+  },
+  'favorites:changed': {
+    description: 'Favorites were pinned, unpinned or reordered. workspaceId is null for global pins and reordering',
+    fields: { workspaceId: 'string?' },
+    replay: false
+  },
+  'browser:bookmarksChanged': {
+    description: 'Bookmarks in a browser profile were added, changed, or removed',
+    fields: { profile: 'string' },
+    replay: false
+  },
+  'browser:console': {
+    description: 'A browser view frame logged to the console or threw an uncaught error',
+    fields: { id: 'number', viewId: 'string', windowId: 'number', timestamp: 'string', kind: 'string', level: 'string', message: 'string', source: 'string?', line: 'number?', column: 'number?', stack: 'string?', frameId: 'number?', frameUrl: 'string?', frameOrigin: 'string?', frameName: 'string?', isMainFrame: 'boolean' },
+    replay: false
+  },
+  'browser:navigationBlocked': {
+    description: 'A browser view page tried to leave the navigation policy of its window and was stopped',
+    fields: { id: 'string', url: 'string', host: 'string?', reason: 'string', disposition: 'string?' },
+    replay: false
+  },
+  'browser:throttlingChanged': {
+    description: 'Network or CPU throttling of a browser view was changed or ended',
+    fields: { id: 'string', network: 'object?', cpuRate: 'number' },
+    replay: false
+  },
+  'browser:automationChanged': {
+    description: 'The automation bridge started or stopped, or a client attached to or detached from a browser view',
+    fields: { running: 'boolean', port: 'number?', endpoint: 'string?', wsEndpoint: 'string?', clients: 'number', controlled: 'array' },
+    replay: false
+  },
+  'browser:actionRecorded': {
+    description: 'A user action in a recording browser view was captured',
+    fields: { id: 'string', index: 'number', action: 'object' },
+    replay: false
+  },
+  'mock:status': {
+    description: 'The workspace mock server started, stopped, or reloaded its routes',
+    fields: { running: 'boolean', root: 'string', port: 'number?', url: 'string?', routes: 'array', error: 'string?', requests: 'number' },
+    replay: false
+  },
+  'mock:request': {
+    description: 'The mock server answered a request',
+    fields: { root: 'string', method: 'string', path: 'string', status: 'number', routeIndex: 'number?', duration: 'number' },
+    replay: false
+  },
+  'graphql:subscriptionData': {
+    description: 'A GraphQL subscription delivered a result',
+    fields: { subscriptionId: 'string', data: 'any?', errors: 'array?', receivedAt: 'string' },
+    replay: false
+  },
+  'graphql:subscriptionEnded': {
+    description: 'A GraphQL subscription completed, failed, or was stopped',
+    fields: { subscriptionId: 'string', reason: 'string', error: 'string?' },
+    replay: false
+  },
+  'ci:status': {
+    description: 'CI status of a watched repository changed (runs, overall state, or an error)',
+    fields: { repoRoot: 'string', state: 'string', branch: 'string?', sha: 'string?', runs: 'array', pullRequest: 'object?', checkedAt: 'string', error: 'string?' },
+    replay: true
+  },
+  'ci:log': {
+    description: 'New output from a followed CI job log',
+    fields: { streamId: 'string', jobId: 'string', chunk: 'string' },
+    replay: false
+  },
+  'ci:logEnded': {
+    description: 'A followed CI job log finished, failed, or was stopped',
+    fields: { streamId: 'string', jobId: 'string', status: 'string?', error: 'string?' },
+    replay: false
+  },
+  'notebook:output': {
+    description: 'A running notebook cell produced output (nbformat output shape)',
+    fields: { kernelId: 'string', msgId: 'string', cellId: 'string?', output: 'object' },
+    replay: false
+  },
+  'notebook:kernelStatus': {
+    description: 'A notebook kernel changed state (starting, idle, busy, restarting or dead)',
+    fields: { kernelId: 'string', status: 'string', message: 'string?' },
+    replay: true
+  },
+  'logViewer:lines': {
+    description: 'Lines appended to a followed log file or source that pass the view\'s filters',
+    fields: { viewId: 'string', lines: 'array', skippedBytes: 'number?', skippedEntries: 'number?' },
+    replay: false
+  },
+  'logViewer:status': {
+    description: 'A log view\'s file was rotated, truncated or went missing, its source stopped, or following paused or resumed',
+    fields: { viewId: 'string', status: 'string', message: 'string?' },
+    replay: false
+  }
+};
+
+module.exports = { EVENT_CATALOG };
@@ -0,0 +1,147 @@
+const { BrowserWindow } = require('electron');
+const { EVENT_CATALOG } = require('./eventCatalog');
+const { createLogger } = require('./logger');
+
+const log = createLogger('events');
+
+const REPLAY_BUFFER_SIZE = 500;
+const REPLAY_TTL = 60 * 1000;
+
+let sequence = 0;
+// { envelope, targetId } where targetId is the webContents an event was sent to, or null
+// for events broadcast to every window
+const replayBuffer = [];
+
+// Pending throttled payloads keyed by channel + throttle key + target
+const throttled = new Map();
+
+function matchesType(value, type) {
+  const optional = type.endsWith('?');
+  const baseType = optional ? type.slice(0, -1) : type;
+  if (value === undefined || value === null) return optional || baseType === 'any';
+  switch (baseType) {
+    case 'any': return true;
+    case 'array': return Array.isArray(value);
+    case 'object': return typeof value === 'object' && !Array.isArray(value);
+    default: return typeof value === baseType;
+  }
+}
+
+// Check a payload against the catalog. Mismatches are logged, not thrown,
+// so a schema slip never breaks event delivery.
+function validatePayload(channel, payload) {
+  const definition = EVENT_CATALOG[channel];
+  if (!definition) {
+    log.warn(`Emitting uncatalogued event: ${channel}`);
+    return;
+  }
+  for (const [field, type] of Object.entries(definition.fields)) {
+    if (!matchesType(payload ? payload[field] : undefined, type)) {
+      log.warn(`Event ${channel} field "${field}" does not match type ${type}`);
+    }
+  }
+}
+
+function deliver(channel, payload, target) {
+  const definition = EVENT_CATALOG[channel] || {};
+  const envelope = {
+    seq: ++sequence,
+    channel,
+    timestamp: Date.now(),
+    payload
+  };
+
+  if (definition.replay) {
+    replayBuffer.push({ envelope, targetId: target ? target.id : null });
+    if (replayBuffer.length > REPLAY_BUFFER_SIZE) {
+      replayBuffer.splice(0, replayBuffer.length - REPLAY_BUFFER_SIZE);
+    }
+  }
+
+  const message = payload && typeof payload === 'object' && !Array.isArray(payload)
+    ? { ...payload, seq: envelope.seq }
+    : payload;
+
+  const targets = target ? [target] : BrowserWindow.getAllWindows().map((win) => win.webContents);
+  for (const contents of targets) {
+    if (contents && !contents.isDestroyed()) {
+      contents.send(channel, message);
+    }
+  }
+}
+
+// Emit a catalogued event to every window, or to a single webContents via options.target
+function emit(channel, payload, options = {}) {
+  if (log.isEnabled('debug')) {
+    validatePayload(channel, payload);
+  }
+
+  const definition = EVENT_CATALOG[channel];
+  if (!definition || !definition.throttleMs) {
+    deliver(channel, payload, options.target);
+    return;
+  }
+
+  // Events for different windows never coalesce into each other
+  const targetKey = options.target ? options.target.id : '*';
+  const key = `${channel}:${definition.throttleKey && payload ? payload[definition.throttleKey] : ''}:${targetKey}`;
+  const pending = throttled.get(key);
+  if (pending) {
+    pending.payload = payload;
+    return;
+  }
+
+  // Leading edge delivers immediately; later payloads in the window are coalesced
+  deliver(channel, payload, options.target);
+  const entry = { payload: undefined, target: options.target };
+  throttled.set(key, entry);
+  setTimeout(() => {
+    throttled.delete(key);
+    if (entry.payload !== undefined) {
+      deliver(channel, entry.payload, entry.target);
+    }
+  }, definition.throttleMs);
+}
+
+// Broadcast events plus those sent to `targetId`; without a targetId, broadcasts only
+function getReplay(sinceSeq = 0, channels, targetId = null) {
+  const cutoff = Date.now() - REPLAY_TTL;
+  return replayBuffer
+    .filter(({ envelope, targetId: sentTo }) =>
+      envelope.seq > sinceSeq &&
+      envelope.timestamp >= cutoff &&
+      (!channels || channels.includes(envelope.channel)) &&
+      (sentTo === null || sentTo === targetId)
+    )
+    .map(({ envelope }) => envelope);
+}
+
+function getCatalog() {
+  return Object.fromEntries(
+    Object.entries(EVENT_CATALOG).map(([channel, definition]) => [
+      channel,
+      {
+        description: definition.description,
+        fields: definition.fields,
+        replay: Boolean(definition.replay),
+        throttleMs: definition.throttleMs || 0
+      }
+    ])
+  );
+}
+
+function setupEventHandlers(ipcMain) {
+  ipcMain.handle('events:getCatalog', async () => {
+    return getCatalog();
+  });
+
+  ipcMain.handle('events:replay', async (event, sinceSeq, channels) => {
+    return getReplay(sinceSeq, channels, event.sender.id);
+  });
+
+  ipcMain.handle('events:getLastSeq', async () => {
+    return sequence;
+  });
+}
+
+module.exports = { setupEventHandlers, emit, getReplay, getCatalog };
@@ -0,0 +1,156 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+
+const log = createLogger('favorites');
+
+const MAX_FAVORITES = 500;
+const KINDS = ['file', 'directory', 'terminalProfile'];
+
+const store = new JsonStore('favorites', {
+  // { id, kind, path | profileId, label, workspaceId (null: every workspace), pinnedAt }
+  // in the order the working set shows them
+  items: []
+});
+
+function publish(workspaceId) {
+  emit('favorites:changed', { workspaceId: workspaceId || null });
+}
+
+function workspaceOf(sender) {
+  const context = sender ? windowManager.fromSender(sender) : null;
+  return context ? context.workspaceId : null;
+}
+
+const sameTarget = (a, b) => a.kind === b.kind && a.workspaceId === b.workspaceId &&
+  (a.kind === 'terminalProfile' ? a.profileId === b.profileId : a.path === b.path);
+
+// The working set for a workspace: its own pins and the global ones, in order. Files and
+// folders carry `exists` so the panel can dim ones that were moved or deleted.
+async function listFavorites(workspaceId) {
+  const items = store.get('items').filter((item) => item.workspaceId === null || item.workspaceId === workspaceId);
+  return Promise.all(items.map(async (item) => {
+    if (item.kind === 'terminalProfile') return item;
+    const exists = await fs.stat(item.path).then(() => true, () => false);
+    return { ...item, exists };
+  }));
+}
+
+// Pin a file, folder ({ path }) or terminal profile ({ kind: 'terminalProfile', profileId,
+// label }). Files and folders are told apart on disk when `kind` is omitted. Pins belong to
+// `workspaceId`, or every workspace when it's null. Pinning something again returns the
+// existing pin.
+async function pinFavorite(target, workspaceId) {
+  if (!target || typeof target !== 'object') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A favorite needs a path or a terminal profile');
+  if (target.kind !== undefined && !KINDS.includes(target.kind)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Kind must be one of ${KINDS.join(', ')}`);
+  }
+  const label = typeof target.label === 'string' && target.label.trim() ? target.label.trim() : null;
+  let favorite;
+  if (target.kind === 'terminalProfile') {
+    if (typeof target.profileId !== 'string' || !target.profileId) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A terminal profile favorite needs a profileId');
+    }
+    favorite = { kind: 'terminalProfile', profileId: target.profileId, label: label || target.profileId };
+  } else {
+    if (typeof target.path !== 'string' || !path.isAbsolute(target.path)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Favorites need an absolute path');
+    }
+    const resolved = path.resolve(target.path);
+    const stats = await fs.stat(resolved).catch((error) => {
+      throw ForgeError.from(error, `Cannot pin ${resolved}`);
+    });
+    const kind = stats.isDirectory() ? 'directory' : 'file';
+    if (target.kind && target.kind !== kind) {
+      throw new ForgeError(kind === 'directory' ? ErrorCode.IS_DIRECTORY : ErrorCode.NOT_DIRECTORY, `${resolved} is not a ${target.kind}`);
+    }
+    favorite = { kind, path: resolved, label: label || path.basename(resolved) || resolved };
+  }
+  favorite.workspaceId = workspaceId || null;
+
+  const items = store.get('items');
+  const existing = items.find((item) => sameTarget(item, favorite));
+  if (existing) return existing;
+  if (items.length >= MAX_FAVORITES) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `At most ${MAX_FAVORITES} favorites can be pinned`);
+  }
+  const pinned = { id: `favorite_${uuidv4()}`, ...favorite, pinnedAt: new Date().toISOString() };
+  store.set('items', [...items, pinned]);
+  log.info(`Pinned ${pinned.kind} ${pinned.path || pinned.profileId}`);
+  publish(pinned.workspaceId);
+  return pinned;
+}
+
+function unpinFavorite(id) {
+  const items = store.get('items');
+  const favorite = items.find((item) => item.id === id);
+  if (!favorite) return false;
+  store.set('items', items.filter((item) => item.id !== id));
+  publish(favorite.workspaceId);
+  return true;
+}
+
+// Move the given favorites into this order; ones not listed keep their places after them
+function reorderFavorites(ids) {
+  if (!Array.isArray(ids)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A list of favorite ids is required');
+  const items = store.get('items');
+  const byId = new Map(items.map((item) => [item.id, item]));
+  const ordered = ids.filter((id) => byId.has(id)).map((id) => byId.get(id));
+  const rest = items.filter((item) => !ids.includes(item.id));
+  store.set('items', [...ordered, ...rest]);
+  publish(null);
+  return ordered.length;
+}
+
+// A workspace's own pins for a workspace state bundle; paths are tokenized by the caller
+function exportFavorites(workspaceId) {
+  return store.get('items')
+    .filter((item) => item.workspaceId === workspaceId)
+    .map(({ kind, path: itemPath, profileId, label, pinnedAt }) => ({ kind, path: itemPath, profileId, label, pinnedAt }));
+}
+
+// Add pins from a workspace state bundle to `workspaceId`, skipping ones already pinned
+function importFavorites(favorites, workspaceId) {
+  const items = store.get('items');
+  let added = 0;
+  for (const favorite of Array.isArray(favorites) ? favorites : []) {
+    if (!favorite || !KINDS.includes(favorite.kind) || items.length >= MAX_FAVORITES) continue;
+    const isProfile = favorite.kind === 'terminalProfile';
+    const target = isProfile ? favorite.profileId : favorite.path;
+    if (typeof target !== 'string' || !target || (!isProfile && !path.isAbsolute(target))) continue;
+    const candidate = {
+      kind: favorite.kind,
+      ...(isProfile ? { profileId: target } : { path: path.resolve(target) }),
+      label: typeof favorite.label === 'string' && favorite.label ? favorite.label : isProfile ? target : path.basename(target),
+      workspaceId: workspaceId || null
+    };
+    if (items.some((item) => sameTarget(item, candidate))) continue;
+    items.push({ id: `favorite_${uuidv4()}`, ...candidate, pinnedAt: typeof favorite.pinnedAt === 'string' ? favorite.pinnedAt : new Date().toISOString() });
+    added++;
+  }
+  store.set('items', items);
+  if (added > 0) publish(workspaceId);
+  return added;
+}
+
+function setupFavoriteHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('favorites:list', async (event) => listFavorites(workspaceOf(event.sender)));
+
+  // `global` pins for every workspace instead of the calling window's
+  ipcMain.handle('favorites:pin', async (event, target, options = {}) => {
+    return pinFavorite(target, options.global ? null : workspaceOf(event.sender));
+  });
+
+  ipcMain.handle('favorites:unpin', async (event, id) => unpinFavorite(id));
+
+  ipcMain.handle('favorites:reorder', async (event, ids) => reorderFavorites(ids));
+}
+
+module.exports = { setupFavoriteHandlers, listFavorites, pinFavorite, unpinFavorite, exportFavorites, importFavorites };
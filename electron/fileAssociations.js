@@ -0,0 +1,135 @@
+const { execFile } = require('child_process');
+const { app } = require('electron');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('file-associations');
+
+// Packaged installers register these through electron-builder.yml (fileAssociations /
+// mimeTypes); keep the two lists in sync. Runtime registration covers zip, AppImage,
+// and portable builds that never ran an installer.
+const MIME_TYPES = [
+  'text/plain',
+  'text/markdown',
+  'text/x-python',
+  'text/x-rust',
+  'text/x-go',
+  'text/x-csrc',
+  'text/x-c++src',
+  'text/x-shellscript',
+  'text/css',
+  'text/html',
+  'application/json',
+  'application/javascript',
+  'application/x-typescript',
+  'application/x-yaml',
+  'application/toml',
+  'inode/directory'
+];
+
+const LINUX_DESKTOP_FILE = 'forge-moi.desktop';
+const WINDOWS_MENU_KEY = 'ForgeMOI';
+const MENU_LABEL = 'Open with Forge';
+
+function run(command, args) {
+  return new Promise((resolve, reject) => {
+    execFile(command, args, { timeout: 10000, windowsHide: true }, (error, stdout, stderr) => {
+      if (error) {
+        const code = error.code === 'ENOENT' ? ErrorCode.NOT_FOUND : ErrorCode.IO_ERROR;
+        reject(new ForgeError(code, (stderr || error.message).trim(), { details: { command, args } }));
+        return;
+      }
+      resolve(stdout);
+    });
+  });
+}
+
+// Explorer context-menu entries, per user so no elevation is needed:
+// files, folders, and the background of an open folder
+function windowsMenuEntries() {
+  const exe = process.execPath;
+  return [
+    { key: `HKCU\\Software\\Classes\\*\\shell\\${WINDOWS_MENU_KEY}`, command: `"${exe}" "%1"` },
+    { key: `HKCU\\Software\\Classes\\Directory\\shell\\${WINDOWS_MENU_KEY}`, command: `"${exe}" "%1"` },
+    { key: `HKCU\\Software\\Classes\\Directory\\Background\\shell\\${WINDOWS_MENU_KEY}`, command: `"${exe}" "%V"` }
+  ];
+}
+
+async function registerWindows() {
+  for (const entry of windowsMenuEntries()) {
+    await run('reg', ['add', entry.key, '/ve', '/d', MENU_LABEL, '/f']);
+    await run('reg', ['add', entry.key, '/v', 'Icon', '/d', process.execPath, '/f']);
+    await run('reg', ['add', `${entry.key}\\command`, '/ve', '/d', entry.command, '/f']);
+  }
+}
+
+async function unregisterWindows() {
+  for (const entry of windowsMenuEntries()) {
+    await run('reg', ['delete', entry.key, '/f']).catch(() => {});
+  }
+}
+
+async function windowsStatus() {
+  try {
+    const output = await run('reg', ['query', `${windowsMenuEntries()[0].key}\\command`, '/ve']);
+    return { contextMenu: output.includes(process.execPath) };
+  } catch {
+    return { contextMenu: false };
+  }
+}
+
+async function registerLinux() {
+  await run('xdg-mime', ['default', LINUX_DESKTOP_FILE, ...MIME_TYPES]);
+}
+
+async function linuxStatus() {
+  const defaults = {};
+  for (const mimeType of MIME_TYPES) {
+    try {
+      defaults[mimeType] = (await run('xdg-mime', ['query', 'default', mimeType])).trim() === LINUX_DESKTOP_FILE;
+    } catch {
+      defaults[mimeType] = false;
+    }
+  }
+  return { defaults };
+}
+
+async function getAssociationStatus() {
+  const status = { platform: process.platform, packaged: app.isPackaged, runtimeRegistration: false };
+  if (process.platform === 'win32') return { ...status, runtimeRegistration: true, ...(await windowsStatus()) };
+  if (process.platform === 'linux') return { ...status, runtimeRegistration: true, ...(await linuxStatus()) };
+  // macOS reads associations from Info.plist; Launch Services picks them up on install
+  return status;
+}
+
+async function registerAssociations() {
+  if (!app.isPackaged) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'File associations can only be registered from a packaged build');
+  }
+  if (process.platform === 'win32') await registerWindows();
+  else if (process.platform === 'linux') await registerLinux();
+  else throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'On macOS, file associations are set in Finder with Get Info > Open with');
+  log.info(`Registered file associations for ${process.platform}`);
+  return getAssociationStatus();
+}
+
+async function unregisterAssociations() {
+  if (process.platform === 'win32') {
+    await unregisterWindows();
+  } else if (process.platform !== 'linux') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'On macOS, file associations are set in Finder with Get Info > Open with');
+  }
+  // xdg-mime has no "unset"; choosing another default app replaces ours on Linux
+  log.info(`Removed file associations for ${process.platform}`);
+  return getAssociationStatus();
+}
+
+function setupFileAssociationHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('fileAssociations:getStatus', async () => getAssociationStatus());
+  ipcMain.handle('fileAssociations:register', async () => registerAssociations());
+  ipcMain.handle('fileAssociations:unregister', async () => unregisterAssociations());
+}
+
+module.exports = { setupFileAssociationHandlers, getAssociationStatus, registerAssociations, unregisterAssociations };
@@ -0,0 +1,177 @@
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runGit } = require('./git');
+
+const MAX_QUERY_PATHS = 20000;
+const MAX_DECORATIONS = 5000;
+const MAX_LABEL_LENGTH = 40;
+const LSTAT_BATCH = 200;
+
+// Named colours the file tree maps to its theme; custom colours are #rrggbb
+const COLORS = ['red', 'orange', 'yellow', 'green', 'teal', 'blue', 'purple', 'pink', 'gray'];
+// Folders whose contents are build output wherever they appear
+const GENERATED_DIRECTORIES = new Set(['dist', 'build', 'out', '.next', '.nuxt', 'coverage', 'target', '__pycache__']);
+const GENERATED_FILES = [
+  /\.min\.(js|css)$/, /\.(js|css)\.map$/, /\.generated\.\w+$/, /\.g\.dart$/, /_pb2(_grpc)?\.py$/, /\.pb\.go$/,
+  /(^|\/)(package-lock\.json|yarn\.lock|pnpm-lock\.yaml|Cargo\.lock|poetry\.lock|composer\.lock|Gemfile\.lock)$/
+];
+
+// User-assigned decorations per workspace root: relative path -> { color, badge, label }
+const store = new JsonStore('file-decorations', { workspaces: {} });
+
+// The same label always gets the same colour
+function colorForLabel(label) {
+  const hash = crypto.createHash('sha1').update(label).digest();
+  return COLORS[hash[0] % COLORS.length];
+}
+
+function relativeTo(root, targetPath) {
+  const relative = path.relative(root, path.resolve(root, targetPath)).split(path.sep).join('/');
+  if (!relative || relative.startsWith('..') || path.isAbsolute(relative)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${targetPath} is not inside ${root}`);
+  }
+  return relative;
+}
+
+function validateDecoration(decoration) {
+  const { color, badge, label } = decoration;
+  if (color !== undefined && color !== null && !COLORS.includes(color) && !/^#[0-9a-fA-F]{6}$/.test(color)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Color must be one of ${COLORS.join(', ')} or #rrggbb`);
+  }
+  // Badges sit in a tree row, so one or two characters (an emoji counts as one)
+  if (badge !== undefined && badge !== null && (typeof badge !== 'string' || Array.from(badge).length === 0 || Array.from(badge).length > 2)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A badge is one or two characters');
+  }
+  if (label !== undefined && label !== null && (typeof label !== 'string' || !label.trim() || label.length > MAX_LABEL_LENGTH)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `A label is 1-${MAX_LABEL_LENGTH} characters`);
+  }
+  if (!color && !badge && !label) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A decoration needs a color, badge, or label');
+  }
+}
+
+// Decorate a file or folder in the workspace at `rootPath`; `decoration` null removes it.
+// A label without a colour is given a stable one.
+function setDecoration(rootPath, targetPath, decoration) {
+  const root = path.resolve(rootPath);
+  const relative = relativeTo(root, targetPath);
+  const workspaces = store.get('workspaces');
+  const decorations = { ...(workspaces[root] || {}) };
+  if (decoration === null) {
+    delete decorations[relative];
+  } else {
+    if (!decoration || typeof decoration !== 'object') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A decoration object or null is required');
+    validateDecoration(decoration);
+    if (!decorations[relative] && Object.keys(decorations).length >= MAX_DECORATIONS) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `A workspace can have at most ${MAX_DECORATIONS} decorated paths`);
+    }
+    const label = decoration.label ? decoration.label.trim() : null;
+    decorations[relative] = {
+      color: decoration.color || (label ? colorForLabel(label) : null),
+      badge: decoration.badge || null,
+      label
+    };
+  }
+  store.set('workspaces', { ...workspaces, [root]: decorations });
+  emit('decorations:changed', { rootPath: root, paths: [path.join(root, relative)] });
+  return decoration === null ? null : decorations[relative];
+}
+
+function listDecorations(rootPath) {
+  const root = path.resolve(rootPath);
+  const decorations = store.get('workspaces')[root] || {};
+  return Object.entries(decorations).map(([relative, decoration]) => ({ path: path.join(root, relative), ...decoration }));
+}
+
+// Relative paths git ignores, from one check-ignore over the whole batch
+async function gitIgnored(root, relatives) {
+  try {
+    const output = await runGit(root, ['check-ignore', '-z', '--stdin'], { input: relatives.join('\0') });
+    return new Set(output.split('\0').filter(Boolean));
+  } catch {
+    // Exit code 1 means nothing matched; outside a repository nothing is ignored either
+    return new Set();
+  }
+}
+
+// Relative paths marked linguist-generated in .gitattributes
+async function gitGenerated(root, relatives) {
+  try {
+    const output = await runGit(root, ['check-attr', '-z', '--stdin', 'linguist-generated'], { input: relatives.join('\0') });
+    const fields = output.split('\0');
+    const generated = new Set();
+    for (let i = 0; i + 2 < fields.length; i += 3) {
+      if (fields[i + 2] === 'set' || fields[i + 2] === 'true') generated.add(fields[i]);
+    }
+    return generated;
+  } catch {
+    return new Set();
+  }
+}
+
+function looksGenerated(relative) {
+  const segments = relative.split('/');
+  return segments.slice(0, -1).some((segment) => GENERATED_DIRECTORIES.has(segment)) ||
+    GENERATED_DIRECTORIES.has(segments[segments.length - 1]) ||
+    GENERATED_FILES.some((pattern) => pattern.test(relative));
+}
+
+async function symlinks(root, relatives) {
+  const found = new Set();
+  for (let i = 0; i < relatives.length; i += LSTAT_BATCH) {
+    const batch = relatives.slice(i, i + LSTAT_BATCH);
+    const stats = await Promise.all(batch.map((relative) => fs.lstat(path.join(root, relative)).catch(() => null)));
+    stats.forEach((stat, index) => {
+      if (stat && stat.isSymbolicLink()) found.add(batch[index]);
+    });
+  }
+  return found;
+}
+
+// Decorations for many tree nodes at once: { [path]: { color, badge, label, badges } },
+// leaving out undecorated paths. `badges` are automatic: 'generated', 'ignored', 'symlink'.
+async function queryDecorations(rootPath, paths) {
+  if (!Array.isArray(paths) || paths.length > MAX_QUERY_PATHS) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Query at most ${MAX_QUERY_PATHS} paths at a time`);
+  }
+  const root = path.resolve(rootPath);
+  const byRelative = new Map();
+  for (const targetPath of paths) {
+    const relative = path.relative(root, path.resolve(root, targetPath)).split(path.sep).join('/');
+    // The root itself and paths outside it get no decoration
+    if (relative && !relative.startsWith('..') && !path.isAbsolute(relative)) byRelative.set(relative, targetPath);
+  }
+  const relatives = Array.from(byRelative.keys());
+  if (relatives.length === 0) return {};
+
+  const [ignored, attributed, linked] = await Promise.all([gitIgnored(root, relatives), gitGenerated(root, relatives), symlinks(root, relatives)]);
+  const assigned = store.get('workspaces')[root] || {};
+  const result = {};
+  for (const [relative, targetPath] of byRelative) {
+    const badges = [];
+    if (attributed.has(relative) || looksGenerated(relative)) badges.push('generated');
+    // Directories come back from check-ignore with a trailing slash when matched as such
+    if (ignored.has(relative) || ignored.has(`${relative}/`)) badges.push('ignored');
+    if (linked.has(relative)) badges.push('symlink');
+    const own = assigned[relative];
+    if (!own && badges.length === 0) continue;
+    result[targetPath] = { color: own ? own.color : null, badge: own ? own.badge : null, label: own ? own.label : null, badges };
+  }
+  return result;
+}
+
+function setupFileDecorationHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('decorations:query', async (event, rootPath, paths) => queryDecorations(rootPath, paths));
+
+  ipcMain.handle('decorations:set', async (event, rootPath, targetPath, decoration) => setDecoration(rootPath, targetPath, decoration));
+
+  ipcMain.handle('decorations:list', async (event, rootPath) => listDecorations(rootPath));
+}
+
+module.exports = { setupFileDecorationHandlers, queryDecorations, setDecoration };
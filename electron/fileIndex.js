@@ -0,0 +1,285 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { EventEmitter } = require('events');
+const { createLogger } = require('./logger');
+const { watchPath } = require('./watchHub');
+const { runJob, cancelJob } = require('./jobs');
+const { withForgeErrors } = require('./errors');
+const { DEFAULT_EXCLUDES } = require('./search');
+const { fuzzyScorePath } = require('./fuzzy');
+const { scopeEvents, getScope, isPathIgnored } = require('./indexScope');
+const { configHash, loadCache, saveCache, deleteCache, emitIndexStatus } = require('./indexCache');
+
+const log = createLogger('file-index');
+
+// Emits 'added' | 'changed' | 'removed' with (rootPath, filePath) for dependent indexes
+const indexEvents = new EventEmitter();
+indexEvents.setMaxListeners(0);
+
+// Workspace root -> { root, files: Set<absolute path>, dirs: Map<relative dir, listing>,
+//                     watcher, ready: Promise, jobId, state, stale, fromCache, savedAt }
+// A listing is { mtimeMs, files: [names], subdirs: [names] }.
+const indexes = new Map();
+
+function cacheHash(root) {
+  const scope = getScope(root);
+  return configHash(scope.include, scope.exclude, DEFAULT_EXCLUDES);
+}
+
+function reportStatus(index, extra = {}) {
+  emitIndexStatus({
+    rootPath: index.root,
+    index: 'files',
+    state: index.state,
+    stale: index.stale,
+    fromCache: index.fromCache,
+    processed: null,
+    total: null,
+    count: index.files.size,
+    savedAt: index.savedAt,
+    ...extra
+  });
+}
+
+// Walk the tree, reusing cached listings for directories whose mtime hasn't changed:
+// adding, removing, or renaming an entry bumps its parent directory's mtime, so only
+// changed directories need a readdir.
+async function scanTree(index, cachedDirs, handle) {
+  const dirs = new Map();
+  const files = new Set();
+  const ignored = (entryPath, type) => DEFAULT_EXCLUDES.includes(path.basename(entryPath)) || isPathIgnored(index.root, entryPath, type);
+  const pending = [''];
+  let reused = 0;
+  while (pending.length > 0) {
+    handle.throwIfCancelled();
+    const relativeDir = pending.pop();
+    const dir = path.join(index.root, relativeDir);
+    let stats;
+    try {
+      stats = await fs.stat(dir);
+    } catch {
+      continue; // Removed mid-scan
+    }
+
+    let listing = cachedDirs.get(relativeDir);
+    if (listing && listing.mtimeMs === stats.mtimeMs) {
+      reused++;
+    } else {
+      let entries;
+      try {
+        entries = await fs.readdir(dir, { withFileTypes: true });
+      } catch (error) {
+        log.debug(`Skipping unreadable directory ${dir}: ${error.code}`);
+        continue;
+      }
+      listing = { mtimeMs: stats.mtimeMs, files: [], subdirs: [] };
+      for (const entry of entries) {
+        const entryPath = path.join(dir, entry.name);
+        if (entry.isDirectory()) {
+          if (!ignored(entryPath, 'directory')) listing.subdirs.push(entry.name);
+        } else if (entry.isFile() && !ignored(entryPath, 'file')) {
+          listing.files.push(entry.name);
+        }
+      }
+    }
+
+    dirs.set(relativeDir, listing);
+    for (const name of listing.files) files.add(path.join(dir, name));
+    for (const name of listing.subdirs) pending.push(path.join(relativeDir, name));
+    if (dirs.size % 500 === 0) {
+      handle.reportProgress(null, `${files.size} files`);
+      reportStatus(index, { processed: dirs.size, count: files.size });
+    }
+  }
+  return { dirs, files, reused };
+}
+
+async function buildIndex(index) {
+  const hash = cacheHash(index.root);
+  const cached = await loadCache(index.root, 'files', hash);
+  const cachedDirs = new Map(cached ? Object.entries(cached.data.dirs) : []);
+  if (cached) {
+    // Serve queries from the cache right away; the scan below only verifies it
+    for (const [relativeDir, listing] of cachedDirs) {
+      for (const name of listing.files) index.files.add(path.join(index.root, relativeDir, name));
+    }
+    Object.assign(index, { fromCache: true, stale: true, savedAt: cached.savedAt });
+  }
+  index.state = cached ? 'verifying' : 'indexing';
+  reportStatus(index);
+
+  const title = cached ? `Verify file index for ${path.basename(index.root)}` : `Index files in ${path.basename(index.root)}`;
+  const job = runJob('file-index', title, async (handle) => {
+    // Watcher events that land mid-scan are replayed over the fresh result
+    index.changesDuringScan = [];
+    const result = await scanTree(index, cachedDirs, handle);
+    const changes = index.changesDuringScan;
+    index.changesDuringScan = null;
+
+    index.files = result.files;
+    index.dirs = result.dirs;
+    for (const [type, filePath] of changes) {
+      if (type === 'unlink') index.files.delete(filePath);
+      else index.files.add(filePath);
+    }
+    return result;
+  });
+  index.jobId = job.id;
+
+  try {
+    const result = await job.promise;
+    log.info(`Indexed ${index.files.size} files in ${index.root} (${result.reused}/${result.dirs.size} directories from cache)`);
+    index.stale = false;
+    index.state = 'ready';
+    const dirs = Object.fromEntries(index.dirs);
+    index.savedAt = (await saveCache(index.root, 'files', hash, { dirs })) || index.savedAt;
+    indexEvents.emit('ready', index.root);
+  } catch (error) {
+    index.changesDuringScan = null;
+    index.state = 'failed';
+    log.warn(`File indexing failed for ${index.root}: ${error.message}`);
+  } finally {
+    index.jobId = null;
+    if (indexes.get(index.root) === index) reportStatus(index);
+  }
+}
+
+function ensureIndex(rootPath) {
+  const root = path.resolve(rootPath);
+  if (indexes.has(root)) return indexes.get(root);
+
+  const index = {
+    root,
+    files: new Set(),
+    dirs: new Map(),
+    watcher: null,
+    jobId: null,
+    ready: null,
+    state: 'loading',
+    stale: false,
+    fromCache: false,
+    savedAt: null,
+    changesDuringScan: null
+  };
+  indexes.set(root, index);
+
+  // Shares the workspace root's watcher with open editors and the file tree
+  index.watcher = watchPath(root, (type, filePath) => {
+    if (type === 'add') {
+      if (isPathIgnored(root, filePath, 'file')) return;
+      index.files.add(filePath);
+      if (index.changesDuringScan) index.changesDuringScan.push(['add', filePath]);
+      indexEvents.emit('added', root, filePath);
+    } else if (type === 'change') {
+      if (index.files.has(filePath)) indexEvents.emit('changed', root, filePath);
+    } else if (type === 'unlink') {
+      index.files.delete(filePath);
+      if (index.changesDuringScan) index.changesDuringScan.push(['unlink', filePath]);
+      indexEvents.emit('removed', root, filePath);
+    }
+  }, { kind: 'tree', workspaceRoot: root, owner: 'file-index' });
+
+  index.ready = buildIndex(index);
+  return index;
+}
+
+// A new scope means a different file set; rebuild from scratch so dependent indexes
+// (symbols, embeddings) see the change through 'closed' and the next query
+scopeEvents.on('changed', async (root) => {
+  if (!indexes.has(root)) return;
+  await closeIndex(root);
+  ensureIndex(root);
+});
+
+async function closeIndex(rootPath) {
+  const root = path.resolve(rootPath);
+  const index = indexes.get(root);
+  if (!index) return false;
+  indexes.delete(root);
+  if (index.jobId) cancelJob(index.jobId);
+  index.watcher.close();
+  indexEvents.emit('closed', root);
+  return true;
+}
+
+function getIndexedFiles(rootPath) {
+  const index = indexes.get(path.resolve(rootPath));
+  return index ? Array.from(index.files) : [];
+}
+
+// Fuzzy-find files by relative path. Results are returned even while indexing runs.
+function queryFiles(rootPath, query, limit = 50) {
+  const index = ensureIndex(rootPath);
+  const results = [];
+  for (const filePath of index.files) {
+    const relativePath = path.relative(index.root, filePath).split(path.sep).join('/');
+    const match = fuzzyScorePath(query, relativePath);
+    if (match) results.push({ path: filePath, relativePath, score: match.score, positions: match.positions });
+  }
+  return results.sort((a, b) => b.score - a.score).slice(0, limit);
+}
+
+function getIndexStatus(rootPath) {
+  const index = indexes.get(path.resolve(rootPath));
+  if (!index) return { rootPath: path.resolve(rootPath), indexed: false, indexing: false, fileCount: 0, state: 'closed', stale: false, fromCache: false, savedAt: null };
+  return {
+    rootPath: index.root,
+    indexed: true,
+    indexing: Boolean(index.jobId),
+    fileCount: index.files.size,
+    state: index.state,
+    stale: index.stale,
+    fromCache: index.fromCache,
+    savedAt: index.savedAt
+  };
+}
+
+// Drop the persisted index and rescan from scratch
+async function rebuildIndex(rootPath) {
+  const root = path.resolve(rootPath);
+  const wasOpen = await closeIndex(root);
+  await deleteCache(root, 'files');
+  indexEvents.emit('cacheCleared', root);
+  if (wasOpen) ensureIndex(root);
+  return getIndexStatus(root);
+}
+
+function setupFileIndexHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('fileIndex:open', async (event, rootPath) => {
+    ensureIndex(rootPath);
+    return getIndexStatus(rootPath);
+  });
+
+  ipcMain.handle('fileIndex:close', async (event, rootPath) => {
+    return closeIndex(rootPath);
+  });
+
+  ipcMain.handle('fileIndex:query', async (event, rootPath, query, limit) => {
+    return queryFiles(rootPath, query, limit);
+  });
+
+  ipcMain.handle('fileIndex:getStatus', async (event, rootPath) => {
+    return getIndexStatus(rootPath);
+  });
+
+  ipcMain.handle('fileIndex:rebuild', async (event, rootPath) => {
+    return rebuildIndex(rootPath);
+  });
+
+  process.on('exit', () => {
+    for (const index of indexes.values()) index.watcher.close();
+  });
+}
+
+module.exports = {
+  setupFileIndexHandlers,
+  indexEvents,
+  ensureIndex,
+  closeIndex,
+  getIndexedFiles,
+  queryFiles,
+  getIndexStatus,
+  rebuildIndex
+};
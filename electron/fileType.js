@@ -0,0 +1,167 @@
+const fs = require('fs').promises;
+const { detectLanguage } = require('./languages');
+
+// Bytes read from the head of a file for sniffing
+const SNIFF_BYTES = 8192;
+// Files up to this size get an exact line count; beyond it the count is extrapolated
+const EXACT_LINE_COUNT_LIMIT = 16 * 1024 * 1024;
+const LINE_SAMPLE_BYTES = 1024 * 1024;
+// Text files larger than this open read-only / in a large-file viewer instead of the editor
+const MAX_EDITABLE_SIZE = 50 * 1024 * 1024;
+
+// Magic byte signatures: [offset, bytes, mime, kind]
+const SIGNATURES = [
+  [0, [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a], 'image/png', 'image'],
+  [0, [0xff, 0xd8, 0xff], 'image/jpeg', 'image'],
+  [0, [0x47, 0x49, 0x46, 0x38], 'image/gif', 'image'],
+  [0, [0x42, 0x4d], 'image/bmp', 'image'],
+  [0, [0x00, 0x00, 0x01, 0x00], 'image/x-icon', 'image'],
+  [8, [0x57, 0x45, 0x42, 0x50], 'image/webp', 'image'],
+  [4, [0x66, 0x74, 0x79, 0x70, 0x61, 0x76, 0x69, 0x66], 'image/avif', 'image'],
+  [0, [0x25, 0x50, 0x44, 0x46, 0x2d], 'application/pdf', 'pdf'],
+  [0, [0x50, 0x4b, 0x03, 0x04], 'application/zip', 'archive'],
+  [0, [0x1f, 0x8b], 'application/gzip', 'archive'],
+  [0, [0x42, 0x5a, 0x68], 'application/x-bzip2', 'archive'],
+  [0, [0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00], 'application/x-xz', 'archive'],
+  [0, [0x37, 0x7a, 0xbc, 0xaf, 0x27, 0x1c], 'application/x-7z-compressed', 'archive'],
+  [0, [0x52, 0x61, 0x72, 0x21, 0x1a, 0x07], 'application/vnd.rar', 'archive'],
+  [0, [0x28, 0xb5, 0x2f, 0xfd], 'application/zstd', 'archive'],
+  [0, [0x7f, 0x45, 0x4c, 0x46], 'application/x-elf', 'executable'],
+  [0, [0x4d, 0x5a], 'application/vnd.microsoft.portable-executable', 'executable'],
+  [0, [0xcf, 0xfa, 0xed, 0xfe], 'application/x-mach-binary', 'executable'],
+  [0, [0xca, 0xfe, 0xba, 0xbe], 'application/x-mach-binary', 'executable'],
+  [0, [0x00, 0x61, 0x73, 0x6d], 'application/wasm', 'executable'],
+  [0, [0x53, 0x51, 0x4c, 0x69, 0x74, 0x65, 0x20, 0x66], 'application/vnd.sqlite3', 'database'],
+  [0, [0x49, 0x44, 0x33], 'audio/mpeg', 'audio'],
+  [0, [0x4f, 0x67, 0x67, 0x53], 'audio/ogg', 'audio'],
+  [0, [0x66, 0x4c, 0x61, 0x43], 'audio/flac', 'audio'],
+  [8, [0x57, 0x41, 0x56, 0x45], 'audio/wav', 'audio'],
+  [4, [0x66, 0x74, 0x79, 0x70], 'video/mp4', 'video'],
+  [0, [0x1a, 0x45, 0xdf, 0xa3], 'video/webm', 'video'],
+  [0, [0x77, 0x4f, 0x46, 0x46], 'font/woff', 'font'],
+  [0, [0x77, 0x4f, 0x46, 0x32], 'font/woff2', 'font'],
+  [0, [0x00, 0x01, 0x00, 0x00, 0x00], 'font/ttf', 'font'],
+  [0, [0x4f, 0x54, 0x54, 0x4f], 'font/otf', 'font']
+];
+
+function matchSignature(head) {
+  for (const [offset, bytes, mime, kind] of SIGNATURES) {
+    if (head.length < offset + bytes.length) continue;
+    if (bytes.every((byte, i) => head[offset + i] === byte)) return { mime, kind };
+  }
+  return null;
+}
+
+// Byte order marks identify the encoding outright
+function detectBom(head) {
+  if (head[0] === 0xef && head[1] === 0xbb && head[2] === 0xbf) return 'utf-8';
+  if (head[0] === 0xff && head[1] === 0xfe) return 'utf-16le';
+  if (head[0] === 0xfe && head[1] === 0xff) return 'utf-16be';
+  return null;
+}
+
+// Heuristic: NUL bytes mean binary; otherwise too many control characters or invalid
+// UTF-8 sequences (more than a few percent of the sample) mean binary
+function looksBinary(head) {
+  if (head.length === 0) return false;
+  if (head.includes(0)) return true;
+  let suspicious = 0;
+  for (let i = 0; i < head.length; i++) {
+    const byte = head[i];
+    if (byte < 0x20 && byte !== 0x09 && byte !== 0x0a && byte !== 0x0d && byte !== 0x0c && byte !== 0x1b) suspicious++;
+  }
+  // A multi-byte character may be cut at the end of the sample; ignore the last 3 bytes
+  const decoded = head.subarray(0, Math.max(0, head.length - 3)).toString('utf-8');
+  const replacements = (decoded.match(/�/g) || []).length;
+  return (suspicious + replacements) / head.length > 0.05;
+}
+
+function countNewlines(buffer) {
+  let count = 0;
+  let index = buffer.indexOf(0x0a);
+  while (index !== -1) {
+    count++;
+    index = buffer.indexOf(0x0a, index + 1);
+  }
+  return count;
+}
+
+// Exact for modest files; for huge ones, extrapolated from the first megabyte
+async function countLines(handle, size) {
+  if (size === 0) return { lineCount: 0, exact: true };
+  if (size <= EXACT_LINE_COUNT_LIMIT) {
+    const buffer = Buffer.alloc(size);
+    const { bytesRead } = await handle.read(buffer, 0, size, 0);
+    const content = buffer.subarray(0, bytesRead);
+    const trailing = content[content.length - 1] === 0x0a ? 0 : 1;
+    return { lineCount: countNewlines(content) + trailing, exact: true };
+  }
+  const sample = Buffer.alloc(LINE_SAMPLE_BYTES);
+  const { bytesRead } = await handle.read(sample, 0, LINE_SAMPLE_BYTES, 0);
+  const perByte = countNewlines(sample.subarray(0, bytesRead)) / Math.max(1, bytesRead);
+  return { lineCount: Math.max(1, Math.round(perByte * size)), exact: false };
+}
+
+// How the workbench should open a file: 'editor', 'largeFile' (read-only viewer),
+// 'image', 'pdf', 'media', or 'binary' (hex view / refuse)
+function recommendViewer(kind, size) {
+  switch (kind) {
+    case 'text':
+      return size > MAX_EDITABLE_SIZE ? 'largeFile' : 'editor';
+    case 'image':
+      return 'image';
+    case 'pdf':
+      return 'pdf';
+    case 'audio':
+    case 'video':
+      return 'media';
+    default:
+      return 'binary';
+  }
+}
+
+// Sniff a file's type without reading more than necessary
+async function detectFileType(filePath) {
+  const stats = await fs.stat(filePath);
+  if (stats.isDirectory()) {
+    return { path: filePath, kind: 'directory', mime: 'inode/directory', binary: false, encoding: null, language: null, size: stats.size, lineCount: null, lineCountExact: true, viewer: null };
+  }
+
+  const handle = await fs.open(filePath, 'r');
+  try {
+    const head = Buffer.alloc(Math.min(SNIFF_BYTES, stats.size));
+    const { bytesRead } = await handle.read(head, 0, head.length, 0);
+    const sample = head.subarray(0, bytesRead);
+
+    const bom = detectBom(sample);
+    const signature = bom ? null : matchSignature(sample);
+    const isSvg = !signature && /^\s*(<\?xml[^>]*>\s*)?<svg[\s>]/i.test(sample.toString('utf-8', 0, 512));
+    // UTF-16 text is full of NUL bytes; trust the BOM over the heuristic
+    const binary = bom ? false : signature ? true : looksBinary(sample);
+
+    const kind = signature ? signature.kind : binary ? 'binary' : 'text';
+    let mime = signature ? signature.mime : binary ? 'application/octet-stream' : 'text/plain';
+    if (isSvg) mime = 'image/svg+xml';
+
+    const headText = binary ? null : sample.toString(bom && bom.startsWith('utf-16') ? 'utf16le' : 'utf-8');
+    const language = binary ? null : detectLanguage(filePath, headText);
+    const lines = binary ? { lineCount: null, exact: true } : await countLines(handle, stats.size);
+
+    return {
+      path: filePath,
+      kind,
+      mime,
+      binary,
+      encoding: binary ? null : bom || 'utf-8',
+      language,
+      size: stats.size,
+      lineCount: lines.lineCount,
+      lineCountExact: lines.exact,
+      viewer: recommendViewer(kind, stats.size)
+    };
+  } finally {
+    await handle.close();
+  }
+}
+
+module.exports = { detectFileType, MAX_EDITABLE_SIZE };
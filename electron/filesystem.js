@@ -1,12 +1,29 @@
 const fs = require('fs').promises;
 const path = require('path');
-const chokidar = require('chokidar');
 const crypto = require('crypto');
 const { app } = require('electron');
+const { watchPath } = require('./watchHub');
+const { emit } = require('./events');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob } = require('./jobs');
+const { saveWithHooks } = require('./saveHooks');
+const { detectFileType } = require('./fileType');
+const { saveWithElevation, getWriteAccess } = require('./elevation');
+const { enforceGuardrails } = require('./guardrails');
+const { windowManager } = require('./windows');
+const { writeProtectedFile, readProtectedFile, registerMigration, migrateDirectory } = require('./encryption');
 
-// File watchers map
+// Editor file watches, keyed by the watching webContents and path
 const fileWatchers = new Map();
 
+function watchKey(sender, filePath) {
+  return `${sender.id}:${filePath}`;
+}
+
+// Batch stat calls: enough parallelism to hide disk latency without exhausting file handles
+const BATCH_CONCURRENCY = 64;
+const MAX_BATCH_PATHS = 10000;
+
 // Validate path to prevent directory traversal attacks
 function validatePath(filePath) {
   const normalizedPath = path.normalize(filePath);
@@ -15,12 +32,25 @@ function validatePath(filePath) {
   
   // Allow access to home directory and app data
   if (!normalizedPath.startsWith(homeDir) && !normalizedPath.startsWith(appDataDir)) {
-    throw new Error('Access denied: Path outside allowed directories');
+    throw new ForgeError(ErrorCode.ACCESS_DENIED, 'Access denied: Path outside allowed directories', {
+      details: { path: normalizedPath }
+    });
   }
   
   return normalizedPath;
 }
 
+function getBackupDir() {
+  return path.join(app.getPath('userData'), 'backups');
+}
+
+registerMigration('recovery', migrateDirectory(getBackupDir(), (name) => name.endsWith('.backup')));
+
+function workspacePathFor(sender) {
+  const context = windowManager.fromSender(sender);
+  return context ? context.workspacePath : null;
+}
+
 // Get file metadata
 async function getFileMetadata(filePath) {
   const stats = await fs.stat(filePath);
@@ -40,7 +70,85 @@ async function getFileMetadata(filePath) {
   };
 }
 
+// Run `fn` over `items` with at most `limit` in flight, keeping results in input order
+async function mapConcurrent(items, limit, fn) {
+  const results = new Array(items.length);
+  let next = 0;
+  const worker = async () => {
+    while (next < items.length) {
+      const index = next++;
+      results[index] = await fn(items[index], index);
+    }
+  };
+  await Promise.all(Array.from({ length: Math.min(limit, items.length) }, worker));
+  return results;
+}
+
+function requirePathList(paths) {
+  if (!Array.isArray(paths) || paths.some((filePath) => typeof filePath !== 'string')) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Expected a list of paths');
+  }
+  if (paths.length > MAX_BATCH_PATHS) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `At most ${MAX_BATCH_PATHS} paths can be checked at once`, {
+      details: { count: paths.length }
+    });
+  }
+}
+
+// Everything under a directory as { path (relative), type: 'directory' | 'symlink' | 'file' },
+// parents before their contents. Symlinks are listed, not followed.
+async function listEntriesRecursive(rootPath, relative = '') {
+  const entries = await fs.readdir(path.join(rootPath, relative), { withFileTypes: true });
+  const result = [];
+  for (const entry of entries) {
+    const entryPath = path.join(relative, entry.name);
+    if (entry.isSymbolicLink()) {
+      result.push({ path: entryPath, type: 'symlink' });
+    } else if (entry.isDirectory()) {
+      result.push({ path: entryPath, type: 'directory' }, ...await listEntriesRecursive(rootPath, entryPath));
+    } else {
+      result.push({ path: entryPath, type: 'file' });
+    }
+  }
+  return result;
+}
+
+// Copy one entry: directories are created (empty ones included) and symlinks recreated
+// pointing where the original does
+async function copyEntry(fromPath, toPath, type) {
+  if (type === 'directory') {
+    await fs.mkdir(toPath, { recursive: true });
+  } else if (type === 'symlink') {
+    await fs.symlink(await fs.readlink(fromPath), toPath);
+  } else {
+    await fs.copyFile(fromPath, toPath);
+  }
+}
+
+// Copy a file or directory tree as a cancellable job
+function copyAsJob(fromPath, toPath) {
+  return runJob('copy', `Copy ${path.basename(fromPath)}`, async (job) => {
+    const stats = await fs.lstat(fromPath);
+    await fs.mkdir(path.dirname(toPath), { recursive: true });
+    if (!stats.isDirectory()) {
+      await copyEntry(fromPath, toPath, stats.isSymbolicLink() ? 'symlink' : 'file');
+      job.reportProgress(1);
+      return;
+    }
+
+    await fs.mkdir(toPath, { recursive: true });
+    const entries = await listEntriesRecursive(fromPath);
+    for (let i = 0; i < entries.length; i++) {
+      job.throwIfCancelled();
+      await copyEntry(path.join(fromPath, entries[i].path), path.join(toPath, entries[i].path), entries[i].type);
+      job.reportProgress((i + 1) / entries.length, entries[i].path);
+    }
+  }).promise;
+}
+
 function setupFileSystemHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
   // Read file
   ipcMain.handle('fs:readFile', async (event, filePath) => {
     try {
@@ -48,7 +156,7 @@ function setupFileSystemHandlers(ipcMain) {
       const content = await fs.readFile(validPath, 'utf-8');
       return content;
     } catch (error) {
-      throw new Error(`Failed to read file: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to read file');
     }
   });
 
@@ -61,11 +169,47 @@ function setupFileSystemHandlers(ipcMain) {
       const dir = path.dirname(validPath);
       await fs.mkdir(dir, { recursive: true });
       
-      await fs.writeFile(validPath, content, 'utf-8');
+      await saveWithHooks(validPath, content, (text) => fs.writeFile(validPath, text, 'utf-8'), { sender: event.sender });
       return true;
     } catch (error) {
-      throw new Error(`Failed to write file: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to write file');
+    }
+  });
+
+  // Like fs:writeFile, but returns what the save hooks wrote and how each pre-save hook
+  // went, so the editor can pick up e.g. formatter changes without rereading the file
+  ipcMain.handle('fs:save', async (event, filePath, content) => {
+    try {
+      const validPath = validatePath(filePath);
+      await fs.mkdir(path.dirname(validPath), { recursive: true });
+      const { result, ...saved } = await saveWithHooks(validPath, content, (text) => fs.writeFile(validPath, text, 'utf-8'), { sender: event.sender });
+      return saved;
+    } catch (error) {
+      throw ForgeError.from(error, 'Failed to save file');
+    }
+  });
+
+  // Save a file the user can't write (e.g. /etc/hosts): tries a normal write first, then
+  // asks to retry with administrator rights via pkexec/sudo, osascript, or UAC. Not limited
+  // to the home directory, since the OS authentication prompt is the gate.
+  ipcMain.handle('fs:saveWithElevation', async (event, filePath, content) => {
+    if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An absolute path is required');
+    }
+    if (typeof content !== 'string') {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Content must be a string');
+    }
+    const target = path.normalize(filePath);
+    const saved = await saveWithHooks(target, content, (text) => saveWithElevation(target, text, { sender: event.sender }), { sender: event.sender });
+    return saved.result;
+  });
+
+  // Whether a file is writable, so the editor can show it as read-only up front
+  ipcMain.handle('fs:getWriteAccess', async (event, filePath) => {
+    if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An absolute path is required');
     }
+    return getWriteAccess(path.normalize(filePath));
   });
 
   // Check if file exists
@@ -85,21 +229,55 @@ function setupFileSystemHandlers(ipcMain) {
       const validPath = validatePath(filePath);
       return await getFileMetadata(validPath);
     } catch (error) {
-      throw new Error(`Failed to get file info: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to get file info');
     }
   });
 
+  // Sniff content type, binary-ness, language and line count before opening a file,
+  // so the workbench can route images/PDFs to viewers and avoid loading binaries as text
+  ipcMain.handle('fs:detectFileType', async (event, filePath) => {
+    try {
+      return await detectFileType(validatePath(filePath));
+    } catch (error) {
+      throw ForgeError.from(error, 'Failed to detect file type');
+    }
+  });
+
+  // Stat many paths in one round-trip (e.g. when the file tree expands a directory).
+  // One bad path doesn't fail the batch: each entry carries either metadata or an error.
+  ipcMain.handle('fs:getFilesMetadata', async (event, paths) => {
+    requirePathList(paths);
+    return mapConcurrent(paths, BATCH_CONCURRENCY, async (filePath) => {
+      try {
+        return { path: filePath, metadata: await getFileMetadata(validatePath(filePath)), error: null };
+      } catch (error) {
+        return { path: filePath, metadata: null, error: ForgeError.from(error, 'Failed to get file info').toJSON() };
+      }
+    });
+  });
+
+  // Existence of many paths at once, in the same order as `paths`
+  ipcMain.handle('fs:existsBatch', async (event, paths) => {
+    requirePathList(paths);
+    return mapConcurrent(paths, BATCH_CONCURRENCY, async (filePath) => {
+      try {
+        await fs.access(validatePath(filePath));
+        return true;
+      } catch {
+        return false;
+      }
+    });
+  });
+
   // Create file
   ipcMain.handle('fs:createFile', async (event, filePath, content = '') => {
     try {
       const validPath = validatePath(filePath);
       
       // Check if file already exists
-      try {
-        await fs.access(validPath);
-        throw new Error('File already exists');
-      } catch {
-        // File doesn't exist, good to create
+      const exists = await fs.access(validPath).then(() => true, () => false);
+      if (exists) {
+        throw new ForgeError(ErrorCode.ALREADY_EXISTS, 'File already exists', { details: { path: validPath } });
       }
       
       // Ensure parent directory exists
@@ -109,33 +287,39 @@ function setupFileSystemHandlers(ipcMain) {
       await fs.writeFile(validPath, content, 'utf-8');
       return await getFileMetadata(validPath);
     } catch (error) {
-      throw new Error(`Failed to create file: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to create file');
     }
   });
 
   // Delete file or directory
-  ipcMain.handle('fs:deleteFile', async (event, filePath) => {
+  // Guarded: protected paths are refused or need `options.confirmationToken` (see guardrails)
+  ipcMain.handle('fs:deleteFile', async (event, filePath, options = {}) => {
     try {
       const validPath = validatePath(filePath);
+      enforceGuardrails('delete', [validPath], { workspacePath: workspacePathFor(event.sender), confirmationToken: options.confirmationToken });
       const stats = await fs.stat(validPath);
       
       if (stats.isDirectory()) {
-        await fs.rmdir(validPath, { recursive: true });
+        // Large trees can take a while; track them as a job
+        await runJob('delete', `Delete ${path.basename(validPath)}`, () => fs.rm(validPath, { recursive: true }), {
+          cancellable: false
+        }).promise;
       } else {
         await fs.unlink(validPath);
       }
       
       return true;
     } catch (error) {
-      throw new Error(`Failed to delete: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to delete');
     }
   });
 
   // Rename/move file
-  ipcMain.handle('fs:rename', async (event, fromPath, toPath) => {
+  ipcMain.handle('fs:rename', async (event, fromPath, toPath, options = {}) => {
     try {
       const validFromPath = validatePath(fromPath);
       const validToPath = validatePath(toPath);
+      enforceGuardrails('rename', [validFromPath, validToPath], { workspacePath: workspacePathFor(event.sender), confirmationToken: options.confirmationToken });
       
       // Ensure target directory exists
       const dir = path.dirname(validToPath);
@@ -144,7 +328,7 @@ function setupFileSystemHandlers(ipcMain) {
       await fs.rename(validFromPath, validToPath);
       return await getFileMetadata(validToPath);
     } catch (error) {
-      throw new Error(`Failed to rename: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to rename');
     }
   });
 
@@ -154,14 +338,11 @@ function setupFileSystemHandlers(ipcMain) {
       const validFromPath = validatePath(fromPath);
       const validToPath = validatePath(toPath);
       
-      // Ensure target directory exists
-      const dir = path.dirname(validToPath);
-      await fs.mkdir(dir, { recursive: true });
-      
-      await fs.copyFile(validFromPath, validToPath);
+      // Files and directory trees are copied as a cancellable job
+      await copyAsJob(validFromPath, validToPath);
       return await getFileMetadata(validToPath);
     } catch (error) {
-      throw new Error(`Failed to copy: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to copy');
     }
   });
 
@@ -189,7 +370,7 @@ function setupFileSystemHandlers(ipcMain) {
         return a.name.localeCompare(b.name);
       });
     } catch (error) {
-      throw new Error(`Failed to list directory: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to list directory');
     }
   });
 
@@ -197,38 +378,50 @@ function setupFileSystemHandlers(ipcMain) {
   ipcMain.handle('fs:watchFile', async (event, filePath) => {
     try {
       const validPath = validatePath(filePath);
+      const key = watchKey(event.sender, validPath);
       
       // Stop existing watcher if any
-      if (fileWatchers.has(validPath)) {
-        fileWatchers.get(validPath).close();
+      if (fileWatchers.has(key)) {
+        fileWatchers.get(key).close();
       }
       
-      // Create new watcher
-      const watcher = chokidar.watch(validPath, {
-        persistent: true,
-        ignoreInitial: true
-      });
-      
-      watcher.on('change', () => {
-        event.sender.send('fs:fileChanged', {
-          path: validPath,
-          type: 'modified'
-        });
-      });
+      // Files in the window's workspace share its root watcher rather than each taking
+      // native watches of their own
+      const context = windowManager.fromSender(event.sender);
+      const watch = watchPath(validPath, (type, changedPath, oldPath) => {
+        if (event.sender.isDestroyed()) return;
+        // Moved away: the editor can follow it to its new path
+        if (type === 'rename' && oldPath === validPath) {
+          emit('fs:fileChanged', {
+            path: validPath,
+            type: 'renamed',
+            newPath: changedPath
+          }, { target: event.sender });
+        } else if (type === 'change' || type === 'add' || type === 'rename') {
+          // A file recreated after deletion (e.g. by a git checkout), or another file moved
+          // into its place, reads as modified
+          emit('fs:fileChanged', {
+            path: validPath,
+            type: 'modified'
+          }, { target: event.sender });
+        } else if (type === 'unlink') {
+          emit('fs:fileChanged', {
+            path: validPath,
+            type: 'deleted'
+          }, { target: event.sender });
+        }
+      }, { kind: 'file', workspaceRoot: context ? context.workspacePath : null, owner: 'editor', renames: true });
       
-      watcher.on('unlink', () => {
-        event.sender.send('fs:fileChanged', {
-          path: validPath,
-          type: 'deleted'
-        });
-        watcher.close();
-        fileWatchers.delete(validPath);
+      fileWatchers.set(key, watch);
+      event.sender.once('destroyed', () => {
+        if (fileWatchers.get(key) === watch) {
+          watch.close();
+          fileWatchers.delete(key);
+        }
       });
-      
-      fileWatchers.set(validPath, watcher);
       return true;
     } catch (error) {
-      throw new Error(`Failed to watch file: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to watch file');
     }
   });
 
@@ -236,15 +429,16 @@ function setupFileSystemHandlers(ipcMain) {
   ipcMain.handle('fs:unwatchFile', async (event, filePath) => {
     try {
       const validPath = validatePath(filePath);
+      const key = watchKey(event.sender, validPath);
       
-      if (fileWatchers.has(validPath)) {
-        fileWatchers.get(validPath).close();
-        fileWatchers.delete(validPath);
+      if (fileWatchers.has(key)) {
+        fileWatchers.get(key).close();
+        fileWatchers.delete(key);
       }
       
       return true;
     } catch (error) {
-      throw new Error(`Failed to unwatch file: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to unwatch file');
     }
   });
 
@@ -256,19 +450,15 @@ function setupFileSystemHandlers(ipcMain) {
       
       // Generate backup path
       const timestamp = new Date().toISOString().replace(/[:.]/g, '-');
-      const backupDir = path.join(app.getPath('userData'), 'backups');
       const fileName = path.basename(validPath);
-      const backupPath = path.join(backupDir, `${fileName}.${timestamp}.backup`);
-      
-      // Ensure backup directory exists
-      await fs.mkdir(backupDir, { recursive: true });
+      const backupPath = path.join(getBackupDir(), `${fileName}.${timestamp}.backup`);
       
-      // Write backup
-      await fs.writeFile(backupPath, content, 'utf-8');
+      // Write backup (encrypted if recovery data is configured for it)
+      await writeProtectedFile('recovery', backupPath, content);
       
       return backupPath;
     } catch (error) {
-      throw new Error(`Failed to create backup: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to create backup');
     }
   });
 
@@ -278,12 +468,12 @@ function setupFileSystemHandlers(ipcMain) {
       const validBackupPath = validatePath(backupPath);
       const validTargetPath = validatePath(targetPath);
       
-      const content = await fs.readFile(validBackupPath, 'utf-8');
+      const content = await readProtectedFile(validBackupPath);
       await fs.writeFile(validTargetPath, content, 'utf-8');
       
       return true;
     } catch (error) {
-      throw new Error(`Failed to restore backup: ${error.message}`);
+      throw ForgeError.from(error, 'Failed to restore backup');
     }
   });
 
@@ -293,4 +483,4 @@ function setupFileSystemHandlers(ipcMain) {
   });
 }
 
-module.exports = { setupFileSystemHandlers };
\ No newline at end of file
+module.exports = { setupFileSystemHandlers, validatePath };
\ No newline at end of file
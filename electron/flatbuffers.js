@@ -0,0 +1,561 @@
+// FlatBuffers schemas: a .fbs parser that follows includes and resolves names across
+// namespaces, and a decoder that walks a binary buffer's vtables into the JSON flatc
+// --json would print for the same table type.
+
+const fs = require('fs').promises;
+const path = require('path');
+const { ForgeError, ErrorCode } = require('./errors');
+const { SchemaSyntaxError, TokenStream, tokenize, resolveName } = require('./protobuf');
+
+const SCALAR_SIZES = {
+  bool: 1, byte: 1, ubyte: 1, int8: 1, uint8: 1,
+  short: 2, ushort: 2, int16: 2, uint16: 2,
+  int: 4, uint: 4, int32: 4, uint32: 4, float: 4, float32: 4,
+  long: 8, ulong: 8, int64: 8, uint64: 8, double: 8, float64: 8
+};
+const MAX_DEPTH = 64;
+const MAX_INCLUDE_DEPTH = 32;
+
+class BufferError extends Error {}
+
+// `[ubyte]` -> vector, `[float:3]` -> fixed-length array (structs only), else a scalar,
+// string or named type
+function parseType(stream) {
+  if (stream.accept('[')) {
+    const element = parseType(stream);
+    let length = null;
+    if (stream.accept(':')) length = stream.integer();
+    stream.expect(']');
+    return length === null ? { base: 'vector', element } : { base: 'array', element, length };
+  }
+  const name = stream.qualifiedName();
+  if (SCALAR_SIZES[name]) return { base: 'scalar', scalar: name };
+  if (name === 'string') return { base: 'string' };
+  return { base: 'named', name };
+}
+
+// (id: 3, deprecated, force_align: 16)
+function parseMetadata(stream) {
+  const metadata = {};
+  if (!stream.accept('(')) return metadata;
+  if (stream.accept(')')) return metadata;
+  do {
+    const name = stream.ident();
+    metadata[name] = stream.accept(':') ? stream.constant() : true;
+  } while (stream.accept(','));
+  stream.expect(')');
+  return metadata;
+}
+
+function parseFbs(text, fileName = '') {
+  const stream = new TokenStream(tokenize(text));
+  const file = { file: fileName, namespace: '', includes: [], tables: [], enums: [], unions: [], services: [], rootType: null, fileIdentifier: null, fileExtension: null, attributes: [] };
+  let namespace = '';
+  const qualify = (name) => (namespace ? `${namespace}.${name}` : name);
+
+  while (stream.peek()) {
+    const token = stream.next();
+    if (token.kind === 'punct' && token.value === ';') continue;
+    switch (token.kind === 'ident' ? token.value : '') {
+      case 'include':
+        file.includes.push({ path: stream.string(), line: token.line });
+        stream.expect(';');
+        break;
+      case 'native_include':
+        stream.string();
+        stream.expect(';');
+        break;
+      case 'namespace':
+        namespace = stream.is(';') ? '' : stream.qualifiedName();
+        if (!file.namespace) file.namespace = namespace;
+        stream.expect(';');
+        break;
+      case 'attribute':
+        file.attributes.push(stream.peek().kind === 'string' ? stream.string() : stream.ident());
+        stream.expect(';');
+        break;
+      case 'root_type':
+        file.rootType = { name: stream.qualifiedName(), namespace, line: token.line };
+        stream.expect(';');
+        break;
+      case 'file_identifier':
+        file.fileIdentifier = stream.string();
+        stream.expect(';');
+        break;
+      case 'file_extension':
+        file.fileExtension = stream.string();
+        stream.expect(';');
+        break;
+      case 'table':
+      case 'struct': {
+        const name = stream.ident();
+        const metadata = parseMetadata(stream);
+        const definition = { name, fullName: qualify(name), isStruct: token.value === 'struct', namespace, fields: [], metadata, line: token.line, column: token.column };
+        stream.expect('{');
+        while (!stream.accept('}')) {
+          const fieldToken = stream.peek();
+          const fieldName = stream.ident();
+          stream.expect(':');
+          const type = parseType(stream);
+          const field = { name: fieldName, type, default: undefined, line: fieldToken.line, column: fieldToken.column };
+          if (stream.accept('=')) field.default = stream.constant();
+          field.metadata = parseMetadata(stream);
+          stream.expect(';');
+          definition.fields.push(field);
+        }
+        file.tables.push(definition);
+        break;
+      }
+      case 'enum':
+      case 'union': {
+        const name = stream.ident();
+        let underlying = null;
+        if (token.value === 'enum') {
+          stream.expect(':');
+          underlying = stream.ident();
+          if (!SCALAR_SIZES[underlying] || /float|double/.test(underlying)) throw new SchemaSyntaxError(`Enum ${name} needs an integer underlying type`, token);
+        }
+        const metadata = parseMetadata(stream);
+        const definition = { name, fullName: qualify(name), namespace, underlying, values: [], metadata, line: token.line, column: token.column };
+        stream.expect('{');
+        let next = token.value === 'union' ? 1 : 0;
+        while (!stream.accept('}')) {
+          if (stream.accept(',')) continue;
+          const valueToken = stream.peek();
+          let valueName = stream.qualifiedName();
+          let type = null;
+          if (token.value === 'union') {
+            // `Alias: Type` names a member differently from its table
+            type = valueName;
+            if (stream.accept(':')) type = stream.qualifiedName();
+            valueName = valueName.split('.').pop();
+          }
+          if (stream.accept('=')) next = stream.integer();
+          parseMetadata(stream);
+          definition.values.push({ name: valueName, value: next, type, line: valueToken.line });
+          next++;
+        }
+        (token.value === 'enum' ? file.enums : file.unions).push(definition);
+        break;
+      }
+      case 'rpc_service': {
+        const name = stream.ident();
+        const service = { name, fullName: qualify(name), methods: [], line: token.line };
+        stream.expect('{');
+        while (!stream.accept('}')) {
+          const methodName = stream.ident();
+          stream.expect('(');
+          const request = stream.qualifiedName();
+          stream.expect(')');
+          stream.expect(':');
+          const response = stream.qualifiedName();
+          const metadata = parseMetadata(stream);
+          stream.expect(';');
+          service.methods.push({ name: methodName, request, response, streaming: metadata.streaming || 'none' });
+        }
+        file.services.push(service);
+        break;
+      }
+      default:
+        throw new SchemaSyntaxError(`Unexpected "${token.value}" at the top level`, token);
+    }
+  }
+  return file;
+}
+
+// Includes resolve against `includePaths` and then the including file's directory,
+// like flatc -I
+async function loadFbsSchema(filePath, { includePaths = [] } = {}) {
+  const root = path.resolve(filePath);
+  const files = new Map();
+  const problems = [];
+  const stamps = new Map();
+
+  async function load(file, depth) {
+    if (files.has(file)) return;
+    const text = await fs.readFile(file, 'utf-8');
+    stamps.set(file, (await fs.stat(file)).mtimeMs);
+    let parsed;
+    try {
+      parsed = parseFbs(text, file);
+    } catch (error) {
+      if (!(error instanceof SchemaSyntaxError)) throw error;
+      problems.push({ file, line: error.line, column: error.column, severity: 'error', message: error.message });
+      parsed = parseFbs('', file);
+    }
+    files.set(file, parsed);
+    if (depth > MAX_INCLUDE_DEPTH) return;
+    for (const include of parsed.includes) {
+      let found = null;
+      for (const dir of [...includePaths, path.dirname(file)]) {
+        const candidate = path.resolve(dir, include.path);
+        const exists = await fs.stat(candidate).then((stat) => stat.isFile(), () => false);
+        if (exists) {
+          found = candidate;
+          break;
+        }
+      }
+      if (found) await load(found, depth + 1);
+      else problems.push({ file, line: include.line, column: 1, severity: 'error', message: `Include "${include.path}" was not found` });
+    }
+  }
+
+  await load(root, 0);
+  return { root, ...link(files, problems), stamps };
+}
+
+function link(files, problems) {
+  const types = new Map();
+  for (const file of files.values()) {
+    for (const table of file.tables) types.set(table.fullName, { kind: table.isStruct ? 'struct' : 'table', ...table, file: file.file });
+    for (const definition of file.enums) types.set(definition.fullName, { kind: 'enum', ...definition, file: file.file });
+    for (const definition of file.unions) types.set(definition.fullName, { kind: 'union', ...definition, file: file.file });
+  }
+  const scopes = new Set(types.keys());
+  for (const name of types.keys()) {
+    const parts = name.split('.');
+    for (let length = 1; length < parts.length; length++) scopes.add(parts.slice(0, length).join('.'));
+  }
+  const lookup = (name, namespace) => {
+    const resolved = resolveName(scopes, name, namespace);
+    return resolved && types.has(resolved) ? resolved : null;
+  };
+  const resolveType = (type, namespace, where) => {
+    if (type.base === 'vector' || type.base === 'array') return resolveType(type.element, namespace, where);
+    if (type.base !== 'named') return true;
+    type.resolved = lookup(type.name, namespace);
+    if (!type.resolved) problems.push({ ...where, severity: 'error', message: `Unknown type ${type.name}` });
+    return Boolean(type.resolved);
+  };
+
+  for (const type of types.values()) {
+    if (type.kind === 'union') {
+      for (const member of type.values) {
+        member.resolved = lookup(member.type, type.namespace);
+        if (!member.resolved) problems.push({ file: type.file, line: member.line, column: 1, severity: 'error', message: `Unknown union member ${member.type}` });
+      }
+      continue;
+    }
+    if (type.kind === 'enum' && type.metadata.bit_flags) {
+      // bit_flags values are bit positions
+      for (const value of type.values) value.value = 2 ** value.value;
+      continue;
+    }
+    if (type.kind === 'enum') continue;
+
+    // Field ids are declaration order, a union taking two (its _type field first),
+    // unless every field says otherwise with (id: n)
+    const explicit = type.fields.filter((field) => field.metadata.id !== undefined);
+    if (explicit.length && explicit.length !== type.fields.length && !type.isStruct) {
+      problems.push({ file: type.file, line: type.line, column: type.column, severity: 'error', message: `Either all or none of ${type.name}'s fields need an id` });
+    }
+    let nextId = 0;
+    for (const field of type.fields) {
+      resolveType(field.type, type.namespace, { file: type.file, line: field.line, column: field.column });
+      const target = field.type.base === 'named' ? types.get(field.type.resolved) : field.type.base === 'vector' && field.type.element.base === 'named' ? types.get(field.type.element.resolved) : null;
+      const isUnion = Boolean(target && target.kind === 'union');
+      if (explicit.length === type.fields.length && !type.isStruct) {
+        field.id = Number(field.metadata.id);
+      } else {
+        field.id = nextId + (isUnion ? 1 : 0);
+        nextId += isUnion ? 2 : 1;
+      }
+    }
+    if (type.isStruct) {
+      for (const field of type.fields) {
+        if (field.type.base === 'vector' || field.type.base === 'string' || (field.type.resolved && types.get(field.type.resolved).kind === 'table')) {
+          problems.push({ file: type.file, line: field.line, column: field.column, severity: 'error', message: `Struct field ${field.name} must be a scalar, struct or fixed-length array` });
+        }
+      }
+    }
+  }
+  const rootFile = files.values().next().value;
+  let rootType = null;
+  if (rootFile && rootFile.rootType) {
+    rootType = lookup(rootFile.rootType.name, rootFile.rootType.namespace);
+    if (!rootType) problems.push({ file: rootFile.file, line: rootFile.rootType.line, column: 1, severity: 'error', message: `Unknown root_type ${rootFile.rootType.name}` });
+  }
+  return { files, types, problems, rootType };
+}
+
+function scalarLayout(type, types) {
+  if (type.base === 'scalar') return { size: SCALAR_SIZES[type.scalar], align: SCALAR_SIZES[type.scalar] };
+  const named = types.get(type.resolved);
+  if (named.kind === 'enum') return { size: SCALAR_SIZES[named.underlying], align: SCALAR_SIZES[named.underlying] };
+  return structLayout(named, types);
+}
+
+// Struct fields are aligned to their own size and the struct padded to its largest
+// alignment (or force_align)
+function structLayout(struct, types) {
+  if (struct.layout) return struct.layout;
+  let offset = 0;
+  let align = 1;
+  const offsets = [];
+  for (const field of struct.fields) {
+    const element = field.type.base === 'array' ? scalarLayout(field.type.element, types) : scalarLayout(field.type, types);
+    const size = field.type.base === 'array' ? element.size * field.type.length : element.size;
+    offset = Math.ceil(offset / element.align) * element.align;
+    offsets.push(offset);
+    offset += size;
+    align = Math.max(align, element.align);
+  }
+  if (struct.metadata.force_align) align = Math.max(align, Number(struct.metadata.force_align));
+  struct.layout = { offsets, size: Math.ceil(offset / align) * align, align };
+  return struct.layout;
+}
+
+class FlatReader {
+  constructor(buffer) {
+    this.buffer = buffer;
+  }
+
+  check(pos, size) {
+    if (pos < 0 || pos + size > this.buffer.length) throw new BufferError(`Offset ${pos} is outside the ${this.buffer.length}-byte buffer`);
+  }
+
+  u8(pos) { this.check(pos, 1); return this.buffer.readUInt8(pos); }
+  u16(pos) { this.check(pos, 2); return this.buffer.readUInt16LE(pos); }
+  u32(pos) { this.check(pos, 4); return this.buffer.readUInt32LE(pos); }
+  i32(pos) { this.check(pos, 4); return this.buffer.readInt32LE(pos); }
+
+  scalar(type, pos) {
+    this.check(pos, SCALAR_SIZES[type]);
+    const buffer = this.buffer;
+    switch (type) {
+      case 'bool': return buffer[pos] !== 0;
+      case 'byte': case 'int8': return buffer.readInt8(pos);
+      case 'ubyte': case 'uint8': return buffer.readUInt8(pos);
+      case 'short': case 'int16': return buffer.readInt16LE(pos);
+      case 'ushort': case 'uint16': return buffer.readUInt16LE(pos);
+      case 'int': case 'int32': return buffer.readInt32LE(pos);
+      case 'uint': case 'uint32': return buffer.readUInt32LE(pos);
+      case 'float': case 'float32': return finite(buffer.readFloatLE(pos));
+      case 'double': case 'float64': return finite(buffer.readDoubleLE(pos));
+      case 'long': case 'int64': return safe(buffer.readBigInt64LE(pos));
+      default: return safe(buffer.readBigUInt64LE(pos));
+    }
+  }
+
+  // uoffset fields point forward from where they're stored
+  indirect(pos) {
+    return pos + this.u32(pos);
+  }
+
+  string(pos) {
+    const length = this.u32(pos);
+    this.check(pos + 4, length);
+    return this.buffer.toString('utf-8', pos + 4, pos + 4 + length);
+  }
+}
+
+function safe(value) {
+  return Number.isSafeInteger(Number(value)) ? Number(value) : value.toString();
+}
+
+function finite(value) {
+  return Number.isFinite(value) ? value : String(value).toLowerCase().replace('infinity', 'inf');
+}
+
+function enumValue(definition, value) {
+  if (definition.metadata.bit_flags && typeof value === 'number') {
+    const names = definition.values.filter((entry) => entry.value !== 0 && (value & entry.value) === entry.value).map((entry) => entry.name);
+    return names.length ? names.join(' ') : value;
+  }
+  const match = definition.values.find((entry) => entry.value === value);
+  return match ? match.name : value;
+}
+
+function decodeValue(schema, reader, type, pos, depth) {
+  if (type.base === 'scalar') return reader.scalar(type.scalar, pos);
+  if (type.base === 'string') return reader.string(reader.indirect(pos));
+  const named = schema.types.get(type.resolved);
+  if (!named) throw new BufferError(`Type ${type.name} is not defined in the schema`);
+  if (named.kind === 'enum') return enumValue(named, reader.scalar(named.underlying, pos));
+  if (named.kind === 'struct') return decodeStruct(schema, reader, named, pos, depth + 1);
+  return decodeTable(schema, reader, named, reader.indirect(pos), depth + 1);
+}
+
+function decodeStruct(schema, reader, struct, pos, depth) {
+  const layout = structLayout(struct, schema.types);
+  const result = {};
+  struct.fields.forEach((field, index) => {
+    const at = pos + layout.offsets[index];
+    if (field.type.base === 'array') {
+      const element = scalarLayout(field.type.element, schema.types);
+      result[field.name] = Array.from({ length: field.type.length }, (unused, item) => decodeValue(schema, reader, field.type.element, at + item * element.size, depth));
+    } else {
+      result[field.name] = decodeValue(schema, reader, field.type, at, depth);
+    }
+  });
+  return result;
+}
+
+function decodeTable(schema, reader, table, pos, depth) {
+  if (depth > MAX_DEPTH) throw new BufferError(`Tables are nested more than ${MAX_DEPTH} deep`);
+  const vtable = pos - reader.i32(pos);
+  const vtableSize = reader.u16(vtable);
+  // Absent slots (past the vtable's end, or zero) hold the field's default
+  const slot = (id) => (4 + id * 2 < vtableSize ? reader.u16(vtable + 4 + id * 2) : 0);
+  const result = {};
+  for (const field of table.fields) {
+    if (field.metadata.deprecated) continue;
+    const { type } = field;
+    const named = type.base === 'named' ? schema.types.get(type.resolved) : null;
+    const elementNamed = type.base === 'vector' && type.element.base === 'named' ? schema.types.get(type.element.resolved) : null;
+
+    if ((named && named.kind === 'union') || (elementNamed && elementNamed.kind === 'union')) {
+      const union = named || elementNamed;
+      const typeSlot = slot(field.id - 1);
+      const valueSlot = slot(field.id);
+      if (!typeSlot || !valueSlot) continue;
+      // Tag 0 is the implicit NONE member
+      const member = (tag) => (tag === 0 ? { name: 'NONE' } : union.values.find((entry) => entry.value === tag));
+      const decodeMember = (tag, at) => {
+        const entry = member(tag);
+        const target = entry && schema.types.get(entry.resolved);
+        if (!target) return null;
+        if (target.kind === 'struct') return decodeStruct(schema, reader, target, reader.indirect(at), depth + 1);
+        return decodeTable(schema, reader, target, reader.indirect(at), depth + 1);
+      };
+      if (named) {
+        const tag = reader.u8(pos + typeSlot);
+        result[`${field.name}_type`] = member(tag) ? member(tag).name : tag;
+        result[field.name] = decodeMember(tag, pos + valueSlot);
+      } else {
+        const tags = reader.indirect(pos + typeSlot);
+        const values = reader.indirect(pos + valueSlot);
+        const count = Math.min(reader.u32(tags), reader.u32(values));
+        result[`${field.name}_type`] = [];
+        result[field.name] = [];
+        for (let index = 0; index < count; index++) {
+          const tag = reader.u8(tags + 4 + index);
+          result[`${field.name}_type`].push(member(tag) ? member(tag).name : tag);
+          result[field.name].push(decodeMember(tag, values + 4 + index * 4));
+        }
+      }
+      continue;
+    }
+
+    const offset = slot(field.id);
+    if (!offset) continue;
+    const at = pos + offset;
+    if (type.base === 'vector') {
+      const vector = reader.indirect(at);
+      const length = reader.u32(vector);
+      const element = type.element;
+      const elementType = element.base === 'named' ? schema.types.get(element.resolved) : null;
+      // Strings and tables are stored as offsets; scalars, enums and structs inline
+      const stride = element.base === 'string' || (elementType && elementType.kind === 'table') ? 4 : scalarLayout(element, schema.types).size;
+      reader.check(vector + 4, length * stride);
+      // [ubyte] with nested_flatbuffer is a buffer of another type
+      const nested = field.metadata.nested_flatbuffer && schema.types.get(resolveName(new Set(schema.types.keys()), String(field.metadata.nested_flatbuffer), table.namespace));
+      if (nested) {
+        const bytes = reader.buffer.subarray(vector + 4, vector + 4 + length);
+        result[field.name] = decodeFlatBufferRoot(schema, nested, bytes, depth + 1);
+      } else {
+        result[field.name] = Array.from({ length }, (unused, index) => decodeValue(schema, reader, element, vector + 4 + index * stride, depth));
+      }
+    } else {
+      result[field.name] = decodeValue(schema, reader, type, at, depth);
+    }
+  }
+  return result;
+}
+
+function decodeFlatBufferRoot(schema, table, buffer, depth = 0) {
+  const reader = new FlatReader(buffer);
+  return decodeTable(schema, reader, table, reader.indirect(0), depth);
+}
+
+// Binary buffer -> JSON for `typeName`, or the schema's root_type when omitted
+function decodeFlatBuffer(schema, typeName, buffer) {
+  let table;
+  if (typeName) {
+    const wanted = String(typeName);
+    const matches = Array.from(schema.types.values()).filter((type) => type.kind === 'table' && (type.fullName === wanted || type.fullName.endsWith(`.${wanted}`)));
+    table = matches.find((type) => type.fullName === wanted) || (matches.length === 1 ? matches[0] : null);
+    if (!table) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, matches.length ? `${wanted} is ambiguous: ${matches.map((type) => type.fullName).join(', ')}` : `No table named ${wanted}`, {
+        details: { candidates: matches.map((type) => type.fullName) }
+      });
+    }
+  } else {
+    if (!schema.rootType) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'The schema declares no root_type; choose a table to decode as');
+    table = schema.types.get(schema.rootType);
+  }
+  if (buffer.length < 8) throw new BufferError('The buffer is too short to hold a FlatBuffer');
+  // The 4 bytes after the root offset are the file identifier when the schema has one
+  const identifier = buffer.toString('latin1', 4, 8);
+  return {
+    typeName: table.fullName,
+    fileIdentifier: /^[\x20-\x7e]{4}$/.test(identifier) ? identifier : null,
+    message: decodeFlatBufferRoot(schema, table, buffer)
+  };
+}
+
+// Size-prefixed buffers, one after another as flatbuffers' streaming helpers write them
+function splitSizePrefixed(buffer) {
+  const frames = [];
+  let pos = 0;
+  while (pos < buffer.length) {
+    if (pos + 4 > buffer.length) throw new BufferError(`Truncated size prefix at byte ${pos}`);
+    const length = buffer.readUInt32LE(pos);
+    if (pos + 4 + length > buffer.length) throw new BufferError(`Buffer at byte ${pos} runs past the end of the payload`);
+    frames.push({ bytes: buffer.subarray(pos + 4, pos + 4 + length) });
+    pos += 4 + length;
+  }
+  return frames;
+}
+
+function describeType(type) {
+  if (type.base === 'vector') return `[${describeType(type.element)}]`;
+  if (type.base === 'array') return `[${describeType(type.element)}:${type.length}]`;
+  if (type.base === 'scalar') return type.scalar;
+  if (type.base === 'string') return 'string';
+  return type.resolved || type.name;
+}
+
+function describeFbsSchema(schema) {
+  const file = schema.files.get(schema.root);
+  return {
+    namespace: file.namespace || null,
+    includes: file.includes.map((include) => include.path),
+    rootType: schema.rootType,
+    fileIdentifier: file.fileIdentifier,
+    fileExtension: file.fileExtension,
+    tables: file.tables.map((table) => ({
+      name: table.name,
+      fullName: table.fullName,
+      isStruct: table.isStruct,
+      line: table.line,
+      fields: schema.types.get(table.fullName).fields.map((field) => ({
+        name: field.name,
+        type: describeType(field.type),
+        id: table.isStruct ? null : field.id,
+        default: field.default ?? null,
+        deprecated: Boolean(field.metadata.deprecated),
+        required: Boolean(field.metadata.required),
+        key: Boolean(field.metadata.key)
+      }))
+    })),
+    enums: file.enums.map((definition) => ({
+      name: definition.name,
+      fullName: definition.fullName,
+      underlying: definition.underlying,
+      bitFlags: Boolean(definition.metadata.bit_flags),
+      line: definition.line,
+      values: definition.values.map(({ name, value }) => ({ name, value }))
+    })),
+    unions: file.unions.map((definition) => ({
+      name: definition.name,
+      fullName: definition.fullName,
+      line: definition.line,
+      members: definition.values.map((entry) => ({ name: entry.name, type: entry.resolved || entry.type, value: entry.value }))
+    })),
+    services: file.services.map(({ name, fullName, methods, line }) => ({ name, fullName, line, methods })),
+    tableTypes: Array.from(schema.types.values()).filter((type) => type.kind === 'table').map((type) => type.fullName).sort()
+  };
+}
+
+module.exports = { BufferError, parseFbs, loadFbsSchema, describeFbsSchema, decodeFlatBuffer, splitSizePrefixed };
@@ -0,0 +1,456 @@
+const fs = require('fs').promises;
+const os = require('os');
+const path = require('path');
+const zlib = require('zlib');
+const { BrowserWindow } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('fonts');
+
+const MAX_FONT_SIZE = 50 * 1024 * 1024;
+const MIN_SAMPLE_SIZE = 6;
+const MAX_SAMPLE_SIZE = 512;
+const DEFAULT_SAMPLE_SIZE = 48;
+const DEFAULT_WIDTH = 800;
+const MAX_WIDTH = 4096;
+const MAX_HEIGHT = 4096;
+const PADDING = 16;
+const RENDER_TIMEOUT = 15000;
+
+// Table tags by index, for WOFF2 directories that don't spell them out
+const WOFF2_KNOWN_TAGS = [
+  'cmap', 'head', 'hhea', 'hmtx', 'maxp', 'name', 'OS/2', 'post', 'cvt ', 'fpgm', 'glyf', 'loca', 'prep',
+  'CFF ', 'VORG', 'EBDT', 'EBLC', 'gasp', 'hdmx', 'kern', 'LTSH', 'PCLT', 'VDMX', 'vhea', 'vmtx', 'BASE',
+  'GDEF', 'GPOS', 'GSUB', 'EBSC', 'JSTF', 'MATH', 'CBDT', 'CBLC', 'COLR', 'CPAL', 'SVG ', 'sbix', 'acnt',
+  'avar', 'bdat', 'bloc', 'bsln', 'cvar', 'fdsc', 'feat', 'fmtx', 'fvar', 'gvar', 'hsty', 'just', 'lcar',
+  'mort', 'morx', 'opbd', 'prop', 'trak', 'Zapf', 'Silf', 'Glat', 'Gloc', 'Feat', 'Sill'
+];
+// Only these tables are read
+const WANTED_TABLES = new Set(['head', 'maxp', 'name', 'OS/2', 'cmap', 'fvar', 'CFF ', 'CFF2', 'glyf', 'COLR', 'CBDT', 'sbix', 'SVG ']);
+
+// A script counts as supported when the font maps nearly all of its probe characters.
+// `sample` is shown when no sample text is given and Latin isn't supported.
+const SCRIPTS = [
+  { name: 'Latin', probe: 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz', sample: 'The quick brown fox jumps over the lazy dog 0123456789' },
+  { name: 'Latin Extended', probe: 'ÀÁÂÃÄÅÇÈÉÊËÌÍÎÏÑÒÓÔÕÖØÙÚÛÜÝßàáâãäåçèéêëìíîïñòóôõöøùúûüýÿĀāĂăĄąĆćČčĎďĘęĚěŁłŃńŇňŐőŒœŘřŚśŠšŤťŮůŰűŸŹźŻżŽž' },
+  { name: 'Greek', probe: 'ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩαβγδεζηθικλμνξοπρστυφχψω', sample: 'Ταχίστη αλώπηξ βαφής ψημένη γη, δρασκελίζει υπέρ νωθρού κυνός' },
+  { name: 'Cyrillic', probe: 'АБВГДЕЖЗИЙКЛМНОПРСТУФХЦЧШЩЪЫЬЭЮЯабвгдежзийклмнопрстуфхцчшщъыьэюя', sample: 'Съешь же ещё этих мягких французских булок, да выпей чаю' },
+  { name: 'Armenian', probe: 'ԱԲԳԴԵԶԷԸԹԺԻԼԽԾԿՀՁՂՃՄՅՆՇՈՉՊՋՌՍՎՏՐՑՒՓՔՕՖ', sample: 'Բարեւ աշխարհ' },
+  { name: 'Georgian', probe: 'აბგდევზთიკლმნოპჟრსტუფქღყშჩცძწჭხჯჰ', sample: 'გამარჯობა მსოფლიო' },
+  { name: 'Hebrew', probe: 'אבגדהוזחטיכלמנסעפצקרשת', sample: 'דג סקרן שט בים מאוכזב ולפתע מצא חברה' },
+  { name: 'Arabic', probe: 'ابتثجحخدذرزسشصضطظعغفقكلمنهوي', sample: 'نص حكيم له سر قاطع وذو شأن عظيم' },
+  { name: 'Devanagari', probe: 'अआइईउऊएऐओऔकखगघचछजझटठडढणतथदधनपफबभमयरलवशषसह', sample: 'ऋषियों को सताने वाले दुष्ट राक्षसों के राजा रावण का सर्वनाश' },
+  { name: 'Bengali', probe: 'অআইঈউঊএঐওঔকখগঘচছজঝটঠডঢণতথদধনপফবভমযরলশষসহ', sample: 'আমার সোনার বাংলা' },
+  { name: 'Tamil', probe: 'அஆஇஈஉஊஎஏஐஒஓஔகஙசஞடணதநபமயரலவழளறன', sample: 'யாமறிந்த மொழிகளிலே தமிழ்மொழி போல்' },
+  { name: 'Thai', probe: 'กขคฆงจฉชซฌญฎฏฐฑฒณดตถทธนบปผฝพฟภมยรลวศษสหฬอฮ', sample: 'เป็นมนุษย์สุดประเสริฐเลิศคุณค่า' },
+  { name: 'Ethiopic', probe: 'ሀለሐመሠረሰሸቀበተቸኀነኘአከኸወዐዘዠየደጀገጠጨጰጸፀፈፐ', sample: 'ሰላም ልዑል' },
+  { name: 'Hangul', probe: '가나다라마바사아자차카타파하한글국어대한민국', sample: '다람쥐 헌 쳇바퀴에 타고파' },
+  { name: 'Hiragana', probe: 'あいうえおかきくけこさしすせそたちつてとなにぬねのはひふへほまみむめもやゆよらりるれろわをん', sample: 'いろはにほへと ちりぬるを' },
+  { name: 'Katakana', probe: 'アイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワヲン', sample: 'イロハニホヘト チリヌルヲ' },
+  { name: 'Han', probe: '的一是不了人我在有他这中大来上国个到说们为子和你地出道也时年', sample: '天地玄黃 宇宙洪荒 永字八法' }
+];
+const PROBE_THRESHOLD = 0.9;
+const WEIGHT_NAMES = { 100: 'Thin', 200: 'ExtraLight', 300: 'Light', 400: 'Regular', 500: 'Medium', 600: 'SemiBold', 700: 'Bold', 800: 'ExtraBold', 900: 'Black' };
+
+const tag = (buffer, offset) => buffer.toString('latin1', offset, offset + 4);
+const fixed = (buffer, offset) => buffer.readInt32BE(offset) / 65536;
+
+function invalid(message) {
+  return new ForgeError(ErrorCode.INVALID_ARGUMENT, `Not a readable font: ${message}`);
+}
+
+function readUIntBase128(buffer, state) {
+  let value = 0;
+  for (let i = 0; i < 5; i++) {
+    const byte = buffer[state.offset++];
+    if (byte === undefined || (i === 0 && byte === 0x80)) throw invalid('bad WOFF2 directory');
+    value = value * 128 + (byte & 0x7f);
+    if (!(byte & 0x80)) return value;
+  }
+  throw invalid('bad WOFF2 directory');
+}
+
+// { collectionSize, tables: { tag: Buffer } } for TrueType/OpenType, collections, WOFF and WOFF2
+function readTables(buffer, fontIndex = 0) {
+  if (buffer.length < 12) throw invalid('file too short');
+  const signature = tag(buffer, 0);
+  const tables = {};
+  if (signature === 'wOFF') {
+    const numTables = buffer.readUInt16BE(12);
+    for (let i = 0; i < numTables; i++) {
+      const entry = 44 + i * 20;
+      const name = tag(buffer, entry);
+      if (!WANTED_TABLES.has(name)) continue;
+      const offset = buffer.readUInt32BE(entry + 4);
+      const compressed = buffer.readUInt32BE(entry + 8);
+      const original = buffer.readUInt32BE(entry + 12);
+      const data = buffer.subarray(offset, offset + compressed);
+      tables[name] = compressed < original ? zlib.inflateSync(data) : data;
+    }
+    return { collectionSize: 1, tables };
+  }
+  if (signature === 'wOF2') {
+    if (tag(buffer, 4) === 'ttcf') throw invalid('WOFF2 font collections are not supported');
+    const numTables = buffer.readUInt16BE(12);
+    const compressedSize = buffer.readUInt32BE(20);
+    const state = { offset: 48 };
+    const entries = [];
+    for (let i = 0; i < numTables; i++) {
+      const flags = buffer[state.offset++];
+      let name;
+      if ((flags & 0x3f) === 0x3f) {
+        name = tag(buffer, state.offset);
+        state.offset += 4;
+      } else name = WOFF2_KNOWN_TAGS[flags & 0x3f];
+      const version = flags >> 6;
+      const originalLength = readUIntBase128(buffer, state);
+      // glyf and loca are transformed unless version 3; other tables only when nonzero
+      const transformed = name === 'glyf' || name === 'loca' ? version !== 3 : version !== 0;
+      const length = transformed ? readUIntBase128(buffer, state) : originalLength;
+      entries.push({ name, length, transformed });
+    }
+    const stream = zlib.brotliDecompressSync(buffer.subarray(state.offset, state.offset + compressedSize));
+    let offset = 0;
+    for (const entry of entries) {
+      // Transformed tables (glyf, loca, hmtx) aren't read; their presence is enough
+      if (WANTED_TABLES.has(entry.name)) tables[entry.name] = entry.transformed ? Buffer.alloc(0) : stream.subarray(offset, offset + entry.length);
+      offset += entry.length;
+    }
+    return { collectionSize: 1, tables };
+  }
+  let base = 0;
+  let collectionSize = 1;
+  if (signature === 'ttcf') {
+    collectionSize = buffer.readUInt32BE(8);
+    if (fontIndex < 0 || fontIndex >= collectionSize) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `The collection has ${collectionSize} fonts; index ${fontIndex} is out of range`);
+    base = buffer.readUInt32BE(12 + fontIndex * 4);
+  } else if (!['\0\x01\0\0', 'OTTO', 'true', 'typ1'].includes(signature)) {
+    throw invalid('unknown signature');
+  }
+  const numTables = buffer.readUInt16BE(base + 4);
+  for (let i = 0; i < numTables; i++) {
+    const entry = base + 12 + i * 16;
+    const name = tag(buffer, entry);
+    if (!WANTED_TABLES.has(name)) continue;
+    const offset = buffer.readUInt32BE(entry + 8);
+    tables[name] = buffer.subarray(offset, offset + buffer.readUInt32BE(entry + 12));
+  }
+  return { collectionSize, tables };
+}
+
+function decodeName(data, platformId, encodingId) {
+  if (platformId === 0 || (platformId === 3 && (encodingId === 1 || encodingId === 10 || encodingId === 0))) {
+    // UTF-16BE
+    const swapped = Buffer.from(data);
+    swapped.swap16();
+    return swapped.toString('utf16le');
+  }
+  if (platformId === 1 && encodingId === 0) return data.toString('latin1');
+  return null;
+}
+
+// nameID -> string, preferring Windows English names
+function readNames(table) {
+  const names = {};
+  if (!table || table.length < 6) return names;
+  const count = table.readUInt16BE(2);
+  const storage = table.readUInt16BE(4);
+  const rank = (platformId, languageId) => (platformId === 3 && languageId === 0x409 ? 0 : platformId === 3 ? 1 : platformId === 0 ? 2 : platformId === 1 && languageId === 0 ? 3 : 4);
+  const ranks = {};
+  for (let i = 0; i < count; i++) {
+    const record = 6 + i * 12;
+    if (record + 12 > table.length) break;
+    const platformId = table.readUInt16BE(record);
+    const encodingId = table.readUInt16BE(record + 2);
+    const languageId = table.readUInt16BE(record + 4);
+    const nameId = table.readUInt16BE(record + 6);
+    const length = table.readUInt16BE(record + 8);
+    const offset = storage + table.readUInt16BE(record + 10);
+    const score = rank(platformId, languageId);
+    if (ranks[nameId] !== undefined && ranks[nameId] <= score) continue;
+    const value = decodeName(table.subarray(offset, offset + length), platformId, encodingId);
+    if (value === null) continue;
+    names[nameId] = value.replace(/\0/g, '').trim();
+    ranks[nameId] = score;
+  }
+  return names;
+}
+
+// Covered code points as sorted, merged [start, end] ranges
+function readCoverage(table) {
+  if (!table || table.length < 4) return [];
+  const count = table.readUInt16BE(2);
+  const subtables = [];
+  for (let i = 0; i < count; i++) {
+    const record = 4 + i * 8;
+    subtables.push({ platformId: table.readUInt16BE(record), encodingId: table.readUInt16BE(record + 2), offset: table.readUInt32BE(record + 4) });
+  }
+  const pick = (test) => subtables.find((entry) => entry.offset < table.length && test(entry, table.readUInt16BE(entry.offset)));
+  const chosen = pick((entry, format) => format === 12)
+    || pick((entry, format) => format === 4 && (entry.platformId === 3 || entry.platformId === 0));
+  if (!chosen) return [];
+  const start = chosen.offset;
+  const ranges = [];
+  if (table.readUInt16BE(start) === 12) {
+    const groups = table.readUInt32BE(start + 12);
+    for (let i = 0; i < groups; i++) {
+      const group = start + 16 + i * 12;
+      if (group + 12 > table.length) break;
+      ranges.push([table.readUInt32BE(group), table.readUInt32BE(group + 4)]);
+    }
+  } else {
+    const segments = table.readUInt16BE(start + 6) / 2;
+    const ends = start + 14;
+    const starts = ends + segments * 2 + 2;
+    const deltas = starts + segments * 2;
+    const rangeOffsets = deltas + segments * 2;
+    for (let i = 0; i < segments; i++) {
+      const end = table.readUInt16BE(ends + i * 2);
+      const first = table.readUInt16BE(starts + i * 2);
+      if (first === 0xffff) continue;
+      const delta = table.readUInt16BE(deltas + i * 2);
+      const rangeOffset = table.readUInt16BE(rangeOffsets + i * 2);
+      for (let code = first; code <= end; code++) {
+        let glyph;
+        if (rangeOffset === 0) glyph = (code + delta) & 0xffff;
+        else {
+          const address = rangeOffsets + i * 2 + rangeOffset + (code - first) * 2;
+          glyph = address + 2 <= table.length ? table.readUInt16BE(address) : 0;
+          if (glyph) glyph = (glyph + delta) & 0xffff;
+        }
+        if (glyph) ranges.push([code, code]);
+      }
+    }
+  }
+  ranges.sort((a, b) => a[0] - b[0]);
+  const merged = [];
+  for (const range of ranges) {
+    const last = merged[merged.length - 1];
+    if (last && range[0] <= last[1] + 1) last[1] = Math.max(last[1], range[1]);
+    else merged.push([range[0], range[1]]);
+  }
+  return merged;
+}
+
+function covers(ranges, code) {
+  let low = 0;
+  let high = ranges.length - 1;
+  while (low <= high) {
+    const middle = (low + high) >> 1;
+    if (code < ranges[middle][0]) high = middle - 1;
+    else if (code > ranges[middle][1]) low = middle + 1;
+    else return true;
+  }
+  return false;
+}
+
+function readAxes(table, names) {
+  if (!table || table.length < 16) return { axes: [], instances: [] };
+  const axesOffset = table.readUInt16BE(4);
+  const axisCount = table.readUInt16BE(8);
+  const axisSize = table.readUInt16BE(10);
+  const instanceCount = table.readUInt16BE(12);
+  const instanceSize = table.readUInt16BE(14);
+  const axes = [];
+  for (let i = 0; i < axisCount; i++) {
+    const axis = axesOffset + i * axisSize;
+    axes.push({
+      tag: tag(table, axis),
+      name: names[table.readUInt16BE(axis + 18)] || tag(table, axis),
+      min: fixed(table, axis + 4),
+      default: fixed(table, axis + 8),
+      max: fixed(table, axis + 12),
+      hidden: Boolean(table.readUInt16BE(axis + 16) & 1)
+    });
+  }
+  const instances = [];
+  for (let i = 0; i < instanceCount; i++) {
+    const instance = axesOffset + axisCount * axisSize + i * instanceSize;
+    if (instance + 4 + axisCount * 4 > table.length) break;
+    const coordinates = {};
+    axes.forEach((axis, index) => {
+      coordinates[axis.tag] = fixed(table, instance + 4 + index * 4);
+    });
+    instances.push({ name: names[table.readUInt16BE(instance)] || null, coordinates });
+  }
+  return { axes, instances };
+}
+
+// fsType embedding bits, most restrictive first
+function embedding(fsType) {
+  if (fsType & 0x0002) return 'restricted';
+  if (fsType & 0x0004) return 'preview-and-print';
+  if (fsType & 0x0008) return 'editable';
+  return 'installable';
+}
+
+// Family, style, weights, variation axes, supported scripts and licensing from the font's
+// name, OS/2, fvar and cmap tables
+function readFontInfo(buffer, { fontIndex = 0 } = {}) {
+  let parsed;
+  try {
+    parsed = readTables(buffer, fontIndex);
+  } catch (error) {
+    if (error instanceof ForgeError) throw error;
+    throw invalid(error.message);
+  }
+  const { tables, collectionSize } = parsed;
+  if (!tables.cmap || !tables.head) throw invalid('missing cmap or head table');
+  const names = readNames(tables.name);
+  const os2 = tables['OS/2'] && tables['OS/2'].length >= 64 ? tables['OS/2'] : null;
+  const { axes, instances } = readAxes(tables.fvar, names);
+  const coverage = readCoverage(tables.cmap);
+  const weightAxis = axes.find((axis) => axis.tag === 'wght');
+  let weights;
+  if (instances.length && weightAxis) weights = Array.from(new Set(instances.map((instance) => Math.round(instance.coordinates.wght)))).sort((a, b) => a - b);
+  else weights = [os2 ? os2.readUInt16BE(4) : (tables.head.readUInt16BE(44) & 1 ? 700 : 400)];
+  const scripts = SCRIPTS.filter(({ probe }) => {
+    const characters = Array.from(probe);
+    return characters.filter((character) => covers(coverage, character.codePointAt(0))).length / characters.length >= PROBE_THRESHOLD;
+  }).map(({ name }) => name);
+  const signature = tag(buffer, 0);
+  return {
+    format: signature === 'wOFF' ? 'woff' : signature === 'wOF2' ? 'woff2' : signature === 'ttcf' ? 'collection' : tables['CFF '] || tables.CFF2 ? 'opentype' : 'truetype',
+    outlines: tables['CFF '] || tables.CFF2 ? 'cff' : tables.glyf ? 'truetype' : 'bitmap',
+    collectionSize,
+    fontIndex,
+    family: names[16] || names[1] || null,
+    style: names[17] || names[2] || null,
+    fullName: names[4] || null,
+    postScriptName: names[6] || null,
+    version: names[5] || null,
+    designer: names[9] || null,
+    manufacturer: names[8] || null,
+    copyright: names[0] || null,
+    license: names[13] || null,
+    licenseUrl: names[14] || null,
+    weight: os2 ? os2.readUInt16BE(4) : weights[0],
+    weights: weights.map((value) => ({ value, name: WEIGHT_NAMES[Math.round(value / 100) * 100] || null })),
+    italic: os2 ? Boolean(os2.readUInt16BE(62) & 1) : Boolean(tables.head.readUInt16BE(44) & 2),
+    widthClass: os2 ? os2.readUInt16BE(6) : 5,
+    embedding: os2 ? embedding(os2.readUInt16BE(8)) : 'installable',
+    variable: axes.length > 0,
+    axes,
+    instances,
+    color: Boolean(tables.COLR || tables.CBDT || tables.sbix || tables['SVG ']),
+    unitsPerEm: tables.head.readUInt16BE(18),
+    glyphCount: tables.maxp ? tables.maxp.readUInt16BE(4) : null,
+    characterCount: coverage.reduce((sum, [start, end]) => sum + end - start + 1, 0),
+    scripts,
+    coverage
+  };
+}
+
+async function readFontFile(filePath) {
+  if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An absolute file path is required');
+  const stats = await fs.stat(filePath).catch((error) => {
+    throw ForgeError.from(error, `Cannot read ${filePath}`);
+  });
+  if (stats.size > MAX_FONT_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} is too large to preview`);
+  return fs.readFile(filePath);
+}
+
+function escapeHtml(text) {
+  return text.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;').replace(/"/g, '&quot;');
+}
+
+// Lays the sample out with the font (embedded as a data: URL, so no file access is needed)
+// in a hidden window and captures it
+async function renderSample(buffer, info, { text, size, width, weight, variations, color, background }) {
+  const mime = info.format === 'woff' ? 'font/woff' : info.format === 'woff2' ? 'font/woff2' : info.outlines === 'cff' ? 'font/otf' : 'font/ttf';
+  const weightAxis = info.axes.find((axis) => axis.tag === 'wght');
+  const weightRange = weightAxis ? `${weightAxis.min} ${weightAxis.max}` : String(info.weight);
+  const settings = Object.entries(variations || {})
+    .filter(([axis, value]) => /^[A-Za-z0-9 ]{4}$/.test(axis) && Number.isFinite(value))
+    .map(([axis, value]) => `'${axis}' ${value}`)
+    .join(', ');
+  const dir = path.join(os.tmpdir(), `forge-font-${uuidv4()}`);
+  await fs.mkdir(dir);
+  await fs.writeFile(path.join(dir, 'index.html'), `<!DOCTYPE html><html><head><meta charset="utf-8"><style>
+@font-face { font-family: 'Forge Preview'; src: url(data:${mime};base64,${buffer.toString('base64')}); font-weight: ${weightRange}; font-style: ${info.italic ? 'italic' : 'normal'}; }
+html, body { margin: 0; background: ${background}; }
+#sample { box-sizing: border-box; width: ${width}px; padding: ${PADDING}px; color: ${color}; font: ${info.italic ? 'italic ' : ''}${weight || info.weight} ${size}px/1.3 'Forge Preview'; ${settings ? `font-variation-settings: ${settings};` : ''} white-space: pre-wrap; overflow-wrap: anywhere; }
+</style></head><body><div id="sample">${escapeHtml(text)}</div></body></html>`, 'utf-8');
+  const win = new BrowserWindow({
+    show: false,
+    width,
+    height: MAX_HEIGHT,
+    useContentSize: true,
+    enableLargerThanScreen: true,
+    webPreferences: { sandbox: true, offscreen: true }
+  });
+  try {
+    let timer;
+    const timeout = new Promise((resolve, reject) => {
+      timer = setTimeout(() => reject(new ForgeError(ErrorCode.TIMEOUT, `The font did not render within ${RENDER_TIMEOUT / 1000}s`)), RENDER_TIMEOUT);
+    });
+    const height = await Promise.race([
+      (async () => {
+        await win.loadFile(path.join(dir, 'index.html'));
+        return win.webContents.executeJavaScript(`document.fonts.ready.then(() => Math.ceil(document.getElementById('sample').getBoundingClientRect().height))`);
+      })(),
+      timeout
+    ]).finally(() => clearTimeout(timer));
+    const rect = { x: 0, y: 0, width, height: Math.min(Math.max(1, height), MAX_HEIGHT) };
+    let image = await win.webContents.capturePage(rect);
+    // The capture is in device pixels
+    const captured = image.getSize();
+    if (captured.width !== rect.width || captured.height !== rect.height) image = image.resize({ width: rect.width, height: rect.height, quality: 'best' });
+    return { png: image.toPNG(), width: rect.width, height: rect.height };
+  } finally {
+    win.destroy();
+    fs.rm(dir, { recursive: true, force: true }).catch(() => {});
+  }
+}
+
+function cssColor(value, fallback) {
+  return typeof value === 'string' && /^[#\w(),.%\s-]+$/.test(value) ? value : fallback;
+}
+
+// Font metadata plus a PNG of `sampleText` set at `size` pixels. Without sample text, a
+// pangram in the font's first supported script is used. `missing` lists sample characters
+// the font has no glyph for (they render in a fallback font).
+async function getFontPreview(filePath, options = {}) {
+  const buffer = await readFontFile(filePath);
+  const info = readFontInfo(buffer, { fontIndex: Number.isInteger(options.fontIndex) ? options.fontIndex : 0 });
+  const size = options.size === undefined ? DEFAULT_SAMPLE_SIZE : Number(options.size);
+  if (!Number.isFinite(size) || size < MIN_SAMPLE_SIZE || size > MAX_SAMPLE_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Size must be between ${MIN_SAMPLE_SIZE} and ${MAX_SAMPLE_SIZE} pixels`);
+  const width = Math.min(Math.max(Math.round(Number(options.width) || DEFAULT_WIDTH), size + PADDING * 2), MAX_WIDTH);
+  let text = typeof options.sampleText === 'string' && options.sampleText.trim() ? options.sampleText : null;
+  if (!text) {
+    const script = SCRIPTS.find(({ name, sample }) => sample && info.scripts.includes(name));
+    text = script ? script.sample : info.coverage.flatMap(([start, end]) => {
+      const characters = [];
+      for (let code = Math.max(start, 0x21); code <= end && characters.length < 64; code++) characters.push(String.fromCodePoint(code));
+      return characters;
+    }).slice(0, 64).join('');
+  }
+  const missing = Array.from(new Set(Array.from(text).filter((character) => /\S/.test(character) && !covers(info.coverage, character.codePointAt(0)))));
+  const weight = Number.isFinite(options.weight) ? options.weight : null;
+  const rendered = await renderSample(buffer, info, {
+    text,
+    size,
+    width,
+    weight,
+    variations: options.variations,
+    color: cssColor(options.color, '#1f2328'),
+    background: cssColor(options.background, '#ffffff')
+  });
+  log.debug(`Rendered a ${size}px preview of ${info.fullName || filePath}`);
+  const { coverage, ...metadata } = info;
+  return { ...metadata, sampleText: text, size, missing, ...rendered };
+}
+
+function setupFontHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('fonts:info', async (event, filePath, options = {}) => {
+    const { coverage, ...metadata } = readFontInfo(await readFontFile(filePath), options);
+    return metadata;
+  });
+  ipcMain.handle('fonts:preview', async (event, filePath, options) => getFontPreview(filePath, options || {}));
+}
+
+module.exports = { setupFontHandlers, readFontInfo, getFontPreview };
@@ -0,0 +1,157 @@
+const { JsonStore } = require('./jsonStore');
+const { runGit } = require('./git');
+const { getSecret } = require('./secrets');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const REQUEST_TIMEOUT = 30000;
+const KINDS = ['github', 'gitlab'];
+// Secrets holding the API token per forge kind; GITHUB_TOKEN / GITLAB_TOKEN back them
+const TOKEN_SECRETS = { github: 'github.token', gitlab: 'gitlab.token' };
+
+// Self-hosted instances whose host name doesn't say which forge they run, e.g.
+// { "git.example.com": "gitlab" }
+const store = new JsonStore('forge-remotes', { hosts: {} });
+
+// scp-style "git@host:owner/repo.git", ssh://, https:// and git:// remotes -> { host, path }
+function parseRemoteUrl(url) {
+  const trimmed = url.trim();
+  const scp = /^(?:[^@/\s]+@)?([^:/\s]+):(?!\/)(.+)$/.exec(trimmed);
+  let host;
+  let repoPath;
+  if (scp && !/^[a-z][a-z0-9+.-]*:\/\//i.test(trimmed)) {
+    [, host, repoPath] = scp;
+  } else {
+    let parsed;
+    try {
+      parsed = new URL(trimmed);
+    } catch {
+      return null;
+    }
+    host = parsed.hostname;
+    repoPath = parsed.pathname;
+  }
+  repoPath = repoPath.replace(/^\/+|\/+$/g, '').replace(/\.git$/, '');
+  if (!host || !repoPath.includes('/')) return null;
+  return { host: host.toLowerCase(), path: repoPath };
+}
+
+function kindForHost(host) {
+  const configured = store.get('hosts')[host];
+  if (configured) return configured;
+  if (host === 'github.com' || host.includes('github')) return 'github';
+  if (host === 'gitlab.com' || host.includes('gitlab')) return 'gitlab';
+  return null;
+}
+
+// The hosting service behind a git remote: { remote, kind, host, owner, repo, projectPath,
+// apiBase, webUrl }. GitLab projects may sit in nested groups, so `owner` is everything but
+// the last path segment.
+async function getForgeRemote(repoRoot, remoteName = 'origin') {
+  let url;
+  try {
+    url = (await runGit(repoRoot, ['remote', 'get-url', remoteName])).trim();
+  } catch {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `This repository has no remote named ${remoteName}`);
+  }
+  const parsed = parseRemoteUrl(url);
+  if (!parsed) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Could not read a host and project from remote URL ${url}`);
+  const kind = kindForHost(parsed.host);
+  if (!kind) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${parsed.host} is not a known GitHub or GitLab host`, { details: { host: parsed.host } });
+  }
+  const slash = parsed.path.lastIndexOf('/');
+  let apiBase;
+  if (kind === 'github') apiBase = parsed.host === 'github.com' ? 'https://api.github.com' : `https://${parsed.host}/api/v3`;
+  else apiBase = `https://${parsed.host}/api/v4`;
+  return {
+    remote: remoteName,
+    kind,
+    host: parsed.host,
+    owner: parsed.path.slice(0, slash),
+    repo: parsed.path.slice(slash + 1),
+    projectPath: parsed.path,
+    apiBase,
+    webUrl: `https://${parsed.host}/${parsed.path}`
+  };
+}
+
+function authHeaders(remote) {
+  const name = TOKEN_SECRETS[remote.kind];
+  const token = getSecret(name);
+  if (!token) return {};
+  return remote.kind === 'github' ? { Authorization: `Bearer ${token}` } : { 'PRIVATE-TOKEN': token };
+}
+
+// Path of a repository-scoped API endpoint: "/repos/owner/repo" on GitHub,
+// "/projects/group%2Frepo" on GitLab
+function projectApiPath(remote) {
+  return remote.kind === 'github' ? `/repos/${remote.owner}/${remote.repo}` : `/projects/${encodeURIComponent(remote.projectPath)}`;
+}
+
+// Authenticated call to the forge's REST API. `body` is sent as JSON and `form` (FormData)
+// as multipart. Resolves with parsed JSON, or the body text with `text: true`; HTTP errors
+// become ForgeErrors carrying the forge's message.
+async function forgeRequest(remote, method, apiPath, { body, form, query, text = false, signal } = {}) {
+  const url = new URL(`${remote.apiBase}${apiPath}`);
+  for (const [key, value] of Object.entries(query || {})) {
+    if (value !== undefined && value !== null) url.searchParams.set(key, String(value));
+  }
+  const headers = { Accept: remote.kind === 'github' ? 'application/vnd.github+json' : 'application/json', ...authHeaders(remote) };
+  if (body !== undefined) headers['Content-Type'] = 'application/json';
+  let response;
+  try {
+    response = await fetch(url, {
+      method,
+      headers,
+      body: form || (body === undefined ? undefined : JSON.stringify(body)),
+      signal: signal ? AbortSignal.any([signal, AbortSignal.timeout(REQUEST_TIMEOUT)]) : AbortSignal.timeout(REQUEST_TIMEOUT)
+    });
+  } catch (error) {
+    if (error.name === 'TimeoutError') throw new ForgeError(ErrorCode.TIMEOUT, `${remote.host} did not answer within ${REQUEST_TIMEOUT / 1000}s`);
+    if (error.name === 'AbortError') throw new ForgeError(ErrorCode.CANCELLED, 'Request cancelled');
+    throw new ForgeError(ErrorCode.IO_ERROR, `Could not reach ${remote.host}: ${error.cause ? error.cause.message : error.message}`);
+  }
+  const responseText = await response.text();
+  if (!response.ok) {
+    let message = responseText.slice(0, 200);
+    try {
+      const json = JSON.parse(responseText);
+      message = json.message || json.error || (Array.isArray(json.errors) ? json.errors.map((entry) => entry.message || entry).join('; ') : message);
+      if (typeof message !== 'string') message = JSON.stringify(message);
+    } catch {
+      // Not JSON; keep the start of the body
+    }
+    const hasToken = Object.keys(authHeaders(remote)).length > 0;
+    let code = ErrorCode.IO_ERROR;
+    if (response.status === 401 || response.status === 403) code = ErrorCode.PERMISSION_DENIED;
+    else if (response.status === 404) code = ErrorCode.NOT_FOUND;
+    else if (response.status === 409) code = ErrorCode.CONFLICT;
+    else if (response.status === 400 || response.status === 422) code = ErrorCode.INVALID_ARGUMENT;
+    // Private projects answer 404 to anonymous requests
+    const hint = !hasToken && (response.status === 401 || response.status === 403 || response.status === 404) ? ` (set the ${TOKEN_SECRETS[remote.kind]} secret to authenticate)` : '';
+    throw new ForgeError(code, `${remote.host} answered ${response.status}: ${message}${hint}`, { details: { status: response.status } });
+  }
+  if (text) return responseText;
+  return responseText ? JSON.parse(responseText) : null;
+}
+
+function setupForgeRemoteHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('forgeRemote:get', async (event, repoRoot, remoteName) => {
+    const remote = await getForgeRemote(repoRoot, remoteName || 'origin');
+    return { ...remote, hasToken: Boolean(getSecret(TOKEN_SECRETS[remote.kind])) };
+  });
+
+  ipcMain.handle('forgeRemote:setHostKind', async (event, host, kind) => {
+    if (typeof host !== 'string' || !host.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A host name is required');
+    if (kind !== null && !KINDS.includes(kind)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Forge kind must be one of ${KINDS.join(', ')}`);
+    const hosts = { ...store.get('hosts') };
+    if (kind) hosts[host.trim().toLowerCase()] = kind;
+    else delete hosts[host.trim().toLowerCase()];
+    store.set('hosts', hosts);
+    return hosts;
+  });
+}
+
+module.exports = { setupForgeRemoteHandlers, getForgeRemote, forgeRequest, projectApiPath, parseRemoteUrl };
@@ -0,0 +1,59 @@
+// Subsequence fuzzy matching shared by quick-open, file finder, and symbol search.
+// Returns null when `query` isn't a subsequence of `candidate`, otherwise a score
+// (higher is better) and the matched character positions for highlighting.
+
+const SEPARATORS = new Set(['/', '\\', '_', '-', '.', ' ', ':']);
+
+function fuzzyScore(query, candidate) {
+  if (!query) return { score: 0, positions: [] };
+  const lowerQuery = query.toLowerCase();
+  const lowerCandidate = candidate.toLowerCase();
+
+  const positions = [];
+  let score = 0;
+  let previous = -1;
+  let queryIndex = 0;
+
+  for (let i = 0; i < candidate.length && queryIndex < lowerQuery.length; i++) {
+    if (lowerCandidate[i] !== lowerQuery[queryIndex]) continue;
+
+    let charScore = 1;
+    if (i === 0 || SEPARATORS.has(candidate[i - 1])) {
+      // Start of a word or path segment
+      charScore += 8;
+    } else if (candidate[i] !== lowerCandidate[i] && candidate[i - 1] === lowerCandidate[i - 1]) {
+      // camelCase boundary
+      charScore += 6;
+    }
+    if (previous === i - 1) {
+      // Consecutive run
+      charScore += 5;
+    }
+    if (candidate[i] === query[queryIndex]) charScore += 1;
+
+    score += charScore;
+    positions.push(i);
+    previous = i;
+    queryIndex += 1;
+  }
+
+  if (queryIndex < lowerQuery.length) return null;
+
+  // Prefer tighter matches and shorter candidates
+  score -= (positions[positions.length - 1] - positions[0]) * 0.1;
+  score -= candidate.length * 0.05;
+  return { score, positions };
+}
+
+// Score a path, favouring matches in the file name over the directory
+function fuzzyScorePath(query, relativePath) {
+  const slash = Math.max(relativePath.lastIndexOf('/'), relativePath.lastIndexOf('\\'));
+  const name = relativePath.slice(slash + 1);
+  const nameMatch = fuzzyScore(query, name);
+  if (nameMatch) {
+    return { score: nameMatch.score + 20, positions: nameMatch.positions.map((position) => position + slash + 1) };
+  }
+  return fuzzyScore(query, relativePath);
+}
+
+module.exports = { fuzzyScore, fuzzyScorePath };
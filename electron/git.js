@@ -0,0 +1,112 @@
+const { execFile } = require('child_process');
+const path = require('path');
+const { ForgeError, ErrorCode } = require('./errors');
+
+const GIT_TIMEOUT = 30000;
+const MAX_OUTPUT = 64 * 1024 * 1024;
+
+// Run a git command and resolve with stdout; a Buffer with `encoding: 'buffer'`. `input`
+// is written to stdin.
+function runGit(cwd, args, options = {}) {
+  return new Promise((resolve, reject) => {
+    const child = execFile('git', args, {
+      cwd,
+      timeout: options.timeout || GIT_TIMEOUT,
+      maxBuffer: MAX_OUTPUT,
+      encoding: options.encoding || 'utf8',
+      env: { ...process.env, GIT_TERMINAL_PROMPT: '0' }
+    }, (error, stdout, stderr) => {
+      if (error) {
+        const code = error.code === 'ENOENT' ? ErrorCode.NOT_FOUND : ErrorCode.IO_ERROR;
+        reject(new ForgeError(code, (stderr.length > 0 ? stderr.toString() : error.message).trim(), { details: { args, exitCode: typeof error.code === 'number' ? error.code : null } }));
+        return;
+      }
+      resolve(stdout);
+    });
+    if (options.input !== undefined) child.stdin.end(options.input);
+  });
+}
+
+// Repository root containing `filePath`, or null when it isn't under git
+async function findRepoRoot(filePath) {
+  try {
+    return (await runGit(path.dirname(filePath), ['rev-parse', '--show-toplevel'])).trim();
+  } catch {
+    return null;
+  }
+}
+
+// Map of 1-based line number -> { author, authorMail, time, commit } for a file
+async function blameFile(filePath) {
+  const output = await runGit(path.dirname(filePath), ['blame', '--line-porcelain', '--', path.basename(filePath)]);
+  const lines = new Map();
+  let current = null;
+  for (const line of output.split('\n')) {
+    const header = /^([0-9a-f]{40}) \d+ (\d+)/.exec(line);
+    if (header) {
+      current = { commit: header[1], line: Number(header[2]) };
+    } else if (current && line.startsWith('author ')) {
+      current.author = line.slice(7);
+    } else if (current && line.startsWith('author-mail ')) {
+      current.authorMail = line.slice(12).replace(/[<>]/g, '');
+    } else if (current && line.startsWith('author-time ')) {
+      current.time = new Date(Number(line.slice(12)) * 1000).toISOString();
+    } else if (current && line.startsWith('\t')) {
+      // Uncommitted lines are attributed to a zero commit
+      if (/^0+$/.test(current.commit)) current.author = null;
+      lines.set(current.line, current);
+      current = null;
+    }
+  }
+  return lines;
+}
+
+// Staged changes as a unified diff plus a --stat summary
+async function getStagedDiff(repoRoot) {
+  const [diff, stat] = await Promise.all([
+    runGit(repoRoot, ['diff', '--cached', '--no-color', '--no-ext-diff']),
+    runGit(repoRoot, ['diff', '--cached', '--stat', '--no-color'])
+  ]);
+  return { diff, stat };
+}
+
+// Default branch from origin/HEAD, falling back to main or master
+async function getDefaultBranch(repoRoot) {
+  try {
+    return (await runGit(repoRoot, ['symbolic-ref', '--short', 'refs/remotes/origin/HEAD'])).trim();
+  } catch {
+    for (const candidate of ['main', 'master']) {
+      try {
+        await runGit(repoRoot, ['rev-parse', '--verify', '--quiet', candidate]);
+        return candidate;
+      } catch {
+        // Try the next one
+      }
+    }
+    throw new ForgeError(ErrorCode.NOT_FOUND, 'Could not determine the default branch', { details: { repoRoot } });
+  }
+}
+
+// Everything on HEAD since it diverged from `base`: diff, stat, and commit subjects
+async function getBranchDiff(repoRoot, base) {
+  const range = `${base}...HEAD`;
+  const [diff, stat, log, branch] = await Promise.all([
+    runGit(repoRoot, ['diff', '--no-color', '--no-ext-diff', range]),
+    runGit(repoRoot, ['diff', '--stat', '--no-color', range]),
+    runGit(repoRoot, ['log', '--no-color', '--format=%s%n%b%x00', `${base}..HEAD`]),
+    runGit(repoRoot, ['rev-parse', '--abbrev-ref', 'HEAD'])
+  ]);
+  const commits = log.split('\0').map((entry) => entry.trim()).filter(Boolean);
+  return { diff, stat, commits, branch: branch.trim() };
+}
+
+// Subjects of recent commits, used to match a repository's message style
+async function getRecentSubjects(repoRoot, count = 20) {
+  try {
+    return (await runGit(repoRoot, ['log', '--no-color', '--format=%s', `-${count}`])).split('\n').filter(Boolean);
+  } catch {
+    return []; // No commits yet
+  }
+}
+
+module.exports = { runGit, findRepoRoot, blameFile, getStagedDiff, getDefaultBranch, getBranchDiff, getRecentSubjects };
@@ -0,0 +1,221 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { aiManager } = require('./ai');
+const { runJob } = require('./jobs');
+const { runGit, getStagedDiff, getDefaultBranch, getBranchDiff, getRecentSubjects } = require('./git');
+
+const log = createLogger('git-ai');
+
+// Diffs beyond this are cut per file so one huge lockfile can't crowd out real changes
+const DIFF_BUDGET_CHARS = 24000;
+const PER_FILE_CHARS = 4000;
+const PR_TEMPLATE_PATHS = [
+  '.github/pull_request_template.md',
+  '.github/PULL_REQUEST_TEMPLATE.md',
+  'PULL_REQUEST_TEMPLATE.md',
+  'docs/pull_request_template.md'
+];
+// Generated files whose contents add nothing to a description
+const NOISY_FILES = /(^|\/)(package-lock\.json|yarn\.lock|pnpm-lock\.yaml|bun\.lockb?|Cargo\.lock|go\.sum|poetry\.lock)$|\.min\.(js|css)$|\.snap$/;
+
+const store = new JsonStore('git-ai', {
+  rules: {
+    // 'conventional' (feat: …), 'plain' (imperative sentence), or 'match' (follow recent history)
+    commitStyle: 'match',
+    maxSubjectLength: 72,
+    includeBody: true,
+    // Free-form extra instructions, e.g. "Reference the Jira ticket from the branch name"
+    customInstructions: '',
+    providerId: null,
+    model: null
+  }
+});
+
+function getRules() {
+  return store.get('rules');
+}
+
+function updateRules(changes) {
+  const rules = { ...getRules(), ...changes };
+  if (!['conventional', 'plain', 'match'].includes(rules.commitStyle)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown commit style: ${rules.commitStyle}`);
+  }
+  store.set('rules', rules);
+  return rules;
+}
+
+// Trim a unified diff to the budget, file by file, summarizing what was dropped
+function condenseDiff(diff) {
+  const files = diff.split(/^(?=diff --git )/m).filter(Boolean);
+  const kept = [];
+  const omitted = [];
+  let remaining = DIFF_BUDGET_CHARS;
+  for (const file of files) {
+    const header = /^diff --git a\/(.+?) b\//.exec(file);
+    const name = header ? header[1] : 'unknown';
+    if (NOISY_FILES.test(name)) {
+      omitted.push(`${name} (generated)`);
+      continue;
+    }
+    if (remaining <= 0) {
+      omitted.push(name);
+      continue;
+    }
+    const text = file.length > PER_FILE_CHARS ? `${file.slice(0, PER_FILE_CHARS)}\n… (${file.length - PER_FILE_CHARS} more characters)\n` : file;
+    kept.push(text.slice(0, remaining));
+    remaining -= text.length;
+  }
+  return { text: kept.join(''), omitted };
+}
+
+function styleInstructions(rules, recentSubjects) {
+  const lines = [];
+  if (rules.commitStyle === 'conventional') {
+    lines.push('Use Conventional Commits: "<type>(<optional scope>): <description>" with types like feat, fix, refactor, docs, test, chore.');
+  } else if (rules.commitStyle === 'plain') {
+    lines.push('Write the subject as a plain imperative sentence starting with a capital letter, e.g. "Fix crash when saving empty files".');
+  } else if (recentSubjects.length > 0) {
+    lines.push('Match the style of these recent commit subjects from this repository:');
+    lines.push(...recentSubjects.slice(0, 10).map((subject) => `  ${subject}`));
+  }
+  lines.push(`Keep the subject under ${rules.maxSubjectLength} characters, with no trailing period.`);
+  lines.push(rules.includeBody
+    ? 'After a blank line, add a short body explaining what changed and why, wrapped at 72 columns. Omit the body for trivial changes.'
+    : 'Write only the subject line.');
+  if (rules.customInstructions) lines.push(rules.customInstructions);
+  return lines.join('\n');
+}
+
+// Strip code fences and quoting models like to add
+function cleanReply(content) {
+  const fenced = /^```[\w-]*\n([\s\S]*?)\n?```\s*$/.exec(content.trim());
+  return (fenced ? fenced[1] : content).trim().replace(/^"|"$/g, '');
+}
+
+function checkMessage(message, rules) {
+  const [subject, ...rest] = message.split('\n');
+  const warnings = [];
+  if (subject.length > rules.maxSubjectLength) warnings.push(`Subject is ${subject.length} characters (limit ${rules.maxSubjectLength})`);
+  if (/\.$/.test(subject)) warnings.push('Subject ends with a period');
+  if (rest.length > 0 && rest[0].trim() !== '') warnings.push('Missing blank line between subject and body');
+  if (rules.commitStyle === 'conventional' && !/^[a-z]+(\([^)]+\))?!?: \S/.test(subject)) {
+    warnings.push('Subject does not follow Conventional Commits');
+  }
+  return { subject, body: rest.join('\n').trim(), warnings };
+}
+
+async function requireRepo(cwd) {
+  try {
+    return (await runGit(path.resolve(cwd), ['rev-parse', '--show-toplevel'])).trim();
+  } catch {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `${cwd} is not inside a git repository`);
+  }
+}
+
+function ask(title, messages, rules) {
+  const job = runJob('ai', title, (handle) => aiManager.chat({
+    messages,
+    providerId: rules.providerId || undefined,
+    model: rules.model || undefined,
+    temperature: 0.3
+  }, { signal: handle.signal }));
+  return job.promise;
+}
+
+// Draft a commit message from the staged diff. Always a preview: nothing is committed;
+// the renderer puts the draft in the commit box for the user to edit.
+async function generateCommitMessage(cwd, overrides = {}) {
+  const rules = { ...getRules(), ...overrides };
+  const repoRoot = await requireRepo(cwd);
+  const { diff, stat } = await getStagedDiff(repoRoot);
+  if (!diff.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Nothing is staged; stage changes before generating a commit message');
+  }
+
+  const condensed = condenseDiff(diff);
+  const recentSubjects = rules.commitStyle === 'match' ? await getRecentSubjects(repoRoot) : [];
+  const result = await ask('Generating commit message', [
+    {
+      role: 'system',
+      content: 'You write git commit messages for staged changes. Describe what the change does and why, ' +
+        'not how the diff looks. Reply with the commit message only.\n\n' + styleInstructions(rules, recentSubjects)
+    },
+    {
+      role: 'user',
+      content: `Files changed:\n${stat}\n${condensed.omitted.length > 0 ? `Not shown: ${condensed.omitted.join(', ')}\n` : ''}\nDiff:\n${condensed.text}`
+    }
+  ], rules);
+
+  const message = cleanReply(result.content);
+  log.info(`Drafted commit message for ${repoRoot}`);
+  return { repoRoot, message, ...checkMessage(message, rules), dryRun: true };
+}
+
+async function readPrTemplate(repoRoot) {
+  for (const candidate of PR_TEMPLATE_PATHS) {
+    try {
+      return await fs.readFile(path.join(repoRoot, candidate), 'utf-8');
+    } catch {
+      // Try the next location
+    }
+  }
+  return null;
+}
+
+// Draft a PR title and description for the current branch against `base` (default branch
+// if omitted), following the repository's PR template when it has one. Preview only.
+async function generatePrDescription(cwd, options = {}) {
+  const rules = { ...getRules(), ...(options.rules || {}) };
+  const repoRoot = await requireRepo(cwd);
+  const base = options.base || await getDefaultBranch(repoRoot);
+  const { diff, stat, commits, branch } = await getBranchDiff(repoRoot, base);
+  if (!diff.trim() && commits.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${branch} has no changes compared to ${base}`);
+  }
+
+  const condensed = condenseDiff(diff);
+  const template = await readPrTemplate(repoRoot);
+  const result = await ask('Generating PR description', [
+    {
+      role: 'system',
+      content: 'You write pull request descriptions. Open with one or two plain sentences saying what the change does and why. ' +
+        'Keep it under 250 words, and do not restate the file list. ' +
+        (template ? `Fill in this template's sections:\n${template}\n` : 'Use "## Summary" and "## Testing" sections. ') +
+        '\nReply with the title on the first line, a blank line, then the description in Markdown.' +
+        (rules.customInstructions ? `\n${rules.customInstructions}` : '')
+    },
+    {
+      role: 'user',
+      content: `Branch: ${branch} (into ${base})\nCommits:\n${commits.map((commit) => `- ${commit.split('\n')[0]}`).join('\n')}\n\n` +
+        `Files changed:\n${stat}\n${condensed.omitted.length > 0 ? `Not shown: ${condensed.omitted.join(', ')}\n` : ''}\nDiff:\n${condensed.text}`
+    }
+  ], rules);
+
+  const [title, ...rest] = cleanReply(result.content).split('\n');
+  return {
+    repoRoot,
+    base,
+    branch,
+    title: title.replace(/^#+\s*/, '').trim(),
+    body: rest.join('\n').trim(),
+    usedTemplate: Boolean(template),
+    dryRun: true
+  };
+}
+
+function setupGitAiHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('gitAi:generateCommitMessage', async (event, cwd, overrides) => generateCommitMessage(cwd, overrides));
+
+  ipcMain.handle('gitAi:generatePrDescription', async (event, cwd, options) => generatePrDescription(cwd, options));
+
+  ipcMain.handle('gitAi:getRules', async () => getRules());
+
+  ipcMain.handle('gitAi:updateRules', async (event, changes) => updateRules(changes));
+}
+
+module.exports = { setupGitAiHandlers, generateCommitMessage, generatePrDescription };
@@ -0,0 +1,501 @@
+const path = require('path');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { getSecret } = require('./secrets');
+const { connectWebSocket } = require('./websocket');
+const { configHash, loadCache, saveCache, deleteCache } = require('./indexCache');
+const { GraphQLSyntaxError, buildSchema, validateDocument, completeAt, selectOperation, variableTypeString } = require('./graphqlLanguage');
+
+const log = createLogger('graphql');
+
+const REQUEST_TIMEOUT = 60 * 1000;
+const MAX_SUBSCRIPTIONS = 20;
+// Subprotocols in order of preference: graphql-ws's current protocol, then the older
+// subscriptions-transport-ws one many servers still speak
+const WS_PROTOCOLS = ['graphql-transport-ws', 'graphql-ws'];
+
+const INTROSPECTION_QUERY = `query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives { name description locations args { ...InputValue } }
+  }
+}
+fragment FullType on __Type {
+  kind name description
+  fields(includeDeprecated: true) { name description args { ...InputValue } type { ...TypeRef } isDeprecated deprecationReason }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) { name description isDeprecated deprecationReason }
+  possibleTypes { ...TypeRef }
+}
+fragment InputValue on __InputValue { name description type { ...TypeRef } defaultValue }
+fragment TypeRef on __Type {
+  kind name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } } } } }
+}`;
+
+const store = new JsonStore('graphql', {
+  // Workspace root -> { endpoints: { id -> endpoint }, collections: { id -> collection } }
+  workspaces: {}
+});
+
+// Introspected schemas by workspace root and endpoint id: key -> { introspection, schema, fetchedAt }
+const schemas = new Map();
+// Live subscriptions by id: { id, connection, protocol, owner, operationId }
+const subscriptions = new Map();
+
+function workspaceData(root) {
+  const data = store.get('workspaces')[root] || {};
+  return { endpoints: data.endpoints || {}, collections: data.collections || {} };
+}
+
+function saveWorkspaceData(root, data) {
+  const workspaces = store.get('workspaces');
+  if (Object.keys(data.endpoints).length === 0 && Object.keys(data.collections).length === 0) delete workspaces[root];
+  else workspaces[root] = data;
+  store.set('workspaces', workspaces);
+}
+
+function validateEndpoint(input) {
+  if (!input || typeof input.name !== 'string' || !input.name.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'GraphQL endpoint needs a name');
+  const checkUrl = (value, protocols, field) => {
+    let url;
+    try {
+      url = new URL(value);
+    } catch {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${field} is not a valid URL`);
+    }
+    if (!protocols.includes(url.protocol)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${field} must use ${protocols.join(' or ')}`);
+    return url.toString();
+  };
+  const headers = input.headers || {};
+  if (typeof headers !== 'object' || Array.isArray(headers) || Object.values(headers).some((value) => typeof value !== 'string')) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'headers must map names to strings');
+  }
+  return {
+    name: input.name.trim(),
+    url: checkUrl(input.url, ['http:', 'https:'], 'url'),
+    // Defaults to the HTTP URL with a ws:// or wss:// scheme
+    subscriptionUrl: input.subscriptionUrl ? checkUrl(input.subscriptionUrl, ['ws:', 'wss:'], 'subscriptionUrl') : null,
+    headers,
+    // Name of a stored secret sent as the Authorization header, so tokens stay out of
+    // settings files and the renderer
+    authSecret: typeof input.authSecret === 'string' && input.authSecret ? input.authSecret : null
+  };
+}
+
+function listEndpoints(root) {
+  return Object.values(workspaceData(root).endpoints).sort((a, b) => a.name.localeCompare(b.name));
+}
+
+function getEndpoint(root, id) {
+  const endpoint = workspaceData(root).endpoints[id];
+  if (!endpoint) throw new ForgeError(ErrorCode.NOT_FOUND, `GraphQL endpoint ${id} not found`, { details: { id } });
+  return endpoint;
+}
+
+function saveEndpoint(root, input) {
+  const data = workspaceData(root);
+  const existing = input.id ? data.endpoints[input.id] : null;
+  if (input.id && !existing) throw new ForgeError(ErrorCode.NOT_FOUND, `GraphQL endpoint ${input.id} not found`);
+  const now = new Date().toISOString();
+  const endpoint = {
+    id: existing ? existing.id : `graphql_endpoint_${uuidv4()}`,
+    ...validateEndpoint({ ...(existing || {}), ...input }),
+    createdAt: existing ? existing.createdAt : now,
+    updatedAt: now
+  };
+  data.endpoints[endpoint.id] = endpoint;
+  saveWorkspaceData(root, data);
+  // A different URL is a different schema
+  if (existing && existing.url !== endpoint.url) schemas.delete(schemaKey(root, endpoint.id));
+  return endpoint;
+}
+
+async function deleteEndpoint(root, id) {
+  const data = workspaceData(root);
+  getEndpoint(root, id);
+  delete data.endpoints[id];
+  saveWorkspaceData(root, data);
+  schemas.delete(schemaKey(root, id));
+  await deleteCache(root, cacheName(id));
+  return true;
+}
+
+function requestHeaders(endpoint) {
+  const headers = { ...endpoint.headers };
+  if (endpoint.authSecret) {
+    const secret = getSecret(endpoint.authSecret);
+    if (!secret) throw new ForgeError(ErrorCode.NOT_FOUND, `Secret ${endpoint.authSecret} for ${endpoint.name} is not set`);
+    // A bare token is sent as a bearer token
+    headers.Authorization = /^\S+\s+\S/.test(secret) ? secret : `Bearer ${secret}`;
+  }
+  return headers;
+}
+
+async function post(endpoint, body) {
+  const started = Date.now();
+  let response;
+  try {
+    response = await fetch(endpoint.url, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json', Accept: 'application/graphql-response+json, application/json', ...requestHeaders(endpoint) },
+      body: JSON.stringify(body),
+      signal: AbortSignal.timeout(REQUEST_TIMEOUT)
+    });
+  } catch (error) {
+    if (error.name === 'TimeoutError') throw new ForgeError(ErrorCode.TIMEOUT, `${endpoint.name} did not answer within ${REQUEST_TIMEOUT / 1000}s`);
+    throw new ForgeError(ErrorCode.IO_ERROR, `Could not reach ${endpoint.url}: ${error.cause ? error.cause.message : error.message}`);
+  }
+  const text = await response.text();
+  let json = null;
+  try {
+    json = JSON.parse(text);
+  } catch {
+    // Reported below with the start of the body
+  }
+  return { status: response.status, ok: response.ok, duration: Date.now() - started, size: Buffer.byteLength(text), text, json };
+}
+
+function schemaKey(root, endpointId) {
+  return `${root}\0${endpointId}`;
+}
+
+function cacheName(endpointId) {
+  return `graphql-${endpointId}`;
+}
+
+// The endpoint's schema, introspected on first use and cached on disk so completion and
+// validation work offline; `refresh` introspects again
+async function getSchema(root, endpointId, { refresh = false } = {}) {
+  const endpoint = getEndpoint(root, endpointId);
+  const key = schemaKey(root, endpointId);
+  const hash = configHash(endpoint.url);
+  if (!refresh) {
+    const cached = schemas.get(key);
+    if (cached) return cached;
+    const saved = await loadCache(root, cacheName(endpointId), hash);
+    if (saved) {
+      const entry = { introspection: saved.data, schema: buildSchema(saved.data), fetchedAt: saved.savedAt };
+      schemas.set(key, entry);
+      return entry;
+    }
+  }
+  const result = await post(endpoint, { query: INTROSPECTION_QUERY, operationName: 'IntrospectionQuery' });
+  if (!result.json || !result.json.data || !result.json.data.__schema) {
+    const reason = result.json && result.json.errors ? result.json.errors.map((error) => error.message).join('; ') : `HTTP ${result.status}`;
+    throw new ForgeError(ErrorCode.IO_ERROR, `Could not introspect ${endpoint.name}: ${reason}`);
+  }
+  const introspection = result.json.data.__schema;
+  const fetchedAt = (await saveCache(root, cacheName(endpointId), hash, introspection)) || new Date().toISOString();
+  const entry = { introspection, schema: buildSchema(introspection), fetchedAt };
+  schemas.set(key, entry);
+  log.info(`Introspected ${endpoint.name}: ${introspection.types.length} types`);
+  return entry;
+}
+
+// Validation needs a schema; without a cached one the document is only parsed, so the
+// editor doesn't introspect on every keystroke
+async function cachedSchema(root, endpointId) {
+  if (!endpointId) return null;
+  const key = schemaKey(root, endpointId);
+  if (schemas.has(key)) return schemas.get(key).schema;
+  const endpoint = workspaceData(root).endpoints[endpointId];
+  if (!endpoint) return null;
+  const saved = await loadCache(root, cacheName(endpointId), configHash(endpoint.url));
+  if (!saved) return null;
+  const entry = { introspection: saved.data, schema: buildSchema(saved.data), fetchedAt: saved.savedAt };
+  schemas.set(key, entry);
+  return entry.schema;
+}
+
+function parseVariables(variables) {
+  if (variables === undefined || variables === null || variables === '') return {};
+  if (typeof variables === 'string') {
+    try {
+      return parseVariables(JSON.parse(variables));
+    } catch (error) {
+      if (error instanceof ForgeError) throw error;
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Variables are not valid JSON: ${error.message}`);
+    }
+  }
+  if (typeof variables !== 'object' || Array.isArray(variables)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Variables must be a JSON object');
+  return variables;
+}
+
+// The operation to run, with its required variables checked before anything is sent
+function prepareOperation(query, operationName, variables) {
+  let operation;
+  try {
+    operation = selectOperation(query, operationName);
+  } catch (error) {
+    if (error instanceof GraphQLSyntaxError) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Syntax error: ${error.message}`);
+    throw error;
+  }
+  if (!operation) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, operationName ? `No operation named ${operationName}` : 'The document has several operations; pick one by name');
+  }
+  const missing = operation.variables.filter((variable) => variable.type.kind === 'nonNull' && !variable.defaultValue && (variables[variable.name] === undefined || variables[variable.name] === null));
+  if (missing.length > 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Missing required variable(s): ${missing.map((variable) => `$${variable.name}: ${variableTypeString(variable.type)}`).join(', ')}`);
+  }
+  return operation;
+}
+
+async function execute(root, endpointId, { query, variables, operationName = null }) {
+  const endpoint = getEndpoint(root, endpointId);
+  const values = parseVariables(variables);
+  const operation = prepareOperation(query, operationName, values);
+  if (operation.operation === 'subscription') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Subscriptions run over WebSocket; use subscribe');
+  const result = await post(endpoint, { query, variables: values, operationName: operationName || (operation.name ? operation.name.value : undefined) });
+  if (!result.json) {
+    throw new ForgeError(ErrorCode.IO_ERROR, `${endpoint.name} answered HTTP ${result.status} without JSON: ${result.text.slice(0, 200)}`, { details: { status: result.status } });
+  }
+  return {
+    status: result.status,
+    ok: result.ok && !result.json.errors,
+    duration: result.duration,
+    size: result.size,
+    data: result.json.data === undefined ? null : result.json.data,
+    errors: result.json.errors || null,
+    extensions: result.json.extensions || null
+  };
+}
+
+function subscriptionUrl(endpoint) {
+  if (endpoint.subscriptionUrl) return endpoint.subscriptionUrl;
+  const url = new URL(endpoint.url);
+  url.protocol = url.protocol === 'https:' ? 'wss:' : 'ws:';
+  return url.toString();
+}
+
+function endSubscription(subscription, reason, error = null) {
+  if (!subscriptions.delete(subscription.id)) return;
+  const { connection } = subscription;
+  if (connection.isOpen) {
+    connection.send(JSON.stringify({ id: subscription.operationId, type: subscription.protocol === 'graphql-ws' ? 'stop' : 'complete' }));
+    connection.close(1000, 'Subscription ended');
+  }
+  log.debug(`Subscription ${subscription.id} ended (${reason})`);
+  emit('graphql:subscriptionEnded', { subscriptionId: subscription.id, reason, error }, { target: subscription.owner });
+}
+
+function handleSubscriptionMessage(subscription, raw) {
+  let message;
+  try {
+    message = JSON.parse(raw);
+  } catch {
+    return;
+  }
+  const { connection, protocol } = subscription;
+  switch (message.type) {
+    case 'connection_ack':
+      connection.send(JSON.stringify({ id: subscription.operationId, type: protocol === 'graphql-ws' ? 'start' : 'subscribe', payload: subscription.payload }));
+      break;
+    case 'ping':
+      connection.send(JSON.stringify({ type: 'pong', payload: message.payload }));
+      break;
+    case 'next':
+    case 'data':
+      emit('graphql:subscriptionData', {
+        subscriptionId: subscription.id,
+        data: message.payload ? message.payload.data ?? null : null,
+        errors: message.payload ? message.payload.errors || null : null,
+        receivedAt: new Date().toISOString()
+      }, { target: subscription.owner });
+      break;
+    case 'error': {
+      // graphql-transport-ws sends a list of errors; the older protocol an object
+      const errors = Array.isArray(message.payload) ? message.payload : [message.payload];
+      endSubscription(subscription, 'error', errors.map((error) => (error && error.message) || String(error)).join('; '));
+      break;
+    }
+    case 'connection_error':
+      endSubscription(subscription, 'error', (message.payload && message.payload.message) || 'The server refused the connection');
+      break;
+    case 'complete':
+      endSubscription(subscription, 'completed');
+      break;
+    default:
+      // 'ka' keep-alives and anything else
+      break;
+  }
+}
+
+// Start a subscription over WebSocket. Results arrive as graphql:subscriptionData events
+// for the calling window until it completes, fails, or is stopped.
+async function subscribe(root, endpointId, { query, variables, operationName = null }, owner) {
+  const endpoint = getEndpoint(root, endpointId);
+  const values = parseVariables(variables);
+  const operation = prepareOperation(query, operationName, values);
+  if (operation.operation !== 'subscription') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Only subscriptions run over WebSocket; this is a ${operation.operation}`);
+  if (subscriptions.size >= MAX_SUBSCRIPTIONS) throw new ForgeError(ErrorCode.BUSY, `At most ${MAX_SUBSCRIPTIONS} subscriptions can run at once`);
+  const headers = requestHeaders(endpoint);
+  let connection;
+  try {
+    connection = await connectWebSocket(subscriptionUrl(endpoint), { protocols: WS_PROTOCOLS, headers });
+  } catch (error) {
+    throw new ForgeError(ErrorCode.IO_ERROR, `Could not open a subscription to ${endpoint.name}: ${error.message}`);
+  }
+  const subscription = {
+    id: `graphql_subscription_${uuidv4()}`,
+    connection,
+    protocol: connection.protocol === 'graphql-ws' ? 'graphql-ws' : 'graphql-transport-ws',
+    owner,
+    operationId: '1',
+    payload: { query, variables: values, operationName: operationName || (operation.name ? operation.name.value : null) }
+  };
+  subscriptions.set(subscription.id, subscription);
+  connection.on('message', (raw) => handleSubscriptionMessage(subscription, raw));
+  connection.on('close', (code) => endSubscription(subscription, 'closed', code === 1000 ? null : `Connection closed (${code})`));
+  // Headers also travel in the init payload, where most servers read auth for sockets
+  connection.send(JSON.stringify({ type: 'connection_init', payload: headers }));
+  log.info(`Subscription ${subscription.id} opened on ${endpoint.name} (${subscription.protocol})`);
+  return { subscriptionId: subscription.id, protocol: subscription.protocol };
+}
+
+function unsubscribe(subscriptionId) {
+  const subscription = subscriptions.get(subscriptionId);
+  if (!subscription) return false;
+  endSubscription(subscription, 'stopped');
+  return true;
+}
+
+function validateRequestInput(input) {
+  if (!input || typeof input.name !== 'string' || !input.name.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A saved request needs a name');
+  if (typeof input.query !== 'string') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A saved request needs a query');
+  const variables = input.variables === undefined || input.variables === null ? '' : input.variables;
+  return {
+    name: input.name.trim(),
+    endpointId: typeof input.endpointId === 'string' ? input.endpointId : null,
+    query: input.query,
+    // Kept as typed, so comments and formatting in the variables editor survive
+    variables: typeof variables === 'string' ? variables : JSON.stringify(variables, null, 2),
+    operationName: typeof input.operationName === 'string' && input.operationName ? input.operationName : null
+  };
+}
+
+function listCollections(root) {
+  return Object.values(workspaceData(root).collections).sort((a, b) => a.name.localeCompare(b.name));
+}
+
+function getCollection(data, id) {
+  const collection = data.collections[id];
+  if (!collection) throw new ForgeError(ErrorCode.NOT_FOUND, `GraphQL collection ${id} not found`, { details: { id } });
+  return collection;
+}
+
+function saveCollection(root, { id, name }) {
+  if (typeof name !== 'string' || !name.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A collection needs a name');
+  const data = workspaceData(root);
+  const now = new Date().toISOString();
+  const collection = id
+    ? { ...getCollection(data, id), name: name.trim(), updatedAt: now }
+    : { id: `graphql_collection_${uuidv4()}`, name: name.trim(), requests: [], createdAt: now, updatedAt: now };
+  data.collections[collection.id] = collection;
+  saveWorkspaceData(root, data);
+  return collection;
+}
+
+function deleteCollection(root, id) {
+  const data = workspaceData(root);
+  getCollection(data, id);
+  delete data.collections[id];
+  saveWorkspaceData(root, data);
+  return true;
+}
+
+// Add a request to a collection, or update it in place when `request.id` is given
+function saveRequest(root, collectionId, request) {
+  const data = workspaceData(root);
+  const collection = getCollection(data, collectionId);
+  const now = new Date().toISOString();
+  const fields = validateRequestInput(request);
+  const index = request.id ? collection.requests.findIndex((item) => item.id === request.id) : -1;
+  if (request.id && index === -1) throw new ForgeError(ErrorCode.NOT_FOUND, `Request ${request.id} not found in ${collection.name}`);
+  const saved = index === -1
+    ? { id: `graphql_request_${uuidv4()}`, ...fields, createdAt: now, updatedAt: now }
+    : { ...collection.requests[index], ...fields, updatedAt: now };
+  const requests = [...collection.requests];
+  if (index === -1) requests.push(saved);
+  else requests[index] = saved;
+  data.collections[collectionId] = { ...collection, requests, updatedAt: now };
+  saveWorkspaceData(root, data);
+  return saved;
+}
+
+function deleteRequest(root, collectionId, requestId) {
+  const data = workspaceData(root);
+  const collection = getCollection(data, collectionId);
+  const requests = collection.requests.filter((item) => item.id !== requestId);
+  if (requests.length === collection.requests.length) throw new ForgeError(ErrorCode.NOT_FOUND, `Request ${requestId} not found in ${collection.name}`);
+  data.collections[collectionId] = { ...collection, requests, updatedAt: new Date().toISOString() };
+  saveWorkspaceData(root, data);
+  return true;
+}
+
+function workspaceRoot(sender) {
+  const context = windowManager.fromSender(sender);
+  if (!context) throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Window not available');
+  if (!context.workspacePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to use GraphQL endpoints');
+  return path.resolve(context.workspacePath);
+}
+
+function setupGraphQLHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+  // Subscriptions end with the window that started them
+  const watchedSenders = new WeakSet();
+  const watchSender = (sender) => {
+    if (watchedSenders.has(sender)) return;
+    watchedSenders.add(sender);
+    sender.once('destroyed', () => {
+      for (const subscription of Array.from(subscriptions.values())) {
+        if (subscription.owner === sender) endSubscription(subscription, 'window closed');
+      }
+    });
+  };
+
+  ipcMain.handle('graphql:listEndpoints', async (event) => listEndpoints(workspaceRoot(event.sender)));
+  ipcMain.handle('graphql:saveEndpoint', async (event, endpoint) => saveEndpoint(workspaceRoot(event.sender), endpoint || {}));
+  ipcMain.handle('graphql:deleteEndpoint', async (event, id) => deleteEndpoint(workspaceRoot(event.sender), id));
+
+  ipcMain.handle('graphql:getSchema', async (event, endpointId, options = {}) => {
+    const { introspection, fetchedAt } = await getSchema(workspaceRoot(event.sender), endpointId, options);
+    return { endpointId, fetchedAt, schema: introspection };
+  });
+
+  ipcMain.handle('graphql:validate', async (event, endpointId, query) => {
+    const schema = await cachedSchema(workspaceRoot(event.sender), endpointId);
+    return { checkedAgainstSchema: Boolean(schema), problems: validateDocument(String(query || ''), schema) };
+  });
+
+  ipcMain.handle('graphql:complete', async (event, endpointId, query, offset) => {
+    const schema = await cachedSchema(workspaceRoot(event.sender), endpointId);
+    if (!schema) return { from: offset, items: [] };
+    return completeAt(String(query || ''), offset, schema);
+  });
+
+  ipcMain.handle('graphql:execute', async (event, endpointId, request = {}) => execute(workspaceRoot(event.sender), endpointId, request));
+
+  ipcMain.handle('graphql:subscribe', async (event, endpointId, request = {}) => {
+    watchSender(event.sender);
+    return subscribe(workspaceRoot(event.sender), endpointId, request, event.sender);
+  });
+
+  ipcMain.handle('graphql:unsubscribe', async (event, subscriptionId) => unsubscribe(subscriptionId));
+
+  ipcMain.handle('graphql:listCollections', async (event) => listCollections(workspaceRoot(event.sender)));
+  ipcMain.handle('graphql:saveCollection', async (event, collection) => saveCollection(workspaceRoot(event.sender), collection || {}));
+  ipcMain.handle('graphql:deleteCollection', async (event, id) => deleteCollection(workspaceRoot(event.sender), id));
+  ipcMain.handle('graphql:saveRequest', async (event, collectionId, request) => saveRequest(workspaceRoot(event.sender), collectionId, request || {}));
+  ipcMain.handle('graphql:deleteRequest', async (event, collectionId, requestId) => deleteRequest(workspaceRoot(event.sender), collectionId, requestId));
+}
+
+module.exports = { setupGraphQLHandlers, getSchema, execute, subscribe, unsubscribe };
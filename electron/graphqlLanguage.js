@@ -0,0 +1,781 @@
+// GraphQL executable documents against an introspected schema: a parser, the validation
+// rules an editor most needs (unknown fields and arguments, selections on leaf and
+// composite types, variables, fragments), and completion at a cursor position.
+
+class GraphQLSyntaxError extends Error {
+  constructor(message, position) {
+    super(message);
+    this.position = position;
+  }
+}
+
+const PUNCTUATORS = ['!', '$', '&', '(', ')', ':', '=', '@', '[', ']', '{', '|', '}'];
+const OPERATION_KEYWORDS = ['query', 'mutation', 'subscription'];
+const BUILTIN_SCALARS = { Int: 'int', Float: ['int', 'float'], String: 'string', Boolean: 'boolean', ID: ['string', 'int'] };
+
+function tokenize(text, { tolerant = false } = {}) {
+  const tokens = [];
+  let pos = 0;
+  while (pos < text.length) {
+    const ch = text[pos];
+    if (/[\s,\uFEFF]/.test(ch)) {
+      pos++;
+    } else if (ch === '#') {
+      while (pos < text.length && text[pos] !== '\n' && text[pos] !== '\r') pos++;
+    } else if (PUNCTUATORS.includes(ch)) {
+      tokens.push({ kind: ch, value: ch, start: pos, end: pos + 1 });
+      pos++;
+    } else if (text.startsWith('...', pos)) {
+      tokens.push({ kind: '...', value: '...', start: pos, end: pos + 3 });
+      pos += 3;
+    } else if (/[_A-Za-z]/.test(ch)) {
+      const match = text.slice(pos).match(/^[_A-Za-z][_0-9A-Za-z]*/);
+      tokens.push({ kind: 'Name', value: match[0], start: pos, end: pos + match[0].length });
+      pos += match[0].length;
+    } else if (/[-0-9]/.test(ch)) {
+      const match = text.slice(pos).match(/^-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?/);
+      if (!match || !match[0] || match[0] === '-') {
+        if (tolerant) break;
+        throw new GraphQLSyntaxError(`Unexpected character ${ch}`, pos);
+      }
+      tokens.push({ kind: match[2] || match[3] ? 'Float' : 'Int', value: match[0], start: pos, end: pos + match[0].length });
+      pos += match[0].length;
+    } else if (ch === '"') {
+      const block = text.startsWith('"""', pos);
+      let end = pos + (block ? 3 : 1);
+      let value = '';
+      for (;;) {
+        if (end >= text.length || (!block && text[end] === '\n')) {
+          if (tolerant) return { tokens, unterminated: true };
+          throw new GraphQLSyntaxError('Unterminated string', pos);
+        }
+        if (block && text.startsWith('"""', end)) {
+          end += 3;
+          break;
+        }
+        if (!block && text[end] === '"') {
+          end++;
+          break;
+        }
+        if (text[end] === '\\' && !block) {
+          const escape = text[end + 1];
+          if (escape === 'u') {
+            value += String.fromCharCode(parseInt(text.slice(end + 2, end + 6), 16));
+            end += 6;
+          } else {
+            value += { n: '\n', t: '\t', r: '\r', b: '\b', f: '\f' }[escape] || escape;
+            end += 2;
+          }
+        } else {
+          value += text[end++];
+        }
+      }
+      tokens.push({ kind: 'String', value, start: pos, end });
+      pos = end;
+    } else {
+      if (tolerant) break;
+      throw new GraphQLSyntaxError(`Unexpected character ${ch}`, pos);
+    }
+  }
+  return { tokens, unterminated: false };
+}
+
+class Parser {
+  constructor(text) {
+    this.text = text;
+    this.tokens = tokenize(text).tokens;
+    this.index = 0;
+  }
+
+  peek(kind, value) {
+    const token = this.tokens[this.index];
+    return Boolean(token && token.kind === kind && (value === undefined || token.value === value));
+  }
+
+  next() {
+    const token = this.tokens[this.index];
+    if (!token) throw new GraphQLSyntaxError('Unexpected end of document', this.text.length);
+    this.index++;
+    return token;
+  }
+
+  expect(kind, value) {
+    const token = this.tokens[this.index];
+    if (!this.peek(kind, value)) {
+      const wanted = value || kind;
+      throw new GraphQLSyntaxError(token ? `Expected ${wanted}, found ${token.value}` : `Expected ${wanted}, found end of document`, token ? token.start : this.text.length);
+    }
+    return this.next();
+  }
+
+  name() {
+    const token = this.expect('Name');
+    return { value: token.value, start: token.start };
+  }
+
+  parseDocument() {
+    const definitions = [];
+    while (this.index < this.tokens.length) definitions.push(this.parseDefinition());
+    if (definitions.length === 0) throw new GraphQLSyntaxError('The document has no operations', 0);
+    return { definitions };
+  }
+
+  parseDefinition() {
+    const token = this.tokens[this.index];
+    if (this.peek('{')) {
+      return { kind: 'operation', operation: 'query', name: null, variables: [], directives: [], selectionSet: this.parseSelectionSet(), start: token.start };
+    }
+    if (this.peek('Name', 'fragment')) {
+      this.next();
+      const name = this.name();
+      this.expect('Name', 'on');
+      const typeCondition = this.name();
+      return { kind: 'fragment', name, typeCondition, directives: this.parseDirectives(), selectionSet: this.parseSelectionSet(), start: token.start };
+    }
+    if (token.kind === 'Name' && OPERATION_KEYWORDS.includes(token.value)) {
+      this.next();
+      const name = this.peek('Name') ? this.name() : null;
+      const variables = this.peek('(') ? this.parseVariableDefinitions() : [];
+      return { kind: 'operation', operation: token.value, name, variables, directives: this.parseDirectives(), selectionSet: this.parseSelectionSet(), start: token.start };
+    }
+    throw new GraphQLSyntaxError(`Unexpected ${token.value}; expected an operation or fragment`, token.start);
+  }
+
+  parseVariableDefinitions() {
+    this.expect('(');
+    const variables = [];
+    while (!this.peek(')')) {
+      const dollar = this.expect('$');
+      const name = this.name();
+      this.expect(':');
+      const type = this.parseTypeRef();
+      let defaultValue = null;
+      if (this.peek('=')) {
+        this.next();
+        defaultValue = this.parseValue(true);
+      }
+      this.parseDirectives();
+      variables.push({ name: name.value, type, defaultValue, start: dollar.start });
+    }
+    this.expect(')');
+    return variables;
+  }
+
+  parseTypeRef() {
+    let type;
+    if (this.peek('[')) {
+      const open = this.next();
+      type = { kind: 'list', type: this.parseTypeRef(), start: open.start };
+      this.expect(']');
+    } else {
+      const name = this.name();
+      type = { kind: 'named', name: name.value, start: name.start };
+    }
+    if (this.peek('!')) {
+      this.next();
+      type = { kind: 'nonNull', type, start: type.start };
+    }
+    return type;
+  }
+
+  parseDirectives() {
+    const directives = [];
+    while (this.peek('@')) {
+      const at = this.next();
+      const name = this.name();
+      directives.push({ name: name.value, arguments: this.peek('(') ? this.parseArguments() : [], start: at.start });
+    }
+    return directives;
+  }
+
+  parseArguments() {
+    this.expect('(');
+    const args = [];
+    while (!this.peek(')')) {
+      const name = this.name();
+      this.expect(':');
+      args.push({ name: name.value, value: this.parseValue(false), start: name.start });
+    }
+    this.expect(')');
+    return args;
+  }
+
+  parseValue(constant) {
+    const token = this.next();
+    switch (token.kind) {
+      case '$': {
+        if (constant) throw new GraphQLSyntaxError('Variables are not allowed in default values', token.start);
+        return { kind: 'variable', name: this.name().value, start: token.start };
+      }
+      case 'Int':
+        return { kind: 'int', value: token.value, start: token.start };
+      case 'Float':
+        return { kind: 'float', value: token.value, start: token.start };
+      case 'String':
+        return { kind: 'string', value: token.value, start: token.start };
+      case 'Name':
+        if (token.value === 'true' || token.value === 'false') return { kind: 'boolean', value: token.value === 'true', start: token.start };
+        if (token.value === 'null') return { kind: 'null', start: token.start };
+        return { kind: 'enum', value: token.value, start: token.start };
+      case '[': {
+        const values = [];
+        while (!this.peek(']')) values.push(this.parseValue(constant));
+        this.expect(']');
+        return { kind: 'list', values, start: token.start };
+      }
+      case '{': {
+        const fields = [];
+        while (!this.peek('}')) {
+          const name = this.name();
+          this.expect(':');
+          fields.push({ name: name.value, value: this.parseValue(constant), start: name.start });
+        }
+        this.expect('}');
+        return { kind: 'object', fields, start: token.start };
+      }
+      default:
+        throw new GraphQLSyntaxError(`Unexpected ${token.value}; expected a value`, token.start);
+    }
+  }
+
+  parseSelectionSet() {
+    const open = this.expect('{');
+    const selections = [];
+    while (!this.peek('}')) selections.push(this.parseSelection());
+    this.expect('}');
+    if (selections.length === 0) throw new GraphQLSyntaxError('A selection set needs at least one field', open.start);
+    return { selections, start: open.start };
+  }
+
+  parseSelection() {
+    if (this.peek('...')) {
+      const spread = this.next();
+      if (this.peek('Name') && this.tokens[this.index].value !== 'on') {
+        const name = this.name();
+        return { kind: 'spread', name: name.value, directives: this.parseDirectives(), start: spread.start };
+      }
+      let typeCondition = null;
+      if (this.peek('Name', 'on')) {
+        this.next();
+        typeCondition = this.name();
+      }
+      return { kind: 'inline', typeCondition, directives: this.parseDirectives(), selectionSet: this.parseSelectionSet(), start: spread.start };
+    }
+    let name = this.name();
+    let alias = null;
+    if (this.peek(':')) {
+      this.next();
+      alias = name.value;
+      name = this.name();
+    }
+    return {
+      kind: 'field',
+      alias,
+      name: name.value,
+      arguments: this.peek('(') ? this.parseArguments() : [],
+      directives: this.parseDirectives(),
+      selectionSet: this.peek('{') ? this.parseSelectionSet() : null,
+      start: name.start
+    };
+  }
+}
+
+function parseDocument(text) {
+  return new Parser(text).parseDocument();
+}
+
+// Introspection type ref ({ kind, name, ofType }) helpers
+function namedType(ref) {
+  let current = ref;
+  while (current && current.ofType) current = current.ofType;
+  return current ? current.name : null;
+}
+
+function typeString(ref) {
+  if (!ref) return '';
+  if (ref.kind === 'NON_NULL') return `${typeString(ref.ofType)}!`;
+  if (ref.kind === 'LIST') return `[${typeString(ref.ofType)}]`;
+  return ref.name;
+}
+
+// The introspection result's __schema -> lookups used by validation and completion
+function buildSchema(introspection) {
+  const types = new Map();
+  for (const type of introspection.types || []) {
+    types.set(type.name, {
+      ...type,
+      fieldMap: new Map((type.fields || []).map((field) => [field.name, { ...field, argMap: new Map((field.args || []).map((arg) => [arg.name, arg])) }])),
+      inputMap: new Map((type.inputFields || []).map((field) => [field.name, field]))
+    });
+  }
+  return {
+    types,
+    queryType: introspection.queryType ? introspection.queryType.name : null,
+    mutationType: introspection.mutationType ? introspection.mutationType.name : null,
+    subscriptionType: introspection.subscriptionType ? introspection.subscriptionType.name : null,
+    directives: new Map((introspection.directives || []).map((directive) => [directive.name, { ...directive, argMap: new Map((directive.args || []).map((arg) => [arg.name, arg])) }]))
+  };
+}
+
+const META_FIELDS = {
+  __typename: { name: '__typename', type: { kind: 'NON_NULL', ofType: { kind: 'SCALAR', name: 'String' } }, args: [], argMap: new Map(), description: 'The name of the current object type' },
+  __schema: { name: '__schema', type: { kind: 'NON_NULL', ofType: { kind: 'OBJECT', name: '__Schema' } }, args: [], argMap: new Map(), description: 'Access the schema' },
+  __type: {
+    name: '__type',
+    type: { kind: 'OBJECT', name: '__Type' },
+    args: [{ name: 'name', type: { kind: 'NON_NULL', ofType: { kind: 'SCALAR', name: 'String' } } }],
+    argMap: new Map([['name', { name: 'name', type: { kind: 'NON_NULL', ofType: { kind: 'SCALAR', name: 'String' } } }]]),
+    description: 'Request a type by name'
+  }
+};
+
+function isComposite(type) {
+  return Boolean(type) && ['OBJECT', 'INTERFACE', 'UNION'].includes(type.kind);
+}
+
+function isInputType(type) {
+  return Boolean(type) && ['SCALAR', 'ENUM', 'INPUT_OBJECT'].includes(type.kind);
+}
+
+function rootTypeName(schema, operation) {
+  return { query: schema.queryType, mutation: schema.mutationType, subscription: schema.subscriptionType }[operation];
+}
+
+function fieldDefinition(schema, typeName, fieldName) {
+  if (fieldName === '__typename') return META_FIELDS.__typename;
+  if ((fieldName === '__schema' || fieldName === '__type') && typeName === schema.queryType) return META_FIELDS[fieldName];
+  const type = schema.types.get(typeName);
+  return type ? type.fieldMap.get(fieldName) || null : null;
+}
+
+function refFromAst(typeNode) {
+  if (typeNode.kind === 'nonNull') return { kind: 'NON_NULL', ofType: refFromAst(typeNode.type) };
+  if (typeNode.kind === 'list') return { kind: 'LIST', ofType: refFromAst(typeNode.type) };
+  return { kind: 'NAMED', name: typeNode.name };
+}
+
+function lineColumn(text, position) {
+  const before = text.slice(0, position).split('\n');
+  return { line: before.length, column: before[before.length - 1].length + 1 };
+}
+
+// Problems in `text`, checked against `schema` when one is given (syntax only otherwise):
+// [{ message, severity, line, column }]
+function validateDocument(text, schema) {
+  const problems = [];
+  const report = (position, message, severity = 'error') => problems.push({ message, severity, ...lineColumn(text, position) });
+  let document;
+  try {
+    document = parseDocument(text);
+  } catch (error) {
+    if (!(error instanceof GraphQLSyntaxError)) throw error;
+    report(error.position, `Syntax error: ${error.message}`);
+    return problems;
+  }
+
+  const operations = document.definitions.filter((definition) => definition.kind === 'operation');
+  const fragments = new Map();
+  for (const definition of document.definitions.filter((item) => item.kind === 'fragment')) {
+    if (fragments.has(definition.name.value)) report(definition.start, `Fragment ${definition.name.value} is defined more than once`);
+    fragments.set(definition.name.value, definition);
+  }
+  const names = new Set();
+  for (const operation of operations) {
+    if (!operation.name) {
+      if (operations.length > 1) report(operation.start, 'An anonymous operation must be the only operation in the document');
+    } else if (names.has(operation.name.value)) {
+      report(operation.start, `Operation ${operation.name.value} is defined more than once`);
+    } else {
+      names.add(operation.name.value);
+    }
+  }
+  if (!schema) return problems;
+
+  const usedFragments = new Set();
+
+  const checkValue = (value, ref, variables, usedVariables) => {
+    if (!ref) return;
+    if (value.kind === 'variable') {
+      usedVariables.add(value.name);
+      if (!variables.has(value.name)) report(value.start, `Variable $${value.name} is not defined`);
+      return;
+    }
+    if (value.kind === 'null') {
+      if (ref.kind === 'NON_NULL') report(value.start, `Expected a non-null ${typeString(ref.ofType)}`);
+      return;
+    }
+    const inner = ref.kind === 'NON_NULL' ? ref.ofType : ref;
+    if (inner.kind === 'LIST') {
+      // A single value is coerced to a one-item list
+      for (const item of value.kind === 'list' ? value.values : [value]) checkValue(item, inner.ofType, variables, usedVariables);
+      return;
+    }
+    const type = schema.types.get(inner.name);
+    if (!type) return;
+    if (type.kind === 'ENUM') {
+      if (value.kind !== 'enum' || !(type.enumValues || []).some((item) => item.name === value.value)) {
+        report(value.start, `Expected a ${type.name} value (${(type.enumValues || []).map((item) => item.name).join(', ')})`);
+      }
+    } else if (type.kind === 'INPUT_OBJECT') {
+      if (value.kind !== 'object') {
+        report(value.start, `Expected a ${type.name} object`);
+        return;
+      }
+      const given = new Set(value.fields.map((field) => field.name));
+      for (const field of value.fields) {
+        const definition = type.inputMap.get(field.name);
+        if (!definition) report(field.start, `${type.name} has no field ${field.name}`);
+        else checkValue(field.value, definition.type, variables, usedVariables);
+      }
+      for (const definition of type.inputMap.values()) {
+        if (definition.type.kind === 'NON_NULL' && definition.defaultValue == null && !given.has(definition.name)) {
+          report(value.start, `${type.name} requires field ${definition.name}`);
+        }
+      }
+    } else if (BUILTIN_SCALARS[type.name]) {
+      const allowed = [].concat(BUILTIN_SCALARS[type.name]);
+      if (!allowed.includes(value.kind)) report(value.start, `Expected ${type.name}, found ${value.kind === 'enum' ? value.value : value.kind}`);
+    }
+  };
+
+  const checkArguments = (args, definitions, owner, start, variables, usedVariables) => {
+    const given = new Set();
+    for (const arg of args) {
+      if (given.has(arg.name)) report(arg.start, `Argument ${arg.name} is given more than once`);
+      given.add(arg.name);
+      const definition = definitions.get(arg.name);
+      if (!definition) report(arg.start, `${owner} has no argument ${arg.name}`);
+      else checkValue(arg.value, definition.type, variables, usedVariables);
+    }
+    for (const definition of definitions.values()) {
+      if (definition.type.kind === 'NON_NULL' && definition.defaultValue == null && !given.has(definition.name)) {
+        report(start, `${owner} requires argument ${definition.name}: ${typeString(definition.type)}`);
+      }
+    }
+  };
+
+  const checkDirectives = (directives, variables, usedVariables) => {
+    for (const directive of directives) {
+      const definition = schema.directives.get(directive.name);
+      if (!definition) report(directive.start, `Unknown directive @${directive.name}`);
+      else checkArguments(directive.arguments, definition.argMap, `@${directive.name}`, directive.start, variables, usedVariables);
+    }
+  };
+
+  const checkSelectionSet = (selectionSet, typeName, variables, usedVariables, visiting) => {
+    for (const selection of selectionSet.selections) {
+      checkDirectives(selection.directives, variables, usedVariables);
+      if (selection.kind === 'field') {
+        const field = fieldDefinition(schema, typeName, selection.name);
+        if (!field) {
+          report(selection.start, `${typeName} has no field ${selection.name}`);
+          continue;
+        }
+        if (field.isDeprecated) report(selection.start, `${typeName}.${selection.name} is deprecated${field.deprecationReason ? `: ${field.deprecationReason}` : ''}`, 'warning');
+        checkArguments(selection.arguments, field.argMap, `${typeName}.${selection.name}`, selection.start, variables, usedVariables);
+        const target = schema.types.get(namedType(field.type)) || (field === META_FIELDS.__typename ? { kind: 'SCALAR' } : null);
+        if (isComposite(target) && !selection.selectionSet) {
+          report(selection.start, `${typeName}.${selection.name} returns ${typeString(field.type)}; select its fields`);
+        } else if (target && !isComposite(target) && selection.selectionSet) {
+          report(selection.start, `${typeName}.${selection.name} returns ${typeString(field.type)}, which has no fields to select`);
+        } else if (selection.selectionSet && target) {
+          checkSelectionSet(selection.selectionSet, target.name, variables, usedVariables, visiting);
+        }
+      } else if (selection.kind === 'inline') {
+        const conditionName = selection.typeCondition ? selection.typeCondition.value : typeName;
+        const condition = schema.types.get(conditionName);
+        if (!isComposite(condition)) report(selection.typeCondition ? selection.typeCondition.start : selection.start, `Unknown or non-composite type ${conditionName}`);
+        else checkSelectionSet(selection.selectionSet, conditionName, variables, usedVariables, visiting);
+      } else {
+        usedFragments.add(selection.name);
+        const fragment = fragments.get(selection.name);
+        if (!fragment) {
+          report(selection.start, `Unknown fragment ${selection.name}`);
+        } else if (visiting.has(selection.name)) {
+          report(selection.start, `Fragment ${selection.name} spreads itself`);
+        } else if (schema.types.has(fragment.typeCondition.value)) {
+          checkSelectionSet(fragment.selectionSet, fragment.typeCondition.value, variables, usedVariables, new Set([...visiting, selection.name]));
+        }
+      }
+    }
+  };
+
+  for (const operation of operations) {
+    const typeName = rootTypeName(schema, operation.operation);
+    if (!typeName) {
+      report(operation.start, `The schema does not support ${operation.operation} operations`);
+      continue;
+    }
+    const variables = new Map();
+    for (const variable of operation.variables) {
+      if (variables.has(variable.name)) report(variable.start, `Variable $${variable.name} is defined more than once`);
+      variables.set(variable.name, variable);
+      const type = schema.types.get(namedType(refFromAst(variable.type)));
+      if (!type) report(variable.start, `Unknown type ${namedType(refFromAst(variable.type))}`);
+      else if (!isInputType(type)) report(variable.start, `Variable $${variable.name} must have an input type, not ${type.name}`);
+    }
+    const usedVariables = new Set();
+    checkDirectives(operation.directives, variables, usedVariables);
+    checkSelectionSet(operation.selectionSet, typeName, variables, usedVariables, new Set());
+    for (const variable of operation.variables) {
+      if (!usedVariables.has(variable.name)) report(variable.start, `Variable $${variable.name} is never used`, 'warning');
+    }
+    if (operation.operation === 'subscription' && operation.selectionSet.selections.length > 1) {
+      report(operation.start, 'A subscription must select exactly one root field');
+    }
+  }
+
+  for (const fragment of fragments.values()) {
+    const condition = schema.types.get(fragment.typeCondition.value);
+    if (!isComposite(condition)) report(fragment.typeCondition.start, `Unknown or non-composite type ${fragment.typeCondition.value}`);
+    else if (!usedFragments.has(fragment.name.value)) report(fragment.start, `Fragment ${fragment.name.value} is never used`, 'warning');
+    // Fragments nobody spreads are still checked on their own
+    if (condition && !usedFragments.has(fragment.name.value)) {
+      checkSelectionSet(fragment.selectionSet, condition.name, new Map(), new Set(), new Set([fragment.name.value]));
+    }
+  }
+  return problems;
+}
+
+function fieldItem(field) {
+  return {
+    label: field.name,
+    kind: 'field',
+    detail: typeString(field.type),
+    documentation: field.description || null,
+    deprecated: Boolean(field.isDeprecated)
+  };
+}
+
+function argumentItems(definitions, used) {
+  return Array.from(definitions.values()).filter((arg) => !used.has(arg.name)).map((arg) => ({
+    label: arg.name,
+    kind: 'argument',
+    detail: typeString(arg.type),
+    documentation: arg.description || null,
+    deprecated: false
+  }));
+}
+
+function valueItems(schema, ref, variables, onlyVariables) {
+  const items = variables.map((name) => ({ label: onlyVariables ? name : `$${name}`, kind: 'variable', detail: null, documentation: null, deprecated: false }));
+  if (onlyVariables || !ref) return items;
+  const type = schema.types.get(namedType(ref));
+  if (type && type.kind === 'ENUM') {
+    items.push(...(type.enumValues || []).map((value) => ({ label: value.name, kind: 'enumValue', detail: type.name, documentation: value.description || null, deprecated: Boolean(value.isDeprecated) })));
+  } else if (type && type.name === 'Boolean') {
+    items.push(...['true', 'false'].map((label) => ({ label, kind: 'keyword', detail: 'Boolean', documentation: null, deprecated: false })));
+  }
+  if (ref.kind !== 'NON_NULL') items.push({ label: 'null', kind: 'keyword', detail: null, documentation: null, deprecated: false });
+  return items;
+}
+
+function typeItems(schema, predicate) {
+  return Array.from(schema.types.values())
+    .filter((type) => !type.name.startsWith('__') && predicate(type))
+    .map((type) => ({ label: type.name, kind: 'type', detail: type.kind.toLowerCase().replace('_', ' '), documentation: type.description || null, deprecated: false }));
+}
+
+function unwrapList(ref) {
+  const inner = ref && ref.kind === 'NON_NULL' ? ref.ofType : ref;
+  return inner && inner.kind === 'LIST' ? inner.ofType : inner;
+}
+
+// Completions at `offset`: { from, items }, where `from` is where the word being typed
+// starts. Works on incomplete documents by walking tokens up to the cursor.
+function completeAt(text, offset, schema) {
+  const before = text.slice(0, offset);
+  const { tokens, unterminated } = tokenize(before, { tolerant: true });
+  if (unterminated) return { from: offset, items: [] };
+  let prefix = '';
+  const last = tokens[tokens.length - 1];
+  if (last && last.kind === 'Name' && last.end === offset) {
+    prefix = last.value;
+    tokens.pop();
+  }
+  const previous = tokens[tokens.length - 1];
+  const afterDollar = Boolean(previous && previous.kind === '$' && previous.end === offset - prefix.length);
+  const fragmentNames = Array.from(text.matchAll(/\bfragment\s+([_A-Za-z]\w*)\s+on\s+([_A-Za-z]\w*)/g), (match) => ({ name: match[1], type: match[2] }));
+
+  const stack = [{ kind: 'document', op: null, expect: null, typeCondition: null }];
+  let variables = [];
+  const top = () => stack[stack.length - 1];
+  for (let i = 0; i < tokens.length; i++) {
+    const token = tokens[i];
+    const frame = top();
+    const { kind, value } = token;
+    if (frame.kind === 'document') {
+      if (kind === 'Name') {
+        if (frame.expect === 'fragmentName') frame.expect = 'on';
+        else if (frame.expect === 'on' && value === 'on') frame.expect = 'typeCondition';
+        else if (frame.expect === 'typeCondition') {
+          frame.typeCondition = value;
+          frame.expect = null;
+        } else if (!frame.op && (OPERATION_KEYWORDS.includes(value) || value === 'fragment')) {
+          frame.op = value;
+          variables = [];
+          if (value === 'fragment') frame.expect = 'fragmentName';
+        }
+      } else if (kind === '(') {
+        stack.push({ kind: 'variables', expectType: false, inDefault: false });
+      } else if (kind === '{') {
+        const typeName = frame.op === 'fragment' ? frame.typeCondition : rootTypeName(schema, frame.op || 'query');
+        if (!frame.op) variables = [];
+        stack.push({ kind: 'selection', type: typeName });
+      }
+    } else if (frame.kind === 'variables') {
+      if (kind === '$') {
+        frame.inDefault = false;
+        const name = tokens[i + 1];
+        if (name && name.kind === 'Name') {
+          variables.push(name.value);
+          i++;
+        }
+      } else if (kind === ':') frame.expectType = true;
+      else if (kind === 'Name' && frame.expectType) frame.expectType = false;
+      else if (kind === '=') frame.inDefault = true;
+      else if (kind === ')') stack.pop();
+    } else if (frame.kind === 'selection') {
+      if (kind === '...') {
+        frame.spread = true;
+        frame.on = false;
+        frame.inlineType = null;
+      } else if (kind === '@') {
+        frame.atDirective = true;
+      } else if (kind === 'Name') {
+        if (frame.atDirective) {
+          frame.atDirective = false;
+          frame.argsOwner = schema.directives.get(value) || null;
+        } else if (frame.spread && value === 'on' && !frame.on) {
+          frame.on = true;
+        } else if (frame.on) {
+          frame.inlineType = value;
+          frame.on = false;
+        } else if (frame.spread) {
+          frame.spread = false;
+        } else if (tokens[i + 1] && tokens[i + 1].kind === ':') {
+          // An alias; the field name follows
+          i++;
+        } else {
+          frame.field = fieldDefinition(schema, frame.type, value);
+          frame.argsOwner = frame.field;
+        }
+      } else if (kind === '(') {
+        stack.push({ kind: 'arguments', definitions: frame.argsOwner ? frame.argsOwner.argMap : new Map(), used: new Set(), key: null, expectValue: false, valueType: null });
+      } else if (kind === '{') {
+        let type;
+        if (frame.spread) {
+          type = frame.inlineType || frame.type;
+          frame.spread = false;
+        } else {
+          type = frame.field ? namedType(frame.field.type) : null;
+        }
+        stack.push({ kind: 'selection', type });
+      } else if (kind === '}') {
+        stack.pop();
+        if (top().kind === 'document') Object.assign(top(), { op: null, expect: null, typeCondition: null });
+      }
+    } else if (frame.kind === 'arguments' || frame.kind === 'object') {
+      if (kind === 'Name' && !frame.expectValue) {
+        frame.key = value;
+        frame.used.add(value);
+      } else if (kind === ':') {
+        frame.expectValue = true;
+        const definition = frame.definitions.get(frame.key);
+        frame.valueType = definition ? definition.type : null;
+      } else if (kind === '$') {
+        if (tokens[i + 1] && tokens[i + 1].kind === 'Name') i++;
+        frame.expectValue = false;
+      } else if (frame.expectValue && ['Name', 'Int', 'Float', 'String'].includes(kind)) {
+        frame.expectValue = false;
+      } else if (kind === '{' && frame.expectValue) {
+        frame.expectValue = false;
+        const type = schema.types.get(namedType(frame.valueType));
+        stack.push({ kind: 'object', definitions: type ? type.inputMap : new Map(), used: new Set(), key: null, expectValue: false, valueType: null });
+      } else if (kind === '[' && frame.expectValue) {
+        frame.expectValue = false;
+        stack.push({ kind: 'list', itemType: unwrapList(frame.valueType) });
+      } else if ((kind === ')' && frame.kind === 'arguments') || (kind === '}' && frame.kind === 'object')) {
+        stack.pop();
+      }
+    } else if (frame.kind === 'list') {
+      if (kind === '{') {
+        const type = schema.types.get(namedType(frame.itemType));
+        stack.push({ kind: 'object', definitions: type ? type.inputMap : new Map(), used: new Set(), key: null, expectValue: false, valueType: null });
+      } else if (kind === '[') {
+        stack.push({ kind: 'list', itemType: unwrapList(frame.itemType) });
+      } else if (kind === '$' && tokens[i + 1] && tokens[i + 1].kind === 'Name') {
+        i++;
+      } else if (kind === ']') {
+        stack.pop();
+      }
+    }
+  }
+
+  const frame = top();
+  let items = [];
+  if (frame.kind === 'document') {
+    if (frame.expect === 'typeCondition') items = typeItems(schema, isComposite);
+    else if (frame.expect === 'on') items = [{ label: 'on', kind: 'keyword', detail: null, documentation: null, deprecated: false }];
+    else if (!frame.op) items = [...OPERATION_KEYWORDS, 'fragment'].map((label) => ({ label, kind: 'keyword', detail: null, documentation: null, deprecated: false }));
+  } else if (frame.kind === 'variables') {
+    if (frame.expectType) items = typeItems(schema, isInputType);
+  } else if (frame.kind === 'selection') {
+    const type = schema.types.get(frame.type);
+    if (frame.atDirective) {
+      items = Array.from(schema.directives.values()).map((directive) => ({ label: directive.name, kind: 'directive', detail: null, documentation: directive.description || null, deprecated: false }));
+    } else if (frame.on) {
+      // An object narrows only to itself or its interfaces; abstract types to their members
+      let possible = null;
+      if (type && type.kind === 'OBJECT') possible = new Set((type.interfaces || []).map((item) => item.name));
+      else if (type && type.possibleTypes) possible = new Set(type.possibleTypes.map((item) => item.name));
+      items = typeItems(schema, (candidate) => isComposite(candidate) && (!possible || possible.has(candidate.name) || candidate.name === frame.type));
+    } else if (frame.spread) {
+      items = [
+        { label: 'on', kind: 'keyword', detail: null, documentation: null, deprecated: false },
+        ...fragmentNames.map((fragment) => ({ label: fragment.name, kind: 'fragment', detail: `on ${fragment.type}`, documentation: null, deprecated: false }))
+      ];
+    } else if (type && type.fieldMap) {
+      items = Array.from(type.fieldMap.values()).map(fieldItem);
+      items.push(fieldItem(META_FIELDS.__typename));
+      if (frame.type === schema.queryType) items.push(fieldItem(META_FIELDS.__schema), fieldItem(META_FIELDS.__type));
+    } else if (type && type.kind === 'UNION') {
+      items = [fieldItem(META_FIELDS.__typename), { label: '... on', kind: 'keyword', detail: null, documentation: null, deprecated: false }];
+    }
+  } else if (frame.kind === 'arguments' || frame.kind === 'object') {
+    items = frame.expectValue || afterDollar ? valueItems(schema, frame.valueType, variables, afterDollar) : argumentItems(frame.definitions, frame.used);
+  } else if (frame.kind === 'list') {
+    items = valueItems(schema, frame.itemType, variables, afterDollar);
+  }
+  const lower = prefix.toLowerCase();
+  return {
+    from: offset - prefix.length,
+    items: items.filter((item) => item.label.toLowerCase().startsWith(lower))
+  };
+}
+
+// The operation a request runs: the named one, or the only one
+function selectOperation(text, operationName) {
+  const document = parseDocument(text);
+  const operations = document.definitions.filter((definition) => definition.kind === 'operation');
+  if (operationName) return operations.find((operation) => operation.name && operation.name.value === operationName) || null;
+  return operations.length === 1 ? operations[0] : null;
+}
+
+function variableTypeString(typeNode) {
+  if (typeNode.kind === 'nonNull') return `${variableTypeString(typeNode.type)}!`;
+  if (typeNode.kind === 'list') return `[${variableTypeString(typeNode.type)}]`;
+  return typeNode.name;
+}
+
+module.exports = {
+  GraphQLSyntaxError,
+  parseDocument,
+  buildSchema,
+  validateDocument,
+  completeAt,
+  selectOperation,
+  variableTypeString,
+  namedType,
+  typeString
+};
@@ -0,0 +1,188 @@
+const path = require('path');
+const crypto = require('crypto');
+const { app } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { globToRegExp } = require('./editorconfig');
+
+const log = createLogger('guardrails');
+
+const OPERATIONS = ['delete', 'rename', 'replace'];
+const ACTIONS = ['allow', 'confirm', 'block'];
+const TOKEN_TTL_MS = 2 * 60 * 1000;
+
+// Patterns are globs over absolute '/'-separated paths; "~" is the home directory and
+// "${workspaceFolder}" the calling window's workspace. Rules apply to every operation
+// unless they list `operations`. The strictest matching rule wins.
+const DEFAULT_RULES = [
+  { pattern: '/', action: 'block', reason: 'Filesystem root' },
+  { pattern: '?:/', action: 'block', reason: 'Drive root' },
+  { pattern: '~', action: 'block', reason: 'Home directory' },
+  { pattern: '~/*', action: 'confirm', reason: 'Top-level folder in your home directory', operations: ['delete', 'rename'] },
+  { pattern: '${workspaceFolder}', action: 'block', reason: 'Workspace root', operations: ['delete', 'rename'] },
+  { pattern: '**/.git', action: 'confirm', reason: 'Git repository data' },
+  { pattern: '**/.git/**', action: 'confirm', reason: 'Git internals' },
+  { pattern: '/{etc,usr,bin,sbin,lib,var,boot,System,Library}/**', action: 'block', reason: 'System directory' },
+  { pattern: '?:/{Windows,Program Files,Program Files (x86)}/**', action: 'block', reason: 'System directory' }
+];
+
+const store = new JsonStore('guardrails', {
+  policy: {
+    enabled: true,
+    // What to do with paths outside the calling window's workspace
+    outsideWorkspace: 'confirm',
+    rules: DEFAULT_RULES
+  }
+});
+
+// Outstanding confirmation tokens: token -> { key, expiresAt }
+const tokens = new Map();
+
+function getPolicy() {
+  return store.get('policy');
+}
+
+function validateRule(rule) {
+  if (!rule || typeof rule.pattern !== 'string' || !rule.pattern.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Each rule needs a pattern');
+  }
+  if (!ACTIONS.includes(rule.action)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown guardrail action: ${rule.action}`);
+  }
+  if (rule.operations && (!Array.isArray(rule.operations) || rule.operations.some((op) => !OPERATIONS.includes(op)))) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Rule operations must be among: ${OPERATIONS.join(', ')}`);
+  }
+  return { pattern: rule.pattern.trim(), action: rule.action, reason: rule.reason || rule.pattern, ...(rule.operations ? { operations: rule.operations } : {}) };
+}
+
+function updatePolicy(changes = {}) {
+  const policy = { ...getPolicy(), ...changes };
+  if (!ACTIONS.includes(policy.outsideWorkspace)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown guardrail action: ${policy.outsideWorkspace}`);
+  }
+  if (!Array.isArray(policy.rules)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'rules must be a list');
+  }
+  policy.rules = policy.rules.map(validateRule);
+  policy.enabled = policy.enabled !== false;
+  store.set('policy', policy);
+  return policy;
+}
+
+function resetPolicy() {
+  store.delete('policy');
+  return getPolicy();
+}
+
+function toPosix(targetPath) {
+  const posix = path.resolve(targetPath).split(path.sep).join('/');
+  return process.platform === 'win32' ? posix.toLowerCase() : posix;
+}
+
+function ruleMatches(rule, target, workspacePath) {
+  let pattern = rule.pattern;
+  if (pattern.startsWith('${workspaceFolder}')) {
+    if (!workspacePath) return false;
+    pattern = toPosix(workspacePath) + pattern.slice('${workspaceFolder}'.length);
+  } else if (pattern === '~' || pattern.startsWith('~/')) {
+    pattern = toPosix(app.getPath('home')) + pattern.slice(1);
+  }
+  if (process.platform === 'win32') pattern = pattern.toLowerCase();
+  // "/" alone would otherwise become an empty-segment regex
+  if (pattern === '/' || pattern === '?:/') return new RegExp(pattern === '/' ? '^/$' : '^[^/]:/$').test(target);
+  return globToRegExp(pattern.replace(/\/+$/, '')).test(target);
+}
+
+function isInside(parent, child) {
+  const relative = path.relative(parent, child);
+  return relative === '' || (!relative.startsWith('..') && !path.isAbsolute(relative));
+}
+
+const SEVERITY = { allow: 0, confirm: 1, block: 2 };
+
+// Evaluate `paths` for `operation`. Returns { action, findings: [{ path, action, reason }] }
+// where action is the strictest across all paths.
+function checkPaths(operation, paths, { workspacePath } = {}) {
+  const policy = getPolicy();
+  const findings = [];
+  if (policy.enabled) {
+    for (const targetPath of paths) {
+      const target = toPosix(targetPath);
+      for (const rule of policy.rules) {
+        if (rule.operations && !rule.operations.includes(operation)) continue;
+        if (rule.action !== 'allow' && ruleMatches(rule, target, workspacePath)) {
+          findings.push({ path: targetPath, action: rule.action, reason: rule.reason });
+        }
+      }
+      if (workspacePath && policy.outsideWorkspace !== 'allow' && !isInside(path.resolve(workspacePath), path.resolve(targetPath))) {
+        findings.push({ path: targetPath, action: policy.outsideWorkspace, reason: 'Outside the workspace' });
+      }
+    }
+  }
+  const action = findings.reduce((worst, finding) => (SEVERITY[finding.action] > SEVERITY[worst] ? finding.action : worst), 'allow');
+  return { action, findings };
+}
+
+function tokenKey(operation, paths) {
+  return `${operation}\0${paths.map((p) => path.resolve(p)).sort().join('\0')}`;
+}
+
+function consumeToken(token, key) {
+  const entry = token && tokens.get(token);
+  if (!entry) return false;
+  tokens.delete(token);
+  return entry.key === key && entry.expiresAt > Date.now();
+}
+
+// Throw unless `operation` on `paths` is allowed. Blocked operations fail outright;
+// ones needing confirmation fail with CONFIRMATION_REQUIRED carrying a single-use token
+// that, passed back as `confirmationToken`, lets the same operation on the same paths through.
+function enforceGuardrails(operation, paths, { workspacePath, confirmationToken } = {}) {
+  const { action, findings } = checkPaths(operation, paths, { workspacePath });
+  if (action === 'allow') return;
+
+  if (action === 'block') {
+    const blocked = findings.filter((finding) => finding.action === 'block');
+    log.warn(`Blocked ${operation} of ${blocked.map((finding) => finding.path).join(', ')}`);
+    throw new ForgeError(ErrorCode.ACCESS_DENIED, `Blocked by guardrails: ${blocked[0].reason} (${blocked[0].path})`, {
+      details: { operation, findings: blocked },
+      recoverable: false
+    });
+  }
+
+  const key = tokenKey(operation, paths);
+  if (consumeToken(confirmationToken, key)) {
+    log.info(`Confirmed ${operation} of ${paths.length} path(s)`);
+    return;
+  }
+
+  for (const [token, entry] of tokens) {
+    if (entry.expiresAt <= Date.now()) tokens.delete(token);
+  }
+  const token = crypto.randomBytes(16).toString('hex');
+  tokens.set(token, { key, expiresAt: Date.now() + TOKEN_TTL_MS });
+  throw new ForgeError(ErrorCode.CONFIRMATION_REQUIRED, `Confirm ${operation}: ${findings[0].reason} (${findings[0].path})`, {
+    details: { operation, findings, confirmationToken: token, expiresInMs: TOKEN_TTL_MS }
+  });
+}
+
+function setupGuardrailHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('guardrails:getPolicy', async () => getPolicy());
+
+  ipcMain.handle('guardrails:updatePolicy', async (event, changes) => updatePolicy(changes));
+
+  ipcMain.handle('guardrails:resetPolicy', async () => resetPolicy());
+
+  // Dry run, for showing warnings before the user commits to an operation
+  ipcMain.handle('guardrails:check', async (event, operation, paths, workspacePath) => {
+    if (!OPERATIONS.includes(operation) || !Array.isArray(paths)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Expected an operation and a list of paths');
+    }
+    return checkPaths(operation, paths, { workspacePath });
+  });
+}
+
+module.exports = { setupGuardrailHandlers, enforceGuardrails, checkPaths };
@@ -0,0 +1,241 @@
+// Reader for Hunspell .aff/.dic dictionaries (the format LibreOffice, Firefox and most
+// Linux distributions ship). Supports the parts that decide whether a word is spelled
+// right: flag formats and aliases (FLAG, AF), prefixes and suffixes with cross products
+// (PFX, SFX), NEEDAFFIX, FORBIDDENWORD and NOSUGGEST, plus TRY and REP for suggestions.
+// Compounding and two-level affixes are not supported.
+
+const MAX_SUGGESTIONS = 8;
+
+function decode(buffer, encoding) {
+  const normalized = String(encoding || 'UTF-8').toUpperCase();
+  if (normalized === 'UTF-8') return buffer.toString('utf-8');
+  // ISO8859-1 and close relatives; anything else reads as Latin-1 rather than failing
+  return buffer.toString('latin1');
+}
+
+// Escape a Hunspell affix condition into a regexp body; [] classes and "." carry over
+function conditionSource(condition) {
+  if (!condition || condition === '.') return '';
+  return condition.replace(/[\\$()*+?{}|]/g, '\\$&');
+}
+
+class HunspellDictionary {
+  // `aff` and `dic` are the raw file contents as Buffers
+  constructor(aff, dic) {
+    const encoding = /^SET\s+(\S+)/m.exec(aff.toString('latin1'));
+    this.encoding = encoding ? encoding[1] : 'ISO8859-1';
+    this.flagType = 'char';
+    this.aliases = [];
+    this.prefixes = [];
+    this.suffixes = [];
+    this.replacements = [];
+    this.tryChars = '';
+    this.wordChars = '';
+    this.needAffix = null;
+    this.forbidden = null;
+    this.noSuggest = null;
+    // word -> Set of flags
+    this.words = new Map();
+    this.parseAff(decode(aff, this.encoding));
+    this.parseDic(decode(dic, this.encoding));
+  }
+
+  // A .dic entry's flags, which may be an index into the AF alias table
+  parseFlags(text) {
+    if (this.aliases.length > 0 && /^\d+$/.test(text)) return this.aliases[Number(text) - 1] || [];
+    return this.parseRawFlags(text);
+  }
+
+  parseRawFlags(text) {
+    if (!text) return [];
+    switch (this.flagType) {
+      case 'long':
+        return text.match(/../g) || [];
+      case 'num':
+        return text.split(',').filter(Boolean);
+      default:
+        return Array.from(text);
+    }
+  }
+
+  parseAff(text) {
+    const lines = text.split(/\r?\n/).map((line) => line.replace(/^\uFEFF/, ''));
+    for (let i = 0; i < lines.length; i++) {
+      const parts = lines[i].trim().split(/\s+/);
+      const [keyword] = parts;
+      switch (keyword) {
+        case 'FLAG':
+          if (parts[1] === 'long') this.flagType = 'long';
+          else if (parts[1] === 'num') this.flagType = 'num';
+          break;
+        case 'AF': {
+          // "AF count" followed by that many "AF flags" lines, numbered from 1
+          const count = Number(parts[1]);
+          for (let j = 0; j < count && i + 1 < lines.length && /^AF\s/.test(lines[i + 1].trim()); j++) {
+            this.aliases.push(this.parseRawFlags(lines[++i].trim().split(/\s+/)[1]));
+          }
+          break;
+        }
+        case 'TRY':
+          this.tryChars = parts[1] || '';
+          break;
+        case 'WORDCHARS':
+          this.wordChars = parts[1] || '';
+          break;
+        case 'NEEDAFFIX':
+          this.needAffix = parts[1];
+          break;
+        case 'FORBIDDENWORD':
+          this.forbidden = parts[1];
+          break;
+        case 'NOSUGGEST':
+          this.noSuggest = parts[1];
+          break;
+        case 'REP':
+          if (parts.length >= 3) this.replacements.push([parts[1].replace(/_/g, ' '), parts[2].replace(/_/g, ' ')]);
+          break;
+        case 'PFX':
+        case 'SFX': {
+          // Header: "SFX flag cross count"; rules: "SFX flag strip add[/flags] condition"
+          if (parts.length === 4 && /^\d+$/.test(parts[3]) && (parts[2] === 'Y' || parts[2] === 'N')) {
+            const flag = parts[1];
+            const cross = parts[2] === 'Y';
+            const count = Number(parts[3]);
+            for (let j = 0; j < count && i + 1 < lines.length; j++) {
+              const rule = lines[++i].trim().split(/\s+/);
+              if (rule[0] !== keyword || rule[1] !== flag) continue;
+              const strip = rule[2] === '0' ? '' : rule[2];
+              const add = (rule[3] || '').split('/')[0];
+              const condition = conditionSource(rule[4]);
+              const entry = {
+                flag,
+                cross,
+                strip,
+                add: add === '0' ? '' : add,
+                condition: keyword === 'SFX' ? new RegExp(`${condition}$`) : new RegExp(`^${condition}`)
+              };
+              (keyword === 'SFX' ? this.suffixes : this.prefixes).push(entry);
+            }
+          }
+          break;
+        }
+        default:
+          break;
+      }
+    }
+  }
+
+  parseDic(text) {
+    const lines = text.split(/\r?\n/);
+    // The first line is the (approximate) word count
+    for (let i = /^\s*\d+\s*$/.test(lines[0]) ? 1 : 0; i < lines.length; i++) {
+      const line = lines[i];
+      if (!line || line.startsWith('\t') || line.startsWith('#')) continue;
+      // Morphological fields follow the entry after whitespace
+      const entry = line.split(/[\t ]/)[0];
+      const slash = entry.search(/(?<!\\)\//);
+      const word = (slash === -1 ? entry : entry.slice(0, slash)).replace(/\\\//g, '/');
+      if (!word) continue;
+      const flags = slash === -1 ? [] : this.parseFlags(entry.slice(slash + 1));
+      const existing = this.words.get(word);
+      if (existing) flags.forEach((flag) => existing.add(flag));
+      else this.words.set(word, new Set(flags));
+    }
+  }
+
+  // Whether `word` is a dictionary entry, alone or with an allowed affix, exactly as cased
+  lookup(word) {
+    const flags = this.words.get(word);
+    if (flags) {
+      if (this.forbidden && flags.has(this.forbidden)) return false;
+      if (!this.needAffix || !flags.has(this.needAffix)) return true;
+    }
+    return this.withSuffix(word, null) || this.withPrefix(word);
+  }
+
+  // `word` as a base plus one suffix; with `prefix`, the base must also take that prefix
+  withSuffix(word, prefix) {
+    for (const rule of this.suffixes) {
+      if (!word.endsWith(rule.add) || word.length <= rule.add.length) continue;
+      if (prefix && !(prefix.cross && rule.cross)) continue;
+      const base = word.slice(0, word.length - rule.add.length) + rule.strip;
+      if (!rule.condition.test(base)) continue;
+      const flags = this.words.get(base);
+      if (!flags || !flags.has(rule.flag) || (this.forbidden && flags.has(this.forbidden))) continue;
+      if (prefix && !flags.has(prefix.flag)) continue;
+      return true;
+    }
+    return false;
+  }
+
+  withPrefix(word) {
+    for (const rule of this.prefixes) {
+      if (!word.startsWith(rule.add) || word.length <= rule.add.length) continue;
+      const base = rule.strip + word.slice(rule.add.length);
+      if (!rule.condition.test(base)) continue;
+      const flags = this.words.get(base);
+      if (flags && flags.has(rule.flag) && !(this.forbidden && flags.has(this.forbidden))) return true;
+      if (rule.cross && this.withSuffix(base, rule)) return true;
+    }
+    return false;
+  }
+
+  // Like Hunspell, "Word" may be "word" capitalised and "WORD" either
+  check(word) {
+    if (this.lookup(word)) return true;
+    const lower = word.toLowerCase();
+    if (word === lower) return false;
+    const capitalized = lower.charAt(0).toUpperCase() + lower.slice(1);
+    if (word === capitalized) return this.lookup(lower);
+    if (word === word.toUpperCase()) return this.lookup(lower) || this.lookup(capitalized);
+    return false;
+  }
+
+  suggestable(word) {
+    if (!this.check(word)) return false;
+    const flags = this.words.get(word) || this.words.get(word.toLowerCase());
+    return !(this.noSuggest && flags && flags.has(this.noSuggest));
+  }
+
+  // Dictionary words one edit away (REP table replacements first), cased like `word`
+  suggest(word, limit = MAX_SUGGESTIONS) {
+    const found = [];
+    const seen = new Set([word]);
+    const consider = (candidate) => {
+      if (found.length >= limit || seen.has(candidate)) return;
+      seen.add(candidate);
+      if (candidate.includes(' ') ? candidate.split(' ').every((part) => part && this.suggestable(part)) : this.suggestable(candidate)) {
+        found.push(candidate);
+      }
+    };
+
+    for (const [from, to] of this.replacements) {
+      for (let index = word.indexOf(from); index !== -1; index = word.indexOf(from, index + 1)) {
+        consider(word.slice(0, index) + to + word.slice(index + from.length));
+      }
+    }
+    const alphabet = Array.from(new Set(Array.from(this.tryChars || 'esianrtolcdugmphbyfvkwzxjq')));
+    const lower = word.toLowerCase();
+    // Swapped neighbours, a missing letter, a wrong letter, an extra letter
+    for (let i = 0; i < lower.length - 1; i++) consider(lower.slice(0, i) + lower[i + 1] + lower[i] + lower.slice(i + 2));
+    for (const letter of alphabet) {
+      for (let i = 0; i <= lower.length; i++) consider(lower.slice(0, i) + letter + lower.slice(i));
+    }
+    for (const letter of alphabet) {
+      for (let i = 0; i < lower.length; i++) if (lower[i] !== letter) consider(lower.slice(0, i) + letter + lower.slice(i + 1));
+    }
+    for (let i = 0; i < lower.length; i++) consider(lower.slice(0, i) + lower.slice(i + 1));
+    // Two words run together
+    for (let i = 1; i < lower.length; i++) consider(`${lower.slice(0, i)} ${lower.slice(i)}`);
+
+    const upper = word.length > 1 && word === word.toUpperCase();
+    const capitalized = !upper && word.charAt(0) !== word.charAt(0).toLowerCase();
+    return found.map((suggestion) => {
+      if (upper) return suggestion.toUpperCase();
+      if (capitalized) return suggestion.charAt(0).toUpperCase() + suggestion.slice(1);
+      return suggestion;
+    });
+  }
+}
+
+module.exports = { HunspellDictionary };
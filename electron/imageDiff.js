@@ -0,0 +1,105 @@
+const { encodePng } = require('./terminalImages');
+
+// Larger images are compared byte for byte only
+const MAX_IMAGE_PIXELS = 25 * 1000 * 1000;
+// Largest possible YIQ distance between two colours
+const MAX_YIQ_DELTA = 35215;
+const DEFAULT_THRESHOLD = 0.1;
+
+const IMAGE_SIGNATURES = [
+  { format: 'png', test: (b) => b.length > 8 && b.readUInt32BE(0) === 0x89504e47 },
+  { format: 'jpeg', test: (b) => b.length > 3 && b[0] === 0xff && b[1] === 0xd8 && b[2] === 0xff },
+  { format: 'gif', test: (b) => b.toString('ascii', 0, 4) === 'GIF8' },
+  { format: 'bmp', test: (b) => b.toString('ascii', 0, 2) === 'BM' },
+  { format: 'webp', test: (b) => b.toString('ascii', 0, 4) === 'RIFF' && b.toString('ascii', 8, 12) === 'WEBP' }
+];
+
+function imageFormat(buffer) {
+  const found = IMAGE_SIGNATURES.find(({ test }) => test(buffer));
+  return found ? found.format : null;
+}
+
+// { format, width, height, rgba } for an image Chromium can decode, else null
+function decodeImage(buffer) {
+  const format = imageFormat(buffer);
+  if (!format) return null;
+  let nativeImage;
+  try {
+    ({ nativeImage } = require('electron'));
+  } catch {
+    return null;
+  }
+  const image = nativeImage && nativeImage.createFromBuffer(buffer);
+  if (!image || image.isEmpty()) return null;
+  const { width, height } = image.getSize();
+  if (width * height > MAX_IMAGE_PIXELS) return null;
+  // Bitmaps come back BGRA
+  const rgba = Buffer.from(image.toBitmap());
+  for (let i = 0; i < rgba.length; i += 4) {
+    const blue = rgba[i];
+    rgba[i] = rgba[i + 2];
+    rgba[i + 2] = blue;
+  }
+  return { format, width, height, rgba };
+}
+
+// Colour at `offset` blended onto white, as YIQ
+function yiq(rgba, offset) {
+  const alpha = rgba[offset + 3] / 255;
+  const r = 255 + (rgba[offset] - 255) * alpha;
+  const g = 255 + (rgba[offset + 1] - 255) * alpha;
+  const b = 255 + (rgba[offset + 2] - 255) * alpha;
+  return [
+    r * 0.29889531 + g * 0.58662247 + b * 0.11448223,
+    r * 0.59597799 - g * 0.2741761 - b * 0.32180189,
+    r * 0.21147017 - g * 0.52261711 + b * 0.31114694
+  ];
+}
+
+// Perceptual pixel comparison of two decoded images. Colours closer than `threshold` (0-1,
+// in YIQ distance) count as equal, so re-encoding noise isn't reported. The diff image
+// shows the left image faded, changed pixels in red and area only one image covers in
+// magenta.
+function compareImages(left, right, { threshold = DEFAULT_THRESHOLD } = {}) {
+  const width = Math.max(left.width, right.width);
+  const height = Math.max(left.height, right.height);
+  const limit = MAX_YIQ_DELTA * threshold * threshold;
+  const diff = Buffer.alloc(width * height * 4);
+  let differentPixels = 0;
+  for (let y = 0; y < height; y++) {
+    for (let x = 0; x < width; x++) {
+      const out = (y * width + x) * 4;
+      const inLeft = x < left.width && y < left.height;
+      const inRight = x < right.width && y < right.height;
+      let color;
+      if (!inLeft || !inRight) {
+        differentPixels++;
+        color = [255, 0, 255];
+      } else {
+        const [y1, i1, q1] = yiq(left.rgba, (y * left.width + x) * 4);
+        const [y2, i2, q2] = yiq(right.rgba, (y * right.width + x) * 4);
+        const delta = 0.5053 * (y1 - y2) ** 2 + 0.299 * (i1 - i2) ** 2 + 0.1957 * (q1 - q2) ** 2;
+        if (delta > limit) {
+          differentPixels++;
+          color = [255, 0, 0];
+        } else {
+          const faded = Math.round(255 - (255 - y1) * 0.1);
+          color = [faded, faded, faded];
+        }
+      }
+      diff[out] = color[0];
+      diff[out + 1] = color[1];
+      diff[out + 2] = color[2];
+      diff[out + 3] = 255;
+    }
+  }
+  return {
+    width,
+    height,
+    differentPixels,
+    ratio: width * height === 0 ? 0 : differentPixels / (width * height),
+    diff: encodePng(width, height, diff)
+  };
+}
+
+module.exports = { imageFormat, decodeImage, compareImages };
@@ -0,0 +1,86 @@
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { app } = require('electron');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+
+const log = createLogger('index-cache');
+
+// Bump when the on-disk layout changes; older caches are ignored
+const CACHE_VERSION = 1;
+
+function cacheDir() {
+  return path.join(app.getPath('userData'), 'index-cache');
+}
+
+function cacheFile(root, name) {
+  const id = crypto.createHash('sha1').update(root).digest('hex').slice(0, 16);
+  return path.join(cacheDir(), `${id}-${name}.json`);
+}
+
+// Hash of everything that determines an index's contents besides the files themselves
+// (scope patterns, extractor versions); a mismatch discards the cache
+function configHash(...parts) {
+  return crypto.createHash('sha1').update(JSON.stringify(parts)).digest('hex').slice(0, 16);
+}
+
+// The cached `data` for a workspace index, or null if missing, corrupt, or built
+// under a different configuration
+async function loadCache(root, name, hash) {
+  try {
+    const cached = JSON.parse(await fs.readFile(cacheFile(root, name), 'utf-8'));
+    if (cached.version !== CACHE_VERSION || cached.root !== root || cached.configHash !== hash) return null;
+    return { data: cached.data, savedAt: cached.savedAt };
+  } catch {
+    return null;
+  }
+}
+
+async function saveCache(root, name, hash, data) {
+  const file = cacheFile(root, name);
+  const savedAt = new Date().toISOString();
+  try {
+    await fs.mkdir(cacheDir(), { recursive: true });
+    await fs.writeFile(`${file}.tmp`, JSON.stringify({ version: CACHE_VERSION, root, configHash: hash, savedAt, data }));
+    await fs.rename(`${file}.tmp`, file);
+  } catch (error) {
+    log.warn(`Could not save ${name} index for ${root}: ${error.message}`);
+    return null;
+  }
+  return savedAt;
+}
+
+async function deleteCache(root, name) {
+  await fs.rm(cacheFile(root, name), { force: true });
+}
+
+// Collapse bursts of changes into one write `delay` ms after the last
+function debounceSave(save, delay = 10000) {
+  let timer = null;
+  const debounced = () => {
+    clearTimeout(timer);
+    timer = setTimeout(() => {
+      timer = null;
+      save();
+    }, delay);
+  };
+  debounced.flush = () => {
+    if (!timer) return Promise.resolve();
+    clearTimeout(timer);
+    timer = null;
+    return save();
+  };
+  debounced.cancel = () => {
+    clearTimeout(timer);
+    timer = null;
+  };
+  return debounced;
+}
+
+// status: { rootPath, index: 'files' | 'symbols', state, stale, fromCache, processed, total, count, savedAt }
+function emitIndexStatus(status) {
+  emit('index:status', status);
+}
+
+module.exports = { configHash, loadCache, saveCache, deleteCache, debounceSave, emitIndexStatus };
@@ -0,0 +1,144 @@
+const path = require('path');
+const { EventEmitter } = require('events');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { globToRegExp } = require('./editorconfig');
+
+const log = createLogger('index-scope');
+
+const MAX_PATTERNS = 200;
+
+// Per-workspace include/exclude globs, matched against '/'-separated paths relative to
+// the workspace root. Every indexer (file finder, search, symbols, TODOs, embeddings)
+// and the watchers behind them consult this, so excluding a vendored tree skips it everywhere.
+// Patterns without a slash match at any depth ("dist" excludes every dist directory).
+const store = new JsonStore('index-scope', { workspaces: {} });
+
+// Emits 'changed' (rootPath, scope) after a workspace's scope is updated
+const scopeEvents = new EventEmitter();
+scopeEvents.setMaxListeners(0);
+
+// Workspace root -> compiled matchers, rebuilt when the scope changes
+const compiled = new Map();
+
+function toPosix(relativePath) {
+  return relativePath.split(path.sep).join('/');
+}
+
+// "vendor" -> any path segment named vendor; "/vendor" or "libs/vendor" -> anchored at the root
+function anchorPattern(pattern) {
+  const trimmed = pattern.replace(/\/+$/, '');
+  if (trimmed.startsWith('/')) return trimmed.slice(1);
+  return trimmed.includes('/') ? trimmed : `**/${trimmed}`;
+}
+
+// The literal directory prefix of a glob ("packages/app/**/*.ts" -> "packages/app"),
+// used to skip directories that can't contain an included file
+function staticPrefix(pattern) {
+  const segments = [];
+  for (const segment of pattern.split('/')) {
+    if (/[*?[{]/.test(segment)) break;
+    segments.push(segment);
+  }
+  return segments.join('/');
+}
+
+function compile(scope) {
+  const excludes = scope.exclude.map((pattern) => {
+    const anchored = anchorPattern(pattern);
+    return { pattern, regex: globToRegExp(anchored) };
+  });
+  const includes = scope.include.map((pattern) => {
+    const anchored = anchorPattern(pattern);
+    return { pattern, regex: globToRegExp(anchored), prefix: anchored.startsWith('**/') ? '' : staticPrefix(anchored) };
+  });
+  return { excludes, includes };
+}
+
+function getScope(rootPath) {
+  const root = path.resolve(rootPath);
+  const scope = store.get('workspaces')[root];
+  return { rootPath: root, include: scope ? scope.include : [], exclude: scope ? scope.exclude : [] };
+}
+
+function getCompiled(root) {
+  if (!compiled.has(root)) compiled.set(root, compile(getScope(root)));
+  return compiled.get(root);
+}
+
+function validatePatterns(patterns, field) {
+  if (patterns === undefined) return undefined;
+  if (!Array.isArray(patterns) || patterns.some((pattern) => typeof pattern !== 'string' || !pattern.trim())) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${field} must be a list of glob patterns`);
+  }
+  if (patterns.length > MAX_PATTERNS) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `At most ${MAX_PATTERNS} ${field} patterns are supported`);
+  }
+  return Array.from(new Set(patterns.map((pattern) => pattern.trim())));
+}
+
+// Replace a workspace's include and/or exclude lists. Indexers rebuild in response.
+function updateIndexScope(rootPath, changes = {}) {
+  const root = path.resolve(rootPath);
+  const current = getScope(root);
+  const include = validatePatterns(changes.include, 'include') || current.include;
+  const exclude = validatePatterns(changes.exclude, 'exclude') || current.exclude;
+
+  const workspaces = store.get('workspaces');
+  if (include.length === 0 && exclude.length === 0) {
+    delete workspaces[root];
+  } else {
+    workspaces[root] = { include, exclude };
+  }
+  store.set('workspaces', workspaces);
+  compiled.delete(root);
+
+  const scope = getScope(root);
+  log.info(`Index scope for ${root}: ${include.length} include, ${exclude.length} exclude pattern(s)`);
+  scopeEvents.emit('changed', root, scope);
+  return scope;
+}
+
+// Whether `targetPath` is outside the workspace's index scope. `type` is 'file',
+// 'directory', or undefined when unknown (watchers sometimes can't tell), in which case
+// only exclusions apply and include patterns are checked later against the file itself.
+function isPathIgnored(rootPath, targetPath, type) {
+  const root = path.resolve(rootPath);
+  const relative = toPosix(path.relative(root, targetPath));
+  if (!relative || relative.startsWith('..')) return false;
+
+  const { excludes, includes } = getCompiled(root);
+  if (excludes.some(({ regex }) => regex.test(relative))) return true;
+  if (includes.length === 0 || type === undefined) return false;
+
+  if (type === 'directory') {
+    // Keep directories that lead to, or sit inside, an include pattern's fixed prefix
+    return !includes.some(({ prefix }) => !prefix || prefix === relative ||
+      prefix.startsWith(`${relative}/`) || relative.startsWith(`${prefix}/`));
+  }
+  return !includes.some(({ regex }) => regex.test(relative));
+}
+
+// An `ignored` callback for createWatcher/chokidar: (path, stats or Dirent) => boolean
+function watcherIgnore(rootPath, baseIgnored = () => false) {
+  const root = path.resolve(rootPath);
+  return (watchedPath, stats) => {
+    if (baseIgnored(watchedPath)) return true;
+    const type = stats ? (stats.isDirectory() ? 'directory' : 'file') : undefined;
+    return isPathIgnored(root, watchedPath, type);
+  };
+}
+
+function setupIndexScopeHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('indexScope:get', async (event, rootPath) => getScope(rootPath));
+
+  ipcMain.handle('indexScope:update', async (event, rootPath, changes) => updateIndexScope(rootPath, changes));
+
+  // Preview a pattern change: would this path be indexed?
+  ipcMain.handle('indexScope:isIgnored', async (event, rootPath, targetPath, type) => isPathIgnored(rootPath, targetPath, type || 'file'));
+}
+
+module.exports = { setupIndexScopeHandlers, scopeEvents, getScope, updateIndexScope, isPathIgnored, watcherIgnore, anchorPattern };
@@ -0,0 +1,128 @@
+const net = require('net');
+const fs = require('fs');
+const path = require('path');
+const { app } = require('electron');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode } = require('./errors');
+const { PROTOCOL_VERSION, getSocketPath, encodeMessage, createMessageReader } = require('./cliProtocol');
+const { openPaths, openDiff, waitForFile } = require('./openRouter');
+
+const log = createLogger('instance-server');
+
+let server = null;
+
+// Methods the `forge` CLI can call on a running instance
+const METHODS = {
+  ping: async () => ({ version: app.getVersion(), protocol: PROTOCOL_VERSION, pid: process.pid }),
+  open: async ({ targets, newWindow, wait }) => openPaths(targets, { newWindow, wait, source: 'cli' }),
+  diff: async ({ left, right, wait }) => openDiff(left, right, { wait, source: 'cli' }),
+  wait: async ({ path: filePath }) => waitForFile(filePath)
+};
+
+function handleConnection(socket) {
+  const reply = (message) => {
+    if (!socket.destroyed) socket.write(encodeMessage(message));
+  };
+
+  socket.on('data', createMessageReader(async (request) => {
+    const method = METHODS[request.method];
+    if (!method) {
+      reply({ id: request.id, error: { code: ErrorCode.INVALID_ARGUMENT, message: `Unknown method: ${request.method}` } });
+      return;
+    }
+    try {
+      reply({ id: request.id, result: await method(request.params || {}) });
+    } catch (error) {
+      const forgeError = ForgeError.from(error);
+      reply({ id: request.id, error: { code: forgeError.code, message: forgeError.message } });
+    }
+  }));
+  socket.on('error', (error) => log.debug(`CLI connection error: ${error.message}`));
+}
+
+// A socket left behind by a crashed instance refuses connections; remove it so we can listen
+function clearStaleSocket(socketPath) {
+  return new Promise((resolve) => {
+    const probe = net.connect(socketPath);
+    probe.once('connect', () => {
+      probe.destroy();
+      resolve(false);
+    });
+    probe.once('error', () => {
+      try {
+        fs.unlinkSync(socketPath);
+      } catch {
+        // Nothing to clean up
+      }
+      resolve(true);
+    });
+  });
+}
+
+// The socket's directory must be ours alone. mkdir ignores `mode` for a directory that
+// already exists, and in the shared temp directory another user could have created it
+// first to intercept the socket.
+function ensurePrivateDirectory(dir) {
+  fs.mkdirSync(path.dirname(dir), { recursive: true });
+  try {
+    fs.mkdirSync(dir, { mode: 0o700 });
+  } catch (error) {
+    if (error.code !== 'EEXIST') throw error;
+  }
+  const stats = fs.lstatSync(dir);
+  if (!stats.isDirectory() || stats.uid !== process.getuid() || (stats.mode & 0o777) !== 0o700) {
+    throw new ForgeError(ErrorCode.ACCESS_DENIED, `Refusing to use ${dir} for the instance socket: it must be a directory owned by this user with mode 0700`, {
+      details: { path: dir, uid: stats.uid, mode: (stats.mode & 0o777).toString(8), symlink: stats.isSymbolicLink() }
+    });
+  }
+}
+
+async function startInstanceServer() {
+  const socketPath = getSocketPath();
+  if (process.platform !== 'win32') {
+    ensurePrivateDirectory(path.dirname(socketPath));
+    if (fs.existsSync(socketPath) && !(await clearStaleSocket(socketPath))) {
+      log.warn(`Another Forge instance is already listening on ${socketPath}`);
+      return null;
+    }
+  }
+
+  server = net.createServer(handleConnection);
+  // The socket is created owner-only rather than chmodded after the fact
+  const previousUmask = process.platform !== 'win32' ? process.umask(0o077) : null;
+  try {
+    await new Promise((resolve, reject) => {
+      server.once('error', reject);
+      server.listen(socketPath, () => {
+        server.off('error', reject);
+        resolve();
+      });
+    });
+  } finally {
+    if (previousUmask !== null) process.umask(previousUmask);
+  }
+  if (process.platform !== 'win32') fs.chmodSync(socketPath, 0o600);
+
+  // Terminals spawned by this instance inherit the path
+  process.env.FORGE_IPC_PATH = socketPath;
+  log.info(`Listening for CLI requests on ${socketPath}`);
+
+  app.on('will-quit', stopInstanceServer);
+  return socketPath;
+}
+
+function stopInstanceServer() {
+  if (!server) return;
+  const socketPath = server.address();
+  server.close();
+  server = null;
+  if (process.platform !== 'win32' && typeof socketPath === 'string') {
+    try {
+      fs.unlinkSync(socketPath);
+    } catch {
+      // Already gone
+    }
+  }
+}
+
+module.exports = { startInstanceServer, stopInstanceServer };
@@ -1,6 +1,14 @@
 const { shell, app, BrowserWindow, dialog } = require('electron');
 const path = require('path');
+const crypto = require('crypto');
 const fs = require('fs').promises;
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { writeProtectedFile, readProtectedFile, sealRecord, openRecord, isSealed, registerMigration, migrateDirectory } = require('./encryption');
+
+const log = createLogger('ipc');
 
 // Initialize electron-store with error handling
 let Store;
@@ -13,16 +21,16 @@ try {
   Store = ElectronStore;
   store = new ElectronStore();
   editorSessionsStore = new ElectronStore({ name: 'editor-sessions' });
-  console.log('Electron store initialized successfully (ES6 style)');
+  log.debug('Electron store initialized successfully (ES6 style)');
 } catch (e1) {
   try {
     // Try CommonJS import style
     Store = require('electron-store');
     store = new Store();
     editorSessionsStore = new Store({ name: 'editor-sessions' });
-    console.log('Electron store initialized successfully (CommonJS style)');
+    log.debug('Electron store initialized successfully (CommonJS style)');
   } catch (e2) {
-    console.error('Failed to initialize electron-store:', e2);
+    log.error('Failed to initialize electron-store:', e2);
     // Create simple fallback stores
     store = {
       get: (key) => null,
@@ -39,7 +47,134 @@ try {
   }
 }
 
+// Snapshot of persisted settings (used by the diagnostics bundle)
+function getSettingsSnapshot() {
+  try {
+    return Store ? { ...store.store } : {};
+  } catch {
+    return {};
+  }
+}
+
+// Overwrite (or merge into) persisted settings, e.g. from an imported workspace state bundle
+function applySettings(values, replace = false) {
+  if (!Store) return false;
+  store.store = replace ? { ...values } : { ...store.store, ...values };
+  return true;
+}
+
+function getSessionsDir() {
+  return path.join(app.getPath('userData'), 'editor-sessions');
+}
+
+// Sessions may be encrypted at rest; id, workspace, and save time stay readable for lookup
+async function storeEditorSession(session) {
+  const record = await sealRecord('editorSessions', session, { id: session.id, workspaceId: session.workspaceId, savedAt: session.savedAt });
+  editorSessionsStore.set(session.id, record);
+  // Also save to file for backup
+  await writeProtectedFile('editorSessions', path.join(getSessionsDir(), `${session.id}.json`), JSON.stringify(session, null, 2));
+}
+
+// Saved editor sessions, newest first; limited to `workspaceId` (plus unscoped ones) when given
+async function listEditorSessions(workspaceId) {
+  const sessions = Store ? editorSessionsStore.store : {};
+  const records = Object.values(sessions)
+    .filter((session) => !workspaceId || !session.workspaceId || session.workspaceId === workspaceId)
+    .sort((a, b) => new Date(b.savedAt).getTime() - new Date(a.savedAt).getTime());
+  return Promise.all(records.map(openRecord));
+}
+
+async function importEditorSessions(sessions) {
+  for (const session of sessions) {
+    await storeEditorSession(session);
+  }
+  return sessions.length;
+}
+
+registerMigration('editorSessions', async (reseal) => {
+  let migrated = 0;
+  let failed = 0;
+  for (const [id, record] of Object.entries(Store ? editorSessionsStore.store : {})) {
+    try {
+      const current = typeof record.sealed === 'string' ? Buffer.from(record.sealed, 'base64') : Buffer.from(JSON.stringify(record), 'utf-8');
+      const next = await reseal(current);
+      if (next.equals(current)) continue;
+      const { workspaceId, savedAt } = record;
+      editorSessionsStore.set(id, isSealed(next) ? { id, workspaceId, savedAt, sealed: next.toString('base64') } : JSON.parse(next.toString('utf-8')));
+      migrated++;
+    } catch (error) {
+      log.warn(`Could not migrate editor session ${id}: ${error.message}`);
+      failed++;
+    }
+  }
+  return { migrated, failed };
+});
+registerMigration('editorSessions', migrateDirectory(getSessionsDir(), (name) => name.endsWith('.json')));
+
+// Folded regions of each file, keyed by path. Entries record a hash of the content they
+// were saved against; once the file changes the folds no longer line up and are dropped.
+const foldStateStore = new JsonStore('fold-state', { files: {} });
+const MAX_FOLD_STATE_FILES = 2000;
+const MAX_FOLDS_PER_FILE = 5000;
+
+const contentHash = (content) => crypto.createHash('sha1').update(content).digest('hex');
+
+async function currentContent(filePath, content) {
+  if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An absolute file path is required');
+  }
+  if (typeof content === 'string') return content;
+  return fs.readFile(filePath, 'utf-8').catch((error) => {
+    throw ForgeError.from(error, `Cannot read ${filePath}`);
+  });
+}
+
+// Folds saved for `filePath`, or [] when none were saved or the file has changed since.
+// `content` is the editor's text; the file on disk is hashed when it's omitted.
+async function getFoldState(filePath, content) {
+  const text = await currentContent(filePath, content);
+  const files = foldStateStore.get('files');
+  const entry = files[filePath];
+  if (!entry) return [];
+  if (entry.hash !== contentHash(text)) {
+    delete files[filePath];
+    foldStateStore.set('files', files);
+    return [];
+  }
+  return entry.folds;
+}
+
+// Remember `folds` ([{ startLine, endLine }], 1-based) for the current content; an empty
+// list forgets the file
+async function saveFoldState(filePath, folds, content) {
+  if (!Array.isArray(folds) || folds.length > MAX_FOLDS_PER_FILE) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Folds must be a list of at most ${MAX_FOLDS_PER_FILE} regions`);
+  }
+  const valid = folds.every((fold) => fold && Number.isInteger(fold.startLine) && Number.isInteger(fold.endLine) &&
+    fold.startLine >= 1 && fold.endLine > fold.startLine);
+  if (!valid) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Each fold needs an integer startLine and a later endLine');
+  }
+  const text = await currentContent(filePath, content);
+  const files = foldStateStore.get('files');
+  delete files[filePath];
+  if (folds.length > 0) {
+    files[filePath] = {
+      hash: contentHash(text),
+      folds: folds.map(({ startLine, endLine }) => ({ startLine, endLine })),
+      savedAt: new Date().toISOString()
+    };
+    // Forget the least recently saved files; insertion order is save order
+    const paths = Object.keys(files);
+    for (const stale of paths.slice(0, Math.max(0, paths.length - MAX_FOLD_STATE_FILES))) delete files[stale];
+  }
+  foldStateStore.set('files', files);
+  return folds.length;
+}
+
 function setupIPCHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
   // Store API
   ipcMain.handle('store:get', async (event, key) => {
     return Store ? store.get(key) : store.get(key);
@@ -75,7 +210,7 @@ function setupIPCHandlers(ipcMain) {
     // Validate path name
     const validPaths = ['home', 'appData', 'userData', 'temp', 'desktop', 'documents', 'downloads', 'pictures', 'videos'];
     if (!validPaths.includes(name)) {
-      throw new Error(`Invalid path name: ${name}`);
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid path name: ${name}`);
     }
     return app.getPath(name);
   });
@@ -86,7 +221,7 @@ function setupIPCHandlers(ipcMain) {
       const win = BrowserWindow.fromWebContents(event.sender);
       if (win && !win.isDestroyed()) win.minimize();
     } catch (e) {
-      console.log('Window operation failed (expected during close)');
+      log.debug('Window operation failed (expected during close)');
     }
     return true;
   });
@@ -96,7 +231,7 @@ function setupIPCHandlers(ipcMain) {
       const win = BrowserWindow.fromWebContents(event.sender);
       if (win && !win.isDestroyed()) win.maximize();
     } catch (e) {
-      console.log('Window operation failed (expected during close)');
+      log.debug('Window operation failed (expected during close)');
     }
     return true;
   });
@@ -106,7 +241,7 @@ function setupIPCHandlers(ipcMain) {
       const win = BrowserWindow.fromWebContents(event.sender);
       if (win && !win.isDestroyed()) win.unmaximize();
     } catch (e) {
-      console.log('Window operation failed (expected during close)');
+      log.debug('Window operation failed (expected during close)');
     }
     return true;
   });
@@ -116,7 +251,7 @@ function setupIPCHandlers(ipcMain) {
       const win = BrowserWindow.fromWebContents(event.sender);
       if (win && !win.isDestroyed()) win.close();
     } catch (e) {
-      console.log('Window operation failed (expected during close)');
+      log.debug('Window operation failed (expected during close)');
     }
     return true;
   });
@@ -135,7 +270,7 @@ function setupIPCHandlers(ipcMain) {
       const win = BrowserWindow.fromWebContents(event.sender);
       if (win && !win.isDestroyed()) win.setAlwaysOnTop(flag);
     } catch (e) {
-      console.log('Window operation failed (expected during close)');
+      log.debug('Window operation failed (expected during close)');
     }
     return true;
   });
@@ -143,47 +278,45 @@ function setupIPCHandlers(ipcMain) {
   // Editor Session API
   ipcMain.handle('editor:saveSession', async (event, sessionData) => {
     const sessionId = sessionData.id || `session_${Date.now()}`;
+    // Sessions belong to the workspace of the window that saved them
+    const windowContext = windowManager.fromSender(event.sender);
     const sessionWithTimestamp = {
       ...sessionData,
       id: sessionId,
+      workspaceId: sessionData.workspaceId || (windowContext ? windowContext.workspaceId : undefined),
       savedAt: new Date().toISOString()
     };
-    
-    Store ? editorSessionsStore.set(sessionId, sessionWithTimestamp) : editorSessionsStore.set(sessionId, sessionWithTimestamp);
-    
-    // Also save to file for backup
-    const sessionsDir = path.join(app.getPath('userData'), 'editor-sessions');
-    await fs.mkdir(sessionsDir, { recursive: true });
-    await fs.writeFile(
-      path.join(sessionsDir, `${sessionId}.json`),
-      JSON.stringify(sessionWithTimestamp, null, 2)
-    );
-    
+
+    await storeEditorSession(sessionWithTimestamp);
+
     return sessionId;
   });
 
   ipcMain.handle('editor:loadSession', async (event, sessionId) => {
-    const session = Store ? editorSessionsStore.get(sessionId) : editorSessionsStore.get(sessionId);
+    const session = await openRecord(editorSessionsStore.get(sessionId));
     if (!session) {
       // Try to load from file
-      const sessionFile = path.join(app.getPath('userData'), 'editor-sessions', `${sessionId}.json`);
+      const sessionFile = path.join(getSessionsDir(), `${sessionId}.json`);
       try {
-        const content = await fs.readFile(sessionFile, 'utf-8');
-        return JSON.parse(content);
+        return JSON.parse(await readProtectedFile(sessionFile));
       } catch {
-        throw new Error(`Session ${sessionId} not found`);
+        throw new ForgeError(ErrorCode.SESSION_NOT_FOUND, `Session ${sessionId} not found`, { details: { sessionId } });
       }
     }
     return session;
   });
 
-  ipcMain.handle('editor:listSessions', async (event) => {
-    const sessions = Store ? editorSessionsStore.store : Object.fromEntries(editorSessionsStore);
-    return Object.values(sessions).sort((a, b) => {
-      return new Date(b.savedAt).getTime() - new Date(a.savedAt).getTime();
-    });
+  ipcMain.handle('editor:listSessions', async (event, allWorkspaces = false) => {
+    // By default only the calling window's workspace (plus legacy unscoped sessions)
+    const windowContext = windowManager.fromSender(event.sender);
+    const workspaceId = windowContext ? windowContext.workspaceId : null;
+    return listEditorSessions(allWorkspaces ? null : workspaceId);
   });
 
+  ipcMain.handle('editor:getFoldState', async (event, filePath, content) => getFoldState(filePath, content));
+
+  ipcMain.handle('editor:saveFoldState', async (event, filePath, folds, content) => saveFoldState(filePath, folds, content));
+
   // Dialog API (bonus!)
   ipcMain.handle('dialog:showOpenDialog', async (event, options) => {
     const win = BrowserWindow.fromWebContents(event.sender);
@@ -204,4 +337,4 @@ function setupIPCHandlers(ipcMain) {
   });
 }
 
-module.exports = { setupIPCHandlers };
\ No newline at end of file
+module.exports = { setupIPCHandlers, getSettingsSnapshot, applySettings, listEditorSessions, importEditorSessions };
\ No newline at end of file
@@ -0,0 +1,182 @@
+const fs = require('fs').promises;
+const os = require('os');
+const path = require('path');
+const { app } = require('electron');
+const { createLogger } = require('./logger');
+const { runGit } = require('./git');
+const { getTerminal, getScreenOutput } = require('./terminal');
+const { detectLanguage } = require('./languages');
+const { getForgeRemote, forgeRequest, projectApiPath } = require('./forgeRemote');
+const { registerCommand } = require('./commands');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('issues');
+
+const DEFAULT_TERMINAL_LINES = 40;
+const MAX_TERMINAL_LINES = 500;
+const MAX_SELECTION_LINES = 200;
+// Only the end of the scrollback is scanned for the last lines
+const TERMINAL_TAIL = 256 * 1024;
+const ANSI_PATTERN = /\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b\[[0-9;?<>=!]*[ -/]*[@-~]|\x1b[@-Z\\-_]/g;
+// Credentials that commonly end up in terminal output; issues are often public
+const SECRET_PATTERNS = [
+  [/\b(Bearer|Basic)\s+[A-Za-z0-9._~+/=-]{8,}/g, '$1 [REDACTED]'],
+  [/\b(gh[pousr]_[A-Za-z0-9]{20,}|github_pat_[A-Za-z0-9_]{20,}|glpat-[A-Za-z0-9_-]{20,})\b/g, '[REDACTED]'],
+  [/\b(sk-[A-Za-z0-9_-]{20,}|xox[abpr]-[A-Za-z0-9-]{10,}|AKIA[0-9A-Z]{16})\b/g, '[REDACTED]'],
+  [/\beyJ[A-Za-z0-9_-]{8,}\.eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}/g, '[REDACTED]'],
+  [/(\b(?:password|passwd|token|secret|api[_-]?key)\b\s*[:=]\s*"?)[^\s"'[]{6,}/gi, '$1[REDACTED]']
+];
+
+function redact(text) {
+  return SECRET_PATTERNS.reduce((result, [pattern, replacement]) => result.replace(pattern, replacement), text);
+}
+
+// Longest run of backticks in `text` plus one, so fenced content can't close its fence
+function fenceFor(text) {
+  const runs = text.match(/`+/g) || [];
+  return '`'.repeat(Math.max(3, ...runs.map((run) => run.length + 1)));
+}
+
+function codeBlock(text, language = '') {
+  const fence = fenceFor(text);
+  return `${fence}${language}\n${text.replace(/\n+$/, '')}\n${fence}`;
+}
+
+// Last `count` lines of a terminal as plain text: colors and cursor sequences dropped, and
+// carriage-return progress bars reduced to what was left on screen
+function terminalTail(terminalId, count) {
+  const session = getTerminal(terminalId);
+  if (!session) throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { id: terminalId } });
+  const lines = getScreenOutput(session, 'normal')
+    .slice(-TERMINAL_TAIL)
+    .replace(ANSI_PATTERN, '')
+    .split(/\r?\n/)
+    .map((line) => line.split('\r').filter(Boolean).pop() || '')
+    .map((line) => line.replace(/[\x00-\x08\x0b-\x1f\x7f]/g, '').trimEnd());
+  while (lines.length && !lines[lines.length - 1]) lines.pop();
+  return redact(lines.slice(-count).join('\n'));
+}
+
+// Link to the lines at the current commit, when that commit is on the remote; links to a
+// local-only commit would 404
+async function permalink(repoRoot, remote, relative, line, endLine) {
+  const sha = (await runGit(repoRoot, ['rev-parse', 'HEAD'])).trim();
+  const remotes = await runGit(repoRoot, ['branch', '-r', '--contains', sha]).catch(() => '');
+  if (!remotes.split('\n').some((name) => name.trim().startsWith(`${remote.remote}/`))) return null;
+  const encoded = relative.split('/').map(encodeURIComponent).join('/');
+  const blob = `${remote.webUrl}${remote.kind === 'gitlab' ? '/-' : ''}/blob/${sha}/${encoded}`;
+  if (!line) return blob;
+  if (!endLine || endLine === line) return `${blob}#L${line}`;
+  return `${blob}#L${line}${remote.kind === 'gitlab' ? '-' : '-L'}${endLine}`;
+}
+
+async function environmentLines(repoRoot) {
+  const [branch, sha, dirty] = await Promise.all([
+    runGit(repoRoot, ['rev-parse', '--abbrev-ref', 'HEAD']).then((output) => output.trim(), () => null),
+    runGit(repoRoot, ['rev-parse', '--short', 'HEAD']).then((output) => output.trim(), () => null),
+    runGit(repoRoot, ['status', '--porcelain', '--untracked-files=no']).then((output) => output.trim().length > 0, () => false)
+  ]);
+  const lines = [`- Forge ${app.getVersion()} on ${os.type()} ${os.release()} (${process.platform}, ${os.arch()})`];
+  if (sha) lines.push(`- Commit \`${sha}\`${branch && branch !== 'HEAD' ? ` on \`${branch}\`` : ''}${dirty ? ' with uncommitted changes' : ''}`);
+  return lines;
+}
+
+async function captureScreenshot(browserViews, viewId) {
+  const view = browserViews.get(viewId);
+  if (!view) throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${viewId} not found`, { details: { id: viewId } });
+  const image = await view.webContents.capturePage();
+  return { png: image.toPNG(), url: view.webContents.getURL() };
+}
+
+// GitLab takes uploads and hands back markdown to embed. GitHub's API can't attach files to
+// issues, so there the screenshot is saved locally to be dropped into the issue by hand.
+async function attachScreenshot(remote, screenshot, dryRun) {
+  const name = `screenshot-${new Date().toISOString().replace(/[:.]/g, '-')}.png`;
+  if (remote.kind === 'gitlab' && !dryRun) {
+    const form = new FormData();
+    form.append('file', new Blob([screenshot.png], { type: 'image/png' }), name);
+    const upload = await forgeRequest(remote, 'POST', `${projectApiPath(remote)}/uploads`, { form });
+    return { markdown: upload.markdown, savedPath: null };
+  }
+  const dir = path.join(app.getPath('userData'), 'issue-captures');
+  await fs.mkdir(dir, { recursive: true });
+  const savedPath = path.join(dir, name);
+  await fs.writeFile(savedPath, screenshot.png);
+  return { markdown: null, savedPath };
+}
+
+// Builds an issue from the description plus whatever context is given: the editor location
+// and selection, the end of a terminal, and a screenshot of a preview. Creates it on the
+// repository's GitHub / GitLab project unless `dryRun`, which returns the body to review.
+async function captureIssue(options = {}, browserViews) {
+  const { rootPath, title, description = '', editor, terminalId, browserViewId, labels = [], remote: remoteName = 'origin', dryRun = false } = options;
+  if (typeof title !== 'string' || !title.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An issue title is required');
+  if (typeof rootPath !== 'string' || !rootPath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A workspace folder is required');
+  if (!Array.isArray(labels) || labels.some((label) => typeof label !== 'string')) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Labels must be a list of strings');
+  let repoRoot;
+  try {
+    repoRoot = (await runGit(path.resolve(rootPath), ['rev-parse', '--show-toplevel'])).trim();
+  } catch {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `${rootPath} is not in a git repository`);
+  }
+  const remote = await getForgeRemote(repoRoot, remoteName);
+  const sections = [];
+  if (description.trim()) sections.push(description.trim());
+
+  if (editor && typeof editor.path === 'string') {
+    const filePath = path.resolve(repoRoot, editor.path);
+    const relative = path.relative(repoRoot, filePath).split(path.sep).join('/');
+    const inRepo = !relative.startsWith('..') && !path.isAbsolute(relative);
+    const line = Number.isInteger(editor.line) && editor.line > 0 ? editor.line : null;
+    const endLine = Number.isInteger(editor.endLine) && editor.endLine >= (line || 0) ? editor.endLine : null;
+    const label = `${inRepo ? relative : path.basename(filePath)}${line ? `:${line}${endLine && endLine !== line ? `-${endLine}` : ''}` : ''}`;
+    const link = inRepo ? await permalink(repoRoot, remote, relative, line, endLine).catch(() => null) : null;
+    const location = [`**Location:** ${link ? `[\`${label}\`](${link})` : `\`${label}\``}`];
+    if (typeof editor.selection === 'string' && editor.selection.trim()) {
+      const selected = editor.selection.split('\n');
+      const shown = selected.slice(0, MAX_SELECTION_LINES).join('\n');
+      location.push(codeBlock(redact(shown), detectLanguage(filePath, shown.slice(0, 256)) || ''));
+      if (selected.length > MAX_SELECTION_LINES) location.push(`_${selected.length - MAX_SELECTION_LINES} more lines not shown_`);
+    }
+    sections.push(location.join('\n\n'));
+  }
+
+  if (terminalId) {
+    const count = Math.min(Math.max(1, Number(options.terminalLines) || DEFAULT_TERMINAL_LINES), MAX_TERMINAL_LINES);
+    const output = terminalTail(terminalId, count);
+    if (output) sections.push(`**Terminal output** (last ${count} lines)\n\n${codeBlock(output)}`);
+  }
+
+  let screenshotPath = null;
+  if (browserViewId) {
+    const screenshot = await captureScreenshot(browserViews, browserViewId);
+    const attached = await attachScreenshot(remote, screenshot, dryRun);
+    screenshotPath = attached.savedPath;
+    const caption = `**Screenshot** of ${screenshot.url}`;
+    if (attached.markdown) sections.push(`${caption}\n\n${attached.markdown}`);
+    else if (!dryRun) sections.push(`${caption} _(attached separately)_`);
+  }
+
+  sections.push(`<details><summary>Environment</summary>\n\n${(await environmentLines(repoRoot)).join('\n')}\n\n</details>`);
+  const body = sections.join('\n\n');
+  if (dryRun) return { created: false, provider: remote.kind, project: remote.projectPath, title: title.trim(), body, url: null, number: null, screenshotPath };
+
+  const base = projectApiPath(remote);
+  const issue = remote.kind === 'github'
+    ? await forgeRequest(remote, 'POST', `${base}/issues`, { body: { title: title.trim(), body, labels } })
+    : await forgeRequest(remote, 'POST', `${base}/issues`, { body: { title: title.trim(), description: body, labels: labels.join(',') } });
+  const url = remote.kind === 'github' ? issue.html_url : issue.web_url;
+  log.info(`Created issue ${url}`);
+  return { created: true, provider: remote.kind, project: remote.projectPath, title: title.trim(), body, url, number: remote.kind === 'github' ? issue.number : issue.iid, screenshotPath };
+}
+
+function setupIssueHandlers(ipcMain, browserViews) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('issues:capture', async (event, options) => captureIssue(options || {}, browserViews));
+
+  // The renderer gathers the editor and terminal context, then calls issues:capture
+  registerCommand({ id: 'workspace.captureIssue', title: 'Report Issue from Here', category: 'workspace' });
+}
+
+module.exports = { setupIssueHandlers, captureIssue };
@@ -0,0 +1,138 @@
+const { v4: uuidv4 } = require('uuid');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { guardTask } = require('./crash');
+
+const log = createLogger('jobs');
+
+// Active and recently finished jobs keyed by id
+const jobs = new Map();
+const FINISHED_JOB_TTL = 5 * 60 * 1000;
+
+function serializeJob(job) {
+  return {
+    id: job.id,
+    kind: job.kind,
+    title: job.title,
+    status: job.status,
+    cancellable: job.cancellable,
+    progress: job.progress,
+    message: job.message,
+    startedAt: job.startedAt,
+    finishedAt: job.finishedAt,
+    error: job.error
+  };
+}
+
+// Run `task(job)` as a tracked background job. The task receives a handle with
+// `signal`, `reportProgress`, and `throwIfCancelled`. Returns { id, promise }.
+function runJob(kind, title, task, options = {}) {
+  const controller = new AbortController();
+  const job = {
+    id: `job_${uuidv4()}`,
+    kind,
+    title,
+    status: 'running',
+    cancellable: options.cancellable !== false,
+    progress: null,
+    message: null,
+    startedAt: new Date().toISOString(),
+    finishedAt: null,
+    error: null,
+    controller
+  };
+  jobs.set(job.id, job);
+  emit('job:started', serializeJob(job));
+
+  const handle = {
+    id: job.id,
+    signal: controller.signal,
+    get cancelled() {
+      return controller.signal.aborted;
+    },
+    // progress is a 0..1 fraction, or null when indeterminate
+    reportProgress(progress, message) {
+      job.progress = progress === null || progress === undefined ? null : Math.max(0, Math.min(1, progress));
+      if (message !== undefined) job.message = message;
+      emit('job:progress', { id: job.id, progress: job.progress, message: job.message });
+    },
+    throwIfCancelled() {
+      if (controller.signal.aborted) {
+        throw new ForgeError(ErrorCode.CANCELLED, `${title} was cancelled`);
+      }
+    }
+  };
+
+  const finish = (status, result, error) => {
+    job.status = status;
+    job.finishedAt = new Date().toISOString();
+    job.error = error ? { code: error.code, message: error.message } : null;
+    emit('job:completed', { id: job.id, status, error: job.error, result: options.emitResult ? result : undefined });
+    setTimeout(() => jobs.delete(job.id), FINISHED_JOB_TTL).unref();
+  };
+
+  // Bugs in a job's task get a crash report; expected failures only fail the job
+  const promise = guardTask(`job:${kind}`, () => task(handle), { rethrow: true })
+    .then((result) => {
+      if (controller.signal.aborted) {
+        finish('cancelled');
+        throw new ForgeError(ErrorCode.CANCELLED, `${title} was cancelled`);
+      }
+      finish('succeeded', result);
+      return result;
+    }, (error) => {
+      const forgeError = ForgeError.from(error);
+      const status = controller.signal.aborted || forgeError.code === ErrorCode.CANCELLED ? 'cancelled' : 'failed';
+      if (status === 'failed') {
+        log.warn(`Job ${job.id} (${kind}) failed: ${forgeError.message}`);
+      }
+      finish(status, undefined, forgeError);
+      throw forgeError;
+    });
+
+  return { id: job.id, promise };
+}
+
+function cancelJob(id) {
+  const job = jobs.get(id);
+  if (!job) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `Job ${id} not found`);
+  }
+  if (!job.cancellable) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Job ${id} cannot be cancelled`);
+  }
+  if (job.status === 'running') {
+    log.info(`Cancelling job ${id} (${job.kind})`);
+    job.controller.abort();
+  }
+  return true;
+}
+
+function listJobs(includeFinished = false) {
+  return Array.from(jobs.values())
+    .filter((job) => includeFinished || job.status === 'running')
+    .map(serializeJob);
+}
+
+function setupJobHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('jobs:list', async (event, includeFinished) => {
+    return listJobs(includeFinished);
+  });
+
+  ipcMain.handle('jobs:get', async (event, id) => {
+    const job = jobs.get(id);
+    if (!job) {
+      throw new ForgeError(ErrorCode.NOT_FOUND, `Job ${id} not found`);
+    }
+    return serializeJob(job);
+  });
+
+  ipcMain.handle('jobs:cancel', async (event, id) => {
+    return cancelJob(id);
+  });
+}
+
+module.exports = { setupJobHandlers, runJob, cancelJob, listJobs };
@@ -0,0 +1,68 @@
+const fs = require('fs');
+const path = require('path');
+const { app } = require('electron');
+
+// Live stores by name, so export/import can go through the instance a subsystem holds
+const instances = new Map();
+
+// Small JSON-file store with an electron-store compatible surface (get/set/delete/store).
+// electron-store v10 is ESM-only and frequently falls back to a no-op under require(),
+// so subsystems that must persist state use this instead.
+class JsonStore {
+  constructor(name, defaults = {}) {
+    this.name = name;
+    this.defaults = defaults;
+    this.filePath = path.join(app.getPath('userData'), `${name}.json`);
+    this.data = null;
+    instances.set(name, this);
+  }
+
+  load() {
+    if (this.data) return this.data;
+    try {
+      this.data = { ...this.defaults, ...JSON.parse(fs.readFileSync(this.filePath, 'utf-8')) };
+    } catch {
+      this.data = JSON.parse(JSON.stringify(this.defaults));
+    }
+    return this.data;
+  }
+
+  save() {
+    // Write to a temp file and rename so a crash never leaves a truncated store
+    const tempPath = `${this.filePath}.tmp`;
+    fs.mkdirSync(path.dirname(this.filePath), { recursive: true });
+    fs.writeFileSync(tempPath, JSON.stringify(this.data, null, 2));
+    fs.renameSync(tempPath, this.filePath);
+  }
+
+  get(key, fallback) {
+    const value = this.load()[key];
+    return value === undefined ? fallback : value;
+  }
+
+  set(key, value) {
+    this.load()[key] = value;
+    this.save();
+  }
+
+  delete(key) {
+    delete this.load()[key];
+    this.save();
+  }
+
+  get store() {
+    return this.load();
+  }
+
+  set store(value) {
+    this.data = value;
+    this.save();
+  }
+}
+
+// The store a subsystem created under `name`, or a fresh one over the same file
+function getJsonStore(name) {
+  return instances.get(name) || new JsonStore(name);
+}
+
+module.exports = { JsonStore, getJsonStore };
@@ -0,0 +1,557 @@
+const fs = require('fs').promises;
+const net = require('net');
+const os = require('os');
+const path = require('path');
+const crypto = require('crypto');
+const { spawn } = require('child_process');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+const { getSecret } = require('./secrets');
+const { getRestrictions } = require('./trust');
+const { ZmqSocket } = require('./zmtp');
+const { connectWebSocket } = require('./websocket');
+const { ForgeError, ErrorCode } = require('./errors');
+
+const log = createLogger('jupyter');
+
+const PROTOCOL_VERSION = '5.3';
+const DELIMITER = '<IDS|MSG>';
+const START_TIMEOUT = 60000;
+const REQUEST_TIMEOUT = 30000;
+const SHUTDOWN_GRACE_MS = 5000;
+const GATEWAY_TIMEOUT = 30000;
+const STDERR_TAIL = 8 * 1024;
+// iopub messages that become cell outputs
+const OUTPUT_TYPES = new Set(['stream', 'display_data', 'execute_result', 'error', 'update_display_data', 'clear_output']);
+
+// Kernel id -> kernel (see startKernel)
+const kernels = new Map();
+
+function jupyterDataDirs() {
+  const home = os.homedir();
+  const dirs = (process.env.JUPYTER_PATH || '').split(path.delimiter).filter(Boolean).map((dir) => ({ dir, source: 'user' }));
+  if (process.platform === 'win32') dirs.push({ dir: path.join(process.env.APPDATA || path.join(home, 'AppData', 'Roaming'), 'jupyter'), source: 'user' });
+  else if (process.platform === 'darwin') dirs.push({ dir: path.join(home, 'Library', 'Jupyter'), source: 'user' });
+  else dirs.push({ dir: path.join(process.env.XDG_DATA_HOME || path.join(home, '.local', 'share'), 'jupyter'), source: 'user' });
+  if (process.platform === 'win32') dirs.push({ dir: path.join(process.env.PROGRAMDATA || 'C:\\ProgramData', 'jupyter'), source: 'system' });
+  else dirs.push({ dir: '/usr/local/share/jupyter', source: 'system' }, { dir: '/usr/share/jupyter', source: 'system' });
+  return dirs;
+}
+
+// Virtual environments at the top of the workspace (folders holding a pyvenv.cfg)
+async function workspaceEnvironments(rootPath) {
+  if (!rootPath) return [];
+  const entries = await fs.readdir(rootPath, { withFileTypes: true }).catch(() => []);
+  const environments = [];
+  for (const entry of entries) {
+    const env = path.join(rootPath, entry.name);
+    if (entry.isDirectory() && (await fs.stat(path.join(env, 'pyvenv.cfg')).catch(() => null))) environments.push(env);
+  }
+  return environments;
+}
+
+async function readSpec(specDir, name, source, env = null) {
+  let spec;
+  try {
+    spec = JSON.parse(await fs.readFile(path.join(specDir, 'kernel.json'), 'utf-8'));
+  } catch {
+    return null;
+  }
+  if (!Array.isArray(spec.argv) || !spec.argv.length) return null;
+  let argv = spec.argv;
+  // ipykernel's bundled spec runs "python"; inside a venv that must be the venv's interpreter
+  if (env && ['python', 'python3'].includes(argv[0])) {
+    argv = [path.join(env, process.platform === 'win32' ? 'Scripts\\python.exe' : 'bin/python'), ...argv.slice(1)];
+  }
+  const envName = env ? path.basename(env) : null;
+  return {
+    name: envName ? `${envName}:${name}` : name,
+    displayName: envName ? `${spec.display_name || name} (${envName})` : spec.display_name || name,
+    language: spec.language || null,
+    source,
+    path: specDir,
+    argv,
+    env: spec.env || {},
+    interruptMode: spec.interrupt_mode === 'message' ? 'message' : 'signal'
+  };
+}
+
+// Installed kernel specs: JUPYTER_PATH, the user's data dir, the workspace's virtual
+// environments, then system dirs. Names found earlier win, as in Jupyter.
+async function listLocalKernelSpecs(rootPath) {
+  const specs = new Map();
+  const locations = jupyterDataDirs();
+  const environments = await workspaceEnvironments(rootPath);
+  locations.splice(locations.findIndex((entry) => entry.source === 'system'), 0, ...environments.map((env) => ({ dir: path.join(env, 'share', 'jupyter'), source: 'workspace', env })));
+  for (const { dir, source, env } of locations) {
+    const kernelsDir = path.join(dir, 'kernels');
+    for (const name of await fs.readdir(kernelsDir).catch(() => [])) {
+      const spec = await readSpec(path.join(kernelsDir, name), name, source, env);
+      if (spec && !specs.has(spec.name)) specs.set(spec.name, spec);
+    }
+  }
+  return Array.from(specs.values());
+}
+
+// Jupyter Server / Enterprise Gateway: base URL plus a token read from the named secret
+function gatewayConfig(gateway) {
+  if (!gateway || typeof gateway.url !== 'string') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A gateway URL is required');
+  let url;
+  try {
+    url = new URL(gateway.url.replace(/\/+$/, ''));
+  } catch {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid gateway URL: ${gateway.url}`);
+  }
+  if (url.protocol !== 'http:' && url.protocol !== 'https:') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Gateway URLs must be http or https');
+  const headers = {};
+  if (gateway.tokenSecret) {
+    const token = getSecret(gateway.tokenSecret);
+    if (!token) throw new ForgeError(ErrorCode.NOT_FOUND, `Secret ${gateway.tokenSecret} is not set`);
+    headers.Authorization = `token ${token}`;
+  }
+  return { base: url.href.replace(/\/+$/, ''), headers };
+}
+
+async function gatewayRequest(config, method, apiPath, body) {
+  let response;
+  try {
+    response = await fetch(`${config.base}${apiPath}`, {
+      method,
+      headers: { ...config.headers, ...(body ? { 'Content-Type': 'application/json' } : {}) },
+      body: body ? JSON.stringify(body) : undefined,
+      signal: AbortSignal.timeout(GATEWAY_TIMEOUT)
+    });
+  } catch (error) {
+    if (error.name === 'TimeoutError') throw new ForgeError(ErrorCode.TIMEOUT, `The Jupyter gateway did not answer within ${GATEWAY_TIMEOUT / 1000}s`);
+    throw new ForgeError(ErrorCode.IO_ERROR, `Could not reach the Jupyter gateway: ${error.cause ? error.cause.message : error.message}`);
+  }
+  const text = await response.text();
+  if (!response.ok) {
+    const code = response.status === 401 || response.status === 403 ? ErrorCode.PERMISSION_DENIED : response.status === 404 ? ErrorCode.NOT_FOUND : ErrorCode.IO_ERROR;
+    let message = text.slice(0, 200);
+    try {
+      message = JSON.parse(text).message || message;
+    } catch {
+      // Not JSON
+    }
+    throw new ForgeError(code, `The Jupyter gateway answered ${response.status}: ${message}`);
+  }
+  return text ? JSON.parse(text) : null;
+}
+
+async function listKernelSpecs(rootPath, { gateway } = {}) {
+  if (!gateway) return listLocalKernelSpecs(rootPath);
+  const config = gatewayConfig(gateway);
+  const { kernelspecs = {} } = await gatewayRequest(config, 'GET', '/api/kernelspecs');
+  return Object.entries(kernelspecs).map(([name, entry]) => ({
+    name,
+    displayName: (entry.spec && entry.spec.display_name) || name,
+    language: (entry.spec && entry.spec.language) || null,
+    source: 'gateway',
+    path: null,
+    argv: null,
+    env: {},
+    interruptMode: 'message'
+  }));
+}
+
+function freePort() {
+  return new Promise((resolve, reject) => {
+    const server = net.createServer();
+    server.unref();
+    server.on('error', reject);
+    server.listen(0, '127.0.0.1', () => {
+      const { port } = server.address();
+      server.close(() => resolve(port));
+    });
+  });
+}
+
+function createMessage(kernel, msgType, content, channel) {
+  return {
+    header: { msg_id: crypto.randomUUID(), session: kernel.session, username: 'forge', date: new Date().toISOString(), msg_type: msgType, version: PROTOCOL_VERSION },
+    parent_header: {},
+    metadata: {},
+    content,
+    channel
+  };
+}
+
+function sign(key, parts) {
+  if (!key) return '';
+  const hmac = crypto.createHmac('sha256', key);
+  for (const part of parts) hmac.update(part);
+  return hmac.digest('hex');
+}
+
+// Kernels launched from a kernel spec, spoken to over ZeroMQ with HMAC-signed messages
+async function openZmqTransport(kernel, connection) {
+  const sockets = { shell: new ZmqSocket('DEALER'), control: new ZmqSocket('DEALER'), iopub: new ZmqSocket('SUB') };
+  for (const [channel, socket] of Object.entries(sockets)) {
+    socket.on('error', (error) => log.debug(`Kernel ${kernel.id} ${channel} socket: ${error.message}`));
+    socket.on('message', (frames) => {
+      const index = frames.findIndex((frame) => frame.toString('utf-8') === DELIMITER);
+      if (index < 0 || frames.length < index + 6) return;
+      const parts = frames.slice(index + 2, index + 6);
+      const expected = Buffer.from(sign(connection.key, parts), 'utf-8');
+      const signature = frames[index + 1];
+      if (connection.key && (signature.length !== expected.length || !crypto.timingSafeEqual(signature, expected))) {
+        log.warn(`Dropped a kernel message with a bad signature on ${channel}`);
+        return;
+      }
+      try {
+        const [header, parentHeader, metadata, content] = parts.map((part) => JSON.parse(part.toString('utf-8')));
+        handleMessage(kernel, channel, { header, parent_header: parentHeader, metadata, content, buffers: frames.slice(index + 6) });
+      } catch (error) {
+        log.warn(`Unreadable kernel message on ${channel}: ${error.message}`);
+      }
+    });
+  }
+  await Promise.all([
+    sockets.shell.connect(connection.ip, connection.shell_port, { timeout: START_TIMEOUT }),
+    sockets.control.connect(connection.ip, connection.control_port, { timeout: START_TIMEOUT }),
+    sockets.iopub.connect(connection.ip, connection.iopub_port, { timeout: START_TIMEOUT })
+  ]);
+  return {
+    send(message) {
+      const parts = [message.header, message.parent_header, message.metadata, message.content].map((part) => Buffer.from(JSON.stringify(part), 'utf-8'));
+      sockets[message.channel].send([DELIMITER, sign(connection.key, parts), ...parts]);
+    },
+    close() {
+      for (const socket of Object.values(sockets)) socket.close();
+    }
+  };
+}
+
+// Gateway kernels share one WebSocket; each JSON message names its channel
+async function openGatewayTransport(kernel, config) {
+  const wsBase = config.base.replace(/^http/, 'ws');
+  let connection;
+  try {
+    connection = await connectWebSocket(`${wsBase}/api/kernels/${encodeURIComponent(kernel.remoteId)}/channels?session_id=${kernel.session}`, { headers: config.headers });
+  } catch (error) {
+    throw new ForgeError(ErrorCode.IO_ERROR, `Could not open the kernel's channels: ${error.message}`);
+  }
+  connection.on('message', (data) => {
+    try {
+      const message = JSON.parse(typeof data === 'string' ? data : data.toString('utf-8'));
+      handleMessage(kernel, message.channel, message);
+    } catch (error) {
+      log.warn(`Unreadable gateway message: ${error.message}`);
+    }
+  });
+  connection.on('close', () => {
+    if (kernel.transport && !kernel.stopping) setStatus(kernel, 'dead', 'Lost the connection to the gateway');
+  });
+  return {
+    send(message) {
+      connection.send(JSON.stringify(message));
+    },
+    close() {
+      connection.close(1000, 'Kernel closed');
+    }
+  };
+}
+
+function setStatus(kernel, status, message = null) {
+  if (kernel.status === status) return;
+  kernel.status = status;
+  emit('notebook:kernelStatus', { kernelId: kernel.id, status, message }, { target: kernel.owner });
+  if (status === 'dead') abortPending(kernel, message || 'The kernel died');
+}
+
+function abortPending(kernel, reason) {
+  for (const execution of kernel.executions.values()) execution.resolve({ msgId: execution.msgId, status: 'aborted', executionCount: execution.executionCount, error: reason });
+  kernel.executions.clear();
+  for (const request of kernel.requests.values()) request.reject(new ForgeError(ErrorCode.CANCELLED, reason));
+  kernel.requests.clear();
+}
+
+function finishExecution(kernel, execution) {
+  if (!execution.reply || !execution.idle) return;
+  kernel.executions.delete(execution.msgId);
+  const reply = execution.reply;
+  execution.resolve({
+    msgId: execution.msgId,
+    status: reply.status,
+    executionCount: reply.execution_count === undefined ? execution.executionCount : reply.execution_count,
+    error: reply.status === 'error' ? `${reply.ename}: ${reply.evalue}` : null
+  });
+}
+
+function handleMessage(kernel, channel, message) {
+  const type = message.header && message.header.msg_type;
+  const parentId = message.parent_header && message.parent_header.msg_id;
+  const execution = parentId ? kernel.executions.get(parentId) : null;
+  if (channel === 'iopub') {
+    if (type === 'status') {
+      const state = message.content.execution_state;
+      if (state === 'busy' || state === 'idle' || state === 'starting') setStatus(kernel, state);
+      if (execution && state === 'idle') {
+        execution.idle = true;
+        finishExecution(kernel, execution);
+      }
+    } else if (type === 'execute_input' && execution) {
+      execution.executionCount = message.content.execution_count;
+    } else if (OUTPUT_TYPES.has(type) && execution) {
+      emit('notebook:output', {
+        kernelId: kernel.id,
+        msgId: execution.msgId,
+        cellId: execution.cellId,
+        // nbformat output shape; clear_output and update_display_data tell the view to
+        // clear the cell or replace the output with the same transient display_id
+        output: { output_type: type, ...message.content }
+      }, { target: kernel.owner });
+    }
+    return;
+  }
+  if (type === 'execute_reply' && execution) {
+    execution.reply = message.content;
+    finishExecution(kernel, execution);
+    return;
+  }
+  const request = parentId ? kernel.requests.get(parentId) : null;
+  if (request) {
+    kernel.requests.delete(parentId);
+    clearTimeout(request.timer);
+    request.resolve(message);
+  }
+}
+
+function request(kernel, channel, msgType, content, timeout = REQUEST_TIMEOUT) {
+  const message = createMessage(kernel, msgType, content, channel);
+  return new Promise((resolve, reject) => {
+    const timer = setTimeout(() => {
+      kernel.requests.delete(message.header.msg_id);
+      reject(new ForgeError(ErrorCode.TIMEOUT, `The kernel did not answer ${msgType} within ${timeout / 1000}s`));
+    }, timeout);
+    kernel.requests.set(message.header.msg_id, { resolve, reject, timer });
+    kernel.transport.send(message);
+  });
+}
+
+async function launchLocal(kernel) {
+  const { spec, cwd } = kernel;
+  const ports = await Promise.all(Array.from({ length: 5 }, freePort));
+  const connection = {
+    ip: '127.0.0.1',
+    transport: 'tcp',
+    shell_port: ports[0],
+    iopub_port: ports[1],
+    stdin_port: ports[2],
+    control_port: ports[3],
+    hb_port: ports[4],
+    key: crypto.randomUUID(),
+    signature_scheme: 'hmac-sha256',
+    kernel_name: spec.name
+  };
+  kernel.connectionFile = path.join(os.tmpdir(), `forge-kernel-${kernel.id}.json`);
+  await fs.writeFile(kernel.connectionFile, JSON.stringify(connection, null, 2), { mode: 0o600 });
+  const argv = spec.argv.map((arg) => arg.replace('{connection_file}', kernel.connectionFile).replace('{resource_dir}', spec.path));
+
+  let stderr = '';
+  const child = spawn(argv[0], argv.slice(1), { cwd, env: { ...process.env, ...spec.env }, stdio: ['ignore', 'ignore', 'pipe'], windowsHide: true });
+  kernel.process = child;
+  child.stderr.on('data', (chunk) => {
+    stderr = (stderr + chunk.toString('utf-8')).slice(-STDERR_TAIL);
+  });
+  const exited = new Promise((resolve) => {
+    child.once('error', (error) => resolve(`could not run ${argv[0]}: ${error.message}`));
+    child.once('exit', (code, signal) => resolve(`exited with ${signal || `code ${code}`}`));
+  });
+  exited.then((reason) => {
+    if (kernel.process !== child) return;
+    kernel.process = null;
+    if (!kernel.stopping) setStatus(kernel, 'dead', `Kernel ${reason}${stderr ? `\n${stderr.trim()}` : ''}`);
+  });
+
+  const failure = exited.then((reason) => {
+    throw new ForgeError(ErrorCode.IO_ERROR, `Kernel ${spec.displayName} ${reason}${stderr ? `:\n${stderr.trim()}` : ''}`);
+  });
+  kernel.transport = await Promise.race([openZmqTransport(kernel, connection), failure]);
+  await Promise.race([request(kernel, 'shell', 'kernel_info_request', {}, START_TIMEOUT), failure]);
+}
+
+async function launchGateway(kernel) {
+  const created = await gatewayRequest(kernel.gateway, 'POST', '/api/kernels', { name: kernel.spec.name });
+  kernel.remoteId = created.id;
+  kernel.transport = await openGatewayTransport(kernel, kernel.gateway);
+  await request(kernel, 'shell', 'kernel_info_request', {}, START_TIMEOUT);
+}
+
+async function launch(kernel) {
+  setStatus(kernel, 'starting');
+  try {
+    if (kernel.gateway) await launchGateway(kernel);
+    else await launchLocal(kernel);
+  } catch (error) {
+    await stopTransport(kernel);
+    setStatus(kernel, 'dead', error.message);
+    throw error instanceof ForgeError ? error : ForgeError.from(error, 'Could not start the kernel');
+  }
+  setStatus(kernel, 'idle');
+}
+
+// Starts a kernel from a local kernel spec (by name, see listKernelSpecs) or on a Jupyter
+// gateway. Outputs and status changes go to `owner` as notebook:* events.
+async function startKernel(options, owner) {
+  const { specName, rootPath, notebookPath, gateway } = options || {};
+  if (typeof specName !== 'string' || !specName) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A kernel spec name is required');
+  const cwd = notebookPath ? path.dirname(path.resolve(notebookPath)) : rootPath ? path.resolve(rootPath) : os.homedir();
+  let spec;
+  let gatewayConfigured = null;
+  if (gateway) {
+    gatewayConfigured = gatewayConfig(gateway);
+    spec = { name: specName, displayName: specName, language: null, source: 'gateway', interruptMode: 'message' };
+  } else {
+    // A kernel runs arbitrary code from the notebook's folder, like a task or terminal
+    if (!getRestrictions(cwd).trusted) throw new ForgeError(ErrorCode.PERMISSION_DENIED, 'Kernels cannot start in an untrusted workspace');
+    spec = (await listLocalKernelSpecs(rootPath)).find((entry) => entry.name === specName);
+    if (!spec) throw new ForgeError(ErrorCode.NOT_FOUND, `No kernel spec named ${specName}; install one with ipykernel or select another`);
+  }
+  const kernel = {
+    id: crypto.randomUUID(),
+    session: crypto.randomUUID(),
+    spec,
+    cwd,
+    gateway: gatewayConfigured,
+    owner,
+    status: null,
+    process: null,
+    transport: null,
+    remoteId: null,
+    connectionFile: null,
+    executions: new Map(),
+    requests: new Map(),
+    stopping: false,
+    startedAt: new Date().toISOString()
+  };
+  kernels.set(kernel.id, kernel);
+  try {
+    await launch(kernel);
+  } catch (error) {
+    kernels.delete(kernel.id);
+    throw error;
+  }
+  log.info(`Started kernel ${spec.displayName} (${kernel.id})`);
+  return describeKernel(kernel);
+}
+
+function describeKernel(kernel) {
+  return {
+    id: kernel.id,
+    specName: kernel.spec.name,
+    displayName: kernel.spec.displayName,
+    language: kernel.spec.language,
+    remote: Boolean(kernel.gateway),
+    cwd: kernel.cwd,
+    status: kernel.status,
+    startedAt: kernel.startedAt
+  };
+}
+
+function getKernel(kernelId) {
+  const kernel = kernels.get(kernelId);
+  if (!kernel) throw new ForgeError(ErrorCode.NOT_FOUND, `Kernel ${kernelId} is not running`);
+  return kernel;
+}
+
+function listKernels() {
+  return Array.from(kernels.values()).map(describeKernel);
+}
+
+// Runs code and resolves when the kernel has replied and gone idle; outputs stream as
+// notebook:output events tagged with `cellId`
+function execute(kernelId, code, { cellId = null, silent = false, storeHistory = true, stopOnError = true } = {}) {
+  const kernel = getKernel(kernelId);
+  if (typeof code !== 'string') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Code must be a string');
+  if (kernel.status === 'dead' || !kernel.transport) throw new ForgeError(ErrorCode.BUSY, 'The kernel is not running; restart it first');
+  const message = createMessage(kernel, 'execute_request', {
+    code,
+    silent,
+    store_history: storeHistory,
+    user_expressions: {},
+    // Input prompts aren't supported by the view
+    allow_stdin: false,
+    stop_on_error: stopOnError
+  }, 'shell');
+  const msgId = message.header.msg_id;
+  return new Promise((resolve) => {
+    kernel.executions.set(msgId, { msgId, cellId, reply: null, idle: false, executionCount: null, resolve });
+    kernel.transport.send(message);
+  });
+}
+
+async function interrupt(kernelId) {
+  const kernel = getKernel(kernelId);
+  if (kernel.gateway) await gatewayRequest(kernel.gateway, 'POST', `/api/kernels/${encodeURIComponent(kernel.remoteId)}/interrupt`);
+  else if (kernel.spec.interruptMode === 'message' || process.platform === 'win32') await request(kernel, 'control', 'interrupt_request', {});
+  else if (kernel.process) kernel.process.kill('SIGINT');
+  return true;
+}
+
+async function stopTransport(kernel) {
+  if (kernel.transport) {
+    kernel.transport.close();
+    kernel.transport = null;
+  }
+  const child = kernel.process;
+  if (child) {
+    const exited = new Promise((resolve) => child.once('exit', resolve));
+    const timer = setTimeout(() => child.kill('SIGKILL'), SHUTDOWN_GRACE_MS);
+    child.kill('SIGTERM');
+    await exited;
+    clearTimeout(timer);
+    kernel.process = null;
+  }
+  if (kernel.connectionFile) {
+    await fs.unlink(kernel.connectionFile).catch(() => {});
+    kernel.connectionFile = null;
+  }
+}
+
+// Asks the kernel to shut down cleanly, then stops its process
+async function shutdownKernel(kernelId) {
+  const kernel = getKernel(kernelId);
+  kernel.stopping = true;
+  kernels.delete(kernelId);
+  if (kernel.gateway) {
+    kernel.transport && kernel.transport.close();
+    kernel.transport = null;
+    await gatewayRequest(kernel.gateway, 'DELETE', `/api/kernels/${encodeURIComponent(kernel.remoteId)}`).catch((error) => log.warn(`Gateway shutdown failed: ${error.message}`));
+  } else {
+    if (kernel.transport && kernel.status !== 'dead') await request(kernel, 'control', 'shutdown_request', { restart: false }, SHUTDOWN_GRACE_MS).catch(() => {});
+    await stopTransport(kernel);
+  }
+  abortPending(kernel, 'The kernel was shut down');
+  setStatus(kernel, 'dead', 'Shut down');
+  log.info(`Shut down kernel ${kernel.id}`);
+  return true;
+}
+
+// A fresh kernel process under the same id; running cells are aborted
+async function restartKernel(kernelId) {
+  const kernel = getKernel(kernelId);
+  kernel.stopping = true;
+  abortPending(kernel, 'The kernel was restarted');
+  setStatus(kernel, 'restarting');
+  if (kernel.gateway) {
+    await gatewayRequest(kernel.gateway, 'POST', `/api/kernels/${encodeURIComponent(kernel.remoteId)}/restart`);
+    kernel.stopping = false;
+    setStatus(kernel, 'idle');
+  } else {
+    await stopTransport(kernel);
+    kernel.stopping = false;
+    await launch(kernel);
+  }
+  return describeKernel(kernel);
+}
+
+// Shuts down every kernel a window started, when it closes
+function shutdownOwnedKernels(owner) {
+  for (const kernel of Array.from(kernels.values())) {
+    if (kernel.owner === owner) shutdownKernel(kernel.id).catch((error) => log.warn(`Could not shut down kernel ${kernel.id}: ${error.message}`));
+  }
+}
+
+module.exports = { listKernelSpecs, startKernel, listKernels, execute, interrupt, restartKernel, shutdownKernel, shutdownOwnedKernels };
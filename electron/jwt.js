@@ -0,0 +1,371 @@
+const crypto = require('crypto');
+const { getSecret } = require('./secrets');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+// Tokens are decoded and verified locally so captured credentials never leave the machine.
+
+// JWS compact serialization; JWE tokens have five segments and are reported but not decrypted
+const TOKEN_PATTERN = /\beyJ[A-Za-z0-9_-]*\.eyJ[A-Za-z0-9_-]*\.[A-Za-z0-9_-]*(?:\.[A-Za-z0-9_-]*\.[A-Za-z0-9_-]*)?/g;
+const EXPIRING_SOON_SECONDS = 5 * 60;
+const JWKS_TIMEOUT_MS = 10000;
+const MAX_FIND_RESULTS = 200;
+
+// Registered (RFC 7519) and common OpenID Connect claims shown with a description
+const CLAIM_DESCRIPTIONS = {
+  iss: 'Issuer',
+  sub: 'Subject',
+  aud: 'Audience',
+  exp: 'Expiration time',
+  nbf: 'Not before',
+  iat: 'Issued at',
+  jti: 'JWT ID',
+  azp: 'Authorized party',
+  scope: 'Scopes',
+  scp: 'Scopes',
+  nonce: 'Nonce',
+  auth_time: 'Authentication time',
+  sid: 'Session ID',
+  email: 'Email',
+  name: 'Name',
+  roles: 'Roles'
+};
+const TIME_CLAIMS = new Set(['exp', 'nbf', 'iat', 'auth_time', 'updated_at']);
+
+const ALGORITHMS = {
+  HS256: { family: 'hmac', hash: 'sha256' },
+  HS384: { family: 'hmac', hash: 'sha384' },
+  HS512: { family: 'hmac', hash: 'sha512' },
+  RS256: { family: 'rsa', hash: 'sha256' },
+  RS384: { family: 'rsa', hash: 'sha384' },
+  RS512: { family: 'rsa', hash: 'sha512' },
+  PS256: { family: 'rsa-pss', hash: 'sha256' },
+  PS384: { family: 'rsa-pss', hash: 'sha384' },
+  PS512: { family: 'rsa-pss', hash: 'sha512' },
+  ES256: { family: 'ec', hash: 'sha256', curve: 'prime256v1' },
+  ES384: { family: 'ec', hash: 'sha384', curve: 'secp384r1' },
+  ES512: { family: 'ec', hash: 'sha512', curve: 'secp521r1' },
+  ES256K: { family: 'ec', hash: 'sha256', curve: 'secp256k1' },
+  EdDSA: { family: 'eddsa', hash: null }
+};
+
+function base64UrlDecode(segment, part) {
+  if (!/^[A-Za-z0-9_-]*$/.test(segment)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `The token's ${part} is not base64url`);
+  }
+  return Buffer.from(segment, 'base64url');
+}
+
+function decodeJson(segment, part) {
+  const text = base64UrlDecode(segment, part).toString('utf8');
+  try {
+    const value = JSON.parse(text);
+    if (!value || typeof value !== 'object' || Array.isArray(value)) throw new Error('not an object');
+    return value;
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `The token's ${part} is not a JSON object`);
+  }
+}
+
+// Accepts the raw token or an Authorization header value
+function normalizeToken(token) {
+  if (typeof token !== 'string' || !token.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A token is required');
+  return token.trim().replace(/^(?:authorization:\s*)?bearer\s+/i, '').replace(/^["']|["']$/g, '');
+}
+
+function timeClaim(payload, name) {
+  const value = payload[name];
+  return typeof value === 'number' && Number.isFinite(value) ? value : null;
+}
+
+function toIso(seconds) {
+  const date = new Date(seconds * 1000);
+  return Number.isNaN(date.getTime()) ? null : date.toISOString();
+}
+
+function expiryOf(payload, now = Date.now() / 1000) {
+  const exp = timeClaim(payload, 'exp');
+  const nbf = timeClaim(payload, 'nbf');
+  const iat = timeClaim(payload, 'iat');
+  let status = 'valid';
+  if (nbf !== null && now < nbf) status = 'notYetValid';
+  else if (exp !== null && now >= exp) status = 'expired';
+  else if (exp === null) status = 'noExpiry';
+  return {
+    status,
+    expiresAt: exp === null ? null : toIso(exp),
+    notBefore: nbf === null ? null : toIso(nbf),
+    issuedAt: iat === null ? null : toIso(iat),
+    // Negative once expired
+    expiresIn: exp === null ? null : Math.round(exp - now),
+    expiringSoon: status === 'valid' && exp - now < EXPIRING_SOON_SECONDS,
+    lifetime: exp !== null && iat !== null ? exp - iat : null
+  };
+}
+
+function describeClaims(payload) {
+  return Object.entries(payload).map(([name, value]) => ({
+    name,
+    value,
+    description: CLAIM_DESCRIPTIONS[name] || null,
+    time: TIME_CLAIMS.has(name) && typeof value === 'number' ? toIso(value) : null
+  }));
+}
+
+function warningsFor(header, payload, expiry) {
+  const warnings = [];
+  if (String(header.alg).toLowerCase() === 'none') warnings.push('The token is unsigned (alg "none")');
+  else if (header.alg && !ALGORITHMS[header.alg]) warnings.push(`Unknown signing algorithm ${header.alg}`);
+  if (expiry.status === 'expired') warnings.push(`Expired ${expiry.expiresAt}`);
+  if (expiry.status === 'notYetValid') warnings.push(`Not valid before ${expiry.notBefore}`);
+  if (expiry.status === 'noExpiry') warnings.push('The token never expires (no exp claim)');
+  if (expiry.issuedAt && timeClaim(payload, 'iat') > Date.now() / 1000 + 60) warnings.push('Issued in the future (iat); check clock skew');
+  for (const name of ['exp', 'nbf', 'iat']) {
+    if (payload[name] !== undefined && timeClaim(payload, name) === null) warnings.push(`${name} is not a NumericDate`);
+    // Millisecond timestamps are a common mistake and push expiry thousands of years out
+    else if (timeClaim(payload, name) > 1e11) warnings.push(`${name} looks like milliseconds rather than seconds`);
+  }
+  return warnings;
+}
+
+function decodeJwt(token) {
+  const normalized = normalizeToken(token);
+  const parts = normalized.split('.');
+  if (parts.length === 5) {
+    const header = decodeJson(parts[0], 'header');
+    return { type: 'JWE', header, payload: null, signature: null, algorithm: header.alg || null, claims: [], expiry: null, warnings: ['The payload is encrypted (JWE) and cannot be inspected'] };
+  }
+  if (parts.length !== 3) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `A JWT has three dot-separated segments; this has ${parts.length}`);
+  }
+  const header = decodeJson(parts[0], 'header');
+  const payload = decodeJson(parts[1], 'payload');
+  base64UrlDecode(parts[2], 'signature');
+  const expiry = expiryOf(payload);
+  return {
+    type: 'JWS',
+    header,
+    payload,
+    signature: parts[2],
+    algorithm: header.alg || null,
+    keyId: header.kid || null,
+    claims: describeClaims(payload),
+    expiry,
+    warnings: warningsFor(header, payload, expiry)
+  };
+}
+
+async function fetchJwks(url) {
+  let parsed;
+  try {
+    parsed = new URL(url);
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid JWKS URL: ${url}`);
+  }
+  if (!['http:', 'https:'].includes(parsed.protocol)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'JWKS URLs must use http or https');
+  let response;
+  try {
+    response = await fetch(parsed, { headers: { accept: 'application/json' }, signal: AbortSignal.timeout(JWKS_TIMEOUT_MS) });
+  } catch (error) {
+    if (error.name === 'TimeoutError') throw new ForgeError(ErrorCode.TIMEOUT, `Fetching ${url} timed out`);
+    throw new ForgeError(ErrorCode.IO_ERROR, `Could not fetch ${url}: ${error.message}`);
+  }
+  if (!response.ok) throw new ForgeError(ErrorCode.IO_ERROR, `${url} answered HTTP ${response.status}`);
+  try {
+    return await response.json();
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${url} did not return JSON`);
+  }
+}
+
+// Picks the JWK matching the token's kid, or the only usable key when the token has none
+function selectJwk(jwks, header) {
+  const keys = (jwks.keys || []).filter((key) => !key.use || key.use === 'sig');
+  if (header.kid) {
+    const match = keys.find((key) => key.kid === header.kid);
+    if (!match) throw new ForgeError(ErrorCode.NOT_FOUND, `No key with kid ${header.kid} in the key set`, { details: { kid: header.kid } });
+    return match;
+  }
+  const candidates = keys.filter((key) => !key.alg || key.alg === header.alg);
+  if (candidates.length !== 1) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `The token has no kid and the key set has ${candidates.length} candidate keys`);
+  }
+  return candidates[0];
+}
+
+// Resolves the caller's key material: a stored secret name, a JWKS URL, a JWK or key set,
+// a PEM key or certificate, or a raw HMAC secret
+async function resolveKey(options, header, spec) {
+  let key = options.key;
+  if (options.secretName) {
+    key = getSecret(options.secretName);
+    if (!key) throw new ForgeError(ErrorCode.NOT_FOUND, `Secret ${options.secretName} is not set`, { details: { name: options.secretName } });
+  }
+  if (options.jwksUrl) key = await fetchJwks(options.jwksUrl);
+  if (key === undefined || key === null || key === '') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A key, secretName or jwksUrl is required');
+
+  if (typeof key === 'string' && /^\s*\{/.test(key)) {
+    try {
+      key = JSON.parse(key);
+    } catch (error) {
+      // An HMAC secret may legitimately start with a brace
+    }
+  }
+  if (typeof key === 'object') {
+    const jwk = Array.isArray(key.keys) ? selectJwk(key, header) : key;
+    if (jwk.alg && jwk.alg !== header.alg) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `The key is for ${jwk.alg} but the token uses ${header.alg}`);
+    }
+    if (jwk.kty === 'oct') {
+      if (spec.family !== 'hmac') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `A symmetric key cannot verify ${header.alg}`);
+      return { secret: Buffer.from(jwk.k || '', 'base64url') };
+    }
+    try {
+      return { publicKey: crypto.createPublicKey({ key: jwk, format: 'jwk' }) };
+    } catch (error) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid JWK: ${error.message}`);
+    }
+  }
+  if (spec.family === 'hmac') {
+    // Secrets that arrive base64-encoded must be decoded by the caller; this uses the bytes as typed
+    return { secret: Buffer.from(String(key), options.secretEncoding === 'base64' ? 'base64' : 'utf8') };
+  }
+  const text = String(key).trim();
+  if (!text.startsWith('-----BEGIN')) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${header.alg} needs a PEM public key, certificate or JWK`);
+  try {
+    if (text.includes('CERTIFICATE')) return { publicKey: new crypto.X509Certificate(text).publicKey };
+    // A private key works too; only its public half is used
+    return { publicKey: crypto.createPublicKey(text) };
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid PEM key: ${error.message}`);
+  }
+}
+
+function checkKeyType(publicKey, alg, spec) {
+  const type = publicKey.asymmetricKeyType;
+  const expected = { rsa: ['rsa', 'rsa-pss'], 'rsa-pss': ['rsa', 'rsa-pss'], ec: ['ec'], eddsa: ['ed25519', 'ed448'] }[spec.family];
+  if (!expected.includes(type)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Key type ${type} cannot verify ${alg}`);
+  if (spec.curve && publicKey.asymmetricKeyDetails.namedCurve !== spec.curve) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${alg} needs a ${spec.curve} key, not ${publicKey.asymmetricKeyDetails.namedCurve}`);
+  }
+}
+
+function verifySignature(signingInput, signature, alg, spec, key) {
+  if (spec.family === 'hmac') {
+    const expected = crypto.createHmac(spec.hash, key.secret).update(signingInput).digest();
+    return expected.length === signature.length && crypto.timingSafeEqual(expected, signature);
+  }
+  checkKeyType(key.publicKey, alg, spec);
+  const verifyKey = { key: key.publicKey };
+  if (spec.family === 'rsa-pss') {
+    verifyKey.padding = crypto.constants.RSA_PKCS1_PSS_PADDING;
+    verifyKey.saltLength = crypto.constants.RSA_PSS_SALTLEN_DIGEST;
+  }
+  // JWS carries raw r||s rather than DER for ECDSA
+  if (spec.family === 'ec') verifyKey.dsaEncoding = 'ieee-p1363';
+  return crypto.verify(spec.hash, Buffer.from(signingInput), verifyKey, signature);
+}
+
+// Verifies the signature and checks exp/nbf and, when given, iss/aud. The result always
+// carries the decoded token so the inspector can show why a token was rejected.
+async function verifyJwt(token, options = {}) {
+  const decoded = decodeJwt(token);
+  if (decoded.type === 'JWE') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Encrypted tokens (JWE) cannot be verified');
+  const { header, payload } = decoded;
+  const alg = header.alg;
+  const result = (valid, reason, extra = {}) => ({ valid, reason, algorithm: alg || null, keyId: header.kid || null, ...extra, decoded });
+
+  if (!alg || String(alg).toLowerCase() === 'none') return result(false, 'The token is unsigned');
+  const spec = ALGORITHMS[alg];
+  if (!spec) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unsupported algorithm ${alg}`);
+  if (Array.isArray(options.algorithms) && options.algorithms.length && !options.algorithms.includes(alg)) {
+    return result(false, `${alg} is not among the allowed algorithms (${options.algorithms.join(', ')})`);
+  }
+
+  const key = await resolveKey(options, header, spec);
+  const [encodedHeader, encodedPayload, encodedSignature] = normalizeToken(token).split('.');
+  const signatureValid = verifySignature(`${encodedHeader}.${encodedPayload}`, Buffer.from(encodedSignature, 'base64url'), alg, spec, key);
+  if (!signatureValid) return result(false, 'Signature does not match', { signatureValid });
+
+  const tolerance = Number.isFinite(options.clockTolerance) ? options.clockTolerance : 0;
+  const now = Date.now() / 1000;
+  const exp = timeClaim(payload, 'exp');
+  const nbf = timeClaim(payload, 'nbf');
+  if (exp !== null && now - tolerance >= exp) return result(false, `Expired ${toIso(exp)}`, { signatureValid });
+  if (nbf !== null && now + tolerance < nbf) return result(false, `Not valid before ${toIso(nbf)}`, { signatureValid });
+  if (options.issuer && payload.iss !== options.issuer) return result(false, `Issuer is ${payload.iss}, expected ${options.issuer}`, { signatureValid });
+  if (options.audience) {
+    const audiences = Array.isArray(payload.aud) ? payload.aud : [payload.aud];
+    if (!audiences.includes(options.audience)) return result(false, `Audience does not include ${options.audience}`, { signatureValid });
+  }
+  return result(true, null, { signatureValid });
+}
+
+// Finds JWT-shaped strings in text (HTTP headers, response bodies, storage values)
+function findTokens(text, { source = null } = {}) {
+  const found = [];
+  for (const match of String(text || '').matchAll(TOKEN_PATTERN)) {
+    if (found.length >= MAX_FIND_RESULTS) break;
+    try {
+      const decoded = decodeJwt(match[0]);
+      found.push({
+        token: match[0],
+        start: match.index,
+        end: match.index + match[0].length,
+        source,
+        type: decoded.type,
+        algorithm: decoded.algorithm,
+        header: decoded.header,
+        payload: decoded.payload,
+        expiry: decoded.expiry
+      });
+    } catch (error) {
+      // Looked like a token but was not one
+    }
+  }
+  return found;
+}
+
+const STORAGE_DUMP = `(() => {
+  const dump = (storage) => {
+    const entries = [];
+    try {
+      for (let i = 0; i < storage.length; i++) entries.push([storage.key(i), storage.getItem(storage.key(i))]);
+    } catch (error) {}
+    return entries;
+  };
+  return { local: dump(window.localStorage), session: dump(window.sessionStorage) };
+})()`;
+
+// Collects tokens from a browser preview's cookies and web storage
+async function findTokensInBrowser(browserViews, viewId) {
+  const view = browserViews.get(viewId);
+  if (!view || view.webContents.isDestroyed()) {
+    throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${viewId} not found`, { details: { id: viewId } });
+  }
+  const contents = view.webContents;
+  const url = contents.getURL();
+  const found = [];
+  if (/^https?:/.test(url)) {
+    const cookies = await contents.session.cookies.get({ url });
+    for (const cookie of cookies) found.push(...findTokens(cookie.value, { source: { kind: 'cookie', name: cookie.name } }));
+  }
+  const storage = await contents.executeJavaScript(STORAGE_DUMP, true).catch(() => ({ local: [], session: [] }));
+  for (const [kind, entries] of [['localStorage', storage.local], ['sessionStorage', storage.session]]) {
+    for (const [name, value] of entries) found.push(...findTokens(value, { source: { kind, name } }));
+  }
+  return { url, tokens: found };
+}
+
+function setupJwtHandlers(ipcMain, browserViews) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('jwt:decode', async (event, token) => decodeJwt(token));
+
+  ipcMain.handle('jwt:verify', async (event, token, options) => verifyJwt(token, options || {}));
+
+  ipcMain.handle('jwt:find', async (event, text) => findTokens(text));
+
+  ipcMain.handle('jwt:findInBrowser', async (event, viewId) => findTokensInBrowser(browserViews, viewId));
+}
+
+module.exports = { setupJwtHandlers, decodeJwt, verifyJwt, findTokens };
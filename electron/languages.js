@@ -0,0 +1,349 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { JsonStore } = require('./jsonStore');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+// Bytes read from the head of a file to look for a shebang
+const SHEBANG_BYTES = 256;
+
+// Per-language metadata shared by the editor (comment toggling, auto-indent), file type
+// detection and templates. Ids are the editor's (Monaco) ids.
+//
+//   { name, extensions, filenames?, interpreters? (regexps for the shebang's interpreter),
+//     comments: { line, block: [open, close] }, indentation: { increasePattern?,
+//     decreasePattern?, offSide? (blocks are defined by indentation, as in Python) } }
+const C_STYLE = { line: '//', block: ['/*', '*/'] };
+const HASH = { line: '#', block: null };
+const MARKUP = { line: null, block: ['<!--', '-->'] };
+const NONE = { line: null, block: null };
+
+const BRACES = { increasePattern: '^.*(\\{[^}"\'`]*|\\([^)"\'`]*|\\[[^\\]"\'`]*)$', decreasePattern: '^\\s*[}\\])]' };
+const TAGS = {
+  increasePattern: '<(?!/|!|(?:area|base|br|col|embed|hr|img|input|link|meta|param|source|track|wbr)\\b)[^>]*[^/]>\\s*$',
+  decreasePattern: '^\\s*</'
+};
+const FLAT = {};
+
+const BUILTIN_LANGUAGES = {
+  typescript: { name: 'TypeScript', extensions: ['.ts', '.mts', '.cts'], interpreters: ['^(ts-node|tsx)$'], comments: C_STYLE, indentation: BRACES },
+  typescriptreact: { name: 'TypeScript JSX', extensions: ['.tsx'], comments: C_STYLE, indentation: BRACES },
+  javascript: { name: 'JavaScript', extensions: ['.js', '.mjs', '.cjs'], interpreters: ['^(node|nodejs|bun|deno)$'], comments: C_STYLE, indentation: BRACES },
+  javascriptreact: { name: 'JavaScript JSX', extensions: ['.jsx'], comments: C_STYLE, indentation: BRACES },
+  json: { name: 'JSON', extensions: ['.json'], comments: NONE, indentation: BRACES },
+  jsonc: { name: 'JSON with Comments', extensions: ['.jsonc'], comments: C_STYLE, indentation: BRACES },
+  json5: { name: 'JSON5', extensions: ['.json5'], comments: C_STYLE, indentation: BRACES },
+  python: {
+    name: 'Python',
+    extensions: ['.py', '.pyi'],
+    interpreters: ['^python[\\d.]*$'],
+    comments: { line: '#', block: ['"""', '"""'] },
+    indentation: { increasePattern: '^.*:\\s*(#.*)?$', decreasePattern: '^\\s*(elif|else|except|finally)\\b.*:', offSide: true }
+  },
+  ruby: {
+    name: 'Ruby',
+    extensions: ['.rb'],
+    filenames: ['Gemfile', 'Rakefile', 'Vagrantfile'],
+    interpreters: ['^ruby$'],
+    comments: { line: '#', block: ['=begin', '=end'] },
+    indentation: { increasePattern: '^\\s*(class|module|def|if|unless|case|while|until|for|begin)\\b|\\bdo(\\s*\\|[^|]*\\|)?\\s*$', decreasePattern: '^\\s*(end|else|elsif|when|rescue|ensure)\\b' }
+  },
+  php: { name: 'PHP', extensions: ['.php'], interpreters: ['^php[\\d.]*$'], comments: C_STYLE, indentation: BRACES },
+  perl: { name: 'Perl', extensions: ['.pl'], interpreters: ['^perl[\\d.]*$'], comments: HASH, indentation: BRACES },
+  lua: {
+    name: 'Lua',
+    extensions: ['.lua'],
+    interpreters: ['^lua(jit)?[\\d.]*$'],
+    comments: { line: '--', block: ['--[[', ']]'] },
+    indentation: { increasePattern: '^\\s*(function|if|for|while|repeat|do)\\b|\\bfunction\\s*\\(.*\\)\\s*$', decreasePattern: '^\\s*(end|else|elseif|until)\\b' }
+  },
+  rust: { name: 'Rust', extensions: ['.rs'], comments: C_STYLE, indentation: BRACES },
+  go: { name: 'Go', extensions: ['.go'], comments: C_STYLE, indentation: BRACES },
+  java: { name: 'Java', extensions: ['.java'], comments: C_STYLE, indentation: BRACES },
+  kotlin: { name: 'Kotlin', extensions: ['.kt', '.kts'], comments: C_STYLE, indentation: BRACES },
+  scala: { name: 'Scala', extensions: ['.scala'], comments: C_STYLE, indentation: BRACES },
+  swift: { name: 'Swift', extensions: ['.swift'], comments: C_STYLE, indentation: BRACES },
+  c: { name: 'C', extensions: ['.c', '.h'], comments: C_STYLE, indentation: BRACES },
+  cpp: { name: 'C++', extensions: ['.cc', '.cpp', '.cxx', '.hpp', '.hh'], comments: C_STYLE, indentation: BRACES },
+  csharp: { name: 'C#', extensions: ['.cs'], comments: C_STYLE, indentation: BRACES },
+  fsharp: { name: 'F#', extensions: ['.fs'], comments: { line: '//', block: ['(*', '*)'] }, indentation: { offSide: true } },
+  dart: { name: 'Dart', extensions: ['.dart'], comments: C_STYLE, indentation: BRACES },
+  zig: { name: 'Zig', extensions: ['.zig'], comments: { line: '//', block: null }, indentation: BRACES },
+  groovy: { name: 'Groovy', extensions: ['.groovy', '.gradle'], filenames: ['Jenkinsfile'], comments: C_STYLE, indentation: BRACES },
+  elixir: {
+    name: 'Elixir',
+    extensions: ['.ex', '.exs'],
+    comments: HASH,
+    indentation: { increasePattern: '\\bdo\\s*$|->\\s*$', decreasePattern: '^\\s*(end|else|catch|rescue|after)\\b' }
+  },
+  erlang: { name: 'Erlang', extensions: ['.erl'], comments: { line: '%', block: null }, indentation: { increasePattern: '->\\s*$', decreasePattern: '^\\s*end\\b' } },
+  haskell: { name: 'Haskell', extensions: ['.hs'], comments: { line: '--', block: ['{-', '-}'] }, indentation: { offSide: true } },
+  clojure: { name: 'Clojure', extensions: ['.clj'], comments: { line: ';', block: null }, indentation: BRACES },
+  r: { name: 'R', extensions: ['.r'], interpreters: ['^Rscript$'], comments: HASH, indentation: BRACES },
+  julia: { name: 'Julia', extensions: ['.jl'], comments: { line: '#', block: ['#=', '=#'] }, indentation: { increasePattern: '^\\s*(function|if|for|while|begin|let|module|struct|try|do)\\b', decreasePattern: '^\\s*(end|else|elseif|catch|finally)\\b' } },
+  shellscript: {
+    name: 'Shell Script',
+    extensions: ['.sh', '.bash', '.zsh'],
+    filenames: ['.bashrc', '.zshrc', '.profile'],
+    interpreters: ['^(ba|z|k|da|a)?sh$'],
+    comments: HASH,
+    indentation: { increasePattern: '\\b(then|do)\\s*$|^\\s*case\\b|\\{\\s*$', decreasePattern: '^\\s*(fi|done|esac|else|elif)\\b|^\\s*\\}' }
+  },
+  fish: { name: 'Fish', extensions: ['.fish'], interpreters: ['^fish$'], comments: HASH, indentation: { increasePattern: '^\\s*(function|if|for|while|switch|begin)\\b', decreasePattern: '^\\s*(end|else)\\b' } },
+  powershell: { name: 'PowerShell', extensions: ['.ps1', '.psm1'], interpreters: ['^pwsh$'], comments: { line: '#', block: ['<#', '#>'] }, indentation: BRACES },
+  bat: { name: 'Batch', extensions: ['.bat', '.cmd'], comments: { line: 'REM', block: null }, indentation: { increasePattern: '\\($', decreasePattern: '^\\s*\\)' } },
+  html: { name: 'HTML', extensions: ['.html', '.htm'], comments: MARKUP, indentation: TAGS },
+  vue: { name: 'Vue', extensions: ['.vue'], comments: MARKUP, indentation: TAGS },
+  svelte: { name: 'Svelte', extensions: ['.svelte'], comments: MARKUP, indentation: TAGS },
+  astro: { name: 'Astro', extensions: ['.astro'], comments: MARKUP, indentation: TAGS },
+  css: { name: 'CSS', extensions: ['.css'], comments: { line: null, block: ['/*', '*/'] }, indentation: BRACES },
+  scss: { name: 'SCSS', extensions: ['.scss'], comments: C_STYLE, indentation: BRACES },
+  sass: { name: 'Sass', extensions: ['.sass'], comments: C_STYLE, indentation: { offSide: true } },
+  less: { name: 'Less', extensions: ['.less'], comments: C_STYLE, indentation: BRACES },
+  markdown: { name: 'Markdown', extensions: ['.md'], comments: MARKUP, indentation: FLAT },
+  mdx: { name: 'MDX', extensions: ['.mdx'], comments: { line: null, block: ['{/*', '*/}'] }, indentation: FLAT },
+  restructuredtext: { name: 'reStructuredText', extensions: ['.rst'], comments: { line: '..', block: null }, indentation: FLAT },
+  latex: { name: 'LaTeX', extensions: ['.tex'], comments: { line: '%', block: null }, indentation: { increasePattern: '\\\\begin\\{', decreasePattern: '^\\s*\\\\end\\{' } },
+  xml: { name: 'XML', extensions: ['.xml', '.svg'], comments: MARKUP, indentation: TAGS },
+  yaml: { name: 'YAML', extensions: ['.yaml', '.yml'], comments: HASH, indentation: { increasePattern: ':\\s*(#.*)?$|^\\s*-\\s*$', offSide: true } },
+  toml: { name: 'TOML', extensions: ['.toml'], comments: HASH, indentation: BRACES },
+  ini: { name: 'INI', extensions: ['.ini', '.cfg', '.conf'], comments: { line: ';', block: null }, indentation: FLAT },
+  sql: { name: 'SQL', extensions: ['.sql'], comments: { line: '--', block: ['/*', '*/'] }, indentation: BRACES },
+  graphql: { name: 'GraphQL', extensions: ['.graphql', '.gql'], comments: HASH, indentation: BRACES },
+  proto: { name: 'Protocol Buffers', extensions: ['.proto'], comments: C_STYLE, indentation: BRACES },
+  flatbuffers: { name: 'FlatBuffers', extensions: ['.fbs'], comments: C_STYLE, indentation: BRACES },
+  dockerfile: { name: 'Dockerfile', extensions: ['.dockerfile'], filenames: ['Dockerfile', 'Containerfile'], comments: HASH, indentation: FLAT },
+  makefile: { name: 'Makefile', extensions: ['.mk'], filenames: ['Makefile', 'GNUmakefile'], comments: HASH, indentation: FLAT },
+  cmake: { name: 'CMake', extensions: ['.cmake'], filenames: ['CMakeLists.txt'], comments: { line: '#', block: ['#[[', ']]'] }, indentation: BRACES },
+  terraform: { name: 'Terraform', extensions: ['.tf'], comments: { line: '#', block: ['/*', '*/'] }, indentation: BRACES },
+  nix: { name: 'Nix', extensions: ['.nix'], comments: { line: '#', block: ['/*', '*/'] }, indentation: BRACES },
+  diff: { name: 'Diff', extensions: ['.diff', '.patch'], comments: NONE, indentation: FLAT },
+  csv: { name: 'CSV', extensions: ['.csv'], comments: NONE, indentation: FLAT },
+  ignore: { name: 'Ignore', extensions: [], filenames: ['.gitignore', '.dockerignore', '.npmignore'], comments: HASH, indentation: FLAT },
+  dotenv: { name: 'Environment', extensions: [], filenames: ['.env'], comments: HASH, indentation: FLAT },
+  log: { name: 'Log', extensions: ['.log'], comments: NONE, indentation: FLAT },
+  plaintext: { name: 'Plain Text', extensions: ['.txt'], comments: NONE, indentation: FLAT }
+};
+
+const store = new JsonStore('languages', {
+  // User-defined languages and overrides by id, same shape as the built-ins
+  custom: {}
+});
+
+// Language id -> [{ source, definition }] registered by plugins at runtime
+const extensions = new Map();
+
+// Merged view of every layer, rebuilt when one changes:
+// { languages: Map<id, config>, byExtension, byFilename, interpreters: [[RegExp, id]] }
+let registry = null;
+
+// Layers apply in order: built-in, plugins, then the user's own definitions. For one id,
+// list fields (extensions, filenames, interpreters) accumulate and the rest override.
+function buildRegistry() {
+  const languages = new Map();
+  const apply = (id, definition, source) => {
+    const current = languages.get(id) || { id, name: id, extensions: [], filenames: [], interpreters: [], comments: NONE, indentation: FLAT, sources: [] };
+    languages.set(id, {
+      ...current,
+      name: definition.name || current.name,
+      extensions: Array.from(new Set([...current.extensions, ...(definition.extensions || [])])),
+      filenames: Array.from(new Set([...current.filenames, ...(definition.filenames || [])])),
+      interpreters: Array.from(new Set([...current.interpreters, ...(definition.interpreters || [])])),
+      comments: definition.comments ? { ...current.comments, ...definition.comments } : current.comments,
+      indentation: definition.indentation ? { ...current.indentation, ...definition.indentation } : current.indentation,
+      sources: [...current.sources, source]
+    });
+  };
+  for (const [id, definition] of Object.entries(BUILTIN_LANGUAGES)) apply(id, definition, 'builtin');
+  for (const [id, layers] of extensions) for (const { source, definition } of layers) apply(id, definition, source);
+  for (const [id, definition] of Object.entries(store.get('custom', {}))) apply(id, definition, 'user');
+
+  // Later layers win a contested extension or file name
+  const byExtension = new Map();
+  const byFilename = new Map();
+  const interpreters = [];
+  for (const language of languages.values()) {
+    for (const extension of language.extensions) byExtension.set(extension, language.id);
+    for (const filename of language.filenames) byFilename.set(filename, language.id);
+    for (const pattern of language.interpreters) interpreters.push([new RegExp(pattern), language.id]);
+  }
+  return { languages, byExtension, byFilename, interpreters };
+}
+
+function getRegistry() {
+  if (!registry) registry = buildRegistry();
+  return registry;
+}
+
+function isPattern(value) {
+  if (typeof value !== 'string') return false;
+  try {
+    new RegExp(value);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+// Check a definition from a plugin or the user; fields left out keep their current value
+function validateLanguage(id, definition) {
+  if (typeof id !== 'string' || !/^[\w.+-]+$/.test(id)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid language id: ${id}`);
+  }
+  if (!definition || typeof definition !== 'object') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A language definition must be an object');
+  }
+  const stringList = (value, field, check = (item) => typeof item === 'string' && item.length > 0) => {
+    if (value === undefined) return undefined;
+    if (!Array.isArray(value) || !value.every(check)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${field} of ${id} is not valid`);
+    }
+    return value;
+  };
+  const result = {};
+  if (definition.name !== undefined) {
+    if (typeof definition.name !== 'string' || !definition.name) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Name of ${id} must be a string`);
+    result.name = definition.name;
+  }
+  const extensionList = stringList(definition.extensions, 'extensions', (item) => typeof item === 'string' && /^\.[^/\\]+$/.test(item));
+  if (extensionList) result.extensions = extensionList.map((extension) => extension.toLowerCase());
+  if (definition.filenames !== undefined) result.filenames = stringList(definition.filenames, 'filenames');
+  if (definition.interpreters !== undefined) result.interpreters = stringList(definition.interpreters, 'interpreters', isPattern);
+
+  if (definition.comments !== undefined) {
+    const { line, block } = definition.comments || {};
+    const validLine = line === undefined || line === null || (typeof line === 'string' && line.length > 0);
+    const validBlock = block === undefined || block === null || (Array.isArray(block) && block.length === 2 && block.every((token) => typeof token === 'string' && token));
+    if (!validLine || !validBlock) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Comment tokens of ${id} must be { line: string | null, block: [open, close] | null }`);
+    }
+    result.comments = {};
+    if (line !== undefined) result.comments.line = line;
+    if (block !== undefined) result.comments.block = block;
+  }
+  if (definition.indentation !== undefined) {
+    const { increasePattern, decreasePattern, offSide } = definition.indentation || {};
+    if ((increasePattern !== undefined && !isPattern(increasePattern)) || (decreasePattern !== undefined && !isPattern(decreasePattern)) ||
+      (offSide !== undefined && typeof offSide !== 'boolean')) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Indentation rules of ${id} must be regular expressions`);
+    }
+    result.indentation = {};
+    if (increasePattern !== undefined) result.indentation.increasePattern = increasePattern;
+    if (decreasePattern !== undefined) result.indentation.decreasePattern = decreasePattern;
+    if (offSide !== undefined) result.indentation.offSide = offSide;
+  }
+  return result;
+}
+
+// Add a language, or extend an existing one (e.g. more extensions, different comment
+// tokens), from a plugin. `source` names the plugin. Returns an unregister function.
+function registerLanguage(id, definition, source = 'plugin') {
+  const layer = { source, definition: validateLanguage(id, definition) };
+  if (!extensions.has(id)) extensions.set(id, []);
+  extensions.get(id).push(layer);
+  registry = null;
+  return () => {
+    const layers = extensions.get(id) || [];
+    const index = layers.indexOf(layer);
+    if (index === -1) return false;
+    layers.splice(index, 1);
+    if (layers.length === 0) extensions.delete(id);
+    registry = null;
+    return true;
+  };
+}
+
+function saveCustomLanguage(id, definition) {
+  const validated = validateLanguage(id, definition);
+  if (!BUILTIN_LANGUAGES[id] && !extensions.has(id) && (!validated.extensions || validated.extensions.length === 0) && !validated.filenames) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `New language ${id} needs extensions or file names`);
+  }
+  store.set('custom', { ...store.get('custom', {}), [id]: validated });
+  registry = null;
+  return getLanguageConfig(id);
+}
+
+function deleteCustomLanguage(id) {
+  const custom = { ...store.get('custom', {}) };
+  if (!custom[id]) return false;
+  delete custom[id];
+  store.set('custom', custom);
+  registry = null;
+  return true;
+}
+
+function getLanguageConfig(id) {
+  const language = getRegistry().languages.get(id);
+  if (!language) throw new ForgeError(ErrorCode.NOT_FOUND, `Unknown language: ${id}`, { details: { id } });
+  return language;
+}
+
+function listLanguages() {
+  return Array.from(getRegistry().languages.values()).sort((a, b) => a.name.localeCompare(b.name));
+}
+
+// Language named by a `#!` line, or null
+function detectShebang(text) {
+  const match = /^#!\s*(\S+)(?:\s+(\S+))?/.exec(text);
+  if (!match) return null;
+  // `#!/usr/bin/env python3` names the interpreter in the second word
+  let interpreter = path.basename(match[1]);
+  if (interpreter === 'env' && match[2]) interpreter = match[2] === '-S' ? '' : path.basename(match[2]);
+  const found = getRegistry().interpreters.find(([pattern]) => pattern.test(interpreter));
+  return found ? found[1] : null;
+}
+
+// Language id for a file from its name, then extension, then the shebang in `headText`.
+// Extensionless files without a shebang are plain text; unknown extensions are null.
+function detectLanguage(filePath, headText) {
+  const { byFilename, byExtension } = getRegistry();
+  const name = path.basename(filePath);
+  if (byFilename.has(name)) return byFilename.get(name);
+  const extension = path.extname(name).toLowerCase();
+  if (byExtension.has(extension)) return byExtension.get(extension);
+  return (headText && detectShebang(headText)) || (extension ? null : 'plaintext');
+}
+
+async function readHead(filePath) {
+  let handle;
+  try {
+    handle = await fs.open(filePath, 'r');
+    const buffer = Buffer.alloc(SHEBANG_BYTES);
+    const { bytesRead } = await handle.read(buffer, 0, SHEBANG_BYTES, 0);
+    return buffer.toString('utf-8', 0, bytesRead);
+  } catch {
+    return null;
+  } finally {
+    if (handle) await handle.close();
+  }
+}
+
+// Config for the language of a file on disk, reading its first line when the name alone
+// doesn't tell
+async function languageConfigForFile(filePath) {
+  let language = detectLanguage(filePath, null);
+  if (!language || language === 'plaintext') {
+    const head = await readHead(filePath);
+    language = (head && detectShebang(head)) || language;
+  }
+  return language ? getLanguageConfig(language) : null;
+}
+
+function setupLanguageHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('languages:list', async () => listLanguages());
+
+  ipcMain.handle('languages:get', async (event, id) => getLanguageConfig(id));
+
+  // Null for files no language claims
+  ipcMain.handle('languages:forFile', async (event, filePath) => {
+    if (typeof filePath !== 'string' || !filePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A file path is required');
+    return languageConfigForFile(filePath);
+  });
+
+  ipcMain.handle('languages:save', async (event, id, definition) => saveCustomLanguage(id, definition));
+
+  ipcMain.handle('languages:delete', async (event, id) => deleteCustomLanguage(id));
+}
+
+module.exports = { setupLanguageHandlers, registerLanguage, getLanguageConfig, listLanguages, detectLanguage, detectShebang, languageConfigForFile };
@@ -0,0 +1,593 @@
+const fs = require('fs').promises;
+const os = require('os');
+const path = require('path');
+const { execFile } = require('child_process');
+const { createLogger } = require('./logger');
+const { runJob } = require('./jobs');
+const { JsonStore } = require('./jsonStore');
+const { parseJsonc } = require('./snippets');
+const { parseToml } = require('./toml');
+const { getRestrictions } = require('./trust');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('licenses');
+
+const POLICY_FILE = path.join('.forge', 'licenses.json');
+const CARGO_TIMEOUT = 120000;
+const MAX_OUTPUT = 128 * 1024 * 1024;
+// License files are only sniffed from their opening text
+const SNIFF_BYTES = 4096;
+
+// The user-level policy applies to workspaces without their own .forge/licenses.json
+const store = new JsonStore('licenses', {
+  policy: { disallowed: [], allowed: [], ignore: [] }
+});
+
+// Canonical spelling of common SPDX identifiers, looked up case-insensitively
+const SPDX_IDS = [
+  '0BSD', 'AFL-3.0', 'AGPL-3.0-only', 'AGPL-3.0-or-later', 'Apache-1.1', 'Apache-2.0', 'Artistic-2.0', 'BlueOak-1.0.0',
+  'BSD-2-Clause', 'BSD-3-Clause', 'BSD-3-Clause-Clear', 'BSL-1.0', 'BUSL-1.1', 'CC-BY-3.0', 'CC-BY-4.0', 'CC-BY-SA-4.0',
+  'CC0-1.0', 'CDDL-1.0', 'CDDL-1.1', 'EPL-1.0', 'EPL-2.0', 'EUPL-1.2', 'GPL-2.0-only', 'GPL-2.0-or-later',
+  'GPL-3.0-only', 'GPL-3.0-or-later', 'HPND', 'ISC', 'LGPL-2.0-only', 'LGPL-2.0-or-later', 'LGPL-2.1-only',
+  'LGPL-2.1-or-later', 'LGPL-3.0-only', 'LGPL-3.0-or-later', 'MIT', 'MIT-0', 'MPL-1.1', 'MPL-2.0', 'MS-PL', 'NCSA',
+  'ODbL-1.0', 'OFL-1.1', 'OpenSSL', 'PSF-2.0', 'Python-2.0', 'SSPL-1.0', 'Unicode-3.0', 'Unicode-DFS-2016',
+  'Unlicense', 'UPL-1.0', 'W3C', 'WTFPL', 'X11', 'Zlib', 'LLVM-exception', 'Classpath-exception-2.0'
+];
+const CANONICAL = new Map(SPDX_IDS.map((id) => [id.toLowerCase(), id]));
+
+// Free-form names seen in package metadata, keyed by their lower-cased text with
+// "license" and punctuation dropped
+const ALIASES = {
+  'mit': 'MIT',
+  'the mit': 'MIT',
+  'expat': 'MIT',
+  'apache 2.0': 'Apache-2.0',
+  'apache 2': 'Apache-2.0',
+  'apache2': 'Apache-2.0',
+  'apache v2': 'Apache-2.0',
+  'apache version 2.0': 'Apache-2.0',
+  'apache software 2.0': 'Apache-2.0',
+  'asl 2.0': 'Apache-2.0',
+  'isc': 'ISC',
+  'bsd': 'BSD-3-Clause',
+  'new bsd': 'BSD-3-Clause',
+  '3 clause bsd': 'BSD-3-Clause',
+  'bsd 3 clause': 'BSD-3-Clause',
+  'simplified bsd': 'BSD-2-Clause',
+  '2 clause bsd': 'BSD-2-Clause',
+  'bsd 2 clause': 'BSD-2-Clause',
+  'mpl 2.0': 'MPL-2.0',
+  'mozilla public 2.0': 'MPL-2.0',
+  'gplv2': 'GPL-2.0-only',
+  'gpl v2': 'GPL-2.0-only',
+  'gplv3': 'GPL-3.0-only',
+  'gpl v3': 'GPL-3.0-only',
+  'lgplv3': 'LGPL-3.0-only',
+  'lgpl v3': 'LGPL-3.0-only',
+  'agplv3': 'AGPL-3.0-only',
+  'psf': 'PSF-2.0',
+  'python software foundation': 'PSF-2.0',
+  'unlicense': 'Unlicense',
+  'the unlicense': 'Unlicense',
+  'public domain': 'Unlicense',
+  'cc0': 'CC0-1.0',
+  'zlib': 'Zlib',
+  'zlib libpng': 'Zlib'
+};
+
+// Trove classifiers ("License :: OSI Approved :: ...") by their last segment
+const CLASSIFIERS = {
+  'MIT License': 'MIT',
+  'MIT No Attribution License (MIT-0)': 'MIT-0',
+  'Apache Software License': 'Apache-2.0',
+  'BSD License': 'BSD-3-Clause',
+  'ISC License (ISCL)': 'ISC',
+  'Mozilla Public License 2.0 (MPL 2.0)': 'MPL-2.0',
+  'GNU General Public License v2 (GPLv2)': 'GPL-2.0-only',
+  'GNU General Public License v2 or later (GPLv2+)': 'GPL-2.0-or-later',
+  'GNU General Public License v3 (GPLv3)': 'GPL-3.0-only',
+  'GNU General Public License v3 or later (GPLv3+)': 'GPL-3.0-or-later',
+  'GNU Lesser General Public License v2 (LGPLv2)': 'LGPL-2.0-only',
+  'GNU Lesser General Public License v2 or later (LGPLv2+)': 'LGPL-2.0-or-later',
+  'GNU Lesser General Public License v3 (LGPLv3)': 'LGPL-3.0-only',
+  'GNU Lesser General Public License v3 or later (LGPLv3+)': 'LGPL-3.0-or-later',
+  'GNU Affero General Public License v3': 'AGPL-3.0-only',
+  'GNU Affero General Public License v3 or later (AGPLv3+)': 'AGPL-3.0-or-later',
+  'Python Software Foundation License': 'PSF-2.0',
+  'The Unlicense (Unlicense)': 'Unlicense',
+  'Eclipse Public License 2.0 (EPL-2.0)': 'EPL-2.0',
+  'Boost Software License 1.0 (BSL-1.0)': 'BSL-1.0',
+  'zlib/libpng License': 'Zlib',
+  'Public Domain': 'Unlicense',
+  'CC0 1.0 Universal (CC0 1.0) Public Domain Dedication': 'CC0-1.0'
+};
+
+// Recognizes a license file by its opening text; the first match wins
+const LICENSE_TEXTS = [
+  [/GNU AFFERO GENERAL PUBLIC LICENSE\s+Version 3/i, 'AGPL-3.0-only'],
+  [/GNU LESSER GENERAL PUBLIC LICENSE\s+Version 3/i, 'LGPL-3.0-only'],
+  [/GNU LESSER GENERAL PUBLIC LICENSE\s+Version 2\.1/i, 'LGPL-2.1-only'],
+  [/GNU GENERAL PUBLIC LICENSE\s+Version 3/i, 'GPL-3.0-only'],
+  [/GNU GENERAL PUBLIC LICENSE\s+Version 2/i, 'GPL-2.0-only'],
+  [/Mozilla Public License,? (Version|v\.?) 2\.0/i, 'MPL-2.0'],
+  [/Apache License,?\s+Version 2\.0/i, 'Apache-2.0'],
+  [/Permission is hereby granted, free of charge/i, 'MIT'],
+  [/Permission to use, copy, modify, and(\/or)? distribute this software for any purpose/i, 'ISC'],
+  [/Redistribution and use in source and binary forms[\s\S]*Neither the name/i, 'BSD-3-Clause'],
+  [/Redistribution and use in source and binary forms/i, 'BSD-2-Clause'],
+  [/This is free and unencumbered software released into the public domain/i, 'Unlicense'],
+  [/Boost Software License - Version 1\.0/i, 'BSL-1.0'],
+  [/Eclipse Public License - v 2\.0/i, 'EPL-2.0']
+];
+const LICENSE_FILE = /^(licen[cs]e|copying|unlicense)([-._].*)?$/i;
+
+// "GPL-3.0" and "GPL-3.0+" are deprecated spellings of the -only / -or-later forms
+function canonicalId(id) {
+  const legacy = /^(A?GPL|LGPL|GFDL)-(\d\.\d)(\+)?$/i.exec(id);
+  if (legacy) return `${legacy[1].toUpperCase()}-${legacy[2]}-${legacy[3] ? 'or-later' : 'only'}`;
+  return CANONICAL.get(id.toLowerCase()) || id;
+}
+
+function aliasFor(text) {
+  const key = text.toLowerCase().replace(/\blicen[cs]e[ds]?\b/g, ' ').replace(/[^a-z0-9.+ ]+/g, ' ').replace(/\s+/g, ' ').trim();
+  return ALIASES[key] || null;
+}
+
+// Parses an SPDX expression into { id, exception } leaves joined by { op: 'and'|'or', terms }.
+// Operators are accepted in any case and the old "MIT/Apache-2.0" Cargo form as OR.
+function parseExpression(text) {
+  const tokens = text.replace(/\//g, ' OR ').match(/\(|\)|[^\s()]+/g) || [];
+  let index = 0;
+  const peek = () => (index < tokens.length ? tokens[index] : null);
+  const isOperator = (token, name) => token !== null && token.toUpperCase() === name;
+
+  const parseAtom = () => {
+    const token = tokens[index++];
+    if (token === undefined) throw new Error('Unexpected end of expression');
+    if (token === '(') {
+      const inner = parseOr();
+      if (tokens[index++] !== ')') throw new Error('Missing )');
+      return inner;
+    }
+    if (token === ')' || ['AND', 'OR', 'WITH'].includes(token.toUpperCase())) throw new Error(`Unexpected ${token}`);
+    const leaf = { id: canonicalId(token) };
+    if (isOperator(peek(), 'WITH')) {
+      index++;
+      const exception = tokens[index++];
+      if (!exception || exception === '(' || exception === ')') throw new Error('Missing exception after WITH');
+      leaf.exception = canonicalId(exception);
+    }
+    return leaf;
+  };
+  const parseList = (name, parseTerm) => {
+    const terms = [parseTerm()];
+    while (isOperator(peek(), name)) {
+      index++;
+      terms.push(parseTerm());
+    }
+    return terms.length === 1 ? terms[0] : { op: name.toLowerCase(), terms };
+  };
+  const parseAnd = () => parseList('AND', parseAtom);
+  const parseOr = () => parseList('OR', parseAnd);
+
+  const tree = parseOr();
+  if (index < tokens.length) throw new Error(`Unexpected ${tokens[index]}`);
+  return tree;
+}
+
+function formatExpression(node, nested = false) {
+  if (!node.op) return node.exception ? `${node.id} WITH ${node.exception}` : node.id;
+  const text = node.terms.map((term) => formatExpression(term, true)).join(` ${node.op.toUpperCase()} `);
+  return nested ? `(${text})` : text;
+}
+
+function leavesOf(node) {
+  return node.op ? node.terms.flatMap(leavesOf) : [node];
+}
+
+// Declared license text -> expression tree. Anything that isn't a valid expression is kept
+// whole as a single (non-SPDX) identifier so policies can still match it by name.
+function normalizeLicense(declared) {
+  const text = declared.trim();
+  const alias = aliasFor(text);
+  if (alias) return { id: alias };
+  try {
+    return parseExpression(text);
+  } catch {
+    return { id: text };
+  }
+}
+
+function sniffLicenseText(text) {
+  const head = text.slice(0, SNIFF_BYTES);
+  const match = LICENSE_TEXTS.find(([pattern]) => pattern.test(head));
+  return match ? match[1] : null;
+}
+
+// License named by the license files in `dir`, e.g. "MIT AND Apache-2.0" for a crate that
+// ships LICENSE-MIT and LICENSE-APACHE
+async function licenseFromFiles(dir, names) {
+  let files = names;
+  if (!files) {
+    const entries = await fs.readdir(dir).catch(() => []);
+    files = entries.filter((name) => LICENSE_FILE.test(name));
+  }
+  const found = new Set();
+  for (const name of files) {
+    const text = await fs.readFile(path.join(dir, name), 'utf-8').catch(() => null);
+    const id = text && sniffLicenseText(text);
+    if (id) found.add(id);
+  }
+  return found.size ? Array.from(found).join(' AND ') : null;
+}
+
+// Policy entries are license identifiers or package names where "*" matches anything
+function compilePatterns(entries) {
+  if (!Array.isArray(entries)) return [];
+  return entries
+    .filter((entry) => typeof entry === 'string' && entry.trim())
+    .map((entry) => new RegExp(`^${entry.trim().replace(/[.+?^${}()|[\]\\]/g, '\\$&').replace(/\*/g, '.*')}$`, 'i'));
+}
+
+function validatePolicy(raw, source) {
+  if (!raw || typeof raw !== 'object' || Array.isArray(raw)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${source} must be an object with "disallowed", "allowed" and "ignore" lists`);
+  }
+  const policy = {};
+  for (const key of ['disallowed', 'allowed', 'ignore']) {
+    const value = raw[key] === undefined ? [] : raw[key];
+    if (!Array.isArray(value) || value.some((entry) => typeof entry !== 'string')) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `"${key}" in ${source} must be a list of strings`);
+    }
+    policy[key] = value;
+  }
+  return policy;
+}
+
+// The workspace's .forge/licenses.json when it has one, else the user-level policy
+async function loadPolicy(root) {
+  let text;
+  try {
+    text = await fs.readFile(path.join(root, POLICY_FILE), 'utf-8');
+  } catch (error) {
+    if (error.code !== 'ENOENT') throw ForgeError.from(error, `Could not read ${POLICY_FILE}`);
+    return { source: 'user', path: null, ...validatePolicy(store.get('policy'), 'the license policy') };
+  }
+  let raw;
+  try {
+    raw = parseJsonc(text);
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${POLICY_FILE} is not valid JSON: ${error.message}`);
+  }
+  return { source: 'workspace', path: path.join(root, POLICY_FILE), ...validatePolicy(raw, POLICY_FILE) };
+}
+
+// 'allowed', 'disallowed' or 'unknown'. An OR expression passes when any choice passes; an
+// AND expression fails when any part fails.
+function evaluate(node, rules) {
+  if (!node.op) {
+    if (['UNKNOWN', 'UNLICENSED', 'NOASSERTION', 'NONE'].includes(node.id.toUpperCase())) return 'unknown';
+    const names = node.exception ? [node.id, `${node.id} WITH ${node.exception}`] : [node.id];
+    if (rules.disallowed.some((regex) => names.some((name) => regex.test(name)))) return 'disallowed';
+    if (rules.allowed.length && !rules.allowed.some((regex) => names.some((name) => regex.test(name)))) return 'disallowed';
+    return 'allowed';
+  }
+  const results = node.terms.map((term) => evaluate(term, rules));
+  const [pass, fail] = node.op === 'or' ? ['allowed', 'disallowed'] : ['disallowed', 'allowed'];
+  if (results.includes(pass)) return pass;
+  if (results.includes('unknown')) return 'unknown';
+  return fail;
+}
+
+function classify(entry, rules) {
+  const key = `${entry.name}@${entry.version}`;
+  if (rules.ignore.some((regex) => regex.test(entry.name) || regex.test(key))) {
+    return { ...entry, license: entry.declared && formatExpression(normalizeLicense(entry.declared)), status: 'ignored', reason: 'Ignored by policy' };
+  }
+  if (!entry.declared) return { ...entry, license: null, status: 'unknown', reason: 'No license information found' };
+  const tree = normalizeLicense(entry.declared);
+  const license = formatExpression(tree);
+  const status = evaluate(tree, rules);
+  let reason = null;
+  if (status === 'unknown') reason = `License is declared as ${license}`;
+  else if (status === 'disallowed') {
+    const offending = leavesOf(tree).filter((leaf) => evaluate(leaf, rules) === 'disallowed').map((leaf) => leaf.id);
+    reason = rules.allowed.length && !rules.disallowed.some((regex) => offending.some((id) => regex.test(id)))
+      ? `${offending.join(', ')} is not on the allowed list`
+      : `${offending.join(', ')} is disallowed`;
+  }
+  return { ...entry, license, status, reason };
+}
+
+async function readJson(filePath) {
+  try {
+    return JSON.parse(await fs.readFile(filePath, 'utf-8'));
+  } catch {
+    return null;
+  }
+}
+
+// package.json "license" is a string or a legacy { type } object; "licenses" a legacy array
+async function npmLicense(pkg, dir) {
+  const license = pkg.license;
+  if (typeof license === 'string' && license.trim()) {
+    const pointer = /^SEE LICEN[CS]E IN (.+)$/i.exec(license.trim());
+    if (!pointer) return { declared: license.trim(), source: 'metadata' };
+    const fromFile = await licenseFromFiles(dir, [pointer[1].trim()]);
+    return { declared: fromFile, source: fromFile ? 'file' : null };
+  }
+  if (license && typeof license.type === 'string') return { declared: license.type, source: 'metadata' };
+  if (Array.isArray(pkg.licenses) && pkg.licenses.length) {
+    const types = pkg.licenses.map((entry) => (typeof entry === 'string' ? entry : entry && entry.type)).filter(Boolean);
+    if (types.length) return { declared: types.length > 1 ? `(${types.join(' OR ')})` : types[0], source: 'metadata' };
+  }
+  const fromFile = await licenseFromFiles(dir);
+  return { declared: fromFile, source: fromFile ? 'file' : null };
+}
+
+// Every installed package under node_modules, including nested copies, scoped packages and
+// pnpm's .pnpm store. Symlinks are skipped: they're workspace packages or point into .pnpm.
+async function scanNpm(root, handle) {
+  const top = path.join(root, 'node_modules');
+  if (!(await fs.stat(top).catch(() => null))) return null;
+  const packages = new Map();
+
+  const readPackage = async (dir) => {
+    handle.throwIfCancelled();
+    const pkg = await readJson(path.join(dir, 'package.json'));
+    if (pkg && typeof pkg.name === 'string') {
+      const key = `${pkg.name}@${pkg.version}`;
+      if (!packages.has(key)) {
+        const { declared, source } = await npmLicense(pkg, dir);
+        packages.set(key, { ecosystem: 'npm', name: pkg.name, version: String(pkg.version || ''), declared, source, path: path.relative(root, dir) });
+      }
+    }
+    await walk(path.join(dir, 'node_modules'));
+  };
+  const walk = async (dir) => {
+    const entries = await fs.readdir(dir, { withFileTypes: true }).catch(() => []);
+    for (const entry of entries) {
+      if (!entry.isDirectory() || entry.name === '.bin' || entry.name === '.cache') continue;
+      const full = path.join(dir, entry.name);
+      if (entry.name === '.pnpm') {
+        for (const stored of await fs.readdir(full, { withFileTypes: true }).catch(() => [])) {
+          if (stored.isDirectory()) await walk(path.join(full, stored.name, 'node_modules'));
+        }
+      } else if (entry.name.startsWith('@')) {
+        for (const scoped of await fs.readdir(full, { withFileTypes: true }).catch(() => [])) {
+          if (scoped.isDirectory()) await readPackage(path.join(full, scoped.name));
+        }
+      } else {
+        await readPackage(full);
+      }
+    }
+  };
+  await walk(top);
+  return Array.from(packages.values());
+}
+
+function runCargoMetadata(root, signal) {
+  return new Promise((resolve, reject) => {
+    execFile('cargo', ['metadata', '--format-version', '1'], { cwd: root, timeout: CARGO_TIMEOUT, maxBuffer: MAX_OUTPUT, signal, windowsHide: true }, (error, stdout, stderr) => {
+      if (error) {
+        const code = error.code === 'ENOENT' ? ErrorCode.NOT_FOUND : ErrorCode.IO_ERROR;
+        reject(new ForgeError(code, error.code === 'ENOENT' ? 'cargo is not installed' : (stderr || error.message).trim()));
+        return;
+      }
+      resolve(stdout);
+    });
+  });
+}
+
+async function cargoPackage(pkg, root) {
+  const dir = path.dirname(pkg.manifest_path);
+  let declared = pkg.license || null;
+  let source = declared ? 'metadata' : null;
+  if (!declared) {
+    declared = await licenseFromFiles(dir, pkg.license_file ? [pkg.license_file] : null);
+    if (declared) source = 'file';
+  }
+  return { ecosystem: 'cargo', name: pkg.name, version: pkg.version, declared, source, path: path.relative(root, dir) };
+}
+
+// Without running cargo: Cargo.lock's registry packages, with licenses read from the sources
+// cargo has already downloaded to ~/.cargo/registry/src
+async function scanCargoLock(root) {
+  let lock;
+  try {
+    lock = parseToml(await fs.readFile(path.join(root, 'Cargo.lock'), 'utf-8'));
+  } catch {
+    return [];
+  }
+  const cargoHome = process.env.CARGO_HOME || path.join(os.homedir(), '.cargo');
+  const registries = (await fs.readdir(path.join(cargoHome, 'registry', 'src')).catch(() => [])).map((name) => path.join(cargoHome, 'registry', 'src', name));
+  const results = [];
+  for (const pkg of Array.isArray(lock.package) ? lock.package : []) {
+    // Packages without a source are the workspace's own crates
+    if (!pkg.source) continue;
+    let found = null;
+    for (const registry of registries) {
+      const dir = path.join(registry, `${pkg.name}-${pkg.version}`);
+      const manifest = await fs.readFile(path.join(dir, 'Cargo.toml'), 'utf-8').catch(() => null);
+      if (manifest === null) continue;
+      let info = {};
+      try {
+        info = parseToml(manifest).package || {};
+      } catch {
+        // Fall back to the license files
+      }
+      found = await cargoPackage({ name: pkg.name, version: pkg.version, license: info.license, license_file: info['license-file'], manifest_path: path.join(dir, 'Cargo.toml') }, root);
+      break;
+    }
+    results.push(found || { ecosystem: 'cargo', name: pkg.name, version: pkg.version, declared: null, source: null, path: null });
+  }
+  return results;
+}
+
+// Dependencies (not workspace members) from `cargo metadata`. cargo only runs in trusted
+// workspaces; elsewhere, or when it isn't installed, Cargo.lock is read instead.
+async function scanCargo(root, handle, warnings) {
+  const hasManifest = await fs.stat(path.join(root, 'Cargo.toml')).catch(() => null);
+  if (!hasManifest) return null;
+  if (getRestrictions(root).trusted) {
+    try {
+      const metadata = JSON.parse(await runCargoMetadata(root, handle.signal));
+      const members = new Set(metadata.workspace_members || []);
+      const dependencies = metadata.packages.filter((pkg) => !members.has(pkg.id) && pkg.source);
+      return Promise.all(dependencies.map((pkg) => cargoPackage(pkg, root)));
+    } catch (error) {
+      handle.throwIfCancelled();
+      warnings.push({ ecosystem: 'cargo', message: `cargo metadata failed, read Cargo.lock instead: ${error.message}` });
+    }
+  } else {
+    warnings.push({ ecosystem: 'cargo', message: 'Workspace is not trusted, so cargo was not run; licenses come from Cargo.lock and downloaded crates' });
+  }
+  return scanCargoLock(root);
+}
+
+// RFC 822-style METADATA / PKG-INFO headers; repeated fields such as Classifier collect
+function parseMetadataHeaders(text) {
+  const headers = {};
+  let last = null;
+  for (const line of text.split(/\r?\n/)) {
+    if (!line) break;
+    if (/^\s/.test(line) && last) {
+      last.value += `\n${line.trim()}`;
+      continue;
+    }
+    const colon = line.indexOf(':');
+    if (colon < 0) continue;
+    const name = line.slice(0, colon).toLowerCase();
+    last = { value: line.slice(colon + 1).trim() };
+    (headers[name] = headers[name] || []).push(last);
+  }
+  return Object.fromEntries(Object.entries(headers).map(([name, values]) => [name, values.map((entry) => entry.value)]));
+}
+
+async function pythonPackage(infoDir, metadataFile, root) {
+  const text = await fs.readFile(path.join(infoDir, metadataFile), 'utf-8').catch(() => null);
+  if (text === null) return null;
+  const headers = parseMetadataHeaders(text);
+  const first = (name) => (headers[name] ? headers[name][0] : null);
+  const name = first('name');
+  if (!name) return null;
+  const entry = { ecosystem: 'python', name, version: first('version') || '', declared: null, source: null, path: path.relative(root, infoDir) };
+
+  // PEP 639 License-Expression, then a short License field (some packages paste the whole
+  // text there), then trove classifiers, then the bundled license files
+  const expression = first('license-expression');
+  const license = first('license');
+  const classifiers = (headers.classifier || [])
+    .filter((value) => value.startsWith('License ::'))
+    .map((value) => CLASSIFIERS[value.split('::').pop().trim()])
+    .filter(Boolean);
+  if (expression) Object.assign(entry, { declared: expression, source: 'metadata' });
+  else if (license && license.toUpperCase() !== 'UNKNOWN' && !license.includes('\n') && license.length <= 80) Object.assign(entry, { declared: license, source: 'metadata' });
+  else if (classifiers.length) Object.assign(entry, { declared: Array.from(new Set(classifiers)).join(' OR '), source: 'classifier' });
+  else {
+    const fromText = license && sniffLicenseText(license);
+    const fromFiles = fromText || (await licenseFromFiles(path.join(infoDir, 'licenses'), headers['license-file'] || null)) || (await licenseFromFiles(infoDir));
+    if (fromFiles) Object.assign(entry, { declared: fromFiles, source: 'file' });
+  }
+  return entry;
+}
+
+// Installed distributions in the workspace's virtual environments (folders holding a
+// pyvenv.cfg): *.dist-info/METADATA and legacy *.egg-info/PKG-INFO
+async function scanPython(root, handle) {
+  const children = await fs.readdir(root, { withFileTypes: true }).catch(() => []);
+  const environments = [];
+  for (const child of children) {
+    if (child.isDirectory() && (await fs.stat(path.join(root, child.name, 'pyvenv.cfg')).catch(() => null))) environments.push(path.join(root, child.name));
+  }
+  if (!environments.length) return null;
+  const packages = new Map();
+  for (const env of environments) {
+    const sitePackages = [path.join(env, 'Lib', 'site-packages')];
+    for (const lib of ['lib', 'lib64']) {
+      for (const version of await fs.readdir(path.join(env, lib)).catch(() => [])) {
+        if (version.startsWith('python')) sitePackages.push(path.join(env, lib, version, 'site-packages'));
+      }
+    }
+    for (const dir of sitePackages) {
+      for (const name of await fs.readdir(dir).catch(() => [])) {
+        handle.throwIfCancelled();
+        const metadataFile = name.endsWith('.dist-info') ? 'METADATA' : name.endsWith('.egg-info') ? 'PKG-INFO' : null;
+        if (!metadataFile) continue;
+        const entry = await pythonPackage(path.join(dir, name), metadataFile, root);
+        if (entry && !packages.has(`${entry.name.toLowerCase()}@${entry.version}`)) packages.set(`${entry.name.toLowerCase()}@${entry.version}`, entry);
+      }
+    }
+  }
+  return Array.from(packages.values());
+}
+
+const SCANNERS = { npm: scanNpm, cargo: scanCargo, python: scanPython };
+
+// Licenses of a workspace's installed npm, Cargo and Python dependencies, checked against
+// the license policy. Each package is 'allowed', 'disallowed', 'unknown' (nothing declared,
+// or UNLICENSED) or 'ignored'; ecosystems the workspace doesn't use report null.
+async function scanLicenses(rootPath, { ecosystems = Object.keys(SCANNERS) } = {}) {
+  if (typeof rootPath !== 'string' || !rootPath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A workspace folder is required');
+  const root = path.resolve(rootPath);
+  const stats = await fs.stat(root).catch((error) => {
+    throw ForgeError.from(error, 'Workspace folder not found');
+  });
+  if (!stats.isDirectory()) throw new ForgeError(ErrorCode.NOT_DIRECTORY, `${root} is not a folder`);
+  const unknownEcosystem = ecosystems.find((name) => !SCANNERS[name]);
+  if (unknownEcosystem) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown ecosystem "${unknownEcosystem}"; expected ${Object.keys(SCANNERS).join(', ')}`);
+  const policy = await loadPolicy(root);
+  const rules = { disallowed: compilePatterns(policy.disallowed), allowed: compilePatterns(policy.allowed), ignore: compilePatterns(policy.ignore) };
+
+  const job = runJob('licenses', `Scan licenses in ${path.basename(root)}`, async (handle) => {
+    const warnings = [];
+    const counts = {};
+    const packages = [];
+    for (const [index, name] of ecosystems.entries()) {
+      handle.reportProgress(index / ecosystems.length, `Reading ${name} packages`);
+      const found = await SCANNERS[name](root, handle, warnings);
+      counts[name] = found ? found.length : null;
+      if (found) packages.push(...found.map((entry) => classify(entry, rules)));
+    }
+    const order = { disallowed: 0, unknown: 1, allowed: 2, ignored: 3 };
+    packages.sort((a, b) => order[a.status] - order[b.status] || a.ecosystem.localeCompare(b.ecosystem) || a.name.localeCompare(b.name));
+
+    const byLicense = new Map();
+    const summary = { total: packages.length, allowed: 0, disallowed: 0, unknown: 0, ignored: 0 };
+    for (const entry of packages) {
+      summary[entry.status]++;
+      const key = entry.license || 'Unknown';
+      byLicense.set(key, (byLicense.get(key) || 0) + 1);
+    }
+    log.info(`Scanned ${packages.length} packages in ${root}: ${summary.disallowed} disallowed, ${summary.unknown} unknown`);
+    return {
+      rootPath: root,
+      policy: { source: policy.source, path: policy.path },
+      ecosystems: counts,
+      summary,
+      licenses: Array.from(byLicense, ([license, count]) => ({ license, count })).sort((a, b) => b.count - a.count || a.license.localeCompare(b.license)),
+      packages,
+      warnings,
+      scannedAt: new Date().toISOString()
+    };
+  });
+  return job.promise;
+}
+
+function setupLicenseHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('licenses:scan', async (event, rootPath, options) => scanLicenses(rootPath, options || {}));
+  // The policy in effect for a workspace (its .forge/licenses.json or the user policy)
+  ipcMain.handle('licenses:getPolicy', async (event, rootPath) => {
+    if (typeof rootPath !== 'string' || !rootPath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A workspace folder is required');
+    return loadPolicy(path.resolve(rootPath));
+  });
+  ipcMain.handle('licenses:setUserPolicy', async (event, policy) => {
+    const validated = validatePolicy(policy, 'the license policy');
+    store.set('policy', validated);
+    return validated;
+  });
+}
+
+module.exports = { setupLicenseHandlers, scanLicenses, normalizeLicense, formatExpression };
@@ -0,0 +1,268 @@
+const http = require('http');
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { v4: uuidv4 } = require('uuid');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { acceptWebSocket, rejectUpgrade, tokensMatch, lanAddress } = require('./websocket');
+const { terminalEvents, getTerminal, getScreenOutput } = require('./terminal');
+const { windowManager } = require('./windows');
+
+const log = createLogger('live-share');
+
+const MAX_VIEWERS = 50;
+const MAX_SHARED_FILES = 20;
+const MAX_FILE_SIZE = 1024 * 1024;
+
+// Active sessions by id: { id, token, server, terminalId, files, viewers, owner, url }
+const sessions = new Map();
+
+// Self-contained viewer page; the token travels in the page's own query string
+const VIEWER_HTML = `<!doctype html>
+<html><head><meta charset="utf-8"><title>Forge Live Share</title>
+<style>
+body { margin: 0; font: 13px/1.4 ui-monospace, Menlo, Consolas, monospace; background: #1e1e1e; color: #d4d4d4; display: flex; flex-direction: column; height: 100vh; }
+header { padding: 6px 10px; background: #252526; display: flex; gap: 8px; align-items: center; }
+header button { background: #333; color: inherit; border: 0; padding: 3px 8px; cursor: pointer; }
+header button.active { background: #0e639c; }
+#status { margin-left: auto; opacity: .7; }
+pre { flex: 1; margin: 0; padding: 10px; overflow: auto; white-space: pre-wrap; }
+</style></head>
+<body><header id="tabs"><span id="status">Connecting…</span></header><pre id="view"></pre>
+<script>
+const ANSI = /\\x1b\\[[0-?]*[ -\\/]*[@-~]|\\x1b\\][^\\x07\\x1b]*(\\x07|\\x1b\\\\)|\\x1b[@-Z\\\\-_]/g;
+const views = { terminal: '' };
+let current = 'terminal';
+const view = document.getElementById('view');
+const tabs = document.getElementById('tabs');
+const status = document.getElementById('status');
+function render() {
+  const atBottom = view.scrollTop + view.clientHeight >= view.scrollHeight - 4;
+  view.textContent = views[current] || '';
+  if (current === 'terminal' && atBottom) view.scrollTop = view.scrollHeight;
+  for (const button of tabs.querySelectorAll('button')) button.classList.toggle('active', button.dataset.view === current);
+}
+function addTab(name, label) {
+  if (tabs.querySelector('[data-view="' + CSS.escape(name) + '"]')) return;
+  const button = document.createElement('button');
+  button.dataset.view = name;
+  button.textContent = label;
+  button.onclick = () => { current = name; render(); };
+  tabs.insertBefore(button, status);
+}
+const token = new URLSearchParams(location.search).get('token') || '';
+const socket = new WebSocket((location.protocol === 'https:' ? 'wss://' : 'ws://') + location.host + '/stream?token=' + encodeURIComponent(token));
+socket.onopen = () => { status.textContent = 'Live (read-only)'; };
+socket.onclose = () => { status.textContent = 'Session ended'; };
+socket.onmessage = (event) => {
+  const message = JSON.parse(event.data);
+  if (message.type === 'hello') {
+    if (message.terminal) addTab('terminal', message.terminal.title);
+    render();
+  } else if (message.type === 'output') {
+    views.terminal = (views.terminal + message.data.replace(ANSI, '').replace(/\\r\\n/g, '\\n').replace(/\\r/g, '')).slice(-500000);
+    if (current === 'terminal') render();
+  } else if (message.type === 'file') {
+    views['file:' + message.path] = message.content;
+    addTab('file:' + message.path, message.path);
+    if (current === 'file:' + message.path) render();
+  }
+};
+</script></body></html>`;
+
+function broadcast(session, message) {
+  const data = JSON.stringify(message);
+  for (const viewer of session.viewers) viewer.send(data);
+}
+
+function notifyViewers(session) {
+  emit('liveShare:viewers', { sessionId: session.id, viewers: session.viewers.size }, { target: session.owner });
+}
+
+// Label shown to viewers: relative to the sharing window's workspace when inside it
+function displayPath(filePath, workspacePath) {
+  if (workspacePath) {
+    const relative = path.relative(workspacePath, filePath);
+    if (relative && !relative.startsWith('..') && !path.isAbsolute(relative)) return relative.split(path.sep).join('/');
+  }
+  return path.basename(filePath);
+}
+
+async function loadSharedFiles(filePaths, workspacePath) {
+  if (!Array.isArray(filePaths)) return new Map();
+  if (filePaths.length > MAX_SHARED_FILES) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `At most ${MAX_SHARED_FILES} files can be shared`);
+  }
+  const files = new Map();
+  for (const filePath of filePaths) {
+    if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Shared files must be absolute paths');
+    }
+    const stats = await fs.stat(filePath);
+    const content = stats.size > MAX_FILE_SIZE ? '(file too large to share)' : await fs.readFile(filePath, 'utf-8');
+    files.set(path.resolve(filePath), { label: displayPath(filePath, workspacePath), content });
+  }
+  return files;
+}
+
+function handleViewer(session, request, socket, head) {
+  const url = new URL(request.url, 'http://localhost');
+  if (url.pathname !== '/stream' || !tokensMatch(session.token, url.searchParams.get('token'))) {
+    rejectUpgrade(socket, 403, 'Forbidden');
+    return;
+  }
+  if (session.viewers.size >= MAX_VIEWERS) {
+    rejectUpgrade(socket, 503, 'Too Many Viewers');
+    return;
+  }
+  const viewer = acceptWebSocket(request, socket, head);
+  if (!viewer) return;
+
+  session.viewers.add(viewer);
+  log.info(`Viewer joined live share ${session.id} (${session.viewers.size} watching)`);
+  const terminal = session.terminalId ? getTerminal(session.terminalId) : null;
+  viewer.send(JSON.stringify({
+    type: 'hello',
+    terminal: terminal ? { title: path.basename(terminal.shell), cwd: terminal.cwd } : null,
+    files: Array.from(session.files.values()).map((file) => file.label)
+  }));
+  if (terminal) viewer.send(JSON.stringify({ type: 'output', data: getScreenOutput(terminal) }));
+  for (const file of session.files.values()) {
+    viewer.send(JSON.stringify({ type: 'file', path: file.label, content: file.content }));
+  }
+  // Read-only: anything viewers send is ignored
+  viewer.on('close', () => {
+    session.viewers.delete(viewer);
+    if (sessions.has(session.id)) notifyViewers(session);
+  });
+  notifyViewers(session);
+}
+
+function handleRequest(session, request, response) {
+  const url = new URL(request.url, 'http://localhost');
+  if (request.method !== 'GET' || url.pathname !== '/' || !tokensMatch(session.token, url.searchParams.get('token'))) {
+    response.writeHead(403, { 'Content-Type': 'text/plain' });
+    response.end('Forbidden');
+    return;
+  }
+  response.writeHead(200, {
+    'Content-Type': 'text/html; charset=utf-8',
+    'Cache-Control': 'no-store',
+    'Referrer-Policy': 'no-referrer',
+    'Content-Security-Policy': "default-src 'none'; script-src 'unsafe-inline'; style-src 'unsafe-inline'; connect-src 'self'"
+  });
+  response.end(VIEWER_HTML);
+}
+
+// Start serving a read-only view of a terminal and/or files. `lan` listens on all
+// interfaces instead of loopback only; the token is required either way.
+async function startLiveShare({ terminalId, files, lan = false, port = 0 }, { owner, workspacePath }) {
+  if (!terminalId && !(Array.isArray(files) && files.length)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Share a terminal, files, or both');
+  }
+  if (terminalId && !getTerminal(terminalId)) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { id: terminalId } });
+  }
+  const session = {
+    id: `share_${uuidv4()}`,
+    token: crypto.randomBytes(24).toString('base64url'),
+    terminalId: terminalId || null,
+    files: await loadSharedFiles(files, workspacePath),
+    viewers: new Set(),
+    owner,
+    server: null,
+    url: null
+  };
+
+  session.server = http.createServer((request, response) => handleRequest(session, request, response));
+  session.server.on('upgrade', (request, socket, head) => handleViewer(session, request, socket, head));
+  const host = lan ? '0.0.0.0' : '127.0.0.1';
+  await new Promise((resolve, reject) => {
+    session.server.once('error', (error) => reject(ForgeError.from(error, 'Could not start live share server')));
+    session.server.listen(port, host, resolve);
+  });
+  const address = `${lan ? lanAddress() : '127.0.0.1'}:${session.server.address().port}`;
+  session.url = `http://${address}/?token=${session.token}`;
+  sessions.set(session.id, session);
+  log.info(`Live share ${session.id} started on ${host}:${session.server.address().port}`);
+  return describe(session);
+}
+
+function stopLiveShare(id, reason = 'stopped') {
+  const session = sessions.get(id);
+  if (!session) return false;
+  sessions.delete(id);
+  for (const viewer of session.viewers) viewer.close(1001, 'Session ended');
+  session.server.close();
+  log.info(`Live share ${id} ended (${reason})`);
+  emit('liveShare:ended', { sessionId: id, reason }, { target: session.owner });
+  return true;
+}
+
+function describe(session) {
+  return {
+    sessionId: session.id,
+    url: session.url,
+    terminalId: session.terminalId,
+    files: Array.from(session.files.values()).map((file) => file.label),
+    viewers: session.viewers.size
+  };
+}
+
+terminalEvents.on('data', (id, data) => {
+  for (const session of sessions.values()) {
+    if (session.terminalId === id && session.viewers.size > 0) broadcast(session, { type: 'output', data });
+  }
+});
+
+terminalEvents.on('exit', (id) => {
+  for (const session of Array.from(sessions.values())) {
+    if (session.terminalId !== id) continue;
+    // A share that still has files stays up without the terminal
+    if (session.files.size > 0) {
+      session.terminalId = null;
+    } else {
+      stopLiveShare(session.id, 'terminal exited');
+    }
+  }
+});
+
+function setupLiveShareHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('liveShare:start', async (event, options = {}) => {
+    const context = windowManager.fromSender(event.sender);
+    const result = await startLiveShare(options, { owner: event.sender, workspacePath: context ? context.workspacePath : null });
+    // Shares end with the window that started them
+    const win = windowManager.windowFromSender(event.sender);
+    if (win) win.once('closed', () => stopLiveShare(result.sessionId, 'window closed'));
+    return result;
+  });
+
+  ipcMain.handle('liveShare:stop', async (event, sessionId) => stopLiveShare(sessionId));
+
+  ipcMain.handle('liveShare:list', async (event) => {
+    return Array.from(sessions.values()).filter((session) => session.owner === event.sender).map(describe);
+  });
+
+  // Push the current (possibly unsaved) content of a shared file to viewers
+  ipcMain.handle('liveShare:updateFile', async (event, sessionId, filePath, content) => {
+    const session = sessions.get(sessionId);
+    const file = session && session.files.get(path.resolve(String(filePath)));
+    if (!file) return false;
+    if (typeof content !== 'string' || content.length > MAX_FILE_SIZE) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Shared file content must be a string under 1 MB');
+    }
+    file.content = content;
+    broadcast(session, { type: 'file', path: file.label, content });
+    return true;
+  });
+
+  process.on('exit', () => {
+    for (const id of Array.from(sessions.keys())) stopLiveShare(id, 'quit');
+  });
+}
+
+module.exports = { setupLiveShareHandlers, startLiveShare, stopLiveShare };
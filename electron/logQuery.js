@@ -0,0 +1,442 @@
+// JSON-lines log records: parsing, field lookup, schema inference over a sample, and the
+// log viewer's query language.
+//
+//   level=error status>=500          terms are ANDed; `AND` may be written out
+//   service=api OR service=worker    OR binds looser than AND; parentheses group
+//   -user.id=42  NOT path~^/health   negation
+//   duration=100..250  ts>now-15m    inclusive ranges (either end may be left open),
+//                                    numbers, ISO timestamps and now-relative times
+//   msg~"timed? ?out"  host=web-*    regex (case-insensitive) and `*` wildcards
+//   trace_id=*  "connection reset"   field present; bare words and phrases search the line
+//   @level>=warn                     the line's detected level, on JSON and plain lines alike
+//
+// `field:value` is accepted for `field=value`. Fields are dotted paths into the record; a
+// key that itself contains dots ("http.status") is found too, and a path through an
+// array matches if any element does.
+
+class QuerySyntaxError extends Error {
+  constructor(message, position) {
+    super(message);
+    this.position = position;
+  }
+}
+
+const OPERATORS = ['!=', '>=', '<=', '=', ':', '>', '<', '~'];
+const MAX_DEPTH = 6;
+const MAX_SCHEMA_FIELDS = 200;
+const MAX_EXAMPLES = 5;
+const MAX_EXAMPLE_LENGTH = 80;
+const ISO_DATE = /^\d{4}-\d{2}-\d{2}(?:[T ]\d{2}:\d{2}(?::\d{2}(?:\.\d+)?)?(?:Z|[+-]\d{2}:?\d{2})?)?$/;
+const RELATIVE_TIME = /^now(?:([+-])(\d+)(ms|s|m|h|d|w))?$/;
+const UNIT_MS = { ms: 1, s: 1000, m: 60 * 1000, h: 60 * 60 * 1000, d: 24 * 60 * 60 * 1000, w: 7 * 24 * 60 * 60 * 1000 };
+
+// The record for a line that is a JSON object, null for anything else
+function parseRecord(text) {
+  if (text.charCodeAt(0) !== 123 || text.charCodeAt(text.length - 1) !== 125) return null;
+  try {
+    const value = JSON.parse(text);
+    return value && typeof value === 'object' && !Array.isArray(value) ? value : null;
+  } catch {
+    return null;
+  }
+}
+
+function lookup(value, parts, index) {
+  if (index === parts.length) return value;
+  if (value === null || typeof value !== 'object') return undefined;
+  if (Array.isArray(value) && !/^\d+$/.test(parts[index])) {
+    const found = value.map((item) => lookup(item, parts, index)).filter((item) => item !== undefined).flat();
+    return found.length ? found : undefined;
+  }
+  // Longest key first, so {"http.status": 500} and {"http": {"status": 500}} both answer http.status
+  for (let end = parts.length; end > index; end--) {
+    const key = parts.slice(index, end).join('.');
+    if (Object.prototype.hasOwnProperty.call(value, key)) {
+      const found = lookup(value[key], parts, end);
+      if (found !== undefined) return found;
+    }
+  }
+  return undefined;
+}
+
+function getField(record, path) {
+  if (!record) return undefined;
+  if (Object.prototype.hasOwnProperty.call(record, path)) return record[path];
+  return lookup(record, path.split('.'), 0);
+}
+
+function extractFields(record, paths) {
+  const fields = {};
+  for (const path of paths) {
+    const value = getField(record, path);
+    if (value !== undefined) fields[path] = value;
+  }
+  return fields;
+}
+
+// ---- Schema inference ----
+
+function typeOf(value) {
+  if (value === null) return 'null';
+  if (Array.isArray(value)) return 'array';
+  return typeof value;
+}
+
+function exampleOf(value) {
+  const text = typeof value === 'string' ? value : JSON.stringify(value);
+  return text.length > MAX_EXAMPLE_LENGTH ? `${text.slice(0, MAX_EXAMPLE_LENGTH)}…` : text;
+}
+
+function visitLeaves(value, prefix, depth, visit) {
+  for (const [key, item] of Object.entries(value)) {
+    const path = prefix ? `${prefix}.${key}` : key;
+    if (item && typeof item === 'object' && !Array.isArray(item) && depth < MAX_DEPTH && Object.keys(item).length) {
+      visitLeaves(item, path, depth + 1, visit);
+    } else {
+      visit(path, item);
+    }
+  }
+}
+
+// Leaf fields across `records`, most common first: how many records have each, its types
+// by frequency, a few distinct examples, the numeric range and whether every string value
+// looks like a timestamp
+function inferSchema(records) {
+  const fields = new Map();
+  let truncated = false;
+  for (const record of records) {
+    visitLeaves(record, '', 0, (path, value) => {
+      let field = fields.get(path);
+      if (!field) {
+        if (fields.size >= MAX_SCHEMA_FIELDS) {
+          truncated = true;
+          return;
+        }
+        field = { path, count: 0, types: {}, examples: new Set(), min: null, max: null, timestamps: 0, strings: 0 };
+        fields.set(path, field);
+      }
+      const type = typeOf(value);
+      field.count++;
+      field.types[type] = (field.types[type] || 0) + 1;
+      if (field.examples.size < MAX_EXAMPLES && type !== 'null') field.examples.add(exampleOf(value));
+      if (type === 'number') {
+        if (field.min === null || value < field.min) field.min = value;
+        if (field.max === null || value > field.max) field.max = value;
+      } else if (type === 'string') {
+        field.strings++;
+        if (ISO_DATE.test(value) && !Number.isNaN(Date.parse(value))) field.timestamps++;
+      }
+    });
+  }
+  const summary = Array.from(fields.values())
+    .sort((a, b) => b.count - a.count || a.path.localeCompare(b.path))
+    .map((field) => ({
+      path: field.path,
+      count: field.count,
+      types: Object.keys(field.types).sort((a, b) => field.types[b] - field.types[a]),
+      examples: Array.from(field.examples),
+      ...(field.min !== null ? { min: field.min, max: field.max } : {}),
+      timestamp: field.strings > 0 && field.timestamps === field.strings
+    }));
+  return { fields: summary, truncated };
+}
+
+// ---- Query language ----
+
+function tokenize(text) {
+  const tokens = [];
+  let pos = 0;
+  const readString = () => {
+    const start = pos;
+    let value = '';
+    pos++;
+    while (pos < text.length && text[pos] !== '"') {
+      if (text[pos] === '\\' && pos + 1 < text.length) pos++;
+      value += text[pos++];
+    }
+    if (pos >= text.length) throw new QuerySyntaxError('Unterminated string', start);
+    pos++;
+    return value;
+  };
+  // A value runs to whitespace or a closing parenthesis; `a..b` splits into a range
+  const readValue = () => {
+    const start = pos;
+    const parts = [];
+    let quoted = false;
+    let current = '';
+    while (pos < text.length && !/[\s)]/.test(text[pos])) {
+      if (text[pos] === '"') {
+        current += readString();
+        quoted = true;
+      } else if (text.startsWith('..', pos)) {
+        parts.push(current);
+        current = '';
+        pos += 2;
+      } else {
+        current += text[pos++];
+      }
+    }
+    parts.push(current);
+    if (parts.length > 2) throw new QuerySyntaxError('A range has one `..`', start);
+    if (parts.length === 2) return { kind: 'range', from: parts[0], to: parts[1], start };
+    if (!current && !quoted) throw new QuerySyntaxError('Expected a value', start);
+    return { kind: 'value', value: current, quoted, start };
+  };
+
+  while (pos < text.length) {
+    const ch = text[pos];
+    if (/\s/.test(ch)) {
+      pos++;
+    } else if (ch === '(' || ch === ')') {
+      tokens.push({ kind: ch, start: pos++ });
+    } else if (ch === '-' && pos + 1 < text.length && !/[\s)]/.test(text[pos + 1])) {
+      tokens.push({ kind: 'NOT', start: pos++ });
+    } else {
+      const start = pos;
+      let word;
+      let quoted = false;
+      if (ch === '"') {
+        word = readString();
+        quoted = true;
+      } else {
+        word = '';
+        while (pos < text.length && !/[\s()"]/.test(text[pos]) && !OPERATORS.some((op) => text.startsWith(op, pos))) word += text[pos++];
+      }
+      const op = OPERATORS.find((candidate) => text.startsWith(candidate, pos));
+      if (op && word) {
+        pos += op.length;
+        tokens.push({ kind: 'field', path: word, op: op === ':' ? '=' : op, value: readValue(), start });
+      } else if (op) {
+        throw new QuerySyntaxError(`Expected a field name before ${op}`, pos);
+      } else if (!quoted && (word === 'AND' || word === 'OR' || word === 'NOT')) {
+        tokens.push({ kind: word, start });
+      } else {
+        tokens.push({ kind: 'text', value: word, start });
+      }
+    }
+  }
+  return tokens;
+}
+
+function parseQuery(text) {
+  const tokens = tokenize(text);
+  let index = 0;
+  const peek = () => tokens[index];
+
+  function parseOr() {
+    const items = [parseAnd()];
+    while (peek() && peek().kind === 'OR') {
+      index++;
+      items.push(parseAnd());
+    }
+    return items.length === 1 ? items[0] : { type: 'or', items };
+  }
+  function parseAnd() {
+    const items = [parseUnary()];
+    while (peek() && peek().kind !== 'OR' && peek().kind !== ')') {
+      if (peek().kind === 'AND') index++;
+      items.push(parseUnary());
+    }
+    return items.length === 1 ? items[0] : { type: 'and', items };
+  }
+  function parseUnary() {
+    const token = tokens[index++];
+    if (!token) throw new QuerySyntaxError('Unexpected end of query', text.length);
+    if (token.kind === 'NOT') return { type: 'not', item: parseUnary() };
+    if (token.kind === '(') {
+      const inner = parseOr();
+      if (!peek() || peek().kind !== ')') throw new QuerySyntaxError('Expected )', peek() ? peek().start : text.length);
+      index++;
+      return inner;
+    }
+    if (token.kind === 'text') return { type: 'text', value: token.value };
+    if (token.kind === 'field') return { type: 'field', path: token.path, op: token.op, value: token.value, start: token.start };
+    throw new QuerySyntaxError(`Unexpected ${token.kind}`, token.start);
+  }
+
+  if (!tokens.length) return null;
+  const ast = parseOr();
+  if (index < tokens.length) throw new QuerySyntaxError(`Unexpected ${tokens[index].kind}`, tokens[index].start);
+  return ast;
+}
+
+// Bounds are numbers, timestamps (as epoch ms) or plain strings
+function parseBound(raw, quoted) {
+  if (!quoted && raw !== '' && Number.isFinite(Number(raw))) return { kind: 'number', value: Number(raw) };
+  const relative = !quoted && RELATIVE_TIME.exec(raw);
+  if (relative) {
+    const offset = relative[1] ? Number(relative[2]) * UNIT_MS[relative[3]] * (relative[1] === '-' ? -1 : 1) : 0;
+    return { kind: 'time', value: Date.now() + offset };
+  }
+  if (ISO_DATE.test(raw) && !Number.isNaN(Date.parse(raw))) return { kind: 'time', value: Date.parse(raw) };
+  return { kind: 'string', value: raw };
+}
+
+// Numbers that look like epoch seconds are scaled, so ts>2026-01-01 works on either unit
+function toTime(value) {
+  if (typeof value === 'number') return value < 1e11 ? value * 1000 : value;
+  if (typeof value === 'string' && ISO_DATE.test(value)) {
+    const time = Date.parse(value);
+    return Number.isNaN(time) ? null : time;
+  }
+  return null;
+}
+
+// <0, 0, >0 like a comparator, or null when the value can't be compared with the bound
+function compare(value, bound) {
+  if (bound.kind === 'number') {
+    const number = typeof value === 'number' ? value : typeof value === 'string' && value.trim() !== '' ? Number(value) : NaN;
+    return Number.isFinite(number) ? number - bound.value : null;
+  }
+  if (bound.kind === 'time') {
+    const time = toTime(value);
+    return time === null ? null : time - bound.value;
+  }
+  if (typeof value !== 'string' && typeof value !== 'number') return null;
+  const text = String(value);
+  return text < bound.value ? -1 : text > bound.value ? 1 : 0;
+}
+
+function globToRegExp(glob) {
+  return new RegExp(`^${glob.split('*').map((part) => part.replace(/[.*+?^${}()|[\]\\]/g, '\\$&')).join('.*')}$`, 'i');
+}
+
+function scalarText(value) {
+  return value === null ? 'null' : typeof value === 'object' ? null : String(value);
+}
+
+// @level compares by severity, so `@level>=warn` and `@level=warn..error` read naturally
+function compileLevel(node, levels) {
+  const { op, value } = node;
+  const rank = (name, start) => {
+    const index = levels.indexOf(name.toLowerCase());
+    if (index === -1) throw new QuerySyntaxError(`Unknown level ${name}; expected one of ${levels.join(', ')}`, start);
+    return index;
+  };
+  if (op === '~') throw new QuerySyntaxError('@level takes a level name, not a regex', node.start);
+  let low = 0;
+  let high = levels.length - 1;
+  if (value.kind === 'range') {
+    if (op !== '=') throw new QuerySyntaxError(`A range needs =, not ${op}`, node.start);
+    if (value.from) low = rank(value.from, value.start);
+    if (value.to) high = rank(value.to, value.start);
+  } else if (op === '=' || op === '!=') {
+    if (value.value !== '*') low = high = rank(value.value, value.start);
+  } else {
+    const index = rank(value.value, value.start);
+    if (op === '>') low = index + 1;
+    else if (op === '>=') low = index;
+    else if (op === '<') high = index - 1;
+    else high = index;
+  }
+  const matches = (level) => {
+    const index = level ? levels.indexOf(level) : -1;
+    return index !== -1 && index >= low && index <= high;
+  };
+  return op === '!=' ? (level) => !matches(level) : matches;
+}
+
+function compileField(node) {
+  const { path, op, value } = node;
+  let test;
+  if (value.kind === 'range') {
+    if (op !== '=') throw new QuerySyntaxError(`A range needs =, not ${op}`, node.start);
+    const from = value.from === '' ? null : parseBound(value.from, false);
+    const to = value.to === '' ? null : parseBound(value.to, false);
+    if (!from && !to) throw new QuerySyntaxError('A range needs at least one end', value.start);
+    test = (item) => {
+      const low = from ? compare(item, from) : 0;
+      const high = to ? compare(item, to) : 0;
+      return low !== null && high !== null && low >= 0 && high <= 0;
+    };
+  } else if (op === '~') {
+    let regex;
+    try {
+      regex = new RegExp(value.value, 'i');
+    } catch (error) {
+      throw new QuerySyntaxError(`Invalid regex for ${path}: ${error.message}`, value.start);
+    }
+    test = (item) => {
+      const text = scalarText(item);
+      return text !== null && regex.test(text);
+    };
+  } else if (op === '=' || op === '!=') {
+    if (!value.quoted && value.value === '*') {
+      test = (item) => item !== null;
+    } else if (!value.quoted && value.value.includes('*')) {
+      const glob = globToRegExp(value.value);
+      test = (item) => {
+        const text = scalarText(item);
+        return text !== null && glob.test(text);
+      };
+    } else {
+      const bound = parseBound(value.value, value.quoted);
+      const expected = value.value.toLowerCase();
+      test = (item) => {
+        if (bound.kind !== 'string' && typeof item !== 'boolean') {
+          const order = compare(item, bound);
+          if (order !== null) return order === 0;
+        }
+        const text = scalarText(item);
+        return text !== null && text.toLowerCase() === expected;
+      };
+    }
+  } else {
+    const bound = parseBound(value.value, value.quoted);
+    const accept = { '>': (order) => order > 0, '>=': (order) => order >= 0, '<': (order) => order < 0, '<=': (order) => order <= 0 }[op];
+    test = (item) => {
+      const order = compare(item, bound);
+      return order !== null && accept(order);
+    };
+  }
+  const matches = (record) => {
+    const found = getField(record, path);
+    if (found === undefined) return false;
+    return Array.isArray(found) ? found.some(test) : test(found);
+  };
+  return op === '!=' ? (record) => !matches(record) : matches;
+}
+
+function compileNode(node, levels) {
+  switch (node.type) {
+    case 'and': {
+      const items = node.items.map((item) => compileNode(item, levels));
+      return (line) => items.every((item) => item(line));
+    }
+    case 'or': {
+      const items = node.items.map((item) => compileNode(item, levels));
+      return (line) => items.some((item) => item(line));
+    }
+    case 'not': {
+      const item = compileNode(node.item, levels);
+      return (line) => !item(line);
+    }
+    case 'text': {
+      const needle = node.value.toLowerCase();
+      return (line) => line.lower().includes(needle);
+    }
+    default: {
+      if (node.path === '@level') {
+        const level = compileLevel(node, levels);
+        return (line) => level(line.level);
+      }
+      const field = compileField(node);
+      return (line) => field(line.record);
+    }
+  }
+}
+
+// A predicate `(record, text, level) => boolean`, or null for an empty query. `levels` are
+// the level names in order of severity. Field terms never match a line that isn't a JSON
+// record.
+function compileQuery(text, { levels = [] } = {}) {
+  const ast = parseQuery(text);
+  if (!ast) return null;
+  const predicate = compileNode(ast, levels);
+  return (record, line, level) => {
+    let lower = null;
+    return predicate({ record, level, lower: () => (lower === null ? (lower = line.toLowerCase()) : lower) });
+  };
+}
+
+module.exports = { QuerySyntaxError, parseRecord, getField, extractFields, inferSchema, parseQuery, compileQuery };
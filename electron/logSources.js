@@ -0,0 +1,336 @@
+const fs = require('fs').promises;
+const path = require('path');
+const readline = require('readline');
+const { spawn } = require('child_process');
+const { constants: fsConstants } = require('fs');
+const { ForgeError, ErrorCode } = require('./errors');
+
+// Platform log sources for the log viewer. Each adapter runs the platform's own tool in a
+// streaming JSON mode and turns its entries into { text, level, record }: `text` is the
+// line shown, `record` what queries and columns see (normalized time, level and message
+// plus the tool's native fields).
+
+const DEFAULT_HISTORY = 200;
+const MAX_HISTORY = 5000;
+const STDERR_TAIL = 4000;
+const LEVELS = ['trace', 'debug', 'info', 'warn', 'error', 'fatal'];
+// syslog priorities 0-7
+const SYSLOG_LEVELS = ['fatal', 'fatal', 'fatal', 'error', 'warn', 'info', 'info', 'debug'];
+const MACOS_LEVELS = { Fault: 'fatal', Error: 'error', Default: 'info', Info: 'info', Debug: 'debug' };
+// Windows event levels: 0 LogAlways, 1 Critical, 2 Error, 3 Warning, 4 Information, 5 Verbose
+const WINDOWS_LEVELS = ['info', 'fatal', 'error', 'warn', 'info', 'debug'];
+
+function stringList(value, what) {
+  if (value === undefined || value === null) return [];
+  const list = Array.isArray(value) ? value : [value];
+  for (const item of list) {
+    if (typeof item !== 'string' || !item.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${what} must be non-empty strings`);
+  }
+  return list.map((item) => item.trim());
+}
+
+function historyCount(value) {
+  if (value === undefined) return DEFAULT_HISTORY;
+  const count = Math.floor(Number(value));
+  if (!Number.isFinite(count) || count < 0) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'lines must be zero or a positive number');
+  return Math.min(count, MAX_HISTORY);
+}
+
+function minimumLevel(value) {
+  if (value === undefined || value === null) return null;
+  if (!LEVELS.includes(value)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown log level ${value}; expected one of ${LEVELS.join(', ')}`);
+  return LEVELS.indexOf(value);
+}
+
+function plainRecord(raw, omit) {
+  const record = {};
+  for (const [key, value] of Object.entries(raw)) {
+    if (!omit.includes(key)) record[key] = value;
+  }
+  return record;
+}
+
+// ---- journald ----
+
+// MESSAGE is a string, or an array of bytes when it isn't valid UTF-8 or has control characters
+function journalMessage(value) {
+  if (Array.isArray(value)) return Buffer.from(value).toString('utf-8');
+  return typeof value === 'string' ? value : '';
+}
+
+const journald = {
+  label: 'systemd journal',
+  description: 'journalctl --follow, optionally narrowed to units, identifiers or a priority',
+  platforms: ['linux'],
+  command: 'journalctl',
+  // { units, identifiers, level, since, lines, boot, user }
+  args(params) {
+    const args = ['--output=json', '--follow', '--no-pager', '--quiet'];
+    if (params.since !== undefined) {
+      if (typeof params.since !== 'string' || !params.since.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'since must be a journalctl time such as "-1h" or "2026-01-01 10:00"');
+      args.push(`--since=${params.since.trim()}`);
+    } else {
+      args.push(`--lines=${historyCount(params.lines)}`);
+    }
+    for (const unit of stringList(params.units, 'units')) args.push(`--unit=${unit}`);
+    for (const identifier of stringList(params.identifiers, 'identifiers')) args.push(`--identifier=${identifier}`);
+    const level = minimumLevel(params.level);
+    if (level !== null) {
+      // --priority=N keeps N and everything more severe
+      let priority = SYSLOG_LEVELS.length - 1;
+      while (priority > 0 && LEVELS.indexOf(SYSLOG_LEVELS[priority]) < level) priority--;
+      args.push(`--priority=${priority}`);
+    }
+    if (params.boot) args.push('--boot');
+    if (params.user) args.push('--user');
+    return args;
+  },
+  normalize(raw) {
+    const message = journalMessage(raw.MESSAGE);
+    const priority = Number(raw.PRIORITY);
+    const micros = Number(raw.__REALTIME_TIMESTAMP);
+    const time = Number.isFinite(micros) ? new Date(micros / 1000).toISOString() : null;
+    const identifier = raw.SYSLOG_IDENTIFIER || raw._COMM || '';
+    const pid = raw._PID || raw.SYSLOG_PID || null;
+    const level = Number.isInteger(priority) && SYSLOG_LEVELS[priority] ? SYSLOG_LEVELS[priority] : null;
+    return {
+      text: [time, raw._HOSTNAME, `${identifier}${pid ? `[${pid}]` : ''}:`, message].filter(Boolean).join(' '),
+      level,
+      record: {
+        time,
+        level,
+        message,
+        unit: raw._SYSTEMD_UNIT || raw._SYSTEMD_USER_UNIT || null,
+        identifier,
+        pid: pid ? Number(pid) : null,
+        host: raw._HOSTNAME || null,
+        ...plainRecord(raw, ['MESSAGE', '__CURSOR']),
+        MESSAGE: message
+      }
+    };
+  }
+};
+
+// ---- macOS unified log ----
+
+// "2026-10-16 10:00:00.123456-0700" -> ISO 8601 with milliseconds
+function macosTime(value) {
+  const match = /^(\d{4}-\d{2}-\d{2}) (\d{2}:\d{2}:\d{2})(?:\.(\d+))?([+-]\d{2})(\d{2})$/.exec(value || '');
+  if (!match) return null;
+  const time = Date.parse(`${match[1]}T${match[2]}.${(match[3] || '0').padEnd(3, '0').slice(0, 3)}${match[4]}:${match[5]}`);
+  return Number.isNaN(time) ? null : new Date(time).toISOString();
+}
+
+const macos = {
+  label: 'macOS unified log',
+  description: 'log stream, optionally narrowed by a predicate or process',
+  platforms: ['darwin'],
+  command: 'log',
+  // { predicate, process, level }
+  args(params) {
+    const args = ['stream', '--style', 'ndjson'];
+    // log stream only goes down to a verbosity; levels above info are filtered as entries arrive
+    const level = minimumLevel(params.level);
+    let verbosity = 'default';
+    if (level !== null && level <= LEVELS.indexOf('debug')) verbosity = 'debug';
+    else if (level === LEVELS.indexOf('info')) verbosity = 'info';
+    args.push('--level', verbosity);
+    if (params.predicate !== undefined) {
+      if (typeof params.predicate !== 'string' || !params.predicate.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'predicate must be an NSPredicate string');
+      args.push('--predicate', params.predicate);
+    }
+    if (params.process !== undefined) {
+      if (typeof params.process !== 'string' || !params.process.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'process must be a name or pid');
+      args.push('--process', params.process.trim());
+    }
+    return args;
+  },
+  normalize(raw) {
+    // The stream ends with a summary object rather than an entry
+    if (typeof raw.eventMessage !== 'string' && !raw.messageType) return null;
+    const message = raw.eventMessage || '';
+    const time = macosTime(raw.timestamp);
+    const processName = raw.processImagePath ? path.basename(raw.processImagePath) : '';
+    const level = MACOS_LEVELS[raw.messageType] || null;
+    const subsystem = raw.subsystem ? ` (${raw.subsystem}${raw.category ? `:${raw.category}` : ''})` : '';
+    return {
+      text: `${time || raw.timestamp || ''} ${processName}${raw.processID ? `[${raw.processID}]` : ''}${subsystem}: ${message}`.trim(),
+      level,
+      record: { time, level, message, process: processName, pid: raw.processID || null, ...plainRecord(raw, ['eventMessage']) }
+    };
+  }
+};
+
+// ---- Windows Event Log ----
+
+// Get-WinEvent has no follow mode, so the script prints the newest `count` events and then
+// polls for record ids past the last one seen. Parameters arrive through the environment,
+// keeping them out of the script text.
+const WINDOWS_SCRIPT = `
+$ErrorActionPreference = 'Stop'
+[Console]::OutputEncoding = [System.Text.Encoding]::UTF8
+$log = $env:FORGE_EVENT_LOG
+$filter = $env:FORGE_EVENT_FILTER
+$null = Get-WinEvent -ListLog $log
+function Select-Events($condition, $max) {
+  $xpath = if ($condition) { "*[System[$condition]]" } else { '*' }
+  $query = @{ LogName = $log; FilterXPath = $xpath; ErrorAction = 'SilentlyContinue' }
+  if ($max -gt 0) { $query.MaxEvents = $max }
+  @(Get-WinEvent @query | Sort-Object RecordId)
+}
+function Write-Events($events) {
+  foreach ($e in $events) {
+    [pscustomobject]@{
+      recordId = $e.RecordId; time = $e.TimeCreated.ToUniversalTime().ToString('o'); level = $e.Level
+      id = $e.Id; provider = $e.ProviderName; log = $e.LogName; machine = $e.MachineName
+      pid = $e.ProcessId; message = $e.Message
+    } | ConvertTo-Json -Compress
+    if ($e.RecordId -gt $script:last) { $script:last = $e.RecordId }
+  }
+}
+$newest = @(Get-WinEvent -LogName $log -MaxEvents 1 -ErrorAction SilentlyContinue)
+$script:last = if ($newest.Count) { $newest[0].RecordId } else { 0 }
+$count = [int]$env:FORGE_EVENT_COUNT
+if ($count -gt 0) { Write-Events (Select-Events $filter $count) }
+while ($true) {
+  Start-Sleep -Milliseconds 1000
+  $condition = "EventRecordID>$($script:last)"
+  if ($filter) { $condition = "($filter) and $condition" }
+  Write-Events (Select-Events $condition 0)
+}
+`;
+
+// Event log and provider names end up inside an XPath string literal
+const WINDOWS_NAME = /^[\w .\-/]+$/;
+
+const windows = {
+  label: 'Windows Event Log',
+  description: 'Get-WinEvent on a log such as Application or System, optionally narrowed to providers or a level',
+  platforms: ['win32'],
+  command: 'powershell.exe',
+  // { logName = 'Application', providers, level, lines }
+  args() {
+    return ['-NoProfile', '-NonInteractive', '-ExecutionPolicy', 'Bypass', '-Command', WINDOWS_SCRIPT];
+  },
+  env(params) {
+    const logName = params.logName === undefined ? 'Application' : params.logName;
+    if (typeof logName !== 'string' || !WINDOWS_NAME.test(logName)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'logName must be an event log name such as Application or System');
+    const conditions = [];
+    const providers = stringList(params.providers, 'providers');
+    for (const provider of providers) {
+      if (!WINDOWS_NAME.test(provider)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid provider name ${provider}`);
+    }
+    if (providers.length) conditions.push(`(${providers.map((name) => `Provider[@Name='${name}']`).join(' or ')})`);
+    const level = minimumLevel(params.level);
+    if (level !== null && level > LEVELS.indexOf('debug')) {
+      const accepted = [1, 2, 3, 4, 0].filter((value) => LEVELS.indexOf(WINDOWS_LEVELS[value]) >= level);
+      conditions.push(`(${accepted.map((value) => `Level=${value}`).join(' or ')})`);
+    }
+    return { FORGE_EVENT_LOG: logName, FORGE_EVENT_FILTER: conditions.join(' and '), FORGE_EVENT_COUNT: String(historyCount(params.lines)) };
+  },
+  normalize(raw) {
+    const message = typeof raw.message === 'string' ? raw.message.replace(/\r\n/g, '\n').trim() : '';
+    const level = WINDOWS_LEVELS[raw.level] || null;
+    // PowerShell's round-trip format has seven fractional digits
+    const parsed = Date.parse(raw.time);
+    const time = Number.isNaN(parsed) ? null : new Date(parsed).toISOString();
+    return {
+      text: [time, raw.provider, `[${raw.id}]:`, message].filter(Boolean).join(' '),
+      level,
+      record: { ...raw, time, level, eventLevel: raw.level, message }
+    };
+  }
+};
+
+const SOURCES = { journald, macos, windows };
+
+async function findCommand(name) {
+  const extensions = process.platform === 'win32' && !path.extname(name) ? (process.env.PATHEXT || '.EXE;.CMD;.BAT').split(';') : [''];
+  for (const dir of (process.env.PATH || '').split(path.delimiter).filter(Boolean)) {
+    for (const extension of extensions) {
+      try {
+        await fs.access(path.join(dir, name + extension), fsConstants.X_OK);
+        return true;
+      } catch {
+        // Keep looking
+      }
+    }
+  }
+  return false;
+}
+
+// Every source, with whether it can run here
+async function listLogSources() {
+  return Promise.all(Object.entries(SOURCES).map(async ([id, source]) => {
+    const supported = source.platforms.includes(process.platform);
+    const found = supported && await findCommand(source.command);
+    return {
+      id,
+      label: source.label,
+      description: source.description,
+      available: Boolean(found),
+      reason: !supported ? `Only available on ${source.platforms.join(', ')}` : found ? null : `${source.command} was not found on PATH`
+    };
+  }));
+}
+
+function getSource(sourceId) {
+  const source = Object.prototype.hasOwnProperty.call(SOURCES, sourceId) ? SOURCES[sourceId] : null;
+  if (!source) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown log source ${sourceId}; expected one of ${Object.keys(SOURCES).join(', ')}`);
+  if (!source.platforms.includes(process.platform)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${source.label} is only available on ${source.platforms.join(', ')}`, { recoverable: false });
+  }
+  return source;
+}
+
+// Runs a source's tool and calls `onEntry` with each normalized entry at or above
+// `params.level`, and `onExit` with (code, stderr) once it ends on its own. Resolves with
+// { stop } once the process is running.
+async function startLogSource(sourceId, params, { onEntry, onExit }) {
+  const source = getSource(sourceId);
+  const args = source.args(params || {});
+  const env = source.env ? { ...process.env, ...source.env(params || {}) } : process.env;
+  const level = minimumLevel((params || {}).level);
+  const child = spawn(source.command, args, { env, stdio: ['ignore', 'pipe', 'pipe'], windowsHide: true });
+  let stderr = '';
+  let stopping = false;
+  child.stderr.on('data', (chunk) => {
+    stderr = (stderr + chunk.toString('utf-8')).slice(-STDERR_TAIL);
+  });
+  await new Promise((resolve, reject) => {
+    child.once('spawn', resolve);
+    child.once('error', (error) => {
+      reject(error.code === 'ENOENT'
+        ? new ForgeError(ErrorCode.NOT_FOUND, `${source.command} was not found on PATH`, { recoverable: false })
+        : ForgeError.from(error, `Could not start ${source.command}`));
+    });
+  });
+
+  // Anything that isn't a JSON object (banners such as "Filtering the log data using...") is skipped
+  const lines = readline.createInterface({ input: child.stdout, crlfDelay: Infinity });
+  lines.on('line', (line) => {
+    if (!line.startsWith('{')) return;
+    let raw;
+    try {
+      raw = JSON.parse(line);
+    } catch {
+      return;
+    }
+    const entry = source.normalize(raw);
+    if (!entry) return;
+    if (level !== null && entry.level && LEVELS.indexOf(entry.level) < level) return;
+    onEntry(entry);
+  });
+  child.once('exit', (code, signal) => {
+    if (!stopping) onExit(signal ? `signal ${signal}` : code, stderr.trim());
+  });
+
+  return {
+    stop() {
+      stopping = true;
+      if (child.exitCode === null) child.kill();
+    }
+  };
+}
+
+module.exports = { listLogSources, startLogSource };
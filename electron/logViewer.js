@@ -0,0 +1,814 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { v4: uuidv4 } = require('uuid');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+const { watchPath } = require('./watchHub');
+const { windowManager } = require('./windows');
+const { JsonStore } = require('./jsonStore');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { QuerySyntaxError, parseRecord, getField, extractFields, inferSchema, compileQuery } = require('./logQuery');
+const { listLogSources, startLogSource } = require('./logSources');
+
+const log = createLogger('log-viewer');
+
+// Log files are never loaded whole: reads go through an open handle in chunks, backwards
+// from the end for the initial view and for scrolling up, forwards for scrolling down and
+// for tailing appends. Lines are identified by byte offset, which stays valid however
+// large the file grows.
+const CHUNK_SIZE = 1024 * 1024;
+// One request scans at most this much, then returns what it found with a cursor to go on from
+const MAX_SCAN_BYTES = 32 * 1024 * 1024;
+const DEFAULT_LIMIT = 500;
+const MAX_LIMIT = 5000;
+// Longer lines are cut for display
+const MAX_LINE_BYTES = 64 * 1024;
+const MAX_LINE_LENGTH = 16 * 1024;
+const MAX_HIGHLIGHT_RULES = 32;
+const MAX_HIGHLIGHTS_PER_LINE = 100;
+const MAX_COLUMNS = 50;
+const DEFAULT_SCHEMA_SAMPLE = 1000;
+const MAX_VIEWS = 32;
+// Appends are read shortly after the watcher reports them, so a burst arrives as one batch
+const TAIL_DELAY = 100;
+// Watch events can be missed (network mounts, some rotation schemes); a stat catches up
+const POLL_INTERVAL = 2000;
+// A larger backlog while tailing (a burst, or the machine waking from sleep) is skipped to its end
+const MAX_TAIL_BACKLOG = 16 * 1024 * 1024;
+const MAX_LINES_PER_EVENT = 1000;
+// Platform sources (journald, the macOS unified log, Windows Event Log) have no file to seek
+// in, so their views keep this many of the newest entries in memory, trimmed in batches
+const MAX_SOURCE_ENTRIES = 20000;
+const SOURCE_TRIM_BATCH = 1000;
+// Opening a source waits for the tool's backlog, until output goes quiet or the timeout passes
+const SOURCE_SETTLE_QUIET = 250;
+const SOURCE_SETTLE_TIMEOUT = 3000;
+// Levels are looked for near the start of a line, where loggers put them
+const LEVEL_SCAN_LENGTH = 160;
+
+const LEVELS = ['trace', 'debug', 'info', 'warn', 'error', 'fatal'];
+const LEVEL_ALIASES = {
+  trace: 'trace', verbose: 'trace', finest: 'trace',
+  debug: 'debug', dbg: 'debug', fine: 'debug',
+  info: 'info', notice: 'info', information: 'info',
+  warn: 'warn', warning: 'warn',
+  error: 'error', err: 'error', severe: 'error',
+  fatal: 'fatal', crit: 'fatal', critical: 'fatal', panic: 'fatal', emerg: 'fatal', alert: 'fatal'
+};
+const LEVEL_NAMES = Object.keys(LEVEL_ALIASES).join('|');
+// level=warn, "level":"error", severity: INFO
+const KEYED_LEVEL = new RegExp(`\\b(?:level|lvl|severity|loglevel)"?\\s*[=:]\\s*"?(${LEVEL_NAMES})\\b`, 'i');
+// [error], <warn>, (INFO)
+const BRACKETED_LEVEL = new RegExp(`[[<(](${LEVEL_NAMES})[\\]>):]`, 'i');
+// A bare upper-case ERROR or WARN; lower-case words are too often just prose
+const UPPER_LEVEL = new RegExp(`(?:^|[^A-Za-z])(${LEVEL_NAMES.toUpperCase()})(?![A-Za-z])`);
+// Stack frames and wrapped lines belong to the entry above them
+const CONTINUATION = /^(?:\s|at |Caused by|\.\.\. \d+ more)/;
+// Where JSON loggers put the level; numbers are pino/bunyan's scale
+const LEVEL_FIELDS = ['level', 'lvl', 'severity', 'levelname', 'log.level', '@l'];
+const NUMERIC_LEVELS = [[60, 'fatal'], [50, 'error'], [40, 'warn'], [30, 'info'], [20, 'debug'], [10, 'trace']];
+
+const store = new JsonStore('logViewer', {
+  // Workspace root -> { queries: { id -> { id, name, query, columns, createdAt, updatedAt } } }
+  workspaces: {}
+});
+
+// viewId -> { id, kind: 'file', path, owner, filters, handle, ino, position, partialLength,
+//             level, follow, watcher, poll, timer, reading, dirty, status }
+//         | { id, kind: 'source', source, owner, filters, entries, nextSeq, sentSeq, follow,
+//             timer, process, exit, status }
+const views = new Map();
+
+function detectLevel(text) {
+  const head = text.slice(0, LEVEL_SCAN_LENGTH);
+  const match = KEYED_LEVEL.exec(head) || BRACKETED_LEVEL.exec(head) || UPPER_LEVEL.exec(head);
+  return match ? LEVEL_ALIASES[match[1].toLowerCase()] : null;
+}
+
+function recordLevel(record) {
+  for (const field of LEVEL_FIELDS) {
+    const value = getField(record, field);
+    if (typeof value === 'string' && LEVEL_ALIASES[value.toLowerCase()]) return LEVEL_ALIASES[value.toLowerCase()];
+    if (typeof value === 'number' && value >= 10) {
+      const entry = NUMERIC_LEVELS.find(([threshold]) => value >= threshold);
+      return entry ? entry[1] : null;
+    }
+  }
+  return null;
+}
+
+function escapeRegExp(text) {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+}
+
+function compileLogQuery(query) {
+  if (typeof query !== 'string') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A log query must be a string');
+  try {
+    return compileQuery(query, { levels: LEVELS });
+  } catch (error) {
+    if (error instanceof QuerySyntaxError) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Query error at ${error.position + 1}: ${error.message}`, { details: { position: error.position } });
+    }
+    throw error;
+  }
+}
+
+function compilePattern(rule, what) {
+  if (typeof rule.pattern !== 'string' || !rule.pattern) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `The ${what} needs a pattern`);
+  try {
+    return new RegExp(rule.regex === false ? escapeRegExp(rule.pattern) : rule.pattern, rule.caseSensitive ? 'g' : 'gi');
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid ${what} pattern: ${error.message}`);
+  }
+}
+
+// { levels, pattern, regex = true, caseSensitive = false, invert, query, columns,
+//   highlights: [{ pattern, regex, caseSensitive }] }
+function compileFilters(filters = {}) {
+  const levels = Array.isArray(filters.levels) && filters.levels.length ? filters.levels : null;
+  for (const level of levels || []) {
+    if (!LEVELS.includes(level)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown log level ${level}; expected one of ${LEVELS.join(', ')}`);
+  }
+  const highlights = Array.isArray(filters.highlights) ? filters.highlights : [];
+  if (highlights.length > MAX_HIGHLIGHT_RULES) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `At most ${MAX_HIGHLIGHT_RULES} highlight rules are supported`);
+  const columns = Array.isArray(filters.columns) ? filters.columns.filter((column) => typeof column === 'string' && column) : [];
+  if (columns.length > MAX_COLUMNS) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `At most ${MAX_COLUMNS} columns are supported`);
+  return {
+    levels: levels ? new Set(levels) : null,
+    pattern: filters.pattern ? compilePattern(filters, 'filter') : null,
+    invert: Boolean(filters.invert),
+    query: filters.query ? compileLogQuery(filters.query) : null,
+    columns,
+    highlights: highlights.map((rule) => compilePattern(rule || {}, 'highlight'))
+  };
+}
+
+function collectRanges(regex, text, rule, ranges) {
+  regex.lastIndex = 0;
+  let match;
+  while (ranges.length < MAX_HIGHLIGHTS_PER_LINE && (match = regex.exec(text))) {
+    if (!match[0].length) {
+      regex.lastIndex++;
+      continue;
+    }
+    ranges.push({ start: match.index, end: match.index + match[0].length, rule });
+  }
+}
+
+// Highlight ranges for a line that passes, null for one that doesn't. `rule` is the
+// highlight rule's index, or null for the filter pattern itself.
+function filterLine(filters, text, level, record = null) {
+  if (filters.levels && !filters.levels.has(level)) return null;
+  if (filters.query && !filters.query(record, text, level)) return null;
+  const ranges = [];
+  if (filters.pattern) {
+    filters.pattern.lastIndex = 0;
+    if (filters.pattern.test(text) === filters.invert) return null;
+    if (!filters.invert) collectRanges(filters.pattern, text, null, ranges);
+  }
+  filters.highlights.forEach((regex, index) => collectRanges(regex, text, index, ranges));
+  return ranges.sort((a, b) => a.start - b.start);
+}
+
+// Filters raw lines in file order; `state.level` carries the last line's level so
+// continuation lines can inherit it
+function processLines(raw, filters, state) {
+  const lines = [];
+  for (const { offset, bytes, partial } of raw) {
+    const truncated = bytes.length > MAX_LINE_BYTES;
+    let text = bytes.toString('utf-8', 0, Math.min(bytes.length, MAX_LINE_BYTES)).replace(/\r$/, '');
+    if (text.length > MAX_LINE_LENGTH) text = text.slice(0, MAX_LINE_LENGTH);
+    const record = truncated ? null : parseRecord(text);
+    let level = (record && recordLevel(record)) || detectLevel(text);
+    if (!level && !record && CONTINUATION.test(text)) level = state.level;
+    state.level = level;
+    const highlights = filterLine(filters, text, level, record);
+    if (!highlights) continue;
+    const line = { offset, text, level, highlights };
+    if (record) {
+      line.json = true;
+      if (filters.columns.length) line.fields = extractFields(record, filters.columns);
+    }
+    if (truncated || text.length === MAX_LINE_LENGTH) line.truncated = true;
+    if (partial) line.partial = true;
+    lines.push(line);
+  }
+  return lines;
+}
+
+// Newline-terminated lines in `buffer`, which starts at file offset `base`; `rest` is
+// whatever follows the last newline
+function splitLines(buffer, base) {
+  const lines = [];
+  let start = 0;
+  let newline;
+  while ((newline = buffer.indexOf(10, start)) !== -1) {
+    lines.push({ offset: base + start, bytes: buffer.subarray(start, newline), next: base + newline + 1 });
+    start = newline + 1;
+  }
+  return { lines, rest: buffer.subarray(start) };
+}
+
+async function readAt(handle, position, length) {
+  const buffer = Buffer.alloc(length);
+  const { bytesRead } = await handle.read(buffer, 0, length, position);
+  return bytesRead < length ? buffer.subarray(0, bytesRead) : buffer;
+}
+
+// Up to `limit` matching lines ending before `before` (a line start), in file order.
+// `before` in the result is where the next page up starts.
+async function scanBackward(handle, before, filters, limit) {
+  const pages = [];
+  let count = 0;
+  let end = before;
+  let carry = Buffer.alloc(0);
+  let cursor = before;
+  let scanned = 0;
+  while (end > 0 && count < limit && scanned < MAX_SCAN_BYTES) {
+    const start = Math.max(0, end - CHUNK_SIZE);
+    const chunk = await readAt(handle, start, end - start);
+    scanned += chunk.length;
+    const buffer = carry.length ? Buffer.concat([chunk, carry]) : chunk;
+    end = start;
+    // The bytes before the first newline belong to a line that starts in an earlier chunk
+    let first = 0;
+    if (start > 0) {
+      const newline = buffer.indexOf(10);
+      if (newline === -1) {
+        carry = buffer;
+        continue;
+      }
+      first = newline + 1;
+    }
+    const { lines } = splitLines(buffer.subarray(first), start + first);
+    const matches = processLines(lines, filters, { level: null });
+    pages.unshift(matches);
+    count += matches.length;
+    carry = buffer.subarray(0, first);
+    cursor = start + first;
+  }
+  let lines = pages.flat();
+  if (lines.length > limit) {
+    lines = lines.slice(lines.length - limit);
+    cursor = lines[0].offset;
+  }
+  return { lines, before: cursor, atStart: cursor === 0 };
+}
+
+// Up to `limit` matching lines from `after` (a line start) up to `size`. A trailing line
+// without its newline yet comes back as `partial` rather than as a line.
+async function scanForward(handle, after, size, filters, limit, state = { level: null }) {
+  const lines = [];
+  let pos = after;
+  let cursor = after;
+  let carry = Buffer.alloc(0);
+  let scanned = 0;
+  let full = false;
+  while (pos < size && !full && scanned < MAX_SCAN_BYTES) {
+    const chunk = await readAt(handle, pos, Math.min(CHUNK_SIZE, size - pos));
+    if (!chunk.length) break;
+    pos += chunk.length;
+    scanned += chunk.length;
+    const { lines: raw, rest } = splitLines(carry.length ? Buffer.concat([carry, chunk]) : chunk, cursor);
+    for (const line of raw) {
+      lines.push(...processLines([line], filters, state));
+      cursor = line.next;
+      if (lines.length >= limit) {
+        full = true;
+        break;
+      }
+    }
+    if (!full) carry = rest;
+  }
+  const atEnd = !full && pos >= size;
+  return {
+    lines,
+    after: cursor,
+    atEnd,
+    partial: atEnd && carry.length ? { offset: cursor, bytes: carry } : null,
+    level: state.level
+  };
+}
+
+// Start of the line containing `size - 1`, i.e. just past the last newline
+async function lastLineStart(handle, size) {
+  let end = size;
+  while (end > 0) {
+    const start = Math.max(0, end - CHUNK_SIZE);
+    const chunk = await readAt(handle, start, end - start);
+    const newline = chunk.lastIndexOf(10);
+    if (newline !== -1) return start + newline + 1;
+    end = start;
+  }
+  return 0;
+}
+
+async function nextLineStart(handle, offset, size) {
+  for (let pos = offset; pos < size; pos += CHUNK_SIZE) {
+    const chunk = await readAt(handle, pos, Math.min(CHUNK_SIZE, size - pos));
+    const newline = chunk.indexOf(10);
+    if (newline !== -1) return pos + newline + 1;
+  }
+  return size;
+}
+
+function clampLimit(limit) {
+  if (limit === undefined) return DEFAULT_LIMIT;
+  const value = Math.floor(Number(limit));
+  if (!Number.isFinite(value) || value < 1) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'limit must be a positive number');
+  return Math.min(value, MAX_LIMIT);
+}
+
+function getView(viewId) {
+  const view = views.get(viewId);
+  if (!view) throw new ForgeError(ErrorCode.NOT_FOUND, `No log view ${viewId}`);
+  return view;
+}
+
+function setStatus(view, status, message = null) {
+  view.status = status;
+  emit('logViewer:status', { viewId: view.id, status, message }, { target: view.owner });
+}
+
+// The newest matching lines, with the line still being written (if any) last
+async function readLatest(view, limit) {
+  const page = await scanBackward(view.handle, view.position, view.filters, limit);
+  const { size } = await view.handle.stat();
+  if (size > view.position) {
+    const partial = await readAt(view.handle, view.position, Math.min(size - view.position, MAX_LINE_BYTES + 1));
+    page.lines.push(...processLines([{ offset: view.position, bytes: partial, partial: true }], view.filters, { level: null }));
+  }
+  view.partialLength = size - view.position;
+  return { viewId: view.id, path: view.path, size, position: view.position, following: view.follow, ...page };
+}
+
+function sendLines(view, lines, extra = {}) {
+  const batches = [];
+  for (let index = 0; index < lines.length; index += MAX_LINES_PER_EVENT) batches.push(lines.slice(index, index + MAX_LINES_PER_EVENT));
+  if (!batches.length) batches.push([]);
+  batches.forEach((batch, index) => {
+    emit('logViewer:lines', { viewId: view.id, lines: batch, ...(index === 0 ? extra : {}) }, { target: view.owner });
+  });
+}
+
+// Reads what was appended through the open handle, which keeps working after the file
+// is renamed away by rotation
+async function tailHandle(view) {
+  const { size } = await view.handle.stat();
+  if (size < view.position) {
+    view.position = 0;
+    view.partialLength = 0;
+    view.level = null;
+    setStatus(view, 'truncated', 'The file was truncated; following it from the start');
+  }
+  let skippedBytes = 0;
+  if (size - view.position > MAX_TAIL_BACKLOG) {
+    const target = await nextLineStart(view.handle, size - MAX_TAIL_BACKLOG, size);
+    skippedBytes = target - view.position;
+    view.position = target;
+    view.partialLength = 0;
+    view.level = null;
+  }
+  if (size === view.position + view.partialLength && !skippedBytes) return;
+
+  const result = await scanForward(view.handle, view.position, size, view.filters, Infinity, { level: view.level });
+  view.position = result.after;
+  view.level = result.level;
+  const lines = result.lines;
+  // A line still being written is sent as partial each time it grows; the complete line
+  // replaces it later (same offset)
+  const partialLength = result.partial ? result.partial.bytes.length : 0;
+  if (result.partial && partialLength !== view.partialLength) {
+    lines.push(...processLines([{ ...result.partial, partial: true }], view.filters, { level: view.level }));
+  }
+  view.partialLength = partialLength;
+  if (lines.length || skippedBytes) sendLines(view, lines, skippedBytes ? { skippedBytes } : {});
+}
+
+async function followStep(view) {
+  if (view.handle) await tailHandle(view);
+  let stat = null;
+  try {
+    stat = await fs.stat(view.path);
+  } catch (error) {
+    if (error.code !== 'ENOENT') throw error;
+  }
+  if (!stat) {
+    // Moved away and not (yet) recreated; what was written to it has been read above
+    if (view.status !== 'missing') setStatus(view, 'missing', `${path.basename(view.path)} was moved or deleted; waiting for it to reappear`);
+    return;
+  }
+  if (stat.ino === view.ino) {
+    if (view.status !== 'following') setStatus(view, 'following');
+    return;
+  }
+  // Rotated: a new file now has the name. Follow it from its start.
+  await view.handle.close().catch(() => {});
+  view.handle = await fs.open(view.path, 'r');
+  view.ino = stat.ino;
+  view.position = 0;
+  view.partialLength = 0;
+  view.level = null;
+  setStatus(view, 'rotated', `${path.basename(view.path)} was rotated; following the new file`);
+  await tailHandle(view);
+  setStatus(view, 'following');
+}
+
+async function pump(view) {
+  if (view.reading) {
+    view.dirty = true;
+    return;
+  }
+  view.reading = true;
+  try {
+    do {
+      view.dirty = false;
+      await followStep(view);
+    } while (view.dirty && views.has(view.id));
+  } catch (error) {
+    // Closing the view mid-read closes the handle under it
+    if (!views.has(view.id)) return;
+    log.warn(`Tailing ${view.path} failed: ${error.message}`);
+    setStatus(view, 'error', error.message);
+  } finally {
+    view.reading = false;
+  }
+}
+
+function schedule(view) {
+  if (view.timer || !views.has(view.id)) return;
+  view.timer = setTimeout(() => {
+    view.timer = null;
+    if (views.has(view.id)) pump(view);
+  }, TAIL_DELAY);
+}
+
+// Opens a view on a log file: the newest `limit` lines passing `filters`, then (with
+// `follow`) appended lines as logViewer:lines events to the opening window only
+async function openLogView(filePath, { filters, follow = true, limit } = {}, owner = null) {
+  if (views.size >= MAX_VIEWS) throw new ForgeError(ErrorCode.BUSY, `At most ${MAX_VIEWS} log views can be open`);
+  const compiled = compileFilters(filters);
+  const count = clampLimit(limit);
+  let handle;
+  let stat;
+  try {
+    handle = await fs.open(filePath, 'r');
+    stat = await handle.stat();
+  } catch (error) {
+    if (handle) await handle.close();
+    throw ForgeError.from(error, `Could not open ${filePath}`);
+  }
+  if (!stat.isFile()) {
+    await handle.close();
+    throw new ForgeError(ErrorCode.IS_DIRECTORY, `${filePath} is not a file`);
+  }
+
+  const view = {
+    id: `log_${uuidv4()}`,
+    kind: 'file',
+    path: filePath,
+    owner,
+    filters: compiled,
+    handle,
+    ino: stat.ino,
+    position: await lastLineStart(handle, stat.size),
+    partialLength: 0,
+    level: null,
+    follow: Boolean(follow),
+    watcher: null,
+    poll: null,
+    timer: null,
+    reading: false,
+    dirty: false,
+    status: follow ? 'following' : 'paused'
+  };
+  views.set(view.id, view);
+  try {
+    const result = await readLatest(view, count);
+    if (view.follow) startFollowing(view);
+    log.info(`Opened log view ${view.id} on ${filePath} (${stat.size} bytes)`);
+    return result;
+  } catch (error) {
+    await closeLogView(view.id);
+    throw ForgeError.from(error, `Could not read ${filePath}`);
+  }
+}
+
+function startFollowing(view) {
+  const context = view.owner ? windowManager.fromSender(view.owner) : null;
+  view.watcher = watchPath(view.path, () => schedule(view), { kind: 'file', workspaceRoot: context ? context.workspacePath : null, owner: 'logViewer' });
+  view.poll = setInterval(() => schedule(view), POLL_INTERVAL);
+}
+
+// Source entries are numbered in arrival order; the numbers stand in for byte offsets
+function firstSeq(view) {
+  return view.entries.length ? view.entries[0].seq : view.nextSeq;
+}
+
+function entryIndex(view, seq) {
+  return Math.max(0, Math.min(view.entries.length, seq - firstSeq(view)));
+}
+
+function sourceLine(entry, filters) {
+  const text = entry.text.length > MAX_LINE_LENGTH ? entry.text.slice(0, MAX_LINE_LENGTH) : entry.text;
+  const highlights = filterLine(filters, text, entry.level, entry.record);
+  if (!highlights) return null;
+  const line = { offset: entry.seq, text, level: entry.level, highlights };
+  if (text.length < entry.text.length) line.truncated = true;
+  if (filters.columns.length) line.fields = extractFields(entry.record, filters.columns);
+  return line;
+}
+
+function scanEntriesBackward(view, before, limit) {
+  const lines = [];
+  let index = entryIndex(view, before);
+  while (index > 0 && lines.length < limit) {
+    const line = sourceLine(view.entries[--index], view.filters);
+    if (line) lines.push(line);
+  }
+  return { lines: lines.reverse(), before: firstSeq(view) + index, atStart: index === 0 };
+}
+
+function scanEntriesForward(view, after, limit) {
+  const lines = [];
+  let index = entryIndex(view, after);
+  while (index < view.entries.length && lines.length < limit) {
+    const line = sourceLine(view.entries[index++], view.filters);
+    if (line) lines.push(line);
+  }
+  return { lines, after: firstSeq(view) + index, atEnd: index === view.entries.length };
+}
+
+function sourceSnapshot(view, limit) {
+  return { viewId: view.id, source: view.source, following: view.follow, status: view.status, ...scanEntriesBackward(view, view.nextSeq, limit) };
+}
+
+// Sends what arrived since the last send; entries trimmed from the buffer in between
+// (while paused) are reported as skipped
+function flushSource(view) {
+  clearTimeout(view.timer);
+  view.timer = null;
+  if (!views.has(view.id) || !view.follow) return;
+  const skipped = Math.max(0, firstSeq(view) - view.sentSeq);
+  const { lines } = scanEntriesForward(view, view.sentSeq, Infinity);
+  view.sentSeq = view.nextSeq;
+  if (lines.length || skipped) sendLines(view, lines, skipped ? { skippedEntries: skipped } : {});
+}
+
+function addSourceEntry(view, entry) {
+  view.entries.push({ seq: view.nextSeq++, ...entry });
+  if (view.entries.length > MAX_SOURCE_ENTRIES + SOURCE_TRIM_BATCH) view.entries.splice(0, view.entries.length - MAX_SOURCE_ENTRIES);
+  if (view.follow && !view.settling && !view.timer) view.timer = setTimeout(() => flushSource(view), TAIL_DELAY);
+}
+
+function sourceEnded(view) {
+  flushSource(view);
+  const { code, stderr } = view.exit;
+  if (code === 0) setStatus(view, 'stopped', stderr || null);
+  else setStatus(view, 'error', `The ${view.source} source exited with ${typeof code === 'number' ? `code ${code}` : code}${stderr ? `: ${stderr}` : ''}`);
+}
+
+// Opens a view on a platform log source (see listLogSources): the backlog the tool prints
+// on start, then (with `follow`) new entries as logViewer:lines events. `params` are the
+// source's own options, such as journald units or a Windows event log name.
+async function openSourceView(sourceId, params, { filters, follow = true, limit } = {}, owner = null) {
+  if (views.size >= MAX_VIEWS) throw new ForgeError(ErrorCode.BUSY, `At most ${MAX_VIEWS} log views can be open`);
+  const compiled = compileFilters(filters);
+  const count = clampLimit(limit);
+  const view = {
+    id: `log_${uuidv4()}`,
+    kind: 'source',
+    source: sourceId,
+    owner,
+    filters: compiled,
+    entries: [],
+    nextSeq: 0,
+    sentSeq: 0,
+    follow: Boolean(follow),
+    settling: true,
+    timer: null,
+    process: null,
+    exit: null,
+    status: follow ? 'following' : 'paused'
+  };
+
+  let settle;
+  let quiet = null;
+  const settled = new Promise((resolve) => {
+    settle = resolve;
+  });
+  view.process = await startLogSource(sourceId, params, {
+    onEntry: (entry) => {
+      addSourceEntry(view, entry);
+      if (!view.settling) return;
+      clearTimeout(quiet);
+      quiet = setTimeout(settle, SOURCE_SETTLE_QUIET);
+    },
+    onExit: (code, stderr) => {
+      view.exit = { code, stderr };
+      if (view.settling) settle();
+      else if (views.has(view.id)) sourceEnded(view);
+    }
+  });
+  views.set(view.id, view);
+  const deadline = setTimeout(settle, SOURCE_SETTLE_TIMEOUT);
+  await settled;
+  clearTimeout(deadline);
+  clearTimeout(quiet);
+  view.settling = false;
+  view.sentSeq = view.nextSeq;
+
+  if (view.exit) {
+    const { code, stderr } = view.exit;
+    if (code !== 0 && !view.entries.length) {
+      views.delete(view.id);
+      throw new ForgeError(ErrorCode.IO_ERROR, `The ${sourceId} source exited with ${typeof code === 'number' ? `code ${code}` : code}${stderr ? `: ${stderr}` : ''}`);
+    }
+    view.status = code === 0 ? 'stopped' : 'error';
+  }
+  log.info(`Opened log view ${view.id} on the ${sourceId} source (${view.entries.length} entries)`);
+  return sourceSnapshot(view, count);
+}
+
+// Scrolling: `before` pages up from a line offset, `after` pages down from one
+async function readLogView(viewId, { before, after, limit } = {}) {
+  const view = getView(viewId);
+  const count = clampLimit(limit);
+  if (view.kind === 'source') {
+    if (Number.isInteger(before) && before >= 0) return scanEntriesBackward(view, before, count);
+    if (Number.isInteger(after) && after >= 0) return scanEntriesForward(view, after, count);
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Pass a line offset to read before or after');
+  }
+  if (Number.isInteger(before) && before >= 0) return scanBackward(view.handle, Math.min(before, view.position), view.filters, count);
+  if (Number.isInteger(after) && after >= 0) {
+    const { size } = await view.handle.stat();
+    const page = await scanForward(view.handle, after, size, view.filters, count);
+    if (page.partial) page.lines.push(...processLines([{ ...page.partial, partial: true }], view.filters, { level: page.level }));
+    return { lines: page.lines, after: page.after, atEnd: page.atEnd };
+  }
+  throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Pass a line offset to read before or after');
+}
+
+// New filters apply to everything shown, so the view starts over from the newest lines
+async function setLogViewFilters(viewId, filters, { limit } = {}) {
+  const view = getView(viewId);
+  view.filters = compileFilters(filters);
+  if (view.kind === 'source') return sourceSnapshot(view, clampLimit(limit));
+  return readLatest(view, clampLimit(limit));
+}
+
+async function setLogViewFollow(viewId, follow) {
+  const view = getView(viewId);
+  if (Boolean(follow) === view.follow) return { following: view.follow };
+  view.follow = Boolean(follow);
+  if (view.kind === 'source') {
+    // The tool keeps running while paused; resuming sends what arrived meanwhile
+    if (!view.exit) setStatus(view, view.follow ? 'following' : 'paused');
+    flushSource(view);
+  } else if (view.follow) {
+    startFollowing(view);
+    setStatus(view, 'following');
+    schedule(view);
+  } else {
+    stopFollowing(view);
+    setStatus(view, 'paused');
+  }
+  return { following: view.follow };
+}
+
+// Fields of the JSON records among the newest `sample` lines, whatever the filters
+async function inferLogViewSchema(viewId, { sample } = {}) {
+  const view = getView(viewId);
+  const count = sample === undefined ? DEFAULT_SCHEMA_SAMPLE : clampLimit(sample);
+  if (view.kind === 'source') {
+    const records = view.entries.slice(-count).map((entry) => entry.record);
+    return { sampled: records.length, records: records.length, ...inferSchema(records) };
+  }
+  const page = await scanBackward(view.handle, view.position, compileFilters(), count);
+  const records = page.lines.filter((line) => line.json).map((line) => parseRecord(line.text));
+  return { sampled: page.lines.length, records: records.length, ...inferSchema(records) };
+}
+
+function workspaceQueries(root) {
+  const data = store.get('workspaces')[root] || {};
+  return data.queries || {};
+}
+
+function saveWorkspaceQueries(root, queries) {
+  const workspaces = store.get('workspaces');
+  if (Object.keys(queries).length === 0) delete workspaces[root];
+  else workspaces[root] = { queries };
+  store.set('workspaces', workspaces);
+}
+
+function listQueries(root) {
+  return Object.values(workspaceQueries(root)).sort((a, b) => a.name.localeCompare(b.name));
+}
+
+// Add a saved query, or update it in place when `input.id` is given. The query is
+// compiled first so a broken one is never saved.
+function saveQuery(root, input) {
+  if (!input || typeof input.name !== 'string' || !input.name.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A saved query needs a name');
+  if (typeof input.query !== 'string') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A saved query needs a query');
+  const { columns } = compileFilters({ query: input.query, columns: input.columns });
+  const queries = workspaceQueries(root);
+  if (input.id && !queries[input.id]) throw new ForgeError(ErrorCode.NOT_FOUND, `Saved query ${input.id} not found`);
+  const now = new Date().toISOString();
+  const fields = { name: input.name.trim(), query: input.query, columns };
+  const saved = input.id
+    ? { ...queries[input.id], ...fields, updatedAt: now }
+    : { id: `log_query_${uuidv4()}`, ...fields, createdAt: now, updatedAt: now };
+  saveWorkspaceQueries(root, { ...queries, [saved.id]: saved });
+  return saved;
+}
+
+function deleteQuery(root, id) {
+  const queries = { ...workspaceQueries(root) };
+  if (!queries[id]) throw new ForgeError(ErrorCode.NOT_FOUND, `Saved query ${id} not found`);
+  delete queries[id];
+  saveWorkspaceQueries(root, queries);
+  return true;
+}
+
+function workspaceRoot(sender) {
+  const context = windowManager.fromSender(sender);
+  if (!context) throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Window not available');
+  if (!context.workspacePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to save log queries');
+  return path.resolve(context.workspacePath);
+}
+
+function stopFollowing(view) {
+  if (view.watcher) view.watcher.close();
+  clearInterval(view.poll);
+  clearTimeout(view.timer);
+  view.watcher = null;
+  view.poll = null;
+  view.timer = null;
+}
+
+async function closeLogView(viewId) {
+  const view = views.get(viewId);
+  if (!view) return false;
+  views.delete(viewId);
+  if (view.kind === 'source') {
+    clearTimeout(view.timer);
+    view.process.stop();
+    return true;
+  }
+  stopFollowing(view);
+  await view.handle.close().catch(() => {});
+  return true;
+}
+
+function closeOwnedLogViews(owner) {
+  for (const view of Array.from(views.values())) {
+    if (view.owner === owner) closeLogView(view.id);
+  }
+}
+
+function setupLogViewerHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  const owners = new WeakSet();
+  const watchOwner = (sender) => {
+    if (owners.has(sender)) return;
+    owners.add(sender);
+    sender.once('destroyed', () => closeOwnedLogViews(sender));
+  };
+
+  ipcMain.handle('logViewer:open', async (event, filePath, options) => {
+    if (typeof filePath !== 'string' || !filePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A log file path is required');
+    watchOwner(event.sender);
+    const context = windowManager.fromSender(event.sender);
+    const resolved = context && context.workspacePath ? path.resolve(context.workspacePath, filePath) : path.resolve(filePath);
+    return openLogView(resolved, options || {}, event.sender);
+  });
+  ipcMain.handle('logViewer:listSources', async () => listLogSources());
+  ipcMain.handle('logViewer:openSource', async (event, sourceId, params, options) => {
+    watchOwner(event.sender);
+    return openSourceView(sourceId, params || {}, options || {}, event.sender);
+  });
+  ipcMain.handle('logViewer:read', async (event, viewId, options) => readLogView(viewId, options || {}));
+  ipcMain.handle('logViewer:setFilters', async (event, viewId, filters, options) => setLogViewFilters(viewId, filters || {}, options || {}));
+  ipcMain.handle('logViewer:setFollow', async (event, viewId, follow) => setLogViewFollow(viewId, follow));
+  ipcMain.handle('logViewer:close', async (event, viewId) => closeLogView(viewId));
+  ipcMain.handle('logViewer:inferSchema', async (event, viewId, options) => inferLogViewSchema(viewId, options || {}));
+
+  ipcMain.handle('logViewer:listQueries', async (event) => listQueries(workspaceRoot(event.sender)));
+  ipcMain.handle('logViewer:saveQuery', async (event, query) => saveQuery(workspaceRoot(event.sender), query));
+  ipcMain.handle('logViewer:deleteQuery', async (event, id) => deleteQuery(workspaceRoot(event.sender), id));
+}
+
+module.exports = {
+  setupLogViewerHandlers,
+  openLogView,
+  openSourceView,
+  readLogView,
+  setLogViewFilters,
+  setLogViewFollow,
+  closeLogView,
+  inferLogViewSchema,
+  detectLevel,
+  compileFilters,
+  filterLine
+};
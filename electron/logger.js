@@ -0,0 +1,245 @@
+const fs = require('fs');
+const path = require('path');
+const os = require('os');
+const util = require('util');
+const { app, dialog, BrowserWindow } = require('electron');
+const { createZip } = require('./zip');
+
+const LEVELS = { error: 0, warn: 1, info: 2, debug: 3, trace: 4 };
+const DEFAULT_LEVEL = process.env.FORGE_LOG_LEVEL || (process.env.NODE_ENV === 'development' ? 'debug' : 'info');
+
+const MAX_RECENT_ENTRIES = 2000;
+const MAX_LOG_FILE_SIZE = 5 * 1024 * 1024;
+const MAX_LOG_FILES = 5;
+
+// Per-module level overrides, e.g. { terminal: 'trace' }
+const moduleLevels = new Map();
+const recentEntries = [];
+
+let logDir = null;
+let logStream = null;
+let logFileSize = 0;
+
+function getLogDir() {
+  if (!logDir) {
+    logDir = path.join(app.getPath('userData'), 'logs');
+    fs.mkdirSync(logDir, { recursive: true });
+  }
+  return logDir;
+}
+
+function getLogFilePath(index = 0) {
+  return path.join(getLogDir(), index === 0 ? 'forge.log' : `forge.${index}.log`);
+}
+
+// Shift forge.log -> forge.1.log -> ... dropping the oldest file
+function rotateLogFiles() {
+  if (logStream) {
+    logStream.end();
+    logStream = null;
+  }
+
+  for (let i = MAX_LOG_FILES - 1; i >= 0; i--) {
+    const from = getLogFilePath(i);
+    if (!fs.existsSync(from)) continue;
+    if (i === MAX_LOG_FILES - 1) {
+      fs.unlinkSync(from);
+    } else {
+      fs.renameSync(from, getLogFilePath(i + 1));
+    }
+  }
+  logFileSize = 0;
+}
+
+function writeToFile(line) {
+  try {
+    if (!logStream) {
+      const filePath = getLogFilePath();
+      logFileSize = fs.existsSync(filePath) ? fs.statSync(filePath).size : 0;
+      logStream = fs.createWriteStream(filePath, { flags: 'a' });
+    }
+
+    logStream.write(line + '\n');
+    logFileSize += Buffer.byteLength(line) + 1;
+
+    if (logFileSize >= MAX_LOG_FILE_SIZE) {
+      rotateLogFiles();
+    }
+  } catch {
+    // Logging must never take the app down (e.g. before app is ready)
+  }
+}
+
+function getLevel(moduleName) {
+  return moduleLevels.get(moduleName) || moduleLevels.get('*') || DEFAULT_LEVEL;
+}
+
+function isLevelEnabled(moduleName, level) {
+  return LEVELS[level] <= LEVELS[getLevel(moduleName)];
+}
+
+function log(moduleName, level, args) {
+  if (!isLevelEnabled(moduleName, level)) return;
+
+  // A trailing plain object is treated as structured fields
+  let fields;
+  const last = args[args.length - 1];
+  if (args.length > 1 && last && Object.getPrototypeOf(last) === Object.prototype) {
+    fields = last;
+    args = args.slice(0, -1);
+  }
+
+  const entry = {
+    timestamp: new Date().toISOString(),
+    level,
+    module: moduleName,
+    message: util.format(...args),
+    ...(fields ? { fields } : {})
+  };
+
+  recentEntries.push(entry);
+  if (recentEntries.length > MAX_RECENT_ENTRIES) {
+    recentEntries.splice(0, recentEntries.length - MAX_RECENT_ENTRIES);
+  }
+
+  writeToFile(JSON.stringify(entry));
+
+  const consoleMethod = level === 'error' ? 'error' : level === 'warn' ? 'warn' : 'log';
+  const suffix = fields ? ` ${util.inspect(fields, { depth: 4, breakLength: Infinity })}` : '';
+  console[consoleMethod](`[${moduleName}] ${entry.message}${suffix}`);
+}
+
+// Create a logger scoped to a module name
+function createLogger(moduleName) {
+  const logger = {};
+  for (const level of Object.keys(LEVELS)) {
+    logger[level] = (...args) => log(moduleName, level, args);
+  }
+  logger.isEnabled = (level) => isLevelEnabled(moduleName, level);
+  return logger;
+}
+
+function setLogLevel(moduleName, level) {
+  if (!(level in LEVELS)) {
+    throw new Error(`Invalid log level: ${level}`);
+  }
+  moduleLevels.set(moduleName || '*', level);
+}
+
+function getLogLevels() {
+  return {
+    default: DEFAULT_LEVEL,
+    modules: Object.fromEntries(moduleLevels)
+  };
+}
+
+function getRecentLogs(options = {}) {
+  const { limit = 500, level, module: moduleName, since } = options;
+  const maxLevel = level ? LEVELS[level] : LEVELS.trace;
+
+  return recentEntries
+    .filter((entry) => LEVELS[entry.level] <= maxLevel)
+    .filter((entry) => !moduleName || entry.module === moduleName)
+    .filter((entry) => !since || entry.timestamp >= since)
+    .slice(-limit);
+}
+
+const SECRET_KEY_PATTERN = /(token|secret|password|passwd|api[-_]?key|auth|credential|private[-_]?key)/i;
+
+// Recursively replace values of secret-looking keys
+function redactSecrets(value) {
+  if (Array.isArray(value)) {
+    return value.map(redactSecrets);
+  }
+  if (value && typeof value === 'object') {
+    return Object.fromEntries(
+      Object.entries(value).map(([key, child]) => [
+        key,
+        SECRET_KEY_PATTERN.test(key) ? '[REDACTED]' : redactSecrets(child)
+      ])
+    );
+  }
+  return value;
+}
+
+function collectEnvironment() {
+  const safeEnv = Object.fromEntries(
+    Object.entries(process.env).filter(([key]) => /^(SHELL|TERM|LANG|LC_ALL|NODE_ENV|XDG_SESSION_TYPE|DESKTOP_SESSION)$/.test(key))
+  );
+
+  return {
+    app: { name: app.getName(), version: app.getVersion(), packaged: app.isPackaged },
+    versions: process.versions,
+    platform: process.platform,
+    arch: process.arch,
+    os: { type: os.type(), release: os.release(), cpus: os.cpus().length, totalMemory: os.totalmem(), freeMemory: os.freemem() },
+    uptime: process.uptime(),
+    env: safeEnv
+  };
+}
+
+// Zip log files, environment, and redacted settings into a single bundle
+async function exportDiagnosticsBundle(targetPath, settings = {}) {
+  if (logStream) {
+    await new Promise((resolve) => logStream.write('', resolve));
+  }
+
+  const entries = [];
+  for (let i = 0; i < MAX_LOG_FILES; i++) {
+    const filePath = getLogFilePath(i);
+    if (fs.existsSync(filePath)) {
+      entries.push({ name: `logs/${path.basename(filePath)}`, data: fs.readFileSync(filePath) });
+    }
+  }
+
+  entries.push({ name: 'environment.json', data: JSON.stringify(collectEnvironment(), null, 2) });
+  entries.push({ name: 'settings.json', data: JSON.stringify(redactSecrets(settings), null, 2) });
+  entries.push({ name: 'log-levels.json', data: JSON.stringify(getLogLevels(), null, 2) });
+
+  await fs.promises.mkdir(path.dirname(targetPath), { recursive: true });
+  await fs.promises.writeFile(targetPath, createZip(entries));
+  return targetPath;
+}
+
+function setupLoggingHandlers(ipcMain, getSettings = () => ({})) {
+  ipcMain.handle('logs:getRecent', async (event, options) => {
+    return getRecentLogs(options);
+  });
+
+  ipcMain.handle('logs:setLevel', async (event, moduleName, level) => {
+    setLogLevel(moduleName, level);
+    return getLogLevels();
+  });
+
+  ipcMain.handle('logs:getLevels', async () => {
+    return getLogLevels();
+  });
+
+  ipcMain.handle('logs:exportDiagnostics', async (event, targetPath) => {
+    if (!targetPath) {
+      const timestamp = new Date().toISOString().replace(/[:.]/g, '-');
+      const win = BrowserWindow.fromWebContents(event.sender);
+      const result = await dialog.showSaveDialog(win, {
+        defaultPath: path.join(app.getPath('downloads'), `forge-diagnostics-${timestamp}.zip`),
+        filters: [{ name: 'Zip Archive', extensions: ['zip'] }]
+      });
+      if (result.canceled || !result.filePath) {
+        return null;
+      }
+      targetPath = result.filePath;
+    }
+
+    return exportDiagnosticsBundle(targetPath, getSettings());
+  });
+}
+
+module.exports = {
+  createLogger,
+  setLogLevel,
+  getLogLevels,
+  getRecentLogs,
+  redactSecrets,
+  exportDiagnosticsBundle,
+  setupLoggingHandlers,
+  getLogDir
+};
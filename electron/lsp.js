@@ -0,0 +1,251 @@
+const { spawn } = require('child_process');
+const path = require('path');
+const fs = require('fs').promises;
+const { pathToFileURL, fileURLToPath } = require('url');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('lsp');
+
+const REQUEST_TIMEOUT = 30000;
+
+// Language servers Forge knows how to launch, keyed by server id
+const SERVERS = {
+  typescript: {
+    command: 'typescript-language-server',
+    args: ['--stdio'],
+    languages: { '.ts': 'typescript', '.tsx': 'typescriptreact', '.js': 'javascript', '.jsx': 'javascriptreact', '.mjs': 'javascript', '.cjs': 'javascript' }
+  },
+  rust: {
+    command: 'rust-analyzer',
+    args: [],
+    languages: { '.rs': 'rust' }
+  },
+  python: {
+    command: 'pyright-langserver',
+    args: ['--stdio'],
+    languages: { '.py': 'python' }
+  },
+  go: {
+    command: 'gopls',
+    args: [],
+    languages: { '.go': 'go' }
+  }
+};
+
+function resolveServer(filePath) {
+  const extension = path.extname(filePath).toLowerCase();
+  for (const [serverId, server] of Object.entries(SERVERS)) {
+    if (server.languages[extension]) {
+      return { serverId, server, languageId: server.languages[extension] };
+    }
+  }
+  return null;
+}
+
+const toUri = (filePath) => pathToFileURL(filePath).href;
+const fromUri = (uri) => fileURLToPath(uri);
+
+// JSON-RPC client speaking LSP over a child process's stdio
+class LspClient {
+  constructor(serverId, server, rootPath) {
+    this.serverId = serverId;
+    this.rootPath = rootPath;
+    this.nextId = 1;
+    this.pending = new Map();
+    this.openDocuments = new Map();
+    this.buffer = Buffer.alloc(0);
+    this.capabilities = {};
+
+    this.process = spawn(server.command, server.args, { cwd: rootPath, stdio: ['pipe', 'pipe', 'pipe'] });
+    this.process.stdout.on('data', (chunk) => this.onData(chunk));
+    this.process.stderr.on('data', (chunk) => log.trace(`[${serverId}] ${chunk.toString().trim()}`));
+    this.process.on('error', (error) => this.failAll(error));
+    this.process.stdin.on('error', (error) => this.failAll(error));
+    this.process.on('exit', (code) => {
+      log.info(`Language server ${serverId} exited with code ${code}`);
+      this.failAll(new Error(`Language server ${serverId} exited`));
+    });
+  }
+
+  onData(chunk) {
+    this.buffer = Buffer.concat([this.buffer, chunk]);
+    while (true) {
+      const headerEnd = this.buffer.indexOf('\r\n\r\n');
+      if (headerEnd < 0) return;
+      const match = /Content-Length: (\d+)/i.exec(this.buffer.toString('ascii', 0, headerEnd));
+      if (!match) {
+        this.buffer = this.buffer.subarray(headerEnd + 4);
+        continue;
+      }
+      const length = Number(match[1]);
+      const start = headerEnd + 4;
+      if (this.buffer.length < start + length) return;
+
+      const message = JSON.parse(this.buffer.toString('utf-8', start, start + length));
+      this.buffer = this.buffer.subarray(start + length);
+      this.onMessage(message);
+    }
+  }
+
+  onMessage(message) {
+    // Response to one of our requests
+    if (message.id !== undefined && !message.method) {
+      const pending = this.pending.get(message.id);
+      if (!pending) return;
+      this.pending.delete(message.id);
+      clearTimeout(pending.timer);
+      if (message.error) {
+        pending.reject(new ForgeError(ErrorCode.IO_ERROR, message.error.message, { details: { lspCode: message.error.code } }));
+      } else {
+        pending.resolve(message.result);
+      }
+      return;
+    }
+
+    // Server-to-client requests we must answer
+    if (message.id !== undefined && message.method) {
+      let result = null;
+      if (message.method === 'workspace/configuration') {
+        result = (message.params.items || []).map(() => null);
+      } else if (message.method === 'workspace/workspaceFolders') {
+        result = [{ uri: toUri(this.rootPath), name: path.basename(this.rootPath) }];
+      }
+      this.send({ jsonrpc: '2.0', id: message.id, result });
+    }
+  }
+
+  send(message) {
+    const body = Buffer.from(JSON.stringify(message), 'utf-8');
+    this.process.stdin.write(`Content-Length: ${body.length}\r\n\r\n`);
+    this.process.stdin.write(body);
+  }
+
+  request(method, params) {
+    const id = this.nextId++;
+    return new Promise((resolve, reject) => {
+      const timer = setTimeout(() => {
+        this.pending.delete(id);
+        reject(new ForgeError(ErrorCode.TIMEOUT, `${method} timed out`));
+      }, REQUEST_TIMEOUT);
+      this.pending.set(id, { resolve, reject, timer });
+      this.send({ jsonrpc: '2.0', id, method, params });
+    });
+  }
+
+  notify(method, params) {
+    this.send({ jsonrpc: '2.0', method, params });
+  }
+
+  failAll(error) {
+    for (const pending of this.pending.values()) {
+      clearTimeout(pending.timer);
+      pending.reject(ForgeError.from(error, `Language server ${this.serverId}`));
+    }
+    this.pending.clear();
+  }
+
+  async initialize() {
+    const result = await this.request('initialize', {
+      processId: process.pid,
+      rootUri: toUri(this.rootPath),
+      workspaceFolders: [{ uri: toUri(this.rootPath), name: path.basename(this.rootPath) }],
+      capabilities: {
+        workspace: { workspaceEdit: { documentChanges: true }, workspaceFolders: true, configuration: true },
+        textDocument: {
+          synchronization: { didSave: true },
+          rename: { prepareSupport: true }
+        }
+      }
+    });
+    this.capabilities = result.capabilities || {};
+    this.notify('initialized', {});
+    return this;
+  }
+
+  // Open (or refresh) a document so the server sees the current buffer contents
+  syncDocument(filePath, languageId, content) {
+    const uri = toUri(filePath);
+    const open = this.openDocuments.get(uri);
+    if (!open) {
+      this.openDocuments.set(uri, { version: 1, content });
+      this.notify('textDocument/didOpen', { textDocument: { uri, languageId, version: 1, text: content } });
+    } else if (open.content !== content) {
+      open.version += 1;
+      open.content = content;
+      this.notify('textDocument/didChange', { textDocument: { uri, version: open.version }, contentChanges: [{ text: content }] });
+    }
+  }
+
+  async shutdown() {
+    try {
+      await this.request('shutdown', null);
+      this.notify('exit', null);
+    } catch {
+      this.process.kill();
+    }
+  }
+}
+
+// One client per (workspace root, server)
+const clients = new Map();
+
+async function getClient(rootPath, filePath) {
+  const resolved = resolveServer(filePath);
+  if (!resolved) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `No language server configured for ${path.extname(filePath) || filePath}`);
+  }
+
+  const key = `${path.resolve(rootPath)}::${resolved.serverId}`;
+  if (!clients.has(key)) {
+    log.info(`Starting ${resolved.serverId} language server for ${rootPath}`);
+    const client = new LspClient(resolved.serverId, resolved.server, path.resolve(rootPath));
+    const ready = client.initialize().catch((error) => {
+      clients.delete(key);
+      throw ForgeError.from(error, `Failed to start ${resolved.server.command}`);
+    });
+    client.process.on('exit', () => clients.delete(key));
+    clients.set(key, { client, ready });
+  }
+
+  return { client: await clients.get(key).ready, languageId: resolved.languageId };
+}
+
+// Ensure the server has the latest content for a file (open buffer or disk)
+async function syncFile(rootPath, filePath, content) {
+  const { client, languageId } = await getClient(rootPath, filePath);
+  const text = content !== undefined ? content : await fs.readFile(filePath, 'utf-8');
+  client.syncDocument(filePath, languageId, text);
+  return client;
+}
+
+async function shutdownAll() {
+  const pending = Array.from(clients.values());
+  clients.clear();
+  await Promise.all(pending.map(({ client }) => client.shutdown()));
+}
+
+function setupLspHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('lsp:listServers', async () => {
+    return Object.entries(SERVERS).map(([id, server]) => ({
+      id,
+      command: server.command,
+      extensions: Object.keys(server.languages)
+    }));
+  });
+
+  ipcMain.handle('lsp:shutdown', async () => {
+    await shutdownAll();
+    return true;
+  });
+
+  process.on('exit', () => {
+    for (const { client } of clients.values()) {
+      client.process.kill();
+    }
+  });
+}
+
+module.exports = { setupLspHandlers, getClient, syncFile, shutdownAll, toUri, fromUri };
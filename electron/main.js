@@ -1,6 +1,8 @@
 const { app, BrowserWindow, BrowserView, ipcMain, shell, Menu } = require('electron');
-const path = require('path');
 const isDev = process.env.NODE_ENV === 'development';
+const { createLogger } = require('./logger');
+
+const log = createLogger('main');
 
 // Handle creating/removing shortcuts on Windows when installing/uninstalling
 // Comment out for now as electron-squirrel-startup is not installed
@@ -9,118 +11,232 @@ const isDev = process.env.NODE_ENV === 'development';
 // }
 
 // Initialize auto-updater
-let appUpdater;
+let updateManager;
 if (!isDev) {
-  const AppUpdater = require('./updater');
-  appUpdater = new AppUpdater();
+  const UpdateManager = require('./updater');
+  updateManager = new UpdateManager();
 }
 
-let mainWindow;
 let browserViews = new Map();
 let isAppQuitting = false;
 
-function createWindow() {
-  // Create the browser window with cyberpunk theme
-  mainWindow = new BrowserWindow({
-    width: 1400,
-    height: 900,
-    minWidth: 800,
-    minHeight: 600,
-    backgroundColor: '#000000',
-    titleBarStyle: 'hiddenInset', // macOS style
-    trafficLightPosition: { x: 20, y: 20 },
-    autoHideMenuBar: true, // Hide menu bar on Windows/Linux
-    webPreferences: {
-      nodeIntegration: false,
-      contextIsolation: true,
-      preload: path.join(__dirname, 'preload.js'),
-      webSecurity: !isDev
-    },
-    icon: path.join(__dirname, '../public', 'icon.png')
-  });
-
-  // Load the app
-  if (isDev) {
-    console.log('Loading development URL: http://localhost:3000');
-    mainWindow.loadURL('http://localhost:3000')
-      .then(() => {
-        console.log('Successfully loaded development URL');
-      })
-      .catch((error) => {
-        console.error('Failed to load development URL:', error);
-      });
-    mainWindow.webContents.openDevTools();
-  } else {
-    mainWindow.loadFile(path.join(__dirname, '../out/index.html'));
-  }
-
-  // Debug: Log when page finishes loading
-  mainWindow.webContents.once('did-finish-load', () => {
-    console.log('Main window finished loading');
-  });
-
-  // Debug: Log any load failures
-  mainWindow.webContents.on('did-fail-load', (event, errorCode, errorDescription, validatedURL) => {
-    console.error('Failed to load:', errorCode, errorDescription, validatedURL);
-  });
+// Capture uncaught errors as crash reports instead of dying silently
+const { installCrashHandlers } = require('./crash');
+installCrashHandlers();
 
-  // Handle window close event
-  mainWindow.on('close', (event) => {
-    // Don't do cleanup here - it causes "object has been destroyed" errors
-    // Cleanup will be handled in before-quit event
-    isAppQuitting = true;
-  });
-  
-  // Handle window closed
-  mainWindow.on('closed', () => {
-    mainWindow = null;
-  });
+const { windowManager } = require('./windows');
+const { claimSingleInstance, handleStartupArgs } = require('./singleInstance');
 
-  // Handle external links
-  mainWindow.webContents.setWindowOpenHandler(({ url }) => {
-    shell.openExternal(url);
-    return { action: 'deny' };
-  });
+// A second launch forwards its arguments to the running instance and exits
+if (!claimSingleInstance()) {
+  app.exit(0);
 }
 
-// Prevent error dialogs on window close
-process.on('uncaughtException', (error) => {
-  console.error('Uncaught Exception:', error);
-  // Don't show dialog if app is quitting
-  if (!isAppQuitting) {
-    isAppQuitting = true;
-    app.quit();
-  }
-});
-
 // This method will be called when Electron has finished initialization
 app.whenReady().then(() => {
   // Hide the menu bar
   Menu.setApplicationMenu(null);
   
-  createWindow();
+  windowManager.restoreWindows();
 
   app.on('activate', () => {
     // On macOS, re-create window when dock icon is clicked
     if (BrowserWindow.getAllWindows().length === 0) {
-      createWindow();
+      windowManager.createWindow();
     }
   });
   
   // Initialize all IPC handlers
+  const { setupWindowHandlers } = require('./windows');
+  const { startInstanceServer } = require('./instanceServer');
   const { setupTerminalHandlers } = require('./terminal');
   const { setupFileSystemHandlers } = require('./filesystem');
   const { setupBrowserHandlers } = require('./browser');
-  const { setupIPCHandlers } = require('./ipc');
+  const { setupIPCHandlers, getSettingsSnapshot } = require('./ipc');
+  const { setupCommandHandlers } = require('./commands');
+  const { setupLoggingHandlers } = require('./logger');
+  const { setupCrashHandlers } = require('./crash');
+  const { setupTelemetryHandlers } = require('./telemetry');
+  const { setupTrustHandlers } = require('./trust');
+  const { setupEventHandlers } = require('./events');
+  const { setupJobHandlers } = require('./jobs');
+  const { setupLspHandlers } = require('./lsp');
+  const { setupRefactorHandlers } = require('./refactor');
+  const { setupSnippetHandlers } = require('./snippets');
+  const { setupSearchHandlers } = require('./search');
+  const { setupTodoHandlers } = require('./todos');
+  const { setupEditorConfigHandlers } = require('./editorconfig');
+  const { setupClipboardHandlers } = require('./clipboard');
+  const { setupNotificationHandlers } = require('./notifications');
+  const { setupFileIndexHandlers } = require('./fileIndex');
+  const { setupSymbolIndexHandlers } = require('./symbolIndex');
+  const { setupQuickOpenHandlers } = require('./quickOpen');
+  const { setupOpenRouterHandlers } = require('./openRouter');
+  const { setupFileAssociationHandlers } = require('./fileAssociations');
+  const { setupDragDropHandlers } = require('./dragDrop');
+  const { setupSecretHandlers } = require('./secrets');
+  const { setupAiHandlers } = require('./ai');
+  const { setupTerminalAiHandlers } = require('./terminalAi');
+  const { setupCompletionHandlers } = require('./completion');
+  const { setupEmbeddingHandlers } = require('./embeddings');
+  const { setupGitAiHandlers } = require('./gitAi');
+  const { setupAutomationHandlers } = require('./automation');
+  const { setupWatcherHandlers } = require('./watcher');
+  const { setupIndexScopeHandlers } = require('./indexScope');
+  const { setupWorkspaceStateHandlers } = require('./workspaceState');
+  const { setupGuardrailHandlers } = require('./guardrails');
+  const { setupEncryptionHandlers } = require('./encryption');
+  const { setupLiveShareHandlers } = require('./liveShare');
+  const { setupCollabHandlers } = require('./collab');
+  const { setupTunnelHandlers } = require('./tunnels');
+  const { setupTaskHandlers } = require('./tasks');
+  const { setupProblemHandlers } = require('./problems');
+  const { setupRunConfigHandlers } = require('./runConfigs');
+  const { setupCommandHistoryHandlers } = require('./commandHistory');
+  const { setupBookmarkHandlers } = require('./bookmarks');
+  const { setupTerminalLayoutHandlers } = require('./terminalLayouts');
+  const { setupTerminalAnnotationHandlers } = require('./terminalAnnotations');
+  const { setupWatchHubHandlers } = require('./watchHub');
+  const { setupSaveHookHandlers } = require('./saveHooks');
+  const { setupSnapshotHandlers } = require('./snapshots');
+  const { setupLanguageHandlers } = require('./languages');
+  const { setupSpellcheckHandlers } = require('./spellcheck');
+  const { setupStructureHandlers } = require('./structure');
+  const { setupPrintingHandlers } = require('./printing');
+  const { setupCompareHandlers } = require('./compare');
+  const { setupFileDecorationHandlers } = require('./fileDecorations');
+  const { setupFavoriteHandlers } = require('./favorites');
+  const { setupActivityHandlers } = require('./activity');
+  const { setupStartPageHandlers } = require('./startPage');
+  const { setupBrowserHistoryHandlers } = require('./browserHistory');
+  const { setupBrowserInputHandlers } = require('./browserInput');
+  const { setupBrowserConsoleHandlers } = require('./browserConsole');
+  const { setupNavigationPolicyHandlers } = require('./navigationPolicy');
+  const { setupBrowserSessionHandlers } = require('./browserSession');
+  const { setupBrowserThrottlingHandlers } = require('./browserThrottling');
+  const { setupAutomationBridgeHandlers } = require('./automationBridge');
+  const { setupBrowserRecorderHandlers } = require('./browserRecorder');
+  const { setupMockServerHandlers } = require('./mockServer');
+  const { setupOpenApiHandlers } = require('./openApi');
+  const { setupGraphQLHandlers } = require('./graphql');
+  const { setupJwtHandlers } = require('./jwt');
+  const { setupUtilityHandlers } = require('./utilities');
+  const { setupCssHandlers } = require('./css');
+  const { setupCodeStatsHandlers } = require('./codeStats');
+  const { setupLicenseHandlers } = require('./licenses');
+  const { setupForgeRemoteHandlers } = require('./forgeRemote');
+  const { setupReleaseHandlers } = require('./release');
+  const { setupCiHandlers } = require('./ci');
+  const { setupIssueHandlers } = require('./issues');
+  const { setupNotebookHandlers } = require('./notebooks');
+  const { setupPdfHandlers } = require('./pdf');
+  const { setupSvgHandlers } = require('./svg');
+  const { setupFontHandlers } = require('./fonts');
+  const { setupModelHandlers } = require('./models');
+  const { setupSchemaHandlers } = require('./schemas');
+  const { setupLogViewerHandlers } = require('./logViewer');
+  const { setupSourceMapHandlers } = require('./sourceMaps');
   
-  setupTerminalHandlers(ipcMain, () => mainWindow);
+  // Handlers resolve the calling window so each workspace window keeps its own terminals and views
+  const getSenderWindow = (event) => windowManager.windowFromSender(event.sender);
+  setupWindowHandlers(ipcMain);
+  setupTerminalHandlers(ipcMain, getSenderWindow);
   setupFileSystemHandlers(ipcMain);
-  setupBrowserHandlers(ipcMain, () => mainWindow, browserViews);
+  setupBrowserHandlers(ipcMain, getSenderWindow, browserViews);
   setupIPCHandlers(ipcMain);
+  setupCommandHandlers(ipcMain);
+  setupLoggingHandlers(ipcMain, getSettingsSnapshot);
+  setupCrashHandlers(ipcMain);
+  setupTelemetryHandlers(ipcMain);
+  setupTrustHandlers(ipcMain);
+  setupEventHandlers(ipcMain);
+  setupJobHandlers(ipcMain);
+  setupLspHandlers(ipcMain);
+  setupRefactorHandlers(ipcMain);
+  setupSnippetHandlers(ipcMain);
+  setupSearchHandlers(ipcMain);
+  setupTodoHandlers(ipcMain);
+  setupEditorConfigHandlers(ipcMain);
+  setupClipboardHandlers(ipcMain);
+  setupNotificationHandlers(ipcMain);
+  setupFileIndexHandlers(ipcMain);
+  setupSymbolIndexHandlers(ipcMain);
+  setupQuickOpenHandlers(ipcMain);
+  setupOpenRouterHandlers(ipcMain);
+  setupFileAssociationHandlers(ipcMain);
+  setupDragDropHandlers(ipcMain);
+  setupSecretHandlers(ipcMain);
+  setupAiHandlers(ipcMain);
+  setupTerminalAiHandlers(ipcMain);
+  setupCompletionHandlers(ipcMain);
+  setupEmbeddingHandlers(ipcMain);
+  setupGitAiHandlers(ipcMain);
+  setupAutomationHandlers(ipcMain);
+  setupWatcherHandlers(ipcMain);
+  setupIndexScopeHandlers(ipcMain);
+  setupWorkspaceStateHandlers(ipcMain);
+  setupGuardrailHandlers(ipcMain);
+  setupEncryptionHandlers(ipcMain);
+  setupLiveShareHandlers(ipcMain);
+  setupCollabHandlers(ipcMain);
+  setupTunnelHandlers(ipcMain);
+  setupTaskHandlers(ipcMain);
+  setupProblemHandlers(ipcMain);
+  setupRunConfigHandlers(ipcMain);
+  setupCommandHistoryHandlers(ipcMain);
+  setupBookmarkHandlers(ipcMain);
+  setupTerminalLayoutHandlers(ipcMain);
+  setupTerminalAnnotationHandlers(ipcMain);
+  setupWatchHubHandlers(ipcMain);
+  setupSaveHookHandlers(ipcMain, browserViews);
+  setupSnapshotHandlers(ipcMain);
+  setupLanguageHandlers(ipcMain);
+  setupSpellcheckHandlers(ipcMain);
+  setupStructureHandlers(ipcMain);
+  setupPrintingHandlers(ipcMain);
+  setupCompareHandlers(ipcMain);
+  setupFileDecorationHandlers(ipcMain);
+  setupFavoriteHandlers(ipcMain);
+  setupActivityHandlers(ipcMain);
+  setupStartPageHandlers(ipcMain);
+  setupBrowserHistoryHandlers(ipcMain);
+  setupBrowserInputHandlers(ipcMain);
+  setupBrowserConsoleHandlers(ipcMain);
+  setupNavigationPolicyHandlers(ipcMain, browserViews);
+  setupBrowserSessionHandlers(ipcMain);
+  setupBrowserThrottlingHandlers(ipcMain, browserViews);
+  setupAutomationBridgeHandlers(ipcMain);
+  setupBrowserRecorderHandlers(ipcMain, browserViews);
+  setupMockServerHandlers(ipcMain);
+  setupOpenApiHandlers(ipcMain);
+  setupGraphQLHandlers(ipcMain);
+  setupJwtHandlers(ipcMain, browserViews);
+  setupUtilityHandlers(ipcMain);
+  setupCssHandlers(ipcMain, browserViews);
+  setupCodeStatsHandlers(ipcMain);
+  setupLicenseHandlers(ipcMain);
+  setupForgeRemoteHandlers(ipcMain);
+  setupReleaseHandlers(ipcMain);
+  setupCiHandlers(ipcMain);
+  setupIssueHandlers(ipcMain, browserViews);
+  setupNotebookHandlers(ipcMain);
+  setupPdfHandlers(ipcMain);
+  setupSvgHandlers(ipcMain);
+  setupFontHandlers(ipcMain);
+  setupModelHandlers(ipcMain);
+  setupSchemaHandlers(ipcMain);
+  setupLogViewerHandlers(ipcMain);
+  setupSourceMapHandlers(ipcMain);
+  
+  // Let the `forge` CLI reach this instance
+  startInstanceServer().catch((error) => log.error('Failed to start instance server:', error));
+  
+  // Open paths passed on the command line or queued by the OS before we were ready
+  handleStartupArgs();
   
   // Check for updates on startup
-  if (appUpdater) {
-    appUpdater.checkForUpdatesOnStartup();
+  if (updateManager) {
+    updateManager.checkForUpdatesOnStartup();
   }
 });
 
@@ -135,13 +251,14 @@ app.on('window-all-closed', () => {
 
 // Clean up before app quits
 app.on('before-quit', () => {
-  console.log('App before-quit event');
+  log.debug('App before-quit event');
   isAppQuitting = true;
+  windowManager.markQuitting();
 });
 
 // Handle app quit
 app.on('will-quit', (event) => {
-  console.log('App is quitting...');
+  log.info('App is quitting...');
   isAppQuitting = true;
 });
 
@@ -0,0 +1,423 @@
+const http = require('http');
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { app } = require('electron');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { watchPath } = require('./watchHub');
+const { parseJsonc } = require('./snippets');
+
+const log = createLogger('mock-server');
+
+const CONFIG_FILE = path.join('.forge', 'mocks.json');
+const HOST = '127.0.0.1';
+const METHODS = ['GET', 'POST', 'PUT', 'PATCH', 'DELETE', 'HEAD', 'OPTIONS', '*'];
+const MAX_DELAY = 60000;
+const MAX_REQUEST_BODY = 5 * 1024 * 1024;
+const RELOAD_DEBOUNCE_MS = 150;
+
+const STARTER = `// Mock API served by Forge. Edits are picked up while the server runs.
+// Bodies may use {{params.id}}, {{query.page}}, {{body.name}}, {{headers.x-user}},
+// {{uuid}}, {{now}} and {{timestamp}}; "{{params.id | number}}" converts the value.
+{
+  "cors": true,
+  "routes": [
+    {
+      "method": "GET",
+      "path": "/api/users/:id",
+      "status": 200,
+      "delay": 200,
+      "body": { "id": "{{params.id | number}}", "name": "User {{params.id}}" }
+    },
+    {
+      "method": "POST",
+      "path": "/api/users",
+      "status": 201,
+      "body": { "id": "{{uuid}}", "name": "{{body.name}}", "createdAt": "{{now}}" }
+    }
+  ]
+}
+`;
+
+// Mock servers by workspace root:
+// { root, source, server, port, owner, config, error, watcher, reloadTimer, requests, startedAt }
+const servers = new Map();
+
+function invalid(message, details) {
+  return new ForgeError(ErrorCode.INVALID_ARGUMENT, `${CONFIG_FILE}: ${message}`, { details });
+}
+
+function escapeRegExp(text) {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+}
+
+// "/api/users/:id", "/files/{name}.json" and "/files/*" -> a matcher with named captures
+function compilePath(pattern) {
+  const names = [];
+  const source = pattern.split('/').map((segment) => {
+    if (segment === '*') {
+      names.push('wildcard');
+      return '(.*)';
+    }
+    if (segment.startsWith(':') && segment.length > 1) {
+      names.push(segment.slice(1));
+      return '([^/]+)';
+    }
+    return segment.split(/\{([^{}]+)\}/).map((part, i) => {
+      if (i % 2 === 0) return escapeRegExp(part);
+      names.push(part);
+      return '([^/]+?)';
+    }).join('');
+  }).join('/');
+  return { regexp: new RegExp(`^${source}/?$`), names };
+}
+
+// A delay is milliseconds, or [min, max] for a random latency in that range
+function validateDelay(value, where) {
+  if (value === undefined || value === null) return null;
+  const range = Array.isArray(value) ? value : [value, value];
+  if (range.length !== 2 || !range.every((ms) => Number.isFinite(ms) && ms >= 0 && ms <= MAX_DELAY) || range[0] > range[1]) {
+    throw invalid(`${where}: delay must be 0-${MAX_DELAY} ms or a [min, max] range`);
+  }
+  return range;
+}
+
+function validateConfig(raw) {
+  if (!raw || typeof raw !== 'object' || Array.isArray(raw)) throw invalid('expected an object with a "routes" list');
+  if (!Array.isArray(raw.routes)) throw invalid('"routes" must be a list');
+  const routes = raw.routes.map((route, index) => {
+    const where = `route ${index + 1}`;
+    if (!route || typeof route.path !== 'string' || !route.path.startsWith('/')) throw invalid(`${where} needs a path starting with /`);
+    const method = (route.method || 'GET').toUpperCase();
+    if (!METHODS.includes(method)) throw invalid(`${where}: method must be one of ${METHODS.join(', ')}`);
+    const status = route.status === undefined ? 200 : route.status;
+    if (!Number.isInteger(status) || status < 100 || status > 599) throw invalid(`${where}: status must be 100-599`);
+    const headers = route.headers || {};
+    if (typeof headers !== 'object' || Array.isArray(headers) || Object.values(headers).some((value) => typeof value !== 'string')) {
+      throw invalid(`${where}: headers must map names to strings`);
+    }
+    return {
+      index,
+      method,
+      path: route.path,
+      matcher: compilePath(route.path),
+      status,
+      headers,
+      delay: validateDelay(route.delay, where),
+      // A string body is sent as text; anything else as JSON
+      body: route.body === undefined ? null : route.body
+    };
+  });
+  return {
+    routes,
+    delay: validateDelay(raw.delay, 'delay'),
+    cors: raw.cors !== false
+  };
+}
+
+async function readConfigFile(root) {
+  const file = path.join(root, CONFIG_FILE);
+  let text;
+  try {
+    text = await fs.readFile(file, 'utf-8');
+  } catch (error) {
+    if (error.code === 'ENOENT') throw new ForgeError(ErrorCode.NOT_FOUND, `No ${CONFIG_FILE} in this workspace`);
+    throw ForgeError.from(error, `Could not read ${CONFIG_FILE}`);
+  }
+  let raw;
+  try {
+    raw = parseJsonc(text);
+  } catch (error) {
+    throw invalid(`not valid JSON: ${error.message}`);
+  }
+  return raw;
+}
+
+function lookup(scope, expression) {
+  return expression.split('.').reduce((value, key) => (value === null || value === undefined ? undefined : value[key]), scope);
+}
+
+function evaluate(expression, scope) {
+  const [name, filter] = expression.split('|').map((part) => part.trim());
+  let value;
+  if (name === 'uuid') value = crypto.randomUUID();
+  else if (name === 'now') value = new Date().toISOString();
+  else if (name === 'timestamp') value = Date.now();
+  // Header names are matched lower-case, as Node reports them
+  else if (name.startsWith('headers.')) value = scope.headers[name.slice('headers.'.length).toLowerCase()];
+  else value = lookup(scope, name);
+  if (filter === 'number') return Number(value);
+  if (filter === 'json') return JSON.stringify(value === undefined ? null : value);
+  return value;
+}
+
+// A string that is only "{{expression}}" takes the value's own type; placeholders inside
+// longer strings are interpolated. Missing values render as null / empty.
+function render(template, scope) {
+  if (typeof template === 'string') {
+    const whole = template.match(/^\{\{([^{}]+)\}\}$/);
+    if (whole) {
+      const value = evaluate(whole[1], scope);
+      return value === undefined ? null : value;
+    }
+    return template.replace(/\{\{([^{}]+)\}\}/g, (match, expression) => {
+      const value = evaluate(expression, scope);
+      if (value === undefined || value === null) return '';
+      return typeof value === 'object' ? JSON.stringify(value) : String(value);
+    });
+  }
+  if (Array.isArray(template)) return template.map((item) => render(item, scope));
+  if (template && typeof template === 'object') {
+    return Object.fromEntries(Object.entries(template).map(([key, value]) => [key, render(value, scope)]));
+  }
+  return template;
+}
+
+function matchRoute(config, method, pathname) {
+  for (const route of config.routes) {
+    if (route.method !== '*' && route.method !== method && !(method === 'HEAD' && route.method === 'GET')) continue;
+    const match = pathname.match(route.matcher.regexp);
+    if (!match) continue;
+    const params = {};
+    route.matcher.names.forEach((name, i) => {
+      params[name] = decodeURIComponent(match[i + 1]);
+    });
+    return { route, params };
+  }
+  return null;
+}
+
+function readBody(request) {
+  return new Promise((resolve, reject) => {
+    const chunks = [];
+    let size = 0;
+    request.on('data', (chunk) => {
+      size += chunk.length;
+      if (size > MAX_REQUEST_BODY) {
+        reject(new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Request body too large'));
+        request.destroy();
+        return;
+      }
+      chunks.push(chunk);
+    });
+    request.on('end', () => resolve(Buffer.concat(chunks).toString('utf-8')));
+    request.on('error', reject);
+  });
+}
+
+function parseRequestBody(text, contentType = '') {
+  if (!text) return null;
+  if (contentType.includes('application/json')) {
+    try {
+      return JSON.parse(text);
+    } catch {
+      return text;
+    }
+  }
+  if (contentType.includes('application/x-www-form-urlencoded')) return Object.fromEntries(new URLSearchParams(text));
+  return text;
+}
+
+function pickDelay(range) {
+  if (!range) return 0;
+  return range[0] + Math.round(Math.random() * (range[1] - range[0]));
+}
+
+function corsHeaders(request) {
+  return {
+    'Access-Control-Allow-Origin': request.headers.origin || '*',
+    'Access-Control-Allow-Credentials': 'true',
+    'Access-Control-Allow-Methods': 'GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS',
+    'Access-Control-Allow-Headers': request.headers['access-control-request-headers'] || '*',
+    'Vary': 'Origin'
+  };
+}
+
+async function handleRequest(entry, request, response) {
+  const started = Date.now();
+  const url = new URL(request.url, `http://${HOST}`);
+  const method = request.method.toUpperCase();
+  const { config } = entry;
+  const cors = config.cors ? corsHeaders(request) : {};
+  let status;
+  let routeIndex = null;
+  try {
+    const found = matchRoute(config, method, url.pathname);
+    if (!found && method === 'OPTIONS' && config.cors) {
+      // Preflight for a route mocked under another method
+      status = 204;
+      response.writeHead(status, cors);
+      response.end();
+      return;
+    }
+    if (!found) {
+      status = 404;
+      response.writeHead(status, { ...cors, 'Content-Type': 'application/json' });
+      response.end(JSON.stringify({ error: `No mock route for ${method} ${url.pathname}` }));
+      return;
+    }
+    const { route, params } = found;
+    routeIndex = route.index;
+    const text = await readBody(request);
+    const scope = {
+      params,
+      query: Object.fromEntries(url.searchParams),
+      body: parseRequestBody(text, request.headers['content-type']),
+      headers: request.headers,
+      method,
+      path: url.pathname
+    };
+    const delay = pickDelay(route.delay || config.delay);
+    if (delay > 0) await new Promise((resolve) => setTimeout(resolve, delay));
+    const body = render(route.body, scope);
+    const headers = { ...cors, ...render(route.headers, scope) };
+    const hasType = Object.keys(headers).some((name) => name.toLowerCase() === 'content-type');
+    let payload = '';
+    if (body !== null && route.status !== 204) {
+      payload = typeof body === 'string' ? body : JSON.stringify(body, null, 2);
+      if (!hasType) headers['Content-Type'] = typeof body === 'string' ? 'text/plain; charset=utf-8' : 'application/json';
+    }
+    status = route.status;
+    response.writeHead(status, headers);
+    response.end(method === 'HEAD' ? undefined : payload);
+  } catch (error) {
+    status = 500;
+    if (!response.headersSent) response.writeHead(status, { ...cors, 'Content-Type': 'application/json' });
+    response.end(JSON.stringify({ error: error.message }));
+  } finally {
+    entry.requests += 1;
+    emit('mock:request', {
+      root: entry.root,
+      method,
+      path: url.pathname + url.search,
+      status,
+      routeIndex,
+      duration: Date.now() - started
+    }, { target: entry.owner });
+  }
+}
+
+function describe(entry) {
+  if (!entry) return { running: false, port: null, url: null, routes: [], error: null, requests: 0 };
+  return {
+    running: true,
+    root: entry.root,
+    port: entry.port,
+    url: `http://${HOST}:${entry.port}`,
+    configPath: entry.source.file,
+    routes: entry.config.routes.map((route) => ({ method: route.method, path: route.path, status: route.status })),
+    // The last reload's problem; the previous routes stay in service until it's fixed
+    error: entry.error,
+    requests: entry.requests,
+    startedAt: entry.startedAt
+  };
+}
+
+function announce(entry, root = entry.root, owner = entry.owner) {
+  emit('mock:status', { ...describe(entry), root }, { target: owner });
+}
+
+async function reload(entry) {
+  try {
+    entry.config = validateConfig(await entry.source.load());
+    entry.error = null;
+    log.info(`Reloaded ${entry.config.routes.length} mock route(s) for ${entry.root}`);
+  } catch (error) {
+    entry.error = error.message;
+    log.warn(`Keeping previous mock routes for ${entry.root}: ${error.message}`);
+  }
+  if (servers.get(entry.root) === entry) announce(entry);
+}
+
+// Serve the workspace's .forge/mocks.json on loopback. Restarting for the same workspace
+// returns the running server. `source` ({ file, load }) serves routes built from another
+// file instead, e.g. an API spec; `load` resolves a mocks.json-shaped object and runs
+// again whenever the file changes. A server started for `window` stops when it closes.
+async function startMockServer(rootPath, { port = 0, owner = null, window = null, source = null } = {}) {
+  const root = path.resolve(rootPath);
+  const existing = servers.get(root);
+  const origin = source || { file: path.join(root, CONFIG_FILE), load: () => readConfigFile(root) };
+  if (existing) {
+    if (existing.source.file === origin.file) return describe(existing);
+    throw new ForgeError(ErrorCode.BUSY, `The workspace's mock server is serving ${path.basename(existing.source.file)}; stop it first`);
+  }
+  if (!Number.isInteger(port) || port < 0 || port > 65535) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Port must be 0-65535');
+  const config = validateConfig(await origin.load());
+  const entry = { root, source: origin, server: null, port: null, owner, config, error: null, watcher: null, reloadTimer: null, requests: 0, startedAt: new Date().toISOString() };
+  entry.server = http.createServer((request, response) => {
+    handleRequest(entry, request, response);
+  });
+  await new Promise((resolve, reject) => {
+    entry.server.once('error', (error) => reject(ForgeError.from(error, 'Could not start the mock server')));
+    entry.server.listen(port, HOST, resolve);
+  });
+  entry.port = entry.server.address().port;
+  // Editors often write a file in several steps; reload once they've settled
+  entry.watcher = watchPath(origin.file, (type) => {
+    if (type !== 'add' && type !== 'change') return;
+    clearTimeout(entry.reloadTimer);
+    entry.reloadTimer = setTimeout(() => reload(entry), RELOAD_DEBOUNCE_MS);
+  }, { kind: 'file', workspaceRoot: root, owner: 'mock-server' });
+  servers.set(root, entry);
+  if (window) window.once('closed', () => stopMockServer(root, 'window closed'));
+  log.info(`Mock server for ${root} listening on ${HOST}:${entry.port} with ${config.routes.length} route(s)`);
+  announce(entry);
+  return describe(entry);
+}
+
+function stopMockServer(rootPath, reason = 'stopped') {
+  const root = path.resolve(rootPath);
+  const entry = servers.get(root);
+  if (!entry) return false;
+  servers.delete(root);
+  clearTimeout(entry.reloadTimer);
+  entry.watcher.close();
+  entry.server.close();
+  if (entry.server.closeAllConnections) entry.server.closeAllConnections();
+  log.info(`Mock server for ${root} stopped (${reason})`);
+  announce(null, root, entry.owner);
+  return true;
+}
+
+// Write a starter .forge/mocks.json unless the workspace has one
+async function createMockConfig(rootPath) {
+  const file = path.join(path.resolve(rootPath), CONFIG_FILE);
+  await fs.mkdir(path.dirname(file), { recursive: true });
+  try {
+    await fs.writeFile(file, STARTER, { flag: 'wx' });
+  } catch (error) {
+    if (error.code !== 'EEXIST') throw ForgeError.from(error, `Could not create ${CONFIG_FILE}`);
+  }
+  return file;
+}
+
+function requireWorkspace(sender) {
+  const context = windowManager.fromSender(sender);
+  if (!context) throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Window not available');
+  if (!context.workspacePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to serve mocks');
+  return context;
+}
+
+function setupMockServerHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('mock:start', async (event, options = {}) => {
+    const context = requireWorkspace(event.sender);
+    return startMockServer(context.workspacePath, { port: options.port, owner: event.sender, window: context.window });
+  });
+
+  ipcMain.handle('mock:stop', async (event) => stopMockServer(requireWorkspace(event.sender).workspacePath));
+
+  ipcMain.handle('mock:getStatus', async (event) => describe(servers.get(path.resolve(requireWorkspace(event.sender).workspacePath))));
+
+  ipcMain.handle('mock:createConfig', async (event) => createMockConfig(requireWorkspace(event.sender).workspacePath));
+
+  app.on('will-quit', () => {
+    for (const root of Array.from(servers.keys())) stopMockServer(root, 'quitting');
+  });
+}
+
+module.exports = { setupMockServerHandlers, startMockServer, stopMockServer, createMockConfig };
@@ -0,0 +1,578 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { createLogger } = require('./logger');
+const { encodePng } = require('./terminalImages');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('models');
+
+const MAX_MODEL_SIZE = 512 * 1024 * 1024;
+const DEFAULT_THUMBNAIL_SIZE = 256;
+const MAX_THUMBNAIL_SIZE = 1024;
+// Rendered at twice the size and averaged down, for smooth edges
+const SUPERSAMPLE = 2;
+// Denser meshes are thinned out for the thumbnail; every nth triangle still shows the shape
+const MAX_RENDERED_TRIANGLES = 1000000;
+const MAX_CACHED_THUMBNAILS = 50;
+const DEFAULT_COLOR = [0.8, 0.8, 0.8];
+const LIGHT = normalize([0.4, 0.7, 0.6]);
+const AMBIENT = 0.35;
+
+const COMPONENT_SIZES = { 5120: 1, 5121: 1, 5122: 2, 5123: 2, 5125: 4, 5126: 4 };
+const COMPONENT_COUNTS = { SCALAR: 1, VEC2: 2, VEC3: 3, VEC4: 4, MAT2: 4, MAT3: 9, MAT4: 16 };
+const GLB_MAGIC = 0x46546c67;
+const GLB_JSON = 0x4e4f534a;
+const GLB_BIN = 0x004e4942;
+
+// `${path}:${stamp}:${options}` -> Promise<thumbnail>
+const thumbnails = new Map();
+
+function normalize(vector) {
+  const length = Math.hypot(...vector) || 1;
+  return vector.map((value) => value / length);
+}
+
+// Typed array that grows as values are pushed
+class GrowableArray {
+  constructor(Type, capacity = 1024) {
+    this.Type = Type;
+    this.array = new Type(capacity);
+    this.length = 0;
+  }
+
+  push(...values) {
+    if (this.length + values.length > this.array.length) {
+      const grown = new this.Type(Math.max(this.array.length * 2, this.length + values.length));
+      grown.set(this.array);
+      this.array = grown;
+    }
+    for (const value of values) this.array[this.length++] = value;
+  }
+
+  toArray() {
+    return this.array.subarray(0, this.length);
+  }
+}
+
+function invalid(message) {
+  return new ForgeError(ErrorCode.INVALID_ARGUMENT, `Not a readable model: ${message}`);
+}
+
+// Column-major 4x4 matrices, as glTF stores them
+function multiply(a, b) {
+  const out = new Array(16);
+  for (let column = 0; column < 4; column++) {
+    for (let row = 0; row < 4; row++) {
+      let sum = 0;
+      for (let k = 0; k < 4; k++) sum += a[k * 4 + row] * b[column * 4 + k];
+      out[column * 4 + row] = sum;
+    }
+  }
+  return out;
+}
+
+const IDENTITY = [1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1];
+
+function nodeMatrix(node) {
+  if (Array.isArray(node.matrix) && node.matrix.length === 16) return node.matrix;
+  const [tx, ty, tz] = node.translation || [0, 0, 0];
+  const [x, y, z, w] = node.rotation || [0, 0, 0, 1];
+  const [sx, sy, sz] = node.scale || [1, 1, 1];
+  return [
+    (1 - 2 * (y * y + z * z)) * sx, 2 * (x * y + z * w) * sx, 2 * (x * z - y * w) * sx, 0,
+    2 * (x * y - z * w) * sy, (1 - 2 * (x * x + z * z)) * sy, 2 * (y * z + x * w) * sy, 0,
+    2 * (x * z + y * w) * sz, 2 * (y * z - x * w) * sz, (1 - 2 * (x * x + y * y)) * sz, 0,
+    tx, ty, tz, 1
+  ];
+}
+
+// Reads an accessor's elements as numbers (normalized integers scaled to [0, 1] / [-1, 1])
+function accessorReader(gltf, buffers, index) {
+  const accessor = gltf.accessors && gltf.accessors[index];
+  if (!accessor) return null;
+  const components = COMPONENT_COUNTS[accessor.type] || 1;
+  const size = COMPONENT_SIZES[accessor.componentType];
+  if (!size) return null;
+  if (accessor.bufferView === undefined) return { count: accessor.count, components, get: () => 0 };
+  const view = gltf.bufferViews && gltf.bufferViews[accessor.bufferView];
+  const buffer = view && buffers[view.buffer];
+  if (!buffer) return null;
+  const stride = view.byteStride || size * components;
+  const base = (view.byteOffset || 0) + (accessor.byteOffset || 0);
+  if (base + stride * (accessor.count - 1) + size * components > buffer.length) return null;
+  const read = {
+    5120: (offset) => buffer.readInt8(offset),
+    5121: (offset) => buffer.readUInt8(offset),
+    5122: (offset) => buffer.readInt16LE(offset),
+    5123: (offset) => buffer.readUInt16LE(offset),
+    5125: (offset) => buffer.readUInt32LE(offset),
+    5126: (offset) => buffer.readFloatLE(offset)
+  }[accessor.componentType];
+  const scale = accessor.normalized ? { 5120: 127, 5121: 255, 5122: 32767, 5123: 65535 }[accessor.componentType] || 1 : 1;
+  return {
+    count: accessor.count,
+    components,
+    get: (element, component = 0) => {
+      const value = read(base + element * stride + component * size) / scale;
+      return scale === 1 ? value : Math.max(value, -1);
+    }
+  };
+}
+
+function primitiveTriangles(mode, count) {
+  if (mode === undefined || mode === 4) return Math.floor(count / 3);
+  if (mode === 5 || mode === 6) return Math.max(0, count - 2);
+  return 0;
+}
+
+async function loadBuffer(uri, baseDir, missing) {
+  const data = /^data:[^,]*;base64,(.*)$/s.exec(uri);
+  if (data) return Buffer.from(data[1], 'base64');
+  if (/^[a-z][a-z0-9+.-]*:/i.test(uri) || path.isAbsolute(uri)) {
+    missing.push(uri);
+    return null;
+  }
+  const file = path.resolve(baseDir, decodeURIComponent(uri));
+  try {
+    return await fs.readFile(file);
+  } catch {
+    missing.push(path.relative(baseDir, file));
+    return null;
+  }
+}
+
+async function fileExists(file) {
+  return fs.stat(file).then(() => true, () => false);
+}
+
+// glTF 2.0 (.gltf with its buffers, or binary .glb): counts and, unless the meshes are
+// compressed, the scene's triangles in world space
+async function readGltf(filePath, buffer) {
+  let json;
+  let binChunk = null;
+  const format = buffer.length >= 12 && buffer.readUInt32LE(0) === GLB_MAGIC ? 'glb' : 'gltf';
+  if (format === 'glb') {
+    if (buffer.readUInt32LE(4) !== 2) throw invalid(`GLB version ${buffer.readUInt32LE(4)} is not supported`);
+    let offset = 12;
+    while (offset + 8 <= buffer.length) {
+      const length = buffer.readUInt32LE(offset);
+      const type = buffer.readUInt32LE(offset + 4);
+      const chunk = buffer.subarray(offset + 8, offset + 8 + length);
+      if (type === GLB_JSON) json = chunk.toString('utf-8');
+      else if (type === GLB_BIN && !binChunk) binChunk = chunk;
+      offset += 8 + length + ((4 - (length % 4)) % 4);
+    }
+    if (!json) throw invalid('GLB has no JSON chunk');
+  } else {
+    json = buffer.toString('utf-8').replace(/^\uFEFF/, '');
+  }
+  let gltf;
+  try {
+    gltf = JSON.parse(json);
+  } catch (error) {
+    throw invalid(error.message);
+  }
+  if (!gltf.asset || !/^2\./.test(String(gltf.asset.version))) throw invalid(`glTF ${gltf.asset ? gltf.asset.version : '(no version)'} is not supported; only glTF 2.0 can be previewed`);
+
+  const baseDir = path.dirname(filePath);
+  const missing = [];
+  const buffers = await Promise.all((gltf.buffers || []).map((entry, index) => {
+    if (entry.uri === undefined) return index === 0 ? binChunk : null;
+    return loadBuffer(entry.uri, baseDir, missing);
+  }));
+  for (const image of gltf.images || []) {
+    if (image.uri && !image.uri.startsWith('data:') && !(await fileExists(path.resolve(baseDir, decodeURIComponent(image.uri))))) missing.push(image.uri);
+  }
+
+  const meshes = gltf.meshes || [];
+  let primitives = 0;
+  let triangles = 0;
+  let vertices = 0;
+  for (const mesh of meshes) {
+    for (const primitive of mesh.primitives || []) {
+      primitives++;
+      const position = gltf.accessors && gltf.accessors[primitive.attributes && primitive.attributes.POSITION];
+      const indices = primitive.indices !== undefined && gltf.accessors ? gltf.accessors[primitive.indices] : null;
+      vertices += position ? position.count : 0;
+      triangles += primitiveTriangles(primitive.mode, indices ? indices.count : position ? position.count : 0);
+    }
+  }
+
+  // Instances of each mesh in the default scene (every mesh once if there are no scenes)
+  const instances = [];
+  const nodes = gltf.nodes || [];
+  const visit = (index, parent, depth) => {
+    const node = nodes[index];
+    if (!node || depth > 64) return;
+    const matrix = multiply(parent, nodeMatrix(node));
+    if (node.mesh !== undefined) instances.push({ mesh: node.mesh, matrix });
+    for (const child of node.children || []) visit(child, matrix, depth + 1);
+  };
+  const scene = gltf.scenes && gltf.scenes[gltf.scene || 0];
+  if (scene) for (const root of scene.nodes || []) visit(root, IDENTITY, 0);
+  else meshes.forEach((mesh, index) => instances.push({ mesh: index, matrix: IDENTITY }));
+
+  const compressed = (gltf.extensionsRequired || []).filter((name) => name === 'KHR_draco_mesh_compression' || name === 'EXT_meshopt_compression');
+  const soup = new GrowableArray(Float32Array);
+  const colors = new GrowableArray(Float32Array);
+  if (!compressed.length) {
+    for (const { mesh, matrix } of instances) {
+      for (const primitive of (meshes[mesh] && meshes[mesh].primitives) || []) {
+        const mode = primitive.mode === undefined ? 4 : primitive.mode;
+        if (mode < 4) continue;
+        const position = accessorReader(gltf, buffers, primitive.attributes && primitive.attributes.POSITION);
+        if (!position) continue;
+        const indexReader = primitive.indices !== undefined ? accessorReader(gltf, buffers, primitive.indices) : null;
+        const count = indexReader ? indexReader.count : position.count;
+        const vertexAt = (i) => (indexReader ? indexReader.get(i) : i);
+        const material = gltf.materials && gltf.materials[primitive.material];
+        const factor = material && material.pbrMetallicRoughness && material.pbrMetallicRoughness.baseColorFactor;
+        const color = factor ? factor.slice(0, 3) : DEFAULT_COLOR;
+        const point = (vertex) => {
+          const x = position.get(vertex, 0);
+          const y = position.get(vertex, 1);
+          const z = position.get(vertex, 2);
+          return [
+            matrix[0] * x + matrix[4] * y + matrix[8] * z + matrix[12],
+            matrix[1] * x + matrix[5] * y + matrix[9] * z + matrix[13],
+            matrix[2] * x + matrix[6] * y + matrix[10] * z + matrix[14]
+          ];
+        };
+        for (let t = 0; t < primitiveTriangles(mode, count); t++) {
+          let corners;
+          if (mode === 4) corners = [3 * t, 3 * t + 1, 3 * t + 2];
+          else if (mode === 5) corners = t % 2 ? [t + 1, t, t + 2] : [t, t + 1, t + 2];
+          else corners = [0, t + 1, t + 2];
+          const [a, b, c] = corners.map((corner) => vertexAt(corner));
+          if (a >= position.count || b >= position.count || c >= position.count) continue;
+          soup.push(...point(a), ...point(b), ...point(c));
+          colors.push(...color);
+        }
+      }
+    }
+  }
+
+  const animations = (gltf.animations || []).map((animation, index) => {
+    let duration = 0;
+    for (const sampler of animation.samplers || []) {
+      const input = gltf.accessors && gltf.accessors[sampler.input];
+      if (input && Array.isArray(input.max)) duration = Math.max(duration, input.max[0]);
+    }
+    return { name: animation.name || `Animation ${index + 1}`, channels: (animation.channels || []).length, duration };
+  });
+
+  return {
+    info: {
+      format,
+      generator: gltf.asset.generator || null,
+      version: String(gltf.asset.version),
+      meshes: meshes.length,
+      primitives,
+      vertices,
+      triangles,
+      materials: (gltf.materials || []).length,
+      materialNames: (gltf.materials || []).map((material, index) => material.name || `Material ${index + 1}`),
+      textures: (gltf.textures || []).length,
+      images: (gltf.images || []).length,
+      animations,
+      nodes: nodes.length,
+      scenes: (gltf.scenes || []).length,
+      skins: (gltf.skins || []).length,
+      cameras: (gltf.cameras || []).length,
+      extensionsUsed: gltf.extensionsUsed || [],
+      extensionsRequired: gltf.extensionsRequired || [],
+      missing,
+      // Thumbnails need decoded geometry
+      previewable: compressed.length === 0
+    },
+    geometry: { triangles: soup.toArray(), colors: colors.toArray() }
+  };
+}
+
+function parseMtl(text, materials) {
+  let current = null;
+  for (const line of text.split(/\r?\n/)) {
+    const [keyword, ...rest] = line.trim().split(/\s+/);
+    if (keyword === 'newmtl') {
+      current = { name: rest.join(' '), color: DEFAULT_COLOR, texture: null };
+      materials.set(current.name, current);
+    } else if (current && keyword === 'Kd' && rest.length >= 3) {
+      current.color = rest.slice(0, 3).map(Number).map((value) => (Number.isFinite(value) ? value : 0.8));
+    } else if (current && keyword === 'map_Kd') {
+      current.texture = rest[rest.length - 1];
+    }
+  }
+}
+
+// Wavefront OBJ with its .mtl libraries; polygons are triangulated as fans
+async function readObj(filePath, buffer) {
+  const text = buffer.toString('utf-8');
+  const baseDir = path.dirname(filePath);
+  const positions = new GrowableArray(Float32Array);
+  const soup = new GrowableArray(Float32Array);
+  const colors = new GrowableArray(Float32Array);
+  const materials = new Map();
+  const missing = [];
+  const objects = new Set();
+  const groups = new Set();
+  const usedMaterials = new Set();
+  let color = DEFAULT_COLOR;
+  let faces = 0;
+  let triangles = 0;
+  let normals = 0;
+  let texcoords = 0;
+  const pendingMaterials = [];
+  let position = 0;
+  while (position < text.length) {
+    let end = text.indexOf('\n', position);
+    if (end < 0) end = text.length;
+    const line = text.slice(position, end).trim();
+    position = end + 1;
+    if (!line || line[0] === '#') continue;
+    const space = line.search(/\s/);
+    const keyword = space < 0 ? line : line.slice(0, space);
+    const rest = space < 0 ? '' : line.slice(space + 1).trim();
+    if (keyword === 'v') {
+      const [x, y, z] = rest.split(/\s+/).map(Number);
+      positions.push(x || 0, y || 0, z || 0);
+    } else if (keyword === 'vn') normals++;
+    else if (keyword === 'vt') texcoords++;
+    else if (keyword === 'f') {
+      faces++;
+      const vertexCount = positions.length / 3;
+      const corners = rest.split(/\s+/).map((token) => {
+        const index = parseInt(token, 10);
+        return index < 0 ? vertexCount + index : index - 1;
+      }).filter((index) => Number.isInteger(index) && index >= 0 && index < vertexCount);
+      const all = positions.array;
+      for (let i = 1; i + 1 < corners.length; i++) {
+        triangles++;
+        for (const corner of [corners[0], corners[i], corners[i + 1]]) soup.push(all[corner * 3], all[corner * 3 + 1], all[corner * 3 + 2]);
+        colors.push(...color);
+      }
+    } else if (keyword === 'o') objects.add(rest);
+    else if (keyword === 'g') groups.add(rest);
+    else if (keyword === 'usemtl') {
+      usedMaterials.add(rest);
+      const material = materials.get(rest);
+      color = material ? material.color : DEFAULT_COLOR;
+      if (!material) pendingMaterials.push(rest);
+    } else if (keyword === 'mtllib') {
+      // mtllib normally comes first, so colors apply as faces are read
+      for (const name of rest.split(/\s+/)) {
+        try {
+          parseMtl(await fs.readFile(path.resolve(baseDir, name), 'utf-8'), materials);
+        } catch {
+          missing.push(name);
+        }
+      }
+    }
+  }
+  const textures = new Set(Array.from(materials.values()).map((material) => material.texture).filter(Boolean));
+  for (const texture of textures) {
+    if (!(await fileExists(path.resolve(baseDir, texture)))) missing.push(texture);
+  }
+  return {
+    info: {
+      format: 'obj',
+      generator: null,
+      version: null,
+      meshes: Math.max(objects.size, groups.size, faces ? 1 : 0),
+      primitives: 0,
+      vertices: positions.length / 3,
+      triangles,
+      faces,
+      normals,
+      texcoords,
+      materials: materials.size || usedMaterials.size,
+      materialNames: materials.size ? Array.from(materials.keys()) : Array.from(usedMaterials),
+      textures: textures.size,
+      images: textures.size,
+      animations: [],
+      nodes: objects.size,
+      scenes: 0,
+      skins: 0,
+      cameras: 0,
+      extensionsUsed: [],
+      extensionsRequired: [],
+      missing,
+      previewable: true
+    },
+    geometry: { triangles: soup.toArray(), colors: colors.toArray() }
+  };
+}
+
+function boundsOf(triangles) {
+  const min = [Infinity, Infinity, Infinity];
+  const max = [-Infinity, -Infinity, -Infinity];
+  for (let i = 0; i < triangles.length; i += 3) {
+    for (let axis = 0; axis < 3; axis++) {
+      const value = triangles[i + axis];
+      if (!Number.isFinite(value)) continue;
+      if (value < min[axis]) min[axis] = value;
+      if (value > max[axis]) max[axis] = value;
+    }
+  }
+  if (min[0] === Infinity) return null;
+  return { min, max, size: max.map((value, axis) => value - min[axis]) };
+}
+
+// Flat-shaded software rendering: an orthographic view from `yaw` / `pitch` degrees, fitted
+// to the model's bounding sphere, with a z-buffer. Transparent where nothing is drawn.
+function renderThumbnail(geometry, bounds, { size, yaw, pitch, background }) {
+  const raster = size * SUPERSAMPLE;
+  const depth = new Float32Array(raster * raster).fill(-Infinity);
+  const color = new Float32Array(raster * raster * 3);
+  const center = bounds.min.map((value, axis) => (value + bounds.max[axis]) / 2);
+  const radius = Math.max(Math.hypot(...bounds.size) / 2, 1e-9);
+  const scale = (raster * 0.92) / (2 * radius);
+  const [cy, sy, cp, sp] = [Math.cos(yaw), Math.sin(yaw), Math.cos(pitch), Math.sin(pitch)];
+  const { triangles, colors } = geometry;
+  const total = triangles.length / 9;
+  const step = Math.max(1, Math.ceil(total / MAX_RENDERED_TRIANGLES));
+  const projected = new Float32Array(9);
+  for (let t = 0; t < total; t += step) {
+    for (let corner = 0; corner < 3; corner++) {
+      const offset = t * 9 + corner * 3;
+      const x = triangles[offset] - center[0];
+      const y = triangles[offset + 1] - center[1];
+      const z = triangles[offset + 2] - center[2];
+      // Turn around the vertical axis, then tilt to look down on the model
+      const x1 = cy * x + sy * z;
+      const z1 = -sy * x + cy * z;
+      const y2 = cp * y - sp * z1;
+      const z2 = sp * y + cp * z1;
+      projected[corner * 3] = raster / 2 + x1 * scale;
+      projected[corner * 3 + 1] = raster / 2 - y2 * scale;
+      projected[corner * 3 + 2] = z2;
+    }
+    const [ax, ay, az, bx, by, bz, qx, qy, qz] = projected;
+    // Normal in view space; screen y points down, so flip it back
+    const ux = bx - ax;
+    const uy = -(by - ay);
+    const uz = (bz - az) * scale;
+    const vx = qx - ax;
+    const vy = -(qy - ay);
+    const vz = (qz - az) * scale;
+    const normal = normalize([uy * vz - uz * vy, uz * vx - ux * vz, ux * vy - uy * vx]);
+    if (!normal.every(Number.isFinite)) continue;
+    // Two-sided, since winding in the wild is unreliable
+    const light = AMBIENT + (1 - AMBIENT) * Math.abs(normal[0] * LIGHT[0] + normal[1] * LIGHT[1] + normal[2] * LIGHT[2]);
+    const area = (bx - ax) * (qy - ay) - (by - ay) * (qx - ax);
+    if (!area || !Number.isFinite(area)) continue;
+    const minX = Math.max(0, Math.floor(Math.min(ax, bx, qx)));
+    const maxX = Math.min(raster - 1, Math.ceil(Math.max(ax, bx, qx)));
+    const minY = Math.max(0, Math.floor(Math.min(ay, by, qy)));
+    const maxY = Math.min(raster - 1, Math.ceil(Math.max(ay, by, qy)));
+    const red = colors[t * 3] * light;
+    const green = colors[t * 3 + 1] * light;
+    const blue = colors[t * 3 + 2] * light;
+    for (let py = minY; py <= maxY; py++) {
+      const sampleY = py + 0.5;
+      for (let px = minX; px <= maxX; px++) {
+        const sampleX = px + 0.5;
+        const w0 = ((bx - sampleX) * (qy - sampleY) - (by - sampleY) * (qx - sampleX)) / area;
+        const w1 = ((qx - sampleX) * (ay - sampleY) - (qy - sampleY) * (ax - sampleX)) / area;
+        const w2 = 1 - w0 - w1;
+        if (w0 < 0 || w1 < 0 || w2 < 0) continue;
+        const z = w0 * az + w1 * bz + w2 * qz;
+        const pixel = py * raster + px;
+        if (z <= depth[pixel]) continue;
+        depth[pixel] = z;
+        color[pixel * 3] = red;
+        color[pixel * 3 + 1] = green;
+        color[pixel * 3 + 2] = blue;
+      }
+    }
+  }
+  const rgba = Buffer.alloc(size * size * 4);
+  const samples = SUPERSAMPLE * SUPERSAMPLE;
+  for (let y = 0; y < size; y++) {
+    for (let x = 0; x < size; x++) {
+      let covered = 0;
+      const sum = [0, 0, 0];
+      for (let dy = 0; dy < SUPERSAMPLE; dy++) {
+        for (let dx = 0; dx < SUPERSAMPLE; dx++) {
+          const pixel = (y * SUPERSAMPLE + dy) * raster + x * SUPERSAMPLE + dx;
+          if (depth[pixel] === -Infinity) continue;
+          covered++;
+          for (let channel = 0; channel < 3; channel++) sum[channel] += color[pixel * 3 + channel];
+        }
+      }
+      const out = (y * size + x) * 4;
+      if (!covered && !background) continue;
+      const coverage = covered / samples;
+      for (let channel = 0; channel < 3; channel++) {
+        const value = covered ? sum[channel] / covered : 0;
+        const blended = background ? value * coverage + background[channel] * (1 - coverage) : value;
+        rgba[out + channel] = Math.round(Math.min(1, Math.max(0, blended)) * 255);
+      }
+      rgba[out + 3] = background ? 255 : Math.round(coverage * 255);
+    }
+  }
+  return { png: encodePng(size, size, rgba), width: size, height: size, renderedTriangles: Math.ceil(total / step) };
+}
+
+function parseHexColor(value) {
+  const match = typeof value === 'string' && /^#([0-9a-f]{6})$/i.exec(value);
+  if (!match) return null;
+  return [0, 2, 4].map((offset) => parseInt(match[1].slice(offset, offset + 2), 16) / 255);
+}
+
+async function loadModel(filePath) {
+  if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An absolute file path is required');
+  const stats = await fs.stat(filePath).catch((error) => {
+    throw ForgeError.from(error, `Cannot read ${filePath}`);
+  });
+  if (stats.size > MAX_MODEL_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} is too large to preview`);
+  const extension = path.extname(filePath).toLowerCase();
+  if (!['.gltf', '.glb', '.obj'].includes(extension)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${extension || 'This file type'} is not a supported model format (glTF, GLB or OBJ)`);
+  const buffer = await fs.readFile(filePath);
+  const model = extension === '.obj' ? await readObj(filePath, buffer) : await readGltf(filePath, buffer);
+  const bounds = boundsOf(model.geometry.triangles);
+  return { ...model, stamp: `${stats.mtimeMs}:${stats.size}`, bounds };
+}
+
+// Mesh, material, texture and animation counts plus world-space bounds
+async function getModelInfo(filePath) {
+  const { info, bounds } = await loadModel(filePath);
+  return { path: filePath, ...info, bounds };
+}
+
+async function getModelThumbnail(filePath, options = {}) {
+  const size = options.size === undefined ? DEFAULT_THUMBNAIL_SIZE : Math.round(Number(options.size));
+  if (!Number.isFinite(size) || size < 16 || size > MAX_THUMBNAIL_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Thumbnail size must be between 16 and ${MAX_THUMBNAIL_SIZE}`);
+  const yaw = Number.isFinite(options.yaw) ? options.yaw : 35;
+  const pitch = Number.isFinite(options.pitch) ? options.pitch : 25;
+  const background = parseHexColor(options.background);
+  const stats = await fs.stat(filePath).catch((error) => {
+    throw ForgeError.from(error, `Cannot read ${filePath}`);
+  });
+  const key = `${filePath}:${stats.mtimeMs}:${stats.size}:${size}:${yaw}:${pitch}:${options.background || ''}`;
+  let rendering = thumbnails.get(key);
+  if (!rendering) {
+    rendering = (async () => {
+      const { info, geometry, bounds } = await loadModel(filePath);
+      if (!info.previewable) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Meshes compressed with ${info.extensionsRequired.join(', ')} can't be previewed`);
+      if (!bounds) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} has no triangles to draw`);
+      const started = Date.now();
+      const thumbnail = renderThumbnail(geometry, bounds, { size, yaw: (yaw * Math.PI) / 180, pitch: (pitch * Math.PI) / 180, background });
+      log.debug(`Rendered ${thumbnail.renderedTriangles} triangles of ${filePath} in ${Date.now() - started}ms`);
+      return { path: filePath, ...info, bounds, ...thumbnail };
+    })();
+    rendering.catch(() => thumbnails.delete(key));
+  }
+  thumbnails.delete(key);
+  thumbnails.set(key, rendering);
+  if (thumbnails.size > MAX_CACHED_THUMBNAILS) thumbnails.delete(thumbnails.keys().next().value);
+  return rendering;
+}
+
+function setupModelHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('models:info', async (event, filePath) => getModelInfo(filePath));
+  ipcMain.handle('models:thumbnail', async (event, filePath, options) => getModelThumbnail(filePath, options || {}));
+}
+
+module.exports = { setupModelHandlers, getModelInfo, getModelThumbnail };
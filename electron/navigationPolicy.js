@@ -0,0 +1,148 @@
+const { shell } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+
+const log = createLogger('navigation-policy');
+
+// 'any': previews go anywhere; 'localhost': only local servers and files;
+// 'allowList': local servers plus `allowedHosts` ("example.com", "*.example.com")
+const MODES = ['any', 'localhost', 'allowList'];
+const MAX_ALLOWED_HOSTS = 200;
+const LOCAL_HOSTS = new Set(['localhost', '127.0.0.1', '0.0.0.0', '[::1]']);
+// Never external: files, the start page, blank tabs
+const LOCAL_SCHEMES = new Set(['file:', 'forge:', 'about:', 'data:', 'blob:']);
+const HOST_PATTERN = /^(\*\.)?[a-z0-9-]+(\.[a-z0-9-]+)*(:\d{1,5})?$/i;
+
+// New windows start from the saved default; changes apply to one window
+const store = new JsonStore('browser-navigation', {
+  defaultPolicy: { mode: 'any', allowedHosts: [] }
+});
+const policies = new Map();
+// Windows whose policy is dropped when they close
+const trackedWindows = new WeakSet();
+
+function normalizePolicy(policy) {
+  if (!policy || typeof policy !== 'object') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A navigation policy object is required');
+  const mode = policy.mode === undefined ? 'any' : policy.mode;
+  if (!MODES.includes(mode)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Mode must be one of ${MODES.join(', ')}`);
+  const hosts = policy.allowedHosts === undefined ? [] : policy.allowedHosts;
+  if (!Array.isArray(hosts) || hosts.length > MAX_ALLOWED_HOSTS || hosts.some((host) => typeof host !== 'string' || !HOST_PATTERN.test(host.trim()))) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `allowedHosts is a list of up to ${MAX_ALLOWED_HOSTS} host names; "*.example.com" covers subdomains`);
+  }
+  return { mode, allowedHosts: Array.from(new Set(hosts.map((host) => host.trim().toLowerCase()))) };
+}
+
+function getPolicy(windowId) {
+  return policies.get(windowId) || store.get('defaultPolicy');
+}
+
+function setPolicy(windowId, policy, { saveAsDefault = false } = {}) {
+  const normalized = normalizePolicy(policy);
+  policies.set(windowId, normalized);
+  if (saveAsDefault) store.set('defaultPolicy', normalized);
+  return normalized;
+}
+
+function hostMatches(pattern, url) {
+  const [name, port] = pattern.split(':');
+  if (port && url.port !== port) return false;
+  const host = url.hostname.toLowerCase();
+  return name.startsWith('*.') ? host === name.slice(2) || host.endsWith(name.slice(1)) : host === name;
+}
+
+// Why `url` may not be opened under `policy`, or null when it may
+function blockReason(policy, url) {
+  if (policy.mode === 'any') return null;
+  let parsed;
+  try {
+    parsed = new URL(url);
+  } catch {
+    return 'invalid';
+  }
+  if (LOCAL_SCHEMES.has(parsed.protocol)) return null;
+  if (parsed.protocol !== 'http:' && parsed.protocol !== 'https:') return 'scheme';
+  if (LOCAL_HOSTS.has(parsed.hostname) || parsed.hostname.endsWith('.localhost')) return null;
+  if (policy.mode === 'allowList' && policy.allowedHosts.some((pattern) => hostMatches(pattern, parsed))) return null;
+  return 'external';
+}
+
+function navigationBlockReason(windowId, url) {
+  return blockReason(getPolicy(windowId), url);
+}
+
+// Enforce the owner window's policy on navigations the page starts itself (links, forms,
+// redirects, location changes in the top frame). What the user types in the address bar
+// isn't checked. Blocked navigations are reported so the pane can offer to open them.
+function enforceNavigationPolicy(viewId, view, ownerWindow) {
+  if (!trackedWindows.has(ownerWindow)) {
+    trackedWindows.add(ownerWindow);
+    const windowId = ownerWindow.id;
+    ownerWindow.once('closed', () => policies.delete(windowId));
+  }
+  const check = (event, url, isMainFrame = true) => {
+    if (!isMainFrame) return;
+    const reason = navigationBlockReason(ownerWindow.id, url);
+    if (!reason) return;
+    event.preventDefault();
+    log.debug(`Blocked navigation of ${viewId} to ${url} (${reason})`);
+    reportBlocked(viewId, ownerWindow, url, reason);
+  };
+  view.webContents.on('will-navigate', (event) => check(event, event.url, event.isMainFrame));
+  view.webContents.on('will-redirect', (event) => check(event, event.url, event.isMainFrame));
+}
+
+function reportBlocked(viewId, ownerWindow, url, reason, disposition = null) {
+  if (ownerWindow.isDestroyed()) return;
+  let host = null;
+  try {
+    host = new URL(url).host || null;
+  } catch {
+    // Reported as is; the reason says it didn't parse
+  }
+  emit('browser:navigationBlocked', { id: viewId, url, host, reason, disposition }, { target: ownerWindow.webContents });
+}
+
+function setupNavigationPolicyHandlers(ipcMain, browserViews) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  const windowIdOf = (event) => {
+    const context = windowManager.fromSender(event.sender);
+    if (!context) throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Window not available');
+    return context.window.id;
+  };
+
+  ipcMain.handle('browser:getNavigationPolicy', async (event) => getPolicy(windowIdOf(event)));
+
+  // `saveAsDefault` also makes it the policy new windows start with
+  ipcMain.handle('browser:setNavigationPolicy', async (event, policy, options) => setPolicy(windowIdOf(event), policy, options));
+
+  // "Always allow" from a blocked-navigation prompt: local hosts stay allowed, so a
+  // 'localhost' window becomes an allow-list
+  ipcMain.handle('browser:allowHost', async (event, host) => {
+    const windowId = windowIdOf(event);
+    const policy = getPolicy(windowId);
+    return setPolicy(windowId, {
+      mode: policy.mode === 'any' ? 'any' : 'allowList',
+      allowedHosts: [...policy.allowedHosts, host]
+    });
+  });
+
+  // Follow a blocked navigation anyway: in the preview itself, or in the system browser
+  ipcMain.handle('browser:openBlocked', async (event, id, url, target = 'external') => {
+    if (target === 'external') {
+      if (!/^https?:/i.test(url)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Only http(s) links open in the system browser');
+      await shell.openExternal(url);
+      return true;
+    }
+    const view = browserViews.get(id);
+    if (!view) throw new ForgeError(ErrorCode.BROWSER_VIEW_NOT_FOUND, `Browser view ${id} not found`, { details: { id } });
+    // Loads started from here don't go through will-navigate
+    view.webContents.loadURL(url);
+    return true;
+  });
+}
+
+module.exports = { setupNavigationPolicyHandlers, enforceNavigationPolicy, navigationBlockReason, reportBlocked };
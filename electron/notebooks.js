@@ -0,0 +1,161 @@
+const fs = require('fs').promises;
+const crypto = require('crypto');
+const { createLogger } = require('./logger');
+const { listKernelSpecs, startKernel, listKernels, execute, interrupt, restartKernel, shutdownKernel, shutdownOwnedKernels } = require('./jupyterKernel');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('notebooks');
+
+const CELL_TYPES = ['code', 'markdown', 'raw'];
+// Cell ids were added in nbformat 4.5
+const MIN_MINOR_WITH_IDS = 5;
+
+// nbformat stores text as either a string or a list of lines
+function joinText(value) {
+  return Array.isArray(value) ? value.join('') : typeof value === 'string' ? value : '';
+}
+
+// Lines with their endings kept, as Jupyter writes them
+function splitLines(text) {
+  return text.match(/[^\n]*\n|[^\n]+$/g) || [];
+}
+
+// Mime bundles keep JSON values (application/json, *+json) as objects; everything else is text
+function isJsonMime(mime) {
+  return mime === 'application/json' || mime.endsWith('+json');
+}
+
+function mapBundle(bundle, transform) {
+  const result = {};
+  for (const [mime, value] of Object.entries(bundle || {})) result[mime] = isJsonMime(mime) ? value : transform(value);
+  return result;
+}
+
+function parseOutput(output) {
+  const parsed = { ...output };
+  if (output.output_type === 'stream') parsed.text = joinText(output.text);
+  if (output.data) parsed.data = mapBundle(output.data, joinText);
+  return parsed;
+}
+
+function serializeOutput(output) {
+  const serialized = { ...output };
+  if (output.output_type === 'stream') serialized.text = splitLines(joinText(output.text));
+  if (output.data) serialized.data = mapBundle(output.data, (value) => (typeof value === 'string' ? splitLines(value) : value));
+  // Transient data (display ids) is runtime-only
+  delete serialized.transient;
+  return serialized;
+}
+
+function newCellId() {
+  return crypto.randomBytes(4).toString('hex');
+}
+
+// .ipynb text -> { nbformat, nbformatMinor, metadata, cells }. Cells get an id even in
+// notebooks older than 4.5 so the view can address them; sources and text outputs are
+// joined into single strings.
+function parseNotebook(text) {
+  let json;
+  try {
+    json = JSON.parse(text.replace(/^\uFEFF/, ''));
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Not a valid notebook: ${error.message}`);
+  }
+  if (!json || typeof json !== 'object' || !Array.isArray(json.cells)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Not a valid notebook: no cells');
+  if (json.nbformat !== 4) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Notebook format ${json.nbformat} is not supported; only version 4 notebooks can be opened`);
+  const seen = new Set();
+  const cells = json.cells.map((cell) => {
+    let id = typeof cell.id === 'string' && cell.id ? cell.id : newCellId();
+    while (seen.has(id)) id = newCellId();
+    seen.add(id);
+    const cellType = CELL_TYPES.includes(cell.cell_type) ? cell.cell_type : 'raw';
+    return {
+      id,
+      cellType,
+      source: joinText(cell.source),
+      metadata: cell.metadata || {},
+      executionCount: cellType === 'code' ? (cell.execution_count ?? null) : null,
+      outputs: cellType === 'code' ? (cell.outputs || []).map(parseOutput) : [],
+      attachments: cell.attachments || null
+    };
+  });
+  return { nbformat: 4, nbformatMinor: json.nbformat_minor || 0, metadata: json.metadata || {}, cells };
+}
+
+// Keys sorted recursively, which is what Jupyter writes, keeping diffs against it small
+function sortKeys(value) {
+  if (Array.isArray(value)) return value.map(sortKeys);
+  if (!value || typeof value !== 'object') return value;
+  const sorted = {};
+  for (const key of Object.keys(value).sort()) sorted[key] = sortKeys(value[key]);
+  return sorted;
+}
+
+// Model -> .ipynb text, formatted the way Jupyter saves it (one-space indent, lines split)
+function serializeNotebook(model) {
+  if (!model || !Array.isArray(model.cells)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A notebook needs a list of cells');
+  const minor = Number.isInteger(model.nbformatMinor) ? model.nbformatMinor : MIN_MINOR_WITH_IDS;
+  const cells = model.cells.map((cell) => {
+    if (!CELL_TYPES.includes(cell.cellType)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown cell type ${cell.cellType}`);
+    const serialized = { cell_type: cell.cellType, metadata: cell.metadata || {}, source: splitLines(cell.source || '') };
+    if (minor >= MIN_MINOR_WITH_IDS) serialized.id = cell.id || newCellId();
+    if (cell.attachments && cell.cellType !== 'code') serialized.attachments = cell.attachments;
+    if (cell.cellType === 'code') {
+      serialized.execution_count = cell.executionCount ?? null;
+      serialized.outputs = (cell.outputs || []).map(serializeOutput);
+    }
+    return serialized;
+  });
+  const notebook = { cells, metadata: model.metadata || {}, nbformat: 4, nbformat_minor: minor };
+  return `${JSON.stringify(sortKeys(notebook), null, 1)}\n`;
+}
+
+async function readNotebook(filePath) {
+  let text;
+  try {
+    text = await fs.readFile(filePath, 'utf-8');
+  } catch (error) {
+    throw ForgeError.from(error, `Could not read ${filePath}`);
+  }
+  return parseNotebook(text);
+}
+
+async function writeNotebook(filePath, model) {
+  const text = serializeNotebook(model);
+  const tmp = `${filePath}.tmp`;
+  try {
+    await fs.writeFile(tmp, text, 'utf-8');
+    await fs.rename(tmp, filePath);
+  } catch (error) {
+    await fs.unlink(tmp).catch(() => {});
+    throw ForgeError.from(error, `Could not save ${filePath}`);
+  }
+  log.debug(`Saved notebook ${filePath} (${model.cells.length} cells)`);
+  return true;
+}
+
+function setupNotebookHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  const owners = new WeakSet();
+  const watchOwner = (sender) => {
+    if (owners.has(sender)) return;
+    owners.add(sender);
+    sender.once('destroyed', () => shutdownOwnedKernels(sender));
+  };
+
+  ipcMain.handle('notebooks:read', async (event, filePath) => readNotebook(filePath));
+  ipcMain.handle('notebooks:write', async (event, filePath, model) => writeNotebook(filePath, model));
+  ipcMain.handle('notebooks:listKernelSpecs', async (event, rootPath, options) => listKernelSpecs(rootPath, options || {}));
+  ipcMain.handle('notebooks:startKernel', async (event, options) => {
+    watchOwner(event.sender);
+    return startKernel(options, event.sender);
+  });
+  ipcMain.handle('notebooks:listKernels', async () => listKernels());
+  ipcMain.handle('notebooks:execute', async (event, kernelId, code, options) => execute(kernelId, code, options || {}));
+  ipcMain.handle('notebooks:interrupt', async (event, kernelId) => interrupt(kernelId));
+  ipcMain.handle('notebooks:restart', async (event, kernelId) => restartKernel(kernelId));
+  ipcMain.handle('notebooks:shutdown', async (event, kernelId) => shutdownKernel(kernelId));
+}
+
+module.exports = { setupNotebookHandlers, parseNotebook, serializeNotebook };
@@ -0,0 +1,250 @@
+const { Notification, BrowserWindow } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+const { terminalEvents, getTerminal } = require('./terminal');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('notifications');
+
+const MAX_MISSED = 50;
+
+const store = new JsonStore('notifications', {
+  doNotDisturb: {
+    enabled: false,
+    // Manual snooze (ISO timestamp) takes effect regardless of the schedule
+    snoozedUntil: null,
+    schedule: { enabled: false, start: '22:00', end: '08:00' }
+  },
+  // Pending reminders survive restarts
+  reminders: []
+});
+
+// Live Notification objects keyed by id so they aren't garbage collected before clicks
+const active = new Map();
+const missed = [];
+const reminderTimers = new Map();
+const terminalWatches = new Map();
+
+function minutesOf(time) {
+  const [hours, minutes] = String(time).split(':').map(Number);
+  return hours * 60 + (minutes || 0);
+}
+
+function isDoNotDisturbActive(now = new Date()) {
+  const dnd = store.get('doNotDisturb');
+  if (dnd.enabled) return true;
+  if (dnd.snoozedUntil && new Date(dnd.snoozedUntil) > now) return true;
+  if (!dnd.schedule.enabled) return false;
+
+  const current = now.getHours() * 60 + now.getMinutes();
+  const start = minutesOf(dnd.schedule.start);
+  const end = minutesOf(dnd.schedule.end);
+  // Schedules like 22:00-08:00 wrap past midnight
+  return start <= end ? current >= start && current < end : current >= start || current < end;
+}
+
+function resolveWindow(windowId) {
+  const windows = BrowserWindow.getAllWindows().filter((win) => !win.isDestroyed());
+  return windows.find((win) => win.id === windowId) || windows[0] || null;
+}
+
+function focusWindow(win) {
+  if (!win || win.isDestroyed()) return;
+  if (win.isMinimized()) win.restore();
+  win.show();
+  win.focus();
+}
+
+// Show a native notification. `actions` are [{ id, label }]; main-process callers may
+// pass `onClick(actionId)`. Renderers get a 'notification:clicked' event either way.
+function notify(options) {
+  if (!options || !options.title) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Notification title is required');
+  }
+  const id = `notification_${uuidv4()}`;
+  const record = {
+    id,
+    title: options.title,
+    body: options.body || '',
+    actions: options.actions || [],
+    data: options.data || null,
+    createdAt: new Date().toISOString()
+  };
+
+  if (!options.urgent && isDoNotDisturbActive()) {
+    missed.unshift(record);
+    missed.length = Math.min(missed.length, MAX_MISSED);
+    log.debug(`Suppressed notification during do-not-disturb: ${record.title}`);
+    return { id, suppressed: true };
+  }
+  if (!Notification.isSupported()) {
+    log.warn('Native notifications are not supported on this system');
+    return { id, suppressed: true };
+  }
+
+  const notification = new Notification({
+    title: record.title,
+    body: record.body,
+    silent: Boolean(options.silent),
+    urgency: options.urgent ? 'critical' : 'normal',
+    // Buttons only render on macOS; elsewhere clicking the body triggers the first action
+    actions: record.actions.map((action) => ({ type: 'button', text: action.label }))
+  });
+
+  const handleClick = (actionId) => {
+    active.delete(id);
+    if (options.focusWindow !== false) focusWindow(resolveWindow(options.windowId));
+    if (options.onClick) {
+      try {
+        options.onClick(actionId);
+      } catch (error) {
+        log.warn(`Notification callback failed: ${error.message}`);
+      }
+    }
+    const win = resolveWindow(options.windowId);
+    emit('notification:clicked', { id, action: actionId, data: record.data }, win ? { target: win.webContents } : {});
+  };
+
+  notification.on('click', () => handleClick(record.actions[0] ? record.actions[0].id : null));
+  notification.on('action', (event, index) => handleClick(record.actions[index] ? record.actions[index].id : null));
+  notification.on('close', () => active.delete(id));
+  active.set(id, notification);
+  notification.show();
+  return { id, suppressed: false };
+}
+
+// Notify once the next command in a terminal finishes (shell integration), or when
+// the shell exits. Skipped while the window is focused unless `always` is set.
+function notifyWhenCommandFinishes(terminalId, options = {}) {
+  const session = getTerminal(terminalId);
+  if (!session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { id: terminalId } });
+  }
+  cancelTerminalWatch(terminalId);
+
+  const finish = (exitCode, exited) => {
+    cancelTerminalWatch(terminalId);
+    const win = resolveWindow(options.windowId);
+    if (!options.always && win && win.isFocused()) return;
+
+    const failed = exitCode !== null && exitCode !== 0;
+    const label = options.label || 'Command';
+    notify({
+      title: failed ? `${label} failed` : `${label} finished`,
+      body: exited ? `Terminal exited with code ${exitCode}` : (exitCode === null ? session.cwd : `Exit code ${exitCode} in ${session.cwd}`),
+      actions: [{ id: 'focusTerminal', label: 'Focus terminal' }],
+      data: { terminalId, exitCode },
+      windowId: options.windowId
+    });
+  };
+
+  const onCommandFinished = (id, { exitCode }) => {
+    if (id === terminalId) finish(exitCode, false);
+  };
+  const onExit = (id, exitCode) => {
+    if (id === terminalId) finish(exitCode, true);
+  };
+  terminalEvents.on('commandFinished', onCommandFinished);
+  terminalEvents.on('exit', onExit);
+  terminalWatches.set(terminalId, () => {
+    terminalEvents.off('commandFinished', onCommandFinished);
+    terminalEvents.off('exit', onExit);
+  });
+  return true;
+}
+
+function cancelTerminalWatch(terminalId) {
+  const dispose = terminalWatches.get(terminalId);
+  if (!dispose) return false;
+  dispose();
+  terminalWatches.delete(terminalId);
+  return true;
+}
+
+function scheduleReminder(reminder) {
+  const delay = Math.max(0, new Date(reminder.at).getTime() - Date.now());
+  // setTimeout overflows past ~24.8 days; re-arm in chunks
+  const timer = setTimeout(() => {
+    if (new Date(reminder.at).getTime() > Date.now()) {
+      scheduleReminder(reminder);
+      return;
+    }
+    reminderTimers.delete(reminder.id);
+    store.set('reminders', store.get('reminders').filter((entry) => entry.id !== reminder.id));
+    notify({ title: reminder.title, body: reminder.body, data: { reminderId: reminder.id }, urgent: reminder.urgent });
+  }, Math.min(delay, 0x7fffffff));
+  timer.unref();
+  reminderTimers.set(reminder.id, timer);
+}
+
+function addReminder(title, at, options = {}) {
+  if (!title || Number.isNaN(new Date(at).getTime())) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Reminder needs a title and a valid time');
+  }
+  const reminder = { id: `reminder_${uuidv4()}`, title, body: options.body || '', at: new Date(at).toISOString(), urgent: Boolean(options.urgent) };
+  store.set('reminders', [...store.get('reminders'), reminder]);
+  scheduleReminder(reminder);
+  return reminder;
+}
+
+function cancelReminder(id) {
+  clearTimeout(reminderTimers.get(id));
+  reminderTimers.delete(id);
+  const reminders = store.get('reminders');
+  store.set('reminders', reminders.filter((entry) => entry.id !== id));
+  return reminders.some((entry) => entry.id === id);
+}
+
+function setupNotificationHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // Restore reminders from the last session; overdue ones fire right away
+  store.get('reminders').forEach(scheduleReminder);
+
+  ipcMain.handle('notifications:show', async (event, options) => {
+    const win = BrowserWindow.fromWebContents(event.sender);
+    return notify({ ...options, windowId: win ? win.id : undefined, onClick: undefined });
+  });
+
+  ipcMain.handle('notifications:notifyWhenCommandFinishes', async (event, terminalId, options = {}) => {
+    const win = BrowserWindow.fromWebContents(event.sender);
+    return notifyWhenCommandFinishes(terminalId, { ...options, windowId: win ? win.id : undefined });
+  });
+
+  ipcMain.handle('notifications:cancelCommandWatch', async (event, terminalId) => {
+    return cancelTerminalWatch(terminalId);
+  });
+
+  ipcMain.handle('notifications:getDoNotDisturb', async () => {
+    return { ...store.get('doNotDisturb'), active: isDoNotDisturbActive() };
+  });
+
+  ipcMain.handle('notifications:setDoNotDisturb', async (event, changes) => {
+    const current = store.get('doNotDisturb');
+    const next = { ...current, ...changes, schedule: { ...current.schedule, ...(changes.schedule || {}) } };
+    store.set('doNotDisturb', next);
+    return { ...next, active: isDoNotDisturbActive() };
+  });
+
+  ipcMain.handle('notifications:getMissed', async (event, clear) => {
+    const result = missed.slice();
+    if (clear) missed.length = 0;
+    return result;
+  });
+
+  ipcMain.handle('notifications:addReminder', async (event, title, at, options) => {
+    return addReminder(title, at, options);
+  });
+
+  ipcMain.handle('notifications:listReminders', async () => {
+    return store.get('reminders');
+  });
+
+  ipcMain.handle('notifications:cancelReminder', async (event, id) => {
+    return cancelReminder(id);
+  });
+}
+
+module.exports = { setupNotificationHandlers, notify, notifyWhenCommandFinishes, isDoNotDisturbActive };
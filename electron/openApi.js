@@ -0,0 +1,599 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { ensureIndex, getIndexedFiles } = require('./fileIndex');
+const { parseYaml, YamlError } = require('./yaml');
+const { setProblems, listProblems } = require('./problems');
+const { startMockServer } = require('./mockServer');
+
+const log = createLogger('openapi');
+
+const METHODS = ['get', 'put', 'post', 'delete', 'options', 'head', 'patch', 'trace'];
+const PARAMETER_LOCATIONS = ['path', 'query', 'header', 'cookie'];
+const SWAGGER_LOCATIONS = ['path', 'query', 'header', 'body', 'formData'];
+const SPEC_EXTENSIONS = ['.json', '.yaml', '.yml'];
+const MAX_SPEC_SIZE = 10 * 1024 * 1024;
+// Files are sniffed for a top-level openapi/swagger key in their first bytes
+const SNIFF_BYTES = 4096;
+const MAX_SAMPLE_DEPTH = 8;
+const PROBLEM_SOURCE = 'openapi';
+
+// Parsed specs by file, reused while the file's mtime holds:
+// filePath -> { mtimeMs, text, document }
+const documents = new Map();
+
+function invalidSpec(filePath, message, line) {
+  return new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)}: ${message}`, { details: { file: filePath, line } });
+}
+
+async function readDocument(filePath) {
+  let stats;
+  try {
+    stats = await fs.stat(filePath);
+  } catch (error) {
+    throw ForgeError.from(error, `Could not read ${filePath}`);
+  }
+  if (stats.size > MAX_SPEC_SIZE) throw invalidSpec(filePath, 'file is too large for an API spec');
+  const cached = documents.get(filePath);
+  if (cached && cached.mtimeMs === stats.mtimeMs) return cached;
+  const text = await fs.readFile(filePath, 'utf-8');
+  let document;
+  try {
+    document = path.extname(filePath).toLowerCase() === '.json' ? JSON.parse(text) : parseYaml(text);
+  } catch (error) {
+    if (error instanceof YamlError) throw invalidSpec(filePath, error.message, error.line);
+    throw invalidSpec(filePath, `not valid JSON: ${error.message}`);
+  }
+  const entry = { mtimeMs: stats.mtimeMs, text, document };
+  documents.set(filePath, entry);
+  return entry;
+}
+
+function specVersion(document) {
+  if (document && typeof document.openapi === 'string') return document.openapi;
+  if (document && document.swagger !== undefined) return String(document.swagger);
+  return null;
+}
+
+async function loadSpec(filePath) {
+  const file = path.resolve(filePath);
+  const { text, document } = await readDocument(file);
+  const version = specVersion(document);
+  if (!version) throw invalidSpec(file, 'not an OpenAPI or Swagger document (no openapi or swagger version)');
+  return { file, text, document, version, swagger: version.startsWith('2') };
+}
+
+function decodePointer(pointer) {
+  return pointer.replace(/^#?\/?/, '').split('/').filter((part, i, parts) => part || parts.length > 1)
+    .map((part) => part.replace(/~1/g, '/').replace(/~0/g, '~'));
+}
+
+function encodePointer(segments) {
+  return '/' + segments.map((segment) => String(segment).replace(/~/g, '~0').replace(/\//g, '~1')).join('/');
+}
+
+// Follow a $ref: local ("#/components/schemas/Pet") or into another file
+// ("common.yaml#/Error"), relative to the file holding it -> { value, file } or null
+async function resolveRef(ref, fromFile) {
+  const [target, fragment = ''] = ref.split('#');
+  const file = target ? path.resolve(path.dirname(fromFile), target) : fromFile;
+  let document;
+  try {
+    ({ document } = await readDocument(file));
+  } catch {
+    return null;
+  }
+  let value = document;
+  for (const segment of decodePointer(fragment)) {
+    if (value === null || typeof value !== 'object' || !(segment in value)) return null;
+    value = value[segment];
+  }
+  return { value, file };
+}
+
+// Resolve a node's $ref chain -> { value, file }; unresolvable refs resolve to null values
+async function deref(node, file, seen = new Set()) {
+  let current = { value: node, file };
+  while (current.value && typeof current.value === 'object' && typeof current.value.$ref === 'string') {
+    const key = `${current.file}#${current.value.$ref}`;
+    if (seen.has(key)) return { value: null, file: current.file };
+    seen.add(key);
+    current = (await resolveRef(current.value.$ref, current.file)) || { value: null, file: current.file };
+  }
+  return current;
+}
+
+// Line of the node at `pointer` in the spec's text, found by walking its keys in order.
+// Good enough to put a problem next to the right entry in JSON and YAML alike.
+function lineOf(text, pointer) {
+  let position = 0;
+  for (const segment of decodePointer(pointer)) {
+    if (/^\d+$/.test(segment)) continue;
+    const escaped = segment.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+    const pattern = new RegExp(`(^|[\\s{,])(["']?)${escaped}\\2\\s*:`, 'mg');
+    pattern.lastIndex = position;
+    const match = pattern.exec(text);
+    if (!match) break;
+    position = match.index + match[1].length;
+  }
+  return text.slice(0, position).split('\n').length;
+}
+
+function operationKey(method, route) {
+  return `${method.toUpperCase()} ${route}`;
+}
+
+// Path-level parameters merged with the operation's, which override by name and location
+async function collectParameters(spec, pathItem, operation) {
+  const merged = new Map();
+  for (const list of [pathItem.parameters, operation.parameters]) {
+    if (!Array.isArray(list)) continue;
+    for (const item of list) {
+      const { value } = await deref(item, spec.file);
+      if (value && typeof value === 'object') merged.set(`${value.in}:${value.name}`, value);
+    }
+  }
+  return Array.from(merged.values());
+}
+
+async function* eachOperation(spec) {
+  const paths = spec.document.paths || {};
+  for (const [route, rawItem] of Object.entries(paths)) {
+    const { value: pathItem } = await deref(rawItem, spec.file);
+    if (!pathItem || typeof pathItem !== 'object') continue;
+    for (const method of METHODS) {
+      if (pathItem[method] && typeof pathItem[method] === 'object') yield { route, method, pathItem, operation: pathItem[method] };
+    }
+  }
+}
+
+function serverUrls(spec) {
+  const { document } = spec;
+  if (spec.swagger) {
+    if (!document.host) return [document.basePath || '/'];
+    const schemes = Array.isArray(document.schemes) && document.schemes.length ? document.schemes : ['https'];
+    return schemes.map((scheme) => `${scheme}://${document.host}${document.basePath || ''}`);
+  }
+  const servers = Array.isArray(document.servers) && document.servers.length ? document.servers : [{ url: '/' }];
+  // Server variables take their defaults
+  return servers.map((server) => String(server.url || '/').replace(/\{([^}]+)\}/g, (match, name) => {
+    const variable = server.variables && server.variables[name];
+    return variable && variable.default !== undefined ? String(variable.default) : match;
+  }));
+}
+
+function validateParameter(parameter, where, spec, report) {
+  const locations = spec.swagger ? SWAGGER_LOCATIONS : PARAMETER_LOCATIONS;
+  if (!parameter || typeof parameter !== 'object') return report(where, 'error', 'Parameter must be an object');
+  if (typeof parameter.name !== 'string' || !parameter.name) report(where, 'error', 'Parameter needs a name');
+  if (!locations.includes(parameter.in)) report(where, 'error', `Parameter "in" must be one of ${locations.join(', ')}`);
+  if (parameter.in === 'path' && parameter.required !== true) report(where, 'error', `Path parameter ${parameter.name} must be required`);
+  if (spec.swagger) {
+    if (parameter.in === 'body' ? !parameter.schema : !parameter.type) report(where, 'error', `Parameter ${parameter.name} needs a ${parameter.in === 'body' ? 'schema' : 'type'}`);
+  } else if (!parameter.schema && !parameter.content) {
+    report(where, 'warning', `Parameter ${parameter.name} has no schema or content`);
+  }
+}
+
+// Every $ref in the document -> [{ ref, pointer }]
+function collectRefs(node, segments = [], found = []) {
+  if (Array.isArray(node)) {
+    node.forEach((item, i) => collectRefs(item, [...segments, i], found));
+  } else if (node && typeof node === 'object') {
+    if (typeof node.$ref === 'string') found.push({ ref: node.$ref, pointer: encodePointer([...segments, '$ref']) });
+    for (const [key, value] of Object.entries(node)) {
+      if (key !== '$ref') collectRefs(value, [...segments, key], found);
+    }
+  }
+  return found;
+}
+
+// Structural checks an editor can point at: required fields, path templates against
+// their parameters, unique operationIds, responses, and refs that don't resolve.
+// Problems also go to the problems panel under the "openapi" source.
+async function validateSpec(filePath) {
+  const file = path.resolve(filePath);
+  const problems = [];
+  let spec;
+  try {
+    spec = await loadSpec(file);
+  } catch (error) {
+    if (error.code !== ErrorCode.INVALID_ARGUMENT) throw error;
+    problems.push({ pointer: '', line: (error.details && error.details.line) || 1, severity: 'error', message: error.message });
+  }
+  const report = (pointer, severity, message) => {
+    problems.push({ pointer, line: spec ? lineOf(spec.text, pointer) : 1, severity, message });
+  };
+
+  if (spec) {
+    const { document } = spec;
+    if (!spec.swagger && !/^3\.[01]\.\d+$/.test(spec.version)) report('/openapi', 'warning', `OpenAPI version ${spec.version} is not 3.0.x or 3.1.x`);
+    if (spec.swagger && spec.version !== '2.0') report('/swagger', 'error', 'Swagger version must be "2.0"');
+    if (!document.info || typeof document.info !== 'object') report('', 'error', 'Missing info object');
+    else {
+      if (!document.info.title) report('/info', 'error', 'info.title is required');
+      if (document.info.version === undefined) report('/info', 'error', 'info.version is required');
+    }
+    if (!document.paths && !(document.webhooks || (document.components && Object.keys(document.components).length))) {
+      report('', 'error', 'Missing paths object');
+    }
+
+    const operationIds = new Map();
+    for await (const { route, method, pathItem, operation } of eachOperation(spec)) {
+      const where = encodePointer(['paths', route, method]);
+      if (!route.startsWith('/')) report(encodePointer(['paths', route]), 'error', `Path ${route} must start with /`);
+      if (operation.operationId) {
+        if (operationIds.has(operation.operationId)) report(where, 'error', `operationId ${operation.operationId} is also used by ${operationIds.get(operation.operationId)}`);
+        else operationIds.set(operation.operationId, operationKey(method, route));
+      }
+      if (!operation.responses || typeof operation.responses !== 'object' || Object.keys(operation.responses).length === 0) {
+        report(where, 'error', `${operationKey(method, route)} declares no responses`);
+      }
+      const parameters = await collectParameters(spec, pathItem, operation);
+      const seen = new Set();
+      for (const parameter of parameters) {
+        validateParameter(parameter, where, spec, report);
+        const key = `${parameter.in}:${parameter.name}`;
+        if (seen.has(key)) report(where, 'error', `Parameter ${parameter.name} in ${parameter.in} is declared twice`);
+        seen.add(key);
+      }
+      const templated = Array.from(route.matchAll(/\{([^}]+)\}/g), (match) => match[1]);
+      const declared = parameters.filter((parameter) => parameter.in === 'path').map((parameter) => parameter.name);
+      for (const name of templated.filter((item) => !declared.includes(item))) {
+        report(where, 'error', `Path parameter {${name}} is not declared for ${operationKey(method, route)}`);
+      }
+      for (const name of declared.filter((item) => !templated.includes(item))) {
+        report(where, 'error', `Path parameter ${name} does not appear in ${route}`);
+      }
+      if (spec.swagger && parameters.filter((parameter) => parameter.in === 'body').length > 1) {
+        report(where, 'error', `${operationKey(method, route)} has more than one body parameter`);
+      }
+    }
+
+    for (const { ref, pointer } of collectRefs(document)) {
+      if (!(await resolveRef(ref, file))) report(pointer, 'error', `Can't resolve $ref ${ref}`);
+    }
+  }
+
+  const others = listProblems({ source: PROBLEM_SOURCE }).filter((problem) => problem.file !== file);
+  setProblems(PROBLEM_SOURCE, [...others, ...problems.map((problem) => ({
+    file,
+    line: problem.line,
+    column: 1,
+    endLine: null,
+    endColumn: null,
+    severity: problem.severity,
+    code: problem.pointer || null,
+    message: problem.message
+  }))]);
+  return {
+    file,
+    version: spec ? spec.version : null,
+    valid: !problems.some((problem) => problem.severity === 'error'),
+    problems
+  };
+}
+
+async function describeOperation(spec, { route, method, pathItem, operation }) {
+  const parameters = await collectParameters(spec, pathItem, operation);
+  let requestBody = null;
+  if (spec.swagger) {
+    const body = parameters.find((parameter) => parameter.in === 'body');
+    const form = parameters.some((parameter) => parameter.in === 'formData');
+    if (body || form) {
+      const consumes = operation.consumes || spec.document.consumes || [form ? 'application/x-www-form-urlencoded' : 'application/json'];
+      requestBody = { required: Boolean(body && body.required), contentTypes: consumes };
+    }
+  } else if (operation.requestBody) {
+    const { value } = await deref(operation.requestBody, spec.file);
+    if (value) requestBody = { required: Boolean(value.required), contentTypes: Object.keys(value.content || {}) };
+  }
+  return {
+    key: operationKey(method, route),
+    operationId: operation.operationId || null,
+    method: method.toUpperCase(),
+    path: route,
+    summary: operation.summary || null,
+    description: operation.description || null,
+    tags: Array.isArray(operation.tags) ? operation.tags : [],
+    deprecated: Boolean(operation.deprecated),
+    parameters: parameters.filter((parameter) => parameter.in !== 'body').map((parameter) => ({
+      name: parameter.name,
+      in: parameter.in,
+      required: Boolean(parameter.required),
+      description: parameter.description || null
+    })),
+    requestBody,
+    responses: Object.keys(operation.responses || {})
+  };
+}
+
+async function listOperations(filePath) {
+  const spec = await loadSpec(filePath);
+  const operations = [];
+  for await (const entry of eachOperation(spec)) operations.push(await describeOperation(spec, entry));
+  const { info = {} } = spec.document;
+  return { file: spec.file, version: spec.version, title: info.title || null, apiVersion: info.version === undefined ? null : String(info.version), servers: serverUrls(spec), operations };
+}
+
+function sampleString(schema) {
+  switch (schema.format) {
+    case 'date-time': return new Date(0).toISOString();
+    case 'date': return '1970-01-01';
+    case 'time': return '00:00:00Z';
+    case 'email': return 'user@example.com';
+    case 'uuid': return '00000000-0000-4000-8000-000000000000';
+    case 'uri':
+    case 'url': return 'https://example.com';
+    case 'hostname': return 'example.com';
+    case 'ipv4': return '127.0.0.1';
+    case 'byte': return 'ZXhhbXBsZQ==';
+    default: return schema.minLength > 6 ? 'x'.repeat(schema.minLength) : 'string';
+  }
+}
+
+// An example value for a schema: its own example, default, enum or const when given,
+// otherwise built from its type. `purpose` is 'request' (skips readOnly properties) or
+// 'response' (skips writeOnly ones).
+async function sampleSchema(schema, file, purpose, depth = 0, seen = new Set()) {
+  const resolved = await deref(schema, file);
+  const node = resolved.value;
+  if (!node || typeof node !== 'object' || depth > MAX_SAMPLE_DEPTH) return null;
+  // A schema that contains itself samples once
+  const key = schema && schema.$ref ? `${file}#${schema.$ref}` : null;
+  if (key && seen.has(key)) return null;
+  const inner = key ? new Set([...seen, key]) : seen;
+  const next = (child) => sampleSchema(child, resolved.file, purpose, depth + 1, inner);
+
+  if (node.example !== undefined) return node.example;
+  if (Array.isArray(node.examples) && node.examples.length) return node.examples[0];
+  if (node.default !== undefined) return node.default;
+  if (node.const !== undefined) return node.const;
+  if (Array.isArray(node.enum) && node.enum.length) return node.enum[0];
+  if (Array.isArray(node.allOf)) {
+    const parts = await Promise.all(node.allOf.map(next));
+    const objects = parts.filter((part) => part && typeof part === 'object' && !Array.isArray(part));
+    return objects.length ? Object.assign({}, ...objects) : parts.find((part) => part !== null) ?? null;
+  }
+  const choice = (node.oneOf || node.anyOf || [])[0];
+  if (choice) return next(choice);
+
+  const types = Array.isArray(node.type) ? node.type : [node.type];
+  const type = types.find((item) => item && item !== 'null') || (node.properties ? 'object' : node.items ? 'array' : null);
+  switch (type) {
+    case 'object': {
+      const result = {};
+      for (const [name, property] of Object.entries(node.properties || {})) {
+        const { value } = await deref(property, resolved.file);
+        if (value && ((purpose === 'request' && value.readOnly) || (purpose === 'response' && value.writeOnly))) continue;
+        result[name] = await next(property);
+      }
+      return result;
+    }
+    case 'array':
+      return node.items ? [await next(node.items)] : [];
+    case 'string':
+      return sampleString(node);
+    case 'integer':
+    case 'number':
+      return node.minimum !== undefined ? node.minimum : node.exclusiveMinimum !== undefined ? Number(node.exclusiveMinimum) + 1 : 0;
+    case 'boolean':
+      return true;
+    default:
+      return null;
+  }
+}
+
+// Example for a media type object: example, the first named example, or its schema
+async function sampleMedia(media, file, purpose) {
+  if (!media) return null;
+  if (media.example !== undefined) return media.example;
+  if (media.examples && typeof media.examples === 'object') {
+    const first = Object.values(media.examples)[0];
+    if (first) {
+      const { value } = await deref(first, file);
+      if (value && value.value !== undefined) return value.value;
+    }
+  }
+  return media.schema ? sampleSchema(media.schema, file, purpose) : null;
+}
+
+function pickMediaType(content) {
+  const types = Object.keys(content || {});
+  return types.find((type) => /json/i.test(type)) || types[0] || null;
+}
+
+async function findOperation(spec, key) {
+  for await (const entry of eachOperation(spec)) {
+    if (entry.operation.operationId === key || operationKey(entry.method, entry.route) === String(key).replace(/^(\S+)/, (method) => method.toUpperCase())) {
+      return entry;
+    }
+  }
+  throw new ForgeError(ErrorCode.NOT_FOUND, `No operation ${key} in ${path.basename(spec.file)}`);
+}
+
+function formatBody(body, contentType) {
+  if (body === null || body === undefined) return null;
+  if (typeof body === 'string') return body;
+  if (/x-www-form-urlencoded/.test(contentType || '')) {
+    return new URLSearchParams(Object.entries(body).map(([key, value]) => [key, typeof value === 'object' ? JSON.stringify(value) : String(value)])).toString();
+  }
+  return JSON.stringify(body, null, 2);
+}
+
+function shellQuote(text) {
+  return `'${String(text).replace(/'/g, "'\\''")}'`;
+}
+
+// A ready-to-send request for an operation (by operationId or "GET /pets/{id}"), filled
+// with examples from the spec. `values` ({ path, query, header, cookie, body }) override
+// them; `server` picks the base URL. Also rendered as a .http request and a curl command.
+async function generateRequest(filePath, key, { server = null, values = {}, includeOptional = false } = {}) {
+  const spec = await loadSpec(filePath);
+  const entry = await findOperation(spec, key);
+  const { route, method, pathItem, operation } = entry;
+  const parameters = await collectParameters(spec, pathItem, operation);
+  const given = (location, name) => (values[location] && values[location][name] !== undefined ? values[location][name] : undefined);
+
+  const sample = async (parameter) => {
+    const override = given(parameter.in, parameter.name);
+    if (override !== undefined) return override;
+    if (parameter.example !== undefined) return parameter.example;
+    if (parameter.examples && typeof parameter.examples === 'object') {
+      const first = Object.values(parameter.examples)[0];
+      const { value } = await deref(first, spec.file);
+      if (value && value.value !== undefined) return value.value;
+    }
+    if (parameter.content) return sampleMedia(parameter.content[pickMediaType(parameter.content)], spec.file, 'request');
+    // Swagger 2.0 parameters carry their type inline
+    return sampleSchema(parameter.schema || parameter, spec.file, 'request');
+  };
+
+  let url = route;
+  const query = new URLSearchParams();
+  const headers = {};
+  const cookies = [];
+  const form = {};
+  let body = null;
+  let contentType = null;
+  for (const parameter of parameters) {
+    const wanted = parameter.required || includeOptional || given(parameter.in, parameter.name) !== undefined;
+    if (parameter.in === 'body') {
+      body = values.body !== undefined ? values.body : await sampleSchema(parameter.schema, spec.file, 'request');
+      continue;
+    }
+    if (!wanted && parameter.in !== 'path') continue;
+    const value = await sample(parameter);
+    const text = value === null || value === undefined ? '' : typeof value === 'object' ? JSON.stringify(value) : String(value);
+    if (parameter.in === 'path') url = url.replace(`{${parameter.name}}`, encodeURIComponent(text));
+    else if (parameter.in === 'query') (Array.isArray(value) ? value : [text]).forEach((item) => query.append(parameter.name, String(item)));
+    else if (parameter.in === 'header') headers[parameter.name] = text;
+    else if (parameter.in === 'cookie') cookies.push(`${parameter.name}=${encodeURIComponent(text)}`);
+    else if (parameter.in === 'formData') form[parameter.name] = value;
+  }
+  if (cookies.length) headers.Cookie = cookies.join('; ');
+
+  if (spec.swagger) {
+    const consumes = operation.consumes || spec.document.consumes || [];
+    if (Object.keys(form).length) {
+      contentType = consumes.find((type) => /form/.test(type)) || 'application/x-www-form-urlencoded';
+      body = values.body !== undefined ? values.body : form;
+    } else if (body !== null) {
+      contentType = consumes.find((type) => /json/i.test(type)) || consumes[0] || 'application/json';
+    }
+  } else if (operation.requestBody) {
+    const { value: requestBody } = await deref(operation.requestBody, spec.file);
+    contentType = requestBody ? pickMediaType(requestBody.content) : null;
+    if (contentType) body = values.body !== undefined ? values.body : await sampleMedia(requestBody.content[contentType], spec.file, 'request');
+  }
+  if (contentType) headers['Content-Type'] = contentType;
+  const produces = spec.swagger ? operation.produces || spec.document.produces : null;
+  if (produces && produces.length) headers.Accept = produces[0];
+
+  const servers = serverUrls(spec);
+  const base = (server || servers[0]).replace(/\/+$/, '');
+  const search = query.toString();
+  const request = {
+    operation: operationKey(method, route),
+    operationId: operation.operationId || null,
+    method: method.toUpperCase(),
+    url: `${base}${url}${search ? `?${search}` : ''}`,
+    headers,
+    body: formatBody(body, contentType)
+  };
+  const headerLines = Object.entries(request.headers).map(([name, value]) => `${name}: ${value}`);
+  request.http = [`${request.method} ${request.url}`, ...headerLines, ...(request.body !== null ? ['', request.body] : [])].join('\n');
+  request.curl = [
+    `curl -X ${request.method} ${shellQuote(request.url)}`,
+    ...headerLines.map((line) => `-H ${shellQuote(line)}`),
+    ...(request.body !== null ? [`--data ${shellQuote(request.body)}`] : [])
+  ].join(' \\\n  ');
+  return request;
+}
+
+// Mock routes for every operation: the first success response, with its example body
+async function mockConfigFromSpec(filePath) {
+  const spec = await loadSpec(filePath);
+  const routes = [];
+  for await (const { route, method, operation } of eachOperation(spec)) {
+    const codes = Object.keys(operation.responses || {});
+    const code = codes.find((item) => /^2\d\d$/.test(item)) || codes.find((item) => /^2/i.test(item)) || codes.find((item) => item === 'default');
+    const status = code && /^\d{3}$/.test(code) ? Number(code) : 200;
+    const { value: response } = code ? await deref(operation.responses[code], spec.file) : { value: null };
+    let body = null;
+    if (response && spec.swagger) {
+      body = response.examples ? Object.values(response.examples)[0] : response.schema ? await sampleSchema(response.schema, spec.file, 'response') : null;
+    } else if (response && response.content) {
+      body = await sampleMedia(response.content[pickMediaType(response.content)], spec.file, 'response');
+    }
+    // Serve under the first server's path, so clients keep their base URL
+    const basePath = new URL(serverUrls(spec)[0], 'http://localhost').pathname.replace(/\/+$/, '');
+    routes.push({ method: method.toUpperCase(), path: `${basePath}${route}`, status, body: status === 204 ? null : body });
+  }
+  return { cors: true, routes };
+}
+
+async function findSpecs(rootPath) {
+  const root = path.resolve(rootPath);
+  const index = ensureIndex(root);
+  await index.ready;
+  const candidates = getIndexedFiles(root).filter((filePath) => SPEC_EXTENSIONS.includes(path.extname(filePath).toLowerCase()));
+  const specs = [];
+  const buffer = Buffer.alloc(SNIFF_BYTES);
+  for (const filePath of candidates) {
+    let handle;
+    try {
+      handle = await fs.open(filePath, 'r');
+      const { bytesRead } = await handle.read(buffer, 0, SNIFF_BYTES, 0);
+      const head = buffer.toString('utf-8', 0, bytesRead);
+      const match = head.match(/^\s*["']?(openapi|swagger)["']?\s*:\s*["']?([\d.]+)/m);
+      if (match) specs.push({ file: filePath, relativePath: path.relative(root, filePath).split(path.sep).join('/'), version: match[2] });
+    } catch (error) {
+      log.debug(`Skipped ${filePath}: ${error.message}`);
+    } finally {
+      if (handle) await handle.close();
+    }
+  }
+  return specs.sort((a, b) => a.relativePath.localeCompare(b.relativePath));
+}
+
+function resolveSpecPath(sender, filePath) {
+  if (typeof filePath !== 'string' || !filePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A spec file path is required');
+  const context = windowManager.fromSender(sender);
+  return context && context.workspacePath ? path.resolve(context.workspacePath, filePath) : path.resolve(filePath);
+}
+
+function setupOpenApiHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('openapi:findSpecs', async (event) => {
+    const context = windowManager.fromSender(event.sender);
+    if (!context || !context.workspacePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to find API specs');
+    return findSpecs(context.workspacePath);
+  });
+
+  ipcMain.handle('openapi:validate', async (event, filePath) => validateSpec(resolveSpecPath(event.sender, filePath)));
+
+  ipcMain.handle('openapi:listOperations', async (event, filePath) => listOperations(resolveSpecPath(event.sender, filePath)));
+
+  ipcMain.handle('openapi:generateRequest', async (event, filePath, operation, options) => generateRequest(resolveSpecPath(event.sender, filePath), operation, options));
+
+  // Serves the spec's example responses through the workspace mock server, reloading
+  // when the spec changes
+  ipcMain.handle('openapi:startMock', async (event, filePath, options = {}) => {
+    const context = windowManager.fromSender(event.sender);
+    if (!context || !context.workspacePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to serve mocks');
+    const file = resolveSpecPath(event.sender, filePath);
+    await loadSpec(file);
+    return startMockServer(context.workspacePath, {
+      port: options.port,
+      owner: event.sender,
+      window: context.window,
+      source: { file, load: () => mockConfigFromSpec(file) }
+    });
+  });
+}
+
+module.exports = { setupOpenApiHandlers, loadSpec, validateSpec, listOperations, generateRequest, mockConfigFromSpec, findSpecs };
@@ -0,0 +1,155 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { v4: uuidv4 } = require('uuid');
+const { windowManager } = require('./windows');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('open-router');
+
+// Pending `--wait` requests: wait id -> { path, windowId, resolve }
+const waiters = new Map();
+
+function isInside(parent, child) {
+  const relative = path.relative(parent, child);
+  return relative === '' || (!relative.startsWith('..') && !path.isAbsolute(relative));
+}
+
+// Pick the window for a file: the one whose workspace contains it (deepest root wins),
+// otherwise the focused window, otherwise a new one.
+function windowForFile(filePath) {
+  let best = null;
+  for (const context of windowManager.windows.values()) {
+    if (context.workspacePath && isInside(context.workspacePath, filePath)) {
+      if (!best || context.workspacePath.length > best.workspacePath.length) best = context;
+    }
+  }
+  if (best) return best.window;
+  return windowManager.getPrimaryWindow() || windowManager.createWindow();
+}
+
+// Deliver an event once the target renderer has loaded
+async function sendWhenReady(win, channel, payload) {
+  if (win.webContents.isLoading()) {
+    await new Promise((resolve) => win.webContents.once('did-finish-load', resolve));
+  }
+  emit(channel, payload, { target: win.webContents });
+}
+
+function waitForClose(waitId, filePath, win) {
+  return new Promise((resolve) => {
+    waiters.set(waitId, { path: filePath, windowId: win.id, resolve });
+    // A closed window releases everything it was editing
+    win.once('closed', () => releaseWait(waitId, 'window-closed'));
+  });
+}
+
+function releaseWait(waitId, reason) {
+  const waiter = waiters.get(waitId);
+  if (!waiter) return false;
+  waiters.delete(waitId);
+  waiter.resolve({ path: waiter.path, reason });
+  return true;
+}
+
+// Open files and folders. Folders become workspaces (focusing an existing window);
+// files go to the best-matching window at an optional line/column.
+// With `wait`, resolves once every opened file has been closed in the editor.
+async function openPaths(targets, options = {}) {
+  const opened = [];
+  const waits = [];
+
+  for (const target of targets) {
+    const filePath = path.resolve(target.path);
+    let stats = null;
+    try {
+      stats = await fs.stat(filePath);
+    } catch {
+      // New files are allowed so `forge newfile.txt` behaves like other editors
+    }
+
+    if (stats && stats.isDirectory()) {
+      const win = windowManager.createWindow({ workspacePath: filePath, allowDuplicate: options.newWindow });
+      windowManager.focus(win);
+      opened.push({ path: filePath, kind: 'folder', windowId: win.id });
+      continue;
+    }
+
+    const win = options.newWindow
+      ? windowManager.createWindow({ allowDuplicate: true })
+      : (options.windowId && windowManager.get(options.windowId) ? windowManager.get(options.windowId).window : windowForFile(filePath));
+    const waitId = options.wait ? `wait_${uuidv4()}` : null;
+
+    await sendWhenReady(win, 'workbench:openFile', {
+      path: filePath,
+      line: target.line,
+      column: target.column,
+      exists: Boolean(stats),
+      waitId,
+      source: options.source || 'internal'
+    });
+    windowManager.focus(win);
+    opened.push({ path: filePath, kind: 'file', windowId: win.id });
+    if (waitId) waits.push(waitForClose(waitId, filePath, win));
+  }
+
+  log.info(`Opened ${opened.length} path(s) from ${options.source || 'internal'}`);
+  if (waits.length > 0) await Promise.all(waits);
+  return opened;
+}
+
+async function openDiff(leftPath, rightPath, options = {}) {
+  const left = path.resolve(leftPath);
+  const right = path.resolve(rightPath);
+  for (const filePath of [left, right]) {
+    try {
+      await fs.access(filePath);
+    } catch (error) {
+      throw ForgeError.from(error, `Cannot diff ${filePath}`);
+    }
+  }
+
+  const win = windowForFile(left);
+  const waitId = options.wait ? `wait_${uuidv4()}` : null;
+  await sendWhenReady(win, 'workbench:openDiff', { left, right, waitId, source: options.source || 'internal' });
+  windowManager.focus(win);
+  if (waitId) await waitForClose(waitId, right, win);
+  return { left, right, windowId: win.id };
+}
+
+// Block until the given file is closed in whichever window has it open
+function waitForFile(filePath) {
+  const resolved = path.resolve(filePath);
+  const win = windowForFile(resolved);
+  return waitForClose(`wait_${uuidv4()}`, resolved, win);
+}
+
+function documentClosed(filePath, waitId) {
+  if (waitId) return releaseWait(waitId, 'closed');
+  const resolved = path.resolve(filePath);
+  let released = false;
+  for (const [id, waiter] of waiters) {
+    if (waiter.path === resolved) released = releaseWait(id, 'closed') || released;
+  }
+  return released;
+}
+
+function setupOpenRouterHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('workbench:openPaths', async (event, targets, options = {}) => {
+    if (!Array.isArray(targets) || targets.length === 0) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'At least one path is required');
+    }
+    const context = windowManager.fromSender(event.sender);
+    return openPaths(targets, { ...options, wait: false, windowId: options.windowId || (context ? context.window.id : undefined) });
+  });
+
+  // The renderer reports closed documents so `forge --wait` can return
+  ipcMain.handle('workbench:documentClosed', async (event, filePath, waitId) => {
+    return documentClosed(filePath, waitId);
+  });
+}
+
+module.exports = { setupOpenRouterHandlers, openPaths, openDiff, waitForFile, documentClosed, windowForFile };
@@ -0,0 +1,240 @@
+const fs = require('fs').promises;
+const { spawn } = require('child_process');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('pdf');
+
+// Pages are rendered by Poppler's command-line tools (poppler-utils), which most Linux
+// distributions ship and Homebrew / Scoop install; no native module to build
+const TOOL_TIMEOUT = 30000;
+const MAX_OUTPUT = 256 * 1024 * 1024;
+const MIN_SCALE = 0.1;
+const MAX_SCALE = 8;
+// 72 points per inch, so scale 1 is the page's natural size
+const POINTS_PER_INCH = 72;
+const MAX_PAGE_PIXELS = 40 * 1000 * 1000;
+const MAX_CACHED_PAGES = 24;
+const MAX_CACHED_DOCUMENTS = 20;
+// Scrolling through a document requests many pages at once
+const MAX_CONCURRENT_RENDERS = 2;
+
+// path -> { stamp, info, outline }, the last two as promises shared by concurrent callers
+const documents = new Map();
+// `${path}:${stamp}:${page}:${scale}` -> Promise<{ png, width, height }>
+const pages = new Map();
+const renderQueue = [];
+let activeRenders = 0;
+
+function runPoppler(tool, args) {
+  return new Promise((resolve, reject) => {
+    const child = spawn(tool, args, { windowsHide: true, timeout: TOOL_TIMEOUT });
+    const stdout = [];
+    let size = 0;
+    let stderr = '';
+    child.stdout.on('data', (chunk) => {
+      size += chunk.length;
+      if (size > MAX_OUTPUT) child.kill();
+      else stdout.push(chunk);
+    });
+    child.stderr.on('data', (chunk) => { stderr += chunk; });
+    child.on('error', (error) => {
+      reject(error.code === 'ENOENT'
+        ? new ForgeError(ErrorCode.NOT_FOUND, `${tool} was not found on PATH; install Poppler (poppler-utils) to preview PDFs`, { recoverable: false })
+        : error);
+    });
+    child.on('close', (code, signal) => {
+      if (code === 0) resolve(Buffer.concat(stdout));
+      else if (/incorrect password/i.test(stderr)) reject(new ForgeError(ErrorCode.PERMISSION_DENIED, 'The PDF is encrypted; a password is required', { details: { passwordRequired: true } }));
+      else if (size > MAX_OUTPUT) reject(new ForgeError(ErrorCode.IO_ERROR, `${tool} produced more than ${MAX_OUTPUT / 1024 / 1024} MB`));
+      else if (signal === 'SIGTERM') reject(new ForgeError(ErrorCode.TIMEOUT, `${tool} did not finish within ${TOOL_TIMEOUT / 1000}s`));
+      else reject(new ForgeError(ErrorCode.INVALID_ARGUMENT, `${tool} could not read the PDF: ${stderr.trim().split('\n').pop() || `exit code ${code}`}`));
+    });
+  });
+}
+
+function passwordArgs(password) {
+  return typeof password === 'string' && password ? ['-upw', password] : [];
+}
+
+// Cache entries are dropped when the file changes
+async function fileStamp(filePath) {
+  let stat;
+  try {
+    stat = await fs.stat(filePath);
+  } catch (error) {
+    throw ForgeError.from(error, `Could not read ${filePath}`);
+  }
+  if (!stat.isFile()) throw new ForgeError(ErrorCode.IS_DIRECTORY, `${filePath} is not a file`);
+  return `${stat.mtimeMs}:${stat.size}`;
+}
+
+async function cachedDocument(filePath) {
+  const stamp = await fileStamp(filePath);
+  let entry = documents.get(filePath);
+  if (!entry || entry.stamp !== stamp) {
+    entry = { stamp, info: null, outline: null };
+    documents.delete(filePath);
+    documents.set(filePath, entry);
+    if (documents.size > MAX_CACHED_DOCUMENTS) documents.delete(documents.keys().next().value);
+  }
+  return entry;
+}
+
+// pdfinfo's "Key: value" lines, plus "Page    N size: W x H pts" for each page
+function parsePdfInfo(text) {
+  const fields = {};
+  const sizes = [];
+  for (const line of text.split(/\r?\n/)) {
+    const size = /^Page\s+(\d+) size:\s+([\d.]+) x ([\d.]+) pts/.exec(line);
+    if (size) {
+      sizes[Number(size[1]) - 1] = { width: Number(size[2]), height: Number(size[3]) };
+      continue;
+    }
+    const rot = /^Page\s+(\d+) rot:\s+(\d+)/.exec(line);
+    if (rot) {
+      if (sizes[Number(rot[1]) - 1]) sizes[Number(rot[1]) - 1].rotation = Number(rot[2]);
+      continue;
+    }
+    const match = /^([A-Za-z][A-Za-z ]*?):\s*(.*)$/.exec(line);
+    if (match) fields[match[1]] = match[2].trim();
+  }
+  const pageCount = Number(fields.Pages) || 0;
+  const pageSizes = [];
+  for (let index = 0; index < pageCount; index++) {
+    const size = sizes[index] || sizes[0] || { width: 612, height: 792 };
+    pageSizes.push({ width: size.width, height: size.height, rotation: size.rotation || 0 });
+  }
+  return {
+    pageCount,
+    title: fields.Title || null,
+    author: fields.Author || null,
+    subject: fields.Subject || null,
+    creator: fields.Creator || null,
+    producer: fields.Producer || null,
+    created: fields.CreationDate || null,
+    modified: fields.ModDate || null,
+    pdfVersion: fields['PDF version'] || null,
+    encrypted: /^yes/i.test(fields.Encrypted || ''),
+    // Point sizes per page, for laying out placeholders before pages render
+    pages: pageSizes
+  };
+}
+
+async function getPdfInfo(filePath, { password } = {}) {
+  const entry = await cachedDocument(filePath);
+  if (!entry.info) {
+    // Page sizes need an explicit range; -l past the end is clamped to the last page
+    entry.info = runPoppler('pdfinfo', ['-enc', 'UTF-8', '-f', '1', '-l', '100000', ...passwordArgs(password), filePath])
+      .then((output) => parsePdfInfo(output.toString('utf-8')));
+    entry.info.catch(() => { entry.info = null; });
+  }
+  return { path: filePath, ...(await entry.info) };
+}
+
+function decodeEntities(text) {
+  return text.replace(/&(#x[0-9a-f]+|#\d+|amp|lt|gt|quot|apos);/gi, (match, entity) => {
+    if (entity[0] === '#') return String.fromCodePoint(entity[1].toLowerCase() === 'x' ? parseInt(entity.slice(2), 16) : Number(entity.slice(1)));
+    return { amp: '&', lt: '<', gt: '>', quot: '"', apos: "'" }[entity.toLowerCase()];
+  });
+}
+
+// pdftohtml -xml writes the document outline as nested <outline> lists of
+// <item page="N">Title</item>; a nested list belongs to the item before it
+function parseOutline(xml) {
+  const start = xml.indexOf('<outline');
+  if (start < 0) return [];
+  const root = [];
+  const stack = [];
+  let current = null;
+  let last = null;
+  const pattern = /<outline>|<\/outline>|<item(?:\s+page="(\d+)")?\s*>([\s\S]*?)<\/item>/g;
+  pattern.lastIndex = start;
+  let match;
+  while ((match = pattern.exec(xml))) {
+    if (match[0] === '<outline>') {
+      if (!current) current = root;
+      else {
+        stack.push(current);
+        current = last ? last.children : current;
+      }
+    } else if (match[0] === '</outline>') {
+      if (!stack.length) break;
+      current = stack.pop();
+    } else if (current) {
+      last = { title: decodeEntities(match[2].replace(/<[^>]+>/g, '')).trim(), page: match[1] ? Number(match[1]) : null, children: [] };
+      current.push(last);
+    }
+  }
+  return root;
+}
+
+async function getPdfOutline(filePath, { password } = {}) {
+  const entry = await cachedDocument(filePath);
+  if (!entry.outline) {
+    // The outline is written whatever the page range, so only the first page's text is extracted
+    entry.outline = runPoppler('pdftohtml', ['-xml', '-stdout', '-i', '-q', '-nodrm', '-f', '1', '-l', '1', ...passwordArgs(password), filePath])
+      .then((output) => parseOutline(output.toString('utf-8')));
+    entry.outline.catch(() => { entry.outline = null; });
+  }
+  return entry.outline;
+}
+
+function pngSize(png) {
+  if (png.length < 24 || png.readUInt32BE(0) !== 0x89504e47) throw new ForgeError(ErrorCode.INTERNAL, 'pdftoppm did not produce a PNG');
+  return { width: png.readUInt32BE(16), height: png.readUInt32BE(20) };
+}
+
+function withRenderSlot(task) {
+  return new Promise((resolve, reject) => {
+    const run = () => {
+      activeRenders++;
+      task().then(resolve, reject).finally(() => {
+        activeRenders--;
+        if (renderQueue.length) renderQueue.shift()();
+      });
+    };
+    if (activeRenders < MAX_CONCURRENT_RENDERS) run();
+    else renderQueue.push(run);
+  });
+}
+
+// Renders one page (1-based) as PNG at `scale` times its natural size
+async function renderPdfPage(filePath, page, scale = 1, { password } = {}) {
+  if (!Number.isInteger(page) || page < 1) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Page numbers start at 1');
+  const zoom = Number(scale);
+  if (!Number.isFinite(zoom) || zoom < MIN_SCALE || zoom > MAX_SCALE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Scale must be between ${MIN_SCALE} and ${MAX_SCALE}`);
+  const { stamp } = await cachedDocument(filePath);
+  const info = await getPdfInfo(filePath, { password });
+  if (page > info.pageCount) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Page ${page} is past the end of the document (${info.pageCount} pages)`);
+  const { width, height } = info.pages[page - 1];
+  if (width * height * zoom * zoom > MAX_PAGE_PIXELS) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Page ${page} is too large to render at scale ${zoom}`);
+
+  const key = `${filePath}:${stamp}:${page}:${zoom}`;
+  // The promise is cached, so a page requested again while rendering is only rendered once
+  let rendering = pages.get(key);
+  if (!rendering) {
+    const dpi = String(Math.round(POINTS_PER_INCH * zoom * 100) / 100);
+    rendering = withRenderSlot(() => runPoppler('pdftoppm', ['-png', '-singlefile', '-f', String(page), '-l', String(page), '-r', dpi, ...passwordArgs(password), filePath]))
+      .then((png) => {
+        const size = pngSize(png);
+        log.debug(`Rendered page ${page} of ${filePath} at ${zoom}x (${size.width}x${size.height})`);
+        return { png, ...size };
+      });
+    rendering.catch(() => pages.delete(key));
+  }
+  pages.delete(key);
+  pages.set(key, rendering);
+  if (pages.size > MAX_CACHED_PAGES) pages.delete(pages.keys().next().value);
+  return { page, pageCount: info.pageCount, scale: zoom, ...(await rendering) };
+}
+
+function setupPdfHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('pdf:info', async (event, filePath, options) => getPdfInfo(filePath, options || {}));
+  ipcMain.handle('pdf:outline', async (event, filePath, options) => getPdfOutline(filePath, options || {}));
+  ipcMain.handle('pdf:renderPage', async (event, filePath, page, scale, options) => renderPdfPage(filePath, page, scale, options || {}));
+}
+
+module.exports = { setupPdfHandlers, getPdfInfo, getPdfOutline, renderPdfPage };
@@ -1,4 +1,4 @@
-const { contextBridge, ipcRenderer } = require('electron');
+const { contextBridge, ipcRenderer, webUtils } = require('electron');
 
 // Expose protected methods that allow the renderer process to use
 // the ipcRenderer without exposing the entire object
@@ -8,7 +8,32 @@ contextBridge.exposeInMainWorld('electronAPI', {
     create: (options) => ipcRenderer.invoke('terminal:create', options),
     write: (id, data) => ipcRenderer.invoke('terminal:write', id, data),
     resize: (id, cols, rows) => ipcRenderer.invoke('terminal:resize', id, cols, rows),
+    cd: (id, path) => ipcRenderer.invoke('terminal:cd', id, path),
+    listCommands: (id) => ipcRenderer.invoke('terminal:listCommands', id),
+    rerunCommand: (id, commandId, options) => ipcRenderer.invoke('terminal:rerunCommand', id, commandId, options),
+    rerunLastFailed: (id, options) => ipcRenderer.invoke('terminal:rerunLastFailed', id, options),
+    sendMouse: (id, mouseEvent) => ipcRenderer.invoke('terminal:sendMouse', id, mouseEvent),
+    setMouseForwarding: (id, enabled) => ipcRenderer.invoke('terminal:setMouseForwarding', id, enabled),
     close: (id) => ipcRenderer.invoke('terminal:close', id),
+    list: () => ipcRenderer.invoke('terminal:list'),
+    transfer: (id, targetWindowId) => ipcRenderer.invoke('terminal:transfer', id, targetWindowId),
+    getScrollback: (id, options) => ipcRenderer.invoke('terminal:getScrollback', id, options),
+    getMarks: (id) => ipcRenderer.invoke('terminal:getMarks', id),
+    getJumpTarget: (id, line, direction) => ipcRenderer.invoke('terminal:getJumpTarget', id, line, direction),
+    saveLog: (id) => ipcRenderer.invoke('terminal:saveLog', id),
+    listLogs: () => ipcRenderer.invoke('terminal:listLogs'),
+    readLog: (name) => ipcRenderer.invoke('terminal:readLog', name),
+    deleteLog: (name) => ipcRenderer.invoke('terminal:deleteLog', name),
+    onAttached: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('terminal:attached', handler);
+      return () => ipcRenderer.removeListener('terminal:attached', handler);
+    },
+    onDetached: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('terminal:detached', handler);
+      return () => ipcRenderer.removeListener('terminal:detached', handler);
+    },
     onData: (callback) => {
       console.log('[Preload] Setting up terminal:data listener');
       const handler = (event, data) => {
@@ -29,6 +54,21 @@ contextBridge.exposeInMainWorld('electronAPI', {
       // Return cleanup function
       return () => ipcRenderer.removeListener('terminal:exit', handler);
     },
+    onImage: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('terminal:image', handler);
+      return () => ipcRenderer.removeListener('terminal:image', handler);
+    },
+    onMouseMode: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('terminal:mouseMode', handler);
+      return () => ipcRenderer.removeListener('terminal:mouseMode', handler);
+    },
+    onScreenChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('terminal:screenChanged', handler);
+      return () => ipcRenderer.removeListener('terminal:screenChanged', handler);
+    },
     getSessionInfo: (id) => ipcRenderer.invoke('terminal:getSessionInfo', id),
     getHistory: (id) => ipcRenderer.invoke('terminal:getHistory', id),
     getCwd: (id) => ipcRenderer.invoke('terminal:getCwd', id),
@@ -39,11 +79,17 @@ contextBridge.exposeInMainWorld('electronAPI', {
   fs: {
     readFile: (path) => ipcRenderer.invoke('fs:readFile', path),
     writeFile: (path, content) => ipcRenderer.invoke('fs:writeFile', path, content),
+    save: (path, content) => ipcRenderer.invoke('fs:save', path, content),
+    saveWithElevation: (path, content) => ipcRenderer.invoke('fs:saveWithElevation', path, content),
+    getWriteAccess: (path) => ipcRenderer.invoke('fs:getWriteAccess', path),
     exists: (path) => ipcRenderer.invoke('fs:exists', path),
     getInfo: (path) => ipcRenderer.invoke('fs:getInfo', path),
+    detectFileType: (path) => ipcRenderer.invoke('fs:detectFileType', path),
+    getFilesMetadata: (paths) => ipcRenderer.invoke('fs:getFilesMetadata', paths),
+    existsBatch: (paths) => ipcRenderer.invoke('fs:existsBatch', paths),
     createFile: (path, content) => ipcRenderer.invoke('fs:createFile', path, content),
-    deleteFile: (path) => ipcRenderer.invoke('fs:deleteFile', path),
-    rename: (from, to) => ipcRenderer.invoke('fs:rename', from, to),
+    deleteFile: (path, options) => ipcRenderer.invoke('fs:deleteFile', path, options),
+    rename: (from, to, options) => ipcRenderer.invoke('fs:rename', from, to, options),
     copy: (from, to) => ipcRenderer.invoke('fs:copy', from, to),
     listDirectory: (path) => ipcRenderer.invoke('fs:listDirectory', path),
     watchFile: (path) => ipcRenderer.invoke('fs:watchFile', path),
@@ -66,7 +112,7 @@ contextBridge.exposeInMainWorld('electronAPI', {
     setBounds: (id, bounds) => ipcRenderer.invoke('browser:setBounds', id, bounds),
     show: (id) => ipcRenderer.invoke('browser:show', id),
     hide: (id) => ipcRenderer.invoke('browser:hide', id),
-    openDevTools: (id) => ipcRenderer.invoke('browser:openDevTools', id),
+    openDevTools: (id, options) => ipcRenderer.invoke('browser:openDevTools', id, options),
     closeDevTools: (id) => ipcRenderer.invoke('browser:closeDevTools', id),
     captureScreenshot: (id) => ipcRenderer.invoke('browser:captureScreenshot', id),
     captureRegion: (id, rect) => ipcRenderer.invoke('browser:captureRegion', id, rect),
@@ -78,6 +124,71 @@ contextBridge.exposeInMainWorld('electronAPI', {
     getTitle: (id) => ipcRenderer.invoke('browser:getTitle', id),
     canGoBack: (id) => ipcRenderer.invoke('browser:canGoBack', id),
     canGoForward: (id) => ipcRenderer.invoke('browser:canGoForward', id),
+    getStartPage: (options) => ipcRenderer.invoke('browser:getStartPage', options),
+    setStartPage: (url) => ipcRenderer.invoke('browser:setStartPage', url),
+    getStartPageData: (options) => ipcRenderer.invoke('browser:getStartPageData', options),
+    autocomplete: (query, options) => ipcRenderer.invoke('browser:autocomplete', query, options),
+    resolveInput: (text, options) => ipcRenderer.invoke('browser:resolveInput', text, options),
+    searchHistory: (query, options) => ipcRenderer.invoke('browser:searchHistory', query, options),
+    listHistory: (options) => ipcRenderer.invoke('browser:listHistory', options),
+    deleteHistory: (options) => ipcRenderer.invoke('browser:deleteHistory', options),
+    listBookmarks: (options) => ipcRenderer.invoke('browser:listBookmarks', options),
+    addBookmark: (bookmark, options) => ipcRenderer.invoke('browser:addBookmark', bookmark, options),
+    updateBookmark: (id, changes, options) => ipcRenderer.invoke('browser:updateBookmark', id, changes, options),
+    removeBookmark: (id, options) => ipcRenderer.invoke('browser:removeBookmark', id, options),
+    getConsole: (id, filters) => ipcRenderer.invoke('browser:getConsole', id, filters),
+    listFrames: (id) => ipcRenderer.invoke('browser:listFrames', id),
+    clearConsole: (id) => ipcRenderer.invoke('browser:clearConsole', id),
+    onConsole: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('browser:console', handler);
+      return () => ipcRenderer.removeListener('browser:console', handler);
+    },
+    getSession: () => ipcRenderer.invoke('browser:getSession'),
+    clearSession: () => ipcRenderer.invoke('browser:clearSession'),
+    listNetworkProfiles: () => ipcRenderer.invoke('browser:listNetworkProfiles'),
+    getThrottling: (id) => ipcRenderer.invoke('browser:getThrottling', id),
+    setNetworkConditions: (id, profile) => ipcRenderer.invoke('browser:setNetworkConditions', id, profile),
+    setCpuThrottling: (id, rate) => ipcRenderer.invoke('browser:setCpuThrottling', id, rate),
+    onThrottlingChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('browser:throttlingChanged', handler);
+      return () => ipcRenderer.removeListener('browser:throttlingChanged', handler);
+    },
+    startActionRecording: (id) => ipcRenderer.invoke('browser:startActionRecording', id),
+    stopActionRecording: (id) => ipcRenderer.invoke('browser:stopActionRecording', id),
+    getActionRecording: (id) => ipcRenderer.invoke('browser:getActionRecording', id),
+    replayActions: (id, script, options) => ipcRenderer.invoke('browser:replayActions', id, script, options),
+    exportPlaywright: (script) => ipcRenderer.invoke('browser:exportPlaywright', script),
+    saveActionScript: (script, filePath, format) => ipcRenderer.invoke('browser:saveActionScript', script, filePath, format),
+    loadActionScript: (filePath) => ipcRenderer.invoke('browser:loadActionScript', filePath),
+    onActionRecorded: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('browser:actionRecorded', handler);
+      return () => ipcRenderer.removeListener('browser:actionRecorded', handler);
+    },
+    startAutomationBridge: (options) => ipcRenderer.invoke('browser:startAutomationBridge', options),
+    stopAutomationBridge: () => ipcRenderer.invoke('browser:stopAutomationBridge'),
+    getAutomationBridge: () => ipcRenderer.invoke('browser:getAutomationBridge'),
+    onAutomationChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('browser:automationChanged', handler);
+      return () => ipcRenderer.removeListener('browser:automationChanged', handler);
+    },
+    getNavigationPolicy: () => ipcRenderer.invoke('browser:getNavigationPolicy'),
+    setNavigationPolicy: (policy, options) => ipcRenderer.invoke('browser:setNavigationPolicy', policy, options),
+    allowHost: (host) => ipcRenderer.invoke('browser:allowHost', host),
+    openBlocked: (id, url, target) => ipcRenderer.invoke('browser:openBlocked', id, url, target),
+    onNavigationBlocked: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('browser:navigationBlocked', handler);
+      return () => ipcRenderer.removeListener('browser:navigationBlocked', handler);
+    },
+    onBookmarksChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('browser:bookmarksChanged', handler);
+      return () => ipcRenderer.removeListener('browser:bookmarksChanged', handler);
+    },
     onNavigate: (callback) => {
       ipcRenderer.on('browser:navigate', (event, data) => callback(data));
     },
@@ -99,7 +210,9 @@ contextBridge.exposeInMainWorld('electronAPI', {
   editor: {
     saveSession: (sessionData) => ipcRenderer.invoke('editor:saveSession', sessionData),
     loadSession: (sessionId) => ipcRenderer.invoke('editor:loadSession', sessionId),
-    listSessions: () => ipcRenderer.invoke('editor:listSessions'),
+    listSessions: (allWorkspaces) => ipcRenderer.invoke('editor:listSessions', allWorkspaces),
+    getFoldState: (path, content) => ipcRenderer.invoke('editor:getFoldState', path, content),
+    saveFoldState: (path, folds, content) => ipcRenderer.invoke('editor:saveFoldState', path, folds, content),
   },
 
   // Store API
@@ -127,12 +240,914 @@ contextBridge.exposeInMainWorld('electronAPI', {
     setAlwaysOnTop: (flag) => ipcRenderer.invoke('window:setAlwaysOnTop', flag),
   },
 
+  // Command Registry API
+  commands: {
+    list: (context) => ipcRenderer.invoke('commands:list', context),
+    execute: (id, args, context) => ipcRenderer.invoke('commands:execute', id, args, context),
+    setContext: (key, value) => ipcRenderer.invoke('commands:setContext', key, value),
+    onInvoke: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('commands:invoke', handler);
+      return () => ipcRenderer.removeListener('commands:invoke', handler);
+    },
+  },
+
+  // Logging & Diagnostics API
+  logs: {
+    getRecent: (options) => ipcRenderer.invoke('logs:getRecent', options),
+    setLevel: (module, level) => ipcRenderer.invoke('logs:setLevel', module, level),
+    getLevels: () => ipcRenderer.invoke('logs:getLevels'),
+    exportDiagnostics: (targetPath) => ipcRenderer.invoke('logs:exportDiagnostics', targetPath),
+  },
+
+  // Crash Reporting API
+  crash: {
+    list: () => ipcRenderer.invoke('crash:list'),
+    get: (id) => ipcRenderer.invoke('crash:get', id),
+    delete: (id) => ipcRenderer.invoke('crash:delete', id),
+    getSettings: () => ipcRenderer.invoke('crash:getSettings'),
+    setUploadEnabled: (enabled, endpoint) => ipcRenderer.invoke('crash:setUploadEnabled', enabled, endpoint),
+    upload: (id) => ipcRenderer.invoke('crash:upload', id),
+    reportRendererError: (error) => ipcRenderer.invoke('crash:reportRendererError', error),
+    onRecovered: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('app:recoveredFromError', handler);
+      return () => ipcRenderer.removeListener('app:recoveredFromError', handler);
+    },
+  },
+
+  // Telemetry API (opt-in)
+  telemetry: {
+    getSettings: () => ipcRenderer.invoke('telemetry:getSettings'),
+    setEnabled: (enabled) => ipcRenderer.invoke('telemetry:setEnabled', enabled),
+    setUploadEnabled: (enabled, endpoint) => ipcRenderer.invoke('telemetry:setUploadEnabled', enabled, endpoint),
+    record: (name, properties) => ipcRenderer.invoke('telemetry:record', name, properties),
+    view: () => ipcRenderer.invoke('telemetry:view'),
+    clear: () => ipcRenderer.invoke('telemetry:clear'),
+    uploadNow: () => ipcRenderer.invoke('telemetry:uploadNow'),
+  },
+
+  // Workspace Trust & Onboarding API
+  trust: {
+    get: (path) => ipcRenderer.invoke('trust:get', path),
+    set: (path, trusted) => ipcRenderer.invoke('trust:set', path, trusted),
+    remove: (path) => ipcRenderer.invoke('trust:remove', path),
+    list: () => ipcRenderer.invoke('trust:list'),
+    onChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('trust:changed', handler);
+      return () => ipcRenderer.removeListener('trust:changed', handler);
+    },
+  },
+  onboarding: {
+    getState: () => ipcRenderer.invoke('onboarding:getState'),
+    completeStep: (step) => ipcRenderer.invoke('onboarding:completeStep', step),
+    skip: () => ipcRenderer.invoke('onboarding:skip'),
+    reset: () => ipcRenderer.invoke('onboarding:reset'),
+  },
+
+  // Event Bus API
+  events: {
+    getCatalog: () => ipcRenderer.invoke('events:getCatalog'),
+    replay: (sinceSeq, channels) => ipcRenderer.invoke('events:replay', sinceSeq, channels),
+    getLastSeq: () => ipcRenderer.invoke('events:getLastSeq'),
+  },
+
+  // Background Jobs API
+  jobs: {
+    list: (includeFinished) => ipcRenderer.invoke('jobs:list', includeFinished),
+    get: (id) => ipcRenderer.invoke('jobs:get', id),
+    cancel: (id) => ipcRenderer.invoke('jobs:cancel', id),
+    onStarted: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('job:started', handler);
+      return () => ipcRenderer.removeListener('job:started', handler);
+    },
+    onProgress: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('job:progress', handler);
+      return () => ipcRenderer.removeListener('job:progress', handler);
+    },
+    onCompleted: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('job:completed', handler);
+      return () => ipcRenderer.removeListener('job:completed', handler);
+    },
+  },
+
+  // Language Server API
+  lsp: {
+    listServers: () => ipcRenderer.invoke('lsp:listServers'),
+    shutdown: () => ipcRenderer.invoke('lsp:shutdown'),
+  },
+
+  // Refactoring API
+  refactor: {
+    renameSymbol: (options) => ipcRenderer.invoke('refactor:renameSymbol', options),
+    applyWorkspaceEdit: (workspaceEdit, openBuffers, description, options) => ipcRenderer.invoke('refactor:applyWorkspaceEdit', workspaceEdit, openBuffers, description, options),
+    replaceInProject: (request) => ipcRenderer.invoke('refactor:replaceInProject', request),
+    undo: (journalId) => ipcRenderer.invoke('refactor:undo', journalId),
+    listJournal: () => ipcRenderer.invoke('refactor:listJournal'),
+    onApplied: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('refactor:applied', handler);
+      return () => ipcRenderer.removeListener('refactor:applied', handler);
+    },
+  },
+
+  // Snippets API
+  snippets: {
+    list: (language) => ipcRenderer.invoke('snippets:list', language),
+    get: (id) => ipcRenderer.invoke('snippets:get', id),
+    create: (input) => ipcRenderer.invoke('snippets:create', input),
+    update: (id, changes, expectedRevision) => ipcRenderer.invoke('snippets:update', id, changes, expectedRevision),
+    delete: (id) => ipcRenderer.invoke('snippets:delete', id),
+    importVSCode: (filePath, language) => ipcRenderer.invoke('snippets:importVSCode', filePath, language),
+    lookup: (language, typed, context) => ipcRenderer.invoke('snippets:lookup', language, typed, context),
+    resolve: (id, context) => ipcRenderer.invoke('snippets:resolve', id, context),
+  },
+
+  // Search API
+  search: {
+    text: (rootPath, query, options) => ipcRenderer.invoke('search:text', rootPath, query, options),
+  },
+
+  // TODO Index API
+  todos: {
+    index: (rootPath) => ipcRenderer.invoke('todos:index', rootPath),
+    stop: (rootPath) => ipcRenderer.invoke('todos:stop', rootPath),
+    list: (rootPath, filter) => ipcRenderer.invoke('todos:list', rootPath, filter),
+    getTags: () => ipcRenderer.invoke('todos:getTags'),
+    setTags: (tags) => ipcRenderer.invoke('todos:setTags', tags),
+    onUpdated: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('todos:updated', handler);
+      return () => ipcRenderer.removeListener('todos:updated', handler);
+    },
+  },
+
+  // EditorConfig API
+  editorconfig: {
+    resolve: (filePath) => ipcRenderer.invoke('editorconfig:resolve', filePath),
+    getApplyOnSave: () => ipcRenderer.invoke('editorconfig:getApplyOnSave'),
+    setApplyOnSave: (enabled) => ipcRenderer.invoke('editorconfig:setApplyOnSave', enabled),
+  },
+
+  // Clipboard History API
+  clipboard: {
+    getHistory: (query, limit) => ipcRenderer.invoke('clipboard:getHistory', query, limit),
+    pasteHistoryItem: (id, options) => ipcRenderer.invoke('clipboard:pasteHistoryItem', id, options),
+    pin: (id, pinned) => ipcRenderer.invoke('clipboard:pin', id, pinned),
+    remove: (id) => ipcRenderer.invoke('clipboard:remove', id),
+    clear: (includePinned) => ipcRenderer.invoke('clipboard:clear', includePinned),
+    writeSensitive: (text, clearAfterMs) => ipcRenderer.invoke('clipboard:writeSensitive', text, clearAfterMs),
+    getSettings: () => ipcRenderer.invoke('clipboard:getSettings'),
+    updateSettings: (changes) => ipcRenderer.invoke('clipboard:updateSettings', changes),
+    onChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('clipboard:changed', handler);
+      return () => ipcRenderer.removeListener('clipboard:changed', handler);
+    },
+  },
+
+  // Notifications API
+  notifications: {
+    show: (options) => ipcRenderer.invoke('notifications:show', options),
+    notifyWhenCommandFinishes: (terminalId, options) => ipcRenderer.invoke('notifications:notifyWhenCommandFinishes', terminalId, options),
+    cancelCommandWatch: (terminalId) => ipcRenderer.invoke('notifications:cancelCommandWatch', terminalId),
+    getDoNotDisturb: () => ipcRenderer.invoke('notifications:getDoNotDisturb'),
+    setDoNotDisturb: (changes) => ipcRenderer.invoke('notifications:setDoNotDisturb', changes),
+    getMissed: (clear) => ipcRenderer.invoke('notifications:getMissed', clear),
+    addReminder: (title, at, options) => ipcRenderer.invoke('notifications:addReminder', title, at, options),
+    listReminders: () => ipcRenderer.invoke('notifications:listReminders'),
+    cancelReminder: (id) => ipcRenderer.invoke('notifications:cancelReminder', id),
+    onClicked: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('notification:clicked', handler);
+      return () => ipcRenderer.removeListener('notification:clicked', handler);
+    },
+  },
+
+  // File Index API
+  fileIndex: {
+    open: (rootPath) => ipcRenderer.invoke('fileIndex:open', rootPath),
+    close: (rootPath) => ipcRenderer.invoke('fileIndex:close', rootPath),
+    query: (rootPath, query, limit) => ipcRenderer.invoke('fileIndex:query', rootPath, query, limit),
+    getStatus: (rootPath) => ipcRenderer.invoke('fileIndex:getStatus', rootPath),
+    rebuild: (rootPath) => ipcRenderer.invoke('fileIndex:rebuild', rootPath),
+    onStatus: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('index:status', handler);
+      return () => ipcRenderer.removeListener('index:status', handler);
+    },
+  },
+
+  // Symbol Index API
+  symbols: {
+    query: (rootPath, query, limit) => ipcRenderer.invoke('symbols:query', rootPath, query, limit),
+    getFileSymbols: (filePath) => ipcRenderer.invoke('symbols:getFileSymbols', filePath),
+    getStatus: (rootPath) => ipcRenderer.invoke('symbols:getStatus', rootPath),
+  },
+
+  // Quick Open API
+  quickOpen: {
+    query: (query, options) => ipcRenderer.invoke('quickOpen:query', query, options),
+  },
+
+  // Windows API
+  windows: {
+    open: (workspacePath, options) => ipcRenderer.invoke('windows:open', workspacePath, options),
+    list: () => ipcRenderer.invoke('windows:list'),
+    getContext: () => ipcRenderer.invoke('windows:getContext'),
+    setWorkspace: (workspacePath) => ipcRenderer.invoke('windows:setWorkspace', workspacePath),
+    moveTerminal: (terminalId, targetWindowId) => ipcRenderer.invoke('windows:moveTerminal', terminalId, targetWindowId),
+    moveEditor: (editorState, targetWindowId) => ipcRenderer.invoke('windows:moveEditor', editorState, targetWindowId),
+    onEditorAttached: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('editor:attached', handler);
+      return () => ipcRenderer.removeListener('editor:attached', handler);
+    },
+    focus: (windowId) => ipcRenderer.invoke('windows:focus', windowId),
+    onOpened: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('window:opened', handler);
+      return () => ipcRenderer.removeListener('window:opened', handler);
+    },
+    onClosed: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('window:closed', handler);
+      return () => ipcRenderer.removeListener('window:closed', handler);
+    },
+  },
+
+  // Workbench open routing API
+  workbench: {
+    openPaths: (targets, options) => ipcRenderer.invoke('workbench:openPaths', targets, options),
+    documentClosed: (filePath, waitId) => ipcRenderer.invoke('workbench:documentClosed', filePath, waitId),
+    onOpenFile: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('workbench:openFile', handler);
+      return () => ipcRenderer.removeListener('workbench:openFile', handler);
+    },
+    onOpenDiff: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('workbench:openDiff', handler);
+      return () => ipcRenderer.removeListener('workbench:openDiff', handler);
+    },
+  },
+
+  // File association API
+  fileAssociations: {
+    getStatus: () => ipcRenderer.invoke('fileAssociations:getStatus'),
+    register: () => ipcRenderer.invoke('fileAssociations:register'),
+    unregister: () => ipcRenderer.invoke('fileAssociations:unregister'),
+  },
+
+  // Drag and drop API
+  dragDrop: {
+    // Files from a DOM drop event no longer carry .path; resolve it here
+    getPathForFile: (file) => webUtils.getPathForFile(file),
+    drop: (drop) => ipcRenderer.invoke('dragDrop:drop', drop),
+    getSettings: () => ipcRenderer.invoke('dragDrop:getSettings'),
+    updateSettings: (changes) => ipcRenderer.invoke('dragDrop:updateSettings', changes),
+  },
+
+  // Secrets API (values are write-only from the renderer)
+  secrets: {
+    set: (name, value) => ipcRenderer.invoke('secrets:set', name, value),
+    delete: (name) => ipcRenderer.invoke('secrets:delete', name),
+    list: () => ipcRenderer.invoke('secrets:list'),
+    has: (name) => ipcRenderer.invoke('secrets:has', name),
+  },
+
+  // AI assistant API
+  ai: {
+    chat: (request) => ipcRenderer.invoke('ai:chat', request),
+    cancel: (requestId) => ipcRenderer.invoke('ai:cancel', requestId),
+    listProviders: () => ipcRenderer.invoke('ai:listProviders'),
+    saveProvider: (provider) => ipcRenderer.invoke('ai:saveProvider', provider),
+    deleteProvider: (providerId) => ipcRenderer.invoke('ai:deleteProvider', providerId),
+    setDefaultProvider: (providerId) => ipcRenderer.invoke('ai:setDefaultProvider', providerId),
+    listModels: (providerId) => ipcRenderer.invoke('ai:listModels', providerId),
+    onToken: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('ai:token', handler);
+      return () => ipcRenderer.removeListener('ai:token', handler);
+    },
+    onDone: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('ai:done', handler);
+      return () => ipcRenderer.removeListener('ai:done', handler);
+    },
+    onError: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('ai:error', handler);
+      return () => ipcRenderer.removeListener('ai:error', handler);
+    },
+  },
+
+  // Terminal AI API
+  terminalAi: {
+    explain: (text, options) => ipcRenderer.invoke('terminalAi:explain', text, options),
+    generate: (request, options) => ipcRenderer.invoke('terminalAi:generate', request, options),
+    classify: (command) => ipcRenderer.invoke('terminalAi:classify', command),
+    insert: (terminalId, command, options) => ipcRenderer.invoke('terminalAi:insert', terminalId, command, options),
+  },
+
+  // Inline completion API
+  completion: {
+    request: (request) => ipcRenderer.invoke('completion:request', request),
+    cancel: (filePath) => ipcRenderer.invoke('completion:cancel', filePath),
+    getSettings: () => ipcRenderer.invoke('completion:getSettings'),
+    updateSettings: (changes) => ipcRenderer.invoke('completion:updateSettings', changes),
+    getStats: () => ipcRenderer.invoke('completion:getStats'),
+    clearCache: () => ipcRenderer.invoke('completion:clearCache'),
+  },
+
+  // Semantic search API
+  embeddings: {
+    index: (rootPath, options) => ipcRenderer.invoke('embeddings:index', rootPath, options),
+    search: (rootPath, query, options) => ipcRenderer.invoke('embeddings:search', rootPath, query, options),
+    getStatus: (rootPath) => ipcRenderer.invoke('embeddings:getStatus', rootPath),
+    clear: (rootPath) => ipcRenderer.invoke('embeddings:clear', rootPath),
+    getSettings: () => ipcRenderer.invoke('embeddings:getSettings'),
+    updateSettings: (changes) => ipcRenderer.invoke('embeddings:updateSettings', changes),
+  },
+
+  // AI git writing API (drafts only; never commits)
+  gitAi: {
+    generateCommitMessage: (cwd, overrides) => ipcRenderer.invoke('gitAi:generateCommitMessage', cwd, overrides),
+    generatePrDescription: (cwd, options) => ipcRenderer.invoke('gitAi:generatePrDescription', cwd, options),
+    getRules: () => ipcRenderer.invoke('gitAi:getRules'),
+    updateRules: (changes) => ipcRenderer.invoke('gitAi:updateRules', changes),
+  },
+
+  // Automation API (macros and scripts)
+  automation: {
+    startRecording: (name) => ipcRenderer.invoke('automation:startRecording', name),
+    stopRecording: (save) => ipcRenderer.invoke('automation:stopRecording', save),
+    recordAction: (action) => ipcRenderer.invoke('automation:recordAction', action),
+    getRecording: () => ipcRenderer.invoke('automation:getRecording'),
+    listMacros: () => ipcRenderer.invoke('automation:listMacros'),
+    saveMacro: (name, actions) => ipcRenderer.invoke('automation:saveMacro', name, actions),
+    deleteMacro: (name) => ipcRenderer.invoke('automation:deleteMacro', name),
+    playMacro: (name) => ipcRenderer.invoke('automation:playMacro', name),
+    listScripts: () => ipcRenderer.invoke('automation:listScripts'),
+    saveScript: (name, source) => ipcRenderer.invoke('automation:saveScript', name, source),
+    deleteScript: (name) => ipcRenderer.invoke('automation:deleteScript', name),
+    runScript: (source) => ipcRenderer.invoke('automation:runScript', source),
+    onRecordingChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('automation:recordingChanged', handler);
+      return () => ipcRenderer.removeListener('automation:recordingChanged', handler);
+    },
+  },
+
+  // File watcher health API
+  watcher: {
+    getHealth: () => ipcRenderer.invoke('watcher:getHealth'),
+    rescan: (root) => ipcRenderer.invoke('watcher:rescan', root),
+    watchPaths: (subscriptionId, globs, eventKinds) => ipcRenderer.invoke('watcher:watchPaths', subscriptionId, globs, eventKinds),
+    unwatch: (subscriptionId) => ipcRenderer.invoke('watcher:unwatch', subscriptionId),
+    listSubscriptions: () => ipcRenderer.invoke('watcher:listSubscriptions'),
+    getStats: () => ipcRenderer.invoke('watcher:getStats'),
+    onHealth: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('watcher:health', handler);
+      return () => ipcRenderer.removeListener('watcher:health', handler);
+    },
+    onChanges: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('watcher:changes', handler);
+      return () => ipcRenderer.removeListener('watcher:changes', handler);
+    },
+  },
+
+  // Index scope API
+  indexScope: {
+    get: (rootPath) => ipcRenderer.invoke('indexScope:get', rootPath),
+    update: (rootPath, changes) => ipcRenderer.invoke('indexScope:update', rootPath, changes),
+    isIgnored: (rootPath, targetPath, type) => ipcRenderer.invoke('indexScope:isIgnored', rootPath, targetPath, type)
+  },
+
+  // Workspace state export/import API
+  workspaceState: {
+    export: (options) => ipcRenderer.invoke('workspaceState:export', options),
+    inspect: (path) => ipcRenderer.invoke('workspaceState:inspect', path),
+    import: (path, options) => ipcRenderer.invoke('workspaceState:import', path, options)
+  },
+
+  // Guardrails API
+  guardrails: {
+    getPolicy: () => ipcRenderer.invoke('guardrails:getPolicy'),
+    updatePolicy: (changes) => ipcRenderer.invoke('guardrails:updatePolicy', changes),
+    resetPolicy: () => ipcRenderer.invoke('guardrails:resetPolicy'),
+    check: (operation, paths, workspacePath) => ipcRenderer.invoke('guardrails:check', operation, paths, workspacePath)
+  },
+
+  // Encryption-at-rest API
+  encryption: {
+    getStatus: () => ipcRenderer.invoke('encryption:getStatus'),
+    configure: (changes, options) => ipcRenderer.invoke('encryption:configure', changes, options),
+    migrate: (categories) => ipcRenderer.invoke('encryption:migrate', categories)
+  },
+
+  // Live share API
+  liveShare: {
+    start: (options) => ipcRenderer.invoke('liveShare:start', options),
+    stop: (sessionId) => ipcRenderer.invoke('liveShare:stop', sessionId),
+    list: () => ipcRenderer.invoke('liveShare:list'),
+    updateFile: (sessionId, path, content) => ipcRenderer.invoke('liveShare:updateFile', sessionId, path, content),
+    onViewers: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('liveShare:viewers', handler);
+      return () => ipcRenderer.removeListener('liveShare:viewers', handler);
+    },
+    onEnded: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('liveShare:ended', handler);
+      return () => ipcRenderer.removeListener('liveShare:ended', handler);
+    }
+  },
+
+  // Collaborative editing API
+  collab: {
+    start: (options) => ipcRenderer.invoke('collab:start', options),
+    stop: (sessionId) => ipcRenderer.invoke('collab:stop', sessionId),
+    getSession: (sessionId) => ipcRenderer.invoke('collab:getSession', sessionId),
+    getDocument: (sessionId, path) => ipcRenderer.invoke('collab:getDocument', sessionId, path),
+    applyOps: (sessionId, path, ops) => ipcRenderer.invoke('collab:applyOps', sessionId, path, ops),
+    setPresence: (sessionId, path, selection) => ipcRenderer.invoke('collab:setPresence', sessionId, path, selection),
+    save: (sessionId, path) => ipcRenderer.invoke('collab:save', sessionId, path),
+    reload: (sessionId, path) => ipcRenderer.invoke('collab:reload', sessionId, path),
+    onOps: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('collab:ops', handler);
+      return () => ipcRenderer.removeListener('collab:ops', handler);
+    },
+    onParticipants: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('collab:participants', handler);
+      return () => ipcRenderer.removeListener('collab:participants', handler);
+    },
+    onPresence: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('collab:presence', handler);
+      return () => ipcRenderer.removeListener('collab:presence', handler);
+    },
+    onDiskChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('collab:diskChanged', handler);
+      return () => ipcRenderer.removeListener('collab:diskChanged', handler);
+    },
+    onEnded: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('collab:ended', handler);
+      return () => ipcRenderer.removeListener('collab:ended', handler);
+    }
+  },
+
+  // Tunnel API
+  tunnel: {
+    getSettings: () => ipcRenderer.invoke('tunnel:getSettings'),
+    updateSettings: (changes) => ipcRenderer.invoke('tunnel:updateSettings', changes),
+    start: (options) => ipcRenderer.invoke('tunnel:start', options),
+    stop: (id) => ipcRenderer.invoke('tunnel:stop', id),
+    list: () => ipcRenderer.invoke('tunnel:list'),
+    getOutput: (id) => ipcRenderer.invoke('tunnel:getOutput', id),
+    onStatus: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('tunnel:status', handler);
+      return () => ipcRenderer.removeListener('tunnel:status', handler);
+    }
+  },
+
+  // Mock server API (.forge/mocks.json)
+  mock: {
+    start: (options) => ipcRenderer.invoke('mock:start', options),
+    stop: () => ipcRenderer.invoke('mock:stop'),
+    getStatus: () => ipcRenderer.invoke('mock:getStatus'),
+    createConfig: () => ipcRenderer.invoke('mock:createConfig'),
+    onStatus: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('mock:status', handler);
+      return () => ipcRenderer.removeListener('mock:status', handler);
+    },
+    onRequest: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('mock:request', handler);
+      return () => ipcRenderer.removeListener('mock:request', handler);
+    }
+  },
+
+  // OpenAPI / Swagger spec API
+  openApi: {
+    findSpecs: () => ipcRenderer.invoke('openapi:findSpecs'),
+    validate: (filePath) => ipcRenderer.invoke('openapi:validate', filePath),
+    listOperations: (filePath) => ipcRenderer.invoke('openapi:listOperations', filePath),
+    generateRequest: (filePath, operation, options) => ipcRenderer.invoke('openapi:generateRequest', filePath, operation, options),
+    startMock: (filePath, options) => ipcRenderer.invoke('openapi:startMock', filePath, options)
+  },
+
+  // GraphQL API (endpoints and collections are per workspace)
+  graphql: {
+    listEndpoints: () => ipcRenderer.invoke('graphql:listEndpoints'),
+    saveEndpoint: (endpoint) => ipcRenderer.invoke('graphql:saveEndpoint', endpoint),
+    deleteEndpoint: (id) => ipcRenderer.invoke('graphql:deleteEndpoint', id),
+    getSchema: (endpointId, options) => ipcRenderer.invoke('graphql:getSchema', endpointId, options),
+    validate: (endpointId, query) => ipcRenderer.invoke('graphql:validate', endpointId, query),
+    complete: (endpointId, query, offset) => ipcRenderer.invoke('graphql:complete', endpointId, query, offset),
+    execute: (endpointId, request) => ipcRenderer.invoke('graphql:execute', endpointId, request),
+    subscribe: (endpointId, request) => ipcRenderer.invoke('graphql:subscribe', endpointId, request),
+    unsubscribe: (subscriptionId) => ipcRenderer.invoke('graphql:unsubscribe', subscriptionId),
+    listCollections: () => ipcRenderer.invoke('graphql:listCollections'),
+    saveCollection: (collection) => ipcRenderer.invoke('graphql:saveCollection', collection),
+    deleteCollection: (id) => ipcRenderer.invoke('graphql:deleteCollection', id),
+    saveRequest: (collectionId, request) => ipcRenderer.invoke('graphql:saveRequest', collectionId, request),
+    deleteRequest: (collectionId, requestId) => ipcRenderer.invoke('graphql:deleteRequest', collectionId, requestId),
+    onSubscriptionData: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('graphql:subscriptionData', handler);
+      return () => ipcRenderer.removeListener('graphql:subscriptionData', handler);
+    },
+    onSubscriptionEnded: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('graphql:subscriptionEnded', handler);
+      return () => ipcRenderer.removeListener('graphql:subscriptionEnded', handler);
+    }
+  },
+
+  // JWT API (decoded and verified locally)
+  jwt: {
+    decode: (token) => ipcRenderer.invoke('jwt:decode', token),
+    verify: (token, options) => ipcRenderer.invoke('jwt:verify', token, options),
+    find: (text) => ipcRenderer.invoke('jwt:find', text),
+    findInBrowser: (viewId) => ipcRenderer.invoke('jwt:findInBrowser', viewId)
+  },
+
+  // Utilities API (encode/decode, JSON/YAML, hashes, UUIDs, cron, timestamps)
+  utilities: {
+    transform: (operation, input, options) => ipcRenderer.invoke('utilities:transform', operation, input, options),
+    validateJson: (input) => ipcRenderer.invoke('utilities:validateJson', input),
+    hash: (input, options) => ipcRenderer.invoke('utilities:hash', input, options),
+    uuid: (options) => ipcRenderer.invoke('utilities:uuid', options),
+    explainCron: (expression, options) => ipcRenderer.invoke('utilities:explainCron', expression, options),
+    convertTimestamp: (input, options) => ipcRenderer.invoke('utilities:convertTimestamp', input, options),
+    listTimezones: () => ipcRenderer.invoke('utilities:listTimezones')
+  },
+
+  // CSS API (colors, contrast, lengths)
+  css: {
+    convertColor: (color) => ipcRenderer.invoke('css:convertColor', color),
+    checkContrast: (foreground, background, options) => ipcRenderer.invoke('css:checkContrast', foreground, background, options),
+    findColors: (text, options) => ipcRenderer.invoke('css:findColors', text, options),
+    convertUnit: (input, options) => ipcRenderer.invoke('css:convertUnit', input, options),
+    auditContrast: (viewId, options) => ipcRenderer.invoke('css:auditContrast', viewId, options)
+  },
+
+  // Workspace statistics API
+  stats: {
+    countLinesOfCode: (rootPath, options) => ipcRenderer.invoke('stats:countLinesOfCode', rootPath, options)
+  },
+
+  // Licenses API (dependency license scanning)
+  licenses: {
+    scan: (rootPath, options) => ipcRenderer.invoke('licenses:scan', rootPath, options),
+    getPolicy: (rootPath) => ipcRenderer.invoke('licenses:getPolicy', rootPath),
+    setUserPolicy: (policy) => ipcRenderer.invoke('licenses:setUserPolicy', policy)
+  },
+
+  // Forge remote API (GitHub / GitLab hosting of the repository)
+  forgeRemote: {
+    get: (repoRoot, remoteName) => ipcRenderer.invoke('forgeRemote:get', repoRoot, remoteName),
+    setHostKind: (host, kind) => ipcRenderer.invoke('forgeRemote:setHostKind', host, kind)
+  },
+
+  // Release API (changelog, version bumps, tags)
+  release: {
+    changelog: (rootPath, options) => ipcRenderer.invoke('release:changelog', rootPath, options),
+    writeChangelog: (rootPath, markdown, file) => ipcRenderer.invoke('release:writeChangelog', rootPath, markdown, file),
+    bumpVersions: (rootPath, options) => ipcRenderer.invoke('release:bumpVersions', rootPath, options),
+    createTag: (rootPath, options) => ipcRenderer.invoke('release:createTag', rootPath, options),
+    draft: (rootPath, options) => ipcRenderer.invoke('release:draft', rootPath, options),
+    prepare: (rootPath, options) => ipcRenderer.invoke('release:prepare', rootPath, options)
+  },
+
+  // CI API (GitHub Actions / GitLab CI for the checked-out branch)
+  ci: {
+    getStatus: (rootPath, options) => ipcRenderer.invoke('ci:getStatus', rootPath, options),
+    getJobs: (rootPath, runId, options) => ipcRenderer.invoke('ci:getJobs', rootPath, runId, options),
+    watch: (rootPath, options) => ipcRenderer.invoke('ci:watch', rootPath, options),
+    unwatch: (rootPath) => ipcRenderer.invoke('ci:unwatch', rootPath),
+    followLog: (rootPath, jobId, options) => ipcRenderer.invoke('ci:followLog', rootPath, jobId, options),
+    stopLog: (streamId) => ipcRenderer.invoke('ci:stopLog', streamId),
+    onStatus: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('ci:status', handler);
+      return () => ipcRenderer.removeListener('ci:status', handler);
+    },
+    onLog: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('ci:log', handler);
+      return () => ipcRenderer.removeListener('ci:log', handler);
+    },
+    onLogEnded: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('ci:logEnded', handler);
+      return () => ipcRenderer.removeListener('ci:logEnded', handler);
+    }
+  },
+
+  // Issues API (quick capture to the repository's GitHub / GitLab project)
+  issues: {
+    capture: (options) => ipcRenderer.invoke('issues:capture', options)
+  },
+
+  // Notebooks API (.ipynb files and Jupyter kernels)
+  notebooks: {
+    read: (filePath) => ipcRenderer.invoke('notebooks:read', filePath),
+    write: (filePath, model) => ipcRenderer.invoke('notebooks:write', filePath, model),
+    listKernelSpecs: (rootPath, options) => ipcRenderer.invoke('notebooks:listKernelSpecs', rootPath, options),
+    startKernel: (options) => ipcRenderer.invoke('notebooks:startKernel', options),
+    listKernels: () => ipcRenderer.invoke('notebooks:listKernels'),
+    execute: (kernelId, code, options) => ipcRenderer.invoke('notebooks:execute', kernelId, code, options),
+    interrupt: (kernelId) => ipcRenderer.invoke('notebooks:interrupt', kernelId),
+    restart: (kernelId) => ipcRenderer.invoke('notebooks:restart', kernelId),
+    shutdown: (kernelId) => ipcRenderer.invoke('notebooks:shutdown', kernelId),
+    onOutput: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('notebook:output', handler);
+      return () => ipcRenderer.removeListener('notebook:output', handler);
+    },
+    onKernelStatus: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('notebook:kernelStatus', handler);
+      return () => ipcRenderer.removeListener('notebook:kernelStatus', handler);
+    }
+  },
+
+  // PDF API (page rendering for the PDF viewer, via Poppler)
+  pdf: {
+    info: (filePath, options) => ipcRenderer.invoke('pdf:info', filePath, options),
+    outline: (filePath, options) => ipcRenderer.invoke('pdf:outline', filePath, options),
+    renderPage: (filePath, page, scale, options) => ipcRenderer.invoke('pdf:renderPage', filePath, page, scale, options)
+  },
+
+  // SVG API (sanitized previews and PNG export)
+  svg: {
+    sanitize: (filePath, options) => ipcRenderer.invoke('svg:sanitize', filePath, options),
+    rasterize: (filePath, options) => ipcRenderer.invoke('svg:rasterize', filePath, options),
+    exportPng: (filePath, outputPath, options) => ipcRenderer.invoke('svg:exportPng', filePath, outputPath, options)
+  },
+
+  // Fonts API (metadata and rendered samples of TTF / OTF / WOFF files)
+  fonts: {
+    info: (filePath, options) => ipcRenderer.invoke('fonts:info', filePath, options),
+    preview: (filePath, options) => ipcRenderer.invoke('fonts:preview', filePath, options)
+  },
+
+  // Models API (glTF / GLB / OBJ metadata and thumbnails)
+  models: {
+    info: (filePath) => ipcRenderer.invoke('models:info', filePath),
+    thumbnail: (filePath, options) => ipcRenderer.invoke('models:thumbnail', filePath, options)
+  },
+
+  // Schemas API (.proto / .fbs parsing, protoc / flatc compiles and binary payload decoding)
+  schemas: {
+    parse: (filePath, options) => ipcRenderer.invoke('schemas:parse', filePath, options),
+    compile: (filePath, options) => ipcRenderer.invoke('schemas:compile', filePath, options),
+    decode: (filePath, typeName, payload, options) => ipcRenderer.invoke('schemas:decode', filePath, typeName, payload, options),
+    decodeRaw: (payload, options) => ipcRenderer.invoke('schemas:decodeRaw', payload, options)
+  },
+
+  // Log Viewer API (log files and journald / macOS / Windows event sources, with filters and JSON-lines queries; lines arrive as logViewer:lines events)
+  logViewer: {
+    open: (filePath, options) => ipcRenderer.invoke('logViewer:open', filePath, options),
+    listSources: () => ipcRenderer.invoke('logViewer:listSources'),
+    openSource: (sourceId, params, options) => ipcRenderer.invoke('logViewer:openSource', sourceId, params, options),
+    read: (viewId, options) => ipcRenderer.invoke('logViewer:read', viewId, options),
+    setFilters: (viewId, filters, options) => ipcRenderer.invoke('logViewer:setFilters', viewId, filters, options),
+    setFollow: (viewId, follow) => ipcRenderer.invoke('logViewer:setFollow', viewId, follow),
+    close: (viewId) => ipcRenderer.invoke('logViewer:close', viewId),
+    inferSchema: (viewId, options) => ipcRenderer.invoke('logViewer:inferSchema', viewId, options),
+    listQueries: () => ipcRenderer.invoke('logViewer:listQueries'),
+    saveQuery: (query) => ipcRenderer.invoke('logViewer:saveQuery', query),
+    deleteQuery: (id) => ipcRenderer.invoke('logViewer:deleteQuery', id)
+  },
+
+  // Tasks API (.forge/tasks.toml pipelines)
+  tasks: {
+    load: () => ipcRenderer.invoke('tasks:load'),
+    runPipeline: (name) => ipcRenderer.invoke('tasks:runPipeline', name),
+    runTask: (name) => ipcRenderer.invoke('tasks:runTask', name),
+    cancel: (runId) => ipcRenderer.invoke('tasks:cancel', runId),
+    listRuns: () => ipcRenderer.invoke('tasks:listRuns'),
+    onStatus: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('task:status', handler);
+      return () => ipcRenderer.removeListener('task:status', handler);
+    },
+    onOutput: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('task:output', handler);
+      return () => ipcRenderer.removeListener('task:output', handler);
+    },
+    onRunFinished: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('task:runFinished', handler);
+      return () => ipcRenderer.removeListener('task:runFinished', handler);
+    }
+  },
+
+  // Problems API (diagnostics matched from task and terminal output)
+  problems: {
+    list: (filter) => ipcRenderer.invoke('problems:list', filter),
+    clear: (source) => ipcRenderer.invoke('problems:clear', source),
+    watchTerminal: (terminalId, matcherIds, options) => ipcRenderer.invoke('problems:watchTerminal', terminalId, matcherIds, options),
+    unwatchTerminal: (terminalId) => ipcRenderer.invoke('problems:unwatchTerminal', terminalId),
+    listMatchers: () => ipcRenderer.invoke('problems:listMatchers'),
+    saveMatcher: (id, definition) => ipcRenderer.invoke('problems:saveMatcher', id, definition),
+    deleteMatcher: (id) => ipcRenderer.invoke('problems:deleteMatcher', id),
+    onChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('problems:changed', handler);
+      return () => ipcRenderer.removeListener('problems:changed', handler);
+    }
+  },
+
+  // Run configurations API (per workspace)
+  runConfigs: {
+    list: () => ipcRenderer.invoke('runConfigs:list'),
+    get: (id) => ipcRenderer.invoke('runConfigs:get', id),
+    create: (input) => ipcRenderer.invoke('runConfigs:create', input),
+    update: (id, changes) => ipcRenderer.invoke('runConfigs:update', id, changes),
+    delete: (id) => ipcRenderer.invoke('runConfigs:delete', id),
+    importVSCode: () => ipcRenderer.invoke('runConfigs:importVSCode')
+  },
+
+  // Command history API (commands run in any terminal, via shell integration)
+  commandHistory: {
+    search: (query, filters) => ipcRenderer.invoke('commandHistory:search', query, filters),
+    delete: (command) => ipcRenderer.invoke('commandHistory:delete', command),
+    clear: () => ipcRenderer.invoke('commandHistory:clear'),
+    getSettings: () => ipcRenderer.invoke('commandHistory:getSettings'),
+    updateSettings: (changes) => ipcRenderer.invoke('commandHistory:updateSettings', changes),
+    onAdded: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('commandHistory:added', handler);
+      return () => ipcRenderer.removeListener('commandHistory:added', handler);
+    }
+  },
+
+  // Directory bookmarks API
+  bookmarks: {
+    list: () => ipcRenderer.invoke('bookmarks:list'),
+    add: (directory, label) => ipcRenderer.invoke('bookmarks:add', directory, label),
+    update: (id, changes) => ipcRenderer.invoke('bookmarks:update', id, changes),
+    remove: (id) => ipcRenderer.invoke('bookmarks:remove', id),
+    cdTerminal: (id, terminalId) => ipcRenderer.invoke('bookmarks:cdTerminal', id, terminalId),
+    onChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('bookmarks:changed', handler);
+      return () => ipcRenderer.removeListener('bookmarks:changed', handler);
+    }
+  },
+
+  // Terminal layout API (per-workspace tabs and splits)
+  terminalLayout: {
+    get: () => ipcRenderer.invoke('terminalLayout:get'),
+    save: (layout) => ipcRenderer.invoke('terminalLayout:save', layout),
+    clear: () => ipcRenderer.invoke('terminalLayout:clear'),
+    restore: (options) => ipcRenderer.invoke('terminalLayout:restore', options)
+  },
+
+  // Terminal annotations API (metadata attached to ranges of terminal output)
+  terminalAnnotations: {
+    list: (terminalId, range) => ipcRenderer.invoke('terminalAnnotations:list', terminalId, range),
+    remove: (terminalId, id) => ipcRenderer.invoke('terminalAnnotations:remove', terminalId, id),
+    clear: (terminalId, options) => ipcRenderer.invoke('terminalAnnotations:clear', terminalId, options),
+    onChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('terminalAnnotations:changed', handler);
+      return () => ipcRenderer.removeListener('terminalAnnotations:changed', handler);
+    }
+  },
+
+  // Save hooks API
+  saveHooks: {
+    list: () => ipcRenderer.invoke('saveHooks:list'),
+    configure: (id, options) => ipcRenderer.invoke('saveHooks:configure', id, options),
+    getTools: () => ipcRenderer.invoke('saveHooks:getTools'),
+    setTools: (tools) => ipcRenderer.invoke('saveHooks:setTools', tools),
+    onCompleted: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('saveHooks:completed', handler);
+      return () => ipcRenderer.removeListener('saveHooks:completed', handler);
+    }
+  },
+
+  // Project snapshots API
+  snapshots: {
+    export: (rootPath, options) => ipcRenderer.invoke('snapshots:export', rootPath, options)
+  },
+
+  // Languages API
+  languages: {
+    list: () => ipcRenderer.invoke('languages:list'),
+    get: (id) => ipcRenderer.invoke('languages:get', id),
+    forFile: (path) => ipcRenderer.invoke('languages:forFile', path),
+    save: (id, definition) => ipcRenderer.invoke('languages:save', id, definition),
+    delete: (id) => ipcRenderer.invoke('languages:delete', id)
+  },
+
+  // Spellcheck API
+  spellcheck: {
+    checkText: (text, options) => ipcRenderer.invoke('spellcheck:checkText', text, options),
+    suggest: (word, options) => ipcRenderer.invoke('spellcheck:suggest', word, options),
+    addWord: (word) => ipcRenderer.invoke('spellcheck:addWord', word),
+    removeWord: (word) => ipcRenderer.invoke('spellcheck:removeWord', word),
+    listWords: () => ipcRenderer.invoke('spellcheck:listWords'),
+    listDictionaries: () => ipcRenderer.invoke('spellcheck:listDictionaries'),
+    setDictionary: (id) => ipcRenderer.invoke('spellcheck:setDictionary', id),
+    installDictionary: (dicPath) => ipcRenderer.invoke('spellcheck:installDictionary', dicPath)
+  },
+
+  // Structural selection API
+  structure: {
+    expandSelection: (path, range, options) => ipcRenderer.invoke('structure:expandSelection', path, range, options),
+    matchingBracket: (path, position, options) => ipcRenderer.invoke('structure:matchingBracket', path, position, options),
+    sibling: (path, range, direction, options) => ipcRenderer.invoke('structure:sibling', path, range, direction, options)
+  },
+
+  // Printing API
+  print: {
+    file: (path, options) => ipcRenderer.invoke('print:file', path, options),
+    terminal: (terminalId, options) => ipcRenderer.invoke('print:terminal', terminalId, options)
+  },
+
+  // Compare API
+  compare: {
+    files: (pathA, pathB, options) => ipcRenderer.invoke('compare:files', pathA, pathB, options),
+    withContent: (path, text, options) => ipcRenderer.invoke('compare:withContent', path, text, options),
+    withClipboard: (path, options) => ipcRenderer.invoke('compare:withClipboard', path, options),
+    withRevision: (path, ref, options) => ipcRenderer.invoke('compare:withRevision', path, ref, options),
+    getSession: (id) => ipcRenderer.invoke('compare:getSession', id),
+    closeSession: (id) => ipcRenderer.invoke('compare:closeSession', id)
+  },
+
+  // File decorations API
+  decorations: {
+    query: (rootPath, paths) => ipcRenderer.invoke('decorations:query', rootPath, paths),
+    set: (rootPath, path, decoration) => ipcRenderer.invoke('decorations:set', rootPath, path, decoration),
+    list: (rootPath) => ipcRenderer.invoke('decorations:list', rootPath),
+    onChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('decorations:changed', handler);
+      return () => ipcRenderer.removeListener('decorations:changed', handler);
+    }
+  },
+
+  // Favorites API
+  favorites: {
+    list: () => ipcRenderer.invoke('favorites:list'),
+    pin: (target, options) => ipcRenderer.invoke('favorites:pin', target, options),
+    unpin: (id) => ipcRenderer.invoke('favorites:unpin', id),
+    reorder: (ids) => ipcRenderer.invoke('favorites:reorder', ids),
+    onChanged: (callback) => {
+      const handler = (event, data) => callback(data);
+      ipcRenderer.on('favorites:changed', handler);
+      return () => ipcRenderer.removeListener('favorites:changed', handler);
+    }
+  },
+
+  // Activity API (local time tracking per file, terminal directory, and workspace)
+  activity: {
+    focus: (target) => ipcRenderer.invoke('activity:focus', target),
+    getSummary: (range) => ipcRenderer.invoke('activity:getSummary', range),
+    clear: () => ipcRenderer.invoke('activity:clear'),
+    getSettings: () => ipcRenderer.invoke('activity:getSettings'),
+    updateSettings: (changes) => ipcRenderer.invoke('activity:updateSettings', changes)
+  },
+
+  // Source maps API (browser stack traces back to original sources)
+  sourceMaps: {
+    resolveStack: (stack, options) => ipcRenderer.invoke('sourceMaps:resolveStack', stack, options),
+    resolveConsoleEntry: (viewId, entryId, options) => ipcRenderer.invoke('sourceMaps:resolveConsoleEntry', viewId, entryId, options),
+    openFrame: (target) => ipcRenderer.invoke('sourceMaps:openFrame', target)
+  },
+
   // Updater API
   updater: {
     check: () => ipcRenderer.invoke('updater:check'),
     download: () => ipcRenderer.invoke('updater:download'),
     install: () => ipcRenderer.invoke('updater:install'),
     getVersion: () => ipcRenderer.invoke('updater:getVersion'),
+    deferInstall: () => ipcRenderer.invoke('updater:deferInstall'),
+    getChannel: () => ipcRenderer.invoke('updater:getChannel'),
+    setChannel: (channel) => ipcRenderer.invoke('updater:setChannel', channel),
+    setCheckOnStartup: (enabled) => ipcRenderer.invoke('updater:setCheckOnStartup', enabled),
+    getReleaseNotes: () => ipcRenderer.invoke('updater:getReleaseNotes'),
+    getState: () => ipcRenderer.invoke('updater:getState'),
     onStatus: (callback) => {
       ipcRenderer.on('updater:status', (event, data) => callback(data));
     },
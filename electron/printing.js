@@ -0,0 +1,265 @@
+const fs = require('fs').promises;
+const os = require('os');
+const path = require('path');
+const { BrowserWindow } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { getTerminal, getScreenOutput } = require('./terminal');
+const { detectLanguage } = require('./languages');
+const { parseStructure } = require('./structure');
+
+const log = createLogger('printing');
+
+const MAX_PRINT_SIZE = 5 * 1024 * 1024;
+const PROSE_LANGUAGES = new Set(['plaintext', 'markdown', 'mdx', 'restructuredtext']);
+// Common to most languages; enough to make printed code readable without a grammar
+const KEYWORDS = new Set(('abstract as async await break case catch class const continue def default defer del do elif else ' +
+  'enum export extends false final finally fn for from func function go if impl import in interface is lambda let loop ' +
+  'match mod module mut new nil None not null or package pass private protected pub public raise return self static ' +
+  'struct super switch this throw trait true True False try type typeof use var void where while with yield').split(' '));
+const TOKEN_PATTERN = /\b\d[\w.]*\b|\b[A-Za-z_]\w*\b/g;
+
+// SGR sequences are applied; every other escape (cursor movement, titles, modes) is dropped
+const ESCAPE_PATTERN = /\x1b\[([0-9;:]*)m|\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]/g;
+// xterm's 16 colours, darkened where the originals vanish on white paper
+const ANSI_COLORS = ['#000000', '#c91b00', '#00a600', '#a68b00', '#0225c7', '#b42cb4', '#00a0a6', '#6e6e6e',
+  '#555555', '#e6453b', '#30b530', '#b8a000', '#5c6eff', '#d65cd6', '#18b2b8', '#8c8c8c'];
+
+const escapeHtml = (text) => text.replace(/[&<>"]/g, (char) => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;' })[char]);
+
+function color256(index) {
+  if (index < 16) return ANSI_COLORS[index];
+  if (index < 232) {
+    const levels = [0, 95, 135, 175, 215, 255];
+    const value = index - 16;
+    return `rgb(${levels[Math.floor(value / 36)]}, ${levels[Math.floor(value / 6) % 6]}, ${levels[value % 6]})`;
+  }
+  const gray = 8 + (index - 232) * 10;
+  return `rgb(${gray}, ${gray}, ${gray})`;
+}
+
+// Apply one SGR parameter list to `style`
+function applySgr(style, params) {
+  const codes = params === '' ? [0] : params.split(/[;:]/).map(Number);
+  for (let i = 0; i < codes.length; i++) {
+    const code = codes[i];
+    if (code === 0) Object.assign(style, { fg: null, bg: null, bold: false, dim: false, italic: false, underline: false, inverse: false, strike: false });
+    else if (code === 1) style.bold = true;
+    else if (code === 2) style.dim = true;
+    else if (code === 3) style.italic = true;
+    else if (code === 4) style.underline = true;
+    else if (code === 7) style.inverse = true;
+    else if (code === 9) style.strike = true;
+    else if (code === 22) style.bold = style.dim = false;
+    else if (code === 23) style.italic = false;
+    else if (code === 24) style.underline = false;
+    else if (code === 27) style.inverse = false;
+    else if (code === 29) style.strike = false;
+    else if (code >= 30 && code <= 37) style.fg = ANSI_COLORS[code - 30];
+    else if (code >= 90 && code <= 97) style.fg = ANSI_COLORS[code - 90 + 8];
+    else if (code >= 40 && code <= 47) style.bg = ANSI_COLORS[code - 40];
+    else if (code >= 100 && code <= 107) style.bg = ANSI_COLORS[code - 100 + 8];
+    else if (code === 39) style.fg = null;
+    else if (code === 49) style.bg = null;
+    else if (code === 38 || code === 48) {
+      const key = code === 38 ? 'fg' : 'bg';
+      if (codes[i + 1] === 5) {
+        style[key] = color256(codes[i + 2] || 0);
+        i += 2;
+      } else if (codes[i + 1] === 2) {
+        style[key] = `rgb(${codes[i + 2] || 0}, ${codes[i + 3] || 0}, ${codes[i + 4] || 0})`;
+        i += 4;
+      }
+    }
+  }
+}
+
+function styleAttribute(style) {
+  const fg = style.inverse ? style.bg || '#ffffff' : style.fg;
+  const bg = style.inverse ? style.fg || '#000000' : style.bg;
+  const rules = [];
+  if (fg) rules.push(`color: ${fg}`);
+  if (bg) rules.push(`background: ${bg}`);
+  if (style.bold) rules.push('font-weight: bold');
+  if (style.dim) rules.push('opacity: 0.7');
+  if (style.italic) rules.push('font-style: italic');
+  const decorations = [style.underline && 'underline', style.strike && 'line-through'].filter(Boolean);
+  if (decorations.length > 0) rules.push(`text-decoration: ${decorations.join(' ')}`);
+  return rules.length > 0 ? ` style="${rules.join('; ')}"` : '';
+}
+
+// Terminal output as [{ text, attributes }]. A carriage return redraws its line, so only
+// what was written last on each line is kept.
+function terminalSegments(output) {
+  const text = output
+    .split('\n')
+    .map((line) => {
+      const content = line.replace(/\r+$/, '');
+      return content.slice(content.lastIndexOf('\r') + 1);
+    })
+    .join('\n');
+  const segments = [];
+  const style = {};
+  applySgr(style, '');
+  // Bells, backspaces and other controls left between escapes
+  const push = (from, to) => {
+    const chunk = text.slice(from, to).replace(/[^\n\t\x20-\uffff]/g, '');
+    if (chunk) segments.push({ text: chunk, attributes: styleAttribute(style) });
+  };
+  let position = 0;
+  let match;
+  ESCAPE_PATTERN.lastIndex = 0;
+  while ((match = ESCAPE_PATTERN.exec(text)) !== null) {
+    push(position, match.index);
+    if (match[1] !== undefined) applySgr(style, match[1]);
+    position = match.index + match[0].length;
+  }
+  push(position, text.length);
+  return segments;
+}
+
+// Source as [{ text, attributes }], with comments and strings found by the structure parser
+// and keywords and numbers by a shared word list
+function codeSegments(text, languageId) {
+  if (!languageId || PROSE_LANGUAGES.has(languageId)) return [{ text, attributes: '' }];
+  const literals = [];
+  const collect = (node) => {
+    for (const child of node.children) {
+      if (child.kind === 'bracket') collect(child);
+      else literals.push(child);
+    }
+  };
+  collect(parseStructure(text, languageId));
+
+  const segments = [];
+  const plain = (from, to) => {
+    const chunk = text.slice(from, to);
+    let position = 0;
+    let match;
+    TOKEN_PATTERN.lastIndex = 0;
+    while ((match = TOKEN_PATTERN.exec(chunk)) !== null) {
+      const kind = /^\d/.test(match[0]) ? 'number' : KEYWORDS.has(match[0]) ? 'keyword' : null;
+      if (!kind) continue;
+      if (match.index > position) segments.push({ text: chunk.slice(position, match.index), attributes: '' });
+      segments.push({ text: match[0], attributes: ` class="${kind}"` });
+      position = match.index + match[0].length;
+    }
+    if (position < chunk.length) segments.push({ text: chunk.slice(position), attributes: '' });
+  };
+  let position = 0;
+  for (const literal of literals) {
+    plain(position, literal.start);
+    segments.push({ text: text.slice(literal.start, literal.end), attributes: ` class="${literal.kind}"` });
+    position = literal.end;
+  }
+  plain(position, text.length);
+  return segments;
+}
+
+// A printable page: segments split into numbered lines so styles never span a line break
+function renderDocument(title, segments, { lineNumbers = true } = {}) {
+  const lines = [''];
+  for (const { text, attributes } of segments) {
+    text.split('\n').forEach((part, index) => {
+      if (index > 0) lines.push('');
+      if (part) lines[lines.length - 1] += attributes ? `<span${attributes}>${escapeHtml(part)}</span>` : escapeHtml(part);
+    });
+  }
+  if (lines.length > 1 && lines[lines.length - 1] === '') lines.pop();
+  const body = lines.map((line) => `<span class="line">${line || ' '}</span>`).join('');
+  return `<!DOCTYPE html>
+<html><head><meta charset="utf-8"><title>${escapeHtml(title)}</title><style>
+@page { margin: 12mm; }
+body { margin: 0; color: #1f2328; font: 8.5pt/1.45 Menlo, Consolas, 'DejaVu Sans Mono', monospace; }
+header { display: flex; justify-content: space-between; margin-bottom: 8px; padding-bottom: 4px; border-bottom: 1px solid #d0d7de; color: #57606a; font: 8pt system-ui, sans-serif; }
+pre { margin: 0; font: inherit; white-space: pre-wrap; word-break: break-all; counter-reset: line; }
+.line { display: block; }
+.numbered .line { padding-left: 7ch; text-indent: -7ch; }
+.numbered .line::before { counter-increment: line; content: counter(line); display: inline-block; width: 5ch; margin-right: 2ch; text-align: right; text-indent: 0; color: #8c959f; }
+.comment { color: #6e7781; font-style: italic; }
+.string { color: #0a3069; }
+.keyword { color: #cf222e; }
+.number { color: #0550ae; }
+</style></head><body>
+<header><span>${escapeHtml(title)}</span><span>${escapeHtml(new Date().toLocaleString())}</span></header>
+<pre${lineNumbers ? ' class="numbered"' : ''}>${body}</pre>
+</body></html>`;
+}
+
+// Lay `html` out in a hidden window, then either save it as a PDF at `pdfPath` or open the
+// system print dialog. Resolves { path } or { printed }, false when the user cancels.
+async function printHtml(html, { pdfPath = null, landscape = false } = {}) {
+  if (pdfPath !== null && (typeof pdfPath !== 'string' || !path.isAbsolute(pdfPath))) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'pdfPath must be an absolute path');
+  }
+  // Large pages don't fit in a data: URL
+  const htmlPath = path.join(os.tmpdir(), `forge-print-${uuidv4()}.html`);
+  await fs.writeFile(htmlPath, html, 'utf-8');
+  const win = new BrowserWindow({ show: false, webPreferences: { javascript: false, sandbox: true } });
+  try {
+    await win.loadFile(htmlPath);
+    if (pdfPath) {
+      const pdf = await win.webContents.printToPDF({ printBackground: true, landscape });
+      await fs.mkdir(path.dirname(pdfPath), { recursive: true });
+      await fs.writeFile(pdfPath, pdf);
+      return { path: pdfPath };
+    }
+    return await new Promise((resolve, reject) => {
+      win.webContents.print({ silent: false, printBackground: true, landscape }, (success, failureReason) => {
+        if (success || failureReason === 'cancelled') resolve({ printed: success });
+        else reject(new ForgeError(ErrorCode.IO_ERROR, `Printing failed: ${failureReason}`));
+      });
+    });
+  } finally {
+    win.destroy();
+    fs.unlink(htmlPath).catch(() => {});
+  }
+}
+
+// Print a file with syntax colouring. `content` is the editor's text, for unsaved changes.
+async function printFile(filePath, options = {}) {
+  if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An absolute file path is required');
+  }
+  let { content } = options;
+  if (typeof content !== 'string') {
+    const stats = await fs.stat(filePath).catch((error) => {
+      throw ForgeError.from(error, `Cannot read ${filePath}`);
+    });
+    if (stats.size > MAX_PRINT_SIZE) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} is too large to print`);
+    }
+    content = await fs.readFile(filePath, 'utf-8');
+  }
+  if (content.length > MAX_PRINT_SIZE) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} is too large to print`);
+  }
+  const languageId = detectLanguage(filePath, content.slice(0, 256));
+  const html = renderDocument(filePath, codeSegments(content, languageId), options);
+  const result = await printHtml(html, options);
+  log.info(`Printed ${filePath}${result.path ? ` to ${result.path}` : ''}`);
+  return result;
+}
+
+// Print a terminal's scrollback with its colours. A running full-screen program's screen
+// isn't part of it.
+async function printTerminalOutput(terminalId, options = {}) {
+  const session = getTerminal(terminalId);
+  if (!session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { id: terminalId } });
+  }
+  const output = getScreenOutput(session, 'normal');
+  const html = renderDocument(`Terminal — ${session.cwd}`, terminalSegments(output), { lineNumbers: false, ...options });
+  return printHtml(html, options);
+}
+
+function setupPrintingHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('print:file', async (event, filePath, options) => printFile(filePath, options));
+
+  ipcMain.handle('print:terminal', async (event, terminalId, options) => printTerminalOutput(terminalId, options));
+}
+
+module.exports = { setupPrintingHandlers, printFile, printTerminalOutput };
@@ -0,0 +1,237 @@
+const path = require('path');
+const { JsonStore } = require('./jsonStore');
+const { ForgeError, ErrorCode } = require('./errors');
+
+// A matcher is a list of line patterns, each a regexp plus the capture group holding each
+// field. Multi-line matchers must match consecutive lines; a last pattern with `loop`
+// keeps matching (one problem per line) until a line doesn't fit.
+//
+//   { label, severity?, pattern: [{ regexp, file?, line?, column?, endLine?, endColumn?,
+//                                   severity?, code?, message?, loop? }] }
+const BUILTIN_MATCHERS = {
+  rustc: {
+    label: 'rustc / cargo',
+    pattern: [
+      { regexp: '^(warning|error)(?:\\[(\\w+)\\])?: (.*)$', severity: 1, code: 2, message: 3 },
+      { regexp: '^\\s*--> (.*?):(\\d+):(\\d+)$', file: 1, line: 2, column: 3 }
+    ]
+  },
+  tsc: {
+    label: 'TypeScript (tsc)',
+    pattern: [
+      // file(1,2): error TS1234: message, or the --pretty form file:1:2 - error TS1234: message
+      { regexp: '^([^\\s].*?)(?:\\((\\d+),(\\d+)\\):|:(\\d+):(\\d+) -)\\s+(error|warning|info)\\s+(TS\\d+)\\s*:\\s*(.*)$', file: 1, line: [2, 4], column: [3, 5], severity: 6, code: 7, message: 8 }
+    ]
+  },
+  eslint: {
+    label: 'ESLint (stylish)',
+    pattern: [
+      { regexp: '^([^\\s].*)$', file: 1 },
+      { regexp: '^\\s+(\\d+):(\\d+)\\s+(error|warning|info)\\s+(.*?)(?:\\s\\s+(\\S+))?$', line: 1, column: 2, severity: 3, message: 4, code: 5, loop: true }
+    ]
+  },
+  'eslint-compact': {
+    label: 'ESLint (compact)',
+    pattern: [
+      { regexp: '^(.+): line (\\d+), col (\\d+), (Error|Warning|Info) - (.+?)(?: \\((.+)\\))?$', file: 1, line: 2, column: 3, severity: 4, message: 5, code: 6 }
+    ]
+  },
+  gcc: {
+    label: 'GCC / Clang',
+    pattern: [
+      { regexp: '^(.*?):(\\d+):(\\d*):?\\s+(?:fatal\\s+)?(warning|error|note):\\s+(.*)$', file: 1, line: 2, column: 3, severity: 4, message: 5 }
+    ]
+  }
+};
+
+const FIELDS = ['file', 'line', 'column', 'endLine', 'endColumn', 'severity', 'code', 'message'];
+const SEVERITIES = { error: 'error', fatal: 'error', warning: 'warning', warn: 'warning', info: 'info', note: 'info', help: 'info', hint: 'info' };
+
+// eslint-disable-next-line no-control-regex
+const ANSI_PATTERN = /\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]/g;
+const MAX_LINE_LENGTH = 4096;
+
+const store = new JsonStore('problem-matchers', {
+  // User-defined matchers by id, same shape as the built-ins
+  custom: {}
+});
+
+function validateMatcher(definition) {
+  if (!definition || typeof definition !== 'object') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A problem matcher must be an object');
+  }
+  const patterns = Array.isArray(definition.pattern) ? definition.pattern : [definition.pattern];
+  if (patterns.length === 0 || patterns.some((pattern) => !pattern || typeof pattern.regexp !== 'string')) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Each problem matcher pattern needs a regexp');
+  }
+  for (const pattern of patterns) {
+    try {
+      new RegExp(pattern.regexp);
+    } catch (error) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid problem matcher regexp: ${error.message}`);
+    }
+  }
+  if (!patterns.some((pattern) => pattern.file) || !patterns.some((pattern) => pattern.message)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A problem matcher must capture a file and a message');
+  }
+  return {
+    label: typeof definition.label === 'string' ? definition.label : null,
+    severity: SEVERITIES[definition.severity] || null,
+    pattern: patterns.map((pattern) => {
+      const clean = { regexp: pattern.regexp, loop: pattern.loop === true };
+      for (const field of FIELDS) if (pattern[field] !== undefined) clean[field] = pattern[field];
+      return clean;
+    })
+  };
+}
+
+function listMatchers() {
+  const custom = store.get('custom', {});
+  return [
+    ...Object.entries(BUILTIN_MATCHERS).map(([id, matcher]) => ({ id, label: matcher.label, builtin: true })),
+    ...Object.entries(custom).map(([id, matcher]) => ({ id, label: matcher.label || id, builtin: false, definition: matcher }))
+  ];
+}
+
+function getMatcher(id) {
+  const matcher = store.get('custom', {})[id] || BUILTIN_MATCHERS[id];
+  if (!matcher) throw new ForgeError(ErrorCode.NOT_FOUND, `Unknown problem matcher: ${id}`);
+  return matcher;
+}
+
+function saveCustomMatcher(id, definition) {
+  if (typeof id !== 'string' || !/^[\w.-]+$/.test(id)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid problem matcher id: ${id}`);
+  }
+  if (BUILTIN_MATCHERS[id]) {
+    throw new ForgeError(ErrorCode.ALREADY_EXISTS, `${id} is a built-in problem matcher`);
+  }
+  const matcher = validateMatcher(definition);
+  store.set('custom', { ...store.get('custom', {}), [id]: matcher });
+  return matcher;
+}
+
+function deleteCustomMatcher(id) {
+  const custom = { ...store.get('custom', {}) };
+  if (!custom[id]) return false;
+  delete custom[id];
+  store.set('custom', custom);
+  return true;
+}
+
+// Capture group value; a list of groups takes the first that participated
+function capture(match, group) {
+  if (Array.isArray(group)) {
+    for (const index of group) if (match[index] !== undefined) return match[index];
+    return undefined;
+  }
+  return typeof group === 'number' ? match[group] : undefined;
+}
+
+// Feeds streamed output through matchers line by line. `onProblem` receives
+// { file, line, column, endLine, endColumn, severity, code, message, matcher } and the
+// { start, end } character range of the matched lines in everything pushed so far.
+class ProblemCollector {
+  constructor(matcherIds, { cwd, onProblem }) {
+    this.cwd = cwd;
+    this.onProblem = onProblem;
+    this.partial = '';
+    // Stream offset of `partial`'s first character
+    this.partialStart = 0;
+    this.matchers = matcherIds.map((id) => {
+      const matcher = getMatcher(id);
+      return {
+        id,
+        severity: matcher.severity,
+        patterns: matcher.pattern.map((pattern) => ({ ...pattern, regexp: new RegExp(pattern.regexp) })),
+        index: 0,
+        data: {},
+        start: 0
+      };
+    });
+  }
+
+  push(chunk) {
+    const text = this.partial + String(chunk);
+    const newline = /\r?\n/g;
+    let lineStart = 0;
+    let match;
+    while ((match = newline.exec(text)) !== null) {
+      this.matchLine(text.slice(lineStart, match.index), this.partialStart + lineStart, this.partialStart + match.index);
+      lineStart = match.index + match[0].length;
+    }
+    const rest = text.slice(lineStart);
+    this.partialStart += lineStart + Math.max(0, rest.length - MAX_LINE_LENGTH);
+    this.partial = rest.slice(-MAX_LINE_LENGTH);
+  }
+
+  // Process a trailing line without a newline, e.g. when the process exits
+  flush() {
+    if (this.partial) this.matchLine(this.partial, this.partialStart, this.partialStart + this.partial.length);
+    this.partialStart += this.partial.length;
+    this.partial = '';
+    for (const matcher of this.matchers) this.reset(matcher);
+  }
+
+  reset(matcher) {
+    matcher.index = 0;
+    matcher.data = {};
+  }
+
+  matchLine(rawLine, start, end) {
+    const line = rawLine.replace(ANSI_PATTERN, '').replace(/\r/g, '').slice(0, MAX_LINE_LENGTH);
+    for (const matcher of this.matchers) {
+      if (matcher.index > 0 && this.advance(matcher, line, start, end)) continue;
+      this.reset(matcher);
+      this.advance(matcher, line, start, end);
+    }
+  }
+
+  // Try the matcher's current pattern against `line`; false if it doesn't fit
+  advance(matcher, line, start, end) {
+    const pattern = matcher.patterns[matcher.index];
+    const match = pattern.regexp.exec(line);
+    if (!match) return false;
+    const captured = {};
+    for (const field of FIELDS) {
+      const value = capture(match, pattern[field]);
+      if (value !== undefined && value !== '') captured[field] = value;
+    }
+    if (matcher.index === 0) matcher.start = start;
+    const isLast = matcher.index === matcher.patterns.length - 1;
+    if (!isLast) {
+      Object.assign(matcher.data, captured);
+      matcher.index++;
+      return true;
+    }
+    // A looping last pattern reports each line against the data gathered before it
+    this.report(matcher, { ...matcher.data, ...captured }, { start: pattern.loop && matcher.index > 0 ? start : matcher.start, end });
+    if (!pattern.loop) this.reset(matcher);
+    return true;
+  }
+
+  report(matcher, data, range) {
+    if (!data.file || !data.message) return;
+    const number = (value) => (value === undefined ? null : Number(value) || null);
+    this.onProblem({
+      file: path.resolve(this.cwd, data.file.trim()),
+      line: number(data.line) || 1,
+      column: number(data.column) || 1,
+      endLine: number(data.endLine),
+      endColumn: number(data.endColumn),
+      severity: SEVERITIES[String(data.severity || '').toLowerCase()] || matcher.severity || 'error',
+      code: data.code || null,
+      message: data.message.trim(),
+      matcher: matcher.id
+    }, range);
+  }
+}
+
+module.exports = {
+  BUILTIN_MATCHERS,
+  ProblemCollector,
+  listMatchers,
+  getMatcher,
+  saveCustomMatcher,
+  deleteCustomMatcher
+};
@@ -0,0 +1,154 @@
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { terminalEvents, getTerminal } = require('./terminal');
+const { ProblemCollector, listMatchers, saveCustomMatcher, deleteCustomMatcher } = require('./problemMatchers');
+const { addAnnotation } = require('./terminalAnnotations');
+
+const log = createLogger('problems');
+
+// Per source, so one producer can replace its own results without touching others
+const MAX_PROBLEMS_PER_SOURCE = 1000;
+
+// Source id ("task:build", "terminal:<id>", a linter name, ...) -> problem list
+const problems = new Map();
+// Terminal id -> { collector, clearOnCommand, base }, where `base` is the terminal output
+// offset the collector's stream starts at
+const terminalWatches = new Map();
+
+function publish(source) {
+  const list = problems.get(source) || [];
+  emit('problems:changed', {
+    source,
+    count: list.length,
+    errors: list.filter((problem) => problem.severity === 'error').length,
+    warnings: list.filter((problem) => problem.severity === 'warning').length
+  });
+}
+
+function addProblem(source, problem) {
+  const list = problems.get(source) || [];
+  if (list.length >= MAX_PROBLEMS_PER_SOURCE) return;
+  // Compilers repeat themselves (e.g. per build target); keep one of each
+  const duplicate = list.some((existing) => existing.file === problem.file && existing.line === problem.line &&
+    existing.column === problem.column && existing.message === problem.message);
+  if (duplicate) return;
+  list.push({ ...problem, source });
+  problems.set(source, list);
+  publish(source);
+}
+
+// Replace everything a source reported, e.g. after a linter re-runs
+function setProblems(source, list) {
+  problems.set(source, list.slice(0, MAX_PROBLEMS_PER_SOURCE).map((problem) => ({ ...problem, source })));
+  publish(source);
+}
+
+function clearProblems(source) {
+  if (!problems.has(source)) return false;
+  problems.delete(source);
+  publish(source);
+  return true;
+}
+
+function listProblems({ file, source } = {}) {
+  const result = [];
+  for (const [key, list] of problems) {
+    if (source && key !== source) continue;
+    for (const problem of list) if (!file || problem.file === file) result.push(problem);
+  }
+  return result;
+}
+
+// Collector routing matched output into `source`
+function createCollector(source, matcherIds, cwd) {
+  return new ProblemCollector(matcherIds, { cwd, onProblem: (problem) => addProblem(source, problem) });
+}
+
+function watchTerminal(terminalId, matcherIds, { clearOnCommand = true } = {}) {
+  const terminal = getTerminal(terminalId);
+  if (!terminal) throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { terminalId } });
+  if (!Array.isArray(matcherIds) || matcherIds.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Choose at least one problem matcher');
+  }
+  // Paths in compiler output are relative to where the command ran
+  const source = `terminal:${terminalId}`;
+  const watch = { collector: null, clearOnCommand, base: null };
+  // Matched output is annotated in the terminal so the frontend can decorate it
+  watch.collector = new ProblemCollector(matcherIds, {
+    cwd: terminal.cwd || process.cwd(),
+    onProblem: (problem, range) => {
+      addProblem(source, problem);
+      try {
+        addAnnotation(terminalId, {
+          start: watch.base + range.start,
+          end: watch.base + Math.max(range.end, range.start + 1),
+          source: 'problems',
+          kind: 'problem',
+          message: problem.message,
+          data: problem
+        });
+      } catch (error) {
+        log.debug(`Could not annotate problem output: ${error.message}`);
+      }
+    }
+  });
+  terminalWatches.set(terminalId, watch);
+  log.info(`Matching problems in terminal ${terminalId} with ${matcherIds.join(', ')}`);
+  return true;
+}
+
+function unwatchTerminal(terminalId) {
+  const watch = terminalWatches.get(terminalId);
+  if (!watch) return false;
+  watch.collector.flush();
+  terminalWatches.delete(terminalId);
+  return true;
+}
+
+// Normal-screen output only: full-screen programs don't print compiler diagnostics
+terminalEvents.on('output', (id, data, offset) => {
+  const watch = terminalWatches.get(id);
+  if (!watch) return;
+  if (watch.base === null) watch.base = offset;
+  watch.collector.push(data);
+});
+
+// With shell integration each new command starts from a clean slate, in its own cwd
+terminalEvents.on('commandStarted', (id) => {
+  const watch = terminalWatches.get(id);
+  if (!watch || !watch.clearOnCommand) return;
+  watch.collector.flush();
+  const terminal = getTerminal(id);
+  if (terminal && terminal.cwd) watch.collector.cwd = terminal.cwd;
+  clearProblems(`terminal:${id}`);
+});
+
+terminalEvents.on('exit', (id) => unwatchTerminal(id));
+
+function setupProblemHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('problems:list', async (event, filter) => listProblems(filter));
+
+  ipcMain.handle('problems:clear', async (event, source) => clearProblems(source));
+
+  ipcMain.handle('problems:watchTerminal', async (event, terminalId, matcherIds, options) => watchTerminal(terminalId, matcherIds, options));
+
+  ipcMain.handle('problems:unwatchTerminal', async (event, terminalId) => unwatchTerminal(terminalId));
+
+  ipcMain.handle('problems:listMatchers', async () => listMatchers());
+
+  ipcMain.handle('problems:saveMatcher', async (event, id, definition) => saveCustomMatcher(id, definition));
+
+  ipcMain.handle('problems:deleteMatcher', async (event, id) => deleteCustomMatcher(id));
+}
+
+module.exports = {
+  setupProblemHandlers,
+  addProblem,
+  setProblems,
+  clearProblems,
+  listProblems,
+  createCollector
+};
@@ -0,0 +1,1027 @@
+// Protocol Buffers schemas: a .proto parser (proto2, proto3 and editions syntax) that
+// resolves imports and type references, and a wire-format decoder that turns binary
+// payloads into proto3 JSON against a message type, or into field numbers without one.
+
+const fs = require('fs').promises;
+const path = require('path');
+const { ForgeError, ErrorCode } = require('./errors');
+
+class SchemaSyntaxError extends Error {
+  constructor(message, token) {
+    super(message);
+    this.line = token ? token.line : 1;
+    this.column = token ? token.column : 1;
+  }
+}
+
+const SCALARS = new Set(['double', 'float', 'int32', 'int64', 'uint32', 'uint64', 'sint32', 'sint64', 'fixed32', 'fixed64', 'sfixed32', 'sfixed64', 'bool', 'string', 'bytes']);
+const WIRE_VARINT = 0;
+const WIRE_FIXED64 = 1;
+const WIRE_LENGTH = 2;
+const WIRE_START_GROUP = 3;
+const WIRE_END_GROUP = 4;
+const WIRE_FIXED32 = 5;
+const SCALAR_WIRE_TYPES = {
+  double: WIRE_FIXED64, fixed64: WIRE_FIXED64, sfixed64: WIRE_FIXED64,
+  float: WIRE_FIXED32, fixed32: WIRE_FIXED32, sfixed32: WIRE_FIXED32,
+  string: WIRE_LENGTH, bytes: WIRE_LENGTH
+};
+const MAX_DEPTH = 64;
+const MAX_IMPORT_DEPTH = 32;
+
+// Imported when a schema references them but no include path has a copy; enough of each
+// to decode, and the option messages so custom options can extend them
+const WELL_KNOWN = {
+  'google/protobuf/any.proto': 'message Any { string type_url = 1; bytes value = 2; }',
+  'google/protobuf/duration.proto': 'message Duration { int64 seconds = 1; int32 nanos = 2; }',
+  'google/protobuf/empty.proto': 'message Empty {}',
+  'google/protobuf/field_mask.proto': 'message FieldMask { repeated string paths = 1; }',
+  'google/protobuf/timestamp.proto': 'message Timestamp { int64 seconds = 1; int32 nanos = 2; }',
+  'google/protobuf/struct.proto': `message Struct { map<string, Value> fields = 1; }
+    message Value { oneof kind { NullValue null_value = 1; double number_value = 2; string string_value = 3; bool bool_value = 4; Struct struct_value = 5; ListValue list_value = 6; } }
+    enum NullValue { NULL_VALUE = 0; }
+    message ListValue { repeated Value values = 1; }`,
+  'google/protobuf/wrappers.proto': ['Double:double', 'Float:float', 'Int64:int64', 'UInt64:uint64', 'Int32:int32', 'UInt32:uint32', 'Bool:bool', 'String:string', 'Bytes:bytes']
+    .map((pair) => `message ${pair.split(':')[0]}Value { ${pair.split(':')[1]} value = 1; }`).join('\n'),
+  'google/protobuf/descriptor.proto': ['File', 'Message', 'Field', 'Oneof', 'Enum', 'EnumValue', 'Service', 'Method', 'ExtensionRange']
+    .map((name) => `message ${name}Options {}`).join('\n')
+};
+
+function tokenize(text) {
+  const tokens = [];
+  let pos = 0;
+  let line = 1;
+  let lineStart = 0;
+  const newlines = (from, to) => {
+    for (let index = from; index < to; index++) {
+      if (text[index] === '\n') {
+        line++;
+        lineStart = index + 1;
+      }
+    }
+  };
+  while (pos < text.length) {
+    const ch = text[pos];
+    const column = pos - lineStart + 1;
+    if (ch === '\n') {
+      line++;
+      lineStart = ++pos;
+    } else if (/\s/.test(ch) || ch === '\uFEFF') {
+      pos++;
+    } else if (text.startsWith('//', pos)) {
+      while (pos < text.length && text[pos] !== '\n') pos++;
+    } else if (text.startsWith('/*', pos)) {
+      const end = text.indexOf('*/', pos + 2);
+      const stop = end < 0 ? text.length : end + 2;
+      newlines(pos, stop);
+      pos = stop;
+    } else if (/[A-Za-z_]/.test(ch)) {
+      const match = /^[A-Za-z_][A-Za-z0-9_]*/.exec(text.slice(pos, pos + 256));
+      tokens.push({ kind: 'ident', value: match[0], line, column });
+      pos += match[0].length;
+    } else if (/[0-9]/.test(ch) || (ch === '.' && /[0-9]/.test(text[pos + 1] || ''))) {
+      const match = /^(?:0[xX][0-9a-fA-F]+|[0-9]*\.?[0-9]+(?:[eE][+-]?[0-9]+)?|[0-9]+\.?(?:[eE][+-]?[0-9]+)?)/.exec(text.slice(pos, pos + 256));
+      tokens.push({ kind: 'number', value: match[0], line, column });
+      pos += match[0].length;
+    } else if (ch === '"' || ch === "'") {
+      let end = pos + 1;
+      let value = '';
+      while (text[end] !== ch) {
+        if (end >= text.length || text[end] === '\n') throw new SchemaSyntaxError('Unterminated string', { line, column });
+        if (text[end] === '\\') {
+          const escape = /^\\(?:x([0-9a-fA-F]{1,2})|([0-7]{1,3})|u([0-9a-fA-F]{4})|U([0-9a-fA-F]{8})|(.))/.exec(text.slice(end, end + 10));
+          if (escape[1] || escape[2]) value += String.fromCharCode(parseInt(escape[1] || escape[2], escape[1] ? 16 : 8));
+          else if (escape[3] || escape[4]) value += String.fromCodePoint(parseInt(escape[3] || escape[4], 16));
+          else value += { n: '\n', r: '\r', t: '\t', a: '\x07', b: '\b', f: '\f', v: '\v' }[escape[5]] ?? escape[5];
+          end += escape[0].length;
+        } else {
+          value += text[end++];
+        }
+      }
+      // Adjacent literals are concatenated
+      const previous = tokens[tokens.length - 1];
+      if (previous && previous.kind === 'string') previous.value += value;
+      else tokens.push({ kind: 'string', value, line, column });
+      pos = end + 1;
+    } else {
+      tokens.push({ kind: 'punct', value: ch, line, column });
+      pos++;
+    }
+  }
+  return tokens;
+}
+
+// Cursor over a token list, shared with the FlatBuffers parser
+class TokenStream {
+  constructor(tokens) {
+    this.tokens = tokens;
+    this.index = 0;
+  }
+
+  peek(offset = 0) {
+    return this.tokens[this.index + offset] || null;
+  }
+
+  next() {
+    const token = this.tokens[this.index++];
+    if (!token) throw new SchemaSyntaxError('Unexpected end of file', this.tokens[this.tokens.length - 1]);
+    return token;
+  }
+
+  is(value) {
+    const token = this.peek();
+    return Boolean(token && (token.kind === 'punct' || token.kind === 'ident') && token.value === value);
+  }
+
+  accept(value) {
+    if (!this.is(value)) return false;
+    this.index++;
+    return true;
+  }
+
+  expect(value) {
+    const token = this.next();
+    if (token.value !== value || token.kind === 'string') throw new SchemaSyntaxError(`Expected "${value}" but found "${token.value}"`, token);
+    return token;
+  }
+
+  ident() {
+    const token = this.next();
+    if (token.kind !== 'ident') throw new SchemaSyntaxError(`Expected a name but found "${token.value}"`, token);
+    return token.value;
+  }
+
+  // Dotted names, optionally fully qualified with a leading dot
+  qualifiedName() {
+    let name = this.accept('.') ? '.' : '';
+    name += this.ident();
+    while (this.is('.')) {
+      this.next();
+      name += `.${this.ident()}`;
+    }
+    return name;
+  }
+
+  string() {
+    const token = this.next();
+    if (token.kind !== 'string') throw new SchemaSyntaxError(`Expected a string but found "${token.value}"`, token);
+    return token.value;
+  }
+
+  integer() {
+    const negative = this.accept('-');
+    const token = this.next();
+    if (token.kind !== 'number' || !/^(0[xX][0-9a-fA-F]+|[0-9]+)$/.test(token.value)) throw new SchemaSyntaxError(`Expected an integer but found "${token.value}"`, token);
+    const value = /^0[0-7]+$/.test(token.value) ? parseInt(token.value, 8) : Number(token.value);
+    return negative ? -value : value;
+  }
+
+  // A constant or an aggregate { ... } option value, returned as source-ish text
+  constant() {
+    if (this.is('{')) {
+      let depth = 0;
+      const parts = [];
+      do {
+        const token = this.next();
+        if (token.value === '{' && token.kind === 'punct') depth++;
+        if (token.value === '}' && token.kind === 'punct') depth--;
+        parts.push(token.kind === 'string' ? JSON.stringify(token.value) : token.value);
+      } while (depth > 0);
+      return parts.join(' ');
+    }
+    const sign = this.accept('-') ? '-' : '';
+    this.accept('+');
+    const token = this.next();
+    if (token.kind === 'string') return token.value;
+    if (token.kind === 'number') return Number(`${sign}${token.value}`);
+    if (token.value === 'true' || token.value === 'false') return token.value === 'true';
+    if (token.kind === 'ident') {
+      let name = token.value;
+      while (this.is('.')) {
+        this.next();
+        name += `.${this.ident()}`;
+      }
+      return sign ? `${sign}${name}` : name;
+    }
+    throw new SchemaSyntaxError(`Unexpected "${token.value}"`, token);
+  }
+
+  skipStatement() {
+    let depth = 0;
+    for (;;) {
+      const token = this.next();
+      if (token.kind !== 'punct') continue;
+      if (token.value === '{') depth++;
+      else if (token.value === '}') depth--;
+      else if (token.value === ';' && depth === 0) return;
+    }
+  }
+}
+
+// option name: `foo`, `(foo.bar)`, `(foo).bar.baz`
+function optionName(stream) {
+  let name = '';
+  do {
+    if (stream.accept('(')) {
+      name += `(${stream.qualifiedName()})`;
+      stream.expect(')');
+    } else {
+      name += stream.ident();
+    }
+    if (stream.is('.')) name += '.';
+  } while (stream.accept('.'));
+  return name;
+}
+
+// [packed = true, json_name = "x", (custom) = { ... }]
+function fieldOptions(stream) {
+  const options = {};
+  if (!stream.accept('[')) return options;
+  do {
+    const name = optionName(stream);
+    stream.expect('=');
+    options[name] = stream.constant();
+  } while (stream.accept(','));
+  stream.expect(']');
+  return options;
+}
+
+// protoc's default JSON name: underscores dropped and the next letter upper-cased
+function jsonName(name) {
+  return name.replace(/_+([a-z0-9])?/g, (match, letter) => (letter ? letter.toUpperCase() : ''));
+}
+
+function parseProto(text, fileName = '') {
+  const stream = new TokenStream(tokenize(text));
+  const file = { file: fileName, syntax: 'proto2', edition: null, package: '', imports: [], options: {}, messages: [], enums: [], services: [], extensions: [] };
+  const scope = () => (file.package ? `${file.package}.` : '');
+  // `optional int32 x`, `repeated .pkg.Type x`, but not a field whose type is named `optional`
+  const labelAhead = () => {
+    const token = stream.peek();
+    const after = stream.peek(1);
+    return token.kind === 'ident' && ['optional', 'required', 'repeated'].includes(token.value) && Boolean(after) && (after.kind === 'ident' || after.value === '.');
+  };
+
+  function parseEnum(prefix) {
+    const token = stream.peek();
+    const name = stream.ident();
+    const definition = { name, fullName: `${prefix}${name}`, values: [], line: token.line, column: token.column };
+    file.enums.push(definition);
+    stream.expect('{');
+    while (!stream.accept('}')) {
+      if (stream.accept(';')) continue;
+      if (stream.is('option') || stream.is('reserved')) {
+        stream.skipStatement();
+        continue;
+      }
+      const valueToken = stream.peek();
+      const valueName = stream.ident();
+      stream.expect('=');
+      const number = stream.integer();
+      fieldOptions(stream);
+      stream.expect(';');
+      definition.values.push({ name: valueName, number, line: valueToken.line });
+    }
+    return definition;
+  }
+
+  function parseField(message, label, oneof) {
+    const token = stream.peek();
+    if (stream.accept('map')) {
+      stream.expect('<');
+      const keyType = stream.ident();
+      stream.expect(',');
+      const valueType = stream.qualifiedName();
+      stream.expect('>');
+      const name = stream.ident();
+      stream.expect('=');
+      const number = stream.integer();
+      const options = fieldOptions(stream);
+      stream.expect(';');
+      // Maps are repeated entry messages on the wire
+      const entryName = `${name.replace(/(^|_)([a-z])/g, (match, underscore, letter) => letter.toUpperCase())}Entry`;
+      const entry = { name: entryName, fullName: `${message.fullName}.${entryName}`, fields: [], oneofs: [], mapEntry: true, line: token.line, column: token.column };
+      entry.fields.push({ name: 'key', jsonName: 'key', number: 1, label: null, type: keyType, oneof: null, line: token.line, column: token.column });
+      entry.fields.push({ name: 'value', jsonName: 'value', number: 2, label: null, type: valueType, oneof: null, line: token.line, column: token.column });
+      file.messages.push(entry);
+      message.fields.push({ name, jsonName: options.json_name || jsonName(name), number, label: 'repeated', type: entry.fullName, map: { keyType, valueType }, oneof, line: token.line, column: token.column });
+      return;
+    }
+    const type = stream.qualifiedName();
+    if (type === 'group') {
+      // proto2 groups: the field is named after the lower-cased group type
+      const groupName = stream.ident();
+      stream.expect('=');
+      const number = stream.integer();
+      fieldOptions(stream);
+      const nested = parseMessageBody(groupName, `${message.fullName}.`, token);
+      message.fields.push({ name: groupName.toLowerCase(), jsonName: jsonName(groupName.toLowerCase()), number, label, type: nested.fullName, group: true, oneof, line: token.line, column: token.column });
+      return;
+    }
+    const name = stream.ident();
+    stream.expect('=');
+    const number = stream.integer();
+    const options = fieldOptions(stream);
+    stream.expect(';');
+    message.fields.push({
+      name,
+      jsonName: options.json_name || jsonName(name),
+      number,
+      label,
+      type,
+      oneof,
+      default: options.default,
+      deprecated: options.deprecated === true || undefined,
+      line: token.line,
+      column: token.column
+    });
+  }
+
+  function parseMessageBody(name, prefix, token) {
+    const message = { name, fullName: `${prefix}${name}`, fields: [], oneofs: [], line: token.line, column: token.column };
+    file.messages.push(message);
+    stream.expect('{');
+    while (!stream.accept('}')) {
+      if (stream.accept(';')) continue;
+      const keyword = stream.peek();
+      if (stream.accept('message')) {
+        const nestedToken = stream.peek();
+        parseMessageBody(stream.ident(), `${message.fullName}.`, nestedToken);
+      } else if (stream.accept('enum')) {
+        parseEnum(`${message.fullName}.`);
+      } else if (stream.accept('extend')) {
+        parseExtend(`${message.fullName}.`);
+      } else if (stream.accept('oneof')) {
+        const oneof = stream.ident();
+        message.oneofs.push(oneof);
+        stream.expect('{');
+        while (!stream.accept('}')) {
+          if (stream.accept(';')) continue;
+          if (stream.is('option')) stream.skipStatement();
+          else parseField(message, null, oneof);
+        }
+      } else if (stream.is('option') || stream.is('reserved') || stream.is('extensions')) {
+        stream.skipStatement();
+      } else if (labelAhead()) {
+        stream.next();
+        parseField(message, keyword.value, null);
+      } else {
+        parseField(message, null, null);
+      }
+    }
+    return message;
+  }
+
+  function parseExtend(prefix) {
+    const token = stream.peek();
+    const extendee = stream.qualifiedName();
+    // Collected into a throwaway message, then recorded against the extended type
+    const holder = { fullName: prefix.replace(/\.$/, ''), fields: [] };
+    stream.expect('{');
+    while (!stream.accept('}')) {
+      if (stream.accept(';')) continue;
+      const label = labelAhead() ? stream.next().value : null;
+      parseField(holder, label, null);
+    }
+    for (const field of holder.fields) {
+      file.extensions.push({ ...field, extendee, scope: prefix, fullName: `${prefix}${field.name}`, line: field.line || token.line });
+    }
+  }
+
+  function parseService() {
+    const token = stream.peek();
+    const name = stream.ident();
+    const service = { name, fullName: `${scope()}${name}`, methods: [], line: token.line, column: token.column };
+    file.services.push(service);
+    stream.expect('{');
+    while (!stream.accept('}')) {
+      if (stream.accept(';')) continue;
+      if (stream.is('option')) {
+        stream.skipStatement();
+        continue;
+      }
+      const methodToken = stream.peek();
+      stream.expect('rpc');
+      const methodName = stream.ident();
+      stream.expect('(');
+      const clientStreaming = stream.is('stream') && stream.peek(1).value !== ')' ? Boolean(stream.next()) : false;
+      const inputType = stream.qualifiedName();
+      stream.expect(')');
+      stream.expect('returns');
+      stream.expect('(');
+      const serverStreaming = stream.is('stream') && stream.peek(1).value !== ')' ? Boolean(stream.next()) : false;
+      const outputType = stream.qualifiedName();
+      stream.expect(')');
+      if (stream.is('{')) {
+        stream.next();
+        let depth = 1;
+        while (depth > 0) {
+          const inner = stream.next();
+          if (inner.kind === 'punct' && inner.value === '{') depth++;
+          if (inner.kind === 'punct' && inner.value === '}') depth--;
+        }
+        stream.accept(';');
+      } else {
+        stream.expect(';');
+      }
+      service.methods.push({ name: methodName, inputType, outputType, clientStreaming, serverStreaming, line: methodToken.line });
+    }
+  }
+
+  while (stream.peek()) {
+    const token = stream.next();
+    if (token.kind === 'punct' && token.value === ';') continue;
+    switch (token.kind === 'ident' ? token.value : '') {
+      case 'syntax':
+        stream.expect('=');
+        file.syntax = stream.string();
+        stream.expect(';');
+        break;
+      case 'edition':
+        stream.expect('=');
+        file.syntax = 'editions';
+        file.edition = stream.string();
+        stream.expect(';');
+        break;
+      case 'package':
+        file.package = stream.qualifiedName();
+        stream.expect(';');
+        break;
+      case 'import': {
+        const kind = stream.accept('public') ? 'public' : stream.accept('weak') ? 'weak' : 'normal';
+        file.imports.push({ path: stream.string(), kind, line: token.line });
+        stream.expect(';');
+        break;
+      }
+      case 'option': {
+        const name = optionName(stream);
+        stream.expect('=');
+        file.options[name] = stream.constant();
+        stream.expect(';');
+        break;
+      }
+      case 'message':
+        parseMessageBody(stream.ident(), scope(), stream.tokens[stream.index - 1]);
+        break;
+      case 'enum':
+        parseEnum(scope());
+        break;
+      case 'service':
+        parseService();
+        break;
+      case 'extend':
+        parseExtend(scope());
+        break;
+      default:
+        throw new SchemaSyntaxError(`Unexpected "${token.value}" at the top level`, token);
+    }
+  }
+  return file;
+}
+
+// Protobuf scoping: a relative name is looked up in the referencing scope, then each
+// enclosing scope out to the root
+function resolveName(types, name, scope) {
+  if (name.startsWith('.')) return types.has(name.slice(1)) ? name.slice(1) : null;
+  const [first, ...rest] = name.split('.');
+  const parts = scope ? scope.split('.') : [];
+  for (let length = parts.length; length >= 0; length--) {
+    const prefix = parts.slice(0, length).join('.');
+    const candidate = prefix ? `${prefix}.${first}` : first;
+    if (types.has(candidate)) {
+      const full = rest.length ? `${candidate}.${rest.join('.')}` : candidate;
+      return types.has(full) ? full : null;
+    }
+  }
+  return null;
+}
+
+async function readIfExists(filePath) {
+  try {
+    return await fs.readFile(filePath, 'utf-8');
+  } catch (error) {
+    if (error.code === 'ENOENT' || error.code === 'EISDIR') return null;
+    throw error;
+  }
+}
+
+// Parses `filePath` and everything it imports. Imports are looked up in `includePaths`,
+// then the importing file's directory, then the root file's ancestors (up to
+// `stopAt`), the places a protoc -I usually points. Problems are collected rather than
+// thrown so one bad import doesn't hide the rest of the schema.
+async function loadProtoSchema(filePath, { includePaths = [], stopAt = null } = {}) {
+  const root = path.resolve(filePath);
+  const ancestors = [];
+  for (let dir = path.dirname(root); ; dir = path.dirname(dir)) {
+    ancestors.push(dir);
+    if (dir === stopAt || dir === path.dirname(dir) || ancestors.length > 8) break;
+  }
+  const files = new Map();
+  const problems = [];
+  const stamps = new Map();
+
+  async function load(file, text, depth) {
+    if (files.has(file)) return;
+    let parsed;
+    try {
+      parsed = parseProto(text, file);
+    } catch (error) {
+      if (!(error instanceof SchemaSyntaxError)) throw error;
+      problems.push({ file, line: error.line, column: error.column, severity: 'error', message: error.message });
+      parsed = parseProto('', file);
+    }
+    files.set(file, parsed);
+    if (depth > MAX_IMPORT_DEPTH) return;
+    for (const entry of parsed.imports) {
+      let found = null;
+      for (const dir of [...includePaths, path.dirname(file), ...ancestors]) {
+        const candidate = path.resolve(dir, entry.path);
+        if (files.has(candidate)) {
+          found = candidate;
+          break;
+        }
+        const content = await readIfExists(candidate);
+        if (content !== null) {
+          stamps.set(candidate, (await fs.stat(candidate)).mtimeMs);
+          await load(candidate, content, depth + 1);
+          found = candidate;
+          break;
+        }
+      }
+      if (!found && WELL_KNOWN[entry.path]) {
+        found = `builtin:${entry.path}`;
+        await load(found, `syntax = "proto3"; package google.protobuf; ${WELL_KNOWN[entry.path]}`, depth + 1);
+      }
+      if (!found) problems.push({ file, line: entry.line, column: 1, severity: entry.kind === 'weak' ? 'warning' : 'error', message: `Import "${entry.path}" was not found` });
+      entry.resolved = found;
+    }
+  }
+
+  const text = await fs.readFile(root, 'utf-8');
+  stamps.set(root, (await fs.stat(root)).mtimeMs);
+  await load(root, text, 0);
+  return { root, ...link(files, problems), stamps };
+}
+
+// Builds the type table and resolves field, method and extension type references
+function link(files, problems) {
+  const types = new Map();
+  for (const file of files.values()) {
+    for (const message of file.messages) {
+      types.set(message.fullName, { kind: 'message', ...message, byNumber: new Map(), file: file.file, syntax: file.syntax });
+    }
+    for (const definition of file.enums) {
+      types.set(definition.fullName, { kind: 'enum', ...definition, byNumber: new Map(definition.values.map((value) => [value.number, value.name]).reverse()), file: file.file });
+    }
+  }
+  // Package names resolve as scopes too
+  const scopes = new Set(types.keys());
+  for (const name of types.keys()) {
+    const parts = name.split('.');
+    for (let length = 1; length < parts.length; length++) scopes.add(parts.slice(0, length).join('.'));
+  }
+  const resolveType = (field, scope, file) => {
+    if (SCALARS.has(field.type)) return true;
+    const resolved = resolveName(scopes, field.type, scope);
+    const type = resolved && types.get(resolved);
+    if (!type) {
+      problems.push({ file, line: field.line || 1, column: field.column || 1, severity: 'error', message: `Unknown type ${field.type}` });
+      return false;
+    }
+    field.resolvedType = resolved;
+    field.typeKind = type.kind;
+    return true;
+  };
+
+  for (const type of types.values()) {
+    if (type.kind !== 'message') continue;
+    for (const field of type.fields) {
+      resolveType(field, type.fullName, type.file);
+      if (type.byNumber.has(field.number)) problems.push({ file: type.file, line: field.line, column: field.column, severity: 'error', message: `Field number ${field.number} is used twice in ${type.fullName}` });
+      type.byNumber.set(field.number, field);
+    }
+  }
+  for (const file of files.values()) {
+    for (const extension of file.extensions) {
+      const extendee = resolveName(scopes, extension.extendee, extension.scope.replace(/\.$/, '') || file.package);
+      const target = extendee && types.get(extendee);
+      if (!target || target.kind !== 'message') {
+        problems.push({ file: file.file, line: extension.line, column: extension.column || 1, severity: 'error', message: `Unknown extended type ${extension.extendee}` });
+        continue;
+      }
+      const field = { ...extension, jsonName: `[${extension.fullName}]` };
+      if (resolveType(field, extension.scope.replace(/\.$/, '') || file.package, file.file)) target.byNumber.set(field.number, field);
+    }
+    for (const service of file.services) {
+      for (const method of service.methods) {
+        for (const key of ['inputType', 'outputType']) {
+          const resolved = resolveName(scopes, method[key], file.package);
+          if (resolved && types.has(resolved)) method[key] = resolved;
+          else problems.push({ file: file.file, line: method.line, column: 1, severity: 'error', message: `Unknown type ${method[key]} in ${service.name}.${method.name}` });
+        }
+      }
+    }
+  }
+  return { files, types, problems };
+}
+
+// Accepts a fully-qualified name or any unambiguous suffix of one
+function findMessageType(schema, name) {
+  const wanted = String(name || '').replace(/^\./, '');
+  const exact = schema.types.get(wanted);
+  if (exact && exact.kind === 'message') return exact;
+  const matches = Array.from(schema.types.values()).filter((type) => type.kind === 'message' && !type.mapEntry && (type.fullName === wanted || type.fullName.endsWith(`.${wanted}`)));
+  if (matches.length === 1) return matches[0];
+  throw new ForgeError(ErrorCode.INVALID_ARGUMENT, matches.length ? `${wanted} is ambiguous: ${matches.map((type) => type.fullName).join(', ')}` : `No message type named ${wanted}`, {
+    details: { candidates: matches.map((type) => type.fullName) }
+  });
+}
+
+class WireError extends Error {}
+
+class Reader {
+  constructor(buffer, pos = 0, end = buffer.length) {
+    this.buffer = buffer;
+    this.pos = pos;
+    this.end = end;
+  }
+
+  need(count) {
+    if (this.pos + count > this.end) throw new WireError(`Truncated payload at byte ${this.pos}`);
+  }
+
+  varint() {
+    let result = 0n;
+    for (let shift = 0n; shift < 70n; shift += 7n) {
+      this.need(1);
+      const byte = this.buffer[this.pos++];
+      result |= BigInt(byte & 0x7f) << shift;
+      if (byte < 0x80) return BigInt.asUintN(64, result);
+    }
+    throw new WireError(`Malformed varint at byte ${this.pos}`);
+  }
+
+  bytes() {
+    const length = this.varint();
+    if (length > BigInt(this.end - this.pos)) throw new WireError(`Length ${length} at byte ${this.pos} runs past the end of the payload`);
+    const start = this.pos;
+    this.pos += Number(length);
+    return this.buffer.subarray(start, this.pos);
+  }
+
+  fixed(size) {
+    this.need(size);
+    const slice = this.buffer.subarray(this.pos, this.pos + size);
+    this.pos += size;
+    return slice;
+  }
+
+  // Skips one field's value, returning its raw bytes (groups included)
+  skip(wireType, number) {
+    const start = this.pos;
+    switch (wireType) {
+      case WIRE_VARINT: this.varint(); break;
+      case WIRE_FIXED64: this.fixed(8); break;
+      case WIRE_LENGTH: this.bytes(); break;
+      case WIRE_FIXED32: this.fixed(4); break;
+      case WIRE_START_GROUP:
+        for (;;) {
+          const tag = this.tag();
+          if (tag.wireType === WIRE_END_GROUP) {
+            if (tag.number !== number) throw new WireError(`Mismatched end of group ${number} at byte ${this.pos}`);
+            break;
+          }
+          this.skip(tag.wireType, tag.number);
+        }
+        break;
+      default: throw new WireError(`Invalid wire type ${wireType} at byte ${this.pos}`);
+    }
+    return this.buffer.subarray(start, this.pos);
+  }
+
+  tag() {
+    const at = this.pos;
+    const tag = this.varint();
+    const number = Number(tag >> 3n);
+    const wireType = Number(tag & 7n);
+    if (number < 1 || number > 536870911 || wireType > 5) throw new WireError(`Invalid tag at byte ${at}`);
+    return { number, wireType };
+  }
+}
+
+function safeNumber(value) {
+  return Number.isSafeInteger(Number(value)) ? Number(value) : value.toString();
+}
+
+function jsonFloat(value) {
+  return Number.isFinite(value) ? value : String(value);
+}
+
+// proto3 JSON: 64-bit integers as strings, bytes as base64, non-finite floats as strings
+function readScalar(reader, type) {
+  switch (type) {
+    case 'int32': return Number(BigInt.asIntN(32, reader.varint()));
+    case 'uint32': return Number(BigInt.asUintN(32, reader.varint()));
+    case 'int64': return BigInt.asIntN(64, reader.varint()).toString();
+    case 'uint64': return reader.varint().toString();
+    case 'sint32':
+    case 'sint64': {
+      const raw = reader.varint();
+      const value = (raw >> 1n) ^ -(raw & 1n);
+      return type === 'sint32' ? Number(BigInt.asIntN(32, value)) : BigInt.asIntN(64, value).toString();
+    }
+    case 'bool': return reader.varint() !== 0n;
+    case 'fixed32': return reader.fixed(4).readUInt32LE(0);
+    case 'sfixed32': return reader.fixed(4).readInt32LE(0);
+    case 'float': return jsonFloat(reader.fixed(4).readFloatLE(0));
+    case 'fixed64': return reader.fixed(8).readBigUInt64LE(0).toString();
+    case 'sfixed64': return reader.fixed(8).readBigInt64LE(0).toString();
+    case 'double': return jsonFloat(reader.fixed(8).readDoubleLE(0));
+    case 'string': return reader.bytes().toString('utf-8');
+    case 'bytes': return Buffer.from(reader.bytes()).toString('base64');
+    default: throw new WireError(`Unknown scalar type ${type}`);
+  }
+}
+
+function scalarDefault(type) {
+  if (['int64', 'uint64', 'sint64', 'fixed64', 'sfixed64'].includes(type)) return '0';
+  if (type === 'bool') return false;
+  if (type === 'string' || type === 'bytes') return '';
+  return 0;
+}
+
+function wireTypeOf(field) {
+  if (field.group) return WIRE_START_GROUP;
+  if (field.typeKind === 'message') return WIRE_LENGTH;
+  if (field.typeKind === 'enum') return WIRE_VARINT;
+  return SCALAR_WIRE_TYPES[field.type] ?? WIRE_VARINT;
+}
+
+function pad(value, length) {
+  return String(value).padStart(length, '0');
+}
+
+// Fractional seconds trimmed to 0, 3, 6 or 9 digits, as protobuf's JSON printer does
+function fraction(nanos) {
+  if (!nanos) return '';
+  const digits = pad(Math.abs(nanos), 9);
+  return `.${digits.endsWith('000000') ? digits.slice(0, 3) : digits.endsWith('000') ? digits.slice(0, 6) : digits}`;
+}
+
+const WELL_KNOWN_JSON = {
+  'google.protobuf.Timestamp': (values) => {
+    const date = new Date(Number(values.seconds || 0) * 1000);
+    // Out of Timestamp's range; show the fields rather than fail the whole decode
+    if (!Number.isFinite(date.getTime())) return values;
+    return `${date.toISOString().replace(/\.\d{3}Z$/, '')}${fraction(values.nanos || 0)}Z`;
+  },
+  'google.protobuf.Duration': (values) => {
+    const seconds = BigInt(values.seconds || 0);
+    const nanos = values.nanos || 0;
+    const negative = seconds < 0n || nanos < 0;
+    return `${negative ? '-' : ''}${(seconds < 0n ? -seconds : seconds).toString()}${fraction(nanos)}s`;
+  },
+  'google.protobuf.FieldMask': (values) => (values.paths || []).map(jsonName).join(','),
+  'google.protobuf.Struct': (values) => values.fields || {},
+  'google.protobuf.ListValue': (values) => values.values || [],
+  'google.protobuf.Value': (values) => {
+    for (const key of ['number_value', 'string_value', 'bool_value', 'struct_value', 'list_value']) {
+      if (key in values) return values[key];
+    }
+    return null;
+  },
+  'google.protobuf.Empty': () => ({})
+};
+for (const [name, type] of Object.entries({ Double: 'double', Float: 'float', Int64: 'int64', UInt64: 'uint64', Int32: 'int32', UInt32: 'uint32', Bool: 'bool', String: 'string', Bytes: 'bytes' })) {
+  WELL_KNOWN_JSON[`google.protobuf.${name}Value`] = (values) => ('value' in values ? values.value : scalarDefault(type));
+}
+
+function decodeMessage(schema, type, reader, end, options, depth, groupNumber = null) {
+  if (depth > MAX_DEPTH) throw new WireError(`Messages are nested more than ${MAX_DEPTH} deep`);
+  const values = {};
+  const unknown = [];
+  const store = (field, value) => {
+    const key = valueKey(field);
+    if (field.map) {
+      values[key] = values[key] || {};
+      values[key][String(value.key)] = value.value;
+    } else if (field.label === 'repeated') {
+      (values[key] = values[key] || []).push(value);
+    } else {
+      // The last member of a oneof seen on the wire wins
+      if (field.oneof) for (const other of type.fields) if (other.oneof === field.oneof) delete values[other.name];
+      values[key] = value;
+    }
+  };
+  const readValue = (field, fieldReader, wireType, number) => {
+    if (field.typeKind === 'message') {
+      const nestedType = schema.types.get(field.resolvedType);
+      if (wireType === WIRE_START_GROUP) return decodeMessage(schema, nestedType, fieldReader, fieldReader.end, options, depth + 1, number);
+      const bytes = fieldReader.bytes();
+      const nested = decodeMessage(schema, nestedType, new Reader(bytes), bytes.length, options, depth + 1);
+      if (nestedType.mapEntry) {
+        const keyField = nestedType.fields[0];
+        const valueField = nestedType.fields[1];
+        return {
+          key: 'key' in nested ? nested.key : scalarDefault(keyField.type),
+          value: 'value' in nested ? nested.value : valueField.typeKind === 'message' ? {} : valueField.typeKind === 'enum' ? enumName(schema, valueField, 0) : scalarDefault(valueField.type)
+        };
+      }
+      return nested;
+    }
+    if (field.typeKind === 'enum') return enumName(schema, field, Number(BigInt.asIntN(32, fieldReader.varint())));
+    return readScalar(fieldReader, field.type);
+  };
+
+  while (reader.pos < end) {
+    const { number, wireType } = reader.tag();
+    if (wireType === WIRE_END_GROUP) {
+      if (groupNumber === number) return finish(schema, type, values, unknown, options);
+      throw new WireError(`Unexpected end of group ${number} at byte ${reader.pos}`);
+    }
+    const field = type && type.byNumber.get(number);
+    const expected = field ? wireTypeOf(field) : null;
+    if (field && field.label === 'repeated' && wireType === WIRE_LENGTH && expected !== WIRE_LENGTH && expected !== WIRE_START_GROUP) {
+      // Packed repeated scalars: one length-delimited run of values
+      const packed = reader.bytes();
+      const packedReader = new Reader(packed);
+      while (packedReader.pos < packed.length) store(field, readValue(field, packedReader, expected, number));
+    } else if (field && wireType === expected) {
+      store(field, readValue(field, reader, wireType, number));
+    } else {
+      const raw = reader.skip(wireType, number);
+      unknown.push({ number, wireType, value: describeUnknown(raw, wireType, number, depth) });
+    }
+  }
+  if (groupNumber !== null) throw new WireError(`Group ${groupNumber} is not terminated`);
+  return finish(schema, type, values, unknown, options);
+}
+
+// Extensions are keyed by their bracketed full name so they can't clash with fields
+function valueKey(field) {
+  return field.extendee ? field.jsonName : field.name;
+}
+
+function enumName(schema, field, number) {
+  const definition = schema.types.get(field.resolvedType);
+  return (definition && definition.byNumber.get(number)) ?? number;
+}
+
+function finish(schema, type, values, unknown, options) {
+  if (type.fullName === 'google.protobuf.Any') {
+    const typeUrl = values.type_url || '';
+    const inner = schema.types.get(typeUrl.slice(typeUrl.lastIndexOf('/') + 1));
+    if (!inner || inner.kind !== 'message') return { '@type': typeUrl, value: values.value || '' };
+    const bytes = Buffer.from(values.value || '', 'base64');
+    const decoded = decodeMessage(schema, inner, new Reader(bytes), bytes.length, options, 0);
+    return WELL_KNOWN_JSON[inner.fullName] ? { '@type': typeUrl, value: decoded } : { '@type': typeUrl, ...decoded };
+  }
+  if (WELL_KNOWN_JSON[type.fullName]) return WELL_KNOWN_JSON[type.fullName](values);
+  const result = {};
+  for (const field of type.byNumber.values()) {
+    if (valueKey(field) in values) result[options.preserveNames ? valueKey(field) : field.jsonName] = values[valueKey(field)];
+  }
+  // Fields the schema doesn't know; protobuf's JSON printer drops them, a debugger shouldn't
+  if (unknown.length) result.$unknown = unknown;
+  return result;
+}
+
+// Schema-less values, like `protoc --decode_raw`: length-delimited fields are shown as
+// text when they're printable UTF-8, else as nested messages when they parse as one
+function describeUnknown(raw, wireType, number, depth) {
+  const reader = new Reader(raw);
+  switch (wireType) {
+    case WIRE_VARINT: return safeNumber(reader.varint());
+    case WIRE_FIXED32: return `0x${pad(raw.readUInt32LE(0).toString(16), 8)}`;
+    case WIRE_FIXED64: return `0x${pad(raw.readBigUInt64LE(0).toString(16), 16)}`;
+    case WIRE_START_GROUP: {
+      // Tag already consumed; the raw bytes end with the end-group tag
+      const inner = decodeRawFields(raw, 0, raw.length, depth + 1, number);
+      return inner || Buffer.from(raw).toString('base64');
+    }
+    default: {
+      const bytes = reader.bytes();
+      const text = bytes.toString('utf-8');
+      // eslint-disable-next-line no-control-regex
+      if (!text.includes('\uFFFD') && !/[\x00-\x08\x0e-\x1f\x7f]/.test(text)) return text;
+      if (depth < MAX_DEPTH) {
+        const nested = decodeRawFields(bytes, 0, bytes.length, depth + 1);
+        if (nested) return nested;
+      }
+      return Buffer.from(bytes).toString('base64');
+    }
+  }
+}
+
+// { "1": value, "2": [value, value] } or null when the bytes aren't a message
+function decodeRawFields(buffer, start, end, depth, groupNumber = null) {
+  const reader = new Reader(buffer, start, end);
+  const result = {};
+  try {
+    while (reader.pos < end) {
+      const { number, wireType } = reader.tag();
+      if (wireType === WIRE_END_GROUP) {
+        if (number === groupNumber && reader.pos === end) return result;
+        return null;
+      }
+      const raw = reader.skip(wireType, number);
+      const value = describeUnknown(raw, wireType, number, depth);
+      if (number in result) result[number] = [].concat(result[number], [value]);
+      else result[number] = value;
+    }
+  } catch (error) {
+    if (error instanceof WireError) return null;
+    throw error;
+  }
+  return groupNumber === null ? result : null;
+}
+
+// Binary payload -> proto3 JSON for `typeName`; `preserveNames` keeps the .proto field
+// names instead of lowerCamelCase
+function decodeProto(schema, typeName, buffer, options = {}) {
+  const type = findMessageType(schema, typeName);
+  return { typeName: type.fullName, message: decodeMessage(schema, type, new Reader(buffer), buffer.length, options, 0) };
+}
+
+function decodeProtoRaw(buffer) {
+  const message = decodeRawFields(buffer, 0, buffer.length, 0);
+  if (!message) throw new WireError('The payload is not a valid protobuf message');
+  return message;
+}
+
+// Length-prefixed streams: gRPC frames (flag byte + 4-byte big-endian length) or
+// varint-delimited messages as writeDelimitedTo produces
+function splitFrames(buffer, framing) {
+  if (!framing || framing === 'none') return [{ bytes: buffer }];
+  const frames = [];
+  let pos = 0;
+  while (pos < buffer.length) {
+    if (framing === 'grpc') {
+      if (pos + 5 > buffer.length) throw new WireError(`Truncated gRPC frame header at byte ${pos}`);
+      const compressed = buffer[pos] === 1;
+      const length = buffer.readUInt32BE(pos + 1);
+      if (pos + 5 + length > buffer.length) throw new WireError(`gRPC frame at byte ${pos} runs past the end of the payload`);
+      frames.push({ bytes: buffer.subarray(pos + 5, pos + 5 + length), compressed });
+      pos += 5 + length;
+    } else if (framing === 'delimited') {
+      const reader = new Reader(buffer, pos);
+      const bytes = reader.bytes();
+      frames.push({ bytes });
+      pos = reader.pos;
+    } else {
+      throw new WireError(`Unknown framing ${framing}`);
+    }
+  }
+  return frames;
+}
+
+// Summary for a schema browser: the root file's types, with every type the decoder can
+// use (imports included) listed by name
+function describeProtoSchema(schema) {
+  const file = schema.files.get(schema.root);
+  const fieldSummary = (field) => ({
+    name: field.name,
+    jsonName: field.jsonName,
+    number: field.number,
+    label: field.label,
+    type: field.map ? `map<${field.map.keyType}, ${field.map.valueType}>` : field.resolvedType || field.type,
+    oneof: field.oneof || null
+  });
+  return {
+    syntax: file.syntax,
+    edition: file.edition,
+    package: file.package || null,
+    imports: file.imports.map((entry) => ({ path: entry.path, kind: entry.kind, resolved: entry.resolved && !entry.resolved.startsWith('builtin:') ? entry.resolved : null, builtin: Boolean(entry.resolved && entry.resolved.startsWith('builtin:')) })),
+    messages: file.messages.filter((message) => !message.mapEntry).map((message) => ({
+      name: message.name,
+      fullName: message.fullName,
+      line: message.line,
+      fields: schema.types.get(message.fullName).fields.map(fieldSummary),
+      oneofs: message.oneofs
+    })),
+    enums: file.enums.map((definition) => ({ name: definition.name, fullName: definition.fullName, line: definition.line, values: definition.values.map(({ name, number }) => ({ name, number })) })),
+    services: file.services.map((service) => ({
+      name: service.name,
+      fullName: service.fullName,
+      line: service.line,
+      methods: service.methods.map(({ name, inputType, outputType, clientStreaming, serverStreaming }) => ({ name, inputType, outputType, clientStreaming, serverStreaming }))
+    })),
+    extensions: file.extensions.map((extension) => ({ ...fieldSummary(extension), fullName: extension.fullName, extendee: extension.extendee })),
+    messageTypes: Array.from(schema.types.values()).filter((type) => type.kind === 'message' && !type.mapEntry).map((type) => type.fullName).sort()
+  };
+}
+
+module.exports = {
+  SchemaSyntaxError,
+  WireError,
+  TokenStream,
+  tokenize,
+  parseProto,
+  loadProtoSchema,
+  describeProtoSchema,
+  findMessageType,
+  decodeProto,
+  decodeProtoRaw,
+  splitFrames,
+  resolveName
+};
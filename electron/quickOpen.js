@@ -0,0 +1,129 @@
+const path = require('path');
+const { BrowserWindow } = require('electron');
+const { withForgeErrors } = require('./errors');
+const { queryFiles } = require('./fileIndex');
+const { querySymbols } = require('./symbolIndex');
+const { listCommands } = require('./commands');
+const { listTerminals } = require('./terminal');
+const { fuzzyScore } = require('./fuzzy');
+
+const DEFAULT_LIMIT = 50;
+
+// Leading characters that narrow the query to a single source, as in VS Code
+const PREFIXES = {
+  '>': 'command',
+  '#': 'symbol',
+  '@': 'symbol',
+  '$': 'terminal'
+};
+
+// Per-source boosts so, for equal match quality, files rank above symbols above commands
+const TYPE_WEIGHTS = {
+  file: 4,
+  symbol: 2,
+  command: 1,
+  terminal: 0
+};
+
+function parseQuery(raw) {
+  const text = (raw || '').trim();
+  const type = PREFIXES[text[0]];
+  return type ? { type, text: text.slice(1).trim() } : { type: null, text };
+}
+
+function fileResults(rootPath, text, limit) {
+  if (!rootPath || !text) return [];
+  return queryFiles(rootPath, text, limit).map((file) => ({
+    type: 'file',
+    id: file.path,
+    label: path.basename(file.path),
+    description: file.relativePath,
+    score: file.score,
+    positions: file.positions,
+    payload: { path: file.path }
+  }));
+}
+
+function symbolResults(rootPath, text, limit) {
+  if (!rootPath || !text) return [];
+  return querySymbols(rootPath, text, limit).map((symbol) => ({
+    type: 'symbol',
+    id: `${symbol.path}:${symbol.line}:${symbol.name}`,
+    label: symbol.name,
+    description: `${symbol.kind} · ${symbol.relativePath}:${symbol.line}`,
+    score: symbol.score,
+    positions: symbol.positions,
+    payload: { path: symbol.path, line: symbol.line, column: symbol.column, kind: symbol.kind }
+  }));
+}
+
+function commandResults(text, context) {
+  return listCommands(context)
+    .filter((command) => command.enabled)
+    .map((command) => {
+      const match = fuzzyScore(text, command.title);
+      return match && {
+        type: 'command',
+        id: command.id,
+        label: command.title,
+        description: command.keybinding || command.category,
+        score: match.score,
+        positions: match.positions,
+        payload: { commandId: command.id }
+      };
+    })
+    .filter(Boolean);
+}
+
+function terminalResults(text, windowId) {
+  return listTerminals(windowId)
+    .map((terminal, index) => {
+      const label = `${path.basename(terminal.shell)} ${index + 1}`;
+      const match = fuzzyScore(text, `${label} ${terminal.cwd}`);
+      return match && {
+        type: 'terminal',
+        id: terminal.id,
+        label,
+        description: terminal.cwd,
+        score: match.score,
+        positions: match.positions.filter((position) => position < label.length),
+        payload: { terminalId: terminal.id }
+      };
+    })
+    .filter(Boolean);
+}
+
+// Fan a palette query out to every source and merge into one ranked list
+function quickOpen(rawQuery, options = {}) {
+  const { type, text } = parseQuery(rawQuery);
+  const limit = options.limit || DEFAULT_LIMIT;
+  const wanted = (source) => (type ? type === source : !options.types || options.types.includes(source));
+
+  const results = [
+    ...(wanted('file') ? fileResults(options.rootPath, text, limit) : []),
+    ...(wanted('symbol') ? symbolResults(options.rootPath, text, limit) : []),
+    ...(wanted('command') ? commandResults(text, options.context) : []),
+    ...(wanted('terminal') ? terminalResults(text, options.windowId) : [])
+  ];
+
+  return {
+    query: text,
+    type,
+    results: results
+      .map((result) => ({ ...result, score: result.score + TYPE_WEIGHTS[result.type] }))
+      .sort((a, b) => b.score - a.score)
+      .slice(0, limit)
+  };
+}
+
+function setupQuickOpenHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('quickOpen:query', async (event, query, options = {}) => {
+    // Only offer terminals that live in the calling window
+    const win = BrowserWindow.fromWebContents(event.sender);
+    return quickOpen(query, { ...options, windowId: win ? win.id : undefined });
+  });
+}
+
+module.exports = { setupQuickOpenHandlers, quickOpen };
@@ -0,0 +1,356 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { app } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { syncFile, toUri, fromUri } = require('./lsp');
+const { emit } = require('./events');
+const { registerCommand } = require('./commands');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob } = require('./jobs');
+const { buildPattern, searchText, searchWorkspace } = require('./search');
+const { isPathIgnored } = require('./indexScope');
+const { enforceGuardrails } = require('./guardrails');
+const { windowManager } = require('./windows');
+const { validatePath } = require('./filesystem');
+const { writeProtectedFile, readProtectedFile, registerMigration, migrateDirectory } = require('./encryption');
+
+const log = createLogger('refactor');
+
+const MAX_JOURNAL_ENTRIES = 50;
+
+function getJournalDir() {
+  return path.join(app.getPath('userData'), 'refactor-journal');
+}
+
+// Journal entries hold full before/after file contents, so they count as recovery data
+registerMigration('recovery', migrateDirectory(getJournalDir(), (name) => name.endsWith('.json')));
+
+// Convert an LSP position (line + UTF-16 character) to a string offset
+function offsetAt(content, position) {
+  let offset = 0;
+  for (let line = 0; line < position.line; line++) {
+    const next = content.indexOf('\n', offset);
+    if (next < 0) return content.length;
+    offset = next + 1;
+  }
+  return Math.min(offset + position.character, content.length);
+}
+
+// Apply TextEdits to a string. Edits are applied back-to-front so offsets stay valid.
+function applyTextEdits(content, edits) {
+  const resolved = edits
+    .map((edit) => ({
+      start: offsetAt(content, edit.range.start),
+      end: offsetAt(content, edit.range.end),
+      newText: edit.newText
+    }))
+    .sort((a, b) => b.start - a.start || b.end - a.end);
+
+  let result = content;
+  for (const edit of resolved) {
+    result = result.slice(0, edit.start) + edit.newText + result.slice(edit.end);
+  }
+  return result;
+}
+
+// Flatten a WorkspaceEdit into { filePath -> TextEdit[] }
+function collectEdits(workspaceEdit) {
+  const byFile = new Map();
+  const add = (uri, edits) => {
+    const filePath = fromUri(uri);
+    byFile.set(filePath, [...(byFile.get(filePath) || []), ...edits]);
+  };
+
+  if (workspaceEdit.documentChanges) {
+    for (const change of workspaceEdit.documentChanges) {
+      if (change.kind) {
+        throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unsupported workspace edit operation: ${change.kind}`);
+      }
+      add(change.textDocument.uri, change.edits);
+    }
+  } else if (workspaceEdit.changes) {
+    for (const [uri, edits] of Object.entries(workspaceEdit.changes)) {
+      add(uri, edits);
+    }
+  }
+  return byFile;
+}
+
+// Apply edits to disk files atomically (all-or-nothing) and compute new content
+// for open buffers. `openBuffers` maps file path -> unsaved buffer content; those
+// files are left untouched on disk and returned for the editor to apply.
+async function applyWorkspaceEdit(workspaceEdit, openBuffers = {}, description = 'Apply edit') {
+  const edits = collectEdits(workspaceEdit);
+  const files = [];
+
+  for (const [filePath, fileEdits] of edits) {
+    const inBuffer = Object.prototype.hasOwnProperty.call(openBuffers, filePath);
+    const before = inBuffer ? openBuffers[filePath] : await fs.readFile(filePath, 'utf-8');
+    files.push({ path: filePath, onDisk: !inBuffer, before, after: applyTextEdits(before, fileEdits) });
+  }
+
+  // Stage every disk write first so a failure leaves no file half-renamed. Temp files sit
+  // next to the symlink's target, not the link, so the rename updates the target and leaves
+  // the link in place, and they take the original's mode before replacing it.
+  const staged = [];
+  try {
+    for (const file of files.filter((f) => f.onDisk)) {
+      const targetPath = await fs.realpath(file.path);
+      const { mode } = await fs.stat(targetPath);
+      const tempPath = `${targetPath}.forge-${process.pid}.tmp`;
+      staged.push({ file, targetPath, tempPath });
+      await fs.writeFile(tempPath, file.after, { encoding: 'utf-8', mode });
+      // The mode given to writeFile is masked by the umask
+      await fs.chmod(tempPath, mode);
+    }
+  } catch (error) {
+    await Promise.all(staged.map(({ tempPath }) => fs.rm(tempPath, { force: true })));
+    throw ForgeError.from(error, 'Failed to stage workspace edit');
+  }
+
+  const committed = [];
+  try {
+    for (const { file, targetPath, tempPath } of staged) {
+      await fs.rename(tempPath, targetPath);
+      committed.push(file);
+    }
+  } catch (error) {
+    // Roll back files already replaced
+    await Promise.all(committed.map((file) => fs.writeFile(file.path, file.before, 'utf-8').catch(() => {})));
+    await Promise.all(staged.map(({ tempPath }) => fs.rm(tempPath, { force: true })));
+    throw ForgeError.from(error, 'Failed to apply workspace edit');
+  }
+
+  const entry = await recordJournalEntry(description, files);
+  const result = {
+    journalId: entry.id,
+    files: files.map((file) => ({
+      path: file.path,
+      onDisk: file.onDisk,
+      content: file.onDisk ? undefined : file.after
+    }))
+  };
+  emit('refactor:applied', { journalId: entry.id, description, paths: files.map((file) => file.path) });
+  return result;
+}
+
+async function recordJournalEntry(description, files) {
+  const entry = {
+    id: `edit_${Date.now()}_${uuidv4().slice(0, 8)}`,
+    description,
+    timestamp: new Date().toISOString(),
+    files
+  };
+  const dir = getJournalDir();
+  await writeProtectedFile('recovery', path.join(dir, `${entry.id}.json`), JSON.stringify(entry));
+
+  // Trim the oldest entries
+  const existing = (await fs.readdir(dir)).filter((name) => name.endsWith('.json')).sort();
+  await Promise.all(existing.slice(0, Math.max(0, existing.length - MAX_JOURNAL_ENTRIES))
+    .map((name) => fs.rm(path.join(dir, name), { force: true })));
+  return entry;
+}
+
+async function readJournalEntry(id) {
+  if (!/^[\w-]+$/.test(id)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid journal id: ${id}`);
+  }
+  try {
+    return JSON.parse(await readProtectedFile(path.join(getJournalDir(), `${id}.json`)));
+  } catch (error) {
+    throw ForgeError.from(error, `Journal entry ${id}`);
+  }
+}
+
+// Revert a journal entry. Disk files are only restored if nobody changed them since.
+async function undoJournalEntry(id) {
+  const entry = await readJournalEntry(id);
+  const conflicts = [];
+  for (const file of entry.files.filter((f) => f.onDisk)) {
+    const current = await fs.readFile(file.path, 'utf-8').catch(() => null);
+    if (current !== file.after) conflicts.push(file.path);
+  }
+  if (conflicts.length > 0) {
+    throw new ForgeError(ErrorCode.CONFLICT, 'Files changed since the edit was applied', { details: { conflicts } });
+  }
+
+  for (const file of entry.files.filter((f) => f.onDisk)) {
+    await fs.writeFile(file.path, file.before, 'utf-8');
+  }
+  await fs.rm(path.join(getJournalDir(), `${id}.json`), { force: true });
+
+  return {
+    files: entry.files.map((file) => ({
+      path: file.path,
+      onDisk: file.onDisk,
+      content: file.onDisk ? undefined : file.before
+    }))
+  };
+}
+
+// Sandbox and guardrail checks for a WorkspaceEdit that came from the renderer or a
+// language server, before any of it is written
+function guardWorkspaceEdit(workspaceEdit, { workspacePath, confirmationToken } = {}) {
+  const paths = Array.from(collectEdits(workspaceEdit).keys()).map(validatePath);
+  enforceGuardrails('replace', paths, { workspacePath, confirmationToken });
+}
+
+async function renameSymbol({ rootPath, filePath, position, newName, openBuffers = {}, workspacePath, confirmationToken }) {
+  if (!newName || !newName.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'New name must not be empty');
+  }
+
+  // Make sure the server sees unsaved buffers before computing the rename
+  const client = await syncFile(rootPath, filePath, openBuffers[filePath]);
+  for (const [bufferPath, content] of Object.entries(openBuffers)) {
+    if (bufferPath !== filePath) {
+      await syncFile(rootPath, bufferPath, content).catch(() => {});
+    }
+  }
+
+  const textDocument = { uri: toUri(filePath) };
+  const renameProvider = client.capabilities.renameProvider;
+  if (!renameProvider) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${client.serverId} does not support rename`);
+  }
+  if (renameProvider.prepareProvider) {
+    const prepared = await client.request('textDocument/prepareRename', { textDocument, position });
+    if (!prepared) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'The element at this position cannot be renamed');
+    }
+  }
+
+  const workspaceEdit = await client.request('textDocument/rename', { textDocument, position, newName });
+  if (!workspaceEdit) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, 'No symbol to rename at this position');
+  }
+
+  // The server decides which files the rename touches
+  guardWorkspaceEdit(workspaceEdit, { workspacePath, confirmationToken });
+  log.info(`Renaming symbol in ${filePath} to ${newName}`);
+  return applyWorkspaceEdit(workspaceEdit, openBuffers, `Rename to ${newName}`);
+}
+
+function isInside(parent, child) {
+  const relative = path.relative(parent, child);
+  return relative === '' || (!relative.startsWith('..') && !path.isAbsolute(relative));
+}
+
+// The replacement for one regex match, with $1, $<name>, $` etc. expanded. The pattern runs
+// again at the match's position in its whole line, so lookbehind, ^, \b and the like see
+// the same context the search did.
+function expandReplacement(sticky, match, replacement) {
+  const start = match.column - 1;
+  const suffixLength = match.text.length - (start + match.match.length);
+  sticky.lastIndex = start;
+  const replaced = match.text.replace(sticky, replacement);
+  return replaced.slice(start, replaced.length - suffixLength);
+}
+
+// Replace every match of `query` under `rootPath`. Unsaved buffers are searched instead of
+// their files on disk. Goes through the guardrails (operation 'replace') and then
+// applyWorkspaceEdit, so it is all-or-nothing and can be undone from the journal.
+async function replaceInProject({ rootPath, query, replacement, options = {}, openBuffers = {}, confirmationToken, workspacePath }) {
+  if (typeof replacement !== 'string') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A replacement string is required');
+  }
+  if (typeof rootPath !== 'string' || !rootPath) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A project folder is required');
+  }
+  const root = validatePath(path.resolve(rootPath));
+  const pattern = buildPattern(query, options);
+  // Sticky copy for re-running a match where it was found, see expandReplacement
+  const sticky = new RegExp(pattern.source, `${pattern.flags.replace('g', '')}y`);
+  const matchesByFile = new Map();
+
+  const job = runJob('search', `Find matches for ${query}`, (handle) => searchWorkspace(root, pattern, {
+    ...options,
+    maxResults: Infinity,
+    signal: handle.signal,
+    ignored: (entryPath, type) => isPathIgnored(root, entryPath, type),
+    onFile: (filePath, matches) => {
+      if (!Object.prototype.hasOwnProperty.call(openBuffers, filePath)) matchesByFile.set(filePath, matches);
+    }
+  }));
+  await job.promise;
+  for (const [bufferPath, content] of Object.entries(openBuffers)) {
+    if (!isInside(root, bufferPath)) continue;
+    const matches = searchText(content, pattern);
+    if (matches.length > 0) matchesByFile.set(bufferPath, matches);
+  }
+  if (matchesByFile.size === 0) {
+    return { journalId: null, files: [], replacements: 0 };
+  }
+
+  enforceGuardrails('replace', Array.from(matchesByFile.keys()), { workspacePath: workspacePath || root, confirmationToken });
+
+  const changes = {};
+  let replacements = 0;
+  for (const [filePath, matches] of matchesByFile) {
+    changes[toUri(filePath)] = matches.map((match) => {
+      replacements++;
+      const start = { line: match.line - 1, character: match.column - 1 };
+      return {
+        range: { start, end: { line: start.line, character: start.character + match.match.length } },
+        newText: options.regex ? expandReplacement(sticky, match, replacement) : replacement
+      };
+    });
+  }
+  log.info(`Replacing ${replacements} match(es) of ${query} in ${matchesByFile.size} file(s)`);
+  const result = await applyWorkspaceEdit({ changes }, openBuffers, `Replace "${query}" with "${replacement}"`);
+  return { ...result, replacements };
+}
+
+function workspacePathFor(sender) {
+  const context = sender ? windowManager.fromSender(sender) : null;
+  return context ? context.workspacePath : undefined;
+}
+
+function setupRefactorHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  registerCommand({
+    id: 'editor.renameSymbol',
+    title: 'Rename Symbol',
+    category: 'editor',
+    keybinding: 'F2',
+    when: 'editorFocus',
+    palette: false,
+    handler: (...args) => {
+      const context = args.pop();
+      const [options] = args;
+      return renameSymbol({ ...options, workspacePath: workspacePathFor(context.sender) });
+    }
+  });
+
+  ipcMain.handle('refactor:renameSymbol', async (event, options) => {
+    return renameSymbol({ ...options, workspacePath: workspacePathFor(event.sender) });
+  });
+
+  // Edits from the renderer get the same sandbox and guardrail checks as fs:* writes
+  ipcMain.handle('refactor:applyWorkspaceEdit', async (event, workspaceEdit, openBuffers, description, options = {}) => {
+    guardWorkspaceEdit(workspaceEdit, { workspacePath: workspacePathFor(event.sender), confirmationToken: options.confirmationToken });
+    return applyWorkspaceEdit(workspaceEdit, openBuffers, description);
+  });
+
+  ipcMain.handle('refactor:replaceInProject', async (event, request) => {
+    return replaceInProject({ ...request, workspacePath: workspacePathFor(event.sender) });
+  });
+
+  ipcMain.handle('refactor:undo', async (event, journalId) => {
+    return undoJournalEntry(journalId);
+  });
+
+  ipcMain.handle('refactor:listJournal', async () => {
+    const dir = getJournalDir();
+    const names = await fs.readdir(dir).catch(() => []);
+    const entries = await Promise.all(names.filter((name) => name.endsWith('.json')).map(async (name) => {
+      const entry = JSON.parse(await readProtectedFile(path.join(dir, name)));
+      return { id: entry.id, description: entry.description, timestamp: entry.timestamp, paths: entry.files.map((f) => f.path) };
+    }));
+    return entries.sort((a, b) => b.timestamp.localeCompare(a.timestamp));
+  });
+}
+
+module.exports = { setupRefactorHandlers, applyWorkspaceEdit, applyTextEdits, renameSymbol };
@@ -0,0 +1,430 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { createLogger } = require('./logger');
+const { runGit } = require('./git');
+const { listUnignoredFiles } = require('./snapshots');
+const { getForgeRemote, forgeRequest, projectApiPath } = require('./forgeRemote');
+const { registerCommand } = require('./commands');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('release');
+
+const PUSH_TIMEOUT = 120000;
+const DEFAULT_PREFIX = 'v';
+const DEFAULT_CHANGELOG = 'CHANGELOG.md';
+const BUMPS = ['major', 'minor', 'patch'];
+const VERSION_PATTERN = /^(\d+)\.(\d+)\.(\d+)(?:-([0-9A-Za-z.-]+))?(?:\+([0-9A-Za-z.-]+))?$/;
+
+// Changelog sections in order; only the first four are shown unless `includeAll` is set
+const SECTIONS = [
+  ['feat', 'Features'],
+  ['fix', 'Bug Fixes'],
+  ['perf', 'Performance'],
+  ['revert', 'Reverts'],
+  ['refactor', 'Refactoring'],
+  ['docs', 'Documentation'],
+  ['build', 'Build'],
+  ['ci', 'Continuous Integration'],
+  ['test', 'Tests'],
+  ['style', 'Style'],
+  ['chore', 'Chores']
+];
+const DEFAULT_SECTIONS = new Set(['feat', 'fix', 'perf', 'revert']);
+const HEADER_PATTERN = /^(\w+)(?:\(([^)]*)\))?(!)?:\s+(.+)$/;
+const BREAKING_PATTERN = /^BREAKING[ -]CHANGE:\s*([\s\S]*?)(?:\n\s*\n|$)/m;
+
+function parseVersion(text) {
+  const match = VERSION_PATTERN.exec(String(text).trim());
+  if (!match) return null;
+  return { major: Number(match[1]), minor: Number(match[2]), patch: Number(match[3]), prerelease: match[4] || null };
+}
+
+function validateVersion(text) {
+  if (!parseVersion(text)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${text} is not a semantic version (e.g. 1.4.0)`);
+  return String(text).trim();
+}
+
+// A pre-release bumps to its own release ("1.0.0-rc.1" + patch = "1.0.0") like npm version
+function bumpVersion(text, bump) {
+  const version = parseVersion(text);
+  if (!version) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${text} is not a semantic version`);
+  if (!BUMPS.includes(bump)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Bump must be one of ${BUMPS.join(', ')}`);
+  const { major, minor, patch, prerelease } = version;
+  if (bump === 'major') return prerelease && minor === 0 && patch === 0 ? `${major}.0.0` : `${major + 1}.0.0`;
+  if (bump === 'minor') return prerelease && patch === 0 ? `${major}.${minor}.0` : `${major}.${minor + 1}.0`;
+  return prerelease ? `${major}.${minor}.${patch}` : `${major}.${minor}.${patch + 1}`;
+}
+
+async function resolveRepo(rootPath) {
+  if (typeof rootPath !== 'string' || !rootPath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A workspace folder is required');
+  try {
+    return (await runGit(path.resolve(rootPath), ['rev-parse', '--show-toplevel'])).trim();
+  } catch {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `${rootPath} is not in a git repository`);
+  }
+}
+
+// Most recent tag reachable from HEAD that looks like a release ("v1.2.3" with the default
+// prefix), or null before the first release
+async function findLastTag(repoRoot, prefix = DEFAULT_PREFIX) {
+  try {
+    return (await runGit(repoRoot, ['describe', '--tags', '--abbrev=0', '--match', `${prefix}[0-9]*`])).trim();
+  } catch {
+    return null;
+  }
+}
+
+// Conventional commits in `range`; other commits are counted but left out
+async function readCommits(repoRoot, range) {
+  const args = ['log', '--no-merges', '--no-color', '--format=%H%x1f%s%x1f%b%x1e'];
+  if (range) args.push(range);
+  let output;
+  try {
+    output = await runGit(repoRoot, args);
+  } catch (error) {
+    // A repository without commits has nothing to release
+    if (/does not have any commits/.test(error.message)) return { commits: [], skipped: 0 };
+    throw error;
+  }
+  const commits = [];
+  let skipped = 0;
+  for (const record of output.split('\x1e')) {
+    const [hash, subject, body = ''] = record.replace(/^\n/, '').split('\x1f');
+    if (!hash) continue;
+    const header = HEADER_PATTERN.exec(subject);
+    if (!header) {
+      skipped++;
+      continue;
+    }
+    const breaking = BREAKING_PATTERN.exec(body);
+    commits.push({
+      hash,
+      type: header[1].toLowerCase(),
+      scope: header[2] || null,
+      description: header[4].trim(),
+      // "feat!:" without a BREAKING CHANGE footer describes the break in its subject
+      breaking: breaking ? breaking[1].trim() : header[3] ? header[4].trim() : null
+    });
+  }
+  return { commits, skipped };
+}
+
+// Breaking changes bump major (minor before 1.0), features minor, anything else patch
+function recommendBump(commits, currentVersion) {
+  const version = parseVersion(currentVersion);
+  if (commits.some((commit) => commit.breaking)) return version && version.major === 0 ? 'minor' : 'major';
+  if (commits.some((commit) => commit.type === 'feat')) return 'minor';
+  return 'patch';
+}
+
+// Version recorded in the root package.json or Cargo.toml, for repositories not tagged yet
+async function manifestVersion(repoRoot) {
+  const pkg = await fs.readFile(path.join(repoRoot, 'package.json'), 'utf-8').catch(() => null);
+  if (pkg) {
+    try {
+      const version = JSON.parse(pkg).version;
+      if (parseVersion(version || '')) return version;
+    } catch {
+      // Try Cargo.toml
+    }
+  }
+  const cargo = await fs.readFile(path.join(repoRoot, 'Cargo.toml'), 'utf-8').catch(() => null);
+  const version = cargo && cargoVersion(cargo);
+  return version ? version.value : null;
+}
+
+function commitLink(remote, hash) {
+  const short = hash.slice(0, 7);
+  if (!remote) return short;
+  return `[${short}](${remote.webUrl}${remote.kind === 'gitlab' ? '/-' : ''}/commit/${hash})`;
+}
+
+function renderChangelog({ version, tag, previousTag, date, commits, includeAll, remote }) {
+  let heading = version;
+  if (remote && previousTag) heading = `[${version}](${remote.webUrl}${remote.kind === 'gitlab' ? '/-' : ''}/compare/${previousTag}...${tag})`;
+  const lines = [`## ${heading} (${date})`, ''];
+  const entry = (commit, text) => `* ${commit.scope ? `**${commit.scope}:** ` : ''}${text} (${commitLink(remote, commit.hash)})`;
+
+  const breaking = commits.filter((commit) => commit.breaking);
+  if (breaking.length) {
+    lines.push('### BREAKING CHANGES', '', ...breaking.map((commit) => entry(commit, commit.breaking)), '');
+  }
+  for (const [type, title] of SECTIONS) {
+    if (!includeAll && !DEFAULT_SECTIONS.has(type)) continue;
+    const matching = commits.filter((commit) => commit.type === type);
+    if (matching.length) lines.push(`### ${title}`, '', ...matching.map((commit) => entry(commit, commit.description)), '');
+  }
+  return `${lines.join('\n').trimEnd()}\n`;
+}
+
+// Changelog section for the commits since the last release tag. The next version is
+// `version`, else the current one bumped by `bump`, else by what the commits call for.
+async function generateChangelog(rootPath, { version, bump, prefix = DEFAULT_PREFIX, from, includeAll = false, remote: remoteName = 'origin' } = {}) {
+  const repoRoot = await resolveRepo(rootPath);
+  const previousTag = from || (await findLastTag(repoRoot, prefix));
+  const { commits, skipped } = await readCommits(repoRoot, previousTag ? `${previousTag}..HEAD` : null);
+  let currentVersion = null;
+  if (previousTag && previousTag.startsWith(prefix) && parseVersion(previousTag.slice(prefix.length))) currentVersion = previousTag.slice(prefix.length);
+  else currentVersion = await manifestVersion(repoRoot);
+
+  const recommended = recommendBump(commits, currentVersion);
+  let nextVersion;
+  if (version) nextVersion = validateVersion(version);
+  else if (currentVersion) nextVersion = bumpVersion(currentVersion, bump || recommended);
+  else nextVersion = '0.1.0';
+
+  const remote = await getForgeRemote(repoRoot, remoteName).catch(() => null);
+  const tag = `${prefix}${nextVersion}`;
+  const date = new Date().toISOString().slice(0, 10);
+  return {
+    repoRoot,
+    previousTag,
+    currentVersion,
+    version: nextVersion,
+    tag,
+    recommendedBump: recommended,
+    commits,
+    // Commits that don't follow the conventional format
+    skipped,
+    markdown: renderChangelog({ version: nextVersion, tag, previousTag, date, commits, includeAll, remote })
+  };
+}
+
+// Prepends a section to the changelog, below any title or preamble before the first release
+async function writeChangelog(rootPath, markdown, file = DEFAULT_CHANGELOG) {
+  if (typeof markdown !== 'string' || !markdown.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Changelog text is required');
+  const repoRoot = await resolveRepo(rootPath);
+  const filePath = path.resolve(repoRoot, file);
+  const existing = await fs.readFile(filePath, 'utf-8').catch((error) => {
+    if (error.code === 'ENOENT') return null;
+    throw ForgeError.from(error, `Could not read ${file}`);
+  });
+  const section = `${markdown.trim()}\n`;
+  let text;
+  if (existing === null) {
+    text = `# Changelog\n\n${section}`;
+  } else {
+    const firstRelease = existing.search(/^## /m);
+    text = firstRelease < 0
+      ? `${existing.trimEnd()}\n\n${section}`
+      : `${existing.slice(0, firstRelease)}${section}\n${existing.slice(firstRelease)}`;
+  }
+  await fs.writeFile(filePath, text);
+  return filePath;
+}
+
+// Version line of Cargo.toml's [package] or [workspace.package] table; null for
+// `version.workspace = true` or a missing version
+function cargoVersion(text) {
+  let section = null;
+  const lines = text.split('\n');
+  for (let index = 0; index < lines.length; index++) {
+    const header = /^\s*\[([^\]]+)\]\s*(#.*)?$/.exec(lines[index]);
+    if (header) {
+      section = header[1].trim();
+      continue;
+    }
+    if (section !== 'package' && section !== 'workspace.package') continue;
+    const match = /^(\s*version\s*=\s*)"([^"]*)"/.exec(lines[index]);
+    if (match) return { line: index, prefix: match[1], value: match[2] };
+  }
+  return null;
+}
+
+function cargoName(text) {
+  const match = /^\[package\][\s\S]*?^\s*name\s*=\s*"([^"]+)"/m.exec(text);
+  return match ? match[1] : null;
+}
+
+function detectIndent(text) {
+  const match = /^[ \t]+(?=")/m.exec(text);
+  return match ? match[0] : 2;
+}
+
+function rewriteJson(text, data) {
+  return `${JSON.stringify(data, null, detectIndent(text))}${text.endsWith('\n') ? '\n' : ''}`;
+}
+
+// package.json and Cargo.toml files in the repository, skipping ignored ones
+async function findManifests(repoRoot) {
+  const { files } = await listUnignoredFiles(repoRoot);
+  return files.filter((relative) => ['package.json', 'Cargo.toml'].includes(path.posix.basename(relative)) && !relative.split('/').includes('node_modules'));
+}
+
+// Sets the version of every package.json and Cargo.toml in the repository, either to
+// `version` or each manifest's own version bumped by `bump`. Path dependencies between the
+// bumped crates, Cargo.lock and package-lock.json are updated to match.
+async function bumpVersions(rootPath, { version, bump, dryRun = false } = {}) {
+  if (!version && !bump) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Either a version or a bump (major, minor, patch) is required');
+  if (version) validateVersion(version);
+  const repoRoot = await resolveRepo(rootPath);
+  const changes = [];
+  const writes = new Map();
+  const crates = new Map();
+  const npmPackages = new Map();
+
+  for (const relative of await findManifests(repoRoot)) {
+    const filePath = path.join(repoRoot, relative);
+    const text = await fs.readFile(filePath, 'utf-8').catch(() => null);
+    if (text === null) continue;
+    if (relative.endsWith('package.json')) {
+      let data;
+      try {
+        data = JSON.parse(text);
+      } catch {
+        continue;
+      }
+      if (typeof data.version !== 'string' || !parseVersion(data.version)) continue;
+      const next = version || bumpVersion(data.version, bump);
+      changes.push({ path: relative, name: data.name || null, from: data.version, to: next });
+      writes.set(filePath, rewriteJson(text, { ...data, version: next }));
+      npmPackages.set(path.posix.dirname(relative), next);
+    } else {
+      const current = cargoVersion(text);
+      if (!current || !parseVersion(current.value)) continue;
+      const next = version || bumpVersion(current.value, bump);
+      const lines = text.split('\n');
+      lines[current.line] = lines[current.line].replace(/"[^"]*"/, `"${next}"`);
+      const name = cargoName(text);
+      changes.push({ path: relative, name, from: current.value, to: next });
+      writes.set(filePath, lines.join('\n'));
+      if (name) crates.set(name, { from: current.value, to: next });
+    }
+  }
+
+  // `foo = { path = "../foo", version = "1.2.0" }` must follow foo's new version
+  if (crates.size) {
+    for (const [filePath, text] of writes) {
+      if (!filePath.endsWith('Cargo.toml')) continue;
+      writes.set(filePath, text.replace(/^(\s*([\w-]+)\s*=\s*\{[^}\n]*\bpath\s*=[^}\n]*\bversion\s*=\s*")([^"]*)(")/gm, (whole, start, name, current, end) => {
+        const crate = crates.get(name);
+        return crate ? `${start}${current.replace(crate.from, crate.to)}${end}` : whole;
+      }));
+    }
+    const lockPath = path.join(repoRoot, 'Cargo.lock');
+    const lock = await fs.readFile(lockPath, 'utf-8').catch(() => null);
+    if (lock !== null) {
+      // Workspace crates are the [[package]] entries without a source
+      const blocks = lock.split(/\n(?=\[\[package\]\])/).map((block) => {
+        const name = /^name = "([^"]+)"/m.exec(block);
+        const crate = name && crates.get(name[1]);
+        if (!crate || /^source = /m.test(block)) return block;
+        return block.replace(/^version = "[^"]*"/m, `version = "${crate.to}"`);
+      });
+      const updated = blocks.join('\n');
+      if (updated !== lock) writes.set(lockPath, updated);
+    }
+  }
+  const npmLockPath = path.join(repoRoot, 'package-lock.json');
+  const npmLockText = npmPackages.size ? await fs.readFile(npmLockPath, 'utf-8').catch(() => null) : null;
+  if (npmLockText !== null) {
+    try {
+      const lock = JSON.parse(npmLockText);
+      if (npmPackages.has('.')) lock.version = npmPackages.get('.');
+      for (const [dir, next] of npmPackages) {
+        const key = dir === '.' ? '' : dir;
+        if (lock.packages && lock.packages[key]) lock.packages[key].version = next;
+      }
+      writes.set(npmLockPath, rewriteJson(npmLockText, lock));
+    } catch {
+      // Leave a lock file we can't parse for npm to rewrite
+    }
+  }
+
+  if (!dryRun) {
+    for (const [filePath, text] of writes) await fs.writeFile(filePath, text);
+    log.info(`Bumped ${changes.length} manifests in ${repoRoot}`);
+  }
+  return { changes, files: Array.from(writes.keys()).map((filePath) => path.relative(repoRoot, filePath).split(path.sep).join('/')), dryRun };
+}
+
+// Annotated tag on HEAD, pushed to `remote` when `push` is set
+async function createTag(rootPath, { version, tag, prefix = DEFAULT_PREFIX, message, push = false, remote = 'origin' } = {}) {
+  const repoRoot = await resolveRepo(rootPath);
+  const name = tag || (version ? `${prefix}${validateVersion(version)}` : null);
+  if (!name) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A version or tag name is required');
+  const exists = await runGit(repoRoot, ['rev-parse', '--verify', '--quiet', `refs/tags/${name}`]).then(() => true, () => false);
+  if (exists) throw new ForgeError(ErrorCode.ALREADY_EXISTS, `Tag ${name} already exists`);
+  await runGit(repoRoot, ['tag', '-a', name, '-m', message || `Release ${name}`]);
+  if (push) await runGit(repoRoot, ['push', remote, `refs/tags/${name}`], { timeout: PUSH_TIMEOUT });
+  const commit = (await runGit(repoRoot, ['rev-list', '-n', '1', name])).trim();
+  log.info(`Tagged ${commit.slice(0, 7)} as ${name}${push ? ` and pushed to ${remote}` : ''}`);
+  return { tag: name, commit, pushed: push };
+}
+
+// Draft GitHub release for `tag`; GitLab has no drafts, so it isn't supported there
+async function draftRelease(rootPath, { tag, name, body = '', prerelease, remote: remoteName = 'origin' } = {}) {
+  if (typeof tag !== 'string' || !tag) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A tag is required');
+  const repoRoot = await resolveRepo(rootPath);
+  const remote = await getForgeRemote(repoRoot, remoteName);
+  if (remote.kind !== 'github') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Draft releases are only supported on GitHub, not ${remote.host}`);
+  const version = parseVersion(tag.replace(/^[^\d]*/, ''));
+  const release = await forgeRequest(remote, 'POST', `${projectApiPath(remote)}/releases`, {
+    body: {
+      tag_name: tag,
+      name: name || tag,
+      body,
+      draft: true,
+      prerelease: prerelease === undefined ? Boolean(version && version.prerelease) : Boolean(prerelease)
+    }
+  });
+  log.info(`Drafted release ${tag} on ${remote.projectPath}`);
+  return { id: release.id, url: release.html_url, tag: release.tag_name, draft: release.draft };
+}
+
+// Whole release in one go: changelog section, version bumps, a "chore(release)" commit of
+// just those files, the tag, and optionally a push and a draft GitHub release
+async function prepareRelease(rootPath, options = {}) {
+  const { changelogFile = DEFAULT_CHANGELOG, prefix = DEFAULT_PREFIX, push = false, draft = false, remote = 'origin', dryRun = false } = options;
+  const repoRoot = await resolveRepo(rootPath);
+  const changelog = await generateChangelog(repoRoot, options);
+  if (!changelog.commits.length && !options.version) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `No conventional commits since ${changelog.previousTag || 'the first commit'}`);
+  }
+  const exists = await runGit(repoRoot, ['rev-parse', '--verify', '--quiet', `refs/tags/${changelog.tag}`]).then(() => true, () => false);
+  if (exists) throw new ForgeError(ErrorCode.ALREADY_EXISTS, `Tag ${changelog.tag} already exists`);
+  const bumped = await bumpVersions(repoRoot, { version: changelog.version, dryRun });
+  const files = [...bumped.files, changelogFile];
+  if (dryRun) return { version: changelog.version, tag: changelog.tag, changelog, changes: bumped.changes, files, commit: null, release: null, dryRun };
+
+  await writeChangelog(repoRoot, changelog.markdown, changelogFile);
+  await runGit(repoRoot, ['add', '--', ...files]);
+  await runGit(repoRoot, ['commit', '-m', `chore(release): ${changelog.version}`, '--', ...files]);
+  const tagged = await createTag(repoRoot, { tag: changelog.tag, prefix });
+  if (push) {
+    const branch = (await runGit(repoRoot, ['rev-parse', '--abbrev-ref', 'HEAD'])).trim();
+    await runGit(repoRoot, ['push', remote, branch, `refs/tags/${changelog.tag}`], { timeout: PUSH_TIMEOUT });
+  }
+  let release = null;
+  if (draft) {
+    // The body is the section without its heading, which the release title repeats
+    const body = changelog.markdown.replace(/^## .*\n+/, '');
+    release = await draftRelease(repoRoot, { tag: changelog.tag, body, remote });
+  }
+  return { version: changelog.version, tag: changelog.tag, changelog, changes: bumped.changes, files, commit: tagged.commit, release, dryRun };
+}
+
+function setupReleaseHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('release:changelog', async (event, rootPath, options) => generateChangelog(rootPath, options || {}));
+  ipcMain.handle('release:writeChangelog', async (event, rootPath, markdown, file) => writeChangelog(rootPath, markdown, file || DEFAULT_CHANGELOG));
+  ipcMain.handle('release:bumpVersions', async (event, rootPath, options) => bumpVersions(rootPath, options || {}));
+  ipcMain.handle('release:createTag', async (event, rootPath, options) => createTag(rootPath, options || {}));
+  ipcMain.handle('release:draft', async (event, rootPath, options) => draftRelease(rootPath, options || {}));
+  ipcMain.handle('release:prepare', async (event, rootPath, options) => prepareRelease(rootPath, options || {}));
+
+  registerCommand({
+    id: 'workspace.prepareRelease',
+    title: 'Prepare Release',
+    category: 'workspace',
+    palette: false,
+    handler: (...args) => {
+      args.pop();
+      const [rootPath, options] = args;
+      return prepareRelease(rootPath, options || {});
+    }
+  });
+}
+
+module.exports = { setupReleaseHandlers, generateChangelog, writeChangelog, bumpVersions, createTag, draftRelease, prepareRelease, bumpVersion };
@@ -0,0 +1,265 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { parseJsonc } = require('./snippets');
+
+const log = createLogger('run-configs');
+
+const KINDS = ['run', 'debug'];
+
+const store = new JsonStore('run-configs', {
+  // Workspace root -> run configuration id -> configuration
+  workspaces: {}
+});
+
+// Interpreters for launch.json debug types that run a `program`
+const DEBUG_RUNTIMES = {
+  node: 'node',
+  'pwa-node': 'node',
+  python: 'python',
+  debugpy: 'python'
+};
+// launch.json fields that map onto run configuration fields; the rest is kept as `debug`
+const LAUNCH_FIELDS = ['name', 'program', 'module', 'args', 'env', 'cwd', 'preLaunchTask', 'runtimeExecutable', 'runtimeArgs'];
+
+function workspaceConfigs(rootPath) {
+  return store.get('workspaces')[path.resolve(rootPath)] || {};
+}
+
+function saveWorkspaceConfigs(rootPath, configs) {
+  const workspaces = store.get('workspaces');
+  const root = path.resolve(rootPath);
+  if (Object.keys(configs).length === 0) {
+    delete workspaces[root];
+  } else {
+    workspaces[root] = configs;
+  }
+  store.set('workspaces', workspaces);
+}
+
+function stringArray(value, field) {
+  if (value === undefined || value === null) return [];
+  if (!Array.isArray(value) || value.some((item) => typeof item !== 'string')) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${field} must be a list of strings`);
+  }
+  return value;
+}
+
+// Paths and strings may use ${workspaceFolder}, resolved when the configuration runs
+function validateConfig(input) {
+  if (!input || typeof input.name !== 'string' || !input.name.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Run configuration needs a name');
+  }
+  if (typeof input.command !== 'string' || !input.command.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Run configuration ${input.name} needs a command`);
+  }
+  const env = input.env || {};
+  if (typeof env !== 'object' || Array.isArray(env) || Object.values(env).some((value) => typeof value !== 'string')) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'env must map names to strings');
+  }
+  const kind = input.kind || 'run';
+  if (!KINDS.includes(kind)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `kind must be one of: ${KINDS.join(', ')}`);
+  }
+  return {
+    name: input.name.trim(),
+    kind,
+    command: input.command,
+    args: stringArray(input.args, 'args'),
+    env,
+    cwd: typeof input.cwd === 'string' && input.cwd ? input.cwd : '${workspaceFolder}',
+    // A task from .forge/tasks.toml (or another configuration's name) to run first
+    preLaunchTask: typeof input.preLaunchTask === 'string' && input.preLaunchTask ? input.preLaunchTask : null,
+    // Debugger settings carried over from launch.json (type, request, port, ...)
+    debug: input.debug && typeof input.debug === 'object' ? input.debug : null
+  };
+}
+
+function listRunConfigs(rootPath) {
+  return Object.values(workspaceConfigs(rootPath)).sort((a, b) => a.name.localeCompare(b.name));
+}
+
+function getRunConfig(rootPath, id) {
+  const config = workspaceConfigs(rootPath)[id];
+  if (!config) throw new ForgeError(ErrorCode.NOT_FOUND, `Run configuration ${id} not found`);
+  return config;
+}
+
+function createRunConfig(rootPath, input, source = 'user') {
+  const now = new Date().toISOString();
+  const config = { id: `run_config_${uuidv4()}`, ...validateConfig(input), source, createdAt: now, updatedAt: now };
+  saveWorkspaceConfigs(rootPath, { ...workspaceConfigs(rootPath), [config.id]: config });
+  return config;
+}
+
+function updateRunConfig(rootPath, id, changes) {
+  const existing = getRunConfig(rootPath, id);
+  const config = { ...existing, ...validateConfig({ ...existing, ...changes }), updatedAt: new Date().toISOString() };
+  saveWorkspaceConfigs(rootPath, { ...workspaceConfigs(rootPath), [id]: config });
+  return config;
+}
+
+function deleteRunConfig(rootPath, id) {
+  getRunConfig(rootPath, id);
+  const configs = { ...workspaceConfigs(rootPath) };
+  delete configs[id];
+  saveWorkspaceConfigs(rootPath, configs);
+  return true;
+}
+
+function envStrings(env) {
+  return Object.fromEntries(Object.entries(env || {}).map(([key, value]) => [key, String(value)]));
+}
+
+// One launch.json configuration -> run configuration input, or a reason it can't be
+function fromLaunchConfig(entry) {
+  const runtime = entry.runtimeExecutable || DEBUG_RUNTIMES[entry.type];
+  let command;
+  let args;
+  if (entry.request === 'attach') return { skipped: 'attach configurations need a running debuggee' };
+  if (entry.program && runtime) {
+    command = runtime;
+    args = [...stringArray(entry.runtimeArgs, 'runtimeArgs'), entry.program, ...stringArray(entry.args, 'args')];
+  } else if (entry.program) {
+    command = entry.program;
+    args = stringArray(entry.args, 'args');
+  } else if (entry.module && runtime) {
+    command = runtime;
+    args = ['-m', entry.module, ...stringArray(entry.args, 'args')];
+  } else if (entry.runtimeExecutable) {
+    command = entry.runtimeExecutable;
+    args = [...stringArray(entry.runtimeArgs, 'runtimeArgs'), ...stringArray(entry.args, 'args')];
+  } else {
+    return { skipped: `no program to launch for debugger type ${entry.type}` };
+  }
+  const debug = { ...entry };
+  for (const key of LAUNCH_FIELDS) delete debug[key];
+  return {
+    input: {
+      name: entry.name,
+      kind: 'debug',
+      command,
+      args,
+      env: envStrings(entry.env),
+      cwd: entry.cwd,
+      preLaunchTask: typeof entry.preLaunchTask === 'string' ? entry.preLaunchTask : null,
+      debug
+    }
+  };
+}
+
+// One tasks.json task -> run configuration input. Only the first dependency becomes the
+// pre-launch task; ordered multi-step chains belong in .forge/tasks.toml pipelines.
+function fromVSCodeTask(task) {
+  const platform = { win32: task.windows, darwin: task.osx, linux: task.linux }[process.platform];
+  const merged = { ...task, ...(platform || {}) };
+  if (typeof merged.command !== 'string' || !merged.command) {
+    return { skipped: merged.type === 'npm' ? 'npm script tasks are not supported' : 'no command' };
+  }
+  const args = (merged.args || []).map((arg) => (typeof arg === 'string' ? arg : arg.value));
+  const options = merged.options || {};
+  const dependsOn = Array.isArray(merged.dependsOn) ? merged.dependsOn[0] : merged.dependsOn;
+  return {
+    input: {
+      name: merged.label || merged.taskName,
+      kind: 'run',
+      command: merged.command,
+      args,
+      env: envStrings(options.env),
+      cwd: options.cwd,
+      preLaunchTask: typeof dependsOn === 'string' ? dependsOn : null
+    }
+  };
+}
+
+async function readJsoncFile(filePath) {
+  try {
+    return parseJsonc(await fs.readFile(filePath, 'utf-8'));
+  } catch (error) {
+    if (error.code === 'ENOENT') return null;
+    if (error instanceof SyntaxError) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} is not valid JSON: ${error.message}`);
+    }
+    throw ForgeError.from(error, `Could not read ${filePath}`);
+  }
+}
+
+// Import .vscode/launch.json and .vscode/tasks.json. Re-importing replaces what the
+// previous import created under the same name; configurations made in Forge are kept.
+async function importVSCodeConfigs(rootPath) {
+  const vscodeDir = path.join(rootPath, '.vscode');
+  const launch = await readJsoncFile(path.join(vscodeDir, 'launch.json'));
+  const tasks = await readJsoncFile(path.join(vscodeDir, 'tasks.json'));
+  if (!launch && !tasks) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, 'No .vscode/launch.json or .vscode/tasks.json in this workspace');
+  }
+
+  const convert = (source, name, fn, item) => {
+    try {
+      return { source, name, ...fn(item) };
+    } catch (error) {
+      return { source, name, skipped: error.message };
+    }
+  };
+  const candidates = [
+    ...((tasks && tasks.tasks) || []).map((task) => convert('tasks.json', task.label || task.taskName, fromVSCodeTask, task)),
+    ...((launch && launch.configurations) || []).map((entry) => convert('launch.json', entry.name, fromLaunchConfig, entry))
+  ];
+
+  const configs = { ...workspaceConfigs(rootPath) };
+  const imported = [];
+  const skipped = [];
+  for (const candidate of candidates) {
+    if (candidate.skipped) {
+      skipped.push({ name: candidate.name || '(unnamed)', source: candidate.source, reason: candidate.skipped });
+      continue;
+    }
+    let config;
+    try {
+      config = validateConfig(candidate.input);
+    } catch (error) {
+      skipped.push({ name: candidate.name || '(unnamed)', source: candidate.source, reason: error.message });
+      continue;
+    }
+    const previous = Object.values(configs).find((existing) => existing.source === candidate.source && existing.name === config.name);
+    const now = new Date().toISOString();
+    const entry = previous
+      ? { ...previous, ...config, updatedAt: now }
+      : { id: `run_config_${uuidv4()}`, ...config, source: candidate.source, createdAt: now, updatedAt: now };
+    configs[entry.id] = entry;
+    imported.push(entry);
+  }
+  saveWorkspaceConfigs(rootPath, configs);
+  log.info(`Imported ${imported.length} run configuration(s) into ${rootPath}, skipped ${skipped.length}`);
+  return { imported, skipped };
+}
+
+function workspaceOf(sender) {
+  const context = windowManager.fromSender(sender);
+  if (!context || !context.workspacePath) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to manage run configurations');
+  }
+  return context.workspacePath;
+}
+
+function setupRunConfigHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('runConfigs:list', async (event) => listRunConfigs(workspaceOf(event.sender)));
+
+  ipcMain.handle('runConfigs:get', async (event, id) => getRunConfig(workspaceOf(event.sender), id));
+
+  ipcMain.handle('runConfigs:create', async (event, input) => createRunConfig(workspaceOf(event.sender), input));
+
+  ipcMain.handle('runConfigs:update', async (event, id, changes) => updateRunConfig(workspaceOf(event.sender), id, changes));
+
+  ipcMain.handle('runConfigs:delete', async (event, id) => deleteRunConfig(workspaceOf(event.sender), id));
+
+  ipcMain.handle('runConfigs:importVSCode', async (event) => importVSCodeConfigs(workspaceOf(event.sender)));
+}
+
+module.exports = { setupRunConfigHandlers, listRunConfigs, getRunConfig, importVSCodeConfigs };
@@ -0,0 +1,337 @@
+const path = require('path');
+const { spawn } = require('child_process');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { getRestrictions } = require('./trust');
+const { applyEditorConfigOnSave, globToRegExp } = require('./editorconfig');
+const { anchorPattern } = require('./indexScope');
+const { ProblemCollector, listMatchers } = require('./problemMatchers');
+const { listProblems, setProblems } = require('./problems');
+
+const log = createLogger('save-hooks');
+
+const PHASES = ['pre', 'post'];
+// Pre-save hooks hold up the write, so they get less time than post-save ones
+const DEFAULT_TIMEOUTS = { pre: 5000, post: 30000 };
+const MAX_TIMEOUT_MS = 5 * 60 * 1000;
+const MAX_TOOL_OUTPUT = 10 * 1024 * 1024;
+const TIMED_OUT = Symbol('timedOut');
+
+const store = new JsonStore('save-hooks', {
+  // Hook id -> { enabled?, timeoutMs? }, overriding the hook's defaults
+  hooks: {},
+  // [{ pattern, command, args }]: the first whose pattern matches formats the file
+  formatters: [],
+  // [{ name, pattern, command, args, matchers }]: every match runs after the save
+  linters: []
+});
+
+// Registered hooks keyed by id
+const hooks = new Map();
+
+// Browser views, for live reload
+let browserViews = new Map();
+
+// Register a hook that runs before ('pre') or after ('post') files are saved, lowest
+// `order` first. `run(context)` gets { filePath, content, workspacePath, sender, signal,
+// skip(reason) }; a pre-save hook returns the content to write (or nothing to leave it
+// as is). `signal` aborts when the hook runs past its timeout. Returns an unregister
+// function.
+function registerSaveHook(hook) {
+  if (!hook || !hook.id || !PHASES.includes(hook.phase) || typeof hook.run !== 'function') {
+    throw new Error('Save hook requires an id, a phase (pre or post) and a run function');
+  }
+  if (hooks.has(hook.id)) {
+    throw new Error(`Save hook ${hook.id} is already registered`);
+  }
+
+  hooks.set(hook.id, {
+    id: hook.id,
+    title: hook.title || hook.id,
+    phase: hook.phase,
+    order: Number.isFinite(hook.order) ? hook.order : 500,
+    timeoutMs: hook.timeoutMs || DEFAULT_TIMEOUTS[hook.phase],
+    enabled: hook.enabled !== false,
+    source: hook.source || 'core',
+    run: hook.run
+  });
+
+  return () => unregisterSaveHook(hook.id);
+}
+
+function unregisterSaveHook(id) {
+  return hooks.delete(id);
+}
+
+// A hook with the user's overrides applied
+function effective(hook) {
+  const overrides = store.get('hooks')[hook.id] || {};
+  return {
+    ...hook,
+    enabled: overrides.enabled === undefined ? hook.enabled : overrides.enabled,
+    timeoutMs: overrides.timeoutMs || hook.timeoutMs
+  };
+}
+
+function listSaveHooks() {
+  return Array.from(hooks.values(), effective)
+    .sort((a, b) => a.phase.localeCompare(b.phase) || a.order - b.order)
+    .map(({ id, title, phase, order, timeoutMs, enabled, source }) => ({ id, title, phase, order, timeoutMs, enabled, source }));
+}
+
+function configureSaveHook(id, { enabled, timeoutMs } = {}) {
+  if (!hooks.has(id)) throw new ForgeError(ErrorCode.NOT_FOUND, `Save hook ${id} not found`, { details: { id } });
+  if (enabled !== undefined && typeof enabled !== 'boolean') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'enabled must be a boolean');
+  }
+  if (timeoutMs !== undefined && timeoutMs !== null && !(Number.isInteger(timeoutMs) && timeoutMs > 0 && timeoutMs <= MAX_TIMEOUT_MS)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Timeouts must be between 1 and ${MAX_TIMEOUT_MS} ms`);
+  }
+  const overrides = { ...store.get('hooks') };
+  const next = { ...overrides[id] };
+  if (enabled !== undefined) next.enabled = enabled;
+  // null goes back to the hook's default
+  if (timeoutMs === null) delete next.timeoutMs;
+  else if (timeoutMs !== undefined) next.timeoutMs = timeoutMs;
+  overrides[id] = next;
+  store.set('hooks', overrides);
+  return listSaveHooks().find((hook) => hook.id === id);
+}
+
+// Run one hook under its timeout. Resolves { report, value }; failures are reported,
+// never thrown, so one broken hook can't block a save.
+async function runHook(hook, context) {
+  const controller = new AbortController();
+  const startedAt = Date.now();
+  let skipped = null;
+  let timer;
+  const timeout = new Promise((resolve) => {
+    timer = setTimeout(() => {
+      controller.abort();
+      resolve(TIMED_OUT);
+    }, hook.timeoutMs);
+  });
+  const report = (status, message = null) => ({ id: hook.id, phase: hook.phase, status, message, durationMs: Date.now() - startedAt });
+
+  try {
+    const value = await Promise.race([
+      Promise.resolve().then(() => hook.run({
+        ...context,
+        signal: controller.signal,
+        skip: (reason) => { skipped = reason || 'Skipped'; }
+      })),
+      timeout
+    ]);
+    if (value === TIMED_OUT) {
+      log.warn(`Save hook ${hook.id} timed out after ${hook.timeoutMs}ms on ${context.filePath}`);
+      return { report: report('timedOut', `Timed out after ${hook.timeoutMs}ms`) };
+    }
+    if (skipped) return { report: report('skipped', skipped) };
+    return { report: report('ok'), value };
+  } catch (error) {
+    log.warn(`Save hook ${hook.id} failed on ${context.filePath}: ${error.message}`);
+    return { report: report('failed', error.message) };
+  } finally {
+    clearTimeout(timer);
+  }
+}
+
+function enabledHooks(phase) {
+  return Array.from(hooks.values(), effective)
+    .filter((hook) => hook.phase === phase && hook.enabled)
+    .sort((a, b) => a.order - b.order);
+}
+
+// Save `content` to `filePath` through the pipeline: pre-save hooks transform the content
+// in order, `write(content)` stores it, and post-save hooks run afterwards without holding
+// up the caller. Resolves { path, content, changed, hooks, result } where `content` is what
+// was written and `result` what `write` returned. Once post-save hooks finish, the saving
+// window gets saveHooks:completed with every hook's report.
+async function saveWithHooks(filePath, content, write, { sender = null } = {}) {
+  const windowContext = sender ? windowManager.fromSender(sender) : null;
+  const base = { filePath, workspacePath: windowContext ? windowContext.workspacePath : null, sender };
+
+  let current = content;
+  const reports = [];
+  for (const hook of enabledHooks('pre')) {
+    const { report, value } = await runHook(hook, { ...base, content: current });
+    if (typeof value === 'string' && value !== current) {
+      current = value;
+      report.status = 'changed';
+    }
+    reports.push(report);
+  }
+
+  const result = await write(current);
+
+  const post = enabledHooks('post');
+  Promise.all(post.map((hook) => runHook(hook, { ...base, content: current })))
+    .then((outcomes) => {
+      if (!sender || sender.isDestroyed()) return;
+      emit('saveHooks:completed', { path: filePath, hooks: [...reports, ...outcomes.map(({ report }) => report)] }, { target: sender });
+    });
+
+  return { path: filePath, content: current, changed: current !== content, hooks: reports, result };
+}
+
+// Whether a tool's glob matches the file, relative to the workspace when it's inside one
+function toolMatches(tool, filePath, workspacePath) {
+  const inside = workspacePath && !path.relative(workspacePath, filePath).startsWith('..');
+  const relative = inside ? path.relative(workspacePath, filePath) : path.basename(filePath);
+  return globToRegExp(anchorPattern(tool.pattern)).test(relative.split(path.sep).join('/'));
+}
+
+// Formatters and linters come from the user's settings, but they load configuration and
+// plugins from the project; don't run them in an untrusted workspace
+function toolsAllowed(context) {
+  if (context.workspacePath && !getRestrictions(context.workspacePath).trusted) {
+    context.skip('Workspace is not trusted');
+    return false;
+  }
+  return true;
+}
+
+// Run a formatter or linter on the file, feeding `input` on stdin. Resolves
+// { code, stdout, stderr }.
+function runTool(tool, { filePath, workspacePath, input = null, signal }) {
+  const expand = (arg) => arg.replace(/\$\{file\}/g, filePath).replace(/\$\{workspaceFolder\}/g, workspacePath || path.dirname(filePath));
+  return new Promise((resolve, reject) => {
+    const child = spawn(tool.command, (tool.args || []).map(expand), {
+      cwd: workspacePath || path.dirname(filePath),
+      signal,
+      // npm-installed tools are .cmd shims on Windows
+      shell: process.platform === 'win32',
+      windowsHide: true,
+      stdio: [input === null ? 'ignore' : 'pipe', 'pipe', 'pipe']
+    });
+    const output = { stdout: '', stderr: '' };
+    for (const stream of ['stdout', 'stderr']) {
+      child[stream].setEncoding('utf-8');
+      child[stream].on('data', (chunk) => {
+        if (output[stream].length < MAX_TOOL_OUTPUT) output[stream] += chunk;
+      });
+    }
+    child.on('error', (error) => reject(error.code === 'ENOENT' ? new Error(`${tool.command} was not found on PATH`) : error));
+    child.on('close', (code) => resolve({ code, ...output }));
+    if (input !== null) {
+      child.stdin.on('error', () => {});
+      child.stdin.end(input);
+    }
+  });
+}
+
+async function formatOnSave(context) {
+  const formatter = store.get('formatters').find((tool) => toolMatches(tool, context.filePath, context.workspacePath));
+  if (!formatter) return context.skip('No formatter for this file');
+  if (!toolsAllowed(context)) return undefined;
+  const { code, stdout, stderr } = await runTool(formatter, { ...context, input: context.content });
+  if (code !== 0) {
+    throw new Error(`${formatter.command} exited with code ${code}${stderr.trim() ? `: ${stderr.trim().split('\n')[0]}` : ''}`);
+  }
+  // Some formatters print nothing when there's nothing to change
+  return stdout || context.content;
+}
+
+// Each linter's problems for the saved file replace its earlier ones for that file
+async function lintOnSave(context) {
+  const linters = store.get('linters').filter((tool) => toolMatches(tool, context.filePath, context.workspacePath));
+  if (linters.length === 0) return context.skip('No linter for this file');
+  if (!toolsAllowed(context)) return;
+  await Promise.all(linters.map(async (linter) => {
+    const found = [];
+    const cwd = context.workspacePath || path.dirname(context.filePath);
+    const collectors = ['stdout', 'stderr'].map(() => new ProblemCollector(linter.matchers, { cwd, onProblem: (problem) => found.push(problem) }));
+    const { stdout, stderr } = await runTool(linter, context);
+    [stdout, stderr].forEach((text, index) => {
+      collectors[index].push(text);
+      collectors[index].flush();
+    });
+    const source = `lint:${linter.name}`;
+    const others = listProblems({ source }).filter((problem) => problem.file !== context.filePath);
+    setProblems(source, [...others, ...found]);
+  }));
+}
+
+// Reload browser views previewing local content: a dev server on this machine or a file
+function liveReload() {
+  let reloaded = 0;
+  for (const view of browserViews.values()) {
+    if (view.webContents.isDestroyed()) continue;
+    let url;
+    try {
+      url = new URL(view.webContents.getURL());
+    } catch {
+      continue;
+    }
+    if (url.protocol === 'file:' || ['localhost', '127.0.0.1', '[::1]'].includes(url.hostname)) {
+      view.webContents.reload();
+      reloaded++;
+    }
+  }
+  if (reloaded > 0) log.debug(`Live-reloaded ${reloaded} browser view(s)`);
+}
+
+registerSaveHook({
+  id: 'editorconfig',
+  title: 'Trim trailing whitespace and fix the final newline (.editorconfig)',
+  phase: 'pre',
+  order: 100,
+  timeoutMs: 2000,
+  run: ({ filePath, content }) => applyEditorConfigOnSave(filePath, content)
+});
+
+registerSaveHook({ id: 'format', title: 'Format on save', phase: 'pre', order: 200, run: formatOnSave });
+
+registerSaveHook({ id: 'lint', title: 'Lint on save', phase: 'post', order: 100, run: lintOnSave });
+
+registerSaveHook({ id: 'liveReload', title: 'Reload local browser previews', phase: 'post', order: 900, enabled: false, run: liveReload });
+
+function validateTools(list, kind) {
+  if (!Array.isArray(list)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${kind} must be a list`);
+  const matcherIds = new Set(listMatchers().map((matcher) => matcher.id));
+  const names = new Set();
+  return list.map((tool) => {
+    if (!tool || typeof tool.pattern !== 'string' || !tool.pattern || typeof tool.command !== 'string' || !tool.command) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Each of the ${kind} needs a pattern and a command`);
+    }
+    const args = tool.args || [];
+    if (!Array.isArray(args) || !args.every((arg) => typeof arg === 'string')) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Arguments for ${tool.command} must be strings`);
+    }
+    if (kind === 'formatters') return { pattern: tool.pattern, command: tool.command, args };
+
+    if (typeof tool.name !== 'string' || !tool.name || names.has(tool.name)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Each linter needs a unique name');
+    }
+    names.add(tool.name);
+    const matchers = Array.isArray(tool.matchers) ? tool.matchers : [];
+    const unknown = matchers.filter((id) => !matcherIds.has(id));
+    if (matchers.length === 0 || unknown.length > 0) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Linter ${tool.name} needs known problem matchers`, { details: { unknown } });
+    }
+    return { name: tool.name, pattern: tool.pattern, command: tool.command, args, matchers };
+  });
+}
+
+function setupSaveHookHandlers(ipcMain, views) {
+  ipcMain = withForgeErrors(ipcMain);
+  browserViews = views;
+
+  ipcMain.handle('saveHooks:list', async () => listSaveHooks());
+
+  // { enabled?, timeoutMs? }; a null timeout restores the default
+  ipcMain.handle('saveHooks:configure', async (event, id, options) => configureSaveHook(id, options));
+
+  ipcMain.handle('saveHooks:getTools', async () => ({ formatters: store.get('formatters'), linters: store.get('linters') }));
+
+  ipcMain.handle('saveHooks:setTools', async (event, { formatters, linters } = {}) => {
+    if (formatters !== undefined) store.set('formatters', validateTools(formatters, 'formatters'));
+    if (linters !== undefined) store.set('linters', validateTools(linters, 'linters'));
+    return { formatters: store.get('formatters'), linters: store.get('linters') };
+  });
+}
+
+module.exports = { setupSaveHookHandlers, registerSaveHook, unregisterSaveHook, saveWithHooks, listSaveHooks };
@@ -0,0 +1,289 @@
+const fs = require('fs').promises;
+const fsSync = require('fs');
+const os = require('os');
+const path = require('path');
+const zlib = require('zlib');
+const { spawn } = require('child_process');
+const { v4: uuidv4 } = require('uuid');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { setProblems, listProblems } = require('./problems');
+const { loadProtoSchema, describeProtoSchema, decodeProto, decodeProtoRaw, splitFrames, WireError } = require('./protobuf');
+const { loadFbsSchema, describeFbsSchema, decodeFlatBuffer, splitSizePrefixed, BufferError } = require('./flatbuffers');
+
+const log = createLogger('schemas');
+
+const TOOL_TIMEOUT = 60000;
+const MAX_PAYLOAD_SIZE = 64 * 1024 * 1024;
+const MAX_CACHED_SCHEMAS = 20;
+const KINDS = { '.proto': 'protobuf', '.fbs': 'flatbuffers' };
+const TOOLS = { protobuf: 'protoc', flatbuffers: 'flatc' };
+const INSTALL_HINTS = { protoc: 'install the Protocol Buffers compiler', flatc: 'install the FlatBuffers compiler' };
+
+// `${kind}:${file}:${includePaths}` -> { stamps, schema }, reused while no loaded file changes
+const schemas = new Map();
+// Root schema file -> files its last compile reported problems for, replaced on the next compile
+const reported = new Map();
+
+function schemaKind(filePath) {
+  const kind = KINDS[path.extname(filePath).toLowerCase()];
+  if (!kind) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} is not a .proto or .fbs schema`);
+  return kind;
+}
+
+async function stillCurrent(stamps) {
+  for (const [file, mtimeMs] of stamps) {
+    const stat = await fs.stat(file).catch(() => null);
+    if (!stat || stat.mtimeMs !== mtimeMs) return false;
+  }
+  return true;
+}
+
+async function loadSchema(filePath, includePaths, workspacePath) {
+  const kind = schemaKind(filePath);
+  const key = `${kind}:${filePath}:${includePaths.join(path.delimiter)}`;
+  const cached = schemas.get(key);
+  if (cached && (await stillCurrent(cached.stamps))) {
+    schemas.delete(key);
+    schemas.set(key, cached);
+    return { kind, schema: cached.schema };
+  }
+  let schema;
+  try {
+    schema = kind === 'protobuf'
+      ? await loadProtoSchema(filePath, { includePaths, stopAt: workspacePath })
+      : await loadFbsSchema(filePath, { includePaths });
+  } catch (error) {
+    throw ForgeError.from(error, `Could not read ${filePath}`);
+  }
+  schemas.delete(key);
+  schemas.set(key, { stamps: schema.stamps, schema });
+  if (schemas.size > MAX_CACHED_SCHEMAS) schemas.delete(schemas.keys().next().value);
+  log.debug(`Loaded ${kind} schema ${filePath} (${schema.files.size} files, ${schema.problems.length} problems)`);
+  return { kind, schema };
+}
+
+// Messages, enums and services (or tables, unions and root_type) declared in the file,
+// plus every type the decoder can target once imports are resolved
+async function parseSchema(filePath, { includePaths = [], workspacePath = null } = {}) {
+  const { kind, schema } = await loadSchema(filePath, includePaths, workspacePath);
+  const summary = kind === 'protobuf' ? describeProtoSchema(schema) : describeFbsSchema(schema);
+  return { kind, file: filePath, ...summary, problems: schema.problems };
+}
+
+async function readPayload(payload) {
+  if (payload instanceof Uint8Array) return Buffer.from(payload.buffer, payload.byteOffset, payload.byteLength);
+  if (!payload || typeof payload !== 'object') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A payload needs bytes, a file path, base64 or hex');
+  if (typeof payload.path === 'string') {
+    let stat;
+    try {
+      stat = await fs.stat(payload.path);
+    } catch (error) {
+      throw ForgeError.from(error, `Could not read ${payload.path}`);
+    }
+    if (stat.size > MAX_PAYLOAD_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Payloads over ${MAX_PAYLOAD_SIZE / 1024 / 1024} MB can't be decoded`);
+    return fs.readFile(payload.path);
+  }
+  if (typeof payload.base64 === 'string') return Buffer.from(payload.base64.replace(/\s+/g, ''), 'base64');
+  if (typeof payload.hex === 'string') {
+    const hex = payload.hex.replace(/\s+|0x|,/gi, '');
+    if (!/^([0-9a-f]{2})*$/i.test(hex)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'The hex payload has an odd number of digits or a non-hex character');
+    return Buffer.from(hex, 'hex');
+  }
+  throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A payload needs bytes, a file path, base64 or hex');
+}
+
+function malformed(error, frame, count) {
+  if (!(error instanceof WireError) && !(error instanceof BufferError)) return error;
+  const where = count > 1 ? ` (message ${frame + 1} of ${count})` : '';
+  return new ForgeError(ErrorCode.INVALID_ARGUMENT, `${error.message}${where}`, { details: { frame } });
+}
+
+// Decodes a binary payload against a message (or table) type into JSON. `framing` splits
+// a stream first: 'grpc' or 'delimited' for protobuf, 'size-prefixed' for FlatBuffers.
+async function decodePayload(filePath, typeName, payload, { includePaths = [], workspacePath = null, framing = 'none', preserveNames = false } = {}) {
+  const { kind, schema } = await loadSchema(filePath, includePaths, workspacePath);
+  const buffer = await readPayload(payload);
+  const messages = [];
+  let frames;
+  try {
+    frames = kind === 'protobuf' ? splitFrames(buffer, framing) : framing === 'size-prefixed' ? splitSizePrefixed(buffer) : [{ bytes: buffer }];
+  } catch (error) {
+    throw malformed(error, 0, 1);
+  }
+  let resolvedName = null;
+  let fileIdentifier = null;
+  for (const [index, frame] of frames.entries()) {
+    let bytes = frame.bytes;
+    if (frame.compressed) {
+      try {
+        bytes = zlib.gunzipSync(bytes);
+      } catch {
+        throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Message ${index + 1} is compressed with something other than gzip`);
+      }
+    }
+    try {
+      const decoded = kind === 'protobuf' ? decodeProto(schema, typeName, bytes, { preserveNames }) : decodeFlatBuffer(schema, typeName, bytes);
+      resolvedName = decoded.typeName;
+      if (decoded.fileIdentifier !== undefined) fileIdentifier = decoded.fileIdentifier;
+      messages.push(decoded.message);
+    } catch (error) {
+      throw malformed(error, index, frames.length);
+    }
+  }
+  const result = { kind, typeName: resolvedName || typeName, framing, messages };
+  if (kind === 'flatbuffers') result.fileIdentifier = fileIdentifier;
+  return result;
+}
+
+// Field numbers and best-guess values without a schema, like `protoc --decode_raw`
+async function decodeRawPayload(payload, { framing = 'none' } = {}) {
+  const buffer = await readPayload(payload);
+  let frames;
+  try {
+    frames = splitFrames(buffer, framing);
+  } catch (error) {
+    throw malformed(error, 0, 1);
+  }
+  return {
+    kind: 'protobuf',
+    typeName: null,
+    framing,
+    messages: frames.map((frame, index) => {
+      try {
+        return decodeProtoRaw(frame.compressed ? zlib.gunzipSync(frame.bytes) : frame.bytes);
+      } catch (error) {
+        throw malformed(error, index, frames.length);
+      }
+    })
+  };
+}
+
+function runTool(tool, args) {
+  return new Promise((resolve, reject) => {
+    const child = spawn(tool, args, { windowsHide: true, timeout: TOOL_TIMEOUT });
+    let output = '';
+    child.stdout.on('data', (chunk) => { output += chunk; });
+    child.stderr.on('data', (chunk) => { output += chunk; });
+    child.on('error', (error) => {
+      reject(error.code === 'ENOENT'
+        ? new ForgeError(ErrorCode.NOT_FOUND, `${tool} was not found on PATH; ${INSTALL_HINTS[tool]} to compile schemas`, { recoverable: false })
+        : error);
+    });
+    child.on('close', (code, signal) => {
+      if (signal === 'SIGTERM') reject(new ForgeError(ErrorCode.TIMEOUT, `${tool} did not finish within ${TOOL_TIMEOUT / 1000}s`));
+      else resolve({ code, output });
+    });
+  });
+}
+
+// protoc: `dir/file.proto:12:5: message`, `file.proto: warning: message` or `file.proto: message`
+// flatc:  `error: /dir/file.fbs:12: 5: error: message` or `flatc: error: file.fbs(12, 5): message`
+function parseDiagnostics(tool, output, roots, fallbackFile) {
+  const problems = [];
+  const locate = (file) => {
+    if (path.isAbsolute(file)) return file;
+    return roots.map((root) => path.resolve(root, file)).find((candidate) => fsSync.existsSync(candidate)) || path.resolve(roots[roots.length - 1], file);
+  };
+  for (const line of output.split(/\r?\n/)) {
+    if (!line.trim()) continue;
+    let match;
+    if (tool === 'protoc' && (match = /^(.+?\.proto):(\d+):(\d+):\s*(?:(warning|error):\s*)?(.*)$/.exec(line))) {
+      problems.push({ file: locate(match[1]), line: Number(match[2]), column: Number(match[3]), severity: match[4] || 'error', message: match[5] });
+    } else if (tool === 'protoc' && (match = /^(.+?\.proto):\s*(?:(warning|error):\s*)?(.*)$/.exec(line))) {
+      problems.push({ file: locate(match[1]), line: 1, column: 1, severity: match[2] || 'error', message: match[3] });
+    } else if (tool === 'flatc' && (match = /^(?:flatc: )?(?:(?:error|warning): )?(.+?\.fbs)(?::(\d+):\s*(\d+)|\((\d+),\s*(\d+)\)):\s*(?:(error|warning):\s*)?(.*)$/.exec(line))) {
+      // flatc columns are 0-based
+      problems.push({ file: locate(match[1]), line: Number(match[2] || match[4]), column: Number(match[3] || match[5]) + 1, severity: match[6] || 'error', message: match[7] });
+    } else if (/error|warning/i.test(line)) {
+      problems.push({ file: fallbackFile, line: 1, column: 1, severity: /warning/i.test(line) && !/error/i.test(line) ? 'warning' : 'error', message: line.trim() });
+    }
+  }
+  return problems;
+}
+
+function publishProblems(tool, rootFile, problems) {
+  const previous = reported.get(rootFile) || new Set();
+  const files = new Set(problems.map((problem) => problem.file));
+  const others = listProblems({ source: tool }).filter((problem) => !previous.has(problem.file) && !files.has(problem.file));
+  reported.set(rootFile, files);
+  setProblems(tool, [...others, ...problems.map((problem) => ({ ...problem, endLine: null, endColumn: null, code: null }))]);
+}
+
+// Compiles with protoc or flatc. With a `language` (a protoc `--<language>_out` or flatc
+// `--<language>` generator) code is written to `outputDir`; without one the schema is
+// only checked. Diagnostics go to the problems panel under the tool's name.
+async function compileSchema(filePath, { includePaths = [], workspacePath = null, language = null, outputDir = null } = {}) {
+  const kind = schemaKind(filePath);
+  const tool = TOOLS[kind];
+  if (language !== null && !/^[a-z][a-z0-9_]*$/.test(String(language))) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${language} is not a generator name`);
+  if (language && !outputDir) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Generating code needs an output directory');
+  await fs.access(filePath).catch((error) => {
+    throw ForgeError.from(error, `Could not read ${filePath}`);
+  });
+
+  // The schema's own directory goes last so include paths take precedence, as with -I;
+  // .proto imports are usually written relative to the workspace root
+  const inWorkspace = workspacePath && !path.relative(workspacePath, filePath).startsWith('..');
+  const roots = Array.from(new Set([...includePaths, ...(kind === 'protobuf' && inWorkspace ? [workspacePath] : []), path.dirname(filePath)]));
+  const scratch = path.join(os.tmpdir(), `forge-schema-${uuidv4()}`);
+  let args;
+  if (kind === 'protobuf') {
+    args = [...roots.map((root) => `-I${root}`), language ? `--${language}_out=${outputDir}` : `--descriptor_set_out=${path.join(scratch, 'out.pb')}`, filePath];
+  } else {
+    args = [...roots.flatMap((root) => ['-I', root]), ...(language ? [`--${language}`, '-o', outputDir] : ['--schema', '-b', '-o', scratch]), filePath];
+  }
+
+  const started = Date.now();
+  let result;
+  try {
+    await fs.mkdir(language ? outputDir : scratch, { recursive: true });
+    result = await runTool(tool, args);
+  } finally {
+    await fs.rm(scratch, { recursive: true, force: true });
+  }
+  const problems = parseDiagnostics(tool, result.output, roots, filePath);
+  // A failed run always shows up, even when its output didn't parse
+  if (result.code !== 0 && !problems.some((problem) => problem.severity === 'error')) {
+    problems.push({ file: filePath, line: 1, column: 1, severity: 'error', message: result.output.trim().split('\n').pop() || `${tool} exited with code ${result.code}` });
+  }
+  publishProblems(tool, filePath, problems);
+  log.info(`${tool} ${language ? `--${language}` : 'check'} of ${filePath} exited with code ${result.code} after ${Date.now() - started}ms`);
+  return { tool, language, outputDir: language ? outputDir : null, success: result.code === 0, problems, output: result.output.trim() };
+}
+
+function resolvePath(sender, filePath) {
+  if (typeof filePath !== 'string' || !filePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A file path is required');
+  const context = windowManager.fromSender(sender);
+  return context && context.workspacePath ? path.resolve(context.workspacePath, filePath) : path.resolve(filePath);
+}
+
+function resolveOptions(sender, options = {}) {
+  const context = windowManager.fromSender(sender);
+  return {
+    ...options,
+    // Where .proto import lookups stop climbing
+    workspacePath: context ? context.workspacePath : null,
+    includePaths: (Array.isArray(options.includePaths) ? options.includePaths : []).map((dir) => resolvePath(sender, dir)),
+    outputDir: options.outputDir ? resolvePath(sender, options.outputDir) : null
+  };
+}
+
+function setupSchemaHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('schemas:parse', async (event, filePath, options) => parseSchema(resolvePath(event.sender, filePath), resolveOptions(event.sender, options)));
+  ipcMain.handle('schemas:compile', async (event, filePath, options) => compileSchema(resolvePath(event.sender, filePath), resolveOptions(event.sender, options)));
+  ipcMain.handle('schemas:decode', async (event, filePath, typeName, payload, options) => {
+    const resolved = resolveOptions(event.sender, options);
+    const source = payload && typeof payload.path === 'string' ? { path: resolvePath(event.sender, payload.path) } : payload;
+    return decodePayload(resolvePath(event.sender, filePath), typeName, source, resolved);
+  });
+  ipcMain.handle('schemas:decodeRaw', async (event, payload, options) => {
+    const source = payload && typeof payload.path === 'string' ? { path: resolvePath(event.sender, payload.path) } : payload;
+    return decodeRawPayload(source, options || {});
+  });
+}
+
+module.exports = { setupSchemaHandlers, parseSchema, compileSchema, decodePayload, decodeRawPayload };
@@ -0,0 +1,142 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob } = require('./jobs');
+const { isPathIgnored } = require('./indexScope');
+
+const log = createLogger('search');
+
+// Directories never worth searching
+const DEFAULT_EXCLUDES = ['node_modules', '.git', '.hg', '.svn', 'dist', 'build', 'out', 'target', '.next', '.cache', 'coverage'];
+const MAX_FILE_SIZE = 2 * 1024 * 1024;
+const DEFAULT_MAX_RESULTS = 5000;
+
+// Walk a workspace yielding absolute file paths, skipping excluded directories.
+// `options.ignored(path, 'file' | 'directory')` can prune further (see indexScope).
+async function* walkWorkspace(rootPath, options = {}) {
+  const excludes = new Set(options.excludes || DEFAULT_EXCLUDES);
+  const pending = [rootPath];
+  while (pending.length > 0) {
+    if (options.signal && options.signal.aborted) return;
+    const dir = pending.pop();
+    let entries;
+    try {
+      entries = await fs.readdir(dir, { withFileTypes: true });
+    } catch (error) {
+      log.debug(`Skipping unreadable directory ${dir}: ${error.code}`);
+      continue;
+    }
+    for (const entry of entries) {
+      if (excludes.has(entry.name)) continue;
+      const entryPath = path.join(dir, entry.name);
+      if (options.ignored && options.ignored(entryPath, entry.isDirectory() ? 'directory' : 'file')) continue;
+      if (entry.isDirectory()) {
+        pending.push(entryPath);
+      } else if (entry.isFile()) {
+        yield entryPath;
+      }
+    }
+  }
+}
+
+// Read a file as text, or null for large or binary files
+async function readTextFile(filePath) {
+  try {
+    const stats = await fs.stat(filePath);
+    if (stats.size > MAX_FILE_SIZE) return null;
+    const buffer = await fs.readFile(filePath);
+    // NUL bytes in the first 8KB mean binary
+    if (buffer.subarray(0, 8192).includes(0)) return null;
+    return buffer.toString('utf-8');
+  } catch {
+    return null;
+  }
+}
+
+function buildPattern(query, options = {}) {
+  if (query instanceof RegExp) return new RegExp(query.source, query.flags.includes('g') ? query.flags : query.flags + 'g');
+  const source = options.regex ? query : query.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+  const wrapped = options.wholeWord ? `\\b${source}\\b` : source;
+  try {
+    return new RegExp(wrapped, options.caseSensitive ? 'g' : 'gi');
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid search pattern: ${error.message}`);
+  }
+}
+
+// Find every match of `pattern` in a single file's text
+function searchText(text, pattern) {
+  const matches = [];
+  const lines = text.split('\n');
+  for (let i = 0; i < lines.length; i++) {
+    pattern.lastIndex = 0;
+    let match;
+    while ((match = pattern.exec(lines[i])) !== null) {
+      matches.push({ line: i + 1, column: match.index + 1, text: lines[i].replace(/\r$/, ''), match: match[0], groups: match.slice(1) });
+      if (match[0].length === 0) pattern.lastIndex += 1;
+    }
+  }
+  return matches;
+}
+
+async function searchFile(filePath, pattern) {
+  const text = await readTextFile(filePath);
+  return text === null ? [] : searchText(text, pattern);
+}
+
+// Search every text file in a workspace. `onFile` is called with (path, matches)
+// for each file that matched, so callers can index incrementally.
+async function searchWorkspace(rootPath, query, options = {}) {
+  const pattern = buildPattern(query, options);
+  const maxResults = options.maxResults || DEFAULT_MAX_RESULTS;
+  const results = [];
+  let filesSearched = 0;
+
+  for await (const filePath of walkWorkspace(rootPath, options)) {
+    if (options.extensions && !options.extensions.includes(path.extname(filePath).toLowerCase())) continue;
+    const matches = await searchFile(filePath, pattern);
+    filesSearched += 1;
+    if (options.onProgress && filesSearched % 200 === 0) options.onProgress(filesSearched);
+    if (matches.length === 0) continue;
+
+    if (options.onFile) options.onFile(filePath, matches);
+    for (const match of matches) {
+      results.push({ path: filePath, ...match });
+      if (results.length >= maxResults) {
+        return { results, filesSearched, truncated: true };
+      }
+    }
+  }
+  return { results, filesSearched, truncated: false };
+}
+
+function setupSearchHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('search:text', async (event, rootPath, query, options = {}) => {
+    const job = runJob('search', `Search for ${query}`, (handle) => searchWorkspace(rootPath, query, {
+      ...options,
+      ignored: (entryPath, type) => isPathIgnored(rootPath, entryPath, type),
+      signal: handle.signal,
+      onProgress: (count) => handle.reportProgress(null, `${count} files searched`)
+    }));
+    const { results, filesSearched, truncated } = await job.promise;
+    return {
+      results: results.map(({ groups, ...result }) => result),
+      filesSearched,
+      truncated
+    };
+  });
+}
+
+module.exports = {
+  setupSearchHandlers,
+  walkWorkspace,
+  readTextFile,
+  buildPattern,
+  searchText,
+  searchFile,
+  searchWorkspace,
+  DEFAULT_EXCLUDES
+};
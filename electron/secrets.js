@@ -0,0 +1,98 @@
+const { safeStorage } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('secrets');
+
+// Values are encrypted with the OS keychain (safeStorage) and never sent to the renderer;
+// the renderer can only set, delete, and list secret names.
+const store = new JsonStore('secrets', { secrets: {} });
+
+// Environment variables that back well-known secrets when nothing is stored (.env.local)
+const ENV_FALLBACKS = {
+  'openai.apiKey': 'OPENAI_API_KEY',
+  'anthropic.apiKey': 'ANTHROPIC_API_KEY',
+  'fal.apiKey': 'FAL_API_KEY',
+  'studio.apiKey': 'STUDIO_API_KEY',
+  'github.token': 'GITHUB_TOKEN',
+  'gitlab.token': 'GITLAB_TOKEN'
+};
+
+const NAME_PATTERN = /^[a-zA-Z0-9_.-]{1,128}$/;
+
+function validateName(name) {
+  if (typeof name !== 'string' || !NAME_PATTERN.test(name)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid secret name: ${name}`);
+  }
+}
+
+function getSecret(name) {
+  const entry = store.get('secrets')[name];
+  if (entry) {
+    try {
+      return safeStorage.decryptString(Buffer.from(entry.value, 'base64'));
+    } catch (error) {
+      log.error(`Failed to decrypt secret ${name}:`, error);
+      return null;
+    }
+  }
+  const envName = ENV_FALLBACKS[name];
+  return (envName && process.env[envName]) || null;
+}
+
+function setSecret(name, value) {
+  validateName(name);
+  if (typeof value !== 'string' || value.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Secret value must be a non-empty string');
+  }
+  if (!safeStorage.isEncryptionAvailable()) {
+    throw new ForgeError(ErrorCode.ACCESS_DENIED, 'Secure storage is not available on this system', { recoverable: false });
+  }
+  const secrets = store.get('secrets');
+  secrets[name] = {
+    value: safeStorage.encryptString(value).toString('base64'),
+    updatedAt: new Date().toISOString()
+  };
+  store.set('secrets', secrets);
+  log.info(`Stored secret ${name}`);
+}
+
+function deleteSecret(name) {
+  const secrets = store.get('secrets');
+  if (!secrets[name]) return false;
+  delete secrets[name];
+  store.set('secrets', secrets);
+  log.info(`Deleted secret ${name}`);
+  return true;
+}
+
+function listSecrets() {
+  const stored = store.get('secrets');
+  const names = new Set([...Object.keys(stored), ...Object.keys(ENV_FALLBACKS)]);
+  return Array.from(names)
+    .map((name) => ({
+      name,
+      stored: Boolean(stored[name]),
+      fromEnvironment: !stored[name] && Boolean(ENV_FALLBACKS[name] && process.env[ENV_FALLBACKS[name]]),
+      updatedAt: stored[name] ? stored[name].updatedAt : null
+    }))
+    .filter((entry) => entry.stored || entry.fromEnvironment);
+}
+
+function setupSecretHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('secrets:set', async (event, name, value) => {
+    setSecret(name, value);
+    return true;
+  });
+
+  ipcMain.handle('secrets:delete', async (event, name) => deleteSecret(name));
+
+  ipcMain.handle('secrets:list', async () => listSecrets());
+
+  ipcMain.handle('secrets:has', async (event, name) => Boolean(getSecret(name)));
+}
+
+module.exports = { setupSecretHandlers, getSecret, setSecret, deleteSecret, listSecrets };
@@ -0,0 +1,71 @@
+# Forge shell integration for bash: prompt (OSC 133), command line (OSC 633;E) and
+# working directory (OSC 7) marks. Loaded with --init-file, so it stands in for the
+# usual startup files and sources them first.
+
+if [ "$FORGE_SHELL_LOGIN" = "1" ]; then
+  [ -r /etc/profile ] && . /etc/profile
+  if [ -r ~/.bash_profile ]; then
+    . ~/.bash_profile
+  elif [ -r ~/.bash_login ]; then
+    . ~/.bash_login
+  elif [ -r ~/.profile ]; then
+    . ~/.profile
+  fi
+elif [ -r ~/.bashrc ]; then
+  . ~/.bashrc
+fi
+unset FORGE_SHELL_LOGIN
+
+if [ -z "$__forge_integration_loaded" ]; then
+  __forge_integration_loaded=1
+
+  __forge_escape() {
+    local value="${1//\\/\\\\}"
+    value="${value//;/\\x3b}"
+    value="${value//$'\n'/\\x0a}"
+    builtin printf '%s' "$value"
+  }
+
+  # PS0 is expanded in a subshell after a command line is read, before it runs. The line
+  # comes from history, so it is only reported when HISTCMD moved past the value saved at
+  # the prompt: lines kept out of history (HISTCONTROL, set +o history, HISTSIZE=0) would
+  # otherwise report the previous command.
+  __forge_preexec() {
+    if [ "$HISTCMD" = "$__forge_histcmd" ]; then
+      builtin printf '\e]133;C\a'
+      return
+    fi
+    local line
+    line=$(HISTTIMEFORMAT='' builtin history 1)
+    # Drop the entry number and the two characters after it, keeping any leading space
+    line="${line#"${line%%[![:space:]]*}"}"
+    line="${line#"${line%%[!0-9]*}"}"
+    line="${line:2}"
+    builtin printf '\e]633;E;%s\a\e]133;C\a' "$(__forge_escape "$line")"
+  }
+
+  __forge_first_prompt=1
+  __forge_prompt_start() {
+    local status=$?
+    if [ -z "$__forge_first_prompt" ]; then
+      builtin printf '\e]133;D;%s\a' "$status"
+    fi
+    unset __forge_first_prompt
+    builtin printf '\e]7;file://%s%s\a' "$HOSTNAME" "$PWD"
+    return $status
+  }
+
+  # Prompt frameworks rebuild PS1 on every prompt, so re-wrap it each time
+  __forge_prompt_end() {
+    local status=$?
+    case "$PS1" in
+      *'133;A'*) ;;
+      *) PS1='\[\e]133;A\a\]'"$PS1"'\[\e]133;B\a\]' ;;
+    esac
+    __forge_histcmd=$HISTCMD
+    return $status
+  }
+
+  PS0="${PS0}\$(__forge_preexec)"
+  PROMPT_COMMAND="__forge_prompt_start${PROMPT_COMMAND:+; $PROMPT_COMMAND}; __forge_prompt_end"
+fi
@@ -0,0 +1,36 @@
+# Forge shell integration for fish: prompt (OSC 133), command line (OSC 633;E) and
+# working directory (OSC 7) marks. Sourced with --init-command after config.fish.
+
+if not set -q __forge_integration_loaded
+    set -g __forge_integration_loaded 1
+
+    function __forge_escape
+        string replace -a '\\' '\\\\' -- $argv[1] | string replace -a ';' '\\x3b' | string join '\\x0a'
+    end
+
+    function __forge_preexec --on-event fish_preexec
+        printf '\e]633;E;%s\a\e]133;C\a' (__forge_escape "$argv[1]")
+    end
+
+    function __forge_postexec --on-event fish_postexec
+        set -g __forge_status $status
+    end
+
+    function __forge_prompt_start --on-event fish_prompt
+        if set -q __forge_status
+            printf '\e]133;D;%s\a' $__forge_status
+            set -e __forge_status
+        end
+        printf '\e]7;file://%s%s\a' (prompt_hostname) $PWD
+        printf '\e]133;A\a'
+    end
+
+    # Mark the end of the prompt after whatever the user's prompt prints
+    if functions -q fish_prompt
+        functions -c fish_prompt __forge_original_prompt
+        function fish_prompt
+            __forge_original_prompt
+            printf '\e]133;B\a'
+        end
+    end
+end
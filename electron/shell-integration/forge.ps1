@@ -0,0 +1,45 @@
+# Forge shell integration for PowerShell: prompt (OSC 133), command line (OSC 633;E)
+# and working directory (OSC 7) marks. Dot-sourced after the user's profile.
+
+if ($Global:__ForgeIntegrationLoaded) { return }
+$Global:__ForgeIntegrationLoaded = $true
+$Global:__ForgeRan = $false
+$Global:__ForgeOriginalPrompt = $function:prompt
+
+function Global:__ForgeEscape([string]$Value) {
+  return $Value.Replace('\', '\\').Replace(';', '\x3b').Replace("`n", '\x0a').Replace("`r", '\x0d')
+}
+
+function Global:prompt {
+  $success = $?
+  $lastExit = $Global:LASTEXITCODE
+  $esc = [char]0x1b
+  $bel = [char]0x07
+  $out = ''
+  if ($Global:__ForgeRan) {
+    $code = if ($success) { 0 } elseif ($lastExit) { $lastExit } else { 1 }
+    $out += "$esc]133;D;$code$bel"
+  }
+  $Global:__ForgeRan = $false
+  $location = $PWD.ProviderPath -replace '\\', '/'
+  if (-not $location.StartsWith('/')) { $location = '/' + $location }
+  $out += "$esc]7;file://$location$bel$esc]133;A$bel"
+  $out += & $Global:__ForgeOriginalPrompt
+  $out += "$esc]133;B$bel"
+  $Global:LASTEXITCODE = $lastExit
+  return $out
+}
+
+# PSReadLine hands the accepted line back through this hook before it runs
+if (Get-Module -Name PSReadLine) {
+  function Global:PSConsoleHostReadLine {
+    $line = [Microsoft.PowerShell.PSConsoleReadLine]::ReadLine($Host.Runspace, $ExecutionContext)
+    if ($line.Trim()) {
+      $Global:__ForgeRan = $true
+      $esc = [char]0x1b
+      $bel = [char]0x07
+      [Console]::Write("$esc]633;E;$(__ForgeEscape $line)$bel$esc]133;C$bel")
+    }
+    return $line
+  }
+}
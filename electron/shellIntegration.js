@@ -0,0 +1,108 @@
+const fs = require('fs');
+const path = require('path');
+const { app } = require('electron');
+const { createLogger } = require('./logger');
+
+const log = createLogger('shell-integration');
+
+// Scripts ship inside the app bundle, which shells can't read from an asar archive,
+// so they are copied to userData before the first terminal starts
+const SOURCE_DIR = path.join(__dirname, 'shell-integration');
+const SCRIPTS = ['forge.bash', 'forge.fish', 'forge.nu', 'forge.ps1', 'zsh/.zshenv', 'zsh/.zprofile', 'zsh/.zshrc', 'zsh/.zlogin'];
+
+let installedDir = null;
+
+function shellKind(shell) {
+  const name = path.basename(String(shell || '')).toLowerCase().replace(/\.exe$/, '');
+  if (name === 'pwsh' || name === 'powershell') return 'powershell';
+  if (name === 'nu' || name === 'nushell') return 'nu';
+  if (['bash', 'zsh', 'fish', 'sh', 'dash', 'ksh', 'cmd'].includes(name)) return name;
+  return 'other';
+}
+
+// Shells that get prompt, command, and cwd marks from Forge's scripts
+const INTEGRATED_SHELLS = ['bash', 'zsh', 'fish', 'nu', 'powershell'];
+
+function installScripts() {
+  if (installedDir) return installedDir;
+  const target = path.join(app.getPath('userData'), 'shell-integration');
+  for (const name of SCRIPTS) {
+    const content = fs.readFileSync(path.join(SOURCE_DIR, name), 'utf-8');
+    const destination = path.join(target, name);
+    let current = null;
+    try {
+      current = fs.readFileSync(destination, 'utf-8');
+    } catch {
+      // Not installed yet
+    }
+    if (current !== content) {
+      fs.mkdirSync(path.dirname(destination), { recursive: true });
+      fs.writeFileSync(destination, content, 'utf-8');
+    }
+  }
+  installedDir = target;
+  return target;
+}
+
+// Plain interactive/login arguments, used when integration is off or unsupported
+function plainShellArgs(kind) {
+  switch (kind) {
+    case 'bash':
+    case 'zsh':
+    case 'sh':
+    case 'ksh':
+      return ['-il'];
+    case 'fish':
+    case 'nu':
+      return ['-l'];
+    case 'powershell':
+      return ['-NoLogo'];
+    default:
+      return [];
+  }
+}
+
+// Arguments and environment to start `shell` as an interactive login shell with Forge's
+// integration loaded. Returns { args, env, integration } where integration is the shell
+// kind that was set up, or null when the shell runs without it.
+function prepareShellLaunch(shell, { integration = true } = {}) {
+  const kind = shellKind(shell);
+  if (!integration || !INTEGRATED_SHELLS.includes(kind)) {
+    return { args: plainShellArgs(kind), env: {}, integration: null };
+  }
+  let dir;
+  try {
+    dir = installScripts();
+  } catch (error) {
+    log.warn(`Could not install shell integration scripts: ${error.message}`);
+    return { args: plainShellArgs(kind), env: {}, integration: null };
+  }
+  const script = (name) => path.join(dir, name);
+  switch (kind) {
+    case 'bash':
+      // A login shell would ignore --init-file; the script loads the profile files itself
+      return { args: ['--init-file', script('forge.bash'), '-i'], env: { FORGE_SHELL_LOGIN: '1' }, integration: kind };
+    case 'zsh':
+      // zsh reads its startup files from ZDOTDIR; ours chain to the user's
+      return {
+        args: ['-il'],
+        env: { ZDOTDIR: script('zsh'), FORGE_ZDOTDIR: script('zsh'), FORGE_USER_ZDOTDIR: process.env.ZDOTDIR || process.env.HOME || '' },
+        integration: kind
+      };
+    case 'fish':
+      return { args: ['-l', '--init-command', `source '${script('forge.fish').replace(/[\\']/g, '\\$&')}'`], env: {}, integration: kind };
+    case 'nu':
+      return { args: ['--login', '--execute', `source '${script('forge.nu')}'`], env: {}, integration: kind };
+    case 'powershell':
+      // A restrictive execution policy only loses the integration, not the shell
+      return {
+        args: ['-NoLogo', '-NoExit', '-Command', `try { . '${script('forge.ps1').replace(/'/g, "''")}' } catch {}`],
+        env: {},
+        integration: kind
+      };
+    default:
+      return { args: plainShellArgs(kind), env: {}, integration: null };
+  }
+}
+
+module.exports = { prepareShellLaunch, shellKind };
@@ -0,0 +1,111 @@
+const { app } = require('electron');
+const { createLogger } = require('./logger');
+const { windowManager } = require('./windows');
+const { parsePathSpec } = require('./cliProtocol');
+const { openPaths, openDiff } = require('./openRouter');
+const { registerDeepLinkProtocol, registerInternalScheme, isDeepLink, parseDeepLink, extractDeepLinks } = require('./deepLinks');
+
+const log = createLogger('single-instance');
+
+// Requests the OS delivered before the app was ready (macOS open-file/open-url fire early)
+const pendingRequests = [];
+let ready = false;
+
+// Strip the executable (and the app path when running unpackaged via `electron .`),
+// Chromium switches, and URLs; everything left is a file or folder to open.
+function extractOpenTargets(argv, workingDirectory) {
+  const args = argv.slice(app.isPackaged ? 1 : 2);
+  return args
+    .filter((arg) => arg && !arg.startsWith('-') && !/^[a-z][a-z0-9+.-]*:\/\//i.test(arg))
+    .map((arg) => parsePathSpec(arg, workingDirectory));
+}
+
+function routeTargets(targets, source) {
+  if (!ready) {
+    pendingRequests.push({ targets, source });
+    return;
+  }
+  if (targets.length === 0) {
+    windowManager.focus(windowManager.getPrimaryWindow() || windowManager.createWindow());
+    return;
+  }
+  openPaths(targets, { source }).catch((error) => log.error(`Failed to open paths from ${source}:`, error));
+}
+
+function routeDeepLink(url) {
+  if (!ready) {
+    pendingRequests.push({ url });
+    return;
+  }
+  let link;
+  try {
+    link = parseDeepLink(url);
+  } catch (error) {
+    log.warn(`Ignoring deep link ${url}: ${error.message}`);
+    return;
+  }
+  log.info(`Handling deep link: ${link.action}`);
+  const opening = link.action === 'diff'
+    ? openDiff(link.left, link.right, { source: 'deep-link' })
+    : openPaths([link.target], { newWindow: link.newWindow, source: 'deep-link' });
+  opening.catch((error) => log.error(`Failed to handle deep link ${url}:`, error));
+}
+
+function routeArgv(argv, workingDirectory, source) {
+  const links = extractDeepLinks(argv);
+  const targets = extractOpenTargets(argv, workingDirectory);
+  links.forEach(routeDeepLink);
+  // A bare relaunch (no paths, no links) just brings Forge to the front
+  if (targets.length > 0 || links.length === 0) routeTargets(targets, source);
+}
+
+// Claim the single-instance lock. A second launch hands its argv and cwd to the
+// running instance through `second-instance` and then exits.
+function claimSingleInstance() {
+  const gotLock = app.requestSingleInstanceLock({ argv: process.argv, cwd: process.cwd() });
+  if (!gotLock) {
+    log.info('Another Forge instance is running; forwarding arguments to it');
+    return false;
+  }
+
+  registerDeepLinkProtocol();
+  registerInternalScheme();
+
+  app.on('second-instance', (event, argv, workingDirectory, additionalData) => {
+    // additionalData carries the exact argv; Chromium may reorder the argv parameter
+    const forwardedArgv = (additionalData && additionalData.argv) || argv;
+    const cwd = (additionalData && additionalData.cwd) || workingDirectory;
+    log.info('Second instance launched; routing its arguments');
+    routeArgv(forwardedArgv, cwd, 'second-instance');
+  });
+
+  // macOS delivers Finder "Open With" and dock drops as open-file instead of argv
+  app.on('open-file', (event, filePath) => {
+    event.preventDefault();
+    routeTargets([{ path: filePath }], 'open-file');
+  });
+
+  // ...and forge:// links as open-url
+  app.on('open-url', (event, url) => {
+    event.preventDefault();
+    if (isDeepLink(url)) routeDeepLink(url);
+  });
+
+  return true;
+}
+
+// Called once windows and IPC handlers exist: open the launch arguments plus anything queued
+function handleStartupArgs() {
+  ready = true;
+  const links = extractDeepLinks(process.argv);
+  const launchTargets = extractOpenTargets(process.argv, process.cwd());
+  links.forEach(routeDeepLink);
+  if (launchTargets.length > 0) routeTargets(launchTargets, 'launch');
+
+  for (const request of pendingRequests.splice(0)) {
+    if (request.url) routeDeepLink(request.url);
+    else routeTargets(request.targets, request.source);
+  }
+}
+
+module.exports = { claimSingleInstance, handleStartupArgs, extractOpenTargets };
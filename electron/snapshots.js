@@ -0,0 +1,230 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { app, dialog } = require('electron');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob } = require('./jobs');
+const { ZipWriter } = require('./zip');
+const { runGit } = require('./git');
+const { globToRegExp } = require('./editorconfig');
+const { anchorPattern } = require('./indexScope');
+const { windowManager } = require('./windows');
+const { registerCommand } = require('./commands');
+
+const log = createLogger('snapshots');
+
+const FORMAT = 'forge-project-snapshot';
+const FORMAT_VERSION = 1;
+const MANIFEST_NAME = 'forge-snapshot.json';
+// Larger files are listed in the manifest as skipped rather than archived
+const DEFAULT_MAX_FILE_SIZE = 20 * 1024 * 1024;
+
+// The commit a snapshot was taken at, or null outside git
+async function gitState(root) {
+  let topLevel;
+  try {
+    topLevel = (await runGit(root, ['rev-parse', '--show-toplevel'])).trim();
+  } catch {
+    return null;
+  }
+  const run = (args) => runGit(root, args).then((output) => output.trim()).catch(() => null);
+  // An empty repository has no HEAD yet
+  const [sha, branch, status] = await Promise.all([
+    run(['rev-parse', 'HEAD']),
+    run(['rev-parse', '--abbrev-ref', 'HEAD']),
+    run(['status', '--porcelain'])
+  ]);
+  return { root: topLevel, sha, branch: branch === 'HEAD' ? null : branch, dirty: Boolean(status) };
+}
+
+// Tracked and untracked files git doesn't ignore, relative to `root`
+async function listGitFiles(root) {
+  const output = await runGit(root, ['ls-files', '--cached', '--others', '--exclude-standard', '-z', '--', '.']);
+  return Array.from(new Set(output.split('\0').filter(Boolean)));
+}
+
+// .gitignore rules, for folders that aren't a git checkout. Last matching rule wins.
+function parseGitignore(text) {
+  const rules = [];
+  for (const rawLine of text.split(/\r?\n/)) {
+    const line = rawLine.replace(/(?<!\\)\s+$/, '');
+    if (!line || line.startsWith('#')) continue;
+    const negate = line.startsWith('!');
+    const pattern = (negate ? line.slice(1) : line).replace(/^\\(?=[#!])/, '');
+    if (!pattern.replace(/\/+$/, '')) continue;
+    rules.push({ negate, directoryOnly: pattern.endsWith('/'), regex: globToRegExp(anchorPattern(pattern)) });
+  }
+  return rules;
+}
+
+// Walk `root`, applying every .gitignore on the way down the way git would
+async function walkIgnoring(root, signal) {
+  const files = [];
+  const pending = [{ dir: root, ignores: [] }];
+  while (pending.length > 0) {
+    if (signal.aborted) break;
+    const { dir, ignores } = pending.pop();
+    let entries;
+    try {
+      entries = await fs.readdir(dir, { withFileTypes: true });
+    } catch {
+      continue;
+    }
+    const own = entries.some((entry) => entry.name === '.gitignore' && entry.isFile())
+      ? parseGitignore(await fs.readFile(path.join(dir, '.gitignore'), 'utf-8').catch(() => ''))
+      : [];
+    const scopes = own.length > 0 ? [...ignores, { base: dir, rules: own }] : ignores;
+    for (const entry of entries) {
+      if (entry.name === '.git') continue;
+      const entryPath = path.join(dir, entry.name);
+      const isDirectory = entry.isDirectory();
+      let ignored = false;
+      for (const { base, rules } of scopes) {
+        const relative = path.relative(base, entryPath).split(path.sep).join('/');
+        for (const rule of rules) {
+          if ((!rule.directoryOnly || isDirectory) && rule.regex.test(relative)) ignored = !rule.negate;
+        }
+      }
+      if (ignored) continue;
+      if (isDirectory) pending.push({ dir: entryPath, ignores: scopes });
+      else if (entry.isFile()) files.push(path.relative(root, entryPath).split(path.sep).join('/'));
+    }
+  }
+  return files;
+}
+
+// Files under `root` that git (or, outside a checkout, the .gitignore files) doesn't
+// ignore, relative to `root` with '/' separators
+async function listUnignoredFiles(root, signal) {
+  try {
+    return { files: await listGitFiles(root), ignoreSource: 'git' };
+  } catch {
+    return { files: await walkIgnoring(root, signal), ignoreSource: '.gitignore' };
+  }
+}
+
+// Zip the workspace at `rootPath` into `targetPath`, honoring .gitignore, with a manifest
+// recording the git commit, time and Forge version. Files sit under the project's folder
+// name so the archive unpacks cleanly. `maxFileSize` skips larger files (listed in the
+// manifest). Runs as a cancellable job.
+async function exportProjectSnapshot(rootPath, { targetPath, maxFileSize = DEFAULT_MAX_FILE_SIZE } = {}) {
+  if (typeof rootPath !== 'string' || !rootPath) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A project folder is required');
+  }
+  if (typeof targetPath !== 'string' || !path.isAbsolute(targetPath)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An absolute target path is required');
+  }
+  const root = path.resolve(rootPath);
+  const stats = await fs.stat(root).catch((error) => {
+    throw ForgeError.from(error, 'Project folder not found');
+  });
+  if (!stats.isDirectory()) {
+    throw new ForgeError(ErrorCode.NOT_DIRECTORY, `${root} is not a folder`);
+  }
+  const target = path.resolve(targetPath);
+  const projectName = path.basename(root);
+
+  const job = runJob('snapshot', `Snapshot ${projectName}`, async (handle) => {
+    handle.reportProgress(null, 'Listing files');
+    const git = await gitState(root);
+    const files = git ? await listGitFiles(root) : await walkIgnoring(root, handle.signal);
+    handle.throwIfCancelled();
+
+    // Entries stream into a partial file, one file in memory at a time, which replaces the
+    // target once complete. The manifest goes last since it counts what was written.
+    await fs.mkdir(path.dirname(target), { recursive: true });
+    const partial = `${target}.partial`;
+    const zip = await ZipWriter.create(partial);
+    try {
+      const skipped = [];
+      let written = 0;
+      let totalSize = 0;
+      for (let i = 0; i < files.length; i++) {
+        handle.throwIfCancelled();
+        const relative = files[i];
+        const filePath = path.join(root, relative);
+        // Leave out the archive itself when it's written inside the project
+        if (filePath === target || filePath === partial) continue;
+        const fileStats = await fs.lstat(filePath).catch(() => null);
+        // Deleted but still in the index
+        if (!fileStats) continue;
+        if (!fileStats.isFile()) {
+          skipped.push({ path: relative, reason: fileStats.isSymbolicLink() ? 'symlink' : 'notAFile' });
+          continue;
+        }
+        if (fileStats.size > maxFileSize) {
+          skipped.push({ path: relative, reason: 'tooLarge', size: fileStats.size });
+          continue;
+        }
+        await zip.add({ name: `${projectName}/${relative}`, data: await fs.readFile(filePath), date: fileStats.mtime });
+        written++;
+        totalSize += fileStats.size;
+        if (i % 100 === 0) handle.reportProgress(i / files.length, `${i} of ${files.length} files`);
+      }
+
+      const manifest = {
+        format: FORMAT,
+        version: FORMAT_VERSION,
+        createdAt: new Date().toISOString(),
+        forgeVersion: app.getVersion(),
+        platform: process.platform,
+        projectName,
+        // Null outside a git checkout; a dirty tree means the files differ from the commit
+        git: git ? { sha: git.sha, branch: git.branch, dirty: git.dirty } : null,
+        ignoreSource: git ? 'git' : '.gitignore',
+        files: written,
+        totalSize,
+        skipped
+      };
+      handle.reportProgress(1, 'Writing archive');
+      await zip.add({ name: MANIFEST_NAME, data: JSON.stringify(manifest, null, 2) });
+      await zip.finish();
+      await fs.rename(partial, target);
+      log.info(`Snapshot of ${root} (${written} files${git && git.sha ? ` at ${git.sha.slice(0, 12)}` : ''}) written to ${target}`);
+      return { path: target, manifest };
+    } catch (error) {
+      await zip.abort();
+      await fs.unlink(partial).catch(() => {});
+      throw error;
+    }
+  });
+  return job.promise;
+}
+
+async function pickTarget(sender, rootPath) {
+  const win = windowManager.windowFromSender(sender);
+  const result = await dialog.showSaveDialog(win, {
+    defaultPath: path.join(app.getPath('documents'), `${path.basename(rootPath)}-snapshot-${new Date().toISOString().slice(0, 10)}.zip`),
+    filters: [{ name: 'Zip Archive', extensions: ['zip'] }]
+  });
+  return result.canceled ? null : result.filePath;
+}
+
+// Defaults to the calling window's workspace and asks where to save. Resolves null if the
+// user cancels the save dialog.
+async function exportFromWindow(sender, rootPath, options = {}) {
+  const context = sender ? windowManager.fromSender(sender) : null;
+  const root = rootPath || (context && context.workspacePath);
+  if (!root) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to snapshot it');
+  const targetPath = options.targetPath || await pickTarget(sender, root);
+  if (!targetPath) return null;
+  return exportProjectSnapshot(root, { ...options, targetPath });
+}
+
+function setupSnapshotHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('snapshots:export', async (event, rootPath, options) => exportFromWindow(event.sender, rootPath, options));
+
+  registerCommand({
+    id: 'workspace.exportSnapshot',
+    title: 'Export Project Snapshot',
+    category: 'workspace',
+    handler: (...args) => {
+      const context = args.pop();
+      return exportFromWindow(context.sender, null);
+    }
+  });
+}
+
+module.exports = { setupSnapshotHandlers, exportProjectSnapshot, listUnignoredFiles };
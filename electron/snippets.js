@@ -0,0 +1,335 @@
+const fs = require('fs').promises;
+const path = require('path');
+const os = require('os');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('snippets');
+
+const SCHEMA_VERSION = 2;
+
+const store = new JsonStore('snippets', {
+  schemaVersion: SCHEMA_VERSION,
+  // Snippet id -> snippet
+  snippets: {}
+});
+
+// Upgrade older store layouts in place. v1 kept a single `prefix` string.
+const MIGRATIONS = {
+  1: (data) => {
+    for (const snippet of Object.values(data.snippets || {})) {
+      snippet.prefixes = snippet.prefix ? [snippet.prefix] : [];
+      delete snippet.prefix;
+      snippet.revision = snippet.revision || 1;
+    }
+    return data;
+  }
+};
+
+function loadStore() {
+  let data = store.store;
+  let version = data.schemaVersion || 1;
+  if (version === SCHEMA_VERSION) return data;
+
+  while (version < SCHEMA_VERSION) {
+    log.info(`Migrating snippet store from v${version}`);
+    data = MIGRATIONS[version](data);
+    version += 1;
+  }
+  data.schemaVersion = SCHEMA_VERSION;
+  store.store = data;
+  return data;
+}
+
+function saveSnippets(snippets) {
+  store.store = { ...loadStore(), snippets };
+}
+
+// Matches $1, ${1}, ${1:placeholder}, ${1|a,b|}, $NAME, ${NAME}, ${NAME:default}
+const TOKEN_PATTERN = /\\[$}\\]|\$(\d+)|\$\{(\d+)(?::((?:[^{}\\]|\\.)*)|\|([^|]*)\|)?\}|\$([A-Z_][A-Z0-9_]*)|\$\{([A-Z_][A-Z0-9_]*)(?::((?:[^{}\\]|\\.)*))?\}/g;
+
+// Describe the tab stops and variables a snippet body uses
+function parseSnippetBody(body) {
+  const tabStops = new Map();
+  const variables = new Set();
+  for (const match of body.matchAll(TOKEN_PATTERN)) {
+    const [, simpleIndex, index, placeholder, choices, simpleVariable, variable] = match;
+    const stopIndex = simpleIndex || index;
+    if (stopIndex !== undefined) {
+      const stop = tabStops.get(Number(stopIndex)) || { index: Number(stopIndex), placeholder: null, choices: null };
+      if (placeholder !== undefined) stop.placeholder = placeholder;
+      if (choices !== undefined) stop.choices = choices.split(',');
+      tabStops.set(stop.index, stop);
+    } else if (simpleVariable || variable) {
+      variables.add(simpleVariable || variable);
+    }
+  }
+
+  // $0 is the final cursor position and always comes last
+  const ordered = Array.from(tabStops.values()).sort((a, b) =>
+    (a.index === 0) - (b.index === 0) || a.index - b.index);
+  return { tabStops: ordered, variables: Array.from(variables) };
+}
+
+// Values for the VS Code snippet variables Forge supports
+function variableValues(context = {}) {
+  const now = new Date();
+  const pad = (value) => String(value).padStart(2, '0');
+  const filePath = context.filePath || '';
+  return {
+    TM_FILENAME: filePath ? path.basename(filePath) : '',
+    TM_FILENAME_BASE: filePath ? path.basename(filePath, path.extname(filePath)) : '',
+    TM_DIRECTORY: filePath ? path.dirname(filePath) : '',
+    TM_FILEPATH: filePath,
+    RELATIVE_FILEPATH: filePath && context.workspacePath ? path.relative(context.workspacePath, filePath) : filePath,
+    TM_SELECTED_TEXT: context.selectedText || '',
+    TM_CURRENT_LINE: context.currentLine || '',
+    TM_CURRENT_WORD: context.currentWord || '',
+    TM_LINE_INDEX: context.lineNumber !== undefined ? String(context.lineNumber) : '',
+    TM_LINE_NUMBER: context.lineNumber !== undefined ? String(context.lineNumber + 1) : '',
+    CLIPBOARD: context.clipboard || '',
+    WORKSPACE_NAME: context.workspacePath ? path.basename(context.workspacePath) : '',
+    WORKSPACE_FOLDER: context.workspacePath || '',
+    CURRENT_YEAR: String(now.getFullYear()),
+    CURRENT_YEAR_SHORT: String(now.getFullYear()).slice(-2),
+    CURRENT_MONTH: pad(now.getMonth() + 1),
+    CURRENT_MONTH_NAME: now.toLocaleString('en-US', { month: 'long' }),
+    CURRENT_MONTH_NAME_SHORT: now.toLocaleString('en-US', { month: 'short' }),
+    CURRENT_DATE: pad(now.getDate()),
+    CURRENT_DAY_NAME: now.toLocaleString('en-US', { weekday: 'long' }),
+    CURRENT_DAY_NAME_SHORT: now.toLocaleString('en-US', { weekday: 'short' }),
+    CURRENT_HOUR: pad(now.getHours()),
+    CURRENT_MINUTE: pad(now.getMinutes()),
+    CURRENT_SECOND: pad(now.getSeconds()),
+    CURRENT_SECONDS_UNIX: String(Math.floor(now.getTime() / 1000)),
+    RANDOM: String(Math.random()).slice(2, 8),
+    RANDOM_HEX: Math.floor(Math.random() * 0xffffff).toString(16).padStart(6, '0'),
+    UUID: uuidv4(),
+    USER: os.userInfo().username
+  };
+}
+
+// Expand known variables, leaving tab stops in place for the editor's snippet engine.
+// Unknown variables become their default, or a placeholder named after the variable.
+function resolveVariables(body, context) {
+  const values = variableValues(context);
+  return body.replace(TOKEN_PATTERN, (match, simpleIndex, index, placeholder, choices, simpleVariable, variable, fallback) => {
+    const name = simpleVariable || variable;
+    if (!name) return match;
+    if (values[name] !== undefined && values[name] !== '') return values[name].replace(/[$}\\]/g, '\\$&');
+    if (fallback !== undefined) return fallback;
+    return values[name] !== undefined ? '' : name;
+  });
+}
+
+function validateSnippet(input) {
+  if (!input.name || typeof input.name !== 'string') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Snippet name is required');
+  }
+  const prefixes = (Array.isArray(input.prefixes) ? input.prefixes : [input.prefixes]).filter(Boolean);
+  if (prefixes.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Snippet needs at least one prefix');
+  }
+  const body = Array.isArray(input.body) ? input.body.join('\n') : input.body;
+  if (typeof body !== 'string' || body.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Snippet body is required');
+  }
+  return {
+    name: input.name,
+    prefixes,
+    body,
+    description: input.description || '',
+    // '*' applies to every language
+    languages: (input.languages && input.languages.length > 0) ? input.languages : ['*']
+  };
+}
+
+function describe(snippet) {
+  return { ...snippet, ...parseSnippetBody(snippet.body) };
+}
+
+function listSnippets(language) {
+  return Object.values(loadStore().snippets)
+    .filter((snippet) => !language || snippet.languages.includes('*') || snippet.languages.includes(language))
+    .sort((a, b) => a.name.localeCompare(b.name));
+}
+
+function getSnippet(id) {
+  const snippet = loadStore().snippets[id];
+  if (!snippet) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `Snippet ${id} not found`);
+  }
+  return snippet;
+}
+
+function createSnippet(input, source = 'user') {
+  const now = new Date().toISOString();
+  const snippet = {
+    id: `snippet_${uuidv4()}`,
+    ...validateSnippet(input),
+    source,
+    revision: 1,
+    createdAt: now,
+    updatedAt: now
+  };
+  saveSnippets({ ...loadStore().snippets, [snippet.id]: snippet });
+  return snippet;
+}
+
+// `expectedRevision` guards against overwriting an edit made in another window
+function updateSnippet(id, changes, expectedRevision) {
+  const existing = getSnippet(id);
+  if (expectedRevision !== undefined && expectedRevision !== existing.revision) {
+    throw new ForgeError(ErrorCode.CONFLICT, `Snippet ${existing.name} was modified elsewhere`, {
+      details: { expectedRevision, revision: existing.revision }
+    });
+  }
+  const snippet = {
+    ...existing,
+    ...validateSnippet({ ...existing, ...changes }),
+    revision: existing.revision + 1,
+    updatedAt: new Date().toISOString()
+  };
+  saveSnippets({ ...loadStore().snippets, [id]: snippet });
+  return snippet;
+}
+
+function deleteSnippet(id) {
+  getSnippet(id);
+  const snippets = { ...loadStore().snippets };
+  delete snippets[id];
+  saveSnippets(snippets);
+  return true;
+}
+
+// VS Code snippet files are JSON with comments and trailing commas
+function parseJsonc(text) {
+  let result = '';
+  let inString = false;
+  for (let i = 0; i < text.length; i++) {
+    const char = text[i];
+    if (inString) {
+      result += char;
+      if (char === '\\') result += text[++i] || '';
+      else if (char === '"') inString = false;
+    } else if (char === '"') {
+      inString = true;
+      result += char;
+    } else if (char === '/' && text[i + 1] === '/') {
+      while (i < text.length && text[i] !== '\n') i++;
+      result += '\n';
+    } else if (char === '/' && text[i + 1] === '*') {
+      i = text.indexOf('*/', i + 2);
+      if (i < 0) break;
+      i += 1;
+    } else if (char === '}' || char === ']') {
+      // Drop a trailing comma. Only reached outside strings, so commas in values survive.
+      result = result.replace(/,(\s*)$/, '$1') + char;
+    } else {
+      result += char;
+    }
+  }
+  return JSON.parse(result);
+}
+
+// Import a VS Code snippet file. Language-specific files (e.g. typescript.json) pass
+// `language`; global .code-snippets files carry a comma-separated `scope` per entry.
+function importVSCodeSnippets(text, language) {
+  let parsed;
+  try {
+    parsed = parseJsonc(text);
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid snippet JSON: ${error.message}`);
+  }
+
+  const existing = listSnippets();
+  const imported = [];
+  const skipped = [];
+  for (const [name, entry] of Object.entries(parsed)) {
+    if (!entry || typeof entry !== 'object' || !entry.prefix || entry.body === undefined) {
+      skipped.push({ name, reason: 'missing prefix or body' });
+      continue;
+    }
+    const languages = entry.scope
+      ? entry.scope.split(',').map((scope) => scope.trim()).filter(Boolean)
+      : (language ? [language] : ['*']);
+
+    if (existing.some((snippet) => snippet.name === name && snippet.languages.join() === languages.join())) {
+      skipped.push({ name, reason: 'already exists' });
+      continue;
+    }
+    imported.push(createSnippet({
+      name,
+      prefixes: entry.prefix,
+      body: entry.body,
+      description: entry.description,
+      languages
+    }, 'vscode'));
+  }
+
+  log.info(`Imported ${imported.length} VS Code snippets (${skipped.length} skipped)`);
+  return { imported, skipped };
+}
+
+// Completion lookup: snippets for `language` whose prefix starts with `typed`
+function lookupSnippets(language, typed = '', context) {
+  const query = typed.toLowerCase();
+  const results = [];
+  for (const snippet of listSnippets(language)) {
+    const prefix = snippet.prefixes.find((candidate) => candidate.toLowerCase().startsWith(query));
+    if (!prefix) continue;
+    results.push({
+      id: snippet.id,
+      name: snippet.name,
+      prefix,
+      description: snippet.description,
+      // Editor-ready snippet text with variables expanded and tab stops kept
+      insertText: context ? resolveVariables(snippet.body, context) : snippet.body,
+      exact: prefix.toLowerCase() === query
+    });
+  }
+  return results.sort((a, b) => b.exact - a.exact || a.prefix.length - b.prefix.length);
+}
+
+function setupSnippetHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('snippets:list', async (event, language) => {
+    return listSnippets(language);
+  });
+
+  ipcMain.handle('snippets:get', async (event, id) => {
+    return describe(getSnippet(id));
+  });
+
+  ipcMain.handle('snippets:create', async (event, input) => {
+    return createSnippet(input);
+  });
+
+  ipcMain.handle('snippets:update', async (event, id, changes, expectedRevision) => {
+    return updateSnippet(id, changes, expectedRevision);
+  });
+
+  ipcMain.handle('snippets:delete', async (event, id) => {
+    return deleteSnippet(id);
+  });
+
+  ipcMain.handle('snippets:importVSCode', async (event, filePath, language) => {
+    const text = await fs.readFile(filePath, 'utf-8');
+    // typescript.json -> typescript; .code-snippets files rely on per-entry scope
+    const inferred = language || (path.extname(filePath) === '.json' ? path.basename(filePath, '.json') : undefined);
+    return importVSCodeSnippets(text, inferred);
+  });
+
+  ipcMain.handle('snippets:lookup', async (event, language, typed, context) => {
+    return lookupSnippets(language, typed, context);
+  });
+
+  ipcMain.handle('snippets:resolve', async (event, id, context) => {
+    return resolveVariables(getSnippet(id).body, context);
+  });
+}
+
+module.exports = { setupSnippetHandlers, lookupSnippets, resolveVariables, parseSnippetBody, parseJsonc };
@@ -0,0 +1,292 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { fileURLToPath } = require('url');
+const { net } = require('electron');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { openPaths } = require('./openRouter');
+const { getConsoleEntry } = require('./browserConsole');
+
+const log = createLogger('source-maps');
+
+const MAX_FILE_SIZE = 20 * 1024 * 1024;
+const FETCH_TIMEOUT = 10 * 1000;
+// Dev servers rebuild constantly, so maps are only reused briefly
+const CACHE_TTL = 30 * 1000;
+const MAX_CACHED = 30;
+const MAX_FRAMES = 100;
+// Bundler prefixes to try dropping when a source path doesn't exist under the workspace
+const MAX_STRIPPED_SEGMENTS = 3;
+
+const BASE64 = 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/';
+const BASE64_VALUES = new Map(Array.from(BASE64, (char, index) => [char, index]));
+// `at fn (url:1:2)`, `at url:1:2`, `at async fn (url:1:2)`, and Firefox/Safari `fn@url:1:2`
+const V8_FRAME = /^\s*at (?:(?:async )?(.+?) \()?(.+?):(\d+):(\d+)\)?$/;
+const GECKO_FRAME = /^\s*([^@]*)@(.+?):(\d+):(\d+)$/;
+const SOURCE_MAPPING_URL = /\/\/[#@]\s*sourceMappingURL=([^\s'"]+)\s*$/m;
+
+// Parsed maps by script URL: { at, map }
+const cache = new Map();
+
+// Base64 VLQ-encoded "mappings" into per-generated-line arrays of
+// [generatedColumn, sourceIndex, sourceLine, sourceColumn, nameIndex?], all 0-based
+function parseMappings(mappings) {
+  const lines = [];
+  let source = 0;
+  let sourceLine = 0;
+  let sourceColumn = 0;
+  let name = 0;
+  for (const line of mappings.split(';')) {
+    const segments = [];
+    let column = 0;
+    for (const encoded of line.split(',')) {
+      if (!encoded) continue;
+      const values = [];
+      let value = 0;
+      let shift = 0;
+      for (const char of encoded) {
+        const digit = BASE64_VALUES.get(char);
+        if (digit === undefined) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid character in source map mappings: ${char}`);
+        value += (digit & 31) << shift;
+        if (digit & 32) {
+          shift += 5;
+          continue;
+        }
+        values.push(value & 1 ? -(value >>> 1) : value >>> 1);
+        value = 0;
+        shift = 0;
+      }
+      column += values[0];
+      if (values.length < 4) continue; // no original position
+      source += values[1];
+      sourceLine += values[2];
+      sourceColumn += values[3];
+      const segment = [column, source, sourceLine, sourceColumn];
+      if (values.length > 4) {
+        name += values[4];
+        segment.push(name);
+      }
+      segments.push(segment);
+    }
+    segments.sort((a, b) => a[0] - b[0]);
+    lines.push(segments);
+  }
+  return lines;
+}
+
+// A v3 source map, or an index map whose sections are parsed the same way
+function parseSourceMap(json, mapUrl) {
+  const raw = typeof json === 'string' ? JSON.parse(json.replace(/^\)\]\}'[^\n]*\n/, '')) : json;
+  if (raw.version !== 3) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unsupported source map version ${raw.version}`);
+  if (Array.isArray(raw.sections)) {
+    return {
+      sections: raw.sections
+        .filter((section) => section.map)
+        .map((section) => ({ line: section.offset.line, column: section.offset.column, map: parseSourceMap(section.map, mapUrl) }))
+    };
+  }
+  const root = raw.sourceRoot ? raw.sourceRoot.replace(/\/?$/, '/') : '';
+  return {
+    sources: (raw.sources || []).map((source) => {
+      if (source === null) return null;
+      try {
+        return new URL(root + source, mapUrl).href;
+      } catch {
+        return root + source;
+      }
+    }),
+    sourcesContent: raw.sourcesContent || [],
+    names: raw.names || [],
+    lines: parseMappings(raw.mappings || '')
+  };
+}
+
+// Where generated `line`/`column` (0-based) came from: the segment at or before the
+// column on that line
+function originalPositionFor(map, line, column) {
+  if (map.sections) {
+    let section = null;
+    for (const candidate of map.sections) {
+      if (candidate.line < line || (candidate.line === line && candidate.column <= column)) section = candidate;
+    }
+    if (!section) return null;
+    return originalPositionFor(section.map, line - section.line, line === section.line ? column - section.column : column);
+  }
+  const segments = map.lines[line];
+  if (!segments || segments.length === 0) return null;
+  let low = 0;
+  let high = segments.length - 1;
+  let found = -1;
+  while (low <= high) {
+    const middle = (low + high) >> 1;
+    if (segments[middle][0] <= column) {
+      found = middle;
+      low = middle + 1;
+    } else {
+      high = middle - 1;
+    }
+  }
+  if (found < 0) return null;
+  const [, sourceIndex, sourceLine, sourceColumn, nameIndex] = segments[found];
+  return {
+    source: map.sources[sourceIndex] || null,
+    line: sourceLine,
+    column: sourceColumn,
+    name: nameIndex === undefined ? null : map.names[nameIndex] || null,
+    hasContent: typeof map.sourcesContent[sourceIndex] === 'string'
+  };
+}
+
+async function fetchText(url) {
+  const parsed = new URL(url);
+  if (parsed.protocol === 'file:') {
+    const filePath = fileURLToPath(parsed);
+    const stats = await fs.stat(filePath);
+    if (stats.size > MAX_FILE_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${filePath} is too large to read a source map from`);
+    return { text: await fs.readFile(filePath, 'utf-8'), headers: new Map() };
+  }
+  if (parsed.protocol !== 'http:' && parsed.protocol !== 'https:') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Cannot fetch ${parsed.protocol} URLs for source maps`);
+  }
+  const response = await net.fetch(url, { signal: AbortSignal.timeout(FETCH_TIMEOUT) });
+  if (!response.ok) throw new ForgeError(ErrorCode.NOT_FOUND, `${url} returned ${response.status}`);
+  const text = await response.text();
+  if (text.length > MAX_FILE_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${url} is too large to read a source map from`);
+  return { text, headers: response.headers };
+}
+
+// The map for a script: from its SourceMap header or its trailing sourceMappingURL
+// comment, which may be a data: URL
+async function loadSourceMap(scriptUrl) {
+  const cached = cache.get(scriptUrl);
+  if (cached && Date.now() - cached.at < CACHE_TTL) return cached.map;
+
+  const script = await fetchText(scriptUrl);
+  const reference = script.headers.get('sourcemap') || script.headers.get('x-sourcemap') || (script.text.match(SOURCE_MAPPING_URL) || [])[1];
+  let map = null;
+  if (reference) {
+    const mapUrl = new URL(reference, scriptUrl).href;
+    if (mapUrl.startsWith('data:')) {
+      const [header, data] = mapUrl.split(',', 2);
+      const json = header.endsWith(';base64') ? Buffer.from(data, 'base64').toString('utf-8') : decodeURIComponent(data);
+      map = parseSourceMap(json, scriptUrl);
+    } else {
+      map = parseSourceMap((await fetchText(mapUrl)).text, mapUrl);
+    }
+  }
+  cache.delete(scriptUrl);
+  cache.set(scriptUrl, { at: Date.now(), map });
+  if (cache.size > MAX_CACHED) cache.delete(cache.keys().next().value);
+  return map;
+}
+
+// Candidate file paths for a source URL, most likely first
+function sourcePathCandidates(source, workspace) {
+  let parsed;
+  try {
+    parsed = new URL(source);
+  } catch {
+    return path.isAbsolute(source) ? [source] : workspace ? [path.join(workspace, source)] : [];
+  }
+  if (parsed.protocol === 'file:') return [fileURLToPath(parsed)];
+  let pathname = decodeURIComponent(parsed.pathname);
+  // Vite serves files outside its root as /@fs/absolute/path
+  if (pathname.startsWith('/@fs/')) return [pathname.slice(4)];
+  // webpack://app-name/./src/App.tsx and webpack:///./src/App.tsx
+  if (parsed.protocol === 'webpack:' || parsed.protocol === 'webpack-internal:') pathname = pathname.replace(/^\/+\.\//, '/');
+  const relative = pathname.replace(/^\/+/, '').replace(/^\.\//, '');
+  if (!workspace || !relative) return [];
+  const segments = relative.split('/');
+  const candidates = [];
+  for (let drop = 0; drop <= Math.min(MAX_STRIPPED_SEGMENTS, segments.length - 1); drop++) {
+    candidates.push(path.join(workspace, ...segments.slice(drop)));
+  }
+  return candidates;
+}
+
+async function workspacePath(source, workspace) {
+  for (const candidate of sourcePathCandidates(source, workspace)) {
+    const stats = await fs.stat(candidate).catch(() => null);
+    if (stats && stats.isFile()) return candidate;
+  }
+  return null;
+}
+
+function parseStack(stack) {
+  const frames = [];
+  for (const line of String(stack || '').split('\n')) {
+    const match = line.match(V8_FRAME) || line.match(GECKO_FRAME);
+    if (!match) continue;
+    frames.push({ functionName: match[1] || null, url: match[2], line: Number(match[3]), column: Number(match[4]) });
+    if (frames.length >= MAX_FRAMES) break;
+  }
+  return frames;
+}
+
+// Map one generated position (1-based, as in stack traces) back to its original source.
+// `target` is where "open in editor" goes: the original file when it's in the workspace,
+// else the generated file when that's local.
+async function resolvePosition({ functionName = null, url, line, column }, workspace) {
+  const frame = { functionName, url, line, column, original: null, path: null, target: null, error: null };
+  try {
+    const map = /^(https?|file):/.test(url) ? await loadSourceMap(url) : null;
+    const original = map ? originalPositionFor(map, line - 1, column - 1) : null;
+    if (original) {
+      frame.original = { ...original, line: original.line + 1, column: original.column + 1 };
+      if (original.source) frame.path = await workspacePath(original.source, workspace);
+    }
+  } catch (error) {
+    frame.error = error.message;
+    log.debug(`Could not resolve ${url}: ${error.message}`);
+  }
+  if (frame.path) {
+    frame.target = { path: frame.path, line: frame.original.line, column: frame.original.column };
+  } else {
+    const generated = await workspacePath(url, workspace).catch(() => null);
+    if (generated) frame.target = { path: generated, line, column };
+  }
+  return frame;
+}
+
+async function resolveStack(stack, { workspace } = {}) {
+  const frames = parseStack(stack);
+  const message = String(stack || '').split('\n').find((line) => !V8_FRAME.test(line) && !GECKO_FRAME.test(line)) || null;
+  return { message, frames: await Promise.all(frames.map((frame) => resolvePosition(frame, workspace))) };
+}
+
+// A captured console entry: its stack when it has one, else the line it was logged from
+async function resolveConsoleEntry(viewId, entryId, options = {}) {
+  const entry = getConsoleEntry(viewId, entryId);
+  if (entry.stack) return resolveStack(entry.stack, options);
+  if (!entry.source || !entry.line) return { message: entry.message, frames: [] };
+  const frame = await resolvePosition({ url: entry.source, line: entry.line, column: entry.column || 1 }, options.workspace);
+  return { message: entry.message, frames: [frame] };
+}
+
+function setupSourceMapHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // Source paths are looked up under the calling window's workspace unless one is given
+  const withWorkspace = (event, options = {}) => {
+    const context = windowManager.fromSender(event.sender);
+    return { workspace: context ? context.workspacePath : null, ...options };
+  };
+
+  ipcMain.handle('sourceMaps:resolveStack', async (event, stack, options) => resolveStack(stack, withWorkspace(event, options)));
+
+  ipcMain.handle('sourceMaps:resolveConsoleEntry', async (event, viewId, entryId, options) => {
+    return resolveConsoleEntry(viewId, entryId, withWorkspace(event, options));
+  });
+
+  // Open a resolved frame's target in the calling window
+  ipcMain.handle('sourceMaps:openFrame', async (event, target) => {
+    if (!target || typeof target.path !== 'string' || !path.isAbsolute(target.path)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A frame target needs an absolute path');
+    }
+    const context = windowManager.fromSender(event.sender);
+    return openPaths([target], { windowId: context ? context.window.id : undefined, source: 'source-map' });
+  });
+}
+
+module.exports = { setupSourceMapHandlers, parseSourceMap, originalPositionFor, parseStack, resolveStack };
@@ -0,0 +1,311 @@
+const fs = require('fs').promises;
+const os = require('os');
+const path = require('path');
+const { app } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { HunspellDictionary } = require('./hunspell');
+const { getLanguageConfig } = require('./languages');
+
+const log = createLogger('spellcheck');
+
+const MAX_TEXT_LENGTH = 2 * 1024 * 1024;
+const MAX_MISSPELLINGS = 2000;
+const MAX_USER_WORDS = 10000;
+// Shorter words are mostly abbreviations and loop variables
+const MIN_WORD_LENGTH = 3;
+// Languages that are prose throughout; everything else is code, checked in comments and strings
+const PROSE_LANGUAGES = new Set(['plaintext', 'markdown', 'mdx', 'restructuredtext', 'latex']);
+const QUOTES = ['"', "'", '`'];
+
+const store = new JsonStore('spellcheck', {
+  // Dictionary id ("en_US") used when a request doesn't name one; null follows the system locale
+  dictionary: null,
+  // Words the user added, accepted whatever the dictionary
+  userWords: []
+});
+
+// Dictionary id -> Promise<HunspellDictionary>
+const loaded = new Map();
+
+function userDictionaryDir() {
+  return path.join(app.getPath('userData'), 'dictionaries');
+}
+
+// Where Hunspell dictionaries live, user-installed first
+function dictionaryDirs() {
+  const dirs = [userDictionaryDir()];
+  if (process.platform === 'linux') dirs.push('/usr/share/hunspell', '/usr/share/myspell', '/usr/share/myspell/dicts');
+  if (process.platform === 'darwin') dirs.push(path.join(os.homedir(), 'Library', 'Spelling'), '/Library/Spelling');
+  return dirs;
+}
+
+// Installed dictionaries: a .dic with an .aff beside it. The first directory with an id wins.
+async function listDictionaries() {
+  const found = new Map();
+  for (const dir of dictionaryDirs()) {
+    const names = await fs.readdir(dir).catch(() => []);
+    for (const name of names) {
+      if (!name.endsWith('.dic')) continue;
+      const id = name.slice(0, -4);
+      if (found.has(id) || !names.includes(`${id}.aff`)) continue;
+      found.set(id, { id, path: path.join(dir, name), source: dir === userDictionaryDir() ? 'user' : 'system' });
+    }
+  }
+  return Array.from(found.values()).sort((a, b) => a.id.localeCompare(b.id));
+}
+
+// The requested dictionary, else the configured one, else one for the system locale
+async function resolveDictionary(requested) {
+  const available = await listDictionaries();
+  if (available.length === 0) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, 'No spelling dictionaries are installed. Install Hunspell dictionaries (e.g. the hunspell-en-us package) or add an .aff/.dic pair.', {
+      details: { searched: dictionaryDirs() }
+    });
+  }
+  const wanted = requested || store.get('dictionary') || app.getLocale().replace('-', '_');
+  const language = wanted.split(/[_-]/)[0];
+  const match = available.find((entry) => entry.id === wanted) ||
+    (requested ? null : available.find((entry) => entry.id.split(/[_-]/)[0] === language) || available[0]);
+  if (!match) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `Dictionary ${requested} is not installed`, { details: { available: available.map((entry) => entry.id) } });
+  }
+
+  if (!loaded.has(match.id)) {
+    const started = Date.now();
+    const loading = Promise.all([fs.readFile(match.path.replace(/\.dic$/, '.aff')), fs.readFile(match.path)])
+      .then(([aff, dic]) => {
+        const dictionary = new HunspellDictionary(aff, dic);
+        log.info(`Loaded dictionary ${match.id} (${dictionary.words.size} words) in ${Date.now() - started}ms`);
+        return dictionary;
+      })
+      .catch((error) => {
+        loaded.delete(match.id);
+        throw ForgeError.from(error, `Failed to load dictionary ${match.id}`);
+      });
+    loaded.set(match.id, loading);
+  }
+  return { id: match.id, dictionary: await loaded.get(match.id) };
+}
+
+// Comments and string literals in code, using the language's comment tokens
+function codeRanges(text, comments, includeStrings) {
+  const ranges = [];
+  const { line, block } = comments;
+  let i = 0;
+  while (i < text.length) {
+    if (block && text.startsWith(block[0], i)) {
+      const end = text.indexOf(block[1], i + block[0].length);
+      ranges.push([i + block[0].length, end === -1 ? text.length : end]);
+      i = end === -1 ? text.length : end + block[1].length;
+    } else if (line && text.startsWith(line, i)) {
+      const end = text.indexOf('\n', i);
+      ranges.push([i + line.length, end === -1 ? text.length : end]);
+      i = end === -1 ? text.length : end;
+    } else if (QUOTES.includes(text[i])) {
+      // Template literals may span lines; other strings end with theirs
+      const quote = text[i];
+      let j = i + 1;
+      while (j < text.length && text[j] !== quote && (quote === '`' || text[j] !== '\n')) j += text[j] === '\\' ? 2 : 1;
+      if (includeStrings) ranges.push([i + 1, Math.min(j, text.length)]);
+      i = j + 1;
+    } else {
+      i++;
+    }
+  }
+  return ranges;
+}
+
+// Markdown prose: everything but code blocks, inline code, link targets and HTML tags
+function markdownRanges(text) {
+  const masked = [];
+  const patterns = [/^(```|~~~)[^\n]*\n[\s\S]*?(?:^\1[^\n]*$|(?![\s\S]))/gm, /`[^`\n]+`/g, /\]\([^)\n]*\)/g, /<[^>\n]+>/g, /^\s*\[[^\]\n]+\]:.*$/gm];
+  for (const pattern of patterns) {
+    let match;
+    while ((match = pattern.exec(text)) !== null) {
+      masked.push([match.index, match.index + match[0].length]);
+      if (match[0].length === 0) pattern.lastIndex++;
+    }
+  }
+  masked.sort((a, b) => a[0] - b[0]);
+  const ranges = [];
+  let position = 0;
+  for (const [start, end] of masked) {
+    if (start > position) ranges.push([position, start]);
+    position = Math.max(position, end);
+  }
+  if (position < text.length) ranges.push([position, text.length]);
+  return ranges;
+}
+
+// [start, end) ranges of `text` worth checking for `languageId`
+function checkableRanges(text, languageId, includeStrings) {
+  if (languageId === 'markdown' || languageId === 'mdx') return markdownRanges(text);
+  if (!languageId || PROSE_LANGUAGES.has(languageId)) return [[0, text.length]];
+  let config;
+  try {
+    config = getLanguageConfig(languageId);
+  } catch {
+    return [[0, text.length]];
+  }
+  return codeRanges(text, config.comments, includeStrings);
+}
+
+// URLs, e-mail addresses and paths aren't words
+const SKIP_PATTERN = /\b[a-z][\w+.-]*:\/\/\S+|[\w.+-]+@[\w-]+\.[\w.-]+|(?:\.{0,2}\/)?(?:[\w.-]+\/)+[\w.-]+/gi;
+const TOKEN_PATTERN = /[\p{L}\p{M}\d_]+(?:['’][\p{L}\p{M}]+)*/gu;
+// Parts of camelCase and PascalCase identifiers
+const PART_PATTERN = /\p{Lu}?[\p{Ll}\p{M}'’]+|\p{Lu}+(?![\p{Ll}])/gu;
+
+// Words to check in text[start, end) as { word, offset }
+function* wordsIn(text, start, end) {
+  const segment = text.slice(start, end);
+  const skipped = [];
+  let match;
+  SKIP_PATTERN.lastIndex = 0;
+  while ((match = SKIP_PATTERN.exec(segment)) !== null) skipped.push([match.index, match.index + match[0].length]);
+
+  TOKEN_PATTERN.lastIndex = 0;
+  while ((match = TOKEN_PATTERN.exec(segment)) !== null) {
+    const token = match[0];
+    const at = match.index;
+    if (/[\d_]/.test(token) || skipped.some(([from, to]) => at >= from && at < to)) continue;
+    // Escapes and commands (\n, \section)
+    if (segment[at - 1] === '\\') continue;
+    PART_PATTERN.lastIndex = 0;
+    let part;
+    while ((part = PART_PATTERN.exec(token)) !== null) {
+      const word = part[0].replace(/['’]+$/, '');
+      // All-caps parts are usually acronyms
+      if (word.length < MIN_WORD_LENGTH || (word.length <= 5 && word === word.toUpperCase())) continue;
+      yield { word, offset: start + at + part.index };
+    }
+  }
+}
+
+function isUserWord(word) {
+  const lower = word.toLowerCase();
+  return store.get('userWords').some((entry) => entry.toLowerCase() === lower);
+}
+
+// 1-based line and column of each offset, for editor markers
+function lineIndex(text) {
+  const starts = [0];
+  for (let i = text.indexOf('\n'); i !== -1; i = text.indexOf('\n', i + 1)) starts.push(i + 1);
+  return (offset) => {
+    let low = 0;
+    let high = starts.length - 1;
+    while (low < high) {
+      const mid = (low + high + 1) >> 1;
+      if (starts[mid] <= offset) low = mid;
+      else high = mid - 1;
+    }
+    return { line: low + 1, column: offset - starts[low] + 1 };
+  };
+}
+
+// Misspelled words in `text`. With a `languageId` for code, only comments and (unless
+// `strings` is false) string literals are checked; markdown skips code and links.
+async function checkText(text, { languageId = null, dictionary: requested = null, strings = true } = {}) {
+  if (typeof text !== 'string') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Text must be a string');
+  if (text.length > MAX_TEXT_LENGTH) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Text is longer than ${MAX_TEXT_LENGTH} characters; check it in parts`);
+  }
+  const { id, dictionary } = await resolveDictionary(requested);
+  const userWords = new Set(store.get('userWords').map((word) => word.toLowerCase()));
+  const position = lineIndex(text);
+  // Each distinct word is looked up once
+  const verdicts = new Map();
+  const misspellings = [];
+  let truncated = false;
+  for (const [start, end] of checkableRanges(text, languageId, strings)) {
+    for (const { word, offset } of wordsIn(text, start, end)) {
+      if (!verdicts.has(word)) verdicts.set(word, userWords.has(word.toLowerCase()) || dictionary.check(word));
+      if (verdicts.get(word)) continue;
+      if (misspellings.length >= MAX_MISSPELLINGS) {
+        truncated = true;
+        break;
+      }
+      misspellings.push({ word, offset, length: word.length, ...position(offset) });
+    }
+    if (truncated) break;
+  }
+  return { dictionary: id, misspellings, truncated };
+}
+
+async function listSuggestions(word, { dictionary: requested = null, limit } = {}) {
+  if (typeof word !== 'string' || !word.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A word is required');
+  const { dictionary } = await resolveDictionary(requested);
+  return dictionary.suggest(word.trim(), limit);
+}
+
+function addToDictionary(word) {
+  if (typeof word !== 'string' || !/^[\p{L}\p{M}'’-]+$/u.test(word)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Only single words can be added: ${word}`);
+  }
+  if (isUserWord(word)) return false;
+  const words = store.get('userWords');
+  if (words.length >= MAX_USER_WORDS) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `The personal dictionary is limited to ${MAX_USER_WORDS} words`);
+  }
+  store.set('userWords', [...words, word].sort((a, b) => a.localeCompare(b)));
+  return true;
+}
+
+function removeFromDictionary(word) {
+  const lower = String(word).toLowerCase();
+  const words = store.get('userWords');
+  const remaining = words.filter((entry) => entry.toLowerCase() !== lower);
+  if (remaining.length === words.length) return false;
+  store.set('userWords', remaining);
+  return true;
+}
+
+// Copy a .dic and the .aff beside it into Forge's own dictionary folder
+async function installDictionary(dicPath) {
+  if (typeof dicPath !== 'string' || !path.isAbsolute(dicPath) || !dicPath.endsWith('.dic')) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Choose a Hunspell .dic file');
+  }
+  const affPath = dicPath.replace(/\.dic$/, '.aff');
+  const [aff, dic] = await Promise.all([fs.readFile(affPath), fs.readFile(dicPath)]).catch((error) => {
+    throw ForgeError.from(error, `A Hunspell dictionary needs ${path.basename(affPath)} next to ${path.basename(dicPath)}`);
+  });
+  // Fail on files that aren't a dictionary before installing them
+  const dictionary = new HunspellDictionary(aff, dic);
+  if (dictionary.words.size === 0) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(dicPath)} has no words`);
+  const id = path.basename(dicPath, '.dic');
+  await fs.mkdir(userDictionaryDir(), { recursive: true });
+  await fs.writeFile(path.join(userDictionaryDir(), `${id}.aff`), aff);
+  await fs.writeFile(path.join(userDictionaryDir(), `${id}.dic`), dic);
+  loaded.delete(id);
+  log.info(`Installed dictionary ${id}`);
+  return { id, path: path.join(userDictionaryDir(), `${id}.dic`), source: 'user' };
+}
+
+function setupSpellcheckHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('spellcheck:checkText', async (event, text, options) => checkText(text, options));
+
+  ipcMain.handle('spellcheck:suggest', async (event, word, options) => listSuggestions(word, options));
+
+  ipcMain.handle('spellcheck:addWord', async (event, word) => addToDictionary(word));
+
+  ipcMain.handle('spellcheck:removeWord', async (event, word) => removeFromDictionary(word));
+
+  ipcMain.handle('spellcheck:listWords', async () => store.get('userWords'));
+
+  ipcMain.handle('spellcheck:listDictionaries', async () => ({ dictionaries: await listDictionaries(), selected: store.get('dictionary') }));
+
+  // null goes back to following the system locale
+  ipcMain.handle('spellcheck:setDictionary', async (event, id) => {
+    if (id !== null) await resolveDictionary(id);
+    store.set('dictionary', id);
+    return id;
+  });
+
+  ipcMain.handle('spellcheck:installDictionary', async (event, dicPath) => installDictionary(dicPath));
+}
+
+module.exports = { setupSpellcheckHandlers, checkText, listSuggestions, addToDictionary };
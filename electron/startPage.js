@@ -0,0 +1,195 @@
+const net = require('net');
+const path = require('path');
+const { protocol } = require('electron');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { listTerminals, getTerminal, getScreenOutput } = require('./terminal');
+const { listHistory, listBookmarks } = require('./browserHistory');
+const { DEEP_LINK_SCHEME } = require('./deepLinks');
+
+const log = createLogger('start-page');
+
+const START_PAGE = `${DEEP_LINK_SCHEME}://start`;
+const SHOWN_RECENT = 12;
+const SHOWN_BOOKMARKS = 30;
+const PROBE_TIMEOUT = 300;
+// Only the end of each terminal's output is searched for server URLs
+const SCAN_TAIL = 200 * 1024;
+// Ports dev servers default to: CRA/Next/Rails, Vite, Angular, Astro, Storybook, Parcel, ...
+const COMMON_PORTS = [3000, 3001, 4000, 4200, 4321, 5000, 5173, 5174, 6006, 8000, 8080, 8081, 8888, 1234, 9000];
+const LOCAL_URL = /https?:\/\/(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1\]):(\d{2,5})(?:\/[^\s'"<>)\]]*)?/g;
+
+const store = new JsonStore('browser-start-page', {
+  // workspace path -> the URL new browser tabs open there
+  projects: {}
+});
+
+const escapeHtml = (text) => String(text).replace(/[&<>"]/g, (char) => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;' })[char]);
+
+function generatedStartPage(workspace, profile) {
+  const params = new URLSearchParams();
+  if (workspace) params.set('workspace', workspace);
+  if (profile && profile !== 'default') params.set('profile', profile);
+  const query = params.toString();
+  return query ? `${START_PAGE}?${query}` : START_PAGE;
+}
+
+// The page new browser tabs open for a workspace: its configured start page, else the
+// generated one
+function startPageFor(workspace, profile) {
+  const configured = workspace ? store.get('projects')[path.resolve(workspace)] : null;
+  return configured || generatedStartPage(workspace, profile);
+}
+
+function setStartPage(workspace, url) {
+  if (!workspace) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A start page belongs to a workspace; open a folder first');
+  const root = path.resolve(workspace);
+  const projects = { ...store.get('projects') };
+  if (url === null || url === '') {
+    delete projects[root];
+  } else {
+    let parsed;
+    try {
+      parsed = new URL(url);
+    } catch {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Not a URL: ${url}`);
+    }
+    if (!['http:', 'https:', 'file:', `${DEEP_LINK_SCHEME}:`].includes(parsed.protocol)) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A start page must be an http(s), file or forge URL');
+    }
+    projects[root] = parsed.href;
+  }
+  store.set('projects', projects);
+  return startPageFor(root);
+}
+
+function isListening(port) {
+  return new Promise((resolve) => {
+    const socket = net.connect({ host: 'localhost', port });
+    const done = (listening) => {
+      socket.destroy();
+      resolve(listening);
+    };
+    socket.setTimeout(PROBE_TIMEOUT, () => done(false));
+    socket.once('connect', () => done(true));
+    socket.once('error', () => done(false));
+  });
+}
+
+const insideWorkspace = (workspace, directory) => {
+  if (!workspace) return true;
+  const relative = path.relative(workspace, directory || '');
+  return !relative.startsWith('..') && !path.isAbsolute(relative);
+};
+
+// Local servers that are up right now: URLs printed by terminals in the workspace, then
+// anything answering on a common dev server port. { url, port, source, terminalId? }
+async function detectDevServers(workspace) {
+  const candidates = new Map();
+  for (const terminal of listTerminals()) {
+    if (!insideWorkspace(workspace, terminal.cwd)) continue;
+    const session = getTerminal(terminal.id);
+    if (!session) continue;
+    const output = getScreenOutput(session, 'normal').slice(-SCAN_TAIL).replace(/\x1b\[[0-9;?]*[A-Za-z]/g, '');
+    for (const match of output.matchAll(LOCAL_URL)) {
+      const port = Number(match[1]);
+      const url = match[0].replace(/0\.0\.0\.0|\[::1\]|127\.0\.0\.1/, 'localhost').replace(/[.,;:]+$/, '');
+      // The most recent mention of a port wins (servers restart on new paths)
+      candidates.set(port, { url, port, source: 'terminal', terminalId: terminal.id });
+    }
+  }
+  // In development the workbench itself is served from :3000
+  const ownPort = process.env.NODE_ENV === 'development' ? 3000 : null;
+  for (const port of COMMON_PORTS) {
+    if (port !== ownPort && !candidates.has(port)) candidates.set(port, { url: `http://localhost:${port}/`, port, source: 'port' });
+  }
+  const found = await Promise.all(Array.from(candidates.values()).map(async (server) => ((await isListening(server.port)) ? server : null)));
+  return found.filter(Boolean).sort((a, b) => (a.source === b.source ? a.port - b.port : a.source === 'terminal' ? -1 : 1));
+}
+
+async function getStartPageData(workspace, profile) {
+  const root = workspace ? path.resolve(workspace) : null;
+  return {
+    workspace: root,
+    devServers: await detectDevServers(root),
+    recent: listHistory({ profile, workspace: root, limit: SHOWN_RECENT }),
+    bookmarks: listBookmarks({ profile }).slice(0, SHOWN_BOOKMARKS)
+  };
+}
+
+function linkList(items, empty, describe) {
+  if (items.length === 0) return `<p class="empty">${escapeHtml(empty)}</p>`;
+  return `<ul>${items.map((item) => {
+    const { href, label, note } = describe(item);
+    return `<li><a href="${escapeHtml(href)}">${escapeHtml(label)}</a>${note ? ` <span>${escapeHtml(note)}</span>` : ''}</li>`;
+  }).join('')}</ul>`;
+}
+
+function renderStartPage({ workspace, devServers, recent, bookmarks }) {
+  const name = workspace ? path.basename(workspace) : 'Forge';
+  return `<!DOCTYPE html>
+<html><head><meta charset="utf-8">
+<meta http-equiv="Content-Security-Policy" content="default-src 'none'; style-src 'unsafe-inline'">
+<title>${escapeHtml(name)} — Start</title><style>
+:root { color-scheme: light dark; font: 14px system-ui, sans-serif; }
+body { max-width: 720px; margin: 48px auto; padding: 0 24px; }
+h1 { font-size: 22px; font-weight: 600; }
+h2 { font-size: 12px; text-transform: uppercase; letter-spacing: .06em; opacity: .6; margin-top: 32px; }
+ul { list-style: none; padding: 0; }
+li { padding: 4px 0; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; }
+a { color: #3b82f6; text-decoration: none; }
+a:hover { text-decoration: underline; }
+span, .empty { opacity: .6; }
+</style></head><body>
+<h1>${escapeHtml(name)}</h1>
+${workspace ? `<p class="empty">${escapeHtml(workspace)}</p>` : ''}
+<h2>Dev servers</h2>
+${linkList(devServers, 'No local server is running. Start one in a terminal and reload this page.', (server) => ({
+  href: server.url,
+  label: server.url,
+  note: server.source === 'terminal' ? 'from a terminal' : `port ${server.port}`
+}))}
+<h2>Recent</h2>
+${linkList(recent, 'Pages you visit from this project will show up here.', (visit) => ({ href: visit.url, label: visit.title || visit.url, note: visit.title ? visit.url : null }))}
+<h2>Bookmarks</h2>
+${linkList(bookmarks, 'No bookmarks yet.', (bookmark) => ({ href: bookmark.url, label: bookmark.title || bookmark.url, note: bookmark.folder || null }))}
+</body></html>`;
+}
+
+async function handleRequest(request) {
+  const url = new URL(request.url);
+  if (url.hostname !== 'start') return new Response('Not found', { status: 404 });
+  try {
+    const html = renderStartPage(await getStartPageData(url.searchParams.get('workspace'), url.searchParams.get('profile')));
+    return new Response(html, { headers: { 'content-type': 'text/html; charset=utf-8', 'cache-control': 'no-store' } });
+  } catch (error) {
+    log.error('Could not build the start page:', error);
+    return new Response('Could not build the start page', { status: 500 });
+  }
+}
+
+function setupStartPageHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  protocol.handle(DEEP_LINK_SCHEME, handleRequest);
+
+  const workspaceOf = (sender) => {
+    const context = windowManager.fromSender(sender);
+    return context ? context.workspacePath : null;
+  };
+
+  ipcMain.handle('browser:getStartPage', async (event, options = {}) => {
+    const workspace = workspaceOf(event.sender);
+    const url = startPageFor(workspace, options.profile);
+    return { url, generated: url === generatedStartPage(workspace, options.profile) };
+  });
+
+  // null goes back to the generated page
+  ipcMain.handle('browser:setStartPage', async (event, url) => setStartPage(workspaceOf(event.sender), url));
+
+  ipcMain.handle('browser:getStartPageData', async (event, options = {}) => getStartPageData(workspaceOf(event.sender), options.profile));
+}
+
+module.exports = { setupStartPageHandlers, startPageFor, detectDevServers };
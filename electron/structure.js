@@ -0,0 +1,327 @@
+const fs = require('fs').promises;
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { detectLanguage, getLanguageConfig } = require('./languages');
+
+const MAX_FILE_SIZE = 50 * 1024 * 1024;
+const MAX_CACHED_DOCUMENTS = 20;
+const OPENERS = { '(': ')', '[': ']', '{': '}' };
+const CLOSERS = { ')': '(', ']': '[', '}': '{' };
+// Brackets and quotes in prose are punctuation, not structure
+const PROSE_LANGUAGES = new Set(['plaintext', 'markdown', 'mdx', 'restructuredtext']);
+// Rust uses ' for lifetimes as well as character literals
+const CHAR_LITERAL = /^'(?:\\(?:u\{[0-9a-fA-F]{1,6}\}|x[0-9a-fA-F]{2}|.)|[^\\'\n])'/;
+const WORD_CHAR = /[\p{L}\p{N}_$]/u;
+const WORD_PATTERN = /^[\p{L}\p{N}_$]*$/u;
+const MAX_WORD_LENGTH = 256;
+
+// Parse `text` into nested brackets, strings and comments. Each node has [start, end)
+// offsets, the [innerStart, innerEnd) between its delimiters, and its children in order.
+// Mismatched closers end the brackets opened after their partner; stray ones are ignored.
+function parseStructure(text, languageId) {
+  let comments = { line: null, block: null };
+  let quotes = [];
+  if (languageId && !PROSE_LANGUAGES.has(languageId)) {
+    try {
+      ({ comments } = getLanguageConfig(languageId));
+      quotes = languageId === 'rust' ? ['"'] : ['"', "'", '`'];
+    } catch {
+      // Unknown language: brackets only
+    }
+  }
+
+  const root = { kind: 'document', open: null, start: 0, end: text.length, innerStart: 0, innerEnd: text.length, children: [] };
+  const stack = [root];
+  const leaf = (kind, start, end, innerStart, innerEnd) => {
+    stack[stack.length - 1].children.push({ kind, start, end, innerStart, innerEnd, children: [] });
+    return end;
+  };
+
+  let i = 0;
+  while (i < text.length) {
+    const char = text[i];
+    if (comments.block && text.startsWith(comments.block[0], i)) {
+      const [open, close] = comments.block;
+      const end = text.indexOf(close, i + open.length);
+      i = end === -1 ? leaf('comment', i, text.length, i + open.length, text.length) : leaf('comment', i, end + close.length, i + open.length, end);
+    } else if (comments.line && text.startsWith(comments.line, i)) {
+      const end = text.indexOf('\n', i);
+      const stop = end === -1 ? text.length : end;
+      i = leaf('comment', i, stop, i + comments.line.length, stop);
+    } else if (quotes.includes(char)) {
+      // Triple-quoted and template strings span lines; others stop at the line's end
+      const delimiter = char !== '`' && text.startsWith(char.repeat(3), i) ? char.repeat(3) : char;
+      const multiline = delimiter.length === 3 || char === '`';
+      let j = i + delimiter.length;
+      while (j < text.length && !text.startsWith(delimiter, j) && (multiline || text[j] !== '\n')) j += text[j] === '\\' ? 2 : 1;
+      j = Math.min(j, text.length);
+      i = text.startsWith(delimiter, j)
+        ? leaf('string', i, j + delimiter.length, i + delimiter.length, j)
+        : leaf('string', i, j, i + delimiter.length, j);
+    } else if (char === "'" && languageId === 'rust') {
+      const literal = CHAR_LITERAL.exec(text.slice(i, i + 12));
+      i = literal ? leaf('string', i, i + literal[0].length, i + 1, i + literal[0].length - 1) : i + 1;
+    } else if (OPENERS[char]) {
+      const node = { kind: 'bracket', open: char, start: i, end: text.length, innerStart: i + 1, innerEnd: text.length, unclosed: true, children: [] };
+      stack[stack.length - 1].children.push(node);
+      stack.push(node);
+      i++;
+    } else if (CLOSERS[char]) {
+      let index = stack.length - 1;
+      while (index > 0 && stack[index].open !== CLOSERS[char]) index--;
+      if (index > 0) {
+        for (let k = stack.length - 1; k > index; k--) {
+          stack[k].end = i;
+          stack[k].innerEnd = i;
+        }
+        Object.assign(stack[index], { end: i + 1, innerEnd: i, unclosed: false });
+        stack.length = index;
+      }
+      i++;
+    } else {
+      i++;
+    }
+  }
+  return root;
+}
+
+function lineStartsOf(text) {
+  const starts = [0];
+  for (let i = text.indexOf('\n'); i !== -1; i = text.indexOf('\n', i + 1)) starts.push(i + 1);
+  return starts;
+}
+
+function offsetAt(document, position) {
+  if (!position || !Number.isInteger(position.line) || !Number.isInteger(position.character)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Positions need an integer line and character');
+  }
+  const { lineStarts, text } = document;
+  if (position.line >= lineStarts.length) return text.length;
+  const start = lineStarts[Math.max(0, position.line)];
+  const lineEnd = position.line + 1 < lineStarts.length ? lineStarts[position.line + 1] - 1 : text.length;
+  return Math.min(start + Math.max(0, position.character), lineEnd);
+}
+
+function positionAt(document, offset) {
+  const { lineStarts } = document;
+  let low = 0;
+  let high = lineStarts.length - 1;
+  while (low < high) {
+    const mid = (low + high + 1) >> 1;
+    if (lineStarts[mid] <= offset) low = mid;
+    else high = mid - 1;
+  }
+  return { line: low, character: offset - lineStarts[low] };
+}
+
+function toRange(document, start, end) {
+  return { start: positionAt(document, start), end: positionAt(document, end) };
+}
+
+function fromRange(document, range) {
+  if (!range || !range.start || !range.end) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A range with start and end is required');
+  const start = offsetAt(document, range.start);
+  const end = offsetAt(document, range.end);
+  return start <= end ? [start, end] : [end, start];
+}
+
+// Nodes containing [start, end), outermost first
+function chainAt(root, start, end) {
+  const chain = [root];
+  let node = root;
+  for (;;) {
+    const { children } = node;
+    // Last child starting at or before `start`
+    let low = 0;
+    let high = children.length - 1;
+    let found = -1;
+    while (low <= high) {
+      const mid = (low + high) >> 1;
+      if (children[mid].start <= start) {
+        found = mid;
+        low = mid + 1;
+      } else {
+        high = mid - 1;
+      }
+    }
+    if (found === -1 || children[found].end < end) return chain;
+    node = children[found];
+    chain.push(node);
+  }
+}
+
+// The comma- or semicolon-separated items between a container's delimiters, like
+// arguments or statements. In braces and at the top level every line break also ends one.
+// A trailing semicolon belongs to its item; commas don't.
+function itemsOf(document, container) {
+  if (container.items) return container.items;
+  const { text } = document;
+  const lineBreaks = container.kind === 'document' || container.open === '{';
+  const items = [];
+  let itemStart = null;
+  let itemEnd = null;
+  const flush = () => {
+    if (itemStart !== null) items.push({ start: itemStart, end: itemEnd });
+    itemStart = null;
+  };
+  let childIndex = 0;
+  let i = container.innerStart;
+  while (i < container.innerEnd) {
+    const child = container.children[childIndex];
+    if (child && child.start === i) {
+      if (itemStart === null) itemStart = i;
+      i = itemEnd = child.end;
+      childIndex++;
+      continue;
+    }
+    const char = text[i];
+    if (char === ';') {
+      if (itemStart !== null) itemEnd = i + 1;
+      flush();
+    } else if (char === ',' || (char === '\n' && lineBreaks)) {
+      flush();
+    } else if (!/\s/.test(char)) {
+      if (itemStart === null) itemStart = i;
+      itemEnd = i + 1;
+    }
+    i++;
+  }
+  flush();
+  container.items = items;
+  return items;
+}
+
+// Contents of [from, to) without surrounding whitespace
+function trimmed(text, from, to) {
+  let start = from;
+  let end = to;
+  while (start < end && /\s/.test(text[start])) start++;
+  while (end > start && /\s/.test(text[end - 1])) end--;
+  return [start, end];
+}
+
+// Ranges around [start, end) from smallest to largest: the word, then for each enclosing
+// node its contents, the item within it and the node itself
+function selectionChain(document, start, end) {
+  const { text, root } = document;
+  const ranges = [];
+  const add = (from, to) => {
+    const last = ranges[ranges.length - 1];
+    if (from <= start && to >= end && !(last && last[0] === from && last[1] === to)) ranges.push([from, to]);
+  };
+
+  let wordStart = start;
+  let wordEnd = end;
+  while (wordStart > 0 && WORD_CHAR.test(text[wordStart - 1])) wordStart--;
+  while (wordEnd < text.length && WORD_CHAR.test(text[wordEnd])) wordEnd++;
+  if (end - start <= MAX_WORD_LENGTH && WORD_PATTERN.test(text.slice(start, end))) add(wordStart, wordEnd);
+
+  const chain = chainAt(root, start, end);
+  for (let index = chain.length - 1; index >= 0; index--) {
+    const node = chain[index];
+    if (node.kind === 'bracket' || node.kind === 'document') {
+      const item = itemsOf(document, node).find((candidate) => candidate.start <= start && candidate.end >= end);
+      if (item) add(item.start, item.end);
+    }
+    add(...trimmed(text, node.innerStart, node.innerEnd));
+    add(node.start, node.end);
+  }
+  return ranges;
+}
+
+// The smallest structural range strictly containing `range`, or null at the whole file
+function expandSelection(document, range) {
+  const [start, end] = fromRange(document, range);
+  const next = selectionChain(document, start, end).find(([from, to]) => from < start || to > end);
+  return next ? toRange(document, next[0], next[1]) : null;
+}
+
+// The bracket at `position` (before or after the cursor) and its partner, else the
+// innermost brackets around it. `close` is null for a bracket that is never closed.
+function matchingBracket(document, position) {
+  const offset = offsetAt(document, position);
+  const brackets = chainAt(document.root, offset, offset).filter((node) => node.kind === 'bracket');
+  const adjacent = brackets.filter((node) => node.start === offset || node.start === offset - 1 ||
+    (!node.unclosed && (node.end === offset || node.end - 1 === offset)));
+  const node = adjacent.length > 0 ? adjacent[adjacent.length - 1] : brackets[brackets.length - 1];
+  if (!node) return null;
+  return {
+    open: positionAt(document, node.start),
+    close: node.unclosed ? null : positionAt(document, node.end - 1)
+  };
+}
+
+// The item before or after the innermost item holding `range`
+function siblingRange(document, range, direction) {
+  if (direction !== 'next' && direction !== 'previous') {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Direction must be "next" or "previous", got ${direction}`);
+  }
+  const [start, end] = fromRange(document, range);
+  const chain = chainAt(document.root, start, end);
+  for (let index = chain.length - 1; index >= 0; index--) {
+    const node = chain[index];
+    if (node.kind !== 'bracket' && node.kind !== 'document') continue;
+    const items = itemsOf(document, node);
+    const at = items.findIndex((item) => item.start <= start && item.end >= end);
+    if (at === -1) continue;
+    const sibling = items[direction === 'next' ? at + 1 : at - 1];
+    return sibling ? toRange(document, sibling.start, sibling.end) : null;
+  }
+  return null;
+}
+
+// Parsed documents by path, least recently used first
+const documents = new Map();
+
+// The parsed document for `filePath`: `text` when the editor has unsaved changes, else
+// the file on disk. Reparsed only when the text changes.
+async function getDocument(filePath, text) {
+  if (typeof filePath !== 'string' || !filePath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A file path is required');
+  const cached = documents.get(filePath);
+  let stats = null;
+  if (typeof text !== 'string') {
+    stats = await fs.stat(filePath).catch((error) => {
+      throw ForgeError.from(error, `Cannot read ${filePath}`);
+    });
+    if (stats.size > MAX_FILE_SIZE) {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${filePath} is larger than ${MAX_FILE_SIZE / 1024 / 1024} MB`);
+    }
+  }
+
+  let document = null;
+  if (cached && (stats ? cached.mtimeMs === stats.mtimeMs && cached.size === stats.size : cached.text === text)) {
+    document = cached;
+  } else {
+    if (stats) text = await fs.readFile(filePath, 'utf-8');
+    const languageId = detectLanguage(filePath, text.slice(0, 256));
+    document = {
+      text,
+      languageId,
+      root: parseStructure(text, languageId),
+      lineStarts: lineStartsOf(text),
+      mtimeMs: stats ? stats.mtimeMs : null,
+      size: stats ? stats.size : null
+    };
+  }
+  documents.delete(filePath);
+  documents.set(filePath, document);
+  if (documents.size > MAX_CACHED_DOCUMENTS) documents.delete(documents.keys().next().value);
+  return document;
+}
+
+function setupStructureHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('structure:expandSelection', async (event, filePath, range, options = {}) => {
+    return expandSelection(await getDocument(filePath, options.text), range);
+  });
+
+  ipcMain.handle('structure:matchingBracket', async (event, filePath, position, options = {}) => {
+    return matchingBracket(await getDocument(filePath, options.text), position);
+  });
+
+  ipcMain.handle('structure:sibling', async (event, filePath, range, direction, options = {}) => {
+    return siblingRange(await getDocument(filePath, options.text), range, direction);
+  });
+}
+
+module.exports = { setupStructureHandlers, parseStructure };
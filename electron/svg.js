@@ -0,0 +1,361 @@
+const fs = require('fs').promises;
+const os = require('os');
+const path = require('path');
+const { spawn } = require('child_process');
+const { BrowserWindow } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('svg');
+
+const MAX_SVG_SIZE = 10 * 1024 * 1024;
+const MAX_DIMENSION = 16384;
+const MAX_PIXELS = 64 * 1000 * 1000;
+const RESVG_TIMEOUT = 30000;
+const RENDER_TIMEOUT = 15000;
+// Browsers' size for an SVG without width, height or viewBox
+const DEFAULT_SIZE = { width: 300, height: 150 };
+const UNIT_PX = { px: 1, pt: 4 / 3, pc: 16, mm: 96 / 25.4, cm: 96 / 2.54, in: 96 };
+
+// Elements that draw, structure or style; anything else (script, foreignObject, iframe,
+// editor metadata...) is dropped along with its content
+const ALLOWED_ELEMENTS = new Set([
+  'svg', 'g', 'defs', 'symbol', 'use', 'switch', 'view', 'a', 'title', 'desc', 'style',
+  'path', 'rect', 'circle', 'ellipse', 'line', 'polyline', 'polygon', 'image',
+  'text', 'tspan', 'textPath',
+  'linearGradient', 'radialGradient', 'stop', 'pattern', 'clipPath', 'mask', 'marker',
+  'animate', 'animateMotion', 'animateTransform', 'mpath', 'set',
+  'filter', 'feBlend', 'feColorMatrix', 'feComponentTransfer', 'feComposite', 'feConvolveMatrix',
+  'feDiffuseLighting', 'feDisplacementMap', 'feDistantLight', 'feDropShadow', 'feFlood',
+  'feFuncA', 'feFuncB', 'feFuncG', 'feFuncR', 'feGaussianBlur', 'feImage', 'feMerge',
+  'feMergeNode', 'feMorphology', 'feOffset', 'fePointLight', 'feSpecularLighting',
+  'feSpotLight', 'feTile', 'feTurbulence'
+]);
+const REFERENCE_ATTRIBUTES = new Set(['href', 'xlink:href', 'src']);
+// Embedded raster images are fine; embedded SVG or HTML could carry script
+const SAFE_DATA_URL = /^data:image\/(?:png|jpe?g|gif|webp|avif|bmp);/i;
+
+function decodeEntities(text) {
+  return text.replace(/&(#x[0-9a-f]+|#\d+|amp|lt|gt|quot|apos);/gi, (match, entity) => {
+    if (entity[0] === '#') {
+      const code = entity[1].toLowerCase() === 'x' ? parseInt(entity.slice(2), 16) : Number(entity.slice(1));
+      return code > 0 && code <= 0x10ffff ? String.fromCodePoint(code) : '';
+    }
+    return { amp: '&', lt: '<', gt: '>', quot: '"', apos: "'" }[entity.toLowerCase()];
+  });
+}
+
+function escapeText(text) {
+  return text.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;');
+}
+
+function escapeAttribute(value) {
+  return escapeText(value).replace(/"/g, '&quot;');
+}
+
+function isSafeReference(value) {
+  const trimmed = value.trim();
+  return trimmed.startsWith('#') || SAFE_DATA_URL.test(trimmed);
+}
+
+// CSS with @import and url()s pointing outside the document removed
+function sanitizeCss(css, removed) {
+  return css
+    .replace(/@import\b[^;]*;?/gi, () => {
+      removed('css', '@import');
+      return '';
+    })
+    .replace(/url\(\s*(['"]?)(.*?)\1\s*\)/gi, (match, quote, target) => {
+      if (isSafeReference(target)) return match;
+      removed('css', 'url()');
+      return 'none';
+    })
+    .replace(/expression\s*\(/gi, () => {
+      removed('css', 'expression()');
+      return '(';
+    });
+}
+
+// Splits markup into tags, text, comments, CDATA, doctypes and processing instructions
+function* tokenize(text) {
+  const pattern = /<!--[\s\S]*?(?:-->|$)|<!\[CDATA\[([\s\S]*?)(?:\]\]>|$)|<!DOCTYPE(?:[^[>]|\[[\s\S]*?\])*>|<\?([\s\S]*?)\?>|<\/\s*([^\s>]+)\s*>|<([^\s/>!?]+)((?:\s+[^\s=/>]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+))?)*)\s*(\/?)>/gi;
+  let position = 0;
+  let match;
+  while ((match = pattern.exec(text))) {
+    if (match.index > position) yield { type: 'text', value: text.slice(position, match.index) };
+    position = pattern.lastIndex;
+    const raw = match[0];
+    if (raw.startsWith('<!--')) yield { type: 'comment' };
+    else if (raw.startsWith('<![CDATA[')) yield { type: 'cdata', value: match[1] };
+    else if (/^<!DOCTYPE/i.test(raw)) yield { type: 'doctype' };
+    else if (raw.startsWith('<?')) yield { type: 'instruction', value: match[2] };
+    else if (match[3]) yield { type: 'end', name: match[3] };
+    else {
+      const attributes = [];
+      const attributePattern = /([^\s=/>]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+)))?/g;
+      let attribute;
+      while ((attribute = attributePattern.exec(match[5]))) {
+        attributes.push({ name: attribute[1], value: decodeEntities(attribute[2] ?? attribute[3] ?? attribute[4] ?? '') });
+      }
+      yield { type: 'start', name: match[4], attributes, selfClosing: match[6] === '/' };
+    }
+  }
+  if (position < text.length) yield { type: 'text', value: text.slice(position) };
+}
+
+function localName(name) {
+  const colon = name.indexOf(':');
+  return colon < 0 ? name : name.slice(colon + 1);
+}
+
+// null when the attribute must go, else its (possibly rewritten) value
+function sanitizeAttribute(element, { name, value }, removed) {
+  const lower = name.toLowerCase();
+  if (lower.startsWith('on')) {
+    removed('attribute', name);
+    return null;
+  }
+  if (REFERENCE_ATTRIBUTES.has(lower) || lower.endsWith(':href')) {
+    if (isSafeReference(value)) return value;
+    removed('reference', `${element} ${name}`);
+    return null;
+  }
+  // Animations can rewrite links and handlers after sanitizing
+  if ((element === 'animate' || element === 'set') && lower === 'attributename' && /href|^on/i.test(value)) {
+    removed('attribute', `${element} ${name}="${value}"`);
+    return null;
+  }
+  if (/^\s*(?:javascript|vbscript):/i.test(value)) {
+    removed('attribute', name);
+    return null;
+  }
+  if (lower === 'style' || /url\(/i.test(value)) return sanitizeCss(value, removed);
+  return value;
+}
+
+// Rewrites an SVG so it can be shown inline or as an image without running script or
+// loading anything: only drawing elements are kept, event handlers and external references
+// (links, images, CSS imports and url()s) are removed, and DOCTYPEs (entity expansion) and
+// processing instructions dropped. Resolves { svg, removed: [{ kind, name, count }] }.
+function sanitizeSvg(text) {
+  if (typeof text !== 'string') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'SVG content must be a string');
+  const counts = new Map();
+  const removed = (kind, name) => {
+    const key = `${kind}\0${name}`;
+    counts.set(key, (counts.get(key) || 0) + 1);
+  };
+  const output = [];
+  const open = [];
+  let skipDepth = 0;
+  let sawRoot = false;
+  for (const token of tokenize(text.replace(/^\uFEFF/, ''))) {
+    if (skipDepth) {
+      if (token.type === 'start' && !token.selfClosing) skipDepth++;
+      else if (token.type === 'end') skipDepth--;
+      continue;
+    }
+    switch (token.type) {
+      case 'text':
+      case 'cdata': {
+        const value = token.type === 'text' ? decodeEntities(token.value) : token.value;
+        output.push(escapeText(open[open.length - 1] === 'style' ? sanitizeCss(value, removed) : value));
+        break;
+      }
+      case 'comment':
+        break;
+      case 'doctype':
+        removed('doctype', 'DOCTYPE');
+        break;
+      case 'instruction':
+        if (/^xml\s/.test(token.value)) output.push(`<?${token.value}?>`);
+        else removed('instruction', token.value.split(/\s/)[0]);
+        break;
+      case 'start': {
+        const name = localName(token.name);
+        if (!sawRoot && name !== 'svg') throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Not an SVG document');
+        sawRoot = true;
+        if (!ALLOWED_ELEMENTS.has(name)) {
+          removed('element', token.name);
+          if (!token.selfClosing) skipDepth = 1;
+          break;
+        }
+        const attributes = [];
+        for (const attribute of token.attributes) {
+          const value = sanitizeAttribute(name, attribute, removed);
+          if (value !== null) attributes.push(` ${attribute.name}="${escapeAttribute(value)}"`);
+        }
+        output.push(`<${token.name}${attributes.join('')}${token.selfClosing ? '/' : ''}>`);
+        if (!token.selfClosing) open.push(name);
+        break;
+      }
+      case 'end':
+        if (open.length) {
+          open.pop();
+          output.push(`</${token.name}>`);
+        }
+        break;
+    }
+  }
+  if (!sawRoot) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Not an SVG document');
+  // Text inside <style> was escaped above; CSS needs its literal > and & back
+  const svg = output.join('').replace(/(<style\b[^>]*>)([\s\S]*?)(<\/style>)/g, (match, start, css, end) => `${start}<![CDATA[${decodeEntities(css).replace(/\]\]>/g, '')}]]>${end}`);
+  return {
+    svg,
+    removed: Array.from(counts, ([key, count]) => {
+      const [kind, name] = key.split('\0');
+      return { kind, name, count };
+    })
+  };
+}
+
+function parseLength(value) {
+  const match = /^\s*([\d.]+(?:e[+-]?\d+)?)\s*(px|pt|pc|mm|cm|in)?\s*$/i.exec(value || '');
+  if (!match) return null;
+  const length = Number(match[1]) * UNIT_PX[(match[2] || 'px').toLowerCase()];
+  return Number.isFinite(length) && length > 0 ? length : null;
+}
+
+// CSS pixel size from the root's width / height, falling back to the viewBox's aspect ratio
+function intrinsicSize(svg) {
+  let start;
+  for (const token of tokenize(svg)) {
+    if (token.type === 'start') {
+      start = token;
+      break;
+    }
+  }
+  const attribute = (name) => (start.attributes.find((entry) => entry.name === name) || {}).value;
+  let width = parseLength(attribute('width'));
+  let height = parseLength(attribute('height'));
+  const viewBox = (attribute('viewBox') || '').trim().split(/[\s,]+/).map(Number);
+  const hasViewBox = viewBox.length === 4 && viewBox.every(Number.isFinite) && viewBox[2] > 0 && viewBox[3] > 0;
+  if (hasViewBox) {
+    if (!width && !height) {
+      width = viewBox[2];
+      height = viewBox[3];
+    } else if (!height) height = (width * viewBox[3]) / viewBox[2];
+    else if (!width) width = (height * viewBox[2]) / viewBox[3];
+  }
+  return { width: width || DEFAULT_SIZE.width, height: height || DEFAULT_SIZE.height };
+}
+
+// Output size: both dimensions given as is, one given keeps the aspect ratio, neither
+// scales the intrinsic size
+function targetSize(intrinsic, { width, height, scale = 1 }) {
+  let size;
+  if (width && height) size = { width, height };
+  else if (width) size = { width, height: (width * intrinsic.height) / intrinsic.width };
+  else if (height) size = { width: (height * intrinsic.width) / intrinsic.height, height };
+  else size = { width: intrinsic.width * scale, height: intrinsic.height * scale };
+  size = { width: Math.max(1, Math.round(size.width)), height: Math.max(1, Math.round(size.height)) };
+  if (!Number.isFinite(size.width) || !Number.isFinite(size.height) || size.width > MAX_DIMENSION || size.height > MAX_DIMENSION || size.width * size.height > MAX_PIXELS) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${size.width}x${size.height} is too large to rasterize (at most ${MAX_DIMENSION} pixels a side)`);
+  }
+  return size;
+}
+
+function runResvg(svg, { width, height }, background) {
+  return new Promise((resolve, reject) => {
+    const args = ['--width', String(width), '--height', String(height)];
+    if (background) args.push('--background', background);
+    const child = spawn('resvg', [...args, '-', '-c'], { windowsHide: true, timeout: RESVG_TIMEOUT });
+    const stdout = [];
+    let stderr = '';
+    child.stdout.on('data', (chunk) => stdout.push(chunk));
+    child.stderr.on('data', (chunk) => { stderr += chunk; });
+    child.on('error', reject);
+    child.on('close', (code) => {
+      if (code === 0) resolve(Buffer.concat(stdout));
+      else reject(new ForgeError(ErrorCode.INVALID_ARGUMENT, `resvg could not render the SVG: ${stderr.trim() || `exit code ${code}`}`));
+    });
+    child.stdin.on('error', () => {});
+    child.stdin.end(svg);
+  });
+}
+
+// Chromium fallback: the sanitized SVG as an <img> (which never runs script or fetches
+// anything) in a hidden, script-less window, captured at exactly the requested size
+async function renderWithChromium(svg, { width, height }, background) {
+  const dir = path.join(os.tmpdir(), `forge-svg-${uuidv4()}`);
+  await fs.mkdir(dir);
+  await fs.writeFile(path.join(dir, 'image.svg'), svg, 'utf-8');
+  await fs.writeFile(path.join(dir, 'index.html'), `<!DOCTYPE html><html><head><meta charset="utf-8"><style>
+html, body { margin: 0; overflow: hidden; background: ${background ? escapeAttribute(background) : 'transparent'}; }
+img { display: block; width: ${width}px; height: ${height}px; }
+</style></head><body><img src="image.svg"></body></html>`, 'utf-8');
+  const win = new BrowserWindow({
+    show: false,
+    width,
+    height,
+    useContentSize: true,
+    transparent: !background,
+    backgroundColor: '#00000000',
+    enableLargerThanScreen: true,
+    webPreferences: { javascript: false, sandbox: true, offscreen: true }
+  });
+  try {
+    let timer;
+    const timeout = new Promise((resolve, reject) => {
+      timer = setTimeout(() => reject(new ForgeError(ErrorCode.TIMEOUT, `The SVG did not render within ${RENDER_TIMEOUT / 1000}s`)), RENDER_TIMEOUT);
+    });
+    await Promise.race([win.loadFile(path.join(dir, 'index.html')), timeout]).finally(() => clearTimeout(timer));
+    let image = await win.webContents.capturePage({ x: 0, y: 0, width, height });
+    // The capture is in device pixels
+    const captured = image.getSize();
+    if (captured.width !== width || captured.height !== height) image = image.resize({ width, height, quality: 'best' });
+    return image.toPNG();
+  } finally {
+    win.destroy();
+    fs.rm(dir, { recursive: true, force: true }).catch(() => {});
+  }
+}
+
+async function readSvg(filePath, content) {
+  if (typeof content === 'string') {
+    if (content.length > MAX_SVG_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'The SVG is too large');
+    return content;
+  }
+  if (typeof filePath !== 'string' || !path.isAbsolute(filePath)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'An absolute file path is required');
+  const stats = await fs.stat(filePath).catch((error) => {
+    throw ForgeError.from(error, `Cannot read ${filePath}`);
+  });
+  if (stats.size > MAX_SVG_SIZE) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${path.basename(filePath)} is too large to preview`);
+  return fs.readFile(filePath, 'utf-8');
+}
+
+// Sanitizes, then renders a PNG with resvg when it's on PATH, else with Chromium.
+// `width` / `height` in pixels, or `scale` times the SVG's own size.
+async function rasterizeSvg(text, options = {}) {
+  const { svg } = sanitizeSvg(text);
+  const size = targetSize(intrinsicSize(svg), options);
+  const background = typeof options.background === 'string' && /^[#\w(),.%\s-]+$/.test(options.background) ? options.background : null;
+  let png;
+  let renderer = 'resvg';
+  try {
+    png = await runResvg(svg, size, background);
+  } catch (error) {
+    if (error.code !== 'ENOENT') throw error;
+    renderer = 'chromium';
+    png = await renderWithChromium(svg, size, background);
+  }
+  log.debug(`Rasterized SVG at ${size.width}x${size.height} with ${renderer}`);
+  return { png, width: png.readUInt32BE(16), height: png.readUInt32BE(20), renderer };
+}
+
+function setupSvgHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // `content` is the editor's text, for unsaved changes
+  ipcMain.handle('svg:sanitize', async (event, filePath, options = {}) => sanitizeSvg(await readSvg(filePath, options.content)));
+  ipcMain.handle('svg:rasterize', async (event, filePath, options = {}) => rasterizeSvg(await readSvg(filePath, options.content), options));
+  ipcMain.handle('svg:exportPng', async (event, filePath, outputPath, options = {}) => {
+    if (typeof outputPath !== 'string' || !path.isAbsolute(outputPath)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'outputPath must be an absolute path');
+    const { png, width, height, renderer } = await rasterizeSvg(await readSvg(filePath, options.content), options);
+    await fs.mkdir(path.dirname(outputPath), { recursive: true });
+    await fs.writeFile(outputPath, png);
+    return { path: outputPath, width, height, renderer };
+  });
+}
+
+module.exports = { setupSvgHandlers, sanitizeSvg, rasterizeSvg };
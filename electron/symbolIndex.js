@@ -0,0 +1,255 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { createLogger } = require('./logger');
+const { withForgeErrors } = require('./errors');
+const { readTextFile } = require('./search');
+const { indexEvents, ensureIndex, getIndexedFiles } = require('./fileIndex');
+const { fuzzyScore } = require('./fuzzy');
+const { getScope } = require('./indexScope');
+const { configHash, loadCache, saveCache, deleteCache, debounceSave, emitIndexStatus } = require('./indexCache');
+
+const log = createLogger('symbol-index');
+
+// Lightweight declaration patterns per extension: [kind, RegExp with the name in group 1].
+// Good enough for quick-open; precise symbols come from the language server.
+const JS_PATTERNS = [
+  ['function', /^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)/],
+  ['class', /^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+([A-Za-z_$][\w$]*)/],
+  ['interface', /^\s*(?:export\s+)?interface\s+([A-Za-z_$][\w$]*)/],
+  ['type', /^\s*(?:export\s+)?type\s+([A-Za-z_$][\w$]*)\s*(?:<[^=]*>)?\s*=/],
+  ['enum', /^\s*(?:export\s+)?(?:const\s+)?enum\s+([A-Za-z_$][\w$]*)/],
+  ['function', /^\s*(?:export\s+)?const\s+([A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:\([^)]*\)|[A-Za-z_$][\w$]*)\s*(?::[^=]+)?=>/],
+  ['constant', /^(?:export\s+)?const\s+([A-Z][A-Z0-9_]+)\s*=/]
+];
+
+const SYMBOL_PATTERNS = {
+  '.js': JS_PATTERNS,
+  '.jsx': JS_PATTERNS,
+  '.mjs': JS_PATTERNS,
+  '.cjs': JS_PATTERNS,
+  '.ts': JS_PATTERNS,
+  '.tsx': JS_PATTERNS,
+  '.rs': [
+    ['function', /^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?fn\s+([A-Za-z_]\w*)/],
+    ['struct', /^\s*(?:pub(?:\([^)]*\))?\s+)?struct\s+([A-Za-z_]\w*)/],
+    ['enum', /^\s*(?:pub(?:\([^)]*\))?\s+)?enum\s+([A-Za-z_]\w*)/],
+    ['interface', /^\s*(?:pub(?:\([^)]*\))?\s+)?trait\s+([A-Za-z_]\w*)/],
+    ['type', /^\s*(?:pub(?:\([^)]*\))?\s+)?type\s+([A-Za-z_]\w*)/],
+    ['module', /^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+([A-Za-z_]\w*)/],
+    ['constant', /^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const|static)\s+([A-Z_][A-Z0-9_]*)/]
+  ],
+  '.py': [
+    ['function', /^\s*(?:async\s+)?def\s+([A-Za-z_]\w*)/],
+    ['class', /^\s*class\s+([A-Za-z_]\w*)/]
+  ],
+  '.go': [
+    ['function', /^func\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)/],
+    ['type', /^type\s+([A-Za-z_]\w*)/]
+  ]
+};
+
+// Workspace root -> { root, files: Map<file path, symbol[]>, fingerprints: Map<file path, { mtimeMs, size }>,
+//                     state, stale, fromCache, savedAt, save }
+const indexes = new Map();
+
+// Changing the patterns changes what's extracted, so they're part of the cache key
+const PATTERNS_HASH = configHash(Object.entries(SYMBOL_PATTERNS).map(([extension, patterns]) => [extension, patterns.map(([kind, pattern]) => `${kind}:${pattern.source}`)]));
+
+function cacheHash(root) {
+  const scope = getScope(root);
+  return configHash(PATTERNS_HASH, scope.include, scope.exclude);
+}
+
+function extractSymbols(filePath, text) {
+  const patterns = SYMBOL_PATTERNS[path.extname(filePath).toLowerCase()];
+  if (!patterns) return [];
+  const symbols = [];
+  const lines = text.split('\n');
+  for (let i = 0; i < lines.length; i++) {
+    for (const [kind, pattern] of patterns) {
+      const match = pattern.exec(lines[i]);
+      if (match) {
+        symbols.push({ name: match[1], kind, path: filePath, line: i + 1, column: lines[i].indexOf(match[1]) + 1 });
+        break;
+      }
+    }
+  }
+  return symbols;
+}
+
+function reportStatus(index, extra = {}) {
+  emitIndexStatus({
+    rootPath: index.root,
+    index: 'symbols',
+    state: index.state,
+    stale: index.stale,
+    fromCache: index.fromCache,
+    processed: null,
+    total: null,
+    count: index.files.size,
+    savedAt: index.savedAt,
+    ...extra
+  });
+}
+
+// Re-extract a file's symbols unless its mtime and size match what was indexed
+async function indexFile(rootPath, filePath) {
+  const index = indexes.get(rootPath);
+  if (!index || !SYMBOL_PATTERNS[path.extname(filePath).toLowerCase()]) return false;
+  let stats;
+  try {
+    stats = await fs.stat(filePath);
+  } catch {
+    return removeFile(index, filePath);
+  }
+  const known = index.fingerprints.get(filePath);
+  if (known && known.mtimeMs === stats.mtimeMs && known.size === stats.size && index.files.has(filePath)) return false;
+
+  const text = await readTextFile(filePath);
+  if (text === null) return removeFile(index, filePath);
+  index.files.set(filePath, extractSymbols(filePath, text));
+  index.fingerprints.set(filePath, { mtimeMs: stats.mtimeMs, size: stats.size });
+  index.save();
+  return true;
+}
+
+function removeFile(index, filePath) {
+  index.fingerprints.delete(filePath);
+  if (!index.files.delete(filePath)) return false;
+  index.save();
+  return true;
+}
+
+async function loadSymbolCache(index) {
+  const cached = await loadCache(index.root, 'symbols', cacheHash(index.root));
+  if (!cached) return;
+  // Stored compactly as [name, kind, line, column] under relative paths
+  for (const [relativePath, entry] of Object.entries(cached.data.files)) {
+    const filePath = path.join(index.root, relativePath);
+    index.files.set(filePath, entry.symbols.map(([name, kind, line, column]) => ({ name, kind, path: filePath, line, column })));
+    index.fingerprints.set(filePath, { mtimeMs: entry.mtimeMs, size: entry.size });
+  }
+  Object.assign(index, { fromCache: true, stale: true, savedAt: cached.savedAt });
+  reportStatus(index);
+}
+
+async function saveSymbolCache(index) {
+  if (index.state !== 'ready' || indexes.get(index.root) !== index) return;
+  const files = {};
+  for (const [filePath, symbols] of index.files) {
+    const fingerprint = index.fingerprints.get(filePath);
+    if (!fingerprint) continue;
+    files[path.relative(index.root, filePath)] = {
+      mtimeMs: fingerprint.mtimeMs,
+      size: fingerprint.size,
+      symbols: symbols.map((symbol) => [symbol.name, symbol.kind, symbol.line, symbol.column])
+    };
+  }
+  index.savedAt = (await saveCache(index.root, 'symbols', cacheHash(index.root), { files })) || index.savedAt;
+}
+
+// Bring the index in line with the file index: re-extract changed files, drop removed ones
+async function buildSymbolIndex(index) {
+  const files = getIndexedFiles(index.root).filter((filePath) => SYMBOL_PATTERNS[path.extname(filePath).toLowerCase()]);
+  index.state = index.fromCache ? 'verifying' : 'indexing';
+  reportStatus(index, { processed: 0, total: files.length });
+
+  const present = new Set(files);
+  for (const filePath of Array.from(index.files.keys())) {
+    if (!present.has(filePath)) removeFile(index, filePath);
+  }
+  let extracted = 0;
+  for (let i = 0; i < files.length; i++) {
+    if (indexes.get(index.root) !== index) return;
+    if (await indexFile(index.root, files[i])) extracted++;
+    if ((i + 1) % 500 === 0) reportStatus(index, { processed: i + 1, total: files.length });
+  }
+
+  index.state = 'ready';
+  index.stale = false;
+  await index.save.flush();
+  reportStatus(index, { processed: files.length, total: files.length });
+  log.info(`Indexed symbols in ${files.length} files under ${index.root} (${extracted} re-extracted)`);
+}
+
+function ensureSymbolIndex(rootPath) {
+  const root = path.resolve(rootPath);
+  if (indexes.has(root)) return indexes.get(root).files;
+
+  const index = { root, files: new Map(), fingerprints: new Map(), state: 'loading', stale: false, fromCache: false, savedAt: null, save: null };
+  index.save = debounceSave(() => saveSymbolCache(index));
+  indexes.set(root, index);
+
+  const fileIndex = ensureIndex(root);
+  loadSymbolCache(index)
+    .then(() => fileIndex.ready)
+    .then(() => buildSymbolIndex(index))
+    .catch((error) => log.warn(`Symbol indexing failed for ${root}: ${error.message}`));
+  return index.files;
+}
+
+function getSymbolIndexStatus(rootPath) {
+  const index = indexes.get(path.resolve(rootPath));
+  if (!index) return { rootPath: path.resolve(rootPath), state: 'closed', stale: false, fromCache: false, fileCount: 0, savedAt: null };
+  return { rootPath: index.root, state: index.state, stale: index.stale, fromCache: index.fromCache, fileCount: index.files.size, savedAt: index.savedAt };
+}
+
+indexEvents.on('added', (root, filePath) => indexFile(root, filePath));
+indexEvents.on('changed', (root, filePath) => indexFile(root, filePath));
+indexEvents.on('removed', (root, filePath) => {
+  const index = indexes.get(root);
+  if (index) removeFile(index, filePath);
+});
+indexEvents.on('closed', (root) => {
+  const index = indexes.get(root);
+  if (!index) return;
+  indexes.delete(root);
+  index.save.cancel();
+});
+indexEvents.on('cacheCleared', (root) => deleteCache(root, 'symbols'));
+
+function querySymbols(rootPath, query, limit = 50) {
+  const root = path.resolve(rootPath);
+  const symbols = ensureSymbolIndex(root);
+  const results = [];
+  for (const fileSymbols of symbols.values()) {
+    for (const symbol of fileSymbols) {
+      const match = fuzzyScore(query, symbol.name);
+      if (match) {
+        results.push({ ...symbol, relativePath: path.relative(root, symbol.path).split(path.sep).join('/'), score: match.score, positions: match.positions });
+      }
+    }
+  }
+  return results.sort((a, b) => b.score - a.score).slice(0, limit);
+}
+
+// Exact-name lookup, for callers that already know the identifiers they want
+function lookupSymbols(rootPath, names) {
+  const wanted = new Set(names);
+  const results = [];
+  for (const fileSymbols of ensureSymbolIndex(path.resolve(rootPath)).values()) {
+    for (const symbol of fileSymbols) {
+      if (wanted.has(symbol.name)) results.push(symbol);
+    }
+  }
+  return results;
+}
+
+function setupSymbolIndexHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('symbols:query', async (event, rootPath, query, limit) => {
+    return querySymbols(rootPath, query, limit);
+  });
+
+  ipcMain.handle('symbols:getStatus', async (event, rootPath) => {
+    return getSymbolIndexStatus(rootPath);
+  });
+
+  ipcMain.handle('symbols:getFileSymbols', async (event, filePath) => {
+    const text = await readTextFile(filePath);
+    return text === null ? [] : extractSymbols(filePath, text);
+  });
+}
+
+module.exports = { setupSymbolIndexHandlers, ensureSymbolIndex, querySymbols, lookupSymbols, extractSymbols, getSymbolIndexStatus };
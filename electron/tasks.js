@@ -0,0 +1,418 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { spawn, execFile } = require('child_process');
+const { v4: uuidv4 } = require('uuid');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { runJob, cancelJob } = require('./jobs');
+const { guardTask } = require('./crash');
+const { getRestrictions } = require('./trust');
+const { windowManager } = require('./windows');
+const { parseToml, TomlError } = require('./toml');
+const { getMatcher } = require('./problemMatchers');
+const { createCollector, clearProblems } = require('./problems');
+
+const log = createLogger('tasks');
+
+const CONFIG_FILE = path.join('.forge', 'tasks.toml');
+const FAILURE_POLICIES = ['stop', 'continue'];
+const MAX_RECENT_RUNS = 20;
+const KILL_GRACE_MS = 3000;
+
+// Finished and running pipeline runs, newest last
+const runs = new Map();
+
+function invalid(message, details) {
+  return new ForgeError(ErrorCode.INVALID_ARGUMENT, `${CONFIG_FILE}: ${message}`, { details });
+}
+
+function stringList(value, field) {
+  if (value === undefined) return [];
+  const list = typeof value === 'string' ? [value] : value;
+  if (!Array.isArray(list) || list.some((item) => typeof item !== 'string')) throw invalid(`${field} must be a list of names`);
+  return list;
+}
+
+function envTable(value, field) {
+  if (value === undefined) return {};
+  if (!value || typeof value !== 'object' || Array.isArray(value)) throw invalid(`${field} must be a table`);
+  return Object.fromEntries(Object.entries(value).map(([key, item]) => [key, String(item)]));
+}
+
+// Read and validate .forge/tasks.toml:
+//
+//   [env]                      # for every task
+//   [tasks.<name>]             # command, cwd, env, depends_on, timeout (s), retries, continue_on_error,
+//                              # problem_matcher (built-in or custom matcher ids)
+//   [pipelines.<name>]         # steps (a nested list is a parallel group), env, on_failure = "stop" | "continue"
+//
+// Returns null when the workspace has no tasks file.
+async function loadTaskConfig(rootPath) {
+  const file = path.join(rootPath, CONFIG_FILE);
+  let text;
+  try {
+    text = await fs.readFile(file, 'utf-8');
+  } catch (error) {
+    if (error.code === 'ENOENT') return null;
+    throw ForgeError.from(error, `Could not read ${CONFIG_FILE}`);
+  }
+  let raw;
+  try {
+    raw = parseToml(text);
+  } catch (error) {
+    if (error instanceof TomlError) throw invalid(error.message, { line: error.line });
+    throw error;
+  }
+
+  const tasks = {};
+  for (const [name, task] of Object.entries(raw.tasks || {})) {
+    if (!task || typeof task.command !== 'string' || !task.command.trim()) throw invalid(`task "${name}" needs a command`);
+    const cwd = path.resolve(rootPath, task.cwd || '.');
+    const relative = path.relative(rootPath, cwd);
+    if (relative.startsWith('..') || path.isAbsolute(relative)) throw invalid(`task "${name}" cwd must stay inside the workspace`);
+    tasks[name] = {
+      name,
+      command: task.command,
+      cwd,
+      env: envTable(task.env, `tasks.${name}.env`),
+      dependsOn: stringList(task.depends_on, `tasks.${name}.depends_on`),
+      timeout: typeof task.timeout === 'number' && task.timeout > 0 ? task.timeout * 1000 : null,
+      retries: Number.isInteger(task.retries) && task.retries > 0 ? task.retries : 0,
+      continueOnError: task.continue_on_error === true,
+      problemMatchers: stringList(task.problem_matcher, `tasks.${name}.problem_matcher`)
+    };
+    for (const matcherId of tasks[name].problemMatchers) {
+      try {
+        getMatcher(matcherId);
+      } catch {
+        throw invalid(`task "${name}" uses unknown problem matcher "${matcherId}"`);
+      }
+    }
+  }
+  for (const task of Object.values(tasks)) {
+    for (const dependency of task.dependsOn) {
+      if (!tasks[dependency]) throw invalid(`task "${task.name}" depends on unknown task "${dependency}"`);
+    }
+  }
+
+  const pipelines = {};
+  for (const [name, pipeline] of Object.entries(raw.pipelines || {})) {
+    if (!pipeline || !Array.isArray(pipeline.steps) || pipeline.steps.length === 0) throw invalid(`pipeline "${name}" needs steps`);
+    const steps = pipeline.steps.map((step) => stringList(step, `pipelines.${name}.steps`));
+    for (const taskName of steps.flat()) {
+      if (!tasks[taskName]) throw invalid(`pipeline "${name}" uses unknown task "${taskName}"`);
+    }
+    const onFailure = pipeline.on_failure || 'stop';
+    if (!FAILURE_POLICIES.includes(onFailure)) throw invalid(`pipeline "${name}" on_failure must be one of: ${FAILURE_POLICIES.join(', ')}`);
+    pipelines[name] = {
+      name,
+      description: typeof pipeline.description === 'string' ? pipeline.description : null,
+      steps,
+      env: envTable(pipeline.env, `pipelines.${name}.env`),
+      onFailure
+    };
+  }
+
+  return { file, rootPath, env: envTable(raw.env, 'env'), tasks, pipelines };
+}
+
+// Expand steps into a dependency graph: each step waits for the whole previous step, and
+// every task pulls in its depends_on. Returns Map<name, Set<name it waits for>>.
+function buildGraph(config, steps) {
+  const graph = new Map();
+  const include = (name) => {
+    if (graph.has(name)) return;
+    const waits = new Set(config.tasks[name].dependsOn);
+    graph.set(name, waits);
+    for (const dependency of waits) include(dependency);
+  };
+
+  let previous = [];
+  for (const group of steps) {
+    for (const name of group) {
+      include(name);
+      for (const before of previous) if (before !== name) graph.get(name).add(before);
+    }
+    previous = group;
+  }
+  const state = new Map();
+  const visit = (name, trail) => {
+    if (state.get(name) === 'done') return;
+    if (state.get(name) === 'active') throw invalid(`dependency cycle: ${[...trail, name].join(' -> ')}`);
+    state.set(name, 'active');
+    for (const dependency of graph.get(name)) visit(dependency, [...trail, name]);
+    state.set(name, 'done');
+  };
+  for (const name of graph.keys()) visit(name, []);
+  return graph;
+}
+
+function expandVariables(value, rootPath) {
+  return value.replace(/\$\{workspaceFolder\}/g, rootPath);
+}
+
+function killTree(child) {
+  if (child.exitCode !== null || child.killed) return;
+  if (process.platform === 'win32') {
+    execFile('taskkill', ['/pid', String(child.pid), '/T', '/F'], () => {});
+    return;
+  }
+  // Tasks run in their own process group so the shell's children go too
+  try {
+    process.kill(-child.pid, 'SIGTERM');
+  } catch {
+    child.kill('SIGTERM');
+  }
+  setTimeout(() => {
+    if (child.exitCode === null) {
+      try {
+        process.kill(-child.pid, 'SIGKILL');
+      } catch {
+        // Already gone
+      }
+    }
+  }, KILL_GRACE_MS).unref();
+}
+
+// Run one attempt of a task; resolves its exit code (null if killed)
+function spawnTask(run, task, env, signal) {
+  return new Promise((resolve) => {
+    const child = spawn(task.command, {
+      cwd: task.cwd,
+      env,
+      shell: true,
+      detached: process.platform !== 'win32',
+      windowsHide: true,
+      stdio: ['ignore', 'pipe', 'pipe']
+    });
+    // Separate collectors so interleaved stdout/stderr lines don't split a match
+    const collectors = task.problemMatchers.length > 0 ? {
+      stdout: createCollector(`task:${task.name}`, task.problemMatchers, task.cwd),
+      stderr: createCollector(`task:${task.name}`, task.problemMatchers, task.cwd)
+    } : null;
+    const onAbort = () => killTree(child);
+    signal.addEventListener('abort', onAbort, { once: true });
+    let timedOut = false;
+    const timer = task.timeout ? setTimeout(() => {
+      timedOut = true;
+      killTree(child);
+    }, task.timeout) : null;
+
+    const forward = (stream) => (chunk) => {
+      const data = chunk.toString('utf-8');
+      emit('task:output', { runId: run.id, task: task.name, stream, data }, { target: run.owner });
+      if (collectors) collectors[stream].push(data);
+    };
+    child.stdout.on('data', forward('stdout'));
+    child.stderr.on('data', forward('stderr'));
+    child.on('error', (error) => {
+      forward('stderr')(Buffer.from(`${error.message}\n`));
+    });
+    child.on('close', (code) => {
+      clearTimeout(timer);
+      signal.removeEventListener('abort', onAbort);
+      if (timedOut) forward('stderr')(Buffer.from(`Timed out after ${task.timeout / 1000}s\n`));
+      if (collectors) {
+        collectors.stdout.flush();
+        collectors.stderr.flush();
+      }
+      resolve(code);
+    });
+  });
+}
+
+function setTaskStatus(run, name, status, extra = {}) {
+  run.tasks[name] = { ...run.tasks[name], status, ...extra };
+  emit('task:status', { runId: run.id, pipeline: run.pipeline, task: name, ...run.tasks[name] }, { target: run.owner });
+}
+
+// Run the graph with as much parallelism as it allows. A task starts once everything it
+// waits for succeeded (or failed with continue_on_error); dependents of a failure are
+// skipped. With on_failure = "stop" the first failure also kills whatever is running.
+async function executeRun(run, config, pipeline, graph, handle) {
+  const controller = new AbortController();
+  const onCancel = () => controller.abort();
+  handle.signal.addEventListener('abort', onCancel, { once: true });
+  const baseEnv = { ...process.env, ...config.env, ...pipeline.env, FORGE_RUN_ID: run.id, FORGE_PIPELINE: pipeline.name || '' };
+  const pending = new Set(graph.keys());
+  const running = new Map();
+  let stopped = false;
+  let finished = 0;
+
+  const startTask = (name) => {
+    const task = config.tasks[name];
+    const env = { ...baseEnv, FORGE_TASK: name };
+    for (const [key, value] of Object.entries(task.env)) env[key] = expandVariables(value, config.rootPath);
+    const command = expandVariables(task.command, config.rootPath);
+    running.set(name, (async () => {
+      const startedAt = Date.now();
+      let exitCode = null;
+      for (let attempt = 1; attempt <= task.retries + 1; attempt++) {
+        // Problems reflect the latest attempt only
+        clearProblems(`task:${name}`);
+        setTaskStatus(run, name, 'running', { attempt, startedAt: new Date(startedAt).toISOString() });
+        exitCode = await spawnTask(run, { ...task, command }, env, controller.signal);
+        if (exitCode === 0 || controller.signal.aborted) break;
+      }
+      const result = { exitCode, durationMs: Date.now() - startedAt };
+      if (exitCode === 0) {
+        setTaskStatus(run, name, 'succeeded', result);
+      } else if (controller.signal.aborted) {
+        setTaskStatus(run, name, 'cancelled', result);
+      } else {
+        setTaskStatus(run, name, 'failed', { ...result, allowed: task.continueOnError });
+      }
+      return name;
+    })());
+  };
+
+  const isDone = (name) => run.tasks[name].status === 'succeeded' || (run.tasks[name].status === 'failed' && run.tasks[name].allowed);
+  const isBlocked = (name) => ['failed', 'skipped', 'cancelled'].includes(run.tasks[name].status) && !isDone(name);
+
+  for (const name of graph.keys()) setTaskStatus(run, name, 'pending');
+  try {
+    for (;;) {
+      if (!controller.signal.aborted) {
+        for (const name of Array.from(pending)) {
+          const waits = Array.from(graph.get(name));
+          if (waits.some(isBlocked)) {
+            pending.delete(name);
+            setTaskStatus(run, name, 'skipped');
+          } else if (waits.every(isDone)) {
+            pending.delete(name);
+            startTask(name);
+          }
+        }
+      }
+      if (running.size === 0) break;
+      const name = await Promise.race(running.values());
+      running.delete(name);
+      finished++;
+      handle.reportProgress(finished / graph.size, `${name}: ${run.tasks[name].status}`);
+      if (isBlocked(name) && pipeline.onFailure === 'stop' && !controller.signal.aborted) {
+        log.info(`Run ${run.id}: ${name} failed, stopping`);
+        stopped = true;
+        controller.abort();
+      }
+    }
+  } finally {
+    handle.signal.removeEventListener('abort', onCancel);
+  }
+
+  const cancelled = controller.signal.aborted && !stopped;
+  for (const name of pending) setTaskStatus(run, name, cancelled ? 'cancelled' : 'skipped');
+  if (cancelled) return 'cancelled';
+  return Object.values(run.tasks).some((task) => task.status === 'failed' && !task.allowed) ? 'failed' : 'succeeded';
+}
+
+function describeRun(run) {
+  return {
+    runId: run.id,
+    jobId: run.jobId,
+    pipeline: run.pipeline,
+    status: run.status,
+    tasks: run.tasks,
+    startedAt: run.startedAt,
+    finishedAt: run.finishedAt
+  };
+}
+
+// Start a pipeline, or a single task (with its dependencies) when `taskName` is given.
+// Returns once the run is scheduled; progress arrives as task:status events.
+async function startRun(rootPath, { pipeline: pipelineName, task: taskName }, owner) {
+  if (!rootPath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to run its tasks');
+  if (!getRestrictions(rootPath).trusted) {
+    throw new ForgeError(ErrorCode.ACCESS_DENIED, 'Trust this workspace to run its tasks', { recoverable: false });
+  }
+  const config = await loadTaskConfig(rootPath);
+  if (!config) throw new ForgeError(ErrorCode.NOT_FOUND, `No ${CONFIG_FILE} in this workspace`);
+
+  let pipeline;
+  if (pipelineName) {
+    pipeline = config.pipelines[pipelineName];
+    if (!pipeline) throw new ForgeError(ErrorCode.NOT_FOUND, `Pipeline "${pipelineName}" is not defined`);
+  } else {
+    if (!config.tasks[taskName]) throw new ForgeError(ErrorCode.NOT_FOUND, `Task "${taskName}" is not defined`);
+    pipeline = { name: null, steps: [[taskName]], env: {}, onFailure: 'stop' };
+  }
+  const graph = buildGraph(config, pipeline.steps);
+
+  const run = {
+    id: `run_${uuidv4()}`,
+    jobId: null,
+    pipeline: pipeline.name,
+    status: 'running',
+    tasks: {},
+    owner,
+    startedAt: new Date().toISOString(),
+    finishedAt: null
+  };
+  const title = pipeline.name ? `Pipeline ${pipeline.name}` : `Task ${taskName}`;
+  const { id, promise } = runJob('tasks', title, (handle) => executeRun(run, config, pipeline, graph, handle));
+  run.jobId = id;
+  runs.set(run.id, run);
+  for (const oldId of Array.from(runs.keys()).slice(0, Math.max(0, runs.size - MAX_RECENT_RUNS))) {
+    if (runs.get(oldId).status !== 'running') runs.delete(oldId);
+  }
+
+  const outcome = promise.then((status) => status, (error) => (error.code === ErrorCode.CANCELLED ? 'cancelled' : 'failed'));
+  guardTask('tasks:runFinished', async () => {
+    const status = await outcome;
+    run.status = status;
+    run.finishedAt = new Date().toISOString();
+    log.info(`${title} ${status}`);
+    emit('task:runFinished', describeRun(run), { target: run.owner });
+  });
+  return { runId: run.id, jobId: id };
+}
+
+function workspaceOf(sender) {
+  const context = windowManager.fromSender(sender);
+  return context ? context.workspacePath : null;
+}
+
+function setupTaskHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // Parsed .forge/tasks.toml for the window's workspace, or null without one
+  ipcMain.handle('tasks:load', async (event) => {
+    const rootPath = workspaceOf(event.sender);
+    if (!rootPath) return null;
+    const config = await loadTaskConfig(rootPath);
+    if (!config) return null;
+    return {
+      file: config.file,
+      tasks: Object.values(config.tasks).map((task) => ({
+        name: task.name,
+        command: task.command,
+        cwd: task.cwd,
+        dependsOn: task.dependsOn,
+        retries: task.retries,
+        continueOnError: task.continueOnError,
+        problemMatchers: task.problemMatchers
+      })),
+      pipelines: Object.values(config.pipelines).map((pipeline) => ({
+        name: pipeline.name,
+        description: pipeline.description,
+        steps: pipeline.steps,
+        onFailure: pipeline.onFailure
+      }))
+    };
+  });
+
+  ipcMain.handle('tasks:runPipeline', async (event, name) => startRun(workspaceOf(event.sender), { pipeline: name }, event.sender));
+
+  ipcMain.handle('tasks:runTask', async (event, name) => startRun(workspaceOf(event.sender), { task: name }, event.sender));
+
+  ipcMain.handle('tasks:cancel', async (event, runId) => {
+    const run = runs.get(runId);
+    if (!run) throw new ForgeError(ErrorCode.NOT_FOUND, `Task run ${runId} not found`);
+    if (run.status !== 'running') return false;
+    return cancelJob(run.jobId);
+  });
+
+  ipcMain.handle('tasks:listRuns', async () => Array.from(runs.values(), describeRun).reverse());
+}
+
+module.exports = { setupTaskHandlers, loadTaskConfig, startRun };
@@ -0,0 +1,207 @@
+const fs = require('fs');
+const path = require('path');
+const { app, net } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+
+const log = createLogger('telemetry');
+
+const UPLOAD_INTERVAL = 30 * 60 * 1000;
+const MAX_BATCH_SIZE = 500;
+const MAX_PENDING_EVENTS = 10000;
+// Oldest events dropped at once when the log is full, so appends rarely rewrite the file
+const TRIM_BATCH = 1000;
+
+// Both flags default to off: nothing is recorded or sent without explicit consent
+const settings = new JsonStore('telemetry', {
+  enabled: false,
+  uploadEnabled: false,
+  endpoint: process.env.FORGE_TELEMETRY_ENDPOINT || null,
+  installId: null,
+  lastUploadAt: null
+});
+
+let uploadTimer = null;
+// Lines in the events file, read once and then tracked as events are written
+let pendingCount = null;
+
+function getEventsPath() {
+  const dir = path.join(app.getPath('userData'), 'telemetry');
+  fs.mkdirSync(dir, { recursive: true });
+  return path.join(dir, 'events.jsonl');
+}
+
+// Keep only primitive, non-identifying properties. Strings that look like
+// paths, URLs, or emails are dropped rather than risk leaking user data.
+function anonymize(properties = {}) {
+  const result = {};
+  for (const [key, value] of Object.entries(properties)) {
+    if (typeof value === 'number' || typeof value === 'boolean') {
+      result[key] = value;
+    } else if (typeof value === 'string') {
+      if (/[\\/]|:\/\/|@/.test(value) || value.length > 64) continue;
+      result[key] = value;
+    }
+  }
+  return result;
+}
+
+function readPendingEvents() {
+  try {
+    return fs.readFileSync(getEventsPath(), 'utf-8')
+      .split('\n')
+      .filter(Boolean)
+      .map((line) => JSON.parse(line));
+  } catch {
+    return [];
+  }
+}
+
+function writePendingEvents(events) {
+  fs.writeFileSync(getEventsPath(), events.map((event) => JSON.stringify(event)).join('\n') + (events.length ? '\n' : ''));
+  pendingCount = events.length;
+}
+
+function countPendingEvents() {
+  if (pendingCount === null) pendingCount = readPendingEvents().length;
+  return pendingCount;
+}
+
+function recordEvent(name, properties) {
+  if (!settings.get('enabled')) return false;
+
+  let installId = settings.get('installId');
+  if (!installId) {
+    installId = uuidv4();
+    settings.set('installId', installId);
+  }
+
+  const event = {
+    name,
+    properties: anonymize(properties),
+    timestamp: new Date().toISOString(),
+    appVersion: app.getVersion(),
+    platform: process.platform
+  };
+
+  try {
+    // Collection can run with uploads off indefinitely, so the log is capped here rather
+    // than only when an upload trims it
+    if (countPendingEvents() >= MAX_PENDING_EVENTS) {
+      writePendingEvents(readPendingEvents().slice(-(MAX_PENDING_EVENTS - TRIM_BATCH)));
+    }
+    fs.appendFileSync(getEventsPath(), JSON.stringify(event) + '\n');
+    pendingCount++;
+  } catch (error) {
+    log.warn('Failed to record telemetry event:', error.message);
+    return false;
+  }
+  return true;
+}
+
+// Exactly what the next upload would contain
+function buildPayload() {
+  return {
+    installId: settings.get('installId'),
+    events: readPendingEvents().slice(0, MAX_BATCH_SIZE)
+  };
+}
+
+async function uploadPendingEvents() {
+  if (!settings.get('enabled') || !settings.get('uploadEnabled')) {
+    return { uploaded: 0, skipped: true };
+  }
+  const endpoint = settings.get('endpoint');
+  if (!endpoint) {
+    return { uploaded: 0, skipped: true };
+  }
+
+  const payload = buildPayload();
+  if (payload.events.length === 0) {
+    return { uploaded: 0, skipped: false };
+  }
+
+  const response = await net.fetch(endpoint, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(payload)
+  });
+  if (!response.ok) {
+    throw new Error(`Telemetry upload failed with status ${response.status}`);
+  }
+
+  // Drop the uploaded batch, keeping anything recorded since
+  writePendingEvents(readPendingEvents().slice(payload.events.length).slice(-MAX_PENDING_EVENTS));
+  settings.set('lastUploadAt', new Date().toISOString());
+  log.info(`Uploaded ${payload.events.length} telemetry events`);
+  return { uploaded: payload.events.length, skipped: false };
+}
+
+function scheduleUploads() {
+  if (uploadTimer) {
+    clearInterval(uploadTimer);
+    uploadTimer = null;
+  }
+  if (settings.get('enabled') && settings.get('uploadEnabled')) {
+    uploadTimer = setInterval(() => {
+      uploadPendingEvents().catch((error) => log.warn(error.message));
+    }, UPLOAD_INTERVAL);
+    uploadTimer.unref();
+  }
+}
+
+function getPublicSettings() {
+  const { enabled, uploadEnabled, endpoint, lastUploadAt } = settings.store;
+  return { enabled, uploadEnabled, endpoint, lastUploadAt, pendingEvents: countPendingEvents() };
+}
+
+function setupTelemetryHandlers(ipcMain) {
+  scheduleUploads();
+
+  ipcMain.handle('telemetry:getSettings', async () => {
+    return getPublicSettings();
+  });
+
+  ipcMain.handle('telemetry:setEnabled', async (event, enabled) => {
+    settings.set('enabled', Boolean(enabled));
+    if (!enabled) {
+      // Opting out discards everything collected locally
+      settings.set('uploadEnabled', false);
+      writePendingEvents([]);
+    }
+    scheduleUploads();
+    return getPublicSettings();
+  });
+
+  ipcMain.handle('telemetry:setUploadEnabled', async (event, enabled, endpoint) => {
+    if (enabled && !settings.get('enabled')) {
+      throw new Error('Enable telemetry collection before enabling uploads');
+    }
+    settings.set('uploadEnabled', Boolean(enabled));
+    if (endpoint !== undefined) {
+      settings.set('endpoint', endpoint);
+    }
+    scheduleUploads();
+    return getPublicSettings();
+  });
+
+  ipcMain.handle('telemetry:record', async (event, name, properties) => {
+    return recordEvent(name, properties);
+  });
+
+  ipcMain.handle('telemetry:view', async () => {
+    return buildPayload();
+  });
+
+  ipcMain.handle('telemetry:clear', async () => {
+    writePendingEvents([]);
+    return true;
+  });
+
+  ipcMain.handle('telemetry:uploadNow', async () => {
+    return uploadPendingEvents();
+  });
+}
+
+module.exports = { setupTelemetryHandlers, recordEvent };
@@ -1,5 +1,19 @@
 const pty = require('node-pty');
+const fs = require('fs').promises;
+const path = require('path');
+const { EventEmitter } = require('events');
+const { app, BrowserWindow } = require('electron');
 const { v4: uuidv4 } = require('uuid');
+const { createLogger } = require('./logger');
+const { getRestrictions } = require('./trust');
+const { emit } = require('./events');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { writeProtectedFile, readProtectedFile, registerMigration, migrateDirectory } = require('./encryption');
+const { prepareShellLaunch, shellKind } = require('./shellIntegration');
+const { ImageScanner } = require('./terminalImages');
+const { TerminalModes, encodeMouseEvent } = require('./terminalModes');
+
+const log = createLogger('terminal');
 
 // Store active terminal sessions
 const terminals = new Map();
@@ -12,152 +26,613 @@ function getDefaultShell() {
   return process.env.SHELL || '/bin/bash';
 }
 
-// Get shell arguments for interactive mode
-function getShellArgs(shell) {
-  const shellName = shell.split('/').pop().split('\\').pop();
-  
-  // For bash, zsh, sh - use interactive login shell
-  if (shellName === 'bash' || shellName === 'zsh' || shellName === 'sh') {
-    return ['-il']; // interactive login shell
+// Main-process listeners for terminal activity: 'data' (id, data), 'input' (id, text),
+// 'exit' (id, exitCode), 'screenChanged' (id, { altScreen }), 'output' (id, text, offset) for
+// normal-screen output as it enters the scrollback, and from shell integration
+// marks 'commandStarted' (id, { command }) and 'commandFinished' (id, { exitCode, command,
+// commandLine, cwd, startedAt }), where commandLine is the line as typed and command its
+// trimmed form for display
+const terminalEvents = new EventEmitter();
+terminalEvents.setMaxListeners(0);
+
+// Output kept per terminal so it can be replayed when moved to another window
+const SCROLLBACK_LIMIT = 512 * 1024;
+// Full-screen programs redraw constantly, so only their recent output is worth keeping
+const ALT_SCREEN_LIMIT = 128 * 1024;
+
+function countLines(text) {
+  let count = 0;
+  for (let index = text.indexOf('\n'); index !== -1; index = text.indexOf('\n', index + 1)) count++;
+  return count;
+}
+
+// Positions in normal-screen output are absolute: `offset` counts characters and `line`
+// counts newlines since the terminal started, so they stay valid as scrollback is trimmed
+function appendScrollback(session, data) {
+  recordPromptMarks(session, data);
+  const offset = session.output.length;
+  session.scrollback += data;
+  session.output.length += data.length;
+  session.output.lines += countLines(data);
+  if (session.scrollback.length > SCROLLBACK_LIMIT) {
+    const dropped = session.scrollback.length - SCROLLBACK_LIMIT;
+    session.output.startOffset += dropped;
+    session.output.startLine += countLines(session.scrollback.slice(0, dropped));
+    session.scrollback = session.scrollback.slice(-SCROLLBACK_LIMIT);
+    // Marks for prompts that scrolled out of the buffer can't be jumped to
+    const firstKept = session.marks.findIndex((mark) => mark.prompt.offset >= session.output.startOffset);
+    session.marks.splice(0, firstKept === -1 ? session.marks.length : firstKept);
   }
-  
-  // For fish
-  if (shellName === 'fish') {
-    return ['-l']; // login shell
+  terminalEvents.emit('output', session.id, data, offset);
+}
+
+// { offset, line } for an absolute offset that is still in the scrollback, else null
+function positionAt(session, offset) {
+  const index = offset - session.output.startOffset;
+  if (!Number.isInteger(offset) || index < 0 || offset > session.output.length) return null;
+  return { offset, line: session.output.startLine + countLines(session.scrollback.slice(0, index)) };
+}
+
+// Output as the terminal shows it: `session.scrollback` holds the normal screen only, and
+// while a full-screen program runs, its output is kept apart in `altScreenOutput`
+function getScreenOutput(session, screen = 'all') {
+  if (screen === 'normal') return session.scrollback;
+  if (screen === 'alternate') return session.altScreenOutput;
+  return session.modes.altScreen ? `${session.scrollback}\x1b[?1049h${session.altScreenOutput}` : session.scrollback;
+}
+
+// Saved scrollback logs; these hold command history, so they may be encrypted at rest
+function getLogDir() {
+  return path.join(app.getPath('userData'), 'terminal-logs');
+}
+
+registerMigration('terminalLogs', migrateDirectory(getLogDir(), (name) => name.endsWith('.log')));
+
+function logPath(name) {
+  if (typeof name !== 'string' || !/^[\w.-]+\.log$/.test(name)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid terminal log name: ${name}`);
   }
-  
-  // For Windows shells
-  if (shellName === 'cmd.exe' || shellName === 'powershell.exe' || shellName === 'pwsh.exe') {
-    return [];
+  return path.join(getLogDir(), name);
+}
+
+// OSC 133 A/B/C/D mark where the prompt, the typed command, its output, and its end begin
+const PROMPT_MARK_PATTERN = /\x1b\]133;([ABCD])(?:;(-?\d+))?[^\x07\x1b]*(?:\x07|\x1b\\)|\x1b\]633;E;([^;\x07\x1b]*)[^\x07\x1b]*(?:\x07|\x1b\\)/g;
+const MARK_LIMIT = 1000;
+const MARK_FIELDS = { A: 'prompt', B: 'command', C: 'output', D: 'end' };
+
+// One entry per prompt, like iTerm2's and Warp's command blocks: { id, prompt, command,
+// output, end } positions ({ offset, line }, null until reached), the command line, and
+// its exit code
+function recordPromptMarks(session, data) {
+  if (!session.shellIntegration || !data.includes('\x1b]')) return;
+  let match;
+  PROMPT_MARK_PATTERN.lastIndex = 0;
+  while ((match = PROMPT_MARK_PATTERN.exec(data)) !== null) {
+    const last = session.marks[session.marks.length - 1];
+    if (match[3] !== undefined) {
+      if (last && !last.end) last.commandLine = unescapeCommandLine(match[3]).trim() || null;
+      continue;
+    }
+    const position = {
+      offset: session.output.length + match.index,
+      line: session.output.lines + countLines(data.slice(0, match.index))
+    };
+    if (match[1] === 'A') {
+      session.marks.push({ id: session.nextMarkId++, prompt: position, command: null, output: null, end: null, commandLine: null, exitCode: null });
+      if (session.marks.length > MARK_LIMIT) session.marks.shift();
+    } else if (last && !last[MARK_FIELDS[match[1]]]) {
+      last[MARK_FIELDS[match[1]]] = position;
+      if (match[1] === 'D' && match[2] !== undefined) last.exitCode = Number(match[2]);
+    }
   }
-  
-  return [];
 }
 
-function setupTerminalHandlers(ipcMain, getMainWindow) {
-  // Create a new terminal
-  ipcMain.handle('terminal:create', async (event, options = {}) => {
-    const id = `terminal_${uuidv4()}`;
-    const shell = options.shell || getDefaultShell();
-    const cwd = options.cwd || process.env.HOME || process.cwd();
-    const cols = options.cols || 80;
-    const rows = options.rows || 24;
+// The prompt to scroll to from `line`: the nearest one above ('previous') or below ('next')
+function getJumpTarget(session, line, direction) {
+  if (direction === 'previous') {
+    for (let index = session.marks.length - 1; index >= 0; index--) {
+      if (session.marks[index].prompt.line < line) return session.marks[index];
+    }
+    return null;
+  }
+  return session.marks.find((mark) => mark.prompt.line > line) || null;
+}
+
+// OSC 133;D[;exit] marks the end of a command in shells with prompt integration
+const COMMAND_FINISHED_PATTERN = /\x1b\]133;D(?:;(-?\d+))?(?:\x07|\x1b\\)/g;
+// OSC 633;E;<command line> reports the command about to run (VS Code-style integration)
+const COMMAND_LINE_PATTERN = /\x1b\]633;E;([^;\x07\x1b]*)(?:;[^\x07\x1b]*)?(?:\x07|\x1b\\)/g;
+// OSC 7;file://host/path reports the shell's working directory
+const CWD_PATTERN = /\x1b\]7;file:\/\/[^/\x07\x1b]*([^\x07\x1b]*)(?:\x07|\x1b\\)/g;
+const RECENT_COMMAND_LIMIT = 100;
+
+// 633;E escapes ';' and control characters as \xAB and backslashes as \\
+function unescapeCommandLine(value) {
+  return value.replace(/\\(\\|x([0-9a-fA-F]{2}))/g, (match, escaped, hex) => (hex ? String.fromCharCode(parseInt(hex, 16)) : '\\'));
+}
+
+function detectShellMarks(session, data) {
+  if (!data.includes('\x1b]')) return;
+  const { id } = session;
+  let match;
+
+  COMMAND_LINE_PATTERN.lastIndex = 0;
+  while ((match = COMMAND_LINE_PATTERN.exec(data)) !== null) {
+    const commandLine = unescapeCommandLine(match[1]);
+    const command = commandLine.trim();
+    if (command) {
+      session.recentCommands.push({
+        id: session.nextCommandId++,
+        command,
+        commandLine,
+        cwd: session.cwd,
+        startedAt: new Date().toISOString(),
+        finishedAt: null,
+        exitCode: null
+      });
+      if (session.recentCommands.length > RECENT_COMMAND_LIMIT) session.recentCommands.shift();
+      terminalEvents.emit('commandStarted', id, { command });
+    }
+  }
+
+  CWD_PATTERN.lastIndex = 0;
+  while ((match = CWD_PATTERN.exec(data)) !== null) {
+    try {
+      session.cwd = decodeURIComponent(match[1]).replace(/^\/([a-zA-Z]:)/, '$1');
+    } catch {
+      // Malformed escape; keep the previous cwd
+    }
+  }
+
+  COMMAND_FINISHED_PATTERN.lastIndex = 0;
+  while ((match = COMMAND_FINISHED_PATTERN.exec(data)) !== null) {
+    const exitCode = match[1] !== undefined ? Number(match[1]) : null;
+    const last = session.recentCommands[session.recentCommands.length - 1];
+    const finished = last && last.finishedAt === null ? last : null;
+    if (finished) {
+      finished.exitCode = exitCode;
+      finished.finishedAt = new Date().toISOString();
+    }
+    terminalEvents.emit('commandFinished', id, {
+      exitCode,
+      command: finished ? finished.command : null,
+      commandLine: finished ? finished.commandLine : null,
+      cwd: finished ? finished.cwd : session.cwd,
+      startedAt: finished ? finished.startedAt : null
+    });
+  }
+}
+
+function findCommand(session, commandId) {
+  const entry = session.recentCommands.find((item) => item.id === commandId);
+  if (!entry) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `Command ${commandId} is not in terminal ${session.id}'s recent commands`, { details: { id: session.id, commandId } });
+  }
+  return entry;
+}
+
+// Send a recorded command's exact text again, in its terminal or in a new one started with
+// the same shell, cwd, and env
+function rerunCommand(id, commandId, { newTerminal = false } = {}) {
+  const session = terminals.get(id);
+  if (!session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+  }
+  const entry = findCommand(session, commandId);
+  let target = session;
+  if (newTerminal) {
+    const ownerWindow = BrowserWindow.fromId(session.windowId);
+    if (!ownerWindow || ownerWindow.isDestroyed()) {
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'No window available for terminal');
+    }
+    const info = createTerminal({ shell: session.shell, cwd: entry.cwd || session.cwd, env: session.env }, ownerWindow);
+    target = terminals.get(info.id);
+  } else {
+    const last = session.recentCommands[session.recentCommands.length - 1];
+    // Typing now would feed the running program instead of the shell
+    if (last && last.finishedAt === null) {
+      throw new ForgeError(ErrorCode.BUSY, `Terminal ${id} is still running ${last.command}`, { details: { id }, recoverable: true });
+    }
+  }
+  target.pty.write(`${entry.command}\r`);
+  terminalEvents.emit('input', target.id, `${entry.command}\r`);
+  log.info(`Re-running command ${commandId} from ${id} in ${target.id}`);
+  return { terminalId: target.id, command: entry.command, cwd: entry.cwd };
+}
+
+// The most recent command that exited non-zero, in one terminal or across `windowId`'s
+function lastFailedCommand(windowId, id) {
+  let latest = null;
+  for (const session of terminals.values()) {
+    if (id ? session.id !== id : session.windowId !== windowId) continue;
+    for (const entry of session.recentCommands) {
+      if (entry.exitCode === null || entry.exitCode === 0) continue;
+      if (!latest || entry.finishedAt > latest.entry.finishedAt) latest = { terminalId: session.id, entry };
+    }
+  }
+  return latest;
+}
+
+// Mouse reporting as the renderer should treat it: when `active`, mouse events go to the
+// program through terminal:sendMouse instead of driving selection
+function mouseState(session) {
+  const { mouseTracking, mouseEncoding } = session.modes;
+  return {
+    tracking: mouseTracking,
+    encoding: mouseEncoding,
+    forwarding: session.mouseForwarding,
+    active: session.mouseForwarding && mouseTracking !== null
+  };
+}
+
+function publishMouseState(session) {
+  const target = ownerContents(session);
+  if (target) emit('terminal:mouseMode', { terminalId: session.id, ...mouseState(session) }, { target });
+}
+
+// Keep output from the normal and alternate screens apart, and report mode changes
+function recordOutput(session, data) {
+  const { segments, mouseChanged, screenChanged } = session.modes.push(data);
+  for (const { altScreen, text } of segments) {
+    if (!altScreen) {
+      appendScrollback(session, text);
+    } else {
+      session.altScreenOutput += text;
+      if (session.altScreenOutput.length > ALT_SCREEN_LIMIT) session.altScreenOutput = session.altScreenOutput.slice(-ALT_SCREEN_LIMIT);
+    }
+  }
+  if (mouseChanged) {
+    log.debug(`Terminal ${session.id} mouse reporting: ${session.modes.mouseTracking || 'off'} (${session.modes.mouseEncoding})`);
+    publishMouseState(session);
+  }
+  if (screenChanged) {
+    const { altScreen } = session.modes;
+    // What a full-screen program drew is gone once it exits
+    if (!altScreen) session.altScreenOutput = '';
+    terminalEvents.emit('screenChanged', session.id, { altScreen });
+    const target = ownerContents(session);
+    if (target) emit('terminal:screenChanged', { terminalId: session.id, altScreen }, { target });
+  }
+}
+
+// Decode inline images that completed in this chunk. The escape sequences stay in the
+// data stream; the renderer draws each image where its sequence ended.
+function detectImages(session, data) {
+  const { images, replies } = session.images.push(data);
+  // Kitty graphics acknowledgements go back to the program that asked
+  for (const reply of replies) session.pty.write(reply);
+  if (images.length === 0) return;
+  const target = ownerContents(session);
+  if (!target) return;
+  for (const image of images) {
+    log.debug(`Decoded ${image.protocol} image ${image.width}x${image.height} in terminal ${session.id}`);
+    emit('terminal:image', { terminalId: session.id, ...image }, { target });
+  }
+}
+
+// Quote a path as a single literal argument for the given shell
+function quoteForShell(value, shell) {
+  switch (shellKind(shell)) {
+    case 'powershell':
+      return `'${value.replace(/'/g, "''")}'`;
+    case 'cmd':
+      // Windows paths can't contain double quotes, so plain quoting is enough
+      return `"${value}"`;
+    case 'fish':
+      return `'${value.replace(/[\\']/g, '\\$&')}'`;
+    case 'nu':
+      // Nushell's single quotes have no escapes at all
+      return value.includes("'") ? `"${value.replace(/[\\"]/g, '\\$&')}"` : `'${value}'`;
+    default:
+      return `'${value.replace(/'/g, "'\\''")}'`;
+  }
+}
+
+// Type a change-directory command into a terminal's shell
+async function cdTerminalTo(id, targetPath) {
+  const session = terminals.get(id);
+  if (!session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+  }
+  // eslint-disable-next-line no-control-regex
+  if (typeof targetPath !== 'string' || !targetPath || /[\x00-\x1f\x7f]/.test(targetPath)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Directory path must be non-empty and free of control characters');
+  }
+  const directory = path.resolve(session.cwd || app.getPath('home'), targetPath);
+  let stats;
+  try {
+    stats = await fs.stat(directory);
+  } catch (error) {
+    throw ForgeError.from(error, `Cannot change to ${directory}`);
+  }
+  if (!stats.isDirectory()) {
+    throw new ForgeError(ErrorCode.NOT_DIRECTORY, `${directory} is not a directory`, { details: { path: directory } });
+  }
+
+  const kind = shellKind(session.shell);
+  const quoted = quoteForShell(directory, session.shell);
+  const command = kind === 'powershell' ? `Set-Location -LiteralPath ${quoted}`
+    : kind === 'cmd' ? `cd /d ${quoted}`
+    : kind === 'nu' ? `cd ${quoted}`
+    : `cd -- ${quoted}`;
+  session.pty.write(`${command}\r`);
+  terminalEvents.emit('input', id, `${command}\r`);
+  return { command, path: directory };
+}
+
+function getTerminal(id) {
+  return terminals.get(id) || null;
+}
+
+// Terminals belonging to one window, or every terminal when windowId is omitted
+function listTerminals(windowId) {
+  return Array.from(terminals.values())
+    .filter((session) => windowId === undefined || session.windowId === windowId)
+    .map((session) => ({
+      id: session.id,
+      shell: session.shell,
+      cwd: session.cwd,
+      restricted: session.restricted,
+      windowId: session.windowId
+    }));
+}
+
+// Hand a live terminal to another window. The PTY keeps running; the new owner
+// receives the scrollback so it can repaint before live output resumes.
+function transferTerminal(id, targetWindowId) {
+  const session = terminals.get(id);
+  if (!session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+  }
+  const target = BrowserWindow.fromId(targetWindowId);
+  if (!target || target.isDestroyed()) {
+    throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, `Window ${targetWindowId} not found`);
+  }
+  if (session.windowId === targetWindowId) return false;
+
+  const previous = ownerContents(session);
+  const fromWindowId = session.windowId;
+  session.windowId = targetWindowId;
+  target.once('closed', () => {
+    if (terminals.get(id) === session && session.windowId === targetWindowId) {
+      session.pty.kill();
+      terminals.delete(id);
+    }
+  });
+
+  log.info(`Moved terminal ${id} from window ${fromWindowId} to ${targetWindowId}`);
+  if (previous) {
+    emit('terminal:detached', { terminalId: id, toWindowId: targetWindowId }, { target: previous });
+  }
+  emit('terminal:attached', {
+    terminalId: id,
+    fromWindowId,
+    shell: session.shell,
+    cwd: session.cwd,
+    restricted: session.restricted,
+    cols: session.pty.cols,
+    rows: session.pty.rows,
+    scrollback: getScreenOutput(session)
+  }, { target: target.webContents });
+  return true;
+}
+
+// webContents of the window that currently owns a terminal
+function ownerContents(session) {
+  const win = BrowserWindow.fromId(session.windowId);
+  return win && !win.isDestroyed() ? win.webContents : null;
+}
+
+// Spawn a shell owned by `ownerWindow` (closed with it)
+function createTerminal(options, ownerWindow) {
+  const id = `terminal_${uuidv4()}`;
+  const shell = options.shell || getDefaultShell();
+  const cwd = options.cwd || process.env.HOME || process.cwd();
+  const cols = options.cols || 80;
+  const rows = options.rows || 24;
+  
+  // Untrusted workspaces get restricted terminals: caller-supplied env is ignored
+  const restricted = getRestrictions(cwd).restrictedTerminals;
+  
+  log.info(`Creating terminal ${id}`, { shell, cwd, cols, rows, restricted });
+  
+  try {
+    // Interactive login shell, with prompt/command marks unless the caller opts out
+    const launch = prepareShellLaunch(shell, { integration: options.shellIntegration !== false });
+    const ptyProcess = pty.spawn(shell, launch.args, {
+      name: 'xterm-256color',
+      cols: cols,
+      rows: rows,
+      cwd: cwd,
+      env: {
+        ...process.env,
+        ...(!restricted && options.env ? options.env : {}),
+        ...launch.env,
+        TERM: 'xterm-256color',
+        COLORTERM: 'truecolor',
+        FORCE_COLOR: '1'
+      }
+    });
     
-    console.log(`[Terminal] Creating terminal ${id}`);
-    console.log(`[Terminal] Shell: ${shell}`);
-    console.log(`[Terminal] CWD: ${cwd}`);
-    console.log(`[Terminal] Size: ${cols}x${rows}`);
+    log.debug(`PTY created with PID: ${ptyProcess.pid}`);
     
-    try {
-      // Create PTY with proper options
-      const shellArgs = getShellArgs(shell);
-      const ptyProcess = pty.spawn(shell, shellArgs, {
-        name: 'xterm-256color',
-        cols: cols,
-        rows: rows,
-        cwd: cwd,
-        env: {
-          ...process.env,
-          TERM: 'xterm-256color',
-          COLORTERM: 'truecolor',
-          FORCE_COLOR: '1'
-        }
-      });
-      
-      console.log(`[Terminal] PTY created with PID: ${ptyProcess.pid}`);
-      
-      // Store terminal session
-      terminals.set(id, {
-        id: id,
-        pty: ptyProcess,
-        shell: shell,
-        cwd: cwd
-      });
-      
-      // Get main window
-      const mainWindow = getMainWindow();
-      if (!mainWindow) {
-        throw new Error('No main window available');
+    // Store terminal session
+    const session = {
+      id: id,
+      pty: ptyProcess,
+      shell: shell,
+      cwd: cwd,
+      restricted: restricted,
+      windowId: ownerWindow.id,
+      // Shell kind whose integration script was loaded, or null
+      shellIntegration: launch.integration,
+      scrollback: '',
+      altScreenOutput: '',
+      // Normal-screen output so far, and where the kept scrollback starts
+      output: { length: 0, lines: 0, startOffset: 0, startLine: 0 },
+      // Prompt positions from shell integration, oldest first
+      marks: [],
+      nextMarkId: 1,
+      // Commands reported by shell integration, oldest first
+      recentCommands: [],
+      nextCommandId: 1,
+      // Caller-supplied environment, reused when a command is re-run in a new terminal
+      env: !restricted && options.env ? options.env : null,
+      // Inline image sequences (Sixel, iTerm2, Kitty) split across output chunks
+      images: new ImageScanner(),
+      // DEC private modes set by the running program
+      modes: new TerminalModes(),
+      // Whether mouse events from the UI reach programs that enable mouse reporting
+      mouseForwarding: options.mouseForwarding !== false
+    };
+    terminals.set(id, session);
+    
+    // Set up data handler
+    ptyProcess.onData((data) => {
+      recordOutput(session, data);
+      terminalEvents.emit('data', id, data);
+      detectShellMarks(session, data);
+      // Send data to the owning window's renderer
+      const target = ownerContents(session);
+      if (target) {
+        const payload = {
+          terminalId: id,
+          data: Array.from(Buffer.from(data))
+        };
+        log.trace(`Sending data to renderer for terminal ${id}: ${data.length} bytes`);
+        emit('terminal:data', payload, { target });
       }
-      
-      // Set up data handler
-      ptyProcess.onData((data) => {
-        // Send data to renderer
-        if (!mainWindow.isDestroyed()) {
-          const payload = {
-            terminalId: id,
-            data: Array.from(Buffer.from(data))
-          };
-          console.log(`[Terminal] Sending data to renderer for terminal ${id}: ${data.length} bytes`);
-          mainWindow.webContents.send('terminal:data', payload);
-        }
-      });
-      
-      // Set up exit handler
-      ptyProcess.onExit((exitCode) => {
-        console.log(`[Terminal] Terminal ${id} exited with code:`, exitCode);
-        if (!mainWindow.isDestroyed()) {
-          mainWindow.webContents.send('terminal:exit', {
-            terminalId: id,
-            exitCode: exitCode.exitCode
-          });
-        }
+      detectImages(session, data);
+    });
+    
+    // Set up exit handler
+    ptyProcess.onExit((exitCode) => {
+      log.info(`Terminal ${id} exited with code:`, exitCode.exitCode);
+      terminalEvents.emit('exit', id, exitCode.exitCode);
+      const target = ownerContents(session);
+      if (target) {
+        emit('terminal:exit', {
+          terminalId: id,
+          exitCode: exitCode.exitCode
+        }, { target });
+      }
+      terminals.delete(id);
+    });
+    
+    // Close the window's terminals with it
+    ownerWindow.once('closed', () => {
+      if (terminals.get(id) === session && session.windowId === ownerWindow.id) {
+        log.info(`Closing terminal ${id} with its window`);
+        ptyProcess.kill();
         terminals.delete(id);
-      });
-      
-      // Send initial commands to verify PTY is working
-      setTimeout(() => {
-        if (ptyProcess) {
-          console.log(`[Terminal] Sending test echo to terminal ${id}`);
-          ptyProcess.write('echo "Terminal Ready"\n');
-        }
-      }, 200);
-      
-      return {
-        id: id,
-        shell: shell,
-        cwd: cwd
-      };
-    } catch (error) {
-      console.error('[Terminal] Failed to create terminal:', error);
-      throw error;
+      }
+    });
+    
+    return {
+      id: id,
+      shell: shell,
+      cwd: cwd,
+      restricted: restricted,
+      windowId: session.windowId
+    };
+  } catch (error) {
+    log.error('Failed to create terminal:', error);
+    throw ForgeError.from(error, 'Failed to create terminal', ErrorCode.TERMINAL_SPAWN_FAILED);
+  }
+}
+
+function setupTerminalHandlers(ipcMain, getWindow) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // Create a new terminal
+  ipcMain.handle('terminal:create', async (event, options = {}) => {
+    // Terminals belong to the window that created them
+    const ownerWindow = getWindow(event);
+    if (!ownerWindow) {
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'No window available for terminal');
     }
+    return createTerminal(options, ownerWindow);
   });
 
   // Write data to terminal
   ipcMain.handle('terminal:write', async (event, id, data) => {
     const session = terminals.get(id);
     if (!session) {
-      console.error(`[Terminal] Terminal ${id} not found`);
+      log.warn(`Terminal ${id} not found`);
       return false;
     }
     
     try {
       // Convert data to string
       const text = Buffer.from(data).toString('utf8');
-      console.log(`[Terminal] Writing to terminal ${id}: "${text}"`);
+      log.trace(`Writing ${text.length} chars to terminal ${id}`);
       session.pty.write(text);
+      terminalEvents.emit('input', id, text);
       return true;
     } catch (error) {
-      console.error(`[Terminal] Failed to write to terminal ${id}:`, error);
+      log.error(`Failed to write to terminal ${id}:`, error);
       return false;
     }
   });
 
+  ipcMain.handle('terminal:cd', async (event, id, targetPath) => cdTerminalTo(id, targetPath));
+
+  // Commands run in a terminal with their results, oldest first (needs shell integration)
+  ipcMain.handle('terminal:listCommands', async (event, id) => {
+    const session = terminals.get(id);
+    if (!session) {
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+    }
+    return session.recentCommands;
+  });
+
+  ipcMain.handle('terminal:rerunCommand', async (event, id, commandId, options) => rerunCommand(id, commandId, options));
+
+  // Re-run the latest failed command, from `id` or from any of the calling window's terminals
+  ipcMain.handle('terminal:rerunLastFailed', async (event, id, options) => {
+    const win = getWindow(event);
+    const failed = lastFailedCommand(win ? win.id : undefined, id);
+    if (!failed) throw new ForgeError(ErrorCode.NOT_FOUND, 'No failed command to re-run');
+    return rerunCommand(failed.terminalId, failed.entry.id, options);
+  });
+
+  // Report a mouse event to the program; false when it isn't asking for this event
+  ipcMain.handle('terminal:sendMouse', async (event, id, mouseEvent) => {
+    const session = terminals.get(id);
+    if (!session) {
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+    }
+    if (!session.mouseForwarding) return false;
+    const report = encodeMouseEvent(session.modes, mouseEvent);
+    if (!report) return false;
+    session.pty.write(report);
+    return true;
+  });
+
+  ipcMain.handle('terminal:setMouseForwarding', async (event, id, enabled) => {
+    const session = terminals.get(id);
+    if (!session) {
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+    }
+    session.mouseForwarding = Boolean(enabled);
+    publishMouseState(session);
+    return mouseState(session);
+  });
+
   // Resize terminal
   ipcMain.handle('terminal:resize', async (event, id, cols, rows) => {
     const session = terminals.get(id);
     if (!session) {
-      console.error(`[Terminal] Terminal ${id} not found`);
+      log.warn(`Terminal ${id} not found`);
       return false;
     }
     
     try {
-      console.log(`[Terminal] Resizing terminal ${id} to ${cols}x${rows}`);
+      log.debug(`Resizing terminal ${id} to ${cols}x${rows}`);
       session.pty.resize(cols, rows);
       return true;
     } catch (error) {
-      console.error(`[Terminal] Failed to resize terminal ${id}:`, error);
+      log.error(`Failed to resize terminal ${id}:`, error);
       return false;
     }
   });
@@ -170,28 +645,106 @@ function setupTerminalHandlers(ipcMain, getMainWindow) {
     }
     
     try {
-      console.log(`[Terminal] Closing terminal ${id}`);
+      log.info(`Closing terminal ${id}`);
       session.pty.kill();
       terminals.delete(id);
       return true;
     } catch (error) {
-      console.error(`[Terminal] Failed to close terminal ${id}:`, error);
+      log.error(`Failed to close terminal ${id}:`, error);
       terminals.delete(id);
       return false;
     }
   });
 
+  // Move a terminal (live PTY and scrollback) to another window
+  ipcMain.handle('terminal:transfer', async (event, id, targetWindowId) => {
+    return transferTerminal(id, targetWindowId);
+  });
+
+  // Recent output, for repainting a terminal view. `screen` narrows it to the normal
+  // screen's scrollback or the running full-screen program's output.
+  ipcMain.handle('terminal:getScrollback', async (event, id, { screen = 'all' } = {}) => {
+    const session = terminals.get(id);
+    if (!session) {
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+    }
+    return getScreenOutput(session, screen);
+  });
+
+  // Prompt marks still in the scrollback, for "scroll to previous/next command"
+  ipcMain.handle('terminal:getMarks', async (event, id) => {
+    const session = terminals.get(id);
+    if (!session) {
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+    }
+    return {
+      marks: session.marks,
+      scrollbackStart: { offset: session.output.startOffset, line: session.output.startLine },
+      lineCount: session.output.lines
+    };
+  });
+
+  ipcMain.handle('terminal:getJumpTarget', async (event, id, line, direction) => {
+    const session = terminals.get(id);
+    if (!session) {
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+    }
+    if (direction !== 'previous' && direction !== 'next') {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown jump direction: ${direction}`);
+    }
+    return getJumpTarget(session, Number(line), direction);
+  });
+
+  // Persist a terminal's scrollback to a log file; resolves the log's name. Full-screen
+  // program output isn't scrollback, so it stays out of the log.
+  ipcMain.handle('terminal:saveLog', async (event, id) => {
+    const session = terminals.get(id);
+    if (!session) {
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
+    }
+    const name = `${id.replace(/^terminal_/, '').slice(0, 8)}-${new Date().toISOString().replace(/[:.]/g, '-')}.log`;
+    await writeProtectedFile('terminalLogs', logPath(name), session.scrollback);
+    return name;
+  });
+
+  ipcMain.handle('terminal:listLogs', async () => {
+    const names = (await fs.readdir(getLogDir()).catch(() => [])).filter((name) => name.endsWith('.log'));
+    const logs = await Promise.all(names.map(async (name) => {
+      const stats = await fs.stat(logPath(name));
+      return { name, size: stats.size, savedAt: stats.mtime.toISOString() };
+    }));
+    return logs.sort((a, b) => b.savedAt.localeCompare(a.savedAt));
+  });
+
+  ipcMain.handle('terminal:readLog', async (event, name) => readProtectedFile(logPath(name)));
+
+  ipcMain.handle('terminal:deleteLog', async (event, name) => {
+    await fs.rm(logPath(name), { force: true });
+    return true;
+  });
+
+  // List the calling window's terminals
+  ipcMain.handle('terminal:list', async (event) => {
+    const win = getWindow(event);
+    return listTerminals(win ? win.id : undefined);
+  });
+
   // Get terminal info
   ipcMain.handle('terminal:getSessionInfo', async (event, id) => {
     const session = terminals.get(id);
     if (!session) {
-      throw new Error(`Terminal ${id} not found`);
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${id} not found`, { details: { id } });
     }
     
     return {
       id: session.id,
       shell: session.shell,
       cwd: session.cwd,
+      restricted: session.restricted,
+      windowId: session.windowId,
+      shellIntegration: session.shellIntegration,
+      mouse: mouseState(session),
+      altScreen: session.modes.altScreen,
       isRunning: session.pty && !session.pty.killed,
       cols: session.pty.cols,
       rows: session.pty.rows
@@ -212,4 +765,4 @@ function setupTerminalHandlers(ipcMain, getMainWindow) {
   });
 }
 
-module.exports = { setupTerminalHandlers };
\ No newline at end of file
+module.exports = { setupTerminalHandlers, terminalEvents, createTerminal, getTerminal, getScreenOutput, positionAt, listTerminals, transferTerminal, cdTerminalTo, quoteForShell, detectShellMarks, recordPromptMarks };
\ No newline at end of file
@@ -0,0 +1,227 @@
+const os = require('os');
+const path = require('path');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { aiManager } = require('./ai');
+const { runJob } = require('./jobs');
+const { getTerminal } = require('./terminal');
+const { addAnnotation } = require('./terminalAnnotations');
+
+const log = createLogger('terminal-ai');
+
+const RECENT_COMMANDS_IN_PROMPT = 10;
+
+// Safety levels, lowest to highest
+const SAFETY_LEVELS = ['safe', 'caution', 'dangerous'];
+
+// Local rules always run, so a model that calls `rm -rf /` "safe" can't lower the rating
+const DANGEROUS_RULES = [
+  [/\brm\s+(-[a-zA-Z]*[rR][a-zA-Z]*\s+)*-?[a-zA-Z]*[fF]?[a-zA-Z]*\s*(\/|~|\$HOME|\*)(\s|$)/, 'Recursively deletes a root, home, or wildcard path'],
+  [/\brm\s+-[a-zA-Z]*[rR][a-zA-Z]*[fF]|\brm\s+-[a-zA-Z]*[fF][a-zA-Z]*[rR]/, 'Force-deletes files recursively'],
+  [/\bmkfs(\.\w+)?\b|\bformat\s+[a-zA-Z]:/i, 'Formats a filesystem'],
+  [/\bdd\b.*\bof=\/dev\//, 'Writes directly to a block device'],
+  [/>\s*\/dev\/(sd|nvme|hd|disk)/, 'Overwrites a block device'],
+  [/:\(\)\s*\{\s*:\|:&\s*\};:/, 'Fork bomb'],
+  [/\b(curl|wget)\b[^|]*\|\s*(sudo\s+)?(ba|z|fi)?sh\b/, 'Pipes a downloaded script into a shell'],
+  [/\bchmod\s+(-R\s+)?[0-7]*777\s+\/(\s|$)/, 'Makes the root filesystem world-writable'],
+  [/\b(shutdown|reboot|halt|poweroff)\b/, 'Shuts down or restarts the machine'],
+  [/\bgit\s+push\b.*(--force\b|-f\b)/, 'Force-pushes, which can overwrite remote history'],
+  [/\bdrop\s+(database|table)\b/i, 'Drops a database or table'],
+  [/\bRemove-Item\b.*-Recurse\b.*-Force\b|\bdel\s+\/[sS]\b/i, 'Force-deletes files recursively']
+];
+
+const CAUTION_RULES = [
+  [/\bsudo\b|\bdoas\b|\brunas\b/i, 'Runs with elevated privileges'],
+  [/\brm\b|\bRemove-Item\b|\bdel\b|\brmdir\b/i, 'Deletes files'],
+  [/\bgit\s+(reset\s+--hard|clean\s+-[a-zA-Z]*f|checkout\s+--\s|stash\s+drop|branch\s+-D)/, 'Discards uncommitted work or branches'],
+  [/\b(kill|killall|pkill|taskkill)\b/i, 'Terminates processes'],
+  [/\b(chmod|chown|chgrp|icacls)\b/i, 'Changes permissions or ownership'],
+  [/\b(npm|yarn|pnpm|cargo)\s+publish\b/, 'Publishes a package'],
+  [/\b(apt|apt-get|yum|dnf|brew|pacman|choco|winget)\s+(install|remove|uninstall|purge)\b/, 'Installs or removes system packages'],
+  [/(^|[^>])>\s*[^\s&>|]/, 'Overwrites a file with redirected output'],
+  [/\bmv\b/, 'Moves or renames files, possibly overwriting'],
+  [/\bdocker\s+(rm|rmi|system\s+prune|volume\s+rm)\b/, 'Removes Docker resources']
+];
+
+function classifyCommand(command) {
+  const reasons = [];
+  let level = 'safe';
+  for (const [pattern, reason] of DANGEROUS_RULES) {
+    if (pattern.test(command)) {
+      level = 'dangerous';
+      reasons.push(reason);
+    }
+  }
+  for (const [pattern, reason] of CAUTION_RULES) {
+    if (pattern.test(command)) {
+      if (level === 'safe') level = 'caution';
+      reasons.push(reason);
+    }
+  }
+  return { level, reasons: Array.from(new Set(reasons)) };
+}
+
+function higherLevel(a, b) {
+  return SAFETY_LEVELS.indexOf(a) >= SAFETY_LEVELS.indexOf(b) ? a : b;
+}
+
+// OS, shell, cwd, and recent commands from shell integration, when a terminal is given
+function describeEnvironment(options = {}) {
+  const session = options.terminalId ? getTerminal(options.terminalId) : null;
+  if (options.terminalId && !session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${options.terminalId} not found`, { details: { id: options.terminalId } });
+  }
+  const shell = options.shell || (session && session.shell) || process.env.SHELL || (process.platform === 'win32' ? 'powershell' : 'bash');
+  const cwd = options.cwd || (session && session.cwd) || process.cwd();
+  const recent = session ? session.recentCommands.slice(-RECENT_COMMANDS_IN_PROMPT) : [];
+
+  const lines = [
+    `Operating system: ${os.type()} ${os.release()} (${process.platform}, ${os.arch()})`,
+    `Shell: ${path.basename(shell)}`,
+    `Working directory: ${cwd}`
+  ];
+  if (recent.length > 0) {
+    lines.push('Recent commands (oldest first):');
+    for (const entry of recent) {
+      lines.push(`  $ ${entry.command}${entry.exitCode !== null && entry.exitCode !== undefined ? `  # exit ${entry.exitCode}` : ''}`);
+    }
+  }
+  return { shell, cwd, text: lines.join('\n') };
+}
+
+// Models often wrap JSON in prose or code fences; take the first object
+function parseJsonReply(content) {
+  const fenced = /```(?:json)?\s*([\s\S]*?)```/.exec(content);
+  const candidate = fenced ? fenced[1] : content;
+  const start = candidate.indexOf('{');
+  const end = candidate.lastIndexOf('}');
+  if (start < 0 || end <= start) {
+    throw new ForgeError(ErrorCode.INTERNAL, 'The model did not return a JSON answer', { details: { content } });
+  }
+  try {
+    return JSON.parse(candidate.slice(start, end + 1));
+  } catch {
+    throw new ForgeError(ErrorCode.INTERNAL, 'The model returned malformed JSON', { details: { content } });
+  }
+}
+
+async function ask(title, messages, options) {
+  const job = runJob('ai', title, (handle) => aiManager.chat({
+    messages,
+    providerId: options.providerId,
+    model: options.model,
+    temperature: 0.2
+  }, { signal: handle.signal }));
+  return (await job.promise).content;
+}
+
+async function explainCommand(text, options = {}) {
+  if (typeof text !== 'string' || !text.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A command to explain is required');
+  }
+  const environment = describeEnvironment(options);
+  const content = await ask('Explaining command', [
+    {
+      role: 'system',
+      content: 'You explain shell commands to developers. Reply with JSON only: ' +
+        '{"summary": one sentence, "parts": [{"text": fragment, "meaning": explanation}], ' +
+        '"risk": "safe"|"caution"|"dangerous", "riskReason": string or null}.\n\n' + environment.text
+    },
+    { role: 'user', content: text }
+  ], options);
+
+  const reply = parseJsonReply(content);
+  const local = classifyCommand(text);
+  const modelLevel = SAFETY_LEVELS.includes(reply.risk) ? reply.risk : 'safe';
+  const explanation = {
+    command: text,
+    summary: reply.summary || '',
+    parts: Array.isArray(reply.parts) ? reply.parts : [],
+    safety: {
+      level: higherLevel(local.level, modelLevel),
+      reasons: [...local.reasons, ...(reply.riskReason ? [reply.riskReason] : [])]
+    }
+  };
+  // Text explained from a terminal's output keeps its explanation as an annotation
+  if (options.terminalId && options.range) {
+    explanation.annotation = addAnnotation(options.terminalId, {
+      start: options.range.start,
+      end: options.range.end,
+      source: 'ai',
+      kind: 'explanation',
+      message: explanation.summary,
+      data: { ...explanation }
+    });
+  }
+  return explanation;
+}
+
+// Propose a command for a natural-language request. Nothing is written to the terminal;
+// the renderer shows the proposal and calls insertCommand once the user accepts it.
+async function generateCommand(request, options = {}) {
+  if (typeof request !== 'string' || !request.trim()) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Describe what the command should do');
+  }
+  const environment = describeEnvironment(options);
+  const content = await ask('Generating command', [
+    {
+      role: 'system',
+      content: `You write single ${path.basename(environment.shell)} commands for the environment below. ` +
+        'Prefer safe, non-destructive options. Reply with JSON only: ' +
+        '{"command": string, "explanation": one or two sentences, "risk": "safe"|"caution"|"dangerous", "riskReason": string or null}.\n\n' +
+        environment.text
+    },
+    { role: 'user', content: request }
+  ], options);
+
+  const reply = parseJsonReply(content);
+  if (typeof reply.command !== 'string' || !reply.command.trim()) {
+    throw new ForgeError(ErrorCode.INTERNAL, 'The model did not propose a command', { details: { content } });
+  }
+  const command = reply.command.trim();
+  const local = classifyCommand(command);
+  const modelLevel = SAFETY_LEVELS.includes(reply.risk) ? reply.risk : 'safe';
+  log.info(`Generated command rated ${higherLevel(local.level, modelLevel)}`);
+  return {
+    command,
+    explanation: reply.explanation || '',
+    shell: environment.shell,
+    cwd: environment.cwd,
+    safety: {
+      level: higherLevel(local.level, modelLevel),
+      reasons: [...local.reasons, ...(reply.riskReason ? [reply.riskReason] : [])]
+    }
+  };
+}
+
+// Type an accepted command into the terminal without pressing Enter. Dangerous
+// commands need explicit confirmation from the user.
+function insertCommand(terminalId, command, options = {}) {
+  const session = getTerminal(terminalId);
+  if (!session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { id: terminalId } });
+  }
+  const safety = classifyCommand(command);
+  if (safety.level === 'dangerous' && !options.confirmed) {
+    throw new ForgeError(ErrorCode.PERMISSION_DENIED, 'This command is potentially destructive and must be confirmed', {
+      details: { safety }
+    });
+  }
+  // Collapse to one line so a multi-line proposal can't execute on paste
+  session.pty.write(command.replace(/\r?\n/g, ' '));
+  return true;
+}
+
+function setupTerminalAiHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('terminalAi:explain', async (event, text, options = {}) => explainCommand(text, options));
+
+  ipcMain.handle('terminalAi:generate', async (event, request, options = {}) => generateCommand(request, options));
+
+  ipcMain.handle('terminalAi:classify', async (event, command) => classifyCommand(command));
+
+  ipcMain.handle('terminalAi:insert', async (event, terminalId, command, options = {}) => insertCommand(terminalId, command, options));
+}
+
+module.exports = { setupTerminalAiHandlers, explainCommand, generateCommand, classifyCommand, insertCommand };
@@ -0,0 +1,112 @@
+const { v4: uuidv4 } = require('uuid');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { terminalEvents, getTerminal, positionAt } = require('./terminal');
+
+const log = createLogger('terminal-annotations');
+
+const MAX_ANNOTATIONS_PER_TERMINAL = 2000;
+
+// Terminal id -> annotations ordered by start. Ranges are absolute character offsets in the
+// terminal's normal-screen output (the same positions as prompt marks), so they stay put
+// as the scrollback trims; annotations on trimmed output are dropped with it.
+const annotations = new Map();
+
+function publish(terminalId) {
+  emit('terminalAnnotations:changed', { terminalId, count: (annotations.get(terminalId) || []).length });
+}
+
+function requireTerminal(terminalId) {
+  const session = getTerminal(terminalId);
+  if (!session) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { id: terminalId } });
+  }
+  return session;
+}
+
+// Attach metadata to output [start, end). `source` names the subsystem ('problems', 'ai',
+// ...), `kind` what the frontend should draw ('problem', 'explanation', 'link', ...), and
+// `message` the hover text; `data` is passed through untouched.
+function addAnnotation(terminalId, { start, end, source, kind, message = null, data = null }) {
+  const session = requireTerminal(terminalId);
+  if (typeof source !== 'string' || !source || typeof kind !== 'string' || !kind) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Annotations need a source and a kind');
+  }
+  const from = positionAt(session, start);
+  const to = positionAt(session, end);
+  if (!from || !to || end <= start) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Output range ${start}-${end} is not in terminal ${terminalId}'s scrollback`, {
+      details: { start, end, scrollbackStart: session.output.startOffset, outputLength: session.output.length }
+    });
+  }
+
+  const annotation = {
+    id: `annotation_${uuidv4()}`,
+    terminalId,
+    start: from,
+    end: to,
+    source,
+    kind,
+    message,
+    data,
+    createdAt: new Date().toISOString()
+  };
+  const list = annotations.get(terminalId) || [];
+  const index = list.findIndex((item) => item.start.offset > start);
+  list.splice(index === -1 ? list.length : index, 0, annotation);
+  if (list.length > MAX_ANNOTATIONS_PER_TERMINAL) list.shift();
+  annotations.set(terminalId, list);
+  publish(terminalId);
+  return annotation;
+}
+
+// Annotations overlapping [start, end), optionally from one source
+function listAnnotations(terminalId, { start = 0, end = Infinity, source } = {}) {
+  requireTerminal(terminalId);
+  return (annotations.get(terminalId) || []).filter((item) => item.start.offset < end && item.end.offset > start && (!source || item.source === source));
+}
+
+function removeAnnotation(terminalId, id) {
+  const list = annotations.get(terminalId) || [];
+  const index = list.findIndex((item) => item.id === id);
+  if (index === -1) return false;
+  list.splice(index, 1);
+  publish(terminalId);
+  return true;
+}
+
+function clearAnnotations(terminalId, { source } = {}) {
+  const list = annotations.get(terminalId) || [];
+  const remaining = source ? list.filter((item) => item.source !== source) : [];
+  if (remaining.length === list.length) return 0;
+  annotations.set(terminalId, remaining);
+  publish(terminalId);
+  return list.length - remaining.length;
+}
+
+// Drop annotations whose output has been trimmed from the scrollback
+terminalEvents.on('output', (terminalId) => {
+  const list = annotations.get(terminalId);
+  if (!list || list.length === 0) return;
+  const session = getTerminal(terminalId);
+  const kept = list.filter((item) => item.start.offset >= session.output.startOffset);
+  if (kept.length === list.length) return;
+  log.debug(`Dropped ${list.length - kept.length} annotation(s) on trimmed output in terminal ${terminalId}`);
+  annotations.set(terminalId, kept);
+  publish(terminalId);
+});
+
+terminalEvents.on('exit', (terminalId) => annotations.delete(terminalId));
+
+function setupTerminalAnnotationHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('terminalAnnotations:list', async (event, terminalId, range) => listAnnotations(terminalId, range));
+
+  ipcMain.handle('terminalAnnotations:remove', async (event, terminalId, id) => removeAnnotation(terminalId, id));
+
+  ipcMain.handle('terminalAnnotations:clear', async (event, terminalId, options) => clearAnnotations(terminalId, options));
+}
+
+module.exports = { setupTerminalAnnotationHandlers, addAnnotation, listAnnotations, removeAnnotation, clearAnnotations };
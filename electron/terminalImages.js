@@ -0,0 +1,385 @@
+const zlib = require('zlib');
+const { createLogger } = require('./logger');
+const { crc32 } = require('./zip');
+
+const log = createLogger('terminal-images');
+
+// Inline image escape sequences in PTY output:
+//   iTerm2  OSC 1337 ; File=[args] : <base64> (BEL | ST)
+//   Kitty   APC G <key=value,...> ; <base64> ST      (chunked with m=1)
+//   Sixel   DCS <params> q <sixel data> ST
+// The sequences stay in the output stream; each complete image is decoded to PNG so the
+// renderer can draw it at the cursor position where the sequence ended.
+
+const SEQUENCE_STARTS = [
+  { protocol: 'iterm', prefix: '\x1b]1337;File=' },
+  { protocol: 'kitty', prefix: '\x1b_G' },
+  { protocol: 'sixel', prefix: '\x1bP' }
+];
+const ST = '\x1b\\';
+const BEL = '\x07';
+// Larger sequences are dropped rather than buffered
+const MAX_SEQUENCE_LENGTH = 16 * 1024 * 1024;
+const MAX_DIMENSION = 4096;
+// Kitty images kept for later placement (a=p), per terminal
+const MAX_STORED_IMAGES = 32;
+
+// VT340 default colors for the first 16 sixel registers
+const SIXEL_PALETTE = [
+  [0, 0, 0], [20, 20, 80], [80, 13, 13], [20, 80, 20], [80, 20, 80], [20, 80, 80], [80, 80, 20], [53, 53, 53],
+  [26, 26, 26], [33, 33, 60], [60, 26, 26], [33, 60, 33], [60, 33, 60], [33, 60, 60], [60, 60, 33], [80, 80, 80]
+].map(([r, g, b]) => [Math.round(r * 2.55), Math.round(g * 2.55), Math.round(b * 2.55)]);
+
+function pngChunk(type, data) {
+  const length = Buffer.alloc(4);
+  length.writeUInt32BE(data.length);
+  const body = Buffer.concat([Buffer.from(type, 'ascii'), data]);
+  const crc = Buffer.alloc(4);
+  crc.writeUInt32BE(crc32(body));
+  return Buffer.concat([length, body, crc]);
+}
+
+// Encode RGBA pixels (4 bytes each, row-major) as a PNG
+function encodePng(width, height, rgba) {
+  const header = Buffer.alloc(13);
+  header.writeUInt32BE(width, 0);
+  header.writeUInt32BE(height, 4);
+  header[8] = 8;
+  header[9] = 6;
+  const stride = width * 4;
+  const raw = Buffer.alloc((stride + 1) * height);
+  for (let y = 0; y < height; y++) {
+    // Filter type 0 (none) per row
+    rgba.copy(raw, y * (stride + 1) + 1, y * stride, (y + 1) * stride);
+  }
+  return Buffer.concat([
+    Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]),
+    pngChunk('IHDR', header),
+    pngChunk('IDAT', zlib.deflateSync(raw)),
+    pngChunk('IEND', Buffer.alloc(0))
+  ]);
+}
+
+function isPng(buffer) {
+  return buffer.length > 24 && buffer.readUInt32BE(0) === 0x89504e47 && buffer.toString('ascii', 12, 16) === 'IHDR';
+}
+
+// PNG (as is) or another format Chromium can decode, converted; null if undecodable
+function toPng(buffer) {
+  if (isPng(buffer)) return { png: buffer, width: buffer.readUInt32BE(16), height: buffer.readUInt32BE(20) };
+  let nativeImage;
+  try {
+    ({ nativeImage } = require('electron'));
+  } catch {
+    return null;
+  }
+  const image = nativeImage && nativeImage.createFromBuffer(buffer);
+  if (!image || image.isEmpty()) return null;
+  const { width, height } = image.getSize();
+  return { png: image.toPNG(), width, height };
+}
+
+function hlsToRgb(hue, lightness, saturation) {
+  // Sixel hue 0 is blue; shift to the usual red-at-0 wheel
+  const h = ((hue + 240) % 360) / 360;
+  const l = lightness / 100;
+  const s = saturation / 100;
+  if (s === 0) return [l, l, l].map((v) => Math.round(v * 255));
+  const q = l < 0.5 ? l * (1 + s) : l + s - l * s;
+  const p = 2 * l - q;
+  const channel = (t) => {
+    if (t < 0) t += 1;
+    if (t > 1) t -= 1;
+    if (t < 1 / 6) return p + (q - p) * 6 * t;
+    if (t < 1 / 2) return q;
+    if (t < 2 / 3) return p + (q - p) * (2 / 3 - t) * 6;
+    return p;
+  };
+  return [channel(h + 1 / 3), channel(h), channel(h - 1 / 3)].map((v) => Math.round(v * 255));
+}
+
+// Decode sixel data (after the introducer's "q") to RGBA. Unpainted pixels stay transparent.
+function decodeSixel(data) {
+  const palette = SIXEL_PALETTE.slice();
+  const runs = [];
+  let color = 0;
+  let x = 0;
+  let band = 0;
+  let width = 0;
+  let height = 0;
+  let i = 0;
+
+  const readNumbers = () => {
+    const match = /^[0-9;]*/.exec(data.slice(i, i + 64));
+    i += match[0].length;
+    return match[0].split(';').map((value) => (value === '' ? 0 : Number(value)));
+  };
+  const paint = (char, count) => {
+    const bits = char.charCodeAt(0) - 0x3f;
+    if (bits > 0) {
+      runs.push(x, band, bits, count, color);
+      width = Math.max(width, x + count);
+      height = Math.max(height, band * 6 + 32 - Math.clz32(bits));
+    }
+    x += count;
+  };
+
+  while (i < data.length) {
+    const char = data[i++];
+    if (char === '#') {
+      const [register, space, a, b, c] = readNumbers();
+      color = register;
+      if (space === 1) palette[register] = hlsToRgb(a, b, c);
+      else if (space === 2) palette[register] = [a, b, c].map((v) => Math.round(Math.min(v, 100) * 2.55));
+    } else if (char === '!') {
+      const [count] = readNumbers();
+      if (i < data.length) paint(data[i++], Math.max(1, count));
+    } else if (char === '"') {
+      // Raster attributes: aspect ratio and declared size
+      const [, , declaredWidth, declaredHeight] = readNumbers();
+      width = Math.max(width, declaredWidth || 0);
+      height = Math.max(height, declaredHeight || 0);
+    } else if (char === '$') {
+      x = 0;
+    } else if (char === '-') {
+      x = 0;
+      band++;
+    } else if (char >= '?' && char <= '~') {
+      paint(char, 1);
+    }
+    if (width > MAX_DIMENSION || band * 6 > MAX_DIMENSION) return null;
+  }
+  if (width === 0 || height === 0) return null;
+
+  const rgba = Buffer.alloc(width * height * 4);
+  for (let r = 0; r < runs.length; r += 5) {
+    const [runX, runBand, bits, count, register] = runs.slice(r, r + 5);
+    const [red, green, blue] = palette[register] || [0, 0, 0];
+    for (let bit = 0; bit < 6; bit++) {
+      if (!(bits & (1 << bit))) continue;
+      const y = runBand * 6 + bit;
+      if (y >= height) continue;
+      for (let dx = 0; dx < count && runX + dx < width; dx++) {
+        const offset = (y * width + runX + dx) * 4;
+        rgba[offset] = red;
+        rgba[offset + 1] = green;
+        rgba[offset + 2] = blue;
+        rgba[offset + 3] = 255;
+      }
+    }
+  }
+  return { width, height, rgba };
+}
+
+function parseKeyValues(text, separator) {
+  const result = {};
+  for (const pair of text.split(separator)) {
+    const index = pair.indexOf('=');
+    if (index > 0) result[pair.slice(0, index)] = pair.slice(index + 1);
+  }
+  return result;
+}
+
+// Per-terminal scanner. push() returns { images, replies }: decoded images in output
+// order, and protocol responses to write back to the PTY (Kitty acknowledgements).
+class ImageScanner {
+  constructor() {
+    this.pending = '';
+    this.nextId = 1;
+    this.kittyChunks = null;
+    this.kittyImages = new Map();
+  }
+
+  push(data) {
+    const result = { images: [], replies: [] };
+    if (!this.pending && !data.includes('\x1b')) return result;
+    const text = this.pending + data;
+    this.pending = '';
+
+    let position = 0;
+    while (position < text.length) {
+      let start = -1;
+      let kind = null;
+      for (const candidate of SEQUENCE_STARTS) {
+        const index = text.indexOf(candidate.prefix, position);
+        if (index >= 0 && (start < 0 || index < start)) {
+          start = index;
+          kind = candidate;
+        }
+      }
+      if (start < 0) break;
+
+      const bodyStart = start + kind.prefix.length;
+      if (kind.protocol === 'sixel') {
+        const introducer = /^[0-9;]*/.exec(text.slice(bodyStart, bodyStart + 32))[0];
+        const next = text[bodyStart + introducer.length];
+        if (next === undefined) {
+          this.keepPending(text.slice(start));
+          return result;
+        }
+        if (next !== 'q') {
+          // Some other device control string
+          position = bodyStart;
+          continue;
+        }
+      }
+      let end = text.indexOf(ST, bodyStart);
+      let terminatorLength = ST.length;
+      if (kind.protocol === 'iterm') {
+        const bell = text.indexOf(BEL, bodyStart);
+        if (bell >= 0 && (end < 0 || bell < end)) {
+          end = bell;
+          terminatorLength = BEL.length;
+        }
+      }
+      if (end < 0) {
+        this.keepPending(text.slice(start));
+        return result;
+      }
+      try {
+        this.handleSequence(kind.protocol, text.slice(bodyStart, end), result);
+      } catch (error) {
+        log.debug(`Ignoring malformed ${kind.protocol} image: ${error.message}`);
+      }
+      position = end + terminatorLength;
+    }
+
+    // A chunk may end partway through an introducer
+    const tail = text.slice(Math.max(position, text.length - 16));
+    for (let length = Math.min(tail.length, 15); length > 0; length--) {
+      const suffix = tail.slice(-length);
+      if (SEQUENCE_STARTS.some(({ prefix }) => prefix.startsWith(suffix))) {
+        this.pending = suffix;
+        break;
+      }
+    }
+    return result;
+  }
+
+  keepPending(sequence) {
+    this.pending = sequence.length <= MAX_SEQUENCE_LENGTH ? sequence : '';
+    if (!this.pending) log.debug('Dropping an inline image larger than the size limit');
+  }
+
+  emitImage(result, protocol, decoded, placement, id) {
+    if (!decoded) return;
+    result.images.push({
+      imageId: id === undefined ? `img${this.nextId++}` : String(id),
+      protocol,
+      data: decoded.png.toString('base64'),
+      width: decoded.width,
+      height: decoded.height,
+      placement
+    });
+  }
+
+  handleSequence(protocol, body, result) {
+    if (protocol === 'iterm') {
+      const colon = body.indexOf(':');
+      if (colon < 0) return;
+      const args = parseKeyValues(body.slice(0, colon), ';');
+      // inline=0 is a file download, not an image to display
+      if (args.inline !== '1') return;
+      const decoded = toPng(Buffer.from(body.slice(colon + 1), 'base64'));
+      this.emitImage(result, 'iterm', decoded, {
+        width: args.width || 'auto',
+        height: args.height || 'auto',
+        preserveAspectRatio: args.preserveAspectRatio !== '0'
+      });
+    } else if (protocol === 'sixel') {
+      const decoded = decodeSixel(body.slice(body.indexOf('q') + 1));
+      if (!decoded) return;
+      this.emitImage(result, 'sixel', { png: encodePng(decoded.width, decoded.height, decoded.rgba), width: decoded.width, height: decoded.height }, {
+        width: `${decoded.width}px`,
+        height: `${decoded.height}px`,
+        preserveAspectRatio: true
+      });
+    } else {
+      this.handleKitty(body, result);
+    }
+  }
+
+  handleKitty(body, result) {
+    const semicolon = body.indexOf(';');
+    const keys = parseKeyValues(semicolon < 0 ? body : body.slice(0, semicolon), ',');
+    const payload = semicolon < 0 ? '' : body.slice(semicolon + 1);
+
+    // Continuation chunks carry only m (and the payload); keys come from the first chunk
+    if (this.kittyChunks) {
+      this.kittyChunks.payload += payload;
+      if (keys.m === '1') return;
+      Object.assign(keys, this.kittyChunks.keys, { m: '0' });
+      this.finishKitty(keys, this.kittyChunks.payload, result);
+      this.kittyChunks = null;
+      return;
+    }
+    if (keys.m === '1') {
+      this.kittyChunks = { keys, payload };
+      return;
+    }
+    this.finishKitty(keys, payload, result);
+  }
+
+  finishKitty(keys, payload, result) {
+    const action = keys.a || 't';
+    const id = keys.i;
+    const reply = (message) => {
+      // Responses go only to commands that named an image, and q=1/q=2 silence them
+      if (!id || keys.q === '2' || (keys.q === '1' && message === 'OK')) return;
+      result.replies.push(`\x1b_Gi=${id};${message}${ST}`);
+    };
+    const placement = {
+      width: keys.c ? keys.c : 'auto',
+      height: keys.r ? keys.r : 'auto',
+      preserveAspectRatio: !(keys.c && keys.r)
+    };
+
+    if (action === 'p') {
+      const stored = this.kittyImages.get(id);
+      if (!stored) return reply('ENOENT:image not found');
+      this.emitImage(result, 'kitty', stored, placement, id);
+      return reply('OK');
+    }
+    if (action === 'd') return undefined;
+    if ((keys.t || 'd') !== 'd') {
+      // File and shared-memory transmission would read paths named by the program
+      return reply('EINVAL:only direct transmission is supported');
+    }
+
+    let bytes = Buffer.from(payload, 'base64');
+    if (keys.o === 'z') bytes = zlib.inflateSync(bytes);
+    const format = keys.f || '32';
+    let decoded;
+    if (format === '100') {
+      decoded = toPng(bytes);
+    } else if (format === '32' || format === '24') {
+      const width = Number(keys.s);
+      const height = Number(keys.v);
+      const channels = format === '32' ? 4 : 3;
+      if (!width || !height || width > MAX_DIMENSION || height > MAX_DIMENSION || bytes.length < width * height * channels) {
+        return reply('EINVAL:bad image dimensions');
+      }
+      let rgba = bytes;
+      if (channels === 3) {
+        rgba = Buffer.alloc(width * height * 4);
+        for (let p = 0; p < width * height; p++) {
+          bytes.copy(rgba, p * 4, p * 3, p * 3 + 3);
+          rgba[p * 4 + 3] = 255;
+        }
+      }
+      decoded = { png: encodePng(width, height, rgba), width, height };
+    }
+    if (!decoded) return reply('EINVAL:unsupported image data');
+    // a=q only checks that the image could be decoded
+    if (action === 'q') return reply('OK');
+
+    if (id) {
+      this.kittyImages.set(id, decoded);
+      if (this.kittyImages.size > MAX_STORED_IMAGES) this.kittyImages.delete(this.kittyImages.keys().next().value);
+    }
+    if (action === 'T') this.emitImage(result, 'kitty', decoded, placement, id);
+    return reply('OK');
+  }
+}
+
+module.exports = { ImageScanner, decodeSixel, encodePng };
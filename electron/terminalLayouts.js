@@ -0,0 +1,141 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+const { getRestrictions } = require('./trust');
+const { createTerminal, getTerminal } = require('./terminal');
+
+const log = createLogger('terminal-layouts');
+
+const MAX_LAYOUT_TERMINALS = 20;
+// Saved cwds under the workspace are stored relative to this token so a layout survives
+// the project moving
+const WORKSPACE_TOKEN = '${workspaceFolder}';
+
+const store = new JsonStore('terminal-layouts', {
+  // Workspace root -> { terminals: [...], arrangement, autoRestore, savedAt }
+  workspaces: {}
+});
+
+function tokenize(directory, rootPath) {
+  if (directory === rootPath) return WORKSPACE_TOKEN;
+  if (directory.startsWith(rootPath + path.sep)) return WORKSPACE_TOKEN + '/' + path.relative(rootPath, directory).split(path.sep).join('/');
+  return directory;
+}
+
+function expand(directory, rootPath) {
+  if (!directory.startsWith(WORKSPACE_TOKEN)) return directory;
+  return path.join(rootPath, ...directory.slice(WORKSPACE_TOKEN.length).split('/').filter(Boolean));
+}
+
+function getLayout(rootPath) {
+  return store.get('workspaces')[path.resolve(rootPath)] || null;
+}
+
+// `layout.terminals` lists tabs in order: { terminalId?, title, shell?, cwd?, env?,
+// startupCommand?, group? }. An entry naming a live terminal takes its shell and current
+// directory from it. `arrangement` is the renderer's split/tab state, stored as given.
+function saveLayout(rootPath, layout = {}) {
+  const root = path.resolve(rootPath);
+  if (!Array.isArray(layout.terminals) || layout.terminals.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A terminal layout needs at least one terminal');
+  }
+  if (layout.terminals.length > MAX_LAYOUT_TERMINALS) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `A terminal layout can hold at most ${MAX_LAYOUT_TERMINALS} terminals`);
+  }
+  const terminals = layout.terminals.map((entry, order) => {
+    const live = entry.terminalId ? getTerminal(entry.terminalId) : null;
+    if (entry.terminalId && !live) {
+      throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${entry.terminalId} not found`, { details: { id: entry.terminalId } });
+    }
+    const cwd = entry.cwd || (live && live.cwd) || root;
+    return {
+      title: typeof entry.title === 'string' && entry.title ? entry.title : `Terminal ${order + 1}`,
+      shell: entry.shell || (live && live.shell) || null,
+      cwd: tokenize(path.resolve(root, cwd), root),
+      env: entry.env && typeof entry.env === 'object' ? entry.env : {},
+      startupCommand: typeof entry.startupCommand === 'string' && entry.startupCommand.trim() ? entry.startupCommand : null,
+      group: Number.isInteger(entry.group) ? entry.group : order,
+      order
+    };
+  });
+  const saved = {
+    terminals,
+    arrangement: layout.arrangement === undefined ? null : layout.arrangement,
+    autoRestore: layout.autoRestore !== false,
+    savedAt: new Date().toISOString()
+  };
+  const workspaces = store.get('workspaces');
+  workspaces[root] = saved;
+  store.set('workspaces', workspaces);
+  log.info(`Saved a layout of ${terminals.length} terminal(s) for ${root}`);
+  return saved;
+}
+
+function clearLayout(rootPath) {
+  const workspaces = store.get('workspaces');
+  const root = path.resolve(rootPath);
+  if (!workspaces[root]) return false;
+  delete workspaces[root];
+  store.set('workspaces', workspaces);
+  return true;
+}
+
+async function isDirectory(directory) {
+  try {
+    return (await fs.stat(directory)).isDirectory();
+  } catch {
+    return false;
+  }
+}
+
+// Recreate the saved terminals in `ownerWindow`. Startup commands only run in trusted
+// workspaces; elsewhere they are reported back as skipped for the user to run.
+async function restoreLayout(rootPath, ownerWindow, { cols, rows } = {}) {
+  const root = path.resolve(rootPath);
+  const layout = getLayout(root);
+  if (!layout) throw new ForgeError(ErrorCode.NOT_FOUND, 'No terminal layout is saved for this workspace');
+  const { allowTaskAutoRun } = getRestrictions(root);
+
+  const terminals = [];
+  for (const entry of layout.terminals) {
+    let cwd = expand(entry.cwd, root);
+    // A folder deleted since the layout was saved falls back to the workspace root
+    if (!await isDirectory(cwd)) cwd = root;
+    const info = createTerminal({ shell: entry.shell || undefined, cwd, env: entry.env, cols, rows }, ownerWindow);
+    const commandSkipped = Boolean(entry.startupCommand) && !allowTaskAutoRun;
+    if (entry.startupCommand && allowTaskAutoRun) {
+      getTerminal(info.id).pty.write(`${entry.startupCommand}\r`);
+    }
+    terminals.push({ ...info, title: entry.title, group: entry.group, order: entry.order, startupCommand: entry.startupCommand, commandSkipped });
+  }
+  log.info(`Restored ${terminals.length} terminal(s) for ${root}`);
+  return { terminals, arrangement: layout.arrangement };
+}
+
+function contextOf(sender) {
+  const context = windowManager.fromSender(sender);
+  if (!context || !context.workspacePath) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to use terminal layouts');
+  }
+  return context;
+}
+
+function setupTerminalLayoutHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('terminalLayout:get', async (event) => getLayout(contextOf(event.sender).workspacePath));
+
+  ipcMain.handle('terminalLayout:save', async (event, layout) => saveLayout(contextOf(event.sender).workspacePath, layout));
+
+  ipcMain.handle('terminalLayout:clear', async (event) => clearLayout(contextOf(event.sender).workspacePath));
+
+  ipcMain.handle('terminalLayout:restore', async (event, options) => {
+    const context = contextOf(event.sender);
+    return restoreLayout(context.workspacePath, context.window, options);
+  });
+}
+
+module.exports = { setupTerminalLayoutHandlers, getLayout, saveLayout, restoreLayout };
@@ -0,0 +1,126 @@
+// DEC private modes (CSI ? Pm h to set, CSI ? Pm l to reset) that Forge tracks per terminal
+
+// Which mouse events the program wants reported
+const MOUSE_TRACKING_MODES = { 9: 'x10', 1000: 'normal', 1002: 'button', 1003: 'any' };
+// How the reports are encoded
+const MOUSE_ENCODING_MODES = { 1005: 'utf8', 1006: 'sgr', 1015: 'urxvt' };
+// Alternate screen buffer, used by full-screen programs (1049 also saves the cursor)
+const ALT_SCREEN_MODES = [47, 1047, 1049];
+
+// Mode changes, and RIS (ESC c), which resets everything
+const MODE_PATTERN = /\x1b\[\?([0-9;]*)([hl])|\x1bc/g;
+const PARTIAL_MODE_PATTERN = /\x1b(\[(\?[0-9;]*)?)?$/;
+
+const BUTTON_CODES = { left: 0, middle: 1, right: 2, none: 3, wheelUp: 64, wheelDown: 65 };
+// The classic encoding puts coordinates in single bytes offset by 32
+const MAX_DEFAULT_COORDINATE = 223;
+const MAX_UTF8_COORDINATE = 2015;
+
+class TerminalModes {
+  constructor() {
+    this.tail = '';
+    this.mouseTracking = null;
+    this.mouseEncoding = 'default';
+    this.altScreen = false;
+  }
+
+  reset() {
+    this.mouseTracking = null;
+    this.mouseEncoding = 'default';
+    this.altScreen = false;
+  }
+
+  // Apply the mode changes in a chunk of output. Returns { mouseChanged, screenChanged,
+  // segments }, where segments split the chunk into { altScreen, text } runs by the screen
+  // they were written to. Screen switch sequences themselves belong to neither.
+  push(data) {
+    const result = { mouseChanged: false, screenChanged: false, segments: [] };
+    if (!this.tail && !data.includes('\x1b')) {
+      if (data) result.segments.push({ altScreen: this.altScreen, text: data });
+      return result;
+    }
+    // Positions are relative to `data`; the start of a sequence split across chunks was
+    // already written out with the previous chunk
+    const offset = this.tail.length;
+    const text = this.tail + data;
+    const mouseBefore = `${this.mouseTracking}/${this.mouseEncoding}`;
+    let start = 0;
+    let match;
+    MODE_PATTERN.lastIndex = 0;
+    while ((match = MODE_PATTERN.exec(text)) !== null) {
+      const wasAltScreen = this.altScreen;
+      if (match[2] === undefined) {
+        this.reset();
+      } else {
+        const enabled = match[2] === 'h';
+        for (const mode of match[1].split(';').map(Number)) {
+          if (MOUSE_TRACKING_MODES[mode]) {
+            // Like xterm, resetting any tracking mode turns tracking off
+            this.mouseTracking = enabled ? MOUSE_TRACKING_MODES[mode] : null;
+          } else if (MOUSE_ENCODING_MODES[mode]) {
+            this.mouseEncoding = enabled ? MOUSE_ENCODING_MODES[mode] : 'default';
+          } else if (ALT_SCREEN_MODES.includes(mode)) {
+            this.altScreen = enabled;
+          }
+        }
+      }
+      if (this.altScreen !== wasAltScreen) {
+        const end = Math.max(0, match.index - offset);
+        if (end > start) result.segments.push({ altScreen: wasAltScreen, text: data.slice(start, end) });
+        start = Math.max(0, match.index + match[0].length - offset);
+        result.screenChanged = true;
+      }
+    }
+    if (start < data.length) result.segments.push({ altScreen: this.altScreen, text: data.slice(start) });
+    // Keep a sequence cut off at the end of the chunk for the next one
+    const partial = PARTIAL_MODE_PATTERN.exec(text.slice(-32));
+    this.tail = partial ? partial[0] : '';
+    result.mouseChanged = mouseBefore !== `${this.mouseTracking}/${this.mouseEncoding}`;
+    return result;
+  }
+}
+
+// Encode a mouse event from the UI as the report the program asked for, or null when the
+// current mode doesn't report it. `event` is { action: 'press' | 'release' | 'move',
+// button: 'left' | 'middle' | 'right' | 'wheelUp' | 'wheelDown' | 'none', column, row
+// (1-based cells), shift?, alt?, ctrl? }.
+function encodeMouseEvent(modes, event) {
+  const tracking = modes.mouseTracking;
+  if (!tracking || !event) return null;
+  const { action } = event;
+  const button = event.button || 'none';
+  const column = Math.floor(event.column);
+  const row = Math.floor(event.row);
+  if (!Object.prototype.hasOwnProperty.call(BUTTON_CODES, button) || !['press', 'release', 'move'].includes(action) || !(column >= 1) || !(row >= 1)) return null;
+  const wheel = button === 'wheelUp' || button === 'wheelDown';
+
+  if (tracking === 'x10' && (action !== 'press' || wheel)) return null;
+  if (tracking === 'normal' && action === 'move') return null;
+  if (tracking === 'button' && action === 'move' && button === 'none') return null;
+  if (wheel && action === 'release') return null;
+
+  let code = BUTTON_CODES[button];
+  if (action === 'move') code += 32;
+  // X10 mode reports no modifiers
+  if (tracking !== 'x10') {
+    if (event.shift) code += 4;
+    if (event.alt) code += 8;
+    if (event.ctrl) code += 16;
+  }
+
+  switch (modes.mouseEncoding) {
+    case 'sgr':
+      return `\x1b[<${code};${column};${row}${action === 'release' ? 'm' : 'M'}`;
+    case 'urxvt':
+      return `\x1b[${32 + (action === 'release' ? (code & ~3) | 3 : code)};${column};${row}M`;
+    default: {
+      const limit = modes.mouseEncoding === 'utf8' ? MAX_UTF8_COORDINATE : MAX_DEFAULT_COORDINATE;
+      if (column > limit || row > limit) return null;
+      // Only SGR can say which button was released
+      const reported = action === 'release' ? (code & ~3) | 3 : code;
+      return `\x1b[M${String.fromCharCode(32 + reported, 32 + column, 32 + row)}`;
+    }
+  }
+}
+
+module.exports = { TerminalModes, encodeMouseEvent };
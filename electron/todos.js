@@ -0,0 +1,220 @@
+const path = require('path');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { watchPath } = require('./watchHub');
+const { emit } = require('./events');
+const { runJob, cancelJob } = require('./jobs');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { searchWorkspace, searchFile } = require('./search');
+const { blameFile } = require('./git');
+const { scopeEvents, isPathIgnored } = require('./indexScope');
+
+const log = createLogger('todos');
+
+const store = new JsonStore('todos', {
+  tags: ['TODO', 'FIXME', 'HACK']
+});
+
+// Workspace root -> { files: Map<path, todo[]>, watcher, jobId }
+const indexes = new Map();
+
+function escapeTag(tag) {
+  return tag.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+}
+
+// Tags only count inside comments: //, #, /*, *, --, ;, <!--
+function buildTagPattern(tags) {
+  const alternatives = tags.map(escapeTag).join('|');
+  return new RegExp(`(?:\\/\\/|#|\\/\\*|^\\s*\\*|--|;|<!--)\\s*(${alternatives})\\b(?:\\(([^)]*)\\))?:?\\s*(.*?)\\s*(?:\\*\\/.*|-->.*)?$`, 'g');
+}
+
+function toTodos(filePath, matches) {
+  return matches.map((match) => ({
+    path: filePath,
+    line: match.line,
+    column: match.column,
+    tag: match.groups[0],
+    // TODO(name): explicit owner
+    owner: match.groups[1] || null,
+    text: match.groups[2] || '',
+    author: null,
+    authorMail: null,
+    committedAt: null
+  }));
+}
+
+// Fill in author information from git blame. Files outside git keep null authors.
+async function attachAuthors(filePath, todos) {
+  if (todos.length === 0) return todos;
+  try {
+    const blame = await blameFile(filePath);
+    for (const todo of todos) {
+      const entry = blame.get(todo.line);
+      if (entry) {
+        todo.author = entry.author || null;
+        todo.authorMail = entry.author ? entry.authorMail : null;
+        todo.committedAt = entry.author ? entry.time : null;
+      }
+    }
+  } catch (error) {
+    log.trace(`No blame for ${filePath}: ${error.message}`);
+  }
+  return todos;
+}
+
+function countTodos(index) {
+  let count = 0;
+  for (const todos of index.files.values()) count += todos.length;
+  return count;
+}
+
+function notifyUpdated(rootPath, index) {
+  emit('todos:updated', { rootPath, count: countTodos(index), indexing: Boolean(index.jobId) });
+}
+
+async function reindexFile(rootPath, filePath) {
+  const index = indexes.get(rootPath);
+  if (!index) return;
+  const todos = await attachAuthors(filePath, toTodos(filePath, await searchFile(filePath, buildTagPattern(store.get('tags')))));
+  if (todos.length > 0) {
+    index.files.set(filePath, todos);
+  } else if (!index.files.delete(filePath)) {
+    return;
+  }
+  notifyUpdated(rootPath, index);
+}
+
+function indexWorkspace(rootPath) {
+  const index = indexes.get(rootPath);
+  if (index.jobId) cancelJob(index.jobId);
+  index.files.clear();
+
+  const job = runJob('todo-index', `Index TODOs in ${path.basename(rootPath)}`, async (handle) => {
+    const pending = [];
+    await searchWorkspace(rootPath, buildTagPattern(store.get('tags')), {
+      signal: handle.signal,
+      ignored: (entryPath, type) => isPathIgnored(rootPath, entryPath, type),
+      maxResults: Infinity,
+      onProgress: (count) => handle.reportProgress(null, `${count} files scanned`),
+      onFile: (filePath, matches) => {
+        if (handle.cancelled) return;
+        const todos = toTodos(filePath, matches);
+        index.files.set(filePath, todos);
+        pending.push(attachAuthors(filePath, todos));
+      }
+    });
+    await Promise.all(pending);
+    return countTodos(index);
+  });
+
+  index.jobId = job.id;
+  job.promise
+    .then((count) => log.info(`Indexed ${count} TODOs in ${rootPath}`))
+    .catch((error) => log.warn(`TODO indexing stopped for ${rootPath}: ${error.message}`))
+    .finally(() => {
+      if (index.jobId === job.id) index.jobId = null;
+      notifyUpdated(rootPath, index);
+    });
+  return job.id;
+}
+
+// Restart rather than rescan so the watcher also picks up newly included directories
+scopeEvents.on('changed', async (root) => {
+  if (!indexes.has(root)) return;
+  await stopIndex(root);
+  startIndex(root);
+});
+
+function startIndex(rootPath) {
+  const root = path.resolve(rootPath);
+  if (indexes.has(root)) {
+    return { rootPath: root, jobId: indexes.get(root).jobId };
+  }
+
+  const index = { files: new Map(), watcher: null, jobId: null };
+  indexes.set(root, index);
+
+  // Keep the index current as files change, through the workspace root's shared watcher
+  index.watcher = watchPath(root, (type, filePath) => {
+    if (type === 'add' || type === 'change') {
+      if (!isPathIgnored(root, filePath, 'file')) reindexFile(root, filePath);
+    } else if (type === 'unlink') {
+      if (index.files.delete(filePath)) notifyUpdated(root, index);
+    }
+  }, { kind: 'tree', workspaceRoot: root, owner: 'todos' });
+
+  return { rootPath: root, jobId: indexWorkspace(root) };
+}
+
+async function stopIndex(rootPath) {
+  const root = path.resolve(rootPath);
+  const index = indexes.get(root);
+  if (!index) return false;
+  indexes.delete(root);
+  if (index.jobId) cancelJob(index.jobId);
+  index.watcher.close();
+  return true;
+}
+
+function listTodos(rootPath, filter = {}) {
+  const root = path.resolve(rootPath);
+  const index = indexes.get(root);
+  if (!index) {
+    throw new ForgeError(ErrorCode.NOT_FOUND, `No TODO index for ${rootPath}`);
+  }
+
+  const todos = [];
+  for (const fileTodos of index.files.values()) {
+    for (const todo of fileTodos) {
+      if (filter.tags && !filter.tags.includes(todo.tag)) continue;
+      if (filter.author && todo.author !== filter.author && todo.owner !== filter.author) continue;
+      if (filter.path && !todo.path.startsWith(filter.path)) continue;
+      todos.push({ ...todo, relativePath: path.relative(root, todo.path) });
+    }
+  }
+  return todos.sort((a, b) => a.relativePath.localeCompare(b.relativePath) || a.line - b.line);
+}
+
+function setTags(tags) {
+  const cleaned = Array.from(new Set((tags || []).map((tag) => String(tag).trim()).filter(Boolean)));
+  if (cleaned.length === 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'At least one tag is required');
+  }
+  store.set('tags', cleaned);
+
+  // Rescan every open workspace with the new tags
+  for (const root of indexes.keys()) {
+    indexWorkspace(root);
+  }
+  return cleaned;
+}
+
+function setupTodoHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('todos:index', async (event, rootPath) => {
+    return startIndex(rootPath);
+  });
+
+  ipcMain.handle('todos:stop', async (event, rootPath) => {
+    return stopIndex(rootPath);
+  });
+
+  ipcMain.handle('todos:list', async (event, rootPath, filter) => {
+    return listTodos(rootPath, filter);
+  });
+
+  ipcMain.handle('todos:getTags', async () => {
+    return store.get('tags');
+  });
+
+  ipcMain.handle('todos:setTags', async (event, tags) => {
+    return setTags(tags);
+  });
+
+  process.on('exit', () => {
+    for (const index of indexes.values()) index.watcher.close();
+  });
+}
+
+module.exports = { setupTodoHandlers, listTodos, startIndex };
@@ -0,0 +1,287 @@
+// TOML reader covering what Forge's config files use: tables, dotted and quoted keys,
+// arrays of tables, strings (basic, literal, multi-line), integers, floats, booleans,
+// arrays, and inline tables. Dates are returned as strings.
+
+class TomlError extends Error {
+  constructor(message, line) {
+    super(`${message} (line ${line})`);
+    this.line = line;
+  }
+}
+
+const ESCAPES = { b: '\b', t: '\t', n: '\n', f: '\f', r: '\r', '"': '"', '\\': '\\' };
+
+class Parser {
+  constructor(text) {
+    this.text = text.replace(/\r\n/g, '\n');
+    this.pos = 0;
+    this.line = 1;
+  }
+
+  error(message) {
+    return new TomlError(message, this.line);
+  }
+
+  peek(offset = 0) {
+    return this.text[this.pos + offset];
+  }
+
+  startsWith(token) {
+    return this.text.startsWith(token, this.pos);
+  }
+
+  advance(count = 1) {
+    for (let i = 0; i < count; i++) {
+      if (this.text[this.pos] === '\n') this.line++;
+      this.pos++;
+    }
+  }
+
+  skipSpaces() {
+    while (this.peek() === ' ' || this.peek() === '\t') this.advance();
+  }
+
+  // Whitespace, newlines, and comments (inside arrays)
+  skipBlank() {
+    for (;;) {
+      this.skipSpaces();
+      if (this.peek() === '#') {
+        while (this.pos < this.text.length && this.peek() !== '\n') this.advance();
+      } else if (this.peek() === '\n') {
+        this.advance();
+      } else {
+        return;
+      }
+    }
+  }
+
+  expectLineEnd() {
+    this.skipSpaces();
+    if (this.peek() === '#') {
+      while (this.pos < this.text.length && this.peek() !== '\n') this.advance();
+    }
+    if (this.pos < this.text.length && this.peek() !== '\n') throw this.error(`Unexpected "${this.peek()}"`);
+    this.advance();
+  }
+
+  parseKey() {
+    const parts = [];
+    for (;;) {
+      this.skipSpaces();
+      if (this.peek() === '"') {
+        parts.push(this.parseBasicString());
+      } else if (this.peek() === "'") {
+        parts.push(this.parseLiteralString());
+      } else {
+        const match = /^[A-Za-z0-9_-]+/.exec(this.text.slice(this.pos));
+        if (!match) throw this.error('Expected a key');
+        parts.push(match[0]);
+        this.advance(match[0].length);
+      }
+      this.skipSpaces();
+      if (this.peek() !== '.') return parts;
+      this.advance();
+    }
+  }
+
+  parseBasicString() {
+    if (this.startsWith('"""')) {
+      this.advance(3);
+      if (this.peek() === '\n') this.advance();
+      return this.readBasic('"""');
+    }
+    this.advance();
+    return this.readBasic('"');
+  }
+
+  readBasic(terminator) {
+    let value = '';
+    for (;;) {
+      if (this.pos >= this.text.length) throw this.error('Unterminated string');
+      if (this.startsWith(terminator)) {
+        this.advance(terminator.length);
+        return value;
+      }
+      const char = this.peek();
+      if (char === '\n' && terminator === '"') throw this.error('Newline in string');
+      if (char === '\\') {
+        const next = this.peek(1);
+        if (next === 'u' || next === 'U') {
+          const length = next === 'u' ? 4 : 8;
+          value += String.fromCodePoint(parseInt(this.text.substr(this.pos + 2, length), 16));
+          this.advance(2 + length);
+        } else if (next === '\n' && terminator === '"""') {
+          // Line-ending backslash trims the newline and leading whitespace
+          this.advance(2);
+          while (/\s/.test(this.peek() || '')) this.advance();
+        } else if (ESCAPES[next] !== undefined) {
+          value += ESCAPES[next];
+          this.advance(2);
+        } else {
+          throw this.error(`Invalid escape \\${next}`);
+        }
+        continue;
+      }
+      value += char;
+      this.advance();
+    }
+  }
+
+  parseLiteralString() {
+    const multiline = this.startsWith("'''");
+    const terminator = multiline ? "'''" : "'";
+    this.advance(terminator.length);
+    if (multiline && this.peek() === '\n') this.advance();
+    const end = this.text.indexOf(terminator, this.pos);
+    if (end < 0) throw this.error('Unterminated string');
+    const value = this.text.slice(this.pos, end);
+    if (!multiline && value.includes('\n')) throw this.error('Newline in string');
+    this.advance(end - this.pos + terminator.length);
+    return value;
+  }
+
+  parseValue() {
+    this.skipSpaces();
+    const char = this.peek();
+    if (char === '"') return this.parseBasicString();
+    if (char === "'") return this.parseLiteralString();
+    if (char === '[') return this.parseArray();
+    if (char === '{') return this.parseInlineTable();
+    const match = /^[^\s,\]}#]+/.exec(this.text.slice(this.pos));
+    if (!match) throw this.error('Expected a value');
+    const token = match[0];
+    this.advance(token.length);
+    if (token === 'true') return true;
+    if (token === 'false') return false;
+    if (/^[+-]?(inf|nan)$/.test(token)) return token.includes('nan') ? NaN : (token.startsWith('-') ? -Infinity : Infinity);
+    const number = token.replace(/_/g, '');
+    if (/^[+-]?(0x[0-9a-f]+|0o[0-7]+|0b[01]+)$/i.test(number)) {
+      const sign = number.startsWith('-') ? -1 : 1;
+      const digits = number.replace(/^[+-]/, '');
+      const radix = { x: 16, o: 8, b: 2 }[digits[1].toLowerCase()];
+      return sign * parseInt(digits.slice(2), radix);
+    }
+    if (/^[+-]?\d+(\.\d+)?([eE][+-]?\d+)?$/.test(number)) return Number(number);
+    // Dates and times, kept as written
+    if (/^\d{4}-\d{2}-\d{2}|^\d{2}:\d{2}/.test(token)) {
+      const time = /^[T ]\d{2}:\d{2}[^\s,\]}#]*/.exec(this.text.slice(this.pos));
+      if (time && /^\d{4}-\d{2}-\d{2}$/.test(token)) {
+        this.advance(time[0].length);
+        return token + time[0];
+      }
+      return token;
+    }
+    throw this.error(`Invalid value "${token}"`);
+  }
+
+  parseArray() {
+    this.advance();
+    const values = [];
+    for (;;) {
+      this.skipBlank();
+      if (this.peek() === ']') {
+        this.advance();
+        return values;
+      }
+      values.push(this.parseValue());
+      this.skipBlank();
+      if (this.peek() === ',') {
+        this.advance();
+      } else if (this.peek() !== ']') {
+        throw this.error('Expected "," or "]" in array');
+      }
+    }
+  }
+
+  parseInlineTable() {
+    this.advance();
+    const table = {};
+    this.skipSpaces();
+    if (this.peek() === '}') {
+      this.advance();
+      return table;
+    }
+    for (;;) {
+      const key = this.parseKey();
+      if (this.peek() !== '=') throw this.error('Expected "="');
+      this.advance();
+      assign(this, table, key, this.parseValue());
+      this.skipSpaces();
+      if (this.peek() === '}') {
+        this.advance();
+        return table;
+      }
+      if (this.peek() !== ',') throw this.error('Expected "," or "}" in inline table');
+      this.advance();
+    }
+  }
+}
+
+function isTable(value) {
+  return value !== null && typeof value === 'object' && !Array.isArray(value);
+}
+
+// Walk (creating) tables along `path`; arrays of tables resolve to their last element.
+// Each table passed through is added to `visited` when given.
+function descend(parser, root, path, visited) {
+  let table = root;
+  for (const part of path) {
+    if (table[part] === undefined) table[part] = {};
+    let next = table[part];
+    if (Array.isArray(next)) next = next[next.length - 1];
+    if (!isTable(next)) throw parser.error(`"${path.join('.')}" is not a table`);
+    if (visited) visited.add(next);
+    table = next;
+  }
+  return table;
+}
+
+// `defined`, when given, collects the tables a dotted key creates or extends
+function assign(parser, table, key, value, defined) {
+  const target = descend(parser, table, key.slice(0, -1), defined);
+  const name = key[key.length - 1];
+  if (Object.prototype.hasOwnProperty.call(target, name)) throw parser.error(`Duplicate key "${key.join('.')}"`);
+  target[name] = value;
+}
+
+function parseToml(text) {
+  const parser = new Parser(text);
+  const root = {};
+  // Tables that already had a [header] or were defined through dotted keys (`a.b = 1`
+  // defines `a`). Ones only created implicitly, like `a` by [a.b], may still get a header
+  // of their own, but only once.
+  const defined = new Set();
+  let current = root;
+  for (;;) {
+    parser.skipBlank();
+    if (parser.pos >= parser.text.length) return root;
+    if (parser.startsWith('[[')) {
+      parser.advance(2);
+      const key = parser.parseKey();
+      if (!parser.startsWith(']]')) throw parser.error('Expected "]]"');
+      parser.advance(2);
+      const parent = descend(parser, root, key.slice(0, -1));
+      const name = key[key.length - 1];
+      if (parent[name] === undefined) parent[name] = [];
+      if (!Array.isArray(parent[name])) throw parser.error(`"${key.join('.')}" is not an array of tables`);
+      current = {};
+      parent[name].push(current);
+    } else if (parser.peek() === '[') {
+      parser.advance();
+      const key = parser.parseKey();
+      if (parser.peek() !== ']') throw parser.error('Expected "]"');
+      parser.advance();
+      current = descend(parser, root, key);
+      if (defined.has(current)) throw parser.error(`Duplicate table "${key.join('.')}"`);
+      defined.add(current);
+    } else {
+      const key = parser.parseKey();
+      if (parser.peek() !== '=') throw parser.error('Expected "="');
+      parser.advance();
+      assign(parser, current, key, parser.parseValue(), defined);
+    }
+    parser.expectLineEnd();
+  }
+}
+
+module.exports = { parseToml, TomlError };
@@ -0,0 +1,138 @@
+const path = require('path');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+
+const log = createLogger('trust');
+
+const store = new JsonStore('workspace-trust', {
+  // Absolute folder path -> 'trusted' | 'untrusted'
+  decisions: {},
+  onboarding: {
+    completed: false,
+    completedSteps: [],
+    firstRunAt: null
+  }
+});
+
+const ONBOARDING_STEPS = ['welcome', 'theme', 'shell', 'workspace', 'telemetry'];
+
+function normalize(folderPath) {
+  const resolved = path.resolve(folderPath);
+  return process.platform === 'win32' ? resolved.toLowerCase() : resolved;
+}
+
+// Find the closest decision for a path, walking up through parent folders
+function resolveTrust(folderPath) {
+  const decisions = store.get('decisions', {});
+  let current = normalize(folderPath);
+
+  while (true) {
+    if (decisions[current]) {
+      return {
+        path: normalize(folderPath),
+        trusted: decisions[current] === 'trusted',
+        decidedAt: current,
+        inherited: current !== normalize(folderPath)
+      };
+    }
+    const parent = path.dirname(current);
+    if (parent === current) break;
+    current = parent;
+  }
+
+  // Unknown workspaces start untrusted until the user decides
+  return { path: normalize(folderPath), trusted: false, decidedAt: null, inherited: false };
+}
+
+function isPathTrusted(folderPath) {
+  if (!folderPath) return false;
+  return resolveTrust(folderPath).trusted;
+}
+
+// What an untrusted workspace is allowed to do
+function getRestrictions(folderPath) {
+  const trusted = isPathTrusted(folderPath);
+  return {
+    trusted,
+    allowTaskAutoRun: trusted,
+    allowEnvFiles: trusted,
+    restrictedTerminals: !trusted
+  };
+}
+
+function setWorkspaceTrust(folderPath, trusted) {
+  const decisions = { ...store.get('decisions', {}) };
+  decisions[normalize(folderPath)] = trusted ? 'trusted' : 'untrusted';
+  store.set('decisions', decisions);
+  log.info(`Workspace ${trusted ? 'trusted' : 'untrusted'}: ${folderPath}`);
+
+  const result = resolveTrust(folderPath);
+  emit('trust:changed', result);
+  return result;
+}
+
+function getOnboardingState() {
+  const onboarding = store.get('onboarding');
+  if (!onboarding.firstRunAt) {
+    onboarding.firstRunAt = new Date().toISOString();
+    store.set('onboarding', onboarding);
+    return { ...onboarding, isFirstRun: true, steps: ONBOARDING_STEPS };
+  }
+  return { ...onboarding, isFirstRun: false, steps: ONBOARDING_STEPS };
+}
+
+function setupTrustHandlers(ipcMain) {
+  ipcMain.handle('trust:get', async (event, folderPath) => {
+    return { ...resolveTrust(folderPath), restrictions: getRestrictions(folderPath) };
+  });
+
+  ipcMain.handle('trust:set', async (event, folderPath, trusted) => {
+    return setWorkspaceTrust(folderPath, trusted);
+  });
+
+  ipcMain.handle('trust:remove', async (event, folderPath) => {
+    const decisions = { ...store.get('decisions', {}) };
+    delete decisions[normalize(folderPath)];
+    store.set('decisions', decisions);
+    return true;
+  });
+
+  ipcMain.handle('trust:list', async () => {
+    return Object.entries(store.get('decisions', {})).map(([folder, decision]) => ({
+      path: folder,
+      trusted: decision === 'trusted'
+    }));
+  });
+
+  // Onboarding state
+  ipcMain.handle('onboarding:getState', async () => {
+    return getOnboardingState();
+  });
+
+  ipcMain.handle('onboarding:completeStep', async (event, step) => {
+    if (!ONBOARDING_STEPS.includes(step)) {
+      throw new Error(`Unknown onboarding step: ${step}`);
+    }
+    const onboarding = store.get('onboarding');
+    const completedSteps = Array.from(new Set([...onboarding.completedSteps, step]));
+    store.set('onboarding', {
+      ...onboarding,
+      completedSteps,
+      completed: ONBOARDING_STEPS.every((s) => completedSteps.includes(s))
+    });
+    return getOnboardingState();
+  });
+
+  ipcMain.handle('onboarding:skip', async () => {
+    store.set('onboarding', { ...store.get('onboarding'), completed: true });
+    return getOnboardingState();
+  });
+
+  ipcMain.handle('onboarding:reset', async () => {
+    store.set('onboarding', { completed: false, completedSteps: [], firstRunAt: null });
+    return getOnboardingState();
+  });
+}
+
+module.exports = { setupTrustHandlers, isPathTrusted, getRestrictions, resolveTrust, setWorkspaceTrust };
@@ -0,0 +1,251 @@
+const { spawn } = require('child_process');
+const { dialog } = require('electron');
+const { v4: uuidv4 } = require('uuid');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+
+const log = createLogger('tunnels');
+
+// How long a tunnel may take to report its public URL
+const START_TIMEOUT = 60 * 1000;
+const OUTPUT_TAIL = 4000;
+
+// Tunnel backends are external programs; each prints the public URL once connected.
+// `custom` covers self-hosted relays: any command taking the port, with a URL pattern.
+const PROVIDERS = {
+  cloudflared: {
+    label: 'Cloudflare Tunnel',
+    command: (settings) => settings.cloudflaredPath || 'cloudflared',
+    args: (port, host) => ['tunnel', '--no-autoupdate', '--url', `http://${host}:${port}`],
+    urlPattern: /https:\/\/[a-z0-9-]+\.trycloudflare\.com/i
+  },
+  ngrok: {
+    label: 'ngrok',
+    command: (settings) => settings.ngrokPath || 'ngrok',
+    args: (port, host) => ['http', `${host}:${port}`, '--log', 'stdout', '--log-format', 'logfmt'],
+    urlPattern: /url=(https:\/\/[^\s]+)/
+  },
+  custom: {
+    label: 'Custom relay',
+    command: (settings) => settings.custom.command,
+    // "{port}" and "{host}" in the configured arguments are substituted
+    args: (port, host, settings) => settings.custom.args.map((arg) => arg.replace(/\{port\}/g, String(port)).replace(/\{host\}/g, host)),
+    urlPattern: (settings) => new RegExp(settings.custom.urlPattern)
+  }
+};
+
+const store = new JsonStore('tunnels', {
+  settings: {
+    provider: 'cloudflared',
+    cloudflaredPath: null,
+    ngrokPath: null,
+    custom: { command: null, args: ['{port}'], urlPattern: 'https?://\\S+' },
+    // Ask before each port is exposed to the internet
+    confirm: true
+  }
+});
+
+// Live tunnels by id: { id, port, host, provider, status, url, error, process, output, owner, startedAt, stopping }
+const tunnels = new Map();
+
+function describe(tunnel) {
+  return {
+    id: tunnel.id,
+    port: tunnel.port,
+    host: tunnel.host,
+    provider: tunnel.provider,
+    status: tunnel.status,
+    url: tunnel.url,
+    error: tunnel.error,
+    startedAt: tunnel.startedAt
+  };
+}
+
+function publish(tunnel) {
+  emit('tunnel:status', describe(tunnel), { target: tunnel.owner });
+}
+
+function getSettings() {
+  return store.get('settings');
+}
+
+function updateSettings(changes = {}) {
+  const settings = { ...getSettings(), ...changes, custom: { ...getSettings().custom, ...(changes.custom || {}) } };
+  if (!PROVIDERS[settings.provider]) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown tunnel provider: ${settings.provider}`);
+  }
+  if (!Array.isArray(settings.custom.args) || settings.custom.args.some((arg) => typeof arg !== 'string')) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Custom relay arguments must be a list of strings');
+  }
+  try {
+    new RegExp(settings.custom.urlPattern);
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid URL pattern: ${error.message}`);
+  }
+  store.set('settings', settings);
+  return settings;
+}
+
+async function confirmExposure(sender, port, provider) {
+  const win = windowManager.windowFromSender(sender);
+  const options = {
+    type: 'warning',
+    buttons: ['Share Publicly', 'Cancel'],
+    defaultId: 0,
+    cancelId: 1,
+    message: `Expose port ${port} to the internet?`,
+    detail: `Anyone with the link will be able to reach the server on port ${port} through ${PROVIDERS[provider].label} until you stop the tunnel.`
+  };
+  const { response } = win ? await dialog.showMessageBox(win, options) : await dialog.showMessageBox(options);
+  return response === 0;
+}
+
+function finish(tunnel, status, error) {
+  if (tunnel.status === 'closed' || tunnel.status === 'failed') return;
+  tunnel.status = status;
+  tunnel.error = error || null;
+  log.info(`Tunnel ${tunnel.id} for port ${tunnel.port} ${status}${error ? `: ${error}` : ''}`);
+  publish(tunnel);
+  tunnels.delete(tunnel.id);
+}
+
+// Launch the provider and resolve once it reports a public URL
+function startTunnel({ port, host = 'localhost', provider }, owner) {
+  const settings = getSettings();
+  const providerId = provider || settings.provider;
+  const definition = PROVIDERS[providerId];
+  if (!definition) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown tunnel provider: ${providerId}`);
+  }
+  if (!Number.isInteger(port) || port < 1 || port > 65535) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid port: ${port}`);
+  }
+  const existing = Array.from(tunnels.values()).find((tunnel) => tunnel.port === port && tunnel.host === host);
+  if (existing) return Promise.resolve(describe(existing));
+
+  const command = definition.command(settings);
+  if (!command) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'No command is configured for the custom relay');
+  }
+  const urlPattern = typeof definition.urlPattern === 'function' ? definition.urlPattern(settings) : definition.urlPattern;
+
+  const tunnel = {
+    id: `tunnel_${uuidv4()}`,
+    port,
+    host,
+    provider: providerId,
+    status: 'starting',
+    url: null,
+    error: null,
+    process: null,
+    output: '',
+    owner,
+    startedAt: new Date().toISOString()
+  };
+  tunnels.set(tunnel.id, tunnel);
+  publish(tunnel);
+
+  return new Promise((resolve, reject) => {
+    const child = spawn(command, definition.args(port, host, settings), { windowsHide: true, stdio: ['ignore', 'pipe', 'pipe'] });
+    tunnel.process = child;
+
+    const timer = setTimeout(() => {
+      fail(new ForgeError(ErrorCode.TIMEOUT, `${definition.label} did not report a public URL within ${START_TIMEOUT / 1000}s`));
+      child.kill();
+    }, START_TIMEOUT);
+
+    function fail(error) {
+      clearTimeout(timer);
+      finish(tunnel, 'failed', error.message);
+      reject(error);
+    }
+
+    // cloudflared logs to stderr, ngrok to stdout; watch both
+    const onOutput = (chunk) => {
+      tunnel.output = (tunnel.output + chunk).slice(-OUTPUT_TAIL);
+      if (tunnel.url) return;
+      const match = tunnel.output.match(urlPattern);
+      if (!match) return;
+      clearTimeout(timer);
+      tunnel.url = match[1] || match[0];
+      tunnel.status = 'open';
+      log.info(`Tunnel ${tunnel.id} open: port ${port} -> ${tunnel.url}`);
+      publish(tunnel);
+      resolve(describe(tunnel));
+    };
+    child.stdout.on('data', onOutput);
+    child.stderr.on('data', onOutput);
+
+    child.on('error', (error) => {
+      fail(error.code === 'ENOENT'
+        ? new ForgeError(ErrorCode.NOT_FOUND, `${command} was not found; install it or set its path in tunnel settings`, { recoverable: false })
+        : ForgeError.from(error, `Could not start ${definition.label}`));
+    });
+
+    child.on('exit', (code) => {
+      if (!tunnel.url && tunnel.stopping) {
+        clearTimeout(timer);
+        finish(tunnel, 'closed');
+        reject(new ForgeError(ErrorCode.CANCELLED, 'Tunnel stopped before it connected'));
+      } else if (!tunnel.url) {
+        const detail = tunnel.output.trim().split('\n').slice(-3).join('\n');
+        fail(new ForgeError(ErrorCode.INTERNAL, `${definition.label} exited with code ${code} before connecting${detail ? `: ${detail}` : ''}`));
+      } else {
+        finish(tunnel, tunnel.stopping ? 'closed' : 'failed', tunnel.stopping ? null : `${definition.label} exited with code ${code}`);
+      }
+    });
+  });
+}
+
+function stopTunnel(id) {
+  const tunnel = tunnels.get(id);
+  if (!tunnel) return false;
+  tunnel.stopping = true;
+  if (tunnel.process && tunnel.process.exitCode === null) {
+    tunnel.process.kill();
+  } else {
+    finish(tunnel, 'closed');
+  }
+  return true;
+}
+
+function setupTunnelHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('tunnel:getSettings', async () => getSettings());
+
+  ipcMain.handle('tunnel:updateSettings', async (event, changes) => updateSettings(changes));
+
+  ipcMain.handle('tunnel:start', async (event, options = {}) => {
+    const provider = options.provider || getSettings().provider;
+    if (getSettings().confirm && !await confirmExposure(event.sender, options.port, provider)) {
+      throw new ForgeError(ErrorCode.CANCELLED, 'Tunnel cancelled');
+    }
+    const tunnel = await startTunnel(options, event.sender);
+    // Tunnels close with the window that opened them
+    const win = windowManager.windowFromSender(event.sender);
+    if (win) win.once('closed', () => stopTunnel(tunnel.id));
+    return tunnel;
+  });
+
+  ipcMain.handle('tunnel:stop', async (event, id) => stopTunnel(id));
+
+  ipcMain.handle('tunnel:list', async () => Array.from(tunnels.values(), describe));
+
+  // Recent provider output, for diagnosing a tunnel that won't connect
+  ipcMain.handle('tunnel:getOutput', async (event, id) => {
+    const tunnel = tunnels.get(id);
+    return tunnel ? tunnel.output : null;
+  });
+
+  process.on('exit', () => {
+    for (const tunnel of tunnels.values()) {
+      if (tunnel.process && tunnel.process.exitCode === null) tunnel.process.kill();
+    }
+  });
+}
+
+module.exports = { setupTunnelHandlers, startTunnel, stopTunnel };
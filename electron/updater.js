@@ -1,22 +1,48 @@
 const { autoUpdater } = require('electron-updater');
-const { dialog, BrowserWindow, ipcMain } = require('electron');
-const log = require('electron-log');
+const { app, dialog, BrowserWindow, ipcMain } = require('electron');
+const { createLogger } = require('./logger');
+const { JsonStore } = require('./jsonStore');
+const { emit } = require('./events');
 
 // Configure logging
+const log = createLogger('updater');
 autoUpdater.logger = log;
-autoUpdater.logger.transports.file.level = 'info';
-log.info('Auto-updater module loaded');
+log.debug('Auto-updater module loaded');
 
-class AppUpdater {
+const CHANNELS = ['stable', 'beta'];
+
+const settings = new JsonStore('updater', {
+  channel: 'stable',
+  checkOnStartup: true,
+  deferredVersion: null
+});
+
+class UpdateManager {
   constructor() {
     // Configure auto-updater
     autoUpdater.autoDownload = false;
     autoUpdater.autoInstallOnAppQuit = true;
 
+    this.latestInfo = null;
+    this.downloadedInfo = null;
+    this.applyChannel(settings.get('channel'));
+
     // Set up event listeners
     this.setupEventListeners();
   }
 
+  // Beta builds are published as prereleases on the "beta" channel
+  applyChannel(channel) {
+    if (!CHANNELS.includes(channel)) {
+      throw new Error(`Invalid update channel: ${channel}`);
+    }
+    autoUpdater.channel = channel === 'stable' ? 'latest' : channel;
+    autoUpdater.allowPrerelease = channel !== 'stable';
+    autoUpdater.allowDowngrade = false;
+    settings.set('channel', channel);
+    log.info(`Update channel set to ${channel}`);
+  }
+
   setupEventListeners() {
     // Checking for update
     autoUpdater.on('checking-for-update', () => {
@@ -26,15 +52,16 @@ class AppUpdater {
 
     // Update available
     autoUpdater.on('update-available', (info) => {
-      log.info('Update available:', info);
+      log.info('Update available:', info.version);
+      this.latestInfo = info;
       this.sendStatusToWindow('update-available', info);
-      
+
       // Show dialog to user
       dialog.showMessageBox(BrowserWindow.getFocusedWindow(), {
         type: 'info',
         title: 'Update Available',
         message: `A new version ${info.version} is available. Would you like to download it now?`,
-        detail: info.releaseNotes ? `Release notes:\n${info.releaseNotes}` : 'A new version is available with improvements and bug fixes.',
+        detail: info.releaseNotes ? `Release notes:\n${this.formatReleaseNotes(info)}` : 'A new version is available with improvements and bug fixes.',
         buttons: ['Download', 'Later'],
         defaultId: 0,
         cancelId: 1
@@ -47,7 +74,7 @@ class AppUpdater {
 
     // No update available
     autoUpdater.on('update-not-available', (info) => {
-      log.info('Update not available:', info);
+      log.info('Update not available:', info.version);
       this.sendStatusToWindow('update-not-available');
     });
 
@@ -59,18 +86,16 @@ class AppUpdater {
 
     // Download progress
     autoUpdater.on('download-progress', (progressObj) => {
-      let logMessage = `Download speed: ${progressObj.bytesPerSecond}`;
-      logMessage = `${logMessage} - Downloaded ${progressObj.percent}%`;
-      logMessage = `${logMessage} (${progressObj.transferred}/${progressObj.total})`;
-      log.info(logMessage);
+      log.debug(`Downloaded ${progressObj.percent.toFixed(1)}% (${progressObj.transferred}/${progressObj.total}) at ${progressObj.bytesPerSecond} B/s`);
       this.sendStatusToWindow('download-progress', progressObj);
     });
 
     // Update downloaded
     autoUpdater.on('update-downloaded', (info) => {
-      log.info('Update downloaded:', info);
+      log.info('Update downloaded:', info.version);
+      this.downloadedInfo = info;
       this.sendStatusToWindow('update-downloaded', info);
-      
+
       // Show dialog to user
       dialog.showMessageBox(BrowserWindow.getFocusedWindow(), {
         type: 'info',
@@ -82,7 +107,9 @@ class AppUpdater {
         cancelId: 1
       }).then((result) => {
         if (result.response === 0) {
-          autoUpdater.quitAndInstall(false, true);
+          this.installNow();
+        } else {
+          this.deferInstall();
         }
       });
     });
@@ -97,27 +124,103 @@ class AppUpdater {
     });
 
     ipcMain.handle('updater:install', () => {
-      autoUpdater.quitAndInstall(false, true);
+      this.installNow();
+    });
+
+    ipcMain.handle('updater:deferInstall', () => {
+      return this.deferInstall();
     });
 
     ipcMain.handle('updater:getVersion', () => {
       return autoUpdater.currentVersion.version;
     });
+
+    ipcMain.handle('updater:getChannel', () => {
+      return { channel: settings.get('channel'), channels: CHANNELS };
+    });
+
+    ipcMain.handle('updater:setChannel', (event, channel) => {
+      const previous = settings.get('channel');
+      this.applyChannel(channel);
+      // Leaving beta for stable may need to go "down" a version, but only on this one check
+      return this.checkForUpdates({ allowDowngrade: previous === 'beta' && channel === 'stable' });
+    });
+
+    ipcMain.handle('updater:setCheckOnStartup', (event, enabled) => {
+      settings.set('checkOnStartup', Boolean(enabled));
+      return settings.get('checkOnStartup');
+    });
+
+    ipcMain.handle('updater:getReleaseNotes', () => {
+      const info = this.downloadedInfo || this.latestInfo;
+      if (!info) return null;
+      return {
+        version: info.version,
+        releaseDate: info.releaseDate,
+        notes: this.formatReleaseNotes(info)
+      };
+    });
+
+    ipcMain.handle('updater:getState', () => {
+      return {
+        currentVersion: autoUpdater.currentVersion.version,
+        channel: settings.get('channel'),
+        checkOnStartup: settings.get('checkOnStartup'),
+        availableVersion: this.latestInfo ? this.latestInfo.version : null,
+        downloadedVersion: this.downloadedInfo ? this.downloadedInfo.version : null,
+        deferredVersion: settings.get('deferredVersion')
+      };
+    });
+  }
+
+  // releaseNotes is either a string or a list of { version, note } when fullChangelog is on
+  formatReleaseNotes(info) {
+    if (!info.releaseNotes) return '';
+    if (typeof info.releaseNotes === 'string') return info.releaseNotes;
+    return info.releaseNotes.map((entry) => `## ${entry.version}\n${entry.note || ''}`).join('\n\n');
+  }
+
+  installNow() {
+    settings.set('deferredVersion', null);
+    autoUpdater.quitAndInstall(false, true);
+  }
+
+  // Keep the downloaded update and apply it the next time the app quits
+  deferInstall() {
+    if (!this.downloadedInfo) {
+      throw new Error('No downloaded update to defer');
+    }
+    autoUpdater.autoInstallOnAppQuit = true;
+    settings.set('deferredVersion', this.downloadedInfo.version);
+    this.sendStatusToWindow('update-deferred', this.downloadedInfo);
+    return this.downloadedInfo.version;
   }
 
   sendStatusToWindow(status, data = null) {
-    const windows = BrowserWindow.getAllWindows();
-    windows.forEach(window => {
-      window.webContents.send('updater:status', { status, data });
-    });
+    emit('updater:status', { status, data });
   }
 
-  checkForUpdates() {
+  checkForUpdates({ allowDowngrade = false } = {}) {
     log.info('Manual check for updates requested');
-    return autoUpdater.checkForUpdatesAndNotify();
+    autoUpdater.allowDowngrade = allowDowngrade;
+    return autoUpdater.checkForUpdates()
+      .then((result) => (result ? result.updateInfo : null))
+      .finally(() => {
+        autoUpdater.allowDowngrade = false;
+      });
   }
 
   checkForUpdatesOnStartup() {
+    // A deferred update was applied on the previous quit
+    const deferred = settings.get('deferredVersion');
+    if (deferred && deferred === app.getVersion()) {
+      settings.set('deferredVersion', null);
+    }
+
+    if (!settings.get('checkOnStartup')) {
+      return;
+    }
+
     // Check for updates 3 seconds after startup
     setTimeout(() => {
       log.info('Checking for updates on startup');
@@ -126,4 +229,4 @@ class AppUpdater {
   }
 }
 
-module.exports = AppUpdater;
\ No newline at end of file
+module.exports = UpdateManager;
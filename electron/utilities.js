@@ -0,0 +1,757 @@
+const fs = require('fs');
+const path = require('path');
+const crypto = require('crypto');
+const { Readable, Transform, Writable } = require('stream');
+const { pipeline } = require('stream/promises');
+const { StringDecoder } = require('string_decoder');
+const { parseYaml, stringifyYaml, YamlError } = require('./yaml');
+const { parseCron, describeCron, nextRuns, checkTimezone, toWall, fromWall, offsetAt, zonedIso, formatOffset, CronError } = require('./cron');
+const { runJob } = require('./jobs');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+
+const log = createLogger('utilities');
+
+// Transforms stream their input, so files of any size work when the result goes to
+// `outputPath`; results returned to the renderer as text are capped.
+const MAX_TEXT_RESULT = 32 * 1024 * 1024;
+// YAML <-> JSON builds the whole document in memory
+const MAX_DOCUMENT_SIZE = 64 * 1024 * 1024;
+const READ_CHUNK_SIZE = 1024 * 1024;
+const MAX_UUIDS = 10000;
+const MAX_CRON_RUNS = 100;
+const HASH_ALGORITHMS = ['md5', 'sha1', 'sha256', 'sha384', 'sha512', 'sha3-256', 'sha3-512'];
+
+class JsonSyntaxError extends Error {
+  constructor(message, position) {
+    super(`${message} at line ${position.line}, column ${position.column}`);
+    this.reason = message;
+    this.line = position.line;
+    this.column = position.column;
+    this.offset = position.offset;
+  }
+}
+
+const STRING_SPECIAL = /["\\\u0000-\u001f]/g;
+const NUMBER_PATTERN = /^-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][-+]?[0-9]+)?$/;
+const LITERALS = new Set(['true', 'false', 'null']);
+
+// Incremental JSON scanner: validates the grammar chunk by chunk and re-emits the document
+// pretty-printed (`indent` > 0), minified (`indent` 0) or not at all (`indent` null).
+// Strings and numbers are copied verbatim, so escapes and number precision survive.
+class JsonReformatter {
+  constructor(indent) {
+    this.indent = indent;
+    this.unit = indent ? ' '.repeat(indent) : '';
+    this.stack = [];
+    // value | keyOrEnd | key | colon | valueOrEnd | commaOrEnd | done
+    this.expect = 'value';
+    this.token = null;
+    this.escape = 0;
+    this.offset = 0;
+    this.line = 1;
+    this.lineStart = 0;
+    // An opening bracket whose newline waits until we know the container isn't empty
+    this.pendingOpen = false;
+    this.out = [];
+  }
+
+  position(index) {
+    const offset = this.offset + index;
+    return { line: this.line, column: offset - this.lineStart + 1, offset };
+  }
+
+  fail(message, index) {
+    throw new JsonSyntaxError(message, this.position(index));
+  }
+
+  emit(text) {
+    if (this.indent !== null) this.out.push(text);
+  }
+
+  newline(depth) {
+    if (this.indent) this.emit(`\n${this.unit.repeat(depth)}`);
+  }
+
+  // Called before a value or key is written
+  beginItem() {
+    if (this.pendingOpen) {
+      this.pendingOpen = false;
+      this.newline(this.stack.length);
+    }
+  }
+
+  afterValue() {
+    this.expect = this.stack.length ? 'commaOrEnd' : 'done';
+  }
+
+  write(chunk) {
+    let i = 0;
+    const length = chunk.length;
+    while (i < length) {
+      if (this.token) {
+        i = this.continueToken(chunk, i);
+        continue;
+      }
+      const ch = chunk[i];
+      if (ch === ' ' || ch === '\t' || ch === '\r') {
+        i++;
+        continue;
+      }
+      if (ch === '\n') {
+        this.line++;
+        this.lineStart = this.offset + i + 1;
+        i++;
+        continue;
+      }
+      if (i === 0 && this.offset === 0 && ch === '\uFEFF') {
+        i++;
+        continue;
+      }
+      this.punctuation(ch, i);
+      i++;
+    }
+    this.offset += length;
+    const output = this.out.join('');
+    this.out = [];
+    return output;
+  }
+
+  punctuation(ch, i) {
+    const expect = this.expect;
+    if (expect === 'done') this.fail(`Unexpected ${describe(ch)} after the JSON value`, i);
+    if (ch === '}' || ch === ']') {
+      const open = this.stack[this.stack.length - 1];
+      const matches = (ch === '}' && open === 'object') || (ch === ']' && open === 'array');
+      const allowed = expect === 'commaOrEnd' || (expect === 'keyOrEnd' && ch === '}') || (expect === 'valueOrEnd' && ch === ']');
+      if (!matches || !allowed) this.fail(`Unexpected ${describe(ch)}`, i);
+      this.stack.pop();
+      if (this.pendingOpen) this.pendingOpen = false;
+      else this.newline(this.stack.length);
+      this.emit(ch);
+      this.afterValue();
+      return;
+    }
+    if (ch === ',') {
+      if (expect !== 'commaOrEnd') this.fail('Unexpected ","', i);
+      this.emit(',');
+      this.newline(this.stack.length);
+      this.expect = this.stack[this.stack.length - 1] === 'object' ? 'key' : 'value';
+      return;
+    }
+    if (ch === ':') {
+      if (expect !== 'colon') this.fail('Unexpected ":"', i);
+      this.emit(this.indent ? ': ' : ':');
+      this.expect = 'value';
+      return;
+    }
+    if (expect === 'key' || expect === 'keyOrEnd') {
+      if (ch !== '"') this.fail(`Expected a property name in double quotes but found ${describe(ch)}`, i);
+      this.beginItem();
+      this.startToken('key', ch, i);
+      return;
+    }
+    if (expect !== 'value' && expect !== 'valueOrEnd') {
+      this.fail(expect === 'colon' ? `Expected ":" but found ${describe(ch)}` : `Expected "," or a closing bracket but found ${describe(ch)}`, i);
+    }
+    this.beginItem();
+    if (ch === '{' || ch === '[') {
+      this.stack.push(ch === '{' ? 'object' : 'array');
+      this.emit(ch);
+      this.pendingOpen = true;
+      this.expect = ch === '{' ? 'keyOrEnd' : 'valueOrEnd';
+      return;
+    }
+    if (ch === '"') this.startToken('string', ch, i);
+    else if (ch === '-' || (ch >= '0' && ch <= '9')) this.startToken('number', ch, i);
+    else if (ch === 't' || ch === 'f' || ch === 'n') this.startToken('literal', ch, i);
+    else this.fail(`Unexpected ${describe(ch)}`, i);
+  }
+
+  startToken(kind, ch, i) {
+    this.token = { kind, text: [ch], start: this.position(i) };
+    this.escape = 0;
+  }
+
+  continueToken(chunk, i) {
+    const token = this.token;
+    if (token.kind === 'string' || token.kind === 'key') {
+      while (i < chunk.length) {
+        if (this.escape > 0) {
+          const ch = chunk[i];
+          if (this.escape === 1) {
+            if (ch === 'u') this.escape = 5;
+            else if ('"\\/bfnrt'.includes(ch)) this.escape = 0;
+            else this.fail(`Invalid escape "\\${ch}"`, i);
+          } else {
+            if (!/[0-9a-fA-F]/.test(ch)) this.fail('Invalid \\u escape', i);
+            this.escape--;
+            if (this.escape === 1) this.escape = 0;
+          }
+          token.text.push(ch);
+          i++;
+          continue;
+        }
+        STRING_SPECIAL.lastIndex = i;
+        const match = STRING_SPECIAL.exec(chunk);
+        const stop = match ? match.index : chunk.length;
+        if (stop > i) token.text.push(chunk.slice(i, stop));
+        if (!match) return chunk.length;
+        const ch = match[0];
+        if (ch === '"') {
+          token.text.push(ch);
+          this.endToken();
+          return stop + 1;
+        }
+        if (ch === '\\') {
+          token.text.push(ch);
+          this.escape = 1;
+          i = stop + 1;
+          continue;
+        }
+        this.fail(ch === '\n' ? 'Unterminated string' : 'Control character in string', stop);
+      }
+      return i;
+    }
+    // Numbers and literals end at the first character that can't belong to them
+    const pattern = token.kind === 'number' ? /[^0-9eE+\-.]/g : /[^a-z]/g;
+    pattern.lastIndex = i;
+    const match = pattern.exec(chunk);
+    const stop = match ? match.index : chunk.length;
+    token.text.push(chunk.slice(i, stop));
+    if (match) this.endToken();
+    return stop;
+  }
+
+  endToken() {
+    const token = this.token;
+    const text = token.text.join('');
+    this.token = null;
+    if (token.kind === 'number' && !NUMBER_PATTERN.test(text)) throw new JsonSyntaxError(`Invalid number ${text}`, token.start);
+    if (token.kind === 'literal' && !LITERALS.has(text)) throw new JsonSyntaxError(`Unexpected token ${text.slice(0, 20)}`, token.start);
+    this.emit(text);
+    if (token.kind === 'key') this.expect = 'colon';
+    else this.afterValue();
+  }
+
+  end() {
+    if (this.token) {
+      if (this.token.kind === 'string' || this.token.kind === 'key') throw new JsonSyntaxError('Unterminated string', this.token.start);
+      this.endToken();
+    }
+    if (this.expect !== 'done') {
+      const where = this.position(0);
+      if (this.expect === 'value' && !this.stack.length) throw new JsonSyntaxError('No JSON value', where);
+      throw new JsonSyntaxError(`Unexpected end of input; ${this.stack.length} unclosed bracket${this.stack.length === 1 ? '' : 's'}`, where);
+    }
+    const output = this.out.join('') + (this.indent ? '\n' : '');
+    this.out = [];
+    return output;
+  }
+}
+
+function describe(ch) {
+  if (ch === '\uFEFF') return 'byte order mark';
+  return /[\x00-\x1f]/.test(ch) ? `character U+${ch.charCodeAt(0).toString(16).padStart(4, '0').toUpperCase()}` : `"${ch}"`;
+}
+
+function jsonTransform(indent) {
+  const decoder = new StringDecoder('utf8');
+  const formatter = new JsonReformatter(indent);
+  return new Transform({
+    transform(chunk, encoding, callback) {
+      try {
+        callback(null, formatter.write(decoder.write(chunk)));
+      } catch (error) {
+        callback(error);
+      }
+    },
+    flush(callback) {
+      try {
+        const rest = decoder.end();
+        callback(null, (rest ? formatter.write(rest) : '') + formatter.end());
+      } catch (error) {
+        callback(error);
+      }
+    }
+  });
+}
+
+// Base64 works on 3-byte groups, so a partial group carries over to the next chunk
+function base64EncodeTransform({ urlSafe = false } = {}) {
+  let carry = Buffer.alloc(0);
+  const encode = (buffer) => buffer.toString(urlSafe ? 'base64url' : 'base64');
+  return new Transform({
+    transform(chunk, encoding, callback) {
+      const data = carry.length ? Buffer.concat([carry, chunk]) : chunk;
+      const whole = data.length - (data.length % 3);
+      carry = data.subarray(whole);
+      callback(null, encode(data.subarray(0, whole)));
+    },
+    flush(callback) {
+      callback(null, carry.length ? encode(carry) : '');
+    }
+  });
+}
+
+// Accepts standard and URL-safe alphabets with or without padding; whitespace is ignored
+function base64DecodeTransform() {
+  let carry = '';
+  let offset = 0;
+  const decoder = new StringDecoder('latin1');
+  const check = (text) => {
+    const bad = /[^A-Za-z0-9+/\-_=]/.exec(text);
+    if (bad) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid base64 character "${bad[0]}" near offset ${offset + bad.index}`);
+  };
+  return new Transform({
+    transform(chunk, encoding, callback) {
+      try {
+        const text = carry + decoder.write(chunk).replace(/\s+/g, '');
+        check(text);
+        const whole = text.length - (text.length % 4);
+        carry = text.slice(whole);
+        offset += whole;
+        callback(null, Buffer.from(text.slice(0, whole).replace(/-/g, '+').replace(/_/g, '/'), 'base64'));
+      } catch (error) {
+        callback(error);
+      }
+    },
+    flush(callback) {
+      if (carry.length === 1) {
+        callback(new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Truncated base64 input'));
+        return;
+      }
+      callback(null, carry ? Buffer.from(carry.replace(/-/g, '+').replace(/_/g, '/'), 'base64') : Buffer.alloc(0));
+    }
+  });
+}
+
+// `form` encodes spaces as "+" (application/x-www-form-urlencoded)
+function urlEncodeTransform({ form = false } = {}) {
+  const decoder = new StringDecoder('utf8');
+  // StringDecoder holds back split UTF-8 sequences, but a surrogate pair can still end up
+  // split between two writes
+  let pending = '';
+  const encode = (text) => {
+    const encoded = encodeURIComponent(text);
+    return form ? encoded.replace(/%20/g, '+') : encoded;
+  };
+  return new Transform({
+    transform(chunk, encoding, callback) {
+      let text = pending + decoder.write(chunk);
+      const last = text.charCodeAt(text.length - 1);
+      pending = last >= 0xd800 && last <= 0xdbff ? text.slice(-1) : '';
+      if (pending) text = text.slice(0, -1);
+      callback(null, encode(text));
+    },
+    flush(callback) {
+      try {
+        callback(null, encode(pending + decoder.end()));
+      } catch (error) {
+        callback(new ForgeError(ErrorCode.INVALID_ARGUMENT, 'The input contains an unpaired surrogate'));
+      }
+    }
+  });
+}
+
+// Decodes %XX sequences to bytes so multi-byte characters split across escapes and
+// chunks come out intact
+function urlDecodeTransform({ form = false } = {}) {
+  let carry = '';
+  let offset = 0;
+  const decoder = new StringDecoder('latin1');
+  return new Transform({
+    transform(chunk, encoding, callback) {
+      const text = carry + decoder.write(chunk);
+      // Hold back an escape cut off by the chunk boundary
+      const tail = /%[0-9a-fA-F]?$/.exec(text);
+      const end = tail ? tail.index : text.length;
+      carry = text.slice(end);
+      const bytes = [];
+      for (let i = 0; i < end; i++) {
+        const ch = text[i];
+        if (ch === '%') {
+          const hex = text.slice(i + 1, i + 3);
+          if (!/^[0-9a-fA-F]{2}$/.test(hex)) {
+            callback(new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid percent escape "%${hex}" at offset ${offset + i}`));
+            return;
+          }
+          bytes.push(parseInt(hex, 16));
+          i += 2;
+        } else if (form && ch === '+') {
+          bytes.push(0x20);
+        } else {
+          // Decoded as latin1, so every character is one input byte
+          bytes.push(ch.charCodeAt(0));
+        }
+      }
+      offset += end;
+      callback(null, Buffer.from(bytes));
+    },
+    flush(callback) {
+      if (carry) callback(new ForgeError(ErrorCode.INVALID_ARGUMENT, `Truncated percent escape "${carry}" at the end of the input`));
+      else callback();
+    }
+  });
+}
+
+// The whole document is parsed, so these collect their input first
+function documentTransform(convert) {
+  const chunks = [];
+  let size = 0;
+  return new Transform({
+    transform(chunk, encoding, callback) {
+      size += chunk.length;
+      if (size > MAX_DOCUMENT_SIZE) {
+        callback(new ForgeError(ErrorCode.INVALID_ARGUMENT, `Documents over ${MAX_DOCUMENT_SIZE / 1024 / 1024} MB cannot be converted`));
+        return;
+      }
+      chunks.push(chunk);
+      callback();
+    },
+    flush(callback) {
+      try {
+        callback(null, convert(Buffer.concat(chunks).toString('utf8').replace(/^\uFEFF/, '')));
+      } catch (error) {
+        callback(error);
+      }
+    }
+  });
+}
+
+function parseJsonDocument(text) {
+  try {
+    return JSON.parse(text);
+  } catch (error) {
+    // Re-scan for a precise position; JSON.parse messages vary between engines
+    const formatter = new JsonReformatter(null);
+    formatter.write(text);
+    formatter.end();
+    throw error;
+  }
+}
+
+const OPERATIONS = {
+  base64Encode: (options) => base64EncodeTransform(options),
+  base64Decode: () => base64DecodeTransform(),
+  urlEncode: (options) => urlEncodeTransform(options),
+  urlDecode: (options) => urlDecodeTransform(options),
+  jsonFormat: (options) => jsonTransform(Number.isInteger(options.indent) ? Math.max(1, Math.min(8, options.indent)) : 2),
+  jsonMinify: () => jsonTransform(0),
+  yamlToJson: (options) => documentTransform((text) => {
+    const indent = Number.isInteger(options.indent) ? Math.max(0, Math.min(8, options.indent)) : 2;
+    return `${JSON.stringify(parseYaml(text), null, indent)}\n`;
+  }),
+  jsonToYaml: (options) => documentTransform((text) => stringifyYaml(parseJsonDocument(text), { indent: options.indent }))
+};
+
+function toForgeError(error) {
+  if (error instanceof JsonSyntaxError) {
+    return new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid JSON: ${error.message}`, { details: { line: error.line, column: error.column, offset: error.offset } });
+  }
+  if (error instanceof YamlError) {
+    return new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid YAML: ${error.message}`, { details: { line: error.line } });
+  }
+  return ForgeError.from(error);
+}
+
+function checkInput(input) {
+  if (input && typeof input.text === 'string') return;
+  if (input && typeof input.path === 'string' && input.path) return;
+  throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Input needs `text` or a file `path`');
+}
+
+async function openInput(input) {
+  if (typeof input.text === 'string') {
+    const buffer = Buffer.from(input.text, 'utf8');
+    return { stream: Readable.from([buffer]), size: buffer.length };
+  }
+  const filePath = path.resolve(input.path);
+  const stats = await fs.promises.stat(filePath);
+  if (stats.isDirectory()) throw new ForgeError(ErrorCode.IS_DIRECTORY, `${filePath} is a folder`);
+  return { stream: fs.createReadStream(filePath, { highWaterMark: READ_CHUNK_SIZE }), size: stats.size, filePath };
+}
+
+// Collects the result in memory, refusing results too big to send back as text
+function collectSink() {
+  const chunks = [];
+  let size = 0;
+  const sink = new Writable({
+    write(chunk, encoding, callback) {
+      size += chunk.length;
+      if (size > MAX_TEXT_RESULT) {
+        callback(new ForgeError(ErrorCode.INVALID_ARGUMENT, `The result is over ${MAX_TEXT_RESULT / 1024 / 1024} MB; write it to a file with outputPath`));
+        return;
+      }
+      chunks.push(Buffer.isBuffer(chunk) ? chunk : Buffer.from(chunk));
+      callback();
+    }
+  });
+  sink.result = () => Buffer.concat(chunks);
+  return sink;
+}
+
+// Runs a streaming transform over { text } or { path }. With options.outputPath the result
+// is written there; otherwise it comes back as text (null with `binary` set when the bytes
+// aren't UTF-8, e.g. decoded images). File inputs run as cancellable jobs.
+async function transform(operation, input, options = {}) {
+  const factory = OPERATIONS[operation];
+  if (!factory) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown transform ${operation}`, { details: { operations: Object.keys(OPERATIONS) } });
+  checkInput(input);
+  const outputPath = options.outputPath ? path.resolve(options.outputPath) : null;
+
+  const run = async (handle) => {
+    const source = await openInput(input);
+    if (outputPath && source.filePath === outputPath) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'The output file must differ from the input file');
+    let bytesIn = 0;
+    source.stream.on('data', (chunk) => {
+      bytesIn += chunk.length;
+      if (handle && source.size) handle.reportProgress(bytesIn / source.size);
+    });
+    const sink = outputPath ? fs.createWriteStream(outputPath) : collectSink();
+    try {
+      await pipeline(source.stream, factory(options), sink, handle ? { signal: handle.signal } : {});
+    } catch (error) {
+      if (outputPath) await fs.promises.rm(outputPath, { force: true }).catch(() => {});
+      if (error.name === 'AbortError') throw new ForgeError(ErrorCode.CANCELLED, `${operation} was cancelled`);
+      throw toForgeError(error);
+    }
+    if (outputPath) {
+      return { operation, outputPath, text: null, binary: false, bytesIn, bytesOut: sink.bytesWritten };
+    }
+    const bytes = sink.result();
+    let text = null;
+    try {
+      text = new TextDecoder('utf-8', { fatal: true }).decode(bytes);
+    } catch (error) {
+      // Binary output; the caller can repeat with outputPath to save it
+    }
+    return { operation, outputPath: null, text, binary: text === null, bytesIn, bytesOut: bytes.length };
+  };
+
+  if (typeof input.text === 'string') return run(null);
+  const job = runJob('utilities', `${operation} ${path.basename(input.path)}`, run);
+  const result = await job.promise;
+  log.info(`${operation} ${input.path}: ${result.bytesIn} bytes in, ${result.bytesOut} out`);
+  return result;
+}
+
+// Streams the input through the JSON scanner without building the document
+async function validateJson(input) {
+  checkInput(input);
+  const source = await openInput(input);
+  const decoder = new StringDecoder('utf8');
+  const scanner = new JsonReformatter(null);
+  try {
+    for await (const chunk of source.stream) scanner.write(decoder.write(chunk));
+    scanner.write(decoder.end());
+    scanner.end();
+  } catch (error) {
+    if (!(error instanceof JsonSyntaxError)) throw ForgeError.from(error);
+    return { valid: false, error: { message: error.reason, line: error.line, column: error.column, offset: error.offset } };
+  }
+  return { valid: true, error: null };
+}
+
+// One pass over the input computes every requested digest
+async function hash(input, { algorithms = ['sha256'], encoding = 'hex' } = {}) {
+  checkInput(input);
+  const names = Array.isArray(algorithms) ? algorithms : [algorithms];
+  for (const name of names) {
+    if (!HASH_ALGORITHMS.includes(name)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unsupported hash ${name}`, { details: { algorithms: HASH_ALGORITHMS } });
+  }
+  if (!['hex', 'base64'].includes(encoding)) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'encoding must be hex or base64');
+
+  const run = async (handle) => {
+    const source = await openInput(input);
+    const hashes = names.map((name) => [name, crypto.createHash(name)]);
+    let bytes = 0;
+    for await (const chunk of source.stream) {
+      if (handle) {
+        handle.throwIfCancelled();
+        if (source.size) handle.reportProgress((bytes + chunk.length) / source.size);
+      }
+      bytes += chunk.length;
+      for (const [, digest] of hashes) digest.update(chunk);
+    }
+    return { bytes, digests: Object.fromEntries(hashes.map(([name, digest]) => [name, digest.digest(encoding)])) };
+  };
+  if (typeof input.text === 'string') return run(null);
+  return runJob('utilities', `Hash ${path.basename(input.path)}`, run).promise;
+}
+
+// Version 7 puts a millisecond timestamp first so ids sort by creation time
+function uuidV7() {
+  const bytes = crypto.randomBytes(16);
+  const now = Date.now();
+  bytes.writeUIntBE(now, 0, 6);
+  bytes[6] = 0x70 | (bytes[6] & 0x0f);
+  bytes[8] = 0x80 | (bytes[8] & 0x3f);
+  const hex = bytes.toString('hex');
+  return `${hex.slice(0, 8)}-${hex.slice(8, 12)}-${hex.slice(12, 16)}-${hex.slice(16, 20)}-${hex.slice(20)}`;
+}
+
+function generateUuids({ count = 1, version = 4, uppercase = false } = {}) {
+  if (!Number.isInteger(count) || count < 1 || count > MAX_UUIDS) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `count must be between 1 and ${MAX_UUIDS}`);
+  if (version !== 4 && version !== 7) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Only version 4 and 7 UUIDs are supported');
+  const ids = Array.from({ length: count }, () => (version === 7 ? uuidV7() : crypto.randomUUID()));
+  return uppercase ? ids.map((id) => id.toUpperCase()) : ids;
+}
+
+function timezoneOption(timezone) {
+  try {
+    return checkTimezone(timezone || 'UTC');
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, error.message, { details: { timezone } });
+  }
+}
+
+const relativeFormat = new Intl.RelativeTimeFormat('en', { numeric: 'auto' });
+const RELATIVE_STEPS = [
+  ['year', 365 * 864e5],
+  ['month', 30 * 864e5],
+  ['week', 7 * 864e5],
+  ['day', 864e5],
+  ['hour', 36e5],
+  ['minute', 6e4],
+  ['second', 1e3]
+];
+
+// "3 hours ago", "in 2 days", using the largest unit that fits
+function formatRelative(ms, now) {
+  const delta = ms - now;
+  for (const [unit, size] of RELATIVE_STEPS) {
+    if (Math.abs(delta) >= size || unit === 'second') return relativeFormat.format(Math.round(delta / size), unit);
+  }
+  return '';
+}
+
+function describeInstant(ms, timezone, now) {
+  const wall = new Date(toWall(ms, timezone));
+  const startOfYear = Date.UTC(wall.getUTCFullYear(), 0, 1);
+  // ISO weeks start on Monday; week 1 holds the year's first Thursday
+  const thursday = new Date(wall.getTime());
+  thursday.setUTCDate(wall.getUTCDate() + 3 - ((wall.getUTCDay() + 6) % 7));
+  const isoWeek = 1 + Math.floor((thursday.getTime() - Date.UTC(thursday.getUTCFullYear(), 0, 1)) / (7 * 864e5));
+  return {
+    epochSeconds: Math.floor(ms / 1000),
+    epochMillis: ms,
+    iso: new Date(ms).toISOString(),
+    zoned: zonedIso(ms, timezone),
+    timezone,
+    offset: formatOffset(offsetAt(ms, timezone)),
+    rfc2822: new Date(ms).toUTCString(),
+    relative: formatRelative(ms, now),
+    dayOfWeek: new Intl.DateTimeFormat('en-US', { timeZone: timezone, weekday: 'long' }).format(new Date(ms)),
+    dayOfYear: 1 + Math.floor((Date.UTC(wall.getUTCFullYear(), wall.getUTCMonth(), wall.getUTCDate()) - startOfYear) / 864e5),
+    isoWeek
+  };
+}
+
+const EPOCH_UNITS = { seconds: 1e3, milliseconds: 1, microseconds: 1e-3, nanoseconds: 1e-6 };
+const RELATIVE_UNITS = { s: 1e3, sec: 1e3, second: 1e3, m: 6e4, min: 6e4, minute: 6e4, h: 36e5, hr: 36e5, hour: 36e5, d: 864e5, day: 864e5, w: 6048e5, week: 6048e5, mo: 2592e6, month: 2592e6, y: 31536e6, yr: 31536e6, year: 31536e6 };
+const ZONELESS_ISO = /^(\d{4})-(\d{2})-(\d{2})(?:[T ](\d{2}):(\d{2})(?::(\d{2})(?:\.(\d{1,3})\d*)?)?)?$/;
+
+// Epoch values are told apart by magnitude: up to 11 integer digits are seconds, then
+// milliseconds, microseconds and nanoseconds
+function detectEpochUnit(text) {
+  const digits = text.replace(/^-/, '').split('.')[0].length;
+  if (digits <= 11) return 'seconds';
+  if (digits <= 14) return 'milliseconds';
+  if (digits <= 17) return 'microseconds';
+  return 'nanoseconds';
+}
+
+function parseRelative(text, now) {
+  const lower = text.toLowerCase();
+  if (lower === 'now') return now;
+  if (lower === 'yesterday') return now - 864e5;
+  if (lower === 'tomorrow') return now + 864e5;
+  const match = /^(in\s+|[+-]\s*)?((?:\d+(?:\.\d+)?\s*[a-z]+\s*,?\s*(?:and\s+)?)+?)(\s+ago)?$/.exec(lower);
+  if (!match) return null;
+  let total = 0;
+  for (const [, amount, unit] of match[2].matchAll(/(\d+(?:\.\d+)?)\s*([a-z]+)/g)) {
+    const size = RELATIVE_UNITS[unit] || RELATIVE_UNITS[unit.replace(/s$/, '')];
+    if (!size) return null;
+    total += Number(amount) * size;
+  }
+  const past = Boolean(match[3]) || (match[1] || '').trim() === '-';
+  return Math.round(past ? now - total : now + total);
+}
+
+// Converts an epoch number (any unit), ISO-8601/RFC 2822 date, or relative phrase such as
+// "3 days ago" into every other representation. Dates without an offset are read in
+// `timezone`.
+function convertTimestamp(input, { timezone, unit, now = Date.now() } = {}) {
+  const zone = timezoneOption(timezone);
+  if (typeof input === 'number') input = String(input);
+  if (typeof input !== 'string' || !input.trim()) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A timestamp is required');
+  const text = input.trim();
+  let ms = null;
+  let detected;
+  if (/^-?\d+(\.\d+)?$/.test(text)) {
+    if (unit && !EPOCH_UNITS[unit]) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `unit must be one of ${Object.keys(EPOCH_UNITS).join(', ')}`);
+    detected = unit || detectEpochUnit(text);
+    ms = Math.round(Number(text) * EPOCH_UNITS[detected]);
+  } else if (ZONELESS_ISO.test(text)) {
+    const [, year, month, day, hour = '0', minute = '0', second = '0', fraction = '0'] = ZONELESS_ISO.exec(text);
+    const wall = Date.UTC(Number(year), Number(month) - 1, Number(day), Number(hour), Number(minute), Number(second), Number(fraction.padEnd(3, '0')));
+    ms = fromWall(wall, zone);
+    if (ms === null) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `${text} does not exist in ${zone} (skipped by a daylight saving change)`);
+    detected = 'iso';
+  } else {
+    ms = parseRelative(text, now);
+    detected = 'relative';
+    if (ms === null) {
+      ms = Date.parse(text);
+      detected = /^\d{4}-/.test(text) ? 'iso' : 'date';
+    }
+  }
+  if (!Number.isFinite(ms) || Number.isNaN(new Date(ms).getTime())) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Could not read "${text}" as a timestamp`);
+  }
+  return { input: text, detected, ...describeInstant(ms, zone, now) };
+}
+
+function explainCron(expression, { count = 5, timezone, from = Date.now() } = {}) {
+  const zone = timezoneOption(timezone);
+  let schedule;
+  try {
+    schedule = parseCron(expression);
+  } catch (error) {
+    if (error instanceof CronError) throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Invalid cron expression: ${error.message}`, { details: { expression } });
+    throw error;
+  }
+  const limit = Math.max(1, Math.min(MAX_CRON_RUNS, Number.isInteger(count) ? count : 5));
+  const runs = nextRuns(schedule, { count: limit, from, timezone: zone });
+  return {
+    expression: schedule.expression,
+    normalized: schedule.normalized,
+    hasSeconds: schedule.hasSeconds,
+    description: describeCron(schedule),
+    fields: Object.fromEntries(['second', 'minute', 'hour', 'dayOfMonth', 'month', 'dayOfWeek'].map((name) => [name, schedule[name].text])),
+    timezone: zone,
+    // Empty when the expression can never match, e.g. 30 February
+    nextRuns: runs.map((ms) => ({ epochMillis: ms, iso: new Date(ms).toISOString(), zoned: zonedIso(ms, zone), relative: formatRelative(ms, from) }))
+  };
+}
+
+function setupUtilityHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('utilities:transform', async (event, operation, input, options) => transform(operation, input, options || {}));
+
+  ipcMain.handle('utilities:validateJson', async (event, input) => validateJson(input));
+
+  ipcMain.handle('utilities:hash', async (event, input, options) => hash(input, options || {}));
+
+  ipcMain.handle('utilities:uuid', async (event, options) => generateUuids(options || {}));
+
+  ipcMain.handle('utilities:explainCron', async (event, expression, options) => explainCron(expression, options || {}));
+
+  ipcMain.handle('utilities:convertTimestamp', async (event, input, options) => convertTimestamp(input, options || {}));
+
+  ipcMain.handle('utilities:listTimezones', async () => Intl.supportedValuesOf('timeZone'));
+}
+
+module.exports = { setupUtilityHandlers, transform, validateJson, hash, generateUuids, explainCron, convertTimestamp };
@@ -0,0 +1,316 @@
+const fs = require('fs');
+const path = require('path');
+const { createWatcher, remediationHint } = require('./watcher');
+const { scopeEvents, watcherIgnore, anchorPattern } = require('./indexScope');
+const { DEFAULT_EXCLUDES } = require('./search');
+const { globToRegExp } = require('./editorconfig');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { windowManager } = require('./windows');
+
+const log = createLogger('watch-hub');
+
+// Below this, a medium-sized repository can exhaust inotify watches on its own
+const LOW_INOTIFY_LIMIT = 65536;
+// Keep an idle root watcher briefly so closing and reopening a file doesn't rescan the tree
+const IDLE_CLOSE_MS = 5000;
+const EVENT_KINDS = ['add', 'change', 'unlink', 'rename', 'addDir', 'unlinkDir'];
+// Renderer subscriptions get changes in batches; past the cap a batch only says it overflowed
+const BATCH_MS = 50;
+const MAX_BATCH_CHANGES = 1000;
+const MAX_SUBSCRIPTION_GLOBS = 100;
+
+// One watcher per workspace root, shared by everything interested in paths under it,
+// instead of a native watcher per open file. Paths the root watcher skips (node_modules,
+// index scope exclusions) and paths outside any workspace get a watcher on their own
+// directory, shared the same way.
+//
+// Host key -> { key, root, depth, watcher, files, dirs, trees, closeTimer }, where files
+// maps a path to the subscriptions on it, dirs a directory to subscriptions on its direct
+// children, and trees holds subscriptions on everything below a directory.
+const hosts = new Map();
+let limitChecked = false;
+
+function checkInotifyLimit() {
+  if (limitChecked || process.platform !== 'linux') return;
+  limitChecked = true;
+  fs.readFile('/proc/sys/fs/inotify/max_user_watches', 'utf-8', (error, text) => {
+    const limit = error ? NaN : Number(text.trim());
+    if (limit < LOW_INOTIFY_LIMIT) log.warn(`fs.inotify.max_user_watches is ${limit}. ${remediationHint('ENOSPC')}`);
+  });
+}
+
+function isWithin(parent, child) {
+  return child === parent || child.startsWith(parent.endsWith(path.sep) ? parent : parent + path.sep);
+}
+
+function rootIgnore(root) {
+  return watcherIgnore(root, (watchedPath) => DEFAULT_EXCLUDES.includes(path.basename(watchedPath)));
+}
+
+// Whether the root watcher skips `targetPath` or a directory above it
+function skippedByRoot(root, targetPath) {
+  const ignored = rootIgnore(root);
+  let current = targetPath;
+  while (current !== root && isWithin(root, current)) {
+    if (ignored(current)) return true;
+    current = path.dirname(current);
+  }
+  return false;
+}
+
+function interestedIn(host, changedPath) {
+  const listeners = new Set([
+    ...(host.files.get(changedPath) || []),
+    ...(host.dirs.get(path.dirname(changedPath)) || [])
+  ]);
+  for (const subscription of host.trees) {
+    if (isWithin(subscription.path, changedPath)) listeners.add(subscription);
+  }
+  return listeners;
+}
+
+function notify(subscription, type, changedPath, oldPath) {
+  try {
+    subscription.listener(type, changedPath, oldPath);
+  } catch (error) {
+    log.warn(`Watch listener for ${subscription.path} failed: ${error.message}`);
+  }
+}
+
+function dispatch(host, type, changedPath) {
+  for (const subscription of interestedIn(host, changedPath)) notify(subscription, type, changedPath);
+}
+
+// Interests that follow renames hear about one if either side is theirs; the rest see the
+// unlink and add they would have seen before
+function dispatchRename(host, newPath, oldPath) {
+  const fromOld = interestedIn(host, oldPath);
+  const fromNew = interestedIn(host, newPath);
+  for (const subscription of new Set([...fromOld, ...fromNew])) {
+    if (subscription.renames) {
+      notify(subscription, 'rename', newPath, oldPath);
+      continue;
+    }
+    if (fromOld.has(subscription)) notify(subscription, 'unlink', oldPath);
+    if (fromNew.has(subscription)) notify(subscription, 'add', newPath);
+  }
+}
+
+function openHost(root, depth, owner) {
+  const key = depth === undefined ? root : `${root}#${depth}`;
+  let host = hosts.get(key);
+  if (host) {
+    clearTimeout(host.closeTimer);
+    host.closeTimer = null;
+    return host;
+  }
+  checkInotifyLimit();
+  host = { key, root, depth, watcher: null, files: new Map(), dirs: new Map(), trees: new Set(), closeTimer: null };
+  host.watcher = createWatcher(root, {
+    owner: depth === undefined ? 'workspace' : owner,
+    depth,
+    ignored: depth === undefined ? rootIgnore(root) : undefined
+  });
+  for (const type of ['add', 'change', 'unlink', 'addDir', 'unlinkDir']) {
+    host.watcher.on(type, (changedPath) => dispatch(host, type, changedPath));
+  }
+  host.watcher.on('rename', (newPath, oldPath) => dispatchRename(host, newPath, oldPath));
+  hosts.set(key, host);
+  log.info(`Watching ${root}${depth === undefined ? '' : ` (depth ${depth})`}`);
+  return host;
+}
+
+function releaseHost(host) {
+  if (host.files.size > 0 || host.dirs.size > 0 || host.trees.size > 0 || host.closeTimer) return;
+  host.closeTimer = setTimeout(() => {
+    if (hosts.get(host.key) !== host) return;
+    hosts.delete(host.key);
+    host.watcher.close();
+    log.info(`Stopped watching ${host.root}`);
+  }, IDLE_CLOSE_MS);
+}
+
+function addTo(map, key, subscription) {
+  if (!map.has(key)) map.set(key, new Set());
+  map.get(key).add(subscription);
+}
+
+function removeFrom(map, key, subscription) {
+  const set = map.get(key);
+  if (!set) return;
+  set.delete(subscription);
+  if (set.size === 0) map.delete(key);
+}
+
+// Register interest in `targetPath` and call `listener(type, changedPath, oldPath)` for its
+// events: 'add' | 'change' | 'unlink' | 'addDir' | 'unlinkDir', and with `renames`,
+// 'rename' (changedPath is where the file went). Without it a rename arrives as an unlink
+// and an add. `kind` is 'file' (the path itself), 'directory' (its direct children), or
+// 'tree' (everything below it). Interests under `workspaceRoot` share that root's watcher;
+// `owner` labels the watcher opened for anything else. Returns { close() }.
+function watchPath(targetPath, listener, { kind = 'file', workspaceRoot = null, owner = 'editor', renames = false } = {}) {
+  const resolved = path.resolve(targetPath);
+  const root = workspaceRoot ? path.resolve(workspaceRoot) : null;
+  const subscription = { path: resolved, kind, listener, renames };
+
+  let host;
+  if (root && isWithin(root, resolved) && !skippedByRoot(root, resolved)) {
+    host = openHost(root, undefined, owner);
+  } else {
+    // A file's directory, non-recursively, unless the whole tree was asked for
+    const existingRoot = Array.from(hosts.values()).find((candidate) => candidate.depth === undefined && isWithin(candidate.root, resolved) && !skippedByRoot(candidate.root, resolved));
+    if (existingRoot) host = openHost(existingRoot.root, undefined, owner);
+    else if (kind === 'tree') host = openHost(resolved, undefined, owner);
+    else host = openHost(kind === 'file' ? path.dirname(resolved) : resolved, 0, owner);
+  }
+
+  if (kind === 'tree') host.trees.add(subscription);
+  else addTo(kind === 'directory' ? host.dirs : host.files, resolved, subscription);
+
+  let closed = false;
+  return {
+    close() {
+      if (closed) return;
+      closed = true;
+      if (kind === 'tree') host.trees.delete(subscription);
+      else removeFrom(kind === 'directory' ? host.dirs : host.files, resolved, subscription);
+      releaseHost(host);
+    }
+  };
+}
+
+// Index scope changes move the root watcher's exclusions; reopen it so newly included
+// folders get watched
+scopeEvents.on('changed', (root) => {
+  const host = hosts.get(path.resolve(root));
+  if (!host) return;
+  host.watcher.reopen(host.watcher.health.mode === 'polling', host.watcher.pollingReason);
+});
+
+// Renderer subscriptions, keyed by webContents id and subscription id:
+// { subscriptionId, sender, root, globs, matchers, kinds, watch, changes, overflow, timer }
+const subscriptions = new Map();
+
+function subscriptionKey(sender, subscriptionId) {
+  return `${sender.id}:${subscriptionId}`;
+}
+
+function flushSubscription(subscription) {
+  subscription.timer = null;
+  if (subscription.sender.isDestroyed()) return;
+  emit('watcher:changes', {
+    subscriptionId: subscription.subscriptionId,
+    root: subscription.root,
+    changes: subscription.overflow ? [] : subscription.changes,
+    overflow: subscription.overflow
+  }, { target: subscription.sender });
+  subscription.changes = [];
+  subscription.overflow = false;
+}
+
+// Deliver `eventKinds` for workspace paths matching `globs` ("src/**/*.ts"; patterns
+// without a slash match at any depth) to the calling renderer as watcher:changes batches.
+// Reusing a subscription id replaces its interests.
+function watchPaths(sender, subscriptionId, globs, eventKinds) {
+  if (typeof subscriptionId !== 'string' || !subscriptionId) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A subscription id is required');
+  }
+  if (!Array.isArray(globs) || globs.length === 0 || globs.length > MAX_SUBSCRIPTION_GLOBS || !globs.every((glob) => typeof glob === 'string' && glob)) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Watch between 1 and ${MAX_SUBSCRIPTION_GLOBS} non-empty glob patterns`);
+  }
+  const kinds = eventKinds && eventKinds.length ? eventKinds : EVENT_KINDS;
+  const unknown = kinds.filter((kind) => !EVENT_KINDS.includes(kind));
+  if (unknown.length > 0) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Unknown watch event kind(s): ${unknown.join(', ')}`, { details: { allowed: EVENT_KINDS } });
+  }
+  const context = windowManager.fromSender(sender);
+  if (!context || !context.workspacePath) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Open a workspace to watch paths');
+  }
+
+  unwatch(sender, subscriptionId);
+  const root = path.resolve(context.workspacePath);
+  const subscription = {
+    subscriptionId,
+    sender,
+    root,
+    globs,
+    matchers: globs.map((glob) => globToRegExp(anchorPattern(glob))),
+    kinds,
+    watch: null,
+    changes: [],
+    overflow: false,
+    timer: null
+  };
+  const matches = (changedPath) => {
+    const relative = path.relative(root, changedPath).split(path.sep).join('/');
+    return subscription.matchers.some((matcher) => matcher.test(relative));
+  };
+  const record = (change) => {
+    if (subscription.changes.length < MAX_BATCH_CHANGES) subscription.changes.push(change);
+    else subscription.overflow = true;
+    if (!subscription.timer) subscription.timer = setTimeout(() => flushSubscription(subscription), BATCH_MS);
+  };
+  subscription.watch = watchPath(root, (type, changedPath, oldPath) => {
+    if (type !== 'rename') {
+      if (subscription.kinds.includes(type) && matches(changedPath)) record({ type, path: changedPath });
+      return;
+    }
+    const fromMatch = matches(oldPath);
+    const toMatch = matches(changedPath);
+    if (subscription.kinds.includes('rename') && (fromMatch || toMatch)) {
+      record({ type, path: changedPath, oldPath });
+      return;
+    }
+    if (fromMatch && subscription.kinds.includes('unlink')) record({ type: 'unlink', path: oldPath });
+    if (toMatch && subscription.kinds.includes('add')) record({ type: 'add', path: changedPath });
+  }, { kind: 'tree', workspaceRoot: root, renames: true });
+
+  const key = subscriptionKey(sender, subscriptionId);
+  subscriptions.set(key, subscription);
+  sender.once('destroyed', () => {
+    if (subscriptions.get(key) === subscription) unwatch(sender, subscriptionId);
+  });
+  return { subscriptionId, root, globs, kinds };
+}
+
+function unwatch(sender, subscriptionId) {
+  const key = subscriptionKey(sender, subscriptionId);
+  const subscription = subscriptions.get(key);
+  if (!subscription) return false;
+  subscriptions.delete(key);
+  clearTimeout(subscription.timer);
+  subscription.watch.close();
+  return true;
+}
+
+// Watched roots with their interest counts, for diagnostics
+function getWatchHubStats() {
+  return Array.from(hosts.values(), (host) => ({
+    root: host.root,
+    depth: host.depth === undefined ? null : host.depth,
+    files: host.files.size,
+    directories: host.dirs.size,
+    trees: host.trees.size,
+    health: { ...host.watcher.health }
+  }));
+}
+
+function setupWatchHubHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('watcher:watchPaths', async (event, subscriptionId, globs, eventKinds) => watchPaths(event.sender, subscriptionId, globs, eventKinds));
+
+  ipcMain.handle('watcher:unwatch', async (event, subscriptionId) => unwatch(event.sender, subscriptionId));
+
+  // This renderer's subscriptions
+  ipcMain.handle('watcher:listSubscriptions', async (event) => Array.from(subscriptions.values())
+    .filter((subscription) => subscription.sender === event.sender)
+    .map(({ subscriptionId, root, globs, kinds }) => ({ subscriptionId, root, globs, kinds })));
+
+  ipcMain.handle('watcher:getStats', async () => getWatchHubStats());
+}
+
+module.exports = { setupWatchHubHandlers, watchPath, getWatchHubStats };
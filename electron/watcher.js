@@ -0,0 +1,416 @@
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const { execFile } = require('child_process');
+const { EventEmitter } = require('events');
+const chokidar = require('chokidar');
+const { emit } = require('./events');
+const { createLogger } = require('./logger');
+const { withForgeErrors } = require('./errors');
+
+const log = createLogger('watcher');
+
+// Filesystems where native change notifications are missing or unreliable
+const NETWORK_FS_TYPES = new Set([
+  'nfs', 'nfs4', 'cifs', 'smbfs', 'smb3', '9p', 'v9fs', 'drvfs', 'afs', 'afpfs', 'webdav',
+  'fuse.sshfs', 'fuse.rclone', 'fuse.gcsfuse', 'fuse.s3fs'
+]);
+const POLL_INTERVAL = 1000;
+const POLL_BINARY_INTERVAL = 3000;
+// More events than this within one window suggests the kernel queue may have overflowed
+const BURST_WINDOW_MS = 1000;
+const BURST_THRESHOLD = 2000;
+const RECONCILE_QUIET_MS = 1500;
+// Errors that mean native watching can't keep up; switch to polling
+const EXHAUSTION_CODES = new Set(['ENOSPC', 'EMFILE', 'ENFILE']);
+// An unlink and an add of the same file (device and inode) this close together are a
+// rename. chokidar holds unlinks back ~100ms to spot atomic saves, so the add usually
+// arrives first.
+const RENAME_WINDOW_MS = 300;
+
+function fileId(stats) {
+  return stats && stats.ino ? `${stats.dev}:${stats.ino}` : null;
+}
+
+// What the user can do about running out of native watches
+function remediationHint(code) {
+  if (code === 'ENOSPC' && process.platform === 'linux') {
+    return 'The inotify watch limit is exhausted. Raise it with ' +
+      '`echo fs.inotify.max_user_watches=524288 | sudo tee /etc/sysctl.d/60-forge.conf && sudo sysctl --system`, ' +
+      'or exclude large folders from the index scope.';
+  }
+  if (code === 'EMFILE' || code === 'ENFILE') {
+    return process.platform === 'darwin'
+      ? 'Too many open files. Raise the limit with `sudo launchctl limit maxfiles 524288 524288`, or exclude large folders from the index scope.'
+      : 'Too many open files. Raise the limit (`ulimit -n`, or LimitNOFILE for a systemd session), or exclude large folders from the index scope.';
+  }
+  return null;
+}
+
+// All live watchers, for the health overview
+const watchers = new Set();
+
+let mountTable = null;
+
+// [{ mountPoint, fsType }] sorted longest mount point first
+function loadMountTable() {
+  if (mountTable) return Promise.resolve(mountTable);
+  return new Promise((resolve) => {
+    const finish = (entries) => {
+      mountTable = entries.sort((a, b) => b.mountPoint.length - a.mountPoint.length);
+      resolve(mountTable);
+    };
+    if (process.platform === 'linux') {
+      fs.readFile('/proc/mounts', 'utf-8', (error, text) => {
+        if (error) return finish([]);
+        finish(text.split('\n').filter(Boolean).map((line) => {
+          const [, mountPoint, fsType] = line.split(' ');
+          return { mountPoint: mountPoint.replace(/\\040/g, ' '), fsType };
+        }));
+      });
+    } else if (process.platform === 'darwin') {
+      // "//user@host/share on /Volumes/share (smbfs, nodev, nosuid, mounted by user)"
+      execFile('mount', [], { timeout: 5000 }, (error, stdout) => {
+        if (error) return finish([]);
+        finish(stdout.split('\n').map((line) => /^.+ on (.+) \(([^,)]+)/.exec(line)).filter(Boolean)
+          .map((match) => ({ mountPoint: match[1], fsType: match[2] })));
+      });
+    } else {
+      finish([]);
+    }
+  });
+}
+
+// Whether `targetPath` lives on a network, WSL-translated, or FUSE remote filesystem
+async function detectFilesystem(targetPath) {
+  const resolved = path.resolve(targetPath);
+  if (process.platform === 'win32') {
+    return { fsType: resolved.startsWith('\\\\') ? 'unc' : 'local', network: resolved.startsWith('\\\\') };
+  }
+  // Windows drives seen from WSL (drvfs/9p) don't deliver inotify events
+  if (process.platform === 'linux' && /microsoft/i.test(os.release()) && /^\/mnt\/[a-z](\/|$)/.test(resolved)) {
+    return { fsType: 'drvfs', network: true };
+  }
+  const mounts = await loadMountTable();
+  const mount = mounts.find((entry) => resolved === entry.mountPoint || resolved.startsWith(entry.mountPoint.endsWith('/') ? entry.mountPoint : `${entry.mountPoint}/`));
+  const fsType = mount ? mount.fsType : 'unknown';
+  return { fsType, network: NETWORK_FS_TYPES.has(fsType) };
+}
+
+// A chokidar watcher that falls back to polling on network filesystems or when native
+// watching runs out of resources, and rescans to reconcile after suspected event loss.
+// Emits 'add' | 'change' | 'unlink' | 'addDir' | 'unlinkDir' (path) like chokidar, plus
+// 'rename' (newPath, oldPath) for files moved within the tree, 'health' (state) and 'ready'. `depth` limits how many directory levels below root are
+// watched (all when omitted).
+class ResilientWatcher extends EventEmitter {
+  constructor(root, options = {}) {
+    super();
+    this.root = path.resolve(root);
+    this.ignored = options.ignored || (() => false);
+    this.pollingReason = null;
+    this.owner = options.owner || 'workspace';
+    this.forcePolling = Boolean(options.usePolling);
+    this.depth = options.depth;
+    this.watcher = null;
+    // filePath -> mtimeMs, the baseline for reconciling after lost events
+    this.snapshot = new Map();
+    // filePath -> "dev:ino" and back, to pair the unlink and add of a rename
+    this.fileIds = new Map();
+    this.pathsById = new Map();
+    // fileId -> { filePath, timer }: one half of a possible rename, waiting for the other
+    this.pendingUnlinks = new Map();
+    this.pendingAdds = new Map();
+    this.snapshotReady = false;
+    this.burst = { startedAt: 0, count: 0 };
+    this.reconcileTimer = null;
+    this.reconciling = false;
+    this.closed = false;
+    this.health = { root: this.root, owner: this.owner, status: 'starting', mode: 'native', fsType: null, reason: null, hint: null, lastRescanAt: null };
+    watchers.add(this);
+  }
+
+  async start() {
+    const filesystem = await detectFilesystem(this.root);
+    this.health.fsType = filesystem.fsType;
+    const polling = this.forcePolling || filesystem.network;
+    this.open(polling, filesystem.network ? `${filesystem.fsType} filesystem does not report changes reliably` : null);
+    return this;
+  }
+
+  open(polling, reason) {
+    if (this.closed) return;
+    this.pollingReason = polling ? reason : null;
+    this.watcher = chokidar.watch(this.root, {
+      ignoreInitial: true,
+      alwaysStat: true,
+      ignored: this.ignored,
+      usePolling: polling,
+      depth: this.depth,
+      interval: POLL_INTERVAL,
+      binaryInterval: POLL_BINARY_INTERVAL
+    });
+    this.watcher.on('add', (filePath, stats) => this.handle('add', filePath, stats));
+    this.watcher.on('change', (filePath, stats) => this.handle('change', filePath, stats));
+    this.watcher.on('unlink', (filePath) => this.handle('unlink', filePath));
+    // Directories aren't part of the reconcile snapshot; they're passed through for tree views
+    this.watcher.on('addDir', (dirPath) => this.emit('addDir', dirPath));
+    this.watcher.on('unlinkDir', (dirPath) => this.emit('unlinkDir', dirPath));
+    this.watcher.on('error', (error) => this.handleError(error));
+    this.watcher.on('ready', () => {
+      this.setHealth(polling ? 'polling' : 'healthy', polling ? 'polling' : 'native', this.pollingReason);
+      // After a reopen the old baseline is kept so the reconcile pass can find missed changes
+      if (!this.snapshotReady) {
+        this.snapshotReady = true;
+        this.buildSnapshot().then(() => this.emit('ready'));
+      }
+    });
+  }
+
+  handle(type, filePath, stats) {
+    this.trackBurst();
+    if (type === 'unlink') {
+      const id = this.fileIds.get(filePath);
+      this.snapshot.delete(filePath);
+      this.fileIds.delete(filePath);
+      const add = id && this.pendingAdds.get(id);
+      if (add) {
+        clearTimeout(add.timer);
+        this.pendingAdds.delete(id);
+        this.pathsById.set(id, add.filePath);
+        this.emit('rename', add.filePath, filePath);
+      } else if (id && this.pathsById.get(id) === filePath) {
+        this.pathsById.delete(id);
+        this.hold(this.pendingUnlinks, id, 'unlink', filePath);
+      } else {
+        this.emit('unlink', filePath);
+      }
+      return;
+    }
+
+    this.snapshot.set(filePath, stats ? stats.mtimeMs : Date.now());
+    const id = fileId(stats);
+    if (!id) {
+      this.emit(type, filePath);
+      return;
+    }
+    const previousPath = this.pathsById.get(id);
+    this.fileIds.set(filePath, id);
+    if (type === 'add') {
+      const unlink = this.pendingUnlinks.get(id);
+      if (unlink) {
+        clearTimeout(unlink.timer);
+        this.pendingUnlinks.delete(id);
+        this.pathsById.set(id, filePath);
+        this.emit('rename', filePath, unlink.filePath);
+        return;
+      }
+      // The file is still known under another path: a rename whose unlink is on its way,
+      // or a hard link
+      if (previousPath && previousPath !== filePath && this.fileIds.get(previousPath) === id) {
+        this.hold(this.pendingAdds, id, 'add', filePath);
+        return;
+      }
+    }
+    this.pathsById.set(id, filePath);
+    this.emit(type, filePath);
+  }
+
+  // Wait briefly for the other half of a rename before reporting a plain add or unlink
+  hold(pending, id, type, filePath) {
+    const previous = pending.get(id);
+    if (previous) {
+      clearTimeout(previous.timer);
+      this.emit(type, previous.filePath);
+    }
+    const timer = setTimeout(() => {
+      pending.delete(id);
+      this.emit(type, filePath);
+    }, RENAME_WINDOW_MS);
+    pending.set(id, { filePath, timer });
+  }
+
+  // A flood of events (a branch switch, a build) may have overflowed the kernel queue;
+  // reconcile once things settle
+  trackBurst() {
+    const now = Date.now();
+    if (now - this.burst.startedAt > BURST_WINDOW_MS) {
+      this.burst = { startedAt: now, count: 0 };
+    }
+    this.burst.count++;
+    if (this.burst.count === BURST_THRESHOLD) {
+      log.info(`Event burst in ${this.root}; scheduling a rescan`);
+      this.scheduleReconcile('Event burst may have dropped changes');
+    }
+  }
+
+  handleError(error) {
+    if (EXHAUSTION_CODES.has(error.code) && this.health.mode !== 'polling') {
+      log.warn(`Native watching exhausted in ${this.root} (${error.code}); falling back to polling`);
+      this.health.hint = remediationHint(error.code);
+      this.reopen(true, `Native watcher limit reached (${error.code})`);
+      this.scheduleReconcile('Switched to polling');
+      return;
+    }
+    log.warn(`Watcher error in ${this.root}: ${error.message}`);
+    this.setHealth('degraded', this.health.mode, error.message);
+    this.scheduleReconcile(error.message);
+  }
+
+  async reopen(polling, reason) {
+    const previous = this.watcher;
+    this.watcher = null;
+    if (previous) await previous.close().catch(() => {});
+    this.open(polling, reason);
+  }
+
+  scheduleReconcile(reason) {
+    clearTimeout(this.reconcileTimer);
+    this.reconcileTimer = setTimeout(() => this.reconcile(reason), RECONCILE_QUIET_MS);
+  }
+
+  async walk(onFile) {
+    const pending = [{ dir: this.root, level: 0 }];
+    while (pending.length > 0 && !this.closed) {
+      const { dir, level } = pending.pop();
+      let entries;
+      try {
+        entries = await fs.promises.readdir(dir, { withFileTypes: true });
+      } catch {
+        continue;
+      }
+      for (const entry of entries) {
+        const entryPath = path.join(dir, entry.name);
+        if (this.ignored(entryPath, entry)) continue;
+        if (entry.isDirectory()) {
+          if (this.depth === undefined || level < this.depth) pending.push({ dir: entryPath, level: level + 1 });
+        } else if (entry.isFile()) {
+          try {
+            onFile(entryPath, await fs.promises.stat(entryPath));
+          } catch {
+            // Removed mid-walk
+          }
+        }
+      }
+    }
+  }
+
+  async buildSnapshot() {
+    const stats = await fs.promises.stat(this.root).catch(() => null);
+    const record = (filePath, fileStats) => {
+      this.snapshot.set(filePath, fileStats.mtimeMs);
+      const id = fileId(fileStats);
+      if (id) {
+        this.fileIds.set(filePath, id);
+        this.pathsById.set(id, filePath);
+      }
+    };
+    if (stats && stats.isFile()) record(this.root, stats);
+    else await this.walk(record);
+  }
+
+  // Rescan the tree and emit whatever the watcher missed
+  async reconcile(reason) {
+    if (this.reconciling || this.closed) return;
+    this.reconciling = true;
+    this.setHealth('rescanning', this.health.mode, reason);
+
+    const seen = new Map();
+    const seenIds = new Map();
+    const record = (filePath, fileStats) => {
+      seen.set(filePath, fileStats.mtimeMs);
+      const id = fileId(fileStats);
+      if (id) seenIds.set(filePath, id);
+    };
+    const rootStats = await fs.promises.stat(this.root).catch(() => null);
+    if (rootStats && rootStats.isFile()) record(this.root, rootStats);
+    else await this.walk(record);
+
+    // Missing files that turned up elsewhere with the same identity were renamed
+    const removedById = new Map();
+    for (const filePath of this.snapshot.keys()) {
+      if (!seen.has(filePath) && this.fileIds.has(filePath)) removedById.set(this.fileIds.get(filePath), filePath);
+    }
+    const renamed = new Set();
+    let changes = 0;
+    for (const [filePath, mtimeMs] of seen) {
+      const known = this.snapshot.get(filePath);
+      const oldPath = known === undefined ? removedById.get(seenIds.get(filePath)) : undefined;
+      if (oldPath) {
+        removedById.delete(seenIds.get(filePath));
+        renamed.add(oldPath);
+        this.emit('rename', filePath, oldPath);
+        changes++;
+      } else if (known === undefined) {
+        this.emit('add', filePath);
+        changes++;
+      } else if (known !== mtimeMs) {
+        this.emit('change', filePath);
+        changes++;
+      }
+    }
+    for (const filePath of this.snapshot.keys()) {
+      if (!seen.has(filePath) && !renamed.has(filePath)) {
+        this.emit('unlink', filePath);
+        changes++;
+      }
+    }
+    this.snapshot = seen;
+    this.fileIds = seenIds;
+    this.pathsById = new Map(Array.from(seenIds, ([filePath, id]) => [id, filePath]));
+    this.reconciling = false;
+    this.health.lastRescanAt = new Date().toISOString();
+    log.info(`Reconciled ${this.root}: ${changes} missed change(s)`);
+    const polling = this.health.mode === 'polling';
+    this.setHealth(polling ? 'polling' : 'healthy', this.health.mode, this.pollingReason);
+  }
+
+  setHealth(status, mode, reason) {
+    const changed = status !== this.health.status || mode !== this.health.mode || reason !== this.health.reason;
+    Object.assign(this.health, { status, mode, reason: reason || null });
+    if (changed && !this.closed) {
+      emit('watcher:health', { ...this.health });
+      this.emit('health', { ...this.health });
+    }
+  }
+
+  async close() {
+    this.closed = true;
+    clearTimeout(this.reconcileTimer);
+    for (const pending of [this.pendingUnlinks, this.pendingAdds]) {
+      for (const { timer } of pending.values()) clearTimeout(timer);
+      pending.clear();
+    }
+    watchers.delete(this);
+    if (this.watcher) await this.watcher.close();
+  }
+}
+
+// Start a resilient watcher on `root`; listeners can be attached before it is ready
+function createWatcher(root, options = {}) {
+  const watcher = new ResilientWatcher(root, options);
+  watcher.start().catch((error) => {
+    log.error(`Failed to watch ${root}:`, error);
+    watcher.setHealth('failed', watcher.health.mode, error.message);
+  });
+  return watcher;
+}
+
+function getWatcherHealth() {
+  return Array.from(watchers, (watcher) => ({ ...watcher.health }));
+}
+
+function setupWatcherHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  ipcMain.handle('watcher:getHealth', async () => getWatcherHealth());
+
+  // Manual "refresh" for when the user suspects missed changes
+  ipcMain.handle('watcher:rescan', async (event, root) => {
+    const resolved = root ? path.resolve(root) : null;
+    const targets = Array.from(watchers).filter((watcher) => !resolved || watcher.root === resolved);
+    await Promise.all(targets.map((watcher) => watcher.reconcile('Manual rescan')));
+    return targets.length;
+  });
+}
+
+module.exports = { setupWatcherHandlers, createWatcher, detectFilesystem, getWatcherHealth, remediationHint };
@@ -0,0 +1,265 @@
+const os = require('os');
+const http = require('http');
+const https = require('https');
+const crypto = require('crypto');
+const { EventEmitter } = require('events');
+
+// Minimal WebSocket (RFC 6455): text and binary messages, ping/pong, close. No extensions
+// or fragmentation on send; enough for the local collaboration links, and as a client for
+// GraphQL subscriptions and Jupyter gateway kernels.
+
+const HANDSHAKE_GUID = '258EAFA5-E914-47DA-95CA-C5AB0DC85B11';
+const MAX_MESSAGE_SIZE = 16 * 1024 * 1024;
+// A peer this far behind on reads is dropped rather than buffered without bound
+const MAX_BUFFERED = 8 * 1024 * 1024;
+
+const OPCODES = { continuation: 0x0, text: 0x1, binary: 0x2, close: 0x8, ping: 0x9, pong: 0xa };
+const CONNECT_TIMEOUT = 15000;
+
+// Frames a client sends are masked; a server's are not
+function encodeFrame(opcode, payload, masked = false) {
+  const length = payload.length;
+  const maskBit = masked ? 0x80 : 0;
+  let header;
+  if (length < 126) {
+    header = Buffer.from([0x80 | opcode, maskBit | length]);
+  } else if (length < 65536) {
+    header = Buffer.alloc(4);
+    header[0] = 0x80 | opcode;
+    header[1] = maskBit | 126;
+    header.writeUInt16BE(length, 2);
+  } else {
+    header = Buffer.alloc(10);
+    header[0] = 0x80 | opcode;
+    header[1] = maskBit | 127;
+    header.writeBigUInt64BE(BigInt(length), 2);
+  }
+  if (!masked) return Buffer.concat([header, payload]);
+  const mask = crypto.randomBytes(4);
+  const body = Buffer.from(payload);
+  for (let i = 0; i < body.length; i++) body[i] ^= mask[i % 4];
+  return Buffer.concat([header, mask, body]);
+}
+
+// Emits 'message' (data: string | Buffer) and 'close' (code)
+class WebSocketConnection extends EventEmitter {
+  constructor(socket, { client = false } = {}) {
+    super();
+    this.socket = socket;
+    this.client = client;
+    this.buffer = Buffer.alloc(0);
+    this.fragments = [];
+    // Bytes across this.fragments, so a fragmented message is held to MAX_MESSAGE_SIZE too
+    this.fragmentsLength = 0;
+    this.fragmentOpcode = null;
+    this.closed = false;
+    socket.setNoDelay(true);
+    socket.on('data', (chunk) => this.receive(chunk));
+    socket.on('close', () => this.finish(1006));
+    socket.on('error', () => this.finish(1006));
+  }
+
+  get isOpen() {
+    return !this.closed && !this.socket.destroyed;
+  }
+
+  send(data) {
+    if (!this.isOpen) return false;
+    if (this.socket.writableLength > MAX_BUFFERED) {
+      this.close(1008, 'Too slow');
+      return false;
+    }
+    const binary = Buffer.isBuffer(data);
+    this.socket.write(encodeFrame(binary ? OPCODES.binary : OPCODES.text, binary ? data : Buffer.from(String(data), 'utf-8'), this.client));
+    return true;
+  }
+
+  close(code = 1000, reason = '') {
+    if (!this.isOpen) return;
+    const payload = Buffer.alloc(2 + Buffer.byteLength(reason));
+    payload.writeUInt16BE(code, 0);
+    payload.write(reason, 2);
+    this.socket.end(encodeFrame(OPCODES.close, payload, this.client));
+    this.finish(code);
+  }
+
+  finish(code) {
+    if (this.closed) return;
+    this.closed = true;
+    this.emit('close', code);
+  }
+
+  receive(chunk) {
+    this.buffer = Buffer.concat([this.buffer, chunk]);
+    while (this.buffer.length >= 2) {
+      const first = this.buffer[0];
+      const second = this.buffer[1];
+      let length = second & 0x7f;
+      let offset = 2;
+      if (length === 126) {
+        if (this.buffer.length < 4) return;
+        length = this.buffer.readUInt16BE(2);
+        offset = 4;
+      } else if (length === 127) {
+        if (this.buffer.length < 10) return;
+        length = Number(this.buffer.readBigUInt64BE(2));
+        offset = 10;
+      }
+      if (length > MAX_MESSAGE_SIZE) {
+        this.close(1009, 'Message too big');
+        return;
+      }
+      const masked = (second & 0x80) !== 0;
+      // Clients must mask every frame, and servers must not
+      if (masked === this.client) {
+        this.close(1002, masked ? 'Masked frame' : 'Unmasked frame');
+        return;
+      }
+      const maskLength = masked ? 4 : 0;
+      if (this.buffer.length < offset + maskLength + length) return;
+      const payload = Buffer.from(this.buffer.subarray(offset + maskLength, offset + maskLength + length));
+      if (masked) {
+        const mask = this.buffer.subarray(offset, offset + 4);
+        for (let i = 0; i < payload.length; i++) payload[i] ^= mask[i % 4];
+      }
+      this.buffer = this.buffer.subarray(offset + maskLength + length);
+      this.handleFrame((first & 0x80) !== 0, first & 0x0f, payload);
+      if (this.closed) return;
+    }
+  }
+
+  handleFrame(fin, opcode, payload) {
+    switch (opcode) {
+      case OPCODES.close:
+        this.close(payload.length >= 2 ? payload.readUInt16BE(0) : 1000);
+        return;
+      case OPCODES.ping:
+        if (this.isOpen) this.socket.write(encodeFrame(OPCODES.pong, payload, this.client));
+        return;
+      case OPCODES.pong:
+        return;
+      case OPCODES.text:
+      case OPCODES.binary:
+        this.fragmentOpcode = opcode;
+        this.fragments = [payload];
+        this.fragmentsLength = payload.length;
+        break;
+      case OPCODES.continuation:
+        if (this.fragmentOpcode === null) {
+          this.close(1002, 'Unexpected continuation');
+          return;
+        }
+        this.fragmentsLength += payload.length;
+        if (this.fragmentsLength > MAX_MESSAGE_SIZE) {
+          this.fragments = [];
+          this.fragmentsLength = 0;
+          this.close(1009, 'Message too big');
+          return;
+        }
+        this.fragments.push(payload);
+        break;
+      default:
+        this.close(1002, 'Unknown opcode');
+        return;
+    }
+    if (!fin) return;
+    const message = Buffer.concat(this.fragments);
+    const isText = this.fragmentOpcode === OPCODES.text;
+    this.fragments = [];
+    this.fragmentsLength = 0;
+    this.fragmentOpcode = null;
+    this.emit('message', isText ? message.toString('utf-8') : message);
+  }
+}
+
+// Complete the handshake for an http 'upgrade' request (`head` being any bytes that arrived
+// with it). Returns the connection, or null (after rejecting the socket) if the request
+// isn't a valid WebSocket upgrade.
+function acceptWebSocket(request, socket, head) {
+  const key = request.headers['sec-websocket-key'];
+  if (!key || String(request.headers.upgrade).toLowerCase() !== 'websocket') {
+    socket.end('HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n');
+    return null;
+  }
+  const accept = crypto.createHash('sha1').update(key + HANDSHAKE_GUID).digest('base64');
+  socket.write([
+    'HTTP/1.1 101 Switching Protocols',
+    'Upgrade: websocket',
+    'Connection: Upgrade',
+    `Sec-WebSocket-Accept: ${accept}`,
+    '',
+    ''
+  ].join('\r\n'));
+  const connection = new WebSocketConnection(socket);
+  if (head && head.length) connection.receive(head);
+  return connection;
+}
+
+// Open a client connection to a ws:// or wss:// URL. Resolves once the server accepts;
+// `connection.protocol` is the subprotocol it picked from `protocols`.
+function connectWebSocket(url, { protocols = [], headers = {}, timeout = CONNECT_TIMEOUT } = {}) {
+  const target = new URL(url);
+  if (target.protocol !== 'ws:' && target.protocol !== 'wss:') {
+    return Promise.reject(new Error(`Not a WebSocket URL: ${url}`));
+  }
+  const secure = target.protocol === 'wss:';
+  const key = crypto.randomBytes(16).toString('base64');
+  return new Promise((resolve, reject) => {
+    const request = (secure ? https : http).request({
+      hostname: target.hostname.replace(/^\[|\]$/g, ''),
+      port: target.port || (secure ? 443 : 80),
+      path: `${target.pathname}${target.search}`,
+      headers: {
+        ...headers,
+        Connection: 'Upgrade',
+        Upgrade: 'websocket',
+        'Sec-WebSocket-Key': key,
+        'Sec-WebSocket-Version': '13',
+        ...(protocols.length ? { 'Sec-WebSocket-Protocol': protocols.join(', ') } : {})
+      },
+      timeout
+    });
+    request.on('upgrade', (response, socket, head) => {
+      const expected = crypto.createHash('sha1').update(key + HANDSHAKE_GUID).digest('base64');
+      if (response.headers['sec-websocket-accept'] !== expected) {
+        socket.destroy();
+        reject(new Error('The server answered with an invalid WebSocket handshake'));
+        return;
+      }
+      const connection = new WebSocketConnection(socket, { client: true });
+      connection.protocol = response.headers['sec-websocket-protocol'] || null;
+      if (head && head.length) connection.receive(head);
+      resolve(connection);
+    });
+    request.on('response', (response) => {
+      response.resume();
+      reject(new Error(`WebSocket handshake failed: HTTP ${response.statusCode}`));
+    });
+    request.on('timeout', () => request.destroy(new Error('WebSocket handshake timed out')));
+    request.on('error', reject);
+    request.end();
+  });
+}
+
+// Constant-time check of an access token from a query string
+function tokensMatch(expected, provided) {
+  const a = Buffer.from(expected);
+  const b = Buffer.from(String(provided || ''));
+  return a.length === b.length && crypto.timingSafeEqual(a, b);
+}
+
+// This machine's first external IPv4 address, for links shared over the LAN
+function lanAddress() {
+  for (const addresses of Object.values(os.networkInterfaces())) {
+    for (const address of addresses || []) {
+      if (address.family === 'IPv4' && !address.internal) return address.address;
+    }
+  }
+  return '127.0.0.1';
+}
+
+function rejectUpgrade(socket, status, message) {
+  socket.end(`HTTP/1.1 ${status} ${message}\r\nConnection: close\r\nContent-Length: 0\r\n\r\n`);
+}
+
+module.exports = { acceptWebSocket, connectWebSocket, rejectUpgrade, tokensMatch, lanAddress, WebSocketConnection };
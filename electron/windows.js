@@ -0,0 +1,346 @@
+const { BrowserWindow, shell, screen } = require('electron');
+const path = require('path');
+const crypto = require('crypto');
+const { pathToFileURL } = require('url');
+const { JsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { emit } = require('./events');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { registerCommand } = require('./commands');
+const { transferTerminal, getTerminal } = require('./terminal');
+
+const log = createLogger('windows');
+const isDev = process.env.NODE_ENV === 'development';
+// The packaged renderer; in-app navigation stays under it
+const APP_FILE_URL = pathToFileURL(path.join(__dirname, '../out')).href;
+
+const store = new JsonStore('windows', {
+  // Workspaces open at last quit, restored on launch
+  openWorkspaces: [],
+  // Workspace id -> last window bounds
+  bounds: {}
+});
+
+// Stable id for a workspace root; windows without a folder share 'default'
+function workspaceIdFor(workspacePath) {
+  if (!workspacePath) return 'default';
+  const normalized = path.resolve(workspacePath);
+  return crypto.createHash('sha1').update(process.platform === 'win32' ? normalized.toLowerCase() : normalized).digest('hex').slice(0, 12);
+}
+
+// Owns every workbench window and the workspace each one shows. Other managers key
+// their per-window state (terminals, browser views, sessions) off these window ids.
+class WindowManager {
+  constructor() {
+    // BrowserWindow id -> { window, workspaceId, workspacePath, createdAt }
+    this.windows = new Map();
+    this.isQuitting = false;
+  }
+
+  createWindow(options = {}) {
+    const workspacePath = options.workspacePath ? path.resolve(options.workspacePath) : null;
+    const workspaceId = workspaceIdFor(workspacePath);
+
+    // One window per workspace: focus the existing one instead of duplicating it
+    const existing = workspacePath && this.findByWorkspace(workspaceId);
+    if (existing && !options.allowDuplicate) {
+      this.focus(existing.window);
+      return existing.window;
+    }
+
+    const savedBounds = store.get('bounds')[workspaceId];
+    const win = new BrowserWindow({
+      width: 1400,
+      height: 900,
+      ...(savedBounds && this.isOnScreen(savedBounds) ? savedBounds : {}),
+      minWidth: 800,
+      minHeight: 600,
+      backgroundColor: '#000000',
+      titleBarStyle: 'hiddenInset', // macOS style
+      trafficLightPosition: { x: 20, y: 20 },
+      autoHideMenuBar: true, // Hide menu bar on Windows/Linux
+      title: workspacePath ? `${path.basename(workspacePath)} — Forge` : 'Forge',
+      webPreferences: {
+        nodeIntegration: false,
+        contextIsolation: true,
+        preload: path.join(__dirname, 'preload.js'),
+        webSecurity: !isDev
+      },
+      icon: path.join(__dirname, '../public', 'icon.png')
+    });
+
+    const context = { window: win, workspaceId, workspacePath, createdAt: new Date().toISOString() };
+    this.windows.set(win.id, context);
+    const windowId = win.id;
+
+    // The renderer reads its workspace from the query string on first paint
+    const query = { windowId: String(windowId), workspaceId, ...(workspacePath ? { workspace: workspacePath } : {}) };
+    if (isDev) {
+      log.info('Loading development URL: http://localhost:3000');
+      win.loadURL(`http://localhost:3000/?${new URLSearchParams(query)}`)
+        .then(() => log.info('Successfully loaded development URL'))
+        .catch((error) => log.error('Failed to load development URL:', error));
+      if (this.windows.size === 1) win.webContents.openDevTools();
+    } else {
+      win.loadFile(path.join(__dirname, '../out/index.html'), { query });
+    }
+
+    win.webContents.once('did-finish-load', () => {
+      log.info(`Window ${windowId} finished loading (${workspacePath || 'no workspace'})`);
+    });
+
+    win.webContents.on('did-fail-load', (event, errorCode, errorDescription, validatedURL) => {
+      log.error('Failed to load:', errorCode, errorDescription, validatedURL);
+    });
+
+    // Files dropped outside a drop zone would otherwise navigate the window to them
+    win.webContents.on('will-navigate', (event, url) => {
+      if (url.startsWith('file:') && !url.startsWith(APP_FILE_URL)) {
+        event.preventDefault();
+      }
+    });
+
+    // Handle external links
+    win.webContents.setWindowOpenHandler(({ url }) => {
+      shell.openExternal(url);
+      return { action: 'deny' };
+    });
+
+    win.on('close', () => {
+      // Remember placement; don't touch other resources here to avoid "object has been destroyed" errors
+      const bounds = { ...store.get('bounds'), [context.workspaceId]: win.getNormalBounds() };
+      store.set('bounds', bounds);
+      // Closing the last window quits on Windows/Linux, so keep it in the restore list
+      if (!this.isQuitting && this.windows.size > 1) this.persistOpenWorkspaces(windowId);
+    });
+
+    win.on('closed', () => {
+      this.windows.delete(windowId);
+      emit('window:closed', { windowId, workspaceId: context.workspaceId });
+    });
+
+    emit('window:opened', { windowId, workspaceId, workspacePath });
+    return win;
+  }
+
+  isOnScreen(bounds) {
+    return screen.getAllDisplays().some(({ workArea }) =>
+      bounds.x >= workArea.x - 50 && bounds.y >= workArea.y - 50 &&
+      bounds.x < workArea.x + workArea.width && bounds.y < workArea.y + workArea.height);
+  }
+
+  persistOpenWorkspaces(excludeWindowId) {
+    const open = Array.from(this.windows.values())
+      .filter((context) => context.window.id !== excludeWindowId && context.workspacePath)
+      .map((context) => context.workspacePath);
+    store.set('openWorkspaces', open);
+  }
+
+  // Reopen the workspaces that were open at last quit; falls back to an empty window
+  restoreWindows() {
+    const workspaces = store.get('openWorkspaces', []);
+    if (workspaces.length === 0) {
+      return [this.createWindow()];
+    }
+    return workspaces.map((workspacePath) => this.createWindow({ workspacePath }));
+  }
+
+  markQuitting() {
+    if (this.isQuitting) return;
+    this.persistOpenWorkspaces();
+    this.isQuitting = true;
+  }
+
+  focus(win) {
+    if (!win || win.isDestroyed()) return;
+    if (win.isMinimized()) win.restore();
+    win.show();
+    win.focus();
+  }
+
+  get(windowId) {
+    return this.windows.get(windowId) || null;
+  }
+
+  fromSender(sender) {
+    const win = sender && BrowserWindow.fromWebContents(sender);
+    return win ? this.get(win.id) : null;
+  }
+
+  windowFromSender(sender) {
+    const context = this.fromSender(sender);
+    return context ? context.window : null;
+  }
+
+  findByWorkspace(workspaceId) {
+    return Array.from(this.windows.values()).find((context) => context.workspaceId === workspaceId) || null;
+  }
+
+  // The focused workbench window, else the most recently created one
+  getPrimaryWindow() {
+    const focused = BrowserWindow.getFocusedWindow();
+    if (focused && this.windows.has(focused.id)) return focused;
+    const all = Array.from(this.windows.values());
+    return all.length > 0 ? all[all.length - 1].window : null;
+  }
+
+  list() {
+    const focused = BrowserWindow.getFocusedWindow();
+    return Array.from(this.windows.values()).map((context) => ({
+      windowId: context.window.id,
+      workspaceId: context.workspaceId,
+      workspacePath: context.workspacePath,
+      title: context.window.getTitle(),
+      focused: focused ? focused.id === context.window.id : false,
+      createdAt: context.createdAt
+    }));
+  }
+
+  setWorkspace(windowId, workspacePath) {
+    const context = this.get(windowId);
+    if (!context) {
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, `Window ${windowId} not found`);
+    }
+    context.workspacePath = workspacePath ? path.resolve(workspacePath) : null;
+    context.workspaceId = workspaceIdFor(context.workspacePath);
+    context.window.setTitle(context.workspacePath ? `${path.basename(context.workspacePath)} — Forge` : 'Forge');
+    this.persistOpenWorkspaces();
+    return this.describe(context);
+  }
+
+  describe(context) {
+    return {
+      windowId: context.window.id,
+      workspaceId: context.workspaceId,
+      workspacePath: context.workspacePath
+    };
+  }
+}
+
+const windowManager = new WindowManager();
+
+// Resolve a transfer target: an existing window id, or a fresh window once its renderer has loaded
+async function resolveTargetWindow(targetWindowId) {
+  if (targetWindowId !== undefined && targetWindowId !== null) {
+    const context = windowManager.get(targetWindowId);
+    if (!context) {
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, `Window ${targetWindowId} not found`);
+    }
+    return context.window;
+  }
+  const win = windowManager.createWindow({ allowDuplicate: true });
+  if (win.webContents.isLoading()) {
+    await new Promise((resolve) => win.webContents.once('did-finish-load', resolve));
+  }
+  return win;
+}
+
+// Arguments are checked before resolveTargetWindow so a bad request never leaves a new,
+// empty window behind
+async function moveTerminal(terminalId, targetWindowId) {
+  if (!getTerminal(terminalId)) {
+    throw new ForgeError(ErrorCode.TERMINAL_NOT_FOUND, `Terminal ${terminalId} not found`, { details: { id: terminalId } });
+  }
+  const target = await resolveTargetWindow(targetWindowId);
+  transferTerminal(terminalId, target.id);
+  windowManager.focus(target);
+  return { terminalId, windowId: target.id };
+}
+
+// Editors live in the renderer; the backend relays the serialized editor state
+// (path, unsaved content, view state) and the source window closes its tab on success.
+async function moveEditor(editorState, targetWindowId, fromWindowId) {
+  if (!editorState || !editorState.path) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Editor state with a path is required');
+  }
+  if (targetWindowId !== undefined && targetWindowId !== null && targetWindowId === fromWindowId) {
+    return { windowId: targetWindowId, moved: false };
+  }
+  const target = await resolveTargetWindow(targetWindowId);
+
+  emit('editor:attached', { fromWindowId: fromWindowId !== undefined ? fromWindowId : null, editor: editorState }, { target: target.webContents });
+  windowManager.focus(target);
+  return { windowId: target.id, moved: true };
+}
+
+function setupWindowHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  registerCommand({
+    id: 'window.new',
+    title: 'New Window',
+    category: 'navigation',
+    keybinding: 'CmdOrCtrl+Shift+N',
+    handler: () => windowManager.describe(windowManager.get(windowManager.createWindow().id))
+  });
+
+  // Without a target window id these open a new window
+  registerCommand({
+    id: 'terminal.moveToWindow',
+    title: 'Move Terminal to New Window',
+    category: 'terminal',
+    palette: false,
+    handler: (...args) => {
+      args.pop(); // command context
+      const [terminalId, targetWindowId] = args;
+      return moveTerminal(terminalId, targetWindowId);
+    }
+  });
+
+  registerCommand({
+    id: 'editor.moveToWindow',
+    title: 'Move Editor to New Window',
+    category: 'editor',
+    palette: false,
+    handler: (...args) => {
+      const context = args.pop();
+      const [editorState, targetWindowId] = args;
+      return moveEditor(editorState, targetWindowId, context.window ? context.window.id : undefined);
+    }
+  });
+
+  ipcMain.handle('windows:open', async (event, workspacePath, options = {}) => {
+    const win = windowManager.createWindow({ workspacePath, allowDuplicate: options.allowDuplicate });
+    return windowManager.describe(windowManager.get(win.id));
+  });
+
+  ipcMain.handle('windows:list', async () => {
+    return windowManager.list();
+  });
+
+  ipcMain.handle('windows:getContext', async (event) => {
+    const context = windowManager.fromSender(event.sender);
+    if (!context) {
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Sender is not a workbench window');
+    }
+    return windowManager.describe(context);
+  });
+
+  ipcMain.handle('windows:setWorkspace', async (event, workspacePath) => {
+    const context = windowManager.fromSender(event.sender);
+    if (!context) {
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, 'Sender is not a workbench window');
+    }
+    return windowManager.setWorkspace(context.window.id, workspacePath);
+  });
+
+  ipcMain.handle('windows:moveTerminal', async (event, terminalId, targetWindowId) => {
+    return moveTerminal(terminalId, targetWindowId);
+  });
+
+  ipcMain.handle('windows:moveEditor', async (event, editorState, targetWindowId) => {
+    const context = windowManager.fromSender(event.sender);
+    return moveEditor(editorState, targetWindowId, context ? context.window.id : undefined);
+  });
+
+  ipcMain.handle('windows:focus', async (event, windowId) => {
+    const context = windowManager.get(windowId);
+    if (!context) {
+      throw new ForgeError(ErrorCode.WINDOW_UNAVAILABLE, `Window ${windowId} not found`);
+    }
+    windowManager.focus(context.window);
+    return true;
+  });
+}
+
+module.exports = { setupWindowHandlers, windowManager, workspaceIdFor };
@@ -0,0 +1,282 @@
+const fs = require('fs').promises;
+const path = require('path');
+const { app, dialog } = require('electron');
+const { getJsonStore } = require('./jsonStore');
+const { createLogger } = require('./logger');
+const { ForgeError, ErrorCode, withForgeErrors } = require('./errors');
+const { createZip, readZip } = require('./zip');
+const { windowManager } = require('./windows');
+const { listTerminals } = require('./terminal');
+const { getSettingsSnapshot, applySettings, listEditorSessions, importEditorSessions } = require('./ipc');
+const { getScope, updateIndexScope } = require('./indexScope');
+const { exportFavorites, importFavorites } = require('./favorites');
+const { getBrowserSession, setBrowserSession } = require('./browserSession');
+
+const log = createLogger('workspace-state');
+
+const FORMAT = 'forge-workspace-state';
+const FORMAT_VERSION = 1;
+const MAX_BUNDLE_SIZE = 50 * 1024 * 1024;
+// Absolute paths under the workspace are stored relative to this token, so a bundle
+// restores correctly wherever the project is checked out
+const WORKSPACE_TOKEN = '${workspaceFolder}';
+
+// Subsystem stores worth carrying between machines. Secrets, trust decisions, window
+// bounds, telemetry, and clipboard history stay local by design.
+const PORTABLE_STORES = ['snippets', 'automation', 'editorconfig', 'drag-drop', 'completion', 'git-ai', 'todos', 'embeddings', 'ai'];
+// Store keys whose contents run code when invoked; flagged when inspecting a bundle
+const EXECUTABLE_KEYS = { automation: 'scripts' };
+
+const SECTIONS = ['settings', 'editorSessions', 'terminals', 'browsers', 'stores', 'indexScope', 'favorites', 'renderer'];
+
+// Deep-map every string in `value`
+function mapStrings(value, fn) {
+  if (typeof value === 'string') return fn(value);
+  if (Array.isArray(value)) return value.map((item) => mapStrings(item, fn));
+  if (value && typeof value === 'object') {
+    return Object.fromEntries(Object.entries(value).map(([key, item]) => [key, mapStrings(item, fn)]));
+  }
+  return value;
+}
+
+function tokenizePaths(value, workspacePath) {
+  if (!workspacePath) return value;
+  return mapStrings(value, (text) => {
+    if (text === workspacePath) return WORKSPACE_TOKEN;
+    if (text.startsWith(workspacePath + path.sep)) return WORKSPACE_TOKEN + text.slice(workspacePath.length).split(path.sep).join('/');
+    return text;
+  });
+}
+
+function expandPaths(value, workspacePath) {
+  if (!workspacePath) return value;
+  return mapStrings(value, (text) => {
+    if (!text.startsWith(WORKSPACE_TOKEN)) return text;
+    return path.join(workspacePath, ...text.slice(WORKSPACE_TOKEN.length).split('/').filter(Boolean));
+  });
+}
+
+function contextFor(sender) {
+  const context = windowManager.fromSender(sender);
+  return { windowId: context ? context.window.id : undefined, workspaceId: context ? context.workspaceId : null, workspacePath: context ? context.workspacePath : null };
+}
+
+// Gather the requested sections. `rendererState` is whatever the workbench owns itself
+// (terminal layouts and profiles, tasks, panel layout) and is carried opaquely.
+async function collectState(context, options = {}) {
+  const wanted = new Set(options.sections || SECTIONS);
+  const sections = {};
+  if (wanted.has('settings')) sections.settings = getSettingsSnapshot();
+  if (wanted.has('editorSessions')) sections.editorSessions = await listEditorSessions(context.workspaceId);
+  if (wanted.has('terminals')) {
+    sections.terminals = listTerminals(context.windowId).map(({ shell, cwd }) => ({ shell, cwd }));
+  }
+  if (wanted.has('browsers')) sections.browsers = getBrowserSession(context.workspaceId);
+  if (wanted.has('stores')) {
+    sections.stores = Object.fromEntries(PORTABLE_STORES.map((name) => [name, getJsonStore(name).store]));
+  }
+  if (wanted.has('indexScope') && context.workspacePath) {
+    const { include, exclude } = getScope(context.workspacePath);
+    sections.indexScope = { include, exclude };
+  }
+  // The workspace's own pins; global ones stay with the machine
+  if (wanted.has('favorites')) sections.favorites = exportFavorites(context.workspaceId);
+  if (wanted.has('renderer') && options.rendererState !== undefined) sections.renderer = options.rendererState;
+
+  return {
+    format: FORMAT,
+    version: FORMAT_VERSION,
+    exportedAt: new Date().toISOString(),
+    appVersion: app.getVersion(),
+    platform: process.platform,
+    workspaceName: context.workspacePath ? path.basename(context.workspacePath) : null,
+    sections: tokenizePaths(sections, context.workspacePath)
+  };
+}
+
+async function exportWorkspaceState(targetPath, context, options = {}) {
+  const bundle = await collectState(context, options);
+  if (targetPath.toLowerCase().endsWith('.zip')) {
+    const { sections, ...manifest } = bundle;
+    const entries = [{ name: 'manifest.json', data: JSON.stringify({ ...manifest, sections: Object.keys(sections) }, null, 2) }];
+    for (const [name, data] of Object.entries(sections)) {
+      entries.push({ name: `sections/${name}.json`, data: JSON.stringify(data, null, 2) });
+    }
+    await fs.writeFile(targetPath, createZip(entries));
+  } else {
+    await fs.writeFile(targetPath, JSON.stringify(bundle, null, 2));
+  }
+  log.info(`Exported ${Object.keys(bundle.sections).join(', ')} to ${targetPath}`);
+  return { path: targetPath, sections: Object.keys(bundle.sections) };
+}
+
+async function readBundle(sourcePath) {
+  const stats = await fs.stat(sourcePath);
+  if (stats.size > MAX_BUNDLE_SIZE) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'State bundle is too large');
+  }
+  const buffer = await fs.readFile(sourcePath);
+  let bundle;
+  try {
+    // Zip archives start with "PK"
+    if (buffer[0] === 0x50 && buffer[1] === 0x4b) {
+      const entries = new Map(readZip(buffer).map((entry) => [entry.name, entry.data.toString('utf-8')]));
+      const manifest = JSON.parse(entries.get('manifest.json'));
+      const sections = {};
+      for (const name of manifest.sections || []) {
+        const data = entries.get(`sections/${name}.json`);
+        if (data !== undefined) sections[name] = JSON.parse(data);
+      }
+      bundle = { ...manifest, sections };
+    } else {
+      bundle = JSON.parse(buffer.toString('utf-8'));
+    }
+  } catch (error) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Not a readable state bundle: ${error.message}`);
+  }
+  if (!bundle || bundle.format !== FORMAT || !bundle.sections) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'Not a Forge workspace state bundle');
+  }
+  if (bundle.version > FORMAT_VERSION) {
+    throw new ForgeError(ErrorCode.INVALID_ARGUMENT, `Bundle format ${bundle.version} is newer than this version of Forge supports`);
+  }
+  return bundle;
+}
+
+// Summarize a bundle before importing, so the user can pick sections
+async function inspectBundle(sourcePath) {
+  const bundle = await readBundle(sourcePath);
+  const warnings = [];
+  const stores = bundle.sections.stores || {};
+  for (const [name, key] of Object.entries(EXECUTABLE_KEYS)) {
+    const count = stores[name] && stores[name][key] ? Object.keys(stores[name][key]).length : 0;
+    if (count > 0) warnings.push(`Includes ${count} ${name} ${key} that run code when invoked; only import bundles you trust`);
+  }
+  if (bundle.platform && bundle.platform !== process.platform) {
+    warnings.push(`Exported on ${bundle.platform}; shells and paths in terminal settings may need adjusting`);
+  }
+  return {
+    exportedAt: bundle.exportedAt,
+    appVersion: bundle.appVersion,
+    platform: bundle.platform,
+    workspaceName: bundle.workspaceName,
+    sections: Object.keys(bundle.sections),
+    stores: Object.keys(stores),
+    editorSessions: (bundle.sections.editorSessions || []).length,
+    terminals: (bundle.sections.terminals || []).length,
+    browsers: bundle.sections.browsers && Array.isArray(bundle.sections.browsers.views) ? bundle.sections.browsers.views.length : 0,
+    favorites: (bundle.sections.favorites || []).length,
+    warnings
+  };
+}
+
+// Merge keeps existing entries and adds/overwrites the bundle's; one level deep so e.g.
+// imported macros join the existing ones instead of replacing the whole list
+function mergeStore(current, incoming) {
+  const result = { ...current };
+  for (const [key, value] of Object.entries(incoming)) {
+    const existing = current[key];
+    const bothObjects = value && existing && typeof value === 'object' && typeof existing === 'object' && !Array.isArray(value) && !Array.isArray(existing);
+    result[key] = bothObjects ? { ...existing, ...value } : value;
+  }
+  return result;
+}
+
+// Apply a bundle. mode 'merge' (default) layers it over current state; 'replace' overwrites
+// each imported section. Terminals and renderer state are returned for the workbench to
+// restore; nothing is spawned here.
+async function importWorkspaceState(sourcePath, context, options = {}) {
+  const bundle = await readBundle(sourcePath);
+  const replace = options.mode === 'replace';
+  const wanted = new Set(options.sections || SECTIONS);
+  const sections = expandPaths(bundle.sections, context.workspacePath);
+  const imported = [];
+  let restartRecommended = false;
+
+  if (wanted.has('settings') && sections.settings) {
+    if (applySettings(sections.settings, replace)) imported.push('settings');
+  }
+  if (wanted.has('editorSessions') && Array.isArray(sections.editorSessions)) {
+    // Sessions move to the importing window's workspace
+    await importEditorSessions(sections.editorSessions.map((session) => ({ ...session, workspaceId: context.workspaceId || session.workspaceId })));
+    imported.push('editorSessions');
+  }
+  if (wanted.has('browsers') && sections.browsers) {
+    // Saved for the next launch; the workbench can reopen them now via browser:getSession
+    setBrowserSession(context.workspaceId, sections.browsers);
+    imported.push('browsers');
+  }
+  if (wanted.has('stores') && sections.stores) {
+    for (const [name, data] of Object.entries(sections.stores)) {
+      if (!PORTABLE_STORES.includes(name) || !data || typeof data !== 'object') continue;
+      const store = getJsonStore(name);
+      store.store = replace ? data : mergeStore(store.store, data);
+    }
+    imported.push('stores');
+    // Subsystems read some settings (e.g. registered macro commands) only at startup
+    restartRecommended = true;
+  }
+  if (wanted.has('indexScope') && sections.indexScope && context.workspacePath) {
+    const current = getScope(context.workspacePath);
+    const include = replace ? sections.indexScope.include : Array.from(new Set([...current.include, ...sections.indexScope.include]));
+    const exclude = replace ? sections.indexScope.exclude : Array.from(new Set([...current.exclude, ...sections.indexScope.exclude]));
+    // Changing the scope rebuilds every index, so skip it when nothing differs
+    if (JSON.stringify({ include, exclude }) !== JSON.stringify({ include: current.include, exclude: current.exclude })) {
+      updateIndexScope(context.workspacePath, { include, exclude });
+    }
+    imported.push('indexScope');
+  }
+
+  if (wanted.has('favorites') && Array.isArray(sections.favorites)) {
+    importFavorites(sections.favorites, context.workspaceId);
+    imported.push('favorites');
+  }
+
+  const terminals = wanted.has('terminals') ? sections.terminals || [] : [];
+  const rendererState = wanted.has('renderer') ? sections.renderer : undefined;
+  if (terminals.length > 0) imported.push('terminals');
+  if (rendererState !== undefined) imported.push('renderer');
+
+  log.info(`Imported ${imported.join(', ') || 'nothing'} from ${sourcePath}`);
+  return { imported, terminals, rendererState: rendererState === undefined ? null : rendererState, restartRecommended };
+}
+
+async function pickPath(sender, save) {
+  const win = windowManager.windowFromSender(sender);
+  const filters = [{ name: 'Forge State', extensions: ['json', 'zip'] }];
+  if (save) {
+    const result = await dialog.showSaveDialog(win, {
+      defaultPath: path.join(app.getPath('documents'), `forge-state-${new Date().toISOString().slice(0, 10)}.zip`),
+      filters
+    });
+    return result.canceled ? null : result.filePath;
+  }
+  const result = await dialog.showOpenDialog(win, { properties: ['openFile'], filters });
+  return result.canceled ? null : result.filePaths[0];
+}
+
+function setupWorkspaceStateHandlers(ipcMain) {
+  ipcMain = withForgeErrors(ipcMain);
+
+  // Resolves null if the user cancels the save dialog
+  ipcMain.handle('workspaceState:export', async (event, options = {}) => {
+    const targetPath = options.path || await pickPath(event.sender, true);
+    if (!targetPath) return null;
+    return exportWorkspaceState(targetPath, contextFor(event.sender), options);
+  });
+
+  ipcMain.handle('workspaceState:inspect', async (event, sourcePath) => {
+    const resolved = sourcePath || await pickPath(event.sender, false);
+    if (!resolved) return null;
+    return { path: resolved, ...await inspectBundle(resolved) };
+  });
+
+  ipcMain.handle('workspaceState:import', async (event, sourcePath, options = {}) => {
+    if (typeof sourcePath !== 'string') {
+      throw new ForgeError(ErrorCode.INVALID_ARGUMENT, 'A bundle path is required');
+    }
+    return importWorkspaceState(sourcePath, contextFor(event.sender), options);
+  });
+}
+
+module.exports = { setupWorkspaceStateHandlers, exportWorkspaceState, importWorkspaceState, inspectBundle };
@@ -0,0 +1,469 @@
+// YAML reader covering what API specs and CI files use: block mappings and sequences,
+// plain, quoted and block (| and >) scalars, flow collections, anchors, aliases and
+// merge keys. parseYaml reads a single document and rejects multi-document streams, which
+// parseYamlAll reads into an array; tags are ignored.
+// stringifyYaml writes JSON-compatible values back out in block style.
+
+class YamlError extends Error {
+  constructor(message, line) {
+    super(`${message} (line ${line})`);
+    this.line = line;
+  }
+}
+
+const ESCAPES = { 0: '\0', a: '\x07', b: '\b', t: '\t', n: '\n', v: '\v', f: '\f', r: '\r', e: '\x1b', ' ': ' ', '"': '"', '/': '/', '\\': '\\', N: '\x85', _: '\xa0' };
+
+// Quoted scalar starting at `start` -> { value, end }, or null if it doesn't close
+function readQuoted(text, start) {
+  const quote = text[start];
+  let value = '';
+  for (let i = start + 1; i < text.length; i++) {
+    const ch = text[i];
+    if (quote === "'") {
+      if (ch !== "'") value += ch;
+      else if (text[i + 1] === "'") value += text[++i];
+      else return { value, end: i + 1 };
+    } else if (ch === '"') {
+      return { value, end: i + 1 };
+    } else if (ch === '\\') {
+      const next = text[++i];
+      const hex = { x: 2, u: 4, U: 8 }[next];
+      if (hex) {
+        value += String.fromCodePoint(parseInt(text.slice(i + 1, i + 1 + hex), 16));
+        i += hex;
+      } else {
+        value += ESCAPES[next] !== undefined ? ESCAPES[next] : next;
+      }
+    } else {
+      value += ch;
+    }
+  }
+  return null;
+}
+
+// Drop a trailing comment: a # after whitespace, outside quotes
+function stripComment(text) {
+  let quote = null;
+  for (let i = 0; i < text.length; i++) {
+    const ch = text[i];
+    if (quote) {
+      if (quote === '"' && ch === '\\') i++;
+      else if (ch === quote) quote = null;
+    } else if ((ch === '"' || ch === "'") && (i === 0 || /[\s[{,]/.test(text[i - 1]))) {
+      quote = ch;
+    } else if (ch === '#' && (i === 0 || /\s/.test(text[i - 1]))) {
+      return text.slice(0, i);
+    }
+  }
+  return text;
+}
+
+function isSequenceItem(text) {
+  return text === '-' || text.startsWith('- ');
+}
+
+// "key: rest" -> { key, rest }, or null if the line isn't a mapping entry
+function splitKey(text) {
+  if (text[0] === '"' || text[0] === "'") {
+    const quoted = readQuoted(text, 0);
+    if (!quoted) return null;
+    const after = text.slice(quoted.end).replace(/^\s+/, '');
+    if (after[0] !== ':' || (after.length > 1 && !/\s/.test(after[1]))) return null;
+    return { key: quoted.value, rest: after.slice(1).trim() };
+  }
+  if (text[0] === '[' || text[0] === '{' || isSequenceItem(text)) return null;
+  const at = text.search(/:(\s|$)/);
+  if (at <= 0) return null;
+  return { key: text.slice(0, at).trim(), rest: text.slice(at + 1).trim() };
+}
+
+function resolvePlain(text) {
+  if (/^(~|null|Null|NULL)?$/.test(text)) return null;
+  if (/^(true|True|TRUE)$/.test(text)) return true;
+  if (/^(false|False|FALSE)$/.test(text)) return false;
+  if (/^[-+]?(0|[1-9][0-9_]*)$/.test(text)) return Number(text.replace(/_/g, ''));
+  if (/^0x[0-9a-fA-F]+$/.test(text)) return parseInt(text.slice(2), 16);
+  if (/^0o[0-7]+$/.test(text)) return parseInt(text.slice(2), 8);
+  if (/^[-+]?(\.[0-9]+|[0-9][0-9_]*(\.[0-9_]*)?)([eE][-+]?[0-9]+)?$/.test(text)) return Number(text.replace(/_/g, ''));
+  if (/^[-+]?\.(inf|Inf|INF)$/.test(text)) return text[0] === '-' ? -Infinity : Infinity;
+  if (/^\.(nan|NaN|NAN)$/.test(text)) return NaN;
+  return text;
+}
+
+// Split a stream into documents, each an array of lines. '---' starts a document (and may
+// carry its first node), '...' ends one, and directives ahead of a document are dropped.
+function splitDocuments(text) {
+  const documents = [];
+  let current = null;
+  text.replace(/\r\n/g, '\n').split('\n').forEach((raw, index) => {
+    const indent = raw.match(/^ */)[0].length;
+    const line = { raw, indent, text: stripComment(raw.slice(indent)).trimEnd(), number: index + 1 };
+    if (indent === 0 && line.text) {
+      if (!current && line.text.startsWith('%')) return;
+      if (line.text === '---' || line.text.startsWith('--- ')) {
+        if (current) documents.push(current);
+        current = [{ ...line, text: line.text.slice(3).trim() }];
+        return;
+      }
+      if (line.text === '...') {
+        if (current) documents.push(current);
+        current = null;
+        return;
+      }
+    }
+    if (!current) {
+      if (!line.text) return;
+      current = [];
+    }
+    current.push(line);
+  });
+  if (current) documents.push(current);
+  return documents;
+}
+
+class Parser {
+  constructor(lines) {
+    this.lines = lines;
+    this.index = 0;
+    this.anchors = new Map();
+  }
+
+  error(message, line) {
+    const last = this.lines[this.lines.length - 1];
+    return new YamlError(message, line ? line.number : (last ? last.number : 1));
+  }
+
+  // The next line with content, skipping blank and comment-only lines
+  peekLine() {
+    while (this.index < this.lines.length && !this.lines[this.index].text) this.index++;
+    return this.lines[this.index] || null;
+  }
+
+  parseDocument() {
+    const first = this.peekLine();
+    if (!first) return null;
+    const value = this.parseAt(first.indent);
+    const rest = this.peekLine();
+    if (rest) throw this.error('Unexpected content', rest);
+    return value;
+  }
+
+  // The node whose first line is the next line, at `indent`
+  parseAt(indent) {
+    const line = this.peekLine();
+    if (isSequenceItem(line.text)) return this.parseSequence(indent);
+    if (splitKey(line.text)) return this.parseMapping(indent);
+    this.index++;
+    return this.parseValue(line.text, line, indent - 1, false);
+  }
+
+  parseMapping(indent) {
+    const result = {};
+    const merges = [];
+    for (;;) {
+      const line = this.peekLine();
+      if (!line || line.indent < indent) break;
+      if (line.indent > indent) throw this.error('Unexpected indentation', line);
+      const entry = splitKey(line.text);
+      if (!entry) {
+        if (isSequenceItem(line.text)) break;
+        throw this.error('Expected "key: value"', line);
+      }
+      this.index++;
+      const value = this.parseValue(entry.rest, line, indent, true);
+      if (entry.key === '<<') merges.push(...(Array.isArray(value) ? value : [value]));
+      else result[entry.key] = value;
+    }
+    // Keys written out win over merged ones
+    return merges.length > 0 ? Object.assign({}, ...merges.filter((item) => item && typeof item === 'object'), result) : result;
+  }
+
+  parseSequence(indent) {
+    const result = [];
+    for (;;) {
+      const line = this.peekLine();
+      if (!line || line.indent < indent) break;
+      if (line.indent > indent) throw this.error('Unexpected indentation', line);
+      if (!isSequenceItem(line.text)) break;
+      const rest = line.text.slice(1).replace(/^ +/, '');
+      if (rest && (isSequenceItem(rest) || splitKey(rest))) {
+        // "- key: value" opens a mapping (or "- - x" a list) at the column after the dash
+        const column = indent + line.text.length - rest.length;
+        this.lines[this.index] = { ...line, indent: column, text: rest };
+        result.push(this.parseAt(column));
+      } else {
+        this.index++;
+        result.push(this.parseValue(rest, line, indent, false));
+      }
+    }
+    return result;
+  }
+
+  // The value after "key:" or "- " on `line`; nested blocks must sit deeper than `indent`
+  parseValue(rest, line, indent, inMapping) {
+    let text = rest;
+    let anchor = null;
+    const anchorMatch = text.match(/^&(\S+)\s*/);
+    if (anchorMatch) {
+      anchor = anchorMatch[1];
+      text = text.slice(anchorMatch[0].length);
+    }
+    const tag = text.match(/^!\S*\s*/);
+    if (tag) text = text.slice(tag[0].length);
+
+    let value;
+    if (text.startsWith('*')) {
+      const name = text.slice(1).trim();
+      if (!this.anchors.has(name)) throw this.error(`Unknown alias *${name}`, line);
+      value = this.anchors.get(name);
+    } else if (!text) {
+      const next = this.peekLine();
+      if (next && next.indent > indent) value = this.parseAt(next.indent);
+      // A list may sit at its key's own indentation
+      else if (next && inMapping && next.indent === indent && isSequenceItem(next.text)) value = this.parseSequence(indent);
+      else value = null;
+    } else if (text[0] === '|' || text[0] === '>') {
+      value = this.parseBlockScalar(text, line, indent);
+    } else if (text[0] === '[' || text[0] === '{') {
+      value = this.parseFlowText(text, line);
+    } else {
+      value = this.parseScalar(text, line, indent);
+    }
+    if (anchor) this.anchors.set(anchor, value);
+    return value;
+  }
+
+  parseScalar(text, line, indent) {
+    if (text[0] === '"' || text[0] === "'") {
+      let source = text;
+      let quoted = readQuoted(source, 0);
+      // Quoted scalars may run over several lines, folded with spaces
+      while (!quoted && this.index < this.lines.length) {
+        source += ' ' + this.lines[this.index++].raw.trim();
+        quoted = readQuoted(source, 0);
+      }
+      if (!quoted) throw this.error('Unterminated quoted string', line);
+      if (source.slice(quoted.end).trim()) throw this.error('Unexpected text after a quoted string', line);
+      return quoted.value;
+    }
+    let value = text;
+    // Plain scalars continue on more-indented lines
+    for (let next = this.peekLine(); next && next.indent > indent && !splitKey(next.text) && !isSequenceItem(next.text); next = this.peekLine()) {
+      value += ' ' + next.text;
+      this.index++;
+    }
+    return resolvePlain(value.trim());
+  }
+
+  parseBlockScalar(header, line, indent) {
+    const match = header.match(/^([|>])([1-9]?)([+-]?)([1-9]?)$/);
+    if (!match) throw this.error(`Invalid block scalar header ${header}`, line);
+    const [, style, leadingWidth, chomp, trailingWidth] = match;
+    const width = leadingWidth || trailingWidth;
+    let blockIndent = width ? indent + Number(width) : null;
+    const lines = [];
+    while (this.index < this.lines.length) {
+      const { raw } = this.lines[this.index];
+      const blank = !raw.trim();
+      const lineIndent = raw.match(/^ */)[0].length;
+      if (!blank) {
+        if (blockIndent === null) {
+          if (lineIndent <= indent) break;
+          blockIndent = lineIndent;
+        }
+        if (lineIndent < blockIndent) break;
+      }
+      lines.push(blank ? '' : raw.slice(blockIndent));
+      this.index++;
+    }
+
+    let body;
+    if (style === '|') {
+      body = lines.join('\n');
+    } else {
+      // Folding joins adjacent lines with a space; blank and more-indented lines keep breaks
+      body = lines.reduce((folded, text, i) => {
+        if (i === 0) return text;
+        const previous = lines[i - 1];
+        if (text === '') return folded + '\n';
+        if (previous === '') return folded + text;
+        if (/^\s/.test(text) || /^\s/.test(previous)) return `${folded}\n${text}`;
+        return `${folded} ${text}`;
+      }, '');
+    }
+    const content = body.replace(/\n+$/, '');
+    if (chomp === '-') return content;
+    if (chomp === '+') return `${body}\n`;
+    return content ? `${content}\n` : '';
+  }
+
+  // A flow collection, joined with following lines until its brackets close
+  parseFlowText(text, line) {
+    let source = text;
+    const balanced = () => {
+      let depth = 0;
+      let quote = null;
+      for (let i = 0; i < source.length; i++) {
+        const ch = source[i];
+        if (quote) {
+          if (quote === '"' && ch === '\\') i++;
+          else if (ch === quote) quote = null;
+        } else if (ch === '"' || ch === "'") quote = ch;
+        else if (ch === '[' || ch === '{') depth++;
+        else if (ch === ']' || ch === '}') depth--;
+      }
+      return depth <= 0;
+    };
+    while (!balanced() && this.index < this.lines.length) {
+      source += ' ' + this.lines[this.index++].text.trim();
+    }
+    const flow = { text: source, pos: 0 };
+    const value = this.parseFlow(flow, line);
+    this.skipFlowSpace(flow);
+    if (flow.pos < flow.text.length) throw this.error(`Unexpected text after a flow collection: ${flow.text.slice(flow.pos)}`, line);
+    return value;
+  }
+
+  skipFlowSpace(flow) {
+    while (/\s/.test(flow.text[flow.pos] || '')) flow.pos++;
+  }
+
+  parseFlow(flow, line) {
+    this.skipFlowSpace(flow);
+    const ch = flow.text[flow.pos];
+    if (ch === '[' || ch === '{') {
+      const close = ch === '[' ? ']' : '}';
+      const result = ch === '[' ? [] : {};
+      flow.pos++;
+      for (;;) {
+        this.skipFlowSpace(flow);
+        if (flow.text[flow.pos] === close) {
+          flow.pos++;
+          return result;
+        }
+        if (flow.pos >= flow.text.length) throw this.error(`Unclosed ${ch}`, line);
+        const item = this.parseFlow(flow, line);
+        this.skipFlowSpace(flow);
+        if (flow.text[flow.pos] === ':') {
+          flow.pos++;
+          const value = this.parseFlow(flow, line);
+          if (Array.isArray(result)) result.push({ [item]: value });
+          else result[item] = value;
+        } else if (Array.isArray(result)) {
+          result.push(item);
+        } else {
+          result[item] = null;
+        }
+        this.skipFlowSpace(flow);
+        if (flow.text[flow.pos] === ',') flow.pos++;
+        else if (flow.text[flow.pos] !== close) throw this.error(`Expected , or ${close} in a flow collection`, line);
+      }
+    }
+    if (ch === '"' || ch === "'") {
+      const quoted = readQuoted(flow.text, flow.pos);
+      if (!quoted) throw this.error('Unterminated quoted string', line);
+      flow.pos = quoted.end;
+      return quoted.value;
+    }
+    // A plain scalar ends at an indicator; ":" only when followed by space or a delimiter
+    const start = flow.pos;
+    while (flow.pos < flow.text.length) {
+      const current = flow.text[flow.pos];
+      if (current === ',' || current === ']' || current === '}') break;
+      if (current === ':' && /[\s,\]}]|^$/.test(flow.text[flow.pos + 1] || '')) break;
+      flow.pos++;
+    }
+    return resolvePlain(flow.text.slice(start, flow.pos).trim());
+  }
+}
+
+// A single document; a stream holding several is an error rather than silently truncated
+function parseYaml(text) {
+  const documents = splitDocuments(text);
+  if (documents.length > 1) {
+    throw new YamlError('Expected a single document but found several', documents[1][0].number);
+  }
+  return documents.length > 0 ? new Parser(documents[0]).parseDocument() : null;
+}
+
+// Every document in the stream, in order
+function parseYamlAll(text) {
+  return splitDocuments(text).map((lines) => new Parser(lines).parseDocument());
+}
+
+// Plain when it reads back as the same string, double-quoted otherwise
+function formatScalar(value) {
+  if (value === null || value === undefined) return 'null';
+  if (typeof value === 'boolean') return String(value);
+  if (typeof value === 'number') {
+    if (Number.isNaN(value)) return '.nan';
+    if (!Number.isFinite(value)) return value > 0 ? '.inf' : '-.inf';
+    return String(value);
+  }
+  const text = String(value);
+  const plain = text !== ''
+    && text === text.trim()
+    && !/^[-?:,[\]{}#&*!|>'"%@`]/.test(text)
+    && !/: |:$| #|[\x00-\x1f\x7f\u2028\u2029\uFEFF]/.test(text)
+    && resolvePlain(text) === text;
+  return plain ? text : JSON.stringify(text);
+}
+
+function isBlockString(value) {
+  // A first line starting with a space would need an explicit indentation indicator
+  return typeof value === 'string' && value.includes('\n') && !/^[ \n]/.test(value)
+    && !/[\x00-\x08\x0b-\x1f\x7f]/.test(value) && !/\n\n$/.test(value) && !/[ \t]\n/.test(value);
+}
+
+function blockString(value, pad) {
+  const header = value.endsWith('\n') ? '|' : '|-';
+  const body = (value.endsWith('\n') ? value.slice(0, -1) : value).split('\n').map((line) => (line ? pad + line : ''));
+  return { header, body };
+}
+
+function isNonEmptyCollection(value) {
+  return value !== null && typeof value === 'object' && (Array.isArray(value) ? value.length > 0 : Object.keys(value).length > 0);
+}
+
+function formatInline(value) {
+  if (Array.isArray(value)) return '[]';
+  if (value !== null && typeof value === 'object') return '{}';
+  return formatScalar(value);
+}
+
+function renderBlock(value, depth, indent) {
+  const pad = ' '.repeat(depth * indent);
+  const childPad = ' '.repeat((depth + 1) * indent);
+  const lines = [];
+  const entries = Array.isArray(value) ? value.map((item) => [null, item]) : Object.entries(value);
+  for (const [key, item] of entries) {
+    const lead = key === null ? `${pad}-` : `${pad}${formatScalar(key)}:`;
+    if (isNonEmptyCollection(item)) {
+      const child = renderBlock(item, depth + 1, indent);
+      if (key === null) {
+        // "- " takes the place of the child's indentation on its first line
+        lines.push(`${pad}- ${' '.repeat(indent - 2)}${child[0].slice(childPad.length)}`, ...child.slice(1));
+      } else {
+        lines.push(lead, ...child);
+      }
+    } else if (isBlockString(item)) {
+      const { header, body } = blockString(item, childPad);
+      lines.push(`${lead} ${header}`, ...body);
+    } else {
+      lines.push(`${lead} ${formatInline(item)}`);
+    }
+  }
+  return lines;
+}
+
+function stringifyYaml(value, { indent = 2 } = {}) {
+  indent = Math.max(2, Math.min(8, indent));
+  if (isNonEmptyCollection(value)) return `${renderBlock(value, 0, indent).join('\n')}\n`;
+  if (isBlockString(value)) {
+    const { header, body } = blockString(value, ' '.repeat(indent));
+    return `${[header, ...body].join('\n')}\n`;
+  }
+  return `${formatInline(value)}\n`;
+}
+
+module.exports = { parseYaml, parseYamlAll, stringifyYaml, YamlError };
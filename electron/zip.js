@@ -0,0 +1,258 @@
+const fs = require('fs').promises;
+const zlib = require('zlib');
+
+// Minimal ZIP writer (deflate, zip64 when needed) used for diagnostics bundles and exports
+
+const CRC_TABLE = (() => {
+  const table = new Uint32Array(256);
+  for (let n = 0; n < 256; n++) {
+    let c = n;
+    for (let k = 0; k < 8; k++) {
+      c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+    }
+    table[n] = c >>> 0;
+  }
+  return table;
+})();
+
+function crc32(buffer) {
+  let crc = 0xffffffff;
+  for (let i = 0; i < buffer.length; i++) {
+    crc = CRC_TABLE[(crc ^ buffer[i]) & 0xff] ^ (crc >>> 8);
+  }
+  return (crc ^ 0xffffffff) >>> 0;
+}
+
+// Convert a Date to MS-DOS time/date fields
+function dosDateTime(date) {
+  const time = (date.getHours() << 11) | (date.getMinutes() << 5) | Math.floor(date.getSeconds() / 2);
+  const day = ((date.getFullYear() - 1980) << 9) | ((date.getMonth() + 1) << 5) | date.getDate();
+  return { time, day };
+}
+
+// Fields that don't fit the classic headers are written as 0xFFFFFFFF / 0xFFFF with the
+// real values in zip64 records, which every current unzip tool reads
+const MAX_UINT16 = 0xffff;
+const MAX_UINT32 = 0xffffffff;
+const ZIP64_EXTRA = 0x0001;
+
+function writeUInt64LE(buffer, value, position) {
+  buffer.writeUInt32LE(value % 0x100000000, position);
+  buffer.writeUInt32LE(Math.floor(value / 0x100000000), position + 4);
+}
+
+function readUInt64LE(buffer, position) {
+  return buffer.readUInt32LE(position) + buffer.readUInt32LE(position + 4) * 0x100000000;
+}
+
+function zip64Extra(values) {
+  const extra = Buffer.alloc(4 + values.length * 8);
+  extra.writeUInt16LE(ZIP64_EXTRA, 0);
+  extra.writeUInt16LE(values.length * 8, 2);
+  values.forEach((value, index) => writeUInt64LE(extra, value, 4 + index * 8));
+  return extra;
+}
+
+// Local header, body and central directory record for one entry written at `offset`
+function encodeEntry(entry, offset) {
+  const name = Buffer.from(entry.name.replace(/\\/g, '/'), 'utf-8');
+  const data = Buffer.isBuffer(entry.data) ? entry.data : Buffer.from(String(entry.data), 'utf-8');
+  const compressed = zlib.deflateRawSync(data);
+  const useDeflate = compressed.length < data.length;
+  const body = useDeflate ? compressed : data;
+  const crc = crc32(data);
+  const { time, day } = dosDateTime(entry.date || new Date());
+  const largeSizes = data.length >= MAX_UINT32;
+  const largeOffset = offset >= MAX_UINT32;
+
+  // In a local header the zip64 extra carries both sizes, in that order
+  const localExtra = largeSizes ? zip64Extra([data.length, body.length]) : Buffer.alloc(0);
+  const local = Buffer.alloc(30);
+  local.writeUInt32LE(0x04034b50, 0);
+  local.writeUInt16LE(largeSizes ? 45 : 20, 4);
+  local.writeUInt16LE(0x0800, 6); // UTF-8 names
+  local.writeUInt16LE(useDeflate ? 8 : 0, 8);
+  local.writeUInt16LE(time, 10);
+  local.writeUInt16LE(day, 12);
+  local.writeUInt32LE(crc, 14);
+  local.writeUInt32LE(largeSizes ? MAX_UINT32 : body.length, 18);
+  local.writeUInt32LE(largeSizes ? MAX_UINT32 : data.length, 22);
+  local.writeUInt16LE(name.length, 26);
+  local.writeUInt16LE(localExtra.length, 28);
+
+  // In the central record it carries only the fields that overflowed
+  const centralValues = [...(largeSizes ? [data.length, body.length] : []), ...(largeOffset ? [offset] : [])];
+  const centralExtra = centralValues.length ? zip64Extra(centralValues) : Buffer.alloc(0);
+  const version = centralValues.length ? 45 : 20;
+  const central = Buffer.alloc(46);
+  central.writeUInt32LE(0x02014b50, 0);
+  central.writeUInt16LE(version, 4);
+  central.writeUInt16LE(version, 6);
+  central.writeUInt16LE(0x0800, 8);
+  central.writeUInt16LE(useDeflate ? 8 : 0, 10);
+  central.writeUInt16LE(time, 12);
+  central.writeUInt16LE(day, 14);
+  central.writeUInt32LE(crc, 16);
+  central.writeUInt32LE(largeSizes ? MAX_UINT32 : body.length, 20);
+  central.writeUInt32LE(largeSizes ? MAX_UINT32 : data.length, 24);
+  central.writeUInt16LE(name.length, 28);
+  central.writeUInt16LE(centralExtra.length, 30);
+  central.writeUInt32LE(largeOffset ? MAX_UINT32 : offset, 42);
+
+  return {
+    local: Buffer.concat([local, name, localExtra]),
+    body,
+    central: Buffer.concat([central, name, centralExtra])
+  };
+}
+
+// End of central directory, preceded by the zip64 record and locator when the entry count
+// or the directory's size or offset overflow
+function encodeEnd(count, centralSize, centralOffset) {
+  const needsZip64 = count >= MAX_UINT16 || centralSize >= MAX_UINT32 || centralOffset >= MAX_UINT32;
+  const parts = [];
+  if (needsZip64) {
+    const record = Buffer.alloc(56);
+    record.writeUInt32LE(0x06064b50, 0);
+    writeUInt64LE(record, 44, 4);
+    record.writeUInt16LE(45, 12);
+    record.writeUInt16LE(45, 14);
+    writeUInt64LE(record, count, 24);
+    writeUInt64LE(record, count, 32);
+    writeUInt64LE(record, centralSize, 40);
+    writeUInt64LE(record, centralOffset, 48);
+    const locator = Buffer.alloc(20);
+    locator.writeUInt32LE(0x07064b50, 0);
+    writeUInt64LE(locator, centralOffset + centralSize, 8);
+    locator.writeUInt32LE(1, 16);
+    parts.push(record, locator);
+  }
+  const end = Buffer.alloc(22);
+  end.writeUInt32LE(0x06054b50, 0);
+  end.writeUInt16LE(Math.min(count, MAX_UINT16), 8);
+  end.writeUInt16LE(Math.min(count, MAX_UINT16), 10);
+  end.writeUInt32LE(Math.min(centralSize, MAX_UINT32), 12);
+  end.writeUInt32LE(Math.min(centralOffset, MAX_UINT32), 16);
+  parts.push(end);
+  return Buffer.concat(parts);
+}
+
+// Build a zip archive from entries of { name, data (Buffer|string), date? }
+function createZip(entries) {
+  const localParts = [];
+  const centralParts = [];
+  let offset = 0;
+
+  for (const entry of entries) {
+    const { local, body, central } = encodeEntry(entry, offset);
+    localParts.push(local, body);
+    centralParts.push(central);
+    offset += local.length + body.length;
+  }
+
+  const centralSize = centralParts.reduce((sum, part) => sum + part.length, 0);
+  return Buffer.concat([...localParts, ...centralParts, encodeEnd(entries.length, centralSize, offset)]);
+}
+
+// Writes a zip archive to a file one entry at a time, so only the current entry and the
+// central directory are held in memory
+class ZipWriter {
+  constructor(handle) {
+    this.handle = handle;
+    this.offset = 0;
+    this.central = [];
+    this.count = 0;
+  }
+
+  static async create(filePath) {
+    return new ZipWriter(await fs.open(filePath, 'w'));
+  }
+
+  async write(buffer) {
+    await this.handle.write(buffer, 0, buffer.length, this.offset);
+    this.offset += buffer.length;
+  }
+
+  async add(entry) {
+    const { local, body, central } = encodeEntry(entry, this.offset);
+    await this.write(local);
+    await this.write(body);
+    this.central.push(central);
+    this.count++;
+  }
+
+  async finish() {
+    const centralOffset = this.offset;
+    const directory = Buffer.concat(this.central);
+    await this.write(directory);
+    await this.write(encodeEnd(this.count, directory.length, centralOffset));
+    await this.handle.close();
+  }
+
+  // Closes without finishing; the caller removes the partial file
+  async abort() {
+    await this.handle.close().catch(() => {});
+  }
+}
+
+// Read entries back from a zip archive created by createZip or ZipWriter (or any archive
+// without encryption or data descriptors)
+function readZip(buffer) {
+  const entries = [];
+  let eocd = buffer.length - 22;
+  while (eocd >= 0 && buffer.readUInt32LE(eocd) !== 0x06054b50) eocd--;
+  if (eocd < 0) {
+    throw new Error('Invalid zip archive');
+  }
+
+  let count = buffer.readUInt16LE(eocd + 10);
+  let pointer = buffer.readUInt32LE(eocd + 16);
+  const locator = eocd - 20;
+  if (locator >= 0 && buffer.readUInt32LE(locator) === 0x07064b50) {
+    const record = readUInt64LE(buffer, locator + 8);
+    if (buffer.readUInt32LE(record) !== 0x06064b50) throw new Error('Invalid zip64 end of central directory');
+    count = readUInt64LE(buffer, record + 32);
+    pointer = readUInt64LE(buffer, record + 48);
+  }
+
+  for (let i = 0; i < count; i++) {
+    const method = buffer.readUInt16LE(pointer + 10);
+    let compressedSize = buffer.readUInt32LE(pointer + 20);
+    const uncompressedSize = buffer.readUInt32LE(pointer + 24);
+    const nameLength = buffer.readUInt16LE(pointer + 28);
+    const extraLength = buffer.readUInt16LE(pointer + 30);
+    const commentLength = buffer.readUInt16LE(pointer + 32);
+    let localOffset = buffer.readUInt32LE(pointer + 42);
+    const name = buffer.toString('utf-8', pointer + 46, pointer + 46 + nameLength);
+
+    // The zip64 extra lists only the overflowed fields, in header order
+    let extra = pointer + 46 + nameLength;
+    const extraEnd = extra + extraLength;
+    while (extra + 4 <= extraEnd) {
+      const id = buffer.readUInt16LE(extra);
+      const size = buffer.readUInt16LE(extra + 2);
+      if (id === ZIP64_EXTRA) {
+        let field = extra + 4;
+        if (uncompressedSize === MAX_UINT32) field += 8;
+        if (compressedSize === MAX_UINT32) {
+          compressedSize = readUInt64LE(buffer, field);
+          field += 8;
+        }
+        if (localOffset === MAX_UINT32) localOffset = readUInt64LE(buffer, field);
+      }
+      extra += 4 + size;
+    }
+
+    const localNameLength = buffer.readUInt16LE(localOffset + 26);
+    const localExtraLength = buffer.readUInt16LE(localOffset + 28);
+    const start = localOffset + 30 + localNameLength + localExtraLength;
+    const body = buffer.subarray(start, start + compressedSize);
+
+    entries.push({ name, data: method === 8 ? zlib.inflateRawSync(body) : Buffer.from(body) });
+    pointer += 46 + nameLength + extraLength + commentLength;
+  }
+
+  return entries;
+}
+
+module.exports = { createZip, ZipWriter, readZip, crc32 };
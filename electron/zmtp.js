@@ -0,0 +1,184 @@
+const net = require('net');
+const { EventEmitter } = require('events');
+
+// Minimal ZMTP 3.0 client (the ZeroMQ wire protocol) with the NULL security mechanism:
+// DEALER and SUB sockets connecting over TCP, multipart messages, no heartbeats. Enough to
+// talk to Jupyter kernels on loopback without a native zeromq binding.
+
+const FLAG_MORE = 0x01;
+const FLAG_LONG = 0x02;
+const FLAG_COMMAND = 0x04;
+const GREETING_SIZE = 64;
+const MAX_FRAME_SIZE = 256 * 1024 * 1024;
+const RETRY_MS = 100;
+
+function greeting() {
+  const buffer = Buffer.alloc(GREETING_SIZE);
+  buffer[0] = 0xff;
+  buffer[9] = 0x7f;
+  buffer[10] = 3;
+  buffer[11] = 0;
+  buffer.write('NULL', 12, 'ascii');
+  return buffer;
+}
+
+function encodeFrame(body, flags) {
+  const long = body.length > 255;
+  const head = Buffer.alloc(long ? 9 : 2);
+  head[0] = flags | (long ? FLAG_LONG : 0);
+  if (long) head.writeBigUInt64BE(BigInt(body.length), 1);
+  else head[1] = body.length;
+  return Buffer.concat([head, body]);
+}
+
+// READY command carrying the socket's metadata properties
+function readyCommand(socketType) {
+  const parts = [Buffer.from([5]), Buffer.from('READY', 'ascii')];
+  for (const [name, value] of [['Socket-Type', socketType]]) {
+    const size = Buffer.alloc(4);
+    size.writeUInt32BE(Buffer.byteLength(value));
+    parts.push(Buffer.from([name.length]), Buffer.from(name, 'ascii'), size, Buffer.from(value, 'ascii'));
+  }
+  return encodeFrame(Buffer.concat(parts), FLAG_COMMAND);
+}
+
+// Emits 'message' (Buffer[] frames), 'ready', 'error' and 'close'. Sends made before the
+// handshake finishes are queued.
+class ZmqSocket extends EventEmitter {
+  constructor(type) {
+    super();
+    if (!['DEALER', 'SUB'].includes(type)) throw new Error(`Unsupported ZeroMQ socket type ${type}`);
+    this.type = type;
+    this.socket = null;
+    this.buffer = Buffer.alloc(0);
+    this.greeted = false;
+    this.ready = false;
+    this.closed = false;
+    this.queue = [];
+    this.frames = [];
+  }
+
+  // Connects to host:port, retrying refused connections until `timeout` so a kernel that is
+  // still starting up can be waited for
+  connect(host, port, { timeout = 30000 } = {}) {
+    const deadline = Date.now() + timeout;
+    return new Promise((resolve, reject) => {
+      const attempt = () => {
+        if (this.closed) {
+          reject(new Error('Socket closed'));
+          return;
+        }
+        const socket = net.connect({ host, port });
+        const onError = (error) => {
+          socket.destroy();
+          if (error.code === 'ECONNREFUSED' && Date.now() < deadline) setTimeout(attempt, RETRY_MS);
+          else reject(error);
+        };
+        socket.once('error', onError);
+        socket.once('connect', () => {
+          socket.off('error', onError);
+          const onClose = () => reject(new Error('Connection closed during the ZeroMQ handshake'));
+          this.once('close', onClose);
+          this.once('ready', () => {
+            this.off('close', onClose);
+            resolve();
+          });
+          this.attach(socket);
+        });
+      };
+      attempt();
+    });
+  }
+
+  attach(socket) {
+    this.socket = socket;
+    socket.setNoDelay(true);
+    socket.on('data', (chunk) => this.receive(chunk));
+    socket.on('error', (error) => this.emit('error', error));
+    socket.on('close', () => this.close());
+    socket.write(greeting());
+  }
+
+  receive(chunk) {
+    this.buffer = this.buffer.length ? Buffer.concat([this.buffer, chunk]) : chunk;
+    if (!this.greeted) {
+      if (this.buffer.length < GREETING_SIZE) return;
+      if (this.buffer[0] !== 0xff || this.buffer[9] !== 0x7f || this.buffer[10] < 3) {
+        this.fail(new Error('The peer does not speak ZMTP 3'));
+        return;
+      }
+      const mechanism = this.buffer.subarray(12, 32).toString('ascii').replace(/\0+$/, '');
+      if (mechanism !== 'NULL') {
+        this.fail(new Error(`Unsupported ZeroMQ security mechanism ${mechanism}`));
+        return;
+      }
+      this.greeted = true;
+      this.buffer = this.buffer.subarray(GREETING_SIZE);
+      this.socket.write(readyCommand(this.type));
+    }
+    while (this.buffer.length >= 2) {
+      const flags = this.buffer[0];
+      const long = flags & FLAG_LONG;
+      const headSize = long ? 9 : 2;
+      if (this.buffer.length < headSize) return;
+      const size = long ? Number(this.buffer.readBigUInt64BE(1)) : this.buffer[1];
+      if (size > MAX_FRAME_SIZE) {
+        this.fail(new Error('ZeroMQ frame too large'));
+        return;
+      }
+      if (this.buffer.length < headSize + size) return;
+      const body = this.buffer.subarray(headSize, headSize + size);
+      this.buffer = this.buffer.subarray(headSize + size);
+      if (flags & FLAG_COMMAND) this.command(body);
+      else {
+        this.frames.push(Buffer.from(body));
+        if (!(flags & FLAG_MORE)) {
+          const frames = this.frames;
+          this.frames = [];
+          this.emit('message', frames);
+        }
+      }
+    }
+  }
+
+  command(body) {
+    const name = body.subarray(1, 1 + body[0]).toString('ascii');
+    if (name === 'READY') {
+      this.ready = true;
+      // A ZMTP 3.0 subscription is a message starting with 1; an empty topic takes everything
+      if (this.type === 'SUB') this.socket.write(encodeFrame(Buffer.from([1]), 0));
+      for (const frames of this.queue) this.write(frames);
+      this.queue = [];
+      this.emit('ready');
+    } else if (name === 'ERROR') {
+      const reason = body.subarray(2 + body[0], 2 + body[0] + body[1 + body[0]]).toString('utf-8');
+      this.fail(new Error(`ZeroMQ peer error: ${reason}`));
+    }
+  }
+
+  write(frames) {
+    const encoded = frames.map((frame, index) => encodeFrame(Buffer.isBuffer(frame) ? frame : Buffer.from(String(frame), 'utf-8'), index < frames.length - 1 ? FLAG_MORE : 0));
+    this.socket.write(Buffer.concat(encoded));
+  }
+
+  send(frames) {
+    if (this.closed) return false;
+    if (this.ready) this.write(frames);
+    else this.queue.push(frames);
+    return true;
+  }
+
+  fail(error) {
+    this.emit('error', error);
+    this.close();
+  }
+
+  close() {
+    if (this.closed) return;
+    this.closed = true;
+    if (this.socket) this.socket.destroy();
+    this.emit('close');
+  }
+}
+
+module.exports = { ZmqSocket };
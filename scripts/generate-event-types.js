@@ -0,0 +1,59 @@
+#!/usr/bin/env node
+// Generates src/services/electron/events.generated.ts from electron/eventCatalog.js
+const fs = require('fs');
+const path = require('path');
+const { EVENT_CATALOG } = require('../electron/eventCatalog');
+
+const TS_TYPES = {
+  string: 'string',
+  number: 'number',
+  boolean: 'boolean',
+  object: 'Record<string, unknown>',
+  array: 'unknown[]',
+  any: 'unknown'
+};
+
+function toTypeName(channel) {
+  return channel
+    .split(/[:\-_]/)
+    .map((part) => part.charAt(0).toUpperCase() + part.slice(1))
+    .join('') + 'Event';
+}
+
+const lines = [
+  '// This file is generated by scripts/generate-event-types.js. Do not edit by hand.',
+  ''
+];
+
+for (const [channel, definition] of Object.entries(EVENT_CATALOG)) {
+  lines.push(`/** ${definition.description} */`);
+  lines.push(`export interface ${toTypeName(channel)} {`);
+  for (const [field, type] of Object.entries(definition.fields)) {
+    const optional = type.endsWith('?');
+    const tsType = TS_TYPES[optional ? type.slice(0, -1) : type];
+    lines.push(`  ${field}${optional ? '?' : ''}: ${tsType}${optional ? ' | null' : ''};`);
+  }
+  lines.push('  seq: number;');
+  lines.push('}');
+  lines.push('');
+}
+
+lines.push('export interface ForgeEventMap {');
+for (const channel of Object.keys(EVENT_CATALOG)) {
+  lines.push(`  '${channel}': ${toTypeName(channel)};`);
+}
+lines.push('}');
+lines.push('');
+lines.push('export type ForgeEventChannel = keyof ForgeEventMap;');
+lines.push('');
+lines.push('export interface ForgeEventEnvelope<C extends ForgeEventChannel = ForgeEventChannel> {');
+lines.push('  seq: number;');
+lines.push('  channel: C;');
+lines.push('  timestamp: number;');
+lines.push("  payload: Omit<ForgeEventMap[C], 'seq'>;");
+lines.push('}');
+lines.push('');
+
+const outputPath = path.join(__dirname, '../src/services/electron/events.generated.ts');
+fs.writeFileSync(outputPath, lines.join('\n'));
+console.log(`Wrote ${Object.keys(EVENT_CATALOG).length} event types to ${path.relative(process.cwd(), outputPath)}`);
@@ -4,15 +4,17 @@ import { ElectronBrowser } from './ElectronBrowser';
 import { SmartBrowser } from './SmartBrowser';
 import { isElectron } from '@/services/electron';
 import { useEffect, useState } from 'react';
+import type { BrowserSessionView } from '@/services/electron';
 
 interface BrowserProps {
   url?: string;
   className?: string;
   tabId: string;
   isActive?: boolean;
+  restore?: BrowserSessionView;
 }
 
-export function Browser({ url, className, tabId, isActive = true }: BrowserProps) {
+export function Browser({ url, className, tabId, isActive = true, restore }: BrowserProps) {
   const [isElectronEnv, setIsElectronEnv] = useState(false);
   const [isLoading, setIsLoading] = useState(true);
 
@@ -40,6 +42,7 @@ export function Browser({ url, className, tabId, isActive = true }: BrowserProps
           className={className} 
           tabId={tabId}
           isActive={isActive}
+          restore={restore}
         />
       );
     } catch (error) {
@@ -2,6 +2,7 @@
 
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { getBrowserService } from '@/services/electron';
+import type { BrowserSessionView } from '@/services/electron';
 import { useLayoutStore } from '@/stores/layoutStore';
 import { 
   RefreshCw, 
@@ -34,9 +35,11 @@ interface ElectronBrowserProps {
   className?: string;
   tabId: string;
   isActive?: boolean;
+  // Saved state of a tab reopened from the workspace session
+  restore?: BrowserSessionView;
 }
 
-export function ElectronBrowser({ url: initialUrl, className, tabId, isActive = true }: ElectronBrowserProps) {
+export function ElectronBrowser({ url: initialUrl, className, tabId, isActive = true, restore }: ElectronBrowserProps) {
   const [url, setUrl] = useState(initialUrl || 'https://moikas.com');
   const [inputUrl, setInputUrl] = useState(initialUrl || 'https://moikas.com');
   const [loading, setLoading] = useState(false);
@@ -122,7 +125,11 @@ export function ElectronBrowser({ url: initialUrl, className, tabId, isActive =
         
         // Create BrowserView with bounds
         const browserInfo = await browserService.current.create({
-          url: normalizeUrl(url),
+          // Saved URLs are already resolved and may be file: or forge: pages
+          url: restore ? restore.url : normalizeUrl(url),
+          profile: restore?.profile,
+          zoomFactor: restore?.zoomFactor,
+          devTools: restore?.devTools,
           bounds: {
             x: Math.round(rect.left),
             y: Math.round(rect.top),
@@ -11,6 +11,7 @@ import type { editor } from 'monaco-editor';
 import { EditorToolbar } from './EditorToolbar';
 import { FindReplacePanel } from './FindReplacePanel';
 import { useFileWatcher } from '@/hooks/useFileWatcher';
+import { getElectronAPI } from '@/services/electron';
 
 interface CodeEditorProps {
   tabId: string;
@@ -34,6 +35,7 @@ export function CodeEditor({ tabId, path, content: initialContent, className }:
   const editorRef = useRef<editor.IStandaloneCodeEditor | null>(null);
   const monacoRef = useRef<Monaco | null>(null);
   const monacoConfigured = useRef(false);
+  const editorFocused = useRef(false);
   
   const updateTab = useLayoutStore((state) => state.updateTab);
   const { 
@@ -154,6 +156,16 @@ export function CodeEditor({ tabId, path, content: initialContent, className }:
         console.error('Failed to add save command:', commandError);
       }
 
+      // Commands gated on `editorFocus` (e.g. Rename Symbol) follow this editor's focus
+      editor.onDidFocusEditorWidget(() => {
+        editorFocused.current = true;
+        getElectronAPI()?.commands.setContext('editorFocus', true).catch(console.error);
+      });
+      editor.onDidBlurEditorWidget(() => {
+        editorFocused.current = false;
+        getElectronAPI()?.commands.setContext('editorFocus', null).catch(console.error);
+      });
+
       // Focus editor
       try {
         editor.focus();
@@ -281,6 +293,13 @@ export function CodeEditor({ tabId, path, content: initialContent, className }:
     editorRef.current?.updateOptions({ wordWrap: newValue ? 'on' : 'off' });
   }, [wordWrap]);
 
+  // An editor unmounted while focused never blurs, so clear its focus context here
+  useEffect(() => () => {
+    if (editorFocused.current) {
+      getElectronAPI()?.commands.setContext('editorFocus', null).catch(console.error);
+    }
+  }, []);
+
   // Handle theme changes
   useEffect(() => {
     const handleThemeChange = () => {
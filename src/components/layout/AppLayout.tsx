@@ -17,6 +17,7 @@ import { ComponentErrorBoundary } from './ComponentErrorBoundary';
 import { useKeyboardShortcuts } from '@/hooks/useKeyboardShortcuts';
 import { useSkipLinks } from '@/hooks/useSkipLinks';
 import { cn } from '@/lib/utils';
+import { getElectronAPI } from '@/services/electron';
 
 export function AppLayout() {
   const { 
@@ -48,15 +49,35 @@ export function AppLayout() {
   useEffect(() => {
     setIsLoading(false);
     
-    // Create default browser tab if no tabs exist
-    if (tabs.length === 0) {
-      const { addTab } = useLayoutStore.getState();
+    const restoreBrowserTabs = async () => {
+      if (tabs.length > 0) return;
+      const { addTab, setActiveTab } = useLayoutStore.getState();
+
+      // Reopen the browser tabs this workspace had at last quit
+      const api = getElectronAPI();
+      const session = api ? await api.browser.getSession().catch(() => null) : null;
+      if (session && session.views.length > 0) {
+        for (const view of session.views) {
+          addTab({
+            title: view.title || 'New Tab',
+            type: 'browser',
+            path: view.url,
+            browserState: view
+          });
+        }
+        const active = useLayoutStore.getState().tabs.find(t => t.browserState?.id === session.activeId);
+        if (active) setActiveTab(active.id);
+        return;
+      }
+
+      // Create default browser tab if no tabs exist
       addTab({
         title: 'moikas.com',
         type: 'browser',
         path: 'https://moikas.com'
       });
-    }
+    };
+    restoreBrowserTabs();
   }, []);
   
   // Close mobile sidebar when clicking outside (on medium+ screens)
@@ -182,6 +203,7 @@ export function AppLayout() {
                               className="h-full"
                               tabId={tab.id}
                               isActive={tab.id === activeTab?.id}
+                              restore={tab.browserState}
                             />
                           </ComponentErrorBoundary>
                         </TabErrorBoundary>
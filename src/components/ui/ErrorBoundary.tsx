@@ -1,5 +1,6 @@
 import React, { Component, ErrorInfo, ReactNode } from 'react';
 import { Button } from './button';
+import { getElectronAPI } from '@/services/electron';
 
 interface ErrorBoundaryProps {
   children: ReactNode;
@@ -25,6 +26,13 @@ export class ErrorBoundary extends Component<ErrorBoundaryProps, ErrorBoundarySt
   componentDidCatch(error: Error, errorInfo: ErrorInfo) {
     console.error('ErrorBoundary caught an error:', error, errorInfo);
     
+    // Record a crash report in the main process
+    getElectronAPI()?.crash.reportRendererError({
+      message: error.message,
+      stack: error.stack,
+      componentStack: errorInfo.componentStack ?? undefined
+    }).catch(() => {});
+    
     // Call optional error handler
     if (this.props.onError) {
       this.props.onError(error, errorInfo);
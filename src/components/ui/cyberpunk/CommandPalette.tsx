@@ -19,6 +19,8 @@ import {
 } from 'lucide-react';
 import { useLayoutStore } from '@/stores/layoutStore';
 import { cn } from '@/lib/utils';
+import { getElectronAPI } from '@/services/electron';
+import type { CommandInfo } from '@/services/electron';
 
 interface CommandItem {
   id: string;
@@ -26,12 +28,22 @@ interface CommandItem {
   icon: React.ReactNode;
   shortcut?: string;
   action: () => void;
-  category: 'navigation' | 'tools' | 'ai' | 'settings';
+  category: string;
+}
+
+interface LocalCommand {
+  // Title, category and keybinding mirror the backend registry, for web mode without it
+  title: string;
+  category: string;
+  keybinding?: string;
+  icon: React.ReactNode;
+  action: () => void;
 }
 
 export function CommandPalette() {
   const [open, setOpen] = useState(false);
   const [search, setSearch] = useState('');
+  const [registry, setRegistry] = useState<CommandInfo[]>([]);
   const inputRef = useRef<HTMLInputElement>(null);
   const { addTab } = useLayoutStore();
 
@@ -55,130 +67,153 @@ export function CommandPalette() {
     }
   }, [open]);
 
-  const commands: CommandItem[] = [
-    // Navigation
-    {
-      id: 'browser',
-      label: 'Open Browser',
+  // Renderer-side actions for commands registered in the backend registry
+  const localCommands: Record<string, LocalCommand> = {
+    'workbench.openBrowser': {
+      title: 'Open Browser',
+      category: 'navigation',
+      keybinding: 'CmdOrCtrl+B',
       icon: <Globe className="w-4 h-4" />,
-      shortcut: '⌘B',
-      action: () => {
-        addTab({ type: 'browser', title: 'Browser', path: 'https://www.google.com' });
-        setOpen(false);
-      },
-      category: 'navigation'
+      action: () => addTab({ type: 'browser', title: 'Browser', path: 'https://www.google.com' })
     },
-    {
-      id: 'editor',
-      label: 'New Code File',
+    'workbench.newFile': {
+      title: 'New Code File',
+      category: 'navigation',
+      keybinding: 'CmdOrCtrl+N',
       icon: <Code className="w-4 h-4" />,
-      shortcut: '⌘N',
-      action: () => {
-        addTab({ type: 'editor', title: 'Untitled', path: '', content: '' });
-        setOpen(false);
-      },
-      category: 'navigation'
+      action: () => addTab({ type: 'editor', title: 'Untitled', path: '', content: '' })
     },
-    {
-      id: 'terminal',
-      label: 'Open Terminal',
+    'workbench.openTerminal': {
+      title: 'Open Terminal',
+      category: 'navigation',
+      keybinding: 'CmdOrCtrl+T',
       icon: <Terminal className="w-4 h-4" />,
-      shortcut: '⌘T',
       action: () => {
         const { toggleBottomPanel, isBottomPanelCollapsed } = useLayoutStore.getState();
         if (isBottomPanelCollapsed) {
           toggleBottomPanel();
         }
-        setOpen(false);
-      },
-      category: 'navigation'
+      }
     },
-    {
-      id: 'explorer',
-      label: 'File Explorer',
+    'workbench.openExplorer': {
+      title: 'File Explorer',
+      category: 'navigation',
+      keybinding: 'CmdOrCtrl+E',
       icon: <FileText className="w-4 h-4" />,
-      shortcut: '⌘E',
-      action: () => {
-        addTab({ type: 'explorer', title: 'Files', path: '~' });
-        setOpen(false);
-      },
-      category: 'navigation'
+      action: () => addTab({ type: 'explorer', title: 'Files', path: '~' })
     },
-    // AI Tools
-    {
-      id: 'ai-image',
-      label: 'Generate Image',
+    'ai.generateImage': {
+      title: 'Generate Image',
+      category: 'ai',
       icon: <Image className="w-4 h-4" />,
-      action: () => {
-        addTab({ type: 'ai-image', title: 'AI Image', path: '' });
-        setOpen(false);
-      },
-      category: 'ai'
+      action: () => addTab({ type: 'ai-image', title: 'AI Image', path: '' })
     },
-    {
-      id: 'ai-audio',
-      label: 'Generate Audio',
+    'ai.generateAudio': {
+      title: 'Generate Audio',
+      category: 'ai',
       icon: <Music className="w-4 h-4" />,
-      action: () => {
-        addTab({ type: 'ai-audio', title: 'AI Audio', path: '' });
-        setOpen(false);
-      },
-      category: 'ai'
+      action: () => addTab({ type: 'ai-audio', title: 'AI Audio', path: '' })
     },
-    {
-      id: 'ai-video',
-      label: 'Generate Video',
+    'ai.generateVideo': {
+      title: 'Generate Video',
+      category: 'ai',
       icon: <Video className="w-4 h-4" />,
-      action: () => {
-        addTab({ type: 'ai-video', title: 'AI Video', path: '' });
-        setOpen(false);
-      },
-      category: 'ai'
+      action: () => addTab({ type: 'ai-video', title: 'AI Video', path: '' })
     },
-    {
-      id: 'ai-assistant',
-      label: 'AI Assistant',
+    'ai.openAssistant': {
+      title: 'AI Assistant',
+      category: 'ai',
+      keybinding: 'CmdOrCtrl+/',
       icon: <Sparkles className="w-4 h-4" />,
-      shortcut: '⌘/',
-      action: () => {
-        addTab({ type: 'ai-chat', title: 'AI Chat', path: '' });
-        setOpen(false);
-      },
-      category: 'ai'
+      action: () => addTab({ type: 'ai-chat', title: 'AI Chat', path: '' })
     },
-    // Settings
-    {
-      id: 'theme',
-      label: 'Change Theme',
+    'workbench.changeTheme': {
+      title: 'Change Theme',
+      category: 'settings',
       icon: <Palette className="w-4 h-4" />,
       action: () => {
         // TODO: Implement theme switcher
-        setOpen(false);
-      },
-      category: 'settings'
+      }
     },
-    {
-      id: 'settings',
-      label: 'Settings',
+    'workbench.openSettings': {
+      title: 'Settings',
+      category: 'settings',
+      keybinding: 'CmdOrCtrl+,',
       icon: <Settings className="w-4 h-4" />,
-      shortcut: '⌘,',
+      action: () => addTab({ type: 'settings', title: 'Settings', path: '' })
+    }
+  };
+
+  // Refresh the command list from the backend registry whenever the palette opens. Without
+  // Electron (web mode) there is no registry, so the renderer's own commands stand in.
+  useEffect(() => {
+    if (!open) return;
+    const api = getElectronAPI();
+    if (!api) {
+      setRegistry(Object.entries(localCommands).map(([id, command]) => ({
+        id,
+        title: command.title,
+        category: command.category,
+        keybinding: command.keybinding,
+        source: 'core',
+        palette: true,
+        enabled: true,
+        handledBy: 'renderer'
+      })));
+      return;
+    }
+
+    api.commands.list().then(setRegistry).catch((error) => {
+      console.error('Failed to load commands:', error);
+    });
+  // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [open]);
+
+  // Run renderer-side actions requested by the backend registry
+  useEffect(() => {
+    const api = getElectronAPI();
+    if (!api) return;
+
+    return api.commands.onInvoke(({ id }) => {
+      localCommands[id]?.action();
+    });
+  // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
+  const formatShortcut = (keybinding?: string) =>
+    keybinding?.replace('CmdOrCtrl+', '⌘');
+
+  const commands: CommandItem[] = registry
+    .filter(cmd => cmd.enabled && cmd.palette)
+    .map(cmd => ({
+      id: cmd.id,
+      label: cmd.title,
+      icon: localCommands[cmd.id]?.icon ?? <Sparkles className="w-4 h-4" />,
+      shortcut: formatShortcut(cmd.keybinding),
+      // Always through the registry, so history and telemetry see the command; renderer
+      // commands come back as commands:invoke and run their local action there
       action: () => {
-        addTab({ type: 'settings', title: 'Settings', path: '' });
         setOpen(false);
+        const api = getElectronAPI();
+        if (!api) {
+          localCommands[cmd.id]?.action();
+          return;
+        }
+        api.commands.execute(cmd.id).catch((error) => {
+          console.error(`Failed to execute command ${cmd.id}:`, error);
+        });
       },
-      category: 'settings'
-    }
-  ];
+      category: cmd.category
+    }));
 
   const filteredCommands = commands.filter(cmd => 
     cmd.label.toLowerCase().includes(search.toLowerCase())
   );
 
-  const groupedCommands = {
-    navigation: filteredCommands.filter(cmd => cmd.category === 'navigation'),
-    ai: filteredCommands.filter(cmd => cmd.category === 'ai'),
-    settings: filteredCommands.filter(cmd => cmd.category === 'settings')
-  };
+  const groupedCommands = filteredCommands.reduce<Record<string, CommandItem[]>>((groups, cmd) => {
+    (groups[cmd.category] ||= []).push(cmd);
+    return groups;
+  }, {});
 
   if (!open) return null;
 
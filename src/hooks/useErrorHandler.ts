@@ -1,4 +1,5 @@
 import { useCallback } from 'react';
+import { parseForgeError } from '@/services/electron/errors';
 
 interface ErrorHandlerOptions {
   onError?: (error: Error) => void;
@@ -8,9 +9,13 @@ interface ErrorHandlerOptions {
 
 export function useErrorHandler(options: ErrorHandlerOptions = {}) {
   const handle_error = useCallback((error: Error) => {
+    const forge_error = parseForgeError(error);
     const error_context = {
       component: options.component,
-      message: error.message,
+      code: forge_error.code,
+      message: forge_error.message,
+      recoverable: forge_error.recoverable,
+      details: forge_error.details,
       stack: error.stack,
       timestamp: new Date().toISOString(),
     };
@@ -7,6 +7,7 @@ import { useEditorStore } from '@/stores/editorStore';
 interface FileChangeEvent {
   path: string;
   change_type: 'modified' | 'created' | 'deleted' | 'renamed';
+  new_path?: string;
   timestamp: number;
 }
 
@@ -71,7 +72,7 @@ export function useFileWatcher(options: FileWatcherOptions = {}) {
   }, [showNotification]);
 
   const handleFileChange = useCallback(async (event: FileChangeEvent) => {
-    const { path, change_type, timestamp } = event;
+    const { path, change_type, new_path } = event;
     
     // Find tabs with this file
     const affectedTabs = tabs.filter(tab => tab.path === path);
@@ -131,12 +132,19 @@ export function useFileWatcher(options: FileWatcherOptions = {}) {
       }
       
       case 'renamed': {
+        if (!new_path) break;
+        const newName = new_path.split('/').pop() || new_path;
         show_file_change_notification(
-          `File "${path.split('/').pop()}" was renamed externally`,
+          `File "${path.split('/').pop()}" was renamed externally to "${newName}"`,
           'info'
         );
         
-        onFileRenamed?.(path, path); // In a real implementation, you'd get the new path
+        // Follow the file; the tab effect below then moves the watch to the new path
+        affectedTabs.forEach(tab => {
+          updateTab(tab.id, { path: new_path, title: newName });
+        });
+        
+        onFileRenamed?.(path, new_path);
         break;
       }
     }
@@ -154,9 +162,9 @@ export function useFileWatcher(options: FileWatcherOptions = {}) {
         // Convert Electron event to our FileChangeEvent format
         const fileChangeEvent: FileChangeEvent = {
           path: event.path,
-          change_type: event.type === 'change' ? 'modified' : 
-                       event.type === 'rename' ? 'renamed' : 
-                       event.type === 'delete' ? 'deleted' : 'modified',
+          change_type: event.type === 'renamed' ? 'renamed' :
+                       event.type === 'deleted' ? 'deleted' : 'modified',
+          new_path: event.newPath,
           timestamp: Date.now()
         };
         handleFileChange(fileChangeEvent);
@@ -5,7 +5,8 @@ import type {
   BrowserBounds, 
   BrowserNavigateEvent, 
   BrowserTitleEvent, 
-  BrowserLoadEvent 
+  BrowserLoadEvent,
+  BrowserSession
 } from './index';
 
 // Browser service wrapper for Electron API
@@ -115,6 +116,11 @@ export class BrowserService {
     return this.api.browser.openDevTools(id);
   }
 
+  async getSession(): Promise<BrowserSession> {
+    if (!this.api) throw new Error('Electron API not available');
+    return this.api.browser.getSession();
+  }
+
   async closeDevTools(id: string): Promise<boolean> {
     if (!this.api) throw new Error('Electron API not available');
     return this.api.browser.closeDevTools(id);
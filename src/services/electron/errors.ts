@@ -0,0 +1,57 @@
+// Structured errors from the Electron main process (see electron/errors.js)
+
+export type ForgeErrorCode =
+  | 'NOT_FOUND'
+  | 'ALREADY_EXISTS'
+  | 'ACCESS_DENIED'
+  | 'PERMISSION_DENIED'
+  | 'INVALID_ARGUMENT'
+  | 'IS_DIRECTORY'
+  | 'NOT_DIRECTORY'
+  | 'NO_SPACE'
+  | 'BUSY'
+  | 'CONFLICT'
+  | 'IO_ERROR'
+  | 'TIMEOUT'
+  | 'CANCELLED'
+  | 'TERMINAL_NOT_FOUND'
+  | 'TERMINAL_SPAWN_FAILED'
+  | 'BROWSER_VIEW_NOT_FOUND'
+  | 'WINDOW_UNAVAILABLE'
+  | 'SESSION_NOT_FOUND'
+  | 'CONFIRMATION_REQUIRED'
+  | 'INTERNAL';
+
+export interface ForgeErrorInfo {
+  code: ForgeErrorCode;
+  message: string;
+  details?: Record<string, unknown>;
+  recoverable: boolean;
+}
+
+const ERROR_MARKER = 'ForgeError:';
+
+// Decode an error rejected by an IPC call. Errors that did not come from a
+// ForgeError-aware handler are reported as INTERNAL with their original message.
+export function parseForgeError(error: unknown): ForgeErrorInfo {
+  const message = error instanceof Error ? error.message : String(error);
+  const index = message.indexOf(ERROR_MARKER);
+
+  if (index >= 0) {
+    try {
+      return JSON.parse(message.slice(index + ERROR_MARKER.length)) as ForgeErrorInfo;
+    } catch {
+      // Fall through to the generic shape
+    }
+  }
+
+  return {
+    code: 'INTERNAL',
+    message: message.replace(/^Error invoking remote method '[^']+': (Error: )?/, ''),
+    recoverable: false
+  };
+}
+
+export function isForgeErrorCode(error: unknown, code: ForgeErrorCode): boolean {
+  return parseForgeError(error).code === code;
+}
@@ -0,0 +1,717 @@
+// This file is generated by scripts/generate-event-types.js. Do not edit by hand.
+
+/** Output bytes from a terminal PTY */
+export interface TerminalDataEvent {
+  terminalId: string;
+  data: unknown[];
+  seq: number;
+}
+
+/** A terminal process exited */
+export interface TerminalExitEvent {
+  terminalId: string;
+  exitCode: number;
+  seq: number;
+}
+
+/** A watched file changed on disk; renamed files carry their new path */
+export interface FsFileChangedEvent {
+  path: string;
+  type: string;
+  newPath?: string | null;
+  seq: number;
+}
+
+/** A browser view navigated to a new URL */
+export interface BrowserNavigateEvent {
+  id: string;
+  url: string;
+  seq: number;
+}
+
+/** A browser view page title changed */
+export interface BrowserTitleUpdateEvent {
+  id: string;
+  title: string;
+  seq: number;
+}
+
+/** A browser view started loading */
+export interface BrowserLoadStartEvent {
+  id: string;
+  seq: number;
+}
+
+/** A browser view finished loading */
+export interface BrowserLoadStopEvent {
+  id: string;
+  seq: number;
+}
+
+/** A page asked to open a URL in a new tab */
+export interface BrowserNewTabRequestEvent {
+  url: string;
+  disposition: string;
+  seq: number;
+}
+
+/** Auto-updater status change or download progress */
+export interface UpdaterStatusEvent {
+  status: string;
+  data?: unknown | null;
+  seq: number;
+}
+
+/** Forge captured an error and kept running */
+export interface AppRecoveredFromErrorEvent {
+  reportId: string;
+  source: string;
+  message: string;
+  seq: number;
+}
+
+/** A background job started */
+export interface JobStartedEvent {
+  id: string;
+  kind: string;
+  title: string;
+  status: string;
+  cancellable: boolean;
+  progress?: number | null;
+  message?: string | null;
+  startedAt: string;
+  seq: number;
+}
+
+/** Progress update for a running job (0..1, or null when indeterminate) */
+export interface JobProgressEvent {
+  id: string;
+  progress?: number | null;
+  message?: string | null;
+  seq: number;
+}
+
+/** A background job succeeded, failed, or was cancelled */
+export interface JobCompletedEvent {
+  id: string;
+  status: string;
+  error?: Record<string, unknown> | null;
+  result?: unknown | null;
+  seq: number;
+}
+
+/** A workspace trust decision changed */
+export interface TrustChangedEvent {
+  path: string;
+  trusted: boolean;
+  decidedAt?: string | null;
+  inherited: boolean;
+  seq: number;
+}
+
+/** A multi-file edit was applied and recorded in the undo journal */
+export interface RefactorAppliedEvent {
+  journalId: string;
+  description: string;
+  paths: unknown[];
+  seq: number;
+}
+
+/** The TODO index for a workspace changed */
+export interface TodosUpdatedEvent {
+  rootPath: string;
+  count: number;
+  indexing: boolean;
+  seq: number;
+}
+
+/** A new or re-used entry moved to the top of the clipboard history */
+export interface ClipboardChangedEvent {
+  id: string;
+  text: string;
+  preview: string;
+  hasHtml: boolean;
+  source: string;
+  pinned: boolean;
+  createdAt: string;
+  lastUsedAt: string;
+  seq: number;
+}
+
+/** A native notification (or one of its action buttons) was clicked */
+export interface NotificationClickedEvent {
+  id: string;
+  action?: string | null;
+  data?: unknown | null;
+  seq: number;
+}
+
+/** A workbench window was opened */
+export interface WindowOpenedEvent {
+  windowId: number;
+  workspaceId: string;
+  workspacePath?: string | null;
+  seq: number;
+}
+
+/** A workbench window was closed */
+export interface WindowClosedEvent {
+  windowId: number;
+  workspaceId: string;
+  seq: number;
+}
+
+/** A live terminal was moved into this window, with its scrollback for repainting */
+export interface TerminalAttachedEvent {
+  terminalId: string;
+  fromWindowId: number;
+  shell: string;
+  cwd: string;
+  restricted: boolean;
+  cols: number;
+  rows: number;
+  scrollback: string;
+  seq: number;
+}
+
+/** A terminal was moved out of this window */
+export interface TerminalDetachedEvent {
+  terminalId: string;
+  toWindowId: number;
+  seq: number;
+}
+
+/** An editor was moved into this window from another one */
+export interface EditorAttachedEvent {
+  fromWindowId?: number | null;
+  editor: Record<string, unknown>;
+  seq: number;
+}
+
+/** Open a file in this window, optionally at a position. waitId is set when a caller waits for it to close */
+export interface WorkbenchOpenFileEvent {
+  path: string;
+  line?: number | null;
+  column?: number | null;
+  exists: boolean;
+  waitId?: string | null;
+  source: string;
+  seq: number;
+}
+
+/** Open a side-by-side diff of two files or folders in this window. sessionId names a compare session holding the result; for unsaved text, right is its label */
+export interface WorkbenchOpenDiffEvent {
+  left: string;
+  right: string;
+  waitId?: string | null;
+  sessionId?: string | null;
+  source: string;
+  seq: number;
+}
+
+/** Streamed text from an in-flight AI request, batched every few milliseconds */
+export interface AiTokenEvent {
+  requestId: string;
+  delta: string;
+  seq: number;
+}
+
+/** An AI request finished streaming */
+export interface AiDoneEvent {
+  requestId: string;
+  content: string;
+  finishReason?: string | null;
+  usage?: Record<string, unknown> | null;
+  providerId: string;
+  model: string;
+  seq: number;
+}
+
+/** An AI request failed or was cancelled */
+export interface AiErrorEvent {
+  requestId: string;
+  code: string;
+  message: string;
+  seq: number;
+}
+
+/** A macro recording started, stopped, or captured another action */
+export interface AutomationRecordingChangedEvent {
+  recording: boolean;
+  name?: string | null;
+  actionCount: number;
+  seq: number;
+}
+
+/** A file watcher changed state: healthy, polling, rescanning, degraded, or failed */
+export interface WatcherHealthEvent {
+  root: string;
+  owner: string;
+  status: string;
+  mode: string;
+  fsType?: string | null;
+  reason?: string | null;
+  hint?: string | null;
+  lastRescanAt?: string | null;
+  seq: number;
+}
+
+/** Progress and staleness of a workspace file or symbol index; stale while a cached index is being verified */
+export interface IndexStatusEvent {
+  rootPath: string;
+  index: string;
+  state: string;
+  stale: boolean;
+  fromCache: boolean;
+  processed?: number | null;
+  total?: number | null;
+  count: number;
+  savedAt?: string | null;
+  seq: number;
+}
+
+/** The number of viewers watching a live share session changed */
+export interface LiveShareViewersEvent {
+  sessionId: string;
+  viewers: number;
+  seq: number;
+}
+
+/** A live share session stopped: by request, with its window, or because its terminal exited */
+export interface LiveShareEndedEvent {
+  sessionId: string;
+  reason: string;
+  seq: number;
+}
+
+/** CRDT operations from a collaborator or a disk reload, for the host editor to apply; changes are plain-text edits as applied to the shared copy */
+export interface CollabOpsEvent {
+  sessionId: string;
+  path: string;
+  peerId: string;
+  ops: unknown[];
+  changes?: unknown[] | null;
+  seq: number;
+}
+
+/** Someone joined or left a collaboration session */
+export interface CollabParticipantsEvent {
+  sessionId: string;
+  participants: unknown[];
+  seq: number;
+}
+
+/** A collaborator moved their cursor or selection */
+export interface CollabPresenceEvent {
+  sessionId: string;
+  path: string;
+  peerId: string;
+  name: string;
+  selection?: unknown | null;
+  seq: number;
+}
+
+/** A shared file changed on disk while it had unsaved collaborative edits; the host should save or reload */
+export interface CollabDiskChangedEvent {
+  sessionId: string;
+  path: string;
+  seq: number;
+}
+
+/** A collaboration session stopped */
+export interface CollabEndedEvent {
+  sessionId: string;
+  reason: string;
+  seq: number;
+}
+
+/** A tunnel is starting, open with a public URL, closed, or failed */
+export interface TunnelStatusEvent {
+  id: string;
+  port: number;
+  host: string;
+  provider: string;
+  status: string;
+  url?: string | null;
+  error?: string | null;
+  startedAt: string;
+  seq: number;
+}
+
+/** A task in a pipeline run changed status */
+export interface TaskStatusEvent {
+  runId: string;
+  pipeline?: string | null;
+  task: string;
+  status: string;
+  attempt?: number | null;
+  exitCode?: number | null;
+  durationMs?: number | null;
+  allowed?: boolean | null;
+  seq: number;
+}
+
+/** Output from a running pipeline task */
+export interface TaskOutputEvent {
+  runId: string;
+  task: string;
+  stream: string;
+  data: string;
+  seq: number;
+}
+
+/** A task or pipeline run finished */
+export interface TaskRunFinishedEvent {
+  runId: string;
+  jobId: string;
+  pipeline?: string | null;
+  status: string;
+  tasks: Record<string, unknown>;
+  startedAt: string;
+  finishedAt: string;
+  seq: number;
+}
+
+/** The problems reported by one source changed */
+export interface ProblemsChangedEvent {
+  source: string;
+  count: number;
+  errors: number;
+  warnings: number;
+  seq: number;
+}
+
+/** A command run in a terminal was added to the history */
+export interface CommandHistoryAddedEvent {
+  id: string;
+  command: string;
+  cwd?: string | null;
+  exitCode?: number | null;
+  durationMs?: number | null;
+  terminalId: string;
+  shell?: string | null;
+  startedAt?: string | null;
+  finishedAt: string;
+  seq: number;
+}
+
+/** Directory bookmarks were added, renamed, used or removed */
+export interface BookmarksChangedEvent {
+  count: number;
+  seq: number;
+}
+
+/** An inline image (Sixel, iTerm2 or Kitty) finished decoding in terminal output */
+export interface TerminalImageEvent {
+  terminalId: string;
+  imageId: string;
+  protocol: string;
+  data: string;
+  width: number;
+  height: number;
+  placement: Record<string, unknown>;
+  seq: number;
+}
+
+/** Mouse reporting requested by a terminal program, or its forwarding setting, changed */
+export interface TerminalMouseModeEvent {
+  terminalId: string;
+  tracking?: string | null;
+  encoding: string;
+  forwarding: boolean;
+  active: boolean;
+  seq: number;
+}
+
+/** A terminal switched between the normal and alternate screen buffer */
+export interface TerminalScreenChangedEvent {
+  terminalId: string;
+  altScreen: boolean;
+  seq: number;
+}
+
+/** Annotations on a terminal output were added, removed or trimmed */
+export interface TerminalAnnotationsChangedEvent {
+  terminalId: string;
+  count: number;
+  seq: number;
+}
+
+/** A batch of file changes matching one watch subscription */
+export interface WatcherChangesEvent {
+  subscriptionId: string;
+  root: string;
+  changes: unknown[];
+  overflow: boolean;
+  seq: number;
+}
+
+/** Save hooks finished for a saved file */
+export interface SaveHooksCompletedEvent {
+  path: string;
+  hooks: unknown[];
+  seq: number;
+}
+
+/** File decorations in a workspace were assigned or removed */
+export interface DecorationsChangedEvent {
+  rootPath: string;
+  paths: unknown[];
+  seq: number;
+}
+
+/** Favorites were pinned, unpinned or reordered. workspaceId is null for global pins and reordering */
+export interface FavoritesChangedEvent {
+  workspaceId?: string | null;
+  seq: number;
+}
+
+/** Bookmarks in a browser profile were added, changed, or removed */
+export interface BrowserBookmarksChangedEvent {
+  profile: string;
+  seq: number;
+}
+
+/** A browser view frame logged to the console or threw an uncaught error */
+export interface BrowserConsoleEvent {
+  id: number;
+  viewId: string;
+  windowId: number;
+  timestamp: string;
+  kind: string;
+  level: string;
+  message: string;
+  source?: string | null;
+  line?: number | null;
+  column?: number | null;
+  stack?: string | null;
+  frameId?: number | null;
+  frameUrl?: string | null;
+  frameOrigin?: string | null;
+  frameName?: string | null;
+  isMainFrame: boolean;
+  seq: number;
+}
+
+/** A browser view page tried to leave the navigation policy of its window and was stopped */
+export interface BrowserNavigationBlockedEvent {
+  id: string;
+  url: string;
+  host?: string | null;
+  reason: string;
+  disposition?: string | null;
+  seq: number;
+}
+
+/** Network or CPU throttling of a browser view was changed or ended */
+export interface BrowserThrottlingChangedEvent {
+  id: string;
+  network?: Record<string, unknown> | null;
+  cpuRate: number;
+  seq: number;
+}
+
+/** The automation bridge started or stopped, or a client attached to or detached from a browser view */
+export interface BrowserAutomationChangedEvent {
+  running: boolean;
+  port?: number | null;
+  endpoint?: string | null;
+  wsEndpoint?: string | null;
+  clients: number;
+  controlled: unknown[];
+  seq: number;
+}
+
+/** A user action in a recording browser view was captured */
+export interface BrowserActionRecordedEvent {
+  id: string;
+  index: number;
+  action: Record<string, unknown>;
+  seq: number;
+}
+
+/** The workspace mock server started, stopped, or reloaded its routes */
+export interface MockStatusEvent {
+  running: boolean;
+  root: string;
+  port?: number | null;
+  url?: string | null;
+  routes: unknown[];
+  error?: string | null;
+  requests: number;
+  seq: number;
+}
+
+/** The mock server answered a request */
+export interface MockRequestEvent {
+  root: string;
+  method: string;
+  path: string;
+  status: number;
+  routeIndex?: number | null;
+  duration: number;
+  seq: number;
+}
+
+/** A GraphQL subscription delivered a result */
+export interface GraphqlSubscriptionDataEvent {
+  subscriptionId: string;
+  data?: unknown | null;
+  errors?: unknown[] | null;
+  receivedAt: string;
+  seq: number;
+}
+
+/** A GraphQL subscription completed, failed, or was stopped */
+export interface GraphqlSubscriptionEndedEvent {
+  subscriptionId: string;
+  reason: string;
+  error?: string | null;
+  seq: number;
+}
+
+/** CI status of a watched repository changed (runs, overall state, or an error) */
+export interface CiStatusEvent {
+  repoRoot: string;
+  state: string;
+  branch?: string | null;
+  sha?: string | null;
+  runs: unknown[];
+  pullRequest?: Record<string, unknown> | null;
+  checkedAt: string;
+  error?: string | null;
+  seq: number;
+}
+
+/** New output from a followed CI job log */
+export interface CiLogEvent {
+  streamId: string;
+  jobId: string;
+  chunk: string;
+  seq: number;
+}
+
+/** A followed CI job log finished, failed, or was stopped */
+export interface CiLogEndedEvent {
+  streamId: string;
+  jobId: string;
+  status?: string | null;
+  error?: string | null;
+  seq: number;
+}
+
+/** A running notebook cell produced output (nbformat output shape) */
+export interface NotebookOutputEvent {
+  kernelId: string;
+  msgId: string;
+  cellId?: string | null;
+  output: Record<string, unknown>;
+  seq: number;
+}
+
+/** A notebook kernel changed state (starting, idle, busy, restarting or dead) */
+export interface NotebookKernelStatusEvent {
+  kernelId: string;
+  status: string;
+  message?: string | null;
+  seq: number;
+}
+
+/** Lines appended to a followed log file or source that pass the view's filters */
+export interface LogViewerLinesEvent {
+  viewId: string;
+  lines: unknown[];
+  skippedBytes?: number | null;
+  skippedEntries?: number | null;
+  seq: number;
+}
+
+/** A log view's file was rotated, truncated or went missing, its source stopped, or following paused or resumed */
+export interface LogViewerStatusEvent {
+  viewId: string;
+  status: string;
+  message?: string | null;
+  seq: number;
+}
+
+export interface ForgeEventMap {
+  'terminal:data': TerminalDataEvent;
+  'terminal:exit': TerminalExitEvent;
+  'fs:fileChanged': FsFileChangedEvent;
+  'browser:navigate': BrowserNavigateEvent;
+  'browser:titleUpdate': BrowserTitleUpdateEvent;
+  'browser:loadStart': BrowserLoadStartEvent;
+  'browser:loadStop': BrowserLoadStopEvent;
+  'browser:newTabRequest': BrowserNewTabRequestEvent;
+  'updater:status': UpdaterStatusEvent;
+  'app:recoveredFromError': AppRecoveredFromErrorEvent;
+  'job:started': JobStartedEvent;
+  'job:progress': JobProgressEvent;
+  'job:completed': JobCompletedEvent;
+  'trust:changed': TrustChangedEvent;
+  'refactor:applied': RefactorAppliedEvent;
+  'todos:updated': TodosUpdatedEvent;
+  'clipboard:changed': ClipboardChangedEvent;
+  'notification:clicked': NotificationClickedEvent;
+  'window:opened': WindowOpenedEvent;
+  'window:closed': WindowClosedEvent;
+  'terminal:attached': TerminalAttachedEvent;
+  'terminal:detached': TerminalDetachedEvent;
+  'editor:attached': EditorAttachedEvent;
+  'workbench:openFile': WorkbenchOpenFileEvent;
+  'workbench:openDiff': WorkbenchOpenDiffEvent;
+  'ai:token': AiTokenEvent;
+  'ai:done': AiDoneEvent;
+  'ai:error': AiErrorEvent;
+  'automation:recordingChanged': AutomationRecordingChangedEvent;
+  'watcher:health': WatcherHealthEvent;
+  'index:status': IndexStatusEvent;
+  'liveShare:viewers': LiveShareViewersEvent;
+  'liveShare:ended': LiveShareEndedEvent;
+  'collab:ops': CollabOpsEvent;
+  'collab:participants': CollabParticipantsEvent;
+  'collab:presence': CollabPresenceEvent;
+  'collab:diskChanged': CollabDiskChangedEvent;
+  'collab:ended': CollabEndedEvent;
+  'tunnel:status': TunnelStatusEvent;
+  'task:status': TaskStatusEvent;
+  'task:output': TaskOutputEvent;
+  'task:runFinished': TaskRunFinishedEvent;
+  'problems:changed': ProblemsChangedEvent;
+  'commandHistory:added': CommandHistoryAddedEvent;
+  'bookmarks:changed': BookmarksChangedEvent;
+  'terminal:image': TerminalImageEvent;
+  'terminal:mouseMode': TerminalMouseModeEvent;
+  'terminal:screenChanged': TerminalScreenChangedEvent;
+  'terminalAnnotations:changed': TerminalAnnotationsChangedEvent;
+  'watcher:changes': WatcherChangesEvent;
+  'saveHooks:completed': SaveHooksCompletedEvent;
+  'decorations:changed': DecorationsChangedEvent;
+  'favorites:changed': FavoritesChangedEvent;
+  'browser:bookmarksChanged': BrowserBookmarksChangedEvent;
+  'browser:console': BrowserConsoleEvent;
+  'browser:navigationBlocked': BrowserNavigationBlockedEvent;
+  'browser:throttlingChanged': BrowserThrottlingChangedEvent;
+  'browser:automationChanged': BrowserAutomationChangedEvent;
+  'browser:actionRecorded': BrowserActionRecordedEvent;
+  'mock:status': MockStatusEvent;
+  'mock:request': MockRequestEvent;
+  'graphql:subscriptionData': GraphqlSubscriptionDataEvent;
+  'graphql:subscriptionEnded': GraphqlSubscriptionEndedEvent;
+  'ci:status': CiStatusEvent;
+  'ci:log': CiLogEvent;
+  'ci:logEnded': CiLogEndedEvent;
+  'notebook:output': NotebookOutputEvent;
+  'notebook:kernelStatus': NotebookKernelStatusEvent;
+  'logViewer:lines': LogViewerLinesEvent;
+  'logViewer:status': LogViewerStatusEvent;
+}
+
+export type ForgeEventChannel = keyof ForgeEventMap;
+
+export interface ForgeEventEnvelope<C extends ForgeEventChannel = ForgeEventChannel> {
+  seq: number;
+  channel: C;
+  timestamp: number;
+  payload: Omit<ForgeEventMap[C], 'seq'>;
+}
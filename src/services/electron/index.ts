@@ -5,6 +5,11 @@ export * from './browser';
 export * from './store';
 export * from './system';
 export * from './window';
+export * from './errors';
+export type { ForgeEventMap, ForgeEventChannel, ForgeEventEnvelope } from './events.generated';
+
+import type { ForgeEventChannel, ForgeEventEnvelope, ForgeEventMap } from './events.generated';
+import type { ForgeErrorInfo } from './errors';
 
 // Type definitions for the Electron API
 export interface ElectronAPI {
@@ -12,9 +17,38 @@ export interface ElectronAPI {
     create: (options?: TerminalOptions) => Promise<TerminalInfo>;
     write: (id: string, data: Uint8Array) => Promise<boolean>;
     resize: (id: string, cols: number, rows: number) => Promise<boolean>;
+    // Types a cd command quoted for the terminal's shell; relative paths resolve against its cwd
+    cd: (id: string, path: string) => Promise<{ command: string; path: string }>;
+    // Commands reported by shell integration, oldest first
+    listCommands: (id: string) => Promise<TerminalCommand[]>;
+    // Sends the command's exact text again; `newTerminal` starts a terminal with its shell, cwd and env
+    rerunCommand: (id: string, commandId: number, options?: { newTerminal?: boolean }) => Promise<TerminalRerun>;
+    // Without an id, looks across all of this window's terminals
+    rerunLastFailed: (id?: string, options?: { newTerminal?: boolean }) => Promise<TerminalRerun>;
+    // Resolves false when mouse reporting is off or doesn't cover this event
+    sendMouse: (id: string, event: TerminalMouseEvent) => Promise<boolean>;
+    setMouseForwarding: (id: string, enabled: boolean) => Promise<TerminalMouseState>;
     close: (id: string) => Promise<boolean>;
+    list: () => Promise<TerminalInfo[]>;
+    transfer: (id: string, targetWindowId: number) => Promise<boolean>;
+    // 'all' (default) replays what the terminal shows, including a running full-screen
+    // program; 'normal' is the shell's scrollback only; 'alternate' the program's output only
+    getScrollback: (id: string, options?: { screen?: 'all' | 'normal' | 'alternate' }) => Promise<string>;
+    getMarks: (id: string) => Promise<TerminalMarks>;
+    // Nearest prompt above or below `line`, or null at either end
+    getJumpTarget: (id: string, line: number, direction: 'previous' | 'next') => Promise<TerminalMark | null>;
+    // Returns the saved log's name
+    saveLog: (id: string) => Promise<string>;
+    listLogs: () => Promise<TerminalLog[]>;
+    readLog: (name: string) => Promise<string>;
+    deleteLog: (name: string) => Promise<boolean>;
+    onAttached: (callback: (data: ForgeEventMap['terminal:attached']) => void) => () => void;
+    onDetached: (callback: (data: ForgeEventMap['terminal:detached']) => void) => () => void;
     onData: (callback: (data: TerminalData) => void) => void;
     onExit: (callback: (data: TerminalExit) => void) => void;
+    onImage: (callback: (image: TerminalImage) => void) => () => void;
+    onMouseMode: (callback: (data: ForgeEventMap['terminal:mouseMode']) => void) => () => void;
+    onScreenChanged: (callback: (data: ForgeEventMap['terminal:screenChanged']) => void) => () => void;
     getSessionInfo: (id: string) => Promise<TerminalSessionInfo>;
     getHistory: (id: string) => Promise<string[]>;
     getCwd: (id: string) => Promise<string>;
@@ -23,11 +57,19 @@ export interface ElectronAPI {
   fs: {
     readFile: (path: string) => Promise<string>;
     writeFile: (path: string, content: string) => Promise<boolean>;
+    // Runs the save hooks and returns what was written
+    save: (path: string, content: string) => Promise<SaveResult>;
+    saveWithElevation: (path: string, content: string) => Promise<ElevatedSaveResult>;
+    getWriteAccess: (path: string) => Promise<WriteAccess>;
     exists: (path: string) => Promise<boolean>;
     getInfo: (path: string) => Promise<FileInfo>;
+    detectFileType: (path: string) => Promise<FileTypeInfo>;
+    getFilesMetadata: (paths: string[]) => Promise<FileMetadataResult[]>;
+    existsBatch: (paths: string[]) => Promise<boolean[]>;
     createFile: (path: string, content?: string) => Promise<FileInfo>;
-    deleteFile: (path: string) => Promise<boolean>;
-    rename: (from: string, to: string) => Promise<FileInfo>;
+    // Protected paths are refused or fail with CONFIRMATION_REQUIRED; retry with its confirmationToken
+    deleteFile: (path: string, options?: GuardedOperationOptions) => Promise<boolean>;
+    rename: (from: string, to: string, options?: GuardedOperationOptions) => Promise<FileInfo>;
     copy: (from: string, to: string) => Promise<FileInfo>;
     listDirectory: (path: string) => Promise<FileInfo[]>;
     watchFile: (path: string) => Promise<boolean>;
@@ -46,7 +88,7 @@ export interface ElectronAPI {
     setBounds: (id: string, bounds: BrowserBounds) => Promise<boolean>;
     show: (id: string) => Promise<boolean>;
     hide: (id: string) => Promise<boolean>;
-    openDevTools: (id: string) => Promise<boolean>;
+    openDevTools: (id: string, options?: { mode?: BrowserDevToolsMode }) => Promise<boolean>;
     closeDevTools: (id: string) => Promise<boolean>;
     captureScreenshot: (id: string) => Promise<string>;
     captureRegion: (id: string, rect: { x: number; y: number; width: number; height: number }) => Promise<string>;
@@ -58,6 +100,67 @@ export interface ElectronAPI {
     getTitle: (id: string) => Promise<string>;
     canGoBack: (id: string) => Promise<boolean>;
     canGoForward: (id: string) => Promise<boolean>;
+    // What new tabs open in this window's workspace: its own start page, else forge://start
+    getStartPage: (options?: BrowserProfileOptions) => Promise<{ url: string; generated: boolean }>;
+    // null goes back to the generated page
+    setStartPage: (url: string | null) => Promise<string>;
+    // What forge://start lists, for rendering it natively
+    getStartPageData: (options?: BrowserProfileOptions) => Promise<StartPageData>;
+    // Address bar suggestions from history and bookmarks
+    autocomplete: (query: string, options?: BrowserProfileOptions & { limit?: number }) => Promise<BrowserAutocomplete>;
+    // What address bar text resolves to, with suggestions from dev servers, history and bookmarks
+    resolveInput: (text: string, options?: BrowserProfileOptions & { workspace?: string; limit?: number }) => Promise<BrowserInputResolution>;
+    searchHistory: (query: string, options?: BrowserProfileOptions & { limit?: number }) => Promise<BrowserHistoryEntry[]>;
+    // Most recent first; `before` (ISO time) pages through older entries
+    listHistory: (options?: BrowserProfileOptions & { workspace?: string; before?: string; limit?: number }) => Promise<BrowserHistoryEntry[]>;
+    // One URL, a { from, to } range of ISO times, or everything; returns how many were removed
+    deleteHistory: (options?: BrowserProfileOptions & { url?: string; from?: string; to?: string }) => Promise<number>;
+    // `folder` null lists unfiled bookmarks
+    listBookmarks: (options?: BrowserProfileOptions & { folder?: string | null }) => Promise<BrowserBookmark[]>;
+    // Bookmarking a URL twice updates the existing bookmark
+    addBookmark: (bookmark: { url: string; title?: string; folder?: string | null }, options?: BrowserProfileOptions) => Promise<BrowserBookmark>;
+    updateBookmark: (id: string, changes: { title?: string; folder?: string | null }, options?: BrowserProfileOptions) => Promise<BrowserBookmark>;
+    removeBookmark: (id: string, options?: BrowserProfileOptions) => Promise<boolean>;
+    onBookmarksChanged: (callback: (data: ForgeEventMap['browser:bookmarksChanged']) => void) => () => void;
+    // Console output and uncaught errors from every frame, oldest first
+    getConsole: (id: string, filters?: BrowserConsoleFilters) => Promise<BrowserConsoleEntry[]>;
+    listFrames: (id: string) => Promise<(BrowserFrame & { parentId: number | null; hooked: boolean })[]>;
+    clearConsole: (id: string) => Promise<boolean>;
+    onConsole: (callback: (data: ForgeEventMap['browser:console']) => void) => () => void;
+    // Views open when the calling window's workspace was last used; recreate each with create(view)
+    getSession: () => Promise<BrowserSession>;
+    clearSession: () => Promise<boolean>;
+    // Per-view network and CPU emulation, e.g. previewing on "Slow 3G"
+    listNetworkProfiles: () => Promise<{ network: (BrowserNetworkConditions & { id: string })[]; cpuRates: number[] }>;
+    getThrottling: (id: string) => Promise<BrowserThrottling>;
+    // A preset id, custom conditions, or null/'none' for no throttling
+    setNetworkConditions: (id: string, profile: string | Partial<BrowserNetworkConditions> | null) => Promise<BrowserThrottling>;
+    // Slowdown factor; 1 turns CPU throttling off
+    setCpuThrottling: (id: string, rate: number) => Promise<BrowserThrottling>;
+    onThrottlingChanged: (callback: (data: ForgeEventMap['browser:throttlingChanged']) => void) => () => void;
+    // Record clicks, typing and navigations in a view as a replayable script
+    startActionRecording: (id: string) => Promise<{ id: string; startedAt: string; startUrl: string }>;
+    stopActionRecording: (id: string) => Promise<BrowserActionScript>;
+    getActionRecording: (id: string) => Promise<{ recording: boolean; startUrl: string | null; actions: BrowserAction[] }>;
+    // Stops at the first action that fails; `timeout` per element lookup and `delay` between actions, in ms
+    replayActions: (id: string, script: BrowserActionScript, options?: { timeout?: number; delay?: number }) => Promise<BrowserReplayResult>;
+    exportPlaywright: (script: BrowserActionScript) => Promise<string>;
+    saveActionScript: (script: BrowserActionScript, filePath: string, format?: 'json' | 'playwright') => Promise<string>;
+    loadActionScript: (filePath: string) => Promise<BrowserActionScript>;
+    onActionRecorded: (callback: (data: ForgeEventMap['browser:actionRecorded']) => void) => () => void;
+    // CDP endpoint on loopback for this window's previews, for Playwright (connectOverCDP(endpoint))
+    // or Puppeteer (connect({ browserWSEndpoint: wsEndpoint })); port 0 picks a free one
+    startAutomationBridge: (options?: { port?: number }) => Promise<AutomationBridgeStatus>;
+    stopAutomationBridge: () => Promise<boolean>;
+    getAutomationBridge: () => Promise<AutomationBridgeStatus>;
+    onAutomationChanged: (callback: (data: ForgeEventMap['browser:automationChanged']) => void) => () => void;
+    // Applies to the calling window; `saveAsDefault` also applies it to new windows
+    getNavigationPolicy: () => Promise<BrowserNavigationPolicy>;
+    setNavigationPolicy: (policy: BrowserNavigationPolicy, options?: { saveAsDefault?: boolean }) => Promise<BrowserNavigationPolicy>;
+    allowHost: (host: string) => Promise<BrowserNavigationPolicy>;
+    // Follow a blocked navigation in the system browser or in the preview itself
+    openBlocked: (id: string, url: string, target?: 'external' | 'here') => Promise<boolean>;
+    onNavigationBlocked: (callback: (data: ForgeEventMap['browser:navigationBlocked']) => void) => () => void;
     onNavigate: (callback: (data: BrowserNavigateEvent) => void) => void;
     onTitleUpdate: (callback: (data: BrowserTitleEvent) => void) => void;
     onLoadStart: (callback: (data: BrowserLoadEvent) => void) => void;
@@ -67,7 +170,13 @@ export interface ElectronAPI {
   editor: {
     saveSession: (sessionData: EditorSession) => Promise<string>;
     loadSession: (sessionId: string) => Promise<EditorSession>;
-    listSessions: () => Promise<EditorSession[]>;
+    // Defaults to the calling window's workspace
+    listSessions: (allWorkspaces?: boolean) => Promise<EditorSession[]>;
+    // Folds saved for the file, or [] once its content no longer matches. `content` is the
+    // editor's text; the file on disk is used when omitted.
+    getFoldState: (path: string, content?: string) => Promise<FoldRegion[]>;
+    // An empty list forgets the file; resolves the number of folds stored
+    saveFoldState: (path: string, folds: FoldRegion[], content?: string) => Promise<number>;
   };
   store: {
     get: (key: string) => Promise<any>;
@@ -88,6 +197,672 @@ export interface ElectronAPI {
     isMaximized: () => Promise<boolean>;
     setAlwaysOnTop: (flag: boolean) => Promise<boolean>;
   };
+  commands: {
+    list: (context?: CommandContext) => Promise<CommandInfo[]>;
+    execute: (id: string, args?: unknown[], context?: CommandContext) => Promise<unknown>;
+    setContext: (key: string, value: unknown) => Promise<boolean>;
+    onInvoke: (callback: (data: CommandInvokeEvent) => void) => () => void;
+  };
+  logs: {
+    getRecent: (options?: LogQuery) => Promise<LogEntry[]>;
+    setLevel: (module: string, level: LogLevel) => Promise<LogLevels>;
+    getLevels: () => Promise<LogLevels>;
+    exportDiagnostics: (targetPath?: string) => Promise<string | null>;
+  };
+  crash: {
+    list: () => Promise<CrashReportSummary[]>;
+    get: (id: string) => Promise<CrashReport>;
+    delete: (id: string) => Promise<boolean>;
+    getSettings: () => Promise<CrashReportingSettings>;
+    setUploadEnabled: (enabled: boolean, endpoint?: string | null) => Promise<CrashReportingSettings>;
+    upload: (id: string) => Promise<boolean>;
+    reportRendererError: (error: { message: string; stack?: string; componentStack?: string }) => Promise<string>;
+    onRecovered: (callback: (data: RecoveredFromErrorEvent) => void) => () => void;
+  };
+  telemetry: {
+    getSettings: () => Promise<TelemetrySettings>;
+    setEnabled: (enabled: boolean) => Promise<TelemetrySettings>;
+    setUploadEnabled: (enabled: boolean, endpoint?: string | null) => Promise<TelemetrySettings>;
+    record: (name: string, properties?: Record<string, string | number | boolean>) => Promise<boolean>;
+    view: () => Promise<TelemetryPayload>;
+    clear: () => Promise<boolean>;
+    uploadNow: () => Promise<{ uploaded: number; skipped: boolean }>;
+  };
+  trust: {
+    get: (path: string) => Promise<WorkspaceTrustInfo>;
+    set: (path: string, trusted: boolean) => Promise<WorkspaceTrust>;
+    remove: (path: string) => Promise<boolean>;
+    list: () => Promise<{ path: string; trusted: boolean }[]>;
+    onChanged: (callback: (data: WorkspaceTrust) => void) => () => void;
+  };
+  onboarding: {
+    getState: () => Promise<OnboardingState>;
+    completeStep: (step: string) => Promise<OnboardingState>;
+    skip: () => Promise<OnboardingState>;
+    reset: () => Promise<OnboardingState>;
+  };
+  events: {
+    getCatalog: () => Promise<Record<string, EventCatalogEntry>>;
+    replay: (sinceSeq?: number, channels?: ForgeEventChannel[]) => Promise<ForgeEventEnvelope[]>;
+    getLastSeq: () => Promise<number>;
+  };
+  jobs: {
+    list: (includeFinished?: boolean) => Promise<JobInfo[]>;
+    get: (id: string) => Promise<JobInfo>;
+    cancel: (id: string) => Promise<boolean>;
+    onStarted: (callback: (data: ForgeEventMap['job:started']) => void) => () => void;
+    onProgress: (callback: (data: ForgeEventMap['job:progress']) => void) => () => void;
+    onCompleted: (callback: (data: ForgeEventMap['job:completed']) => void) => () => void;
+  };
+  lsp: {
+    listServers: () => Promise<LanguageServerInfo[]>;
+    shutdown: () => Promise<boolean>;
+  };
+  refactor: {
+    // Guarded like replaceInProject, over the files the language server's edit touches
+    renameSymbol: (options: RenameSymbolOptions) => Promise<WorkspaceEditResult>;
+    // Guarded like replaceInProject; paths outside the allowed directories are refused
+    applyWorkspaceEdit: (workspaceEdit: LspWorkspaceEdit, openBuffers?: Record<string, string>, description?: string, options?: GuardedOperationOptions) => Promise<WorkspaceEditResult>;
+    // Guarded like deleteFile; retry with the confirmationToken from a CONFIRMATION_REQUIRED error
+    replaceInProject: (request: ReplaceInProjectRequest) => Promise<ReplaceInProjectResult>;
+    undo: (journalId: string) => Promise<{ files: EditedFile[] }>;
+    listJournal: () => Promise<RefactorJournalEntry[]>;
+    onApplied: (callback: (data: ForgeEventMap['refactor:applied']) => void) => () => void;
+  };
+  snippets: {
+    list: (language?: string) => Promise<Snippet[]>;
+    get: (id: string) => Promise<SnippetDetails>;
+    create: (input: SnippetInput) => Promise<Snippet>;
+    update: (id: string, changes: Partial<SnippetInput>, expectedRevision?: number) => Promise<Snippet>;
+    delete: (id: string) => Promise<boolean>;
+    importVSCode: (filePath: string, language?: string) => Promise<SnippetImportResult>;
+    lookup: (language: string, typed: string, context?: SnippetContext) => Promise<SnippetCompletion[]>;
+    resolve: (id: string, context?: SnippetContext) => Promise<string>;
+  };
+  search: {
+    text: (rootPath: string, query: string, options?: TextSearchOptions) => Promise<TextSearchResponse>;
+  };
+  todos: {
+    index: (rootPath: string) => Promise<{ rootPath: string; jobId: string | null }>;
+    stop: (rootPath: string) => Promise<boolean>;
+    list: (rootPath: string, filter?: TodoFilter) => Promise<TodoItem[]>;
+    getTags: () => Promise<string[]>;
+    setTags: (tags: string[]) => Promise<string[]>;
+    onUpdated: (callback: (data: ForgeEventMap['todos:updated']) => void) => () => void;
+  };
+  editorconfig: {
+    resolve: (filePath: string) => Promise<EditorConfigSettings>;
+    getApplyOnSave: () => Promise<boolean>;
+    setApplyOnSave: (enabled: boolean) => Promise<boolean>;
+  };
+  clipboard: {
+    getHistory: (query?: string, limit?: number) => Promise<ClipboardHistoryItem[]>;
+    pasteHistoryItem: (id: string, options?: { paste?: boolean }) => Promise<ClipboardHistoryItem>;
+    pin: (id: string, pinned: boolean) => Promise<ClipboardHistoryItem>;
+    remove: (id: string) => Promise<boolean>;
+    clear: (includePinned?: boolean) => Promise<boolean>;
+    writeSensitive: (text: string, clearAfterMs?: number) => Promise<boolean>;
+    getSettings: () => Promise<ClipboardSettings>;
+    updateSettings: (changes: Partial<ClipboardSettings>) => Promise<ClipboardSettings>;
+    onChanged: (callback: (data: ForgeEventMap['clipboard:changed']) => void) => () => void;
+  };
+  notifications: {
+    show: (options: NativeNotificationOptions) => Promise<{ id: string; suppressed: boolean }>;
+    notifyWhenCommandFinishes: (terminalId: string, options?: { label?: string; always?: boolean }) => Promise<boolean>;
+    cancelCommandWatch: (terminalId: string) => Promise<boolean>;
+    getDoNotDisturb: () => Promise<DoNotDisturbState>;
+    setDoNotDisturb: (changes: Partial<Omit<DoNotDisturbState, 'active'>>) => Promise<DoNotDisturbState>;
+    getMissed: (clear?: boolean) => Promise<MissedNotification[]>;
+    addReminder: (title: string, at: string, options?: { body?: string; urgent?: boolean }) => Promise<Reminder>;
+    listReminders: () => Promise<Reminder[]>;
+    cancelReminder: (id: string) => Promise<boolean>;
+    onClicked: (callback: (data: ForgeEventMap['notification:clicked']) => void) => () => void;
+  };
+  fileIndex: {
+    open: (rootPath: string) => Promise<FileIndexStatus>;
+    close: (rootPath: string) => Promise<boolean>;
+    query: (rootPath: string, query: string, limit?: number) => Promise<FileMatch[]>;
+    getStatus: (rootPath: string) => Promise<FileIndexStatus>;
+    // Discards the persisted index (files and symbols) and rescans
+    rebuild: (rootPath: string) => Promise<FileIndexStatus>;
+    // Both the file and symbol indexes report here; `index` says which
+    onStatus: (callback: (data: ForgeEventMap['index:status']) => void) => () => void;
+  };
+  symbols: {
+    query: (rootPath: string, query: string, limit?: number) => Promise<SymbolMatch[]>;
+    getFileSymbols: (filePath: string) => Promise<WorkspaceSymbol[]>;
+    getStatus: (rootPath: string) => Promise<SymbolIndexStatus>;
+  };
+  quickOpen: {
+    query: (query: string, options?: QuickOpenOptions) => Promise<QuickOpenResponse>;
+  };
+  windows: {
+    open: (workspacePath?: string, options?: { allowDuplicate?: boolean }) => Promise<WindowContext>;
+    list: () => Promise<WindowSummary[]>;
+    getContext: () => Promise<WindowContext>;
+    setWorkspace: (workspacePath: string | null) => Promise<WindowContext>;
+    // Omit targetWindowId to move into a new window
+    moveTerminal: (terminalId: string, targetWindowId?: number) => Promise<{ terminalId: string; windowId: number }>;
+    moveEditor: (editorState: TransferredEditor, targetWindowId?: number) => Promise<{ windowId: number; moved: boolean }>;
+    onEditorAttached: (callback: (data: ForgeEventMap['editor:attached']) => void) => () => void;
+    focus: (windowId: number) => Promise<boolean>;
+    onOpened: (callback: (data: ForgeEventMap['window:opened']) => void) => () => void;
+    onClosed: (callback: (data: ForgeEventMap['window:closed']) => void) => () => void;
+  };
+  workbench: {
+    openPaths: (targets: OpenTarget[], options?: { newWindow?: boolean; windowId?: number }) => Promise<OpenedPath[]>;
+    // Report a closed editor so `forge --wait` callers can return
+    documentClosed: (filePath: string, waitId?: string) => Promise<boolean>;
+    onOpenFile: (callback: (data: ForgeEventMap['workbench:openFile']) => void) => () => void;
+    onOpenDiff: (callback: (data: ForgeEventMap['workbench:openDiff']) => void) => () => void;
+  };
+  fileAssociations: {
+    getStatus: () => Promise<FileAssociationStatus>;
+    // Windows and Linux only; macOS associations come from the installed app bundle
+    register: () => Promise<FileAssociationStatus>;
+    unregister: () => Promise<FileAssociationStatus>;
+  };
+  dragDrop: {
+    getPathForFile: (file: File) => string;
+    drop: (drop: DropRequest) => Promise<DropResult>;
+    getSettings: () => Promise<DragDropSettings>;
+    updateSettings: (changes: Partial<{ [K in keyof DragDropSettings]: Partial<DragDropSettings[K]> }>) => Promise<DragDropSettings>;
+  };
+  secrets: {
+    set: (name: string, value: string) => Promise<boolean>;
+    delete: (name: string) => Promise<boolean>;
+    list: () => Promise<SecretInfo[]>;
+    has: (name: string) => Promise<boolean>;
+  };
+  ai: {
+    // Starts streaming; text arrives through onToken and ends with onDone or onError
+    chat: (request: AiChatRequest) => Promise<{ requestId: string }>;
+    cancel: (requestId: string) => Promise<boolean>;
+    listProviders: () => Promise<AiProvider[]>;
+    saveProvider: (provider: AiProviderConfig) => Promise<AiProviderConfig>;
+    deleteProvider: (providerId: string) => Promise<boolean>;
+    setDefaultProvider: (providerId: string) => Promise<boolean>;
+    listModels: (providerId?: string) => Promise<string[]>;
+    onToken: (callback: (data: ForgeEventMap['ai:token']) => void) => () => void;
+    onDone: (callback: (data: ForgeEventMap['ai:done']) => void) => () => void;
+    onError: (callback: (data: ForgeEventMap['ai:error']) => void) => () => void;
+  };
+  terminalAi: {
+    explain: (text: string, options?: TerminalAiOptions) => Promise<CommandExplanation>;
+    // Proposes a command only; call insert after the user accepts it
+    generate: (request: string, options?: TerminalAiOptions) => Promise<GeneratedCommand>;
+    classify: (command: string) => Promise<CommandSafety>;
+    insert: (terminalId: string, command: string, options?: { confirmed?: boolean }) => Promise<boolean>;
+  };
+  completion: {
+    // Debounced per document; a newer request resolves older ones with cancelled: true
+    request: (request: CompletionRequest) => Promise<CompletionResult>;
+    cancel: (filePath: string) => Promise<boolean>;
+    getSettings: () => Promise<CompletionSettings>;
+    updateSettings: (changes: Partial<CompletionSettings>) => Promise<CompletionSettings>;
+    getStats: () => Promise<CompletionStats>;
+    clearCache: () => Promise<boolean>;
+  };
+  embeddings: {
+    // Runs as a background job; unchanged chunks reuse their stored vectors
+    index: (rootPath: string, options?: { rebuild?: boolean }) => Promise<{ jobId: string; files: number }>;
+    search: (rootPath: string, query: string, options?: { limit?: number; minScore?: number }) => Promise<SemanticSearchResult[]>;
+    getStatus: (rootPath: string) => Promise<EmbeddingIndexStatus>;
+    clear: (rootPath: string) => Promise<boolean>;
+    getSettings: () => Promise<EmbeddingSettings>;
+    updateSettings: (changes: Partial<EmbeddingSettings>) => Promise<EmbeddingSettings>;
+  };
+  gitAi: {
+    // Drafts from the staged diff; the caller decides whether to commit
+    generateCommitMessage: (cwd: string, overrides?: Partial<GitAiRules>) => Promise<CommitMessageDraft>;
+    generatePrDescription: (cwd: string, options?: { base?: string; rules?: Partial<GitAiRules> }) => Promise<PrDescriptionDraft>;
+    getRules: () => Promise<GitAiRules>;
+    updateRules: (changes: Partial<GitAiRules>) => Promise<GitAiRules>;
+  };
+  automation: {
+    startRecording: (name: string) => Promise<boolean>;
+    // Saves the recording as a macro unless save is false; null when nothing was recorded
+    stopRecording: (save?: boolean) => Promise<Macro | null>;
+    recordAction: (action: MacroAction) => Promise<boolean>;
+    getRecording: () => Promise<{ name: string; actions: MacroAction[] } | null>;
+    listMacros: () => Promise<Macro[]>;
+    saveMacro: (name: string, actions: MacroAction[]) => Promise<Macro>;
+    deleteMacro: (name: string) => Promise<boolean>;
+    playMacro: (name: string) => Promise<{ jobId: string }>;
+    listScripts: () => Promise<AutomationScript[]>;
+    saveScript: (name: string, source: string) => Promise<AutomationScript>;
+    deleteScript: (name: string) => Promise<boolean>;
+    runScript: (source: string) => Promise<{ result: unknown; output: string[] }>;
+    onRecordingChanged: (callback: (data: ForgeEventMap['automation:recordingChanged']) => void) => () => void;
+  };
+  watcher: {
+    getHealth: () => Promise<WatcherHealth[]>;
+    // Rescan every watcher, or only those rooted at `root`; resolves with the number rescanned
+    rescan: (root?: string) => Promise<number>;
+    // Changes under the workspace matching `globs` ("src/**/*.ts"; no slash matches at any
+    // depth) arrive through onChanges; reusing an id replaces its interests. All kinds by default.
+    watchPaths: (subscriptionId: string, globs: string[], eventKinds?: WatchEventKind[]) => Promise<WatchSubscription>;
+    unwatch: (subscriptionId: string) => Promise<boolean>;
+    listSubscriptions: () => Promise<WatchSubscription[]>;
+    getStats: () => Promise<WatchHubRoot[]>;
+    onHealth: (callback: (data: ForgeEventMap['watcher:health']) => void) => () => void;
+    // Batched per subscription; with `overflow` the changes were dropped and the view should reload
+    onChanges: (callback: (data: ForgeEventMap['watcher:changes']) => void) => () => void;
+  };
+  indexScope: {
+    get: (rootPath: string) => Promise<IndexScope>;
+    // Replaces the given lists; indexes and watchers for the workspace rebuild afterwards
+    update: (rootPath: string, changes: Partial<Pick<IndexScope, 'include' | 'exclude'>>) => Promise<IndexScope>;
+    isIgnored: (rootPath: string, targetPath: string, type?: 'file' | 'directory') => Promise<boolean>;
+  };
+  workspaceState: {
+    // Writes a .zip or .json bundle (by extension); prompts for a location when `path` is omitted
+    export: (options?: WorkspaceStateExportOptions) => Promise<{ path: string; sections: WorkspaceStateSection[] } | null>;
+    inspect: (path?: string) => Promise<WorkspaceStateSummary | null>;
+    import: (path: string, options?: WorkspaceStateImportOptions) => Promise<WorkspaceStateImportResult>;
+  };
+  guardrails: {
+    getPolicy: () => Promise<GuardrailPolicy>;
+    updatePolicy: (changes: Partial<GuardrailPolicy>) => Promise<GuardrailPolicy>;
+    resetPolicy: () => Promise<GuardrailPolicy>;
+    check: (operation: GuardedOperation, paths: string[], workspacePath?: string) => Promise<GuardrailCheck>;
+  };
+  encryption: {
+    getStatus: () => Promise<EncryptionStatus>;
+    // Existing data is re-encrypted in a background job unless options.migrate is false
+    configure: (changes: EncryptionChanges, options?: { migrate?: boolean }) => Promise<EncryptionStatus & { migrationJobId: string | null }>;
+    migrate: (categories?: EncryptedDataCategory[]) => Promise<{ jobId: string }>;
+  };
+  liveShare: {
+    start: (options: LiveShareOptions) => Promise<LiveShareSession>;
+    stop: (sessionId: string) => Promise<boolean>;
+    list: () => Promise<LiveShareSession[]>;
+    // Push unsaved content of a shared file; false if the file isn't part of the session
+    updateFile: (sessionId: string, path: string, content: string) => Promise<boolean>;
+    onViewers: (callback: (data: ForgeEventMap['liveShare:viewers']) => void) => () => void;
+    onEnded: (callback: (data: ForgeEventMap['liveShare:ended']) => void) => () => void;
+  };
+  collab: {
+    start: (options: CollabStartOptions) => Promise<CollabSession>;
+    stop: (sessionId: string) => Promise<boolean>;
+    getSession: (sessionId: string) => Promise<CollabSession>;
+    // Snapshot for the host editor's replica (client id "host")
+    getDocument: (sessionId: string, path: string) => Promise<CollabDocument>;
+    // Resolves the plain-text changes the ops made to the shared copy
+    applyOps: (sessionId: string, path: string, ops: CrdtOp[]) => Promise<TextChange[]>;
+    setPresence: (sessionId: string, path: string, selection: unknown) => Promise<boolean>;
+    save: (sessionId: string, path: string) => Promise<boolean>;
+    reload: (sessionId: string, path: string) => Promise<{ text: string }>;
+    onOps: (callback: (data: ForgeEventMap['collab:ops']) => void) => () => void;
+    onParticipants: (callback: (data: ForgeEventMap['collab:participants']) => void) => () => void;
+    onPresence: (callback: (data: ForgeEventMap['collab:presence']) => void) => () => void;
+    onDiskChanged: (callback: (data: ForgeEventMap['collab:diskChanged']) => void) => () => void;
+    onEnded: (callback: (data: ForgeEventMap['collab:ended']) => void) => () => void;
+  };
+  tunnel: {
+    getSettings: () => Promise<TunnelSettings>;
+    updateSettings: (changes: Partial<TunnelSettings>) => Promise<TunnelSettings>;
+    // Resolves once the provider reports a public URL; rejects CANCELLED if the user declines
+    start: (options: { port: number; host?: string; provider?: TunnelProvider }) => Promise<TunnelInfo>;
+    stop: (id: string) => Promise<boolean>;
+    list: () => Promise<TunnelInfo[]>;
+    getOutput: (id: string) => Promise<string | null>;
+    onStatus: (callback: (data: ForgeEventMap['tunnel:status']) => void) => () => void;
+  };
+  mock: {
+    // Serves the workspace's .forge/mocks.json on loopback; port 0 picks a free one
+    start: (options?: { port?: number }) => Promise<MockServerStatus>;
+    stop: () => Promise<boolean>;
+    getStatus: () => Promise<MockServerStatus>;
+    // Writes a starter file unless one exists; resolves its path
+    createConfig: () => Promise<string>;
+    onStatus: (callback: (data: ForgeEventMap['mock:status']) => void) => () => void;
+    onRequest: (callback: (data: ForgeEventMap['mock:request']) => void) => () => void;
+  };
+  openApi: {
+    // JSON and YAML files in the workspace with a top-level openapi or swagger version
+    findSpecs: () => Promise<{ file: string; relativePath: string; version: string }[]>;
+    // Problems are also published to the problems panel under the "openapi" source
+    validate: (filePath: string) => Promise<OpenApiValidation>;
+    listOperations: (filePath: string) => Promise<OpenApiOperationList>;
+    // `operation` is an operationId or "METHOD /path"
+    generateRequest: (filePath: string, operation: string, options?: OpenApiRequestOptions) => Promise<OpenApiRequest>;
+    // Serves the spec's example responses through the workspace mock server
+    startMock: (filePath: string, options?: { port?: number }) => Promise<MockServerStatus>;
+  };
+  graphql: {
+    listEndpoints: () => Promise<GraphQLEndpoint[]>;
+    // Creates an endpoint, or updates the one with `id`
+    saveEndpoint: (endpoint: GraphQLEndpointInput & { id?: string }) => Promise<GraphQLEndpoint>;
+    deleteEndpoint: (id: string) => Promise<boolean>;
+    // Introspects on first use and caches on disk; `refresh` introspects again.
+    // `schema` is the introspection result's __schema.
+    getSchema: (endpointId: string, options?: { refresh?: boolean }) => Promise<{ endpointId: string; fetchedAt: string; schema: unknown }>;
+    // Syntax only until the endpoint's schema has been fetched
+    validate: (endpointId: string | null, query: string) => Promise<{ checkedAgainstSchema: boolean; problems: GraphQLProblem[] }>;
+    complete: (endpointId: string, query: string, offset: number) => Promise<{ from: number; items: GraphQLCompletionItem[] }>;
+    execute: (endpointId: string, request: GraphQLRequest) => Promise<GraphQLResult>;
+    subscribe: (endpointId: string, request: GraphQLRequest) => Promise<{ subscriptionId: string; protocol: 'graphql-transport-ws' | 'graphql-ws' }>;
+    unsubscribe: (subscriptionId: string) => Promise<boolean>;
+    listCollections: () => Promise<GraphQLCollection[]>;
+    saveCollection: (collection: { id?: string; name: string }) => Promise<GraphQLCollection>;
+    deleteCollection: (id: string) => Promise<boolean>;
+    saveRequest: (collectionId: string, request: GraphQLSavedRequestInput & { id?: string }) => Promise<GraphQLSavedRequest>;
+    deleteRequest: (collectionId: string, requestId: string) => Promise<boolean>;
+    onSubscriptionData: (callback: (data: ForgeEventMap['graphql:subscriptionData']) => void) => () => void;
+    onSubscriptionEnded: (callback: (data: ForgeEventMap['graphql:subscriptionEnded']) => void) => () => void;
+  };
+  jwt: {
+    // Accepts a raw token or an "Authorization: Bearer ..." value
+    decode: (token: string) => Promise<DecodedJwt>;
+    verify: (token: string, options: JwtVerifyOptions) => Promise<JwtVerification>;
+    find: (text: string) => Promise<FoundJwt[]>;
+    // Tokens in a browser preview's cookies, localStorage and sessionStorage
+    findInBrowser: (viewId: string) => Promise<{ url: string; tokens: FoundJwt[] }>;
+  };
+  utilities: {
+    // File inputs stream and run as cancellable jobs; pass outputPath for results too large
+    // to return as text
+    transform: (operation: UtilityTransform, input: UtilityInput, options?: UtilityTransformOptions) => Promise<UtilityTransformResult>;
+    // Scans without building the document, so large files are fine
+    validateJson: (input: UtilityInput) => Promise<{ valid: boolean; error: { message: string; line: number; column: number; offset: number } | null }>;
+    hash: (input: UtilityInput, options?: { algorithms?: HashAlgorithm[]; encoding?: 'hex' | 'base64' }) => Promise<{ bytes: number; digests: Partial<Record<HashAlgorithm, string>> }>;
+    uuid: (options?: { count?: number; version?: 4 | 7; uppercase?: boolean }) => Promise<string[]>;
+    // Five fields, or six with leading seconds; timezone defaults to UTC
+    explainCron: (expression: string, options?: { count?: number; timezone?: string; from?: number }) => Promise<CronExplanation>;
+    // Epoch numbers in any unit (detected by magnitude unless `unit` is given), ISO-8601 or
+    // RFC 2822 dates, and relative phrases like "3 days ago"
+    convertTimestamp: (input: string | number, options?: { timezone?: string; unit?: EpochUnit }) => Promise<TimestampConversion>;
+    listTimezones: () => Promise<string[]>;
+  };
+  css: {
+    // Any CSS color: hex, rgb(), hsl(), hwb(), oklab(), oklch(), color(srgb ...) or a name
+    convertColor: (color: string) => Promise<ColorInfo>;
+    // A translucent background is painted over `backdrop` (white by default)
+    checkContrast: (foreground: string, background: string, options?: { backdrop?: string; large?: boolean }) => Promise<ContrastResult>;
+    // For inline swatches; named colors only with `names`
+    findColors: (text: string, options?: { names?: boolean }) => Promise<FoundColor[]>;
+    // "1.5rem", or a number with `from`; relative units resolve against the context
+    convertUnit: (input: string | number, options?: CssUnitContext & { from?: string; to?: string }) => Promise<CssUnitConversion>;
+    // Checks every element with its own text in a browser preview against WCAG 2
+    auditContrast: (viewId: string, options?: { level?: 'AA' | 'AAA' }) => Promise<ContrastAudit>;
+  };
+  stats: {
+    // Runs as a job; unchanged files are served from the per-file cache
+    countLinesOfCode: (rootPath: string, options?: { exclude?: string[]; respectGitignore?: boolean }) => Promise<CodeStats>;
+  };
+  licenses: {
+    // Runs as a job over the installed npm, Cargo and Python dependencies
+    scan: (rootPath: string, options?: { ecosystems?: LicenseEcosystem[] }) => Promise<LicenseScan>;
+    // The workspace's .forge/licenses.json, or the user policy when it has none
+    getPolicy: (rootPath: string) => Promise<LicensePolicy & { source: 'workspace' | 'user'; path: string | null }>;
+    setUserPolicy: (policy: Partial<LicensePolicy>) => Promise<LicensePolicy>;
+  };
+  forgeRemote: {
+    // hasToken tells whether the github.token / gitlab.token secret is set
+    get: (repoRoot: string, remoteName?: string) => Promise<ForgeRemote & { hasToken: boolean }>;
+    // For self-hosted instances whose host name doesn't say; null forgets the host
+    setHostKind: (host: string, kind: ForgeKind | null) => Promise<Record<string, ForgeKind>>;
+  };
+  release: {
+    // Since the last tag matching `${prefix}<version>` (prefix defaults to "v")
+    changelog: (rootPath: string, options?: ReleaseVersionOptions & { includeAll?: boolean; from?: string; remote?: string }) => Promise<ChangelogSection>;
+    // Prepends below the title of CHANGELOG.md (or `file`); returns the file's path
+    writeChangelog: (rootPath: string, markdown: string, file?: string) => Promise<string>;
+    bumpVersions: (rootPath: string, options: { version?: string; bump?: VersionBump; dryRun?: boolean }) => Promise<VersionBumpResult>;
+    createTag: (rootPath: string, options: { version?: string; tag?: string; prefix?: string; message?: string; push?: boolean; remote?: string }) => Promise<{ tag: string; commit: string; pushed: boolean }>;
+    // GitHub only
+    draft: (rootPath: string, options: { tag: string; name?: string; body?: string; prerelease?: boolean; remote?: string }) => Promise<DraftRelease>;
+    // Changelog, bumps, release commit and tag in one step
+    prepare: (rootPath: string, options?: ReleaseVersionOptions & { includeAll?: boolean; changelogFile?: string; push?: boolean; draft?: boolean; remote?: string; dryRun?: boolean }) => Promise<PreparedRelease>;
+  };
+  ci: {
+    getStatus: (rootPath: string, options?: { remote?: string }) => Promise<CiStatus>;
+    getJobs: (rootPath: string, runId: string, options?: { remote?: string }) => Promise<CiJob[]>;
+    // Emits ci:status now and whenever the state changes; shared between windows
+    watch: (rootPath: string, options?: { remote?: string }) => Promise<{ repoRoot: string }>;
+    unwatch: (rootPath: string) => Promise<boolean>;
+    // Streams the log as ci:log chunks (with ANSI colors) until the job finishes
+    followLog: (rootPath: string, jobId: string, options?: { remote?: string }) => Promise<{ streamId: string; job: CiJob }>;
+    stopLog: (streamId: string) => Promise<boolean>;
+    onStatus: (callback: (data: ForgeEventMap['ci:status']) => void) => () => void;
+    onLog: (callback: (data: ForgeEventMap['ci:log']) => void) => () => void;
+    onLogEnded: (callback: (data: ForgeEventMap['ci:logEnded']) => void) => () => void;
+  };
+  issues: {
+    // dryRun returns the body for review without creating the issue
+    capture: (options: IssueCaptureOptions) => Promise<CapturedIssue>;
+  };
+  notebooks: {
+    read: (filePath: string) => Promise<NotebookModel>;
+    write: (filePath: string, model: NotebookModel) => Promise<boolean>;
+    // Local specs include the workspace's virtual environments; pass a gateway to list its specs
+    listKernelSpecs: (rootPath: string | null, options?: { gateway?: JupyterGateway }) => Promise<KernelSpec[]>;
+    // Local kernels require a trusted workspace and shut down when the window closes
+    startKernel: (options: StartKernelOptions) => Promise<KernelInfo>;
+    listKernels: () => Promise<KernelInfo[]>;
+    // Outputs stream as notebook:output events; resolves once the kernel is idle again
+    execute: (kernelId: string, code: string, options?: { cellId?: string; silent?: boolean; storeHistory?: boolean; stopOnError?: boolean }) => Promise<ExecutionResult>;
+    interrupt: (kernelId: string) => Promise<boolean>;
+    restart: (kernelId: string) => Promise<KernelInfo>;
+    shutdown: (kernelId: string) => Promise<boolean>;
+    onOutput: (callback: (data: ForgeEventMap['notebook:output']) => void) => () => void;
+    onKernelStatus: (callback: (data: ForgeEventMap['notebook:kernelStatus']) => void) => () => void;
+  };
+  pdf: {
+    // NOT_FOUND when Poppler isn't installed; PERMISSION_DENIED when a password is needed
+    info: (filePath: string, options?: { password?: string }) => Promise<PdfInfo>;
+    outline: (filePath: string, options?: { password?: string }) => Promise<PdfOutlineItem[]>;
+    // Page numbers start at 1; scale 1 renders at 72 dpi, between 0.1 and 8
+    renderPage: (filePath: string, page: number, scale?: number, options?: { password?: string }) => Promise<PdfPageImage>;
+  };
+  svg: {
+    // Scripts, event handlers and external references removed; `content` is the editor's unsaved text
+    sanitize: (filePath: string | null, options?: { content?: string }) => Promise<SanitizedSvg>;
+    rasterize: (filePath: string | null, options?: SvgRasterOptions) => Promise<SvgRaster>;
+    exportPng: (filePath: string | null, outputPath: string, options?: SvgRasterOptions) => Promise<{ path: string; width: number; height: number; renderer: SvgRenderer }>;
+  };
+  fonts: {
+    // fontIndex picks a font in a .ttc collection
+    info: (filePath: string, options?: { fontIndex?: number }) => Promise<FontInfo>;
+    preview: (filePath: string, options?: FontPreviewOptions) => Promise<FontPreview>;
+  };
+  models: {
+    info: (filePath: string) => Promise<ModelInfo>;
+    // Rejects for meshes compressed with Draco or meshopt (previewable: false)
+    thumbnail: (filePath: string, options?: ModelThumbnailOptions) => Promise<ModelThumbnail>;
+  };
+  schemas: {
+    parse: (filePath: string, options?: { includePaths?: string[] }) => Promise<ProtoSchemaSummary | FbsSchemaSummary>;
+    // Without a language the schema is only checked; diagnostics also go to the problems panel
+    compile: (filePath: string, options?: SchemaCompileOptions) => Promise<SchemaCompileResult>;
+    // typeName may be a suffix of the full name; FlatBuffers default to the root_type
+    decode: (filePath: string, typeName: string | null, payload: SchemaPayload, options?: SchemaDecodeOptions) => Promise<DecodedPayload>;
+    decodeRaw: (payload: SchemaPayload, options?: { framing?: 'none' | 'grpc' | 'delimited' }) => Promise<DecodedPayload>;
+  };
+  logViewer: {
+    open: (filePath: string, options?: { filters?: LogViewFilters; follow?: boolean; limit?: number }) => Promise<LogViewSnapshot>;
+    listSources: () => Promise<LogSourceInfo[]>;
+    openSource: (sourceId: LogSourceId, params?: LogSourceParams, options?: { filters?: LogViewFilters; follow?: boolean; limit?: number }) => Promise<LogSourceSnapshot>;
+    read: (viewId: string, options: { before?: number; after?: number; limit?: number }) => Promise<LogViewPage>;
+    setFilters: (viewId: string, filters: LogViewFilters, options?: { limit?: number }) => Promise<LogViewSnapshot | LogSourceSnapshot>;
+    setFollow: (viewId: string, follow: boolean) => Promise<{ following: boolean }>;
+    close: (viewId: string) => Promise<boolean>;
+    inferSchema: (viewId: string, options?: { sample?: number }) => Promise<LogViewSchema>;
+    listQueries: () => Promise<SavedLogQuery[]>;
+    saveQuery: (query: { id?: string; name: string; query: string; columns?: string[] }) => Promise<SavedLogQuery>;
+    deleteQuery: (id: string) => Promise<boolean>;
+  };
+  tasks: {
+    // null when the workspace has no .forge/tasks.toml
+    load: () => Promise<TaskConfig | null>;
+    // Requires a trusted workspace; progress arrives as task:status events
+    runPipeline: (name: string) => Promise<{ runId: string; jobId: string }>;
+    runTask: (name: string) => Promise<{ runId: string; jobId: string }>;
+    cancel: (runId: string) => Promise<boolean>;
+    listRuns: () => Promise<TaskRun[]>;
+    onStatus: (callback: (data: ForgeEventMap['task:status']) => void) => () => void;
+    onOutput: (callback: (data: ForgeEventMap['task:output']) => void) => () => void;
+    onRunFinished: (callback: (data: ForgeEventMap['task:runFinished']) => void) => () => void;
+  };
+  problems: {
+    list: (filter?: { file?: string; source?: string }) => Promise<Problem[]>;
+    clear: (source: string) => Promise<boolean>;
+    // Match a terminal's output; with shell integration each command clears the previous results
+    watchTerminal: (terminalId: string, matcherIds: string[], options?: { clearOnCommand?: boolean }) => Promise<boolean>;
+    unwatchTerminal: (terminalId: string) => Promise<boolean>;
+    listMatchers: () => Promise<ProblemMatcherInfo[]>;
+    saveMatcher: (id: string, definition: ProblemMatcherDefinition) => Promise<ProblemMatcherDefinition>;
+    deleteMatcher: (id: string) => Promise<boolean>;
+    onChanged: (callback: (data: ForgeEventMap['problems:changed']) => void) => () => void;
+  };
+  runConfigs: {
+    list: () => Promise<RunConfig[]>;
+    get: (id: string) => Promise<RunConfig>;
+    create: (input: RunConfigInput) => Promise<RunConfig>;
+    update: (id: string, changes: Partial<RunConfigInput>) => Promise<RunConfig>;
+    delete: (id: string) => Promise<boolean>;
+    // Reads .vscode/launch.json and tasks.json; re-importing updates earlier imports
+    importVSCode: () => Promise<{ imported: RunConfig[]; skipped: { name: string; source: string; reason: string }[] }>;
+  };
+  commandHistory: {
+    // Fuzzy search, one result per distinct command
+    search: (query?: string, filters?: CommandHistoryFilters) => Promise<CommandHistoryResult[]>;
+    // Forget every run of this exact command
+    delete: (command: string) => Promise<boolean>;
+    clear: () => Promise<boolean>;
+    getSettings: () => Promise<CommandHistorySettings>;
+    updateSettings: (changes: Partial<CommandHistorySettings>) => Promise<CommandHistorySettings>;
+    onAdded: (callback: (data: ForgeEventMap['commandHistory:added']) => void) => () => void;
+  };
+  bookmarks: {
+    // Most used first
+    list: () => Promise<DirectoryBookmark[]>;
+    // Bookmarking a directory twice returns the existing bookmark
+    add: (directory: string, label?: string) => Promise<DirectoryBookmark>;
+    update: (id: string, changes: { label: string }) => Promise<DirectoryBookmark>;
+    remove: (id: string) => Promise<boolean>;
+    cdTerminal: (id: string, terminalId: string) => Promise<{ command: string; path: string }>;
+    onChanged: (callback: (data: ForgeEventMap['bookmarks:changed']) => void) => () => void;
+  };
+  terminalLayout: {
+    get: () => Promise<TerminalLayout | null>;
+    save: (layout: TerminalLayoutInput) => Promise<TerminalLayout>;
+    clear: () => Promise<boolean>;
+    // Startup commands run only in trusted workspaces; otherwise commandSkipped is set
+    restore: (options?: { cols?: number; rows?: number }) => Promise<{ terminals: RestoredTerminal[]; arrangement: unknown }>;
+  };
+  terminalAnnotations: {
+    // Annotations overlapping [start, end) output offsets, optionally from one source
+    list: (terminalId: string, range?: { start?: number; end?: number; source?: string }) => Promise<TerminalAnnotation[]>;
+    remove: (terminalId: string, id: string) => Promise<boolean>;
+    // Resolves how many were removed
+    clear: (terminalId: string, options?: { source?: string }) => Promise<number>;
+    onChanged: (callback: (data: ForgeEventMap['terminalAnnotations:changed']) => void) => () => void;
+  };
+  saveHooks: {
+    list: () => Promise<SaveHook[]>;
+    // A null timeoutMs restores the hook's default
+    configure: (id: string, options: { enabled?: boolean; timeoutMs?: number | null }) => Promise<SaveHook>;
+    getTools: () => Promise<SaveTools>;
+    setTools: (tools: Partial<SaveTools>) => Promise<SaveTools>;
+    // After post-save hooks finish, with every hook's report for that save
+    onCompleted: (callback: (data: ForgeEventMap['saveHooks:completed']) => void) => () => void;
+  };
+  snapshots: {
+    // Zips the workspace (or `rootPath`) honoring .gitignore; prompts for a location without targetPath
+    export: (rootPath?: string | null, options?: ProjectSnapshotOptions) => Promise<ProjectSnapshot | null>;
+  };
+  languages: {
+    list: () => Promise<LanguageConfig[]>;
+    get: (id: string) => Promise<LanguageConfig>;
+    // By file name, extension, then shebang; null when no language claims the file
+    forFile: (path: string) => Promise<LanguageConfig | null>;
+    // Adds a language or overrides parts of one; list fields add to the built-in ones
+    save: (id: string, definition: LanguageDefinition) => Promise<LanguageConfig>;
+    delete: (id: string) => Promise<boolean>;
+  };
+  spellcheck: {
+    // Code is checked only in comments and string literals; markdown skips code and links
+    checkText: (text: string, options?: SpellcheckOptions) => Promise<SpellcheckResult>;
+    suggest: (word: string, options?: { dictionary?: string; limit?: number }) => Promise<string[]>;
+    // Personal dictionary; false when the word was already there (or not there, for remove)
+    addWord: (word: string) => Promise<boolean>;
+    removeWord: (word: string) => Promise<boolean>;
+    listWords: () => Promise<string[]>;
+    listDictionaries: () => Promise<{ dictionaries: SpellcheckDictionary[]; selected: string | null }>;
+    // null follows the system locale
+    setDictionary: (id: string | null) => Promise<string | null>;
+    // Copies a .dic and the .aff beside it into Forge's dictionary folder
+    installDictionary: (dicPath: string) => Promise<SpellcheckDictionary>;
+  };
+  // Brackets, strings and comments of a file, parsed in the main process and cached. Pass
+  // `text` for an editor with unsaved changes; otherwise the file on disk is used.
+  structure: {
+    // The next larger word, item, bracket contents or bracket; null at the whole file
+    expandSelection: (path: string, range: LspRange, options?: StructureOptions) => Promise<LspRange | null>;
+    // The bracket at the cursor and its partner, else the innermost pair around it
+    matchingBracket: (path: string, position: LspPosition, options?: StructureOptions) => Promise<BracketPair | null>;
+    // The neighbouring argument, element or statement
+    sibling: (path: string, range: LspRange, direction: 'next' | 'previous', options?: StructureOptions) => Promise<LspRange | null>;
+  };
+  // Rendered off-screen, then sent to the system print dialog, or saved as a PDF when
+  // `pdfPath` is given
+  print: {
+    // Syntax coloured, with line numbers unless turned off
+    file: (path: string, options?: PrintFileOptions) => Promise<PrintResult>;
+    // The terminal's scrollback with its colours
+    terminal: (terminalId: string, options?: PrintOptions) => Promise<PrintResult>;
+  };
+  // Each comparison is kept as a session and opened as a diff view in this window
+  // (workbench:openDiff with its sessionId) unless `open` is false
+  compare: {
+    // Two files, or two folders paired by relative path
+    files: (pathA: string, pathB: string, options?: CompareOptions) => Promise<CompareSession>;
+    // A file on disk against unsaved text
+    withContent: (path: string, text: string, options?: CompareOptions & { label?: string }) => Promise<CompareSession>;
+    withClipboard: (path: string, options?: CompareOptions) => Promise<CompareSession>;
+    // The file against its version at a git revision, HEAD by default
+    withRevision: (path: string, ref?: string, options?: CompareOptions) => Promise<CompareSession>;
+    getSession: (id: string) => Promise<CompareSession>;
+    closeSession: (id: string) => Promise<boolean>;
+  };
+  // Colours, badges and labels for file tree nodes, per workspace
+  decorations: {
+    // Undecorated paths are left out of the result
+    query: (rootPath: string, paths: string[]) => Promise<Record<string, FileDecoration>>;
+    // null removes the path's decoration; a label without a colour gets a stable one
+    set: (rootPath: string, path: string, decoration: FileDecorationInput | null) => Promise<FileDecorationInput | null>;
+    list: (rootPath: string) => Promise<(FileDecorationInput & { path: string })[]>;
+    onChanged: (callback: (data: ForgeEventMap['decorations:changed']) => void) => () => void;
+  };
+  // The working set: pinned files, folders and terminal profiles for this window's
+  // workspace, plus global pins
+  favorites: {
+    list: () => Promise<Favorite[]>;
+    // Pinning something already pinned returns the existing pin
+    pin: (target: FavoriteTarget, options?: { global?: boolean }) => Promise<Favorite>;
+    unpin: (id: string) => Promise<boolean>;
+    // Listed ids move to the front in this order
+    reorder: (ids: string[]) => Promise<number>;
+    onChanged: (callback: (data: ForgeEventMap['favorites:changed']) => void) => () => void;
+  };
+  activity: {
+    // Report the focused editor or terminal; null when neither has focus
+    focus: (target: ActivityTarget | null) => Promise<boolean>;
+    // The last seven days when the range is omitted
+    getSummary: (range?: ActivityRange) => Promise<ActivitySummary>;
+    clear: () => Promise<boolean>;
+    getSettings: () => Promise<ActivitySettings>;
+    updateSettings: (changes: Partial<ActivitySettings>) => Promise<ActivitySettings>;
+  };
+  sourceMaps: {
+    // Source paths are looked up under this window's workspace unless one is given
+    resolveStack: (stack: string, options?: { workspace?: string }) => Promise<ResolvedStack>;
+    // A captured browser console entry: its stack, else the line it was logged from
+    resolveConsoleEntry: (viewId: string, entryId: number, options?: { workspace?: string }) => Promise<ResolvedStack>;
+    openFrame: (target: SourceFrameTarget) => Promise<{ path: string; kind: 'file' | 'folder'; windowId: number }[]>;
+  };
   removeAllListeners: (channel: string) => void;
 }
 
@@ -99,12 +874,19 @@ export interface TerminalOptions {
   env?: Record<string, string>;
   cols?: number;
   rows?: number;
+  // Load Forge's prompt/command marks for bash, zsh, fish, nushell, and PowerShell (default true)
+  shellIntegration?: boolean;
+  // Forward UI mouse events to programs that enable mouse reporting (default true)
+  mouseForwarding?: boolean;
 }
 
 export interface TerminalInfo {
   id: string;
   shell: string;
   cwd: string;
+  restricted: boolean;
+  // Id of the window that owns the terminal's output
+  windowId: number;
 }
 
 export interface TerminalData {
@@ -117,11 +899,93 @@ export interface TerminalExit {
   exitCode: number;
 }
 
+// An inline image decoded from terminal output, to draw where its escape sequence ended.
+// Placement sizes use iTerm2 notation: "N" cells, "Npx", "N%", or "auto".
+export interface TerminalImage {
+  terminalId: string;
+  imageId: string;
+  protocol: 'sixel' | 'iterm' | 'kitty';
+  data: string; // base64 PNG
+  width: number;
+  height: number;
+  placement: { width: string; height: string; preserveAspectRatio: boolean };
+}
+
+// Mouse reporting requested by the program (DECSET 9/1000/1002/1003 and 1005/1006/1015).
+// While `active`, the terminal view sends mouse events with sendMouse instead of selecting.
+export interface TerminalMouseState {
+  tracking: 'x10' | 'normal' | 'button' | 'any' | null;
+  encoding: 'default' | 'utf8' | 'sgr' | 'urxvt';
+  forwarding: boolean;
+  active: boolean;
+}
+
+export interface TerminalMouseEvent {
+  action: 'press' | 'release' | 'move';
+  button: 'left' | 'middle' | 'right' | 'wheelUp' | 'wheelDown' | 'none';
+  // 1-based cell coordinates
+  column: number;
+  row: number;
+  shift?: boolean;
+  alt?: boolean;
+  ctrl?: boolean;
+}
+
+export interface TerminalCommand {
+  id: number;
+  // Trimmed for display; commandLine is the line exactly as typed
+  command: string;
+  commandLine: string;
+  cwd: string;
+  startedAt: string;
+  // null while the command is still running
+  finishedAt: string | null;
+  exitCode: number | null;
+}
+
+export interface TerminalRerun {
+  terminalId: string;
+  command: string;
+  cwd: string;
+}
+
+// A position in normal-screen output since the terminal started: characters and newlines
+// (wrapped lines aren't counted)
+export interface TerminalPosition {
+  offset: number;
+  line: number;
+}
+
+// One prompt and the command run from it, from shell integration's OSC 133 marks
+export interface TerminalMark {
+  id: number;
+  prompt: TerminalPosition;
+  command: TerminalPosition | null;
+  output: TerminalPosition | null;
+  end: TerminalPosition | null;
+  commandLine: string | null;
+  exitCode: number | null;
+}
+
+export interface TerminalMarks {
+  marks: TerminalMark[];
+  // Where the kept scrollback begins; earlier output has been trimmed
+  scrollbackStart: TerminalPosition;
+  lineCount: number;
+}
+
 export interface TerminalSessionInfo {
   id: string;
   shell: string;
   cwd: string;
   environment: Record<string, string>;
+  restricted: boolean;
+  windowId: number;
+  // Shell whose integration script is loaded (prompt, command, and cwd marks), or null
+  shellIntegration: 'bash' | 'zsh' | 'fish' | 'nu' | 'powershell' | null;
+  mouse: TerminalMouseState;
+  // A full-screen program is using the alternate screen buffer
+  altScreen: boolean;
   isRunning: boolean;
   cols: number;
   rows: number;
@@ -147,9 +1011,49 @@ export interface FileInfo {
   extension: string;
 }
 
+export type ElevationMethod = 'pkexec' | 'sudo-askpass' | 'osascript' | 'uac';
+
+export interface ElevatedSaveResult {
+  saved: boolean;
+  elevated: boolean;
+  method?: ElevationMethod;
+}
+
+export interface WriteAccess {
+  path: string;
+  exists: boolean;
+  writable: boolean;
+  readOnly: boolean;
+  elevationAvailable: boolean;
+  method: ElevationMethod | null;
+}
+
+export type FileKind = 'text' | 'binary' | 'directory' | 'image' | 'pdf' | 'archive' | 'executable' | 'database' | 'audio' | 'video' | 'font';
+
+export interface FileTypeInfo {
+  path: string;
+  kind: FileKind;
+  mime: string;
+  binary: boolean;
+  encoding: 'utf-8' | 'utf-16le' | 'utf-16be' | null;
+  language: string | null;
+  size: number;
+  // Extrapolated from the first megabyte when lineCountExact is false
+  lineCount: number | null;
+  lineCountExact: boolean;
+  viewer: 'editor' | 'largeFile' | 'image' | 'pdf' | 'media' | 'binary' | null;
+}
+
+export interface FileMetadataResult {
+  path: string;
+  metadata: FileInfo | null;
+  error: ForgeErrorInfo | null;
+}
+
 export interface FileChangeEvent {
   path: string;
-  type: 'modified' | 'deleted' | 'created';
+  type: 'modified' | 'deleted' | 'created' | 'renamed';
+  newPath?: string; // where a renamed file went
 }
 
 export interface BrowserOptions {
@@ -157,6 +1061,30 @@ export interface BrowserOptions {
   url?: string;
   bounds?: BrowserBounds;
   hidden?: boolean;
+  // Which history and bookmarks the view uses; 'default' when omitted
+  profile?: string;
+  // Restored from a saved session
+  zoomFactor?: number;
+  devTools?: { mode: BrowserDevToolsMode } | null;
+}
+
+export type BrowserDevToolsMode = 'right' | 'bottom' | 'left' | 'undocked' | 'detach';
+
+export interface BrowserSessionView {
+  id: string;
+  url: string;
+  title: string | null;
+  profile: string;
+  bounds: BrowserBounds | null;
+  zoomFactor: number;
+  devTools: { mode: BrowserDevToolsMode } | null;
+}
+
+export interface BrowserSession {
+  savedAt: string | null;
+  // The view that was showing
+  activeId: string | null;
+  views: BrowserSessionView[];
 }
 
 export interface BrowserInfo {
@@ -194,9 +1122,2577 @@ export interface EditorSession {
   id?: string;
   openFiles: string[];
   activeFile?: string;
+  // Workspace the session belongs to; filled in from the saving window when omitted
+  workspaceId?: string;
   savedAt?: string;
 }
 
+export type CommandContext = Record<string, unknown>;
+
+export interface CommandInfo {
+  id: string;
+  title: string;
+  category: string;
+  keybinding?: string;
+  source: string;
+  // False for commands that need arguments, which the palette can't supply
+  palette: boolean;
+  enabled: boolean;
+  handledBy: 'backend' | 'renderer';
+}
+
+export interface CommandInvokeEvent {
+  id: string;
+  args: unknown[];
+}
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export interface LogEntry {
+  timestamp: string;
+  level: LogLevel;
+  module: string;
+  message: string;
+  fields?: Record<string, unknown>;
+}
+
+export interface LogQuery {
+  limit?: number;
+  level?: LogLevel;
+  module?: string;
+  since?: string;
+}
+
+export interface LogLevels {
+  default: LogLevel;
+  modules: Record<string, LogLevel>;
+}
+
+export interface CrashReportSummary {
+  id: string;
+  timestamp: string;
+  source: string;
+  message: string;
+  fatal: boolean;
+  uploaded: boolean;
+}
+
+export interface CrashReport extends CrashReportSummary {
+  name: string;
+  stack?: string;
+  context?: Record<string, unknown>;
+  app: { version: string; electron: string; node: string };
+  os: { platform: string; arch: string; release: string };
+}
+
+export interface CrashReportingSettings {
+  uploadEnabled: boolean;
+  endpoint: string | null;
+}
+
+export interface RecoveredFromErrorEvent {
+  reportId: string;
+  source: string;
+  message: string;
+}
+
+export interface TelemetrySettings {
+  enabled: boolean;
+  uploadEnabled: boolean;
+  endpoint: string | null;
+  lastUploadAt: string | null;
+  pendingEvents: number;
+}
+
+export interface TelemetryEvent {
+  name: string;
+  properties: Record<string, string | number | boolean>;
+  timestamp: string;
+  appVersion: string;
+  platform: string;
+}
+
+export interface TelemetryPayload {
+  installId: string | null;
+  events: TelemetryEvent[];
+}
+
+export interface WorkspaceTrust {
+  path: string;
+  trusted: boolean;
+  decidedAt: string | null;
+  inherited: boolean;
+}
+
+export interface WorkspaceRestrictions {
+  trusted: boolean;
+  allowTaskAutoRun: boolean;
+  allowEnvFiles: boolean;
+  restrictedTerminals: boolean;
+}
+
+export interface WorkspaceTrustInfo extends WorkspaceTrust {
+  restrictions: WorkspaceRestrictions;
+}
+
+export interface OnboardingState {
+  completed: boolean;
+  completedSteps: string[];
+  firstRunAt: string | null;
+  isFirstRun: boolean;
+  steps: string[];
+}
+
+export interface EventCatalogEntry {
+  description: string;
+  fields: Record<string, string>;
+  replay: boolean;
+  throttleMs: number;
+}
+
+export type JobStatus = 'running' | 'succeeded' | 'failed' | 'cancelled';
+
+export interface JobInfo {
+  id: string;
+  kind: string;
+  title: string;
+  status: JobStatus;
+  cancellable: boolean;
+  progress: number | null;
+  message: string | null;
+  startedAt: string;
+  finishedAt: string | null;
+  error: { code: string; message: string } | null;
+}
+
+export interface LanguageServerInfo {
+  id: string;
+  command: string;
+  extensions: string[];
+}
+
+export interface LspPosition {
+  line: number;
+  character: number;
+}
+
+export interface LspTextEdit {
+  range: { start: LspPosition; end: LspPosition };
+  newText: string;
+}
+
+export interface LspWorkspaceEdit {
+  changes?: Record<string, LspTextEdit[]>;
+  documentChanges?: { textDocument: { uri: string; version?: number | null }; edits: LspTextEdit[] }[];
+}
+
+export interface RenameSymbolOptions {
+  rootPath: string;
+  filePath: string;
+  position: LspPosition;
+  newName: string;
+  // Unsaved editor buffers keyed by file path; these are edited in memory, not on disk
+  openBuffers?: Record<string, string>;
+  // From a CONFIRMATION_REQUIRED error when the rename touches protected paths
+  confirmationToken?: string;
+}
+
+export interface EditedFile {
+  path: string;
+  onDisk: boolean;
+  content?: string;
+}
+
+export interface WorkspaceEditResult {
+  journalId: string;
+  files: EditedFile[];
+}
+
+export interface ReplaceInProjectRequest {
+  rootPath: string;
+  query: string;
+  // With options.regex, may reference capture groups ($1)
+  replacement: string;
+  options?: { regex?: boolean; caseSensitive?: boolean; wholeWord?: boolean; extensions?: string[] };
+  openBuffers?: Record<string, string>;
+  confirmationToken?: string;
+}
+
+export interface ReplaceInProjectResult {
+  // null when nothing matched
+  journalId: string | null;
+  files: EditedFile[];
+  replacements: number;
+}
+
+export interface RefactorJournalEntry {
+  id: string;
+  description: string;
+  timestamp: string;
+  paths: string[];
+}
+
+export interface SnippetInput {
+  name: string;
+  prefixes: string[];
+  body: string | string[];
+  description?: string;
+  // Language ids, or ['*'] for every language
+  languages?: string[];
+}
+
+export interface Snippet {
+  id: string;
+  name: string;
+  prefixes: string[];
+  body: string;
+  description: string;
+  languages: string[];
+  source: 'user' | 'vscode';
+  revision: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface SnippetDetails extends Snippet {
+  tabStops: { index: number; placeholder: string | null; choices: string[] | null }[];
+  variables: string[];
+}
+
+export interface SnippetContext {
+  filePath?: string;
+  workspacePath?: string;
+  selectedText?: string;
+  currentLine?: string;
+  currentWord?: string;
+  lineNumber?: number;
+  clipboard?: string;
+}
+
+export interface SnippetCompletion {
+  id: string;
+  name: string;
+  prefix: string;
+  description: string;
+  insertText: string;
+  exact: boolean;
+}
+
+export interface SnippetImportResult {
+  imported: Snippet[];
+  skipped: { name: string; reason: string }[];
+}
+
+export interface TextSearchOptions {
+  regex?: boolean;
+  caseSensitive?: boolean;
+  wholeWord?: boolean;
+  // Lowercase extensions including the dot, e.g. ['.ts', '.tsx']
+  extensions?: string[];
+  excludes?: string[];
+  maxResults?: number;
+}
+
+export interface TextSearchMatch {
+  path: string;
+  line: number;
+  column: number;
+  text: string;
+  match: string;
+}
+
+export interface TextSearchResponse {
+  results: TextSearchMatch[];
+  filesSearched: number;
+  truncated: boolean;
+}
+
+export interface TodoItem {
+  path: string;
+  relativePath: string;
+  line: number;
+  column: number;
+  tag: string;
+  // Owner named in the comment itself, e.g. TODO(alice)
+  owner: string | null;
+  text: string;
+  author: string | null;
+  authorMail: string | null;
+  committedAt: string | null;
+}
+
+export interface TodoFilter {
+  tags?: string[];
+  author?: string;
+  path?: string;
+}
+
+export interface EditorConfigSettings {
+  indentStyle: 'tab' | 'space' | null;
+  indentSize: number | null;
+  tabWidth: number | null;
+  endOfLine: 'lf' | 'crlf' | 'cr' | null;
+  charset: string | null;
+  trimTrailingWhitespace: boolean | null;
+  insertFinalNewline: boolean | null;
+  maxLineLength: number | null;
+  // .editorconfig files that contributed, outermost first
+  sources: string[];
+  // Raw merged properties, including non-standard keys
+  properties: Record<string, string>;
+}
+
+export interface ClipboardHistoryItem {
+  id: string;
+  text: string;
+  preview: string;
+  hasHtml: boolean;
+  source: string;
+  pinned: boolean;
+  createdAt: string;
+  lastUsedAt: string;
+}
+
+export interface ClipboardSettings {
+  enabled: boolean;
+  maxItems: number;
+  excludeSecrets: boolean;
+}
+
+export interface NativeNotificationOptions {
+  title: string;
+  body?: string;
+  actions?: { id: string; label: string }[];
+  data?: any;
+  silent?: boolean;
+  // Urgent notifications bypass do-not-disturb
+  urgent?: boolean;
+  focusWindow?: boolean;
+}
+
+export interface DoNotDisturbState {
+  enabled: boolean;
+  snoozedUntil: string | null;
+  schedule: { enabled: boolean; start: string; end: string };
+  active: boolean;
+}
+
+export interface MissedNotification {
+  id: string;
+  title: string;
+  body: string;
+  actions: { id: string; label: string }[];
+  data: any;
+  createdAt: string;
+}
+
+export interface Reminder {
+  id: string;
+  title: string;
+  body: string;
+  at: string;
+  urgent: boolean;
+}
+
+// 'verifying' means a cached index is being served while it is checked against disk
+export type IndexState = 'closed' | 'loading' | 'indexing' | 'verifying' | 'ready' | 'failed';
+
+export interface FileIndexStatus {
+  rootPath: string;
+  indexed: boolean;
+  indexing: boolean;
+  fileCount: number;
+  state: IndexState;
+  stale: boolean;
+  fromCache: boolean;
+  savedAt: string | null;
+}
+
+export interface SymbolIndexStatus {
+  rootPath: string;
+  state: IndexState;
+  stale: boolean;
+  fromCache: boolean;
+  fileCount: number;
+  savedAt: string | null;
+}
+
+export interface FileMatch {
+  path: string;
+  relativePath: string;
+  score: number;
+  // Matched character offsets in relativePath, for highlighting
+  positions: number[];
+}
+
+export type SymbolKind = 'function' | 'class' | 'interface' | 'type' | 'enum' | 'struct' | 'module' | 'constant';
+
+export interface WorkspaceSymbol {
+  name: string;
+  kind: SymbolKind;
+  path: string;
+  line: number;
+  column: number;
+}
+
+export interface SymbolMatch extends WorkspaceSymbol {
+  relativePath: string;
+  score: number;
+  positions: number[];
+}
+
+export type QuickOpenResultType = 'file' | 'symbol' | 'command' | 'terminal';
+
+export interface QuickOpenOptions {
+  rootPath?: string;
+  limit?: number;
+  // Restrict sources when the query has no prefix (">" commands, "#"/"@" symbols, "$" terminals)
+  types?: QuickOpenResultType[];
+  // Context keys for evaluating command `when` clauses
+  context?: Record<string, unknown>;
+}
+
+export interface QuickOpenResult {
+  type: QuickOpenResultType;
+  id: string;
+  label: string;
+  description: string;
+  score: number;
+  positions: number[];
+  payload: Record<string, any>;
+}
+
+export interface QuickOpenResponse {
+  query: string;
+  type: QuickOpenResultType | null;
+  results: QuickOpenResult[];
+}
+
+export interface WindowContext {
+  windowId: number;
+  // Stable hash of the workspace root, or 'default' for windows without a folder
+  workspaceId: string;
+  workspacePath: string | null;
+}
+
+export interface TransferredEditor {
+  path: string;
+  // Unsaved buffer content, when dirty
+  content?: string;
+  dirty?: boolean;
+  language?: string;
+  viewState?: unknown;
+}
+
+export interface WindowSummary extends WindowContext {
+  title: string;
+  focused: boolean;
+  createdAt: string;
+}
+
+export interface OpenTarget {
+  path: string;
+  line?: number;
+  column?: number;
+}
+
+export interface OpenedPath {
+  path: string;
+  kind: 'file' | 'folder';
+  windowId: number;
+}
+
+export interface FileAssociationStatus {
+  platform: string;
+  packaged: boolean;
+  runtimeRegistration: boolean;
+  contextMenu?: boolean;
+  defaults?: Record<string, boolean>;
+}
+
+export type DropTarget = 'workbench' | 'editor' | 'terminal';
+
+export interface DropRequest {
+  target: DropTarget;
+  paths: string[];
+  terminalId?: string;
+}
+
+export interface DropResult {
+  action: string;
+  opened?: OpenedPath[];
+  paths?: string[];
+}
+
+export interface DragDropSettings {
+  workbench: { folder: 'openWorkspace' | 'openInNewWindow' | 'replaceWorkspace' | 'ignore'; file: 'open' | 'ignore' };
+  editor: { folder: 'openWorkspace' | 'openInNewWindow' | 'replaceWorkspace' | 'ignore'; file: 'open' | 'ignore' };
+  terminal: { action: 'insertPath' | 'cd' | 'ignore'; trailingSpace: boolean };
+}
+
+export interface SecretInfo {
+  name: string;
+  stored: boolean;
+  fromEnvironment: boolean;
+  updatedAt: string | null;
+}
+
+export interface AiMessage {
+  role: 'system' | 'user' | 'assistant';
+  content: string;
+}
+
+export interface AiContext {
+  instructions?: string;
+  // content is sent for unsaved buffers; otherwise the file is read from disk
+  files?: { path: string; content?: string; selection?: string }[];
+  terminals?: string[];
+}
+
+export interface AiChatRequest {
+  messages: AiMessage[];
+  providerId?: string;
+  model?: string;
+  temperature?: number;
+  maxTokens?: number;
+  stop?: string[];
+  context?: AiContext;
+  contextChars?: number;
+}
+
+export interface AiProviderConfig {
+  id: string;
+  name?: string;
+  type: 'openai' | 'ollama';
+  baseUrl: string;
+  model?: string | null;
+  // Name of the secret holding the API key (see secrets.set)
+  apiKeySecret?: string | null;
+}
+
+export interface AiProvider extends AiProviderConfig {
+  isDefault: boolean;
+  hasApiKey: boolean | null;
+}
+
+export interface TerminalAiOptions {
+  terminalId?: string;
+  // Output offsets of the explained text in the terminal; the explanation is annotated there
+  range?: { start: number; end: number };
+  shell?: string;
+  cwd?: string;
+  providerId?: string;
+  model?: string;
+}
+
+export interface CommandSafety {
+  level: 'safe' | 'caution' | 'dangerous';
+  reasons: string[];
+}
+
+export interface CommandExplanation {
+  command: string;
+  summary: string;
+  parts: { text: string; meaning: string }[];
+  safety: CommandSafety;
+  // Present when the text was explained from a terminal range (options.range)
+  annotation?: TerminalAnnotation;
+}
+
+export interface GeneratedCommand {
+  command: string;
+  explanation: string;
+  shell: string;
+  cwd: string;
+  safety: CommandSafety;
+}
+
+export interface CompletionRequest {
+  path: string;
+  content: string;
+  offset: number;
+  languageId?: string;
+  rootPath?: string;
+  neighborPaths?: string[];
+}
+
+export interface CompletionInsertion {
+  text: string;
+  kind: 'full' | 'line';
+  score: number;
+}
+
+export interface CompletionResult {
+  insertions: CompletionInsertion[];
+  latencyMs?: number;
+  cached?: boolean;
+  cancelled?: boolean;
+  disabled?: boolean;
+}
+
+export interface CompletionSettings {
+  enabled: boolean;
+  providerId: string | null;
+  model: string | null;
+  debounceMs: number;
+  maxTokens: number;
+  contextTokens: number;
+}
+
+export interface CompletionStats {
+  requests: number;
+  cacheHits: number;
+  debounced: number;
+  failures: number;
+  completed: number;
+  totalLatencyMs: number;
+  averageLatencyMs: number | null;
+  cacheSize: number;
+}
+
+export interface SemanticSearchResult {
+  path: string;
+  relativePath: string;
+  startLine: number;
+  endLine: number;
+  score: number;
+  preview: string;
+}
+
+export interface EmbeddingIndexStatus {
+  indexed: boolean;
+  chunks?: number;
+  files?: number;
+  model?: string | null;
+  providerId?: string | null;
+  dims?: number;
+  builtAt?: string | null;
+  jobId?: string | null;
+  pendingFiles?: number;
+}
+
+export interface EmbeddingSettings {
+  providerId: string | null;
+  model: string | null;
+  chunkLines: number;
+  overlapLines: number;
+  batchSize: number;
+}
+
+export interface GitAiRules {
+  commitStyle: 'conventional' | 'plain' | 'match';
+  maxSubjectLength: number;
+  includeBody: boolean;
+  customInstructions: string;
+  providerId: string | null;
+  model: string | null;
+}
+
+export interface CommitMessageDraft {
+  repoRoot: string;
+  message: string;
+  subject: string;
+  body: string;
+  warnings: string[];
+  dryRun: true;
+}
+
+export interface PrDescriptionDraft {
+  repoRoot: string;
+  base: string;
+  branch: string;
+  title: string;
+  body: string;
+  usedTemplate: boolean;
+  dryRun: true;
+}
+
+export type MacroAction =
+  | { type: 'command'; id: string; args?: unknown[] }
+  | { type: 'terminalInput'; terminalId: string; text: string }
+  | { type: 'openFile'; path: string; line?: number; column?: number }
+  | { type: 'wait'; ms: number };
+
+export interface Macro {
+  name: string;
+  actions: MacroAction[];
+  updatedAt: string;
+}
+
+export interface AutomationScript {
+  name: string;
+  source: string;
+  updatedAt: string;
+}
+
+export interface WatcherHealth {
+  root: string;
+  owner: string;
+  status: 'starting' | 'healthy' | 'polling' | 'rescanning' | 'degraded' | 'failed';
+  mode: 'native' | 'polling';
+  fsType: string | null;
+  reason: string | null;
+  // How to fix a watcher limit problem (e.g. raising fs.inotify.max_user_watches)
+  hint: string | null;
+  lastRescanAt: string | null;
+}
+
+// Globs relative to the workspace root; patterns without a slash match at any depth
+export interface IndexScope {
+  rootPath: string;
+  include: string[];
+  exclude: string[];
+}
+
+export type WorkspaceStateSection = 'settings' | 'editorSessions' | 'terminals' | 'browsers' | 'stores' | 'indexScope' | 'favorites' | 'renderer';
+
+export interface WorkspaceStateExportOptions {
+  path?: string;
+  sections?: WorkspaceStateSection[];
+  // Workbench-owned state (terminal layouts and profiles, tasks, panels), carried as-is
+  rendererState?: unknown;
+}
+
+export interface WorkspaceStateImportOptions {
+  sections?: WorkspaceStateSection[];
+  mode?: 'merge' | 'replace';
+}
+
+export interface WorkspaceStateSummary {
+  path: string;
+  exportedAt: string;
+  appVersion: string;
+  platform: string;
+  workspaceName: string | null;
+  sections: WorkspaceStateSection[];
+  stores: string[];
+  editorSessions: number;
+  terminals: number;
+  browsers: number;
+  favorites: number;
+  warnings: string[];
+}
+
+export interface WorkspaceStateImportResult {
+  imported: WorkspaceStateSection[];
+  // For the workbench to reopen; nothing is spawned by the import itself
+  terminals: { shell: string; cwd: string }[];
+  rendererState: unknown;
+  restartRecommended: boolean;
+}
+
+export type GuardedOperation = 'delete' | 'rename' | 'replace';
+export type GuardrailAction = 'allow' | 'confirm' | 'block';
+
+export interface GuardrailRule {
+  // Glob over absolute paths; "~" is home and "${workspaceFolder}" the window's workspace
+  pattern: string;
+  action: GuardrailAction;
+  reason: string;
+  operations?: GuardedOperation[];
+}
+
+export interface GuardrailPolicy {
+  enabled: boolean;
+  outsideWorkspace: GuardrailAction;
+  rules: GuardrailRule[];
+}
+
+export interface GuardrailCheck {
+  action: GuardrailAction;
+  findings: { path: string; action: GuardrailAction; reason: string }[];
+}
+
+export interface GuardedOperationOptions {
+  confirmationToken?: string;
+}
+
+export interface TerminalLog {
+  name: string;
+  size: number;
+  savedAt: string;
+}
+
+export type EncryptedDataCategory = 'editorSessions' | 'terminalLogs' | 'recovery';
+export type EncryptionProvider = 'aes-gcm' | 'age';
+
+export interface EncryptionStatus {
+  provider: EncryptionProvider;
+  categories: Record<EncryptedDataCategory, boolean>;
+  age: { recipients: string[]; identityFile: string | null };
+  keychainAvailable: boolean;
+  hasKey: boolean;
+}
+
+export interface EncryptionChanges {
+  provider?: EncryptionProvider;
+  categories?: Partial<Record<EncryptedDataCategory, boolean>>;
+  age?: { recipients?: string[]; identityFile?: string | null };
+}
+
+export interface LiveShareOptions {
+  terminalId?: string;
+  // Absolute paths of files to show alongside (or instead of) the terminal
+  files?: string[];
+  // Listen on all interfaces instead of loopback only
+  lan?: boolean;
+  port?: number;
+}
+
+export interface LiveShareSession {
+  sessionId: string;
+  // Viewer link, including the access token
+  url: string;
+  terminalId: string | null;
+  files: string[];
+  viewers: number;
+}
+
+export type CrdtId = [client: string, clock: number];
+
+export type CrdtOp =
+  | { type: 'insert'; id: CrdtId; origin: CrdtId | null; text: string }
+  | { type: 'delete'; ranges: [client: string, clock: number, length: number][] };
+
+export interface TextChange {
+  index: number;
+  deleteCount: number;
+  text: string;
+}
+
+export interface CollabStartOptions {
+  // Absolute paths
+  files: string[];
+  // Unsaved editor content to start from, by file path
+  buffers?: Record<string, string>;
+  lan?: boolean;
+  port?: number;
+}
+
+export interface CollabSession {
+  sessionId: string;
+  // WebSocket link for participants, including the access token
+  url: string;
+  files: { path: string; filePath: string; dirty: boolean; conflict: boolean }[];
+  participants: { peerId: string; name: string }[];
+}
+
+export interface CollabDocument {
+  path: string;
+  filePath: string;
+  clientId: string;
+  state: { clock: number; items: { client: string; clock: number; text: string; deleted: boolean }[] };
+  text: string;
+}
+
+export type TunnelProvider = 'cloudflared' | 'ngrok' | 'custom';
+
+export interface TunnelSettings {
+  provider: TunnelProvider;
+  cloudflaredPath: string | null;
+  ngrokPath: string | null;
+  // "{port}" and "{host}" in args are substituted; urlPattern finds the public URL in the output
+  custom: { command: string | null; args: string[]; urlPattern: string };
+  confirm: boolean;
+}
+
+export interface TunnelInfo {
+  id: string;
+  port: number;
+  host: string;
+  provider: TunnelProvider;
+  status: 'starting' | 'open' | 'closed' | 'failed';
+  url: string | null;
+  error: string | null;
+  startedAt: string;
+}
+
+// `error` is the last reload's problem; the previous routes keep serving until it's fixed
+export interface MockServerStatus {
+  running: boolean;
+  root?: string;
+  port: number | null;
+  url: string | null;
+  configPath?: string;
+  routes: { method: string; path: string; status: number }[];
+  error: string | null;
+  requests: number;
+  startedAt?: string;
+}
+
+// `pointer` is the JSON pointer of the offending node ("/paths/~1pets/get")
+export interface OpenApiValidation {
+  file: string;
+  version: string | null;
+  valid: boolean;
+  problems: { pointer: string; line: number; severity: 'error' | 'warning'; message: string }[];
+}
+
+export interface OpenApiOperation {
+  // "GET /pets/{id}"
+  key: string;
+  operationId: string | null;
+  method: string;
+  path: string;
+  summary: string | null;
+  description: string | null;
+  tags: string[];
+  deprecated: boolean;
+  parameters: { name: string; in: 'path' | 'query' | 'header' | 'cookie' | 'formData'; required: boolean; description: string | null }[];
+  requestBody: { required: boolean; contentTypes: string[] } | null;
+  responses: string[];
+}
+
+export interface OpenApiOperationList {
+  file: string;
+  version: string;
+  title: string | null;
+  apiVersion: string | null;
+  servers: string[];
+  operations: OpenApiOperation[];
+}
+
+// Values override the spec's examples; optional parameters are left out unless given
+// or `includeOptional` is set
+export interface OpenApiRequestOptions {
+  server?: string;
+  values?: { path?: Record<string, unknown>; query?: Record<string, unknown>; header?: Record<string, unknown>; cookie?: Record<string, unknown>; body?: unknown };
+  includeOptional?: boolean;
+}
+
+export interface OpenApiRequest {
+  operation: string;
+  operationId: string | null;
+  method: string;
+  url: string;
+  headers: Record<string, string>;
+  body: string | null;
+  // The same request as a .http file entry and as a curl command
+  http: string;
+  curl: string;
+}
+
+export interface GraphQLEndpointInput {
+  name: string;
+  url: string;
+  // ws:// or wss://; defaults to `url` with a WebSocket scheme
+  subscriptionUrl?: string | null;
+  headers?: Record<string, string>;
+  // Stored secret sent as the Authorization header (a bare token as "Bearer <token>")
+  authSecret?: string | null;
+}
+
+export interface GraphQLEndpoint extends Required<GraphQLEndpointInput> {
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+// `variables` may be a JSON string as typed in the variables editor
+export interface GraphQLRequest {
+  query: string;
+  variables?: Record<string, unknown> | string | null;
+  operationName?: string | null;
+}
+
+export interface GraphQLResult {
+  status: number;
+  // HTTP success without GraphQL errors
+  ok: boolean;
+  duration: number;
+  size: number;
+  data: unknown;
+  errors: { message: string; path?: (string | number)[]; locations?: { line: number; column: number }[] }[] | null;
+  extensions: Record<string, unknown> | null;
+}
+
+export interface GraphQLProblem {
+  message: string;
+  severity: 'error' | 'warning';
+  line: number;
+  column: number;
+}
+
+export interface GraphQLCompletionItem {
+  label: string;
+  kind: 'field' | 'argument' | 'type' | 'enumValue' | 'variable' | 'keyword' | 'fragment' | 'directive';
+  // The field or argument's type, e.g. "[Pet!]!"
+  detail: string | null;
+  documentation: string | null;
+  deprecated: boolean;
+}
+
+export interface GraphQLSavedRequestInput {
+  name: string;
+  endpointId?: string | null;
+  query: string;
+  variables?: string | Record<string, unknown> | null;
+  operationName?: string | null;
+}
+
+export interface GraphQLSavedRequest {
+  id: string;
+  name: string;
+  endpointId: string | null;
+  query: string;
+  variables: string;
+  operationName: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface GraphQLCollection {
+  id: string;
+  name: string;
+  requests: GraphQLSavedRequest[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface JwtExpiry {
+  status: 'valid' | 'expired' | 'notYetValid' | 'noExpiry';
+  expiresAt: string | null;
+  notBefore: string | null;
+  issuedAt: string | null;
+  // Seconds until exp; negative once expired
+  expiresIn: number | null;
+  // Valid but expiring within five minutes
+  expiringSoon: boolean;
+  lifetime: number | null;
+}
+
+export interface JwtClaim {
+  name: string;
+  value: unknown;
+  description: string | null;
+  // ISO form of NumericDate claims such as exp and iat
+  time: string | null;
+}
+
+// JWE tokens only expose their header
+export interface DecodedJwt {
+  type: 'JWS' | 'JWE';
+  header: Record<string, unknown>;
+  payload: Record<string, unknown> | null;
+  signature: string | null;
+  algorithm: string | null;
+  keyId?: string | null;
+  claims: JwtClaim[];
+  expiry: JwtExpiry | null;
+  warnings: string[];
+}
+
+// One of key, secretName or jwksUrl. `key` may be an HMAC secret, a PEM key or
+// certificate, a JWK, or a JWK set (matched on the token's kid).
+export interface JwtVerifyOptions {
+  key?: string | Record<string, unknown>;
+  secretName?: string;
+  jwksUrl?: string;
+  secretEncoding?: 'utf8' | 'base64';
+  algorithms?: string[];
+  issuer?: string;
+  audience?: string;
+  // Seconds of leeway for exp and nbf
+  clockTolerance?: number;
+}
+
+export interface JwtVerification {
+  valid: boolean;
+  reason: string | null;
+  algorithm: string | null;
+  keyId: string | null;
+  signatureValid?: boolean;
+  decoded: DecodedJwt;
+}
+
+export interface FoundJwt {
+  token: string;
+  start: number;
+  end: number;
+  source: { kind: 'cookie' | 'localStorage' | 'sessionStorage'; name: string } | null;
+  type: 'JWS' | 'JWE';
+  algorithm: string | null;
+  header: Record<string, unknown>;
+  payload: Record<string, unknown> | null;
+  expiry: JwtExpiry | null;
+}
+
+export type UtilityTransform =
+  | 'base64Encode'
+  | 'base64Decode'
+  | 'urlEncode'
+  | 'urlDecode'
+  | 'jsonFormat'
+  | 'jsonMinify'
+  | 'yamlToJson'
+  | 'jsonToYaml';
+
+export type HashAlgorithm = 'md5' | 'sha1' | 'sha256' | 'sha384' | 'sha512' | 'sha3-256' | 'sha3-512';
+
+export type UtilityInput = { text: string } | { path: string };
+
+export interface UtilityTransformOptions {
+  outputPath?: string;
+  // jsonFormat, yamlToJson and jsonToYaml
+  indent?: number;
+  // base64Encode: the base64url alphabet without padding
+  urlSafe?: boolean;
+  // urlEncode/urlDecode: spaces as "+"
+  form?: boolean;
+}
+
+export interface UtilityTransformResult {
+  operation: UtilityTransform;
+  outputPath: string | null;
+  // null when written to outputPath, or when the result isn't UTF-8 (`binary`)
+  text: string | null;
+  binary: boolean;
+  bytesIn: number;
+  bytesOut: number;
+}
+
+export type EpochUnit = 'seconds' | 'milliseconds' | 'microseconds' | 'nanoseconds';
+
+export interface TimestampConversion {
+  input: string;
+  detected: EpochUnit | 'iso' | 'date' | 'relative';
+  epochSeconds: number;
+  epochMillis: number;
+  // UTC
+  iso: string;
+  // ISO-8601 in `timezone` with its offset
+  zoned: string;
+  timezone: string;
+  offset: string;
+  rfc2822: string;
+  // e.g. "3 hours ago"
+  relative: string;
+  dayOfWeek: string;
+  dayOfYear: number;
+  isoWeek: number;
+}
+
+export interface CronExplanation {
+  expression: string;
+  // Macros such as @daily expanded
+  normalized: string;
+  hasSeconds: boolean;
+  description: string;
+  fields: Record<'second' | 'minute' | 'hour' | 'dayOfMonth' | 'month' | 'dayOfWeek', string>;
+  timezone: string;
+  // Empty when the expression can never match
+  nextRuns: { epochMillis: number; iso: string; zoned: string; relative: string }[];
+}
+
+export interface ColorInfo {
+  input: string;
+  // #rrggbb, or #rrggbbaa when translucent
+  hex: string;
+  rgb: string;
+  hsl: string;
+  hwb: string;
+  oklab: string;
+  oklch: string;
+  name: string | null;
+  components: {
+    rgb: [number, number, number];
+    hsl: [number, number, number];
+    oklch: [number, number, number];
+    alpha: number;
+  };
+  luminance: number;
+  // false when the input was outside sRGB and has been clipped
+  inGamut: boolean;
+}
+
+export interface ContrastResult {
+  // Opaque colors after compositing
+  foreground: string;
+  background: string;
+  ratio: number;
+  passes: { aa: boolean; aaLarge: boolean; aaa: boolean; aaaLarge: boolean; nonText: boolean };
+  // Nearest foreground that meets AA, or null when it already does
+  suggestion: string | null;
+}
+
+export interface FoundColor {
+  start: number;
+  end: number;
+  line: number;
+  column: number;
+  text: string;
+  hex: string;
+  rgb: [number, number, number];
+  alpha: number;
+}
+
+export interface CssUnitContext {
+  rootFontSize?: number;
+  fontSize?: number;
+  viewportWidth?: number;
+  viewportHeight?: number;
+  // Size in px that 100% refers to; % is unsupported without it
+  percentOf?: number;
+}
+
+export interface CssUnitConversion {
+  value: number;
+  unit: string;
+  px: number;
+  conversions: Record<string, number>;
+  // Set when `to` was given, e.g. "1.5rem"
+  converted?: string;
+}
+
+export interface ContrastFailure {
+  selector: string;
+  text: string;
+  foreground: string;
+  background: string;
+  ratio: number;
+  required: number;
+  large: boolean;
+  fontSize: number;
+  fontWeight: number;
+  suggestion: string | null;
+}
+
+export interface ContrastAudit {
+  url: string;
+  level: 'AA' | 'AAA';
+  checked: number;
+  passed: number;
+  // Text over background images or in unsupported color spaces
+  indeterminate: number;
+  // Worst first
+  failures: ContrastFailure[];
+  truncated: boolean;
+}
+
+export interface LineCounts {
+  files: number;
+  code: number;
+  comment: number;
+  blank: number;
+  lines: number;
+}
+
+export interface CodeStats {
+  rootPath: string;
+  // How ignored files were excluded; null with respectGitignore: false
+  ignoreSource: 'git' | '.gitignore' | null;
+  totals: LineCounts;
+  // Most code first
+  languages: (LineCounts & { id: string; name: string })[];
+  // Per top-level folder; files at the root are under "."
+  directories: (LineCounts & { path: string })[];
+  largestFiles: { path: string; language: string; code: number; comment: number; blank: number }[];
+  // Binary or over-size files, and files in no known language
+  skipped: { binary: number; unknown: number };
+  // Files whose counts came from the cache
+  reused: number;
+  countedAt: string;
+}
+
+export type LicenseEcosystem = 'npm' | 'cargo' | 'python';
+
+// Entries are SPDX identifiers (or package names for `ignore`); "*" matches anything.
+// A non-empty `allowed` list flags every license not on it.
+export interface LicensePolicy {
+  disallowed: string[];
+  allowed: string[];
+  ignore: string[];
+}
+
+export interface LicensedPackage {
+  ecosystem: LicenseEcosystem;
+  name: string;
+  version: string;
+  // As written in the package metadata, or detected from its license files
+  declared: string | null;
+  source: 'metadata' | 'classifier' | 'file' | null;
+  // Relative to the workspace; null for crates cargo hasn't downloaded
+  path: string | null;
+  // Normalized SPDX expression
+  license: string | null;
+  status: 'allowed' | 'disallowed' | 'unknown' | 'ignored';
+  reason: string | null;
+}
+
+export interface LicenseScan {
+  rootPath: string;
+  policy: { source: 'workspace' | 'user'; path: string | null };
+  // Packages found per ecosystem; null when the workspace doesn't use it
+  ecosystems: Partial<Record<LicenseEcosystem, number | null>>;
+  summary: { total: number; allowed: number; disallowed: number; unknown: number; ignored: number };
+  licenses: { license: string; count: number }[];
+  // Disallowed first, then unknown
+  packages: LicensedPackage[];
+  warnings: { ecosystem: LicenseEcosystem; message: string }[];
+  scannedAt: string;
+}
+
+export type ForgeKind = 'github' | 'gitlab';
+
+export interface ForgeRemote {
+  remote: string;
+  kind: ForgeKind;
+  host: string;
+  // Everything before the project name; GitLab groups may be nested
+  owner: string;
+  repo: string;
+  projectPath: string;
+  apiBase: string;
+  webUrl: string;
+}
+
+export type VersionBump = 'major' | 'minor' | 'patch';
+
+export interface ReleaseVersionOptions {
+  // Exact next version; otherwise the current one bumped by `bump` or the recommended bump
+  version?: string;
+  bump?: VersionBump;
+  prefix?: string;
+}
+
+export interface ConventionalCommit {
+  hash: string;
+  type: string;
+  scope: string | null;
+  description: string;
+  // Description of the breaking change, when the commit has one
+  breaking: string | null;
+}
+
+export interface ChangelogSection {
+  repoRoot: string;
+  previousTag: string | null;
+  currentVersion: string | null;
+  version: string;
+  tag: string;
+  recommendedBump: VersionBump;
+  commits: ConventionalCommit[];
+  // Commits that aren't conventional commits
+  skipped: number;
+  markdown: string;
+}
+
+export interface VersionBumpResult {
+  changes: { path: string; name: string | null; from: string; to: string }[];
+  // Every file rewritten, including lock files
+  files: string[];
+  dryRun: boolean;
+}
+
+export interface DraftRelease {
+  id: number;
+  url: string;
+  tag: string;
+  draft: boolean;
+}
+
+export interface PreparedRelease {
+  version: string;
+  tag: string;
+  changelog: ChangelogSection;
+  changes: VersionBumpResult['changes'];
+  files: string[];
+  // null for a dry run
+  commit: string | null;
+  release: DraftRelease | null;
+  dryRun: boolean;
+}
+
+export type CiState = 'queued' | 'running' | 'success' | 'failure' | 'cancelled' | 'skipped';
+
+// A GitHub workflow run or a GitLab pipeline
+export interface CiRun {
+  id: string;
+  name: string;
+  state: CiState;
+  sha: string;
+  event: string | null;
+  url: string;
+  attempt: number;
+  startedAt: string;
+  updatedAt: string;
+}
+
+export interface CiJob {
+  id: string;
+  name: string;
+  state: CiState;
+  url: string;
+  startedAt: string | null;
+  completedAt: string | null;
+  // GitHub only
+  failedStep: string | null;
+}
+
+export interface CiStatus {
+  repoRoot: string;
+  provider: ForgeKind;
+  project: string;
+  // null when HEAD is detached
+  branch: string | null;
+  // Commit the runs are for; `upToDate` is false when that isn't the local HEAD
+  sha: string | null;
+  upToDate: boolean;
+  // A failure wins over runs still in progress; 'none' when CI never ran
+  state: CiState | 'none';
+  runs: CiRun[];
+  pullRequest: { number: number; title: string; url: string } | null;
+  checkedAt: string;
+}
+
+export interface IssueCaptureOptions {
+  rootPath: string;
+  title: string;
+  description?: string;
+  // Linked at the current commit when it has been pushed
+  editor?: { path: string; line?: number; endLine?: number; selection?: string };
+  terminalId?: string;
+  // Defaults to 40, at most 500
+  terminalLines?: number;
+  // Preview to screenshot
+  browserViewId?: string;
+  labels?: string[];
+  remote?: string;
+  dryRun?: boolean;
+}
+
+export interface CapturedIssue {
+  created: boolean;
+  provider: ForgeKind;
+  project: string;
+  title: string;
+  body: string;
+  url: string | null;
+  number: number | null;
+  // GitHub can't take attachments over its API, so the screenshot is saved here to add by hand
+  screenshotPath: string | null;
+}
+
+export type NotebookCellType = 'code' | 'markdown' | 'raw';
+
+// Outputs keep the nbformat shape (output_type, data, text, ...) with text joined into strings
+export type NotebookOutput = { output_type: string } & Record<string, unknown>;
+
+export interface NotebookCell {
+  id: string;
+  cellType: NotebookCellType;
+  source: string;
+  metadata: Record<string, unknown>;
+  executionCount: number | null;
+  outputs: NotebookOutput[];
+  attachments: Record<string, Record<string, string>> | null;
+}
+
+export interface NotebookModel {
+  nbformat: 4;
+  nbformatMinor: number;
+  metadata: Record<string, unknown>;
+  cells: NotebookCell[];
+}
+
+export interface JupyterGateway {
+  url: string;
+  // Name of the secret holding the gateway token
+  tokenSecret?: string;
+}
+
+export interface KernelSpec {
+  name: string;
+  displayName: string;
+  language: string | null;
+  source: 'user' | 'workspace' | 'system' | 'gateway';
+  path: string | null;
+  argv: string[] | null;
+  env: Record<string, string>;
+  interruptMode: 'signal' | 'message';
+}
+
+export interface StartKernelOptions {
+  specName: string;
+  rootPath?: string;
+  // The kernel runs in the notebook's folder
+  notebookPath?: string;
+  gateway?: JupyterGateway;
+}
+
+export type KernelStatus = 'starting' | 'idle' | 'busy' | 'restarting' | 'dead';
+
+export interface KernelInfo {
+  id: string;
+  specName: string;
+  displayName: string;
+  language: string | null;
+  remote: boolean;
+  cwd: string;
+  status: KernelStatus;
+  startedAt: string;
+}
+
+export interface ExecutionResult {
+  msgId: string;
+  status: 'ok' | 'error' | 'aborted';
+  executionCount: number | null;
+  error: string | null;
+}
+
+export interface PdfInfo {
+  path: string;
+  pageCount: number;
+  title: string | null;
+  author: string | null;
+  subject: string | null;
+  creator: string | null;
+  producer: string | null;
+  created: string | null;
+  modified: string | null;
+  pdfVersion: string | null;
+  encrypted: boolean;
+  // Sizes in points (1/72 inch), before rotation
+  pages: { width: number; height: number; rotation: number }[];
+}
+
+export interface PdfOutlineItem {
+  title: string;
+  page: number | null;
+  children: PdfOutlineItem[];
+}
+
+export interface PdfPageImage {
+  page: number;
+  pageCount: number;
+  scale: number;
+  png: Uint8Array;
+  width: number;
+  height: number;
+}
+
+export interface SanitizedSvg {
+  svg: string;
+  removed: { kind: 'element' | 'attribute' | 'reference' | 'css' | 'doctype' | 'instruction'; name: string; count: number }[];
+}
+
+export interface SvgRasterOptions {
+  content?: string;
+  // Pixels; with only one of the two the aspect ratio is kept
+  width?: number;
+  height?: number;
+  // Multiplies the SVG's own size when neither width nor height is given
+  scale?: number;
+  // CSS color; transparent when omitted
+  background?: string;
+}
+
+// resvg when it's on PATH, else Chromium
+export type SvgRenderer = 'resvg' | 'chromium';
+
+export interface SvgRaster {
+  png: Uint8Array;
+  width: number;
+  height: number;
+  renderer: SvgRenderer;
+}
+
+export interface FontInfo {
+  format: 'truetype' | 'opentype' | 'collection' | 'woff' | 'woff2';
+  outlines: 'truetype' | 'cff' | 'bitmap';
+  collectionSize: number;
+  fontIndex: number;
+  family: string | null;
+  style: string | null;
+  fullName: string | null;
+  postScriptName: string | null;
+  version: string | null;
+  designer: string | null;
+  manufacturer: string | null;
+  copyright: string | null;
+  license: string | null;
+  licenseUrl: string | null;
+  weight: number;
+  // Named instances' weights for variable fonts, else the font's own
+  weights: { value: number; name: string | null }[];
+  italic: boolean;
+  widthClass: number;
+  // From OS/2 fsType: what the license allows when embedding the font in documents
+  embedding: 'installable' | 'restricted' | 'preview-and-print' | 'editable';
+  variable: boolean;
+  axes: { tag: string; name: string; min: number; default: number; max: number; hidden: boolean }[];
+  instances: { name: string | null; coordinates: Record<string, number> }[];
+  color: boolean;
+  unitsPerEm: number;
+  glyphCount: number | null;
+  characterCount: number;
+  // e.g. 'Latin', 'Cyrillic', 'Han'
+  scripts: string[];
+}
+
+export interface FontPreviewOptions {
+  fontIndex?: number;
+  // A pangram in the font's first supported script when omitted
+  sampleText?: string;
+  // Pixels, 6 to 512
+  size?: number;
+  width?: number;
+  weight?: number;
+  // Variable font axis values, e.g. { wdth: 75 }
+  variations?: Record<string, number>;
+  color?: string;
+  background?: string;
+}
+
+export interface FontPreview extends FontInfo {
+  sampleText: string;
+  size: number;
+  // Sample characters without a glyph in the font; they render in a fallback font
+  missing: string[];
+  png: Uint8Array;
+  width: number;
+  height: number;
+}
+
+export interface ModelInfo {
+  path: string;
+  format: 'gltf' | 'glb' | 'obj';
+  generator: string | null;
+  version: string | null;
+  meshes: number;
+  primitives: number;
+  vertices: number;
+  triangles: number;
+  // OBJ only
+  faces?: number;
+  normals?: number;
+  texcoords?: number;
+  materials: number;
+  materialNames: string[];
+  textures: number;
+  images: number;
+  // Durations in seconds
+  animations: { name: string; channels: number; duration: number }[];
+  nodes: number;
+  scenes: number;
+  skins: number;
+  cameras: number;
+  extensionsUsed: string[];
+  extensionsRequired: string[];
+  // Referenced buffers, images and material libraries that aren't on disk
+  missing: string[];
+  previewable: boolean;
+  // null when the model has no triangles
+  bounds: { min: [number, number, number]; max: [number, number, number]; size: [number, number, number] } | null;
+}
+
+export interface ModelThumbnailOptions {
+  // Square, 16-1024 pixels (default 256)
+  size?: number;
+  // Camera angles in degrees (defaults 35 and 25)
+  yaw?: number;
+  pitch?: number;
+  // '#rrggbb'; transparent when omitted
+  background?: string;
+}
+
+export interface ModelThumbnail extends ModelInfo {
+  png: Uint8Array;
+  width: number;
+  height: number;
+  // Fewer than `triangles` for very large meshes, which are thinned out
+  renderedTriangles: number;
+}
+
+export interface SchemaProblem {
+  file: string;
+  line: number;
+  column: number;
+  severity: 'error' | 'warning';
+  message: string;
+}
+
+export interface ProtoFieldSummary {
+  name: string;
+  jsonName: string;
+  number: number;
+  label: 'optional' | 'required' | 'repeated' | null;
+  // Fully-qualified for messages and enums; map<K, V> for maps
+  type: string;
+  oneof: string | null;
+}
+
+export interface ProtoSchemaSummary {
+  kind: 'protobuf';
+  file: string;
+  syntax: 'proto2' | 'proto3' | 'editions';
+  edition: string | null;
+  package: string | null;
+  // builtin: not found on disk, so Forge's copy of a google/protobuf well-known type was used
+  imports: { path: string; kind: 'normal' | 'public' | 'weak'; resolved: string | null; builtin: boolean }[];
+  messages: { name: string; fullName: string; line: number; fields: ProtoFieldSummary[]; oneofs: string[] }[];
+  enums: { name: string; fullName: string; line: number; values: { name: string; number: number }[] }[];
+  services: { name: string; fullName: string; line: number; methods: { name: string; inputType: string; outputType: string; clientStreaming: boolean; serverStreaming: boolean }[] }[];
+  extensions: (ProtoFieldSummary & { fullName: string; extendee: string })[];
+  // Every message the decoder can target, imports included
+  messageTypes: string[];
+  problems: SchemaProblem[];
+}
+
+export interface FbsSchemaSummary {
+  kind: 'flatbuffers';
+  file: string;
+  namespace: string | null;
+  includes: string[];
+  rootType: string | null;
+  fileIdentifier: string | null;
+  fileExtension: string | null;
+  tables: {
+    name: string;
+    fullName: string;
+    isStruct: boolean;
+    line: number;
+    fields: { name: string; type: string; id: number | null; default: string | number | boolean | null; deprecated: boolean; required: boolean; key: boolean }[];
+  }[];
+  enums: { name: string; fullName: string; underlying: string; bitFlags: boolean; line: number; values: { name: string; value: number }[] }[];
+  unions: { name: string; fullName: string; line: number; members: { name: string; type: string; value: number }[] }[];
+  services: { name: string; fullName: string; line: number; methods: { name: string; request: string; response: string; streaming: string }[] }[];
+  tableTypes: string[];
+  problems: SchemaProblem[];
+}
+
+export interface SchemaCompileOptions {
+  includePaths?: string[];
+  // A protoc --<language>_out or flatc --<language> generator, e.g. 'python', 'go', 'ts'
+  language?: string;
+  outputDir?: string;
+}
+
+export interface SchemaCompileResult {
+  tool: 'protoc' | 'flatc';
+  language: string | null;
+  outputDir: string | null;
+  success: boolean;
+  problems: SchemaProblem[];
+  output: string;
+}
+
+export type SchemaPayload = Uint8Array | { path: string } | { base64: string } | { hex: string };
+
+export interface SchemaDecodeOptions {
+  includePaths?: string[];
+  // 'grpc' and 'delimited' are protobuf streams, 'size-prefixed' FlatBuffers ones
+  framing?: 'none' | 'grpc' | 'delimited' | 'size-prefixed';
+  // Protobuf only: .proto field names instead of lowerCamelCase JSON names
+  preserveNames?: boolean;
+}
+
+export interface DecodedPayload {
+  kind: 'protobuf' | 'flatbuffers';
+  typeName: string | null;
+  framing: string;
+  // One per frame; fields the schema doesn't know are listed under $unknown
+  messages: unknown[];
+  fileIdentifier?: string | null;
+}
+
+// Log viewer: filters and highlighting run in the main process, only matching lines are sent
+export type LogViewLevel = 'trace' | 'debug' | 'info' | 'warn' | 'error' | 'fatal';
+
+export interface LogViewPattern {
+  pattern: string;
+  regex?: boolean;
+  caseSensitive?: boolean;
+}
+
+export interface LogViewFilters {
+  levels?: LogViewLevel[];
+  pattern?: string;
+  regex?: boolean;
+  caseSensitive?: boolean;
+  invert?: boolean;
+  // e.g. `@level>=warn http.status=500..599 -path=/health`; field terms only match JSON lines
+  query?: string;
+  // Field paths extracted from JSON lines into `LogViewLine.fields`
+  columns?: string[];
+  highlights?: LogViewPattern[];
+}
+
+export interface LogViewLine {
+  offset: number;
+  text: string;
+  level: LogViewLevel | null;
+  // `rule` is the index into `highlights`, or null for the filter pattern
+  highlights: { start: number; end: number; rule: number | null }[];
+  truncated?: boolean;
+  partial?: boolean;
+  json?: boolean;
+  fields?: Record<string, unknown>;
+}
+
+export interface LogViewPage {
+  lines: LogViewLine[];
+  before?: number;
+  atStart?: boolean;
+  after?: number;
+  atEnd?: boolean;
+}
+
+export interface LogViewSnapshot extends LogViewPage {
+  viewId: string;
+  path: string;
+  size: number;
+  position: number;
+  following: boolean;
+}
+
+export type LogSourceId = 'journald' | 'macos' | 'windows';
+
+export interface LogSourceInfo {
+  id: LogSourceId;
+  label: string;
+  description: string;
+  available: boolean;
+  reason: string | null;
+}
+
+// Options for the platform tool; each source reads the ones that apply to it
+export interface LogSourceParams {
+  // Entries below this level are dropped at the source
+  level?: LogViewLevel;
+  // How many past entries to start with (journald, windows)
+  lines?: number;
+  // journald
+  units?: string[];
+  identifiers?: string[];
+  since?: string;
+  boot?: boolean;
+  user?: boolean;
+  // macos
+  predicate?: string;
+  process?: string;
+  // windows
+  logName?: string;
+  providers?: string[];
+}
+
+// Source lines carry entry sequence numbers as their `offset`
+export interface LogSourceSnapshot extends LogViewPage {
+  viewId: string;
+  source: LogSourceId;
+  following: boolean;
+  status: 'following' | 'paused' | 'stopped' | 'error';
+}
+
+export interface LogViewField {
+  path: string;
+  count: number;
+  types: ('string' | 'number' | 'boolean' | 'null' | 'array' | 'object')[];
+  examples: string[];
+  min?: number;
+  max?: number;
+  timestamp: boolean;
+}
+
+export interface LogViewSchema {
+  sampled: number;
+  records: number;
+  fields: LogViewField[];
+  truncated: boolean;
+}
+
+export interface SavedLogQuery {
+  id: string;
+  name: string;
+  query: string;
+  columns: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface TaskConfig {
+  file: string;
+  tasks: { name: string; command: string; cwd: string; dependsOn: string[]; retries: number; continueOnError: boolean; problemMatchers: string[] }[];
+  // Each step is a group of tasks run in parallel
+  pipelines: { name: string; description: string | null; steps: string[][]; onFailure: 'stop' | 'continue' }[];
+}
+
+export type TaskStatus = 'pending' | 'running' | 'succeeded' | 'failed' | 'skipped' | 'cancelled';
+
+export interface TaskResult {
+  status: TaskStatus;
+  attempt?: number;
+  startedAt?: string;
+  exitCode?: number | null;
+  durationMs?: number;
+  // Failed, but continue_on_error let dependents run
+  allowed?: boolean;
+}
+
+export interface TaskRun {
+  runId: string;
+  jobId: string;
+  pipeline: string | null;
+  status: 'running' | 'succeeded' | 'failed' | 'cancelled';
+  tasks: Record<string, TaskResult>;
+  startedAt: string;
+  finishedAt: string | null;
+}
+
+export interface Problem {
+  file: string;
+  line: number;
+  column: number;
+  endLine: number | null;
+  endColumn: number | null;
+  severity: 'error' | 'warning' | 'info';
+  code: string | null;
+  message: string;
+  matcher: string;
+  // "task:<name>", "terminal:<id>", ...
+  source: string;
+}
+
+// Field values are capture group indices; a list takes the first group that matched
+export interface ProblemPattern {
+  regexp: string;
+  file?: number;
+  line?: number | number[];
+  column?: number | number[];
+  endLine?: number;
+  endColumn?: number;
+  severity?: number;
+  code?: number;
+  message?: number;
+  // Last pattern only: keep matching one problem per line
+  loop?: boolean;
+}
+
+export interface ProblemMatcherDefinition {
+  label?: string | null;
+  severity?: 'error' | 'warning' | 'info' | null;
+  pattern: ProblemPattern | ProblemPattern[];
+}
+
+export interface ProblemMatcherInfo {
+  id: string;
+  label: string;
+  builtin: boolean;
+  definition?: ProblemMatcherDefinition;
+}
+
+// Strings may use ${workspaceFolder}
+export interface RunConfigInput {
+  name: string;
+  kind?: 'run' | 'debug';
+  command: string;
+  args?: string[];
+  env?: Record<string, string>;
+  cwd?: string;
+  // A .forge/tasks.toml task or another configuration to run first
+  preLaunchTask?: string | null;
+  // Debugger settings from launch.json (type, request, ...)
+  debug?: Record<string, unknown> | null;
+}
+
+export interface RunConfig extends Required<RunConfigInput> {
+  id: string;
+  source: 'user' | 'launch.json' | 'tasks.json';
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CommandHistoryFilters {
+  cwd?: string;
+  terminalId?: string;
+  successOnly?: boolean;
+  failedOnly?: boolean;
+  // ISO timestamp
+  since?: string;
+  limit?: number;
+  rank?: 'frecency' | 'frequency' | 'recent';
+}
+
+export interface CommandHistoryResult {
+  command: string;
+  count: number;
+  lastUsedAt: string;
+  lastExitCode: number | null;
+  lastCwd: string | null;
+  lastDurationMs: number | null;
+  // Matched character positions, for highlighting
+  positions: number[];
+}
+
+export interface CommandHistorySettings {
+  enabled: boolean;
+  maxEntries: number;
+  excludeSecrets: boolean;
+  ignoreLeadingSpace: boolean;
+}
+
+export interface DirectoryBookmark {
+  id: string;
+  path: string;
+  label: string;
+  createdAt: string;
+  lastUsedAt: string | null;
+  useCount: number;
+}
+
+export interface TerminalLayoutInput {
+  // In tab order. An entry with terminalId takes its shell and current directory from that terminal.
+  terminals: { terminalId?: string; title?: string; shell?: string; cwd?: string; env?: Record<string, string>; startupCommand?: string; group?: number }[];
+  // Renderer split/tab state, stored as given
+  arrangement?: unknown;
+  autoRestore?: boolean;
+}
+
+export interface TerminalLayout {
+  terminals: { title: string; shell: string | null; cwd: string; env: Record<string, string>; startupCommand: string | null; group: number; order: number }[];
+  arrangement: unknown;
+  autoRestore: boolean;
+  savedAt: string;
+}
+
+export interface RestoredTerminal extends TerminalInfo {
+  title: string;
+  group: number;
+  order: number;
+  startupCommand: string | null;
+  commandSkipped: boolean;
+}
+
+// Metadata a backend subsystem attached to a range of terminal output, for hover cards and
+// decorations. Dropped once its output is trimmed from the scrollback.
+export interface TerminalAnnotation {
+  id: string;
+  terminalId: string;
+  start: TerminalPosition;
+  end: TerminalPosition;
+  // 'problems', 'ai', ...
+  source: string;
+  // 'problem', 'explanation', ...
+  kind: string;
+  message: string | null;
+  data: unknown;
+  createdAt: string;
+}
+
+export type WatchEventKind = 'add' | 'change' | 'unlink' | 'rename' | 'addDir' | 'unlinkDir';
+
+// One entry of a watcher:changes batch; renames carry the path the file moved from
+export interface WatchChange {
+  type: WatchEventKind;
+  path: string;
+  oldPath?: string;
+}
+
+export interface WatchSubscription {
+  subscriptionId: string;
+  root: string;
+  globs: string[];
+  kinds: WatchEventKind[];
+}
+
+// A shared watcher and how many interests use it; depth 0 watches one directory
+export interface WatchHubRoot {
+  root: string;
+  depth: number | null;
+  files: number;
+  directories: number;
+  trees: number;
+  health: WatcherHealth;
+}
+
+export interface SaveHook {
+  id: string;
+  title: string;
+  phase: 'pre' | 'post';
+  order: number;
+  timeoutMs: number;
+  enabled: boolean;
+  source: string;
+}
+
+export interface SaveHookReport {
+  id: string;
+  phase: 'pre' | 'post';
+  status: 'ok' | 'changed' | 'skipped' | 'failed' | 'timedOut';
+  message: string | null;
+  durationMs: number;
+}
+
+export interface SaveResult {
+  path: string;
+  content: string; // what was written, after pre-save hooks
+  changed: boolean;
+  hooks: SaveHookReport[];
+}
+
+// ${file} and ${workspaceFolder} in args are expanded
+export interface SaveFormatter {
+  pattern: string;
+  command: string;
+  args: string[];
+}
+
+export interface SaveLinter extends SaveFormatter {
+  name: string;
+  matchers: string[]; // problem matcher ids
+}
+
+export interface SaveTools {
+  formatters: SaveFormatter[];
+  linters: SaveLinter[];
+}
+
+export interface ProjectSnapshotOptions {
+  targetPath?: string;
+  maxFileSize?: number; // bytes; larger files are listed as skipped
+}
+
+export interface ProjectSnapshotManifest {
+  format: 'forge-project-snapshot';
+  version: number;
+  createdAt: string;
+  forgeVersion: string;
+  platform: string;
+  projectName: string;
+  git: { sha: string | null; branch: string | null; dirty: boolean } | null;
+  ignoreSource: 'git' | '.gitignore';
+  files: number;
+  totalSize: number;
+  skipped: { path: string; reason: 'symlink' | 'notAFile' | 'tooLarge'; size?: number }[];
+}
+
+export interface ProjectSnapshot {
+  path: string;
+  manifest: ProjectSnapshotManifest;
+}
+
+export interface LanguageComments {
+  line: string | null;
+  block: [string, string] | null;
+}
+
+// Regular expression sources, tested against a line
+export interface LanguageIndentation {
+  increasePattern?: string;
+  decreasePattern?: string;
+  offSide?: boolean; // blocks are defined by indentation, as in Python
+}
+
+export interface LanguageDefinition {
+  name?: string;
+  extensions?: string[];
+  filenames?: string[];
+  interpreters?: string[]; // patterns for a shebang's interpreter
+  comments?: Partial<LanguageComments>;
+  indentation?: LanguageIndentation;
+}
+
+export interface LanguageConfig {
+  id: string;
+  name: string;
+  extensions: string[];
+  filenames: string[];
+  interpreters: string[];
+  comments: LanguageComments;
+  indentation: LanguageIndentation;
+  sources: string[]; // 'builtin', plugin names, 'user'
+}
+
+export interface SpellcheckOptions {
+  languageId?: string; // plain text when omitted
+  dictionary?: string; // e.g. "en_US"
+  strings?: boolean; // check string literals in code, default true
+}
+
+export interface Misspelling {
+  word: string;
+  offset: number;
+  length: number;
+  line: number; // 1-based
+  column: number; // 1-based
+}
+
+export interface SpellcheckResult {
+  dictionary: string;
+  misspellings: Misspelling[];
+  truncated: boolean;
+}
+
+export interface SpellcheckDictionary {
+  id: string;
+  path: string;
+  source: 'user' | 'system';
+}
+
+export interface LspRange {
+  start: LspPosition;
+  end: LspPosition;
+}
+
+export interface StructureOptions {
+  text?: string;
+}
+
+export interface BracketPair {
+  open: LspPosition;
+  close: LspPosition | null; // null when the bracket is never closed
+}
+
+// 1-based lines, inclusive
+export interface FoldRegion {
+  startLine: number;
+  endLine: number;
+}
+
+export interface PrintOptions {
+  pdfPath?: string;
+  landscape?: boolean;
+  lineNumbers?: boolean;
+}
+
+export interface PrintFileOptions extends PrintOptions {
+  content?: string; // the editor's text, for unsaved changes
+}
+
+// `printed` is false when the print dialog was cancelled
+export type PrintResult = { printed: boolean } | { path: string };
+
+export interface CompareOptions {
+  open?: boolean;
+  // Image colour distance (0-1) below which pixels count as equal; default 0.1
+  threshold?: number;
+}
+
+// 1-based; lines are prefixed ' ', '-' or '+' as in a unified diff
+export interface DiffHunk {
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  lines: string[];
+}
+
+export interface CompareSessionBase {
+  id: string;
+  left: { path: string };
+  createdAt: string;
+}
+
+export interface ByteDiff {
+  leftSize: number;
+  rightSize: number;
+  differentBytes: number;
+  ranges: { start: number; end: number }[]; // nearby differences are merged
+  truncated: boolean;
+}
+
+export interface ImageDiff {
+  left: { format: string; width: number; height: number };
+  right: { format: string; width: number; height: number };
+  width: number;
+  height: number;
+  differentPixels: number;
+  ratio: number;
+  // PNG data URL: the left image faded, changed pixels red, area only one image covers magenta
+  diff: string;
+}
+
+// Text is diffed by line (hunks); binary files report bytes, and image for decodable images
+export interface TextCompareResult {
+  binary: boolean;
+  identical: boolean;
+  hunks: DiffHunk[];
+  added: number;
+  removed: number;
+  bytes?: ByteDiff;
+  image?: ImageDiff | null;
+}
+
+export type CompareSession =
+  | (CompareSessionBase & TextCompareResult & { kind: 'files'; right: { path: string } })
+  | (CompareSessionBase & TextCompareResult & { kind: 'content'; right: { label: string; content: string } })
+  | (Omit<CompareSessionBase, 'left'> & TextCompareResult & { kind: 'revision'; left: { path: string; revision: string }; right: { path: string } })
+  | (CompareSessionBase & {
+      kind: 'directories';
+      right: { path: string };
+      // Relative paths; added are only on the right, removed only on the left
+      added: string[];
+      removed: string[];
+      modified: string[];
+      unchanged: number;
+    });
+
+export type DecorationColor = 'red' | 'orange' | 'yellow' | 'green' | 'teal' | 'blue' | 'purple' | 'pink' | 'gray';
+
+export interface FileDecorationInput {
+  color?: DecorationColor | string | null; // a named colour or #rrggbb
+  badge?: string | null; // one or two characters
+  label?: string | null;
+}
+
+export interface FileDecoration {
+  color: string | null;
+  badge: string | null;
+  label: string | null;
+  // Assigned automatically from the path, git and the file system
+  badges: ('generated' | 'ignored' | 'symlink')[];
+}
+
+export type FavoriteTarget =
+  | { kind?: 'file' | 'directory'; path: string; label?: string }
+  | { kind: 'terminalProfile'; profileId: string; label?: string };
+
+export interface Favorite {
+  id: string;
+  kind: 'file' | 'directory' | 'terminalProfile';
+  path?: string;
+  profileId?: string;
+  label: string;
+  workspaceId: string | null; // null when pinned for every workspace
+  pinnedAt: string;
+  exists?: boolean; // files and folders only; false once moved or deleted
+}
+
+export type ActivityTarget = { kind: 'file'; path: string } | { kind: 'terminal'; terminalId: string };
+
+export interface ActivityRange {
+  // Local YYYY-MM-DD dates, both inclusive
+  from?: string;
+  to?: string;
+  // Only time spent in this workspace folder
+  workspace?: string;
+}
+
+export interface ActivityItem {
+  kind: 'file' | 'terminal';
+  // The file path, or the directory a terminal was in
+  target: string;
+  workspace: string | null;
+  seconds: number;
+}
+
+export interface ActivitySummary {
+  from: string;
+  to: string;
+  totalSeconds: number;
+  // Days with no activity are left out
+  days: { date: string; seconds: number; items: ActivityItem[] }[];
+  items: ActivityItem[];
+  workspaces: { workspace: string | null; seconds: number }[];
+}
+
+export interface ActivitySettings {
+  enabled: boolean;
+  // No input for this long stops the clock
+  idleMinutes: number;
+}
+
+export interface DevServer {
+  url: string;
+  port: number;
+  // 'terminal': printed by a terminal in the workspace; 'port': answering on a common port
+  source: 'terminal' | 'port';
+  terminalId?: string;
+}
+
+export interface StartPageData {
+  workspace: string | null;
+  devServers: DevServer[];
+  recent: BrowserHistoryEntry[];
+  bookmarks: BrowserBookmark[];
+}
+
+export interface BrowserProfileOptions {
+  profile?: string;
+}
+
+export interface BrowserHistoryEntry {
+  url: string;
+  title: string;
+  visitCount: number;
+  // Visits entered in the address bar rather than followed links
+  typedCount: number;
+  firstVisitedAt: string;
+  lastVisitedAt: string;
+  // The workspace folder of the window it was last visited from
+  workspace: string | null;
+}
+
+export interface BrowserBookmark {
+  id: string;
+  url: string;
+  title: string;
+  folder: string | null;
+  createdAt: string;
+}
+
+export interface BrowserAutocomplete {
+  query: string;
+  // Text to show after the caret when the top suggestion starts with the query
+  inline: string | null;
+  suggestions: { url: string; title: string; bookmarked: boolean; visitCount: number; lastVisitedAt: string | null; score: number }[];
+}
+
+export interface BrowserInputSuggestion {
+  // 'navigate': the typed text as a URL
+  kind: 'navigate' | 'search' | 'devServer' | 'history' | 'bookmark';
+  url: string;
+  // The URL with a unicode host
+  display: string;
+  title: string;
+}
+
+export interface BrowserInputResolution {
+  input: string;
+  kind: 'url' | 'localhost' | 'file' | 'internal' | 'search' | 'invalid';
+  // Normalized: scheme filled in, host punycoded; null when invalid
+  url: string | null;
+  display: string | null;
+  error: string | null;
+  inline: string | null;
+  suggestions: BrowserInputSuggestion[];
+}
+
+export interface BrowserFrame {
+  // Chromium's frame tree node id; stable while the frame exists
+  frameId: number | null;
+  frameUrl: string | null;
+  frameOrigin: string | null;
+  frameName: string | null;
+  isMainFrame: boolean;
+}
+
+export interface BrowserConsoleEntry extends BrowserFrame {
+  id: number;
+  viewId: string;
+  windowId: number;
+  timestamp: string;
+  // 'error' and 'rejection' are uncaught, captured by the frame hook with a stack
+  kind: 'console' | 'error' | 'rejection';
+  level: 'debug' | 'info' | 'warning' | 'error';
+  message: string;
+  source: string | null;
+  line: number | null;
+  column: number | null;
+  stack: string | null;
+}
+
+export interface BrowserConsoleFilters {
+  frameId?: number;
+  // Substring of the frame URL
+  frameUrl?: string;
+  origin?: string;
+  // This level and above
+  level?: BrowserConsoleEntry['level'];
+  kind?: BrowserConsoleEntry['kind'];
+  // Only entries with a larger id
+  after?: number;
+  limit?: number;
+}
+
+export interface SourceFrameTarget {
+  path: string;
+  line: number;
+  column: number;
+}
+
+export interface ResolvedStackFrame {
+  functionName: string | null;
+  // The generated script position from the stack, 1-based
+  url: string;
+  line: number;
+  column: number;
+  // Where the source map says it came from, 1-based
+  original: { source: string | null; line: number; column: number; name: string | null; hasContent: boolean } | null;
+  // The original source as a file in the workspace
+  path: string | null;
+  // What "open in editor" opens: the original file, else a local generated file
+  target: SourceFrameTarget | null;
+  // Why the map could not be loaded
+  error: string | null;
+}
+
+export interface ResolvedStack {
+  message: string | null;
+  frames: ResolvedStackFrame[];
+}
+
+// 'goto' loads a URL the user opened; 'navigate' waits for one the page went to itself.
+// Element actions carry a selector unique when recorded, and `frame` selectors for iframes.
+export interface BrowserAction {
+  type: 'goto' | 'navigate' | 'click' | 'fill' | 'check' | 'select' | 'press';
+  // Milliseconds since recording started
+  at: number;
+  url?: string;
+  selector?: string | null;
+  testId?: string | null;
+  tag?: string;
+  text?: string | null;
+  label?: string | null;
+  frame?: string[];
+  frameUrl?: string;
+  button?: number;
+  value?: string;
+  // Password fields: the value isn't recorded
+  redacted?: boolean;
+  checked?: boolean;
+  values?: string[];
+  // e.g. 'Enter', 'Control+s'
+  key?: string;
+}
+
+export interface BrowserActionScript {
+  format: 'forge-browser-actions';
+  version: number;
+  recordedAt: string;
+  startUrl: string;
+  viewport: { width: number; height: number };
+  actions: BrowserAction[];
+}
+
+export interface BrowserReplayResult {
+  completed: number;
+  total: number;
+  error: { index: number; message: string } | null;
+}
+
+// Endpoints carry an access token; `controlled` lists previews a client is driving
+export interface AutomationBridgeStatus {
+  running: boolean;
+  port: number | null;
+  endpoint: string | null;
+  wsEndpoint: string | null;
+  clients: number;
+  controlled: string[];
+}
+
+// Throughputs in bytes per second (-1 is unthrottled), latency in ms
+export interface BrowserNetworkConditions {
+  label: string;
+  latency: number;
+  downloadThroughput: number;
+  uploadThroughput: number;
+  offline: boolean;
+}
+
+export interface BrowserThrottling {
+  id: string;
+  network: (BrowserNetworkConditions & { profile: string }) | null;
+  cpuRate: number;
+}
+
+// Where pages in a window's previews may navigate on their own; typed URLs always load.
+// 'localhost' allows local servers and files; 'allowList' adds `allowedHosts` ("*.example.com")
+export interface BrowserNavigationPolicy {
+  mode: 'any' | 'localhost' | 'allowList';
+  allowedHosts: string[];
+}
+
 export type PathName = 'home' | 'appData' | 'userData' | 'temp' | 'desktop' | 'documents' | 'downloads' | 'pictures' | 'videos';
 
 // Check if we're in Electron environment
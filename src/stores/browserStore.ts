@@ -67,7 +67,7 @@ export const use_browser_store = create<BrowserState>()(
       
       settings: {
         default_search_engine: 'https://www.google.com/search?q=',
-        homepage: 'https://www.google.com',
+        homepage: 'forge://start',
         enable_javascript: true,
         enable_cookies: true,
         enable_popups: false,
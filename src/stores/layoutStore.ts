@@ -1,6 +1,7 @@
 import { create } from 'zustand';
 import { persist } from 'zustand/middleware';
 import { getStorage } from '@/lib/zustand-electron-storage';
+import type { BrowserSessionView } from '@/services/electron';
 
 export type AppMode = 'developer' | 'studio';
 export type Theme = 'light' | 'dark';
@@ -14,6 +15,8 @@ export interface Tab {
   isActive: boolean;
   isDirty?: boolean;
   isPinned?: boolean;
+  // Browser tabs reopened from the workspace's saved session
+  browserState?: BrowserSessionView;
 }
 
 export interface LayoutState {
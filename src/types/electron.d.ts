@@ -1,7 +1,7 @@
 // Type definitions for Electron IPC API
 
 interface UpdaterStatus {
-  status: 'checking-for-update' | 'update-available' | 'update-not-available' | 'update-error' | 'download-progress' | 'update-downloaded';
+  status: 'checking-for-update' | 'update-available' | 'update-not-available' | 'update-error' | 'download-progress' | 'update-downloaded' | 'update-deferred';
   data?: any;
 }
 
@@ -17,6 +17,23 @@ interface UpdateInfo {
   releaseNotes?: string;
 }
 
+type UpdateChannel = 'stable' | 'beta';
+
+interface UpdateReleaseNotes {
+  version: string;
+  releaseDate?: string;
+  notes: string;
+}
+
+interface UpdateState {
+  currentVersion: string;
+  channel: UpdateChannel;
+  checkOnStartup: boolean;
+  availableVersion: string | null;
+  downloadedVersion: string | null;
+  deferredVersion: string | null;
+}
+
 interface ElectronAPI {
   terminal: {
     create: (options: any) => Promise<string>;
@@ -99,10 +116,16 @@ interface ElectronAPI {
     setAlwaysOnTop: (flag: boolean) => Promise<void>;
   };
   updater: {
-    check: () => Promise<void>;
+    check: () => Promise<UpdateInfo | null>;
     download: () => Promise<void>;
     install: () => Promise<void>;
     getVersion: () => Promise<string>;
+    deferInstall: () => Promise<string>;
+    getChannel: () => Promise<{ channel: UpdateChannel; channels: UpdateChannel[] }>;
+    setChannel: (channel: UpdateChannel) => Promise<UpdateInfo | null>;
+    setCheckOnStartup: (enabled: boolean) => Promise<boolean>;
+    getReleaseNotes: () => Promise<UpdateReleaseNotes | null>;
+    getState: () => Promise<UpdateState>;
     onStatus: (callback: (status: UpdaterStatus) => void) => void;
   };
   removeAllListeners: (channel: string) => void;
@@ -119,4 +142,4 @@ declare global {
   }
 }
 
-export { ElectronAPI, BrowserNewTabEvent, UpdaterStatus, UpdateProgress, UpdateInfo };
\ No newline at end of file
+export { ElectronAPI, BrowserNewTabEvent, UpdaterStatus, UpdateProgress, UpdateInfo, UpdateChannel, UpdateReleaseNotes, UpdateState };
\ No newline at end of file
@@ -0,0 +1,47 @@
+require('./support/setup');
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const { registerCommand, executeCommand, listCommands, setContext, setupCommandHandlers } = require('../../electron/commands');
+const { BrowserWindow } = require('electron');
+
+setupCommandHandlers({ handle() {} });
+
+test('passes arguments and the calling window to handlers', async () => {
+  const calls = [];
+  registerCommand({ id: 'test.echo', title: 'Echo', handler: (...args) => calls.push(args) });
+  const win = new BrowserWindow();
+  await executeCommand('test.echo', ['a', 1], { sender: win.webContents });
+  assert.equal(calls[0].length, 3);
+  assert.deepEqual(calls[0].slice(0, 2), ['a', 1]);
+  assert.equal(calls[0][2].window, win);
+});
+
+test('toggles always-on-top on the calling window, with or without arguments', async () => {
+  const win = new BrowserWindow();
+  let onTop = false;
+  win.isAlwaysOnTop = () => onTop;
+  win.setAlwaysOnTop = (value) => { onTop = value; };
+  assert.equal(await executeCommand('window.toggleAlwaysOnTop', [], { sender: win.webContents }), true);
+  assert.equal(await executeCommand('window.toggleAlwaysOnTop', ['ignored'], { sender: win.webContents }), false);
+  assert.equal(await executeCommand('window.toggleAlwaysOnTop', []), false);
+});
+
+test('evaluates when clauses against context keys', async () => {
+  registerCommand({ id: 'test.editorOnly', title: 'Editor only', when: 'editorFocus && !editorReadOnly', handler: () => true });
+  const enabled = () => listCommands().find((command) => command.id === 'test.editorOnly').enabled;
+  assert.equal(enabled(), false);
+  await assert.rejects(executeCommand('test.editorOnly'), /not enabled/);
+  setContext('editorFocus', true);
+  assert.equal(enabled(), true);
+  assert.equal(await executeCommand('test.editorOnly'), true);
+  setContext('editorFocus', null);
+  assert.equal(enabled(), false);
+});
+
+test('lists commands in the palette unless they opt out', () => {
+  registerCommand({ id: 'test.needsArgs', title: 'Needs args', palette: false, handler: () => {} });
+  const byId = new Map(listCommands().map((command) => [command.id, command]));
+  assert.equal(byId.get('test.needsArgs').palette, false);
+  assert.equal(byId.get('workbench.openSettings').palette, true);
+  assert.equal(byId.get('workbench.openSettings').handledBy, 'renderer');
+});
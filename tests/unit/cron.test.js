@@ -0,0 +1,31 @@
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const { parseCron, describeCron, nextRuns, CronError } = require('../../electron/cron');
+
+const iso = (runs) => runs.map((run) => new Date(run).toISOString());
+
+test('describes a schedule in words', () => {
+  assert.equal(describeCron(parseCron('*/15 9-17 * * 1-5')), 'Every 15 minutes past hour 9 through 17, on Monday through Friday');
+  assert.equal(describeCron(parseCron('@yearly')), 'At 00:00, on day 1 of the month, in January');
+});
+
+test('lists the next runs after a given instant', () => {
+  const runs = nextRuns(parseCron('0 9 * * *'), { count: 2, from: Date.UTC(2026, 0, 1, 10), timezone: 'UTC' });
+  assert.deepEqual(iso(runs), ['2026-01-02T09:00:00.000Z', '2026-01-03T09:00:00.000Z']);
+});
+
+test('skips wall times that do not exist on a DST change', () => {
+  // 02:30 doesn't happen in New York on 2026-03-08
+  const runs = nextRuns(parseCron('30 2 * * *'), { count: 2, from: Date.UTC(2026, 2, 7, 12), timezone: 'America/New_York' });
+  assert.deepEqual(iso(runs), ['2026-03-09T06:30:00.000Z', '2026-03-10T06:30:00.000Z']);
+});
+
+test('returns no runs for a date that never occurs', () => {
+  assert.deepEqual(nextRuns(parseCron('0 0 31 2 *'), { count: 2 }), []);
+});
+
+test('rejects malformed expressions', () => {
+  for (const expression of ['', '* * *', '61 * * * *', '@reboot']) {
+    assert.throws(() => parseCron(expression), CronError, expression);
+  }
+});
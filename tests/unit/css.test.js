@@ -0,0 +1,27 @@
+require('./support/setup');
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const { convertColor, parseColor } = require('../../electron/css');
+
+test('converts a hex color to every representation', () => {
+  const color = convertColor('#ff0000');
+  assert.equal(color.hex, '#ff0000');
+  assert.equal(color.rgb, 'rgb(255 0 0)');
+  assert.equal(color.hsl, 'hsl(0 100% 50%)');
+  assert.equal(color.name, 'red');
+});
+
+test('normalizes hues that round up to 360', () => {
+  const color = convertColor('hsl(359.97 100% 50%)');
+  assert.equal(color.hsl, 'hsl(0 100% 50%)');
+  assert.equal(color.hwb, 'hwb(0 0% 0%)');
+  assert.ok(color.components.hsl[0] < 360);
+});
+
+test('keeps alpha in modern syntax', () => {
+  assert.equal(convertColor('rgb(0 0 255 / 50%)').rgb, 'rgb(0 0 255 / 50%)');
+});
+
+test('rejects text that is not a color', () => {
+  assert.throws(() => parseColor('not-a-color'));
+});
@@ -0,0 +1,40 @@
+const { root, fakeIpcMain } = require('./support/setup');
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const fs = require('fs');
+const path = require('path');
+const { setupFileSystemHandlers } = require('../../electron/filesystem');
+
+const ipcMain = fakeIpcMain();
+setupFileSystemHandlers(ipcMain);
+
+test('copies a tree with its empty directories and symlinks', async () => {
+  const source = fs.mkdtempSync(path.join(root, 'copy-'));
+  fs.mkdirSync(path.join(source, 'empty'));
+  fs.mkdirSync(path.join(source, 'src'));
+  fs.writeFileSync(path.join(source, 'src', 'main.js'), 'main');
+  fs.symlinkSync('src/main.js', path.join(source, 'link.js'));
+  fs.symlinkSync('src', path.join(source, 'link-dir'));
+  const target = `${source}-copy`;
+
+  await ipcMain.invoke('fs:copy', null, source, target);
+
+  assert.ok(fs.statSync(path.join(target, 'empty')).isDirectory());
+  assert.equal(fs.readFileSync(path.join(target, 'src', 'main.js'), 'utf8'), 'main');
+  assert.equal(fs.readlinkSync(path.join(target, 'link.js')), 'src/main.js');
+  assert.equal(fs.readlinkSync(path.join(target, 'link-dir')), 'src');
+  assert.ok(fs.lstatSync(path.join(target, 'link-dir')).isSymbolicLink());
+});
+
+test('copies a single symlink as a symlink', async () => {
+  const dir = fs.mkdtempSync(path.join(root, 'copy-'));
+  fs.writeFileSync(path.join(dir, 'file'), 'x');
+  fs.symlinkSync('file', path.join(dir, 'link'));
+  await ipcMain.invoke('fs:copy', null, path.join(dir, 'link'), path.join(dir, 'link-copy'));
+  assert.equal(fs.readlinkSync(path.join(dir, 'link-copy')), 'file');
+});
+
+test('refuses paths outside the allowed directories', async () => {
+  await assert.rejects(ipcMain.invoke('fs:copy', null, '/etc/hostname', path.join(root, 'hostname')), { code: 'ACCESS_DENIED' });
+  await assert.rejects(ipcMain.invoke('fs:readFile', null, '/etc/hostname'), { code: 'ACCESS_DENIED' });
+});
@@ -0,0 +1,82 @@
+const { root, fakeIpcMain } = require('./support/setup');
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const fs = require('fs');
+const path = require('path');
+const { pathToFileURL } = require('url');
+const { enforceGuardrails } = require('../../electron/guardrails');
+const { setupRefactorHandlers } = require('../../electron/refactor');
+
+const ipcMain = fakeIpcMain();
+setupRefactorHandlers(ipcMain);
+
+function project(files) {
+  const dir = fs.mkdtempSync(path.join(root, 'project-'));
+  for (const [name, content] of Object.entries(files)) {
+    fs.writeFileSync(path.join(dir, name), content);
+  }
+  return dir;
+}
+
+test('blocks operations on protected paths', () => {
+  assert.throws(() => enforceGuardrails('delete', [root]), { code: 'ACCESS_DENIED' });
+  assert.throws(() => enforceGuardrails('replace', ['/etc/hosts']), { code: 'ACCESS_DENIED' });
+});
+
+test('asks for confirmation and accepts its token once', () => {
+  const target = path.join(root, 'Documents');
+  let token;
+  assert.throws(() => enforceGuardrails('delete', [target]), (error) => {
+    assert.equal(error.code, 'CONFIRMATION_REQUIRED');
+    token = error.details.confirmationToken;
+    return true;
+  });
+  assert.doesNotThrow(() => enforceGuardrails('delete', [target], { confirmationToken: token }));
+  assert.throws(() => enforceGuardrails('delete', [target], { confirmationToken: token }), { code: 'CONFIRMATION_REQUIRED' });
+});
+
+test('a confirmation token only covers the paths it was issued for', () => {
+  let token;
+  try {
+    enforceGuardrails('delete', [path.join(root, 'Music')]);
+  } catch (error) {
+    token = error.details.confirmationToken;
+  }
+  assert.throws(() => enforceGuardrails('delete', [path.join(root, 'Pictures')], { confirmationToken: token }), { code: 'CONFIRMATION_REQUIRED' });
+});
+
+test('refuses workspace edits outside the allowed directories', async () => {
+  const edit = {
+    changes: {
+      [pathToFileURL('/etc/passwd').href]: [{ range: { start: { line: 0, character: 0 }, end: { line: 0, character: 0 } }, newText: 'x' }]
+    }
+  };
+  await assert.rejects(ipcMain.invoke('refactor:applyWorkspaceEdit', null, edit), { code: 'ACCESS_DENIED' });
+});
+
+test('requires a project folder inside the allowed directories to replace in', async () => {
+  const request = { query: 'a', replacement: 'b' };
+  await assert.rejects(ipcMain.invoke('refactor:replaceInProject', null, { ...request, rootPath: '' }), { code: 'INVALID_ARGUMENT' });
+  await assert.rejects(ipcMain.invoke('refactor:replaceInProject', null, { ...request, rootPath: '/etc' }), { code: 'ACCESS_DENIED' });
+});
+
+test('expands regex replacements with the context of the whole line', async () => {
+  const dir = project({ 'a.txt': 'foo.bar foo.baz\nbar foo.bar\n' });
+  const result = await ipcMain.invoke('refactor:replaceInProject', null, {
+    rootPath: dir,
+    query: '(?<=foo\\.)ba(r|z)',
+    replacement: 'qu$1',
+    options: { regex: true }
+  });
+  assert.equal(result.replacements, 3);
+  assert.equal(fs.readFileSync(path.join(dir, 'a.txt'), 'utf8'), 'foo.qur foo.quz\nbar foo.qur\n');
+
+  const anchored = await ipcMain.invoke('refactor:replaceInProject', null, {
+    rootPath: dir,
+    query: '^(\\w+)',
+    replacement: '<$1>',
+    options: { regex: true }
+  });
+  assert.equal(anchored.replacements, 2);
+  assert.equal(fs.readFileSync(path.join(dir, 'a.txt'), 'utf8'), '<foo>.qur foo.quz\n<bar> foo.qur\n');
+});
@@ -0,0 +1,57 @@
+const { root } = require('./support/setup');
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const fs = require('fs');
+const net = require('net');
+const path = require('path');
+const { startInstanceServer, stopInstanceServer } = require('../../electron/instanceServer');
+const { encodeMessage, createMessageReader } = require('../../electron/cliProtocol');
+
+const skip = process.platform === 'win32' && 'Unix sockets only';
+
+function useRuntimeDir() {
+  const runtimeDir = fs.mkdtempSync(path.join(root, 'run-'));
+  delete process.env.FORGE_IPC_PATH;
+  process.env.XDG_RUNTIME_DIR = runtimeDir;
+  return path.join(runtimeDir, 'forge');
+}
+
+function request(socketPath, message) {
+  return new Promise((resolve, reject) => {
+    const socket = net.connect(socketPath);
+    socket.on('error', reject);
+    socket.on('data', createMessageReader((reply) => {
+      socket.destroy();
+      resolve(reply);
+    }));
+    socket.write(encodeMessage(message));
+  });
+}
+
+test('refuses a socket directory other users can read', { skip }, async () => {
+  const dir = useRuntimeDir();
+  fs.mkdirSync(dir, { mode: 0o755 });
+  fs.chmodSync(dir, 0o755);
+  await assert.rejects(startInstanceServer(), { code: 'ACCESS_DENIED' });
+});
+
+test('refuses a socket directory that is a symlink', { skip }, async () => {
+  const dir = useRuntimeDir();
+  const real = fs.mkdtempSync(path.join(root, 'elsewhere-'));
+  fs.chmodSync(real, 0o700);
+  fs.symlinkSync(real, dir);
+  await assert.rejects(startInstanceServer(), { code: 'ACCESS_DENIED' });
+});
+
+test('listens on an owner-only socket in the runtime directory', { skip }, async (t) => {
+  const dir = useRuntimeDir();
+  const socketPath = await startInstanceServer();
+  t.after(stopInstanceServer);
+
+  assert.equal(socketPath, path.join(dir, 'instance.sock'));
+  assert.equal(fs.statSync(dir).mode & 0o777, 0o700);
+  assert.equal(fs.statSync(socketPath).mode & 0o777, 0o600);
+  const reply = await request(socketPath, { id: 1, method: 'ping' });
+  assert.equal(reply.id, 1);
+  assert.equal(reply.result.pid, process.pid);
+});
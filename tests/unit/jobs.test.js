@@ -0,0 +1,58 @@
+const { userData } = require('./support/setup');
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const fs = require('fs');
+const path = require('path');
+const { runJob, listJobs } = require('../../electron/jobs');
+const { guardTask } = require('../../electron/crash');
+const { ForgeError, ErrorCode } = require('../../electron/errors');
+
+const crashDir = path.join(userData, 'crash-reports');
+const reports = () => (fs.existsSync(crashDir) ? fs.readdirSync(crashDir) : []);
+
+test('reports progress and the result of a job', async () => {
+  const job = runJob('test', 'Counting', async (handle) => {
+    handle.reportProgress(0.5, 'halfway');
+    assert.equal(listJobs().find((entry) => entry.id === handle.id).progress, 0.5);
+    return 42;
+  });
+  assert.equal(await job.promise, 42);
+  assert.equal(listJobs(true).find((entry) => entry.id === job.id).status, 'succeeded');
+});
+
+test('captures a crash report for a bug in a job', async () => {
+  const before = reports().length;
+  const job = runJob('test', 'Buggy', async () => {
+    null.property;
+  });
+  await assert.rejects(job.promise, { code: ErrorCode.INTERNAL });
+  assert.equal(reports().length, before + 1);
+  assert.equal(listJobs(true).find((entry) => entry.id === job.id).status, 'failed');
+});
+
+test('does not capture expected failures of a job', async () => {
+  const before = reports().length;
+  const job = runJob('test', 'Missing', async () => {
+    throw new ForgeError(ErrorCode.NOT_FOUND, 'Nothing here');
+  });
+  await assert.rejects(job.promise, { code: ErrorCode.NOT_FOUND });
+  assert.equal(reports().length, before);
+});
+
+test('marks a job cancelled when its task stops for cancellation', async () => {
+  const job = runJob('test', 'Cancelled', async () => {
+    throw new ForgeError(ErrorCode.CANCELLED, 'Stopped');
+  });
+  await assert.rejects(job.promise, { code: ErrorCode.CANCELLED });
+  assert.equal(listJobs(true).find((entry) => entry.id === job.id).status, 'cancelled');
+});
+
+test('guardTask swallows and captures failures of background tasks', async () => {
+  const before = reports().length;
+  assert.equal(await guardTask('test', async () => {
+    throw new Error('background failure');
+  }), undefined);
+  assert.equal(reports().length, before + 1);
+  const report = JSON.parse(fs.readFileSync(path.join(crashDir, reports().sort().pop()), 'utf8'));
+  assert.equal(report.source, 'task:test');
+});
@@ -0,0 +1,125 @@
+// Loaded first by every unit test. The backend modules require('electron') and node-pty at
+// load time; outside Electron those are stubbed here, with app paths pointing at a fresh
+// temp directory per test process.
+
+const Module = require('module');
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+
+const root = fs.realpathSync(fs.mkdtempSync(path.join(os.tmpdir(), 'forge-unit-')));
+const userData = path.join(root, 'userData');
+fs.mkdirSync(userData, { recursive: true });
+process.on('exit', () => fs.rmSync(root, { recursive: true, force: true }));
+
+// Windows opened through the stub, so tests can check none were created
+const createdWindows = [];
+
+class BrowserWindow {
+  constructor(options = {}) {
+    this.id = createdWindows.length + 1000;
+    this.options = options;
+    this.destroyed = false;
+    this.webContents = {
+      id: this.id,
+      isLoading: () => false,
+      isDestroyed: () => this.destroyed,
+      send() {},
+      on() {},
+      once() {},
+      loadURL: async () => {},
+      setWindowOpenHandler() {}
+    };
+    createdWindows.push(this);
+  }
+
+  static getAllWindows() {
+    return createdWindows.filter((win) => !win.destroyed);
+  }
+
+  static getFocusedWindow() {
+    return null;
+  }
+
+  static fromWebContents(contents) {
+    return createdWindows.find((win) => win.webContents === contents) || null;
+  }
+
+  static fromId(id) {
+    return createdWindows.find((win) => win.id === id) || null;
+  }
+
+  on() {}
+  once() {}
+  loadURL() { return Promise.resolve(); }
+  loadFile() { return Promise.resolve(); }
+  isDestroyed() { return this.destroyed; }
+  getTitle() { return 'Forge'; }
+  setTitle() {}
+  getBounds() { return { x: 0, y: 0, width: 1200, height: 800 }; }
+  focus() {}
+  show() {}
+  isMinimized() { return false; }
+  restore() {}
+}
+
+const electron = {
+  app: {
+    getPath: (name) => (name === 'home' ? root : path.join(userData, name === 'userData' ? '' : name)),
+    getVersion: () => '0.0.0-test',
+    getName: () => 'forge',
+    isPackaged: false,
+    on() {},
+    once() {},
+    whenReady: () => Promise.resolve()
+  },
+  BrowserWindow,
+  ipcMain: { handle() {}, on() {} },
+  dialog: {},
+  shell: {},
+  screen: { getPrimaryDisplay: () => ({ workAreaSize: { width: 1920, height: 1080 } }), getAllDisplays: () => [] },
+  clipboard: { readText: () => '' },
+  net: {},
+  crashReporter: { start() {} },
+  safeStorage: { isEncryptionAvailable: () => false },
+  nativeTheme: {},
+  Menu: {},
+  webContents: { getAllWebContents: () => [] }
+};
+
+const stubs = {
+  electron,
+  'node-pty': {
+    spawn() {
+      throw new Error('node-pty is not available in unit tests');
+    }
+  }
+};
+
+const load = Module._load;
+Module._load = function loadWithStubs(request, ...rest) {
+  if (Object.prototype.hasOwnProperty.call(stubs, request)) return stubs[request];
+  return load.call(this, request, ...rest);
+};
+
+// Collects the handlers a setup*Handlers function registers, to call them like the renderer.
+// Errors encoded by withForgeErrors are decoded back into { code, message, details }.
+function fakeIpcMain() {
+  const handlers = new Map();
+  return {
+    handle: (channel, handler) => handlers.set(channel, handler),
+    on() {},
+    removeHandler: (channel) => handlers.delete(channel),
+    async invoke(channel, sender, ...args) {
+      try {
+        return await handlers.get(channel)({ sender }, ...args);
+      } catch (error) {
+        const marker = 'ForgeError:';
+        if (!error.message.startsWith(marker)) throw error;
+        throw Object.assign(new Error(), JSON.parse(error.message.slice(marker.length)));
+      }
+    }
+  };
+}
+
+module.exports = { root, userData, createdWindows, fakeIpcMain };
@@ -0,0 +1,86 @@
+require('./support/setup');
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const { detectShellMarks, recordPromptMarks, terminalEvents } = require('../../electron/terminal');
+
+function newSession() {
+  return {
+    id: 'term-1',
+    cwd: '/home/user',
+    shellIntegration: true,
+    output: { length: 0, lines: 0 },
+    marks: [],
+    nextMarkId: 1,
+    recentCommands: [],
+    nextCommandId: 1
+  };
+}
+
+function collect(event) {
+  const payloads = [];
+  const listener = (id, payload) => payloads.push({ id, ...payload });
+  terminalEvents.on(event, listener);
+  return { payloads, stop: () => terminalEvents.off(event, listener) };
+}
+
+test('reports a command from start to finish', () => {
+  const session = newSession();
+  const started = collect('commandStarted');
+  const finished = collect('commandFinished');
+  try {
+    detectShellMarks(session, '\x1b]633;E;npm test\x07\x1b]133;C\x07');
+    detectShellMarks(session, 'output\r\n\x1b]133;D;1\x07\x1b]7;file://host/home/user/app\x07');
+  } finally {
+    started.stop();
+    finished.stop();
+  }
+  assert.deepEqual(started.payloads, [{ id: 'term-1', command: 'npm test' }]);
+  assert.equal(finished.payloads.length, 1);
+  assert.equal(finished.payloads[0].exitCode, 1);
+  assert.equal(finished.payloads[0].command, 'npm test');
+  assert.equal(finished.payloads[0].cwd, '/home/user');
+  assert.equal(session.cwd, '/home/user/app');
+});
+
+test('keeps the untrimmed command line alongside the command', () => {
+  const session = newSession();
+  const finished = collect('commandFinished');
+  try {
+    detectShellMarks(session, '\x1b]633;E; secret --token x\x07');
+    detectShellMarks(session, '\x1b]133;D;0\x07');
+  } finally {
+    finished.stop();
+  }
+  assert.equal(finished.payloads[0].command, 'secret --token x');
+  assert.equal(finished.payloads[0].commandLine, ' secret --token x');
+});
+
+test('unescapes semicolons, newlines and backslashes in the command line', () => {
+  const session = newSession();
+  detectShellMarks(session, '\x1b]633;E;echo a\\x3b echo b\\x0aecho c\\\\d\x07');
+  assert.equal(session.recentCommands[0].command, 'echo a; echo b\necho c\\d');
+});
+
+test('reports a finish with no command when none was seen', () => {
+  const session = newSession();
+  const finished = collect('commandFinished');
+  try {
+    detectShellMarks(session, '\x1b]133;D\x07');
+  } finally {
+    finished.stop();
+  }
+  assert.equal(finished.payloads[0].command, null);
+  assert.equal(finished.payloads[0].exitCode, null);
+});
+
+test('records prompt marks with their positions and exit code', () => {
+  const session = newSession();
+  recordPromptMarks(session, '\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]633;E;ls\x07\x1b]133;C\x07a b\r\n\x1b]133;D;0\x07');
+  assert.equal(session.marks.length, 1);
+  const [mark] = session.marks;
+  assert.equal(mark.commandLine, 'ls');
+  assert.equal(mark.exitCode, 0);
+  assert.equal(mark.prompt.line, 0);
+  assert.equal(mark.output.line, 1);
+  assert.equal(mark.end.line, 2);
+});
@@ -0,0 +1,51 @@
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const { parseToml, TomlError } = require('../../electron/toml');
+
+test('parses tables, dotted keys and arrays of tables', () => {
+  const parsed = parseToml([
+    'title = "forge"',
+    '[server]',
+    'host = "localhost"',
+    'ports = [8000, 8001]',
+    'limits.memory = "1g"',
+    '[[tasks]]',
+    'name = "build"',
+    '[[tasks]]',
+    'name = "test"'
+  ].join('\n'));
+  assert.deepEqual(parsed, {
+    title: 'forge',
+    server: { host: 'localhost', ports: [8000, 8001], limits: { memory: '1g' } },
+    tasks: [{ name: 'build' }, { name: 'test' }]
+  });
+});
+
+test('rejects duplicate keys', () => {
+  assert.throws(() => parseToml('a = 1\na = 2'), TomlError);
+});
+
+test('rejects a table given two headers', () => {
+  assert.throws(() => parseToml('[a]\nx = 1\n[a]\ny = 2'), /Duplicate table "a"/);
+  assert.throws(() => parseToml('[a]\n[a.b]\n[a.b]'), /Duplicate table "a.b"/);
+});
+
+test('allows a header for a table only created implicitly', () => {
+  assert.deepEqual(parseToml('[a.b]\nx = 1\n[a]\ny = 2'), { a: { b: { x: 1 }, y: 2 } });
+});
+
+test('rejects a header for a table defined by dotted keys', () => {
+  assert.throws(() => parseToml('a.b.c = 1\n[a]'), /Duplicate table "a"/);
+  assert.throws(() => parseToml('a.b.c = 1\n[a.b]'), /Duplicate table "a.b"/);
+  assert.throws(() => parseToml('[fruit]\napple.color = "red"\n[fruit.apple]'), /Duplicate table "fruit.apple"/);
+});
+
+test('allows sub-tables under a table defined by dotted keys', () => {
+  assert.deepEqual(parseToml('[fruit]\napple.color = "red"\n[fruit.apple.texture]\nsmooth = true'), {
+    fruit: { apple: { color: 'red', texture: { smooth: true } } }
+  });
+});
+
+test('keeps each array-of-tables element separate', () => {
+  assert.deepEqual(parseToml('[[p]]\nq.r = 1\n[[p]]\nq.r = 2'), { p: [{ q: { r: 1 } }, { q: { r: 2 } }] });
+});
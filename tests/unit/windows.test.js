@@ -0,0 +1,26 @@
+const { createdWindows, fakeIpcMain } = require('./support/setup');
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const { setupWindowHandlers } = require('../../electron/windows');
+const { executeCommand, listCommands } = require('../../electron/commands');
+
+const ipcMain = fakeIpcMain();
+setupWindowHandlers(ipcMain);
+
+test('moving an unknown terminal fails without opening a window', async () => {
+  await assert.rejects(ipcMain.invoke('windows:moveTerminal', null, 'missing'), { code: 'TERMINAL_NOT_FOUND' });
+  await assert.rejects(executeCommand('terminal.moveToWindow', ['missing']), { code: 'TERMINAL_NOT_FOUND' });
+  assert.equal(createdWindows.length, 0);
+});
+
+test('moving an editor without a path fails without opening a window', async () => {
+  await assert.rejects(ipcMain.invoke('windows:moveEditor', null, {}), { code: 'INVALID_ARGUMENT' });
+  assert.equal(createdWindows.length, 0);
+});
+
+test('commands that need arguments stay out of the palette', () => {
+  const byId = new Map(listCommands().map((command) => [command.id, command]));
+  assert.equal(byId.get('terminal.moveToWindow').palette, false);
+  assert.equal(byId.get('editor.moveToWindow').palette, false);
+  assert.equal(byId.get('window.new').palette, true);
+});